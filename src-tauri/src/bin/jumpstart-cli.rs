@@ -0,0 +1,227 @@
+//! @module bin/jumpstart-cli
+//! @description Terminal companion that starts, lists, and follows RALPH loops in the running app
+//!
+//! PURPOSE:
+//! - Read the running app's control endpoint from ~/.project-jumpstart/control.json
+//! - Send one request per invocation and print the result
+//! - Tail a loop's iteration and activity events with --follow
+//!
+//! DEPENDENCIES:
+//! - serde_json - Request and response JSON lines
+//! - dirs - Home directory lookup
+//!
+//! PATTERNS:
+//! - Usage:
+//!   jumpstart-cli ralph list [--project <id|name|path>] [--json]
+//!   jumpstart-cli ralph start "<prompt>" [--project <id|name|path>] [--max-deleted-percent N] [--max-minutes N]
+//!     [--resume-session] [--follow]
+//!   jumpstart-cli ralph follow <loop-id>
+//! - --project defaults to the current directory (any folder inside a project resolves to it)
+//! - Exit code 0 on success, 1 on errors, 2 when a followed loop ends in "failed"
+//!
+//! CLAUDE NOTES:
+//! - Talks to core::control in the app; wire shapes are documented in models/control.rs
+//! - Deliberately depends only on std, serde_json, and dirs so it stays a small binary
+
+use std::io::{BufRead, BufReader, Write};
+use std::net::TcpStream;
+use std::process::ExitCode;
+
+use serde_json::{json, Value};
+
+const USAGE: &str = "Usage:
+  jumpstart-cli ralph list [--project <id|name|path>] [--json]
+  jumpstart-cli ralph start \"<prompt>\" [--project <id|name|path>] [--max-deleted-percent N] [--max-minutes N]
+    [--resume-session] [--follow]
+  jumpstart-cli ralph follow <loop-id>";
+
+struct Endpoint {
+    port: u16,
+    token: String,
+}
+
+fn endpoint() -> Result<Endpoint, String> {
+    let path = dirs::home_dir()
+        .ok_or("Could not determine home directory")?
+        .join(".project-jumpstart")
+        .join("control.json");
+    let content = std::fs::read_to_string(&path)
+        .map_err(|_| "Project Jumpstart is not running (no control.json); start the app first".to_string())?;
+    let value: Value = serde_json::from_str(&content).map_err(|e| format!("Invalid control.json: {}", e))?;
+    Ok(Endpoint {
+        port: value.get("port").and_then(Value::as_u64).ok_or("control.json has no port")? as u16,
+        token: value.get("token").and_then(Value::as_str).ok_or("control.json has no token")?.to_string(),
+    })
+}
+
+/// Send a request and return the connection's response lines.
+fn request(command: &str, args: Value) -> Result<impl Iterator<Item = Value>, String> {
+    let endpoint = endpoint()?;
+    let mut stream = TcpStream::connect(("127.0.0.1", endpoint.port))
+        .map_err(|_| "Could not connect to Project Jumpstart; is the app running?".to_string())?;
+    let body = json!({ "token": endpoint.token, "command": command, "args": args });
+    writeln!(stream, "{}", body).map_err(|e| format!("Failed to send request: {}", e))?;
+    Ok(BufReader::new(stream)
+        .lines()
+        .map_while(Result::ok)
+        .filter_map(|line| serde_json::from_str::<Value>(&line).ok()))
+}
+
+/// The single {"ok": ..} response of a non-streaming command.
+fn call(command: &str, args: Value) -> Result<Value, String> {
+    let response = request(command, args)?.next().ok_or("The app closed the connection")?;
+    if response.get("ok").and_then(Value::as_bool) == Some(true) {
+        Ok(response.get("data").cloned().unwrap_or(Value::Null))
+    } else {
+        Err(response.get("error").and_then(Value::as_str).unwrap_or("Request failed").to_string())
+    }
+}
+
+fn text<'a>(value: &'a Value, key: &str) -> &'a str {
+    value.get(key).and_then(Value::as_str).unwrap_or("")
+}
+
+fn truncate(s: &str, max: usize) -> String {
+    let line = s.lines().next().unwrap_or("");
+    if line.chars().count() > max {
+        format!("{}…", line.chars().take(max - 1).collect::<String>())
+    } else {
+        line.to_string()
+    }
+}
+
+fn print_event(event: &Value) {
+    match text(event, "type") {
+        "loop_iteration_finished" => {
+            let story = event
+                .get("storyIndex")
+                .and_then(Value::as_u64)
+                .map(|s| format!(" story {}", s + 1))
+                .unwrap_or_default();
+            println!(
+                "iteration {}{}: {} ({} issues, {} files changed)",
+                event.get("iteration").and_then(Value::as_u64).unwrap_or(0),
+                story,
+                text(event, "status"),
+                event.get("issuesCount").and_then(Value::as_u64).unwrap_or(0),
+                event.get("filesChanged").and_then(Value::as_u64).unwrap_or(0),
+            );
+        }
+        _ => println!("{}", text(event, "message")),
+    }
+}
+
+/// Tail a loop until it stops running. Returns the exit code.
+fn follow(loop_id: &str) -> Result<ExitCode, String> {
+    let mut lines = request("follow_ralph_loop", json!({ "loopId": loop_id }))?;
+    let first = lines.next().ok_or("The app closed the connection")?;
+    if first.get("ok").and_then(Value::as_bool) != Some(true) {
+        return Err(text(&first, "error").to_string());
+    }
+    println!("Following loop {} ({})", loop_id, text(&first["data"], "status"));
+
+    for line in lines {
+        if let Some(event) = line.get("event") {
+            print_event(event);
+        } else if line.get("done").is_some() {
+            if let Some(error) = line.get("error").and_then(Value::as_str) {
+                return Err(error.to_string());
+            }
+            let status = text(&line, "status");
+            println!("Loop {}: {}", status, text(&line, "outcome"));
+            return Ok(if status == "failed" { ExitCode::from(2) } else { ExitCode::SUCCESS });
+        }
+    }
+    Err("Lost connection to Project Jumpstart".to_string())
+}
+
+fn run(args: &[String]) -> Result<ExitCode, String> {
+    let mut positional = Vec::new();
+    let mut project = None;
+    let mut max_deleted_percent = None;
+    let mut max_minutes = None;
+    let (mut as_json, mut follow_flag, mut resume_session) = (false, false, false);
+
+    let mut iter = args.iter();
+    while let Some(arg) = iter.next() {
+        match arg.as_str() {
+            "--project" | "-p" => project = Some(iter.next().ok_or("--project needs a value")?.clone()),
+            "--max-deleted-percent" => {
+                let value = iter.next().ok_or("--max-deleted-percent needs a value")?;
+                max_deleted_percent = Some(value.parse::<u32>().map_err(|_| "--max-deleted-percent must be a number")?);
+            }
+            "--max-minutes" => {
+                let value = iter.next().ok_or("--max-minutes needs a value")?;
+                max_minutes = Some(value.parse::<u32>().map_err(|_| "--max-minutes must be a number")?);
+            }
+            "--json" => as_json = true,
+            "--resume-session" => resume_session = true,
+            "--follow" | "-f" => follow_flag = true,
+            "-h" | "--help" => {
+                println!("{}", USAGE);
+                return Ok(ExitCode::SUCCESS);
+            }
+            other => positional.push(other.to_string()),
+        }
+    }
+    let project = match project {
+        Some(p) => p,
+        None => std::env::current_dir()
+            .and_then(|d| d.canonicalize())
+            .map(|d| d.to_string_lossy().to_string())
+            .map_err(|e| format!("Could not read the current directory: {}", e))?,
+    };
+
+    match positional.iter().map(String::as_str).collect::<Vec<_>>().as_slice() {
+        ["ralph", "list"] => {
+            let loops = call("list_ralph_loops", json!({ "project": project }))?;
+            if as_json {
+                println!("{}", serde_json::to_string_pretty(&loops).unwrap_or_default());
+                return Ok(ExitCode::SUCCESS);
+            }
+            for l in loops.as_array().into_iter().flatten() {
+                println!(
+                    "{:<36}  {:<12}  {:>2} it  {}  {}",
+                    text(l, "id"),
+                    text(l, "status"),
+                    l.get("iterations").and_then(Value::as_u64).unwrap_or(0),
+                    text(l, "createdAt").get(..16).unwrap_or(""),
+                    truncate(text(l, "prompt"), 60),
+                );
+            }
+            Ok(ExitCode::SUCCESS)
+        }
+        ["ralph", "start", prompt] => {
+            let started = call(
+                "start_ralph_loop",
+                json!({
+                    "project": project,
+                    "prompt": prompt,
+                    "maxDeletedPercent": max_deleted_percent,
+                    "maxDurationMinutes": max_minutes,
+                    "resumeSession": resume_session,
+                }),
+            )?;
+            let loop_id = text(&started, "id").to_string();
+            println!("Started loop {} (quality score {})", loop_id, started["qualityScore"]);
+            if follow_flag {
+                follow(&loop_id)
+            } else {
+                Ok(ExitCode::SUCCESS)
+            }
+        }
+        ["ralph", "follow", loop_id] => follow(loop_id),
+        _ => Err(USAGE.to_string()),
+    }
+}
+
+fn main() -> ExitCode {
+    let args: Vec<String> = std::env::args().skip(1).collect();
+    match run(&args) {
+        Ok(code) => code,
+        Err(e) => {
+            eprintln!("{}", e);
+            ExitCode::FAILURE
+        }
+    }
+}
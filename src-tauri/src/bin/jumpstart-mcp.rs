@@ -0,0 +1,286 @@
+//! @module bin/jumpstart-mcp
+//! @description Stdio MCP server that lets Claude Code sessions query module docs and conventions
+//!
+//! PURPOSE:
+//! - Speak MCP (JSON-RPC 2.0, one message per line) on stdin/stdout
+//! - Offer the tools get_module_doc, search_module_docs, and get_project_conventions
+//! - Answer each tool call through the running app's control channel
+//!
+//! DEPENDENCIES:
+//! - serde_json - JSON-RPC messages and control channel requests
+//! - dirs - Home directory lookup
+//!
+//! PATTERNS:
+//! - Usage: jumpstart-mcp [--project <id|name|path>]
+//!   --project defaults to the current directory, which Claude Code sets to where it runs
+//! - Tool names are the control channel command names; the project is added to every call
+//! - Failures inside a tool (app not running, file excluded) are tool results with
+//!   isError: true, so the model sees them; unknown methods and tools are JSON-RPC errors
+//!
+//! CLAUDE NOTES:
+//! - Installed per project by add_jumpstart_mcp_server (commands/context.rs) as the
+//!   "project-jumpstart" entry in .mcp.json
+//! - stdout carries only protocol messages; diagnostics go to stderr
+//! - endpoint() duplicates jumpstart-cli's; wire shapes are documented in models/control.rs
+//! - Deliberately depends only on std, serde_json, and dirs, like jumpstart-cli
+
+use std::io::{BufRead, BufReader, Write};
+use std::net::TcpStream;
+use std::process::ExitCode;
+
+use serde_json::{json, Value};
+
+/// Protocol revisions this server speaks, newest first.
+const PROTOCOL_VERSIONS: [&str; 3] = ["2025-06-18", "2025-03-26", "2024-11-05"];
+
+const SERVER_NAME: &str = "project-jumpstart";
+
+const INSTRUCTIONS: &str = "Project Jumpstart keeps a documentation header (purpose, exports, patterns, notes) \
+for each module of this project. Use search_module_docs to find the modules relevant to a task, \
+get_module_doc to read one module's documentation before changing it, and get_project_conventions \
+for the project's stack and coding conventions.";
+
+const PARSE_ERROR: i64 = -32700;
+const INVALID_REQUEST: i64 = -32600;
+const METHOD_NOT_FOUND: i64 = -32601;
+const INVALID_PARAMS: i64 = -32602;
+
+struct Endpoint {
+    port: u16,
+    token: String,
+}
+
+fn endpoint() -> Result<Endpoint, String> {
+    let path = dirs::home_dir()
+        .ok_or("Could not determine home directory")?
+        .join(".project-jumpstart")
+        .join("control.json");
+    let content = std::fs::read_to_string(&path)
+        .map_err(|_| "Project Jumpstart is not running (no control.json); start the app first".to_string())?;
+    let value: Value = serde_json::from_str(&content).map_err(|e| format!("Invalid control.json: {}", e))?;
+    Ok(Endpoint {
+        port: value.get("port").and_then(Value::as_u64).ok_or("control.json has no port")? as u16,
+        token: value.get("token").and_then(Value::as_str).ok_or("control.json has no token")?.to_string(),
+    })
+}
+
+/// Send one control request and return its data.
+fn call(command: &str, args: Value) -> Result<Value, String> {
+    let endpoint = endpoint()?;
+    let mut stream = TcpStream::connect(("127.0.0.1", endpoint.port))
+        .map_err(|_| "Could not connect to Project Jumpstart; is the app running?".to_string())?;
+    let body = json!({ "token": endpoint.token, "command": command, "args": args });
+    writeln!(stream, "{}", body).map_err(|e| format!("Failed to send request: {}", e))?;
+
+    let mut line = String::new();
+    BufReader::new(stream)
+        .read_line(&mut line)
+        .map_err(|e| format!("Failed to read response: {}", e))?;
+    let response: Value = serde_json::from_str(&line).map_err(|_| "The app closed the connection".to_string())?;
+    if response.get("ok").and_then(Value::as_bool) == Some(true) {
+        Ok(response.get("data").cloned().unwrap_or(Value::Null))
+    } else {
+        Err(response.get("error").and_then(Value::as_str).unwrap_or("Request failed").to_string())
+    }
+}
+
+/// Tool definitions for tools/list.
+fn tools() -> Value {
+    json!([
+        {
+            "name": "get_module_doc",
+            "description": "Documentation header of one project file: module path, description, purpose, \
+dependencies, exports, patterns, and notes. `documented` is false when the file has no header.",
+            "inputSchema": {
+                "type": "object",
+                "properties": {
+                    "path": { "type": "string", "description": "File path, relative to the project root or absolute" }
+                },
+                "required": ["path"]
+            }
+        },
+        {
+            "name": "search_module_docs",
+            "description": "Project files most relevant to a free-text query, ranked by their documentation \
+headers and paths, with each file's description.",
+            "inputSchema": {
+                "type": "object",
+                "properties": {
+                    "query": { "type": "string", "description": "What you are looking for, e.g. \"invoice refunds\"" },
+                    "limit": { "type": "integer", "minimum": 1, "maximum": 50, "description": "Most results to return (default 10)" }
+                },
+                "required": ["query"]
+            }
+        },
+        {
+            "name": "get_project_conventions",
+            "description": "The project's tech stack and the coding conventions, patterns, and notes from its CLAUDE.md.",
+            "inputSchema": { "type": "object", "properties": {} }
+        }
+    ])
+}
+
+fn error_response(id: Value, code: i64, message: &str) -> Value {
+    json!({ "jsonrpc": "2.0", "id": id, "error": { "code": code, "message": message } })
+}
+
+/// Result of a tools/call, or a JSON-RPC error (code, message) for an unknown tool.
+fn call_tool(
+    params: &Value,
+    project: &str,
+    call: &dyn Fn(&str, Value) -> Result<Value, String>,
+) -> Result<Value, (i64, String)> {
+    let name = params.get("name").and_then(Value::as_str).unwrap_or("");
+    if !tools().as_array().into_iter().flatten().any(|t| t["name"] == name) {
+        return Err((INVALID_PARAMS, format!("Unknown tool: {}", name)));
+    }
+    let mut args = match params.get("arguments") {
+        Some(Value::Object(args)) => args.clone(),
+        _ => serde_json::Map::new(),
+    };
+    args.insert("project".to_string(), json!(project));
+
+    let (text, is_error) = match call(name, Value::Object(args)) {
+        Ok(data) => (serde_json::to_string_pretty(&data).unwrap_or_default(), false),
+        Err(e) => (e, true),
+    };
+    Ok(json!({ "content": [{ "type": "text", "text": text }], "isError": is_error }))
+}
+
+/// Response to one incoming message; None for notifications.
+fn handle(message: &Value, project: &str, call: &dyn Fn(&str, Value) -> Result<Value, String>) -> Option<Value> {
+    let method = message.get("method").and_then(Value::as_str);
+    let Some(id) = message.get("id").cloned() else {
+        // Notifications (notifications/initialized, cancellations) need no answer
+        return None;
+    };
+    let Some(method) = method else {
+        return Some(error_response(id, INVALID_REQUEST, "Missing method"));
+    };
+    let params = message.get("params").cloned().unwrap_or(Value::Null);
+
+    let result = match method {
+        "initialize" => {
+            let requested = params.get("protocolVersion").and_then(Value::as_str).unwrap_or("");
+            let version = PROTOCOL_VERSIONS
+                .iter()
+                .find(|v| **v == requested)
+                .unwrap_or(&PROTOCOL_VERSIONS[0]);
+            json!({
+                "protocolVersion": version,
+                "capabilities": { "tools": {} },
+                "serverInfo": { "name": SERVER_NAME, "version": env!("CARGO_PKG_VERSION") },
+                "instructions": INSTRUCTIONS,
+            })
+        }
+        "ping" => json!({}),
+        "tools/list" => json!({ "tools": tools() }),
+        "tools/call" => match call_tool(&params, project, call) {
+            Ok(result) => result,
+            Err((code, message)) => return Some(error_response(id, code, &message)),
+        },
+        other => return Some(error_response(id, METHOD_NOT_FOUND, &format!("Method not found: {}", other))),
+    };
+    Some(json!({ "jsonrpc": "2.0", "id": id, "result": result }))
+}
+
+fn run(args: &[String]) -> Result<(), String> {
+    let mut project = None;
+    let mut iter = args.iter();
+    while let Some(arg) = iter.next() {
+        match arg.as_str() {
+            "--project" | "-p" => project = Some(iter.next().ok_or("--project needs a value")?.clone()),
+            other => return Err(format!("Unknown argument: {}\nUsage: jumpstart-mcp [--project <id|name|path>]", other)),
+        }
+    }
+    let project = match project {
+        Some(p) => p,
+        None => std::env::current_dir()
+            .and_then(|d| d.canonicalize())
+            .map(|d| d.to_string_lossy().to_string())
+            .map_err(|e| format!("Could not read the current directory: {}", e))?,
+    };
+
+    let stdin = std::io::stdin();
+    let mut stdout = std::io::stdout();
+    for line in stdin.lock().lines() {
+        let line = line.map_err(|e| format!("Failed to read stdin: {}", e))?;
+        if line.trim().is_empty() {
+            continue;
+        }
+        let response = match serde_json::from_str::<Value>(&line) {
+            Ok(message) => handle(&message, &project, &call),
+            Err(e) => Some(error_response(Value::Null, PARSE_ERROR, &format!("Parse error: {}", e))),
+        };
+        if let Some(response) = response {
+            writeln!(stdout, "{}", response)
+                .and_then(|_| stdout.flush())
+                .map_err(|e| format!("Failed to write stdout: {}", e))?;
+        }
+    }
+    Ok(())
+}
+
+fn main() -> ExitCode {
+    let args: Vec<String> = std::env::args().skip(1).collect();
+    match run(&args) {
+        Ok(()) => ExitCode::SUCCESS,
+        Err(e) => {
+            eprintln!("{}", e);
+            ExitCode::FAILURE
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn unreachable_app(_: &str, _: Value) -> Result<Value, String> {
+        Err("Could not connect to Project Jumpstart; is the app running?".to_string())
+    }
+
+    #[test]
+    fn test_handshake_and_listing() {
+        let init = json!({ "jsonrpc": "2.0", "id": 1, "method": "initialize",
+                           "params": { "protocolVersion": "2024-11-05", "capabilities": {} } });
+        let response = handle(&init, "/work/web", &unreachable_app).unwrap();
+        assert_eq!(response["id"], json!(1));
+        assert_eq!(response["result"]["protocolVersion"], json!("2024-11-05"));
+        assert_eq!(response["result"]["serverInfo"]["name"], json!(SERVER_NAME));
+
+        let newer = json!({ "jsonrpc": "2.0", "id": 2, "method": "initialize", "params": { "protocolVersion": "2099-01-01" } });
+        assert_eq!(handle(&newer, "/work/web", &unreachable_app).unwrap()["result"]["protocolVersion"], json!(PROTOCOL_VERSIONS[0]));
+
+        let initialized = json!({ "jsonrpc": "2.0", "method": "notifications/initialized" });
+        assert!(handle(&initialized, "/work/web", &unreachable_app).is_none());
+
+        let list = handle(&json!({ "jsonrpc": "2.0", "id": "l", "method": "tools/list" }), "/work/web", &unreachable_app).unwrap();
+        let names: Vec<&str> = list["result"]["tools"].as_array().unwrap().iter().filter_map(|t| t["name"].as_str()).collect();
+        assert_eq!(names, vec!["get_module_doc", "search_module_docs", "get_project_conventions"]);
+
+        let unknown = handle(&json!({ "jsonrpc": "2.0", "id": 3, "method": "resources/list" }), "/work/web", &unreachable_app).unwrap();
+        assert_eq!(unknown["error"]["code"], json!(METHOD_NOT_FOUND));
+    }
+
+    #[test]
+    fn test_tool_calls_forward_to_the_app() {
+        let app = |command: &str, args: Value| -> Result<Value, String> {
+            assert_eq!(command, "get_module_doc");
+            assert_eq!(args["project"], json!("/work/web"));
+            Ok(json!({ "path": args["path"], "documented": false, "doc": null }))
+        };
+        let request = json!({ "jsonrpc": "2.0", "id": 4, "method": "tools/call",
+                              "params": { "name": "get_module_doc", "arguments": { "path": "src/a.rs" } } });
+        let response = handle(&request, "/work/web", &app).unwrap();
+        assert_eq!(response["result"]["isError"], json!(false));
+        let text = response["result"]["content"][0]["text"].as_str().unwrap();
+        assert_eq!(serde_json::from_str::<Value>(text).unwrap()["path"], json!("src/a.rs"));
+
+        let offline = handle(&request, "/work/web", &unreachable_app).unwrap();
+        assert_eq!(offline["result"]["isError"], json!(true));
+
+        let bad_tool = json!({ "jsonrpc": "2.0", "id": 5, "method": "tools/call", "params": { "name": "rm_rf" } });
+        assert_eq!(handle(&bad_tool, "/work/web", &app).unwrap()["error"]["code"], json!(INVALID_PARAMS));
+    }
+}
@@ -1,47 +1,53 @@
 //! @module commands/activity
-//! @description Tauri IPC commands for the activity feed
+//! @description Tauri IPC commands for the activity feed and activity retention
 //!
 //! PURPOSE:
 //! - Record project activities (scans, doc generation, health checks, etc.)
-//! - Retrieve recent activities for the dashboard feed
+//! - Retrieve recent activities for the dashboard feed, optionally filtered by type
+//! - Apply a per-type retention policy and prune expired activities
 //!
 //! DEPENDENCIES:
 //! - tauri - Command macro and State
 //! - db::AppState - Database connection
+//! - db::db_path - Database the background pruner opens
+//! - models::activity - ActivityType, Activity, ActivityRetention types
 //! - rusqlite - SQLite queries
 //! - uuid - Activity ID generation
 //! - chrono - Timestamp generation
 //!
 //! EXPORTS:
 //! - log_activity - Record a new activity event
-//! - get_recent_activities - Fetch recent activities for a project
+//! - get_recent_activities - Fetch recent activities for a project (optional type filter)
+//! - get_activity_retention - List the retention period for every activity type
+//! - set_activity_retention - Override the retention period for one activity type
+//! - prune_activities - Delete activities older than their type's retention period
+//! - prune_expired_activities - (internal) Pruning on an existing connection
+//! - spawn_activity_pruner - Start the background pruning job
 //!
 //! PATTERNS:
-//! - activity_type values: "scan", "generate", "edit", "health", "enforcement", "skill", "info"
+//! - activity_type values are ActivityType names (see models/activity.rs)
 //! - Activities are ordered by created_at DESC (most recent first)
 //! - Default limit is 20 activities
+//! - Retention overrides live in the settings table as "activity_retention.<type>"
+//! - set_activity_retention rejects names outside ActivityType::ALL instead of mapping them to info
 //!
 //! CLAUDE NOTES:
 //! - The activities table was added in Phase 10 (schema.rs)
 //! - Activities drive the RecentActivity dashboard component
 //! - log_activity is called by other commands as a side effect
+//! - log_activity accepts free-form strings from the frontend; unknown types become "info"
+//! - The pruner runs on its own DB connection at startup and then every 24 hours
 
-use chrono::Utc;
-use serde::{Deserialize, Serialize};
+use chrono::{Duration, Utc};
+use rusqlite::Connection;
 use tauri::State;
 use uuid::Uuid;
 
 use crate::db::AppState;
+use crate::models::activity::{Activity, ActivityRetention, ActivityType};
 
-#[derive(Debug, Clone, Serialize, Deserialize)]
-#[serde(rename_all = "camelCase")]
-pub struct Activity {
-    pub id: String,
-    pub project_id: String,
-    pub activity_type: String,
-    pub message: String,
-    pub created_at: String,
-}
+/// How often the background job prunes expired activities.
+const PRUNE_INTERVAL_HOURS: u64 = 24;
 
 /// Record a new activity event for a project.
 #[tauri::command]
@@ -58,10 +64,11 @@ pub async fn log_activity(
 
     let id = Uuid::new_v4().to_string();
     let created_at = Utc::now().to_rfc3339();
+    let activity_type = ActivityType::parse(&activity_type);
 
     db.execute(
         "INSERT INTO activities (id, project_id, activity_type, message, created_at) VALUES (?1, ?2, ?3, ?4, ?5)",
-        rusqlite::params![id, project_id, activity_type, message, created_at],
+        rusqlite::params![id, project_id, activity_type.as_str(), message, created_at],
     )
     .map_err(|e| format!("Failed to log activity: {}", e))?;
 
@@ -75,10 +82,12 @@ pub async fn log_activity(
 }
 
 /// Fetch recent activities for a project, ordered by most recent first.
+/// When activity_type is given, only activities of that type are returned.
 #[tauri::command]
 pub async fn get_recent_activities(
     project_id: String,
     limit: Option<u32>,
+    activity_type: Option<String>,
     state: State<'_, AppState>,
 ) -> Result<Vec<Activity>, String> {
     let db = state
@@ -86,27 +95,243 @@ pub async fn get_recent_activities(
         .lock()
         .map_err(|e| format!("Failed to lock database: {}", e))?;
 
-    let max = limit.unwrap_or(20);
+    query_activities(&db, &project_id, limit.unwrap_or(20), activity_type.as_deref().map(ActivityType::parse))
+}
+
+/// List the effective retention period for every activity type.
+#[tauri::command]
+pub async fn get_activity_retention(state: State<'_, AppState>) -> Result<Vec<ActivityRetention>, String> {
+    let db = state.db.lock().map_err(|e| format!("DB lock error: {}", e))?;
+    Ok(retention_policy(&db))
+}
+
+/// Override how many days activities of one type are kept (0 = forever).
+/// Passing None restores the built-in default.
+#[tauri::command]
+pub async fn set_activity_retention(
+    activity_type: String,
+    retention_days: Option<u32>,
+    state: State<'_, AppState>,
+) -> Result<Vec<ActivityRetention>, String> {
+    let activity_type = retention_type(&activity_type)?;
+    let db = state.db.lock().map_err(|e| format!("DB lock error: {}", e))?;
+    let key = retention_setting_key(activity_type);
+
+    match retention_days {
+        Some(days) => db.execute(
+            "INSERT OR REPLACE INTO settings (key, value) VALUES (?1, ?2)",
+            rusqlite::params![key, days.to_string()],
+        ),
+        None => db.execute("DELETE FROM settings WHERE key = ?1", [&key]),
+    }
+    .map_err(|e| format!("Failed to save retention policy: {}", e))?;
+
+    Ok(retention_policy(&db))
+}
+
+/// The activity type a retention override names. Only current type names are
+/// accepted, so a typo cannot silently change the Info policy.
+fn retention_type(name: &str) -> Result<ActivityType, String> {
+    let name = name.trim().to_lowercase();
+    ActivityType::ALL
+        .into_iter()
+        .find(|t| t.as_str() == name)
+        .ok_or_else(|| format!("Unknown activity type '{}'", name))
+}
+
+/// Delete activities older than their type's retention period.
+/// Returns the number of activities removed.
+#[tauri::command]
+pub async fn prune_activities(state: State<'_, AppState>) -> Result<u32, String> {
+    let db = state.db.lock().map_err(|e| format!("DB lock error: {}", e))?;
+    prune_expired_activities(&db)
+}
 
+/// Prune expired activities on an existing connection.
+/// Unrecognized stored types are pruned with the Info policy.
+pub fn prune_expired_activities(db: &Connection) -> Result<u32, String> {
+    let now = Utc::now();
+    let mut removed = 0u32;
+
+    for policy in retention_policy(db) {
+        if policy.retention_days == 0 {
+            continue;
+        }
+        let cutoff = (now - Duration::days(policy.retention_days as i64)).to_rfc3339();
+        let count = if policy.activity_type == ActivityType::Info {
+            let known: Vec<String> = ActivityType::ALL
+                .iter()
+                .filter(|t| **t != ActivityType::Info)
+                .map(|t| format!("'{}'", t.as_str()))
+                .collect();
+            db.execute(
+                &format!(
+                    "DELETE FROM activities WHERE activity_type NOT IN ({}) AND created_at < ?1",
+                    known.join(", ")
+                ),
+                [&cutoff],
+            )
+        } else {
+            db.execute(
+                "DELETE FROM activities WHERE activity_type = ?1 AND created_at < ?2",
+                rusqlite::params![policy.activity_type.as_str(), cutoff],
+            )
+        }
+        .map_err(|e| format!("Failed to prune activities: {}", e))?;
+        removed += count as u32;
+    }
+
+    Ok(removed)
+}
+
+/// Start a background thread that prunes expired activities at startup and
+/// then every PRUNE_INTERVAL_HOURS. Uses its own DB connection so it never
+/// contends with the UI for the AppState lock.
+pub fn spawn_activity_pruner() {
+    std::thread::spawn(|| loop {
+        match crate::db::db_path().map(Connection::open) {
+            Ok(Ok(conn)) => match prune_expired_activities(&conn) {
+                Ok(n) if n > 0 => tracing::info!(count = n, "Pruned expired activities"),
+                Ok(_) => {}
+                Err(e) => tracing::warn!(error = %e, "Activity pruning failed"),
+            },
+            Ok(Err(e)) => tracing::warn!(error = %e, "Activity pruner could not open database"),
+            Err(e) => tracing::warn!(error = %e, "Activity pruner could not locate database"),
+        }
+        std::thread::sleep(std::time::Duration::from_secs(PRUNE_INTERVAL_HOURS * 60 * 60));
+    });
+}
+
+fn query_activities(
+    db: &Connection,
+    project_id: &str,
+    limit: u32,
+    activity_type: Option<ActivityType>,
+) -> Result<Vec<Activity>, String> {
     let mut stmt = db
         .prepare(
-            "SELECT id, project_id, activity_type, message, created_at FROM activities WHERE project_id = ?1 ORDER BY created_at DESC LIMIT ?2",
+            "SELECT id, project_id, activity_type, message, created_at FROM activities
+             WHERE project_id = ?1 AND (?2 IS NULL OR activity_type = ?2)
+             ORDER BY created_at DESC LIMIT ?3",
         )
         .map_err(|e| format!("Failed to query activities: {}", e))?;
 
     let activities = stmt
-        .query_map(rusqlite::params![project_id, max], |row| {
-            Ok(Activity {
-                id: row.get(0)?,
-                project_id: row.get(1)?,
-                activity_type: row.get(2)?,
-                message: row.get(3)?,
-                created_at: row.get(4)?,
-            })
-        })
+        .query_map(
+            rusqlite::params![project_id, activity_type.map(|t| t.as_str()), limit],
+            |row| {
+                Ok(Activity {
+                    id: row.get(0)?,
+                    project_id: row.get(1)?,
+                    activity_type: ActivityType::parse(&row.get::<_, String>(2)?),
+                    message: row.get(3)?,
+                    created_at: row.get(4)?,
+                })
+            },
+        )
         .map_err(|e| format!("Failed to read activities: {}", e))?
         .filter_map(|r| r.ok())
         .collect();
 
     Ok(activities)
 }
+
+fn retention_setting_key(activity_type: ActivityType) -> String {
+    format!("activity_retention.{}", activity_type.as_str())
+}
+
+/// Effective retention for every type: settings overrides, else the default.
+fn retention_policy(db: &Connection) -> Vec<ActivityRetention> {
+    ActivityType::ALL
+        .iter()
+        .map(|&activity_type| {
+            let custom: Option<u32> = db
+                .query_row(
+                    "SELECT value FROM settings WHERE key = ?1",
+                    [retention_setting_key(activity_type)],
+                    |row| row.get::<_, String>(0),
+                )
+                .ok()
+                .and_then(|v| v.trim().parse().ok());
+            ActivityRetention {
+                activity_type,
+                retention_days: custom.unwrap_or_else(|| activity_type.default_retention_days()),
+                customized: custom.is_some(),
+            }
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_support::with_project;
+
+    fn insert_activity(conn: &Connection, activity_type: &str, days_ago: i64) {
+        conn.execute(
+            "INSERT INTO activities (id, project_id, activity_type, message, created_at) VALUES (?1, 'p1', ?2, 'msg', ?3)",
+            rusqlite::params![
+                Uuid::new_v4().to_string(),
+                activity_type,
+                (Utc::now() - Duration::days(days_ago)).to_rfc3339()
+            ],
+        )
+        .unwrap();
+    }
+
+    #[test]
+    fn test_activity_type_parse_legacy() {
+        assert_eq!(ActivityType::parse("tdd"), ActivityType::Test);
+        assert_eq!(ActivityType::parse("learn"), ActivityType::Memory);
+        assert_eq!(ActivityType::parse("Enforcement"), ActivityType::Enforcement);
+        assert_eq!(ActivityType::parse("something_new"), ActivityType::Info);
+        for t in ActivityType::ALL {
+            assert_eq!(ActivityType::parse(t.as_str()), t);
+        }
+    }
+
+    #[test]
+    fn test_retention_type_rejects_unknown_types() {
+        assert_eq!(retention_type(" Scan ").unwrap(), ActivityType::Scan);
+        assert_eq!(retention_type("info").unwrap(), ActivityType::Info);
+        assert!(retention_type("scans").unwrap_err().contains("scans"));
+        // Legacy aliases map to a type when reading, but are not retention targets
+        assert!(retention_type("tdd").is_err());
+        for t in ActivityType::ALL {
+            assert_eq!(retention_type(t.as_str()).unwrap(), t);
+        }
+    }
+
+    #[test]
+    fn test_prune_respects_per_type_retention() {
+        let conn = with_project();
+        insert_activity(&conn, "scan", 45); // past 30-day scan retention
+        insert_activity(&conn, "scan", 5);
+        insert_activity(&conn, "enforcement", 200); // within 1 year
+        insert_activity(&conn, "legacy_kind", 45); // treated as info (30 days)
+
+        assert_eq!(prune_expired_activities(&conn).unwrap(), 2);
+
+        // Override: shorten enforcement retention to 90 days
+        conn.execute("INSERT INTO settings (key, value) VALUES ('activity_retention.enforcement', '90')", [])
+            .unwrap();
+        assert_eq!(prune_expired_activities(&conn).unwrap(), 1);
+
+        let remaining = query_activities(&conn, "p1", 20, None).unwrap();
+        assert_eq!(remaining.len(), 1);
+        assert_eq!(remaining[0].activity_type, ActivityType::Scan);
+    }
+
+    #[test]
+    fn test_query_activities_filters_by_type() {
+        let conn = with_project();
+        insert_activity(&conn, "scan", 1);
+        insert_activity(&conn, "test", 1);
+        insert_activity(&conn, "test", 2);
+
+        let tests = query_activities(&conn, "p1", 20, Some(ActivityType::Test)).unwrap();
+        assert_eq!(tests.len(), 2);
+        assert!(tests.iter().all(|a| a.activity_type == ActivityType::Test));
+        assert_eq!(query_activities(&conn, "p1", 20, None).unwrap().len(), 3);
+    }
+}
@@ -13,6 +13,9 @@
 //! - chrono - Timestamp generation
 //! - uuid - Unique ID generation
 //! - core::ai - Claude API caller for enhancement
+//! - core::ai_queue - Prioritized slot for each Claude call
+//! - commands::versions - Version snapshots on create/update/delete
+//! - core::subagent_lint - Checks for .claude/agents/*.md files
 //!
 //! EXPORTS:
 //! - list_agents - List all agents for a project
@@ -21,6 +24,7 @@
 //! - delete_agent - Delete an agent by ID
 //! - increment_agent_usage - Bump usage count for an agent
 //! - enhance_agent_instructions - AI-enhance an agent's instructions
+//! - lint_subagent_configs - Validate the project's .claude/agents/*.md files
 //!
 //! PATTERNS:
 //! - All commands use AppState for DB access
@@ -32,13 +36,21 @@
 //! - Agents support advanced workflows with steps, tools, and triggers
 //! - Timestamps use chrono::Utc::now() in RFC 3339 format
 //! - enhance_agent_instructions requires API key in settings
+//! - Every create/update writes an agent_versions row (see commands/versions.rs for rollback)
+//! - lint_subagent_configs reads files on disk only; it does not touch the agents table
 
 use chrono::Utc;
 use tauri::State;
 use uuid::Uuid;
 
-use crate::db::{self, AppState};
-use crate::models::agent::{Agent, AgentTool, WorkflowStep};
+use crate::commands::versions;
+use crate::core::ai_queue;
+use crate::core::subagent_lint;
+use crate::core::events::{self, AppEvent};
+use crate::db::AppState;
+use crate::models::activity::ActivityType;
+use crate::models::ai_queue::AiJobKind;
+use crate::models::agent::{Agent, AgentTool, SubagentLintReport, WorkflowStep};
 
 /// List all agents for a project (or global agents if project_id is None).
 #[tauri::command]
@@ -129,9 +141,11 @@ pub async fn create_agent(
     )
     .map_err(|e| format!("Failed to insert agent: {}", e))?;
 
+    versions::record_version(&db, "agent", &id)?;
+
     // Log activity
     if let Some(ref pid) = project_id {
-        let _ = db::log_activity_db(&db, pid, "agent", &format!("Created agent: {}", &name));
+        events::publish(&db, AppEvent::activity(pid, ActivityType::Agent, &format!("Created agent: {}", &name)));
     }
 
     Ok(Agent {
@@ -182,6 +196,9 @@ pub async fn update_agent(
         .as_ref()
         .map(|tp| serde_json::to_string(tp).unwrap_or_default());
 
+    // Preserve the pre-update instructions for agents created before versioning existed
+    versions::ensure_version_baseline(&db, "agent", &id)?;
+
     let rows_affected = db
         .execute(
             "UPDATE agents SET name = ?1, description = ?2, tier = ?3, category = ?4,
@@ -206,6 +223,8 @@ pub async fn update_agent(
         return Err(format!("Agent not found: {}", id));
     }
 
+    versions::record_version(&db, "agent", &id)?;
+
     // Fetch the updated agent
     let agent = db
         .query_row(
@@ -242,9 +261,11 @@ pub async fn delete_agent(id: String, state: State<'_, AppState>) -> Result<(),
         return Err(format!("Agent not found: {}", id));
     }
 
+    let _ = versions::delete_versions(&db, "agent", &id);
+
     // Log activity
     if let Some((name, Some(pid))) = agent_info {
-        let _ = db::log_activity_db(&db, &pid, "agent", &format!("Deleted agent: {}", name));
+        events::publish(&db, AppEvent::activity(&pid, ActivityType::Agent, &format!("Deleted agent: {}", name)));
     }
 
     Ok(())
@@ -363,7 +384,8 @@ pub async fn enhance_agent_instructions(
         project_language.as_deref().unwrap_or("any")
     ));
 
-    crate::core::ai::call_claude(&state.http_client, &api_key, &system, &prompt).await
+    let call = crate::core::ai::call_claude(&state.http_client, &api_key, &system, &prompt);
+    ai_queue::run(AiJobKind::Interactive, "agent enhancement", call).await
 }
 
 /// Get a tier-appropriate example for agent enhancement.
@@ -445,6 +467,27 @@ describe('[ComponentName]', () => {
     }
 }
 
+/// Lint the subagent files in `.claude/agents/` (frontmatter fields, tool names,
+/// instruction length, duplicate names). A project without the directory has no issues.
+#[tauri::command]
+pub async fn lint_subagent_configs(project_path: String) -> Result<SubagentLintReport, String> {
+    let dir = std::path::Path::new(&project_path).join(".claude").join("agents");
+    let mut files: Vec<(String, String)> = Vec::new();
+    if dir.is_dir() {
+        let entries = std::fs::read_dir(&dir).map_err(|e| format!("Failed to read {}: {}", dir.display(), e))?;
+        for path in entries.filter_map(|e| e.ok()).map(|e| e.path()) {
+            if !path.is_file() || path.extension().and_then(|e| e.to_str()) != Some("md") {
+                continue;
+            }
+            let name = path.file_name().unwrap_or_default().to_string_lossy();
+            let content = std::fs::read_to_string(&path).map_err(|e| format!("Failed to read {}: {}", name, e))?;
+            files.push((format!(".claude/agents/{}", name), content));
+        }
+    }
+    files.sort();
+    Ok(subagent_lint::lint_agent_files(&files))
+}
+
 // ---------------------------------------------------------------------------
 // Row mapping helper
 // ---------------------------------------------------------------------------
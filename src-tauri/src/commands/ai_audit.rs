@@ -0,0 +1,77 @@
+//! @module commands/ai_audit
+//! @description Tauri IPC commands for the opt-in per-project AI prompt audit log
+//!
+//! PURPOSE:
+//! - Turn the audit log on or off for a project
+//! - Query audited prompts/responses for a time range
+//! - Export them for security review
+//!
+//! DEPENDENCIES:
+//! - tauri - Command macro and State
+//! - db::AppState - Database connection
+//! - core::ai_audit - Opt-in setting, query, and export
+//! - core::events - Activity entry when auditing is switched
+//! - models::ai_audit - AiAuditEntry, AiAuditRange types
+//!
+//! EXPORTS:
+//! - get_ai_audit_enabled - Whether a project records its AI exchanges
+//! - set_ai_audit_enabled - Opt a project in or out (existing entries are kept)
+//! - get_ai_audit_log - Entries for a project within a range, newest first
+//! - export_ai_audit_log - The same entries as "json" or "markdown"
+//!
+//! PATTERNS:
+//! - Entries are redacted when written; nothing here re-reads prompts from disk
+//!
+//! CLAUDE NOTES:
+//! - Turning auditing off stops new entries only; existing entries stay available for review
+
+use tauri::State;
+
+use crate::core::ai_audit;
+use crate::core::events::{self, AppEvent};
+use crate::db::AppState;
+use crate::models::activity::ActivityType;
+use crate::models::ai_audit::{AiAuditEntry, AiAuditRange};
+
+#[tauri::command]
+pub async fn get_ai_audit_enabled(project_id: String, state: State<'_, AppState>) -> Result<bool, String> {
+    let db = state.db.lock().map_err(|e| format!("DB lock error: {}", e))?;
+    Ok(ai_audit::is_enabled(&db, &project_id))
+}
+
+#[tauri::command]
+pub async fn set_ai_audit_enabled(
+    project_id: String,
+    enabled: bool,
+    state: State<'_, AppState>,
+) -> Result<bool, String> {
+    let db = state.db.lock().map_err(|e| format!("DB lock error: {}", e))?;
+    ai_audit::set_enabled(&db, &project_id, enabled)?;
+    let message = if enabled { "AI prompt audit log enabled" } else { "AI prompt audit log disabled" };
+    events::publish(&db, AppEvent::activity(&project_id, ActivityType::Settings, message));
+    Ok(enabled)
+}
+
+/// Audited AI exchanges for a project. An empty range returns the whole log.
+#[tauri::command]
+pub async fn get_ai_audit_log(
+    project_id: String,
+    range: Option<AiAuditRange>,
+    state: State<'_, AppState>,
+) -> Result<Vec<AiAuditEntry>, String> {
+    let db = state.db.lock().map_err(|e| format!("DB lock error: {}", e))?;
+    ai_audit::query(&db, &project_id, &range.unwrap_or_default())
+}
+
+/// Export audited exchanges. `format` is "json" or "markdown".
+#[tauri::command]
+pub async fn export_ai_audit_log(
+    project_id: String,
+    range: Option<AiAuditRange>,
+    format: String,
+    state: State<'_, AppState>,
+) -> Result<String, String> {
+    let db = state.db.lock().map_err(|e| format!("DB lock error: {}", e))?;
+    let entries = ai_audit::query(&db, &project_id, &range.unwrap_or_default())?;
+    ai_audit::export(&entries, &format)
+}
@@ -0,0 +1,118 @@
+//! @module commands/ai_queue
+//! @description Tauri IPC commands for inspecting and controlling the AI work queue
+//!
+//! PURPOSE:
+//! - Report queued and running AI jobs
+//! - Pause and resume non-interactive AI work
+//! - Change the concurrency limit and the priority of queued jobs
+//! - Read and change the token budgets AI prompts are truncated to
+//!
+//! DEPENDENCIES:
+//! - tauri - Command macro and State
+//! - db::AppState - Database connection (settings)
+//! - core::ai_queue - The app-wide queue
+//! - core::ai - Token limits in effect for the model
+//! - models::ai_queue - AiQueueStatus, AiPriority
+//! - models::ai_limits - AiTokenLimits
+//!
+//! EXPORTS:
+//! - get_ai_queue - Snapshot of the queue
+//! - pause_ai_queue - Hold queued non-interactive jobs
+//! - resume_ai_queue - Let held jobs start again
+//! - set_ai_queue_concurrency - Change and persist the concurrency limit
+//! - set_ai_job_priority - Re-prioritize a queued job
+//! - get_ai_token_limits - Token budgets in effect for the current model
+//! - set_ai_token_limits - Change and persist the current model's token budgets
+//!
+//! PATTERNS:
+//! - Every queue command returns the updated AiQueueStatus so the UI can re-render directly
+//! - Token limits are stored per model ("ai.token_limits.<model>"), so a model change starts
+//!   from that model's defaults
+//!
+//! CLAUDE NOTES:
+//! - Interactive calls (prompt analysis etc.) run while paused; pausing only affects batch work
+
+use tauri::State;
+
+use crate::core::{ai, ai_queue};
+use crate::db::AppState;
+use crate::models::ai_limits::AiTokenLimits;
+use crate::models::ai_queue::{AiPriority, AiQueueStatus};
+
+/// Snapshot of queued and running AI jobs.
+#[tauri::command]
+pub async fn get_ai_queue() -> Result<AiQueueStatus, String> {
+    ai_queue::queue().status()
+}
+
+/// Pause non-interactive AI work. Running jobs finish; queued ones wait.
+#[tauri::command]
+pub async fn pause_ai_queue() -> Result<AiQueueStatus, String> {
+    ai_queue::queue().set_paused(true)
+}
+
+/// Resume non-interactive AI work.
+#[tauri::command]
+pub async fn resume_ai_queue() -> Result<AiQueueStatus, String> {
+    ai_queue::queue().set_paused(false)
+}
+
+/// Set how many AI calls may run at once (1-8) and store it in settings.
+#[tauri::command]
+pub async fn set_ai_queue_concurrency(
+    max_concurrent: u32,
+    state: State<'_, AppState>,
+) -> Result<AiQueueStatus, String> {
+    if max_concurrent == 0 || max_concurrent > ai_queue::MAX_CONCURRENT_LIMIT {
+        return Err(format!(
+            "Concurrency must be between 1 and {}",
+            ai_queue::MAX_CONCURRENT_LIMIT
+        ));
+    }
+
+    {
+        let db = state.db.lock().map_err(|e| format!("DB lock error: {}", e))?;
+        db.execute(
+            "INSERT OR REPLACE INTO settings (key, value) VALUES (?1, ?2)",
+            rusqlite::params![ai_queue::MAX_CONCURRENT_SETTING, max_concurrent.to_string()],
+        )
+        .map_err(|e| format!("Failed to save AI queue concurrency: {}", e))?;
+    }
+
+    ai_queue::queue().set_max_concurrent(max_concurrent)
+}
+
+/// Change the priority of a queued AI job.
+#[tauri::command]
+pub async fn set_ai_job_priority(job_id: String, priority: AiPriority) -> Result<AiQueueStatus, String> {
+    ai_queue::queue().set_priority(&job_id, priority)
+}
+
+/// Token budgets AI prompts are truncated to for the current model.
+#[tauri::command]
+pub async fn get_ai_token_limits() -> Result<AiTokenLimits, String> {
+    Ok(ai::token_limits())
+}
+
+/// Change the current model's token budgets and store them in settings.
+#[tauri::command]
+pub async fn set_ai_token_limits(
+    limits: AiTokenLimits,
+    state: State<'_, AppState>,
+) -> Result<AiTokenLimits, String> {
+    ai::validate_token_limits(&limits)?;
+    let limits = AiTokenLimits {
+        model: ai::MODEL.to_string(),
+        ..limits
+    };
+    let json = serde_json::to_string(&limits).map_err(|e| format!("Failed to serialize token limits: {}", e))?;
+    {
+        let db = state.db.lock().map_err(|e| format!("DB lock error: {}", e))?;
+        db.execute(
+            "INSERT OR REPLACE INTO settings (key, value) VALUES (?1, ?2)",
+            rusqlite::params![format!("{}{}", ai::TOKEN_LIMITS_SETTING_PREFIX, ai::MODEL), json],
+        )
+        .map_err(|e| format!("Failed to save token limits: {}", e))?;
+    }
+    ai::set_token_limits(limits)
+}
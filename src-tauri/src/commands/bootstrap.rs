@@ -0,0 +1,238 @@
+//! @module commands/bootstrap
+//! @description Tauri IPC commands for the resumable, cost-estimated CLAUDE.md bootstrap of undocumented repos
+//!
+//! PURPOSE:
+//! - Preview a bootstrap: directory clusters, AI calls, tokens, and estimated cost
+//! - Run (or resume) the pipeline: summarize clusters, write nested CLAUDE.md files, write the overview
+//! - Report the latest run so the UI can show progress and offer to resume
+//!
+//! DEPENDENCIES:
+//! - tauri - Command macro and State
+//! - db::AppState - Database connection and HTTP client
+//! - core::bootstrap - Clustering, digests, prompts, estimates, file writes, run storage
+//! - core::ai / core::ai_queue / core::ai_audit - Queued, audited Claude calls
+//! - core::events - Activity entry when a run completes
+//! - commands::project - load_project
+//! - models::bootstrap - BootstrapRun type
+//!
+//! EXPORTS:
+//! - plan_project_bootstrap - Clusters and cost estimate; nothing is sent or written
+//! - bootstrap_project_docs - Start or resume a bootstrap and run it to completion
+//! - get_project_bootstrap - The project's latest run (None if it never ran)
+//!
+//! PATTERNS:
+//! - The run is saved after every cluster, so a crash or failure resumes where it stopped:
+//!   calling bootstrap_project_docs again with the same depth continues the unfinished run
+//! - The DB lock is never held across an AI call
+//! - A cluster whose AI call fails (or without an API key) gets a heuristic summary instead
+//!
+//! CLAUDE NOTES:
+//! - One run per project at a time; a second call while one is running is rejected
+//! - Default depth is 2 (e.g. src/api, src/db); accepted range is core::bootstrap MIN_DEPTH..=MAX_DEPTH
+
+use std::collections::HashSet;
+use std::path::Path;
+use std::sync::{Mutex, OnceLock};
+
+use chrono::Utc;
+use tauri::State;
+
+use crate::commands::project::load_project;
+use crate::core::bootstrap;
+use crate::core::events::{self, AppEvent};
+use crate::core::{ai, ai_audit, ai_queue};
+use crate::db::AppState;
+use crate::models::activity::ActivityType;
+use crate::models::ai_queue::AiJobKind;
+use crate::models::bootstrap::BootstrapRun;
+use crate::models::project::Project;
+
+const DEFAULT_DEPTH: u32 = 2;
+const AUDIT_FEATURE: &str = "docs bootstrap";
+
+/// Projects with a bootstrap in progress.
+fn running() -> &'static Mutex<HashSet<String>> {
+    static RUNNING: OnceLock<Mutex<HashSet<String>>> = OnceLock::new();
+    RUNNING.get_or_init(|| Mutex::new(HashSet::new()))
+}
+
+/// Marks a project's bootstrap as running until dropped.
+struct RunGuard(String);
+
+impl RunGuard {
+    fn acquire(project_id: &str) -> Result<RunGuard, String> {
+        let mut set = running().lock().map_err(|e| format!("Lock error: {}", e))?;
+        if !set.insert(project_id.to_string()) {
+            return Err("A docs bootstrap is already running for this project".to_string());
+        }
+        Ok(RunGuard(project_id.to_string()))
+    }
+}
+
+impl Drop for RunGuard {
+    fn drop(&mut self) {
+        if let Ok(mut set) = running().lock() {
+            set.remove(&self.0);
+        }
+    }
+}
+
+/// Scan and cluster the project and estimate the bootstrap's cost. Nothing is sent or written.
+#[tauri::command]
+pub async fn plan_project_bootstrap(
+    project_id: String,
+    depth: Option<u32>,
+    state: State<'_, AppState>,
+) -> Result<BootstrapRun, String> {
+    let (project, uses_ai) = {
+        let db = state.db.lock().map_err(|e| format!("DB lock error: {}", e))?;
+        (load_project(&db, &project_id)?, ai::get_api_key(&db).is_ok())
+    };
+    bootstrap::plan(&project, depth.unwrap_or(DEFAULT_DEPTH), uses_ai)
+}
+
+#[tauri::command]
+pub async fn get_project_bootstrap(
+    project_id: String,
+    state: State<'_, AppState>,
+) -> Result<Option<BootstrapRun>, String> {
+    let db = state.db.lock().map_err(|e| format!("DB lock error: {}", e))?;
+    bootstrap::latest_run(&db, &project_id)
+}
+
+/// Start a bootstrap, or resume the project's unfinished one when `depth` matches,
+/// and run it to completion. Returns the finished run.
+#[tauri::command]
+pub async fn bootstrap_project_docs(
+    project_id: String,
+    depth: Option<u32>,
+    state: State<'_, AppState>,
+) -> Result<BootstrapRun, String> {
+    let _guard = RunGuard::acquire(&project_id)?;
+    let depth = depth.unwrap_or(DEFAULT_DEPTH).clamp(bootstrap::MIN_DEPTH, bootstrap::MAX_DEPTH);
+    let (project, api_key, latest) = {
+        let db = state.db.lock().map_err(|e| format!("DB lock error: {}", e))?;
+        (
+            load_project(&db, &project_id)?,
+            ai::get_api_key(&db).ok(),
+            bootstrap::latest_run(&db, &project_id)?,
+        )
+    };
+
+    let mut run = match latest {
+        Some(run) if run.stage != "completed" && run.depth == depth => run,
+        _ => {
+            let mut run = bootstrap::plan(&project, depth, api_key.is_some())?;
+            run.id = uuid::Uuid::new_v4().to_string();
+            run
+        }
+    };
+    run.error = None;
+
+    if let Err(e) = execute(&state, &project, api_key.as_deref(), &mut run).await {
+        run.error = Some(e.clone());
+        if let Err(save_error) = set_stage(&state, &mut run, "failed") {
+            tracing::warn!(error = %save_error, "Failed to record bootstrap failure");
+        }
+        return Err(e);
+    }
+    Ok(run)
+}
+
+async fn execute(state: &AppState, project: &Project, api_key: Option<&str>, run: &mut BootstrapRun) -> Result<(), String> {
+    let excludes = ai::load_ai_excludes(&project.path);
+
+    set_stage(state, run, "summarizing")?;
+    for i in 0..run.clusters.len() {
+        if run.clusters[i].summary.is_some() {
+            continue;
+        }
+        let cluster = &run.clusters[i];
+        let digest = bootstrap::cluster_digest(&project.path, cluster, &excludes);
+        let ai_summary = match api_key {
+            Some(key) if !ai::is_ai_excluded(&excludes, &cluster.dir) => {
+                let prompt = bootstrap::cluster_prompt(project, cluster, &digest);
+                let call = ai::call_claude(&state.http_client, key, bootstrap::CLUSTER_SYSTEM_PROMPT, &prompt);
+                let queued = ai_queue::run(AiJobKind::DocGeneration, AUDIT_FEATURE, call);
+                match ai_audit::scoped(&project.id, AUDIT_FEATURE, queued).await {
+                    Ok(summary) => Some(summary),
+                    Err(e) => {
+                        tracing::warn!(error = %e, dir = %cluster.dir, "Cluster summary failed; using heuristic summary");
+                        None
+                    }
+                }
+            }
+            _ => None,
+        };
+        let (summary, source) = match ai_summary {
+            Some(summary) => (summary, "ai"),
+            None => (bootstrap::heuristic_summary(cluster, &digest), "heuristic"),
+        };
+        run.clusters[i].summary = Some(summary);
+        run.clusters[i].source = Some(source.to_string());
+        save(state, run)?;
+    }
+
+    set_stage(state, run, "writing")?;
+    for cluster in run.clusters.iter_mut().filter(|c| c.outcome.is_none()) {
+        cluster.outcome = Some(bootstrap::write_cluster(&project.path, cluster)?.to_string());
+    }
+
+    set_stage(state, run, "overview")?;
+    if run.overview.is_none() {
+        let from_ai = match api_key {
+            Some(key) => {
+                // Excluded directories and root files are not named to the AI
+                let clusters: Vec<_> =
+                    run.clusters.iter().filter(|c| !ai::is_ai_excluded(&excludes, &c.dir)).cloned().collect();
+                let root_files: Vec<_> =
+                    run.root_files.iter().filter(|f| !ai::is_ai_excluded(&excludes, f)).cloned().collect();
+                let prompt = bootstrap::overview_prompt(project, &clusters, &root_files);
+                let call = ai::call_claude(&state.http_client, key, bootstrap::OVERVIEW_SYSTEM_PROMPT, &prompt);
+                let queued = ai_queue::run(AiJobKind::DocGeneration, AUDIT_FEATURE, call);
+                ai_audit::scoped(&project.id, AUDIT_FEATURE, queued)
+                    .await
+                    .map_err(|e| tracing::warn!(error = %e, "Overview generation failed; using template overview"))
+                    .ok()
+            }
+            None => None,
+        };
+        run.overview = Some(from_ai.unwrap_or_else(|| bootstrap::heuristic_overview(project, &run.clusters)));
+        save(state, run)?;
+    }
+    let root = Path::new(&project.path).join("CLAUDE.md");
+    if run.overview_path.is_none() && !root.exists() {
+        let overview = run.overview.as_deref().unwrap_or_default();
+        std::fs::write(&root, format!("{}\n", overview.trim_end()))
+            .map_err(|e| format!("Failed to write CLAUDE.md: {}", e))?;
+        run.overview_path = Some("CLAUDE.md".to_string());
+    }
+
+    set_stage(state, run, "completed")?;
+    let created = run.clusters.iter().filter(|c| c.outcome.as_deref() == Some("created")).count();
+    let db = state.db.lock().map_err(|e| format!("DB lock error: {}", e))?;
+    events::publish(
+        &db,
+        AppEvent::activity(
+            &project.id,
+            ActivityType::Generate,
+            &format!(
+                "Bootstrapped docs: {} nested CLAUDE.md files created{}",
+                created,
+                if run.overview_path.is_some() { " plus the top-level CLAUDE.md" } else { "" }
+            ),
+        ),
+    );
+    Ok(())
+}
+
+fn set_stage(state: &AppState, run: &mut BootstrapRun, stage: &str) -> Result<(), String> {
+    run.stage = stage.to_string();
+    save(state, run)
+}
+
+fn save(state: &AppState, run: &mut BootstrapRun) -> Result<(), String> {
+    run.updated_at = Utc::now().to_rfc3339();
+    let db = state.db.lock().map_err(|e| format!("DB lock error: {}", e))?;
+    bootstrap::save_run(&db, run)
+}
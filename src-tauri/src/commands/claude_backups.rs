@@ -0,0 +1,78 @@
+//! @module commands/claude_backups
+//! @description Tauri IPC commands for listing and restoring .claude directory backups
+//!
+//! PURPOSE:
+//! - List the .claude snapshots taken before Jumpstart wrote into a project's .claude/
+//! - Restore a snapshot by id
+//!
+//! DEPENDENCIES:
+//! - tauri - Command macro and State
+//! - db::AppState - Database connection (projects)
+//! - core::claude_backup - Snapshot storage and restore
+//! - commands::project - load_project
+//! - models::claude_backup - ClaudeDirBackup
+//!
+//! EXPORTS:
+//! - list_claude_dir_backups - A project's backups, newest first
+//! - restore_claude_dir_backup - Restore a backup over the project's .claude/ by id
+//!
+//! PATTERNS:
+//! - Snapshots are taken by the writers themselves (slash command deploy, ai-exclude edits,
+//!   policy pack apply) via core::claude_backup::snapshot, not by these commands
+//!
+//! CLAUDE NOTES:
+//! - Backup ids are unique across projects, so restore looks the id up in every registered
+//!   project's .jumpstart/backups instead of taking a project id
+//! - Restoring logs an "edit" activity for the project
+
+use tauri::State;
+
+use crate::commands::project;
+use crate::core::claude_backup;
+use crate::core::events::{self, AppEvent};
+use crate::db::AppState;
+use crate::models::activity::ActivityType;
+use crate::models::claude_backup::ClaudeDirBackup;
+
+/// A project's .claude backups, newest first.
+#[tauri::command]
+pub async fn list_claude_dir_backups(
+    project_id: String,
+    state: State<'_, AppState>,
+) -> Result<Vec<ClaudeDirBackup>, String> {
+    let db = state.db.lock().map_err(|e| format!("DB lock error: {}", e))?;
+    let project = project::load_project(&db, &project_id)?;
+    Ok(claude_backup::list_backups(&project.path))
+}
+
+/// Replace a project's .claude/ with the backup `id`. The current .claude/ is backed up first.
+#[tauri::command]
+pub async fn restore_claude_dir_backup(id: String, state: State<'_, AppState>) -> Result<ClaudeDirBackup, String> {
+    let db = state.db.lock().map_err(|e| format!("DB lock error: {}", e))?;
+    let projects: Vec<(String, String)> = {
+        let mut stmt = db
+            .prepare("SELECT id, path FROM projects")
+            .map_err(|e| format!("Failed to prepare query: {}", e))?;
+        let rows = stmt
+            .query_map([], |row| Ok((row.get(0)?, row.get(1)?)))
+            .map_err(|e| format!("Failed to list projects: {}", e))?
+            .filter_map(|r| r.ok())
+            .collect();
+        rows
+    };
+    let (project_id, project_path) = projects
+        .into_iter()
+        .find(|(_, path)| claude_backup::find_backup(path, &id).is_some())
+        .ok_or_else(|| format!("Backup not found: {}", id))?;
+
+    let backup = claude_backup::restore(&project_path, &id)?;
+    events::publish(
+        &db,
+        AppEvent::activity(
+            &project_id,
+            ActivityType::Edit,
+            &format!("Restored .claude backup from {} ({})", backup.created_at, backup.reason),
+        ),
+    );
+    Ok(backup)
+}
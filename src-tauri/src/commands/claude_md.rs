@@ -6,12 +6,24 @@
 //! - Write CLAUDE.md content to disk
 //! - Generate new CLAUDE.md from project configuration
 //! - Calculate health scores for projects
+//! - Summarize the project's SQL schema into CLAUDE.md's Architecture section
+//! - Propose, apply, and reject AI-written diffs for natural-language CLAUDE.md edits
 //!
 //! DEPENDENCIES:
 //! - tauri - Command macro and State
 //! - db::AppState - Database connection for project lookup
 //! - core::generator - Template-based CLAUDE.md generation
 //! - core::health - Health score calculation and token estimation
+//! - core::doc_goals - Doc goal checks attached to the health score
+//! - core::conventions - Recorded convention violations attached to the health score
+//! - core::health_alerts - Alert threshold checks after each health computation
+//! - core::sql_schema - SQL parsing and Architecture section merge
+//! - commands::health_history - Per-commit health snapshots
+//! - commands::project - load_project for generate_claude_md
+//! - core::ai / core::ai_queue - Claude call for edit proposals
+//! - core::ai_audit - Edit proposals count toward the project's audit log
+//! - core::patch - Diff normalization and the configured patch engine for edit proposals and schema overviews
+//! - models::claude_md - ClaudeMdEdit
 //! - std::fs - File read/write operations
 //!
 //! EXPORTS:
@@ -19,31 +31,77 @@
 //! - write_claude_md - Write content to CLAUDE.md file
 //! - generate_claude_md - Generate CLAUDE.md from project data in database
 //! - get_health_score - Calculate health score for a project path (uses State for skill count)
+//! - compute_health_score - Shared health calculation used by get_health_score and the watcher
+//! - generate_schema_overview - Write a "### Database Schema" summary into CLAUDE.md
+//! - propose_claude_md_edit - AI diff for an edit instruction, stored for review
+//! - list_claude_md_edits - Edit proposals for a project, newest first
+//! - apply_claude_md_edit - Apply a reviewed edit (all hunks or none)
+//! - reject_claude_md_edit - Mark an edit proposal rejected
 //!
 //! PATTERNS:
 //! - All commands are async and return Result<T, String>
 //! - File paths are resolved from the project path + "CLAUDE.md"
 //! - Token estimation uses ~4 chars per token approximation
 //! - get_health_score queries skills count from DB for health scoring
+//! - get_health_score records a health snapshot when the project's git HEAD moves
+//! - compute_health_score uses the watcher's DocHealthCache (no tree walk) for the watched project
+//! - compute_health_score attaches doc goal results when the project has goals; git history is
+//!   only read (for outdated files) when a stale_days goal is set
+//! - compute_health_score reports the last check_conventions totals (it never scans for them)
+//! - compute_health_score checks the project's alert thresholds; alerts fire once per crossing
 //!
 //! CLAUDE NOTES:
 //! - CLAUDE.md is the most critical file for context rot prevention
 //! - read_claude_md returns exists=false if file not found (not an error)
 //! - generate_claude_md looks up project from DB by ID, then calls generator
 //! - write_claude_md always overwrites the entire file
+//! - generate_schema_overview only replaces its own subsection and never creates CLAUDE.md
+//! - Edit proposals are "conflict" when the diff no longer applies (CLAUDE.md changed since);
+//!   both patch engines write all hunks or none, so a failed apply leaves CLAUDE.md untouched
 
-use std::path::PathBuf;
+use std::path::{Path, PathBuf};
 
-use chrono::DateTime;
+use chrono::Utc;
+use rusqlite::Connection;
 use serde::Serialize;
 use tauri::State;
 
+use crate::commands::{health_history, project};
 use crate::core::ai;
+use crate::core::ai_audit;
+use crate::core::ai_queue;
+use crate::core::conventions;
+use crate::core::doc_goals;
+use crate::core::health_alerts;
 use crate::core::generator;
 use crate::core::health;
+use crate::core::patch;
+use crate::core::sql_schema;
 use crate::core::test_runner;
-use crate::db::{self, AppState};
-use crate::models::project::{HealthScore, Project};
+use crate::core::events::{self, AppEvent};
+use crate::db::AppState;
+use crate::models::activity::ActivityType;
+use crate::models::ai_queue::AiJobKind;
+use crate::models::claude_md::ClaudeMdEdit;
+use crate::models::project::HealthScore;
+use crate::models::sql_schema::SchemaOverview;
+
+const EDIT_COLUMNS: &str = "id, project_id, instruction, diff, explanation, status, error, created_at, applied_at";
+
+const EDIT_SYSTEM_PROMPT: &str = r#"You edit a project's CLAUDE.md to carry out one instruction with the smallest possible change.
+
+OUTPUT FORMAT (JSON only, no markdown fences):
+{
+  "diff": "unified diff of CLAUDE.md",
+  "explanation": "One sentence describing the change"
+}
+
+Rules:
+- The diff must only touch CLAUDE.md, with --- a/CLAUDE.md and +++ b/CLAUDE.md headers
+- Use 3 lines of unchanged context around each change, copied exactly from the file
+- Put new content in the section it belongs to; match the file's heading and bullet style
+- Do not rewrite, reorder, or reformat anything the instruction does not ask for
+- If the instruction is already satisfied or cannot be applied, return {"diff": "", "explanation": "<why>"}"#;
 
 /// Metadata about a CLAUDE.md file returned to the frontend.
 #[derive(Debug, Clone, Serialize)]
@@ -101,13 +159,13 @@ pub async fn write_claude_md(
         Ok(db) => {
             if let Ok(pid) = db.query_row(
                 "SELECT id FROM projects WHERE path = ?1",
-                [&project_path],
+                [project_path],
                 |row| row.get::<_, String>(0),
             ) {
-                let _ = db::log_activity_db(&db, &pid, "edit", "Updated CLAUDE.md");
+                events::publish(&db, AppEvent::activity(&pid, ActivityType::Edit, "Updated CLAUDE.md"));
             }
         }
-        Err(e) => eprintln!("Failed to lock DB for activity logging: {}", e),
+        Err(e) => tracing::warn!(error = %e, "Failed to lock DB for activity logging"),
     }
 
     Ok(())
@@ -127,38 +185,7 @@ pub async fn generate_claude_md(
             .lock()
             .map_err(|e| format!("Failed to lock database: {}", e))?;
 
-        let project = db
-            .query_row(
-                "SELECT id, name, path, description, project_type, language, framework, database_tech, testing, styling, stack_extras, health_score, created_at FROM projects WHERE id = ?1",
-                rusqlite::params![project_id],
-                |row| {
-                    let stack_extras_json: Option<String> = row.get(10)?;
-                    let stack_extras = stack_extras_json
-                        .and_then(|json| serde_json::from_str(&json).ok());
-
-                    let created_str: String = row.get(12)?;
-                    let created_at = DateTime::parse_from_rfc3339(&created_str)
-                        .map(|dt| dt.with_timezone(&chrono::Utc))
-                        .unwrap_or_else(|_| chrono::Utc::now());
-
-                    Ok(Project {
-                        id: row.get(0)?,
-                        name: row.get(1)?,
-                        path: row.get(2)?,
-                        description: row.get(3)?,
-                        project_type: row.get(4)?,
-                        language: row.get(5)?,
-                        framework: row.get(6)?,
-                        database: row.get(7)?,
-                        testing: row.get(8)?,
-                        styling: row.get(9)?,
-                        stack_extras,
-                        health_score: row.get(11)?,
-                        created_at,
-                    })
-                },
-            )
-            .map_err(|e| format!("Project not found: {}", e))?;
+        let project = project::load_project(&db, &project_id)?;
 
         let api_key_result = ai::get_api_key(&db);
         (project, api_key_result)
@@ -171,9 +198,9 @@ pub async fn generate_claude_md(
                 // Log activity on success (best-effort)
                 match state.db.lock() {
                     Ok(db) => {
-                        let _ = db::log_activity_db(&db, &project.id, "generate", "Generated CLAUDE.md (AI)");
+                        events::publish(&db, AppEvent::activity(&project.id, ActivityType::Generate, "Generated CLAUDE.md (AI)"));
                     }
-                    Err(e) => eprintln!("Failed to lock DB for activity logging: {}", e),
+                    Err(e) => tracing::warn!(error = %e, "Failed to lock DB for activity logging"),
                 }
                 return Ok(content);
             }
@@ -188,14 +215,159 @@ pub async fn generate_claude_md(
     // Log activity (best-effort)
     match state.db.lock() {
         Ok(db) => {
-            let _ = db::log_activity_db(&db, &project.id, "generate", "Generated CLAUDE.md (template)");
+            events::publish(&db, AppEvent::activity(&project.id, ActivityType::Generate, "Generated CLAUDE.md (template)"));
         }
-        Err(e) => eprintln!("Failed to lock DB for activity logging: {}", e),
+        Err(e) => tracing::warn!(error = %e, "Failed to lock DB for activity logging"),
     }
 
     Ok(content)
 }
 
+/// Summarize the project's SQL files (migrations included) into a
+/// "### Database Schema" subsection of CLAUDE.md's Architecture section.
+/// Returns the overview; CLAUDE.md is only updated when it already exists.
+#[tauri::command]
+pub async fn generate_schema_overview(
+    project_id: String,
+    state: State<'_, AppState>,
+) -> Result<SchemaOverview, String> {
+    let (project_path, engine): (String, _) = {
+        let db = state.db.lock().map_err(|e| format!("DB lock error: {}", e))?;
+        let path = db
+            .query_row("SELECT path FROM projects WHERE id = ?1", [&project_id], |row| row.get(0))
+            .map_err(|e| format!("Project not found: {}", e))?;
+        (path, patch::engine(&db))
+    };
+
+    let overview = sql_schema::generate_overview(engine, &project_path)?;
+
+    if overview.updated_claude_md {
+        match state.db.lock() {
+            Ok(db) => {
+                let message = format!(
+                    "Updated CLAUDE.md database schema ({} tables from {} SQL files)",
+                    overview.tables.len(),
+                    overview.source_files
+                );
+                events::publish(&db, AppEvent::activity(&project_id, ActivityType::Generate, &message));
+            }
+            Err(e) => tracing::warn!(error = %e, "Failed to lock DB for activity logging"),
+        }
+    }
+
+    Ok(overview)
+}
+
+/// Ask the AI for a minimal diff to CLAUDE.md that carries out `instruction`.
+/// The diff is stored for review ("pending", or "conflict" if it does not apply)
+/// and CLAUDE.md is not changed. Requires an API key.
+#[tauri::command]
+pub async fn propose_claude_md_edit(
+    project_id: String,
+    instruction: String,
+    state: State<'_, AppState>,
+) -> Result<ClaudeMdEdit, String> {
+    let instruction = instruction.trim().to_string();
+    if instruction.is_empty() {
+        return Err("Describe the change to make to CLAUDE.md".to_string());
+    }
+
+    let (project_path, api_key, engine) = {
+        let db = state.db.lock().map_err(|e| format!("DB lock error: {}", e))?;
+        let project_path: String = db
+            .query_row("SELECT path FROM projects WHERE id = ?1", [&project_id], |row| row.get(0))
+            .map_err(|e| format!("Project not found: {}", e))?;
+        (project_path, ai::get_api_key(&db)?, patch::engine(&db))
+    };
+
+    let file_path = PathBuf::from(&project_path).join("CLAUDE.md");
+    ai::ensure_ai_allowed(&project_path, &file_path.to_string_lossy())?;
+    let content = std::fs::read_to_string(&file_path)
+        .map_err(|e| format!("Failed to read CLAUDE.md (generate one first): {}", e))?;
+
+    let prompt = format!(
+        "Instruction: {}\n\nCurrent CLAUDE.md:\n```markdown\n{}\n```",
+        instruction, content
+    );
+    let call = ai::call_claude(&state.http_client, &api_key, EDIT_SYSTEM_PROMPT, &prompt);
+    let queued = ai_queue::run(AiJobKind::Interactive, "CLAUDE.md edit", call);
+    let response = ai_audit::scoped(&project_id, "CLAUDE.md edit", queued).await?;
+    let (raw_diff, explanation) = parse_edit_response(&response)?;
+    let diff = patch::normalize_diff(&raw_diff, "CLAUDE.md")?;
+    let (status, error) = match patch::check(engine, Path::new(&project_path), &diff) {
+        Ok(()) => ("pending", None),
+        Err(e) => ("conflict", Some(e)),
+    };
+
+    let edit = ClaudeMdEdit {
+        id: uuid::Uuid::new_v4().to_string(),
+        project_id,
+        instruction,
+        diff,
+        explanation,
+        status: status.to_string(),
+        error,
+        created_at: Utc::now().to_rfc3339(),
+        applied_at: None,
+    };
+    let db = state.db.lock().map_err(|e| format!("DB lock error: {}", e))?;
+    insert_edit_db(&db, &edit)?;
+    Ok(edit)
+}
+
+/// CLAUDE.md edit proposals for a project, newest first.
+#[tauri::command]
+pub async fn list_claude_md_edits(project_id: String, state: State<'_, AppState>) -> Result<Vec<ClaudeMdEdit>, String> {
+    let db = state.db.lock().map_err(|e| format!("DB lock error: {}", e))?;
+    list_edits_db(&db, &project_id)
+}
+
+/// Apply a reviewed edit to CLAUDE.md. An edit that no longer applies is marked
+/// "conflict", CLAUDE.md is left unchanged, and an error is returned.
+#[tauri::command]
+pub async fn apply_claude_md_edit(id: String, state: State<'_, AppState>) -> Result<ClaudeMdEdit, String> {
+    let (edit, project_path, engine) = {
+        let db = state.db.lock().map_err(|e| format!("DB lock error: {}", e))?;
+        let edit = load_edit_db(&db, &id)?;
+        let project_path: String = db
+            .query_row("SELECT path FROM projects WHERE id = ?1", [&edit.project_id], |row| row.get(0))
+            .map_err(|e| format!("Project not found: {}", e))?;
+        (edit, project_path, patch::engine(&db))
+    };
+    if edit.status == "applied" || edit.status == "rejected" {
+        return Err(format!("CLAUDE.md edit is already {}", edit.status));
+    }
+
+    let root = Path::new(&project_path);
+    let result = patch::check(engine, root, &edit.diff).and_then(|_| patch::apply(engine, root, &edit.diff));
+
+    let db = state.db.lock().map_err(|e| format!("DB lock error: {}", e))?;
+    match result {
+        Ok(()) => {
+            set_edit_status_db(&db, &id, "applied", None, Some(&Utc::now().to_rfc3339()))?;
+            let message = format!("Edited CLAUDE.md: {}", edit.instruction);
+            events::publish(&db, AppEvent::activity(&edit.project_id, ActivityType::Edit, &message));
+            load_edit_db(&db, &id)
+        }
+        Err(e) => {
+            set_edit_status_db(&db, &id, "conflict", Some(&e), None)?;
+            Err(format!("CLAUDE.md edit no longer applies: {}", e))
+        }
+    }
+}
+
+/// Mark an edit proposal rejected. Applied edits cannot be rejected.
+#[tauri::command]
+pub async fn reject_claude_md_edit(id: String, state: State<'_, AppState>) -> Result<ClaudeMdEdit, String> {
+    let db = state.db.lock().map_err(|e| format!("DB lock error: {}", e))?;
+    let edit = load_edit_db(&db, &id)?;
+    if edit.status == "applied" {
+        return Err("CLAUDE.md edit is already applied".to_string());
+    }
+    set_edit_status_db(&db, &id, "rejected", None, None)?;
+    load_edit_db(&db, &id)
+}
+
 /// Calculate and return the health score for a project path.
 /// Queries the database for skill count and latest test metrics to include in the calculation.
 #[tauri::command]
@@ -203,7 +375,21 @@ pub async fn get_health_score(
     project_path: String,
     state: State<'_, AppState>,
 ) -> Result<HealthScore, String> {
-    let (skill_count, test_coverage, test_pass_rate, perf_score) = {
+    let (project_id, score) = compute_health_score(&state, &project_path)?;
+
+    // Snapshot health the first time a new git HEAD is seen (regression tracking)
+    if let Some(pid) = &project_id {
+        let _ = health_history::record_snapshot_if_head_moved(&state, pid, &project_path, &score);
+    }
+
+    Ok(score)
+}
+
+/// Compute the health score for a project path, using the watcher's
+/// DocHealthCache when it covers this project. Stores the total in
+/// projects.health_score and returns the project id (if registered).
+pub fn compute_health_score(state: &AppState, project_path: &str) -> Result<(Option<String>, HealthScore), String> {
+    let (project_id, skill_count, test_coverage, test_pass_rate, perf_score, goals, convention_totals) = {
         let db = state
             .db
             .lock()
@@ -255,9 +441,11 @@ pub async fn get_health_score(
                 )
                 .ok();
 
-            (skills, Some(coverage), Some(pass_rate), perf_score)
+            let goals = health_history::load_doc_goals(&db, pid);
+            let convention_totals = conventions::violation_totals(&db, pid);
+            (project_id, skills, Some(coverage), Some(pass_rate), perf_score, goals, convention_totals)
         } else {
-            (0, None, None, None)
+            (None, 0, None, None, None, Default::default(), None)
         }
     };
 
@@ -268,18 +456,221 @@ pub async fn get_health_score(
         || test_pass_rate.is_some_and(|r| r > 0.0);
 
     let discovered_test_count = if !has_run_data {
-        let count = test_runner::count_static_grep(std::path::Path::new(&project_path));
+        let count = test_runner::count_static_grep(std::path::Path::new(project_path));
         if count > 0 { Some(count) } else { None }
     } else {
         None
     };
 
-    Ok(health::calculate_health_with_tests(
-        &project_path,
-        skill_count,
-        test_coverage,
-        test_pass_rate,
-        perf_score,
-        discovered_test_count,
-    ))
+    let cached = {
+        let cache = state
+            .health_cache
+            .lock()
+            .map_err(|e| format!("Failed to lock health cache: {}", e))?;
+        cache.as_ref().filter(|c| c.project_path() == project_path).map(|docs| {
+            let score = health::calculate_health_cached(
+                docs,
+                skill_count,
+                test_coverage,
+                test_pass_rate,
+                perf_score,
+                discovered_test_count,
+            );
+            (score, docs.doc_coverage(), docs.outdated_files())
+        })
+    };
+    let (mut score, doc_coverage, outdated) = match cached {
+        Some(result) => result,
+        None => {
+            let docs = health::DocHealthCache::build(project_path);
+            let score = health::calculate_health_cached(
+                &docs,
+                skill_count,
+                test_coverage,
+                test_pass_rate,
+                perf_score,
+                discovered_test_count,
+            );
+            (score, docs.doc_coverage(), docs.outdated_files())
+        }
+    };
+
+    if !goals.is_empty() {
+        let stale = if goals.max_stale_days.is_some() {
+            doc_goals::stale_doc_ages(project_path, &outdated, chrono::Utc::now())
+        } else {
+            Vec::new()
+        };
+        score.goals = doc_goals::evaluate_goals(&goals, doc_coverage, score.total, &stale);
+    }
+
+    if let Some((violations, rules)) = convention_totals {
+        score.convention_violations = Some(violations);
+        if let Some(win) = conventions::quick_win(violations, rules) {
+            score.quick_wins.push(win);
+            score.quick_wins.sort_by(|a, b| b.impact.cmp(&a.impact));
+        }
+    }
+
+    if let Some(pid) = &project_id {
+        let db = state
+            .db
+            .lock()
+            .map_err(|e| format!("Failed to lock database: {}", e))?;
+        let _ = db.execute(
+            "UPDATE projects SET health_score = ?1 WHERE path = ?2 AND health_score != ?1",
+            rusqlite::params![score.total, project_path],
+        );
+        if let Err(e) = health_alerts::check(&db, pid, score.total, outdated.len() as u32) {
+            tracing::warn!(error = %e, "Health alert check failed");
+        }
+    }
+
+    Ok((project_id, score))
+}
+
+/// (diff, explanation) from the AI's JSON. Err carries the AI's reason when it
+/// declined (empty diff).
+fn parse_edit_response(response: &str) -> Result<(String, String), String> {
+    let json = response
+        .trim()
+        .trim_start_matches("```json")
+        .trim_start_matches("```")
+        .trim_end_matches("```")
+        .trim();
+    let val: serde_json::Value =
+        serde_json::from_str(json).map_err(|e| format!("Failed to parse AI edit response: {}", e))?;
+    let explanation = val.get("explanation").and_then(|v| v.as_str()).unwrap_or("").trim().to_string();
+    let diff = val.get("diff").and_then(|v| v.as_str()).unwrap_or("").trim();
+    if diff.is_empty() {
+        return Err(if explanation.is_empty() {
+            "The AI proposed no change to CLAUDE.md".to_string()
+        } else {
+            format!("No change proposed: {}", explanation)
+        });
+    }
+    Ok((diff.to_string(), explanation))
+}
+
+fn row_to_edit(row: &rusqlite::Row) -> rusqlite::Result<ClaudeMdEdit> {
+    Ok(ClaudeMdEdit {
+        id: row.get(0)?,
+        project_id: row.get(1)?,
+        instruction: row.get(2)?,
+        diff: row.get(3)?,
+        explanation: row.get(4)?,
+        status: row.get(5)?,
+        error: row.get(6)?,
+        created_at: row.get(7)?,
+        applied_at: row.get(8)?,
+    })
+}
+
+fn insert_edit_db(db: &Connection, edit: &ClaudeMdEdit) -> Result<(), String> {
+    db.execute(
+        &format!(
+            "INSERT INTO claude_md_edits ({}) VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7, ?8, ?9)",
+            EDIT_COLUMNS
+        ),
+        rusqlite::params![
+            edit.id,
+            edit.project_id,
+            edit.instruction,
+            edit.diff,
+            edit.explanation,
+            edit.status,
+            edit.error,
+            edit.created_at,
+            edit.applied_at
+        ],
+    )
+    .map_err(|e| format!("Failed to save CLAUDE.md edit: {}", e))?;
+    Ok(())
+}
+
+fn load_edit_db(db: &Connection, id: &str) -> Result<ClaudeMdEdit, String> {
+    db.query_row(
+        &format!("SELECT {} FROM claude_md_edits WHERE id = ?1", EDIT_COLUMNS),
+        [id],
+        row_to_edit,
+    )
+    .map_err(|e| format!("CLAUDE.md edit not found: {}", e))
+}
+
+fn list_edits_db(db: &Connection, project_id: &str) -> Result<Vec<ClaudeMdEdit>, String> {
+    let mut stmt = db
+        .prepare(&format!(
+            "SELECT {} FROM claude_md_edits WHERE project_id = ?1 ORDER BY created_at DESC",
+            EDIT_COLUMNS
+        ))
+        .map_err(|e| format!("Failed to query CLAUDE.md edits: {}", e))?;
+    let edits = stmt
+        .query_map([project_id], row_to_edit)
+        .map_err(|e| format!("Failed to read CLAUDE.md edits: {}", e))?
+        .filter_map(|r| r.ok())
+        .collect();
+    Ok(edits)
+}
+
+fn set_edit_status_db(
+    db: &Connection,
+    id: &str,
+    status: &str,
+    error: Option<&str>,
+    applied_at: Option<&str>,
+) -> Result<(), String> {
+    db.execute(
+        "UPDATE claude_md_edits SET status = ?1, error = ?2, applied_at = ?3 WHERE id = ?4",
+        rusqlite::params![status, error, applied_at, id],
+    )
+    .map_err(|e| format!("Failed to update CLAUDE.md edit: {}", e))?;
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_support::with_project;
+
+    fn edit(id: &str, created_at: &str) -> ClaudeMdEdit {
+        ClaudeMdEdit {
+            id: id.to_string(),
+            project_id: "p1".to_string(),
+            instruction: "Document API handlers".to_string(),
+            diff: "--- a/CLAUDE.md\n+++ b/CLAUDE.md\n@@ -1 +1,2 @@\n # App\n+- All API handlers must be documented\n".to_string(),
+            explanation: "Adds a convention".to_string(),
+            status: "pending".to_string(),
+            error: None,
+            created_at: created_at.to_string(),
+            applied_at: None,
+        }
+    }
+
+    #[test]
+    fn test_edit_status_roundtrip() {
+        let conn = with_project();
+        insert_edit_db(&conn, &edit("e1", "2025-01-02T00:00:00Z")).unwrap();
+        insert_edit_db(&conn, &edit("e2", "2025-01-03T00:00:00Z")).unwrap();
+        set_edit_status_db(&conn, "e1", "conflict", Some("patch does not apply"), None).unwrap();
+
+        let listed = list_edits_db(&conn, "p1").unwrap();
+        assert_eq!(listed.iter().map(|e| e.id.as_str()).collect::<Vec<_>>(), vec!["e2", "e1"]);
+        assert_eq!(listed[1].status, "conflict");
+        assert_eq!(listed[1].error.as_deref(), Some("patch does not apply"));
+        assert!(load_edit_db(&conn, "missing").is_err());
+    }
+
+    #[test]
+    fn test_parse_edit_response() {
+        let response = "```json\n{\"diff\": \"--- a/CLAUDE.md\\n+++ b/CLAUDE.md\\n\", \"explanation\": \"Add rule\"}\n```";
+        assert_eq!(
+            parse_edit_response(response).unwrap(),
+            ("--- a/CLAUDE.md\n+++ b/CLAUDE.md".to_string(), "Add rule".to_string())
+        );
+        assert_eq!(
+            parse_edit_response("{\"diff\": \"\", \"explanation\": \"Already documented\"}").unwrap_err(),
+            "No change proposed: Already documented"
+        );
+        assert!(parse_edit_response("not json").is_err());
+    }
 }
@@ -0,0 +1,75 @@
+//! @module commands/command_guard
+//! @description Tauri IPC commands for the per-project command allowlist
+//!
+//! PURPOSE:
+//! - List commands seen in PRDs and loop templates with their approval status
+//! - Approve or deny a command (the confirmation step for first-time commands)
+//! - Remove a command from the allowlist
+//!
+//! DEPENDENCIES:
+//! - tauri - Command macro and State
+//! - db::AppState - Database connection (command_approvals, projects)
+//! - core::command_guard - Validation and allowlist storage
+//! - models::command_guard - CommandApproval type
+//!
+//! EXPORTS:
+//! - list_command_approvals - Allowlist entries for a project, pending first
+//! - set_command_approval - Approve or deny a command for a project
+//! - remove_command_approval - Forget a command so it needs approval again
+//!
+//! PATTERNS:
+//! - Starting a PRD or template loop with unapproved commands fails with
+//!   "Commands need approval..." and records them as pending; the UI shows
+//!   them via list_command_approvals and confirms with set_command_approval
+//!
+//! CLAUDE NOTES:
+//! - set_command_approval validates the command, so a command with shell
+//!   metacharacters or paths outside the project can never be approved
+
+use tauri::State;
+
+use crate::core::command_guard;
+use crate::core::events::{self, AppEvent};
+use crate::db::AppState;
+use crate::models::activity::ActivityType;
+use crate::models::command_guard::CommandApproval;
+
+/// List the commands recorded for a project, pending first.
+#[tauri::command]
+pub async fn list_command_approvals(
+    project_id: String,
+    state: State<'_, AppState>,
+) -> Result<Vec<CommandApproval>, String> {
+    let db = state.db.lock().map_err(|e| format!("DB lock error: {}", e))?;
+    command_guard::list_approvals(&db, &project_id)
+}
+
+/// Approve (or deny) a command for a project.
+#[tauri::command]
+pub async fn set_command_approval(
+    project_id: String,
+    command: String,
+    approved: bool,
+    state: State<'_, AppState>,
+) -> Result<(), String> {
+    let db = state.db.lock().map_err(|e| format!("DB lock error: {}", e))?;
+    let project_path: String = db
+        .query_row("SELECT path FROM projects WHERE id = ?1", [&project_id], |row| row.get(0))
+        .map_err(|e| format!("Project not found: {}", e))?;
+    command_guard::set_approval(&db, &project_id, &project_path, &command, approved)?;
+
+    let verb = if approved { "Approved" } else { "Denied" };
+    events::publish(&db, AppEvent::activity(&project_id, ActivityType::Enforcement, &format!("{} command: {}", verb, command.trim())));
+    Ok(())
+}
+
+/// Remove a command from a project's allowlist. Returns false if it was not recorded.
+#[tauri::command]
+pub async fn remove_command_approval(
+    project_id: String,
+    command: String,
+    state: State<'_, AppState>,
+) -> Result<bool, String> {
+    let db = state.db.lock().map_err(|e| format!("DB lock error: {}", e))?;
+    command_guard::remove_approval(&db, &project_id, &command)
+}
@@ -10,14 +10,22 @@
 //! - tauri - Command macro and State
 //! - db::AppState - Database connection for project/skills/checkpoint queries
 //! - core::health - Token estimation utility
-//! - models::context - ContextHealth, TokenBreakdown, McpServerStatus, Checkpoint types
+//! - core::mcp_catalog - Curated MCP servers and .mcp.json edits
+//! - models::context - ContextHealth, TokenBreakdown, McpServerStatus, Checkpoint, CheckpointDiff types
+//! - commands::versions - diff_lines for the CLAUDE.md diff
 //! - std::path::Path - File system checks for MCP config
+//! - core::safe_read - Streamed header reads for doc token estimates
 //!
 //! EXPORTS:
 //! - get_context_health - Calculate context token usage and rot risk
 //! - get_mcp_status - List MCP servers with overhead and recommendations
 //! - create_checkpoint - Save a context state snapshot
 //! - list_checkpoints - Get checkpoints for a project
+//! - compare_checkpoint_to_current - What restoring a checkpoint would change (CLAUDE.md, skills, MCP, tokens)
+//! - list_mcp_catalog - Curated MCP servers, marked installed for a project
+//! - add_mcp_server_to_project - Add a catalog server to the project's .mcp.json
+//! - remove_mcp_server - Remove a server from the project's .mcp.json
+//! - add_jumpstart_mcp_server - Add the app's own jumpstart-mcp server to the project's .mcp.json
 //!
 //! PATTERNS:
 //! - Context budget is 200k tokens (Claude's context window)
@@ -29,16 +37,28 @@
 //! - Token estimation uses ~4 chars per token (same as core::health::estimate_tokens)
 //! - Context health drives the status bar "Context: XX%" indicator
 //! - Checkpoints are manually created snapshots for context recovery
+//! - Checkpoints store CheckpointState JSON; older checkpoints without it only compare token totals
 //! - MCP detection reads project-level config files using serde_json
 //! - Conversation tokens scale with code_tokens (min 2000, +10% of code tokens)
 //! - MCP token estimation: config content tokens + 400 per server for tool schemas
+//! - Catalog add/remove only edits .mcp.json; .claude/mcp_servers.json is read-only here
+//! - The jumpstart-mcp server is opt-in per project and removed with remove_mcp_server
+//!   (server "project-jumpstart"); it needs the app running to answer
+
+use std::collections::HashMap;
 
 use chrono::Utc;
 use tauri::State;
 
-use crate::core::health;
-use crate::db::{self, AppState};
-use crate::models::context::{Checkpoint, ContextHealth, McpServerStatus, TokenBreakdown};
+use crate::commands::versions::diff_lines;
+use crate::core::{health, mcp_catalog, safe_read};
+use crate::core::events::{self, AppEvent};
+use crate::db::AppState;
+use crate::models::activity::ActivityType;
+use crate::models::context::{
+    Checkpoint, CheckpointDiff, CheckpointState, ContextHealth, McpCatalogEntry, McpConfigChange,
+    McpServerStatus, TokenBreakdown,
+};
 
 /// Maximum context budget in tokens (Claude's context window).
 const CONTEXT_BUDGET: u32 = 200_000;
@@ -128,6 +148,8 @@ pub async fn get_mcp_status(project_path: String) -> Result<Vec<McpServerStatus>
 }
 
 /// Create a context checkpoint — a snapshot of the current context state.
+/// CLAUDE.md, skill and MCP server names, and the token breakdown are stored with it
+/// so compare_checkpoint_to_current can diff against them later.
 #[tauri::command]
 pub async fn create_checkpoint(
     project_id: String,
@@ -136,13 +158,11 @@ pub async fn create_checkpoint(
     project_path: String,
     state: State<'_, AppState>,
 ) -> Result<Checkpoint, String> {
-    let path = std::path::Path::new(&project_path);
-    let code_tokens = estimate_code_tokens(path);
-    let skills_tokens = estimate_skills_tokens(&project_path, &state)?;
-    let mcp_tokens = estimate_mcp_tokens(path);
-    let conversation_tokens = estimate_conversation_tokens(code_tokens);
-    let total = code_tokens + skills_tokens + mcp_tokens + conversation_tokens;
+    let context_state = capture_state(&project_path, &state)?;
+    let total = breakdown_total(&context_state.breakdown);
     let context_percent = (total as f64 / CONTEXT_BUDGET as f64 * 100.0).min(100.0);
+    let state_json = serde_json::to_string(&context_state)
+        .map_err(|e| format!("Failed to serialize checkpoint state: {}", e))?;
 
     let db = state
         .db
@@ -153,13 +173,13 @@ pub async fn create_checkpoint(
     let now = Utc::now().to_rfc3339();
 
     db.execute(
-        "INSERT INTO checkpoints (id, project_id, label, summary, token_snapshot, context_percent, created_at) VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7)",
-        rusqlite::params![id, project_id, label, summary, total, context_percent, now],
+        "INSERT INTO checkpoints (id, project_id, label, summary, token_snapshot, context_percent, created_at, context_state) VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7, ?8)",
+        rusqlite::params![id, project_id, label, summary, total, context_percent, now, state_json],
     )
     .map_err(|e| format!("Failed to create checkpoint: {}", e))?;
 
     // Log activity
-    let _ = db::log_activity_db(&db, &project_id, "health", &format!("Created checkpoint: {}", &label));
+    events::publish(&db, AppEvent::activity(&project_id, ActivityType::Health, &format!("Created checkpoint: {}", &label)));
 
     Ok(Checkpoint {
         id,
@@ -208,6 +228,275 @@ pub async fn list_checkpoints(
     Ok(checkpoints)
 }
 
+/// Compare a checkpoint with the project's current context: CLAUDE.md line diff,
+/// skills and MCP servers restoring would add or remove, and the token delta.
+#[tauri::command]
+pub async fn compare_checkpoint_to_current(
+    checkpoint_id: String,
+    state: State<'_, AppState>,
+) -> Result<CheckpointDiff, String> {
+    let (checkpoint, saved, project_path) = {
+        let db = state.db.lock().map_err(|e| format!("DB lock error: {}", e))?;
+        let (checkpoint, state_json) = db
+            .query_row(
+                "SELECT id, project_id, label, summary, token_snapshot, context_percent, created_at, context_state FROM checkpoints WHERE id = ?1",
+                [&checkpoint_id],
+                |row| {
+                    Ok((
+                        Checkpoint {
+                            id: row.get(0)?,
+                            project_id: row.get(1)?,
+                            label: row.get(2)?,
+                            summary: row.get(3)?,
+                            token_snapshot: row.get(4)?,
+                            context_percent: row.get(5)?,
+                            created_at: row.get(6)?,
+                        },
+                        row.get::<_, Option<String>>(7)?,
+                    ))
+                },
+            )
+            .map_err(|e| format!("Checkpoint not found: {}", e))?;
+        let saved = match state_json {
+            Some(json) => Some(
+                serde_json::from_str::<CheckpointState>(&json)
+                    .map_err(|e| format!("Invalid checkpoint state: {}", e))?,
+            ),
+            None => None,
+        };
+        let project_path = lookup_project_path(&db, &checkpoint.project_id)?;
+        (checkpoint, saved, project_path)
+    };
+
+    let current = capture_state(&project_path, &state)?;
+    Ok(diff_checkpoint(checkpoint, saved, current))
+}
+
+/// List the curated MCP server catalog. With a project, entries already in
+/// the project's .mcp.json are marked installed.
+#[tauri::command]
+pub async fn list_mcp_catalog(
+    project_id: Option<String>,
+    state: State<'_, AppState>,
+) -> Result<Vec<McpCatalogEntry>, String> {
+    let mut entries = mcp_catalog::catalog();
+    if let Some(project_id) = project_id {
+        let db = state.db.lock().map_err(|e| format!("DB lock error: {}", e))?;
+        let project_path = lookup_project_path(&db, &project_id)?;
+        let configured = mcp_catalog::configured_servers(std::path::Path::new(&project_path));
+        for entry in &mut entries {
+            entry.installed = configured.contains(&entry.id);
+        }
+    }
+    Ok(entries)
+}
+
+/// Add a catalog MCP server to the project's .mcp.json, keeping all other
+/// servers and settings. env supplies values for the server's variables;
+/// secret ones must be "${VAR}" references (omitted secrets become "${NAME}").
+#[tauri::command]
+pub async fn add_mcp_server_to_project(
+    project_id: String,
+    server: String,
+    env: Option<HashMap<String, String>>,
+    state: State<'_, AppState>,
+) -> Result<McpConfigChange, String> {
+    let entry = mcp_catalog::find_entry(&server)
+        .ok_or_else(|| format!("Unknown MCP server '{}'", server))?;
+    let server_json = mcp_catalog::build_server_entry(&entry, &env.unwrap_or_default())?;
+
+    let db = state.db.lock().map_err(|e| format!("DB lock error: {}", e))?;
+    let project_path = lookup_project_path(&db, &project_id)?;
+    let path = std::path::Path::new(&project_path);
+    let action = mcp_catalog::add_server(path, &entry.id, server_json)?;
+
+    if action != "unchanged" {
+        events::publish(
+            &db,
+            AppEvent::activity(
+                &project_id,
+                ActivityType::Settings,
+                &format!("MCP server {} {} (.mcp.json)", entry.name, action),
+            ),
+        );
+    }
+
+    Ok(McpConfigChange {
+        server: entry.id,
+        config_path: path.join(mcp_catalog::MCP_CONFIG_FILE).to_string_lossy().to_string(),
+        action: action.to_string(),
+    })
+}
+
+/// Remove an MCP server (catalog or custom) from the project's .mcp.json.
+#[tauri::command]
+pub async fn remove_mcp_server(
+    project_id: String,
+    server: String,
+    state: State<'_, AppState>,
+) -> Result<McpConfigChange, String> {
+    let db = state.db.lock().map_err(|e| format!("DB lock error: {}", e))?;
+    let project_path = lookup_project_path(&db, &project_id)?;
+    let path = std::path::Path::new(&project_path);
+    let removed = mcp_catalog::remove_server(path, &server)?;
+
+    if removed {
+        events::publish(
+            &db,
+            AppEvent::activity(
+                &project_id,
+                ActivityType::Settings,
+                &format!("MCP server {} removed (.mcp.json)", server),
+            ),
+        );
+    }
+
+    Ok(McpConfigChange {
+        server,
+        config_path: path.join(mcp_catalog::MCP_CONFIG_FILE).to_string_lossy().to_string(),
+        action: if removed { "removed" } else { "not_found" }.to_string(),
+    })
+}
+
+/// Add the built-in jumpstart-mcp server (module docs and conventions on demand) to the
+/// project's .mcp.json.
+#[tauri::command]
+pub async fn add_jumpstart_mcp_server(
+    project_id: String,
+    state: State<'_, AppState>,
+) -> Result<McpConfigChange, String> {
+    let exe = std::env::current_exe().map_err(|e| format!("Could not locate the app executable: {}", e))?;
+    let app_dir = exe.parent().ok_or("Could not locate the app directory")?;
+    let server_json = mcp_catalog::jumpstart_server_entry(app_dir)?;
+
+    let db = state.db.lock().map_err(|e| format!("DB lock error: {}", e))?;
+    let project_path = lookup_project_path(&db, &project_id)?;
+    let path = std::path::Path::new(&project_path);
+    let action = mcp_catalog::add_server(path, mcp_catalog::JUMPSTART_SERVER, server_json)?;
+
+    if action != "unchanged" {
+        events::publish(
+            &db,
+            AppEvent::activity(
+                &project_id,
+                ActivityType::Settings,
+                &format!("MCP server Project Jumpstart {} (.mcp.json)", action),
+            ),
+        );
+    }
+
+    Ok(McpConfigChange {
+        server: mcp_catalog::JUMPSTART_SERVER.to_string(),
+        config_path: path.join(mcp_catalog::MCP_CONFIG_FILE).to_string_lossy().to_string(),
+        action: action.to_string(),
+    })
+}
+
+fn lookup_project_path(db: &rusqlite::Connection, project_id: &str) -> Result<String, String> {
+    db.query_row("SELECT path FROM projects WHERE id = ?1", [project_id], |row| row.get(0))
+        .map_err(|e| format!("Project not found: {}", e))
+}
+
+// --- Checkpoint State Helpers ---
+
+/// Capture the project's current context state (takes the DB lock briefly).
+fn capture_state(project_path: &str, state: &State<'_, AppState>) -> Result<CheckpointState, String> {
+    let path = std::path::Path::new(project_path);
+    let code = estimate_code_tokens(path);
+    let skills_tokens = estimate_skills_tokens(project_path, state)?;
+    let skills = {
+        let db = state.db.lock().map_err(|e| format!("DB lock error: {}", e))?;
+        skill_names(&db, project_path)?
+    };
+
+    Ok(CheckpointState {
+        claude_md: std::fs::read_to_string(path.join("CLAUDE.md")).unwrap_or_default(),
+        skills,
+        mcp_servers: mcp_server_names(path),
+        breakdown: TokenBreakdown {
+            conversation: estimate_conversation_tokens(code),
+            code,
+            mcp: estimate_mcp_tokens(path),
+            skills: skills_tokens,
+        },
+    })
+}
+
+/// Sorted names of the skills counted by estimate_skills_tokens.
+fn skill_names(db: &rusqlite::Connection, project_path: &str) -> Result<Vec<String>, String> {
+    let mut stmt = db
+        .prepare(
+            "SELECT DISTINCT name FROM skills WHERE project_id = (SELECT id FROM projects WHERE path = ?1) OR project_id IS NULL ORDER BY name",
+        )
+        .map_err(|e| format!("Failed to query skills: {}", e))?;
+    let names = stmt
+        .query_map([project_path], |row| row.get(0))
+        .map_err(|e| format!("Failed to read skills: {}", e))?
+        .filter_map(|r| r.ok())
+        .collect();
+    Ok(names)
+}
+
+/// Sorted, de-duplicated MCP server names from both project config files.
+fn mcp_server_names(project_path: &std::path::Path) -> Vec<String> {
+    let mut servers = Vec::new();
+    for config_path in [
+        project_path.join(".mcp.json"),
+        project_path.join(".claude").join("mcp_servers.json"),
+    ] {
+        if let Ok(content) = std::fs::read_to_string(&config_path) {
+            parse_mcp_config(&content, &mut servers);
+        }
+    }
+    let mut names: Vec<String> = servers.into_iter().map(|s| s.name).collect();
+    names.sort();
+    names.dedup();
+    names
+}
+
+fn breakdown_total(breakdown: &TokenBreakdown) -> u32 {
+    breakdown.code + breakdown.skills + breakdown.mcp + breakdown.conversation
+}
+
+/// Names in `from` that are missing from `to`.
+fn missing_from(from: &[String], to: &[String]) -> Vec<String> {
+    from.iter().filter(|name| !to.contains(name)).cloned().collect()
+}
+
+/// Diff from the current state to the checkpoint. Without saved state only tokens are compared.
+fn diff_checkpoint(checkpoint: Checkpoint, saved: Option<CheckpointState>, current: CheckpointState) -> CheckpointDiff {
+    let current_tokens = breakdown_total(&current.breakdown);
+    let checkpoint_tokens = checkpoint.token_snapshot;
+    let mut diff = CheckpointDiff {
+        checkpoint,
+        has_state: saved.is_some(),
+        claude_md_changed: false,
+        claude_md_diff: Vec::new(),
+        skills_added: Vec::new(),
+        skills_removed: Vec::new(),
+        mcp_servers_added: Vec::new(),
+        mcp_servers_removed: Vec::new(),
+        current_tokens,
+        checkpoint_tokens,
+        token_delta: checkpoint_tokens as i64 - current_tokens as i64,
+        current_breakdown: current.breakdown.clone(),
+        checkpoint_breakdown: None,
+    };
+
+    if let Some(saved) = saved {
+        diff.claude_md_changed = saved.claude_md != current.claude_md;
+        if diff.claude_md_changed {
+            diff.claude_md_diff = diff_lines(&current.claude_md, &saved.claude_md);
+        }
+        diff.skills_added = missing_from(&saved.skills, &current.skills);
+        diff.skills_removed = missing_from(&current.skills, &saved.skills);
+        diff.mcp_servers_added = missing_from(&saved.mcp_servers, &current.mcp_servers);
+        diff.mcp_servers_removed = missing_from(&current.mcp_servers, &saved.mcp_servers);
+        diff.checkpoint_breakdown = Some(saved.breakdown);
+    }
+    diff
+}
+
 // --- Token Estimation Helpers ---
 
 /// Estimate tokens used by code context (CLAUDE.md + source files with doc headers).
@@ -255,9 +544,8 @@ fn estimate_dir_doc_tokens(dir: &std::path::Path) -> u32 {
         if path.is_dir() {
             tokens += estimate_dir_doc_tokens(&path);
         } else if is_source_file(&name) {
-            if let Ok(content) = std::fs::read_to_string(&path) {
-                // Only count the doc header portion (first 30 lines)
-                let header: String = content.lines().take(30).collect::<Vec<_>>().join("\n");
+            // Only count the doc header portion (first 30 lines), streamed
+            if let Ok(header) = safe_read::read_head(&path, 30) {
                 if header.contains("@module") || header.contains("@description") {
                     tokens += health::estimate_tokens(&header);
                 }
@@ -479,4 +767,53 @@ mod tests {
         assert_eq!(count_mcp_servers_in_config("{}"), 0);
         assert_eq!(count_mcp_servers_in_config("invalid json"), 0);
     }
+
+    fn checkpoint(tokens: u32) -> Checkpoint {
+        Checkpoint {
+            id: "cp-1".to_string(),
+            project_id: "p1".to_string(),
+            label: "before refactor".to_string(),
+            summary: String::new(),
+            token_snapshot: tokens,
+            context_percent: 0.0,
+            created_at: "2026-01-01T00:00:00Z".to_string(),
+        }
+    }
+
+    fn names(list: &[&str]) -> Vec<String> {
+        list.iter().map(|s| s.to_string()).collect()
+    }
+
+    #[test]
+    fn test_diff_checkpoint() {
+        let saved = CheckpointState {
+            claude_md: "# App\nUse pnpm".to_string(),
+            skills: names(&["api-design", "testing"]),
+            mcp_servers: names(&["filesystem"]),
+            breakdown: TokenBreakdown { conversation: 2000, code: 1000, mcp: 500, skills: 500 },
+        };
+        let current = CheckpointState {
+            claude_md: "# App\nUse npm".to_string(),
+            skills: names(&["testing", "tracing"]),
+            mcp_servers: names(&["filesystem", "postgres"]),
+            breakdown: TokenBreakdown { conversation: 2000, code: 1200, mcp: 1300, skills: 500 },
+        };
+
+        let diff = diff_checkpoint(checkpoint(4000), Some(saved), current.clone());
+        assert!(diff.has_state && diff.claude_md_changed);
+        assert!(diff.claude_md_diff.iter().any(|l| l.kind == "removed" && l.text == "Use npm"));
+        assert!(diff.claude_md_diff.iter().any(|l| l.kind == "added" && l.text == "Use pnpm"));
+        assert_eq!(diff.skills_added, names(&["api-design"]));
+        assert_eq!(diff.skills_removed, names(&["tracing"]));
+        assert!(diff.mcp_servers_added.is_empty());
+        assert_eq!(diff.mcp_servers_removed, names(&["postgres"]));
+        assert_eq!(diff.current_tokens, 5000);
+        assert_eq!(diff.token_delta, -1000);
+
+        // Checkpoints without stored state only compare token totals
+        let legacy = diff_checkpoint(checkpoint(6000), None, current);
+        assert!(!legacy.has_state && !legacy.claude_md_changed);
+        assert!(legacy.skills_added.is_empty() && legacy.checkpoint_breakdown.is_none());
+        assert_eq!(legacy.token_delta, 1000);
+    }
 }
@@ -0,0 +1,86 @@
+//! @module commands/conventions
+//! @description Tauri IPC commands for CLAUDE.md conventions and convention violation checks
+//!
+//! PURPOSE:
+//! - Parse a project's CLAUDE.md into stored convention rules
+//! - List stored rules with their last check result
+//! - Check the codebase against the rules and record the result for health and RALPH
+//!
+//! DEPENDENCIES:
+//! - tauri - Command macro and State
+//! - db::AppState - Database connection
+//! - core::conventions - Parsing, storage, and the violation scan
+//! - core::events - Activity for completed checks
+//! - models::convention - Convention, ConventionReport types
+//!
+//! EXPORTS:
+//! - extract_conventions - Re-parse CLAUDE.md and store its rules
+//! - list_conventions - Stored rules with their last violation counts
+//! - check_conventions - Re-parse, scan the codebase, and record violations
+//!
+//! PATTERNS:
+//! - check_conventions always re-parses CLAUDE.md first, so edits are picked up without a
+//!   separate extract step
+//!
+//! CLAUDE NOTES:
+//! - The scan runs with the DB lock released; results are recorded afterwards
+//! - The health score reads the recorded totals, so it only changes after a check
+
+use rusqlite::Connection;
+use tauri::State;
+
+use crate::core::conventions;
+use crate::core::events::{self, AppEvent};
+use crate::db::AppState;
+use crate::models::activity::ActivityType;
+use crate::models::convention::{Convention, ConventionReport};
+
+fn project_path(db: &Connection, project_id: &str) -> Result<String, String> {
+    db.query_row("SELECT path FROM projects WHERE id = ?1", [project_id], |row| row.get(0))
+        .map_err(|e| format!("Project not found: {}", e))
+}
+
+/// Re-parse the project's CLAUDE.md and store its convention rules.
+#[tauri::command]
+pub async fn extract_conventions(project_id: String, state: State<'_, AppState>) -> Result<Vec<Convention>, String> {
+    let db = state.db.lock().map_err(|e| format!("DB lock error: {}", e))?;
+    let path = project_path(&db, &project_id)?;
+    conventions::sync_conventions(&db, &project_id, &path)
+}
+
+#[tauri::command]
+pub async fn list_conventions(project_id: String, state: State<'_, AppState>) -> Result<Vec<Convention>, String> {
+    let db = state.db.lock().map_err(|e| format!("DB lock error: {}", e))?;
+    conventions::load_conventions(&db, &project_id)
+}
+
+/// Check the codebase against the project's CLAUDE.md conventions and record the result.
+#[tauri::command]
+pub async fn check_conventions(project_id: String, state: State<'_, AppState>) -> Result<ConventionReport, String> {
+    let (path, mut rules) = {
+        let db = state.db.lock().map_err(|e| format!("DB lock error: {}", e))?;
+        let path = project_path(&db, &project_id)?;
+        let rules = conventions::sync_conventions(&db, &project_id, &path)?;
+        (path, rules)
+    };
+
+    let (violations, files_checked) = conventions::check(&path, &mut rules);
+    let checked_at = chrono::Utc::now().to_rfc3339();
+    for rule in &mut rules {
+        rule.checked_at = Some(checked_at.clone());
+    }
+
+    let db = state.db.lock().map_err(|e| format!("DB lock error: {}", e))?;
+    conventions::record_results(&db, &rules, &checked_at)?;
+    let total: u32 = rules.iter().map(|r| r.violation_count).sum();
+    events::publish(
+        &db,
+        AppEvent::activity(
+            &project_id,
+            ActivityType::Enforcement,
+            &format!("Convention check: {} violations of {} rules in {} files", total, rules.len(), files_checked),
+        ),
+    );
+
+    Ok(ConventionReport { conventions: rules, violations, files_checked, checked_at })
+}
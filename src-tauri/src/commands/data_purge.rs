@@ -0,0 +1,201 @@
+//! @module commands/data_purge
+//! @description Tauri IPC commands for purging stored project data (data retention / erasure)
+//!
+//! PURPOSE:
+//! - Count, then delete, selected data classes for one project
+//! - Purge all AI-derived content across every project in one step
+//!
+//! DEPENDENCIES:
+//! - tauri - Command macro and State
+//! - db::AppState - Database connection
+//! - models::data_purge - PurgeScope, PurgeCount, PurgeReport types
+//! - rusqlite - SQLite queries and transactions
+//!
+//! EXPORTS:
+//! - purge_project_data - Dry-run count or delete the selected scopes for one project
+//! - purge_ai_content - Dry-run count or delete every AI-derived scope for all projects
+//! - purge_db - (internal) Count or delete on an existing connection
+//!
+//! PATTERNS:
+//! - Callers run with dry_run = true first, show the counts, then repeat with dry_run = false
+//! - Deletes run in one transaction; a failure leaves every table untouched
+//! - A completed project purge is logged as a Settings activity (counts only, no content)
+//!
+//! CLAUDE NOTES:
+//! - Scope -> table mapping lives in PurgeScope::tables (models/data_purge.rs); children are
+//!   listed before their parent rows (ralph_iterations before ralph_loops)
+//! - purge_ai_content also removes rows with a NULL project_id (global learnings, unscoped
+//!   prompt analyses); activities are never AI-derived and are kept
+//! - Projects, skills, agents, settings, and files on disk are never touched
+
+use rusqlite::Connection;
+use tauri::State;
+
+use crate::core::events::{self, AppEvent};
+use crate::db::AppState;
+use crate::models::activity::ActivityType;
+use crate::models::data_purge::{PurgeCount, PurgeReport, PurgeScope};
+
+/// Count (dry_run) or delete the selected data classes for one project.
+#[tauri::command]
+pub async fn purge_project_data(
+    project_id: String,
+    scopes: Vec<PurgeScope>,
+    dry_run: bool,
+    state: State<'_, AppState>,
+) -> Result<PurgeReport, String> {
+    if scopes.is_empty() {
+        return Err("Select at least one kind of data to purge.".to_string());
+    }
+    let db = state.db.lock().map_err(|e| format!("DB lock error: {}", e))?;
+    let report = purge_db(&db, Some(&project_id), &scopes, dry_run)?;
+
+    if !dry_run {
+        let kinds: Vec<&str> = scopes.iter().map(|s| s.as_str()).collect();
+        let message = format!("Purged {} stored rows ({})", report.total_rows, kinds.join(", "));
+        events::publish(&db, AppEvent::activity(&project_id, ActivityType::Settings, &message));
+    }
+    Ok(report)
+}
+
+/// Count (dry_run) or delete all AI-derived content (analyses, learnings,
+/// loop outcomes) for every project.
+#[tauri::command]
+pub async fn purge_ai_content(dry_run: bool, state: State<'_, AppState>) -> Result<PurgeReport, String> {
+    let db = state.db.lock().map_err(|e| format!("DB lock error: {}", e))?;
+    purge_db(&db, None, &PurgeScope::AI_DERIVED, dry_run)
+}
+
+/// Count or delete the scopes' rows for one project (Some) or all projects (None).
+pub fn purge_db(
+    db: &Connection,
+    project_id: Option<&str>,
+    scopes: &[PurgeScope],
+    dry_run: bool,
+) -> Result<PurgeReport, String> {
+    let mut unique: Vec<PurgeScope> = Vec::new();
+    for scope in scopes {
+        if !unique.contains(scope) {
+            unique.push(*scope);
+        }
+    }
+
+    let tx = db
+        .unchecked_transaction()
+        .map_err(|e| format!("Failed to start transaction: {}", e))?;
+
+    let mut counts = Vec::new();
+    for scope in unique {
+        for (table, filter) in scope.tables() {
+            let rows = match project_id {
+                Some(id) => {
+                    let count: u32 = tx
+                        .query_row(&format!("SELECT COUNT(*) FROM {} WHERE {}", table, filter), [id], |row| {
+                            row.get(0)
+                        })
+                        .map_err(|e| format!("Failed to count {}: {}", table, e))?;
+                    if !dry_run {
+                        tx.execute(&format!("DELETE FROM {} WHERE {}", table, filter), [id])
+                            .map_err(|e| format!("Failed to purge {}: {}", table, e))?;
+                    }
+                    count
+                }
+                None => {
+                    let count: u32 = tx
+                        .query_row(&format!("SELECT COUNT(*) FROM {}", table), [], |row| row.get(0))
+                        .map_err(|e| format!("Failed to count {}: {}", table, e))?;
+                    if !dry_run {
+                        tx.execute(&format!("DELETE FROM {}", table), [])
+                            .map_err(|e| format!("Failed to purge {}: {}", table, e))?;
+                    }
+                    count
+                }
+            };
+            counts.push(PurgeCount {
+                scope,
+                table: table.to_string(),
+                rows,
+            });
+        }
+    }
+
+    tx.commit().map_err(|e| format!("Failed to commit purge: {}", e))?;
+
+    Ok(PurgeReport {
+        project_id: project_id.map(|s| s.to_string()),
+        dry_run,
+        total_rows: counts.iter().map(|c| c.rows).sum(),
+        counts,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_support::memory_db;
+
+    fn setup() -> Connection {
+        let conn = memory_db();
+        for p in ["p1", "p2"] {
+            conn.execute_batch(&format!(
+                "INSERT INTO projects (id, name, path, created_at) VALUES ('{p}', '{p}', '/tmp/{p}', '2025-01-01T00:00:00Z');
+                 INSERT INTO ralph_loops (id, project_id, prompt, created_at) VALUES ('{p}-loop', '{p}', 'Fix', '2025-01-01T00:00:00Z');
+                 INSERT INTO ralph_iterations (id, loop_id, iteration, status, started_at, completed_at)
+                     VALUES ('{p}-it', '{p}-loop', 1, 'completed', '2025-01-01T00:00:00Z', '2025-01-01T00:01:00Z');
+                 INSERT INTO ralph_mistakes (id, project_id, loop_id, description, created_at)
+                     VALUES ('{p}-m', '{p}', '{p}-loop', 'type error', '2025-01-01T00:00:00Z');
+                 INSERT INTO learnings (id, project_id, content, created_at, updated_at)
+                     VALUES ('{p}-l', '{p}', 'Use Result', '2025-01-01T00:00:00Z', '2025-01-01T00:00:00Z');
+                 INSERT INTO prompt_analyses (id, project_id, prompt, created_at) VALUES ('{p}-pa', '{p}', 'Fix', '2025-01-01T00:00:00Z');
+                 INSERT INTO activities (id, project_id, activity_type, message, created_at)
+                     VALUES ('{p}-a', '{p}', 'scan', 'Scanned', '2025-01-01T00:00:00Z');"
+            ))
+            .unwrap();
+        }
+        conn.execute(
+            "INSERT INTO learnings (id, project_id, content, created_at, updated_at)
+             VALUES ('global', NULL, 'Prefer small PRs', '2025-01-01T00:00:00Z', '2025-01-01T00:00:00Z')",
+            [],
+        )
+        .unwrap();
+        conn
+    }
+
+    fn count(conn: &Connection, table: &str) -> u32 {
+        conn.query_row(&format!("SELECT COUNT(*) FROM {}", table), [], |row| row.get(0))
+            .unwrap()
+    }
+
+    #[test]
+    fn test_dry_run_counts_without_deleting() {
+        let conn = setup();
+        let report = purge_db(&conn, Some("p1"), &[PurgeScope::LoopOutcomes, PurgeScope::LoopOutcomes], true).unwrap();
+        assert!(report.dry_run);
+        assert_eq!(report.counts.len(), 4);
+        assert_eq!(report.total_rows, 3);
+        assert_eq!(count(&conn, "ralph_loops"), 2);
+    }
+
+    #[test]
+    fn test_project_purge_leaves_other_projects() {
+        let conn = setup();
+        let report = purge_db(&conn, Some("p1"), &PurgeScope::ALL, false).unwrap();
+        assert_eq!(report.total_rows, 6);
+        for table in ["ralph_loops", "ralph_iterations", "ralph_mistakes", "prompt_analyses", "activities"] {
+            assert_eq!(count(&conn, table), 1, "{}", table);
+        }
+        assert_eq!(count(&conn, "learnings"), 2);
+        assert_eq!(count(&conn, "projects"), 2);
+    }
+
+    #[test]
+    fn test_ai_purge_keeps_activities() {
+        let conn = setup();
+        let report = purge_db(&conn, None, &PurgeScope::AI_DERIVED, false).unwrap();
+        assert_eq!(report.project_id, None);
+        assert_eq!(report.total_rows, 11);
+        assert_eq!(count(&conn, "learnings"), 0);
+        assert_eq!(count(&conn, "ralph_loops"), 0);
+        assert_eq!(count(&conn, "activities"), 2);
+    }
+}
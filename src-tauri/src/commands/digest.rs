@@ -0,0 +1,87 @@
+//! @module commands/digest
+//! @description Tauri IPC commands and the background scheduler for weekly project digests
+//!
+//! PURPOSE:
+//! - Generate a project's digest on demand (any week, default the last completed one)
+//! - List stored digests
+//! - Generate due digests in the background
+//!
+//! DEPENDENCIES:
+//! - tauri - Command macro and State
+//! - db::AppState - Database connection
+//! - core::digest - Stats collection, Markdown rendering, storage, and due-week logic
+//! - models::digest - WeeklyDigest type
+//!
+//! EXPORTS:
+//! - generate_weekly_digest - Build (or rebuild) a project's digest for a week
+//! - list_weekly_digests - Stored digests for a project, newest week first
+//! - spawn_digest_scheduler - Start the background job that generates due digests
+//!
+//! PATTERNS:
+//! - Default list limit is 12 digests (about three months)
+//! - The scheduler runs on its own DB connection, like the activity pruner
+//!
+//! CLAUDE NOTES:
+//! - The scheduler checks at startup and then every DIGEST_CHECK_HOURS, so a digest appears
+//!   within a few hours of the week ending (or at next launch)
+//! - Set digest.enabled to "false" to stop scheduled digests; on-demand generation still works
+
+use chrono::{DateTime, Utc};
+use rusqlite::Connection;
+use tauri::State;
+
+use crate::core::digest;
+use crate::db::AppState;
+use crate::models::digest::WeeklyDigest;
+
+/// How often the background job looks for due digests.
+const DIGEST_CHECK_HOURS: u64 = 6;
+
+/// Build (or rebuild) a project's digest. `week_start` is any time in the wanted week
+/// (RFC 3339); None means the last completed week.
+#[tauri::command]
+pub async fn generate_weekly_digest(
+    project_id: String,
+    week_start: Option<String>,
+    state: State<'_, AppState>,
+) -> Result<WeeklyDigest, String> {
+    let week = match week_start {
+        Some(at) => {
+            let at = DateTime::parse_from_rfc3339(&at)
+                .map_err(|e| format!("Invalid week start '{}': {}", at, e))?
+                .with_timezone(&Utc);
+            digest::week_start_of(at)
+        }
+        None => digest::last_completed_week(Utc::now()),
+    };
+    let db = state.db.lock().map_err(|e| format!("DB lock error: {}", e))?;
+    digest::generate(&db, &project_id, week)
+}
+
+#[tauri::command]
+pub async fn list_weekly_digests(
+    project_id: String,
+    limit: Option<u32>,
+    state: State<'_, AppState>,
+) -> Result<Vec<WeeklyDigest>, String> {
+    let db = state.db.lock().map_err(|e| format!("DB lock error: {}", e))?;
+    digest::list(&db, &project_id, limit.unwrap_or(12))
+}
+
+/// Start a background thread that generates due digests at startup and then every
+/// DIGEST_CHECK_HOURS, on its own DB connection.
+pub fn spawn_digest_scheduler() {
+    std::thread::spawn(|| loop {
+        match crate::db::db_path().and_then(|path| Connection::open(path).map_err(|e| e.to_string())) {
+            Ok(conn) => match digest::generate_due(&conn, Utc::now()) {
+                Ok(digests) if !digests.is_empty() => {
+                    tracing::info!(count = digests.len(), "Generated weekly digests")
+                }
+                Ok(_) => {}
+                Err(e) => tracing::warn!(error = %e, "Weekly digest generation failed"),
+            },
+            Err(e) => tracing::warn!(error = %e, "Digest scheduler could not open database"),
+        }
+        std::thread::sleep(std::time::Duration::from_secs(DIGEST_CHECK_HOURS * 60 * 60));
+    });
+}
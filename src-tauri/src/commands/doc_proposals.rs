@@ -0,0 +1,360 @@
+//! @module commands/doc_proposals
+//! @description Tauri IPC commands for the doc generation approval queue
+//!
+//! PURPOSE:
+//! - Queue template doc headers for files the watcher or freshness check flags
+//! - List queued proposals with a diff preview
+//! - Approve (write) or reject proposals in bulk
+//!
+//! DEPENDENCIES:
+//! - tauri - Command macro and State
+//! - db::AppState - Database connection
+//! - core::events - doc_applied events for approved proposals
+//! - core::analyzer - Template doc generation and header rendering
+//! - core::freshness - Missing/outdated detection and scan scope
+//! - core::patch - Configured patch engine for approved header writes
+//! - commands::versions - Line diff for previews
+//! - models::doc_proposal - DocProposal, DocProposalResult types
+//!
+//! EXPORTS:
+//! - queue_doc_proposals - Queue proposals for stale or undocumented files (freshness view)
+//! - list_doc_proposals - Proposals for a project with diff previews (pending by default)
+//! - apply_doc_proposals - Apply approved proposals and reject the rest of a selection
+//! - propose_docs - (internal) Queue proposals on an existing connection (used by the watcher)
+//! - DOC_PROPOSALS_SETTING - Setting that turns on queueing from the watcher
+//!
+//! PATTERNS:
+//! - Nothing is written to source files until a proposal is approved
+//! - A file has at most one pending proposal; re-queueing replaces it
+//! - Missing headers get a template doc; outdated headers keep their prose and take the
+//!   template's EXPORTS and DEPENDENCIES
+//!
+//! CLAUDE NOTES:
+//! - Proposals are template-based (no AI) so the watcher never spends API calls on its own
+//! - The watcher queues only when the "watcher.doc_proposals" setting is "true"
+//! - Approving renders the header against the file as it is at approval time
+
+use chrono::Utc;
+use rusqlite::Connection;
+use std::path::Path;
+use tauri::State;
+
+use crate::commands::versions::diff_lines;
+use crate::core::{analyzer, freshness, patch};
+use crate::core::events::{self, AppEvent};
+use crate::db::AppState;
+use crate::models::doc_proposal::{DocProposal, DocProposalResult};
+use crate::models::module_doc::ModuleDoc;
+use crate::models::version::DiffLine;
+
+/// Setting that turns on queueing doc proposals from the file watcher ("true" / "false").
+pub const DOC_PROPOSALS_SETTING: &str = "watcher.doc_proposals";
+
+/// Unchanged lines kept around each change in a preview diff
+const DIFF_CONTEXT: usize = 2;
+
+/// Queue doc proposals for the given files, or for every missing/outdated file
+/// in the project when none are given. Returns the number queued.
+#[tauri::command]
+pub async fn queue_doc_proposals(
+    project_path: String,
+    file_paths: Option<Vec<String>>,
+    state: State<'_, AppState>,
+) -> Result<u32, String> {
+    let paths: Vec<String> = match file_paths {
+        Some(paths) => paths,
+        None => freshness::check_project_freshness(&project_path)?
+            .into_iter()
+            .filter(|m| m.status == "missing" || m.status == "outdated")
+            .map(|m| Path::new(&project_path).join(&m.path).to_string_lossy().to_string())
+            .collect(),
+    };
+    let db = state.db.lock().map_err(|e| format!("DB lock error: {}", e))?;
+    propose_docs(&db, &project_path, &paths, "freshness")
+}
+
+/// List a project's proposals (pending unless `status` is given), newest first,
+/// each with a diff preview against the file's current content.
+#[tauri::command]
+pub async fn list_doc_proposals(
+    project_path: String,
+    status: Option<String>,
+    state: State<'_, AppState>,
+) -> Result<Vec<DocProposal>, String> {
+    let db = state.db.lock().map_err(|e| format!("DB lock error: {}", e))?;
+    let mut proposals = load_proposals(&db, &project_path, status.as_deref().unwrap_or("pending"))?;
+    drop(db);
+
+    for proposal in &mut proposals {
+        if proposal.status == "pending" {
+            proposal.diff = preview_diff(&proposal.file_path, &proposal.doc);
+        }
+    }
+    Ok(proposals)
+}
+
+/// Write the headers of `approve` proposals and mark `reject` proposals rejected.
+/// Proposals that are not pending are reported as failed.
+#[tauri::command]
+pub async fn apply_doc_proposals(
+    approve: Vec<String>,
+    reject: Vec<String>,
+    state: State<'_, AppState>,
+) -> Result<Vec<DocProposalResult>, String> {
+    let db = state.db.lock().map_err(|e| format!("DB lock error: {}", e))?;
+    decide_proposals(&db, &approve, &reject)
+}
+
+/// Queue proposals for files on an existing connection. Skips files that are
+/// untracked, out of scan scope, or already current. Returns the number queued.
+pub fn propose_docs(db: &Connection, project_path: &str, file_paths: &[String], source: &str) -> Result<u32, String> {
+    let overrides = analyzer::load_generated_overrides(project_path);
+    let mut queued = 0;
+
+    for file_path in file_paths {
+        let path = Path::new(file_path);
+        let Ok(rel) = path.strip_prefix(project_path) else {
+            continue;
+        };
+        let rel = rel.to_string_lossy().replace('\\', "/");
+        let name = path.file_name().and_then(|n| n.to_str()).unwrap_or("");
+        if !path.is_file() || !freshness::in_scan_scope(&rel) {
+            continue;
+        }
+        let Ok(content) = analyzer::read_source(file_path) else {
+            continue;
+        };
+        if !analyzer::should_track_file(name, &rel, &content, &overrides) {
+            continue;
+        }
+
+        let reason = freshness::check_file_freshness(file_path, project_path).status;
+        let doc = match (reason.as_str(), analyzer::parse_doc_header(&content)) {
+            ("missing", _) => analyzer::generate_module_doc_for_file(file_path, project_path)?,
+            ("outdated", Some(existing)) => {
+                let template = analyzer::generate_module_doc_for_file(file_path, project_path)?;
+                refresh_doc(existing, template)
+            }
+            _ => continue,
+        };
+        let doc_json = serde_json::to_string(&doc).map_err(|e| format!("Failed to serialize doc: {}", e))?;
+
+        db.execute(
+            "DELETE FROM doc_proposals WHERE file_path = ?1 AND status = 'pending'",
+            [file_path],
+        )
+        .map_err(|e| format!("Failed to replace doc proposal: {}", e))?;
+        db.execute(
+            "INSERT INTO doc_proposals (id, project_path, file_path, reason, source, doc, status, created_at)
+             VALUES (?1, ?2, ?3, ?4, ?5, ?6, 'pending', ?7)",
+            rusqlite::params![
+                uuid::Uuid::new_v4().to_string(),
+                project_path,
+                file_path,
+                reason,
+                source,
+                doc_json,
+                Utc::now().to_rfc3339()
+            ],
+        )
+        .map_err(|e| format!("Failed to queue doc proposal: {}", e))?;
+        queued += 1;
+    }
+
+    Ok(queued)
+}
+
+/// An outdated header with its prose kept and the template's detected
+/// exports and dependencies.
+fn refresh_doc(existing: ModuleDoc, template: ModuleDoc) -> ModuleDoc {
+    ModuleDoc {
+        exports: template.exports,
+        dependencies: template.dependencies,
+        ..existing
+    }
+}
+
+fn load_proposals(db: &Connection, project_path: &str, status: &str) -> Result<Vec<DocProposal>, String> {
+    let mut stmt = db
+        .prepare(
+            "SELECT id, project_path, file_path, reason, source, doc, status, created_at, decided_at
+             FROM doc_proposals WHERE project_path = ?1 AND status = ?2 ORDER BY created_at DESC",
+        )
+        .map_err(|e| format!("Failed to query doc proposals: {}", e))?;
+    let proposals = stmt
+        .query_map([project_path, status], map_proposal_row)
+        .map_err(|e| format!("Failed to read doc proposals: {}", e))?
+        .filter_map(|r| r.ok())
+        .collect();
+    Ok(proposals)
+}
+
+fn map_proposal_row(row: &rusqlite::Row) -> rusqlite::Result<DocProposal> {
+    let doc: String = row.get(5)?;
+    Ok(DocProposal {
+        id: row.get(0)?,
+        project_path: row.get(1)?,
+        file_path: row.get(2)?,
+        reason: row.get(3)?,
+        source: row.get(4)?,
+        doc: serde_json::from_str(&doc).map_err(|e| {
+            rusqlite::Error::FromSqlConversionFailure(5, rusqlite::types::Type::Text, Box::new(e))
+        })?,
+        diff: Vec::new(),
+        status: row.get(6)?,
+        created_at: row.get(7)?,
+        decided_at: row.get(8)?,
+    })
+}
+
+/// Changed lines (with DIFF_CONTEXT lines around them) of applying `doc` to the file.
+fn preview_diff(file_path: &str, doc: &ModuleDoc) -> Vec<DiffLine> {
+    let Ok(content) = analyzer::read_source(file_path) else {
+        return Vec::new();
+    };
+    let Ok(rendered) = analyzer::render_doc_into(&content, file_path, doc) else {
+        return Vec::new();
+    };
+    trim_context(diff_lines(&content, &rendered), DIFF_CONTEXT)
+}
+
+/// Keep changed lines plus `context` unchanged lines on either side.
+fn trim_context(lines: Vec<DiffLine>, context: usize) -> Vec<DiffLine> {
+    let changed: Vec<usize> = lines
+        .iter()
+        .enumerate()
+        .filter(|(_, l)| l.kind != "unchanged")
+        .map(|(i, _)| i)
+        .collect();
+    lines
+        .into_iter()
+        .enumerate()
+        .filter(|(i, _)| changed.iter().any(|c| c.abs_diff(*i) <= context))
+        .map(|(_, l)| l)
+        .collect()
+}
+
+fn decide_proposals(db: &Connection, approve: &[String], reject: &[String]) -> Result<Vec<DocProposalResult>, String> {
+    let mut results = Vec::new();
+    let decisions = approve.iter().map(|id| (id, true)).chain(reject.iter().map(|id| (id, false)));
+
+    for (id, approved) in decisions {
+        let row: Option<(String, String, String, String)> = db
+            .query_row(
+                "SELECT project_path, file_path, doc, status FROM doc_proposals WHERE id = ?1",
+                [id],
+                |row| Ok((row.get(0)?, row.get(1)?, row.get(2)?, row.get(3)?)),
+            )
+            .ok();
+        let Some((project_path, file_path, doc_json, status)) = row else {
+            results.push(failed(id, "", "Proposal not found"));
+            continue;
+        };
+        if status != "pending" {
+            results.push(failed(id, &file_path, &format!("Proposal is already {}", status)));
+            continue;
+        }
+
+        if approved {
+            let applied = serde_json::from_str::<ModuleDoc>(&doc_json)
+                .map_err(|e| format!("Invalid proposal: {}", e))
+                .and_then(|doc| {
+                    analyzer::apply_doc_to_file(patch::engine(db), Path::new(&project_path), &file_path, &doc)
+                });
+            if let Err(e) = applied {
+                results.push(failed(id, &file_path, &e));
+                continue;
+            }
+        }
+
+        let new_status = if approved { "applied" } else { "rejected" };
+        db.execute(
+            "UPDATE doc_proposals SET status = ?1, decided_at = ?2 WHERE id = ?3",
+            rusqlite::params![new_status, Utc::now().to_rfc3339(), id],
+        )
+        .map_err(|e| format!("Failed to update doc proposal: {}", e))?;
+
+        if approved {
+            if let Ok(project_id) = db.query_row(
+                "SELECT id FROM projects WHERE path = ?1",
+                [&project_path],
+                |row| row.get::<_, String>(0),
+            ) {
+                events::publish(
+                    db,
+                    AppEvent::DocApplied {
+                        project_id,
+                        file_path: file_path.clone(),
+                    },
+                );
+            }
+        }
+        results.push(DocProposalResult {
+            id: id.clone(),
+            file_path,
+            status: new_status.to_string(),
+            error: None,
+        });
+    }
+
+    Ok(results)
+}
+
+fn failed(id: &str, file_path: &str, error: &str) -> DocProposalResult {
+    DocProposalResult {
+        id: id.to_string(),
+        file_path: file_path.to_string(),
+        status: "failed".to_string(),
+        error: Some(error.to_string()),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_support::{insert_project, memory_db};
+    use std::fs;
+
+    fn setup(root: &Path) -> Connection {
+        let conn = memory_db();
+        insert_project(&conn, "p1", root.to_str().unwrap());
+        conn
+    }
+
+    #[test]
+    fn test_propose_and_apply_docs() {
+        let dir = tempfile::tempdir().unwrap();
+        let root = dir.path();
+        let project = root.to_str().unwrap();
+        fs::create_dir_all(root.join("src")).unwrap();
+        let file = root.join("src/utils.ts");
+        fs::write(&file, "export function add(a: number, b: number) {\n  return a + b;\n}\n").unwrap();
+        let file = file.to_str().unwrap().to_string();
+        let conn = setup(root);
+
+        // Queueing never writes, and re-queueing replaces the pending proposal
+        assert_eq!(propose_docs(&conn, project, &[file.clone()], "watcher").unwrap(), 1);
+        assert_eq!(propose_docs(&conn, project, &[file.clone()], "watcher").unwrap(), 1);
+        assert!(!fs::read_to_string(&file).unwrap().contains("@module"));
+        let pending = load_proposals(&conn, project, "pending").unwrap();
+        assert_eq!(pending.len(), 1);
+        assert_eq!(pending[0].reason, "missing");
+        assert!(preview_diff(&file, &pending[0].doc).iter().any(|l| l.kind == "added" && l.text.contains("@module")));
+
+        let results = decide_proposals(&conn, &[pending[0].id.clone()], &["missing".to_string()]).unwrap();
+        assert_eq!(results[0].status, "applied");
+        assert_eq!(results[1].status, "failed");
+        assert!(fs::read_to_string(&file).unwrap().contains("@module"));
+        assert_eq!(decide_proposals(&conn, &[pending[0].id.clone()], &[]).unwrap()[0].status, "failed");
+    }
+
+    #[test]
+    fn test_trim_context() {
+        let line = |kind: &str, text: &str| DiffLine { kind: kind.to_string(), text: text.to_string() };
+        let lines: Vec<DiffLine> = (0..10)
+            .map(|i| if i == 5 { line("added", "new") } else { line("unchanged", &i.to_string()) })
+            .collect();
+        let texts: Vec<String> = trim_context(lines, 2).into_iter().map(|l| l.text).collect();
+        assert_eq!(texts, vec!["3", "4", "new", "6", "7"]);
+    }
+}
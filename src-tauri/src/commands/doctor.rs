@@ -0,0 +1,152 @@
+//! @module commands/doctor
+//! @description Tauri IPC commands for self-diagnostics and one-click fixes
+//!
+//! PURPOSE:
+//! - Run every doctor check and return a structured report
+//! - Apply a fix offered by a check (migrations, reindex, interrupted jobs, unreadable secrets)
+//!
+//! DEPENDENCIES:
+//! - tauri - Command macro and State
+//! - db::AppState - Database connection, HTTP client, and live test run flags
+//! - core::doctor - Individual checks and fixes
+//! - core::ralph_preflight - Claude CLI and disk space checks
+//! - core::ai - API key and request headers for the key probe
+//! - commands::ralph - find_claude_cli
+//! - models::doctor - DoctorReport
+//!
+//! EXPORTS:
+//! - run_doctor - Database, schema, tools, API key, secret storage, disk, jobs, and project checks
+//! - apply_doctor_fix - Run a check's fix_action
+//!
+//! PATTERNS:
+//! - DB checks run under one lock; CLI, tool, and network checks run after it is released
+//! - The API key probe lists models (no tokens are spent)
+//!
+//! CLAUDE NOTES:
+//! - A missing or malformed API key is reported without calling the API
+//! - jq is a warning, not a failure: only the generated enforcement hooks use it
+
+use std::collections::HashSet;
+use std::time::Duration;
+
+use chrono::Utc;
+use tauri::State;
+
+use crate::commands::ralph;
+use crate::core::ai;
+use crate::core::doctor;
+use crate::core::ralph_preflight;
+use crate::db::AppState;
+use crate::models::doctor::{DoctorCheck, DoctorReport};
+
+/// Authenticated endpoint used to test the API key.
+const MODELS_URL: &str = "https://api.anthropic.com/v1/models?limit=1";
+
+const API_PROBE_TIMEOUT: Duration = Duration::from_secs(10);
+
+fn live_test_runs(state: &AppState) -> Result<HashSet<String>, String> {
+    let runs = state.test_runs.lock().map_err(|e| format!("Test run lock error: {}", e))?;
+    Ok(runs.keys().cloned().collect())
+}
+
+/// The API key check: configuration problems are reported directly, a configured key is probed.
+async fn api_key_check(key: Result<String, String>, client: &reqwest::Client) -> DoctorCheck {
+    let key = match key {
+        Ok(key) if key.starts_with("sk-ant-") => key,
+        Ok(_) => {
+            return DoctorCheck {
+                id: "api_key".to_string(),
+                label: "API key".to_string(),
+                status: "fail".to_string(),
+                detail: "The saved key does not look like an Anthropic key (sk-ant-...)".to_string(),
+                fix: Some("Save a valid key in Settings.".to_string()),
+                fix_action: None,
+            }
+        }
+        Err(e) => {
+            let undecryptable = e.starts_with("Failed to decrypt");
+            return DoctorCheck {
+                id: "api_key".to_string(),
+                label: "API key".to_string(),
+                status: if undecryptable { "fail" } else { "warn" }.to_string(),
+                detail: e,
+                fix: Some("Add your Anthropic API key in Settings.".to_string()),
+                fix_action: None,
+            };
+        }
+    };
+
+    let status = client
+        .get(MODELS_URL)
+        .header("x-api-key", key)
+        .header("anthropic-version", ai::ANTHROPIC_VERSION)
+        .timeout(API_PROBE_TIMEOUT)
+        .send()
+        .await
+        .map(|resp| resp.status().as_u16())
+        .map_err(|e| e.to_string());
+    doctor::api_key_check(status)
+}
+
+/// Run all self-diagnostics.
+#[tauri::command]
+pub async fn run_doctor(state: State<'_, AppState>) -> Result<DoctorReport, String> {
+    let live = live_test_runs(&state)?;
+    let (integrity, schema, key_storage, orphans, projects, api_key) = {
+        let db = state.db.lock().map_err(|e| format!("DB lock error: {}", e))?;
+        (
+            doctor::check_integrity(&db),
+            doctor::check_schema(&db),
+            doctor::check_key_storage(&db),
+            doctor::check_orphaned_jobs(&doctor::orphaned_jobs(&db, &live)),
+            doctor::check_project_paths(&db),
+            ai::get_api_key(&db),
+        )
+    };
+
+    let cli = ralph::find_claude_cli();
+    let data_dir = dirs::home_dir()
+        .map(|h| h.join(".project-jumpstart").to_string_lossy().to_string())
+        .unwrap_or_default();
+
+    let checks = vec![
+        integrity,
+        schema,
+        doctor::from_preflight(ralph_preflight::check_cli(cli.as_deref())),
+        doctor::check_tool(
+            "git",
+            "Git",
+            "git",
+            "fail",
+            "Install git (on macOS: `xcode-select --install`).",
+        ),
+        doctor::check_tool(
+            "jq",
+            "jq (used by hooks)",
+            "jq",
+            "warn",
+            "Install jq (`brew install jq` or your package manager) so enforcement hooks can read their input.",
+        ),
+        api_key_check(api_key, &state.http_client).await,
+        key_storage,
+        doctor::from_preflight(ralph_preflight::check_disk(&data_dir)),
+        orphans,
+        projects,
+    ];
+
+    Ok(DoctorReport {
+        healthy: checks.iter().all(|c| c.status != "fail"),
+        checks,
+        generated_at: Utc::now().to_rfc3339(),
+    })
+}
+
+/// Run a doctor fix_action. Returns what changed; run_doctor again to refresh the report.
+#[tauri::command]
+pub async fn apply_doctor_fix(action: String, state: State<'_, AppState>) -> Result<String, String> {
+    let live = live_test_runs(&state)?;
+    let db = state.db.lock().map_err(|e| format!("DB lock error: {}", e))?;
+    let message = doctor::apply_fix(&db, &action, &live)?;
+    tracing::info!(action = %action, result = %message, "Applied doctor fix");
+    Ok(message)
+}
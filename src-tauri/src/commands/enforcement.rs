@@ -3,7 +3,9 @@
 //!
 //! PURPOSE:
 //! - Install and check git pre-commit hooks for documentation enforcement
-//! - Generate CI integration snippets (GitHub Actions, GitLab CI)
+//! - Install a post-merge hook that records merges for the doc drift scan
+//! - Generate CI integration snippets (GitHub Actions, GitLab CI, Bitbucket Pipelines, CircleCI,
+//!   Azure Pipelines) scoped to the project's source layout
 //! - Track and list enforcement events (blocks, warnings)
 //! - Calculate enforcement score for health integration
 //!
@@ -13,6 +15,11 @@
 //! - models::enforcement - EnforcementEvent, HookStatus, CiSnippet types
 //! - std::fs - File system for hook installation
 //! - std::path::Path - Path operations
+//! - core::proc - git init with a timeout
+//! - core::doc_goals - CI fail-under threshold from the project's doc goals
+//! - core::git_forge - Doc-check runs from the project's forge as enforcement events
+//! - core::merge_drift - Pending-merges file the post-merge hook writes
+//! - core::scanner - Source directories, extensions, and workspace path filters for CI snippets
 //!
 //! EXPORTS:
 //! - install_git_hooks - Install pre-commit hook for doc enforcement
@@ -21,15 +28,33 @@
 //! - check_hooks_configured - Check if Claude Code PostToolUse hooks are configured
 //! - get_enforcement_events - List recent enforcement events
 //! - get_ci_snippets - Generate CI integration templates
+//! - sync_ci_enforcement - Import failed doc-check CI runs (GitHub, GitLab, Bitbucket) as enforcement events
 //! - get_enforcement_score - Calculate enforcement score (0-10) for health
 //! - get_hook_health - Read hook self-healing health status
 //! - reset_hook_health - Reset hook health and optionally reinstall hook
 //! - export_api_key_for_hook - (internal) Export decrypted API key to JSON for auto-update hook
+//! - uninstall_git_hooks - Remove the Project Jumpstart pre-commit (and post-merge) hook from a project
+//! - install_merge_drift_hook - Install the post-merge hook feeding post_merge_scan
+//! - rotate_hook_api_key - Re-export the hook key from Settings with a fresh expiry
+//! - get_hook_key_status - Exported key expiry and the projects that depend on it
+//! - refresh_exported_hook_key - Re-export or remove settings.json at startup / on key change
+//! - generate_hook_script - (internal) Block/warn pre-commit hook script (also installed on remote projects)
+//! - hook_status_from - (internal) HookStatus from hook content (shared with remote hook status)
+//! - generate_github_actions_snippet - (internal) doc-check.yml content (shared with commands::policy)
 //!
 //! PATTERNS:
 //! - install_git_hooks writes a shell script to .git/hooks/pre-commit
+//! - install_merge_drift_hook writes .git/hooks/post-merge; it only records "<ORIG_HEAD> <HEAD>"
+//!   and never runs the scan itself (the app does, in post_merge_scan / get_stale_files)
 //! - Hook checks for @module/@description headers in staged source files
-//! - CI snippets are returned as copyable template strings
+//! - Hooks skip generated/vendored files (is_generated) unless listed in .claude/generated-overrides,
+//!   and always skip files with a `jumpstart:ignore` marker in their first 20 lines
+//! - The auto-update hook never sends files matching .claude/ai-exclude (is_ai_excluded) to the
+//!   API; it warns instead so the header can be added from the app's template docs
+//! - CI snippets are returned as copyable template strings, scanning only the detected source
+//!   directories and extensions; monorepo workspaces become the provider's path filters
+//! - With a CI-enforced coverage goal the snippets report coverage and fail under it
+//!   (`--fail-under` semantics; DOC_FAIL_UNDER overrides the threshold in CI)
 //! - Enforcement score: 5 for hooks installed, 5 for CI config present
 //!
 //! CLAUDE NOTES:
@@ -39,23 +64,36 @@
 //! - Model ID for hook comes from settings.json "claude_model" key (set by export_api_key_for_hook)
 //! - When installing auto-update hook, API key + model are exported from encrypted SQLite to JSON
 //! - The settings.json file has 0600 permissions (owner read/write only)
+//! - settings.json is written atomically (temp + rename) under HOOK_SETTINGS_LOCK
+//! - Exported keys expire after HOOK_KEY_TTL_DAYS; the hook skips auto-update once expired
+//! - settings.json is deleted when no registered project has an auto-update hook installed
 //! - Husky detection: checks for .husky/ directory
-//! - CI detection: checks for .github/workflows/ or .gitlab-ci.yml
+//! - CI detection: checks for .github/workflows/, .gitlab-ci.yml, bitbucket-pipelines.yml,
+//!   .circleci/config.yml, or azure-pipelines.yml
 //! - Enforcement events are logged to the DB for the event log UI
+//! - sync_ci_enforcement records failed runs with source "ci" next to hook events; it needs an
+//!   origin remote, the forge's token setting, and (for unrecognized hosts) the project's
+//!   forge settings (see core::git_forge)
 
-use std::path::Path;
+use std::path::{Path, PathBuf};
+use std::sync::Mutex;
 use tauri::State;
 
-use crate::core::{ai, crypto};
-use crate::db::{self, AppState};
-use crate::models::enforcement::{CiSnippet, EnforcementEvent, HookHealth, HookStatus};
+use crate::core::proc::{self, ProcLimits};
+use crate::commands::health_history;
+use crate::core::{ai, bitbucket_ci, crypto, doc_goals, git_forge, gitlab_ci, merge_drift, scanner};
+use crate::core::events::{self, AppEvent};
+use crate::db::AppState;
+use crate::models::activity::ActivityType;
+use crate::models::enforcement::{CiSnippet, CiSyncReport, EnforcementEvent, HookHealth, HookKeyStatus, HookStatus};
+use crate::models::project::SourceLayout;
 
 /// Current hook version - increment when hook logic changes
 /// Format: MAJOR.MINOR.PATCH
 /// - MAJOR: Breaking changes (requires jq, different behavior)
 /// - MINOR: New features (backward compatible)
 /// - PATCH: Bug fixes
-pub const HOOK_VERSION: &str = "4.0.0";
+pub const HOOK_VERSION: &str = "4.3.0";
 
 /// Parse version from hook script content
 fn parse_hook_version(content: &str) -> Option<String> {
@@ -91,10 +129,21 @@ fn is_version_outdated(installed: &str, current: &str) -> bool {
     (i_major, i_minor, i_patch) < (c_major, c_minor, c_patch)
 }
 
-/// Export the decrypted API key to a JSON file for the auto-update hook.
-/// The hook script reads this file since it can't decrypt the SQLite-stored key.
-fn export_api_key_for_hook(db: &rusqlite::Connection) -> Result<(), String> {
-    // Read the encrypted API key from the database
+/// Days an exported hook key stays valid before the hook refuses to use it.
+const HOOK_KEY_TTL_DAYS: i64 = 30;
+
+/// Serializes writes and removals of ~/.project-jumpstart/settings.json so
+/// concurrent installs, rotations, and uninstalls cannot interleave.
+static HOOK_SETTINGS_LOCK: Mutex<()> = Mutex::new(());
+
+/// Path of the JSON file the auto-update hook reads its API key from.
+fn hook_settings_path() -> Result<PathBuf, String> {
+    let home = dirs::home_dir().ok_or("Could not determine home directory")?;
+    Ok(home.join(".project-jumpstart").join("settings.json"))
+}
+
+/// Read and decrypt the stored Anthropic API key from the settings table.
+fn read_stored_api_key(db: &rusqlite::Connection) -> Result<String, String> {
     let encrypted_value: String = db
         .query_row(
             "SELECT value FROM settings WHERE key = 'anthropic_api_key'",
@@ -103,7 +152,6 @@ fn export_api_key_for_hook(db: &rusqlite::Connection) -> Result<(), String> {
         )
         .map_err(|_| "No API key configured. Please add your Anthropic API key in Settings.")?;
 
-    // Decrypt the API key
     let api_key = if let Some(stripped) = encrypted_value.strip_prefix("enc:") {
         crypto::decrypt(stripped)
             .map_err(|e| format!("Failed to decrypt API key: {}", e))?
@@ -115,20 +163,53 @@ fn export_api_key_for_hook(db: &rusqlite::Connection) -> Result<(), String> {
         return Err("API key is empty. Please configure your Anthropic API key in Settings.".to_string());
     }
 
-    // Write to ~/.project-jumpstart/settings.json
-    let home = dirs::home_dir().ok_or("Could not determine home directory")?;
-    let settings_dir = home.join(".project-jumpstart");
-    std::fs::create_dir_all(&settings_dir)
+    Ok(api_key)
+}
+
+/// Paths of registered projects whose installed pre-commit hook runs in auto-update mode.
+fn auto_update_hook_projects(db: &rusqlite::Connection) -> Vec<String> {
+    let paths: Vec<String> = db
+        .prepare("SELECT path FROM projects ORDER BY path")
+        .and_then(|mut stmt| {
+            stmt.query_map([], |row| row.get::<_, String>(0))
+                .map(|rows| rows.flatten().collect())
+        })
+        .unwrap_or_default();
+
+    paths
+        .into_iter()
+        .filter(|p| {
+            let hook = Path::new(p).join(".git").join("hooks").join("pre-commit");
+            std::fs::read_to_string(hook)
+                .map(|c| c.contains("# Mode: auto-update"))
+                .unwrap_or(false)
+        })
+        .collect()
+}
+
+/// Write the hook settings JSON atomically with owner-only permissions.
+/// Writes a temp file in the same directory and renames it over the target,
+/// so a hook running mid-write never sees a partial file.
+fn write_hook_settings(settings_path: &Path, api_key: &str, now: chrono::DateTime<chrono::Utc>) -> Result<(), String> {
+    let settings_dir = settings_path
+        .parent()
+        .ok_or("Invalid settings path")?;
+    std::fs::create_dir_all(settings_dir)
         .map_err(|e| format!("Failed to create settings directory: {}", e))?;
 
-    let settings_path = settings_dir.join("settings.json");
+    let expires_at = now + chrono::Duration::days(HOOK_KEY_TTL_DAYS);
     let json = serde_json::json!({
         "anthropic_api_key": api_key,
-        "claude_model": ai::MODEL
+        "claude_model": ai::MODEL,
+        "exported_at": now.to_rfc3339(),
+        "expires_at": expires_at.to_rfc3339(),
+        "expires_at_epoch": expires_at.timestamp(),
     });
     let json_bytes = serde_json::to_string_pretty(&json)
         .map_err(|e| format!("Failed to serialize settings: {}", e))?;
 
+    let temp_path = settings_dir.join(format!(".settings.json.{}.tmp", std::process::id()));
+
     // Write file with restrictive permissions from the start (no race condition)
     #[cfg(unix)]
     {
@@ -141,23 +222,149 @@ fn export_api_key_for_hook(db: &rusqlite::Connection) -> Result<(), String> {
             .write(true)
             .truncate(true)
             .mode(0o600) // Owner read/write only - set at creation time
-            .open(&settings_path)
+            .open(&temp_path)
             .map_err(|e| format!("Failed to create settings.json: {}", e))?;
 
         file.write_all(json_bytes.as_bytes())
+            .and_then(|_| file.sync_all())
             .map_err(|e| format!("Failed to write settings.json: {}", e))?;
     }
 
     #[cfg(not(unix))]
     {
         // On Windows, just write normally (no mode support)
-        std::fs::write(&settings_path, json_bytes)
+        std::fs::write(&temp_path, json_bytes)
             .map_err(|e| format!("Failed to write settings.json: {}", e))?;
     }
 
+    std::fs::rename(&temp_path, settings_path).map_err(|e| {
+        let _ = std::fs::remove_file(&temp_path);
+        format!("Failed to replace settings.json: {}", e)
+    })
+}
+
+/// Build the exported-key status from the settings file on disk.
+fn read_hook_key_status(settings_path: &Path, projects: Vec<String>) -> HookKeyStatus {
+    let parsed: Option<serde_json::Value> = std::fs::read_to_string(settings_path)
+        .ok()
+        .and_then(|c| serde_json::from_str(&c).ok());
+
+    let Some(value) = parsed else {
+        return HookKeyStatus {
+            exported: false,
+            exported_at: None,
+            expires_at: None,
+            expired: false,
+            projects,
+        };
+    };
+
+    let get_str = |key: &str| value.get(key).and_then(|v| v.as_str()).map(|s| s.to_string());
+    // Files exported before rotation support have no expiry; treat them as expired
+    let expired = value
+        .get("expires_at_epoch")
+        .and_then(|v| v.as_i64())
+        .map(|epoch| chrono::Utc::now().timestamp() >= epoch)
+        .unwrap_or(true);
+
+    HookKeyStatus {
+        exported: true,
+        exported_at: get_str("exported_at"),
+        expires_at: get_str("expires_at"),
+        expired,
+        projects,
+    }
+}
+
+/// Export the decrypted API key to a JSON file for the auto-update hook.
+/// The hook script reads this file since it can't decrypt the SQLite-stored key.
+/// Each export starts a new expiry window of HOOK_KEY_TTL_DAYS.
+fn export_api_key_for_hook(db: &rusqlite::Connection) -> Result<HookKeyStatus, String> {
+    let api_key = read_stored_api_key(db)?;
+    let settings_path = hook_settings_path()?;
+
+    let _guard = HOOK_SETTINGS_LOCK
+        .lock()
+        .map_err(|e| format!("Hook settings lock error: {}", e))?;
+    write_hook_settings(&settings_path, &api_key, chrono::Utc::now())?;
+
+    Ok(read_hook_key_status(&settings_path, auto_update_hook_projects(db)))
+}
+
+/// Delete the exported settings.json when no registered project still has an
+/// auto-update hook installed. Returns true if the file was removed.
+fn remove_hook_settings_if_unused(db: &rusqlite::Connection) -> Result<bool, String> {
+    let settings_path = hook_settings_path()?;
+
+    let _guard = HOOK_SETTINGS_LOCK
+        .lock()
+        .map_err(|e| format!("Hook settings lock error: {}", e))?;
+    if !settings_path.exists() || !auto_update_hook_projects(db).is_empty() {
+        return Ok(false);
+    }
+
+    std::fs::remove_file(&settings_path)
+        .map_err(|e| format!("Failed to remove settings.json: {}", e))?;
+    Ok(true)
+}
+
+/// Keep the exported hook key in line with the app: re-export it when the key
+/// changed or expired while auto-update hooks still need it, and remove it when
+/// none do. Called at startup and after the API key setting is saved.
+pub fn refresh_exported_hook_key(db: &rusqlite::Connection, key_changed: bool) -> Result<(), String> {
+    let settings_path = hook_settings_path()?;
+    if !settings_path.exists() {
+        return Ok(());
+    }
+
+    if auto_update_hook_projects(db).is_empty() {
+        remove_hook_settings_if_unused(db)?;
+        return Ok(());
+    }
+
+    let status = read_hook_key_status(&settings_path, Vec::new());
+    if key_changed || status.expired {
+        match read_stored_api_key(db) {
+            Ok(_) => {
+                export_api_key_for_hook(db)?;
+            }
+            // Key was cleared in Settings: don't leave the old one on disk
+            Err(_) => {
+                let _guard = HOOK_SETTINGS_LOCK
+                    .lock()
+                    .map_err(|e| format!("Hook settings lock error: {}", e))?;
+                std::fs::remove_file(&settings_path)
+                    .map_err(|e| format!("Failed to remove settings.json: {}", e))?;
+            }
+        }
+    }
+
     Ok(())
 }
 
+/// Re-export the hook API key from the current (possibly new) key in Settings,
+/// starting a fresh expiry window. Fails if no project uses auto-update hooks.
+#[tauri::command]
+pub async fn rotate_hook_api_key(state: State<'_, AppState>) -> Result<HookKeyStatus, String> {
+    let db = state.db.lock().map_err(|e| format!("DB lock error: {}", e))?;
+
+    if auto_update_hook_projects(&db).is_empty() {
+        remove_hook_settings_if_unused(&db)?;
+        return Err("No projects use auto-update hooks; nothing to export.".to_string());
+    }
+
+    export_api_key_for_hook(&db)
+}
+
+/// Report whether a hook API key is exported, when it expires, and which
+/// projects' auto-update hooks depend on it.
+#[tauri::command]
+pub async fn get_hook_key_status(state: State<'_, AppState>) -> Result<HookKeyStatus, String> {
+    let db = state.db.lock().map_err(|e| format!("DB lock error: {}", e))?;
+    let settings_path = hook_settings_path()?;
+    Ok(read_hook_key_status(&settings_path, auto_update_hook_projects(&db)))
+}
+
 /// Install a pre-commit git hook that checks documentation headers.
 /// Creates .git/hooks/pre-commit with a doc-checking script.
 #[tauri::command]
@@ -193,45 +400,7 @@ pub async fn install_git_hooks(
     let hook_script = if mode == "auto-update" {
         generate_auto_update_hook_script()
     } else {
-        let exit_code = if mode == "block" { "1" } else { "0" };
-        format!(
-            r#"#!/bin/sh
-# Project Jumpstart — Documentation Enforcement Hook
-# Version: {version}
-# Mode: {mode}
-# Auto-generated. Edit via Project Jumpstart settings.
-
-EXTENSIONS="ts tsx js jsx rs py go"
-MISSING_FILE=$(mktemp "${{TMPDIR:-/tmp}}/jumpstart-hook.XXXXXX") || exit 0
-trap 'rm -f "$MISSING_FILE"' EXIT
-
-# Use null-delimited output to handle filenames with spaces/special chars
-git diff --cached --name-only --diff-filter=ACM -z | while IFS= read -r -d '' file; do
-    ext="${{file##*.}}"
-    case " $EXTENSIONS " in
-        *" $ext "*)
-            head -30 "$file" 2>/dev/null | grep -q "@module\|@description\|//! @module" || {{
-                echo "WARNING: Missing documentation header in $file"
-                printf '%s\n' "$file" >> "$MISSING_FILE"
-            }}
-            ;;
-    esac
-done
-
-if [ -s "$MISSING_FILE" ]; then
-    MISSING_DOCS=$(wc -l < "$MISSING_FILE" | tr -d ' ')
-    echo ""
-    echo "Found $MISSING_DOCS file(s) without documentation headers."
-    echo "Run Project Jumpstart to generate missing docs."
-    exit {exit_code}
-fi
-
-exit 0
-"#,
-            version = HOOK_VERSION,
-            mode = mode,
-            exit_code = exit_code,
-        )
+        generate_hook_script(&mode)
     };
 
     std::fs::write(&hook_path, &hook_script)
@@ -251,20 +420,28 @@ exit 0
     // Log activity (best-effort, non-critical)
     match state.db.lock() {
         Ok(db) => {
+            // Switching away from auto-update may leave the exported key unused
+            if mode != "auto-update" {
+                if let Err(e) = remove_hook_settings_if_unused(&db) {
+                    tracing::warn!(error = %e, "Failed to clean up hook settings");
+                }
+            }
             if let Ok(pid) = db.query_row(
                 "SELECT id FROM projects WHERE path = ?1",
                 [&project_path],
                 |row| row.get::<_, String>(0),
             ) {
-                let _ = db::log_activity_db(
+                events::publish(
                     &db,
-                    &pid,
-                    "enforcement",
-                    &format!("Installed git hooks ({})", &mode),
+                    AppEvent::activity(
+                        &pid,
+                        ActivityType::Enforcement,
+                        &format!("Installed git hooks ({})", &mode),
+                    ),
                 );
             }
         }
-        Err(e) => eprintln!("Failed to lock DB for activity logging: {}", e),
+        Err(e) => tracing::warn!(error = %e, "Failed to lock DB for activity logging"),
     }
 
     Ok(HookStatus {
@@ -279,6 +456,60 @@ exit 0
     })
 }
 
+/// Pre-commit hook script for "block" and "warn" modes (any mode other than "block" warns).
+pub(crate) fn generate_hook_script(mode: &str) -> String {
+    let exit_code = if mode == "block" { "1" } else { "0" };
+    format!(
+        r#"#!/bin/sh
+# Project Jumpstart — Documentation Enforcement Hook
+# Version: {version}
+# Mode: {mode}
+# Auto-generated. Edit via Project Jumpstart settings.
+
+EXTENSIONS="ts tsx js jsx rs py go"
+# Generated/vendored files are exempt unless listed in .claude/generated-overrides;
+# files marked jumpstart:ignore are always exempt
+is_generated() {{
+    head -20 "$1" 2>/dev/null | grep -q "jumpstart:ignore" && return 0
+    grep -qxF "$1" .claude/generated-overrides 2>/dev/null && return 1
+    case "$1" in
+        *.pb.go|*.gen.go|*_pb2.py|*.generated.ts|*.generated.tsx|*.gen.ts|*.min.js|*.bundle.js) return 0 ;;
+    esac
+    head -20 "$1" 2>/dev/null | grep -q "@generated\|DO NOT EDIT"
+}}
+MISSING_FILE=$(mktemp "${{TMPDIR:-/tmp}}/jumpstart-hook.XXXXXX") || exit 0
+trap 'rm -f "$MISSING_FILE"' EXIT
+
+# Use null-delimited output to handle filenames with spaces/special chars
+git diff --cached --name-only --diff-filter=ACM -z | while IFS= read -r -d '' file; do
+    ext="${{file##*.}}"
+    case " $EXTENSIONS " in
+        *" $ext "*)
+            is_generated "$file" && continue
+            head -30 "$file" 2>/dev/null | grep -q "@module\|@description\|//! @module" || {{
+                echo "WARNING: Missing documentation header in $file"
+                printf '%s\n' "$file" >> "$MISSING_FILE"
+            }}
+            ;;
+    esac
+done
+
+if [ -s "$MISSING_FILE" ]; then
+    MISSING_DOCS=$(wc -l < "$MISSING_FILE" | tr -d ' ')
+    echo ""
+    echo "Found $MISSING_DOCS file(s) without documentation headers."
+    echo "Run Project Jumpstart to generate missing docs."
+    exit {exit_code}
+fi
+
+exit 0
+"#,
+        version = HOOK_VERSION,
+        mode = mode,
+        exit_code = exit_code,
+    )
+}
+
 /// Internal function to install git hooks without State (used by onboarding).
 /// This is a synchronous version that takes the db connection directly.
 pub fn install_git_hooks_internal(
@@ -314,45 +545,7 @@ pub fn install_git_hooks_internal(
     let hook_script = if mode == "auto-update" {
         generate_auto_update_hook_script()
     } else {
-        let exit_code = if mode == "block" { "1" } else { "0" };
-        format!(
-            r#"#!/bin/sh
-# Project Jumpstart — Documentation Enforcement Hook
-# Version: {version}
-# Mode: {mode}
-# Auto-generated. Edit via Project Jumpstart settings.
-
-EXTENSIONS="ts tsx js jsx rs py go"
-MISSING_FILE=$(mktemp "${{TMPDIR:-/tmp}}/jumpstart-hook.XXXXXX") || exit 0
-trap 'rm -f "$MISSING_FILE"' EXIT
-
-# Use null-delimited output to handle filenames with spaces/special chars
-git diff --cached --name-only --diff-filter=ACM -z | while IFS= read -r -d '' file; do
-    ext="${{file##*.}}"
-    case " $EXTENSIONS " in
-        *" $ext "*)
-            head -30 "$file" 2>/dev/null | grep -q "@module\|@description\|//! @module" || {{
-                echo "WARNING: Missing documentation header in $file"
-                printf '%s\n' "$file" >> "$MISSING_FILE"
-            }}
-            ;;
-    esac
-done
-
-if [ -s "$MISSING_FILE" ]; then
-    MISSING_DOCS=$(wc -l < "$MISSING_FILE" | tr -d ' ')
-    echo ""
-    echo "Found $MISSING_DOCS file(s) without documentation headers."
-    echo "Run Project Jumpstart to generate missing docs."
-    exit {exit_code}
-fi
-
-exit 0
-"#,
-            version = HOOK_VERSION,
-            mode = mode,
-            exit_code = exit_code,
-        )
+        generate_hook_script(mode)
     };
 
     std::fs::write(&hook_path, &hook_script)
@@ -367,9 +560,127 @@ exit 0
             .map_err(|e| format!("Failed to set hook permissions: {}", e))?;
     }
 
+    if mode != "auto-update" {
+        if let Some(conn) = db {
+            if let Err(e) = remove_hook_settings_if_unused(conn) {
+                tracing::warn!(error = %e, "Failed to clean up hook settings");
+            }
+        }
+    }
+
     Ok(())
 }
 
+/// Remove the Project Jumpstart pre-commit hook from a project.
+/// Hooks not written by Project Jumpstart are left untouched. When the last
+/// auto-update hook is removed, the exported settings.json key is deleted too.
+#[tauri::command]
+pub async fn uninstall_git_hooks(
+    project_path: String,
+    state: State<'_, AppState>,
+) -> Result<HookStatus, String> {
+    let hook_path = Path::new(&project_path)
+        .join(".git")
+        .join("hooks")
+        .join("pre-commit");
+
+    if hook_path.exists() {
+        let content = std::fs::read_to_string(&hook_path)
+            .map_err(|e| format!("Failed to read hook: {}", e))?;
+        if !content.contains("Project Jumpstart") {
+            return Err("The pre-commit hook was not installed by Project Jumpstart; leaving it in place.".to_string());
+        }
+        std::fs::remove_file(&hook_path)
+            .map_err(|e| format!("Failed to remove hook: {}", e))?;
+    }
+
+    // The post-merge hook only goes when it is ours
+    let post_merge = Path::new(&project_path).join(".git").join("hooks").join("post-merge");
+    if std::fs::read_to_string(&post_merge).is_ok_and(|c| c.contains("Project Jumpstart")) {
+        std::fs::remove_file(&post_merge).map_err(|e| format!("Failed to remove post-merge hook: {}", e))?;
+    }
+
+    match state.db.lock() {
+        Ok(db) => {
+            if let Err(e) = remove_hook_settings_if_unused(&db) {
+                tracing::warn!(error = %e, "Failed to clean up hook settings");
+            }
+            if let Ok(pid) = db.query_row(
+                "SELECT id FROM projects WHERE path = ?1",
+                [&project_path],
+                |row| row.get::<_, String>(0),
+            ) {
+                events::publish(&db, AppEvent::activity(&pid, ActivityType::Enforcement, "Uninstalled git hooks"));
+            }
+        }
+        Err(e) => tracing::warn!(error = %e, "Failed to lock DB for activity logging"),
+    }
+
+    get_hook_status(project_path).await
+}
+
+/// Install a post-merge hook that records each merge's range for post_merge_scan.
+/// Refuses to replace a post-merge hook that Project Jumpstart did not install.
+#[tauri::command]
+pub async fn install_merge_drift_hook(project_path: String, state: State<'_, AppState>) -> Result<String, String> {
+    let hooks_dir = Path::new(&project_path).join(".git").join("hooks");
+    if !hooks_dir.parent().is_some_and(|git| git.exists()) {
+        return Err("Not a git repository. Initialize git first.".to_string());
+    }
+    std::fs::create_dir_all(&hooks_dir).map_err(|e| format!("Failed to create hooks directory: {}", e))?;
+
+    let hook_path = hooks_dir.join("post-merge");
+    if let Ok(existing) = std::fs::read_to_string(&hook_path) {
+        if !existing.contains("Project Jumpstart") {
+            return Err("A post-merge hook already exists and was not installed by Project Jumpstart.".to_string());
+        }
+    }
+    std::fs::write(&hook_path, generate_post_merge_hook_script())
+        .map_err(|e| format!("Failed to write post-merge hook: {}", e))?;
+
+    #[cfg(unix)]
+    {
+        use std::os::unix::fs::PermissionsExt;
+        std::fs::set_permissions(&hook_path, std::fs::Permissions::from_mode(0o755))
+            .map_err(|e| format!("Failed to set hook permissions: {}", e))?;
+    }
+
+    match state.db.lock() {
+        Ok(db) => {
+            if let Ok(pid) = db.query_row(
+                "SELECT id FROM projects WHERE path = ?1",
+                [&project_path],
+                |row| row.get::<_, String>(0),
+            ) {
+                events::publish(&db, AppEvent::activity(&pid, ActivityType::Enforcement, "Installed post-merge drift hook"));
+            }
+        }
+        Err(e) => tracing::warn!(error = %e, "Failed to lock DB for activity logging"),
+    }
+
+    Ok(hook_path.to_string_lossy().to_string())
+}
+
+/// Post-merge hook script: appends "<ORIG_HEAD> <HEAD>" to the pending-merges file. Never fails.
+fn generate_post_merge_hook_script() -> String {
+    format!(
+        r#"#!/bin/sh
+# Project Jumpstart — Merge Drift Hook
+# Records merged ranges; Project Jumpstart checks them for stale doc headers.
+# Auto-generated. Remove via Project Jumpstart settings.
+
+GIT_DIR=$(git rev-parse --absolute-git-dir 2>/dev/null) || exit 0
+OLD=$(git rev-parse -q --verify ORIG_HEAD) || exit 0
+NEW=$(git rev-parse -q --verify HEAD) || exit 0
+if [ "$OLD" != "$NEW" ]; then
+    echo "$OLD $NEW" >> "$GIT_DIR/{pending}"
+fi
+exit 0
+"#,
+        pending = merge_drift::PENDING_MERGES_FILE
+    )
+}
+
 /// Initialize a git repository in the project directory.
 #[tauri::command]
 pub async fn init_git(project_path: String) -> Result<(), String> {
@@ -384,10 +695,7 @@ pub async fn init_git(project_path: String) -> Result<(), String> {
     }
 
     // Run git init
-    let output = std::process::Command::new("git")
-        .arg("init")
-        .current_dir(path)
-        .output()
+    let output = proc::run(std::process::Command::new("git").arg("init").current_dir(path), ProcLimits::GIT)
         .map_err(|e| format!("Failed to run git init: {}", e))?;
 
     if !output.status.success() {
@@ -407,22 +715,34 @@ pub async fn get_hook_status(project_path: String) -> Result<HookStatus, String>
     let hook_path = git_dir.join("hooks").join("pre-commit");
     let has_husky = path.join(".husky").exists();
 
-    if !has_git || !hook_path.exists() {
-        return Ok(HookStatus {
+    let content = if has_git && hook_path.exists() {
+        Some(std::fs::read_to_string(&hook_path).map_err(|e| format!("Failed to read hook: {}", e))?)
+    } else {
+        None
+    };
+
+    Ok(hook_status_from(
+        content.as_deref(),
+        hook_path.to_string_lossy().to_string(),
+        has_git,
+        has_husky,
+    ))
+}
+
+/// Build a HookStatus from the pre-commit hook's content (None when there is no hook).
+pub(crate) fn hook_status_from(content: Option<&str>, hook_path: String, has_git: bool, has_husky: bool) -> HookStatus {
+    let Some(content) = content else {
+        return HookStatus {
             installed: false,
-            hook_path: hook_path.to_string_lossy().to_string(),
+            hook_path,
             mode: "none".to_string(),
             has_husky,
             has_git,
             version: None,
             outdated: false,
             current_version: HOOK_VERSION.to_string(),
-        });
-    }
-
-    // Read hook to determine mode
-    let content = std::fs::read_to_string(&hook_path)
-        .map_err(|e| format!("Failed to read hook: {}", e))?;
+        };
+    };
 
     // Check for our hook (support both old and new app names)
     let is_jumpstart_hook = content.contains("Project Jumpstart") || content.contains("Claude Code Copilot");
@@ -438,7 +758,7 @@ pub async fn get_hook_status(project_path: String) -> Result<HookStatus, String>
 
     // Parse version from hook content
     let version = if is_jumpstart_hook {
-        parse_hook_version(&content)
+        parse_hook_version(content)
     } else {
         None
     };
@@ -450,16 +770,16 @@ pub async fn get_hook_status(project_path: String) -> Result<HookStatus, String>
         false
     };
 
-    Ok(HookStatus {
+    HookStatus {
         installed: is_jumpstart_hook,
-        hook_path: hook_path.to_string_lossy().to_string(),
+        hook_path,
         mode,
         has_husky,
         has_git,
         version,
         outdated,
         current_version: HOOK_VERSION.to_string(),
-    })
+    }
 }
 
 /// Check if Claude Code PostToolUse hooks are configured for the project.
@@ -526,45 +846,148 @@ pub async fn get_enforcement_events(
     Ok(events)
 }
 
+/// Import recent doc-check runs from the project's forge (GitHub Actions, GitLab CI,
+/// or Bitbucket Pipelines). Failed runs are recorded as enforcement events with
+/// source "ci"; runs already imported are skipped.
+#[tauri::command]
+pub async fn sync_ci_enforcement(project_id: String, state: State<'_, AppState>) -> Result<CiSyncReport, String> {
+    let forge = {
+        let db = state.db.lock().map_err(|e| format!("DB lock error: {}", e))?;
+        let project_path: String = db
+            .query_row("SELECT path FROM projects WHERE id = ?1", [&project_id], |row| row.get(0))
+            .map_err(|e| format!("Project not found: {}", e))?;
+        git_forge::forge_for_project(&db, &project_id, &project_path)?
+    };
+
+    let repository = forge.repository();
+    let runs = forge.fetch_doc_check_runs(&state.http_client).await?;
+    let failures: Vec<EnforcementEvent> = runs
+        .iter()
+        .filter_map(|run| git_forge::failure_event(&project_id, run))
+        .collect();
+
+    let db = state.db.lock().map_err(|e| format!("DB lock error: {}", e))?;
+    let new_events = git_forge::record_events(&db, &failures)?;
+    if new_events > 0 {
+        events::publish(
+            &db,
+            AppEvent::activity(
+                &project_id,
+                ActivityType::Enforcement,
+                &format!("Imported {} failed CI doc checks from {}", new_events, repository),
+            ),
+        );
+    }
+
+    Ok(CiSyncReport {
+        provider: forge.provider(),
+        repository,
+        runs_checked: runs.len() as u32,
+        failures_found: failures.len() as u32,
+        new_events,
+    })
+}
+
 /// Generate CI integration snippets for documentation enforcement.
+/// When the project's doc goals enforce CI, the check fails under the coverage goal
+/// instead of on any missing header. Snippets only scan the source directories and
+/// extensions the scanner finds, and monorepos get path filters for their workspaces.
 #[tauri::command]
-pub async fn get_ci_snippets(project_path: String) -> Result<Vec<CiSnippet>, String> {
+pub async fn get_ci_snippets(project_path: String, state: State<'_, AppState>) -> Result<Vec<CiSnippet>, String> {
     let path = Path::new(&project_path);
+    let fail_under = {
+        let db = state.db.lock().map_err(|e| format!("DB lock error: {}", e))?;
+        db.query_row("SELECT id FROM projects WHERE path = ?1", [&project_path], |row| {
+            row.get::<_, String>(0)
+        })
+        .ok()
+        .and_then(|id| doc_goals::ci_fail_under(&health_history::load_doc_goals(&db, &id)))
+    };
+    let layout = scanner::detect_source_layout(path);
+    let check = match fail_under {
+        Some(threshold) => format!("Fails when documentation coverage is under {}%", threshold),
+        None => "Checks that all source files have documentation headers".to_string(),
+    };
+    let scope = layout_summary(&layout);
 
-    let mut snippets = Vec::new();
-
-    // GitHub Actions snippet
-    let has_github = path.join(".github").join("workflows").exists();
-    snippets.push(CiSnippet {
-        provider: "github_actions".to_string(),
-        name: "Documentation Coverage Check".to_string(),
-        description: "Checks that all source files have documentation headers on pull requests."
-            .to_string(),
-        filename: ".github/workflows/doc-check.yml".to_string(),
-        content: generate_github_actions_snippet(),
-    });
-
-    // GitLab CI snippet
-    let has_gitlab = path.join(".gitlab-ci.yml").exists();
-    snippets.push(CiSnippet {
-        provider: "gitlab_ci".to_string(),
-        name: "Documentation Coverage Check".to_string(),
-        description: "Checks documentation headers as part of the GitLab CI pipeline.".to_string(),
-        filename: ".gitlab-ci.yml (add stage)".to_string(),
-        content: generate_gitlab_ci_snippet(),
-    });
+    // (provider, filename, trigger, config file, already-configured note, content)
+    let variants = [
+        (
+            "github_actions",
+            ".github/workflows/doc-check.yml",
+            "on pull requests",
+            path.join(".github").join("workflows"),
+            "workflows directory exists",
+            generate_github_actions_snippet(fail_under, &layout),
+        ),
+        (
+            "gitlab_ci",
+            ".gitlab-ci.yml (add stage)",
+            "in merge request pipelines",
+            path.join(".gitlab-ci.yml"),
+            ".gitlab-ci.yml exists",
+            generate_gitlab_ci_snippet(fail_under, &layout),
+        ),
+        (
+            "bitbucket_pipelines",
+            "bitbucket-pipelines.yml (add step)",
+            "in Bitbucket Pipelines on pull requests",
+            path.join("bitbucket-pipelines.yml"),
+            "bitbucket-pipelines.yml exists",
+            generate_bitbucket_pipelines_snippet(fail_under, &layout),
+        ),
+        (
+            "circleci",
+            ".circleci/config.yml (add job)",
+            "in CircleCI on every push (CircleCI has no native path filters)",
+            path.join(".circleci").join("config.yml"),
+            ".circleci/config.yml exists",
+            generate_circleci_snippet(fail_under, &layout),
+        ),
+        (
+            "azure_pipelines",
+            "azure-pipelines.yml",
+            "in Azure Pipelines on pull requests",
+            path.join("azure-pipelines.yml"),
+            "azure-pipelines.yml exists",
+            generate_azure_pipelines_snippet(fail_under, &layout),
+        ),
+    ];
 
-    // Mark which ones are already configured
-    if has_github {
-        snippets[0].description = format!("{} (workflows directory exists)", snippets[0].description);
-    }
-    if has_gitlab {
-        snippets[1].description = format!("{} (.gitlab-ci.yml exists)", snippets[1].description);
-    }
+    let snippets = variants
+        .into_iter()
+        .map(|(provider, filename, trigger, config_path, note, content)| {
+            let configured = config_path.exists();
+            let mut description = format!("{} {}. {}", check, trigger, scope);
+            if configured {
+                description = format!("{} ({})", description, note);
+            }
+            CiSnippet {
+                provider: provider.to_string(),
+                name: "Documentation Coverage Check".to_string(),
+                description,
+                filename: filename.to_string(),
+                content,
+                configured,
+                layout: layout.clone(),
+            }
+        })
+        .collect();
 
     Ok(snippets)
 }
 
+/// One-line description of what the snippets scan, e.g. "Scans src (rs, ts) in packages/**.".
+fn layout_summary(layout: &SourceLayout) -> String {
+    let dirs = if layout.source_dirs.is_empty() { "src".to_string() } else { layout.source_dirs.join(", ") };
+    let extensions = doc_check_extensions(layout).replace(' ', ", ");
+    if layout.path_filters.is_empty() {
+        format!("Scans {} ({}).", dirs, extensions)
+    } else {
+        format!("Scans {} ({}) when {} change.", dirs, extensions, layout.path_filters.join(", "))
+    }
+}
+
 /// Calculate the enforcement score for health integration (0-10).
 /// 5 points for git hooks installed, 5 points for CI config present.
 pub fn calculate_enforcement_score(project_path: &str) -> u32 {
@@ -587,7 +1010,10 @@ pub fn calculate_enforcement_score(project_path: &str) -> u32 {
     // Check for CI config
     let has_github_ci = path.join(".github").join("workflows").exists();
     let has_gitlab_ci = path.join(".gitlab-ci.yml").exists();
-    if has_github_ci || has_gitlab_ci {
+    let has_bitbucket_ci = path.join("bitbucket-pipelines.yml").exists();
+    let has_circleci = path.join(".circleci").join("config.yml").exists();
+    let has_azure_ci = path.join("azure-pipelines.yml").exists();
+    if has_github_ci || has_gitlab_ci || has_bitbucket_ci || has_circleci || has_azure_ci {
         score += 5;
     }
 
@@ -741,6 +1167,32 @@ HEALTH_FILE="$HOME/.project-jumpstart/.hook-health"
 BACKUP_DIR=$(mktemp -d "${{TMPDIR:-/tmp}}/jumpstart-backup.XXXXXX") || BACKUP_DIR=""
 MAX_CONSECUTIVE_FAILURES=3
 
+# Generated/vendored files are exempt unless listed in .claude/generated-overrides;
+# files marked jumpstart:ignore are always exempt
+is_generated() {{
+    head -20 "$1" 2>/dev/null | grep -q "jumpstart:ignore" && return 0
+    grep -qxF "$1" .claude/generated-overrides 2>/dev/null && return 1
+    case "$1" in
+        *.pb.go|*.gen.go|*_pb2.py|*.generated.ts|*.generated.tsx|*.gen.ts|*.min.js|*.bundle.js) return 0 ;;
+    esac
+    head -20 "$1" 2>/dev/null | grep -q "@generated\|DO NOT EDIT"
+}}
+
+# Files matching .claude/ai-exclude globs are never sent to the API
+is_ai_excluded() {{
+    [ -f .claude/ai-exclude ] || return 1
+    while IFS= read -r pattern || [ -n "$pattern" ]; do
+        case "$pattern" in ''|'#'*) continue ;; esac
+        pattern="${{pattern#./}}"
+        pattern="${{pattern#/}}"
+        pattern="${{pattern%/}}"
+        case "$1" in
+            $pattern|$pattern/*|*/$pattern|*/$pattern/*) return 0 ;;
+        esac
+    done < .claude/ai-exclude
+    return 1
+}}
+
 # --- Counters ---
 FILES_PROCESSED=0
 FILES_SKIPPED=0
@@ -942,6 +1394,13 @@ if [ -z "$API_KEY" ]; then
     exit 0
 fi
 
+EXPIRES_AT=$(jq -r '.expires_at_epoch // empty' "$SETTINGS_FILE" 2>/dev/null)
+if [ -z "$EXPIRES_AT" ] || [ "$(date +%s)" -ge "$EXPIRES_AT" ]; then
+    echo "[Project Jumpstart] Warning: Exported API key has expired. Skipping auto-update."
+    echo "  Open Project Jumpstart to re-export it (Enforcement > Rotate Hook Key)."
+    exit 0
+fi
+
 case "$API_KEY" in
     sk-ant-*)
         ;;
@@ -968,6 +1427,7 @@ if check_downgraded; then
         ext="${{file##*.}}"
         case " $EXTENSIONS " in
             *" $ext "*)
+                is_generated "$file" && continue
                 if ! head -30 "$file" 2>/dev/null | grep -q "@module\|@description\|//! @module"; then
                     echo "  [warn] Missing documentation header in $file"
                 fi
@@ -983,7 +1443,12 @@ git diff --cached --name-only --diff-filter=ACM -z | while IFS= read -r -d '' fi
     ext="${{file##*.}}"
     case " $EXTENSIONS " in
         *" $ext "*)
+            is_generated "$file" && continue
             if ! head -30 "$file" 2>/dev/null | grep -q "@module\|@description\|//! @module"; then
+                if is_ai_excluded "$file"; then
+                    echo "  [warn] $file is excluded from AI (.claude/ai-exclude). Add its header from Project Jumpstart."
+                    continue
+                fi
                 printf '%s\0' "$file" >> "$HOME/.project-jumpstart/.missing_files_$$"
             fi
             ;;
@@ -1218,13 +1683,105 @@ exit 0
 
 // --- CI Template Generators ---
 
-fn generate_github_actions_snippet() -> String {
-    r#"name: Documentation Check
+/// Extensions the doc check scans: those present in the project, or the default set when
+/// the scanner found none.
+fn doc_check_extensions(layout: &SourceLayout) -> String {
+    if layout.extensions.is_empty() {
+        DEFAULT_DOC_CHECK_EXTENSIONS.to_string()
+    } else {
+        layout.extensions.join(" ")
+    }
+}
+
+/// Extensions checked when the project has no detected sources yet
+const DEFAULT_DOC_CHECK_EXTENSIONS: &str = "ts tsx js jsx rs py go";
+
+/// Shell script that checks doc headers in the layout's source directories (src/ when none
+/// were detected), skipping dependency and build directories. Without a threshold every file
+/// must have a header; with `fail_under` it reports coverage and fails only below that
+/// percentage (DOC_FAIL_UNDER in the CI environment overrides it). Lines are unindented.
+fn doc_check_script(fail_under: Option<f64>, error_prefix: &str, layout: &SourceLayout) -> String {
+    let dirs = if layout.source_dirs.is_empty() {
+        "src".to_string()
+    } else {
+        layout.source_dirs.iter().map(|d| format!("\"{}\"", d)).collect::<Vec<_>>().join(" ")
+    };
+    let mut script = format!(
+        r#"TOTAL=0
+MISSING=0
+EXTENSIONS="{extensions}"
+for file in $(find {dirs} \( -name node_modules -o -name target -o -name dist -o -name build -o -name vendor -o -name .git \) -prune -o -type f -print); do
+  ext="${{file##*.}}"
+  case " $EXTENSIONS " in
+    *" $ext "*)
+      TOTAL=$((TOTAL + 1))
+      if ! head -30 "$file" | grep -q "@module\|@description\|//! @module"; then
+        echo "Missing doc header: $file"
+        MISSING=$((MISSING + 1))
+      fi
+      ;;
+  esac
+done
+"#,
+        extensions = doc_check_extensions(layout),
+    );
+    match fail_under {
+        Some(threshold) => script.push_str(&format!(
+            r#"FAIL_UNDER="${{DOC_FAIL_UNDER:-{threshold}}}"
+if [ $TOTAL -eq 0 ]; then
+  echo "No source files to check"
+  exit 0
+fi
+COVERAGE=$(awk "BEGIN {{ printf \"%.1f\", ($TOTAL - $MISSING) * 100 / $TOTAL }}")
+echo "Documentation coverage: $COVERAGE% ($((TOTAL - MISSING))/$TOTAL files, goal $FAIL_UNDER%)"
+if awk "BEGIN {{ exit !($COVERAGE < $FAIL_UNDER) }}"; then
+  echo "{error_prefix}Documentation coverage $COVERAGE% is under the $FAIL_UNDER% goal"
+  exit 1
+fi
+"#
+        )),
+        None => script.push_str(&format!(
+            r#"if [ $MISSING -gt 0 ]; then
+  echo "{error_prefix}Found $MISSING file(s) without documentation headers"
+  exit 1
+fi
+echo "All source files have documentation headers"
+"#
+        )),
+    }
+    script
+}
+
+/// Indent every non-empty line of a script by `spaces`.
+fn indent(script: &str, spaces: usize) -> String {
+    let pad = " ".repeat(spaces);
+    script
+        .lines()
+        .map(|line| if line.is_empty() { String::new() } else { format!("{}{}", pad, line) })
+        .collect::<Vec<_>>()
+        .join("\n")
+        + "\n"
+}
+
+/// YAML list items ("- 'glob'") of the layout's path filters, one per line, indented by `spaces`.
+fn path_filter_list(layout: &SourceLayout, spaces: usize) -> String {
+    let items: Vec<String> = layout.path_filters.iter().map(|f| format!("- '{}'", f)).collect();
+    indent(&items.join("\n"), spaces)
+}
+
+pub(crate) fn generate_github_actions_snippet(fail_under: Option<f64>, layout: &SourceLayout) -> String {
+    let paths = if layout.path_filters.is_empty() {
+        String::new()
+    } else {
+        format!("    paths:\n{}", path_filter_list(layout, 6))
+    };
+    format!(
+        r#"name: Documentation Check
 
 on:
   pull_request:
     branches: [main]
-
+{}
 jobs:
   doc-check:
     runs-on: ubuntu-latest
@@ -1233,60 +1790,111 @@ jobs:
 
       - name: Check documentation headers
         run: |
-          MISSING=0
-          EXTENSIONS="ts tsx js jsx rs py go"
-          for file in $(find src -type f); do
-            ext="${file##*.}"
-            case " $EXTENSIONS " in
-              *" $ext "*)
-                if ! head -30 "$file" | grep -q "@module\|@description\|//! @module"; then
-                  echo "Missing doc header: $file"
-                  MISSING=$((MISSING + 1))
-                fi
-                ;;
-            esac
-          done
-          if [ $MISSING -gt 0 ]; then
-            echo "::error::Found $MISSING file(s) without documentation headers"
-            exit 1
-          fi
-          echo "All source files have documentation headers"
-"#
-    .to_string()
+{}"#,
+        paths,
+        indent(&doc_check_script(fail_under, "::error::", layout), 10)
+    )
 }
 
-fn generate_gitlab_ci_snippet() -> String {
-    r#"doc-check:
+fn generate_gitlab_ci_snippet(fail_under: Option<f64>, layout: &SourceLayout) -> String {
+    let only = if layout.path_filters.is_empty() {
+        "  only:\n    - merge_requests\n".to_string()
+    } else {
+        format!("  only:\n    refs:\n      - merge_requests\n    changes:\n{}", path_filter_list(layout, 6))
+    };
+    format!(
+        r#"{}:
   stage: test
   script:
     - |
-      MISSING=0
-      EXTENSIONS="ts tsx js jsx rs py go"
-      for file in $(find src -type f); do
-        ext="${file##*.}"
-        case " $EXTENSIONS " in
-          *" $ext "*)
-            if ! head -30 "$file" | grep -q "@module\|@description\|//! @module"; then
-              echo "Missing doc header: $file"
-              MISSING=$((MISSING + 1))
-            fi
-            ;;
-        esac
-      done
-      if [ $MISSING -gt 0 ]; then
-        echo "Found $MISSING file(s) without documentation headers"
-        exit 1
-      fi
-      echo "All source files have documentation headers"
-  only:
-    - merge_requests
-"#
-    .to_string()
+{}{}"#,
+        gitlab_ci::DOC_CHECK_JOB,
+        indent(&doc_check_script(fail_under, "", layout), 6),
+        only
+    )
+}
+
+fn generate_bitbucket_pipelines_snippet(fail_under: Option<f64>, layout: &SourceLayout) -> String {
+    let condition = if layout.path_filters.is_empty() {
+        String::new()
+    } else {
+        format!("          condition:\n            changesets:\n              includePaths:\n{}", path_filter_list(layout, 16))
+    };
+    format!(
+        r#"pipelines:
+  pull-requests:
+    '**':
+      - step:
+          name: {}
+{}          script:
+            - |
+{}"#,
+        bitbucket_ci::DOC_CHECK_STEP,
+        condition,
+        indent(&doc_check_script(fail_under, "", layout), 14)
+    )
+}
+
+/// CircleCI job and workflow. CircleCI cannot filter by changed paths without the
+/// path-filtering orb, so the job runs on every push.
+fn generate_circleci_snippet(fail_under: Option<f64>, layout: &SourceLayout) -> String {
+    format!(
+        r#"version: 2.1
+
+jobs:
+  doc-check:
+    docker:
+      - image: cimg/base:stable
+    steps:
+      - checkout
+      - run:
+          name: Check documentation headers
+          command: |
+{}
+workflows:
+  documentation:
+    jobs:
+      - doc-check
+"#,
+        indent(&doc_check_script(fail_under, "", layout), 12)
+    )
+}
+
+/// Azure Pipelines definition for pull request validation. Azure path filters are
+/// directory prefixes, so "packages/**" becomes "packages".
+fn generate_azure_pipelines_snippet(fail_under: Option<f64>, layout: &SourceLayout) -> String {
+    let paths = if layout.path_filters.is_empty() {
+        String::new()
+    } else {
+        let prefixes: Vec<String> =
+            layout.path_filters.iter().map(|f| format!("- {}", f.trim_end_matches("/**"))).collect();
+        format!("  paths:\n    include:\n{}", indent(&prefixes.join("\n"), 6))
+    };
+    format!(
+        r#"trigger: none
+
+pr:
+  branches:
+    include:
+      - main
+{}
+pool:
+  vmImage: ubuntu-latest
+
+steps:
+  - checkout: self
+  - script: |
+{}    displayName: Check documentation headers
+"#,
+        paths,
+        indent(&doc_check_script(fail_under, "##vso[task.logissue type=error]", layout), 6)
+    )
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::test_support::{insert_project, memory_db};
 
     #[test]
     fn test_enforcement_score_no_git() {
@@ -1296,18 +1904,99 @@ mod tests {
 
     #[test]
     fn test_github_actions_snippet() {
-        let snippet = generate_github_actions_snippet();
+        let snippet = generate_github_actions_snippet(None, &SourceLayout::default());
         assert!(snippet.contains("Documentation Check"));
         assert!(snippet.contains("@module"));
         assert!(snippet.contains("pull_request"));
+        assert!(snippet.contains("          if [ $MISSING -gt 0 ]; then"));
+        assert!(snippet.contains("::error::Found $MISSING file(s)"));
     }
 
     #[test]
     fn test_gitlab_ci_snippet() {
-        let snippet = generate_gitlab_ci_snippet();
+        let snippet = generate_gitlab_ci_snippet(None, &SourceLayout::default());
         assert!(snippet.contains("doc-check"));
         assert!(snippet.contains("@module"));
         assert!(snippet.contains("merge_requests"));
+        assert!(snippet.contains("      done\n      if [ $MISSING -gt 0 ]; then"));
+    }
+
+    #[test]
+    fn test_ci_snippet_fail_under() {
+        let snippet = generate_github_actions_snippet(Some(90.0), &SourceLayout::default());
+        assert!(snippet.contains("FAIL_UNDER=\"${DOC_FAIL_UNDER:-90}\""));
+        assert!(snippet.contains("::error::Documentation coverage $COVERAGE% is under"));
+        assert!(!snippet.contains("if [ $MISSING -gt 0 ]"));
+
+        let snippet = generate_gitlab_ci_snippet(Some(87.5), &SourceLayout::default());
+        assert!(snippet.contains("${DOC_FAIL_UNDER:-87.5}"));
+        assert!(snippet.contains("awk \"BEGIN { exit !($COVERAGE < $FAIL_UNDER) }\""));
+        assert!(snippet.ends_with("  only:\n    - merge_requests\n"));
+    }
+
+    #[test]
+    fn test_bitbucket_pipelines_snippet() {
+        let snippet = generate_bitbucket_pipelines_snippet(None, &SourceLayout::default());
+        assert!(snippet.contains("pull-requests:"));
+        assert!(snippet.contains("name: Documentation check"));
+        assert!(snippet.contains("@module"));
+        assert!(snippet.contains("              if [ $MISSING -gt 0 ]; then"));
+    }
+
+    #[test]
+    fn test_ci_snippets_follow_source_layout() {
+        let layout = SourceLayout {
+            source_dirs: vec!["packages/api".to_string(), "packages/web/src".to_string()],
+            extensions: vec!["tsx".to_string(), "py".to_string()],
+            path_filters: vec!["packages/**".to_string()],
+        };
+
+        let snippet = generate_github_actions_snippet(None, &layout);
+        assert!(snippet.contains("EXTENSIONS=\"tsx py\""));
+        assert!(snippet.contains("find \"packages/api\" \"packages/web/src\" \\( -name node_modules"));
+        assert!(snippet.contains("    branches: [main]\n    paths:\n      - 'packages/**'\n"));
+
+        let snippet = generate_gitlab_ci_snippet(None, &layout);
+        assert!(snippet.ends_with("    refs:\n      - merge_requests\n    changes:\n      - 'packages/**'\n"));
+
+        let snippet = generate_bitbucket_pipelines_snippet(None, &layout);
+        assert!(snippet.contains("              includePaths:\n                - 'packages/**'\n          script:"));
+
+        let snippet = generate_azure_pipelines_snippet(None, &layout);
+        assert!(snippet.contains("  paths:\n    include:\n      - packages\n"));
+    }
+
+    #[test]
+    fn test_ci_snippet_defaults_without_layout() {
+        let snippet = generate_github_actions_snippet(None, &SourceLayout::default());
+        assert!(snippet.contains("EXTENSIONS=\"ts tsx js jsx rs py go\""));
+        assert!(snippet.contains("$(find src \\("));
+        assert!(!snippet.contains("paths:"));
+    }
+
+    #[test]
+    fn test_circleci_and_azure_snippets() {
+        let snippet = generate_circleci_snippet(Some(80.0), &SourceLayout::default());
+        assert!(snippet.contains("version: 2.1"));
+        assert!(snippet.contains("      - doc-check\n"));
+        assert!(snippet.contains("            FAIL_UNDER=\"${DOC_FAIL_UNDER:-80}\""));
+
+        let snippet = generate_azure_pipelines_snippet(None, &SourceLayout::default());
+        assert!(snippet.contains("trigger: none"));
+        assert!(snippet.contains("      echo \"##vso[task.logissue type=error]Found $MISSING file(s)"));
+        assert!(snippet.ends_with("    displayName: Check documentation headers\n"));
+        assert!(!snippet.contains("paths:"));
+    }
+
+    #[test]
+    fn test_post_merge_hook_script() {
+        let script = generate_post_merge_hook_script();
+        assert!(script.contains("Project Jumpstart"));
+        assert!(script.contains("ORIG_HEAD"));
+        assert!(script.contains("$GIT_DIR/jumpstart-merges"));
+        // The hook must never fail a merge
+        assert!(script.trim_end().ends_with("exit 0"));
+        assert!(!script.contains("exit 1"));
     }
 
     #[test]
@@ -1332,6 +2021,81 @@ mod tests {
         assert!(!script.contains("set -e"));
     }
 
+    #[test]
+    fn test_hook_settings_written_with_expiry() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("settings.json");
+        assert!(!read_hook_key_status(&path, Vec::new()).exported);
+
+        let now = chrono::Utc::now();
+        write_hook_settings(&path, "sk-ant-test", now).unwrap();
+        let value: serde_json::Value =
+            serde_json::from_str(&std::fs::read_to_string(&path).unwrap()).unwrap();
+        assert_eq!(value["anthropic_api_key"], "sk-ant-test");
+        assert_eq!(
+            value["expires_at_epoch"].as_i64().unwrap(),
+            (now + chrono::Duration::days(HOOK_KEY_TTL_DAYS)).timestamp()
+        );
+
+        let status = read_hook_key_status(&path, vec!["/p".to_string()]);
+        assert!(status.exported && !status.expired);
+        assert_eq!(status.projects, vec!["/p"]);
+
+        // An export whose window has passed reports as expired
+        write_hook_settings(&path, "sk-ant-test", now - chrono::Duration::days(HOOK_KEY_TTL_DAYS + 1)).unwrap();
+        assert!(read_hook_key_status(&path, Vec::new()).expired);
+        // No temp files are left behind
+        assert_eq!(std::fs::read_dir(dir.path()).unwrap().count(), 1);
+    }
+
+    #[test]
+    fn test_auto_update_hook_projects_detects_mode() {
+        let auto = tempfile::tempdir().unwrap();
+        let warn = tempfile::tempdir().unwrap();
+        for (dir, mode) in [(&auto, "auto-update"), (&warn, "warn")] {
+            let hooks = dir.path().join(".git").join("hooks");
+            std::fs::create_dir_all(&hooks).unwrap();
+            std::fs::write(hooks.join("pre-commit"), format!("#!/bin/sh\n# Mode: {}\n", mode)).unwrap();
+        }
+
+        let conn = memory_db();
+        for (id, dir) in [("p1", &auto), ("p2", &warn)] {
+            insert_project(&conn, id, &dir.path().to_string_lossy());
+        }
+
+        assert_eq!(
+            auto_update_hook_projects(&conn),
+            vec![auto.path().to_string_lossy().to_string()]
+        );
+    }
+
+    #[test]
+    fn test_auto_update_hook_checks_key_expiry() {
+        let script = generate_auto_update_hook_script();
+        assert!(script.contains(".expires_at_epoch"));
+        assert!(script.contains("Exported API key has expired"));
+    }
+
+    #[test]
+    fn test_auto_update_hook_skips_generated_files() {
+        let script = generate_auto_update_hook_script();
+        assert!(script.contains("is_generated() {"));
+        assert!(script.contains(".claude/generated-overrides"));
+        assert!(script.contains("@generated\\|DO NOT EDIT"));
+        assert!(script.contains("grep -q \"jumpstart:ignore\" && return 0"));
+        assert!(generate_hook_script("block").contains("grep -q \"jumpstart:ignore\" && return 0"));
+        assert_eq!(script.matches("is_generated \"$file\" && continue").count(), 2);
+    }
+
+    #[test]
+    fn test_auto_update_hook_skips_ai_excluded_files() {
+        let script = generate_auto_update_hook_script();
+        assert!(script.contains("is_ai_excluded() {"));
+        assert!(script.contains("done < .claude/ai-exclude"));
+        assert!(script.contains("$pattern|$pattern/*|*/$pattern|*/$pattern/*) return 0 ;;"));
+        assert!(script.contains("if is_ai_excluded \"$file\"; then"));
+    }
+
     #[test]
     fn test_auto_update_hook_never_blocks() {
         let script = generate_auto_update_hook_script();
@@ -1675,7 +2439,7 @@ echo "Hello"
 
     #[test]
     fn test_hook_version_is_4() {
-        assert_eq!(HOOK_VERSION, "4.0.0");
+        assert_eq!(HOOK_VERSION, "4.3.0");
     }
 
     #[test]
@@ -5,29 +5,57 @@
 //! - Expose freshness checking for single files and entire projects
 //! - Return stale files for the frontend to display
 //! - Provide detailed freshness results with staleness signals
+//! - Report API contract drift (OpenAPI/GraphQL schema vs. handlers) next to doc staleness
+//! - Validate DEPENDENCIES/EXPORTS entries against detected imports and exports
+//! - Find files a merge changed without touching their headers (merge drift)
+//! - Read and save per-directory freshness policies
 //!
 //! DEPENDENCIES:
 //! - tauri - Command macro
 //! - core::freshness - Staleness detection engine
-//! - models::module_doc - ModuleStatus type for batch results
+//! - core::analyzer - Doc header parsing and export/import detection
+//! - core::api_contracts - Schema/handler fingerprints and drift detection
+//! - core::doc_validation - Phantom and undocumented dependency/export detection
+//! - core::merge_drift - Post-merge drift detection and storage
+//! - db::AppState - Project lookup and the API contract baseline
+//! - models::module_doc - ModuleStatus, ChangeAuthor, and FreshnessPolicy types
 //!
 //! EXPORTS:
 //! - check_freshness - Check freshness of a single file, returns FreshnessCheckResult
 //! - get_stale_files - Get all files with outdated or missing docs
+//! - explain_freshness - Break down the signals, git history, and change authors behind a file's score
+//! - check_api_contracts - Find API schemas and report contract drift
+//! - accept_api_contracts - Record the current schemas and handlers as the drift baseline
+//! - validate_doc_dependencies - Doc lint counts for phantom/undocumented dependencies and exports
+//! - post_merge_scan - Record merge drift for hook-recorded merges (or the latest merge)
+//! - get_freshness_policies - A project's per-directory freshness policies
+//! - save_freshness_policies - Replace a project's freshness policies
 //!
 //! PATTERNS:
 //! - Commands are thin wrappers over core::freshness functions
 //! - check_freshness returns detailed signal info for single-file view
-//! - get_stale_files filters to only outdated/missing for quick win lists
+//! - get_stale_files filters to only outdated/missing for quick win lists; it first scans merges
+//!   the post-merge hook recorded, then marks files with unresolved merge drift outdated,
+//!   marks files past their freshness policy deadline outdated,
+//!   and names who changed each outdated file's code since its header (change_authors)
 //!
 //! CLAUDE NOTES:
 //! - FreshnessCheckResult is a serializable version of core FreshnessResult
 //! - The core FreshnessResult doesn't derive Serialize; this wraps it for IPC
+//! - explain_freshness git fields are None when the file isn't committed to a git repo
+//! - check_api_contracts records the baseline on its first run for a project
 
 use serde::Serialize;
+use std::fs;
+use std::path::Path;
+use tauri::State;
 
-use crate::core::freshness;
-use crate::models::module_doc::ModuleStatus;
+use crate::core::{analyzer, api_contracts, doc_validation, freshness, merge_drift, notebook};
+use crate::core::events::{self, AppEvent};
+use crate::db::AppState;
+use crate::models::activity::ActivityType;
+use crate::models::api_contracts::ApiContractReport;
+use crate::models::module_doc::{ChangeAuthor, DocValidationReport, FreshnessPolicy, MergeDriftReport, ModuleStatus};
 
 /// Serializable freshness result for IPC.
 #[derive(Debug, Clone, Serialize)]
@@ -52,14 +80,301 @@ pub async fn check_freshness(
     })
 }
 
+/// A single staleness signal with its score contribution, for IPC.
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct FreshnessSignalDetail {
+    pub signal_type: String,
+    pub weight: u32,
+    pub description: String,
+}
+
+/// Full breakdown of why a file received its freshness score.
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct FreshnessExplanation {
+    pub file_path: String,
+    pub score: u32,
+    pub status: String,
+    /// Total points deducted from 100 (sum of signal weights)
+    pub total_penalty: u32,
+    pub signals: Vec<FreshnessSignalDetail>,
+    pub documented_exports: Vec<String>,
+    pub detected_exports: Vec<String>,
+    pub documented_dependencies: Vec<String>,
+    pub detected_imports: Vec<String>,
+    /// Filesystem modification time (RFC 3339), includes uncommitted edits
+    pub file_modified_at: Option<String>,
+    pub header_last_commit: Option<String>,
+    pub header_changed_at: Option<String>,
+    pub file_changed_at: Option<String>,
+    pub commits_since_header_change: Option<u32>,
+    /// Who committed code changes since the header last changed, most lines first
+    pub change_authors: Vec<ChangeAuthor>,
+}
+
+/// Explain the signals behind a file's freshness score.
+/// Returns each weighted signal, the documented vs detected exports/imports,
+/// and git history for the doc header so users can see why a file is flagged.
+#[tauri::command]
+pub async fn explain_freshness(file_path: String) -> Result<FreshnessExplanation, String> {
+    let content = analyzer::read_source(&file_path)?;
+
+    let result = freshness::check_file_freshness(&file_path, "");
+
+    let ext = Path::new(&file_path)
+        .extension()
+        .and_then(|e| e.to_str())
+        .unwrap_or("")
+        .to_string();
+    let doc = analyzer::parse_doc_header(&content);
+
+    let file_modified_at = fs::metadata(&file_path)
+        .and_then(|m| m.modified())
+        .ok()
+        .map(|t| chrono::DateTime::<chrono::Utc>::from(t).to_rfc3339());
+
+    // A notebook's header cell has no stable line range in the JSON file
+    let header_lines = if notebook::is_notebook(&file_path) {
+        0
+    } else {
+        freshness::doc_header_line_count(&content)
+    };
+    let history = freshness::header_git_history(&file_path, header_lines);
+    let change_authors = history
+        .header_commit
+        .as_deref()
+        .map(|commit| freshness::change_authors_since(&file_path, commit))
+        .unwrap_or_default();
+
+    Ok(FreshnessExplanation {
+        file_path,
+        score: result.score,
+        status: result.status,
+        total_penalty: result.signals.iter().map(|s| s.weight).sum(),
+        signals: result
+            .signals
+            .iter()
+            .map(|s| FreshnessSignalDetail {
+                signal_type: s.signal_type.as_str().to_string(),
+                weight: s.weight,
+                description: s.description.clone(),
+            })
+            .collect(),
+        documented_exports: doc
+            .as_ref()
+            .map(|d| freshness::extract_export_names(&d.exports))
+            .unwrap_or_default(),
+        detected_exports: analyzer::detect_exports(&content, &ext),
+        documented_dependencies: doc
+            .as_ref()
+            .map(|d| freshness::extract_dependency_paths(&d.dependencies))
+            .unwrap_or_default(),
+        detected_imports: analyzer::detect_imports(&content, &ext),
+        file_modified_at,
+        header_last_commit: history.header_commit,
+        header_changed_at: history.header_changed_at,
+        file_changed_at: history.file_changed_at,
+        commits_since_header_change: history.commits_since_header_change,
+        change_authors,
+    })
+}
+
+/// Cross-check every documented file's DEPENDENCIES and EXPORTS against the
+/// imports and exports detected in its code.
+#[tauri::command]
+pub async fn validate_doc_dependencies(
+    project_id: String,
+    state: State<'_, AppState>,
+) -> Result<DocValidationReport, String> {
+    let project_path: String = {
+        let db = state.db.lock().map_err(|e| format!("DB lock error: {}", e))?;
+        db.query_row("SELECT path FROM projects WHERE id = ?1", [&project_id], |row| row.get(0))
+            .map_err(|e| format!("Project not found: {}", e))?
+    };
+    doc_validation::validate_project(&project_id, &project_path)
+}
+
+/// Diff merges brought into the project and record documented files whose code
+/// changed while their header did not. Uses ranges recorded by the post-merge hook,
+/// or the most recent merge when there are none.
+#[tauri::command]
+pub async fn post_merge_scan(
+    project_id: String,
+    state: State<'_, AppState>,
+) -> Result<MergeDriftReport, String> {
+    let project_path = project_path_for(&state, &project_id)?;
+    let mut ranges = merge_drift::take_pending_ranges(&project_path);
+    if ranges.is_empty() {
+        ranges.extend(merge_drift::last_merge_range(&project_path));
+    }
+    let report = scan_merge_ranges(&state, &project_id, &project_path, &ranges)?;
+
+    if !report.drifted.is_empty() {
+        let db = state.db.lock().map_err(|e| format!("DB lock error: {}", e))?;
+        let message = format!("Merge left {} file(s) with stale doc headers", report.drifted.len());
+        events::publish(&db, AppEvent::activity(&project_id, ActivityType::Scan, &message));
+    }
+    Ok(report)
+}
+
+fn scan_merge_ranges(
+    state: &AppState,
+    project_id: &str,
+    project_path: &str,
+    ranges: &[(String, String)],
+) -> Result<MergeDriftReport, String> {
+    let drifted: Vec<_> = ranges
+        .iter()
+        .flat_map(|(base, head)| merge_drift::detect_drift(project_path, base, head))
+        .collect();
+    if !drifted.is_empty() {
+        let db = state.db.lock().map_err(|e| format!("DB lock error: {}", e))?;
+        merge_drift::record_drift(&db, project_id, &drifted)?;
+    }
+    Ok(MergeDriftReport {
+        project_id: project_id.to_string(),
+        merges_scanned: ranges.len() as u32,
+        drifted: drifted.into_iter().map(|(file, _)| file).collect(),
+    })
+}
+
 /// Get all files with outdated or missing documentation.
 /// Returns only stale files (status != "current"), useful for quick win lists.
+/// Files with unresolved merge drift are reported as outdated.
 #[tauri::command]
-pub async fn get_stale_files(project_path: String) -> Result<Vec<ModuleStatus>, String> {
-    let all = freshness::check_project_freshness(&project_path)?;
-    let stale: Vec<ModuleStatus> = all
+pub async fn get_stale_files(project_path: String, state: State<'_, AppState>) -> Result<Vec<ModuleStatus>, String> {
+    let mut all = freshness::check_project_freshness(&project_path)?;
+
+    let project_id: Option<String> = {
+        let db = state.db.lock().map_err(|e| format!("DB lock error: {}", e))?;
+        db.query_row("SELECT id FROM projects WHERE path = ?1", [&project_path], |row| row.get(0))
+            .ok()
+    };
+    if let Some(pid) = project_id {
+        let pending = merge_drift::take_pending_ranges(&project_path);
+        if !pending.is_empty() {
+            scan_merge_ranges(&state, &pid, &project_path, &pending)?;
+        }
+        let policies = {
+            let db = state.db.lock().map_err(|e| format!("DB lock error: {}", e))?;
+            merge_drift::apply_drift(&db, &pid, &project_path, &mut all)?;
+            freshness::load_policies(&db, &pid)
+        };
+        freshness::apply_policies(&project_path, &policies, &mut all);
+    }
+
+    let mut stale: Vec<ModuleStatus> = all
         .into_iter()
         .filter(|m| m.status != "current")
         .collect();
+    freshness::attach_change_authors(&project_path, &mut stale);
     Ok(stale)
 }
+
+/// Get a project's per-directory freshness policies, in priority order.
+#[tauri::command]
+pub async fn get_freshness_policies(
+    project_id: String,
+    state: State<'_, AppState>,
+) -> Result<Vec<FreshnessPolicy>, String> {
+    let db = state.db.lock().map_err(|e| format!("DB lock error: {}", e))?;
+    Ok(freshness::load_policies(&db, &project_id))
+}
+
+/// Replace a project's freshness policies. Patterns are trimmed; empty patterns and
+/// zero-day deadlines are rejected. Returns the stored policies.
+#[tauri::command]
+pub async fn save_freshness_policies(
+    project_id: String,
+    policies: Vec<FreshnessPolicy>,
+    state: State<'_, AppState>,
+) -> Result<Vec<FreshnessPolicy>, String> {
+    let policies: Vec<FreshnessPolicy> = policies
+        .into_iter()
+        .map(|p| FreshnessPolicy { pattern: p.pattern.trim().trim_start_matches("./").to_string(), ..p })
+        .collect();
+    if let Some(policy) = policies.iter().find(|p| p.pattern.is_empty() || p.max_days == 0) {
+        return Err(format!(
+            "Each freshness policy needs a pattern and at least 1 day (got '{}', {} days)",
+            policy.pattern, policy.max_days
+        ));
+    }
+    let json = serde_json::to_string(&policies).map_err(|e| format!("Failed to serialize freshness policies: {}", e))?;
+
+    let db = state.db.lock().map_err(|e| format!("DB lock error: {}", e))?;
+    db.execute(
+        "INSERT OR REPLACE INTO settings (key, value) VALUES (?1, ?2)",
+        rusqlite::params![format!("{}{}", freshness::POLICY_SETTING_PREFIX, project_id), json],
+    )
+    .map_err(|e| format!("Failed to save freshness policies: {}", e))?;
+    events::publish(&db, AppEvent::activity(&project_id, ActivityType::Settings, "Updated freshness policies"));
+
+    Ok(policies)
+}
+
+fn project_path_for(state: &AppState, project_id: &str) -> Result<String, String> {
+    let db = state.db.lock().map_err(|e| format!("DB lock error: {}", e))?;
+    db.query_row("SELECT path FROM projects WHERE id = ?1", [project_id], |row| row.get(0))
+        .map_err(|e| format!("Project not found: {}", e))
+}
+
+/// Find OpenAPI/GraphQL schemas and report where route handlers or resolvers
+/// have drifted from them. The first check for a project records the baseline.
+#[tauri::command]
+pub async fn check_api_contracts(
+    project_id: String,
+    state: State<'_, AppState>,
+) -> Result<ApiContractReport, String> {
+    let project_path = project_path_for(&state, &project_id)?;
+    let snapshot = api_contracts::scan_contracts(&project_path);
+
+    let db = state.db.lock().map_err(|e| format!("DB lock error: {}", e))?;
+    let baseline = api_contracts::load_baseline(&db, &project_id)?;
+    let baseline_at = match baseline.first() {
+        Some(entry) => Some(entry.recorded_at.clone()),
+        None if !snapshot.schemas.is_empty() => Some(api_contracts::save_baseline(&db, &project_id, &snapshot)?),
+        None => None,
+    };
+
+    Ok(ApiContractReport {
+        schemas: snapshot.schema_summaries(),
+        handler_files: snapshot.handlers.iter().map(|h| h.path.clone()).collect(),
+        drift: api_contracts::detect_drift(&snapshot, &baseline),
+        baseline_at,
+    })
+}
+
+/// Accept the current schemas and handlers as in sync, clearing
+/// "handler_changed" drift until they change again.
+#[tauri::command]
+pub async fn accept_api_contracts(
+    project_id: String,
+    state: State<'_, AppState>,
+) -> Result<ApiContractReport, String> {
+    let project_path = project_path_for(&state, &project_id)?;
+    let snapshot = api_contracts::scan_contracts(&project_path);
+
+    let db = state.db.lock().map_err(|e| format!("DB lock error: {}", e))?;
+    let recorded_at = api_contracts::save_baseline(&db, &project_id, &snapshot)?;
+    let baseline = api_contracts::load_baseline(&db, &project_id)?;
+    events::publish(
+        &db,
+        AppEvent::activity(
+            &project_id,
+            ActivityType::Scan,
+            &format!(
+                "Accepted API contracts ({} schemas, {} handler files)",
+                snapshot.schemas.len(),
+                snapshot.handlers.len()
+            ),
+        ),
+    );
+
+    Ok(ApiContractReport {
+        schemas: snapshot.schema_summaries(),
+        handler_files: snapshot.handlers.iter().map(|h| h.path.clone()).collect(),
+        drift: api_contracts::detect_drift(&snapshot, &baseline),
+        baseline_at: Some(recorded_at),
+    })
+}
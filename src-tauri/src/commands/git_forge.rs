@@ -0,0 +1,91 @@
+//! @module commands/git_forge
+//! @description Tauri IPC commands for a project's git forge (GitHub, GitLab, Bitbucket) settings
+//!
+//! PURPOSE:
+//! - Read and save which forge a project uses and its API URL override
+//! - Report the forge and repository the integration would use right now
+//!
+//! DEPENDENCIES:
+//! - tauri - Command macro and State
+//! - db::AppState - Database connection (settings)
+//! - core::git_forge - Config storage, remote parsing, provider detection
+//! - core::events - Activity event when the settings change
+//! - models::git_forge - ForgeConfig, DetectedForge
+//!
+//! EXPORTS:
+//! - get_forge_config - A project's forge settings (defaults when unset)
+//! - save_forge_config - Replace a project's forge settings
+//! - detect_forge - Provider and repository resolved from settings and the origin remote
+//!
+//! PATTERNS:
+//! - Settings live under "git_forge.<project_id>" as JSON ForgeConfig
+//! - The provider set here wins over detection from the remote host
+//!
+//! CLAUDE NOTES:
+//! - detect_forge does not need a token; sync_ci_enforcement does (per-provider setting)
+
+use tauri::State;
+
+use crate::core::events::{self, AppEvent};
+use crate::core::git_forge;
+use crate::db::AppState;
+use crate::models::activity::ActivityType;
+use crate::models::git_forge::{DetectedForge, ForgeConfig};
+
+/// Get the forge settings for a project.
+#[tauri::command]
+pub async fn get_forge_config(project_id: String, state: State<'_, AppState>) -> Result<ForgeConfig, String> {
+    let db = state.db.lock().map_err(|e| format!("DB lock error: {}", e))?;
+    Ok(git_forge::load_config(&db, &project_id))
+}
+
+/// Save the forge settings for a project. Returns the stored settings.
+#[tauri::command]
+pub async fn save_forge_config(
+    project_id: String,
+    config: ForgeConfig,
+    state: State<'_, AppState>,
+) -> Result<ForgeConfig, String> {
+    let config = ForgeConfig {
+        api_url: config.api_url.map(|u| u.trim().trim_end_matches('/').to_string()).filter(|u| !u.is_empty()),
+        ..config
+    };
+    if let Some(url) = &config.api_url {
+        if !url.starts_with("https://") && !url.starts_with("http://") {
+            return Err(format!("API URL must start with https:// or http://: {}", url));
+        }
+    }
+    let json = serde_json::to_string(&config).map_err(|e| format!("Failed to serialize forge settings: {}", e))?;
+
+    let db = state.db.lock().map_err(|e| format!("DB lock error: {}", e))?;
+    db.execute(
+        "INSERT OR REPLACE INTO settings (key, value) VALUES (?1, ?2)",
+        rusqlite::params![format!("{}{}", git_forge::FORGE_SETTING_PREFIX, project_id), json],
+    )
+    .map_err(|e| format!("Failed to save forge settings: {}", e))?;
+    events::publish(&db, AppEvent::activity(&project_id, ActivityType::Settings, "Updated git forge settings"));
+
+    Ok(config)
+}
+
+/// Resolve a project's forge and repository from its settings and origin remote.
+#[tauri::command]
+pub async fn detect_forge(project_id: String, state: State<'_, AppState>) -> Result<DetectedForge, String> {
+    let (config, project_path) = {
+        let db = state.db.lock().map_err(|e| format!("DB lock error: {}", e))?;
+        let project_path: String = db
+            .query_row("SELECT path FROM projects WHERE id = ?1", [&project_id], |row| row.get(0))
+            .map_err(|e| format!("Project not found: {}", e))?;
+        (git_forge::load_config(&db, &project_id), project_path)
+    };
+
+    let url = git_forge::origin_url(&project_path)?;
+    let (host, repository) =
+        git_forge::parse_remote(&url).ok_or_else(|| format!("Unrecognized origin remote: {}", url))?;
+    Ok(DetectedForge {
+        provider: config.provider.or_else(|| git_forge::detect_provider(&host)),
+        host,
+        repository,
+        configured: config.provider.is_some(),
+    })
+}
@@ -0,0 +1,66 @@
+//! @module commands/git_policy
+//! @description Tauri IPC commands for per-project git permissions
+//!
+//! PURPOSE:
+//! - Read and save a project's git policy (branch / commit / push: yes, no, ask)
+//! - Answer a pending "git-permission-request" confirmation
+//!
+//! DEPENDENCIES:
+//! - tauri - Command macro and State
+//! - db::AppState - Database connection (settings)
+//! - core::git_policy - Policy storage and pending confirmations
+//! - core::events - Activity event when a policy changes
+//! - models::git_policy - GitPolicy type
+//!
+//! EXPORTS:
+//! - get_git_policy - Git policy for a project (defaults when unset)
+//! - save_git_policy - Replace a project's git policy
+//! - answer_git_permission - Allow or deny a pending git operation
+//!
+//! PATTERNS:
+//! - Policies live in settings under "git_policy.<project_id>" as JSON GitPolicy
+//! - RALPH loops check the policy before creating branches and committing
+//!
+//! CLAUDE NOTES:
+//! - answer_git_permission fails if the request already timed out or was answered
+
+use tauri::State;
+
+use crate::core::events::{self, AppEvent};
+use crate::core::git_policy;
+use crate::db::AppState;
+use crate::models::activity::ActivityType;
+use crate::models::git_policy::GitPolicy;
+
+/// Get the git policy for a project.
+#[tauri::command]
+pub async fn get_git_policy(project_id: String, state: State<'_, AppState>) -> Result<GitPolicy, String> {
+    let db = state.db.lock().map_err(|e| format!("DB lock error: {}", e))?;
+    Ok(git_policy::load_policy(&db, &project_id))
+}
+
+/// Save the git policy for a project. Returns the stored policy.
+#[tauri::command]
+pub async fn save_git_policy(
+    project_id: String,
+    policy: GitPolicy,
+    state: State<'_, AppState>,
+) -> Result<GitPolicy, String> {
+    let json = serde_json::to_string(&policy).map_err(|e| format!("Failed to serialize git policy: {}", e))?;
+
+    let db = state.db.lock().map_err(|e| format!("DB lock error: {}", e))?;
+    db.execute(
+        "INSERT OR REPLACE INTO settings (key, value) VALUES (?1, ?2)",
+        rusqlite::params![format!("{}{}", git_policy::POLICY_SETTING_PREFIX, project_id), json],
+    )
+    .map_err(|e| format!("Failed to save git policy: {}", e))?;
+    events::publish(&db, AppEvent::activity(&project_id, ActivityType::Settings, "Updated git policy"));
+
+    Ok(policy)
+}
+
+/// Allow or deny a git operation waiting on a "git-permission-request".
+#[tauri::command]
+pub async fn answer_git_permission(request_id: String, allow: bool) -> Result<(), String> {
+    git_policy::answer(&request_id, allow)
+}
@@ -0,0 +1,61 @@
+//! @module commands/health_alerts
+//! @description Tauri IPC commands for per-project health alert thresholds
+//!
+//! PURPOSE:
+//! - Read and save a project's alert thresholds
+//! - List the project's tracked alert state (active and recovered alerts)
+//!
+//! DEPENDENCIES:
+//! - tauri - Command macro and State
+//! - db::AppState - Database connection
+//! - core::health_alerts - Validation, storage, and alert state
+//! - models::health_alert - HealthAlertThresholds, HealthAlert types
+//!
+//! EXPORTS:
+//! - get_health_alert_thresholds - A project's thresholds (all unset when none saved)
+//! - save_health_alert_thresholds - Validate and save thresholds, resetting alert state
+//! - list_health_alerts - Tracked alert state, most recently changed first
+//!
+//! PATTERNS:
+//! - Alerts are evaluated by compute_health_score (commands::claude_md), not here
+//!
+//! CLAUDE NOTES:
+//! - Saving all-unset thresholds turns alerts off for the project
+
+use tauri::State;
+
+use crate::core::events::{self, AppEvent};
+use crate::core::health_alerts;
+use crate::db::AppState;
+use crate::models::activity::ActivityType;
+use crate::models::health_alert::{HealthAlert, HealthAlertThresholds};
+
+#[tauri::command]
+pub async fn get_health_alert_thresholds(
+    project_id: String,
+    state: State<'_, AppState>,
+) -> Result<HealthAlertThresholds, String> {
+    let db = state.db.lock().map_err(|e| format!("DB lock error: {}", e))?;
+    Ok(health_alerts::load_thresholds(&db, &project_id))
+}
+
+/// Save a project's thresholds. Alert state is reset, so a threshold that is already
+/// breached fires once on the next health check.
+#[tauri::command]
+pub async fn save_health_alert_thresholds(
+    project_id: String,
+    thresholds: HealthAlertThresholds,
+    state: State<'_, AppState>,
+) -> Result<HealthAlertThresholds, String> {
+    let thresholds = health_alerts::validate_thresholds(thresholds)?;
+    let db = state.db.lock().map_err(|e| format!("DB lock error: {}", e))?;
+    health_alerts::store_thresholds(&db, &project_id, &thresholds)?;
+    events::publish(&db, AppEvent::activity(&project_id, ActivityType::Settings, "Updated health alert thresholds"));
+    Ok(thresholds)
+}
+
+#[tauri::command]
+pub async fn list_health_alerts(project_id: String, state: State<'_, AppState>) -> Result<Vec<HealthAlert>, String> {
+    let db = state.db.lock().map_err(|e| format!("DB lock error: {}", e))?;
+    health_alerts::list_alerts(&db, &project_id)
+}
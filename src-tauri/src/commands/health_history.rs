@@ -0,0 +1,311 @@
+//! @module commands/health_history
+//! @description Tauri IPC commands for health snapshots per git commit and regression detection
+//!
+//! PURPOSE:
+//! - Record a health snapshot whenever a project's git HEAD moves
+//! - List recorded snapshots
+//! - Find commit ranges after which doc coverage or freshness dropped sharply
+//! - Store per-project doc goals and record whether they were met at each commit
+//!
+//! DEPENDENCIES:
+//! - tauri - Command macro and State
+//! - db::AppState - Database connection (health_snapshots, projects)
+//! - core::health_history - Doc metrics, git helpers, regression detection
+//! - core::freshness - Per-file freshness scan for snapshot metrics
+//! - core::doc_goals - Doc goal validation
+//! - models::health_history - HealthSnapshot, HealthRegression, DocGoals types
+//!
+//! EXPORTS:
+//! - record_snapshot_if_head_moved - Snapshot health when HEAD differs from the latest snapshot
+//! - list_health_snapshots - Snapshots for a project, newest first
+//! - find_health_regressions - Regressions with commit range and files involved, newest first
+//! - get_doc_goals - Doc goals for a project (defaults to none set)
+//! - save_doc_goals - Validate and save doc goals for a project
+//! - load_doc_goals - (internal) Read a project's doc goals from settings
+//! - store_doc_goals - (internal) Write a project's doc goals (shared with commands::policy)
+//!
+//! PATTERNS:
+//! - get_health_score calls record_snapshot_if_head_moved, so snapshots accrue as the
+//!   dashboard polls; at most one snapshot is written per commit
+//! - The DB lock is released while the freshness scan and git commands run
+//!
+//! CLAUDE NOTES:
+//! - Snapshots measure the working tree at the time HEAD was first seen, so uncommitted
+//!   edits are included; history before the first snapshot is not reconstructed
+//! - Projects that are not git repositories never get snapshots
+//! - Doc goals live in settings under "doc_goals.<project_id>" as JSON DocGoals
+//! - goals_met comes from the HealthScore the snapshot was taken for (None without goals)
+
+use std::path::Path;
+
+use chrono::Utc;
+use rusqlite::{Connection, OptionalExtension};
+use tauri::State;
+
+use crate::core::{doc_goals, freshness, health_history};
+use crate::db::AppState;
+use crate::models::health_history::{DocGoals, HealthRegression, HealthSnapshot};
+use crate::models::project::HealthScore;
+
+/// Settings key prefix for per-project doc goals.
+const DOC_GOALS_SETTING_PREFIX: &str = "doc_goals.";
+
+/// Record a snapshot for the project if its git HEAD is not the commit of
+/// the latest snapshot. Returns the new snapshot, or None when nothing changed.
+pub fn record_snapshot_if_head_moved(
+    state: &AppState,
+    project_id: &str,
+    project_path: &str,
+    score: &HealthScore,
+) -> Result<Option<HealthSnapshot>, String> {
+    let Some(head) = health_history::git_head(Path::new(project_path)) else {
+        return Ok(None);
+    };
+
+    {
+        let db = state.db.lock().map_err(|e| format!("DB lock error: {}", e))?;
+        if latest_commit(&db, project_id)?.as_deref() == Some(head.as_str()) {
+            return Ok(None);
+        }
+    }
+
+    let modules = freshness::check_project_freshness(project_path)?;
+    let metrics = health_history::doc_metrics(&modules);
+    let snapshot = HealthSnapshot {
+        id: uuid::Uuid::new_v4().to_string(),
+        project_id: project_id.to_string(),
+        commit_sha: head,
+        health_score: score.total,
+        doc_coverage: metrics.doc_coverage,
+        avg_freshness: metrics.avg_freshness,
+        total_files: metrics.total_files,
+        documented_files: metrics.documented_files,
+        degraded_files: metrics.degraded_files,
+        goals_met: score.goals.as_ref().map(|g| g.met),
+        created_at: Utc::now().to_rfc3339(),
+    };
+
+    let db = state.db.lock().map_err(|e| format!("DB lock error: {}", e))?;
+    insert_snapshot(&db, &snapshot)?;
+    Ok(Some(snapshot))
+}
+
+/// List health snapshots for a project, newest first.
+#[tauri::command]
+pub async fn list_health_snapshots(
+    project_id: String,
+    limit: Option<u32>,
+    state: State<'_, AppState>,
+) -> Result<Vec<HealthSnapshot>, String> {
+    let db = state.db.lock().map_err(|e| format!("DB lock error: {}", e))?;
+    let mut snapshots = load_snapshots(&db, &project_id)?;
+    snapshots.reverse();
+    if let Some(limit) = limit {
+        snapshots.truncate(limit as usize);
+    }
+    Ok(snapshots)
+}
+
+/// Find commits after which doc coverage or average freshness dropped by at
+/// least min_drop percentage points (default 10). Newest first.
+#[tauri::command]
+pub async fn find_health_regressions(
+    project_id: String,
+    min_drop: Option<f64>,
+    state: State<'_, AppState>,
+) -> Result<Vec<HealthRegression>, String> {
+    let (project_path, snapshots) = {
+        let db = state.db.lock().map_err(|e| format!("DB lock error: {}", e))?;
+        let project_path: String = db
+            .query_row("SELECT path FROM projects WHERE id = ?1", [&project_id], |row| row.get(0))
+            .map_err(|e| format!("Project not found: {}", e))?;
+        (project_path, load_snapshots(&db, &project_id)?)
+    };
+
+    let path = Path::new(&project_path);
+    let min_drop = min_drop.unwrap_or(health_history::DEFAULT_MIN_DROP).max(0.0);
+    let mut regressions = health_history::detect_regressions(&snapshots, min_drop, |from, to| {
+        (
+            health_history::changed_files(path, from, to),
+            health_history::commits_between(path, from, to),
+        )
+    });
+    regressions.reverse();
+    Ok(regressions)
+}
+
+/// Get the doc goals for a project. Unset goals come back as None.
+#[tauri::command]
+pub async fn get_doc_goals(project_id: String, state: State<'_, AppState>) -> Result<DocGoals, String> {
+    let db = state.db.lock().map_err(|e| format!("DB lock error: {}", e))?;
+    Ok(load_doc_goals(&db, &project_id))
+}
+
+/// Validate and save the doc goals for a project. Returns the stored goals.
+#[tauri::command]
+pub async fn save_doc_goals(
+    project_id: String,
+    goals: DocGoals,
+    state: State<'_, AppState>,
+) -> Result<DocGoals, String> {
+    let goals = doc_goals::validate_goals(goals)?;
+    let db = state.db.lock().map_err(|e| format!("DB lock error: {}", e))?;
+    store_doc_goals(&db, &project_id, &goals)?;
+    Ok(goals)
+}
+
+/// Write a project's (already validated) doc goals to settings.
+pub(crate) fn store_doc_goals(db: &Connection, project_id: &str, goals: &DocGoals) -> Result<(), String> {
+    let json = serde_json::to_string(goals).map_err(|e| format!("Failed to serialize doc goals: {}", e))?;
+    db.execute(
+        "INSERT OR REPLACE INTO settings (key, value) VALUES (?1, ?2)",
+        rusqlite::params![format!("{}{}", DOC_GOALS_SETTING_PREFIX, project_id), json],
+    )
+    .map_err(|e| format!("Failed to save doc goals: {}", e))?;
+    Ok(())
+}
+
+/// Doc goals for a project from settings; no goals when unset or unreadable.
+pub(crate) fn load_doc_goals(db: &Connection, project_id: &str) -> DocGoals {
+    db.query_row(
+        "SELECT value FROM settings WHERE key = ?1",
+        [format!("{}{}", DOC_GOALS_SETTING_PREFIX, project_id)],
+        |row| row.get::<_, String>(0),
+    )
+    .ok()
+    .and_then(|json| serde_json::from_str(&json).ok())
+    .unwrap_or_default()
+}
+
+fn latest_commit(db: &Connection, project_id: &str) -> Result<Option<String>, String> {
+    db.query_row(
+        "SELECT commit_sha FROM health_snapshots WHERE project_id = ?1 ORDER BY created_at DESC LIMIT 1",
+        [project_id],
+        |row| row.get(0),
+    )
+    .optional()
+    .map_err(|e| format!("Failed to query health snapshots: {}", e))
+}
+
+fn insert_snapshot(db: &Connection, snapshot: &HealthSnapshot) -> Result<(), String> {
+    let degraded = serde_json::to_string(&snapshot.degraded_files).unwrap_or_else(|_| "[]".to_string());
+    db.execute(
+        "INSERT INTO health_snapshots (id, project_id, commit_sha, health_score, doc_coverage, avg_freshness,
+                                       total_files, documented_files, degraded_files, goals_met, created_at)
+         VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7, ?8, ?9, ?10, ?11)",
+        rusqlite::params![
+            snapshot.id,
+            snapshot.project_id,
+            snapshot.commit_sha,
+            snapshot.health_score,
+            snapshot.doc_coverage,
+            snapshot.avg_freshness,
+            snapshot.total_files,
+            snapshot.documented_files,
+            degraded,
+            snapshot.goals_met,
+            snapshot.created_at,
+        ],
+    )
+    .map_err(|e| format!("Failed to save health snapshot: {}", e))?;
+    Ok(())
+}
+
+/// All snapshots for a project, oldest first.
+fn load_snapshots(db: &Connection, project_id: &str) -> Result<Vec<HealthSnapshot>, String> {
+    let mut stmt = db
+        .prepare(
+            "SELECT id, project_id, commit_sha, health_score, doc_coverage, avg_freshness,
+                    total_files, documented_files, degraded_files, goals_met, created_at
+             FROM health_snapshots WHERE project_id = ?1 ORDER BY created_at ASC",
+        )
+        .map_err(|e| format!("Failed to query health snapshots: {}", e))?;
+
+    let snapshots = stmt
+        .query_map([project_id], |row| {
+            let degraded: String = row.get(8)?;
+            Ok(HealthSnapshot {
+                id: row.get(0)?,
+                project_id: row.get(1)?,
+                commit_sha: row.get(2)?,
+                health_score: row.get(3)?,
+                doc_coverage: row.get(4)?,
+                avg_freshness: row.get(5)?,
+                total_files: row.get(6)?,
+                documented_files: row.get(7)?,
+                degraded_files: serde_json::from_str(&degraded).unwrap_or_default(),
+                goals_met: row.get(9)?,
+                created_at: row.get(10)?,
+            })
+        })
+        .map_err(|e| format!("Failed to read health snapshots: {}", e))?
+        .filter_map(|r| r.ok())
+        .collect();
+
+    Ok(snapshots)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_support::with_project;
+
+    fn setup() -> Connection {
+        let conn = with_project();
+        crate::db::schema::migrate_add_snapshot_goals(&conn).unwrap();
+        conn
+    }
+
+    #[test]
+    fn test_snapshot_round_trip() {
+        let db = setup();
+        assert_eq!(latest_commit(&db, "p1").unwrap(), None);
+
+        for (i, commit) in ["abc", "def"].iter().enumerate() {
+            insert_snapshot(
+                &db,
+                &HealthSnapshot {
+                    id: format!("s{}", i),
+                    project_id: "p1".to_string(),
+                    commit_sha: commit.to_string(),
+                    health_score: 60,
+                    doc_coverage: 80.0,
+                    avg_freshness: 90.0,
+                    total_files: 5,
+                    documented_files: 4,
+                    degraded_files: vec!["src/a.rs".to_string()],
+                    goals_met: if i == 0 { None } else { Some(false) },
+                    created_at: format!("2025-01-0{}T00:00:00Z", i + 1),
+                },
+            )
+            .unwrap();
+        }
+
+        assert_eq!(latest_commit(&db, "p1").unwrap().as_deref(), Some("def"));
+        let snapshots = load_snapshots(&db, "p1").unwrap();
+        assert_eq!(snapshots.len(), 2);
+        assert_eq!(snapshots[0].commit_sha, "abc");
+        assert_eq!(snapshots[1].degraded_files, vec!["src/a.rs"]);
+        assert_eq!(snapshots[0].goals_met, None);
+        assert_eq!(snapshots[1].goals_met, Some(false));
+    }
+
+    #[test]
+    fn test_doc_goals_round_trip() {
+        let db = setup();
+        assert!(load_doc_goals(&db, "p1").is_empty());
+
+        let goals = DocGoals {
+            min_doc_coverage: Some(90.0),
+            max_stale_days: Some(30),
+            ..DocGoals::default()
+        };
+        db.execute(
+            "INSERT INTO settings (key, value) VALUES ('doc_goals.p1', ?1)",
+            [serde_json::to_string(&goals).unwrap()],
+        )
+        .unwrap();
+        assert_eq!(load_doc_goals(&db, "p1"), goals);
+        assert!(load_doc_goals(&db, "p2").is_empty());
+    }
+}
@@ -0,0 +1,673 @@
+//! @module commands/imports
+//! @description Tauri IPC commands for importing shared knowledge and resolving import conflicts
+//!
+//! PURPOSE:
+//! - Import a JSON bundle of skills, agents, and team templates
+//! - Merge incoming rows (bundle or shared backend) without silent last-write-wins
+//! - Stage conflicting rows and apply the user's decision for each
+//!
+//! DEPENDENCIES:
+//! - tauri - Command macro and State
+//! - db::AppState - Database connection
+//! - db::shared - Shared table layout and local row helpers
+//! - core::merge - Classification of incoming rows
+//! - commands::versions - Snapshot skills/agents before and after overwrites
+//! - models::import - ImportConflict, ConflictDecision, ImportSummary types
+//!
+//! EXPORTS:
+//! - import_knowledge_bundle - Import a bundle and report what was merged
+//! - list_import_conflicts - List pending conflicts
+//! - resolve_import_conflicts - Apply keep_local / take_incoming / keep_both decisions
+//! - merge_incoming - (internal) Merge rows into one table; used by sync_shared_db and run_sync
+//! - stage_setting_conflict / pending_setting_keys - (internal) Setting conflicts raised by run_sync
+//! - row_from_json / row_to_json - (internal) Convert shared rows to and from JSON objects
+//!
+//! PATTERNS:
+//! - new and newer rows are applied, identical and outdated rows are skipped, conflicting rows are staged
+//! - A pending conflict keeps blocking its row on later syncs until it is resolved
+//! - keep_local is remembered per incoming version so the same row is not raised again
+//! - keep_local / keep_both bump the local updated_at so the local copy wins the next sync
+//!
+//! CLAUDE NOTES:
+//! - Bundle format: {"skills": [...], "agents": [...], "teamTemplates": [...]}; each item uses
+//!   the shared column names (snake_case or camelCase); missing id/timestamps are generated
+//! - Learnings have no names and are not merged here; they sync by id in sync_shared_db
+//! - keep_both renames the incoming copy with an " (imported)" suffix
+//! - Setting conflicts use entity_type "setting" with the key as both ids; keep_both is rejected
+
+use std::collections::{HashMap, HashSet};
+
+use chrono::Utc;
+use rusqlite::types::Value;
+use rusqlite::{Connection, OptionalExtension};
+use serde_json::{Map, Value as JsonValue};
+use tauri::State;
+use uuid::Uuid;
+
+use crate::commands::versions;
+use crate::core::merge::{self, MergeClass};
+use crate::db::shared::{self, ColumnKind, SharedTable};
+use crate::db::AppState;
+use crate::models::import::{ConflictDecision, ConflictResolution, ImportConflict, ImportSummary, ResolveSummary};
+
+/// Entity type for settings changed on two machines (raised by run_sync).
+const SETTING_ENTITY: &str = "setting";
+
+/// Entity types that can be imported, with their shared table and bundle key.
+const IMPORT_ENTITIES: &[(&str, &str, &str)] = &[
+    ("skill", "skills", "skills"),
+    ("agent", "agents", "agents"),
+    ("team_template", "team_templates", "teamTemplates"),
+];
+
+/// Text columns that are nullable locally.
+const NULLABLE_COLUMNS: &[&str] = &["workflow", "tools", "trigger_patterns", "topic"];
+
+/// Text columns holding JSON arrays (default "[]"); other text defaults to "".
+const JSON_ARRAY_COLUMNS: &[&str] = &["tags", "teammates", "tasks", "hooks"];
+
+fn table_for_entity(entity_type: &str) -> Result<&'static SharedTable, String> {
+    IMPORT_ENTITIES
+        .iter()
+        .find(|(e, _, _)| *e == entity_type)
+        .and_then(|(_, table, _)| shared::shared_table(table))
+        .ok_or_else(|| format!("Unknown import entity type '{}'", entity_type))
+}
+
+fn entity_for_table(table: &SharedTable) -> &'static str {
+    IMPORT_ENTITIES
+        .iter()
+        .find(|(_, t, _)| *t == table.name)
+        .map(|(e, _, _)| *e)
+        .unwrap_or("unknown")
+}
+
+/// Entity type used by version history, if this table is versioned.
+fn versioned_entity(table: &SharedTable) -> Option<&'static str> {
+    match table.name {
+        "skills" => Some("skill"),
+        "agents" => Some("agent"),
+        _ => None,
+    }
+}
+
+/// Import a bundle of skills, agents, and team templates.
+#[tauri::command]
+pub async fn import_knowledge_bundle(
+    bundle_json: String,
+    state: State<'_, AppState>,
+) -> Result<Vec<ImportSummary>, String> {
+    let bundle: JsonValue = serde_json::from_str(&bundle_json).map_err(|e| format!("Invalid bundle JSON: {}", e))?;
+    let bundle = bundle.as_object().ok_or("Bundle must be a JSON object")?;
+
+    let mut incoming = Vec::new();
+    for (entity_type, table_name, key) in IMPORT_ENTITIES {
+        let table = shared::shared_table(table_name).ok_or("Missing shared table")?;
+        let items = bundle.get(*key).or_else(|| bundle.get(*table_name));
+        let rows = match items {
+            None => Vec::new(),
+            Some(JsonValue::Array(items)) => items
+                .iter()
+                .map(|item| {
+                    item.as_object()
+                        .ok_or_else(|| format!("Each {} in the bundle must be an object", entity_type))
+                        .and_then(|obj| row_from_json(table, obj))
+                })
+                .collect::<Result<Vec<_>, _>>()?,
+            Some(_) => return Err(format!("Bundle field '{}' must be an array", key)),
+        };
+        incoming.push((table, rows));
+    }
+
+    let db = state.db.lock().map_err(|e| format!("DB lock error: {}", e))?;
+    incoming
+        .into_iter()
+        .map(|(table, rows)| merge_incoming(&db, table, rows, None, "bundle").map(|(summary, _)| summary))
+        .collect()
+}
+
+/// List conflicts waiting for a decision, oldest first.
+#[tauri::command]
+pub async fn list_import_conflicts(state: State<'_, AppState>) -> Result<Vec<ImportConflict>, String> {
+    let db = state.db.lock().map_err(|e| format!("DB lock error: {}", e))?;
+    pending_conflicts(&db)
+}
+
+/// Apply a decision to each listed conflict. Unknown or already resolved
+/// conflict ids are rejected before anything is changed.
+#[tauri::command]
+pub async fn resolve_import_conflicts(
+    decisions: Vec<ConflictDecision>,
+    state: State<'_, AppState>,
+) -> Result<ResolveSummary, String> {
+    let db = state.db.lock().map_err(|e| format!("DB lock error: {}", e))?;
+    apply_decisions(&db, &decisions)
+}
+
+/// Merge incoming rows into one shared table. Applies new and newer rows,
+/// stages conflicts, and returns the local ids that must not be pushed.
+pub fn merge_incoming(
+    db: &Connection,
+    table: &SharedTable,
+    incoming: Vec<Vec<Value>>,
+    last_synced_at: Option<&str>,
+    source: &str,
+) -> Result<(ImportSummary, HashSet<String>), String> {
+    let entity_type = entity_for_table(table);
+    let mut summary = ImportSummary {
+        entity_type: entity_type.to_string(),
+        source: source.to_string(),
+        ..Default::default()
+    };
+    let mut blocked = HashSet::new();
+
+    let local = shared::read_local_rows(db, table)?;
+    let recorded = recorded_conflicts(db, entity_type)?;
+    let updated_idx = table.updated_at_index();
+
+    for candidate in merge::classify_incoming(table, &local, incoming, last_synced_at) {
+        let incoming_id = shared::text_at(&candidate.incoming, 0).to_string();
+        let local_id = candidate.local.as_ref().map(|l| shared::text_at(l, 0).to_string());
+        let prior = local_id
+            .as_ref()
+            .and_then(|lid| recorded.get(&(lid.clone(), incoming_id.clone())));
+
+        // A pending conflict blocks the row until resolved; a keep_local decision
+        // suppresses the same incoming version from being raised again.
+        let class = match (candidate.class, prior) {
+            (MergeClass::Identical, Some(_)) => {
+                clear_pending(db, entity_type, local_id.as_deref().unwrap_or_default(), &incoming_id)?;
+                MergeClass::Identical
+            }
+            (MergeClass::Identical, None) | (MergeClass::New, _) => candidate.class,
+            (_, Some((status, _))) if status == "pending" => MergeClass::Conflicting,
+            (MergeClass::Conflicting, Some((_, kept_version)))
+                if kept_version == shared::text_at(&candidate.incoming, updated_idx) =>
+            {
+                MergeClass::Outdated
+            }
+            (class, _) => class,
+        };
+
+        match class {
+            MergeClass::New => {
+                shared::replace_local_row(db, table, &candidate.incoming)?;
+                summary.added += 1;
+            }
+            MergeClass::Newer => {
+                overwrite_local(db, table, &incoming_id, &candidate.incoming)?;
+                summary.updated += 1;
+            }
+            MergeClass::Identical => summary.identical += 1,
+            MergeClass::Outdated => summary.skipped += 1,
+            MergeClass::Conflicting => {
+                let local = candidate.local.as_ref().ok_or("Conflict without a local row")?;
+                stage_conflict(db, table, local, &candidate.incoming, source)?;
+                if let Some(lid) = local_id {
+                    blocked.insert(lid);
+                }
+                summary.conflicts += 1;
+            }
+        }
+    }
+
+    Ok((summary, blocked))
+}
+
+fn apply_decisions(db: &Connection, decisions: &[ConflictDecision]) -> Result<ResolveSummary, String> {
+    let mut conflicts = Vec::new();
+    for decision in decisions {
+        let conflict: Option<(String, String, String, String)> = db
+            .query_row(
+                "SELECT entity_type, local_id, incoming_id, incoming_row FROM import_conflicts WHERE id = ?1 AND status = 'pending'",
+                [&decision.conflict_id],
+                |row| Ok((row.get(0)?, row.get(1)?, row.get(2)?, row.get(3)?)),
+            )
+            .optional()
+            .map_err(|e| format!("Failed to read conflict: {}", e))?;
+        let conflict = conflict.ok_or_else(|| format!("Conflict '{}' not found or already resolved", decision.conflict_id))?;
+        if conflict.0 == SETTING_ENTITY && decision.resolution == ConflictResolution::KeepBoth {
+            return Err(format!("Setting '{}' can only keep one value", conflict.1));
+        }
+        conflicts.push((decision, conflict));
+    }
+
+    let mut summary = ResolveSummary::default();
+    let now = Utc::now().to_rfc3339();
+    for (decision, (entity_type, local_id, incoming_id, incoming_json)) in conflicts {
+        if entity_type == SETTING_ENTITY {
+            apply_setting_decision(db, decision.resolution, &local_id, &incoming_json)?;
+            match decision.resolution {
+                ConflictResolution::TakeIncoming => summary.took_incoming += 1,
+                _ => summary.kept_local += 1,
+            }
+            mark_resolved(db, decision, &now)?;
+            continue;
+        }
+
+        let table = table_for_entity(&entity_type)?;
+        let incoming_obj: Map<String, JsonValue> =
+            serde_json::from_str(&incoming_json).map_err(|e| format!("Corrupt staged conflict: {}", e))?;
+        let mut incoming = row_from_json(table, &incoming_obj)?;
+        let local_exists = local_project(db, table, &local_id)?.is_some();
+
+        match decision.resolution {
+            ConflictResolution::KeepLocal => {
+                if local_exists {
+                    touch_local(db, table, &local_id, &now)?;
+                }
+                summary.kept_local += 1;
+            }
+            ConflictResolution::TakeIncoming => {
+                if local_id == incoming_id || !local_exists {
+                    overwrite_local(db, table, &incoming_id, &incoming)?;
+                } else {
+                    let project_id = local_project(db, table, &local_id)?.flatten();
+                    db.execute(&format!("DELETE FROM {} WHERE id = ?1", table.name), [&local_id])
+                        .map_err(|e| format!("Failed to replace local {}: {}", entity_type, e))?;
+                    if let Some(entity) = versioned_entity(table) {
+                        versions::delete_versions(db, entity, &local_id)?;
+                    }
+                    overwrite_local(db, table, &incoming_id, &incoming)?;
+                    db.execute(
+                        &format!("UPDATE {} SET project_id = ?1 WHERE id = ?2", table.name),
+                        rusqlite::params![project_id, incoming_id],
+                    )
+                    .map_err(|e| format!("Failed to keep project for {}: {}", entity_type, e))?;
+                }
+                summary.took_incoming += 1;
+            }
+            ConflictResolution::KeepBoth => {
+                if let Some(i) = table.column_index("name") {
+                    let name = shared::text_at(&incoming, i).to_string();
+                    incoming[i] = Value::Text(format!("{} (imported)", name));
+                }
+                if local_id == incoming_id {
+                    incoming[0] = Value::Text(Uuid::new_v4().to_string());
+                    touch_local(db, table, &local_id, &now)?;
+                }
+                let updated_idx = table.updated_at_index();
+                incoming[updated_idx] = Value::Text(now.clone());
+                shared::replace_local_row(db, table, &incoming)?;
+                summary.kept_both += 1;
+            }
+        }
+
+        mark_resolved(db, decision, &now)?;
+    }
+
+    summary.remaining = pending_conflicts(db)?.len() as u32;
+    Ok(summary)
+}
+
+fn mark_resolved(db: &Connection, decision: &ConflictDecision, now: &str) -> Result<(), String> {
+    db.execute(
+        "UPDATE import_conflicts SET status = 'resolved', resolution = ?1, resolved_at = ?2 WHERE id = ?3",
+        rusqlite::params![decision.resolution.as_str(), now, decision.conflict_id],
+    )
+    .map_err(|e| format!("Failed to mark conflict resolved: {}", e))?;
+    Ok(())
+}
+
+/// take_incoming writes the staged value (a null value deletes the setting); keep_local
+/// changes nothing because run_sync pushes the local value once the conflict is gone.
+fn apply_setting_decision(
+    db: &Connection,
+    resolution: ConflictResolution,
+    key: &str,
+    incoming_json: &str,
+) -> Result<(), String> {
+    if resolution != ConflictResolution::TakeIncoming {
+        return Ok(());
+    }
+    let incoming: JsonValue = serde_json::from_str(incoming_json).map_err(|e| format!("Corrupt staged conflict: {}", e))?;
+    match incoming.get("value").and_then(|v| v.as_str()) {
+        Some(value) => db.execute(
+            "INSERT OR REPLACE INTO settings (key, value) VALUES (?1, ?2)",
+            rusqlite::params![key, value],
+        ),
+        None => db.execute("DELETE FROM settings WHERE key = ?1", [key]),
+    }
+    .map_err(|e| format!("Failed to apply setting '{}': {}", key, e))?;
+    Ok(())
+}
+
+/// Stage a setting whose local and incoming values both changed since the last sync.
+pub fn stage_setting_conflict(
+    db: &Connection,
+    key: &str,
+    incoming_value: Option<&str>,
+    incoming_at: &str,
+    source: &str,
+) -> Result<(), String> {
+    let incoming_json = serde_json::json!({ "key": key, "value": incoming_value }).to_string();
+    db.execute(
+        "INSERT INTO import_conflicts (id, entity_type, local_id, incoming_id, name, incoming_row, changed_fields,
+                                       local_updated_at, incoming_updated_at, source, status, created_at)
+         VALUES (?1, ?2, ?3, ?3, ?3, ?4, '[\"value\"]', '', ?5, ?6, 'pending', ?7)
+         ON CONFLICT (entity_type, local_id, incoming_id) DO UPDATE SET
+            incoming_row = excluded.incoming_row, incoming_updated_at = excluded.incoming_updated_at,
+            source = excluded.source, status = 'pending', resolution = NULL, resolved_at = NULL",
+        rusqlite::params![
+            Uuid::new_v4().to_string(),
+            SETTING_ENTITY,
+            key,
+            incoming_json,
+            incoming_at,
+            source,
+            Utc::now().to_rfc3339(),
+        ],
+    )
+    .map_err(|e| format!("Failed to stage setting conflict: {}", e))?;
+    Ok(())
+}
+
+/// Keys of settings with a pending conflict.
+pub fn pending_setting_keys(db: &Connection) -> Result<HashSet<String>, String> {
+    Ok(recorded_conflicts(db, SETTING_ENTITY)?
+        .into_iter()
+        .filter(|(_, (status, _))| status == "pending")
+        .map(|((key, _), _)| key)
+        .collect())
+}
+
+/// Overwrite (or insert) a row, snapshotting skill/agent versions around it.
+fn overwrite_local(db: &Connection, table: &SharedTable, id: &str, row: &[Value]) -> Result<(), String> {
+    let entity = versioned_entity(table);
+    let existed = local_project(db, table, id)?.is_some();
+    if let (Some(entity), true) = (entity, existed) {
+        versions::ensure_version_baseline(db, entity, id)?;
+    }
+    shared::replace_local_row(db, table, row)?;
+    if let Some(entity) = entity {
+        versions::record_version(db, entity, id)?;
+    }
+    Ok(())
+}
+
+/// Mark the local row as edited now so it wins the next sync.
+fn touch_local(db: &Connection, table: &SharedTable, id: &str, now: &str) -> Result<(), String> {
+    db.execute(
+        &format!("UPDATE {} SET updated_at = ?1 WHERE id = ?2", table.name),
+        rusqlite::params![now, id],
+    )
+    .map_err(|e| format!("Failed to update {}: {}", table.name, e))?;
+    Ok(())
+}
+
+/// Some(project_id) if the local row exists.
+fn local_project(db: &Connection, table: &SharedTable, id: &str) -> Result<Option<Option<String>>, String> {
+    db.query_row(
+        &format!("SELECT project_id FROM {} WHERE id = ?1", table.name),
+        [id],
+        |row| row.get::<_, Option<String>>(0),
+    )
+    .optional()
+    .map_err(|e| format!("Failed to read {}: {}", table.name, e))
+}
+
+/// (local_id, incoming_id)
+type ConflictPair = (String, String);
+
+/// Previously recorded conflicts for an entity type, keyed by (local_id, incoming_id).
+/// Value is (status, incoming_updated_at) where keep_local decisions count as "kept".
+fn recorded_conflicts(
+    db: &Connection,
+    entity_type: &str,
+) -> Result<HashMap<ConflictPair, (String, String)>, String> {
+    let mut stmt = db
+        .prepare(
+            "SELECT local_id, incoming_id, status, resolution, incoming_updated_at FROM import_conflicts
+             WHERE entity_type = ?1 AND (status = 'pending' OR resolution = 'keep_local')",
+        )
+        .map_err(|e| format!("Failed to query import conflicts: {}", e))?;
+
+    let rows = stmt
+        .query_map([entity_type], |row| {
+            Ok((
+                (row.get::<_, String>(0)?, row.get::<_, String>(1)?),
+                (row.get::<_, String>(2)?, row.get::<_, String>(4)?),
+            ))
+        })
+        .map_err(|e| format!("Failed to read import conflicts: {}", e))?
+        .filter_map(|r| r.ok())
+        .collect();
+    Ok(rows)
+}
+
+fn clear_pending(db: &Connection, entity_type: &str, local_id: &str, incoming_id: &str) -> Result<(), String> {
+    db.execute(
+        "DELETE FROM import_conflicts WHERE entity_type = ?1 AND local_id = ?2 AND incoming_id = ?3 AND status = 'pending'",
+        rusqlite::params![entity_type, local_id, incoming_id],
+    )
+    .map_err(|e| format!("Failed to clear import conflict: {}", e))?;
+    Ok(())
+}
+
+/// Record (or refresh) a pending conflict for a local/incoming pair.
+fn stage_conflict(
+    db: &Connection,
+    table: &SharedTable,
+    local: &[Value],
+    incoming: &[Value],
+    source: &str,
+) -> Result<(), String> {
+    let updated_idx = table.updated_at_index();
+    let name = table
+        .column_index("name")
+        .map(|i| shared::text_at(local, i).to_string())
+        .unwrap_or_default();
+    let changed = serde_json::to_string(&merge::changed_fields(table, local, incoming)).unwrap_or_else(|_| "[]".into());
+    let incoming_json = JsonValue::Object(row_to_json(table, incoming)).to_string();
+
+    db.execute(
+        "INSERT INTO import_conflicts (id, entity_type, local_id, incoming_id, name, incoming_row, changed_fields,
+                                       local_updated_at, incoming_updated_at, source, status, created_at)
+         VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7, ?8, ?9, ?10, 'pending', ?11)
+         ON CONFLICT (entity_type, local_id, incoming_id) DO UPDATE SET
+            name = excluded.name, incoming_row = excluded.incoming_row, changed_fields = excluded.changed_fields,
+            local_updated_at = excluded.local_updated_at, incoming_updated_at = excluded.incoming_updated_at,
+            source = excluded.source, status = 'pending', resolution = NULL, resolved_at = NULL",
+        rusqlite::params![
+            Uuid::new_v4().to_string(),
+            entity_for_table(table),
+            shared::text_at(local, 0),
+            shared::text_at(incoming, 0),
+            name,
+            incoming_json,
+            changed,
+            shared::text_at(local, updated_idx),
+            shared::text_at(incoming, updated_idx),
+            source,
+            Utc::now().to_rfc3339(),
+        ],
+    )
+    .map_err(|e| format!("Failed to stage import conflict: {}", e))?;
+    Ok(())
+}
+
+fn pending_conflicts(db: &Connection) -> Result<Vec<ImportConflict>, String> {
+    let mut stmt = db
+        .prepare(
+            "SELECT id, entity_type, name, local_id, incoming_id, changed_fields, local_updated_at,
+                    incoming_updated_at, source, created_at
+             FROM import_conflicts WHERE status = 'pending' ORDER BY created_at ASC",
+        )
+        .map_err(|e| format!("Failed to query import conflicts: {}", e))?;
+
+    let conflicts = stmt
+        .query_map([], |row| {
+            let changed: String = row.get(5)?;
+            Ok(ImportConflict {
+                id: row.get(0)?,
+                entity_type: row.get(1)?,
+                name: row.get(2)?,
+                local_id: row.get(3)?,
+                incoming_id: row.get(4)?,
+                changed_fields: serde_json::from_str(&changed).unwrap_or_default(),
+                local_updated_at: row.get(6)?,
+                incoming_updated_at: row.get(7)?,
+                source: row.get(8)?,
+                created_at: row.get(9)?,
+            })
+        })
+        .map_err(|e| format!("Failed to read import conflicts: {}", e))?
+        .filter_map(|r| r.ok())
+        .collect();
+    Ok(conflicts)
+}
+
+fn to_snake_case(key: &str) -> String {
+    let mut out = String::new();
+    for c in key.chars() {
+        if c.is_ascii_uppercase() {
+            out.push('_');
+            out.push(c.to_ascii_lowercase());
+        } else {
+            out.push(c);
+        }
+    }
+    out
+}
+
+/// Build a shared-column row from a JSON object (snake_case or camelCase keys).
+pub fn row_from_json(table: &SharedTable, obj: &Map<String, JsonValue>) -> Result<Vec<Value>, String> {
+    let fields: HashMap<String, &JsonValue> = obj.iter().map(|(k, v)| (to_snake_case(k), v)).collect();
+    let now = Utc::now().to_rfc3339();
+
+    table
+        .columns
+        .iter()
+        .map(|(column, kind)| {
+            let value = fields.get(*column).copied().filter(|v| !v.is_null());
+            Ok(match (*column, kind, value) {
+                ("name", _, None) => return Err(format!("Every {} needs a name", entity_for_table(table))),
+                ("id", _, None) => Value::Text(Uuid::new_v4().to_string()),
+                ("created_at" | "updated_at", _, None) => Value::Text(now.clone()),
+                (_, ColumnKind::Integer, v) => Value::Integer(v.and_then(|v| v.as_i64()).unwrap_or(0)),
+                (c, ColumnKind::Text, None) if NULLABLE_COLUMNS.contains(&c) => Value::Null,
+                (c, ColumnKind::Text, None) if JSON_ARRAY_COLUMNS.contains(&c) => Value::Text("[]".to_string()),
+                (_, ColumnKind::Text, None) => Value::Text(String::new()),
+                (_, ColumnKind::Text, Some(JsonValue::String(s))) => Value::Text(s.clone()),
+                (_, ColumnKind::Text, Some(other)) => Value::Text(other.to_string()),
+            })
+        })
+        .collect()
+}
+
+pub fn row_to_json(table: &SharedTable, row: &[Value]) -> Map<String, JsonValue> {
+    table
+        .columns
+        .iter()
+        .zip(row.iter())
+        .map(|((column, _), value)| {
+            let json = match value {
+                Value::Integer(i) => JsonValue::from(*i),
+                Value::Text(s) => JsonValue::from(s.clone()),
+                Value::Real(f) => JsonValue::from(*f),
+                _ => JsonValue::Null,
+            };
+            (column.to_string(), json)
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_support::with_project;
+
+    fn setup_db() -> Connection {
+        let conn = with_project();
+        conn.execute(
+            "INSERT INTO skills (id, project_id, name, description, content, usage_count, tags, created_at, updated_at)
+             VALUES ('local', 'p1', 'Review', '', 'mine', 0, '[]', '2025-01-01T00:00:00Z', '2025-01-01T00:00:00Z')",
+            [],
+        )
+        .unwrap();
+        conn
+    }
+
+    fn incoming_skill(id: &str, name: &str, content: &str) -> Vec<Value> {
+        let obj = serde_json::json!({ "id": id, "name": name, "content": content, "updatedAt": "2025-02-01T00:00:00Z" });
+        row_from_json(shared::shared_table("skills").unwrap(), obj.as_object().unwrap()).unwrap()
+    }
+
+    fn skill_content(conn: &Connection, id: &str) -> Option<String> {
+        conn.query_row("SELECT content FROM skills WHERE id = ?1", [id], |r| r.get(0)).optional().unwrap()
+    }
+
+    #[test]
+    fn test_merge_stages_name_collision_and_take_incoming() {
+        let conn = setup_db();
+        let skills = shared::shared_table("skills").unwrap();
+        let rows = vec![incoming_skill("remote", "review", "theirs"), incoming_skill("other", "Other", "x")];
+
+        let (summary, blocked) = merge_incoming(&conn, skills, rows.clone(), None, "bundle").unwrap();
+        assert_eq!((summary.added, summary.conflicts), (1, 1));
+        assert!(blocked.contains("local"));
+        assert_eq!(skill_content(&conn, "local").as_deref(), Some("mine"));
+
+        // Re-importing does not duplicate the conflict
+        merge_incoming(&conn, skills, rows, None, "bundle").unwrap();
+        let conflicts = pending_conflicts(&conn).unwrap();
+        assert_eq!(conflicts.len(), 1);
+        assert_eq!(conflicts[0].changed_fields, vec!["name".to_string(), "content".to_string()]);
+
+        let decision = ConflictDecision { conflict_id: conflicts[0].id.clone(), resolution: ConflictResolution::TakeIncoming };
+        let result = apply_decisions(&conn, std::slice::from_ref(&decision)).unwrap();
+        assert_eq!((result.took_incoming, result.remaining), (1, 0));
+        assert_eq!(skill_content(&conn, "local"), None);
+        assert_eq!(skill_content(&conn, "remote").as_deref(), Some("theirs"));
+        let project: Option<String> = conn.query_row("SELECT project_id FROM skills WHERE id = 'remote'", [], |r| r.get(0)).unwrap();
+        assert_eq!(project.as_deref(), Some("p1"));
+
+        assert!(apply_decisions(&conn, &[decision]).is_err());
+    }
+
+    #[test]
+    fn test_keep_local_is_remembered_and_keep_both_renames() {
+        let conn = setup_db();
+        let skills = shared::shared_table("skills").unwrap();
+
+        merge_incoming(&conn, skills, vec![incoming_skill("remote", "Review", "theirs")], None, "shared").unwrap();
+        let id = pending_conflicts(&conn).unwrap()[0].id.clone();
+        apply_decisions(&conn, &[ConflictDecision { conflict_id: id, resolution: ConflictResolution::KeepLocal }]).unwrap();
+
+        // Same incoming version: skipped, not raised again
+        let (summary, _) = merge_incoming(&conn, skills, vec![incoming_skill("remote", "Review", "theirs")], None, "shared").unwrap();
+        assert_eq!((summary.skipped, summary.conflicts), (1, 0));
+
+        // A different incoming row with the same name: keep both
+        merge_incoming(&conn, skills, vec![incoming_skill("third", "Review", "again")], None, "bundle").unwrap();
+        let id = pending_conflicts(&conn).unwrap()[0].id.clone();
+        apply_decisions(&conn, &[ConflictDecision { conflict_id: id, resolution: ConflictResolution::KeepBoth }]).unwrap();
+        let name: String = conn.query_row("SELECT name FROM skills WHERE id = 'third'", [], |r| r.get(0)).unwrap();
+        assert_eq!(name, "Review (imported)");
+        assert_eq!(skill_content(&conn, "local").as_deref(), Some("mine"));
+    }
+
+    #[test]
+    fn test_setting_conflict_take_incoming_and_keep_both_rejected() {
+        let conn = setup_db();
+        conn.execute("INSERT INTO settings (key, value) VALUES ('ai_queue.max_concurrent', '2')", []).unwrap();
+
+        stage_setting_conflict(&conn, "ai_queue.max_concurrent", Some("4"), "2025-02-01T00:00:00Z", "sync").unwrap();
+        stage_setting_conflict(&conn, "ai_queue.max_concurrent", Some("5"), "2025-02-02T00:00:00Z", "sync").unwrap();
+        let conflicts = pending_conflicts(&conn).unwrap();
+        assert_eq!(conflicts.len(), 1);
+        assert_eq!(conflicts[0].entity_type, "setting");
+        assert!(pending_setting_keys(&conn).unwrap().contains("ai_queue.max_concurrent"));
+
+        let keep_both = ConflictDecision { conflict_id: conflicts[0].id.clone(), resolution: ConflictResolution::KeepBoth };
+        assert!(apply_decisions(&conn, &[keep_both]).is_err());
+
+        let take = ConflictDecision { conflict_id: conflicts[0].id.clone(), resolution: ConflictResolution::TakeIncoming };
+        assert_eq!(apply_decisions(&conn, &[take]).unwrap().took_incoming, 1);
+        let value: String = conn
+            .query_row("SELECT value FROM settings WHERE key = 'ai_queue.max_concurrent'", [], |r| r.get(0))
+            .unwrap();
+        assert_eq!(value, "5");
+        assert!(pending_setting_keys(&conn).unwrap().is_empty());
+    }
+}
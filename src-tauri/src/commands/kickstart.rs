@@ -11,31 +11,53 @@
 //! - tauri - Command macro and State
 //! - db::AppState - Database and HTTP client access
 //! - core::ai - Claude API caller
+//! - core::ai_queue - Prioritized slot for each Claude call
+//! - core::stack_presets - Built-in presets, preset matching, and prompt guidance
+//! - core::package_manager - Package manager named in the tech stack
+//! - models::stack_preset - StackPreset, StarterSkill
 //! - serde - JSON serialization for input/output
 //!
 //! EXPORTS:
 //! - generate_kickstart_prompt - Generate a kickstart prompt from user input
 //! - generate_kickstart_claude_md - Generate and save initial CLAUDE.md from kickstart input
 //! - infer_tech_stack - Use AI to suggest optimal tech stack based on project description
+//! - list_stack_presets - User-defined presets followed by the built-in library
+//! - create_stack_preset - Save a user-defined stack preset
+//! - update_stack_preset - Update a user-defined stack preset
+//! - delete_stack_preset - Delete a user-defined stack preset
 //!
 //! PATTERNS:
 //! - Uses core::ai::call_claude for AI generation
 //! - Returns full prompt text with token estimate
 //! - Token estimate uses rough approximation (4 chars = 1 token)
 //! - Stack inference returns suggestions with reasoning
+//! - An explicit preset_id wins; otherwise the tech preferences snap to the closest preset
 //!
 //! CLAUDE NOTES:
 //! - System prompt instructs Claude to generate CLAUDE.md-style content
 //! - Output includes: Overview, Tech Stack, Architecture, Structure, Conventions, Roadmap
 //! - Stack inference distinguishes between user selections and AI suggestions
+//! - Built-in presets are read-only; only stack_presets rows can be updated or deleted
+//! - The matched preset is returned so the UI can offer its starter skills
+//! - The tech stack always names a package manager for JS/Python so generated commands match it
 //! - App name: Project Jumpstart
 
+use std::path::Path;
+
+use chrono::Utc;
+use rusqlite::Connection;
 use serde::{Deserialize, Serialize};
 use tauri::State;
+use uuid::Uuid;
 
 use crate::core::ai;
+use crate::core::ai_queue;
 use crate::core::crypto;
+use crate::core::package_manager::{self, PackageManager};
+use crate::core::stack_presets;
 use crate::db::AppState;
+use crate::models::ai_queue::AiJobKind;
+use crate::models::stack_preset::{StackPreset, StarterSkill};
 
 /// Tech stack preferences for the new project
 #[derive(Debug, Deserialize)]
@@ -45,6 +67,9 @@ pub struct TechPreferences {
     pub framework: Option<String>,
     pub database: Option<String>,
     pub styling: Option<String>,
+    /// npm, pnpm, yarn, bun, uv, poetry, pipenv, or pip; None detects or uses the language default
+    #[serde(default)]
+    pub package_manager: Option<String>,
 }
 
 /// User-provided information about the new project
@@ -56,6 +81,9 @@ pub struct KickstartInput {
     pub key_features: Vec<String>,
     pub tech_preferences: TechPreferences,
     pub constraints: Option<String>,
+    /// Preset to follow; when None the tech preferences snap to the closest preset
+    #[serde(default)]
+    pub preset_id: Option<String>,
 }
 
 /// Generated kickstart prompt with metadata
@@ -64,6 +92,8 @@ pub struct KickstartInput {
 pub struct KickstartPrompt {
     pub full_prompt: String,
     pub token_estimate: u32,
+    /// Preset the prompt followed, if any
+    pub preset: Option<StackPreset>,
 }
 
 /// A single tech stack suggestion with reasoning
@@ -99,8 +129,34 @@ pub struct InferredStack {
     pub database: Option<StackSuggestion>,
     pub styling: Option<StackSuggestion>,
     pub warnings: Vec<String>,
+    /// Preset matching the user's selections combined with the suggestions
+    #[serde(default)]
+    pub preset: Option<StackPreset>,
 }
 
+/// A user-defined stack preset to create or update
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct StackPresetInput {
+    pub name: String,
+    #[serde(default)]
+    pub description: String,
+    pub language: String,
+    pub framework: String,
+    pub database: Option<String>,
+    pub styling: Option<String>,
+    pub testing: Option<String>,
+    #[serde(default)]
+    pub conventions: Vec<String>,
+    #[serde(default)]
+    pub directory_layout: String,
+    #[serde(default)]
+    pub starter_skills: Vec<StarterSkill>,
+}
+
+const PRESET_COLUMNS: &str = "id, name, description, language, framework, database_name, styling, testing,
+                              conventions, directory_layout, starter_skills, created_at, updated_at";
+
 const KICKSTART_SYSTEM_PROMPT: &str = r#"You are an expert software architect creating a starter prompt for Claude Code to build a new project from scratch.
 
 Generate a DETAILED, ACTIONABLE prompt that a developer would paste into Claude Code to kick off development. This is NOT documentation - it's instructions for an AI to start building.
@@ -152,19 +208,31 @@ End with a clear, specific first task like:
 IMPORTANT GUIDELINES:
 - Be SPECIFIC, not generic. Name actual libraries, actual file names, actual commands.
 - Include version numbers for dependencies when it matters.
+- Use the package manager from the tech stack for every install, run, and test command.
 - The prompt should be copy-paste ready for Claude Code.
 - Assume the developer wants to START CODING immediately after reading this.
 - Don't be wishy-washy - make clear decisions and state them confidently.
 - Output markdown only, no preamble."#;
 
+/// Package manager for a kickstart stack: the user's choice, then one detected in the
+/// target directory (if it already has a lockfile), then the language default.
+fn kickstart_package_manager(prefs: &TechPreferences, project_path: Option<&str>) -> Option<PackageManager> {
+    prefs
+        .package_manager
+        .as_deref()
+        .and_then(PackageManager::parse)
+        .or_else(|| project_path.and_then(|p| package_manager::detect(Path::new(p))).map(|(pm, _)| pm))
+        .or_else(|| prefs.language.as_deref().and_then(package_manager::default_for))
+}
+
 /// Generate a kickstart prompt for a new project based on user input.
 #[tauri::command]
 pub async fn generate_kickstart_prompt(
     input: KickstartInput,
     state: State<'_, AppState>,
 ) -> Result<KickstartPrompt, String> {
-    // Get API key from database
-    let api_key = {
+    // Get API key and stack presets from database
+    let (api_key, presets) = {
         let db = state
             .db
             .lock()
@@ -179,13 +247,15 @@ pub async fn generate_kickstart_prompt(
             .map_err(|_| "Anthropic API key not configured. Set it in Settings.".to_string())?;
 
         // Decrypt if encrypted
-        if let Some(stripped) = encrypted.strip_prefix("enc:") {
+        let api_key = if let Some(stripped) = encrypted.strip_prefix("enc:") {
             crypto::decrypt(stripped)
                 .map_err(|e| format!("Failed to decrypt API key: {}", e))?
         } else {
             encrypted
-        }
+        };
+        (api_key, load_presets(&db)?)
     };
+    let preset = resolve_preset(&presets, &input)?;
 
     // Build the user prompt
     let features_list = input
@@ -196,11 +266,12 @@ pub async fn generate_kickstart_prompt(
         .join("\n");
 
     let tech_stack = format!(
-        "Language: {}\nFramework: {}\nDatabase: {}\nStyling: {}",
+        "Language: {}\nFramework: {}\nDatabase: {}\nStyling: {}\nPackage Manager: {}",
         input.tech_preferences.language.as_deref().unwrap_or("None specified"),
         input.tech_preferences.framework.as_deref().unwrap_or("None specified"),
         input.tech_preferences.database.as_deref().unwrap_or("None specified"),
         input.tech_preferences.styling.as_deref().unwrap_or("None specified"),
+        kickstart_package_manager(&input.tech_preferences, None).map(|pm| pm.as_str()).unwrap_or("None specified"),
     );
 
     let constraints_section = input
@@ -208,6 +279,9 @@ pub async fn generate_kickstart_prompt(
         .as_ref()
         .map(|c| format!("\n\nConstraints/Requirements:\n{}", c))
         .unwrap_or_default();
+    let preset_section = preset
+        .map(|p| format!("\n\n{}", stack_presets::preset_guidance(p)))
+        .unwrap_or_default();
 
     let user_prompt = format!(
         r#"Generate a kickstart prompt for this new project:
@@ -223,21 +297,21 @@ pub async fn generate_kickstart_prompt(
 
 **Tech Stack Decisions:**
 {}
-{}
+{}{}
 Create a detailed, actionable kickstart prompt that I can paste into Claude Code to start building immediately. Be specific with file names, commands, and dependencies."#,
         input.app_purpose,
         input.target_users,
         features_list,
         tech_stack,
-        constraints_section
+        constraints_section,
+        preset_section
     );
 
     // Call Claude API
-    let full_prompt = ai::call_claude(
-        &state.http_client,
-        &api_key,
-        KICKSTART_SYSTEM_PROMPT,
-        &user_prompt,
+    let full_prompt = ai_queue::run(
+        AiJobKind::Interactive,
+        "kickstart prompt",
+        ai::call_claude(&state.http_client, &api_key, KICKSTART_SYSTEM_PROMPT, &user_prompt),
     )
     .await?;
 
@@ -247,6 +321,7 @@ Create a detailed, actionable kickstart prompt that I can paste into Claude Code
     Ok(KickstartPrompt {
         full_prompt,
         token_estimate,
+        preset: preset.cloned(),
     })
 }
 
@@ -311,6 +386,7 @@ Things Claude should ALWAYS remember:
 ## GUIDELINES:
 - Be SPECIFIC and ACTIONABLE
 - Include real file paths, real commands, real patterns
+- Write every command for the package manager in the stack (the pnpm examples above are only examples)
 - This file should survive context loss - Claude reads it fresh each session
 - Focus on what helps Claude write correct code on the first try
 - No marketing speak, no aspirational content - just facts
@@ -324,8 +400,8 @@ pub async fn generate_kickstart_claude_md(
     project_path: String,
     state: State<'_, AppState>,
 ) -> Result<String, String> {
-    // Get API key from database
-    let api_key = {
+    // Get API key and stack presets from database
+    let (api_key, presets) = {
         let db = state
             .db
             .lock()
@@ -340,13 +416,15 @@ pub async fn generate_kickstart_claude_md(
             .map_err(|_| "Anthropic API key not configured. Set it in Settings.".to_string())?;
 
         // Decrypt if encrypted
-        if let Some(stripped) = encrypted.strip_prefix("enc:") {
+        let api_key = if let Some(stripped) = encrypted.strip_prefix("enc:") {
             crypto::decrypt(stripped)
                 .map_err(|e| format!("Failed to decrypt API key: {}", e))?
         } else {
             encrypted
-        }
+        };
+        (api_key, load_presets(&db)?)
     };
+    let preset = resolve_preset(&presets, &input)?;
 
     // Build the user prompt
     let features_list = input
@@ -357,11 +435,14 @@ pub async fn generate_kickstart_claude_md(
         .join("\n");
 
     let tech_stack = format!(
-        "Language: {}\nFramework: {}\nDatabase: {}\nStyling: {}",
+        "Language: {}\nFramework: {}\nDatabase: {}\nStyling: {}\nPackage Manager: {}",
         input.tech_preferences.language.as_deref().unwrap_or("None"),
         input.tech_preferences.framework.as_deref().unwrap_or("None"),
         input.tech_preferences.database.as_deref().unwrap_or("None"),
         input.tech_preferences.styling.as_deref().unwrap_or("None"),
+        kickstart_package_manager(&input.tech_preferences, Some(&project_path))
+            .map(|pm| pm.as_str())
+            .unwrap_or("None"),
     );
 
     let constraints_section = input
@@ -369,6 +450,14 @@ pub async fn generate_kickstart_claude_md(
         .as_ref()
         .map(|c| format!("\n\nConstraints/Requirements:\n{}", c))
         .unwrap_or_default();
+    let preset_section = preset
+        .map(|p| {
+            format!(
+                "\n\n{}\nUse these conventions in Code Patterns and this layout in Project Structure.\n",
+                stack_presets::preset_guidance(p)
+            )
+        })
+        .unwrap_or_default();
 
     let user_prompt = format!(
         r#"Create a CLAUDE.md file for this project:
@@ -384,21 +473,21 @@ pub async fn generate_kickstart_claude_md(
 
 **Stack:**
 {}
-{}
+{}{}
 Generate a complete CLAUDE.md with all required sections. Be specific - use actual file paths, actual commands, actual patterns for this tech stack. This file will be read at the start of every Claude Code session."#,
         input.app_purpose,
         input.target_users,
         features_list,
         tech_stack,
-        constraints_section
+        constraints_section,
+        preset_section
     );
 
     // Call Claude API
-    let content = ai::call_claude(
-        &state.http_client,
-        &api_key,
-        CLAUDE_MD_SYSTEM_PROMPT,
-        &user_prompt,
+    let content = ai_queue::run(
+        AiJobKind::Interactive,
+        "kickstart CLAUDE.md",
+        ai::call_claude(&state.http_client, &api_key, CLAUDE_MD_SYSTEM_PROMPT, &user_prompt),
     )
     .await?;
 
@@ -456,8 +545,8 @@ pub async fn infer_tech_stack(
     input: InferStackInput,
     state: State<'_, AppState>,
 ) -> Result<InferredStack, String> {
-    // Get API key from database
-    let api_key = {
+    // Get API key and stack presets from database
+    let (api_key, presets) = {
         let db = state
             .db
             .lock()
@@ -471,12 +560,13 @@ pub async fn infer_tech_stack(
             )
             .map_err(|_| "Anthropic API key not configured. Set it in Settings.".to_string())?;
 
-        if let Some(stripped) = encrypted.strip_prefix("enc:") {
+        let api_key = if let Some(stripped) = encrypted.strip_prefix("enc:") {
             crypto::decrypt(stripped)
                 .map_err(|e| format!("Failed to decrypt API key: {}", e))?
         } else {
             encrypted
-        }
+        };
+        (api_key, load_presets(&db)?)
     };
 
     // Build the user prompt
@@ -524,21 +614,222 @@ Respond with JSON only. For any field where the user's selection is appropriate,
     );
 
     // Call Claude API
-    let response = ai::call_claude(
-        &state.http_client,
-        &api_key,
-        INFER_STACK_SYSTEM_PROMPT,
-        &user_prompt,
+    let response = ai_queue::run(
+        AiJobKind::Interactive,
+        "tech stack inference",
+        ai::call_claude(&state.http_client, &api_key, INFER_STACK_SYSTEM_PROMPT, &user_prompt),
     )
     .await?;
 
     // Parse the JSON response
-    let inferred: InferredStack = serde_json::from_str(&response)
+    let mut inferred: InferredStack = serde_json::from_str(&response)
         .map_err(|e| format!("Failed to parse AI response: {}. Response was: {}", e, response))?;
 
+    // Snap the combined selection (user choice, else suggestion) to a preset
+    let pick = |current: &Option<String>, suggested: &Option<StackSuggestion>| {
+        current.clone().or_else(|| suggested.as_ref().map(|s| s.value.clone()))
+    };
+    let language = pick(&input.current_language, &inferred.language);
+    let framework = pick(&input.current_framework, &inferred.framework);
+    let database = pick(&input.current_database, &inferred.database);
+    let styling = pick(&input.current_styling, &inferred.styling);
+    inferred.preset = stack_presets::match_preset(
+        &presets,
+        language.as_deref(),
+        framework.as_deref(),
+        database.as_deref(),
+        styling.as_deref(),
+    )
+    .cloned();
+
     Ok(inferred)
 }
 
+/// List user-defined stack presets followed by the built-in library.
+#[tauri::command]
+pub async fn list_stack_presets(state: State<'_, AppState>) -> Result<Vec<StackPreset>, String> {
+    let db = state.db.lock().map_err(|e| format!("DB lock error: {}", e))?;
+    load_presets(&db)
+}
+
+/// Save a user-defined stack preset.
+#[tauri::command]
+pub async fn create_stack_preset(
+    input: StackPresetInput,
+    state: State<'_, AppState>,
+) -> Result<StackPreset, String> {
+    let input = validate_preset(input)?;
+    let db = state.db.lock().map_err(|e| format!("DB lock error: {}", e))?;
+    let id = Uuid::new_v4().to_string();
+    let now = Utc::now().to_rfc3339();
+
+    db.execute(
+        "INSERT INTO stack_presets (id, name, description, language, framework, database_name, styling, testing,
+                                    conventions, directory_layout, starter_skills, created_at, updated_at)
+         VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7, ?8, ?9, ?10, ?11, ?12, ?12)",
+        rusqlite::params![
+            id,
+            input.name,
+            input.description,
+            input.language,
+            input.framework,
+            input.database,
+            input.styling,
+            input.testing,
+            serde_json::to_string(&input.conventions).unwrap_or_else(|_| "[]".to_string()),
+            input.directory_layout,
+            serde_json::to_string(&input.starter_skills).unwrap_or_else(|_| "[]".to_string()),
+            now
+        ],
+    )
+    .map_err(|e| format!("Failed to insert stack preset: {}", e))?;
+
+    load_preset(&db, &id)
+}
+
+/// Update a user-defined stack preset. Built-in presets cannot be changed.
+#[tauri::command]
+pub async fn update_stack_preset(
+    id: String,
+    input: StackPresetInput,
+    state: State<'_, AppState>,
+) -> Result<StackPreset, String> {
+    if stack_presets::find_preset(&stack_presets::builtin_presets(), &id).is_some() {
+        return Err(format!("Built-in preset '{}' cannot be changed; save a copy instead", id));
+    }
+    let input = validate_preset(input)?;
+    let db = state.db.lock().map_err(|e| format!("DB lock error: {}", e))?;
+    let rows_affected = db
+        .execute(
+            "UPDATE stack_presets SET name = ?1, description = ?2, language = ?3, framework = ?4, database_name = ?5,
+             styling = ?6, testing = ?7, conventions = ?8, directory_layout = ?9, starter_skills = ?10, updated_at = ?11
+             WHERE id = ?12",
+            rusqlite::params![
+                input.name,
+                input.description,
+                input.language,
+                input.framework,
+                input.database,
+                input.styling,
+                input.testing,
+                serde_json::to_string(&input.conventions).unwrap_or_else(|_| "[]".to_string()),
+                input.directory_layout,
+                serde_json::to_string(&input.starter_skills).unwrap_or_else(|_| "[]".to_string()),
+                Utc::now().to_rfc3339(),
+                id
+            ],
+        )
+        .map_err(|e| format!("Failed to update stack preset: {}", e))?;
+
+    if rows_affected == 0 {
+        return Err(format!("Stack preset not found: {}", id));
+    }
+
+    load_preset(&db, &id)
+}
+
+/// Delete a user-defined stack preset.
+#[tauri::command]
+pub async fn delete_stack_preset(id: String, state: State<'_, AppState>) -> Result<(), String> {
+    let db = state.db.lock().map_err(|e| format!("DB lock error: {}", e))?;
+    let rows_affected = db
+        .execute("DELETE FROM stack_presets WHERE id = ?1", [&id])
+        .map_err(|e| format!("Failed to delete stack preset: {}", e))?;
+
+    if rows_affected == 0 {
+        return Err(format!("Stack preset not found: {}", id));
+    }
+    Ok(())
+}
+
+/// The preset a kickstart request follows: its explicit preset_id, else the
+/// closest match for its tech preferences.
+fn resolve_preset<'a>(presets: &'a [StackPreset], input: &KickstartInput) -> Result<Option<&'a StackPreset>, String> {
+    if let Some(ref id) = input.preset_id {
+        return stack_presets::find_preset(presets, id)
+            .map(Some)
+            .ok_or_else(|| format!("Stack preset not found: {}", id));
+    }
+    let prefs = &input.tech_preferences;
+    Ok(stack_presets::match_preset(
+        presets,
+        prefs.language.as_deref(),
+        prefs.framework.as_deref(),
+        prefs.database.as_deref(),
+        prefs.styling.as_deref(),
+    ))
+}
+
+/// Trim preset fields, drop blank list entries, and require name, language, and framework.
+fn validate_preset(mut input: StackPresetInput) -> Result<StackPresetInput, String> {
+    input.name = input.name.trim().to_string();
+    input.language = input.language.trim().to_string();
+    input.framework = input.framework.trim().to_string();
+    if input.name.is_empty() {
+        return Err("Preset name is required".to_string());
+    }
+    if input.language.is_empty() || input.framework.is_empty() {
+        return Err("Preset language and framework are required".to_string());
+    }
+    let blank_to_none = |v: Option<String>| v.map(|s| s.trim().to_string()).filter(|s| !s.is_empty());
+    input.database = blank_to_none(input.database);
+    input.styling = blank_to_none(input.styling);
+    input.testing = blank_to_none(input.testing);
+    input.conventions = input
+        .conventions
+        .into_iter()
+        .map(|c| c.trim().to_string())
+        .filter(|c| !c.is_empty())
+        .collect();
+    input.starter_skills.retain(|s| !s.name.trim().is_empty());
+    Ok(input)
+}
+
+/// User-defined presets (newest first) followed by the built-in library, so user
+/// presets win ties in stack_presets::match_preset.
+fn load_presets(db: &Connection) -> Result<Vec<StackPreset>, String> {
+    let mut stmt = db
+        .prepare(&format!("SELECT {} FROM stack_presets ORDER BY updated_at DESC", PRESET_COLUMNS))
+        .map_err(|e| format!("Failed to query stack presets: {}", e))?;
+    let mut presets: Vec<StackPreset> = stmt
+        .query_map([], map_preset_row)
+        .map_err(|e| format!("Failed to read stack presets: {}", e))?
+        .filter_map(|r| r.ok())
+        .collect();
+    presets.extend(stack_presets::builtin_presets());
+    Ok(presets)
+}
+
+fn load_preset(db: &Connection, id: &str) -> Result<StackPreset, String> {
+    db.query_row(
+        &format!("SELECT {} FROM stack_presets WHERE id = ?1", PRESET_COLUMNS),
+        [id],
+        map_preset_row,
+    )
+    .map_err(|_| format!("Stack preset not found: {}", id))
+}
+
+fn map_preset_row(row: &rusqlite::Row) -> rusqlite::Result<StackPreset> {
+    let conventions: String = row.get(8)?;
+    let starter_skills: String = row.get(10)?;
+    Ok(StackPreset {
+        id: row.get(0)?,
+        name: row.get(1)?,
+        description: row.get(2)?,
+        language: row.get(3)?,
+        framework: row.get(4)?,
+        database: row.get(5)?,
+        styling: row.get(6)?,
+        testing: row.get(7)?,
+        conventions: serde_json::from_str(&conventions).unwrap_or_default(),
+        directory_layout: row.get(9)?,
+        starter_skills: serde_json::from_str(&starter_skills).unwrap_or_default(),
+        builtin: false,
+        created_at: row.get(11)?,
+        updated_at: row.get(12)?,
+    })
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -565,4 +856,60 @@ mod tests {
         assert_eq!(input.tech_preferences.framework, Some("React".to_string()));
         assert!(input.tech_preferences.database.is_none());
     }
+
+    #[test]
+    fn test_kickstart_package_manager() {
+        let mut prefs: TechPreferences =
+            serde_json::from_str(r#"{"language": "TypeScript", "framework": null, "database": null, "styling": null}"#)
+                .unwrap();
+        assert_eq!(kickstart_package_manager(&prefs, None), Some(PackageManager::Pnpm));
+
+        let dir = tempfile::tempdir().unwrap();
+        std::fs::write(dir.path().join("bun.lock"), "").unwrap();
+        let path = dir.path().to_str().unwrap();
+        assert_eq!(kickstart_package_manager(&prefs, Some(path)), Some(PackageManager::Bun));
+
+        prefs.package_manager = Some("yarn".to_string());
+        assert_eq!(kickstart_package_manager(&prefs, Some(path)), Some(PackageManager::Yarn));
+    }
+
+    #[test]
+    fn test_resolve_preset() {
+        let presets = stack_presets::builtin_presets();
+        let mut input: KickstartInput = serde_json::from_str(
+            r#"{
+            "appPurpose": "An API",
+            "targetUsers": "Developers",
+            "keyFeatures": [],
+            "techPreferences": {"language": "Python", "framework": "FastAPI", "database": null, "styling": null},
+            "constraints": null
+        }"#,
+        )
+        .unwrap();
+        assert!(input.preset_id.is_none());
+        assert_eq!(resolve_preset(&presets, &input).unwrap().unwrap().id, "fastapi-sqlmodel");
+
+        input.preset_id = Some("go-gin".to_string());
+        assert_eq!(resolve_preset(&presets, &input).unwrap().unwrap().id, "go-gin");
+        input.preset_id = Some("missing".to_string());
+        assert!(resolve_preset(&presets, &input).is_err());
+    }
+
+    #[test]
+    fn test_validate_preset_and_inferred_without_preset() {
+        let input: StackPresetInput = serde_json::from_str(
+            r#"{"name": " Remix ", "language": "TypeScript", "framework": "Remix", "database": " ",
+                "styling": "Tailwind CSS", "testing": null, "conventions": ["Loaders fetch data", "  "]}"#,
+        )
+        .unwrap();
+        let input = validate_preset(input).unwrap();
+        assert_eq!(input.name, "Remix");
+        assert!(input.database.is_none());
+        assert_eq!(input.conventions, vec!["Loaders fetch data"]);
+
+        let inferred: InferredStack =
+            serde_json::from_str(r#"{"language": null, "framework": null, "database": null, "styling": null, "warnings": []}"#)
+                .unwrap();
+        assert!(inferred.preset.is_none());
+    }
 }
@@ -0,0 +1,60 @@
+//! @module commands/logs
+//! @description Tauri IPC command for the application log viewer
+//!
+//! PURPOSE:
+//! - Return recent application log entries filtered by level, time range, and module
+//!
+//! DEPENDENCIES:
+//! - core::logging - Log directory, range parsing, and file reading
+//! - models::logs - AppLogs
+//!
+//! EXPORTS:
+//! - get_app_logs - Matching log entries (newest first) and the log directory
+//!
+//! PATTERNS:
+//! - range is "SINCE..UNTIL" with YYYY-MM-DD dates or RFC 3339 timestamps; either side may be empty
+//! - level is a minimum ("warn" returns WARN and ERROR)
+//!
+//! CLAUDE NOTES:
+//! - Reads files only; no DB access, so it works even when the database is broken
+
+use crate::core::logging::{self, LogFilter};
+use crate::models::logs::AppLogs;
+
+/// Entries returned when no limit is given.
+const DEFAULT_LOG_LIMIT: u32 = 500;
+
+/// Upper bound on entries returned in one call.
+const MAX_LOG_LIMIT: u32 = 5000;
+
+/// Application log entries, newest first.
+#[tauri::command]
+pub async fn get_app_logs(
+    level: Option<String>,
+    range: Option<String>,
+    module: Option<String>,
+    limit: Option<u32>,
+) -> Result<AppLogs, String> {
+    let (since, until) = logging::parse_range(range.as_deref())?;
+    let filter = LogFilter {
+        level,
+        since,
+        until,
+        module,
+    };
+    let limit = limit.unwrap_or(DEFAULT_LOG_LIMIT).min(MAX_LOG_LIMIT) as usize;
+
+    let Some(dir) = logging::logs_dir() else {
+        return Ok(AppLogs {
+            entries: Vec::new(),
+            truncated: false,
+            log_dir: None,
+        });
+    };
+    let (entries, truncated) = logging::read_logs(&dir, &filter, limit)?;
+    Ok(AppLogs {
+        entries,
+        truncated,
+        log_dir: Some(dir.to_string_lossy().to_string()),
+    })
+}
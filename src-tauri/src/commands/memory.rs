@@ -7,6 +7,7 @@
 //! - Analyze CLAUDE.md quality and provide improvement suggestions
 //! - Calculate overall memory health metrics
 //! - Promote learnings from local files to shared targets
+//! - Import memory files written by other tools into learnings (source = "imported")
 //!
 //! DEPENDENCIES:
 //! - tauri - Command macro and State
@@ -14,6 +15,8 @@
 //! - models::memory - MemorySource, Learning, MemoryHealth, ClaudeMdAnalysis, etc.
 //! - chrono - Timestamp generation
 //! - uuid - Unique ID generation
+//! - sha2 - Content hashes for de-duplicating imported learnings
+//! - core::learnings - Referenced-file churn and bullet removal for stale learning review
 //! - std::fs - File system operations
 //!
 //! EXPORTS:
@@ -23,6 +26,9 @@
 //! - analyze_claude_md - Analyze CLAUDE.md quality and suggest improvements
 //! - get_memory_health - Aggregate health metrics from all memory sources
 //! - promote_learning - Move a learning from local to a target file
+//! - import_memory_learnings - Import .claude memory files and "# Learned" sections as learnings
+//! - review_stale_learnings - Promoted learnings whose referenced files changed since verification
+//! - demote_learning - Remove a promoted learning from its target file (keeps provenance in DB)
 //!
 //! PATTERNS:
 //! - All commands are async and return Result<T, String>
@@ -37,18 +43,29 @@
 //! - CLAUDE.md score: 100 if <=100 lines, -1 per line over 100 (floor 0)
 //! - Self-evident phrases trigger removal suggestions
 //! - Code blocks in CLAUDE.md trigger move-to-rules suggestions
+//! - Imports read .claude/memory/*.md, Claude Code auto-memory, and "# Learned"/"## Learnings"
+//!   sections of CLAUDE.md, .claude/CLAUDE.md, AGENTS.md, and .cursorrules; list_learnings runs
+//!   the import on every call, and learnings.content_hash keeps it from importing an entry twice
+//! - list_learnings only returns DB learnings for the project (or with no project_id)
+//! - last_verified_at is set on promotion and whenever status becomes "verified"; staleness is
+//!   measured from it (falling back to promoted_at)
 
 use chrono::Utc;
+use rusqlite::Connection;
+use sha2::{Digest, Sha256};
 use tauri::State;
 use uuid::Uuid;
 
 use std::fs;
 use std::path::{Path, PathBuf};
 
+use crate::core::learnings;
+use crate::core::events::{self, AppEvent};
 use crate::db::AppState;
+use crate::models::activity::ActivityType;
 use crate::models::memory::{
-    AnalysisSuggestion, ClaudeMdAnalysis, Learning, LineMoveTarget, LineRemovalSuggestion,
-    MemoryHealth, MemorySource,
+    AnalysisSuggestion, ClaudeMdAnalysis, Learning, LearningDemotion, LineMoveTarget, LineRemovalSuggestion,
+    MemoryHealth, MemoryImportResult, MemorySource, StaleLearning,
 };
 
 // ---------------------------------------------------------------------------
@@ -235,6 +252,7 @@ fn read_memory_source(
 // ---------------------------------------------------------------------------
 
 /// Parse CLAUDE.local.md to extract learnings and merge with DB entries.
+/// Memory files and "# Learned" sections are imported into the DB first (new entries only).
 #[tauri::command]
 pub async fn list_learnings(
     project_path: String,
@@ -253,6 +271,16 @@ pub async fn list_learnings(
 
     // 2. Load from database
     let db = state.db.lock().map_err(|e| format!("DB lock error: {}", e))?;
+    let project_id: Option<String> = db
+        .query_row("SELECT id FROM projects WHERE path = ?1", [&project_path], |row| row.get(0))
+        .ok();
+
+    // Import memory files from other tools (best-effort; re-imports are skipped by content hash)
+    if let Some(pid) = &project_id {
+        if let Err(e) = import_memory_learnings_db(&db, pid, Path::new(&project_path), dirs::home_dir().as_deref()) {
+            tracing::warn!(error = %e, "Memory import failed");
+        }
+    }
 
     // Check if the learnings table exists (it may not in older databases)
     let table_exists: bool = db
@@ -266,14 +294,15 @@ pub async fn list_learnings(
         let mut stmt = db
             .prepare(
                 "SELECT id, session_id, category, content, topic, confidence, status, source_file,
-                        created_at, updated_at
+                        created_at, updated_at, source, last_verified_at
                  FROM learnings
+                 WHERE project_id IS NULL OR project_id = ?1
                  ORDER BY created_at DESC",
             )
             .map_err(|e| format!("Failed to prepare learnings query: {}", e))?;
 
         let db_learnings = stmt
-            .query_map([], |row| {
+            .query_map([&project_id], |row| {
                 Ok(Learning {
                     id: row.get(0)?,
                     session_id: row.get(1)?,
@@ -285,6 +314,8 @@ pub async fn list_learnings(
                     source_file: row.get(7)?,
                     created_at: row.get(8)?,
                     updated_at: row.get(9)?,
+                    source: row.get(10)?,
+                    last_verified_at: row.get(11)?,
                 })
             })
             .map_err(|e| format!("Failed to query learnings: {}", e))?;
@@ -373,6 +404,8 @@ fn parse_local_md_learnings(content: &str, source_path: &Path) -> Vec<Learning>
                     source_file: source_file.clone(),
                     created_at: created,
                     updated_at: now,
+                    source: "local".to_string(),
+                    last_verified_at: None,
                 });
             }
         }
@@ -381,6 +414,242 @@ fn parse_local_md_learnings(content: &str, source_path: &Path) -> Vec<Learning>
     learnings
 }
 
+// ---------------------------------------------------------------------------
+// import_memory_learnings
+// ---------------------------------------------------------------------------
+
+/// Project files that may carry "# Learned" sections written by other tools.
+const LEARNED_SECTION_FILES: &[&str] = &["CLAUDE.md", ".claude/CLAUDE.md", "AGENTS.md", ".cursorrules"];
+
+/// An entry found in a memory file, before it becomes a Learning.
+#[derive(Debug, Clone, PartialEq)]
+struct ImportedEntry {
+    category: String,
+    content: String,
+    topic: Option<String>,
+}
+
+/// Import memory files into learnings for a project (source = "imported").
+/// Entries whose content hash is already stored for the project are skipped.
+#[tauri::command]
+pub async fn import_memory_learnings(
+    project_id: String,
+    state: State<'_, AppState>,
+) -> Result<MemoryImportResult, String> {
+    let db = state.db.lock().map_err(|e| format!("DB lock error: {}", e))?;
+    let project_path: String = db
+        .query_row("SELECT path FROM projects WHERE id = ?1", [&project_id], |row| row.get(0))
+        .map_err(|e| format!("Project not found: {}", e))?;
+
+    import_memory_learnings_db(&db, &project_id, Path::new(&project_path), dirs::home_dir().as_deref())
+}
+
+/// Memory files to import: project `.claude/memory/`, Claude Code auto-memory under
+/// `<home>/.claude/projects/<encoded>/memory/`, and files that may have "# Learned" sections.
+/// Returns (path, is_memory_file).
+fn memory_import_files(project_dir: &Path, home: Option<&Path>) -> Vec<(PathBuf, bool)> {
+    let mut files = Vec::new();
+
+    let mut memory_dirs = vec![project_dir.join(".claude").join("memory")];
+    if let Some(home) = home {
+        let encoded = encode_project_path(&project_dir.to_string_lossy());
+        memory_dirs.push(home.join(".claude").join("projects").join(encoded).join("memory"));
+    }
+    for dir in memory_dirs {
+        if let Ok(entries) = fs::read_dir(&dir) {
+            let mut paths: Vec<PathBuf> = entries
+                .flatten()
+                .map(|e| e.path())
+                .filter(|p| p.extension().and_then(|e| e.to_str()) == Some("md"))
+                .collect();
+            paths.sort();
+            files.extend(paths.into_iter().map(|p| (p, true)));
+        }
+    }
+
+    for name in LEARNED_SECTION_FILES {
+        let path = project_dir.join(name);
+        if path.is_file() {
+            files.push((path, false));
+        }
+    }
+    files
+}
+
+/// Split "[Category] text | topic:x | confidence:y" (the CLAUDE.local.md bullet format)
+/// or a plain bullet into an entry. `default_category` applies when there is no tag.
+fn parse_learning_bullet(text: &str, default_category: &str) -> Option<ImportedEntry> {
+    let (category, rest) = match text.strip_prefix('[').and_then(|t| t.split_once(']')) {
+        Some((category, rest)) if !rest.starts_with('(') => (category.trim().to_string(), rest),
+        _ => (default_category.to_string(), text),
+    };
+    let mut parts = rest.split(" | ");
+    let content = parts.next()?.trim().to_string();
+    let topic = parts.find_map(|p| p.trim().strip_prefix("topic:").map(|t| t.trim().to_string()));
+    if content.is_empty() {
+        return None;
+    }
+    Some(ImportedEntry { category, content, topic })
+}
+
+/// Text of a markdown list item ("- ", "* ", "1. "), or None.
+fn bullet_text(line: &str) -> Option<&str> {
+    let trimmed = line.trim_start();
+    if let Some(rest) = trimmed.strip_prefix("- ").or_else(|| trimmed.strip_prefix("* ")) {
+        return Some(rest.trim());
+    }
+    let digits = trimmed.chars().take_while(|c| c.is_ascii_digit()).count();
+    if digits > 0 {
+        return trimmed[digits..].strip_prefix(". ").map(str::trim);
+    }
+    None
+}
+
+/// Bullets under headings such as "# Learned", "## Learned Patterns", or "## Learnings".
+fn parse_learned_sections(content: &str) -> Vec<ImportedEntry> {
+    let mut entries = Vec::new();
+    let mut section_level: Option<usize> = None;
+    let mut in_fence = false;
+
+    for line in content.lines() {
+        if line.trim_start().starts_with("```") {
+            in_fence = !in_fence;
+            continue;
+        }
+        if in_fence {
+            continue;
+        }
+        let level = line.chars().take_while(|c| *c == '#').count();
+        if level > 0 && line[level..].starts_with(' ') {
+            let title = line[level..].trim().to_lowercase();
+            if section_level.is_some_and(|l| level <= l) {
+                section_level = None;
+            }
+            if section_level.is_none() && (title.starts_with("learned") || title.starts_with("learnings")) {
+                section_level = Some(level);
+            }
+            continue;
+        }
+        if section_level.is_some() {
+            if let Some(entry) = bullet_text(line).and_then(|t| parse_learning_bullet(t, "Pattern")) {
+                entries.push(entry);
+            }
+        }
+    }
+    entries
+}
+
+/// Entries from a memory file. A file with frontmatter (name/description/type) is one
+/// memory; otherwise every bullet is one. Index bullets linking to other .md files are skipped.
+fn parse_memory_file(content: &str) -> Vec<ImportedEntry> {
+    if let Some(rest) = content.strip_prefix("---\n") {
+        if let Some((front, body)) = rest.split_once("\n---") {
+            let field = |key: &str| {
+                front.lines().find_map(|l| {
+                    l.trim()
+                        .strip_prefix(key)
+                        .and_then(|v| v.strip_prefix(':'))
+                        .map(|v| v.trim().trim_matches('"').to_string())
+                })
+            };
+            let body = body.trim_start_matches('-').trim();
+            // First paragraph of the body; fall back to the description
+            let text = body
+                .split("\n\n")
+                .next()
+                .map(|p| p.split_whitespace().collect::<Vec<_>>().join(" "))
+                .filter(|p| !p.is_empty())
+                .or_else(|| field("description"));
+            let category = match field("type").as_deref() {
+                Some("user") | Some("feedback") => "Preference",
+                _ => "Pattern",
+            };
+            return text
+                .map(|content| vec![ImportedEntry { category: category.to_string(), content, topic: field("name") }])
+                .unwrap_or_default();
+        }
+    }
+
+    content
+        .lines()
+        .filter_map(bullet_text)
+        .filter(|t| !(t.starts_with('[') && t.contains(".md)")))
+        .filter_map(|t| parse_learning_bullet(t, "Pattern"))
+        .collect()
+}
+
+/// Hash of a learning's content, ignoring case and whitespace differences.
+fn learning_hash(content: &str) -> String {
+    let normalized = content.split_whitespace().collect::<Vec<_>>().join(" ").to_lowercase();
+    format!("{:x}", Sha256::digest(normalized.as_bytes()))
+}
+
+/// Import memory files for a project into learnings, skipping known content hashes.
+fn import_memory_learnings_db(
+    db: &Connection,
+    project_id: &str,
+    project_dir: &Path,
+    home: Option<&Path>,
+) -> Result<MemoryImportResult, String> {
+    let mut known: std::collections::HashSet<String> = db
+        .prepare("SELECT content_hash, content FROM learnings WHERE project_id IS NULL OR project_id = ?1")
+        .and_then(|mut stmt| {
+            let rows = stmt.query_map([project_id], |row| {
+                let hash: Option<String> = row.get(0)?;
+                let content: String = row.get(1)?;
+                Ok(hash.unwrap_or_else(|| learning_hash(&content)))
+            })?;
+            Ok(rows.flatten().collect())
+        })
+        .map_err(|e| format!("Failed to read learnings: {}", e))?;
+
+    let mut result = MemoryImportResult::default();
+    let now = Utc::now().to_rfc3339();
+
+    for (path, is_memory_file) in memory_import_files(project_dir, home) {
+        let Ok(content) = fs::read_to_string(&path) else {
+            continue;
+        };
+        let entries = if is_memory_file {
+            parse_memory_file(&content)
+        } else {
+            parse_learned_sections(&content)
+        };
+        if entries.is_empty() {
+            continue;
+        }
+        let source_file = path.to_string_lossy().to_string();
+        result.source_files.push(source_file.clone());
+
+        for entry in entries {
+            let hash = learning_hash(&entry.content);
+            if !known.insert(hash.clone()) {
+                result.skipped += 1;
+                continue;
+            }
+            db.execute(
+                "INSERT INTO learnings (id, project_id, session_id, category, content, topic, confidence, status,
+                                        source_file, created_at, updated_at, source, content_hash)
+                 VALUES (?1, ?2, '', ?3, ?4, ?5, 'medium', 'active', ?6, ?7, ?7, 'imported', ?8)",
+                rusqlite::params![
+                    Uuid::new_v4().to_string(),
+                    project_id,
+                    entry.category,
+                    entry.content,
+                    entry.topic,
+                    source_file,
+                    now,
+                    hash
+                ],
+            )
+            .map_err(|e| format!("Failed to save learning: {}", e))?;
+            result.imported += 1;
+        }
+    }
+
+    Ok(result)
+}
+
 // ---------------------------------------------------------------------------
 // update_learning_status
 // ---------------------------------------------------------------------------
@@ -405,9 +674,12 @@ pub async fn update_learning_status(
     let db = state.db.lock().map_err(|e| format!("DB lock error: {}", e))?;
     let now = Utc::now().to_rfc3339();
 
+    // Verifying a learning confirms it is still correct, which resets its staleness clock
     let rows_affected = db
         .execute(
-            "UPDATE learnings SET status = ?1, updated_at = ?2 WHERE id = ?3",
+            "UPDATE learnings SET status = ?1, updated_at = ?2,
+                    last_verified_at = CASE WHEN ?1 = 'verified' THEN ?2 ELSE last_verified_at END
+             WHERE id = ?3",
             rusqlite::params![status, now, id],
         )
         .map_err(|e| format!("Failed to update learning status: {}", e))?;
@@ -419,7 +691,7 @@ pub async fn update_learning_status(
     let learning = db
         .query_row(
             "SELECT id, session_id, category, content, topic, confidence, status, source_file,
-                    created_at, updated_at
+                    created_at, updated_at, source, last_verified_at
              FROM learnings WHERE id = ?1",
             [&id],
             |row| {
@@ -434,6 +706,8 @@ pub async fn update_learning_status(
                     source_file: row.get(7)?,
                     created_at: row.get(8)?,
                     updated_at: row.get(9)?,
+                    source: row.get(10)?,
+                    last_verified_at: row.get(11)?,
                 })
             },
         )
@@ -904,18 +1178,186 @@ pub async fn promote_learning(
     fs::write(&target_path, new_content)
         .map_err(|e| format!("Failed to write to target file: {}", e))?;
 
-    // Mark as verified in DB
+    // Mark as verified in DB and record where it was promoted (staleness review, demotion)
     if table_exists {
         let now = Utc::now().to_rfc3339();
         let _ = db.execute(
-            "UPDATE learnings SET status = 'verified', updated_at = ?1 WHERE id = ?2",
-            rusqlite::params![now, id],
+            "UPDATE learnings SET status = 'verified', updated_at = ?1, promoted_to = ?2, promoted_at = ?1,
+                    last_verified_at = ?1, demoted_at = NULL,
+                    project_id = COALESCE(project_id, (SELECT id FROM projects WHERE path = ?3))
+             WHERE id = ?4",
+            rusqlite::params![now, target, project_path, id],
         );
     }
 
     Ok(())
 }
 
+// ---------------------------------------------------------------------------
+// review_stale_learnings / demote_learning
+// ---------------------------------------------------------------------------
+
+/// List promoted learnings older than `months` (default 6) whose referenced files changed
+/// by at least `min_changed_lines` (default 50) lines, or were deleted, since promotion or
+/// last verification.
+#[tauri::command]
+pub async fn review_stale_learnings(
+    project_id: String,
+    months: Option<u32>,
+    min_changed_lines: Option<u32>,
+    state: State<'_, AppState>,
+) -> Result<Vec<StaleLearning>, String> {
+    let db = state.db.lock().map_err(|e| format!("DB lock error: {}", e))?;
+    let project_path: String = db
+        .query_row("SELECT path FROM projects WHERE id = ?1", [&project_id], |row| row.get(0))
+        .map_err(|e| format!("Project not found: {}", e))?;
+
+    stale_learnings_db(
+        &db,
+        &project_id,
+        Path::new(&project_path),
+        months.unwrap_or(learnings::DEFAULT_STALE_MONTHS),
+        min_changed_lines.unwrap_or(learnings::DEFAULT_MIN_CHANGED_LINES),
+    )
+}
+
+/// Promoted, non-demoted learnings of a project that are past the review age and whose
+/// referenced files changed significantly.
+fn stale_learnings_db(
+    db: &Connection,
+    project_id: &str,
+    project_dir: &Path,
+    months: u32,
+    min_changed_lines: u32,
+) -> Result<Vec<StaleLearning>, String> {
+    let now = Utc::now();
+    let cutoff = (now - chrono::Duration::days(i64::from(months) * 30)).to_rfc3339();
+
+    let mut stmt = db
+        .prepare(
+            "SELECT id, session_id, category, content, topic, confidence, status, source_file,
+                    created_at, updated_at, source, last_verified_at, promoted_to, promoted_at
+             FROM learnings
+             WHERE project_id = ?1 AND promoted_to IS NOT NULL AND demoted_at IS NULL
+               AND COALESCE(last_verified_at, promoted_at) <= ?2
+             ORDER BY COALESCE(last_verified_at, promoted_at) ASC",
+        )
+        .map_err(|e| format!("Failed to prepare learnings query: {}", e))?;
+
+    let candidates: Vec<(Learning, String, String)> = stmt
+        .query_map(rusqlite::params![project_id, cutoff], |row| {
+            Ok((
+                Learning {
+                    id: row.get(0)?,
+                    session_id: row.get(1)?,
+                    category: row.get(2)?,
+                    content: row.get(3)?,
+                    topic: row.get(4)?,
+                    confidence: row.get(5)?,
+                    status: row.get(6)?,
+                    source_file: row.get(7)?,
+                    created_at: row.get(8)?,
+                    updated_at: row.get(9)?,
+                    source: row.get(10)?,
+                    last_verified_at: row.get(11)?,
+                },
+                row.get(12)?,
+                row.get(13)?,
+            ))
+        })
+        .map_err(|e| format!("Failed to query learnings: {}", e))?
+        .filter_map(|r| r.ok())
+        .collect();
+
+    let mut stale = Vec::new();
+    for (learning, promoted_to, promoted_at) in candidates {
+        let since = learning.last_verified_at.clone().unwrap_or_else(|| promoted_at.clone());
+        let paths = learnings::referenced_paths(&learning.content, project_dir, |p| {
+            learnings::in_git_history(project_dir, p)
+        });
+        let changed_files: Vec<_> = paths
+            .iter()
+            .filter_map(|p| learnings::file_change_since(project_dir, p, &since))
+            .filter(|c| c.deleted || c.lines_changed >= min_changed_lines)
+            .collect();
+        if changed_files.is_empty() {
+            continue;
+        }
+
+        let age_days = chrono::DateTime::parse_from_rfc3339(&since)
+            .map(|t| (now - t.with_timezone(&Utc)).num_days().max(0) as u32)
+            .unwrap_or(0);
+        stale.push(StaleLearning { learning, promoted_to, promoted_at, age_days, changed_files });
+    }
+
+    Ok(stale)
+}
+
+/// Remove a promoted learning from the file it was promoted into and mark it deprecated.
+/// The learning row keeps promoted_to/promoted_at and gains demoted_at as provenance.
+#[tauri::command]
+pub async fn demote_learning(
+    id: String,
+    project_path: String,
+    state: State<'_, AppState>,
+) -> Result<LearningDemotion, String> {
+    let db = state.db.lock().map_err(|e| format!("DB lock error: {}", e))?;
+    let demotion = demote_learning_db(&db, &id, Path::new(&project_path))?;
+
+    if let Ok(pid) = db.query_row("SELECT id FROM projects WHERE path = ?1", [&project_path], |row| {
+        row.get::<_, String>(0)
+    }) {
+        events::publish(
+            &db,
+            AppEvent::activity(
+                &pid,
+                ActivityType::Memory,
+                &format!("Demoted stale learning from {}", demotion.file),
+            ),
+        );
+    }
+    Ok(demotion)
+}
+
+fn demote_learning_db(db: &Connection, id: &str, project_dir: &Path) -> Result<LearningDemotion, String> {
+    let (content, promoted_to): (String, Option<String>) = db
+        .query_row("SELECT content, promoted_to FROM learnings WHERE id = ?1", [id], |row| {
+            Ok((row.get(0)?, row.get(1)?))
+        })
+        .map_err(|_| format!("Learning not found: {}", id))?;
+    let promoted_to = promoted_to.ok_or("Learning was never promoted, so there is nothing to demote.")?;
+
+    let target = if promoted_to.starts_with('/') || promoted_to.starts_with('\\') {
+        PathBuf::from(&promoted_to)
+    } else {
+        project_dir.join(&promoted_to)
+    };
+
+    let mut removed_lines = 0;
+    if target.exists() {
+        let existing = fs::read_to_string(&target).map_err(|e| format!("Failed to read target file: {}", e))?;
+        let (updated, removed) = learnings::remove_learning_line(&existing, &content);
+        if removed > 0 {
+            fs::write(&target, updated).map_err(|e| format!("Failed to write target file: {}", e))?;
+        }
+        removed_lines = removed;
+    }
+
+    let now = Utc::now().to_rfc3339();
+    db.execute(
+        "UPDATE learnings SET status = 'deprecated', demoted_at = ?1, updated_at = ?1 WHERE id = ?2",
+        rusqlite::params![now, id],
+    )
+    .map_err(|e| format!("Failed to update learning: {}", e))?;
+
+    Ok(LearningDemotion {
+        learning_id: id.to_string(),
+        file: target.to_string_lossy().to_string(),
+        removed_lines,
+        demoted_at: now,
+    })
+}
+
 // ---------------------------------------------------------------------------
 // append_to_project_file
 // ---------------------------------------------------------------------------
@@ -951,6 +1393,7 @@ pub async fn append_to_project_file(
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::test_support::{memory_db, with_project};
 
     #[test]
     fn test_parse_local_md_learnings_basic() {
@@ -1201,4 +1644,98 @@ mod tests {
             "home-dev-app"
         );
     }
+
+    #[test]
+    fn test_parse_learned_sections() {
+        let content = "# Project\n\n## Learned Patterns\n\n- [Gotcha] Run migrations first | topic:db\n\
+            * Prefer pnpm\n\n```bash\n# Learned\n- not a learning\n```\n\n### Details\n1. Cache the client\n\n\
+            ## Commands\n\n- npm test\n";
+        let entries = parse_learned_sections(content);
+        assert_eq!(
+            entries,
+            vec![
+                ImportedEntry { category: "Gotcha".into(), content: "Run migrations first".into(), topic: Some("db".into()) },
+                ImportedEntry { category: "Pattern".into(), content: "Prefer pnpm".into(), topic: None },
+                ImportedEntry { category: "Pattern".into(), content: "Cache the client".into(), topic: None },
+            ]
+        );
+    }
+
+    #[test]
+    fn test_parse_memory_file() {
+        let fact = "---\nname: use-they\ndescription: Pronoun default\nmetadata:\n  type: feedback\n---\n\n\
+            Use they/them by default.\nApplies everywhere.\n\n**Why:** respect.\n";
+        assert_eq!(
+            parse_memory_file(fact),
+            vec![ImportedEntry {
+                category: "Preference".into(),
+                content: "Use they/them by default. Applies everywhere.".into(),
+                topic: Some("use-they".into()),
+            }]
+        );
+
+        let index = "- [Pronouns](use-they.md) — default\n- Tests live next to the code\n";
+        assert_eq!(parse_memory_file(index).len(), 1);
+        assert_eq!(parse_memory_file(index)[0].content, "Tests live next to the code");
+    }
+
+    #[test]
+    fn test_import_memory_learnings_skips_known_hashes() {
+        let conn = with_project();
+        crate::db::schema::migrate_add_learning_source(&conn).unwrap();
+
+        let dir = tempfile::tempdir().unwrap();
+        fs::create_dir_all(dir.path().join(".claude/memory")).unwrap();
+        fs::write(dir.path().join(".claude/memory/notes.md"), "- Use sqlx offline mode\n- Keep PRs small\n").unwrap();
+        fs::write(dir.path().join("CLAUDE.md"), "# App\n\n# Learned\n\n- keep   PRs small\n- Seed data lives in fixtures/\n")
+            .unwrap();
+
+        let first = import_memory_learnings_db(&conn, "p1", dir.path(), None).unwrap();
+        assert_eq!(first.imported, 3);
+        assert_eq!(first.skipped, 1);
+        assert_eq!(first.source_files.len(), 2);
+
+        let second = import_memory_learnings_db(&conn, "p1", dir.path(), None).unwrap();
+        assert_eq!(second.imported, 0);
+        assert_eq!(second.skipped, 4);
+
+        let source: String = conn
+            .query_row("SELECT source FROM learnings WHERE content = 'Use sqlx offline mode'", [], |row| row.get(0))
+            .unwrap();
+        assert_eq!(source, "imported");
+    }
+
+    #[test]
+    fn test_demote_learning_removes_line_and_keeps_provenance() {
+        let conn = memory_db();
+        crate::db::schema::migrate_add_learning_source(&conn).unwrap();
+        crate::db::schema::migrate_add_learning_promotion(&conn).unwrap();
+        let dir = tempfile::tempdir().unwrap();
+        fs::write(dir.path().join("CLAUDE.md"), "# App\n\n- Use the pool in src/db/pool.rs\n- Keep PRs small\n").unwrap();
+        conn.execute(
+            "INSERT INTO learnings (id, content, status, created_at, updated_at, promoted_to, promoted_at)
+             VALUES ('l1', 'Use the pool in src/db/pool.rs', 'verified', '2025-01-01', '2025-01-01', 'CLAUDE.md', '2025-01-01')",
+            [],
+        )
+        .unwrap();
+
+        let demotion = demote_learning_db(&conn, "l1", dir.path()).unwrap();
+        assert_eq!(demotion.removed_lines, 1);
+        assert_eq!(fs::read_to_string(dir.path().join("CLAUDE.md")).unwrap(), "# App\n\n- Keep PRs small\n");
+
+        let (status, promoted_to, demoted_at): (String, String, Option<String>) = conn
+            .query_row("SELECT status, promoted_to, demoted_at FROM learnings WHERE id = 'l1'", [], |row| {
+                Ok((row.get(0)?, row.get(1)?, row.get(2)?))
+            })
+            .unwrap();
+        assert_eq!(status, "deprecated");
+        assert_eq!(promoted_to, "CLAUDE.md");
+        assert!(demoted_at.is_some());
+
+        // Demoted learnings are no longer candidates for review
+        assert!(stale_learnings_db(&conn, "p1", dir.path(), 0, 1).unwrap().is_empty());
+        conn.execute("INSERT INTO learnings (id, content, created_at, updated_at) VALUES ('l2', 'x', 'a', 'a')", [])
+            .unwrap();
+        assert!(demote_learning_db(&conn, "l2", dir.path()).is_err());
+    }
 }
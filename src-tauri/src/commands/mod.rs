@@ -8,7 +8,7 @@
 //! EXPORTS:
 //! - project - Project CRUD commands
 //! - onboarding - Setup wizard commands
-//! - claude_md - CLAUDE.md operations
+//! - claude_md - CLAUDE.md operations, including reviewable AI diff edits
 //! - modules - Module documentation commands
 //! - freshness - Staleness detection commands
 //! - skills - Skills management commands
@@ -22,6 +22,36 @@
 //! - test_plans - Test plan management and TDD workflow commands
 //! - session_analysis - AI-powered session transcript analysis
 //! - memory - Memory management commands (sources, learnings, health, analysis)
+//! - versions - Skill and agent version history (list, diff, rollback)
+//! - windows - Detachable RALPH loop / test run monitor windows
+//! - shared_db - Team-shared database configuration and sync
+//! - imports - Knowledge bundle import and import conflict resolution
+//! - slash_commands - Deploy skills/prompts as Claude Code slash commands
+//! - health_history - Health snapshots per git commit and regression detection
+//! - ralph_templates - Reusable RALPH loop templates
+//! - command_guard - Per-project allowlist for PRD and template commands
+//! - readme - README section generation with diff preview
+//! - remote - Remote (ssh) projects: local mirror sync and remote hook install
+//! - ralph_patches - AI-proposed patches for located RALPH issues (propose, review, apply)
+//! - data_purge - Dry-run and purge of stored analyses, learnings, loop outcomes, and activities
+//! - doc_proposals - Approval queue for watcher/freshness doc header proposals
+//! - git_policy - Per-project git permissions and answers to git confirmation prompts
+//! - ai_queue - AI work queue status, pause/resume, concurrency, and job priorities
+//! - rules - Automation rules (list, create, simulate, enable, delete) and their runs
+//! - logs - Application log viewer
+//! - doctor - Self-diagnostics report and one-click fixes
+//! - policy - Organization policy packs (import, apply, compliance audit)
+//! - claude_backups - List and restore .claude directory backups
+//! - plugins - Installed analyzer plugins and reload
+//! - sync - Encrypted settings and knowledge sync through an S3, WebDAV, or git remote
+//! - ai_audit - Per-project AI prompt audit log (opt-in, query, export)
+//! - digest - Weekly project digests (on demand, list, background scheduler)
+//! - bootstrap - Resumable, cost-estimated nested CLAUDE.md bootstrap for undocumented repos
+//! - pr_health - Markdown health block (coverage delta, newly stale docs, test plans) for pull requests
+//! - git_forge - Per-project forge (GitHub, GitLab, Bitbucket) settings and detection
+//! - ralph_workspace - PRD runs whose stories span several registered projects
+//! - conventions - CLAUDE.md convention rules and codebase violation checks
+//! - health_alerts - Per-project health alert thresholds and alert state
 //!
 //! PATTERNS:
 //! - Each submodule contains #[tauri::command] functions
@@ -50,3 +80,33 @@ pub mod session_analysis;
 pub mod team_templates;
 pub mod memory;
 pub mod performance;
+pub mod versions;
+pub mod windows;
+pub mod shared_db;
+pub mod imports;
+pub mod slash_commands;
+pub mod health_history;
+pub mod ralph_templates;
+pub mod command_guard;
+pub mod readme;
+pub mod remote;
+pub mod ralph_patches;
+pub mod data_purge;
+pub mod doc_proposals;
+pub mod git_policy;
+pub mod ai_queue;
+pub mod rules;
+pub mod logs;
+pub mod doctor;
+pub mod policy;
+pub mod claude_backups;
+pub mod plugins;
+pub mod sync;
+pub mod ai_audit;
+pub mod digest;
+pub mod bootstrap;
+pub mod pr_health;
+pub mod git_forge;
+pub mod ralph_workspace;
+pub mod conventions;
+pub mod health_alerts;
@@ -6,10 +6,15 @@
 //! - Generate documentation for individual files
 //! - Apply generated documentation to files
 //! - Batch generate documentation for multiple files
+//! - Sync a header's EXPORTS section with detected exports without regenerating it
 //!
 //! DEPENDENCIES:
 //! - tauri - Command macro and State
 //! - core::analyzer - Module scanning, doc generation, doc application
+//! - core::events - scan_completed, doc_applied, and activity events
+//! - core::health - Per-file quality scores attached to scan results
+//! - core::patch - Configured patch engine for doc header writes
+//! - core::claude_backup - .claude snapshot before set_ai_excludes rewrites .claude/ai-exclude
 //! - models::module_doc - ModuleStatus, ModuleDoc types
 //!
 //! EXPORTS:
@@ -18,14 +23,23 @@
 //! - generate_module_doc - Generate a doc template for a single file (uses AI if available)
 //! - apply_module_doc - Write a doc header to a file
 //! - batch_generate_docs - Generate and apply docs to multiple files
+//! - auto_sync_exports - Update only the EXPORTS section of a file's header in place
+//! - get_generated_overrides - Read paths exempt from generated-file detection
+//! - set_generated_overrides - Replace the project's generated-file override list
+//! - get_ai_excludes - Read the project's "never send to AI" globs
+//! - set_ai_excludes - Replace the project's "never send to AI" globs
 //!
 //! PATTERNS:
 //! - All commands are async and return Result<T, String>
-//! - scan_modules returns Vec<ModuleStatus> for the file tree UI
+//! - scan_modules returns Vec<ModuleStatus> for the file tree UI and publishes scan_completed;
+//!   each module carries a quality score so the table can sort worst files first
 //! - parse_module_doc is fast (local only) - use for instant preview of existing docs
 //! - generate_module_doc is slow (AI call) - use when generating new docs
 //! - apply_module_doc writes the doc header to the actual file
 //! - batch_generate_docs combines generate + apply for multiple files
+//! - auto_sync_exports is local only (no AI) and leaves other header sections untouched
+//! - Generated-file overrides live in <project>/.claude/generated-overrides so git hooks can read them
+//! - AI exclusions live in <project>/.claude/ai-exclude; matched files get template docs only
 //!
 //! CLAUDE NOTES:
 //! - Commands registered in lib.rs invoke_handler
@@ -35,15 +49,43 @@
 use tauri::State;
 
 use crate::core::ai;
+use crate::core::claude_backup;
 use crate::core::analyzer;
-use crate::db::{self, AppState};
-use crate::models::module_doc::{ModuleDoc, ModuleStatus};
+use crate::core::events::{self, AppEvent};
+use crate::core::health;
+use crate::core::patch::{self, PatchEngine};
+use crate::db::AppState;
+use crate::models::activity::ActivityType;
+use crate::models::module_doc::{ExportSyncResult, ModuleDoc, ModuleStatus};
 
 /// Scan all source files in a project and return their documentation status.
 /// Used by the file tree UI to show status icons (current/missing).
 #[tauri::command]
-pub async fn scan_modules(project_path: String) -> Result<Vec<ModuleStatus>, String> {
-    analyzer::scan_all_modules(&project_path)
+pub async fn scan_modules(
+    project_path: String,
+    state: State<'_, AppState>,
+) -> Result<Vec<ModuleStatus>, String> {
+    let mut modules = analyzer::scan_all_modules(&project_path)?;
+    health::attach_module_quality(&project_path, &mut modules);
+
+    if let Ok(db) = state.db.lock() {
+        let project_id = db
+            .query_row("SELECT id FROM projects WHERE path = ?1", [&project_path], |row| row.get(0))
+            .ok();
+        let count = |status: &str| modules.iter().filter(|m| m.status == status).count() as u32;
+        events::publish(
+            &db,
+            AppEvent::ScanCompleted {
+                project_id,
+                project_path: project_path.clone(),
+                total: modules.len() as u32,
+                missing: count("missing"),
+                outdated: count("outdated"),
+            },
+        );
+    }
+
+    Ok(modules)
 }
 
 /// Parse and return the existing documentation header from a file.
@@ -54,8 +96,7 @@ pub async fn parse_module_doc(
     file_path: String,
     project_path: String,
 ) -> Result<Option<ModuleDoc>, String> {
-    let content = std::fs::read_to_string(&file_path)
-        .map_err(|e| format!("Failed to read {}: {}", file_path, e))?;
+    let content = analyzer::read_source(&file_path)?;
 
     // Parse existing doc header (returns None if no valid header)
     if let Some(mut doc) = analyzer::parse_doc_header(&content) {
@@ -95,8 +136,7 @@ pub async fn generate_module_doc(
     };
 
     if let Ok(api_key) = api_key_result {
-        let content = std::fs::read_to_string(&file_path)
-            .map_err(|e| format!("Failed to read {}: {}", file_path, e))?;
+        let content = analyzer::read_source(&file_path)?;
 
         let ext = std::path::Path::new(&file_path)
             .extension()
@@ -135,44 +175,142 @@ pub async fn apply_module_doc(
     doc: ModuleDoc,
     state: State<'_, AppState>,
 ) -> Result<(), String> {
-    analyzer::apply_doc_to_file(&file_path, &doc)?;
-
-    // Log activity
-    let filename = std::path::Path::new(&file_path)
-        .file_name()
-        .and_then(|n| n.to_str())
-        .unwrap_or("file");
-    // Log activity (best-effort, non-critical)
-    match state.db.lock() {
-        Ok(db) => {
-            let mut stmt = db
-                .prepare("SELECT id, path FROM projects")
-                .ok();
-            if let Some(ref mut s) = stmt {
-                let _ = s
-                    .query_map([], |row| {
-                        Ok((row.get::<_, String>(0)?, row.get::<_, String>(1)?))
+    // The owning project decides the patch engine and the root the diff is relative to
+    let (project, engine) = {
+        let db = state.db.lock().map_err(|e| format!("DB lock error: {}", e))?;
+        let project = db
+            .prepare("SELECT id, path FROM projects")
+            .and_then(|mut stmt| {
+                stmt.query_map([], |row| Ok((row.get::<_, String>(0)?, row.get::<_, String>(1)?)))
+                    .map(|rows| {
+                        rows.flatten()
+                            .find(|(_, path)| std::path::Path::new(&file_path).starts_with(path))
                     })
+            })
+            .ok()
+            .flatten();
+        (project, patch::engine(&db))
+    };
+
+    match &project {
+        Some((_, project_path)) => analyzer::apply_doc_to_file(
+            engine,
+            std::path::Path::new(project_path),
+            &file_path,
+            &doc,
+        )?,
+        None => {
+            // Not inside a known project: patch relative to the file's own directory
+            let parent = std::path::Path::new(&file_path)
+                .parent()
+                .ok_or_else(|| format!("Invalid file path: {}", file_path))?;
+            analyzer::apply_doc_to_file(PatchEngine::Builtin, parent, &file_path, &doc)?
+        }
+    }
+
+    // Publish doc_applied (best-effort, non-critical)
+    if let Some((project_id, _)) = project {
+        match state.db.lock() {
+            Ok(db) => events::publish(
+                &db,
+                AppEvent::DocApplied {
+                    project_id,
+                    file_path: file_path.clone(),
+                },
+            ),
+            Err(e) => tracing::warn!(error = %e, "Failed to lock DB for activity logging"),
+        }
+    }
+
+    Ok(())
+}
+
+/// Update only the EXPORTS section of a file's doc header to match the
+/// exports detected in code. Existing entry descriptions are kept; other
+/// header sections and formatting are preserved.
+#[tauri::command]
+pub async fn auto_sync_exports(
+    file_path: String,
+    state: State<'_, AppState>,
+) -> Result<ExportSyncResult, String> {
+    let result = analyzer::sync_exports_in_file(&file_path)?;
+
+    if result.updated {
+        let filename = std::path::Path::new(&file_path)
+            .file_name()
+            .and_then(|n| n.to_str())
+            .unwrap_or("file");
+        // Log activity (best-effort, non-critical)
+        match state.db.lock() {
+            Ok(db) => {
+                let project_id: Option<String> = db
+                    .prepare("SELECT id, path FROM projects")
                     .ok()
-                    .map(|rows| {
-                        for r in rows.flatten() {
-                            if file_path.starts_with(&r.1) {
-                                let _ = db::log_activity_db(
-                                    &db,
-                                    &r.0,
-                                    "generate",
-                                    &format!("Applied docs to {}", filename),
-                                );
-                                break;
-                            }
-                        }
+                    .and_then(|mut s| {
+                        s.query_map([], |row| {
+                            Ok((row.get::<_, String>(0)?, row.get::<_, String>(1)?))
+                        })
+                        .ok()
+                        .and_then(|rows| {
+                            rows.flatten()
+                                .find(|r| file_path.starts_with(&r.1))
+                                .map(|r| r.0)
+                        })
                     });
+                if let Some(pid) = project_id {
+                    events::publish(
+                        &db,
+                        AppEvent::activity(
+                            &pid,
+                            ActivityType::Edit,
+                            &format!(
+                                "Synced exports in {} (+{} / -{})",
+                                filename,
+                                result.added.len(),
+                                result.removed.len()
+                            ),
+                        ),
+                    );
+                }
             }
+            Err(e) => tracing::warn!(error = %e, "Failed to lock DB for activity logging"),
         }
-        Err(e) => eprintln!("Failed to lock DB for activity logging: {}", e),
     }
 
-    Ok(())
+    Ok(result)
+}
+
+/// List project-relative paths that are documented even though they look
+/// generated or vendored.
+#[tauri::command]
+pub async fn get_generated_overrides(project_path: String) -> Result<Vec<String>, String> {
+    Ok(analyzer::load_generated_overrides(&project_path))
+}
+
+/// Replace the project's generated-file override list. Listed files are
+/// included in doc coverage, freshness, and hook checks.
+#[tauri::command]
+pub async fn set_generated_overrides(
+    project_path: String,
+    paths: Vec<String>,
+) -> Result<Vec<String>, String> {
+    analyzer::save_generated_overrides(&project_path, &paths)?;
+    Ok(analyzer::load_generated_overrides(&project_path))
+}
+
+/// List the project's "never send to AI" globs.
+#[tauri::command]
+pub async fn get_ai_excludes(project_path: String) -> Result<Vec<String>, String> {
+    Ok(ai::load_ai_excludes(&project_path))
+}
+
+/// Replace the project's "never send to AI" globs. Matching files are
+/// documented from templates and never included in API requests.
+#[tauri::command]
+pub async fn set_ai_excludes(project_path: String, globs: Vec<String>) -> Result<Vec<String>, String> {
+    claude_backup::snapshot(&project_path, "Before editing AI exclusions")?;
+    ai::save_ai_excludes(&project_path, &globs)?;
+    Ok(ai::load_ai_excludes(&project_path))
 }
 
 /// Batch generate and apply documentation for multiple files.
@@ -184,20 +322,35 @@ pub async fn batch_generate_docs(
     project_path: String,
     state: State<'_, AppState>,
 ) -> Result<Vec<ModuleStatus>, String> {
-    let api_key_result = {
+    let (api_key_result, engine) = {
         let db = state.db.lock().map_err(|e| format!("DB lock error: {}", e))?;
-        ai::get_api_key(&db)
+        (ai::get_api_key(&db), patch::engine(&db))
     };
 
     let mut results = Vec::new();
 
     for file_path in &file_paths {
+        // Files opted out with the in-file marker are never auto-documented
+        if analyzer::read_source(file_path).is_ok_and(|content| analyzer::is_ignored_content(&content)) {
+            results.push(ModuleStatus {
+                path: file_path.clone(),
+                status: "ignored".to_string(),
+                freshness_score: 100,
+                changes: None,
+                suggested_doc: None,
+                quality: None,
+                analyzer: None,
+                change_authors: None,
+            });
+            continue;
+        }
+
         let doc_result = if let Ok(ref api_key) = api_key_result {
-            // Try AI generation — skip files >2MB to prevent OOM
+            // Try AI generation — skip oversized files to prevent OOM
             let content = std::fs::metadata(file_path)
                 .ok()
-                .filter(|m| m.len() <= 2_000_000)
-                .and_then(|_| std::fs::read_to_string(file_path).ok());
+                .filter(|m| m.len() <= analyzer::max_source_bytes(file_path))
+                .and_then(|_| analyzer::read_source(file_path).ok());
             if let Some(content) = content {
                 let ext = std::path::Path::new(file_path)
                     .extension()
@@ -229,13 +382,21 @@ pub async fn batch_generate_docs(
 
         match doc_result {
             Ok(doc) => {
-                if let Err(e) = analyzer::apply_doc_to_file(file_path, &doc) {
+                if let Err(e) = analyzer::apply_doc_to_file(
+                    engine,
+                    std::path::Path::new(&project_path),
+                    file_path,
+                    &doc,
+                ) {
                     results.push(ModuleStatus {
                         path: file_path.clone(),
                         status: "missing".to_string(),
                         freshness_score: 0,
                         changes: Some(vec![format!("Failed to apply: {}", e)]),
                         suggested_doc: Some(doc),
+                        quality: None,
+                        analyzer: None,
+                        change_authors: None,
                     });
                 } else {
                     results.push(ModuleStatus {
@@ -244,6 +405,9 @@ pub async fn batch_generate_docs(
                         freshness_score: 100,
                         changes: None,
                         suggested_doc: None,
+                        quality: None,
+                        analyzer: None,
+                        change_authors: None,
                     });
                 }
             }
@@ -254,6 +418,9 @@ pub async fn batch_generate_docs(
                     freshness_score: 0,
                     changes: Some(vec![format!("Failed to generate: {}", e)]),
                     suggested_doc: None,
+                    quality: None,
+                    analyzer: None,
+                    change_authors: None,
                 });
             }
         }
@@ -268,16 +435,59 @@ pub async fn batch_generate_docs(
                 [&project_path],
                 |row| row.get::<_, String>(0),
             ) {
-                let _ = db::log_activity_db(
+                events::publish(
                     &db,
-                    &pid,
-                    "generate",
-                    &format!("Generated docs for {} files", count),
+                    AppEvent::activity(
+                        &pid,
+                        ActivityType::Generate,
+                        &format!("Generated docs for {} files", count),
+                    ),
                 );
             }
         }
-        Err(e) => eprintln!("Failed to lock DB for activity logging: {}", e),
+        Err(e) => tracing::warn!(error = %e, "Failed to lock DB for activity logging"),
     }
 
     Ok(results)
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_support::{assert_snapshot, TestEnv};
+
+    #[tokio::test]
+    async fn test_batch_generate_docs_end_to_end() {
+        let env = TestEnv::new();
+        let (_, project_path) = env.add_project("web");
+        let file = env.write_file(
+            "web/src/utils/math.ts",
+            "export function add(a: number, b: number): number {\n  return a + b;\n}\n",
+        );
+        let _api = env
+            .mock_anthropic(&[r#"{
+                "description": "Arithmetic helpers shared by the checkout totals",
+                "purpose": ["Add two numbers without coercion"],
+                "dependencies": [],
+                "exports": ["add (function) - Returns the sum of a and b"],
+                "patterns": ["Call add(a, b) with numbers only"],
+                "claude_notes": ["No rounding; callers format currency"]
+            }"#])
+            .await;
+
+        let file_path = file.to_string_lossy().to_string();
+        let results = batch_generate_docs(vec![file_path.clone()], project_path, env.state()).await.unwrap();
+        assert_eq!(results.len(), 1);
+        assert_eq!(results[0].status, "current");
+
+        let written = std::fs::read_to_string(&file).unwrap();
+        assert!(written.contains("Arithmetic helpers shared by the checkout totals"));
+        assert!(written.ends_with("export function add(a: number, b: number): number {\n  return a + b;\n}\n"));
+
+        let header: Vec<&str> = written.lines().take_while(|l| !l.starts_with("export")).collect();
+        assert_snapshot(
+            "batch_generate_docs",
+            &env.redact(&serde_json::json!({ "results": results, "header": header })),
+        );
+    }
+}
@@ -9,6 +9,7 @@
 //! DEPENDENCIES:
 //! - tauri - Command macro and State
 //! - core::scanner - Project detection logic
+//! - core::package_manager - Package manager stored on the project
 //! - db - AppState with database connection
 //! - models::project - DetectionResult, ProjectSetup types
 //!
@@ -28,17 +29,27 @@
 //! - save_project creates the database record, auto-adds Skeptical Reviewer, and installs git hooks if setup_enforcement is true
 //! - If setup_enforcement is true but no .git exists, git is auto-initialized first (great for new projects)
 //! - Git hooks use "auto-update" mode (generates docs automatically at commit time)
+//! - Remote (ssh) projects pass remote_host/remote_path with path set to the local mirror;
+//!   their hook is installed on the remote side in "warn" mode
+//! - save_project stores setup.package_manager when valid, else the one detected from the files
 //! - API key is mandatory, so auto-update hooks always work
 //! - See spec Part 2 for the full onboarding flow
 //! - Skeptical Reviewer is auto-added to help catch issues in every new project
 
+use std::path::Path;
+
 use chrono::Utc;
 use tauri::State;
 use uuid::Uuid;
 
 use crate::commands::enforcement::install_git_hooks_internal;
+use crate::commands::remote as remote_cmd;
+use crate::core::package_manager::{self, PackageManager};
+use crate::core::remote::RemoteTarget;
 use crate::core::scanner;
-use crate::db::{self, AppState};
+use crate::core::events::{self, AppEvent};
+use crate::db::AppState;
+use crate::models::activity::ActivityType;
 use crate::models::project::{DetectionResult, Project, ProjectSetup};
 
 #[tauri::command]
@@ -51,6 +62,19 @@ pub async fn save_project(
     setup: ProjectSetup,
     state: State<'_, AppState>,
 ) -> Result<Project, String> {
+    let remote = match &setup.remote_host {
+        Some(host) => Some(RemoteTarget::new(host, setup.remote_path.as_deref().unwrap_or_default())?),
+        None => None,
+    };
+    let location_type = if remote.is_some() { "ssh" } else { "local" };
+    // Keep the wizard's package manager, or detect it from the (mirrored) project files
+    let package_manager = setup
+        .package_manager
+        .as_deref()
+        .and_then(PackageManager::parse)
+        .or_else(|| package_manager::detect(Path::new(&setup.path)).map(|(pm, _)| pm))
+        .map(|pm| pm.as_str().to_string());
+
     let db = state.db.lock().map_err(|e| format!("DB lock error: {}", e))?;
     let now = Utc::now();
     let id = Uuid::new_v4().to_string();
@@ -62,8 +86,9 @@ pub async fn save_project(
         .map(|e| serde_json::to_string(e).unwrap_or_default());
 
     db.execute(
-        "INSERT INTO projects (id, name, path, description, project_type, language, framework, database_tech, testing, styling, stack_extras, health_score, created_at)
-         VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7, ?8, ?9, ?10, ?11, ?12, ?13)",
+        "INSERT INTO projects (id, name, path, description, project_type, language, framework, database_tech, testing, styling, stack_extras, health_score, created_at,
+                               location_type, remote_host, remote_path, package_manager)
+         VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7, ?8, ?9, ?10, ?11, ?12, ?13, ?14, ?15, ?16, ?17)",
         rusqlite::params![
             &id,
             &setup.name,
@@ -78,6 +103,10 @@ pub async fn save_project(
             &extras_json,
             0,
             now.to_rfc3339(),
+            location_type,
+            remote.as_ref().map(|r| &r.host),
+            remote.as_ref().map(|r| &r.path),
+            &package_manager,
         ],
     )
     .map_err(|e| format!("Failed to insert project: {}", e))?;
@@ -96,10 +125,14 @@ pub async fn save_project(
         stack_extras: setup.stack_extras,
         health_score: 0,
         created_at: now,
+        location_type: location_type.to_string(),
+        remote_host: remote.as_ref().map(|r| r.host.clone()),
+        remote_path: remote.as_ref().map(|r| r.path.clone()),
+        package_manager,
     };
 
     // Log activity
-    let _ = db::log_activity_db(&db, &id, "scan", &format!("Project added: {}", &project.name));
+    events::publish(&db, AppEvent::activity(&id, ActivityType::Scan, &format!("Project added: {}", &project.name)));
 
     // Auto-add the Skeptical Reviewer agent to new projects
     let _ = add_default_agents(&db, &id);
@@ -107,7 +140,15 @@ pub async fn save_project(
     // Auto-install git hooks if setup_enforcement is enabled (one-click setup!)
     // Uses "auto-update" mode - automatically generates docs for undocumented files at commit
     // API key is required, so auto-update will always work
-    if setup.setup_enforcement {
+    if let (true, Some(target)) = (setup.setup_enforcement, &remote) {
+        // The auto-update hook needs the API key on the remote machine, so remote projects warn
+        match remote_cmd::install_remote_hook(target, "warn") {
+            Ok(_) => {
+                events::publish(&db, AppEvent::activity(&id, ActivityType::Enforcement, "Installed remote git hooks (warn)"));
+            }
+            Err(e) => tracing::warn!(error = %e, "Failed to install remote git hooks"),
+        }
+    } else if setup.setup_enforcement {
         // First, check if git is installed
         let git_available = std::process::Command::new("git")
             .args(["--version"])
@@ -131,13 +172,13 @@ pub async fn save_project(
                 .output()
             {
                 Ok(output) if output.status.success() => {
-                    let _ = db::log_activity_db(&db, &id, "enforcement", "Auto-initialized git repository");
+                    events::publish(&db, AppEvent::activity(&id, ActivityType::Enforcement, "Auto-initialized git repository"));
                 }
                 Ok(output) => {
-                    eprintln!("git init failed: {}", String::from_utf8_lossy(&output.stderr));
+                    tracing::warn!(stderr = %String::from_utf8_lossy(&output.stderr), "git init failed");
                 }
                 Err(e) => {
-                    eprintln!("Failed to run git init: {}", e);
+                    tracing::warn!(error = %e, "Failed to run git init");
                 }
             }
         }
@@ -145,10 +186,10 @@ pub async fn save_project(
         // Install auto-update hooks (API key is mandatory, so this will work)
         match install_git_hooks_internal(&project.path, "auto-update", Some(&db)) {
             Ok(()) => {
-                let _ = db::log_activity_db(&db, &id, "enforcement", "Auto-installed git hooks (auto-update)");
+                events::publish(&db, AppEvent::activity(&id, ActivityType::Enforcement, "Auto-installed git hooks (auto-update)"));
             }
             Err(e) => {
-                eprintln!("Failed to install git hooks: {}", e);
+                tracing::warn!(error = %e, "Failed to install git hooks");
             }
         }
     }
@@ -225,7 +266,7 @@ For each issue found:
     )
     .map_err(|e| format!("Failed to add default agent: {}", e))?;
 
-    let _ = db::log_activity_db(db, project_id, "generate", "Auto-added Skeptical Reviewer agent");
+    events::publish(db, AppEvent::activity(project_id, ActivityType::Generate, "Auto-added Skeptical Reviewer agent"));
 
     Ok(())
 }
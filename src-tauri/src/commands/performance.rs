@@ -6,12 +6,16 @@
 //! - Store and retrieve performance reviews from database
 //! - List and delete performance review history
 //! - Auto-remediate performance issues via AI for a single file
+//! - Audit the app's own SQLite query plans and indexes
 //!
 //! DEPENDENCIES:
 //! - tauri - Command macro and State
 //! - db::AppState - Database connection
 //! - core::performance - Analysis engine
 //! - core::ai - Claude API calls for remediation
+//! - core::ai_queue - Prioritized slot for each Claude call
+//! - core::ai_audit - Audit scope for remediation calls
+//! - core::query_plan - EXPLAIN QUERY PLAN audit of the app database
 //! - models::performance - PerformanceReview, PerformanceIssue, RemediationResult types
 //!
 //! EXPORTS:
@@ -20,6 +24,7 @@
 //! - get_performance_review - Get a single review by ID
 //! - delete_performance_review - Delete a review by ID
 //! - remediate_performance_file - Fix performance issues in a single file via AI
+//! - analyze_db_performance - Query plans of hot app queries and missing indexes
 //!
 //! PATTERNS:
 //! - All commands are async and return Result<T, String>
@@ -34,9 +39,15 @@
 
 use tauri::State;
 
+use crate::core::ai_audit;
+use crate::core::ai_queue;
 use crate::core::performance;
+use crate::core::query_plan;
+use crate::core::events::{self, AppEvent};
 use crate::db::AppState;
-use crate::models::performance::{PerformanceIssue, PerformanceReview, RemediationResult};
+use crate::models::activity::ActivityType;
+use crate::models::ai_queue::AiJobKind;
+use crate::models::performance::{DbPerformanceReport, PerformanceIssue, PerformanceReview, RemediationResult};
 
 /// Run performance analysis on a project, store the result, and return it.
 #[tauri::command]
@@ -93,11 +104,13 @@ pub async fn analyze_performance(
         .map_err(|e| format!("Failed to store performance review: {}", e))?;
 
         // Log activity
-        let _ = crate::db::log_activity_db(
+        events::publish(
             &db,
-            &review.project_id,
-            "analyze",
-            &format!("Performance analysis completed (score: {})", review.overall_score),
+            AppEvent::activity(
+                &review.project_id,
+                ActivityType::Health,
+                &format!("Performance analysis completed (score: {})", review.overall_score),
+            ),
         );
     }
 
@@ -234,6 +247,14 @@ pub async fn delete_performance_review(
     Ok(())
 }
 
+/// Audit the app database: EXPLAIN QUERY PLAN for the hot list queries and any
+/// expected indexes that are missing.
+#[tauri::command]
+pub async fn analyze_db_performance(state: State<'_, AppState>) -> Result<DbPerformanceReport, String> {
+    let db = state.db.lock().map_err(|e| format!("DB lock error: {}", e))?;
+    query_plan::analyze(&db)
+}
+
 /// Strip markdown code fences from AI response.
 /// Handles ```lang\n...\n``` and bare ``` fences.
 fn strip_code_fences(text: &str) -> String {
@@ -290,6 +311,19 @@ pub async fn remediate_performance_file(
         format!("{}/{}", project_path, file_path)
     };
 
+    // Files on the project's "never send to AI" list are left for manual fixes
+    if let Err(reason) = ai::ensure_ai_allowed(&project_path, &abs_path) {
+        return Ok(issues
+            .iter()
+            .map(|issue| RemediationResult {
+                issue_id: issue.id.clone(),
+                file_path: file_path.clone(),
+                status: "skipped".to_string(),
+                message: reason.clone(),
+            })
+            .collect());
+    }
+
     // Check file size
     let metadata = std::fs::metadata(&abs_path)
         .map_err(|e| format!("Cannot read file {}: {}", abs_path, e))?;
@@ -331,13 +365,12 @@ pub async fn remediate_performance_file(
     );
 
     // Call AI
-    let response = ai::call_claude_long(
-        &state.http_client,
-        &api_key,
-        system_prompt,
-        &user_prompt,
-    )
-    .await?;
+    let call = ai_queue::run(
+        AiJobKind::Interactive,
+        &file_path,
+        ai::call_claude_long(&state.http_client, &api_key, system_prompt, &user_prompt),
+    );
+    let response = ai_audit::scoped(&project_path, "performance remediation", call).await?;
 
     // Strip code fences if present
     let corrected = strip_code_fences(&response);
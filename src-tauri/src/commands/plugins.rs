@@ -0,0 +1,36 @@
+//! @module commands/plugins
+//! @description Tauri IPC commands for custom analyzer plugins
+//!
+//! PURPOSE:
+//! - Show which analyzer plugins are installed and which manifests were rejected
+//! - Re-discover plugins after the user adds or edits one
+//!
+//! DEPENDENCIES:
+//! - tauri - Command macro
+//! - core::plugins - Discovery, validation, and the installed plugin set
+//! - models::plugin - PluginScanReport
+//!
+//! EXPORTS:
+//! - list_analyzer_plugins - The plugins discovered at startup (or the last reload)
+//! - reload_analyzer_plugins - Re-scan ~/.project-jumpstart/plugins and install the result
+//!
+//! PATTERNS:
+//! - Plugins are discovered once at startup (lib.rs setup); edits take effect on reload
+//!
+//! CLAUDE NOTES:
+//! - Reloading clears cached plugin analyses, so the next scan re-runs every plugin
+
+use crate::core::plugins;
+use crate::models::plugin::PluginScanReport;
+
+/// Installed analyzer plugins and rejected manifests.
+#[tauri::command]
+pub async fn list_analyzer_plugins() -> Result<PluginScanReport, String> {
+    Ok((*plugins::report()).clone())
+}
+
+/// Re-discover analyzer plugins and install them.
+#[tauri::command]
+pub async fn reload_analyzer_plugins() -> Result<PluginScanReport, String> {
+    Ok(plugins::load())
+}
@@ -0,0 +1,264 @@
+//! @module commands/policy
+//! @description Tauri IPC commands for organization policy packs
+//!
+//! PURPOSE:
+//! - Import, list, and delete policy packs shared by platform teams
+//! - Assign a pack to a project and bring the project's enforcement into line with it
+//! - Audit a project against its assigned pack
+//!
+//! DEPENDENCIES:
+//! - tauri - Command macro and State
+//! - db::AppState - Database connection (policy_packs, settings)
+//! - core::policy - Pack parsing, CI check detection, and compliance evaluation
+//! - core::health - DocHealthCache for current doc coverage
+//! - core::ai - .claude/ai-exclude read/write for protected paths
+//! - core::claude_backup - .claude snapshot before ai-exclude is rewritten
+//! - core::doc_goals - CI fail-under threshold for the generated workflow
+//! - core::scanner - Source layout the generated workflow scans
+//! - commands::enforcement - Hook install, hook status, and the GitHub Actions doc check
+//! - commands::health_history - Doc goals read/write
+//! - commands::project - load_project
+//! - models::policy - PolicyPack, PolicyComplianceReport
+//!
+//! EXPORTS:
+//! - import_policy_pack - Parse a pack file's JSON and store it (replacing a pack with the same id)
+//! - list_policy_packs - Stored packs by name
+//! - delete_policy_pack - Remove a pack and unassign it from every project
+//! - get_project_policy_pack - The pack assigned to a project, if any
+//! - apply_policy_pack - Assign a pack and apply its fixable requirements
+//! - audit_policy_compliance - Compare a project against its assigned pack
+//!
+//! PATTERNS:
+//! - The assignment lives in settings under "policy_pack.<project_id>" (the pack id)
+//! - apply_policy_pack installs the required hook, writes .github/workflows/doc-check.yml when no
+//!   CI doc check exists, raises the doc coverage goal (CI-enforced when the pack requires CI),
+//!   and appends missing protected paths to .claude/ai-exclude; it never lowers a stricter goal
+//! - Coverage itself cannot be applied; audits report it until the docs catch up
+//!
+//! CLAUDE NOTES:
+//! - A GitLab-only project gets no generated CI file (editing .gitlab-ci.yml safely needs a
+//!   YAML merge); its ci_check stays non-compliant until the stage from get_ci_snippets is added
+//! - Every apply and non-compliant audit is logged as an enforcement activity
+
+use std::path::Path;
+
+use chrono::Utc;
+use rusqlite::{Connection, OptionalExtension};
+use tauri::State;
+
+use crate::commands::{enforcement, health_history, project};
+use crate::core::events::{self, AppEvent};
+use crate::core::health::DocHealthCache;
+use crate::core::policy::{self, PolicyFacts};
+use crate::core::{ai, claude_backup, doc_goals, scanner};
+use crate::db::AppState;
+use crate::models::activity::ActivityType;
+use crate::models::policy::{PolicyComplianceReport, PolicyPack};
+
+/// Settings key prefix for a project's assigned pack id.
+const POLICY_SETTING_PREFIX: &str = "policy_pack.";
+
+/// Parse a policy pack file's content and store it. A pack with the same id is replaced.
+#[tauri::command]
+pub async fn import_policy_pack(content: String, state: State<'_, AppState>) -> Result<PolicyPack, String> {
+    let pack = policy::parse_pack(&content)?;
+    let json = serde_json::to_string(&pack).map_err(|e| format!("Failed to serialize policy pack: {}", e))?;
+    let db = state.db.lock().map_err(|e| format!("DB lock error: {}", e))?;
+    db.execute(
+        "INSERT OR REPLACE INTO policy_packs (id, name, pack, imported_at) VALUES (?1, ?2, ?3, ?4)",
+        rusqlite::params![pack.id, pack.name, json, Utc::now().to_rfc3339()],
+    )
+    .map_err(|e| format!("Failed to save policy pack: {}", e))?;
+    Ok(pack)
+}
+
+/// Stored policy packs, by name.
+#[tauri::command]
+pub async fn list_policy_packs(state: State<'_, AppState>) -> Result<Vec<PolicyPack>, String> {
+    let db = state.db.lock().map_err(|e| format!("DB lock error: {}", e))?;
+    let mut stmt = db
+        .prepare("SELECT pack FROM policy_packs ORDER BY name")
+        .map_err(|e| format!("Failed to prepare query: {}", e))?;
+    let packs = stmt
+        .query_map([], |row| row.get::<_, String>(0))
+        .map_err(|e| format!("Failed to list policy packs: {}", e))?
+        .filter_map(|r| r.ok())
+        .filter_map(|json| serde_json::from_str(&json).ok())
+        .collect();
+    Ok(packs)
+}
+
+/// Remove a policy pack and unassign it from every project.
+#[tauri::command]
+pub async fn delete_policy_pack(id: String, state: State<'_, AppState>) -> Result<(), String> {
+    let db = state.db.lock().map_err(|e| format!("DB lock error: {}", e))?;
+    db.execute("DELETE FROM policy_packs WHERE id = ?1", [&id])
+        .map_err(|e| format!("Failed to delete policy pack: {}", e))?;
+    db.execute(
+        "DELETE FROM settings WHERE key LIKE ?1 AND value = ?2",
+        rusqlite::params![format!("{}%", POLICY_SETTING_PREFIX), id],
+    )
+    .map_err(|e| format!("Failed to unassign policy pack: {}", e))?;
+    Ok(())
+}
+
+/// The pack assigned to a project, if any.
+#[tauri::command]
+pub async fn get_project_policy_pack(
+    project_id: String,
+    state: State<'_, AppState>,
+) -> Result<Option<PolicyPack>, String> {
+    let db = state.db.lock().map_err(|e| format!("DB lock error: {}", e))?;
+    assigned_pack(&db, &project_id)
+}
+
+/// Assign a pack to a project and apply every fixable requirement. Returns the audit afterwards.
+#[tauri::command]
+pub async fn apply_policy_pack(
+    project_id: String,
+    pack_id: String,
+    state: State<'_, AppState>,
+) -> Result<PolicyComplianceReport, String> {
+    let db = state.db.lock().map_err(|e| format!("DB lock error: {}", e))?;
+    let project = project::load_project(&db, &project_id)?;
+    let pack = load_pack(&db, &pack_id)?.ok_or_else(|| format!("Policy pack not found: {}", pack_id))?;
+
+    db.execute(
+        "INSERT OR REPLACE INTO settings (key, value) VALUES (?1, ?2)",
+        rusqlite::params![format!("{}{}", POLICY_SETTING_PREFIX, project.id), pack.id],
+    )
+    .map_err(|e| format!("Failed to assign policy pack: {}", e))?;
+
+    if pack.min_doc_coverage.is_some() || pack.require_ci_check {
+        let mut goals = health_history::load_doc_goals(&db, &project.id);
+        if let Some(min) = pack.min_doc_coverage {
+            goals.min_doc_coverage = Some(goals.min_doc_coverage.map_or(min, |current| current.max(min)));
+        }
+        goals.enforce_in_ci |= pack.require_ci_check && goals.min_doc_coverage.is_some();
+        health_history::store_doc_goals(&db, &project.id, &doc_goals::validate_goals(goals)?)?;
+    }
+
+    if let Some(ref mode) = pack.hook_mode {
+        if facts_hook_mode(&project.path) != *mode {
+            enforcement::install_git_hooks_internal(&project.path, mode, Some(&db))?;
+        }
+    }
+
+    let root = Path::new(&project.path);
+    if pack.require_ci_check && !policy::ci_doc_check_present(&project.path) && !root.join(".gitlab-ci.yml").exists() {
+        let workflow = root.join(".github").join("workflows").join("doc-check.yml");
+        if !workflow.exists() {
+            let fail_under = doc_goals::ci_fail_under(&health_history::load_doc_goals(&db, &project.id));
+            std::fs::create_dir_all(workflow.parent().unwrap_or(root))
+                .map_err(|e| format!("Failed to create .github/workflows: {}", e))?;
+            std::fs::write(&workflow, enforcement::generate_github_actions_snippet(fail_under, &scanner::detect_source_layout(root)))
+                .map_err(|e| format!("Failed to write doc-check.yml: {}", e))?;
+        }
+    }
+
+    let excludes = ai::load_ai_excludes(&project.path);
+    let missing = policy::missing_protected_paths(&pack, &excludes);
+    if !missing.is_empty() {
+        claude_backup::snapshot(&project.path, &format!("Before applying policy pack {}", pack.name))?;
+        let mut globs = excludes;
+        globs.extend(missing);
+        ai::save_ai_excludes(&project.path, &globs)?;
+    }
+
+    events::publish(
+        &db,
+        AppEvent::activity(&project.id, ActivityType::Enforcement, &format!("Applied policy pack: {}", pack.name)),
+    );
+    Ok(audit(&project.id, &project.path, &pack))
+}
+
+/// Compare a project against its assigned policy pack.
+#[tauri::command]
+pub async fn audit_policy_compliance(
+    project_id: String,
+    state: State<'_, AppState>,
+) -> Result<PolicyComplianceReport, String> {
+    let (project, pack) = {
+        let db = state.db.lock().map_err(|e| format!("DB lock error: {}", e))?;
+        let project = project::load_project(&db, &project_id)?;
+        let pack = assigned_pack(&db, &project.id)?.ok_or("No policy pack is assigned to this project")?;
+        (project, pack)
+    };
+
+    // The coverage walk runs without the DB lock
+    let report = audit(&project.id, &project.path, &pack);
+
+    if !report.compliant {
+        let failed: Vec<&str> = report.checks.iter().filter(|c| !c.compliant).map(|c| c.label.as_str()).collect();
+        let db = state.db.lock().map_err(|e| format!("DB lock error: {}", e))?;
+        events::publish(
+            &db,
+            AppEvent::activity(
+                &project.id,
+                ActivityType::Enforcement,
+                &format!("Policy pack {} not met: {}", pack.name, failed.join(", ")),
+            ),
+        );
+    }
+    Ok(report)
+}
+
+fn audit(project_id: &str, project_path: &str, pack: &PolicyPack) -> PolicyComplianceReport {
+    let facts = PolicyFacts {
+        hook_mode: facts_hook_mode(project_path),
+        ci_check: policy::ci_doc_check_present(project_path),
+        doc_coverage: if pack.min_doc_coverage.is_some() {
+            DocHealthCache::build(project_path).doc_coverage()
+        } else {
+            0.0
+        },
+        ai_excludes: ai::load_ai_excludes(project_path),
+    };
+    let checks = policy::evaluate(pack, &facts);
+    PolicyComplianceReport {
+        project_id: project_id.to_string(),
+        pack_id: pack.id.clone(),
+        pack_name: pack.name.clone(),
+        compliant: checks.iter().all(|c| c.compliant),
+        checks,
+        audited_at: Utc::now().to_rfc3339(),
+    }
+}
+
+/// Mode of the project's pre-commit hook ("none" without git or a hook).
+fn facts_hook_mode(project_path: &str) -> String {
+    let path = Path::new(project_path);
+    let hook_path = path.join(".git").join("hooks").join("pre-commit");
+    let content = std::fs::read_to_string(&hook_path).ok();
+    enforcement::hook_status_from(
+        content.as_deref(),
+        hook_path.to_string_lossy().to_string(),
+        path.join(".git").exists(),
+        path.join(".husky").exists(),
+    )
+    .mode
+}
+
+fn load_pack(db: &Connection, pack_id: &str) -> Result<Option<PolicyPack>, String> {
+    let json: Option<String> = db
+        .query_row("SELECT pack FROM policy_packs WHERE id = ?1", [pack_id], |row| row.get(0))
+        .optional()
+        .map_err(|e| format!("Failed to load policy pack: {}", e))?;
+    json.map(|j| serde_json::from_str(&j).map_err(|e| format!("Stored policy pack is invalid: {}", e)))
+        .transpose()
+}
+
+fn assigned_pack(db: &Connection, project_id: &str) -> Result<Option<PolicyPack>, String> {
+    let pack_id: Option<String> = db
+        .query_row(
+            "SELECT value FROM settings WHERE key = ?1",
+            [format!("{}{}", POLICY_SETTING_PREFIX, project_id)],
+            |row| row.get(0),
+        )
+        .optional()
+        .map_err(|e| format!("Failed to read policy assignment: {}", e))?;
+    match pack_id {
+        Some(id) => load_pack(db, &id),
+        None => Ok(None),
+    }
+}
@@ -0,0 +1,49 @@
+//! @module commands/pr_health
+//! @description Tauri IPC command that renders a project's health between two git refs as a PR Markdown block
+//!
+//! PURPOSE:
+//! - Build the PR health block (doc coverage delta, newly stale files, test plan status)
+//!   for posting as a pull request comment or description section
+//!
+//! DEPENDENCIES:
+//! - tauri - Command macro and State
+//! - db::AppState - Database connection
+//! - core::pr_health - Git comparison, test plan statuses, Markdown rendering
+//! - commands::project - load_project
+//! - models::pr_health - PrHealthReport type
+//!
+//! EXPORTS:
+//! - generate_pr_health_comment - Compare base_ref and head_ref and return the report with its Markdown
+//!
+//! PATTERNS:
+//! - The DB lock is taken only to load the project and, after the git work, the test plans
+//!
+//! CLAUDE NOTES:
+//! - Refs are anything git rev-parse accepts ("main", "origin/main", a hash); fetch first
+//!   when comparing remote branches
+//! - Nothing is posted: CI or the forge integration posts report.markdown, updating the
+//!   comment that starts with pr_health::COMMENT_MARKER if there is one
+
+use tauri::State;
+
+use crate::commands::project::load_project;
+use crate::core::pr_health;
+use crate::db::AppState;
+use crate::models::pr_health::PrHealthReport;
+
+#[tauri::command]
+pub async fn generate_pr_health_comment(
+    project_id: String,
+    base_ref: String,
+    head_ref: String,
+    state: State<'_, AppState>,
+) -> Result<PrHealthReport, String> {
+    let project = {
+        let db = state.db.lock().map_err(|e| format!("DB lock error: {}", e))?;
+        load_project(&db, &project_id)?
+    };
+    pr_health::build_report(&project.id, &project.path, &base_ref, &head_ref, |changed| {
+        let db = state.db.lock().map_err(|e| format!("DB lock error: {}", e))?;
+        pr_health::test_plan_statuses(&db, &project.id, changed)
+    })
+}
@@ -5,18 +5,23 @@
 //! - List all registered projects
 //! - Get a single project by ID
 //! - Remove a project from the database
+//! - Move a project to a new path on disk, rewriting stored paths under the old root
+//! - Merge a project registered twice (under two paths) into one
 //!
 //! DEPENDENCIES:
 //! - tauri - Command macro and State
 //! - rusqlite - Database queries
 //! - chrono - Timestamp parsing
-//! - models::project - Project type
-//! - db - AppState with database connection
+//! - models::project - Project, ProjectRelocation, ProjectMerge types
+//! - db - AppState with database connection, activity logging
 //!
 //! EXPORTS:
 //! - list_projects - Fetch all projects ordered by creation date
 //! - get_project - Fetch a single project by ID
 //! - remove_project - Delete a project record
+//! - update_project_path - Point a project at a new directory and rewrite dependent paths
+//! - merge_projects - Move every row of a source project into a target, then delete the source
+//! - load_project - (internal) Fetch a project by ID from a connection
 //!
 //! PATTERNS:
 //! - All commands are async, return Result<T, String>
@@ -27,12 +32,34 @@
 //! - list_projects returns newest first
 //! - remove_project only deletes the DB record, not project files
 //! - Row mapping uses column indices for performance
+//! - Stored absolute paths are listed in PATH_COLUMNS; paths outside the old root and
+//!   relative paths are left alone
+//! - merge_projects finds project-scoped tables from the schema (any project_id column), so new
+//!   tables are merged without changes here; rows that clash with a unique target row are dropped
+//! - Per-project settings ("<prefix>.<project_id>") move with a merge unless the target has its own
+//! - Changing or merging away the watched project's path stops the file watcher
+
+use std::path::Path;
 
 use chrono::DateTime;
+use rusqlite::{Connection, OptionalExtension};
 use tauri::State;
 
+use crate::core::events::{self, AppEvent};
 use crate::db::AppState;
-use crate::models::project::Project;
+use crate::models::activity::ActivityType;
+use crate::models::project::{Project, ProjectMerge, ProjectRelocation};
+
+/// Columns holding absolute paths inside a project, with the clause selecting
+/// the project's rows (?1 = project id).
+const PATH_COLUMNS: &[(&str, &str, &str)] = &[
+    ("module_docs", "file_path", "project_id = ?1"),
+    ("freshness_history", "file_path", "project_id = ?1"),
+    ("enforcement_events", "file_path", "project_id = ?1"),
+    ("tdd_sessions", "test_file_path", "project_id = ?1"),
+    ("learnings", "source_file", "project_id = ?1"),
+    ("test_cases", "file_path", "plan_id IN (SELECT id FROM test_plans WHERE project_id = ?1)"),
+];
 
 #[tauri::command]
 pub async fn list_projects(state: State<'_, AppState>) -> Result<Vec<Project>, String> {
@@ -40,37 +67,14 @@ pub async fn list_projects(state: State<'_, AppState>) -> Result<Vec<Project>, S
 
     let mut stmt = db
         .prepare(
-            "SELECT id, name, path, description, project_type, language, framework, database_tech, testing, styling, stack_extras, health_score, created_at
+            "SELECT id, name, path, description, project_type, language, framework, database_tech, testing, styling, stack_extras, health_score, created_at,
+                    location_type, remote_host, remote_path, package_manager
              FROM projects ORDER BY created_at DESC",
         )
         .map_err(|e| format!("Query prepare error: {}", e))?;
 
     let projects = stmt
-        .query_map([], |row| {
-            let extras_str: Option<String> = row.get(10)?;
-            let stack_extras = extras_str.and_then(|s| serde_json::from_str(&s).ok());
-
-            let created_str: String = row.get(12)?;
-            let created_at = DateTime::parse_from_rfc3339(&created_str)
-                .map(|dt| dt.with_timezone(&chrono::Utc))
-                .unwrap_or_else(|_| chrono::Utc::now());
-
-            Ok(Project {
-                id: row.get(0)?,
-                name: row.get(1)?,
-                path: row.get(2)?,
-                description: row.get(3)?,
-                project_type: row.get(4)?,
-                language: row.get(5)?,
-                framework: row.get(6)?,
-                database: row.get(7)?,
-                testing: row.get(8)?,
-                styling: row.get(9)?,
-                stack_extras,
-                health_score: row.get(11)?,
-                created_at,
-            })
-        })
+        .query_map([], map_project_row)
         .map_err(|e| format!("Query error: {}", e))?
         .collect::<Result<Vec<_>, _>>()
         .map_err(|e| format!("Row mapping error: {}", e))?;
@@ -81,40 +85,7 @@ pub async fn list_projects(state: State<'_, AppState>) -> Result<Vec<Project>, S
 #[tauri::command]
 pub async fn get_project(id: String, state: State<'_, AppState>) -> Result<Project, String> {
     let db = state.db.lock().map_err(|e| format!("DB lock error: {}", e))?;
-
-    let mut stmt = db
-        .prepare(
-            "SELECT id, name, path, description, project_type, language, framework, database_tech, testing, styling, stack_extras, health_score, created_at
-             FROM projects WHERE id = ?1",
-        )
-        .map_err(|e| format!("Query prepare error: {}", e))?;
-
-    stmt.query_row(rusqlite::params![&id], |row| {
-        let extras_str: Option<String> = row.get(10)?;
-        let stack_extras = extras_str.and_then(|s| serde_json::from_str(&s).ok());
-
-        let created_str: String = row.get(12)?;
-        let created_at = DateTime::parse_from_rfc3339(&created_str)
-            .map(|dt| dt.with_timezone(&chrono::Utc))
-            .unwrap_or_else(|_| chrono::Utc::now());
-
-        Ok(Project {
-            id: row.get(0)?,
-            name: row.get(1)?,
-            path: row.get(2)?,
-            description: row.get(3)?,
-            project_type: row.get(4)?,
-            language: row.get(5)?,
-            framework: row.get(6)?,
-            database: row.get(7)?,
-            testing: row.get(8)?,
-            styling: row.get(9)?,
-            stack_extras,
-            health_score: row.get(11)?,
-            created_at,
-        })
-    })
-    .map_err(|e| format!("Project not found: {}", e))
+    load_project(&db, &id)
 }
 
 #[tauri::command]
@@ -126,3 +97,312 @@ pub async fn remove_project(id: String, state: State<'_, AppState>) -> Result<()
 
     Ok(())
 }
+
+/// Point a project at a new directory after the repo moved on disk.
+/// Fails when another project is already registered at the new path (use merge_projects).
+#[tauri::command]
+pub async fn update_project_path(
+    project_id: String,
+    new_path: String,
+    state: State<'_, AppState>,
+) -> Result<ProjectRelocation, String> {
+    let new_path = normalize_project_path(&new_path)?;
+    let relocation = {
+        let db = state.db.lock().map_err(|e| format!("DB lock error: {}", e))?;
+        relocate_project_db(&db, &project_id, &new_path)?
+    };
+    release_watched_project(&state, &relocation.old_path)?;
+    Ok(relocation)
+}
+
+/// Merge a project that was registered twice: move everything from `source_id`
+/// into `target_id` and delete the source. The target keeps its name and path.
+#[tauri::command]
+pub async fn merge_projects(
+    source_id: String,
+    target_id: String,
+    state: State<'_, AppState>,
+) -> Result<ProjectMerge, String> {
+    let (merge, source_path) = {
+        let db = state.db.lock().map_err(|e| format!("DB lock error: {}", e))?;
+        merge_projects_db(&db, &source_id, &target_id)?
+    };
+    release_watched_project(&state, &source_path)?;
+    Ok(merge)
+}
+
+/// Fetch a project by ID.
+pub(crate) fn load_project(db: &Connection, id: &str) -> Result<Project, String> {
+    db.query_row(
+        "SELECT id, name, path, description, project_type, language, framework, database_tech, testing, styling, stack_extras, health_score, created_at,
+                location_type, remote_host, remote_path, package_manager
+         FROM projects WHERE id = ?1",
+        [id],
+        map_project_row,
+    )
+    .map_err(|e| format!("Project not found: {}", e))
+}
+
+fn map_project_row(row: &rusqlite::Row) -> rusqlite::Result<Project> {
+    let extras_str: Option<String> = row.get(10)?;
+    let stack_extras = extras_str.and_then(|s| serde_json::from_str(&s).ok());
+
+    let created_str: String = row.get(12)?;
+    let created_at = DateTime::parse_from_rfc3339(&created_str)
+        .map(|dt| dt.with_timezone(&chrono::Utc))
+        .unwrap_or_else(|_| chrono::Utc::now());
+
+    Ok(Project {
+        id: row.get(0)?,
+        name: row.get(1)?,
+        path: row.get(2)?,
+        description: row.get(3)?,
+        project_type: row.get(4)?,
+        language: row.get(5)?,
+        framework: row.get(6)?,
+        database: row.get(7)?,
+        testing: row.get(8)?,
+        styling: row.get(9)?,
+        stack_extras,
+        health_score: row.get(11)?,
+        created_at,
+        location_type: row.get(13)?,
+        remote_host: row.get(14)?,
+        remote_path: row.get(15)?,
+        package_manager: row.get(16)?,
+    })
+}
+
+/// Trim whitespace and trailing separators; the path must be an existing absolute directory.
+fn normalize_project_path(path: &str) -> Result<String, String> {
+    let trimmed = path.trim().trim_end_matches(['/', '\\']);
+    if trimmed.is_empty() {
+        return Err("Project path cannot be empty or the filesystem root.".to_string());
+    }
+    let dir = Path::new(trimmed);
+    if !dir.is_absolute() {
+        return Err(format!("Project path must be absolute: {}", trimmed));
+    }
+    if !dir.is_dir() {
+        return Err(format!("Directory does not exist: {}", trimmed));
+    }
+    Ok(trimmed.to_string())
+}
+
+fn project_path(db: &Connection, project_id: &str) -> Result<String, String> {
+    db.query_row("SELECT path FROM projects WHERE id = ?1", [project_id], |row| row.get(0))
+        .map_err(|e| format!("Project not found: {}", e))
+}
+
+fn relocate_project_db(db: &Connection, project_id: &str, new_path: &str) -> Result<ProjectRelocation, String> {
+    let old_path = project_path(db, project_id)?;
+    if old_path == new_path {
+        return Ok(ProjectRelocation {
+            project: load_project(db, project_id)?,
+            old_path,
+            rewritten_paths: 0,
+        });
+    }
+
+    let registered: Option<String> = db
+        .query_row("SELECT name FROM projects WHERE path = ?1", [new_path], |row| row.get(0))
+        .optional()
+        .map_err(|e| format!("Failed to query projects: {}", e))?;
+    if let Some(name) = registered {
+        return Err(format!(
+            "{} is already registered as project '{}'. Merge the two projects instead.",
+            new_path, name
+        ));
+    }
+
+    let tx = db
+        .unchecked_transaction()
+        .map_err(|e| format!("Failed to start transaction: {}", e))?;
+    tx.execute(
+        "UPDATE projects SET path = ?1 WHERE id = ?2",
+        rusqlite::params![new_path, project_id],
+    )
+    .map_err(|e| format!("Failed to update project path: {}", e))?;
+    let rewritten_paths = rewrite_stored_paths(&tx, project_id, &old_path, new_path)?;
+    tx.commit().map_err(|e| format!("Failed to commit path change: {}", e))?;
+
+    let message = format!("Moved project from {} to {}", old_path, new_path);
+    events::publish(db, AppEvent::activity(project_id, ActivityType::Settings, &message));
+
+    Ok(ProjectRelocation {
+        project: load_project(db, project_id)?,
+        old_path,
+        rewritten_paths,
+    })
+}
+
+/// Rewrite a project's stored paths equal to or under `old_root` to sit under `new_root`.
+fn rewrite_stored_paths(db: &Connection, project_id: &str, old_root: &str, new_root: &str) -> Result<u32, String> {
+    // SQLite substr/length count characters, and the compared prefix includes the "/"
+    let prefix_len = old_root.chars().count() as i64 + 1;
+    let mut rewritten = 0;
+    for (table, column, scope) in PATH_COLUMNS {
+        let sql = format!(
+            "UPDATE {table} SET {column} = ?2 || substr({column}, ?4)
+             WHERE {scope} AND ({column} = ?3 OR substr({column}, 1, ?4) = ?3 || '/')"
+        );
+        rewritten += db
+            .execute(&sql, rusqlite::params![project_id, new_root, old_root, prefix_len])
+            .map_err(|e| format!("Failed to rewrite {}.{}: {}", table, column, e))? as u32;
+    }
+    Ok(rewritten)
+}
+
+/// Tables (other than projects) with a project_id column.
+fn project_tables(db: &Connection) -> Result<Vec<String>, String> {
+    let mut stmt = db
+        .prepare(
+            "SELECT m.name FROM sqlite_master m, pragma_table_info(m.name) p
+             WHERE m.type = 'table' AND m.name != 'projects' AND p.name = 'project_id'
+             ORDER BY m.name",
+        )
+        .map_err(|e| format!("Failed to list tables: {}", e))?;
+    let tables = stmt
+        .query_map([], |row| row.get(0))
+        .map_err(|e| format!("Failed to list tables: {}", e))?
+        .filter_map(|r| r.ok())
+        .collect();
+    Ok(tables)
+}
+
+/// Merge source into target. Returns the merge result and the source's old path.
+fn merge_projects_db(db: &Connection, source_id: &str, target_id: &str) -> Result<(ProjectMerge, String), String> {
+    if source_id == target_id {
+        return Err("Cannot merge a project into itself.".to_string());
+    }
+    let source_path = project_path(db, source_id)?;
+    let target_path = project_path(db, target_id)?;
+
+    let tx = db
+        .unchecked_transaction()
+        .map_err(|e| format!("Failed to start transaction: {}", e))?;
+
+    let mut moved_rows = 0;
+    let mut dropped_rows = 0;
+    for table in project_tables(&tx)? {
+        moved_rows += tx
+            .execute(
+                &format!("UPDATE OR IGNORE {} SET project_id = ?2 WHERE project_id = ?1", table),
+                [source_id, target_id],
+            )
+            .map_err(|e| format!("Failed to move {} rows: {}", table, e))? as u32;
+        dropped_rows += tx
+            .execute(&format!("DELETE FROM {} WHERE project_id = ?1", table), [source_id])
+            .map_err(|e| format!("Failed to drop duplicate {} rows: {}", table, e))? as u32;
+    }
+
+    // Per-project settings are keyed "<prefix>.<project_id>"; the target's own settings win
+    let suffix = format!(".{}", source_id);
+    tx.execute(
+        "UPDATE OR IGNORE settings SET key = substr(key, 1, length(key) - length(?1)) || ?2
+         WHERE substr(key, -length(?1)) = ?1",
+        [suffix.as_str(), &format!(".{}", target_id)],
+    )
+    .map_err(|e| format!("Failed to move project settings: {}", e))?;
+    tx.execute("DELETE FROM settings WHERE substr(key, -length(?1)) = ?1", [&suffix])
+        .map_err(|e| format!("Failed to drop project settings: {}", e))?;
+
+    let rewritten_paths = rewrite_stored_paths(&tx, target_id, &source_path, &target_path)?;
+    tx.execute("DELETE FROM projects WHERE id = ?1", [source_id])
+        .map_err(|e| format!("Failed to delete merged project: {}", e))?;
+    tx.commit().map_err(|e| format!("Failed to commit merge: {}", e))?;
+
+    let message = format!("Merged duplicate project at {} ({} rows moved)", source_path, moved_rows);
+    events::publish(db, AppEvent::activity(target_id, ActivityType::Settings, &message));
+
+    let merge = ProjectMerge {
+        project: load_project(db, target_id)?,
+        moved_rows,
+        dropped_rows,
+        rewritten_paths,
+    };
+    Ok((merge, source_path))
+}
+
+/// Stop the file watcher when it watches `path`, which no longer belongs to a project.
+fn release_watched_project(state: &AppState, path: &str) -> Result<(), String> {
+    let watched = state
+        .health_cache
+        .lock()
+        .map_err(|e| format!("Failed to lock health cache: {}", e))?
+        .as_ref()
+        .is_some_and(|cache| cache.project_path() == path);
+    if watched {
+        *state.watcher.lock().map_err(|e| format!("Failed to lock watcher: {}", e))? = None;
+        *state
+            .health_cache
+            .lock()
+            .map_err(|e| format!("Failed to lock health cache: {}", e))? = None;
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_support::memory_db;
+
+    fn setup() -> Connection {
+        let conn = memory_db();
+        crate::db::schema::migrate_add_project_location(&conn).unwrap();
+        conn.execute_batch(
+            "INSERT INTO projects (id, name, path, created_at) VALUES ('p1', 'App', '/tmp/app', '2025-01-01T00:00:00Z');
+             INSERT INTO projects (id, name, path, created_at) VALUES ('p2', 'App copy', '/tmp/app-link', '2025-01-02T00:00:00Z');
+             INSERT INTO module_docs (id, project_id, file_path, last_checked)
+                 VALUES ('m1', 'p1', '/tmp/app/src/a.rs', 'now'), ('m2', 'p1', '/tmp/app-other/b.rs', 'now'),
+                        ('m3', 'p1', 'src/c.rs', 'now'), ('m4', 'p2', '/tmp/app-link/src/d.rs', 'now');
+             INSERT INTO command_approvals (id, project_id, command, source, requested_at)
+                 VALUES ('c1', 'p1', 'npm test', 'prd', 'now'), ('c2', 'p2', 'npm test', 'prd', 'now'),
+                        ('c3', 'p2', 'cargo test', 'prd', 'now');
+             INSERT INTO settings (key, value) VALUES ('doc_goals.p2', '{}');",
+        )
+        .unwrap();
+        conn
+    }
+
+    fn module_paths(db: &Connection, project_id: &str) -> Vec<String> {
+        let mut stmt = db
+            .prepare("SELECT file_path FROM module_docs WHERE project_id = ?1 ORDER BY id")
+            .unwrap();
+        stmt.query_map([project_id], |row| row.get(0)).unwrap().map(|r| r.unwrap()).collect()
+    }
+
+    #[test]
+    fn test_relocate_project() {
+        let db = setup();
+        let relocation = relocate_project_db(&db, "p1", "/srv/app").unwrap();
+        assert_eq!(relocation.old_path, "/tmp/app");
+        assert_eq!(relocation.project.path, "/srv/app");
+        assert_eq!(relocation.rewritten_paths, 1);
+        assert_eq!(module_paths(&db, "p1"), vec!["/srv/app/src/a.rs", "/tmp/app-other/b.rs", "src/c.rs"]);
+
+        let err = relocate_project_db(&db, "p1", "/tmp/app-link").unwrap_err();
+        assert!(err.contains("App copy"));
+    }
+
+    #[test]
+    fn test_merge_projects() {
+        let db = setup();
+        let (merge, source_path) = merge_projects_db(&db, "p2", "p1").unwrap();
+        assert_eq!(source_path, "/tmp/app-link");
+        assert_eq!(merge.project.id, "p1");
+        assert_eq!(merge.moved_rows, 2, "module doc m4 and command c3");
+        assert_eq!(merge.dropped_rows, 1, "duplicate approval c2");
+        assert_eq!(merge.rewritten_paths, 1);
+        assert_eq!(module_paths(&db, "p1").last().unwrap(), "/tmp/app/src/d.rs");
+
+        let projects: u32 = db.query_row("SELECT COUNT(*) FROM projects", [], |row| row.get(0)).unwrap();
+        assert_eq!(projects, 1);
+        let goals: Option<String> = db
+            .query_row("SELECT key FROM settings WHERE key = 'doc_goals.p1'", [], |row| row.get(0))
+            .optional()
+            .unwrap();
+        assert!(goals.is_some());
+        assert!(merge_projects_db(&db, "p1", "p1").is_err());
+    }
+}
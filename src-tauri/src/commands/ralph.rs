@@ -17,134 +17,500 @@
 //! - uuid - Loop ID generation
 //! - chrono - Timestamp handling
 //! - core::ai - Claude API for AI-powered enhancement and issue extraction
-//! - std::process::Command - Execute Claude CLI
+//! - core::ai_queue - Prioritized slot for each Claude call
+//! - core::ai_audit - Audit scope for API calls; Claude CLI runs are recorded directly
+//! - std::process::Command - Execute Claude CLI (via core::proc for timeouts and output limits)
 //! - tokio - Async runtime for background execution
 //! - reqwest - HTTP client for AI API calls in background tasks
+//! - core::monitor - Window-scoped "ralph-loop-progress" events
+//! - core::command_guard - Approval and validation for PRD commands and acceptance gates
+//! - core::worktree - Git working-tree snapshots for per-iteration changed files
+//! - core::issue_rules - Local tsc/eslint/cargo/pytest/go rules for issue extraction without an API key
+//! - core::ralph_preflight - Checks behind check_ralph_prerequisites
+//! - core::events - loop_iteration_finished and activity events
+//! - core::git_policy - Per-project branch/commit permissions (yes/no/ask)
+//! - core::doc_index - Relevant-file ranking for prompts
+//! - core::safe_read - Char-boundary-safe truncation of loop output
+//! - core::conventions - Stored CLAUDE.md conventions offered as learnings
 //!
 //! EXPORTS:
 //! - analyze_ralph_prompt - Score prompt quality and generate suggestions (heuristic)
+//! - quick_analyze_ralph_prompt - Score and top suggestion only, for every keystroke (heuristic)
+//! - get_prompt_criteria / save_prompt_criteria - Team criteria and extra keywords for heuristic scoring
 //! - analyze_ralph_prompt_with_ai - AI-powered prompt analysis and enhancement
+//! - suggest_relevant_files - Rank project files likely relevant to a prompt (doc index + path heuristics)
+//! - list_prompt_analyses - Get saved prompt analyses for a project (prompt editor history)
+//! - check_ralph_prerequisites - Preflight checklist (CLI, login, project/git, API key, disk space)
 //! - start_ralph_loop - Create loop and execute via Claude CLI in background
 //! - pause_ralph_loop - Pause an active loop
 //! - resume_ralph_loop - Resume a paused loop
 //! - kill_ralph_loop - Kill a running or paused loop and mark as failed
 //! - list_ralph_loops - Get loops for a project
+//! - create_followup_loop - Start a loop seeded with a finished loop's remaining issues and TODOs
+//! - retry_failed_prd_stories - Start a PRD continuation running only the stories that failed validation
+//!   or were cut off by the time limit
+//! - get_ralph_loop_chain - Get the parent_loop_id chain a loop belongs to (first loop first)
 //! - list_ralph_mistakes - Get mistakes for a project (for UI display)
+//! - get_ralph_iterations - Get a loop's per-iteration timeline (status, summary, files changed)
 //! - get_ralph_context - Get CLAUDE.md summary, recent mistakes, and project patterns
 //! - record_ralph_mistake - Record a mistake from a RALPH loop for learning
 //! - update_claude_md_with_pattern - Append learned pattern to CLAUDE.md CLAUDE NOTES section
+//! - generate_changelog - Build a Keep a Changelog fragment from completed loops, optionally writing CHANGELOG.md
+//! - LoopOptions - Per-loop tools, iteration budget, acceptance gates, branch, deletion threshold,
+//!   Claude CLI session resumption, and time limit
+//! - validate_max_duration - Check a requested loop time limit (shared with ralph_templates)
+//! - spawn_iterative_loop - Insert and start an iterative loop (shared with ralph_templates)
+//! - categorize_mistake - Mistake category for error output (shared with core::rules)
+//! - find_claude_cli - Resolved Claude CLI path (shared with commands::doctor)
+//! - CLAUDE_CLI_ENV - Environment variable naming a stub Claude CLI (test builds only)
+//! - insert_prd_loop / execute_ralph_loop_prd - Create and run a PRD loop (shared with ralph_workspace)
+//! - prd_story_order / load_ralph_iterations / open_db_connection - (internal) shared with ralph_workspace
 //!
 //! PATTERNS:
 //! - analyze_ralph_prompt uses fast heuristics for immediate feedback
+//! - quick_analyze_ralph_prompt is the per-keystroke variant (the UI debounces it): same total as
+//!   analyze_ralph_prompt, one suggestion, no enhanced prompt, no ai-exclude warning, nothing saved
 //! - analyze_ralph_prompt_with_ai uses Claude for deeper analysis (when API key available)
+//! - Both analysis commands save their result to prompt_analyses when given a project (pruned to 100 per project)
+//! - The UI runs check_ralph_prerequisites before start and blocks on any "fail" item
 //! - start_ralph_loop stores loop in DB then spawns background task to execute claude CLI
 //! - execute_ralph_loop runs iteratively: up to 5 iterations, extracting issues via AI after each
 //! - pause_ralph_loop transitions "running" to "paused"
-//! - Loop statuses: idle -> running -> paused/completed/failed
+//! - An iterative loop moves itself to "needs_review" (diff in ralph_loops.review_diff) when an
+//!   iteration removes a file or deletes more than LoopOptions.max_deleted_percent of a file's
+//!   lines; resume_ralph_loop accepts it like "paused", kill_ralph_loop ends it
+//! - Loop statuses: idle -> running -> paused/needs_review/completed/failed
 //! - Failed/killed loops automatically record mistakes for learning (categorized by error type)
 //! - Iteration count updates in real-time for UI progress display
+//! - Every status/progress change emits "ralph-loop-progress" to the main window and the
+//!   loop's monitor window ("monitor-ralph-<loop_id>"), never as a global broadcast
+//! - PRD loops also emit "ralph-prd-story" (PrdStoryProgress) per story step: started, iteration,
+//!   validation_failed, committed, failed, skipped, blocked, timed_out; after each finished story the rolling ETA is
+//!   stored in ralph_loops.eta_at / avg_story_secs and included in both events
 //!
 //! CLAUDE NOTES:
 //! - RALPH = Review, Analyze, List, Plan, Handoff
-//! - Quality score is sum of 4 criteria (clarity, specificity, context, scope), each 0-25
+//! - Quality score is sum of 4 criteria (clarity, specificity, context, scope), each 0-25, plus any
+//!   custom criteria (0-weight) from the "ralph.prompt_criteria" setting, normalized to 0-100
+//! - Extra keywords (e.g. another language's action verbs) extend the built-in keyword lists
 //! - Heuristic analysis is instant; AI analysis takes 2-5 seconds
 //! - AI enhancement provides project-aware suggestions when context is provided
-//! - Claude CLI is executed with: claude -p "prompt" --allowedTools ... in project directory
+//! - Claude CLI is executed with: claude -p "prompt" --allowedTools ... in project directory,
+//!   killed after ProcLimits::CLAUDE (30 min) so a hung CLI cannot stall the background task
 //! - Iterative refinement: after each Claude run, AI extracts issues → feeds to next iteration
+//! - With LoopOptions.resume_session, runs add --output-format json and later iterations pass
+//!   --resume <session_id> with only the new issues; once a run reports session_token_limit tokens
+//!   or more (usage summed over the run's turns, so it trips early rather than late), or the output
+//!   is not a JSON result, the next iteration starts a fresh session with the full task and issues
+//! - Issue extraction sends the last issue_output_tokens (core::ai::token_limits) of the output
+//! - Without an API key (or when the AI call fails), core::issue_rules extracts file/line issues from
+//!   compiler, linter, and test output; generic "error:"/"warning:" markers are the last resort
 //! - MAX_ITERATIONS = 5 prevents infinite loops; exits early if no issues found
-//! - Each iteration's issues are stored as mistakes for learning
+//! - Time-boxed loops (LoopOptions / PrdFile max_duration_minutes) check the wall clock between
+//!   iterations and stories, never mid-run: an iterative loop completes with a "Stopped at the
+//!   N-minute time limit" summary; a PRD loop keeps its committed stories and lists the rest as
+//!   "⏱ Story N" lines that retry_failed_prd_stories runs. The clock restarts on resume
+//! - Template loops override tools/iterations via LoopOptions; failing acceptance gates are fed
+//!   back as "acceptance_gate" issues, so a loop only finishes early once every gate passes;
+//!   a loop that runs out of iterations with a gate still failing is marked failed
+//! - PRD validation commands and acceptance gates run without a shell and must be approved for
+//!   the project (command_approvals) before a loop starts; resume re-checks gate approval
+//! - Each iteration's issues are stored as mistakes for learning, with file_path/line when the
+//!   extractor located them (commands::ralph_patches proposes diffs for those)
+//! - Each Claude run is stored in ralph_iterations with the files it changed, from git working-tree
+//!   snapshots taken before and after the run (core::worktree); files_changed is empty outside git
 //! - Prior issues are included in subsequent prompts for context-aware fixing
+//! - Iterative loops prepend up to 5 CLAUDE NOTES patterns / resolved mistakes that share keywords
+//!   with the task; the injected list is stored in ralph_loops.injected_patterns (JSON)
 //! - get_ralph_context reads CLAUDE.md from project path and fetches recent mistakes from DB
+//! - Follow-up loops carry the last iteration's recorded issues (iterative) or the "✗" story lines
+//!   (PRD), TODO/FIXME lines from the outcome, and the parent's LoopOptions; parent_loop_id links them
+//! - analyze_ralph_prompt_with_ai sends the top SUGGESTED_FILES_FOR_AI files from core::doc_index as
+//!   project_files when a project_id is given, falling back to the caller's list when none match
+//! - Both analysis commands put a warning first in suggestions when the prompt names paths that
+//!   match the project's .claude/ai-exclude globs (see core::ai::excluded_references)
 //! - update_claude_md_with_pattern appends to CLAUDE NOTES section in CLAUDE.md file
+//! - generate_changelog reads PRD story commits from the "✓ Story N: title (commit: hash)" outcome lines
+//! - PRD stories run in dependency order (prd_story_order: dependsOn first, then file order);
+//!   start_ralph_loop_prd rejects unknown ids and cycles, and dependents of a failed story get a
+//!   "⊘ Story N: title (blocked by ids)" line instead of running. Story N is always the file position
+//! - PRD retries keep earlier commits as completed stories (commit_hash set) and list them as
+//!   "↺ Story N: title (commit: hash, earlier run)", which changelog and follow-ups ignore; story
+//!   numbers in a retry's outcome refer to the retry PRD, whose stories are a subset of the original
+//! - PrdFile.cross_repo_context (set by workspace runs) is added to every story prompt under
+//!   "Changes Already Made in Other Repositories"
+//! - Branch checkouts and PRD story commits go through core::git_policy; a denied commit leaves
+//!   "(commit: no commit)" plus a "⚠ Story N not committed" line, a denied template branch fails the loop
 
 use chrono::Utc;
 use rusqlite::Connection;
-use tauri::State;
+use serde::{Deserialize, Serialize};
+use tauri::{AppHandle, Runtime, State};
 
 use std::fs;
 use std::path::Path;
 use std::process::Command;
-
-/// Get the database path for opening new connections in background tasks.
-fn get_db_path() -> Result<std::path::PathBuf, String> {
-    let home = dirs::home_dir().ok_or("Could not determine home directory")?;
-    Ok(home.join(".project-jumpstart").join("jumpstart.db"))
-}
+use std::sync::RwLock;
 
 /// Open a new database connection for background tasks.
-fn open_db_connection() -> Result<Connection, String> {
-    let db_path = get_db_path()?;
+pub(crate) fn open_db_connection() -> Result<Connection, String> {
+    let db_path = crate::db::db_path()?;
     Connection::open(&db_path).map_err(|e| format!("Failed to open database: {}", e))
 }
 
 use crate::core::ai;
-use crate::db::{self, AppState};
-use crate::models::ralph::{PromptAnalysis, PromptCriterion, RalphLoop, RalphMistake, RalphLoopContext};
+use crate::core::ai_audit;
+use crate::core::ai_queue;
+use crate::core::command_guard;
+use crate::core::safe_read;
+use crate::core::issue_rules;
+use crate::core::proc::{self, ProcLimits};
+use crate::core::monitor::{self, MonitorKind, PrdStoryProgress, RalphLoopProgress};
+use crate::core::ralph_preflight;
+use crate::core::worktree;
+use crate::core::events::{self, AppEvent};
+use crate::core::git_policy;
+use crate::core::doc_index;
+use crate::core::conventions;
+use crate::db::AppState;
+use crate::models::activity::ActivityType;
+use crate::models::ai_queue::AiJobKind;
+use crate::models::git_policy::GitOperation;
+use crate::models::ralph::{
+    ChangelogResult, CustomPromptCriterion, PromptAnalysis, PromptAnalysisRecord, PromptCriteriaConfig,
+    PromptCriterion, QuickPromptAnalysis, RalphIteration, RalphIterationFile, FileSuggestion, RalphLoop, RalphMistake, RalphLoopContext, RalphPreflight,
+};
+
+/// Settings key holding the PromptCriteriaConfig JSON.
+const PROMPT_CRITERIA_SETTING: &str = "ralph.prompt_criteria";
+
+/// Names reserved by the built-in criteria.
+const BUILTIN_CRITERIA: [&str; 4] = ["clarity", "specificity", "context", "scope"];
 
 /// Analyze a prompt's quality for use in a RALPH loop.
-/// Scores clarity, specificity, context, and scope (0-25 each, 0-100 total).
+/// Scores clarity, specificity, context, and scope (0-25 each) plus any custom
+/// criteria from the prompt criteria setting, normalized to 0-100.
 /// Returns suggestions for improvement and an optional auto-enhanced version.
+/// With a project_id, the result is saved to prompt_analyses so earlier iterations can be revisited.
 #[tauri::command]
-pub async fn analyze_ralph_prompt(prompt: String) -> Result<PromptAnalysis, String> {
-    let clarity = score_clarity(&prompt);
-    let specificity = score_specificity(&prompt);
-    let context = score_context(&prompt);
-    let scope = score_scope(&prompt);
+pub async fn analyze_ralph_prompt(
+    prompt: String,
+    project_id: Option<String>,
+    state: State<'_, AppState>,
+) -> Result<PromptAnalysis, String> {
+    let db = state.db.lock().map_err(|e| format!("DB lock error: {}", e))?;
+    let mut analysis = analyze_prompt_heuristic(&prompt, &load_prompt_criteria(&db));
+    if let Some(warning) = excluded_paths_warning(&db, project_id.as_deref(), &prompt) {
+        analysis.suggestions.insert(0, warning);
+    }
+    if let Some(pid) = project_id.as_deref() {
+        let _ = save_prompt_analysis(&db, pid, &prompt, &analysis, "heuristic");
+    }
+
+    Ok(analysis)
+}
+
+/// Latency-optimized prompt check for live feedback while the user types: only the
+/// heuristic total and the single most useful suggestion. Never waits for the DB
+/// (uses the last loaded criteria while it is busy), builds no enhanced prompt, and
+/// saves nothing; analyze_ralph_prompt(_with_ai) gives the full breakdown on request.
+#[tauri::command]
+pub async fn quick_analyze_ralph_prompt(
+    prompt: String,
+    state: State<'_, AppState>,
+) -> Result<QuickPromptAnalysis, String> {
+    let config = match state.db.try_lock() {
+        Ok(db) => load_prompt_criteria(&db),
+        Err(_) => cached_prompt_criteria(),
+    };
+    Ok(quick_analyze_prompt(&prompt, &config))
+}
 
-    let quality_score = clarity.score + specificity.score + context.score + scope.score;
+/// Get the team's custom prompt criteria and extra keywords (empty when unset).
+#[tauri::command]
+pub async fn get_prompt_criteria(state: State<'_, AppState>) -> Result<PromptCriteriaConfig, String> {
+    let db = state.db.lock().map_err(|e| format!("DB lock error: {}", e))?;
+    Ok(load_prompt_criteria(&db))
+}
 
-    let mut suggestions = Vec::new();
+/// Validate and save the team's custom prompt criteria. Returns the stored (trimmed) config.
+#[tauri::command]
+pub async fn save_prompt_criteria(
+    config: PromptCriteriaConfig,
+    state: State<'_, AppState>,
+) -> Result<PromptCriteriaConfig, String> {
+    let config = validate_prompt_criteria(config)?;
+    let json = serde_json::to_string(&config).map_err(|e| format!("Failed to serialize criteria: {}", e))?;
 
-    if clarity.score < 15 {
-        suggestions.push("Add clearer action verbs (e.g., 'implement', 'fix', 'refactor', 'add').".to_string());
-    }
-    if specificity.score < 15 {
-        suggestions.push("Mention specific files, functions, or components to modify.".to_string());
+    let db = state.db.lock().map_err(|e| format!("DB lock error: {}", e))?;
+    db.execute(
+        "INSERT OR REPLACE INTO settings (key, value) VALUES (?1, ?2)",
+        rusqlite::params![PROMPT_CRITERIA_SETTING, json],
+    )
+    .map_err(|e| format!("Failed to save prompt criteria: {}", e))?;
+    cache_prompt_criteria(&config);
+
+    Ok(config)
+}
+
+/// Prompt criteria config from settings; defaults when unset or unreadable.
+/// Also refreshes the copy quick_analyze_ralph_prompt uses while the DB is busy.
+pub(crate) fn load_prompt_criteria(db: &Connection) -> PromptCriteriaConfig {
+    let config: PromptCriteriaConfig = db
+        .query_row(
+            "SELECT value FROM settings WHERE key = ?1",
+            [PROMPT_CRITERIA_SETTING],
+            |row| row.get::<_, String>(0),
+        )
+        .ok()
+        .and_then(|json| serde_json::from_str(&json).ok())
+        .unwrap_or_default();
+    cache_prompt_criteria(&config);
+    config
+}
+
+/// Last criteria loaded or saved (None until the first load).
+static CACHED_PROMPT_CRITERIA: RwLock<Option<PromptCriteriaConfig>> = RwLock::new(None);
+
+fn cache_prompt_criteria(config: &PromptCriteriaConfig) {
+    if let Ok(mut cached) = CACHED_PROMPT_CRITERIA.write() {
+        *cached = Some(config.clone());
     }
-    if context.score < 15 {
-        suggestions.push("Include context about the current state and why this change is needed.".to_string());
+}
+
+fn cached_prompt_criteria() -> PromptCriteriaConfig {
+    CACHED_PROMPT_CRITERIA
+        .read()
+        .ok()
+        .and_then(|cached| cached.clone())
+        .unwrap_or_default()
+}
+
+/// Trim names and keywords, drop blank keywords, and reject unusable criteria.
+fn validate_prompt_criteria(config: PromptCriteriaConfig) -> Result<PromptCriteriaConfig, String> {
+    fn clean(keywords: Vec<String>) -> Vec<String> {
+        keywords
+            .into_iter()
+            .map(|k| k.trim().to_string())
+            .filter(|k| !k.is_empty())
+            .collect()
     }
-    if scope.score < 15 {
-        suggestions.push("Define clear boundaries — what should and should NOT be changed.".to_string());
+
+    let mut seen = std::collections::HashSet::new();
+    let mut custom_criteria = Vec::new();
+    for criterion in config.custom_criteria {
+        let name = criterion.name.trim().to_string();
+        if name.is_empty() {
+            return Err("Criterion name cannot be empty.".to_string());
+        }
+        let key = name.to_lowercase();
+        if BUILTIN_CRITERIA.contains(&key.as_str()) {
+            return Err(format!("'{}' is a built-in criterion; add keywords to it instead.", name));
+        }
+        if !seen.insert(key) {
+            return Err(format!("Duplicate criterion '{}'.", name));
+        }
+        let keywords = clean(criterion.keywords);
+        if keywords.is_empty() {
+            return Err(format!("Criterion '{}' needs at least one keyword.", name));
+        }
+        if !(1..=100).contains(&criterion.weight) {
+            return Err(format!("Criterion '{}' weight must be between 1 and 100.", name));
+        }
+        custom_criteria.push(CustomPromptCriterion {
+            name,
+            keywords,
+            weight: criterion.weight,
+            suggestion: criterion.suggestion.map(|s| s.trim().to_string()).filter(|s| !s.is_empty()),
+        });
     }
 
+    let extra = config.extra_keywords;
+    Ok(PromptCriteriaConfig {
+        custom_criteria,
+        extra_keywords: crate::models::ralph::PromptKeywordExtras {
+            clarity: clean(extra.clarity),
+            specificity: clean(extra.specificity),
+            context: clean(extra.context),
+            scope: clean(extra.scope),
+        },
+    })
+}
+
+/// Score a prompt with the fast heuristic criteria (no DB or network access),
+/// including the team's custom criteria and extra keywords.
+/// The total is normalized to 0-100 over the built-in and custom criteria.
+pub(crate) fn analyze_prompt_heuristic(prompt: &str, config: &PromptCriteriaConfig) -> PromptAnalysis {
+    let (criteria, weak) = score_prompt_criteria(prompt, config);
+    let quality_score = normalized_quality_score(&criteria);
+
     let enhanced_prompt = if quality_score < 70 {
-        Some(generate_enhanced_prompt(&prompt))
+        Some(generate_enhanced_prompt(prompt))
     } else {
         None
     };
 
-    Ok(PromptAnalysis {
+    PromptAnalysis {
         quality_score,
-        criteria: vec![clarity, specificity, context, scope],
-        suggestions,
+        criteria,
+        suggestions: weak.into_iter().map(|(_, suggestion)| suggestion).collect(),
         enhanced_prompt,
-    })
+    }
+}
+
+/// The heuristic score and the suggestion for the weakest criterion (earliest on ties),
+/// skipping the enhanced prompt. Same total as analyze_prompt_heuristic.
+pub(crate) fn quick_analyze_prompt(prompt: &str, config: &PromptCriteriaConfig) -> QuickPromptAnalysis {
+    let (criteria, weak) = score_prompt_criteria(prompt, config);
+    let top_suggestion = weak
+        .into_iter()
+        .min_by_key(|(i, _)| criteria[*i].score * 1000 / criteria[*i].max_score.max(1))
+        .map(|(_, suggestion)| suggestion);
+
+    QuickPromptAnalysis {
+        quality_score: normalized_quality_score(&criteria),
+        top_suggestion,
+    }
+}
+
+/// Sum of criterion scores normalized to 0-100.
+fn normalized_quality_score(criteria: &[PromptCriterion]) -> u32 {
+    let (earned, possible) = criteria
+        .iter()
+        .fold((0, 0), |(earned, possible), c| (earned + c.score, possible + c.max_score));
+    (earned * 100 + possible / 2) / possible.max(1)
+}
+
+/// Built-in then custom criteria, plus (criterion index, suggestion) for each one
+/// scored low enough to deserve a suggestion, in criteria order.
+fn score_prompt_criteria(prompt: &str, config: &PromptCriteriaConfig) -> (Vec<PromptCriterion>, Vec<(usize, String)>) {
+    let extra = &config.extra_keywords;
+    let clarity = score_clarity(prompt, &extra.clarity);
+    let specificity = score_specificity(prompt, &extra.specificity);
+    let context = score_context(prompt, &extra.context);
+    let scope = score_scope(prompt, &extra.scope);
+    let custom: Vec<PromptCriterion> = config
+        .custom_criteria
+        .iter()
+        .filter(|c| c.weight > 0)
+        .map(|c| score_custom(prompt, c))
+        .collect();
+
+    let mut weak = Vec::new();
+
+    if clarity.score < 15 {
+        weak.push((0, "Add clearer action verbs (e.g., 'implement', 'fix', 'refactor', 'add').".to_string()));
+    }
+    if specificity.score < 15 {
+        weak.push((1, "Mention specific files, functions, or components to modify.".to_string()));
+    }
+    if context.score < 15 {
+        weak.push((2, "Include context about the current state and why this change is needed.".to_string()));
+    }
+    if scope.score < 15 {
+        weak.push((3, "Define clear boundaries — what should and should NOT be changed.".to_string()));
+    }
+    for (i, (criterion, config)) in custom.iter().zip(config.custom_criteria.iter().filter(|c| c.weight > 0)).enumerate() {
+        if criterion.score * 5 < criterion.max_score * 3 {
+            weak.push((4 + i, config.suggestion.clone().unwrap_or_else(|| criterion.feedback.clone())));
+        }
+    }
+
+    let criteria = [clarity, specificity, context, scope].into_iter().chain(custom).collect();
+    (criteria, weak)
 }
 
 /// AI-powered prompt analysis and enhancement.
 /// Provides deeper analysis and project-aware suggestions when context is provided.
 /// Falls back to heuristic analysis if API call fails.
+/// The result is saved to prompt_analyses with its source ("ai" or "heuristic").
 #[tauri::command]
 pub async fn analyze_ralph_prompt_with_ai(
     prompt: String,
+    project_id: Option<String>,
     project_name: Option<String>,
     project_language: Option<String>,
     project_framework: Option<String>,
     project_files: Option<Vec<String>>,
     state: State<'_, AppState>,
 ) -> Result<PromptAnalysis, String> {
-    // Try to get API key
-    let api_key = {
+    // Try to get API key; prefer indexed suggestions over the caller's file list
+    let (api_key, project_files) = {
         let db = state.db.lock().map_err(|e| format!("DB lock error: {}", e))?;
-        ai::get_api_key(&db).ok()
+        let suggested = project_id
+            .as_deref()
+            .and_then(|pid| suggested_files(&db, pid, &prompt, SUGGESTED_FILES_FOR_AI).ok())
+            .filter(|files| !files.is_empty())
+            .map(|files| files.into_iter().map(|f| f.path).collect());
+        (ai::get_api_key(&db).ok(), suggested.or(project_files))
+    };
+
+    let ai_analysis = match api_key {
+        Some(api_key) => {
+            let analysis = analyze_prompt_with_ai(
+                &state.http_client,
+                &api_key,
+                &prompt,
+                project_name.as_deref(),
+                project_language.as_deref(),
+                project_framework.as_deref(),
+                project_files.as_deref(),
+            );
+            ai_audit::scoped(project_id.as_deref().unwrap_or_default(), "prompt analysis", analysis).await
+        }
+        None => None,
     };
 
-    // If no API key, fall back to heuristic analysis
-    let Some(api_key) = api_key else {
-        return analyze_ralph_prompt(prompt).await;
+    // If no API key or the AI call failed, fall back to heuristic analysis
+    let db = state.db.lock().map_err(|e| format!("DB lock error: {}", e))?;
+    let (mut analysis, source) = match ai_analysis {
+        Some(analysis) => (analysis, "ai"),
+        None => (analyze_prompt_heuristic(&prompt, &load_prompt_criteria(&db)), "heuristic"),
     };
+    if let Some(warning) = excluded_paths_warning(&db, project_id.as_deref(), &prompt) {
+        analysis.suggestions.insert(0, warning);
+    }
+
+    if let Some(pid) = project_id.as_deref() {
+        let _ = save_prompt_analysis(&db, pid, &prompt, &analysis, source);
+    }
+
+    Ok(analysis)
+}
+
+/// Files passed to the AI prompt analysis from core::doc_index.
+const SUGGESTED_FILES_FOR_AI: usize = 20;
+
+/// Rank the project's files by likely relevance to a prompt, best first (default 10, max 50).
+/// Rebuilds the project's doc index first when it is older than doc_index::INDEX_TTL_SECS.
+#[tauri::command]
+pub async fn suggest_relevant_files(
+    project_id: String,
+    prompt_text: String,
+    limit: Option<u32>,
+    state: State<'_, AppState>,
+) -> Result<Vec<FileSuggestion>, String> {
+    let db = state.db.lock().map_err(|e| format!("DB lock error: {}", e))?;
+    suggested_files(&db, &project_id, &prompt_text, limit.unwrap_or(10).clamp(1, 50) as usize)
+}
 
+fn suggested_files(db: &Connection, project_id: &str, prompt: &str, limit: usize) -> Result<Vec<FileSuggestion>, String> {
+    let project_path: String = db
+        .query_row("SELECT path FROM projects WHERE id = ?1", [project_id], |row| row.get(0))
+        .map_err(|_| format!("Project not found: {}", project_id))?;
+    doc_index::suggest_files(db, project_id, &project_path, prompt, limit)
+}
+
+/// Run the Claude-backed prompt analysis. Returns None on API or parse failure.
+async fn analyze_prompt_with_ai(
+    client: &reqwest::Client,
+    api_key: &str,
+    prompt: &str,
+    project_name: Option<&str>,
+    project_language: Option<&str>,
+    project_framework: Option<&str>,
+    project_files: Option<&[String]>,
+) -> Option<PromptAnalysis> {
     let system = r#"You are an expert at analyzing prompts for AI coding assistants. Your job is to:
 1. Score the prompt quality (0-100) based on clarity, specificity, context, and scope
 2. Provide specific, actionable suggestions to improve weak areas
@@ -198,19 +564,19 @@ ENHANCED PROMPT REQUIREMENTS:
     // Add project context if available
     if project_name.is_some() || project_language.is_some() || project_framework.is_some() {
         user_prompt.push_str("\n## Project Context\n");
-        if let Some(ref name) = project_name {
+        if let Some(name) = project_name {
             user_prompt.push_str(&format!("- Project: {}\n", name));
         }
-        if let Some(ref lang) = project_language {
+        if let Some(lang) = project_language {
             user_prompt.push_str(&format!("- Language: {}\n", lang));
         }
-        if let Some(ref fw) = project_framework {
+        if let Some(fw) = project_framework {
             user_prompt.push_str(&format!("- Framework: {}\n", fw));
         }
     }
 
     // Add relevant files if provided
-    if let Some(ref files) = project_files {
+    if let Some(files) = project_files {
         if !files.is_empty() {
             user_prompt.push_str("\n## Relevant Project Files\n");
             for file in files.iter().take(20) {
@@ -223,77 +589,301 @@ ENHANCED PROMPT REQUIREMENTS:
     user_prompt.push_str("\nProvide your analysis as JSON only.");
 
     // Call Claude API
-    let response = match ai::call_claude(&state.http_client, &api_key, system, &user_prompt).await {
-        Ok(r) => r,
-        Err(_) => {
-            // Fall back to heuristic on API error
-            return analyze_ralph_prompt(prompt).await;
-        }
-    };
+    let call = ai::call_claude(client, api_key, system, &user_prompt);
+    let response = ai_queue::run(AiJobKind::Interactive, "prompt analysis", call).await.ok()?;
 
     // Parse AI response
-    match serde_json::from_str::<serde_json::Value>(&response) {
-        Ok(val) => {
-            let quality_score = val.get("qualityScore")
-                .and_then(|v| v.as_u64())
-                .unwrap_or(50) as u32;
-
-            let criteria = val.get("criteria")
-                .and_then(|v| v.as_array())
-                .map(|arr| {
-                    arr.iter().map(|c| PromptCriterion {
-                        name: c.get("name").and_then(|v| v.as_str()).unwrap_or("Unknown").to_string(),
-                        score: c.get("score").and_then(|v| v.as_u64()).unwrap_or(0) as u32,
-                        max_score: 25,
-                        feedback: c.get("feedback").and_then(|v| v.as_str()).unwrap_or("").to_string(),
-                    }).collect()
-                })
-                .unwrap_or_else(|| {
-                    // Fallback criteria
-                    vec![
-                        PromptCriterion { name: "Clarity".to_string(), score: quality_score / 4, max_score: 25, feedback: "AI analysis".to_string() },
-                        PromptCriterion { name: "Specificity".to_string(), score: quality_score / 4, max_score: 25, feedback: "AI analysis".to_string() },
-                        PromptCriterion { name: "Context".to_string(), score: quality_score / 4, max_score: 25, feedback: "AI analysis".to_string() },
-                        PromptCriterion { name: "Scope".to_string(), score: quality_score / 4, max_score: 25, feedback: "AI analysis".to_string() },
-                    ]
-                });
+    let val = serde_json::from_str::<serde_json::Value>(&response).ok()?;
+
+    let quality_score = val.get("qualityScore")
+        .and_then(|v| v.as_u64())
+        .unwrap_or(50) as u32;
+
+    let criteria = val.get("criteria")
+        .and_then(|v| v.as_array())
+        .map(|arr| {
+            arr.iter().map(|c| PromptCriterion {
+                name: c.get("name").and_then(|v| v.as_str()).unwrap_or("Unknown").to_string(),
+                score: c.get("score").and_then(|v| v.as_u64()).unwrap_or(0) as u32,
+                max_score: 25,
+                feedback: c.get("feedback").and_then(|v| v.as_str()).unwrap_or("").to_string(),
+            }).collect()
+        })
+        .unwrap_or_else(|| {
+            // Fallback criteria
+            vec![
+                PromptCriterion { name: "Clarity".to_string(), score: quality_score / 4, max_score: 25, feedback: "AI analysis".to_string() },
+                PromptCriterion { name: "Specificity".to_string(), score: quality_score / 4, max_score: 25, feedback: "AI analysis".to_string() },
+                PromptCriterion { name: "Context".to_string(), score: quality_score / 4, max_score: 25, feedback: "AI analysis".to_string() },
+                PromptCriterion { name: "Scope".to_string(), score: quality_score / 4, max_score: 25, feedback: "AI analysis".to_string() },
+            ]
+        });
 
-            let suggestions = val.get("suggestions")
-                .and_then(|v| v.as_array())
-                .map(|arr| {
-                    arr.iter()
-                        .filter_map(|s| s.as_str().map(|s| s.to_string()))
-                        .collect()
-                })
-                .unwrap_or_default();
+    let suggestions = val.get("suggestions")
+        .and_then(|v| v.as_array())
+        .map(|arr| {
+            arr.iter()
+                .filter_map(|s| s.as_str().map(|s| s.to_string()))
+                .collect()
+        })
+        .unwrap_or_default();
 
-            let enhanced_prompt = val.get("enhancedPrompt")
-                .and_then(|v| v.as_str())
-                .map(|s| s.to_string());
+    let enhanced_prompt = val.get("enhancedPrompt")
+        .and_then(|v| v.as_str())
+        .map(|s| s.to_string());
 
-            Ok(PromptAnalysis {
-                quality_score,
-                criteria,
-                suggestions,
-                enhanced_prompt,
-            })
-        }
-        Err(_) => {
-            // AI returned non-JSON, fall back to heuristic
-            analyze_ralph_prompt(prompt).await
-        }
+    Some(PromptAnalysis {
+        quality_score,
+        criteria,
+        suggestions,
+        enhanced_prompt,
+    })
+}
+
+/// Maximum number of saved prompt analyses per project (prevents DB bloat)
+const MAX_PROMPT_ANALYSES_PER_PROJECT: i64 = 100;
+
+/// Persist a prompt analysis so it can be revisited from the prompt editor.
+/// Warning for a prompt that references files on the project's "never send to AI" list.
+/// The loop's Claude CLI run could read them, so the user is told before starting.
+fn excluded_paths_warning(db: &Connection, project_id: Option<&str>, prompt: &str) -> Option<String> {
+    let project_path: String = db
+        .query_row("SELECT path FROM projects WHERE id = ?1", [project_id?], |row| row.get(0))
+        .ok()?;
+    let paths = ai::excluded_references(prompt, &ai::load_ai_excludes(&project_path));
+    if paths.is_empty() {
+        return None;
     }
+    Some(format!(
+        "This task references files excluded from AI ({}): {}. Remove them from the prompt or the loop may read them.",
+        ai::AI_EXCLUDE_FILE,
+        paths.join(", ")
+    ))
+}
+
+/// Save an analysis for a project and prune it to the newest MAX_PROMPT_ANALYSES_PER_PROJECT.
+/// Analyses without a project are not saved: list_prompt_analyses could never return them.
+fn save_prompt_analysis(
+    db: &Connection,
+    project_id: &str,
+    prompt: &str,
+    analysis: &PromptAnalysis,
+    source: &str,
+) -> Result<(), String> {
+    let id = uuid::Uuid::new_v4().to_string();
+    let now = Utc::now().to_rfc3339();
+    let criteria_json = serde_json::to_string(&analysis.criteria).unwrap_or_else(|_| "[]".to_string());
+    let suggestions_json = serde_json::to_string(&analysis.suggestions).unwrap_or_else(|_| "[]".to_string());
+
+    db.execute(
+        "INSERT INTO prompt_analyses (id, project_id, prompt, quality_score, criteria, suggestions, enhanced_prompt, source, created_at)
+         VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7, ?8, ?9)",
+        rusqlite::params![
+            id,
+            project_id,
+            prompt,
+            analysis.quality_score,
+            criteria_json,
+            suggestions_json,
+            analysis.enhanced_prompt,
+            source,
+            now
+        ],
+    )
+    .map_err(|e| format!("Failed to save prompt analysis: {}", e))?;
+
+    // Prune old analyses (keep only the most recent N per project)
+    let _ = db.execute(
+        "DELETE FROM prompt_analyses WHERE project_id = ?1 AND id NOT IN (
+            SELECT id FROM prompt_analyses WHERE project_id = ?1 ORDER BY created_at DESC LIMIT ?2
+        )",
+        rusqlite::params![project_id, MAX_PROMPT_ANALYSES_PER_PROJECT],
+    );
+
+    Ok(())
+}
+
+/// List saved prompt analyses for a project, newest first.
+#[tauri::command]
+pub async fn list_prompt_analyses(
+    project_id: String,
+    limit: Option<u32>,
+    state: State<'_, AppState>,
+) -> Result<Vec<PromptAnalysisRecord>, String> {
+    let db = state.db.lock().map_err(|e| format!("DB lock error: {}", e))?;
+    let limit = limit.unwrap_or(50);
+
+    let mut stmt = db
+        .prepare(
+            "SELECT id, project_id, prompt, quality_score, criteria, suggestions, enhanced_prompt, source, created_at
+             FROM prompt_analyses
+             WHERE project_id = ?1
+             ORDER BY created_at DESC
+             LIMIT ?2",
+        )
+        .map_err(|e| format!("Failed to query prompt analyses: {}", e))?;
+
+    let records = stmt
+        .query_map(rusqlite::params![project_id, limit], |row| {
+            let criteria_json: String = row.get(4)?;
+            let suggestions_json: String = row.get(5)?;
+            Ok(PromptAnalysisRecord {
+                id: row.get(0)?,
+                project_id: row.get(1)?,
+                prompt: row.get(2)?,
+                quality_score: row.get(3)?,
+                criteria: serde_json::from_str(&criteria_json).unwrap_or_default(),
+                suggestions: serde_json::from_str(&suggestions_json).unwrap_or_default(),
+                enhanced_prompt: row.get(6)?,
+                source: row.get(7)?,
+                created_at: row.get(8)?,
+            })
+        })
+        .map_err(|e| format!("Failed to read prompt analyses: {}", e))?
+        .filter_map(|r| r.ok())
+        .collect();
+
+    Ok(records)
+}
+
+/// Check that a loop can run before starting it: Claude CLI installed and logged in,
+/// project folder present (and a git repository for PRD mode, which commits per story),
+/// API key for issue extraction, and free disk space. `mode` is "iterative" (default) or "prd".
+#[tauri::command]
+pub async fn check_ralph_prerequisites(
+    project_id: String,
+    mode: Option<String>,
+    state: State<'_, AppState>,
+) -> Result<RalphPreflight, String> {
+    let (project_path, has_api_key) = {
+        let db = state.db.lock().map_err(|e| format!("DB lock error: {}", e))?;
+        let path: String = db
+            .query_row("SELECT path FROM projects WHERE id = ?1", [&project_id], |row| row.get(0))
+            .map_err(|e| format!("Project not found: {}", e))?;
+        (path, ai::get_api_key(&db).is_ok())
+    };
+    let require_git = mode.as_deref() == Some("prd");
+    let cli = find_claude_cli();
+    Ok(ralph_preflight::preflight(cli.as_deref(), &project_path, require_git, has_api_key))
 }
 
 /// Start a new RALPH loop for a project (iterative mode).
 /// Creates a loop record in the DB with "running" status and executes via Claude CLI.
+/// `max_deleted_percent` (1-100) overrides the destructive-change threshold;
+/// `max_duration_minutes` wraps the loop up once that much wall-clock time has passed.
 #[tauri::command]
-pub async fn start_ralph_loop(
+#[allow(clippy::too_many_arguments)]
+pub async fn start_ralph_loop<R: Runtime>(
     project_id: String,
     prompt: String,
     enhanced_prompt: Option<String>,
     quality_score: u32,
+    max_deleted_percent: Option<u32>,
+    resume_session: Option<bool>,
+    max_duration_minutes: Option<u32>,
+    app: AppHandle<R>,
     state: State<'_, AppState>,
+) -> Result<RalphLoop, String> {
+    let mut options = LoopOptions::default();
+    if let Some(percent) = max_deleted_percent {
+        options.max_deleted_percent = percent.clamp(1, 100);
+    }
+    options.resume_session = resume_session.unwrap_or(false);
+    options.max_duration_minutes = validate_max_duration(max_duration_minutes)?;
+    spawn_iterative_loop(
+        &state,
+        app,
+        project_id,
+        prompt,
+        enhanced_prompt,
+        quality_score,
+        options,
+        "Started RALPH loop (iterative mode)",
+    )
+}
+
+/// Execution settings for an iterative loop. Stored as JSON in
+/// ralph_loops.loop_options so a resumed loop keeps them.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct LoopOptions {
+    /// Comma-separated --allowedTools value
+    pub allowed_tools: String,
+    pub max_iterations: u32,
+    /// Commands (run without a shell) that must all exit 0 before the loop may complete
+    #[serde(default)]
+    pub acceptance_gates: Vec<String>,
+    /// Branch to create (or switch to) before the first iteration
+    #[serde(default)]
+    pub branch: Option<String>,
+    /// Stop for review when an iteration deletes more than this percent of a file's lines
+    #[serde(default = "default_max_deleted_percent")]
+    pub max_deleted_percent: u32,
+    /// Continue the previous iteration's Claude CLI session (--resume) instead of starting fresh
+    #[serde(default)]
+    pub resume_session: bool,
+    /// Session size in tokens at or above which the next iteration starts a fresh session
+    #[serde(default = "default_session_token_limit")]
+    pub session_token_limit: u32,
+    /// Wall-clock limit; no new iteration starts once it has passed (None = no limit)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub max_duration_minutes: Option<u32>,
+}
+
+fn default_max_deleted_percent() -> u32 {
+    worktree::DEFAULT_MAX_DELETED_PERCENT
+}
+
+fn default_session_token_limit() -> u32 {
+    DEFAULT_SESSION_TOKEN_LIMIT
+}
+
+impl Default for LoopOptions {
+    fn default() -> Self {
+        LoopOptions {
+            allowed_tools: DEFAULT_ALLOWED_TOOLS.to_string(),
+            max_iterations: MAX_ITERATIONS,
+            acceptance_gates: Vec::new(),
+            branch: None,
+            max_deleted_percent: worktree::DEFAULT_MAX_DELETED_PERCENT,
+            resume_session: false,
+            session_token_limit: DEFAULT_SESSION_TOKEN_LIMIT,
+            max_duration_minutes: None,
+        }
+    }
+}
+
+/// Longest time box a loop accepts (one day).
+const MAX_DURATION_MINUTES: u32 = 24 * 60;
+
+/// Check a requested loop time box: None stays unlimited, 1..=MAX_DURATION_MINUTES is kept.
+pub(crate) fn validate_max_duration(minutes: Option<u32>) -> Result<Option<u32>, String> {
+    match minutes {
+        Some(m) if m == 0 || m > MAX_DURATION_MINUTES => {
+            Err(format!("Time limit must be between 1 and {} minutes", MAX_DURATION_MINUTES))
+        }
+        other => Ok(other),
+    }
+}
+
+/// True once `max_duration_minutes` of wall-clock time have passed since `started`.
+fn time_limit_reached(started: std::time::Instant, max_duration_minutes: Option<u32>) -> bool {
+    max_duration_minutes
+        .is_some_and(|minutes| started.elapsed() >= std::time::Duration::from_secs(u64::from(minutes) * 60))
+}
+
+/// Insert an iterative loop record and execute it in the background.
+/// Shared by start_ralph_loop and start_ralph_loop_from_template.
+#[allow(clippy::too_many_arguments)]
+pub(crate) fn spawn_iterative_loop<R: Runtime>(
+    state: &AppState,
+    app: AppHandle<R>,
+    project_id: String,
+    prompt: String,
+    enhanced_prompt: Option<String>,
+    quality_score: u32,
+    options: LoopOptions,
+    activity_message: &str,
 ) -> Result<RalphLoop, String> {
     // Get project path first
     let project_path = {
@@ -306,12 +896,20 @@ pub async fn start_ralph_loop(
             .prepare("SELECT path FROM projects WHERE id = ?1")
             .map_err(|e| format!("Failed to prepare query: {}", e))?;
 
-        stmt.query_row(rusqlite::params![&project_id], |row| row.get::<_, String>(0))
-            .map_err(|e| format!("Project not found: {}", e))?
+        let path = stmt
+            .query_row(rusqlite::params![&project_id], |row| row.get::<_, String>(0))
+            .map_err(|e| format!("Project not found: {}", e))?;
+        command_guard::require_approved(&db, &project_id, &path, &options.acceptance_gates, "acceptance_gate")?;
+        path
     };
 
     let id = uuid::Uuid::new_v4().to_string();
     let now = Utc::now().to_rfc3339();
+    let options_json = if options == LoopOptions::default() {
+        None
+    } else {
+        serde_json::to_string(&options).ok()
+    };
 
     // Insert loop record
     {
@@ -321,13 +919,13 @@ pub async fn start_ralph_loop(
             .map_err(|e| format!("Failed to lock database: {}", e))?;
 
         db.execute(
-            "INSERT INTO ralph_loops (id, project_id, prompt, enhanced_prompt, status, quality_score, iterations, outcome, started_at, created_at, mode) VALUES (?1, ?2, ?3, ?4, 'running', ?5, 0, NULL, ?6, ?6, 'iterative')",
-            rusqlite::params![&id, &project_id, &prompt, &enhanced_prompt, quality_score, &now],
+            "INSERT INTO ralph_loops (id, project_id, prompt, enhanced_prompt, status, quality_score, iterations, outcome, started_at, created_at, mode, loop_options) VALUES (?1, ?2, ?3, ?4, 'running', ?5, 0, NULL, ?6, ?6, 'iterative', ?7)",
+            rusqlite::params![&id, &project_id, &prompt, &enhanced_prompt, quality_score, &now, options_json],
         )
         .map_err(|e| format!("Failed to create RALPH loop: {}", e))?;
 
         // Log activity
-        let _ = db::log_activity_db(&db, &project_id, "generate", "Started RALPH loop (iterative mode)");
+        events::publish(&db, AppEvent::activity(&project_id, ActivityType::Ralph, activity_message));
     }
 
     // Create the loop result to return immediately
@@ -347,6 +945,11 @@ pub async fn start_ralph_loop(
         mode: "iterative".to_string(),
         current_story: None,
         total_stories: None,
+        injected_patterns: None,
+        review_diff: None,
+        parent_loop_id: None,
+        eta_at: None,
+        avg_story_secs: None,
     };
 
     // Prepare data for background task
@@ -355,81 +958,213 @@ pub async fn start_ralph_loop(
 
     // Spawn background task to execute Claude CLI
     tokio::spawn(async move {
-        execute_ralph_loop(loop_id, project_id, project_path, final_prompt).await;
+        execute_ralph_loop(app, loop_id, project_id, project_path, final_prompt, options).await;
     });
 
     Ok(loop_result)
 }
 
 /// Start a new RALPH loop in PRD mode (fresh context per story, git commits between).
-/// Parses the PRD JSON and executes each story sequentially.
+/// Parses the PRD JSON and executes each story sequentially. `max_duration_minutes`
+/// overrides the PRD's own maxDurationMinutes time limit.
 #[tauri::command]
 pub async fn start_ralph_loop_prd(
     project_id: String,
     prd_json: String,
+    max_duration_minutes: Option<u32>,
+    app: AppHandle,
     state: State<'_, AppState>,
 ) -> Result<RalphLoop, String> {
     use crate::models::ralph::PrdFile;
 
     // Parse the PRD JSON
-    let prd: PrdFile = serde_json::from_str(&prd_json)
+    let mut prd: PrdFile = serde_json::from_str(&prd_json)
         .map_err(|e| format!("Invalid PRD JSON: {}", e))?;
+    prd.max_duration_minutes = validate_max_duration(prd.max_duration_minutes)?;
+    let prd_json = match validate_max_duration(max_duration_minutes)? {
+        Some(minutes) => {
+            prd.max_duration_minutes = Some(minutes);
+            serde_json::to_string(&prd).map_err(|e| format!("Failed to serialize PRD: {}", e))?
+        }
+        None => prd_json,
+    };
 
     if prd.stories.is_empty() {
         return Err("PRD must contain at least one story".to_string());
     }
+    prd_story_order(&prd.stories)?;
 
-    let total_stories = prd.stories.len() as u32;
-
-    // Get project path
-    let project_path = {
-        let db = state
-            .db
-            .lock()
-            .map_err(|e| format!("Failed to lock database: {}", e))?;
-
-        let mut stmt = db
-            .prepare("SELECT path FROM projects WHERE id = ?1")
-            .map_err(|e| format!("Failed to prepare query: {}", e))?;
-
-        stmt.query_row(rusqlite::params![&project_id], |row| row.get::<_, String>(0))
-            .map_err(|e| format!("Project not found: {}", e))?
-    };
-
-    let id = uuid::Uuid::new_v4().to_string();
-    let now = Utc::now().to_rfc3339();
-
-    // Create a summary prompt for display
     let prompt_summary = format!(
         "PRD: {} ({} stories)\n{}",
         prd.name,
-        total_stories,
+        prd.stories.len(),
         prd.description.as_deref().unwrap_or("No description")
     );
+    let activity = format!("Started RALPH PRD loop: {}", prd.name);
+    spawn_prd_loop(&state, app, project_id, prd, prd_json, prompt_summary, None, &activity)
+}
 
-    // Insert loop record
-    {
+/// Start a continuation of a finished PRD loop that runs only the stories that failed
+/// validation. Stories committed by earlier runs are carried over as completed with their
+/// commit hashes; the new loop records the original in parent_loop_id.
+#[tauri::command]
+pub async fn retry_failed_prd_stories(
+    loop_id: String,
+    app: AppHandle,
+    state: State<'_, AppState>,
+) -> Result<RalphLoop, String> {
+    let (project_id, retry) = {
+        let db = state.db.lock().map_err(|e| format!("DB lock error: {}", e))?;
+        let (project_id, status, mode, prd_json, outcome): (String, String, String, Option<String>, Option<String>) = db
+            .query_row(
+                "SELECT project_id, status, COALESCE(mode, 'iterative'), enhanced_prompt, outcome FROM ralph_loops WHERE id = ?1",
+                [&loop_id],
+                |row| Ok((row.get(0)?, row.get(1)?, row.get(2)?, row.get(3)?, row.get(4)?)),
+            )
+            .map_err(|e| format!("Loop not found: {}", e))?;
+        if mode != "prd" {
+            return Err("Only PRD loops have stories to retry.".to_string());
+        }
+        if status != "completed" && status != "failed" {
+            return Err(format!("Loop is {}; only finished loops can be retried.", status));
+        }
+        let prd: crate::models::ralph::PrdFile = serde_json::from_str(prd_json.as_deref().unwrap_or(""))
+            .map_err(|e| format!("Loop has no readable PRD: {}", e))?;
+        (project_id, retry_prd(&prd, outcome.as_deref().unwrap_or(""))?)
+    };
+
+    let pending = retry.stories.iter().filter(|s| !s.completed).count();
+    let prd_json = serde_json::to_string(&retry).map_err(|e| format!("Failed to serialize PRD: {}", e))?;
+    let prompt_summary = format!(
+        "PRD retry: {} ({} failed or blocked {} of {})\n{}",
+        retry.name,
+        pending,
+        if pending == 1 { "story" } else { "stories" },
+        retry.stories.len(),
+        retry.description.as_deref().unwrap_or("No description")
+    );
+    let activity = format!("Retrying {} failed PRD stories: {}", pending, retry.name);
+    spawn_prd_loop(&state, app, project_id, retry, prd_json, prompt_summary, Some(&loop_id), &activity)
+}
+
+/// PRD for retrying a finished run: stories with a "✗ Story N" (failed), "⊘ Story N"
+/// (blocked by a failure), or "⏱ Story N" (cut off by the time limit) outcome line become pending,
+/// "✓ Story N" stories (and stories already completed before the run) are kept as completed
+/// with their commit hashes, and stories the run never reached are dropped.
+fn retry_prd(prd: &crate::models::ralph::PrdFile, outcome: &str) -> Result<crate::models::ralph::PrdFile, String> {
+    let story_number = |line: &str, marker: &str| -> Option<usize> {
+        let rest = line.trim().strip_prefix(marker)?.trim_start().strip_prefix("Story ")?;
+        rest.split_once(':')?.0.trim().parse::<usize>().ok()
+    };
+    let mut committed: std::collections::HashMap<usize, Option<String>> = std::collections::HashMap::new();
+    let mut failed: std::collections::HashSet<usize> = std::collections::HashSet::new();
+    for line in outcome.lines() {
+        if let Some(n) = story_number(line, "✓") {
+            committed.insert(n, parse_prd_outcome_line(line).and_then(|(_, hash)| hash));
+        } else if let Some(n) = ["✗", "⊘", "⏱"].into_iter().find_map(|marker| story_number(line, marker)) {
+            failed.insert(n);
+        }
+    }
+    if failed.is_empty() {
+        return Err("No stories failed validation in this run.".to_string());
+    }
+
+    let mut retry = prd.clone();
+    retry.stories = prd
+        .stories
+        .iter()
+        .enumerate()
+        .filter_map(|(index, story)| {
+            let number = index + 1;
+            let mut story = story.clone();
+            if failed.contains(&number) {
+                story.completed = false;
+                story.commit_hash = None;
+            } else if let Some(hash) = committed.get(&number) {
+                story.completed = true;
+                story.commit_hash = hash.clone().or(story.commit_hash);
+            } else if !story.completed {
+                return None;
+            }
+            Some(story)
+        })
+        .collect();
+    Ok(retry)
+}
+
+/// Insert a PRD loop (optionally linked to the loop it continues) and start it in the background.
+#[allow(clippy::too_many_arguments)]
+fn spawn_prd_loop(
+    state: &State<'_, AppState>,
+    app: AppHandle,
+    project_id: String,
+    prd: crate::models::ralph::PrdFile,
+    prd_json: String,
+    prompt_summary: String,
+    parent_loop_id: Option<&str>,
+    activity: &str,
+) -> Result<RalphLoop, String> {
+    let (loop_result, project_path) = {
         let db = state
             .db
             .lock()
             .map_err(|e| format!("Failed to lock database: {}", e))?;
+        insert_prd_loop(&db, &project_id, &prd, prd_json, prompt_summary, parent_loop_id, activity)?
+    };
 
-        db.execute(
-            "INSERT INTO ralph_loops (id, project_id, prompt, enhanced_prompt, status, quality_score, iterations, outcome, started_at, created_at, mode, current_story, total_stories) VALUES (?1, ?2, ?3, ?4, 'running', 100, 0, NULL, ?5, ?5, 'prd', 0, ?6)",
-            rusqlite::params![&id, &project_id, &prompt_summary, &prd_json, &now, total_stories],
-        )
-        .map_err(|e| format!("Failed to create RALPH loop: {}", e))?;
+    // Spawn background task to execute PRD
+    let loop_id = loop_result.id.clone();
+    tokio::spawn(async move {
+        execute_ralph_loop_prd(app, loop_id, project_id, project_path, prd).await;
+    });
 
-        // Log activity
-        let _ = db::log_activity_db(&db, &project_id, "generate", &format!("Started RALPH PRD loop: {}", prd.name));
-    }
+    Ok(loop_result)
+}
+
+/// Insert a "running" PRD loop record after checking its commands are approved for the project.
+/// Returns the loop and the project path; the caller runs execute_ralph_loop_prd.
+/// Shared with commands::ralph_workspace, which runs each workspace step this way.
+pub(crate) fn insert_prd_loop(
+    db: &Connection,
+    project_id: &str,
+    prd: &crate::models::ralph::PrdFile,
+    prd_json: String,
+    prompt_summary: String,
+    parent_loop_id: Option<&str>,
+    activity: &str,
+) -> Result<(RalphLoop, String), String> {
+    let total_stories = prd.stories.len() as u32;
+
+    let project_path: String = db
+        .query_row("SELECT path FROM projects WHERE id = ?1", rusqlite::params![project_id], |row| row.get(0))
+        .map_err(|e| format!("Project not found: {}", e))?;
+
+    // PRD files come from outside the app; their commands must be approved first
+    let commands: Vec<String> = [&prd.typecheck_command, &prd.test_command]
+        .into_iter()
+        .flatten()
+        .cloned()
+        .collect();
+    command_guard::require_approved(db, project_id, &project_path, &commands, "prd")?;
+
+    let id = uuid::Uuid::new_v4().to_string();
+    let now = Utc::now().to_rfc3339();
+
+    db.execute(
+        "INSERT INTO ralph_loops (id, project_id, prompt, enhanced_prompt, status, quality_score, iterations, outcome, started_at, created_at, mode, current_story, total_stories, parent_loop_id) VALUES (?1, ?2, ?3, ?4, 'running', 100, 0, NULL, ?5, ?5, 'prd', 0, ?6, ?7)",
+        rusqlite::params![&id, project_id, &prompt_summary, &prd_json, &now, total_stories, parent_loop_id],
+    )
+    .map_err(|e| format!("Failed to create RALPH loop: {}", e))?;
+
+    // Log activity
+    events::publish(db, AppEvent::activity(project_id, ActivityType::Ralph, activity));
 
     // Create the loop result to return immediately
     let loop_result = RalphLoop {
-        id: id.clone(),
-        project_id: project_id.clone(),
+        id,
+        project_id: project_id.to_string(),
         prompt: prompt_summary,
-        enhanced_prompt: Some(prd_json.clone()),
+        enhanced_prompt: Some(prd_json),
         status: "running".to_string(),
         quality_score: 100,
         iterations: 0,
@@ -441,39 +1176,78 @@ pub async fn start_ralph_loop_prd(
         mode: "prd".to_string(),
         current_story: Some(0),
         total_stories: Some(total_stories),
+        injected_patterns: None,
+        review_diff: None,
+        parent_loop_id: parent_loop_id.map(str::to_string),
+        eta_at: None,
+        avg_story_secs: None,
     };
 
-    // Spawn background task to execute PRD
-    let loop_id = id.clone();
-    tokio::spawn(async move {
-        execute_ralph_loop_prd(loop_id, project_id, project_path, prd).await;
-    });
-
-    Ok(loop_result)
+    Ok((loop_result, project_path))
 }
 
 /// Maximum iterations for a RALPH loop (prevents infinite loops)
 const MAX_ITERATIONS: u32 = 5;
 
+/// Tools granted to Claude in iterative loops unless a template overrides them
+pub(crate) const DEFAULT_ALLOWED_TOOLS: &str = "Read,Write,Edit,Bash,Glob,Grep";
+
+/// Characters of acceptance gate output kept in the issue fed to the next iteration
+const GATE_OUTPUT_CHARS: usize = 2000;
+
+/// Maximum learned patterns/mistakes prepended to a loop prompt
+const MAX_INJECTED_LEARNINGS: usize = 5;
+
+/// Default LoopOptions.session_token_limit
+const DEFAULT_SESSION_TOKEN_LIMIT: u32 = 150_000;
+
 /// Execute a RALPH loop via the Claude CLI in a background task.
 /// Runs iteratively: after each execution, uses AI to extract issues and feeds them
 /// to the next iteration until no issues remain or max iterations reached.
 /// Updates iteration count in real-time for UI progress display.
-async fn execute_ralph_loop(
+async fn execute_ralph_loop<R: Runtime>(
+    app: AppHandle<R>,
     loop_id: String,
     project_id: String,
     project_path: String,
     initial_prompt: String,
+    options: LoopOptions,
 ) {
     // Open a fresh database connection for this background task
     let db = match open_db_connection() {
         Ok(conn) => conn,
         Err(e) => {
-            eprintln!("RALPH: Failed to open database connection: {}", e);
+            tracing::error!(error = %e, "RALPH: Failed to open database connection");
             return;
         }
     };
 
+    // Work on the template's branch (created on first run, reused on resume)
+    if let Some(branch) = options.branch.as_deref().filter(|b| !on_branch(&project_path, b)) {
+        let permission = git_policy::load_policy(&db, &project_id).permission(GitOperation::Branch);
+        let checked_out = git_policy::authorize(&app, permission, &project_id, GitOperation::Branch, branch)
+            .await
+            .and_then(|_| checkout_loop_branch(&project_path, branch));
+        if let Err(e) = checked_out {
+            let _ = db.execute(
+                "UPDATE ralph_loops SET status = 'failed', outcome = ?1, completed_at = ?2 WHERE id = ?3",
+                rusqlite::params![e, Utc::now().to_rfc3339(), &loop_id],
+            );
+            emit_loop_progress(&app, &db, &loop_id);
+            return;
+        }
+    }
+
+    // Prepend relevant project patterns and resolved-mistake learnings to the task
+    let candidates = gather_learning_candidates(&db, &project_id, &project_path);
+    let injected = select_relevant_learnings(&initial_prompt, &candidates, MAX_INJECTED_LEARNINGS);
+    let injected_json = serde_json::to_string(&injected).unwrap_or_else(|_| "[]".to_string());
+    let _ = db.execute(
+        "UPDATE ralph_loops SET injected_patterns = ?1 WHERE id = ?2",
+        rusqlite::params![injected_json, &loop_id],
+    );
+    let initial_prompt = inject_learnings(&initial_prompt, &injected);
+
     // Create HTTP client for AI calls
     let http_client = reqwest::Client::new();
 
@@ -481,40 +1255,33 @@ async fn execute_ralph_loop(
     let api_key = ai::get_api_key(&db).ok();
 
     // Check if claude CLI is available
-    let claude_check = Command::new("which")
-        .arg("claude")
-        .output();
-
-    let claude_path = match claude_check {
-        Ok(output) if output.status.success() => {
-            String::from_utf8_lossy(&output.stdout).trim().to_string()
-        }
-        _ => {
-            // Try common paths
-            if Path::new("/usr/local/bin/claude").exists() {
-                "/usr/local/bin/claude".to_string()
-            } else if Path::new("/opt/homebrew/bin/claude").exists() {
-                "/opt/homebrew/bin/claude".to_string()
-            } else {
-                // Claude CLI not found - mark as failed
-                let now = Utc::now().to_rfc3339();
-                let _ = db.execute(
-                    "UPDATE ralph_loops SET status = 'failed', outcome = ?1, completed_at = ?2 WHERE id = ?3",
-                    rusqlite::params!["Claude CLI not found. Install with: npm install -g @anthropic-ai/claude-code", &now, &loop_id],
-                );
-                return;
-            }
-        }
+    let Some(claude_path) = find_claude_cli() else {
+        // Claude CLI not found - mark as failed
+        let now = Utc::now().to_rfc3339();
+        let _ = db.execute(
+            "UPDATE ralph_loops SET status = 'failed', outcome = ?1, completed_at = ?2 WHERE id = ?3",
+            rusqlite::params!["Claude CLI not found. Install with: npm install -g @anthropic-ai/claude-code", &now, &loop_id],
+        );
+        emit_loop_progress(&app, &db, &loop_id);
+        return;
     };
 
     // Track accumulated issues across iterations
     let mut all_issues: Vec<ExtractedIssue> = Vec::new();
     let mut current_prompt = initial_prompt.clone();
+    // Claude CLI session the next iteration resumes (LoopOptions.resume_session)
+    let mut resume_id: Option<String> = None;
     let mut final_outcome = String::new();
     let mut final_status = "completed".to_string();
+    // Issues the latest iteration left open, for the time limit summary
+    let mut open_issues: Vec<ExtractedIssue> = Vec::new();
+    let mut timed_out = false;
+    // The time box counts from this run (a resumed loop gets the full limit again)
+    let started = std::time::Instant::now();
 
     // Iterative loop
-    for iteration in 1..=MAX_ITERATIONS {
+    let max_iterations = options.max_iterations.max(1);
+    for iteration in 1..=max_iterations {
         // Check if loop was paused or killed
         let loop_status: Option<String> = db
             .query_row(
@@ -531,28 +1298,59 @@ async fn execute_ralph_loop(
             }
         }
 
+        // Out of time: wrap up with the progress so far instead of starting another iteration
+        if iteration > 1 && time_limit_reached(started, options.max_duration_minutes) {
+            timed_out = true;
+            final_outcome = time_limit_outcome(
+                options.max_duration_minutes.unwrap_or_default(),
+                iteration - 1,
+                &open_issues,
+                &final_outcome,
+            );
+            break;
+        }
+
         // Update iteration count immediately (real-time progress)
         let _ = db.execute(
             "UPDATE ralph_loops SET iterations = ?1 WHERE id = ?2",
             rusqlite::params![iteration, &loop_id],
         );
+        emit_loop_progress(&app, &db, &loop_id);
+
+        // Snapshot the working tree so the files this run touches can be attributed to it
+        let iteration_started = Utc::now().to_rfc3339();
+        let snapshot = worktree::take_snapshot(&project_path);
 
         // Execute claude with the current prompt
-        let result = Command::new(&claude_path)
+        let mut command = Command::new(&claude_path);
+        command
             .arg("-p")
             .arg(&current_prompt)
             .arg("--allowedTools")
-            .arg("Read,Write,Edit,Bash,Glob,Grep")
-            .current_dir(&project_path)
-            .output();
+            .arg(&options.allowed_tools)
+            .current_dir(&project_path);
+        if options.resume_session {
+            command.arg("--output-format").arg("json");
+        }
+        if let Some(session_id) = &resume_id {
+            command.arg("--resume").arg(session_id);
+        }
+        let result = proc::run(&mut command, ProcLimits::CLAUDE);
 
+        let mut session = CliSession::default();
         let (output_text, execution_failed) = match result {
             Ok(output) => {
-                let stdout = String::from_utf8_lossy(&output.stdout);
+                let raw_stdout = String::from_utf8_lossy(&output.stdout);
+                let stdout = if options.resume_session {
+                    session = parse_cli_json(&raw_stdout);
+                    session.text.clone()
+                } else {
+                    raw_stdout.to_string()
+                };
                 let stderr = String::from_utf8_lossy(&output.stderr);
 
-                if output.status.success() {
-                    (stdout.to_string(), false)
+                if output.success() {
+                    (stdout, false)
                 } else {
                     let error_msg = if stderr.is_empty() {
                         format!("Claude exited with code: {:?}\n{}", output.status.code(), stdout)
@@ -566,11 +1364,64 @@ async fn execute_ralph_loop(
                 (format!("Failed to execute Claude: {}", e), true)
             }
         };
+        audit_cli_run(&db, &project_id, &project_path, "RALPH iteration", &current_prompt, &output_text, !execution_failed);
+        let files_changed = snapshot
+            .as_ref()
+            .map(|s| worktree::changed_between(&project_path, s))
+            .unwrap_or_default();
+
+        // Stop before later iterations build on a destructive change
+        let destructive = snapshot
+            .as_ref()
+            .and_then(|s| worktree::check_destructive(&project_path, s, &files_changed, options.max_deleted_percent));
+        if let Some((changes, diff)) = destructive {
+            let summary = format!(
+                "Destructive change: {}",
+                changes.iter().map(|c| c.describe()).collect::<Vec<_>>().join(", ")
+            );
+            record_iteration(
+                &db,
+                &loop_id,
+                IterationRecord {
+                    iteration,
+                    story_index: None,
+                    status: "needs_review",
+                    summary: summary.clone(),
+                    issues_count: 0,
+                    files_changed: &files_changed,
+                    started_at: &iteration_started,
+                },
+            );
+            hold_for_review(&db, &loop_id, &summary, &diff);
+            emit_loop_progress(&app, &db, &loop_id);
+            events::publish(
+                &db,
+                AppEvent::activity(
+                    &project_id,
+                    ActivityType::Ralph,
+                    "RALPH loop paused for review after a destructive change",
+                ),
+            );
+            return;
+        }
 
         // If execution failed completely, mark as failed and exit
         if execution_failed && iteration == 1 {
             final_status = "failed".to_string();
             final_outcome = output_text.clone();
+            record_iteration(
+                &db,
+                &loop_id,
+                IterationRecord {
+                    iteration,
+                    story_index: None,
+                    status: "failed",
+                    summary: first_line(&output_text),
+                    issues_count: 0,
+                    files_changed: &files_changed,
+                    started_at: &iteration_started,
+                },
+            );
 
             // Record the failure as a mistake
             record_iteration_mistake(
@@ -584,20 +1435,47 @@ async fn execute_ralph_loop(
         }
 
         // Extract issues from the output using AI (if API key available)
-        let extracted_issues = if let Some(ref key) = api_key {
-            extract_issues_with_ai(&http_client, key, &output_text).await
+        let mut extracted_issues = if let Some(ref key) = api_key {
+            ai_audit::scoped(&project_id, "RALPH issue extraction", extract_issues_with_ai(&http_client, key, &output_text))
+                .await
         } else {
             // Fallback: simple heuristic issue extraction
             extract_issues_heuristic(&output_text)
         };
 
+        // Failing acceptance gates keep the loop going
+        let gate_failures = run_acceptance_gates(&project_path, &options.acceptance_gates);
+        let failing_gates: Vec<&str> = gate_failures.iter().map(|(gate, _)| *gate).collect();
+        extracted_issues.extend(gate_failures.iter().map(|(_, issue)| issue.clone()));
+
+        let iteration_status = if execution_failed {
+            "failed"
+        } else if extracted_issues.is_empty() {
+            "passed"
+        } else {
+            "issues"
+        };
+        record_iteration(
+            &db,
+            &loop_id,
+            IterationRecord {
+                iteration,
+                story_index: None,
+                status: iteration_status,
+                summary: issues_summary(&extracted_issues),
+                issues_count: extracted_issues.len() as u32,
+                files_changed: &files_changed,
+                started_at: &iteration_started,
+            },
+        );
+
         // Record each extracted issue as a mistake for learning
         for issue in &extracted_issues {
             let mistake_id = uuid::Uuid::new_v4().to_string();
             let now = Utc::now().to_rfc3339();
             let _ = db.execute(
-                "INSERT INTO ralph_mistakes (id, project_id, loop_id, mistake_type, description, context, resolution, learned_pattern, created_at)
-                 VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7, NULL, ?8)",
+                "INSERT INTO ralph_mistakes (id, project_id, loop_id, mistake_type, description, context, resolution, learned_pattern, created_at, file_path, line)
+                 VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7, NULL, ?8, ?9, ?10)",
                 rusqlite::params![
                     mistake_id,
                     project_id,
@@ -606,7 +1484,9 @@ async fn execute_ralph_loop(
                     issue.description,
                     format!("Iteration {}: {}", iteration, current_prompt),
                     issue.suggested_fix,
-                    now
+                    now,
+                    issue.file,
+                    issue.line
                 ],
             );
         }
@@ -616,7 +1496,7 @@ async fn execute_ralph_loop(
             final_status = "completed".to_string();
             // Truncate output if too long
             final_outcome = if output_text.len() > 10000 {
-                format!("{}...\n[Output truncated]", &output_text[..10000])
+                format!("{}...\n[Output truncated]", safe_read::truncate_str(&output_text, 10000))
             } else {
                 output_text
             };
@@ -625,16 +1505,24 @@ async fn execute_ralph_loop(
 
         // Add issues to accumulated list
         all_issues.extend(extracted_issues.clone());
+        open_issues = extracted_issues.clone();
+
+        // Out of iterations with a gate still failing: the loop did not meet its acceptance criteria
+        if iteration == max_iterations && !failing_gates.is_empty() {
+            final_status = "failed".to_string();
+            final_outcome = failing_gates_outcome(iteration, &failing_gates, &output_text);
+            break;
+        }
 
         // If this is the last iteration, mark as completed with issues noted
-        if iteration == MAX_ITERATIONS {
+        if iteration == max_iterations {
             final_status = "completed".to_string();
             final_outcome = format!(
                 "Completed after {} iterations. {} issues addressed.\n\n{}",
                 iteration,
                 all_issues.len(),
                 if output_text.len() > 8000 {
-                    format!("{}...\n[Output truncated]", &output_text[..8000])
+                    format!("{}...\n[Output truncated]", safe_read::truncate_str(&output_text, 8000))
                 } else {
                     output_text
                 }
@@ -642,8 +1530,17 @@ async fn execute_ralph_loop(
             break;
         }
 
-        // Build enhanced prompt for next iteration with context from prior issues
-        current_prompt = build_iteration_prompt(&initial_prompt, &all_issues, iteration);
+        // Resume the session with only the new issues while it is small enough; otherwise
+        // start fresh with the task and every prior issue
+        resume_id = next_session(&options, &session);
+        current_prompt = if resume_id.is_some() {
+            build_resume_prompt(&extracted_issues, iteration)
+        } else {
+            if options.resume_session && session.session_id.is_some() {
+                tracing::info!(loop_id = %loop_id, tokens = session.context_tokens, "RALPH: session over token limit, starting fresh");
+            }
+            build_iteration_prompt(&initial_prompt, &all_issues, iteration)
+        };
 
         // Store intermediate outcome
         final_outcome = output_text;
@@ -655,14 +1552,17 @@ async fn execute_ralph_loop(
         "UPDATE ralph_loops SET status = ?1, outcome = ?2, completed_at = ?3 WHERE id = ?4",
         rusqlite::params![&final_status, &final_outcome, &now, &loop_id],
     );
+    emit_loop_progress(&app, &db, &loop_id);
 
     // Log completion activity
-    let activity_msg = if final_status == "completed" {
+    let activity_msg = if timed_out {
+        "RALPH loop stopped at its time limit"
+    } else if final_status == "completed" {
         "RALPH loop completed successfully"
     } else {
         "RALPH loop failed"
     };
-    let _ = db::log_activity_db(&db, &project_id, "generate", activity_msg);
+    events::publish(&db, AppEvent::activity(&project_id, ActivityType::Ralph, activity_msg));
 
     // Prune old mistakes (keep only most recent 50 per project)
     let _ = db.execute(
@@ -673,22 +1573,54 @@ async fn execute_ralph_loop(
     );
 }
 
+/// Outcome of an iterative loop that used every iteration without its acceptance gates
+/// passing: the failing gates, then the last iteration's output.
+fn failing_gates_outcome(iterations: u32, failing_gates: &[&str], last_output: &str) -> String {
+    format!(
+        "Failed: acceptance gates still failing after {} {}: {}\n\n{}",
+        iterations,
+        if iterations == 1 { "iteration" } else { "iterations" },
+        failing_gates.iter().map(|gate| format!("`{}`", gate)).collect::<Vec<_>>().join(", "),
+        if last_output.len() > 8000 {
+            format!("{}...\n[Output truncated]", safe_read::truncate_str(last_output, 8000))
+        } else {
+            last_output.to_string()
+        }
+    )
+}
+
+/// Outcome of an iterative loop that stopped at its time limit: what is still open, then
+/// the last iteration's output.
+fn time_limit_outcome(minutes: u32, iterations: u32, open_issues: &[ExtractedIssue], last_output: &str) -> String {
+    format!(
+        "Stopped at the {}-minute time limit after {} {}. Still open: {}\n\n{}",
+        minutes,
+        iterations,
+        if iterations == 1 { "iteration" } else { "iterations" },
+        issues_summary(open_issues),
+        if last_output.len() > 8000 {
+            format!("{}...\n[Output truncated]", safe_read::truncate_str(last_output, 8000))
+        } else {
+            last_output.to_string()
+        }
+    )
+}
+
 /// Execute a RALPH loop in PRD mode (fresh context per story).
 /// Like the original "Ralph Wiggum" approach: each story gets a fresh Claude context,
 /// git commits between stories, validation runs after each story.
-async fn execute_ralph_loop_prd(
+pub(crate) async fn execute_ralph_loop_prd(
+    app: AppHandle,
     loop_id: String,
     project_id: String,
     project_path: String,
     prd: crate::models::ralph::PrdFile,
 ) {
-    use std::process::Command as StdCommand;
-
     // Open a fresh database connection
     let db = match open_db_connection() {
         Ok(conn) => conn,
         Err(e) => {
-            eprintln!("RALPH PRD: Failed to open database connection: {}", e);
+            tracing::error!(error = %e, "RALPH PRD: Failed to open database connection");
             return;
         }
     };
@@ -702,6 +1634,7 @@ async fn execute_ralph_loop_prd(
                 "UPDATE ralph_loops SET status = 'failed', outcome = ?1, completed_at = ?2 WHERE id = ?3",
                 rusqlite::params!["Claude CLI not found. Install with: npm install -g @anthropic-ai/claude-code", &now, &loop_id],
             );
+            emit_loop_progress(&app, &db, &loop_id);
             return;
         }
     };
@@ -709,17 +1642,69 @@ async fn execute_ralph_loop_prd(
     let total_stories = prd.stories.len();
     let mut completed_count = 0;
     let mut outcomes: Vec<String> = Vec::new();
+    let mut story_durations: Vec<u64> = Vec::new();
+    let mut eta: Option<(String, u64)> = None;
+    let story_event = |index: usize, phase: &str, iteration: Option<u32>, commit: Option<String>, completed: u32, eta: &Option<(String, u64)>| {
+        let story = &prd.stories[index];
+        monitor::emit_scoped(
+            &app,
+            MonitorKind::RalphLoop,
+            &loop_id,
+            monitor::PRD_STORY_EVENT,
+            PrdStoryProgress {
+                loop_id: loop_id.clone(),
+                project_id: project_id.clone(),
+                story_index: index as u32,
+                story_id: story.id.clone(),
+                story_title: story.title.clone(),
+                total_stories: total_stories as u32,
+                phase: phase.to_string(),
+                iteration,
+                max_iterations: prd.max_iterations_per_story,
+                commit,
+                completed_stories: completed,
+                eta_at: eta.as_ref().map(|(at, _)| at.clone()),
+                remaining_secs: eta.as_ref().map(|(_, secs)| *secs),
+                timestamp: Utc::now().to_rfc3339(),
+            },
+        );
+    };
 
-    // Create or checkout branch if specified
-    if prd.branch != "main" && prd.branch != "master" {
-        let _ = StdCommand::new("git")
-            .args(["checkout", "-B", &prd.branch])
-            .current_dir(&project_path)
-            .output();
+    // Create or checkout branch if specified and the project's git policy allows it
+    if prd.branch != "main" && prd.branch != "master" && !on_branch(&project_path, &prd.branch) {
+        let permission = git_policy::load_policy(&db, &project_id).permission(GitOperation::Branch);
+        match git_policy::authorize(&app, permission, &project_id, GitOperation::Branch, &prd.branch).await {
+            Ok(()) => {
+                let _ = proc::run(
+                    Command::new("git").args(["checkout", "-B", &prd.branch]).current_dir(&project_path),
+                    ProcLimits::GIT,
+                );
+            }
+            Err(e) => outcomes.push(format!("⚠ Stayed on the current branch instead of {}: {}", prd.branch, e)),
+        }
     }
 
-    // Process each story
-    for (index, story) in prd.stories.iter().enumerate() {
+    let order = match prd_story_order(&prd.stories) {
+        Ok(order) => order,
+        Err(e) => {
+            let now = Utc::now().to_rfc3339();
+            let _ = db.execute(
+                "UPDATE ralph_loops SET status = 'failed', outcome = ?1, completed_at = ?2 WHERE id = ?3",
+                rusqlite::params![e, &now, &loop_id],
+            );
+            emit_loop_progress(&app, &db, &loop_id);
+            return;
+        }
+    };
+    // Ids of stories that failed or were blocked; their dependents are blocked in turn
+    let mut failed_ids: std::collections::HashSet<&str> = std::collections::HashSet::new();
+    // Time box: once it has passed, no story or story iteration starts (see PrdFile.max_duration_minutes)
+    let started = std::time::Instant::now();
+    let mut timed_out = false;
+
+    // Process each story in dependency order
+    for (position, &index) in order.iter().enumerate() {
+        let story = &prd.stories[index];
         // Check if loop was paused or killed
         let loop_status: Option<String> = db
             .query_row(
@@ -738,15 +1723,44 @@ async fn execute_ralph_loop_prd(
         // Update current story progress
         let _ = db.execute(
             "UPDATE ralph_loops SET current_story = ?1, iterations = ?2 WHERE id = ?3",
-            rusqlite::params![index as u32, index as u32 + 1, &loop_id],
+            rusqlite::params![position as u32, position as u32 + 1, &loop_id],
         );
+        emit_loop_progress(&app, &db, &loop_id);
 
         // Skip completed stories
         if story.completed {
             completed_count += 1;
+            if let Some(ref hash) = story.commit_hash {
+                outcomes.push(format!("↺ Story {}: {} (commit: {}, earlier run)", index + 1, story.title, hash));
+            }
+            story_event(index, "skipped", None, None, completed_count, &eta);
             continue;
         }
 
+        // Skip stories whose dependencies failed
+        let blockers: Vec<&str> = story
+            .depends_on
+            .iter()
+            .map(String::as_str)
+            .filter(|dep| failed_ids.contains(dep))
+            .collect();
+        if !blockers.is_empty() {
+            outcomes.push(format!("⊘ Story {}: {} (blocked by {})", index + 1, story.title, blockers.join(", ")));
+            failed_ids.insert(story.id.as_str());
+            story_event(index, "blocked", None, None, completed_count, &eta);
+            continue;
+        }
+
+        // Out of time: list the story as not started so a retry picks it up
+        if time_limit_reached(started, prd.max_duration_minutes) {
+            timed_out = true;
+            outcomes.push(format!("⏱ Story {}: {} (not started: time limit reached)", index + 1, story.title));
+            story_event(index, "timed_out", None, None, completed_count, &eta);
+            continue;
+        }
+        let story_started = std::time::Instant::now();
+        story_event(index, "started", None, None, completed_count, &eta);
+
         // Build prompt for this story
         let story_prompt = build_story_prompt(story, &prd);
 
@@ -757,24 +1771,34 @@ async fn execute_ralph_loop_prd(
 
         while story_iterations < max_story_iterations && !story_success {
             story_iterations += 1;
-
-            let result = Command::new(&claude_path)
-                .arg("-p")
-                .arg(&story_prompt)
-                .arg("--allowedTools")
-                .arg("Read,Write,Edit,Bash,Glob,Grep")
-                .current_dir(&project_path)
-                .output();
+            story_event(index, "iteration", Some(story_iterations), None, completed_count, &eta);
+            let iteration_started = Utc::now().to_rfc3339();
+            let snapshot = worktree::take_snapshot(&project_path);
+
+            let result = proc::run(
+                Command::new(&claude_path)
+                    .arg("-p")
+                    .arg(&story_prompt)
+                    .arg("--allowedTools")
+                    .arg("Read,Write,Edit,Bash,Glob,Grep")
+                    .current_dir(&project_path),
+                ProcLimits::CLAUDE,
+            );
 
             let (output_text, execution_success) = match result {
                 Ok(output) => {
                     let stdout = String::from_utf8_lossy(&output.stdout);
-                    (stdout.to_string(), output.status.success())
+                    (stdout.to_string(), output.success())
                 }
                 Err(e) => {
                     (format!("Failed to execute: {}", e), false)
                 }
             };
+            audit_cli_run(&db, &project_id, &project_path, "RALPH story", &story_prompt, &output_text, execution_success);
+            let files_changed = snapshot
+                .as_ref()
+                .map(|s| worktree::changed_between(&project_path, s))
+                .unwrap_or_default();
 
             // Run validation if configured
             let validation_passed = if execution_success {
@@ -783,43 +1807,44 @@ async fn execute_ralph_loop_prd(
                 false
             };
 
+            let iteration_summary = if validation_passed {
+                "Validation passed".to_string()
+            } else if execution_success {
+                "Validation failed".to_string()
+            } else {
+                first_line(&output_text)
+            };
+            record_iteration(
+                &db,
+                &loop_id,
+                IterationRecord {
+                    iteration: story_iterations,
+                    story_index: Some(index as u32),
+                    status: if validation_passed { "passed" } else { "failed" },
+                    summary: iteration_summary,
+                    issues_count: 0,
+                    files_changed: &files_changed,
+                    started_at: &iteration_started,
+                },
+            );
+
             if validation_passed {
                 story_success = true;
 
-                // Git commit the changes
+                // Git commit the changes, if the project's git policy allows it
                 let commit_msg = format!("feat: {} [RALPH PRD]", story.title);
-                let _ = StdCommand::new("git")
-                    .args(["add", "-A"])
-                    .current_dir(&project_path)
-                    .output();
-                let commit_output = StdCommand::new("git")
-                    .args(["commit", "-m", &commit_msg])
-                    .current_dir(&project_path)
-                    .output();
-
-                let commit_hash = if let Ok(output) = commit_output {
-                    if output.status.success() {
-                        // Get the commit hash
-                        StdCommand::new("git")
-                            .args(["rev-parse", "--short", "HEAD"])
-                            .current_dir(&project_path)
-                            .output()
-                            .ok()
-                            .map(|o| String::from_utf8_lossy(&o.stdout).trim().to_string())
-                    } else {
-                        None
+                let permission = git_policy::load_policy(&db, &project_id).permission(GitOperation::Commit);
+                let commit = match git_policy::authorize(&app, permission, &project_id, GitOperation::Commit, &commit_msg).await {
+                    Ok(()) => commit_all(&project_path, &commit_msg).unwrap_or_else(|| "no commit".to_string()),
+                    Err(e) => {
+                        outcomes.push(format!("⚠ Story {} not committed: {}", index + 1, e));
+                        "no commit".to_string()
                     }
-                } else {
-                    None
                 };
 
-                outcomes.push(format!(
-                    "✓ Story {}: {} (commit: {})",
-                    index + 1,
-                    story.title,
-                    commit_hash.as_deref().unwrap_or("no commit")
-                ));
+                outcomes.push(format!("✓ Story {}: {} (commit: {})", index + 1, story.title, commit));
                 completed_count += 1;
+                story_event(index, "committed", Some(story_iterations), Some(commit), completed_count, &eta);
             } else {
                 // Record the failure as a mistake
                 let mistake_id = uuid::Uuid::new_v4().to_string();
@@ -842,47 +1867,122 @@ async fn execute_ralph_loop_prd(
                         "✗ Story {}: {} (failed after {} iterations)",
                         index + 1, story.title, story_iterations
                     ));
+                    failed_ids.insert(story.id.as_str());
+                    story_event(index, "failed", Some(story_iterations), None, completed_count, &eta);
+                } else if time_limit_reached(started, prd.max_duration_minutes) {
+                    // Uncommitted changes of the unfinished story stay in the working tree
+                    timed_out = true;
+                    outcomes.push(format!(
+                        "⏱ Story {}: {} (stopped after {} iterations: time limit reached)",
+                        index + 1, story.title, story_iterations
+                    ));
+                    story_event(index, "timed_out", Some(story_iterations), None, completed_count, &eta);
+                    break;
+                } else {
+                    story_event(index, "validation_failed", Some(story_iterations), None, completed_count, &eta);
                 }
             }
         }
+
+        // Rolling ETA over the stories still to run
+        story_durations.push(story_started.elapsed().as_secs());
+        let remaining = order[position + 1..].iter().filter(|&&i| !prd.stories[i].completed).count() as u32;
+        if let Some(secs) = monitor::estimate_remaining_secs(&story_durations, remaining) {
+            let at = (Utc::now() + chrono::Duration::seconds(secs as i64)).to_rfc3339();
+            let avg = monitor::estimate_remaining_secs(&story_durations, 1).unwrap_or(0) as u32;
+            let _ = db.execute(
+                "UPDATE ralph_loops SET eta_at = ?1, avg_story_secs = ?2 WHERE id = ?3",
+                rusqlite::params![at, avg, &loop_id],
+            );
+            eta = Some((at, secs));
+            emit_loop_progress(&app, &db, &loop_id);
+        }
     }
 
-    // Final outcome
-    let final_status = if completed_count > 0 {
+    // Final outcome: stories carried over as completed do not make a run of failures succeed
+    let already_completed = prd.stories.iter().filter(|s| s.completed).count();
+    let final_status = if completed_count > already_completed || already_completed == total_stories {
         "completed"
     } else {
         "failed"
     };
 
+    let time_limit_note = match (timed_out, prd.max_duration_minutes) {
+        (true, Some(minutes)) => format!("\nStopped at the {}-minute time limit; retry to run the ⏱ stories", minutes),
+        _ => String::new(),
+    };
     let final_outcome = format!(
-        "PRD: {}\nCompleted: {}/{} stories\n\n{}",
+        "PRD: {}\nCompleted: {}/{} stories{}\n\n{}",
         prd.name,
         completed_count,
         total_stories,
+        time_limit_note,
         outcomes.join("\n")
     );
 
     let now = Utc::now().to_rfc3339();
     let _ = db.execute(
         "UPDATE ralph_loops SET status = ?1, outcome = ?2, completed_at = ?3, current_story = ?4 WHERE id = ?5",
-        rusqlite::params![final_status, final_outcome, now, total_stories as u32, loop_id],
+        rusqlite::params![final_status, final_outcome, now, total_stories as u32, &loop_id],
     );
+    emit_loop_progress(&app, &db, &loop_id);
 
     // Log completion
-    let _ = db::log_activity_db(
+    events::publish(
         &db,
-        &project_id,
-        "generate",
-        &format!("RALPH PRD completed: {}/{} stories", completed_count, total_stories),
+        AppEvent::activity(
+            &project_id,
+            ActivityType::Ralph,
+            &format!(
+                "RALPH PRD {}: {}/{} stories",
+                if timed_out { "stopped at its time limit" } else { "completed" },
+                completed_count,
+                total_stories
+            ),
+        ),
     );
 }
 
-/// Find the Claude CLI path
-fn find_claude_cli() -> Option<String> {
+/// Emit the loop's current status and progress to the main window and the
+/// loop's monitor window (if open). Best-effort: a missing row emits nothing.
+fn emit_loop_progress<R: Runtime>(app: &AppHandle<R>, db: &Connection, loop_id: &str) {
+    let progress = db
+        .query_row(
+            "SELECT project_id, status, iterations, current_story, total_stories, outcome, eta_at FROM ralph_loops WHERE id = ?1",
+            rusqlite::params![loop_id],
+            |row| {
+                Ok(RalphLoopProgress {
+                    loop_id: loop_id.to_string(),
+                    project_id: row.get(0)?,
+                    status: row.get(1)?,
+                    iterations: row.get(2)?,
+                    current_story: row.get(3)?,
+                    total_stories: row.get(4)?,
+                    outcome: row.get(5)?,
+                    eta_at: row.get(6)?,
+                })
+            },
+        )
+        .ok();
+
+    if let Some(progress) = progress {
+        monitor::emit_scoped(app, MonitorKind::RalphLoop, loop_id, monitor::RALPH_PROGRESS_EVENT, progress);
+    }
+}
+
+/// Environment variable naming a stub Claude CLI to run instead of the one on PATH (test builds only).
+#[cfg(test)]
+pub const CLAUDE_CLI_ENV: &str = "JUMPSTART_CLAUDE_CLI";
+
+/// Find the Claude CLI path (PATH, then common install locations; JUMPSTART_CLAUDE_CLI first in test builds)
+pub fn find_claude_cli() -> Option<String> {
+    #[cfg(test)]
+    if let Some(path) = std::env::var(CLAUDE_CLI_ENV).ok().filter(|p| !p.trim().is_empty()) {
+        return Some(path);
+    }
+
     // Check if claude CLI is available via which
-    let claude_check = Command::new("which")
-        .arg("claude")
-        .output();
+    let claude_check = proc::run(Command::new("which").arg("claude"), ProcLimits::GIT);
 
     match claude_check {
         Ok(output) if output.status.success() => {
@@ -901,12 +2001,75 @@ fn find_claude_cli() -> Option<String> {
     }
 }
 
+/// Story indices in run order: each story after the stories it depends on, otherwise in file
+/// order. Rejects unknown or ambiguous dependency ids and dependency cycles.
+pub(crate) fn prd_story_order(stories: &[crate::models::ralph::PrdStory]) -> Result<Vec<usize>, String> {
+    let mut index_of: std::collections::HashMap<&str, usize> = std::collections::HashMap::new();
+    for (i, story) in stories.iter().enumerate() {
+        if index_of.insert(story.id.as_str(), i).is_some()
+            && stories.iter().any(|s| s.depends_on.contains(&story.id))
+        {
+            return Err(format!(
+                "Story id \"{}\" is used more than once, so stories depending on it are ambiguous.",
+                story.id
+            ));
+        }
+    }
+    let mut deps: Vec<Vec<usize>> = Vec::with_capacity(stories.len());
+    for story in stories {
+        let mut story_deps = Vec::new();
+        for dep in &story.depends_on {
+            let &i = index_of.get(dep.as_str()).ok_or_else(|| {
+                format!("Story \"{}\" depends on \"{}\", which is not a story in this PRD.", story.id, dep)
+            })?;
+            story_deps.push(i);
+        }
+        deps.push(story_deps);
+    }
+
+    let mut order = Vec::with_capacity(stories.len());
+    let mut placed = vec![false; stories.len()];
+    while order.len() < stories.len() {
+        let ready = (0..stories.len()).find(|&i| !placed[i] && deps[i].iter().all(|&d| placed[d]));
+        let Some(next) = ready else {
+            // Every unplaced story waits on another unplaced one; walk those edges to a cycle
+            let mut path = vec![(0..stories.len()).find(|&i| !placed[i]).unwrap_or(0)];
+            loop {
+                let current = path[path.len() - 1];
+                let Some(&dep) = deps[current].iter().find(|&&d| !placed[d]) else { break };
+                if let Some(start) = path.iter().position(|&i| i == dep) {
+                    let cycle: Vec<&str> = path[start..]
+                        .iter()
+                        .chain(std::iter::once(&dep))
+                        .map(|&i| stories[i].id.as_str())
+                        .collect();
+                    return Err(format!(
+                        "Story dependencies form a cycle: {}. Remove one of these dependsOn entries.",
+                        cycle.join(" → ")
+                    ));
+                }
+                path.push(dep);
+            }
+            return Err("Story dependencies form a cycle.".to_string());
+        };
+        placed[next] = true;
+        order.push(next);
+    }
+    Ok(order)
+}
+
 /// Build a prompt for a single PRD story
 fn build_story_prompt(story: &crate::models::ralph::PrdStory, prd: &crate::models::ralph::PrdFile) -> String {
     let mut prompt = format!("## Task: {}\n\n", story.title);
     prompt.push_str(&story.description);
     prompt.push_str("\n\n");
 
+    if let Some(ref context) = prd.cross_repo_context {
+        prompt.push_str("### Changes Already Made in Other Repositories\n");
+        prompt.push_str(context);
+        prompt.push_str("\n\n");
+    }
+
     if let Some(ref criteria) = story.acceptance_criteria {
         prompt.push_str("### Acceptance Criteria\n");
         prompt.push_str(criteria);
@@ -924,40 +2087,36 @@ fn build_story_prompt(story: &crate::models::ralph::PrdStory, prd: &crate::model
     prompt
 }
 
-/// Run validation commands for PRD (typecheck and tests)
-fn run_prd_validation(project_path: &str, prd: &crate::models::ralph::PrdFile) -> bool {
-    use std::process::Command as StdCommand;
-
-    // Run typecheck if configured
-    if let Some(ref cmd) = prd.typecheck_command {
-        let parts: Vec<&str> = cmd.split_whitespace().collect();
-        if !parts.is_empty() {
-            let result = StdCommand::new(parts[0])
-                .args(&parts[1..])
-                .current_dir(project_path)
-                .output();
-
-            if let Ok(output) = result {
-                if !output.status.success() {
-                    return false;
-                }
-            }
-        }
+/// Run validation commands for PRD (typecheck and tests). Commands were approved
+/// when the loop started; here they are re-validated and run without a shell.
+/// Record one Claude CLI run in the project's AI audit log (no-op unless enabled).
+fn audit_cli_run(db: &Connection, project_id: &str, project_path: &str, feature: &str, prompt: &str, output: &str, ok: bool) {
+    let record = ai_audit::AuditRecord {
+        project_id,
+        project_path,
+        feature,
+        provider: ai_audit::PROVIDER_CLI,
+        model: "claude-cli",
+        system: "",
+        prompt,
+        outcome: if ok { Ok(output) } else { Err(output) },
+    };
+    if let Err(e) = ai_audit::record(db, &record) {
+        tracing::warn!(error = %e, "Failed to record AI audit entry");
     }
+}
 
-    // Run tests if configured
-    if let Some(ref cmd) = prd.test_command {
-        let parts: Vec<&str> = cmd.split_whitespace().collect();
-        if !parts.is_empty() {
-            let result = StdCommand::new(parts[0])
-                .args(&parts[1..])
-                .current_dir(project_path)
-                .output();
-
-            if let Ok(output) = result {
-                if !output.status.success() {
-                    return false;
-                }
+fn run_prd_validation(project_path: &str, prd: &crate::models::ralph::PrdFile) -> bool {
+    for cmd in [&prd.typecheck_command, &prd.test_command].into_iter().flatten() {
+        if cmd.trim().is_empty() {
+            continue;
+        }
+        if command_guard::validate_command(project_path, cmd).is_err() {
+            return false;
+        }
+        if let Ok(output) = command_guard::run_validated(project_path, cmd, ProcLimits::TESTS) {
+            if !output.success() {
+                return false;
             }
         }
     }
@@ -966,11 +2125,14 @@ fn run_prd_validation(project_path: &str, prd: &crate::models::ralph::PrdFile) -
 }
 
 /// Extracted issue from Claude output
-#[derive(Clone)]
+#[derive(Clone, Default)]
 struct ExtractedIssue {
     issue_type: String,
     description: String,
     suggested_fix: Option<String>,
+    /// Project-relative file the issue points at, when the output names one
+    file: Option<String>,
+    line: Option<u32>,
 }
 
 /// Extract issues from Claude output using AI
@@ -994,7 +2156,9 @@ OUTPUT FORMAT (JSON only, no markdown fences):
     {
       "type": "error|warning|incomplete|test_failure|type_error|missing_dependency",
       "description": "Brief description of the issue",
-      "suggestedFix": "How to fix it (optional)"
+      "suggestedFix": "How to fix it (optional)",
+      "file": "path/to/file as printed in the output (optional)",
+      "line": 42
     }
   ]
 }
@@ -1002,12 +2166,14 @@ OUTPUT FORMAT (JSON only, no markdown fences):
 If there are no issues and the output looks successful, return: {"issues": []}
 Be conservative - only extract clear issues, not general observations."#;
 
+    // The end of the output holds the final summary and any errors, so keep that part
     let user_prompt = format!(
         "Analyze this Claude Code output and extract any issues:\n\n```\n{}\n```",
-        if output.len() > 8000 { &output[..8000] } else { output }
+        ai::truncate_tail_to_tokens(output, ai::token_limits().issue_output_tokens)
     );
 
-    match ai::call_claude(client, api_key, system, &user_prompt).await {
+    let call = ai::call_claude(client, api_key, system, &user_prompt);
+    match ai_queue::run(AiJobKind::RalphLoop, "RALPH issue extraction", call).await {
         Ok(response) => {
             // Parse the JSON response
             if let Ok(val) = serde_json::from_str::<serde_json::Value>(&response) {
@@ -1024,10 +2190,20 @@ Be conservative - only extract clear issues, not general observations."#;
                             .and_then(|v| v.as_str())
                             .map(|s| s.to_string());
 
+                        let file = issue.get("file")
+                            .and_then(|v| v.as_str())
+                            .filter(|f| !f.trim().is_empty())
+                            .map(|f| f.trim().trim_start_matches("./").to_string());
+                        let line = issue.get("line")
+                            .and_then(|v| v.as_u64())
+                            .map(|l| l as u32);
+
                         Some(ExtractedIssue {
                             issue_type,
                             description,
                             suggested_fix,
+                            file,
+                            line,
                         })
                     }).collect();
                 }
@@ -1041,8 +2217,112 @@ Be conservative - only extract clear issues, not general observations."#;
     }
 }
 
-/// Heuristic issue extraction when AI is not available
+/// Whether the project repo currently has `branch` checked out.
+fn on_branch(project_path: &str, branch: &str) -> bool {
+    proc::run(
+        Command::new("git").args(["rev-parse", "--abbrev-ref", "HEAD"]).current_dir(project_path),
+        ProcLimits::GIT,
+    )
+    .is_ok_and(|o| String::from_utf8_lossy(&o.stdout).trim() == branch)
+}
+
+/// Create `branch` (or switch to it if it already exists) in the project repo.
+fn checkout_loop_branch(project_path: &str, branch: &str) -> Result<(), String> {
+    let git = |args: &[&str]| {
+        proc::run(Command::new("git").args(args).current_dir(project_path), ProcLimits::GIT)
+            .map_err(|e| format!("Failed to run git: {}", e))
+    };
+
+    if on_branch(project_path, branch) {
+        return Ok(());
+    }
+    if git(&["checkout", "-b", branch])?.status.success() {
+        return Ok(());
+    }
+    let output = git(&["checkout", branch])?;
+    if output.status.success() {
+        Ok(())
+    } else {
+        Err(format!(
+            "Failed to switch to branch {}: {}",
+            branch,
+            String::from_utf8_lossy(&output.stderr).trim()
+        ))
+    }
+}
+
+/// Stage everything and commit with `message`. Returns the short hash, or
+/// None when there was nothing to commit or git failed.
+fn commit_all(project_path: &str, message: &str) -> Option<String> {
+    let _ = proc::run(
+        Command::new("git").args(["add", "-A"]).current_dir(project_path),
+        ProcLimits::GIT,
+    );
+    let committed = proc::run(
+        Command::new("git").args(["commit", "-m", message]).current_dir(project_path),
+        ProcLimits::GIT,
+    )
+    .is_ok_and(|o| o.status.success());
+    if !committed {
+        return None;
+    }
+    proc::run(
+        Command::new("git").args(["rev-parse", "--short", "HEAD"]).current_dir(project_path),
+        ProcLimits::GIT,
+    )
+    .ok()
+    .map(|o| String::from_utf8_lossy(&o.stdout).trim().to_string())
+}
+
+/// Run acceptance gate commands in the project directory. Each failing gate is
+/// returned with an issue carrying the tail of its output.
+fn run_acceptance_gates<'a>(project_path: &str, gates: &'a [String]) -> Vec<(&'a str, ExtractedIssue)> {
+    gates
+        .iter()
+        .filter(|gate| !gate.trim().is_empty())
+        .filter_map(|gate| {
+            let detail = match command_guard::run_validated(project_path, gate, ProcLimits::TESTS) {
+                Ok(o) if o.success() => return None,
+                Ok(o) => format!("{}{}", String::from_utf8_lossy(&o.stdout), String::from_utf8_lossy(&o.stderr)),
+                Err(e) => e,
+            };
+            Some((gate.as_str(), gate_issue(gate, &detail)))
+        })
+        .collect()
+}
+
+fn gate_issue(gate: &str, output: &str) -> ExtractedIssue {
+    let chars: Vec<char> = output.trim().chars().collect();
+    let tail: String = chars[chars.len().saturating_sub(GATE_OUTPUT_CHARS)..].iter().collect();
+    ExtractedIssue {
+        issue_type: "acceptance_gate".to_string(),
+        description: format!("Acceptance gate `{}` failed:\n{}", gate, tail),
+        suggested_fix: Some(format!("Make `{}` pass", gate)),
+        ..Default::default()
+    }
+}
+
+/// Issue extraction when AI is not available: the local rules engine
+/// (core::issue_rules) first, then generic error/warning/test-failure markers.
 fn extract_issues_heuristic(output: &str) -> Vec<ExtractedIssue> {
+    let rule_issues = issue_rules::extract(output);
+    if !rule_issues.is_empty() {
+        return rule_issues
+            .into_iter()
+            .map(|issue| ExtractedIssue {
+                issue_type: issue.kind.to_string(),
+                suggested_fix: match (&issue.file, issue.kind) {
+                    (Some(_), "test_failure") => Some(format!("Fix the failing test at {}", issue.location())),
+                    (Some(_), _) => Some(format!("Fix the {} reported by {} at {}", issue.kind.replace('_', " "), issue.tool, issue.location())),
+                    (None, _) => None,
+                },
+                description: issue.description(),
+                file: issue.file,
+                line: issue.line,
+            })
+            .collect();
+    }
+
     let mut issues = Vec::new();
     let lower = output.to_lowercase();
 
@@ -1058,6 +2338,7 @@ fn extract_issues_heuristic(output: &str) -> Vec<ExtractedIssue> {
             issue_type: "test_failure".to_string(),
             description: "One or more tests failed".to_string(),
             suggested_fix: Some("Review test output and fix failing tests".to_string()),
+            ..Default::default()
         });
         return issues; // Test failures are a specific category, don't mix with generic errors
     }
@@ -1071,7 +2352,7 @@ fn extract_issues_heuristic(output: &str) -> Vec<ExtractedIssue> {
                 issues.push(ExtractedIssue {
                     issue_type: "error".to_string(),
                     description: line.trim().chars().take(200).collect(),
-                    suggested_fix: None,
+                    ..Default::default()
                 });
                 break; // Just capture first error to avoid noise
             }
@@ -1084,7 +2365,7 @@ fn extract_issues_heuristic(output: &str) -> Vec<ExtractedIssue> {
                 issues.push(ExtractedIssue {
                     issue_type: "warning".to_string(),
                     description: line.trim().chars().take(200).collect(),
-                    suggested_fix: None,
+                    ..Default::default()
                 });
                 break;
             }
@@ -1094,6 +2375,222 @@ fn extract_issues_heuristic(output: &str) -> Vec<ExtractedIssue> {
     issues
 }
 
+/// Collect injectable learnings for a project: CLAUDE NOTES patterns from CLAUDE.md, stored
+/// CLAUDE.md conventions (with their last violations), plus recent mistakes that have a
+/// learned pattern or resolution recorded.
+fn gather_learning_candidates(db: &Connection, project_id: &str, project_path: &str) -> Vec<String> {
+    let mut candidates = Vec::new();
+
+    let claude_md_path = Path::new(project_path).join("CLAUDE.md");
+    if let Ok(content) = fs::read_to_string(&claude_md_path) {
+        candidates.extend(extract_claude_notes_patterns(&content));
+    }
+    candidates.extend(conventions::ralph_candidates(db, project_id));
+
+    if let Ok(mut stmt) = db.prepare(
+        "SELECT description, resolution, learned_pattern
+         FROM ralph_mistakes
+         WHERE project_id = ?1 AND mistake_type != 'user_cancelled'
+           AND (learned_pattern IS NOT NULL OR resolution IS NOT NULL)
+         ORDER BY created_at DESC
+         LIMIT 50",
+    ) {
+        let rows = stmt.query_map(rusqlite::params![project_id], |row| {
+            Ok((
+                row.get::<_, String>(0)?,
+                row.get::<_, Option<String>>(1)?,
+                row.get::<_, Option<String>>(2)?,
+            ))
+        });
+        if let Ok(rows) = rows {
+            for (description, resolution, learned_pattern) in rows.filter_map(|r| r.ok()) {
+                match (learned_pattern, resolution) {
+                    (Some(pattern), _) if !pattern.trim().is_empty() => candidates.push(pattern),
+                    (_, Some(fix)) if !fix.trim().is_empty() => {
+                        candidates.push(format!("Previously hit \"{}\" - fix: {}", description, fix))
+                    }
+                    _ => {}
+                }
+            }
+        }
+    }
+
+    candidates
+}
+
+/// Extract lowercase keywords (4+ chars, non-stopword) used for similarity matching.
+fn learning_keywords(text: &str) -> std::collections::HashSet<String> {
+    const STOPWORDS: [&str; 16] = [
+        "this", "that", "with", "from", "have", "should", "would", "could", "when", "then",
+        "into", "only", "make", "sure", "also", "each",
+    ];
+    text.split(|c: char| !c.is_alphanumeric() && c != '_')
+        .map(|w| w.to_lowercase())
+        .filter(|w| w.len() >= 4 && !STOPWORDS.contains(&w.as_str()))
+        .collect()
+}
+
+/// Pick the learnings most similar to the task (by shared keywords), best first.
+/// Learnings with no keyword overlap are never injected.
+fn select_relevant_learnings(task: &str, candidates: &[String], limit: usize) -> Vec<String> {
+    let task_keywords = learning_keywords(task);
+
+    let mut scored: Vec<(usize, &String)> = candidates
+        .iter()
+        .map(|c| (learning_keywords(c).intersection(&task_keywords).count(), c))
+        .filter(|(score, _)| *score > 0)
+        .collect();
+    // Stable sort keeps CLAUDE.md patterns and newer mistakes first on ties
+    scored.sort_by_key(|(score, _)| std::cmp::Reverse(*score));
+
+    let mut selected: Vec<String> = Vec::new();
+    for (_, candidate) in scored {
+        if !selected.iter().any(|s| s.eq_ignore_ascii_case(candidate)) {
+            selected.push(candidate.clone());
+        }
+        if selected.len() >= limit {
+            break;
+        }
+    }
+    selected
+}
+
+/// Outcome of one Claude run, written to ralph_iterations.
+struct IterationRecord<'a> {
+    iteration: u32,
+    story_index: Option<u32>,
+    status: &'a str,
+    summary: String,
+    issues_count: u32,
+    files_changed: &'a [RalphIterationFile],
+    started_at: &'a str,
+}
+
+/// Store one iteration for the loop timeline (best-effort, like mistake recording)
+/// and publish loop_iteration_finished.
+fn record_iteration(db: &Connection, loop_id: &str, record: IterationRecord) {
+    let files_json = serde_json::to_string(record.files_changed).unwrap_or_else(|_| "[]".to_string());
+    let _ = db.execute(
+        "INSERT INTO ralph_iterations (id, loop_id, iteration, story_index, status, summary, issues_count, files_changed, started_at, completed_at)
+         VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7, ?8, ?9, ?10)",
+        rusqlite::params![
+            uuid::Uuid::new_v4().to_string(),
+            loop_id,
+            record.iteration,
+            record.story_index,
+            record.status,
+            record.summary,
+            record.issues_count,
+            files_json,
+            record.started_at,
+            Utc::now().to_rfc3339()
+        ],
+    );
+
+    if let Ok(project_id) = db.query_row(
+        "SELECT project_id FROM ralph_loops WHERE id = ?1",
+        [loop_id],
+        |row| row.get::<_, String>(0),
+    ) {
+        events::publish(
+            db,
+            AppEvent::LoopIterationFinished {
+                project_id,
+                loop_id: loop_id.to_string(),
+                iteration: record.iteration,
+                story_index: record.story_index,
+                status: record.status.to_string(),
+                issues_count: record.issues_count,
+                files_changed: record.files_changed.len() as u32,
+                failure_type: (record.status == "failed").then(|| categorize_mistake(&record.summary).to_string()),
+            },
+        );
+    }
+}
+
+/// Put a running loop in "needs_review" with the destructive diff attached.
+fn hold_for_review(db: &Connection, loop_id: &str, summary: &str, diff: &str) {
+    let outcome = format!(
+        "{}\n\nReview the diff, then resume the loop to continue or kill it and revert the changes.",
+        summary
+    );
+    let _ = db.execute(
+        "UPDATE ralph_loops SET status = 'needs_review', outcome = ?1, review_diff = ?2, paused_at = ?3
+         WHERE id = ?4 AND status = 'running'",
+        rusqlite::params![outcome, diff, Utc::now().to_rfc3339(), loop_id],
+    );
+}
+
+/// First non-empty line of CLI output, capped for the timeline.
+fn first_line(output: &str) -> String {
+    output
+        .lines()
+        .map(str::trim)
+        .find(|l| !l.is_empty())
+        .unwrap_or("")
+        .chars()
+        .take(200)
+        .collect()
+}
+
+/// One-line summary of the issues extracted from an iteration.
+fn issues_summary(issues: &[ExtractedIssue]) -> String {
+    match issues {
+        [] => "No issues found".to_string(),
+        [issue] => format!("1 issue: {}", first_line(&issue.description)),
+        [issue, rest @ ..] => format!("{} issues: {} (+{} more)", rest.len() + 1, first_line(&issue.description), rest.len()),
+    }
+}
+
+/// Iterations of a loop in execution order.
+pub(crate) fn load_ralph_iterations(db: &Connection, loop_id: &str) -> Result<Vec<RalphIteration>, String> {
+    let mut stmt = db
+        .prepare(
+            "SELECT id, loop_id, iteration, story_index, status, summary, issues_count, files_changed, started_at, completed_at
+             FROM ralph_iterations
+             WHERE loop_id = ?1
+             ORDER BY started_at ASC, COALESCE(story_index, 0) ASC, iteration ASC",
+        )
+        .map_err(|e| format!("Failed to query iterations: {}", e))?;
+
+    let iterations = stmt
+        .query_map(rusqlite::params![loop_id], |row| {
+            Ok(RalphIteration {
+                id: row.get(0)?,
+                loop_id: row.get(1)?,
+                iteration: row.get(2)?,
+                story_index: row.get(3)?,
+                status: row.get(4)?,
+                summary: row.get(5)?,
+                issues_count: row.get(6)?,
+                files_changed: serde_json::from_str(&row.get::<_, String>(7)?).unwrap_or_default(),
+                started_at: row.get(8)?,
+                completed_at: row.get(9)?,
+            })
+        })
+        .map_err(|e| format!("Failed to read iterations: {}", e))?
+        .filter_map(|r| r.ok())
+        .collect();
+
+    Ok(iterations)
+}
+
+/// Prepend injected learnings to a task prompt. Returns the prompt unchanged if none.
+fn inject_learnings(prompt: &str, learnings: &[String]) -> String {
+    if learnings.is_empty() {
+        return prompt.to_string();
+    }
+
+    let mut result = String::from("## Project Learnings\n");
+    result.push_str("Apply these patterns learned from earlier work on this project:\n\n");
+    for learning in learnings {
+        result.push_str(&format!("- {}\n", learning));
+    }
+    result.push_str("\n## Task\n");
+    result.push_str(prompt);
+    result
+}
+
 /// Build an enhanced prompt for the next iteration, including context from prior issues
 fn build_iteration_prompt(original_prompt: &str, prior_issues: &[ExtractedIssue], iteration: u32) -> String {
     let mut prompt = format!(
@@ -1121,6 +2618,61 @@ fn build_iteration_prompt(original_prompt: &str, prior_issues: &[ExtractedIssue]
     prompt
 }
 
+/// Prompt for an iteration that resumes the previous one's session: the session already
+/// holds the task and earlier issues, so only the latest issues are sent.
+fn build_resume_prompt(issues: &[ExtractedIssue], iteration: u32) -> String {
+    let mut prompt = format!("## RALPH Loop - Iteration {} (Continuing This Session)\n\n", iteration + 1);
+    prompt.push_str("Checks after your last changes found these issues:\n\n");
+    for (i, issue) in issues.iter().enumerate() {
+        prompt.push_str(&format!("{}. **[{}]** {}\n", i + 1, issue.issue_type, issue.description));
+        if let Some(ref fix) = issue.suggested_fix {
+            prompt.push_str(&format!("   - Suggested fix: {}\n", fix));
+        }
+    }
+    prompt.push_str("\nFix them, finish anything left of the original task, and verify your changes before finishing.\n");
+    prompt
+}
+
+/// A Claude CLI run read from `--output-format json`.
+#[derive(Debug, Clone, Default, PartialEq)]
+struct CliSession {
+    /// The run's final text (what plain -p prints)
+    text: String,
+    session_id: Option<String>,
+    /// Input tokens (cached and uncached) plus output tokens reported for the run
+    context_tokens: u32,
+}
+
+/// Parse `claude -p --output-format json` output. Output that is not a JSON result
+/// (an older CLI, a crash) is kept as text without a session, so the next iteration starts fresh.
+fn parse_cli_json(stdout: &str) -> CliSession {
+    let Some(value) = serde_json::from_str::<serde_json::Value>(stdout.trim())
+        .ok()
+        .filter(|v| v.get("result").is_some() || v.get("session_id").is_some())
+    else {
+        return CliSession { text: stdout.to_string(), ..CliSession::default() };
+    };
+    let usage = &value["usage"];
+    let context_tokens = ["input_tokens", "cache_creation_input_tokens", "cache_read_input_tokens", "output_tokens"]
+        .iter()
+        .filter_map(|key| usage.get(*key).and_then(serde_json::Value::as_u64))
+        .sum::<u64>();
+    CliSession {
+        text: value.get("result").and_then(serde_json::Value::as_str).unwrap_or_default().to_string(),
+        session_id: value.get("session_id").and_then(serde_json::Value::as_str).map(str::to_string),
+        context_tokens: context_tokens.min(u64::from(u32::MAX)) as u32,
+    }
+}
+
+/// Session for the next iteration to resume: the last run's, when resuming is on and the
+/// session is still under the loop's token limit.
+fn next_session(options: &LoopOptions, last: &CliSession) -> Option<String> {
+    if !options.resume_session || last.context_tokens >= options.session_token_limit {
+        return None;
+    }
+    last.session_id.clone()
+}
+
 /// Record a mistake from a failed iteration
 fn record_iteration_mistake(
     db: &Connection,
@@ -1132,7 +2684,7 @@ fn record_iteration_mistake(
     let mistake_id = uuid::Uuid::new_v4().to_string();
     let mistake_type = categorize_mistake(error_output);
     let description = if error_output.len() > 500 {
-        format!("{}...", &error_output[..500])
+        format!("{}...", safe_read::truncate_str(error_output, 500))
     } else {
         error_output.to_string()
     };
@@ -1154,7 +2706,7 @@ fn record_iteration_mistake(
 }
 
 /// Categorize a mistake based on error message content.
-fn categorize_mistake(error: &str) -> &'static str {
+pub fn categorize_mistake(error: &str) -> &'static str {
     let lower = error.to_lowercase();
 
     if lower.contains("not found") || lower.contains("no such file") || lower.contains("doesn't exist") {
@@ -1183,6 +2735,7 @@ fn categorize_mistake(error: &str) -> &'static str {
 #[tauri::command]
 pub async fn pause_ralph_loop(
     loop_id: String,
+    app: AppHandle,
     state: State<'_, AppState>,
 ) -> Result<(), String> {
     let db = state
@@ -1203,32 +2756,39 @@ pub async fn pause_ralph_loop(
         return Err("Loop not found or not currently running.".to_string());
     }
 
+    emit_loop_progress(&app, &db, &loop_id);
     Ok(())
 }
 
 /// Resume a paused RALPH loop by ID.
-/// Transitions status from "paused" back to "running" and re-executes the loop.
+/// Transitions status from "paused" (or "needs_review", once the diff has been reviewed)
+/// back to "running" and re-executes the loop.
 #[tauri::command]
 pub async fn resume_ralph_loop(
     loop_id: String,
+    app: AppHandle,
     state: State<'_, AppState>,
 ) -> Result<(), String> {
     // Get loop details and project info
-    let (project_id, project_path, prompt) = {
+    let (project_id, project_path, prompt, options) = {
         let db = state
             .db
             .lock()
             .map_err(|e| format!("Failed to lock database: {}", e))?;
 
         let mut stmt = db
-            .prepare("SELECT rl.project_id, p.path, COALESCE(rl.enhanced_prompt, rl.prompt) FROM ralph_loops rl JOIN projects p ON rl.project_id = p.id WHERE rl.id = ?1 AND rl.status = 'paused'")
+            .prepare("SELECT rl.project_id, p.path, COALESCE(rl.enhanced_prompt, rl.prompt), rl.loop_options FROM ralph_loops rl JOIN projects p ON rl.project_id = p.id WHERE rl.id = ?1 AND rl.status IN ('paused', 'needs_review')")
             .map_err(|e| format!("Failed to prepare query: {}", e))?;
 
         stmt.query_row(rusqlite::params![&loop_id], |row| {
+            let options: Option<String> = row.get(3)?;
             Ok((
                 row.get::<_, String>(0)?,
                 row.get::<_, String>(1)?,
                 row.get::<_, String>(2)?,
+                options
+                    .and_then(|o| serde_json::from_str::<LoopOptions>(&o).ok())
+                    .unwrap_or_default(),
             ))
         })
         .map_err(|_| "Loop not found or not currently paused.".to_string())?
@@ -1241,28 +2801,33 @@ pub async fn resume_ralph_loop(
             .lock()
             .map_err(|e| format!("Failed to lock database: {}", e))?;
 
+        // A gate revoked while the loop was paused must be re-approved first
+        command_guard::require_approved(&db, &project_id, &project_path, &options.acceptance_gates, "acceptance_gate")?;
+
         db.execute(
-            "UPDATE ralph_loops SET status = 'running', paused_at = NULL WHERE id = ?1",
+            "UPDATE ralph_loops SET status = 'running', paused_at = NULL, review_diff = NULL WHERE id = ?1",
             rusqlite::params![&loop_id],
         )
         .map_err(|e| format!("Failed to resume RALPH loop: {}", e))?;
+        emit_loop_progress(&app, &db, &loop_id);
     }
 
     // Re-execute in background
     let lid = loop_id.clone();
     let pid = project_id.clone();
     tokio::spawn(async move {
-        execute_ralph_loop(lid, pid, project_path, prompt).await;
+        execute_ralph_loop(app, lid, pid, project_path, prompt, options).await;
     });
 
     Ok(())
 }
 
-/// Kill a running or paused RALPH loop by ID.
+/// Kill a running, paused, or needs_review RALPH loop by ID.
 /// Marks the loop as failed, records a mistake, and attempts to kill any associated Claude process.
 #[tauri::command]
 pub async fn kill_ralph_loop(
     loop_id: String,
+    app: AppHandle,
     state: State<'_, AppState>,
 ) -> Result<(), String> {
     let db = state
@@ -1273,7 +2838,7 @@ pub async fn kill_ralph_loop(
     // Get loop info before updating (for mistake recording)
     let loop_info: Option<(String, String)> = db
         .query_row(
-            "SELECT project_id, prompt FROM ralph_loops WHERE id = ?1 AND status IN ('running', 'paused')",
+            "SELECT project_id, prompt FROM ralph_loops WHERE id = ?1 AND status IN ('running', 'paused', 'needs_review')",
             rusqlite::params![&loop_id],
             |row| Ok((row.get(0)?, row.get(1)?)),
         )
@@ -1283,7 +2848,7 @@ pub async fn kill_ralph_loop(
 
     let rows_updated = db
         .execute(
-            "UPDATE ralph_loops SET status = 'failed', outcome = 'Killed by user', completed_at = ?1 WHERE id = ?2 AND status IN ('running', 'paused')",
+            "UPDATE ralph_loops SET status = 'failed', outcome = 'Killed by user', completed_at = ?1 WHERE id = ?2 AND status IN ('running', 'paused', 'needs_review')",
             rusqlite::params![now, loop_id],
         )
         .map_err(|e| format!("Failed to kill RALPH loop: {}", e))?;
@@ -1291,6 +2856,7 @@ pub async fn kill_ralph_loop(
     if rows_updated == 0 {
         return Err("Loop not found or already completed/failed.".to_string());
     }
+    emit_loop_progress(&app, &db, &loop_id);
 
     // Record as a user-cancelled mistake for tracking
     if let Some((project_id, prompt)) = loop_info {
@@ -1327,36 +2893,287 @@ pub async fn list_ralph_loops(
         .map_err(|e| format!("Failed to lock database: {}", e))?;
 
     let mut stmt = db
-        .prepare(
-            "SELECT id, project_id, prompt, enhanced_prompt, status, quality_score, iterations, outcome, started_at, paused_at, completed_at, created_at, COALESCE(mode, 'iterative'), current_story, total_stories FROM ralph_loops WHERE project_id = ?1 ORDER BY created_at DESC",
-        )
+        .prepare(&format!(
+            "SELECT {} FROM ralph_loops WHERE project_id = ?1 ORDER BY created_at DESC",
+            LOOP_COLUMNS
+        ))
         .map_err(|e| format!("Failed to query loops: {}", e))?;
 
     let loops = stmt
-        .query_map(rusqlite::params![project_id], |row| {
-            Ok(RalphLoop {
-                id: row.get(0)?,
-                project_id: row.get(1)?,
-                prompt: row.get(2)?,
-                enhanced_prompt: row.get(3)?,
-                status: row.get(4)?,
-                quality_score: row.get(5)?,
-                iterations: row.get(6)?,
-                outcome: row.get(7)?,
-                started_at: row.get(8)?,
-                paused_at: row.get(9)?,
-                completed_at: row.get(10)?,
-                created_at: row.get(11)?,
-                mode: row.get(12)?,
-                current_story: row.get(13)?,
-                total_stories: row.get(14)?,
-            })
-        })
+        .query_map(rusqlite::params![project_id], map_loop_row)
         .map_err(|e| format!("Failed to read loops: {}", e))?
         .filter_map(|r| r.ok())
         .collect();
 
-    Ok(loops)
+    Ok(loops)
+}
+
+const LOOP_COLUMNS: &str = "id, project_id, prompt, enhanced_prompt, status, quality_score, iterations, outcome, started_at, paused_at, completed_at, created_at, COALESCE(mode, 'iterative'), current_story, total_stories, injected_patterns, review_diff, parent_loop_id, eta_at, avg_story_secs";
+
+fn map_loop_row(row: &rusqlite::Row) -> rusqlite::Result<RalphLoop> {
+    Ok(RalphLoop {
+        id: row.get(0)?,
+        project_id: row.get(1)?,
+        prompt: row.get(2)?,
+        enhanced_prompt: row.get(3)?,
+        status: row.get(4)?,
+        quality_score: row.get(5)?,
+        iterations: row.get(6)?,
+        outcome: row.get(7)?,
+        started_at: row.get(8)?,
+        paused_at: row.get(9)?,
+        completed_at: row.get(10)?,
+        created_at: row.get(11)?,
+        mode: row.get(12)?,
+        current_story: row.get(13)?,
+        total_stories: row.get(14)?,
+        injected_patterns: row
+            .get::<_, Option<String>>(15)?
+            .and_then(|s| serde_json::from_str(&s).ok()),
+        review_diff: row.get(16)?,
+        parent_loop_id: row.get(17)?,
+        eta_at: row.get(18)?,
+        avg_story_secs: row.get(19)?,
+    })
+}
+
+/// Start a follow-up loop seeded with a finished loop's remaining issues, TODOs,
+/// and summarized outcome. The new loop keeps the parent's options and records it
+/// in parent_loop_id.
+#[tauri::command]
+pub async fn create_followup_loop(
+    loop_id: String,
+    app: AppHandle,
+    state: State<'_, AppState>,
+) -> Result<RalphLoop, String> {
+    let (project_id, prompt, options) = {
+        let db = state.db.lock().map_err(|e| format!("DB lock error: {}", e))?;
+        followup_seed(&db, &loop_id)?
+    };
+
+    let mut followup = spawn_iterative_loop(
+        &state,
+        app,
+        project_id,
+        prompt,
+        None,
+        0,
+        options,
+        "Started RALPH follow-up loop",
+    )?;
+
+    let db = state.db.lock().map_err(|e| format!("DB lock error: {}", e))?;
+    db.execute(
+        "UPDATE ralph_loops SET parent_loop_id = ?1 WHERE id = ?2",
+        rusqlite::params![&loop_id, &followup.id],
+    )
+    .map_err(|e| format!("Failed to link follow-up loop: {}", e))?;
+    followup.parent_loop_id = Some(loop_id);
+
+    Ok(followup)
+}
+
+/// Every loop in a loop's follow-up chain, from the first loop to the latest follow-up.
+#[tauri::command]
+pub async fn get_ralph_loop_chain(
+    loop_id: String,
+    state: State<'_, AppState>,
+) -> Result<Vec<RalphLoop>, String> {
+    let db = state.db.lock().map_err(|e| format!("DB lock error: {}", e))?;
+    load_loop_chain(&db, &loop_id)
+}
+
+/// Most remaining issues and TODOs carried into a follow-up prompt
+const MAX_FOLLOWUP_ITEMS: usize = 15;
+
+/// Project ID, prompt, and options for a follow-up to a finished loop.
+fn followup_seed(db: &Connection, loop_id: &str) -> Result<(String, String, LoopOptions), String> {
+    let (project_id, prompt, status, mode, iterations, outcome, options_json): (
+        String,
+        String,
+        String,
+        String,
+        u32,
+        Option<String>,
+        Option<String>,
+    ) = db
+        .query_row(
+            "SELECT project_id, prompt, status, COALESCE(mode, 'iterative'), iterations, outcome, loop_options
+             FROM ralph_loops WHERE id = ?1",
+            [loop_id],
+            |row| Ok((row.get(0)?, row.get(1)?, row.get(2)?, row.get(3)?, row.get(4)?, row.get(5)?, row.get(6)?)),
+        )
+        .map_err(|e| format!("Loop not found: {}", e))?;
+    if status != "completed" && status != "failed" {
+        return Err(format!("Loop is {}; only finished loops can be followed up.", status));
+    }
+    let outcome = outcome.unwrap_or_default();
+
+    let mut remaining: Vec<String> = if mode == "prd" {
+        // PRD outcomes list each story; "✗" lines are the ones that never passed validation
+        outcome
+            .lines()
+            .map(str::trim)
+            .filter(|l| l.starts_with('✗'))
+            .map(|l| l.trim_start_matches('✗').trim().to_string())
+            .collect()
+    } else {
+        last_iteration_issues(db, loop_id)?
+    };
+    remaining.truncate(MAX_FOLLOWUP_ITEMS);
+    let todos = outcome_todos(&outcome);
+
+    if remaining.is_empty() && todos.is_empty() {
+        return Err("The loop finished with no remaining issues or TODOs to follow up on.".to_string());
+    }
+
+    let options = options_json
+        .and_then(|json| serde_json::from_str(&json).ok())
+        .unwrap_or_default();
+    Ok((project_id, followup_prompt(loop_id, &prompt, iterations, &outcome, &remaining, &todos), options))
+}
+
+/// Issues recorded for the loop's last iteration, when that iteration still had issues.
+fn last_iteration_issues(db: &Connection, loop_id: &str) -> Result<Vec<String>, String> {
+    let last: Option<(u32, String)> = db
+        .query_row(
+            "SELECT iteration, status FROM ralph_iterations WHERE loop_id = ?1 ORDER BY iteration DESC LIMIT 1",
+            [loop_id],
+            |row| Ok((row.get(0)?, row.get(1)?)),
+        )
+        .ok();
+    let Some((iteration, status)) = last else {
+        return Ok(Vec::new());
+    };
+    if status == "passed" {
+        return Ok(Vec::new());
+    }
+
+    let mut stmt = db
+        .prepare(
+            "SELECT mistake_type, description, file_path, line FROM ralph_mistakes
+             WHERE loop_id = ?1 AND context LIKE ?2 ORDER BY created_at",
+        )
+        .map_err(|e| format!("Failed to query issues: {}", e))?;
+    let issues = stmt
+        .query_map(rusqlite::params![loop_id, format!("Iteration {}:%", iteration)], |row| {
+            let (kind, description): (String, String) = (row.get(0)?, row.get(1)?);
+            let location = match (row.get::<_, Option<String>>(2)?, row.get::<_, Option<u32>>(3)?) {
+                (Some(file), Some(line)) => format!(" ({}:{})", file, line),
+                (Some(file), None) => format!(" ({})", file),
+                _ => String::new(),
+            };
+            Ok(format!("[{}] {}{}", kind, first_line(&description), location))
+        })
+        .map_err(|e| format!("Failed to read issues: {}", e))?
+        .filter_map(|r| r.ok())
+        .collect();
+    Ok(issues)
+}
+
+/// TODO/FIXME lines from a loop's output, deduplicated.
+fn outcome_todos(outcome: &str) -> Vec<String> {
+    let mut todos: Vec<String> = Vec::new();
+    for line in outcome.lines() {
+        let Some(pos) = line.find("TODO").or_else(|| line.find("FIXME")) else {
+            continue;
+        };
+        let todo: String = line[pos..].trim().chars().take(200).collect();
+        if !todos.contains(&todo) {
+            todos.push(todo);
+        }
+        if todos.len() >= MAX_FOLLOWUP_ITEMS {
+            break;
+        }
+    }
+    todos
+}
+
+fn followup_prompt(
+    parent_id: &str,
+    parent_prompt: &str,
+    iterations: u32,
+    outcome: &str,
+    remaining: &[String],
+    todos: &[String],
+) -> String {
+    let summary: Vec<&str> = outcome
+        .lines()
+        .map(str::trim)
+        .filter(|l| !l.is_empty())
+        .take(12)
+        .collect();
+    let mut prompt = format!(
+        "Follow-up to RALPH loop {} ({} iterations).\n\nOriginal task:\n{}\n\nOutcome summary:\n{}\n",
+        parent_id.chars().take(8).collect::<String>(),
+        iterations,
+        parent_prompt.trim(),
+        summary.join("\n").chars().take(1500).collect::<String>()
+    );
+    if !remaining.is_empty() {
+        prompt.push_str("\nRemaining issues:\n");
+        for issue in remaining {
+            prompt.push_str(&format!("- {}\n", issue));
+        }
+    }
+    if !todos.is_empty() {
+        prompt.push_str("\nTODOs left in the output:\n");
+        for todo in todos {
+            prompt.push_str(&format!("- {}\n", todo));
+        }
+    }
+    prompt.push_str("\nFinish the remaining items above. Do not redo work the previous loop already completed.");
+    prompt
+}
+
+/// The first loop of a chain, followed by every follow-up descended from it (oldest first).
+fn load_loop_chain(db: &Connection, loop_id: &str) -> Result<Vec<RalphLoop>, String> {
+    let root: String = db
+        .query_row(
+            "WITH RECURSIVE ancestors(id, parent, depth) AS (
+                 SELECT id, parent_loop_id, 0 FROM ralph_loops WHERE id = ?1
+                 UNION ALL
+                 SELECT l.id, l.parent_loop_id, a.depth + 1 FROM ralph_loops l JOIN ancestors a ON l.id = a.parent
+                 WHERE a.depth < 100
+             )
+             SELECT id FROM ancestors ORDER BY depth DESC LIMIT 1",
+            [loop_id],
+            |row| row.get(0),
+        )
+        .map_err(|e| format!("Loop not found: {}", e))?;
+
+    let mut stmt = db
+        .prepare(&format!(
+            "WITH RECURSIVE chain(id, depth) AS (
+                 SELECT ?1, 0
+                 UNION ALL
+                 SELECT l.id, c.depth + 1 FROM ralph_loops l JOIN chain c ON l.parent_loop_id = c.id
+                 WHERE c.depth < 100
+             )
+             SELECT {} FROM ralph_loops WHERE id IN (SELECT id FROM chain) ORDER BY created_at",
+            LOOP_COLUMNS
+        ))
+        .map_err(|e| format!("Failed to query loop chain: {}", e))?;
+    let loops = stmt
+        .query_map([&root], map_loop_row)
+        .map_err(|e| format!("Failed to read loop chain: {}", e))?
+        .filter_map(|r| r.ok())
+        .collect();
+    Ok(loops)
+}
+
+/// Get the per-iteration timeline of a loop: status, summary, and files changed by each run.
+#[tauri::command]
+pub async fn get_ralph_iterations(
+    loop_id: String,
+    state: State<'_, AppState>,
+) -> Result<Vec<RalphIteration>, String> {
+    let db = state
+        .db
+        .lock()
+        .map_err(|e| format!("Failed to lock database: {}", e))?;
+
+    load_ralph_iterations(&db, &loop_id)
 }
 
 /// List all RALPH mistakes for a project, ordered by creation time (newest first).
@@ -1405,7 +3222,7 @@ pub async fn list_ralph_mistakes(
 
 /// Score prompt clarity (0-25).
 /// Looks for action verbs, sentence structure, and absence of ambiguity.
-fn score_clarity(prompt: &str) -> PromptCriterion {
+fn score_clarity(prompt: &str, extra: &[String]) -> PromptCriterion {
     let mut score: u32 = 0;
     let lower = prompt.to_lowercase();
 
@@ -1415,7 +3232,7 @@ fn score_clarity(prompt: &str) -> PromptCriterion {
         "change", "modify", "build", "write", "test", "move", "rename", "extract",
         "optimize", "improve", "migrate", "convert", "replace",
     ];
-    let verb_count = action_verbs.iter().filter(|v| lower.contains(**v)).count();
+    let verb_count = count_keywords(&lower, &action_verbs, extra);
     if verb_count >= 2 {
         score += 10;
     } else if verb_count >= 1 {
@@ -1457,7 +3274,7 @@ fn score_clarity(prompt: &str) -> PromptCriterion {
 
 /// Score prompt specificity (0-25).
 /// Looks for file paths, function names, and technical references.
-fn score_specificity(prompt: &str) -> PromptCriterion {
+fn score_specificity(prompt: &str, extra: &[String]) -> PromptCriterion {
     let mut score: u32 = 0;
 
     // Contains file paths or extensions
@@ -1485,7 +3302,7 @@ fn score_specificity(prompt: &str) -> PromptCriterion {
         "module", "endpoint", "route", "query", "table", "column", "field",
         "prop", "state", "parameter", "argument", "return",
     ];
-    let tech_count = tech_terms.iter().filter(|t| prompt.to_lowercase().contains(**t)).count();
+    let tech_count = count_keywords(&prompt.to_lowercase(), &tech_terms, extra);
     if tech_count >= 3 {
         score += 7;
     } else if tech_count >= 1 {
@@ -1508,7 +3325,7 @@ fn score_specificity(prompt: &str) -> PromptCriterion {
 
 /// Score prompt context (0-25).
 /// Looks for background information, reasoning, and current state description.
-fn score_context(prompt: &str) -> PromptCriterion {
+fn score_context(prompt: &str, extra: &[String]) -> PromptCriterion {
     let mut score: u32 = 0;
     let lower = prompt.to_lowercase();
 
@@ -1517,7 +3334,7 @@ fn score_context(prompt: &str) -> PromptCriterion {
         "because", "currently", "right now", "existing", "already", "the current",
         "before", "after", "when", "so that", "in order to", "needs to", "should",
     ];
-    let ctx_count = context_words.iter().filter(|w| lower.contains(**w)).count();
+    let ctx_count = count_keywords(&lower, &context_words, extra);
     if ctx_count >= 3 {
         score += 12;
     } else if ctx_count >= 1 {
@@ -1562,7 +3379,7 @@ fn score_context(prompt: &str) -> PromptCriterion {
 
 /// Score prompt scope (0-25).
 /// Looks for clear boundaries and defined deliverables.
-fn score_scope(prompt: &str) -> PromptCriterion {
+fn score_scope(prompt: &str, extra: &[String]) -> PromptCriterion {
     let mut score: u32 = 0;
     let lower = prompt.to_lowercase();
 
@@ -1577,7 +3394,7 @@ fn score_scope(prompt: &str) -> PromptCriterion {
         "only", "just", "scope", "limit", "focus on", "don't change",
         "leave", "ignore", "skip", "specifically",
     ];
-    let boundary_count = boundary_words.iter().filter(|w| lower.contains(**w)).count();
+    let boundary_count = count_keywords(&lower, &boundary_words, extra);
     if boundary_count >= 2 {
         score += 8;
     } else if boundary_count >= 1 {
@@ -1610,6 +3427,40 @@ fn score_scope(prompt: &str) -> PromptCriterion {
     }
 }
 
+/// Number of built-in and extra keywords found in a lowercased prompt.
+fn count_keywords(lower: &str, builtin: &[&str], extra: &[String]) -> usize {
+    builtin.iter().filter(|k| lower.contains(**k)).count()
+        + extra
+            .iter()
+            .map(|k| k.trim().to_lowercase())
+            .filter(|k| !k.is_empty() && lower.contains(k.as_str()))
+            .count()
+}
+
+/// Score a team-defined criterion (0-weight) by keyword matches:
+/// two or more earn the full weight, one earns 60%.
+fn score_custom(prompt: &str, criterion: &CustomPromptCriterion) -> PromptCriterion {
+    let lower = prompt.to_lowercase();
+    let hits = count_keywords(&lower, &[], &criterion.keywords);
+    let score = match hits {
+        0 => 0,
+        1 => criterion.weight * 3 / 5,
+        _ => criterion.weight,
+    };
+    let sample = criterion.keywords.iter().take(3).cloned().collect::<Vec<_>>().join(", ");
+
+    PromptCriterion {
+        name: criterion.name.clone(),
+        score,
+        max_score: criterion.weight,
+        feedback: match hits {
+            0 => format!("Prompt does not address {}. Consider mentioning: {}.", criterion.name, sample),
+            1 => format!("Prompt touches on {}; be more explicit.", criterion.name),
+            _ => format!("Prompt covers {}.", criterion.name),
+        },
+    }
+}
+
 /// Generate an auto-enhanced prompt by adding RALPH structure.
 fn generate_enhanced_prompt(original: &str) -> String {
     format!(
@@ -1648,7 +3499,7 @@ pub async fn get_ralph_context(
             .collect::<Vec<_>>()
             .join("\n");
         if summary.len() > 500 {
-            format!("{}...", &summary[..500])
+            format!("{}...", safe_read::truncate_str(&summary, 500))
         } else {
             summary
         }
@@ -1764,7 +3615,7 @@ pub async fn record_ralph_mistake(
     );
 
     // Log activity
-    let _ = db::log_activity_db(&db, &project_id, "learn", &format!("Recorded RALPH mistake: {}", &description));
+    events::publish(&db, AppEvent::activity(&project_id, ActivityType::Memory, &format!("Recorded RALPH mistake: {}", &description)));
 
     Ok(RalphMistake {
         id,
@@ -1850,17 +3701,430 @@ fn append_pattern_to_claude_notes(content: &str, pattern: &str) -> String {
     result.join("\n")
 }
 
+// --- Changelog Generation ---
+
+/// Keep a Changelog heading for changes not yet released (it has no date).
+const CHANGELOG_UNRELEASED: &str = "## [Unreleased]";
+
+/// Keep a Changelog section order.
+const CHANGELOG_CATEGORIES: [&str; 6] = ["Added", "Changed", "Deprecated", "Removed", "Fixed", "Security"];
+
+/// Generate a CHANGELOG.md fragment (Keep a Changelog format) from completed RALPH loops.
+/// PRD loops contribute one entry per completed story with its commit hash; iterative
+/// loops contribute one entry from the first line of their prompt.
+/// `range` is "SINCE..UNTIL" with YYYY-MM-DD dates (either side may be empty).
+/// When `write_to_file` is true the entries are merged into the Unreleased section of the
+/// project's CHANGELOG.md, skipping entries it already lists.
+#[tauri::command]
+pub async fn generate_changelog(
+    project_id: String,
+    range: Option<String>,
+    write_to_file: Option<bool>,
+    state: State<'_, AppState>,
+) -> Result<ChangelogResult, String> {
+    let (since, until) = parse_changelog_range(range.as_deref())?;
+
+    let db = state.db.lock().map_err(|e| format!("DB lock error: {}", e))?;
+
+    let project_path: String = db
+        .query_row(
+            "SELECT path FROM projects WHERE id = ?1",
+            rusqlite::params![project_id],
+            |row| row.get(0),
+        )
+        .map_err(|e| format!("Failed to find project: {}", e))?;
+
+    let mut stmt = db
+        .prepare(
+            "SELECT prompt, outcome, completed_at, COALESCE(mode, 'iterative')
+             FROM ralph_loops
+             WHERE project_id = ?1 AND status = 'completed' AND completed_at IS NOT NULL
+             ORDER BY completed_at ASC",
+        )
+        .map_err(|e| format!("Failed to query loops: {}", e))?;
+
+    let rows: Vec<(String, Option<String>, String, String)> = stmt
+        .query_map(rusqlite::params![project_id], |row| {
+            Ok((row.get(0)?, row.get(1)?, row.get(2)?, row.get(3)?))
+        })
+        .map_err(|e| format!("Failed to read loops: {}", e))?
+        .filter_map(|r| r.ok())
+        .collect();
+
+    let mut entries: Vec<(&'static str, String, Option<String>)> = Vec::new();
+    for (prompt, outcome, completed_at, mode) in rows {
+        if !in_changelog_range(&completed_at, since.as_deref(), until.as_deref()) {
+            continue;
+        }
+        if mode == "prd" {
+            for line in outcome.as_deref().unwrap_or("").lines() {
+                if let Some((title, commit_hash)) = parse_prd_outcome_line(line) {
+                    entries.push((changelog_category(&title), title, commit_hash));
+                }
+            }
+        } else if let Some(first_line) = prompt.lines().map(str::trim).find(|l| !l.is_empty()) {
+            let title: String = if first_line.chars().count() > 100 {
+                format!("{}...", first_line.chars().take(100).collect::<String>())
+            } else {
+                first_line.to_string()
+            };
+            entries.push((changelog_category(&title), title, None));
+        }
+    }
+
+    let content = render_changelog_fragment(&entries);
+
+    let written_path = if write_to_file.unwrap_or(false) && !entries.is_empty() {
+        let changelog_path = Path::new(&project_path).join("CHANGELOG.md");
+        let existing = if changelog_path.exists() {
+            Some(
+                fs::read_to_string(&changelog_path)
+                    .map_err(|e| format!("Failed to read CHANGELOG.md: {}", e))?,
+            )
+        } else {
+            None
+        };
+        let updated = merge_into_changelog(existing.as_deref(), &entries);
+        fs::write(&changelog_path, updated)
+            .map_err(|e| format!("Failed to write CHANGELOG.md: {}", e))?;
+        Some(changelog_path.to_string_lossy().to_string())
+    } else {
+        None
+    };
+
+    events::publish(
+        &db,
+        AppEvent::activity(
+            &project_id,
+            ActivityType::Generate,
+            &format!("Generated changelog with {} entries", entries.len()),
+        ),
+    );
+
+    Ok(ChangelogResult {
+        content,
+        entry_count: entries.len() as u32,
+        written_path,
+    })
+}
+
+/// Parse a "SINCE..UNTIL" date range. Either side may be empty; a bare date means "since".
+fn parse_changelog_range(range: Option<&str>) -> Result<(Option<String>, Option<String>), String> {
+    let range = match range.map(str::trim) {
+        Some(r) if !r.is_empty() => r,
+        _ => return Ok((None, None)),
+    };
+
+    let (since, until) = match range.split_once("..") {
+        Some((s, u)) => (s.trim(), u.trim()),
+        None => (range, ""),
+    };
+
+    let validate = |value: &str| -> Result<Option<String>, String> {
+        if value.is_empty() {
+            return Ok(None);
+        }
+        chrono::NaiveDate::parse_from_str(value, "%Y-%m-%d")
+            .map_err(|_| format!("Invalid date '{}' in range (expected YYYY-MM-DD)", value))?;
+        Ok(Some(value.to_string()))
+    };
+
+    Ok((validate(since)?, validate(until)?))
+}
+
+/// Check whether an RFC 3339 timestamp falls within an inclusive YYYY-MM-DD range.
+fn in_changelog_range(completed_at: &str, since: Option<&str>, until: Option<&str>) -> bool {
+    let date = completed_at.get(..10).unwrap_or(completed_at);
+    since.is_none_or(|s| date >= s) && until.is_none_or(|u| date <= u)
+}
+
+/// Parse a PRD outcome line like "✓ Story 2: Add login form (commit: abc1234)".
+/// Returns the story title and commit hash; failed stories and other lines return None.
+fn parse_prd_outcome_line(line: &str) -> Option<(String, Option<String>)> {
+    let rest = line.trim().strip_prefix("✓ Story ")?;
+    let (_, title_part) = rest.split_once(": ")?;
+
+    match title_part.rfind(" (commit: ") {
+        Some(idx) => {
+            let title = title_part[..idx].trim().to_string();
+            let hash = title_part[idx + " (commit: ".len()..].trim_end_matches(')').trim();
+            let commit_hash = if hash.is_empty() || hash == "no commit" {
+                None
+            } else {
+                Some(hash.to_string())
+            };
+            Some((title, commit_hash))
+        }
+        None => Some((title_part.trim().to_string(), None)),
+    }
+}
+
+/// Map an entry title to a Keep a Changelog category based on its leading verb.
+fn changelog_category(title: &str) -> &'static str {
+    let lower = title.to_lowercase();
+    let first_word = lower.split_whitespace().next().unwrap_or("");
+
+    if lower.contains("security") || lower.contains("vulnerab") {
+        "Security"
+    } else if first_word.starts_with("fix") || first_word.starts_with("resolve") || first_word.starts_with("correct") {
+        "Fixed"
+    } else if first_word.starts_with("remove") || first_word.starts_with("delete") || first_word.starts_with("drop") {
+        "Removed"
+    } else if first_word.starts_with("deprecate") {
+        "Deprecated"
+    } else if ["add", "create", "implement", "introduce", "support", "build"]
+        .iter()
+        .any(|v| first_word.starts_with(v))
+    {
+        "Added"
+    } else {
+        "Changed"
+    }
+}
+
+/// Bullet line for one changelog entry.
+fn changelog_entry_line(title: &str, commit_hash: Option<&str>) -> String {
+    match commit_hash {
+        Some(hash) => format!("- {} ({})", title, hash),
+        None => format!("- {}", title),
+    }
+}
+
+/// Render changelog entries grouped by category under the Unreleased heading.
+fn render_changelog_fragment(entries: &[(&'static str, String, Option<String>)]) -> String {
+    let mut lines = vec![CHANGELOG_UNRELEASED.to_string()];
+
+    for category in CHANGELOG_CATEGORIES {
+        let items: Vec<_> = entries.iter().filter(|(c, _, _)| *c == category).collect();
+        if items.is_empty() {
+            continue;
+        }
+        lines.push(String::new());
+        lines.push(format!("### {}", category));
+        lines.push(String::new());
+        for (_, title, commit_hash) in items {
+            lines.push(changelog_entry_line(title, commit_hash.as_deref()));
+        }
+    }
+
+    if entries.is_empty() {
+        lines.push(String::new());
+        lines.push("_No completed RALPH loops in this range._".to_string());
+    }
+
+    lines.push(String::new());
+    lines.join("\n")
+}
+
+/// Merge entries into CHANGELOG.md's Unreleased section. Each entry goes at the end of its
+/// `### Category` subsection (created in Keep a Changelog order when missing), and entries the
+/// section already lists are skipped. Without an Unreleased section, one is inserted above the
+/// first release; a missing or empty file gets the standard header.
+fn merge_into_changelog(existing: Option<&str>, entries: &[(&'static str, String, Option<String>)]) -> String {
+    let existing = match existing {
+        Some(content) if !content.trim().is_empty() => content,
+        _ => {
+            return format!(
+                "# Changelog\n\nAll notable changes to this project will be documented in this file.\n\nThe format is based on [Keep a Changelog](https://keepachangelog.com/en/1.1.0/).\n\n{}",
+                render_changelog_fragment(entries)
+            );
+        }
+    };
+
+    let mut lines: Vec<String> = existing.lines().map(str::to_string).collect();
+    let Some(start) = lines.iter().position(|l| l.starts_with(CHANGELOG_UNRELEASED)) else {
+        let fragment = render_changelog_fragment(entries);
+        return match lines.iter().position(|l| l.starts_with("## ")) {
+            Some(idx) => {
+                let mut inserted: Vec<String> = fragment.trim_end().lines().map(str::to_string).collect();
+                inserted.push(String::new());
+                lines.splice(idx..idx, inserted);
+                format!("{}\n", lines.join("\n"))
+            }
+            None => format!("{}\n\n{}", existing.trim_end(), fragment),
+        };
+    };
+    // Unreleased has no date (older versions of this command wrote one)
+    lines[start] = CHANGELOG_UNRELEASED.to_string();
+
+    for (category, title, commit_hash) in entries {
+        let end = lines[start + 1..]
+            .iter()
+            .position(|l| l.starts_with("## "))
+            .map_or(lines.len(), |i| start + 1 + i);
+        let line = changelog_entry_line(title, commit_hash.as_deref());
+        if lines[start..end].iter().any(|l| l.trim() == line) {
+            continue;
+        }
+        let last_content = |before: usize, lines: &[String]| {
+            (start..before).rev().find(|&k| !lines[k].trim().is_empty()).unwrap_or(start)
+        };
+
+        let heading = format!("### {}", category);
+        match (start..end).find(|&k| lines[k].trim() == heading) {
+            Some(sub) => {
+                let sub_end = (sub + 1..end).find(|&k| lines[k].starts_with("### ")).unwrap_or(end);
+                let last = last_content(sub_end, &lines);
+                if last == sub {
+                    lines.splice(sub + 1..sub + 1, [String::new(), line]);
+                } else {
+                    lines.insert(last + 1, line);
+                }
+            }
+            None => {
+                let rank = CHANGELOG_CATEGORIES.iter().position(|c| c == category);
+                let before = (start + 1..end)
+                    .find(|&k| {
+                        let later = lines[k]
+                            .strip_prefix("### ")
+                            .and_then(|name| CHANGELOG_CATEGORIES.iter().position(|c| *c == name.trim()));
+                        later.is_some() && later > rank
+                    })
+                    .unwrap_or(end);
+                let anchor = last_content(before, &lines);
+                lines.splice(anchor + 1..anchor + 1, [String::new(), heading, String::new(), line]);
+            }
+        }
+    }
+
+    format!("{}\n", lines.join("\n"))
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::test_support::{insert_project, memory_db, with_project};
+
+    #[test]
+    fn test_record_and_load_iterations() {
+        let conn = with_project();
+        conn.execute(
+            "INSERT INTO ralph_loops (id, project_id, prompt, status, created_at) VALUES ('loop1', 'p1', 'Fix', 'running', '2025-01-01T00:00:00Z')",
+            [],
+        )
+        .unwrap();
+        let files = vec![RalphIterationFile { path: "src/a.rs".into(), change: "modified".into() }];
+        let issues = vec![
+            ExtractedIssue { issue_type: "testing".into(), description: "Test fails\ndetails".into(), ..Default::default() },
+            ExtractedIssue { issue_type: "logic".into(), description: "Off by one".into(), ..Default::default() },
+        ];
+
+        record_iteration(&conn, "loop1", IterationRecord {
+            iteration: 1,
+            story_index: None,
+            status: "issues",
+            summary: issues_summary(&issues),
+            issues_count: 2,
+            files_changed: &files,
+            started_at: "2025-01-01T00:00:00Z",
+        });
+        record_iteration(&conn, "loop1", IterationRecord {
+            iteration: 2,
+            story_index: None,
+            status: "passed",
+            summary: issues_summary(&[]),
+            issues_count: 0,
+            files_changed: &[],
+            started_at: "2025-01-01T00:05:00Z",
+        });
+
+        let iterations = load_ralph_iterations(&conn, "loop1").unwrap();
+        assert_eq!(iterations.len(), 2);
+        assert_eq!(iterations[0].summary, "2 issues: Test fails (+1 more)");
+        assert_eq!(iterations[0].files_changed, files);
+        assert_eq!(iterations[1].status, "passed");
+        assert_eq!(iterations[1].summary, "No issues found");
+        assert!(load_ralph_iterations(&conn, "other").unwrap().is_empty());
+    }
+
+    #[test]
+    fn test_hold_for_review() {
+        let conn = memory_db();
+        crate::db::schema::migrate_add_loop_options(&conn).unwrap();
+        crate::db::schema::migrate_add_review_diff(&conn).unwrap();
+        insert_project(&conn, "p1", "/tmp/p1");
+        conn.execute(
+            "INSERT INTO ralph_loops (id, project_id, prompt, status, created_at) VALUES ('loop1', 'p1', 'Fix', 'running', '2025-01-01T00:00:00Z')",
+            [],
+        )
+        .unwrap();
+
+        hold_for_review(&conn, "loop1", "Destructive change: src/a.rs (removed)", "-fn a() {}");
+        let (status, outcome, diff): (String, String, Option<String>) = conn
+            .query_row("SELECT status, outcome, review_diff FROM ralph_loops WHERE id = 'loop1'", [], |row| {
+                Ok((row.get(0)?, row.get(1)?, row.get(2)?))
+            })
+            .unwrap();
+        assert_eq!(status, "needs_review");
+        assert!(outcome.starts_with("Destructive change: src/a.rs (removed)"));
+        assert_eq!(diff.as_deref(), Some("-fn a() {}"));
+
+        // Loops saved before the threshold existed get the default
+        let options: LoopOptions = serde_json::from_str(r#"{"allowedTools":"Read","maxIterations":3}"#).unwrap();
+        assert_eq!(options.max_deleted_percent, worktree::DEFAULT_MAX_DELETED_PERCENT);
+    }
+
+    fn setup_loops() -> Connection {
+        let conn = memory_db();
+        crate::db::schema::migrate_add_injected_patterns(&conn).unwrap();
+        crate::db::schema::migrate_add_loop_options(&conn).unwrap();
+        crate::db::schema::migrate_add_review_diff(&conn).unwrap();
+        crate::db::schema::migrate_add_mistake_location(&conn).unwrap();
+        crate::db::schema::migrate_add_parent_loop(&conn).unwrap();
+        conn.execute_batch(
+            "INSERT INTO projects (id, name, path, created_at) VALUES ('p1', 'Test', '/tmp/p1', '2025-01-01T00:00:00Z');
+             INSERT INTO ralph_loops (id, project_id, prompt, status, iterations, outcome, created_at, loop_options)
+                 VALUES ('loop1', 'p1', 'Add export', 'completed', 2, 'Completed after 2 iterations.\n// TODO: handle empty input\nTODO: handle empty input', '2025-01-01T00:00:00Z', '{\"allowedTools\":\"Read\",\"maxIterations\":3}');
+             INSERT INTO ralph_iterations (id, loop_id, iteration, status, started_at, completed_at) VALUES
+                 ('i1', 'loop1', 1, 'issues', '2025-01-01T00:00:00Z', '2025-01-01T00:01:00Z'),
+                 ('i2', 'loop1', 2, 'issues', '2025-01-01T00:01:00Z', '2025-01-01T00:02:00Z');
+             INSERT INTO ralph_mistakes (id, project_id, loop_id, mistake_type, description, context, created_at, file_path, line) VALUES
+                 ('m1', 'p1', 'loop1', 'type_error', 'old issue', 'Iteration 1: Add export', '2025-01-01T00:00:30Z', NULL, NULL),
+                 ('m2', 'p1', 'loop1', 'test_failure', 'test_export fails', 'Iteration 2: Add export', '2025-01-01T00:01:30Z', 'src/a.rs', 12);",
+        )
+        .unwrap();
+        conn
+    }
+
+    #[test]
+    fn test_followup_seed() {
+        let conn = setup_loops();
+        let (project_id, prompt, options) = followup_seed(&conn, "loop1").unwrap();
+        assert_eq!(project_id, "p1");
+        assert_eq!(options.max_iterations, 3);
+        assert!(prompt.contains("Original task:\nAdd export"));
+        assert!(prompt.contains("- [test_failure] test_export fails (src/a.rs:12)"));
+        assert!(!prompt.contains("old issue"));
+        assert_eq!(prompt.matches("- TODO: handle empty input").count(), 1);
+
+        conn.execute("UPDATE ralph_loops SET status = 'running' WHERE id = 'loop1'", []).unwrap();
+        assert!(followup_seed(&conn, "loop1").is_err());
+    }
+
+    #[test]
+    fn test_load_loop_chain() {
+        let conn = setup_loops();
+        conn.execute_batch(
+            "INSERT INTO ralph_loops (id, project_id, prompt, status, created_at, parent_loop_id) VALUES
+                 ('loop2', 'p1', 'Follow-up', 'completed', '2025-01-02T00:00:00Z', 'loop1'),
+                 ('loop3', 'p1', 'Follow-up 2', 'running', '2025-01-03T00:00:00Z', 'loop2'),
+                 ('other', 'p1', 'Unrelated', 'completed', '2025-01-04T00:00:00Z', NULL);",
+        )
+        .unwrap();
+
+        for id in ["loop1", "loop2", "loop3"] {
+            let chain: Vec<String> = load_loop_chain(&conn, id).unwrap().into_iter().map(|l| l.id).collect();
+            assert_eq!(chain, vec!["loop1", "loop2", "loop3"]);
+        }
+        let chain = load_loop_chain(&conn, "loop3").unwrap();
+        assert_eq!(chain[2].parent_loop_id.as_deref(), Some("loop2"));
+        assert!(load_loop_chain(&conn, "missing").is_err());
+    }
 
     #[test]
     fn test_analyze_short_prompt() {
         // A very short, vague prompt should score low
-        let result = tokio::runtime::Runtime::new()
-            .unwrap()
-            .block_on(analyze_ralph_prompt("fix bug".to_string()))
-            .unwrap();
+        let result = analyze_prompt_heuristic("fix bug", &PromptCriteriaConfig::default());
 
         assert!(result.quality_score < 50);
         assert_eq!(result.criteria.len(), 4);
@@ -1879,15 +4143,101 @@ mod tests {
             3. Export it from the module. \
             The function should return an f64 representing the trend percentage.";
 
-        let result = tokio::runtime::Runtime::new()
-            .unwrap()
-            .block_on(analyze_ralph_prompt(detailed.to_string()))
-            .unwrap();
+        let result = analyze_prompt_heuristic(detailed, &PromptCriteriaConfig::default());
 
         assert!(result.quality_score >= 50);
         assert_eq!(result.criteria.len(), 4);
     }
 
+    #[test]
+    fn test_analyze_prompt_with_custom_criteria() {
+        let prompt = "Add rate limiting to the login endpoint in src/auth.rs so that brute force attacks fail. \
+            Validate input and never log the password.";
+        let base = analyze_prompt_heuristic(prompt, &PromptCriteriaConfig::default());
+        assert_eq!(base.criteria.len(), 4);
+
+        let config = PromptCriteriaConfig {
+            custom_criteria: vec![
+                CustomPromptCriterion {
+                    name: "Security".into(),
+                    keywords: vec!["password".into(), "validate".into(), "attack".into()],
+                    weight: 25,
+                    suggestion: None,
+                },
+                CustomPromptCriterion {
+                    name: "Performance".into(),
+                    keywords: vec!["latency".into(), "benchmark".into()],
+                    weight: 25,
+                    suggestion: Some("State latency or memory budgets.".into()),
+                },
+            ],
+            ..Default::default()
+        };
+        let custom = analyze_prompt_heuristic(prompt, &config);
+        assert_eq!(custom.criteria.len(), 6);
+        assert_eq!(custom.criteria[4].score, 25);
+        assert_eq!(custom.criteria[5].score, 0);
+        let builtin_total: u32 = base.criteria.iter().map(|c| c.score).sum();
+        assert_eq!(custom.quality_score, ((builtin_total + 25) * 100 + 75) / 150);
+        assert!(custom.suggestions.contains(&"State latency or memory budgets.".to_string()));
+
+        // Extra keywords localize the built-in keyword lists
+        let german = "Implementiere die Funktion";
+        let mut localized = PromptCriteriaConfig::default();
+        localized.extra_keywords.clarity = vec!["implementiere".into()];
+        assert!(
+            analyze_prompt_heuristic(german, &localized).criteria[0].score
+                > analyze_prompt_heuristic(german, &PromptCriteriaConfig::default()).criteria[0].score
+        );
+    }
+
+    #[test]
+    fn test_quick_analyze_matches_full_analysis() {
+        let config = PromptCriteriaConfig {
+            custom_criteria: vec![CustomPromptCriterion {
+                name: "Performance".into(),
+                keywords: vec!["latency".into(), "benchmark".into()],
+                weight: 25,
+                suggestion: Some("State latency or memory budgets.".into()),
+            }],
+            ..Default::default()
+        };
+        for prompt in ["fix bug", "Add rate limiting to the login endpoint in src/auth.rs because brute force works.", ""] {
+            let full = analyze_prompt_heuristic(prompt, &config);
+            let quick = quick_analyze_prompt(prompt, &config);
+            assert_eq!(quick.quality_score, full.quality_score);
+            match &quick.top_suggestion {
+                Some(top) => assert!(full.suggestions.contains(top)),
+                None => assert!(full.suggestions.is_empty()),
+            }
+        }
+
+        // The weakest criterion wins: nothing about performance scores 0 of 25
+        let quick = quick_analyze_prompt("Add rate limiting to the login endpoint in src/auth.rs.", &config);
+        assert_eq!(quick.top_suggestion.as_deref(), Some("State latency or memory budgets."));
+    }
+
+    #[test]
+    fn test_validate_prompt_criteria() {
+        let criterion = |name: &str, keywords: Vec<&str>, weight: u32| CustomPromptCriterion {
+            name: name.into(),
+            keywords: keywords.into_iter().map(String::from).collect(),
+            weight,
+            suggestion: Some("  ".into()),
+        };
+        let config = |criteria| PromptCriteriaConfig { custom_criteria: criteria, ..Default::default() };
+
+        let valid = validate_prompt_criteria(config(vec![criterion(" Security ", vec![" auth ", ""], 10)])).unwrap();
+        assert_eq!(valid.custom_criteria[0].name, "Security");
+        assert_eq!(valid.custom_criteria[0].keywords, vec!["auth"]);
+        assert_eq!(valid.custom_criteria[0].suggestion, None);
+
+        assert!(validate_prompt_criteria(config(vec![criterion("Scope", vec!["x"], 10)])).is_err());
+        assert!(validate_prompt_criteria(config(vec![criterion("A", vec![" "], 10)])).is_err());
+        assert!(validate_prompt_criteria(config(vec![criterion("A", vec!["x"], 0)])).is_err());
+        assert!(validate_prompt_criteria(config(vec![criterion("A", vec!["x"], 5), criterion("a", vec!["y"], 5)])).is_err());
+    }
+
     #[test]
     fn test_generate_enhanced_prompt() {
         let enhanced = generate_enhanced_prompt("fix the login bug");
@@ -1902,8 +4252,8 @@ mod tests {
     fn test_score_clarity_with_verbs() {
         let good = "Implement a new component and add tests for it.";
         let bad = "thing";
-        let good_score = score_clarity(good);
-        let bad_score = score_clarity(bad);
+        let good_score = score_clarity(good, &[]);
+        let bad_score = score_clarity(bad, &[]);
         assert!(good_score.score > bad_score.score);
     }
 
@@ -1911,8 +4261,8 @@ mod tests {
     fn test_score_specificity_with_paths() {
         let specific = "Update the calculate_health function in src/core/health.rs";
         let vague = "update the thing";
-        let specific_score = score_specificity(specific);
-        let vague_score = score_specificity(vague);
+        let specific_score = score_specificity(specific, &[]);
+        let vague_score = score_specificity(vague, &[]);
         assert!(specific_score.score > vague_score.score);
     }
 
@@ -1955,6 +4305,7 @@ mod tests {
                 issue_type: "error".to_string(),
                 description: "undefined variable 'user'".to_string(),
                 suggested_fix: Some("Define user before using".to_string()),
+                ..Default::default()
             },
         ];
         let prompt = build_iteration_prompt(original, &issues, 1);
@@ -1966,6 +4317,37 @@ mod tests {
         assert!(prompt.contains("Fix the bug in login"));
     }
 
+    #[test]
+    fn test_cli_session_resumption() {
+        let json = r#"{"type": "result", "subtype": "success", "is_error": false, "result": "Added the helper",
+            "session_id": "5f1c", "usage": {"input_tokens": 1200, "cache_creation_input_tokens": 30000,
+            "cache_read_input_tokens": 90000, "output_tokens": 800}}"#;
+        let session = parse_cli_json(json);
+        assert_eq!(session.text, "Added the helper");
+        assert_eq!(session.session_id.as_deref(), Some("5f1c"));
+        assert_eq!(session.context_tokens, 122_000);
+
+        let plain = parse_cli_json("Implemented add()\n");
+        assert_eq!(plain.text, "Implemented add()\n");
+        assert_eq!(plain.session_id, None);
+
+        let mut options = LoopOptions { resume_session: true, ..LoopOptions::default() };
+        assert_eq!(next_session(&options, &session).as_deref(), Some("5f1c"));
+        assert_eq!(next_session(&options, &plain), None);
+        options.session_token_limit = 100_000;
+        assert_eq!(next_session(&options, &session), None);
+        assert_eq!(next_session(&LoopOptions::default(), &session), None);
+
+        let issues = vec![ExtractedIssue {
+            issue_type: "test_failure".to_string(),
+            description: "add() has no test".to_string(),
+            ..Default::default()
+        }];
+        let prompt = build_resume_prompt(&issues, 1);
+        assert!(prompt.contains("Iteration 2"));
+        assert!(prompt.contains("add() has no test"));
+    }
+
     #[test]
     fn test_prd_parsing() {
         use crate::models::ralph::PrdFile;
@@ -2007,6 +4389,8 @@ mod tests {
             priority: 1,
             completed: false,
             commit_hash: None,
+            depends_on: Vec::new(),
+            project: None,
         };
 
         let prd = PrdFile {
@@ -2017,6 +4401,8 @@ mod tests {
             typecheck_command: None,
             max_iterations_per_story: 3,
             stories: vec![story.clone()],
+            cross_repo_context: None,
+            max_duration_minutes: None,
         };
 
         let prompt = build_story_prompt(&story, &prd);
@@ -2040,4 +4426,321 @@ mod tests {
         assert_eq!(categorize_mistake("process killed by user"), "user_cancelled");
         assert_eq!(categorize_mistake("something went wrong"), "implementation");
     }
+
+    #[test]
+    fn test_parse_prd_outcome_line() {
+        assert_eq!(
+            parse_prd_outcome_line("✓ Story 2: Add login form (commit: abc1234)"),
+            Some(("Add login form".to_string(), Some("abc1234".to_string())))
+        );
+        assert_eq!(
+            parse_prd_outcome_line("✓ Story 3: Fix header (commit: no commit)"),
+            Some(("Fix header".to_string(), None))
+        );
+        assert_eq!(parse_prd_outcome_line("✗ Story 4: Broken (failed after 3 iterations)"), None);
+        assert_eq!(parse_prd_outcome_line("Completed: 2/3 stories"), None);
+    }
+
+    #[test]
+    fn test_prd_story_order() {
+        use crate::models::ralph::PrdStory;
+
+        let story = |id: &str, deps: &[&str]| PrdStory {
+            id: id.to_string(),
+            title: id.to_string(),
+            description: String::new(),
+            acceptance_criteria: None,
+            priority: 1,
+            completed: false,
+            commit_hash: None,
+            depends_on: deps.iter().map(|d| d.to_string()).collect(),
+            project: None,
+        };
+
+        let stories = vec![story("ui", &["api"]), story("schema", &[]), story("api", &["schema"]), story("docs", &[])];
+        assert_eq!(prd_story_order(&stories).unwrap(), vec![1, 2, 0, 3]);
+
+        let cycle = vec![story("a", &["c"]), story("b", &["a"]), story("c", &["b"]), story("d", &["a"])];
+        let err = prd_story_order(&cycle).unwrap_err();
+        assert!(err.contains("a → c → b → a"), "{}", err);
+        assert!(prd_story_order(&[story("a", &["a"])]).unwrap_err().contains("a → a"));
+
+        assert!(prd_story_order(&[story("a", &["missing"])]).unwrap_err().contains("\"missing\""));
+        assert!(prd_story_order(&[story("a", &[]), story("a", &[]), story("b", &["a"])]).is_err());
+        assert_eq!(prd_story_order(&[story("a", &[]), story("a", &[])]).unwrap(), vec![0, 1]);
+    }
+
+    #[test]
+    fn test_retry_prd_keeps_commits_and_failed_stories() {
+        use crate::models::ralph::{PrdFile, PrdStory};
+
+        let story = |id: &str, completed: bool, commit: Option<&str>| PrdStory {
+            id: id.to_string(),
+            title: format!("Story {}", id),
+            description: String::new(),
+            acceptance_criteria: None,
+            priority: 1,
+            completed,
+            commit_hash: commit.map(str::to_string),
+            depends_on: Vec::new(),
+            project: None,
+        };
+        let prd = PrdFile {
+            name: "Auth".to_string(),
+            description: None,
+            branch: "main".to_string(),
+            test_command: None,
+            typecheck_command: None,
+            max_iterations_per_story: 3,
+            stories: vec![story("a", true, Some("0000aaa")), story("b", false, None), story("c", false, None), story("d", false, None)],
+            cross_repo_context: None,
+            max_duration_minutes: None,
+        };
+        let outcome = "✓ Story 2: Story b (commit: abc1234)\n✗ Story 3: Story c (failed after 3 iterations)\n⊘ Story 4: Story d (blocked by c)\nCompleted: 2/4 stories";
+
+        let retry = retry_prd(&prd, outcome).unwrap();
+        let stories: Vec<(&str, bool, Option<&str>)> = retry
+            .stories
+            .iter()
+            .map(|s| (s.id.as_str(), s.completed, s.commit_hash.as_deref()))
+            .collect();
+        assert_eq!(
+            stories,
+            vec![("a", true, Some("0000aaa")), ("b", true, Some("abc1234")), ("c", false, None), ("d", false, None)]
+        );
+
+        assert!(retry_prd(&prd, "✓ Story 2: Story b (commit: abc1234)").is_err());
+
+        // Stories cut off by the time limit are retried like failed ones
+        let outcome = "✓ Story 2: Story b (commit: abc1234)\n⏱ Story 3: Story c (stopped after 1 iterations: time limit reached)\n⏱ Story 4: Story d (not started: time limit reached)";
+        let retry = retry_prd(&prd, outcome).unwrap();
+        let pending: Vec<&str> = retry.stories.iter().filter(|s| !s.completed).map(|s| s.id.as_str()).collect();
+        assert_eq!(pending, vec!["c", "d"]);
+    }
+
+    #[test]
+    fn test_loop_time_limit() {
+        use std::time::{Duration, Instant};
+
+        assert_eq!(validate_max_duration(None).unwrap(), None);
+        assert_eq!(validate_max_duration(Some(90)).unwrap(), Some(90));
+        assert!(validate_max_duration(Some(0)).is_err());
+        assert!(validate_max_duration(Some(MAX_DURATION_MINUTES + 1)).is_err());
+
+        let now = Instant::now();
+        assert!(!time_limit_reached(now, Some(1)));
+        assert!(!time_limit_reached(now, None));
+        if let Some(earlier) = now.checked_sub(Duration::from_secs(61)) {
+            assert!(time_limit_reached(earlier, Some(1)));
+            assert!(!time_limit_reached(earlier, Some(2)));
+        }
+
+        let open = vec![ExtractedIssue { description: "add() has no test".to_string(), ..Default::default() }];
+        let outcome = time_limit_outcome(30, 2, &open, "Implemented add()");
+        assert!(outcome.starts_with("Stopped at the 30-minute time limit after 2 iterations. Still open: 1 issue: add() has no test"));
+        assert!(outcome.ends_with("\n\nImplemented add()"));
+
+        // Old stored options without the field deserialize to no limit
+        let options: LoopOptions = serde_json::from_str(r#"{"allowedTools":"Read","maxIterations":3}"#).unwrap();
+        assert_eq!(options.max_duration_minutes, None);
+    }
+
+    #[test]
+    fn test_failing_gates_fail_the_loop() {
+        let dir = tempfile::tempdir().unwrap();
+        let gates = vec!["true".to_string(), "false".to_string(), " ".to_string()];
+        let failures = run_acceptance_gates(&dir.path().to_string_lossy(), &gates);
+        assert_eq!(failures.len(), 1);
+        assert_eq!(failures[0].0, "false");
+        assert_eq!(failures[0].1.issue_type, "acceptance_gate");
+
+        let outcome = failing_gates_outcome(3, &["false", "npm test"], "Tried again");
+        assert_eq!(
+            outcome,
+            "Failed: acceptance gates still failing after 3 iterations: `false`, `npm test`\n\nTried again"
+        );
+    }
+
+    #[test]
+    fn test_parse_changelog_range() {
+        assert_eq!(parse_changelog_range(None).unwrap(), (None, None));
+        assert_eq!(
+            parse_changelog_range(Some("2025-01-01..2025-02-01")).unwrap(),
+            (Some("2025-01-01".to_string()), Some("2025-02-01".to_string()))
+        );
+        assert_eq!(
+            parse_changelog_range(Some("..2025-02-01")).unwrap(),
+            (None, Some("2025-02-01".to_string()))
+        );
+        assert!(parse_changelog_range(Some("last-week")).is_err());
+        assert!(in_changelog_range("2025-01-15T10:00:00+00:00", Some("2025-01-01"), Some("2025-01-15")));
+        assert!(!in_changelog_range("2025-01-16T10:00:00+00:00", None, Some("2025-01-15")));
+    }
+
+    #[test]
+    fn test_render_and_merge_changelog() {
+        let entries = vec![
+            (changelog_category("Add login form"), "Add login form".to_string(), Some("abc1234".to_string())),
+            (changelog_category("Fix header overflow"), "Fix header overflow".to_string(), None),
+            (changelog_category("Refactor auth store"), "Refactor auth store".to_string(), None),
+        ];
+        let fragment = render_changelog_fragment(&entries);
+        assert!(fragment.starts_with("## [Unreleased]\n"));
+        assert!(fragment.contains("### Added\n\n- Add login form (abc1234)"));
+        assert!(fragment.contains("### Fixed\n\n- Fix header overflow"));
+        assert!(fragment.contains("### Changed\n\n- Refactor auth store"));
+        assert!(fragment.find("### Added").unwrap() < fragment.find("### Fixed").unwrap());
+
+        let created = merge_into_changelog(None, &entries);
+        assert!(created.starts_with("# Changelog"));
+        assert!(created.contains("Keep a Changelog"));
+        assert!(created.ends_with(&fragment));
+
+        let existing = "# Changelog\n\nIntro.\n\n## [1.0.0] - 2024-12-01\n\n### Added\n\n- First release\n";
+        let merged = merge_into_changelog(Some(existing), &entries);
+        assert!(merged.find("## [Unreleased]").unwrap() < merged.find("## [1.0.0]").unwrap());
+        assert!(merged.contains("Intro."));
+
+        // Writing the same loops again leaves the file unchanged
+        assert_eq!(merge_into_changelog(Some(&merged), &entries), merged);
+    }
+
+    #[test]
+    fn test_merge_into_existing_unreleased_section() {
+        let existing = "# Changelog\n\n## [Unreleased] - 2025-01-10\n\n### Fixed\n\n- Fix header overflow\n\n## [1.0.0] - 2024-12-01\n\n### Added\n\n- First release\n";
+        let entries = vec![
+            ("Fixed", "Fix header overflow".to_string(), None),
+            ("Fixed", "Fix login redirect".to_string(), Some("def5678".to_string())),
+            ("Added", "Add login form".to_string(), Some("abc1234".to_string())),
+            ("Security", "Patch token leak".to_string(), None),
+        ];
+
+        let merged = merge_into_changelog(Some(existing), &entries);
+        assert_eq!(
+            merged,
+            "# Changelog\n\n## [Unreleased]\n\n### Added\n\n- Add login form (abc1234)\n\n\
+             ### Fixed\n\n- Fix header overflow\n- Fix login redirect (def5678)\n\n\
+             ### Security\n\n- Patch token leak\n\n\
+             ## [1.0.0] - 2024-12-01\n\n### Added\n\n- First release\n"
+        );
+        assert_eq!(merged.matches("## [Unreleased]").count(), 1);
+        assert_eq!(merge_into_changelog(Some(&merged), &entries), merged);
+    }
+
+    #[test]
+    fn test_save_prompt_analysis_persists_result() {
+        let db = with_project();
+
+        let analysis = analyze_prompt_heuristic("fix bug", &PromptCriteriaConfig::default());
+        save_prompt_analysis(&db, "p1", "fix bug", &analysis, "heuristic").unwrap();
+
+        let (score, criteria): (u32, String) = db
+            .query_row(
+                "SELECT quality_score, criteria FROM prompt_analyses WHERE project_id = 'p1'",
+                [],
+                |row| Ok((row.get(0)?, row.get(1)?)),
+            )
+            .unwrap();
+        assert_eq!(score, analysis.quality_score);
+        let parsed: Vec<PromptCriterion> = serde_json::from_str(&criteria).unwrap();
+        assert_eq!(parsed.len(), 4);
+    }
+
+    #[test]
+    fn test_select_relevant_learnings_filters_by_similarity() {
+        let candidates = vec![
+            "Always run migrations after editing schema.rs".to_string(),
+            "Use camelCase for serde fields in models".to_string(),
+            "Previously hit \"login form crashed\" - fix: guard against null session".to_string(),
+        ];
+        let selected = select_relevant_learnings(
+            "Fix the login form so the session survives a refresh",
+            &candidates,
+            5,
+        );
+        assert_eq!(selected.len(), 1);
+        assert!(selected[0].contains("login form"));
+
+        assert!(select_relevant_learnings("Update README", &candidates, 5).is_empty());
+    }
+
+    #[test]
+    fn test_inject_learnings() {
+        assert_eq!(inject_learnings("Do the thing", &[]), "Do the thing");
+        let prompt = inject_learnings("Do the thing", &["Run tests first".to_string()]);
+        assert!(prompt.starts_with("## Project Learnings"));
+        assert!(prompt.contains("- Run tests first"));
+        assert!(prompt.ends_with("## Task\nDo the thing"));
+    }
+
+    #[tokio::test(flavor = "multi_thread", worker_threads = 2)]
+    async fn test_start_ralph_loop_end_to_end() {
+        use crate::test_support::{assert_snapshot, wait_for, TestEnv};
+        use std::time::Duration;
+
+        let env = TestEnv::new();
+        let (project_id, _) = env.add_project("web");
+        let _api = env
+            .mock_anthropic(&[
+                r#"{"issues": [{"type": "test_failure", "description": "add() has no test"}]}"#,
+                r#"{"issues": []}"#,
+            ])
+            .await;
+        let calls = env.dir.path().join("claude-calls.log");
+        env.stub_claude_cli(&format!(
+            "printf '%s\\n---\\n' \"$2\" >> '{}'\necho 'Implemented add()'",
+            calls.display()
+        ));
+
+        let started = start_ralph_loop(
+            project_id.clone(),
+            "Add an add() helper".into(),
+            None,
+            80,
+            None,
+            None,
+            None,
+            env.handle(),
+            env.state(),
+        )
+        .await
+        .unwrap();
+        assert_eq!(started.status, "running");
+
+        let status = || -> String {
+            env.db()
+                .query_row("SELECT status FROM ralph_loops WHERE id = ?1", [&started.id], |row| row.get(0))
+                .unwrap()
+        };
+        assert!(wait_for(Duration::from_secs(20), || status() != "running").await, "loop did not finish");
+
+        let (status, iterations, outcome): (String, u32, String) = env
+            .db()
+            .query_row(
+                "SELECT status, iterations, outcome FROM ralph_loops WHERE id = ?1",
+                [&started.id],
+                |row| Ok((row.get(0)?, row.get(1)?, row.get(2)?)),
+            )
+            .unwrap();
+        assert_eq!(status, "completed");
+        assert_eq!(iterations, 2);
+
+        let prompts = std::fs::read_to_string(&calls).unwrap();
+        let prompts: Vec<&str> = prompts.split("\n---\n").filter(|p| !p.is_empty()).collect();
+        assert_eq!(prompts.len(), 2);
+        assert!(prompts[0].ends_with("Add an add() helper"));
+        assert!(prompts[1].contains("**[test_failure]** add() has no test"));
+
+        let records = load_ralph_iterations(&env.db(), &started.id).unwrap();
+        let snapshot = serde_json::json!({
+            "status": status,
+            "iterations": iterations,
+            "outcome": outcome,
+            "records": records
+                .iter()
+                .map(|r| serde_json::json!({ "iteration": r.iteration, "status": r.status, "summary": r.summary }))
+                .collect::<Vec<_>>(),
+        });
+        assert_snapshot("start_ralph_loop", &env.redact(&snapshot));
+    }
 }
@@ -0,0 +1,458 @@
+//! @module commands/ralph_patches
+//! @description Tauri IPC commands for AI-proposed patches that fix located RALPH issues
+//!
+//! PURPOSE:
+//! - Ask the AI for a minimal unified diff per issue that has a file (and usually a line)
+//! - Store the diffs as reviewable patch proposals instead of starting another CLI run
+//! - List, apply, and reject proposals
+//!
+//! DEPENDENCIES:
+//! - tauri - Command macro and State
+//! - db::AppState - Database connection and shared HTTP client
+//! - core::ai - API key and Claude calls
+//! - core::ai_queue - Prioritized slot for each Claude call
+//! - core::ai_audit - Audit scope for fix proposals
+//! - core::patch - Path resolution, file excerpts, diff normalization, patch engines
+//! - models::ralph - PatchProposal
+//!
+//! EXPORTS:
+//! - propose_issue_fixes - Create proposals for a loop's located issues that have none yet
+//! - list_patch_proposals - Proposals for a loop, newest first
+//! - apply_patch_proposal - Apply a pending (or re-check a conflicting) proposal
+//! - reject_patch_proposal - Mark a proposal rejected
+//!
+//! PATTERNS:
+//! - Located issues are ralph_mistakes rows of the loop with file_path set (from core::issue_rules
+//!   or the AI extractor's file/line fields)
+//! - At most MAX_PROPOSALS_PER_RUN issues are sent per call; call again for the rest
+//! - A proposal is "pending" when the configured patch engine's check passes (builtin by
+//!   default, `git apply --check` when patch.engine is "git"), otherwise "conflict" with the error
+//! - The DB lock is released while the AI and the patch engine run
+//!
+//! CLAUDE NOTES:
+//! - Issues the AI declines to fix (empty diff) or whose diff touches another file are skipped,
+//!   not stored, so they are offered again on the next call
+//! - Applying never commits; the change shows up as a working-tree edit for the user to review
+
+use std::fs;
+use std::path::Path;
+
+use chrono::Utc;
+use rusqlite::Connection;
+use tauri::State;
+
+use crate::core::ai;
+use crate::core::ai_audit;
+use crate::core::ai_queue;
+use crate::core::patch;
+use crate::core::events::{self, AppEvent};
+use crate::db::AppState;
+use crate::models::activity::ActivityType;
+use crate::models::ai_queue::AiJobKind;
+use crate::models::ralph::PatchProposal;
+
+/// Issues sent to the AI per propose_issue_fixes call.
+const MAX_PROPOSALS_PER_RUN: usize = 5;
+
+const SELECT_COLUMNS: &str = "id, loop_id, project_id, mistake_id, file_path, line, issue, diff, explanation,
+                              status, error, created_at, applied_at";
+
+const FIX_SYSTEM_PROMPT: &str = r#"You fix one reported issue in one file with the smallest possible change.
+
+OUTPUT FORMAT (JSON only, no markdown fences):
+{
+  "diff": "unified diff of the file",
+  "explanation": "One sentence describing the change"
+}
+
+Rules:
+- The diff must only touch the given file, with --- a/<path> and +++ b/<path> headers
+- Use 3 lines of unchanged context around each change, copied exactly from the file
+- Do not refactor, reformat, or fix anything other than the reported issue
+- If the issue cannot be fixed in this file, return {"diff": "", "explanation": "<why>"}"#;
+
+/// A loop issue with a file location and no proposal yet.
+#[derive(Debug, Clone, PartialEq)]
+struct LocatedIssue {
+    mistake_id: String,
+    file_path: String,
+    line: Option<u32>,
+    description: String,
+    suggested_fix: Option<String>,
+}
+
+/// Create patch proposals for the loop's located issues that do not have one yet.
+/// Requires an API key. Returns the proposals created by this call.
+#[tauri::command]
+pub async fn propose_issue_fixes(loop_id: String, state: State<'_, AppState>) -> Result<Vec<PatchProposal>, String> {
+    let (project_id, project_path, api_key, engine, issues) = {
+        let db = state.db.lock().map_err(|e| format!("DB lock error: {}", e))?;
+        let (project_id, project_path): (String, String) = db
+            .query_row(
+                "SELECT l.project_id, p.path FROM ralph_loops l JOIN projects p ON p.id = l.project_id WHERE l.id = ?1",
+                [&loop_id],
+                |row| Ok((row.get(0)?, row.get(1)?)),
+            )
+            .map_err(|e| format!("Loop not found: {}", e))?;
+        let api_key = ai::get_api_key(&db)?;
+        (project_id, project_path, api_key, patch::engine(&db), located_issues_db(&db, &loop_id)?)
+    };
+
+    let root = Path::new(&project_path);
+    let excludes = ai::load_ai_excludes(&project_path);
+    let mut proposals = Vec::new();
+    let mut last_error = None;
+    for issue in issues {
+        let Some(rel) = patch::relative_path(root, &issue.file_path) else { continue };
+        if ai::is_ai_excluded(&excludes, &rel) {
+            continue;
+        }
+        let Ok(content) = fs::read_to_string(root.join(&rel)) else { continue };
+        let (start, end, text) = patch::excerpt(&content, issue.line);
+
+        let prompt = fix_prompt(&issue, &rel, start, end, &text);
+        let call = ai::call_claude(&state.http_client, &api_key, FIX_SYSTEM_PROMPT, &prompt);
+        let queued = ai_queue::run(AiJobKind::RalphLoop, &rel, call);
+        let response = match ai_audit::scoped(&project_id, "RALPH fix proposal", queued).await {
+            Ok(response) => response,
+            Err(e) => {
+                last_error = Some(e);
+                continue;
+            }
+        };
+        let Some((raw_diff, explanation)) = parse_fix_response(&response) else { continue };
+        let Ok(diff) = patch::normalize_diff(&raw_diff, &rel) else { continue };
+        let (status, error) = match patch::check(engine, root, &diff) {
+            Ok(()) => ("pending", None),
+            Err(e) => ("conflict", Some(e)),
+        };
+
+        proposals.push(PatchProposal {
+            id: uuid::Uuid::new_v4().to_string(),
+            loop_id: loop_id.clone(),
+            project_id: project_id.clone(),
+            mistake_id: Some(issue.mistake_id),
+            file_path: rel,
+            line: issue.line,
+            issue: issue.description,
+            diff,
+            explanation,
+            status: status.to_string(),
+            error,
+            created_at: Utc::now().to_rfc3339(),
+            applied_at: None,
+        });
+    }
+
+    if proposals.is_empty() {
+        if let Some(e) = last_error {
+            return Err(format!("Failed to propose fixes: {}", e));
+        }
+    }
+
+    let db = state.db.lock().map_err(|e| format!("DB lock error: {}", e))?;
+    for proposal in &proposals {
+        insert_proposal_db(&db, proposal)?;
+    }
+    if !proposals.is_empty() {
+        events::publish(
+            &db,
+            AppEvent::activity(
+                &project_id,
+                ActivityType::Ralph,
+                &format!("Proposed {} patch(es) for RALPH loop issues", proposals.len()),
+            ),
+        );
+    }
+    Ok(proposals)
+}
+
+/// Patch proposals for a loop, newest first.
+#[tauri::command]
+pub async fn list_patch_proposals(loop_id: String, state: State<'_, AppState>) -> Result<Vec<PatchProposal>, String> {
+    let db = state.db.lock().map_err(|e| format!("DB lock error: {}", e))?;
+    list_proposals_db(&db, &loop_id)
+}
+
+/// Apply a proposal to the project's files. A proposal that no longer applies is
+/// marked "conflict" and an error is returned.
+#[tauri::command]
+pub async fn apply_patch_proposal(id: String, state: State<'_, AppState>) -> Result<PatchProposal, String> {
+    let (proposal, project_path, engine) = {
+        let db = state.db.lock().map_err(|e| format!("DB lock error: {}", e))?;
+        let proposal = load_proposal_db(&db, &id)?;
+        let project_path: String = db
+            .query_row("SELECT path FROM projects WHERE id = ?1", [&proposal.project_id], |row| row.get(0))
+            .map_err(|e| format!("Project not found: {}", e))?;
+        (proposal, project_path, patch::engine(&db))
+    };
+    if proposal.status == "applied" || proposal.status == "rejected" {
+        return Err(format!("Patch proposal is already {}", proposal.status));
+    }
+
+    let root = Path::new(&project_path);
+    let result = patch::check(engine, root, &proposal.diff).and_then(|_| patch::apply(engine, root, &proposal.diff));
+
+    let db = state.db.lock().map_err(|e| format!("DB lock error: {}", e))?;
+    match result {
+        Ok(()) => {
+            set_status_db(&db, &id, "applied", None, Some(&Utc::now().to_rfc3339()))?;
+            events::publish(
+                &db,
+                AppEvent::activity(
+                    &proposal.project_id,
+                    ActivityType::Ralph,
+                    &format!("Applied patch proposal to {}", proposal.file_path),
+                ),
+            );
+            load_proposal_db(&db, &id)
+        }
+        Err(e) => {
+            set_status_db(&db, &id, "conflict", Some(&e), None)?;
+            Err(format!("Patch no longer applies: {}", e))
+        }
+    }
+}
+
+/// Mark a proposal rejected. Applied proposals cannot be rejected.
+#[tauri::command]
+pub async fn reject_patch_proposal(id: String, state: State<'_, AppState>) -> Result<PatchProposal, String> {
+    let db = state.db.lock().map_err(|e| format!("DB lock error: {}", e))?;
+    let proposal = load_proposal_db(&db, &id)?;
+    if proposal.status == "applied" {
+        return Err("Patch proposal is already applied".to_string());
+    }
+    set_status_db(&db, &id, "rejected", None, None)?;
+    load_proposal_db(&db, &id)
+}
+
+fn fix_prompt(issue: &LocatedIssue, rel: &str, start: usize, end: usize, text: &str) -> String {
+    let mut prompt = format!("File: {}\n", rel);
+    if let Some(line) = issue.line {
+        prompt.push_str(&format!("Line: {}\n", line));
+    }
+    prompt.push_str(&format!("Issue: {}\n", issue.description));
+    if let Some(fix) = &issue.suggested_fix {
+        prompt.push_str(&format!("Suggested fix: {}\n", fix));
+    }
+    prompt.push_str(&format!("\nLines {}-{} of {}:\n```\n{}\n```", start, end, rel, text));
+    prompt
+}
+
+/// (diff, explanation) from the AI's JSON; None when it declined or the JSON is invalid.
+fn parse_fix_response(response: &str) -> Option<(String, String)> {
+    let json = response
+        .trim()
+        .trim_start_matches("```json")
+        .trim_start_matches("```")
+        .trim_end_matches("```")
+        .trim();
+    let val: serde_json::Value = serde_json::from_str(json).ok()?;
+    let diff = val.get("diff")?.as_str()?.trim();
+    if diff.is_empty() {
+        return None;
+    }
+    let explanation = val.get("explanation").and_then(|v| v.as_str()).unwrap_or("").trim();
+    Some((diff.to_string(), explanation.to_string()))
+}
+
+/// Located issues of a loop without a proposal, newest first, one per (file, line).
+fn located_issues_db(db: &Connection, loop_id: &str) -> Result<Vec<LocatedIssue>, String> {
+    let mut stmt = db
+        .prepare(
+            "SELECT id, file_path, line, description, resolution FROM ralph_mistakes
+             WHERE loop_id = ?1 AND file_path IS NOT NULL
+               AND id NOT IN (SELECT mistake_id FROM patch_proposals WHERE mistake_id IS NOT NULL)
+             ORDER BY created_at DESC",
+        )
+        .map_err(|e| format!("Failed to query issues: {}", e))?;
+    let rows: Vec<LocatedIssue> = stmt
+        .query_map([loop_id], |row| {
+            Ok(LocatedIssue {
+                mistake_id: row.get(0)?,
+                file_path: row.get(1)?,
+                line: row.get(2)?,
+                description: row.get(3)?,
+                suggested_fix: row.get(4)?,
+            })
+        })
+        .map_err(|e| format!("Failed to read issues: {}", e))?
+        .filter_map(|r| r.ok())
+        .collect();
+
+    let mut issues: Vec<LocatedIssue> = Vec::new();
+    for issue in rows {
+        if !issues.iter().any(|i| i.file_path == issue.file_path && i.line == issue.line) {
+            issues.push(issue);
+        }
+    }
+    issues.truncate(MAX_PROPOSALS_PER_RUN);
+    Ok(issues)
+}
+
+fn row_to_proposal(row: &rusqlite::Row) -> rusqlite::Result<PatchProposal> {
+    Ok(PatchProposal {
+        id: row.get(0)?,
+        loop_id: row.get(1)?,
+        project_id: row.get(2)?,
+        mistake_id: row.get(3)?,
+        file_path: row.get(4)?,
+        line: row.get(5)?,
+        issue: row.get(6)?,
+        diff: row.get(7)?,
+        explanation: row.get(8)?,
+        status: row.get(9)?,
+        error: row.get(10)?,
+        created_at: row.get(11)?,
+        applied_at: row.get(12)?,
+    })
+}
+
+fn insert_proposal_db(db: &Connection, p: &PatchProposal) -> Result<(), String> {
+    db.execute(
+        &format!(
+            "INSERT INTO patch_proposals ({}) VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7, ?8, ?9, ?10, ?11, ?12, ?13)",
+            SELECT_COLUMNS
+        ),
+        rusqlite::params![
+            p.id,
+            p.loop_id,
+            p.project_id,
+            p.mistake_id,
+            p.file_path,
+            p.line,
+            p.issue,
+            p.diff,
+            p.explanation,
+            p.status,
+            p.error,
+            p.created_at,
+            p.applied_at
+        ],
+    )
+    .map_err(|e| format!("Failed to save patch proposal: {}", e))?;
+    Ok(())
+}
+
+fn load_proposal_db(db: &Connection, id: &str) -> Result<PatchProposal, String> {
+    db.query_row(
+        &format!("SELECT {} FROM patch_proposals WHERE id = ?1", SELECT_COLUMNS),
+        [id],
+        row_to_proposal,
+    )
+    .map_err(|e| format!("Patch proposal not found: {}", e))
+}
+
+fn list_proposals_db(db: &Connection, loop_id: &str) -> Result<Vec<PatchProposal>, String> {
+    let mut stmt = db
+        .prepare(&format!(
+            "SELECT {} FROM patch_proposals WHERE loop_id = ?1 ORDER BY created_at DESC",
+            SELECT_COLUMNS
+        ))
+        .map_err(|e| format!("Failed to query patch proposals: {}", e))?;
+    let proposals = stmt
+        .query_map([loop_id], row_to_proposal)
+        .map_err(|e| format!("Failed to read patch proposals: {}", e))?
+        .filter_map(|r| r.ok())
+        .collect();
+    Ok(proposals)
+}
+
+fn set_status_db(
+    db: &Connection,
+    id: &str,
+    status: &str,
+    error: Option<&str>,
+    applied_at: Option<&str>,
+) -> Result<(), String> {
+    db.execute(
+        "UPDATE patch_proposals SET status = ?1, error = ?2, applied_at = ?3 WHERE id = ?4",
+        rusqlite::params![status, error, applied_at, id],
+    )
+    .map_err(|e| format!("Failed to update patch proposal: {}", e))?;
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::db::schema;
+    use crate::test_support::with_project;
+
+    fn setup() -> Connection {
+        let conn = with_project();
+        schema::migrate_add_mistake_location(&conn).unwrap();
+        conn.execute(
+            "INSERT INTO ralph_loops (id, project_id, prompt, status, created_at) VALUES ('loop1', 'p1', 'Fix', 'completed', '2025-01-01T00:00:00Z')",
+            [],
+        )
+        .unwrap();
+        let mistakes = [
+            ("m1", Some("src/a.rs"), Some(3), "2025-01-01T00:00:01Z"),
+            ("m2", Some("src/a.rs"), Some(3), "2025-01-01T00:00:02Z"),
+            ("m3", Some("src/b.rs"), None, "2025-01-01T00:00:03Z"),
+            ("m4", None, None, "2025-01-01T00:00:04Z"),
+        ];
+        for (id, file, line, at) in mistakes {
+            conn.execute(
+                "INSERT INTO ralph_mistakes (id, project_id, loop_id, mistake_type, description, created_at, file_path, line)
+                 VALUES (?1, 'p1', 'loop1', 'error', 'issue ' || ?1, ?2, ?3, ?4)",
+                rusqlite::params![id, at, file, line],
+            )
+            .unwrap();
+        }
+        conn
+    }
+
+    fn proposal(id: &str, mistake_id: &str) -> PatchProposal {
+        PatchProposal {
+            id: id.to_string(),
+            loop_id: "loop1".to_string(),
+            project_id: "p1".to_string(),
+            mistake_id: Some(mistake_id.to_string()),
+            file_path: "src/b.rs".to_string(),
+            line: None,
+            issue: "issue".to_string(),
+            diff: "--- a/src/b.rs\n+++ b/src/b.rs\n@@ -1 +1 @@\n-a\n+b\n".to_string(),
+            explanation: "Fix".to_string(),
+            status: "pending".to_string(),
+            error: None,
+            created_at: "2025-01-02T00:00:00Z".to_string(),
+            applied_at: None,
+        }
+    }
+
+    #[test]
+    fn test_located_issues_skip_unlocated_duplicates_and_proposed() {
+        let conn = setup();
+        let ids = |issues: Vec<LocatedIssue>| issues.into_iter().map(|i| i.mistake_id).collect::<Vec<_>>();
+        assert_eq!(ids(located_issues_db(&conn, "loop1").unwrap()), vec!["m3", "m2"]);
+
+        insert_proposal_db(&conn, &proposal("pp1", "m3")).unwrap();
+        assert_eq!(ids(located_issues_db(&conn, "loop1").unwrap()), vec!["m2"]);
+    }
+
+    #[test]
+    fn test_proposal_status_roundtrip() {
+        let conn = setup();
+        insert_proposal_db(&conn, &proposal("pp1", "m3")).unwrap();
+        set_status_db(&conn, "pp1", "applied", None, Some("2025-01-03T00:00:00Z")).unwrap();
+
+        let listed = list_proposals_db(&conn, "loop1").unwrap();
+        assert_eq!(listed.len(), 1);
+        assert_eq!(listed[0].status, "applied");
+        assert_eq!(listed[0].applied_at.as_deref(), Some("2025-01-03T00:00:00Z"));
+        assert!(load_proposal_db(&conn, "missing").is_err());
+    }
+
+    #[test]
+    fn test_parse_fix_response() {
+        let response = "```json\n{\"diff\": \"--- a/x\\n+++ b/x\\n\", \"explanation\": \"Rename\"}\n```";
+        assert_eq!(
+            parse_fix_response(response),
+            Some(("--- a/x\n+++ b/x".to_string(), "Rename".to_string()))
+        );
+        assert_eq!(parse_fix_response("{\"diff\": \"\", \"explanation\": \"Needs a new file\"}"), None);
+        assert_eq!(parse_fix_response("not json"), None);
+    }
+}
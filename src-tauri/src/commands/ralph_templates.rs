@@ -0,0 +1,446 @@
+//! @module commands/ralph_templates
+//! @description Tauri IPC commands for reusable RALPH loop templates
+//!
+//! PURPOSE:
+//! - List, create, update, and delete RALPH loop templates
+//! - Instantiate a template into a running iterative loop with variable substitution
+//!
+//! DEPENDENCIES:
+//! - tauri - Command macro, State, AppHandle
+//! - db::AppState - Database connection state
+//! - commands::ralph - spawn_iterative_loop, LoopOptions, validate_max_duration, prompt heuristics
+//! - core::slash_commands - command_name (kebab-case slug for branch names)
+//! - core::command_guard - Gate syntax check (gates need project approval before running)
+//! - models::ralph - RalphTemplate, RalphLoop types
+//!
+//! EXPORTS:
+//! - list_ralph_templates - Templates for a project plus global templates
+//! - create_ralph_template - Create a template
+//! - update_ralph_template - Update a template
+//! - delete_ralph_template - Delete a template by ID
+//! - start_ralph_loop_from_template - Render the prompt and start an iterative loop
+//!
+//! PATTERNS:
+//! - Placeholders are {{name}} (letters, digits, _ and -; surrounding spaces allowed)
+//! - Every placeholder must be supplied; unknown vars are rejected to catch typos
+//! - allowed_tools / acceptance_gates are stored as JSON arrays
+//! - "new_branch" loops run on ralph/<template-slug>-<timestamp>
+//!
+//! CLAUDE NOTES:
+//! - Templates are scoped to a project_id or global (None), like team templates
+//! - Loop settings are stored on the loop (ralph_loops.loop_options) so resume keeps them
+//! - Empty allowed_tools means the loop default (Read,Write,Edit,Bash,Glob,Grep)
+
+use std::collections::HashMap;
+
+use chrono::Utc;
+use rusqlite::Connection;
+use tauri::{AppHandle, State};
+use uuid::Uuid;
+
+use crate::commands::ralph::{self, LoopOptions, DEFAULT_ALLOWED_TOOLS};
+use crate::core::command_guard;
+use crate::core::slash_commands;
+use crate::core::events::{self, AppEvent};
+use crate::db::AppState;
+use crate::models::activity::ActivityType;
+use crate::models::ralph::{RalphLoop, RalphTemplate};
+
+/// Upper bound on a template's iteration budget.
+const MAX_TEMPLATE_ITERATIONS: u32 = 10;
+
+const BRANCH_STRATEGIES: &[&str] = &["current", "new_branch"];
+
+const SELECT_COLUMNS: &str = "id, project_id, name, description, prompt_skeleton, allowed_tools, acceptance_gates,
+                              max_iterations, branch_strategy, usage_count, created_at, updated_at";
+
+/// List templates for a project (including global ones), or only global templates when project_id is None.
+#[tauri::command]
+pub async fn list_ralph_templates(
+    project_id: Option<String>,
+    state: State<'_, AppState>,
+) -> Result<Vec<RalphTemplate>, String> {
+    let db = state.db.lock().map_err(|e| format!("DB lock error: {}", e))?;
+
+    let sql = format!(
+        "SELECT {} FROM ralph_templates WHERE project_id = ?1 OR project_id IS NULL ORDER BY usage_count DESC, name",
+        SELECT_COLUMNS
+    );
+    let mut stmt = db
+        .prepare(&sql)
+        .map_err(|e| format!("Failed to query RALPH templates: {}", e))?;
+    let templates = stmt
+        .query_map([project_id], map_template_row)
+        .map_err(|e| format!("Failed to read RALPH templates: {}", e))?
+        .filter_map(|r| r.ok())
+        .collect();
+
+    Ok(templates)
+}
+
+/// Create a new RALPH loop template.
+#[tauri::command]
+#[allow(clippy::too_many_arguments)]
+pub async fn create_ralph_template(
+    name: String,
+    description: String,
+    prompt_skeleton: String,
+    allowed_tools: Vec<String>,
+    acceptance_gates: Vec<String>,
+    max_iterations: u32,
+    branch_strategy: String,
+    project_id: Option<String>,
+    state: State<'_, AppState>,
+) -> Result<RalphTemplate, String> {
+    let (allowed_tools, acceptance_gates) =
+        validate_template(&name, &prompt_skeleton, allowed_tools, acceptance_gates, max_iterations, &branch_strategy)?;
+
+    let db = state.db.lock().map_err(|e| format!("DB lock error: {}", e))?;
+    let id = Uuid::new_v4().to_string();
+    let now = Utc::now().to_rfc3339();
+
+    db.execute(
+        "INSERT INTO ralph_templates (id, project_id, name, description, prompt_skeleton, allowed_tools, acceptance_gates,
+                                      max_iterations, branch_strategy, usage_count, created_at, updated_at)
+         VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7, ?8, ?9, 0, ?10, ?10)",
+        rusqlite::params![
+            id,
+            project_id,
+            name.trim(),
+            description,
+            prompt_skeleton,
+            serde_json::to_string(&allowed_tools).unwrap_or_else(|_| "[]".to_string()),
+            serde_json::to_string(&acceptance_gates).unwrap_or_else(|_| "[]".to_string()),
+            max_iterations,
+            branch_strategy,
+            now
+        ],
+    )
+    .map_err(|e| format!("Failed to insert RALPH template: {}", e))?;
+
+    if let Some(ref pid) = project_id {
+        events::publish(&db, AppEvent::activity(pid, ActivityType::Ralph, &format!("Created RALPH template: {}", name.trim())));
+    }
+
+    load_template(&db, &id)
+}
+
+/// Update an existing RALPH loop template.
+#[tauri::command]
+#[allow(clippy::too_many_arguments)]
+pub async fn update_ralph_template(
+    id: String,
+    name: String,
+    description: String,
+    prompt_skeleton: String,
+    allowed_tools: Vec<String>,
+    acceptance_gates: Vec<String>,
+    max_iterations: u32,
+    branch_strategy: String,
+    state: State<'_, AppState>,
+) -> Result<RalphTemplate, String> {
+    let (allowed_tools, acceptance_gates) =
+        validate_template(&name, &prompt_skeleton, allowed_tools, acceptance_gates, max_iterations, &branch_strategy)?;
+
+    let db = state.db.lock().map_err(|e| format!("DB lock error: {}", e))?;
+    let rows_affected = db
+        .execute(
+            "UPDATE ralph_templates SET name = ?1, description = ?2, prompt_skeleton = ?3, allowed_tools = ?4,
+             acceptance_gates = ?5, max_iterations = ?6, branch_strategy = ?7, updated_at = ?8 WHERE id = ?9",
+            rusqlite::params![
+                name.trim(),
+                description,
+                prompt_skeleton,
+                serde_json::to_string(&allowed_tools).unwrap_or_else(|_| "[]".to_string()),
+                serde_json::to_string(&acceptance_gates).unwrap_or_else(|_| "[]".to_string()),
+                max_iterations,
+                branch_strategy,
+                Utc::now().to_rfc3339(),
+                id
+            ],
+        )
+        .map_err(|e| format!("Failed to update RALPH template: {}", e))?;
+
+    if rows_affected == 0 {
+        return Err(format!("RALPH template not found: {}", id));
+    }
+
+    load_template(&db, &id)
+}
+
+/// Delete a RALPH loop template by ID.
+#[tauri::command]
+pub async fn delete_ralph_template(id: String, state: State<'_, AppState>) -> Result<(), String> {
+    let db = state.db.lock().map_err(|e| format!("DB lock error: {}", e))?;
+    let template = load_template(&db, &id)?;
+
+    db.execute("DELETE FROM ralph_templates WHERE id = ?1", [&id])
+        .map_err(|e| format!("Failed to delete RALPH template: {}", e))?;
+
+    if let Some(pid) = template.project_id {
+        events::publish(&db, AppEvent::activity(&pid, ActivityType::Ralph, &format!("Deleted RALPH template: {}", template.name)));
+    }
+
+    Ok(())
+}
+
+/// Render a template with `vars` and start an iterative loop with its tools,
+/// acceptance gates, iteration budget, and branch strategy. project_id
+/// defaults to the template's project (required for global templates).
+#[tauri::command]
+pub async fn start_ralph_loop_from_template(
+    template_id: String,
+    vars: HashMap<String, String>,
+    project_id: Option<String>,
+    max_duration_minutes: Option<u32>,
+    app: AppHandle,
+    state: State<'_, AppState>,
+) -> Result<RalphLoop, String> {
+    let max_duration_minutes = ralph::validate_max_duration(max_duration_minutes)?;
+    let (template, criteria) = {
+        let db = state.db.lock().map_err(|e| format!("DB lock error: {}", e))?;
+        (load_template(&db, &template_id)?, ralph::load_prompt_criteria(&db))
+    };
+    let project_id = project_id
+        .or_else(|| template.project_id.clone())
+        .ok_or("A project is required to start a loop from a global template")?;
+
+    let prompt = render_prompt(&template.prompt_skeleton, &vars)?;
+    let quality_score = ralph::analyze_prompt_heuristic(&prompt, &criteria).quality_score;
+    let options = LoopOptions {
+        max_duration_minutes,
+        ..loop_options(&template, &Utc::now().format("%Y%m%d-%H%M%S").to_string())
+    };
+
+    let started = ralph::spawn_iterative_loop(
+        &state,
+        app,
+        project_id,
+        prompt,
+        None,
+        quality_score,
+        options,
+        &format!("Started RALPH loop from template: {}", template.name),
+    )?;
+
+    let db = state.db.lock().map_err(|e| format!("DB lock error: {}", e))?;
+    let _ = db.execute(
+        "UPDATE ralph_templates SET usage_count = usage_count + 1 WHERE id = ?1",
+        [&template_id],
+    );
+
+    Ok(started)
+}
+
+/// Placeholder names in a skeleton, in order of first use.
+pub fn template_variables(skeleton: &str) -> Vec<String> {
+    let mut names = Vec::new();
+    let mut rest = skeleton;
+    while let Some(start) = rest.find("{{") {
+        let after = &rest[start + 2..];
+        let Some(end) = after.find("}}") else {
+            break;
+        };
+        let name = after[..end].trim();
+        if is_variable_name(name) && !names.iter().any(|n| n == name) {
+            names.push(name.to_string());
+        }
+        rest = &after[end + 2..];
+    }
+    names
+}
+
+fn is_variable_name(name: &str) -> bool {
+    !name.is_empty() && name.chars().all(|c| c.is_ascii_alphanumeric() || c == '_' || c == '-')
+}
+
+/// Substitute every {{name}} in the skeleton. All placeholders must be
+/// supplied and every supplied var must be used.
+pub fn render_prompt(skeleton: &str, vars: &HashMap<String, String>) -> Result<String, String> {
+    let variables = template_variables(skeleton);
+
+    let mut missing: Vec<&str> = variables
+        .iter()
+        .filter(|v| vars.get(*v).map(|s| s.trim().is_empty()).unwrap_or(true))
+        .map(|v| v.as_str())
+        .collect();
+    if !missing.is_empty() {
+        missing.sort();
+        return Err(format!("Missing template variables: {}", missing.join(", ")));
+    }
+    let mut unknown: Vec<&str> = vars.keys().filter(|k| !variables.contains(k)).map(|k| k.as_str()).collect();
+    if !unknown.is_empty() {
+        unknown.sort();
+        return Err(format!("Template does not use: {}", unknown.join(", ")));
+    }
+
+    let mut out = String::with_capacity(skeleton.len());
+    let mut rest = skeleton;
+    while let Some(start) = rest.find("{{") {
+        let after = &rest[start + 2..];
+        let Some(end) = after.find("}}") else {
+            break;
+        };
+        out.push_str(&rest[..start]);
+        match vars.get(after[..end].trim()) {
+            Some(value) => out.push_str(value.trim()),
+            None => out.push_str(&rest[start..start + end + 4]),
+        }
+        rest = &after[end + 2..];
+    }
+    out.push_str(rest);
+    Ok(out)
+}
+
+/// Loop settings for a template. `stamp` makes new branch names unique.
+fn loop_options(template: &RalphTemplate, stamp: &str) -> LoopOptions {
+    LoopOptions {
+        allowed_tools: if template.allowed_tools.is_empty() {
+            DEFAULT_ALLOWED_TOOLS.to_string()
+        } else {
+            template.allowed_tools.join(",")
+        },
+        max_iterations: template.max_iterations,
+        acceptance_gates: template.acceptance_gates.clone(),
+        branch: (template.branch_strategy == "new_branch")
+            .then(|| format!("ralph/{}-{}", slash_commands::command_name(&template.name), stamp)),
+        ..LoopOptions::default()
+    }
+}
+
+/// Validate template fields; returns trimmed, non-empty tools and gates.
+fn validate_template(
+    name: &str,
+    prompt_skeleton: &str,
+    allowed_tools: Vec<String>,
+    acceptance_gates: Vec<String>,
+    max_iterations: u32,
+    branch_strategy: &str,
+) -> Result<(Vec<String>, Vec<String>), String> {
+    if name.trim().is_empty() {
+        return Err("Template name is required".to_string());
+    }
+    if prompt_skeleton.trim().is_empty() {
+        return Err("Prompt skeleton is required".to_string());
+    }
+    if !(1..=MAX_TEMPLATE_ITERATIONS).contains(&max_iterations) {
+        return Err(format!("max_iterations must be between 1 and {}", MAX_TEMPLATE_ITERATIONS));
+    }
+    if !BRANCH_STRATEGIES.contains(&branch_strategy) {
+        return Err(format!(
+            "Unknown branch strategy '{}'; expected {}",
+            branch_strategy,
+            BRANCH_STRATEGIES.join(" or ")
+        ));
+    }
+
+    let clean = |items: Vec<String>| -> Vec<String> {
+        items.into_iter().map(|s| s.trim().to_string()).filter(|s| !s.is_empty()).collect()
+    };
+    let allowed_tools = clean(allowed_tools);
+    if let Some(bad) = allowed_tools.iter().find(|t| t.contains(',')) {
+        return Err(format!("Tool names cannot contain commas: {}", bad));
+    }
+    let acceptance_gates = clean(acceptance_gates);
+    for gate in &acceptance_gates {
+        command_guard::parse_command(gate)?;
+    }
+    Ok((allowed_tools, acceptance_gates))
+}
+
+fn load_template(db: &Connection, id: &str) -> Result<RalphTemplate, String> {
+    db.query_row(
+        &format!("SELECT {} FROM ralph_templates WHERE id = ?1", SELECT_COLUMNS),
+        [id],
+        map_template_row,
+    )
+    .map_err(|_| format!("RALPH template not found: {}", id))
+}
+
+fn map_template_row(row: &rusqlite::Row) -> rusqlite::Result<RalphTemplate> {
+    let prompt_skeleton: String = row.get(4)?;
+    let allowed_tools: String = row.get(5)?;
+    let acceptance_gates: String = row.get(6)?;
+    Ok(RalphTemplate {
+        id: row.get(0)?,
+        project_id: row.get(1)?,
+        name: row.get(2)?,
+        description: row.get(3)?,
+        variables: template_variables(&prompt_skeleton),
+        prompt_skeleton,
+        allowed_tools: serde_json::from_str(&allowed_tools).unwrap_or_default(),
+        acceptance_gates: serde_json::from_str(&acceptance_gates).unwrap_or_default(),
+        max_iterations: row.get(7)?,
+        branch_strategy: row.get(8)?,
+        usage_count: row.get(9)?,
+        created_at: row.get(10)?,
+        updated_at: row.get(11)?,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn vars(pairs: &[(&str, &str)]) -> HashMap<String, String> {
+        pairs.iter().map(|(k, v)| (k.to_string(), v.to_string())).collect()
+    }
+
+    #[test]
+    fn test_template_variables_and_render() {
+        let skeleton = "Fix {{ bug }} in {{file}}. Then re-check {{bug}}. Keep {{not a var}}.";
+        assert_eq!(template_variables(skeleton), vec!["bug", "file"]);
+
+        let rendered = render_prompt(skeleton, &vars(&[("bug", "the crash"), ("file", "src/a.rs")])).unwrap();
+        assert_eq!(rendered, "Fix the crash in src/a.rs. Then re-check the crash. Keep {{not a var}}.");
+
+        assert_eq!(
+            render_prompt(skeleton, &vars(&[("bug", "x")])).unwrap_err(),
+            "Missing template variables: file"
+        );
+        assert!(render_prompt(skeleton, &vars(&[("bug", "x"), ("file", "y"), ("fiel", "z")]))
+            .unwrap_err()
+            .contains("fiel"));
+    }
+
+    #[test]
+    fn test_validate_and_loop_options() {
+        assert!(validate_template("", "p", vec![], vec![], 5, "current").is_err());
+        assert!(validate_template("n", "p", vec![], vec![], 0, "current").is_err());
+        assert!(validate_template("n", "p", vec![], vec![], 5, "rebase").is_err());
+        assert!(validate_template("n", "p", vec!["Read,Write".to_string()], vec![], 5, "current").is_err());
+        assert!(validate_template("n", "p", vec![], vec!["cargo test && rm -rf .".to_string()], 5, "current").is_err());
+
+        let (tools, gates) = validate_template(
+            "n",
+            "p",
+            vec![" Read ".to_string(), "".to_string()],
+            vec!["cargo test".to_string(), " ".to_string()],
+            3,
+            "new_branch",
+        )
+        .unwrap();
+        assert_eq!(tools, vec!["Read"]);
+        assert_eq!(gates, vec!["cargo test"]);
+
+        let template = RalphTemplate {
+            id: "t1".to_string(),
+            project_id: None,
+            name: "Add Tests".to_string(),
+            description: String::new(),
+            prompt_skeleton: "p".to_string(),
+            variables: vec![],
+            allowed_tools: vec![],
+            acceptance_gates: gates,
+            max_iterations: 3,
+            branch_strategy: "new_branch".to_string(),
+            usage_count: 0,
+            created_at: String::new(),
+            updated_at: String::new(),
+        };
+        let options = loop_options(&template, "20250101-000000");
+        assert_eq!(options.allowed_tools, DEFAULT_ALLOWED_TOOLS);
+        assert_eq!(options.branch.as_deref(), Some("ralph/add-tests-20250101-000000"));
+        assert_eq!(options.max_iterations, 3);
+    }
+}
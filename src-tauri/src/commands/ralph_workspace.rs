@@ -0,0 +1,575 @@
+//! @module commands/ralph_workspace
+//! @description Tauri IPC commands for workspace RALPH runs: one PRD across several registered projects
+//!
+//! PURPOSE:
+//! - Start a PRD whose stories name the registered project they apply to
+//! - Run the stories one project step at a time, each step as a PRD loop in that project
+//! - Pass a summary of what earlier steps changed into the story prompts of later steps
+//! - List, inspect, and cancel workspace runs
+//!
+//! DEPENDENCIES:
+//! - tauri - Command macro, State, AppHandle
+//! - db::AppState - Database connection (projects, ralph_workspace_runs)
+//! - commands::ralph - insert_prd_loop / execute_ralph_loop_prd (one loop per step), prd_story_order,
+//!   load_ralph_iterations (files a step changed), open_db_connection
+//! - core::events - Activity events per started and finished run
+//! - models::ralph - PrdFile, PrdStory, WorkspaceRun, WorkspaceRunStep
+//!
+//! EXPORTS:
+//! - start_workspace_prd - Validate a workspace PRD, store the run, and execute it in the background
+//! - get_workspace_run - A workspace run with its steps
+//! - list_workspace_runs - Recent workspace runs (newest first)
+//! - cancel_workspace_run - Stop a run after its current step
+//! - plan_workspace_steps - Resolve each story's project and group the ordered stories into steps
+//! - step_summary - What a finished step changed, as passed to later steps
+//! - cross_repo_context - Context block for a step from the summaries of the steps before it
+//!
+//! PATTERNS:
+//! - Stories run in prd_story_order over the whole PRD; consecutive stories of the same project
+//!   form one step, so a project can appear in several steps (A, B, then A again)
+//! - Dependencies on stories of earlier steps are satisfied by the order and dropped from the
+//!   step's PRD (the PRD loop rejects ids it does not know)
+//! - A step fails when its loop fails or any of its stories failed or was blocked ("✗"/"⊘"
+//!   outcome lines); the run stops and later steps are "skipped", since they may build on it
+//! - Runs are stored in ralph_workspace_runs with steps as JSON WorkspaceRunStep
+//!
+//! CLAUDE NOTES:
+//! - Every step's typecheck/test commands must be approved in its project before the run starts
+//! - Step loops are ordinary PRD loops: they show up in each project's loop history and emit the
+//!   usual progress events; the run waits (polling) until the step loop is completed or failed
+//! - Cancel takes effect between steps; kill_ralph_loop stops the step that is running
+//! - cross_repo_context keeps the last MAX_CONTEXT_STEPS summaries so prompts stay bounded
+
+use std::collections::HashSet;
+
+use chrono::Utc;
+use rusqlite::Connection;
+use tauri::{AppHandle, State};
+
+use crate::commands::ralph;
+use crate::core::command_guard;
+use crate::core::events::{self, AppEvent};
+use crate::db::AppState;
+use crate::models::activity::ActivityType;
+use crate::models::ralph::{PrdFile, WorkspaceRun, WorkspaceRunStep};
+
+/// Summaries of earlier steps included in a step's prompts
+const MAX_CONTEXT_STEPS: usize = 5;
+
+/// Changed files listed per step summary
+const MAX_SUMMARY_FILES: usize = 20;
+
+/// How often a run checks whether a paused or resumed step loop has finished
+const STEP_POLL_SECS: u64 = 5;
+
+const RUN_COLUMNS: &str = "id, name, status, current_step, steps, outcome, started_at, completed_at";
+
+/// A step of a workspace run before it is stored: the project and its slice of the PRD.
+#[derive(Debug, Clone)]
+pub struct PlannedStep {
+    pub project_id: String,
+    pub project_name: String,
+    pub project_path: String,
+    pub prd: PrdFile,
+}
+
+/// Start a workspace PRD. Every story must name a registered project (id or name) in
+/// `project`. Returns the stored run; steps execute in the background.
+#[tauri::command]
+pub async fn start_workspace_prd(
+    prd_json: String,
+    app: AppHandle,
+    state: State<'_, AppState>,
+) -> Result<WorkspaceRun, String> {
+    let prd: PrdFile = serde_json::from_str(&prd_json).map_err(|e| format!("Invalid PRD JSON: {}", e))?;
+    if prd.stories.is_empty() {
+        return Err("PRD must contain at least one story".to_string());
+    }
+
+    let run = {
+        let db = state.db.lock().map_err(|e| format!("DB lock error: {}", e))?;
+        let projects = registered_projects(&db)?;
+        let planned = plan_workspace_steps(&prd, &projects)?;
+
+        // Check approvals up front so a run does not stop halfway on an unapproved command
+        let commands: Vec<String> =
+            [&prd.typecheck_command, &prd.test_command].into_iter().flatten().cloned().collect();
+        for step in &planned {
+            command_guard::require_approved(&db, &step.project_id, &step.project_path, &commands, "prd")?;
+        }
+
+        let now = Utc::now().to_rfc3339();
+        let run = WorkspaceRun {
+            id: uuid::Uuid::new_v4().to_string(),
+            name: prd.name.clone(),
+            status: "running".to_string(),
+            current_step: 0,
+            steps: planned
+                .iter()
+                .map(|step| WorkspaceRunStep {
+                    project_id: step.project_id.clone(),
+                    project_name: step.project_name.clone(),
+                    story_ids: step.prd.stories.iter().map(|s| s.id.clone()).collect(),
+                    loop_id: None,
+                    status: "pending".to_string(),
+                    summary: None,
+                })
+                .collect(),
+            outcome: None,
+            started_at: now.clone(),
+            completed_at: None,
+        };
+        db.execute(
+            "INSERT INTO ralph_workspace_runs (id, name, prd_json, status, current_step, steps, started_at, created_at)
+             VALUES (?1, ?2, ?3, 'running', 0, ?4, ?5, ?5)",
+            rusqlite::params![run.id, run.name, prd_json, steps_json(&run.steps), now],
+        )
+        .map_err(|e| format!("Failed to create workspace run: {}", e))?;
+
+        let projects_in_run: HashSet<&str> = planned.iter().map(|s| s.project_id.as_str()).collect();
+        for project_id in projects_in_run {
+            events::publish(
+                &db,
+                AppEvent::activity(
+                    project_id,
+                    ActivityType::Ralph,
+                    &format!("Started workspace RALPH run: {} ({} steps)", prd.name, planned.len()),
+                ),
+            );
+        }
+        tokio::spawn(execute_workspace_run(app, run.clone(), planned));
+        run
+    };
+
+    Ok(run)
+}
+
+/// Get a workspace run with its steps.
+#[tauri::command]
+pub async fn get_workspace_run(run_id: String, state: State<'_, AppState>) -> Result<WorkspaceRun, String> {
+    let db = state.db.lock().map_err(|e| format!("DB lock error: {}", e))?;
+    load_run(&db, &run_id)
+}
+
+/// List the most recent workspace runs, newest first.
+#[tauri::command]
+pub async fn list_workspace_runs(
+    limit: Option<u32>,
+    state: State<'_, AppState>,
+) -> Result<Vec<WorkspaceRun>, String> {
+    let db = state.db.lock().map_err(|e| format!("DB lock error: {}", e))?;
+    let mut stmt = db
+        .prepare(&format!(
+            "SELECT {} FROM ralph_workspace_runs ORDER BY created_at DESC LIMIT ?1",
+            RUN_COLUMNS
+        ))
+        .map_err(|e| format!("Failed to query workspace runs: {}", e))?;
+    let runs = stmt
+        .query_map([limit.unwrap_or(50)], map_run_row)
+        .map_err(|e| format!("Failed to read workspace runs: {}", e))?
+        .filter_map(|r| r.ok())
+        .collect();
+    Ok(runs)
+}
+
+/// Cancel a running workspace run. The step that is running finishes (or can be killed
+/// with kill_ralph_loop); no further steps start.
+#[tauri::command]
+pub async fn cancel_workspace_run(run_id: String, state: State<'_, AppState>) -> Result<WorkspaceRun, String> {
+    let db = state.db.lock().map_err(|e| format!("DB lock error: {}", e))?;
+    let updated = db
+        .execute(
+            "UPDATE ralph_workspace_runs SET status = 'cancelled' WHERE id = ?1 AND status = 'running'",
+            [&run_id],
+        )
+        .map_err(|e| format!("Failed to cancel workspace run: {}", e))?;
+    if updated == 0 {
+        return Err("Workspace run not found or already finished.".to_string());
+    }
+    load_run(&db, &run_id)
+}
+
+/// Registered projects as (id, name, path).
+fn registered_projects(db: &Connection) -> Result<Vec<(String, String, String)>, String> {
+    let mut stmt = db
+        .prepare("SELECT id, name, path FROM projects")
+        .map_err(|e| format!("Failed to query projects: {}", e))?;
+    let projects = stmt
+        .query_map([], |row| Ok((row.get(0)?, row.get(1)?, row.get(2)?)))
+        .map_err(|e| format!("Failed to read projects: {}", e))?
+        .filter_map(|r| r.ok())
+        .collect();
+    Ok(projects)
+}
+
+/// Resolve every story's project (by id, then case-insensitive name) and split the stories,
+/// in dependency order, into steps of consecutive stories sharing a project. Each step gets
+/// a copy of the PRD with only its stories and without dependencies on other steps.
+pub fn plan_workspace_steps(prd: &PrdFile, projects: &[(String, String, String)]) -> Result<Vec<PlannedStep>, String> {
+    let mut resolved: Vec<&(String, String, String)> = Vec::with_capacity(prd.stories.len());
+    let mut unassigned = Vec::new();
+    for story in &prd.stories {
+        let Some(wanted) = story.project.as_deref().map(str::trim).filter(|p| !p.is_empty()) else {
+            unassigned.push(story.id.clone());
+            continue;
+        };
+        let by_id = projects.iter().find(|(id, _, _)| id == wanted);
+        let by_name: Vec<_> = projects.iter().filter(|(_, name, _)| name.eq_ignore_ascii_case(wanted)).collect();
+        let project = match (by_id, by_name.as_slice()) {
+            (Some(project), _) => project,
+            (None, [project]) => *project,
+            (None, []) => return Err(format!("Story \"{}\": no registered project \"{}\"", story.id, wanted)),
+            (None, _) => {
+                return Err(format!(
+                    "Story \"{}\": several projects are named \"{}\"; use the project id",
+                    story.id, wanted
+                ))
+            }
+        };
+        resolved.push(project);
+    }
+    if !unassigned.is_empty() {
+        return Err(format!("Stories without a project: {}", unassigned.join(", ")));
+    }
+
+    let order = ralph::prd_story_order(&prd.stories)?;
+    let mut steps: Vec<PlannedStep> = Vec::new();
+    for index in order {
+        let (project_id, project_name, project_path) = resolved[index];
+        if steps.last().map(|s| &s.project_id) != Some(project_id) {
+            steps.push(PlannedStep {
+                project_id: project_id.clone(),
+                project_name: project_name.clone(),
+                project_path: project_path.clone(),
+                prd: PrdFile { stories: Vec::new(), cross_repo_context: None, ..prd.clone() },
+            });
+        }
+        if let Some(step) = steps.last_mut() {
+            step.prd.stories.push(prd.stories[index].clone());
+        }
+    }
+    for step in &mut steps {
+        let ids: HashSet<String> = step.prd.stories.iter().map(|s| s.id.clone()).collect();
+        for story in &mut step.prd.stories {
+            story.depends_on.retain(|dep| ids.contains(dep));
+        }
+    }
+    Ok(steps)
+}
+
+/// What a finished step changed: its committed stories (from the loop outcome) and the files
+/// its iterations touched.
+pub fn step_summary(project_name: &str, outcome: &str, files_changed: &[String]) -> String {
+    let mut summary = format!("Repository {}:\n", project_name);
+    for line in outcome.lines().map(str::trim).filter(|l| l.starts_with('✓') || l.starts_with('↺')) {
+        summary.push_str(&format!("- {}\n", line.trim_start_matches(['✓', '↺']).trim()));
+    }
+    if !files_changed.is_empty() {
+        let shown: Vec<&str> = files_changed.iter().take(MAX_SUMMARY_FILES).map(String::as_str).collect();
+        summary.push_str(&format!("Files changed: {}", shown.join(", ")));
+        if files_changed.len() > MAX_SUMMARY_FILES {
+            summary.push_str(&format!(" and {} more", files_changed.len() - MAX_SUMMARY_FILES));
+        }
+        summary.push('\n');
+    }
+    summary.trim_end().to_string()
+}
+
+/// Context block for a step: the summaries of the last MAX_CONTEXT_STEPS steps before it.
+pub fn cross_repo_context(summaries: &[String]) -> Option<String> {
+    if summaries.is_empty() {
+        return None;
+    }
+    let recent = &summaries[summaries.len().saturating_sub(MAX_CONTEXT_STEPS)..];
+    let mut context = String::from(
+        "This task is part of a change spanning several repositories. Earlier steps already made these changes; \
+         build on them and keep names and interfaces consistent:\n\n",
+    );
+    context.push_str(&recent.join("\n\n"));
+    Some(context)
+}
+
+/// Whether a finished step loop completed all of its stories.
+fn step_succeeded(loop_status: &str, outcome: &str) -> bool {
+    loop_status == "completed"
+        && !outcome.lines().map(str::trim).any(|l| l.starts_with("✗ Story") || l.starts_with("⊘ Story"))
+}
+
+/// Run the steps one after another, each as a PRD loop, passing summaries forward.
+async fn execute_workspace_run(app: AppHandle, mut run: WorkspaceRun, planned: Vec<PlannedStep>) {
+    let db = match ralph::open_db_connection() {
+        Ok(conn) => conn,
+        Err(e) => {
+            tracing::error!(error = %e, "Workspace RALPH: Failed to open database connection");
+            return;
+        }
+    };
+
+    let mut summaries: Vec<String> = Vec::new();
+    let mut failure: Option<String> = None;
+    for (index, step) in planned.into_iter().enumerate() {
+        if failure.is_some() || run_status(&db, &run.id).as_deref() != Some("running") {
+            run.steps[index].status = "skipped".to_string();
+            continue;
+        }
+        run.current_step = index as u32;
+        run.steps[index].status = "running".to_string();
+
+        let mut prd = step.prd;
+        prd.cross_repo_context = cross_repo_context(&summaries);
+        let started = serde_json::to_string(&prd).map_err(|e| e.to_string()).and_then(|prd_json| {
+            let prompt_summary = format!(
+                "Workspace PRD: {} (step {} of {}, {} stories)\n{}",
+                run.name,
+                index + 1,
+                run.steps.len(),
+                prd.stories.len(),
+                prd.description.as_deref().unwrap_or("No description")
+            );
+            let activity = format!("Started workspace RALPH step {}: {}", index + 1, run.name);
+            ralph::insert_prd_loop(&db, &step.project_id, &prd, prd_json, prompt_summary, None, &activity)
+        });
+        let (step_loop, project_path) = match started {
+            Ok(started) => started,
+            Err(e) => {
+                run.steps[index].status = "failed".to_string();
+                failure = Some(format!("Step {} ({}) could not start: {}", index + 1, step.project_name, e));
+                continue;
+            }
+        };
+        run.steps[index].loop_id = Some(step_loop.id.clone());
+        save_run(&db, &run);
+
+        ralph::execute_ralph_loop_prd(app.clone(), step_loop.id.clone(), step.project_id.clone(), project_path, prd)
+            .await;
+        // The connection is not Sync, so no borrow of it may be held across an await
+        let (status, outcome) = loop {
+            if let Some(finished) = finished_loop(&db, &run.id, &step_loop.id) {
+                break finished;
+            }
+            tokio::time::sleep(std::time::Duration::from_secs(STEP_POLL_SECS)).await;
+        };
+
+        let files: Vec<String> = ralph::load_ralph_iterations(&db, &step_loop.id)
+            .unwrap_or_default()
+            .into_iter()
+            .flat_map(|iteration| iteration.files_changed)
+            .map(|file| file.path)
+            .fold(Vec::new(), |mut files, path| {
+                if !files.contains(&path) {
+                    files.push(path);
+                }
+                files
+            });
+        let summary = step_summary(&step.project_name, &outcome, &files);
+        run.steps[index].summary = Some(summary.clone());
+        summaries.push(summary);
+
+        if step_succeeded(&status, &outcome) {
+            run.steps[index].status = "completed".to_string();
+        } else {
+            run.steps[index].status = "failed".to_string();
+            failure = Some(format!("Step {} ({}) did not complete all its stories", index + 1, step.project_name));
+        }
+        save_run(&db, &run);
+    }
+
+    let cancelled = run_status(&db, &run.id).as_deref() == Some("cancelled");
+    let completed = run.steps.iter().filter(|s| s.status == "completed").count();
+    run.status = if cancelled {
+        "cancelled"
+    } else if failure.is_some() {
+        "failed"
+    } else {
+        "completed"
+    }
+    .to_string();
+    let mut outcome = format!("Workspace PRD: {}\nCompleted: {}/{} steps", run.name, completed, run.steps.len());
+    if let Some(reason) = &failure {
+        outcome.push_str(&format!("\n\n{}", reason));
+    }
+    if !summaries.is_empty() {
+        outcome.push_str(&format!("\n\n{}", summaries.join("\n\n")));
+    }
+    run.outcome = Some(outcome);
+    run.completed_at = Some(Utc::now().to_rfc3339());
+    save_run(&db, &run);
+
+    let projects: HashSet<&str> = run.steps.iter().map(|s| s.project_id.as_str()).collect();
+    for project_id in projects {
+        events::publish(
+            &db,
+            AppEvent::activity(
+                project_id,
+                ActivityType::Ralph,
+                &format!("Workspace RALPH run {}: {}/{} steps completed", run.status, completed, run.steps.len()),
+            ),
+        );
+    }
+}
+
+/// Status and outcome of a step loop once it is completed or failed (a paused loop may still
+/// be resumed), or once the run was cancelled. None while the loop is still going.
+fn finished_loop(db: &Connection, run_id: &str, loop_id: &str) -> Option<(String, String)> {
+    let row: Option<(String, Option<String>)> = db
+        .query_row("SELECT status, outcome FROM ralph_loops WHERE id = ?1", [loop_id], |row| {
+            Ok((row.get(0)?, row.get(1)?))
+        })
+        .ok();
+    let Some((status, outcome)) = row else {
+        return Some(("failed".to_string(), String::new()));
+    };
+    if status == "completed" || status == "failed" || run_status(db, run_id).as_deref() == Some("cancelled") {
+        Some((status, outcome.unwrap_or_default()))
+    } else {
+        None
+    }
+}
+
+fn run_status(db: &Connection, run_id: &str) -> Option<String> {
+    db.query_row("SELECT status FROM ralph_workspace_runs WHERE id = ?1", [run_id], |row| row.get(0))
+        .ok()
+}
+
+fn steps_json(steps: &[WorkspaceRunStep]) -> String {
+    serde_json::to_string(steps).unwrap_or_else(|_| "[]".to_string())
+}
+
+/// Store the run's progress. A cancelled status set by cancel_workspace_run is kept until
+/// the run itself records its final status.
+fn save_run(db: &Connection, run: &WorkspaceRun) {
+    let result = db.execute(
+        "UPDATE ralph_workspace_runs
+         SET status = CASE WHEN status = 'cancelled' AND ?2 = 'running' THEN status ELSE ?2 END,
+             current_step = ?3, steps = ?4, outcome = ?5, completed_at = ?6
+         WHERE id = ?1",
+        rusqlite::params![run.id, run.status, run.current_step, steps_json(&run.steps), run.outcome, run.completed_at],
+    );
+    if let Err(e) = result {
+        tracing::warn!(error = %e, run_id = %run.id, "Failed to save workspace run");
+    }
+}
+
+fn load_run(db: &Connection, run_id: &str) -> Result<WorkspaceRun, String> {
+    db.query_row(
+        &format!("SELECT {} FROM ralph_workspace_runs WHERE id = ?1", RUN_COLUMNS),
+        [run_id],
+        map_run_row,
+    )
+    .map_err(|e| format!("Workspace run not found: {}", e))
+}
+
+fn map_run_row(row: &rusqlite::Row) -> rusqlite::Result<WorkspaceRun> {
+    Ok(WorkspaceRun {
+        id: row.get(0)?,
+        name: row.get(1)?,
+        status: row.get(2)?,
+        current_step: row.get(3)?,
+        steps: serde_json::from_str(&row.get::<_, String>(4)?).unwrap_or_default(),
+        outcome: row.get(5)?,
+        started_at: row.get(6)?,
+        completed_at: row.get(7)?,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::models::ralph::PrdStory;
+
+    fn story(id: &str, project: &str, deps: &[&str]) -> PrdStory {
+        PrdStory {
+            id: id.to_string(),
+            title: id.to_string(),
+            description: String::new(),
+            acceptance_criteria: None,
+            priority: 1,
+            completed: false,
+            commit_hash: None,
+            depends_on: deps.iter().map(|d| d.to_string()).collect(),
+            project: Some(project.to_string()),
+        }
+    }
+
+    fn prd(stories: Vec<PrdStory>) -> PrdFile {
+        PrdFile {
+            name: "Billing".to_string(),
+            description: None,
+            branch: "feature/billing".to_string(),
+            test_command: None,
+            typecheck_command: None,
+            max_iterations_per_story: 3,
+            stories,
+            cross_repo_context: None,
+            max_duration_minutes: None,
+        }
+    }
+
+    fn projects() -> Vec<(String, String, String)> {
+        vec![
+            ("p-api".to_string(), "api".to_string(), "/work/api".to_string()),
+            ("p-web".to_string(), "Web".to_string(), "/work/web".to_string()),
+        ]
+    }
+
+    #[test]
+    fn test_plan_workspace_steps_groups_ordered_stories() {
+        let prd = prd(vec![
+            story("ui", "web", &["endpoint"]),
+            story("schema", "p-api", &[]),
+            story("endpoint", "api", &["schema"]),
+            story("copy", "web", &[]),
+        ]);
+        let steps = plan_workspace_steps(&prd, &projects()).unwrap();
+
+        let shape: Vec<(&str, Vec<&str>)> = steps
+            .iter()
+            .map(|s| (s.project_id.as_str(), s.prd.stories.iter().map(|st| st.id.as_str()).collect()))
+            .collect();
+        assert_eq!(shape, vec![("p-api", vec!["schema", "endpoint"]), ("p-web", vec!["ui", "copy"])]);
+        assert_eq!(steps[1].project_name, "Web");
+        // "ui" depended on "endpoint" in the api step; the order covers it
+        assert!(steps[1].prd.stories[0].depends_on.is_empty());
+        assert_eq!(steps[0].prd.stories[1].depends_on, vec!["schema"]);
+        assert_eq!(steps[0].prd.branch, "feature/billing");
+    }
+
+    #[test]
+    fn test_plan_workspace_steps_rejects_unknown_or_missing_projects() {
+        let err = plan_workspace_steps(&prd(vec![story("a", "mobile", &[])]), &projects()).unwrap_err();
+        assert!(err.contains("no registered project \"mobile\""), "{}", err);
+
+        let mut unassigned = story("b", "", &[]);
+        unassigned.project = None;
+        let err = plan_workspace_steps(&prd(vec![story("a", "api", &[]), unassigned]), &projects()).unwrap_err();
+        assert_eq!(err, "Stories without a project: b");
+
+        let mut twins = projects();
+        twins.push(("p-api2".to_string(), "API".to_string(), "/work/api2".to_string()));
+        assert!(plan_workspace_steps(&prd(vec![story("a", "api", &[])]), &twins).unwrap_err().contains("project id"));
+    }
+
+    #[test]
+    fn test_step_summary_and_context() {
+        let outcome = "PRD: Billing\nCompleted: 2/2 stories\n\n✓ Story 1: Add invoices table (commit: abc1234)\n✓ Story 2: Expose /invoices (commit: def5678)";
+        let summary = step_summary("api", outcome, &["db/schema.sql".to_string(), "src/routes.rs".to_string()]);
+        assert_eq!(
+            summary,
+            "Repository api:\n- Story 1: Add invoices table (commit: abc1234)\n- Story 2: Expose /invoices (commit: def5678)\nFiles changed: db/schema.sql, src/routes.rs"
+        );
+
+        assert_eq!(cross_repo_context(&[]), None);
+        let summaries: Vec<String> = (1..=7).map(|i| format!("Repository r{}:", i)).collect();
+        let context = cross_repo_context(&summaries).unwrap();
+        assert!(context.contains("Repository r3:") && context.contains("Repository r7:"));
+        assert!(!context.contains("Repository r2:"));
+    }
+
+    #[test]
+    fn test_step_succeeded() {
+        assert!(step_succeeded("completed", "✓ Story 1: a (commit: abc)"));
+        assert!(!step_succeeded("completed", "✓ Story 1: a (commit: abc)\n✗ Story 2: b (failed after 3 iterations)"));
+        assert!(!step_succeeded("completed", "⊘ Story 2: b (blocked by a)"));
+        assert!(!step_succeeded("failed", "Killed by user"));
+    }
+}
@@ -0,0 +1,93 @@
+//! @module commands/readme
+//! @description Tauri IPC command for generating a project README section
+//!
+//! PURPOSE:
+//! - Generate a README section from module docs, CLAUDE.md, and the scanner
+//! - Preview it as a diff against the current README.md before anything is written
+//! - Write the merged README.md when asked
+//!
+//! DEPENDENCIES:
+//! - tauri - Command macro and State
+//! - db::AppState - Project lookup and activity logging
+//! - core::readme - Section rendering and marker-based merge
+//! - commands::versions - diff_lines for the preview
+//! - models::readme - ReadmeOptions, ReadmeResult
+//!
+//! EXPORTS:
+//! - generate_readme - Build the section, merge it into README.md, and return the diff
+//!
+//! PATTERNS:
+//! - Preview first: options.write defaults to false, so the UI shows the diff and
+//!   calls again with write = true to apply it
+//!
+//! CLAUDE NOTES:
+//! - Only the marked section of an existing README is replaced; hand-written content stays
+//! - A project without README.md gets a new one titled with the project name
+
+use std::fs;
+use std::path::Path;
+
+use tauri::State;
+
+use crate::commands::versions;
+use crate::core::readme;
+use crate::core::events::{self, AppEvent};
+use crate::db::AppState;
+use crate::models::activity::ActivityType;
+use crate::models::readme::{ReadmeOptions, ReadmeResult};
+
+/// Generate the README section for a project and merge it into README.md.
+/// Returns the merged content and a line diff; writes only when options.write is set.
+#[tauri::command]
+pub async fn generate_readme(
+    project_id: String,
+    options: Option<ReadmeOptions>,
+    state: State<'_, AppState>,
+) -> Result<ReadmeResult, String> {
+    let options = options.unwrap_or_default();
+
+    let (name, description, project_path): (String, String, String) = {
+        let db = state.db.lock().map_err(|e| format!("DB lock error: {}", e))?;
+        db.query_row(
+            "SELECT name, description, path FROM projects WHERE id = ?1",
+            [&project_id],
+            |row| Ok((row.get(0)?, row.get::<_, Option<String>>(1)?.unwrap_or_default(), row.get(2)?)),
+        )
+        .map_err(|e| format!("Project not found: {}", e))?
+    };
+
+    let section = readme::build_section(&project_path, &options)?;
+
+    let readme_path = Path::new(&project_path).join("README.md");
+    let existing = if readme_path.exists() {
+        Some(fs::read_to_string(&readme_path).map_err(|e| format!("Failed to read README.md: {}", e))?)
+    } else {
+        None
+    };
+    let merged = readme::merge_readme(existing.as_deref(), &section, &name, &description);
+    let has_changes = existing.as_deref() != Some(merged.as_str());
+    let diff = versions::diff_lines(existing.as_deref().unwrap_or(""), &merged);
+
+    let written_path = if options.write && has_changes {
+        fs::write(&readme_path, &merged).map_err(|e| format!("Failed to write README.md: {}", e))?;
+        match state.db.lock() {
+            Ok(db) => {
+                let message = "Updated README.md from module docs";
+                events::publish(&db, AppEvent::activity(&project_id, ActivityType::Generate, message));
+            }
+            Err(e) => tracing::warn!(error = %e, "Failed to lock DB for activity logging"),
+        }
+        Some(readme_path.to_string_lossy().to_string())
+    } else {
+        None
+    };
+
+    Ok(ReadmeResult {
+        section,
+        existing,
+        merged,
+        diff,
+        has_changes,
+        written_path,
+    })
+}
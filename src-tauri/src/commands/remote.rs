@@ -0,0 +1,184 @@
+//! @module commands/remote
+//! @description Tauri IPC commands for projects on remote machines and dev containers (ssh)
+//!
+//! PURPOSE:
+//! - Check an ssh location and mirror it locally before onboarding
+//! - Re-sync a registered remote project's mirror
+//! - Install and inspect the documentation pre-commit hook on the remote side
+//!
+//! DEPENDENCIES:
+//! - tauri - Command macro and State
+//! - db::AppState - Database connection (projects, activities)
+//! - core::remote - ssh/rsync executor
+//! - commands::enforcement - Hook script and status parsing shared with local hooks
+//! - models::project - RemoteSync type
+//!
+//! EXPORTS:
+//! - prepare_remote_project - Verify ssh access and mirror host:path; returns the mirror path
+//! - sync_remote_project - Re-sync a registered remote project into its mirror
+//! - install_remote_git_hooks - Write the pre-commit hook on the remote host
+//! - get_remote_hook_status - Read the remote pre-commit hook and report its mode/version
+//! - install_remote_hook - (internal) Hook install used by onboarding
+//!
+//! PATTERNS:
+//! - Onboarding calls prepare_remote_project, then scan_project and save_project with the
+//!   mirror as the path; every local command then works on the mirror
+//! - Re-sync before scans, freshness checks, or health refreshes to see remote edits
+//!
+//! CLAUDE NOTES:
+//! - Remote hooks support "block" and "warn" only; auto-update needs the API key exported
+//!   on the machine running the commit
+//! - The DB lock is released while ssh/rsync run
+
+use chrono::Utc;
+use tauri::State;
+
+use crate::commands::enforcement::{self, HOOK_VERSION};
+use crate::core::remote::{self, RemoteTarget};
+use crate::core::events::{self, AppEvent};
+use crate::db::AppState;
+use crate::models::activity::ActivityType;
+use crate::models::enforcement::HookStatus;
+use crate::models::project::RemoteSync;
+
+/// Project-relative path of the pre-commit hook.
+const REMOTE_HOOK_PATH: &str = ".git/hooks/pre-commit";
+
+/// Check that `host` is reachable over ssh and `remote_path` is a directory, then
+/// mirror it locally. Use the returned mirror path to scan and save the project.
+#[tauri::command]
+pub async fn prepare_remote_project(host: String, remote_path: String) -> Result<RemoteSync, String> {
+    let target = RemoteTarget::new(&host, &remote_path)?;
+    remote::check_connection(&target)?;
+    sync(&target)
+}
+
+/// Re-sync a registered remote project's local mirror.
+#[tauri::command]
+pub async fn sync_remote_project(project_id: String, state: State<'_, AppState>) -> Result<RemoteSync, String> {
+    let target = load_target(&state, &project_id)?;
+    let result = sync(&target)?;
+
+    let db = state.db.lock().map_err(|e| format!("DB lock error: {}", e))?;
+    events::publish(
+        &db,
+        AppEvent::activity(
+            &project_id,
+            ActivityType::Scan,
+            &format!("Synced remote project from {}", result.remote),
+        ),
+    );
+    Ok(result)
+}
+
+/// Install the documentation pre-commit hook on a remote project ("block" or "warn").
+#[tauri::command]
+pub async fn install_remote_git_hooks(
+    project_id: String,
+    mode: String,
+    state: State<'_, AppState>,
+) -> Result<HookStatus, String> {
+    let target = load_target(&state, &project_id)?;
+    let status = install_remote_hook(&target, &mode)?;
+
+    let db = state.db.lock().map_err(|e| format!("DB lock error: {}", e))?;
+    events::publish(
+        &db,
+        AppEvent::activity(
+            &project_id,
+            ActivityType::Enforcement,
+            &format!("Installed remote git hooks ({})", mode),
+        ),
+    );
+    Ok(status)
+}
+
+/// Read the remote pre-commit hook and report whether it is ours, its mode, and version.
+#[tauri::command]
+pub async fn get_remote_hook_status(project_id: String, state: State<'_, AppState>) -> Result<HookStatus, String> {
+    let target = load_target(&state, &project_id)?;
+    let (has_git, has_husky) = remote_dirs(&target)?;
+    let content = if has_git {
+        remote::read_file(&target, REMOTE_HOOK_PATH).ok()
+    } else {
+        None
+    };
+    Ok(enforcement::hook_status_from(
+        content.as_deref(),
+        hook_display_path(&target),
+        has_git,
+        has_husky,
+    ))
+}
+
+/// Write the hook script on the remote side. The remote path must be a git repository.
+pub fn install_remote_hook(target: &RemoteTarget, mode: &str) -> Result<HookStatus, String> {
+    if mode != "block" && mode != "warn" {
+        return Err(format!("Remote hooks support \"block\" or \"warn\" mode, not \"{}\".", mode));
+    }
+    let (has_git, has_husky) = remote_dirs(target)?;
+    if !has_git {
+        return Err(format!("{} is not a git repository.", target.display()));
+    }
+
+    remote::write_file(target, REMOTE_HOOK_PATH, &enforcement::generate_hook_script(mode), true)?;
+
+    Ok(HookStatus {
+        installed: true,
+        hook_path: hook_display_path(target),
+        mode: mode.to_string(),
+        has_husky,
+        has_git,
+        version: Some(HOOK_VERSION.to_string()),
+        outdated: false,
+        current_version: HOOK_VERSION.to_string(),
+    })
+}
+
+fn sync(target: &RemoteTarget) -> Result<RemoteSync, String> {
+    let mirror = remote::sync_to_mirror(target)?;
+    Ok(RemoteSync {
+        mirror_path: mirror.to_string_lossy().to_string(),
+        remote: target.display(),
+        synced_at: Utc::now().to_rfc3339(),
+    })
+}
+
+/// (has .git, has .husky) in the remote project directory.
+fn remote_dirs(target: &RemoteTarget) -> Result<(bool, bool), String> {
+    let out = remote::run(
+        target,
+        "if [ -d .git ]; then echo git; fi; if [ -d .husky ]; then echo husky; fi",
+    )?;
+    let mut has_git = false;
+    let mut has_husky = false;
+    for line in out.lines() {
+        match line.trim() {
+            "git" => has_git = true,
+            "husky" => has_husky = true,
+            _ => {}
+        }
+    }
+    Ok((has_git, has_husky))
+}
+
+fn hook_display_path(target: &RemoteTarget) -> String {
+    format!("{}/{}", target.display(), REMOTE_HOOK_PATH)
+}
+
+/// The ssh location of a remote project; errors for local projects.
+fn load_target(state: &AppState, project_id: &str) -> Result<RemoteTarget, String> {
+    let db = state.db.lock().map_err(|e| format!("DB lock error: {}", e))?;
+    let (location_type, host, path): (String, Option<String>, Option<String>) = db
+        .query_row(
+            "SELECT location_type, remote_host, remote_path FROM projects WHERE id = ?1",
+            [project_id],
+            |row| Ok((row.get(0)?, row.get(1)?, row.get(2)?)),
+        )
+        .map_err(|e| format!("Project not found: {}", e))?;
+
+    match (location_type.as_str(), host, path) {
+        ("ssh", Some(host), Some(path)) => RemoteTarget::new(&host, &path),
+        _ => Err("Project is not a remote (ssh) project.".to_string()),
+    }
+}
@@ -0,0 +1,275 @@
+//! @module commands/rules
+//! @description Tauri IPC commands for user-defined automation rules
+//!
+//! PURPOSE:
+//! - List, create, enable/disable, and delete a project's automation rules
+//! - Dry-run a rule against the project's current state before saving it
+//! - Show recent rule runs with their action output
+//!
+//! DEPENDENCIES:
+//! - tauri - Command macro and State
+//! - db::AppState - Database connection
+//! - core::rules - Validation, evaluation, and rule storage
+//! - core::command_guard - Registers run_command commands for approval
+//! - core::freshness - Current stale counts for simulating scan_completed rules
+//! - commands::project - load_project
+//! - commands::ralph - categorize_mistake for simulated loop events
+//! - models::automation - AutomationRule, NewAutomationRule, RuleSimulation, RuleRun
+//!
+//! EXPORTS:
+//! - list_rules - A project's rules, oldest first
+//! - create_rule - Validate and store a rule
+//! - simulate_rule - Evaluate a rule against facts built from the project's current state
+//! - set_rule_enabled - Turn a rule on or off
+//! - delete_rule - Remove a rule and its runs
+//! - list_rule_runs - Recent rule runs for a project, newest first
+//!
+//! PATTERNS:
+//! - Rules fire from core::rules::RulesSubscriber on the event bus, not from these commands
+//! - simulate_rule never runs the action; it reports what would happen
+//!
+//! CLAUDE NOTES:
+//! - create_rule registers a run_command command as pending approval, so the user approves it
+//!   once in the command allowlist instead of the rule silently skipping later
+//! - Simulated facts: scan_completed from a fresh freshness check, loop_iteration_finished from
+//!   the project's latest RALPH iteration, doc_applied from the latest applied doc proposal,
+//!   activity from the latest activity entry
+
+use rusqlite::{Connection, OptionalExtension};
+use tauri::State;
+
+use crate::commands::{project, ralph};
+use crate::core::command_guard;
+use crate::core::events::{self, AppEvent};
+use crate::core::freshness;
+use crate::core::rules;
+use crate::db::AppState;
+use crate::models::activity::ActivityType;
+use crate::models::automation::{AutomationRule, NewAutomationRule, RuleAction, RuleRun, RuleSimulation};
+
+/// Rule runs returned by list_rule_runs.
+const RULE_RUNS_LIMIT: u32 = 50;
+
+/// A project's automation rules, oldest first.
+#[tauri::command]
+pub async fn list_rules(project_id: String, state: State<'_, AppState>) -> Result<Vec<AutomationRule>, String> {
+    let db = state.db.lock().map_err(|e| format!("DB lock error: {}", e))?;
+    rules::list_rules(&db, &project_id)
+}
+
+/// Validate and store a rule. A run_command action's command must pass validation
+/// and is registered for approval if it is new to the project.
+#[tauri::command]
+pub async fn create_rule(rule: NewAutomationRule, state: State<'_, AppState>) -> Result<AutomationRule, String> {
+    rules::validate_rule(&rule)?;
+    let db = state.db.lock().map_err(|e| format!("DB lock error: {}", e))?;
+    let project = project::load_project(&db, &rule.project_id)?;
+
+    if let RuleAction::RunCommand { command } = &rule.action {
+        if let command_guard::CommandDecision::Denied =
+            command_guard::check_command(&db, &project.id, &project.path, command, "rule")?
+        {
+            return Err(format!("Command `{}` was denied for this project", command.trim()));
+        }
+    }
+
+    let stored = rules::insert_rule(&db, &rule)?;
+    events::publish(
+        &db,
+        AppEvent::activity(&project.id, ActivityType::Settings, &format!("Created automation rule: {}", stored.name)),
+    );
+    Ok(stored)
+}
+
+/// Evaluate a rule against facts built from the project's current state. The action is not run.
+#[tauri::command]
+pub async fn simulate_rule(rule: NewAutomationRule, state: State<'_, AppState>) -> Result<RuleSimulation, String> {
+    rules::validate_rule(&rule)?;
+    let (project, stored_event) = {
+        let db = state.db.lock().map_err(|e| format!("DB lock error: {}", e))?;
+        let project = project::load_project(&db, &rule.project_id)?;
+        let event = match rule.trigger.as_str() {
+            "scan_completed" => None,
+            trigger => latest_event(&db, &project.id, &project.path, trigger)?,
+        };
+        (project, event)
+    };
+
+    let event = match rule.trigger.as_str() {
+        "scan_completed" => {
+            let modules = freshness::check_project_freshness(&project.path)?;
+            let count = |status: &str| modules.iter().filter(|m| m.status == status).count() as u32;
+            Some(AppEvent::ScanCompleted {
+                project_id: Some(project.id.clone()),
+                project_path: project.path.clone(),
+                total: modules.len() as u32,
+                missing: count("missing"),
+                outdated: count("outdated"),
+            })
+        }
+        _ => stored_event,
+    };
+
+    Ok(simulate(&rule, event.as_ref()))
+}
+
+/// Evaluate `rule` against `event` (None when there is no event to simulate with).
+fn simulate(rule: &NewAutomationRule, event: Option<&AppEvent>) -> RuleSimulation {
+    let action_preview = rules::describe_action(&rule.action);
+    let Some(event) = event else {
+        return RuleSimulation {
+            matched: false,
+            facts: None,
+            conditions: Vec::new(),
+            action_preview,
+            note: Some(format!("No {} event recorded for this project yet", rule.trigger)),
+        };
+    };
+
+    let facts = rules::event_facts(event);
+    let conditions = rules::evaluate(&rule.conditions, &facts);
+    let matched = conditions.iter().all(|c| c.passed);
+    let action_preview = match &rule.action {
+        RuleAction::LogActivity { message } => format!("Log activity: {}", rules::render_message(message, &facts)),
+        _ => action_preview,
+    };
+    RuleSimulation {
+        matched,
+        facts: Some(facts),
+        conditions,
+        action_preview,
+        note: None,
+    }
+}
+
+/// The most recent stored event of a non-scan trigger for a project.
+fn latest_event(db: &Connection, project_id: &str, project_path: &str, trigger: &str) -> Result<Option<AppEvent>, String> {
+    let event = match trigger {
+        "loop_iteration_finished" => db
+            .query_row(
+                "SELECT i.loop_id, i.iteration, i.story_index, i.status, i.summary, i.issues_count, i.files_changed
+                 FROM ralph_iterations i JOIN ralph_loops l ON l.id = i.loop_id
+                 WHERE l.project_id = ?1 ORDER BY i.completed_at DESC LIMIT 1",
+                [project_id],
+                |row| {
+                    let status: String = row.get(3)?;
+                    let summary: String = row.get(4)?;
+                    let files: String = row.get(6)?;
+                    let files_changed = serde_json::from_str::<Vec<serde_json::Value>>(&files)
+                        .map(|f| f.len() as u32)
+                        .unwrap_or(0);
+                    Ok(AppEvent::LoopIterationFinished {
+                        project_id: project_id.to_string(),
+                        loop_id: row.get(0)?,
+                        iteration: row.get(1)?,
+                        story_index: row.get(2)?,
+                        failure_type: (status == "failed").then(|| ralph::categorize_mistake(&summary).to_string()),
+                        status,
+                        issues_count: row.get(5)?,
+                        files_changed,
+                    })
+                },
+            )
+            .optional(),
+        "doc_applied" => db
+            .query_row(
+                "SELECT file_path FROM doc_proposals WHERE project_path = ?1 AND status = 'applied'
+                 ORDER BY decided_at DESC LIMIT 1",
+                [project_path],
+                |row| {
+                    Ok(AppEvent::DocApplied {
+                        project_id: project_id.to_string(),
+                        file_path: row.get(0)?,
+                    })
+                },
+            )
+            .optional(),
+        _ => db
+            .query_row(
+                "SELECT activity_type, message FROM activities WHERE project_id = ?1 ORDER BY created_at DESC LIMIT 1",
+                [project_id],
+                |row| {
+                    let activity_type: String = row.get(0)?;
+                    let message: String = row.get(1)?;
+                    Ok(AppEvent::activity(project_id, ActivityType::parse(&activity_type), &message))
+                },
+            )
+            .optional(),
+    };
+    event.map_err(|e| format!("Failed to load latest {} event: {}", trigger, e))
+}
+
+/// Turn a rule on or off.
+#[tauri::command]
+pub async fn set_rule_enabled(rule_id: String, enabled: bool, state: State<'_, AppState>) -> Result<(), String> {
+    let db = state.db.lock().map_err(|e| format!("DB lock error: {}", e))?;
+    let updated = db
+        .execute(
+            "UPDATE automation_rules SET enabled = ?1 WHERE id = ?2",
+            rusqlite::params![enabled as i32, rule_id],
+        )
+        .map_err(|e| format!("Failed to update rule: {}", e))?;
+    if updated == 0 {
+        return Err(format!("Rule not found: {}", rule_id));
+    }
+    Ok(())
+}
+
+/// Delete a rule and its run history.
+#[tauri::command]
+pub async fn delete_rule(rule_id: String, state: State<'_, AppState>) -> Result<(), String> {
+    let db = state.db.lock().map_err(|e| format!("DB lock error: {}", e))?;
+    db.execute("DELETE FROM rule_runs WHERE rule_id = ?1", [&rule_id])
+        .map_err(|e| format!("Failed to delete rule runs: {}", e))?;
+    db.execute("DELETE FROM automation_rules WHERE id = ?1", [&rule_id])
+        .map_err(|e| format!("Failed to delete rule: {}", e))?;
+    Ok(())
+}
+
+/// Recent rule runs for a project, newest first.
+#[tauri::command]
+pub async fn list_rule_runs(project_id: String, state: State<'_, AppState>) -> Result<Vec<RuleRun>, String> {
+    let db = state.db.lock().map_err(|e| format!("DB lock error: {}", e))?;
+    rules::list_runs(&db, &project_id, RULE_RUNS_LIMIT)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_support::with_project;
+    use crate::models::automation::{RuleCondition, RuleOp};
+    use serde_json::json;
+
+    #[test]
+    fn test_simulate_loop_rule_against_latest_iteration() {
+        let db = with_project();
+        db.execute_batch(
+            "INSERT INTO ralph_loops (id, project_id, prompt, status, created_at) VALUES ('l1', 'p1', 'Fix', 'failed', '2025-01-01T00:00:00Z');
+             INSERT INTO ralph_iterations (id, loop_id, iteration, status, summary, started_at, completed_at)
+             VALUES ('i1', 'l1', 2, 'failed', 'error TS2322: Type string is not assignable', '2025-01-01T00:00:00Z', '2025-01-01T00:01:00Z');",
+        )
+        .unwrap();
+
+        let rule = NewAutomationRule {
+            project_id: "p1".into(),
+            name: "Typecheck on type errors".into(),
+            trigger: "loop_iteration_finished".into(),
+            conditions: vec![RuleCondition {
+                field: "failureType".into(),
+                op: RuleOp::Eq,
+                value: json!("type_error"),
+            }],
+            action: RuleAction::RunCommand { command: "npx tsc --noEmit".into() },
+        };
+        let event = latest_event(&db, "p1", "/tmp/p1", &rule.trigger).unwrap();
+        let simulation = simulate(&rule, event.as_ref());
+        assert!(simulation.matched);
+        assert_eq!(simulation.conditions[0].actual, Some(json!("type_error")));
+        assert!(simulation.action_preview.contains("npx tsc --noEmit"));
+
+        let none = latest_event(&db, "p1", "/tmp/p1", "doc_applied").unwrap();
+        let simulation = simulate(&rule, none.as_ref());
+        assert!(!simulation.matched);
+        assert!(simulation.note.is_some());
+    }
+}
@@ -10,30 +10,48 @@
 //! - tauri - Command macro and State
 //! - db::AppState - Database connection and HTTP client
 //! - core::ai - Claude API caller
+//! - core::ai_queue - Prioritized slot for each Claude call
+//! - core::ai_audit - Audit scope keyed by the transcript's project path
 //! - serde_json - JSON parsing
 //! - chrono - Timestamp handling
+//! - core::transcript_cache - Parsed transcripts reused until the JSONL file changes
+//! - core::plan_prd - Plan-mode plans found in transcripts and converted to PRD drafts
 //!
 //! EXPORTS:
 //! - analyze_session - Analyze session transcript and return recommendations
+//! - analyze_session_incremental - Analyze only messages appended since the last report and merge
+//! - extract_plan_prd - Convert the session's latest plan-mode plan into a PRD draft (no AI); validation
+//!   commands the plan does not name come from the project's files and package manager
 //! - get_session_transcript - Read recent transcript content
 //!
 //! PATTERNS:
 //! - Reads JSONL transcript files from Claude Code's storage
 //! - Uses AI to extract actionable insights
 //! - Returns typed SessionRecommendations struct
+//! - Reports are stored per session in session_reports; messages_analyzed marks how much of the
+//!   transcript a report covers, so incremental runs only send the messages after it
 //!
 //! CLAUDE NOTES:
 //! - Session transcripts are in ~/.claude/projects/{project-hash}/*.jsonl
-//! - Only analyze last N messages to control costs
+//! - Only analyze last N messages to control costs, then drop the oldest of those until the
+//!   transcript fits session_transcript_tokens (core::ai::token_limits)
+//! - Parsed transcripts are cached in transcript_cache; a grown file only has its new lines parsed
 //! - Cache results to avoid redundant API calls
 //! - User should opt-in to this feature (privacy)
 
 use serde::{Deserialize, Serialize};
 use std::fs;
-use std::path::PathBuf;
+use std::path::{Path, PathBuf};
 use tauri::State;
 
+use crate::core::ai;
+use crate::core::ai_audit;
+use crate::core::ai_queue;
+use crate::core::plan_prd;
+use crate::core::transcript_cache::{self, TranscriptMessage};
 use crate::db::AppState;
+use crate::models::ai_queue::AiJobKind;
+use crate::models::ralph::PrdFile;
 
 /// A single AI-generated recommendation
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -61,8 +79,11 @@ pub struct SessionAnalysis {
     pub session_summary: String,
     /// Timestamp of analysis
     pub analyzed_at: String,
-    /// Number of messages analyzed
+    /// Number of transcript messages the report covers
     pub messages_analyzed: u32,
+    /// Transcript file stem; pass to analyze_session_incremental to extend the report
+    #[serde(default)]
+    pub session_id: Option<String>,
 }
 
 /// Find the most recent session transcript for a project
@@ -147,135 +168,53 @@ fn find_most_recent_jsonl(dir: &PathBuf) -> Option<(PathBuf, std::time::SystemTi
     best
 }
 
-/// Read the last N messages from a JSONL transcript
-///
-/// Claude Code JSONL format:
-/// - type: "user" or "assistant"
-/// - message.role: "user" or "assistant"
-/// - message.content: string (human text) or array (tool results, thinking, tool_use)
-fn read_recent_messages(transcript_path: &PathBuf, max_messages: usize) -> Vec<String> {
-    let content = match fs::read_to_string(transcript_path) {
-        Ok(c) => c,
-        Err(_) => return vec![],
-    };
-
-    let lines: Vec<&str> = content.lines().collect();
-    let start = if lines.len() > max_messages * 2 {
-        // Read more lines than needed since we'll filter some out
-        lines.len() - (max_messages * 2)
-    } else {
-        0
+/// Parsed messages of a transcript, from transcript_cache when the file is unchanged.
+/// Parsing runs without the DB lock; only the cache read and write hold it.
+fn load_transcript_messages(
+    state: &AppState,
+    project_path: &str,
+    transcript_path: &Path,
+) -> Result<Vec<TranscriptMessage>, String> {
+    let key = transcript_path.to_string_lossy().to_string();
+    let cached = {
+        let db = state.db.lock().map_err(|e| format!("DB lock error: {}", e))?;
+        transcript_cache::load_cached(&db, &key)
     };
 
-    let mut messages = Vec::new();
-
-    for line in &lines[start..] {
-        if let Ok(json) = serde_json::from_str::<serde_json::Value>(line) {
-            let msg_type = json.get("type").and_then(|v| v.as_str()).unwrap_or("");
-
-            // Get the nested message object
-            let message = match json.get("message") {
-                Some(m) => m,
-                None => continue,
-            };
-
-            let role = message.get("role").and_then(|v| v.as_str()).unwrap_or(msg_type);
-
-            // Extract content - can be string or array
-            if let Some(content) = message.get("content") {
-                let text = extract_message_text(content);
-                if !text.is_empty() {
-                    let truncated = if text.len() > 800 {
-                        format!("{}...", &text[..800])
-                    } else {
-                        text
-                    };
-                    messages.push(format!("[{}]: {}", role, truncated));
-                }
-            }
-        }
-
-        // Stop once we have enough meaningful messages
-        if messages.len() >= max_messages {
-            break;
-        }
+    let (transcript, changed) = transcript_cache::refresh(transcript_path, project_path, cached)?;
+    if changed {
+        let db = state.db.lock().map_err(|e| format!("DB lock error: {}", e))?;
+        transcript_cache::save_cached(&db, &transcript)?;
     }
-
-    messages
+    Ok(transcript.messages)
 }
 
-/// Extract human-readable text from message content
-/// Handles both string content and array of content blocks
-fn extract_message_text(content: &serde_json::Value) -> String {
-    // If content is a plain string (human messages)
-    if let Some(text) = content.as_str() {
-        return text.to_string();
-    }
-
-    // If content is an array (tool results, thinking blocks, etc.)
-    if let Some(arr) = content.as_array() {
-        let mut texts = Vec::new();
-
-        for item in arr {
-            let item_type = item.get("type").and_then(|t| t.as_str()).unwrap_or("");
-
-            match item_type {
-                // Plain text content
-                "text" => {
-                    if let Some(text) = item.get("text").and_then(|t| t.as_str()) {
-                        texts.push(text.to_string());
-                    }
-                }
-                // Tool use - extract the tool name and brief description
-                "tool_use" => {
-                    if let Some(name) = item.get("name").and_then(|n| n.as_str()) {
-                        // Only mention tool use briefly, don't include full input
-                        texts.push(format!("[Used tool: {}]", name));
-                    }
-                }
-                // Skip tool_result and thinking blocks - they're verbose and not useful for analysis
-                "tool_result" | "thinking" => {}
-                _ => {}
-            }
-        }
-
-        return texts.join(" ");
-    }
-
-    String::new()
+/// The last `max_messages` messages formatted as "[role]: text".
+fn recent_messages(messages: &[TranscriptMessage], max_messages: usize) -> Vec<String> {
+    let start = messages.len().saturating_sub(max_messages);
+    messages[start..]
+        .iter()
+        .map(|m| format!("[{}]: {}", m.role, m.text))
+        .collect()
 }
 
-/// Analyze the session transcript with AI
-#[tauri::command]
-pub async fn analyze_session(
-    project_path: String,
-    project_name: String,
-    project_language: Option<String>,
-    project_framework: Option<String>,
-    state: State<'_, AppState>,
-) -> Result<SessionAnalysis, String> {
-    // Get API key
-    let api_key = {
-        let db = state.db.lock().map_err(|e| format!("DB lock error: {}", e))?;
-        crate::core::ai::get_api_key(&db)?
+/// The newest messages that fit the session_transcript_tokens budget (core::ai::token_limits),
+/// oldest first. A newest message larger than the budget keeps only its end.
+fn within_transcript_budget(messages: Vec<String>, max_tokens: usize) -> Vec<String> {
+    let newest_first: Vec<&str> = messages.iter().rev().map(String::as_str).collect();
+    let Some(kept) = ai::split_by_tokens(&newest_first, max_tokens).into_iter().next() else {
+        return messages;
     };
+    let mut fitted: Vec<String> = newest_first[kept]
+        .iter()
+        .map(|m| ai::truncate_tail_to_tokens(m, max_tokens))
+        .collect();
+    fitted.reverse();
+    fitted
+}
 
-    // Find session transcript
-    let transcript_path = find_session_transcript(&project_path)
-        .ok_or_else(|| "No session transcript found. Start a Claude Code session first.".to_string())?;
-
-    // Read recent messages
-    let messages = read_recent_messages(&transcript_path, 30);
-
-    if messages.is_empty() {
-        return Err("No recent messages found in session transcript.".to_string());
-    }
-
-    let messages_analyzed = messages.len() as u32;
-    let transcript_excerpt = messages.join("\n\n");
-
-    // Build analysis prompt
-    let system = r#"You are an expert at analyzing Claude Code session transcripts to help developers improve their workflow.
+/// System prompt for session analysis (full and incremental)
+const SESSION_SYSTEM_PROMPT: &str = r#"You are an expert at analyzing Claude Code session transcripts to help developers improve their workflow.
 
 Analyze the conversation and suggest specific, actionable improvements. Return ONLY a JSON object (no markdown, no explanation):
 
@@ -327,13 +266,57 @@ GUIDELINES:
 - If the session is just exploration/reading, it's OK to return fewer recommendations
 - Focus on things that would SAVE TIME or PREVENT MISTAKES in future sessions"#;
 
-    let lang_info = match (project_language.as_deref(), project_framework.as_deref()) {
-        (Some(lang), Some(fw)) => format!("{} with {}", lang, fw),
-        (Some(lang), None) => lang.to_string(),
-        (None, Some(fw)) => fw.to_string(),
-        (None, None) => "Unknown stack".to_string(),
+/// Added to the system prompt when extending an existing report
+const INCREMENTAL_INSTRUCTIONS: &str = r#"
+
+INCREMENTAL MODE:
+You are given the summary and recommendation titles of an earlier analysis of this session, followed by
+ONLY the messages added since then. Return an updated session_summary covering the whole session and
+ONLY recommendations that are new (do not repeat earlier ones)."#;
+
+/// Messages sent on a first analysis
+const FULL_ANALYSIS_MESSAGES: usize = 30;
+
+/// Most new messages sent when extending a report; older new messages are skipped
+const MAX_INCREMENTAL_MESSAGES: usize = 60;
+
+/// Analyze the session transcript with AI
+#[tauri::command]
+pub async fn analyze_session(
+    project_path: String,
+    project_name: String,
+    project_language: Option<String>,
+    project_framework: Option<String>,
+    state: State<'_, AppState>,
+) -> Result<SessionAnalysis, String> {
+    // Get API key
+    let api_key = {
+        let db = state.db.lock().map_err(|e| format!("DB lock error: {}", e))?;
+        ai::get_api_key(&db)?
     };
 
+    // Find session transcript
+    let transcript_path = find_session_transcript(&project_path)
+        .ok_or_else(|| "No session transcript found. Start a Claude Code session first.".to_string())?;
+
+    // Read recent messages (parsed once, then served from transcript_cache)
+    let all_messages = load_transcript_messages(&state, &project_path, &transcript_path)?;
+    let messages = within_transcript_budget(
+        recent_messages(&all_messages, FULL_ANALYSIS_MESSAGES),
+        ai::token_limits().session_transcript_tokens,
+    );
+
+    if messages.is_empty() {
+        return Err("No recent messages found in session transcript.".to_string());
+    }
+
+    let transcript_excerpt = messages.join("\n\n");
+
+    // Build analysis prompt
+
+
+    let lang_info = stack_label(project_language.as_deref(), project_framework.as_deref());
+
     let prompt = format!(
         "Project: {} ({})\n\nRecent Claude Code session transcript:\n\n{}\n\nAnalyze and provide recommendations as JSON.",
         project_name,
@@ -342,14 +325,189 @@ GUIDELINES:
     );
 
     // Call Claude API
-    let response = crate::core::ai::call_claude(&state.http_client, &api_key, system, &prompt).await?;
+    let call = ai::call_claude(&state.http_client, &api_key, SESSION_SYSTEM_PROMPT, &prompt);
+    let queued = ai_queue::run(AiJobKind::SessionAnalysis, "session analysis", call);
+    let response = ai_audit::scoped(&project_path, "session analysis", queued).await?;
 
-    // Parse response
-    let analysis: SessionAnalysis = parse_analysis_response(&response, messages_analyzed)?;
+    // Parse response; the whole transcript so far counts as covered for incremental analysis
+    let mut analysis: SessionAnalysis = parse_analysis_response(&response, all_messages.len() as u32)?;
+    analysis.session_id = Some(session_id_of(&transcript_path));
+    save_session_report(&state, &project_path, &analysis)?;
 
     Ok(analysis)
 }
 
+/// Analyze only the messages appended to a session since its last analysis and
+/// merge the findings into the stored report. Returns the stored report unchanged
+/// when nothing was appended.
+#[tauri::command]
+pub async fn analyze_session_incremental(
+    session_id: String,
+    project_path: String,
+    project_language: Option<String>,
+    project_framework: Option<String>,
+    state: State<'_, AppState>,
+) -> Result<SessionAnalysis, String> {
+    let transcript_path = find_transcript_by_id(&project_path, &session_id)
+        .ok_or_else(|| format!("No transcript found for session {}.", session_id))?;
+    let messages = load_transcript_messages(&state, &project_path, &transcript_path)?;
+
+    let previous = {
+        let db = state.db.lock().map_err(|e| format!("DB lock error: {}", e))?;
+        load_session_report(&db, &session_id)
+    };
+    // A transcript shorter than the report was rewritten; start over
+    let previous = previous.filter(|p| (p.messages_analyzed as usize) <= messages.len());
+
+    let new_messages = match &previous {
+        Some(p) if p.messages_analyzed as usize == messages.len() => return Ok(p.clone()),
+        Some(p) => recent_messages(&messages[p.messages_analyzed as usize..], MAX_INCREMENTAL_MESSAGES),
+        None => recent_messages(&messages, FULL_ANALYSIS_MESSAGES),
+    };
+    if new_messages.is_empty() {
+        return Err("No recent messages found in session transcript.".to_string());
+    }
+    let new_messages = within_transcript_budget(new_messages, ai::token_limits().session_transcript_tokens);
+
+    let api_key = {
+        let db = state.db.lock().map_err(|e| format!("DB lock error: {}", e))?;
+        ai::get_api_key(&db)?
+    };
+
+    let project_name = Path::new(&project_path)
+        .file_name()
+        .and_then(|n| n.to_str())
+        .unwrap_or("project");
+    let lang_info = stack_label(project_language.as_deref(), project_framework.as_deref());
+    let (system, prompt) = match &previous {
+        Some(p) => {
+            let earlier: Vec<String> = p
+                .recommendations
+                .iter()
+                .map(|r| format!("- [{}] {}", r.rec_type, r.title))
+                .collect();
+            (
+                format!("{}{}", SESSION_SYSTEM_PROMPT, INCREMENTAL_INSTRUCTIONS),
+                format!(
+                    "Project: {} ({})\n\nEarlier summary: {}\n\nEarlier recommendations:\n{}\n\nNew session messages:\n\n{}\n\nUpdate the summary and add new recommendations as JSON.",
+                    project_name,
+                    lang_info,
+                    p.session_summary,
+                    if earlier.is_empty() { "(none)".to_string() } else { earlier.join("\n") },
+                    new_messages.join("\n\n")
+                ),
+            )
+        }
+        None => (
+            SESSION_SYSTEM_PROMPT.to_string(),
+            format!(
+                "Project: {} ({})\n\nRecent Claude Code session transcript:\n\n{}\n\nAnalyze and provide recommendations as JSON.",
+                project_name,
+                lang_info,
+                new_messages.join("\n\n")
+            ),
+        ),
+    };
+
+    let call = ai::call_claude(&state.http_client, &api_key, &system, &prompt);
+    let queued = ai_queue::run(AiJobKind::SessionAnalysis, "incremental session analysis", call);
+    let response = ai_audit::scoped(&project_path, "session analysis", queued).await?;
+    let mut analysis = parse_analysis_response(&response, messages.len() as u32)?;
+    analysis.session_id = Some(session_id);
+    if let Some(p) = previous {
+        analysis.recommendations = merge_recommendations(p.recommendations, analysis.recommendations);
+    }
+    save_session_report(&state, &project_path, &analysis)?;
+
+    Ok(analysis)
+}
+
+/// "Language with Framework" for the analysis prompt
+fn stack_label(language: Option<&str>, framework: Option<&str>) -> String {
+    match (language, framework) {
+        (Some(lang), Some(fw)) => format!("{} with {}", lang, fw),
+        (Some(lang), None) => lang.to_string(),
+        (None, Some(fw)) => fw.to_string(),
+        (None, None) => "Unknown stack".to_string(),
+    }
+}
+
+/// Session ID of a transcript (its file stem)
+fn session_id_of(transcript_path: &Path) -> String {
+    transcript_path
+        .file_stem()
+        .map(|s| s.to_string_lossy().to_string())
+        .unwrap_or_default()
+}
+
+/// Find <session_id>.jsonl in the project's transcript folder, falling back to
+/// any folder under ~/.claude/projects.
+fn find_transcript_by_id(project_path: &str, session_id: &str) -> Option<PathBuf> {
+    if session_id.is_empty() || !session_id.chars().all(|c| c.is_ascii_alphanumeric() || c == '-' || c == '_') {
+        return None;
+    }
+    let claude_projects = dirs::home_dir()?.join(".claude").join("projects");
+    let file_name = format!("{}.jsonl", session_id);
+
+    let exact = claude_projects.join(project_path.replace("/", "-")).join(&file_name);
+    if exact.is_file() {
+        return Some(exact);
+    }
+    fs::read_dir(&claude_projects)
+        .ok()?
+        .flatten()
+        .map(|entry| entry.path().join(&file_name))
+        .find(|path| path.is_file())
+}
+
+/// Earlier recommendations followed by new ones whose type and title are not
+/// already present (case-insensitive), ordered by priority.
+fn merge_recommendations(
+    existing: Vec<SessionRecommendation>,
+    new: Vec<SessionRecommendation>,
+) -> Vec<SessionRecommendation> {
+    let key = |r: &SessionRecommendation| (r.rec_type.to_lowercase(), r.title.trim().to_lowercase());
+    let mut merged = existing;
+    for rec in new {
+        if !merged.iter().any(|m| key(m) == key(&rec)) {
+            merged.push(rec);
+        }
+    }
+    merged.sort_by_key(|r| r.priority);
+    merged
+}
+
+fn load_session_report(db: &rusqlite::Connection, session_id: &str) -> Option<SessionAnalysis> {
+    db.query_row(
+        "SELECT report FROM session_reports WHERE session_id = ?1",
+        [session_id],
+        |row| row.get::<_, String>(0),
+    )
+    .ok()
+    .and_then(|json| serde_json::from_str(&json).ok())
+}
+
+fn save_session_report(state: &AppState, project_path: &str, analysis: &SessionAnalysis) -> Result<(), String> {
+    let Some(session_id) = analysis.session_id.as_deref() else {
+        return Ok(());
+    };
+    let report = serde_json::to_string(analysis).map_err(|e| format!("Failed to serialize report: {}", e))?;
+    let db = state.db.lock().map_err(|e| format!("DB lock error: {}", e))?;
+    db.execute(
+        "INSERT OR REPLACE INTO session_reports (session_id, project_path, messages_analyzed, report, updated_at)
+         VALUES (?1, ?2, ?3, ?4, ?5)",
+        rusqlite::params![
+            session_id,
+            project_path,
+            analysis.messages_analyzed,
+            report,
+            chrono::Utc::now().to_rfc3339()
+        ],
+    )
+    .map_err(|e| format!("Failed to save session report: {}", e))?;
+    Ok(())
+}
+
 /// Parse the AI response into structured analysis
 fn parse_analysis_response(response: &str, messages_analyzed: u32) -> Result<SessionAnalysis, String> {
     // Try to extract JSON from the response
@@ -377,19 +535,43 @@ fn parse_analysis_response(response: &str, messages_analyzed: u32) -> Result<Ses
         session_summary: raw.session_summary.unwrap_or_else(|| "Session analysis complete.".to_string()),
         analyzed_at: chrono::Utc::now().to_rfc3339(),
         messages_analyzed,
+        session_id: None,
     })
 }
 
+/// Convert the latest plan a session proposed in plan mode into a PRD draft for
+/// RALPH PRD mode. Uses the given session, or the project's most recent one.
+#[tauri::command]
+pub async fn extract_plan_prd(project_path: String, session_id: Option<String>) -> Result<PrdFile, String> {
+    let transcript_path = match session_id.as_deref() {
+        Some(id) => find_transcript_by_id(&project_path, id),
+        None => find_session_transcript(&project_path),
+    }
+    .ok_or_else(|| "No session transcript found.".to_string())?;
+
+    let content = fs::read(&transcript_path).map_err(|e| format!("Failed to read transcript: {}", e))?;
+    let plan = plan_prd::find_plans(&String::from_utf8_lossy(&content))
+        .pop()
+        .ok_or_else(|| "No plan-mode plan found in this session.".to_string())?;
+    let mut prd = plan_prd::plan_to_prd(&plan)?;
+    plan_prd::apply_validation_defaults(&mut prd, &project_path);
+    Ok(prd)
+}
+
 /// Get raw transcript content (for debugging)
 #[tauri::command]
 pub async fn get_session_transcript(
     project_path: String,
     max_messages: Option<usize>,
+    state: State<'_, AppState>,
 ) -> Result<Vec<String>, String> {
     let transcript_path = find_session_transcript(&project_path)
         .ok_or_else(|| "No session transcript found.".to_string())?;
 
-    let messages = read_recent_messages(&transcript_path, max_messages.unwrap_or(20));
+    let messages = recent_messages(
+        &load_transcript_messages(&state, &project_path, &transcript_path)?,
+        max_messages.unwrap_or(20),
+    );
 
     if messages.is_empty() {
         return Err("No messages found in transcript.".to_string());
@@ -397,3 +579,51 @@ pub async fn get_session_transcript(
 
     Ok(messages)
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn rec(rec_type: &str, title: &str, priority: u32) -> SessionRecommendation {
+        SessionRecommendation {
+            rec_type: rec_type.to_string(),
+            title: title.to_string(),
+            reason: String::new(),
+            details: String::new(),
+            priority,
+        }
+    }
+
+    #[test]
+    fn test_merge_recommendations() {
+        let existing = vec![rec("test", "Test the parser", 2), rec("doc", "Document hooks", 4)];
+        let new = vec![rec("test", "test the parser ", 1), rec("skill", "Add migration skill", 1)];
+        let merged = merge_recommendations(existing, new);
+        let titles: Vec<&str> = merged.iter().map(|r| r.title.as_str()).collect();
+        assert_eq!(titles, vec!["Add migration skill", "Test the parser", "Document hooks"]);
+    }
+
+    #[test]
+    fn test_recent_messages_and_session_id() {
+        let messages: Vec<TranscriptMessage> = (0..5)
+            .map(|i| TranscriptMessage { role: "user".to_string(), text: format!("m{}", i) })
+            .collect();
+        assert_eq!(recent_messages(&messages, 2), vec!["[user]: m3", "[user]: m4"]);
+        assert_eq!(recent_messages(&messages[5..], 2), Vec::<String>::new());
+        assert_eq!(session_id_of(Path::new("/x/abc-123.jsonl")), "abc-123");
+        assert!(find_transcript_by_id("/p", "../etc/passwd").is_none());
+    }
+
+    #[test]
+    fn test_within_transcript_budget_keeps_newest() {
+        let messages: Vec<String> = (0..10).map(|i| format!("[user]: message number {}", i)).collect();
+        let kept = within_transcript_budget(messages.clone(), 30);
+        assert_eq!(kept, messages[7..].to_vec());
+        assert_eq!(within_transcript_budget(messages.clone(), 10_000), messages);
+
+        let huge = vec!["old".to_string(), format!("[assistant]: {}", "word ".repeat(2_000))];
+        let kept = within_transcript_budget(huge, 300);
+        assert_eq!(kept.len(), 1);
+        assert!(kept[0].starts_with("[... truncated ~"));
+    }
+}
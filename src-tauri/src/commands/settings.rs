@@ -12,6 +12,7 @@
 //! - db::AppState - Database connection for settings table
 //! - rusqlite - SQLite queries
 //! - core::crypto - AES-256-GCM encryption for sensitive values
+//! - commands::enforcement - Refresh the hook-exported API key when it changes
 //!
 //! EXPORTS:
 //! - get_setting - Read a single setting by key (decrypts if encrypted)
@@ -25,7 +26,8 @@
 //! - Values are always strings; the frontend converts to appropriate types
 //! - save_setting uses INSERT OR REPLACE for upsert behavior
 //! - Encrypted values are prefixed with "enc:" to distinguish from plain values
-//! - API keys (anthropic_api_key) are automatically encrypted
+//! - API keys and tokens (anthropic_api_key, shared_db.auth_token, github_token, gitlab_token,
+//!   bitbucket_token) are automatically encrypted
 //!
 //! CLAUDE NOTES:
 //! - The settings table was created in Phase 1 (schema.rs) with key TEXT PRIMARY KEY, value TEXT
@@ -36,11 +38,20 @@
 use std::collections::HashMap;
 use tauri::State;
 
+use crate::commands::enforcement;
 use crate::core::crypto;
 use crate::db::AppState;
 
 /// Keys that should be encrypted when stored
-const ENCRYPTED_KEYS: &[&str] = &["anthropic_api_key"];
+const ENCRYPTED_KEYS: &[&str] = &[
+    "anthropic_api_key",
+    "shared_db.auth_token",
+    "github_token",
+    "gitlab_token",
+    "bitbucket_token",
+    "sync.secret",
+    "sync.passphrase",
+];
 
 /// Read a single setting value by key. Returns None (null) if not found.
 /// Automatically decrypts values that were stored encrypted (prefixed with "enc:").
@@ -104,6 +115,13 @@ pub async fn save_setting(
     )
     .map_err(|e| format!("Failed to save setting: {}", e))?;
 
+    // Rotate (or remove) the key exported for auto-update hooks
+    if key == "anthropic_api_key" {
+        if let Err(e) = enforcement::refresh_exported_hook_key(&db, true) {
+            tracing::warn!(error = %e, "Failed to refresh exported hook key");
+        }
+    }
+
     Ok(())
 }
 
@@ -0,0 +1,176 @@
+//! @module commands/shared_db
+//! @description Tauri IPC commands for configuring and syncing the team-shared database
+//!
+//! PURPOSE:
+//! - Configure a shared libsql/Turso or Postgres backend for team knowledge
+//! - Test the connection and create the shared tables
+//! - Sync skills, agents, team templates, and learnings with the shared backend
+//!
+//! DEPENDENCIES:
+//! - tauri - Command macro and State
+//! - db::AppState - Local database and shared HTTP client
+//! - db::shared - SharedBackend, settings, and sync planning
+//! - models::shared_db - SharedDbConfig, SharedSyncReport types
+//! - commands::imports - Merge engine for skills, agents, and team templates
+//!
+//! EXPORTS:
+//! - get_shared_db_config - Current backend kind, URL, and last sync time
+//! - set_shared_db_config - Choose local, libsql, or postgres and store URL/token
+//! - test_shared_db_connection - Connect and create the shared tables
+//! - sync_shared_db - Push local changes and pull team changes (conflicts are staged)
+//!
+//! PATTERNS:
+//! - The DB lock is never held across a network call: read, drop, await, lock again
+//! - set_shared_db_config keeps the stored token when auth_token is None; "" clears it
+//!
+//! CLAUDE NOTES:
+//! - Projects and file-local data always stay in local SQLite (see db/shared.rs)
+//! - Sync is manual; there is no background replication
+//! - The sync start time is stored as the last sync so edits made during a sync count as new
+//! - Pending conflicts are resolved with resolve_import_conflicts (commands/imports.rs)
+
+use chrono::Utc;
+use tauri::State;
+
+use crate::commands::imports;
+use crate::core::merge;
+use crate::db::shared::{self, SharedBackend, SharedBackendSettings, SHARED_TABLES};
+use crate::db::AppState;
+use crate::models::shared_db::{SharedBackendKind, SharedDbConfig, SharedSyncReport, SharedTableSync};
+
+/// Return the shared database configuration (never the token itself).
+#[tauri::command]
+pub async fn get_shared_db_config(state: State<'_, AppState>) -> Result<SharedDbConfig, String> {
+    let db = state.db.lock().map_err(|e| format!("DB lock error: {}", e))?;
+    let settings = shared::load_settings(&db)?;
+    Ok(to_config(&db, &settings))
+}
+
+/// Configure the shared database backend. kind is "local", "libsql", or "postgres".
+#[tauri::command]
+pub async fn set_shared_db_config(
+    kind: String,
+    url: Option<String>,
+    auth_token: Option<String>,
+    state: State<'_, AppState>,
+) -> Result<SharedDbConfig, String> {
+    let kind = SharedBackendKind::parse(&kind)
+        .ok_or_else(|| format!("Unknown shared database kind '{}'; expected local, libsql, or postgres", kind))?;
+    let url = url.map(|u| u.trim().to_string()).filter(|u| !u.is_empty());
+
+    match (kind, url.as_deref()) {
+        (SharedBackendKind::Local, _) => {}
+        (_, None) => return Err("A URL is required for a shared database".to_string()),
+        (SharedBackendKind::Libsql, Some(u))
+            if !(u.starts_with("libsql://") || u.starts_with("https://") || u.starts_with("http://")) =>
+        {
+            return Err("libsql URL must start with libsql://, https:// or http://".to_string())
+        }
+        (SharedBackendKind::Postgres, Some(u)) if !(u.starts_with("postgres://") || u.starts_with("postgresql://")) => {
+            return Err("Postgres URL must start with postgres:// or postgresql://".to_string())
+        }
+        _ => {}
+    }
+
+    let db = state.db.lock().map_err(|e| format!("DB lock error: {}", e))?;
+    let existing = shared::load_settings(&db)?;
+    let settings = SharedBackendSettings {
+        kind,
+        url,
+        auth_token: auth_token.or(existing.auth_token),
+    };
+    shared::save_settings(&db, &settings)?;
+
+    Ok(to_config(&db, &settings))
+}
+
+/// Connect to the configured shared backend and create the shared tables.
+#[tauri::command]
+pub async fn test_shared_db_connection(state: State<'_, AppState>) -> Result<(), String> {
+    let settings = {
+        let db = state.db.lock().map_err(|e| format!("DB lock error: {}", e))?;
+        shared::load_settings(&db)?
+    };
+
+    let backend = SharedBackend::connect(&settings, &state.http_client).await?;
+    backend.ensure_schema().await
+}
+
+/// Sync team-shared tables. Skills, agents, and team templates go through the
+/// import merge engine (conflicts are staged, not overwritten); learnings merge
+/// by id with last-write-wins. Local rows newer than the shared copy are pushed.
+#[tauri::command]
+pub async fn sync_shared_db(state: State<'_, AppState>) -> Result<SharedSyncReport, String> {
+    let started_at = Utc::now().to_rfc3339();
+    let settings = {
+        let db = state.db.lock().map_err(|e| format!("DB lock error: {}", e))?;
+        shared::load_settings(&db)?
+    };
+
+    let backend = SharedBackend::connect(&settings, &state.http_client).await?;
+    backend.ensure_schema().await?;
+
+    let mut remote_tables = Vec::new();
+    for table in SHARED_TABLES {
+        remote_tables.push(backend.fetch_rows(table).await?);
+    }
+
+    let mut tables = Vec::new();
+    let mut pushes = Vec::new();
+    {
+        let db = state.db.lock().map_err(|e| format!("DB lock error: {}", e))?;
+        let last_synced_at = shared::load_last_sync(&db);
+
+        for (table, remote) in SHARED_TABLES.iter().zip(remote_tables) {
+            let local = shared::read_local_rows(&db, table)?;
+            let plan = shared::plan_sync(table, local, remote.clone());
+
+            let (pulled, conflicts, push) = if merge::is_mergeable(table) {
+                let (summary, blocked) =
+                    imports::merge_incoming(&db, table, remote, last_synced_at.as_deref(), "shared")?;
+                let push: Vec<_> = plan
+                    .push
+                    .into_iter()
+                    .filter(|row| !blocked.contains(shared::text_at(row, 0)))
+                    .collect();
+                (summary.added + summary.updated, summary.conflicts, push)
+            } else {
+                (shared::apply_local_rows(&db, table, &plan.pull)?, 0, plan.push)
+            };
+
+            tables.push(SharedTableSync {
+                table: table.name.to_string(),
+                pushed: push.len() as u32,
+                pulled,
+                conflicts,
+            });
+            pushes.push(push);
+        }
+    }
+
+    for (table, rows) in SHARED_TABLES.iter().zip(pushes) {
+        backend.upsert_rows(table, rows).await?;
+    }
+
+    let db = state.db.lock().map_err(|e| format!("DB lock error: {}", e))?;
+    db.execute(
+        "INSERT OR REPLACE INTO settings (key, value) VALUES (?1, ?2)",
+        rusqlite::params![shared::LAST_SYNC_SETTING, started_at],
+    )
+    .map_err(|e| format!("Failed to record sync time: {}", e))?;
+
+    Ok(SharedSyncReport {
+        backend: settings.kind,
+        tables,
+        synced_at: started_at,
+    })
+}
+
+fn to_config(db: &rusqlite::Connection, settings: &SharedBackendSettings) -> SharedDbConfig {
+    SharedDbConfig {
+        kind: settings.kind,
+        url: settings.url.clone(),
+        has_auth_token: settings.auth_token.is_some(),
+        last_synced_at: shared::load_last_sync(db),
+    }
+}
@@ -5,6 +5,7 @@
 //! - List, create, update, and delete skills via IPC
 //! - Detect project patterns that could become reusable skills
 //! - Track skill usage analytics
+//! - Flag skills and agents whose instructions contradict CLAUDE.md
 //!
 //! DEPENDENCIES:
 //! - tauri - Command macro and State
@@ -12,6 +13,9 @@
 //! - models::skill - Skill, Pattern data types
 //! - chrono - Timestamp generation
 //! - uuid - Unique ID generation
+//! - commands::versions - Version snapshots on create/update/delete
+//! - core::skill_conflicts - Instruction sources, rule-based and AI conflict detection
+//! - core::ai / ai_queue / ai_audit - Optional AI pass for detect_skill_conflicts
 //!
 //! EXPORTS:
 //! - list_skills - List all skills for a project
@@ -20,41 +24,56 @@
 //! - delete_skill - Delete a skill by ID
 //! - detect_patterns - Analyze project to suggest skills
 //! - increment_skill_usage - Bump usage count for a skill
+//! - bulk_update_skills - Add/remove tags on many skills in one transaction
+//! - bulk_delete_skills - Delete many skills in one transaction
+//! - detect_skill_conflicts - Skills and agents that contradict the project's CLAUDE.md
 //!
 //! PATTERNS:
 //! - All commands use AppState for DB access
 //! - Skills are scoped to a project_id (or global if None)
 //! - detect_patterns analyzes project structure and tech stack
+//! - Tags are a JSON array in skills.tags; list_skills filters by tag in Rust after the query
 //!
 //! CLAUDE NOTES:
 //! - Skills reduce token usage by capturing reusable patterns
 //! - Pattern detection is heuristic-based (not AI-powered yet)
 //! - Timestamps use chrono::Utc::now() in RFC 3339 format
+//! - Every create/update writes a skill_versions row (see commands/versions.rs for rollback)
+//! - detect_skill_conflicts always runs the rule check; the AI pass is added when an API key
+//!   is set and use_ai is not false, and an AI failure falls back to the rule results
 
 use chrono::Utc;
 use tauri::State;
 use uuid::Uuid;
 
-use crate::db::{self, AppState};
-use crate::models::skill::{Pattern, Skill};
+use crate::commands::project::load_project;
+use crate::commands::versions;
+use crate::core::events::{self, AppEvent};
+use crate::core::{ai, ai_audit, ai_queue, safe_read, skill_conflicts};
+use crate::db::AppState;
+use crate::models::activity::ActivityType;
+use crate::models::ai_queue::AiJobKind;
+use crate::models::skill::{Pattern, Skill, SkillConflict};
 
 /// List all skills for a project (or global skills if project_id is None).
+/// When `tag` is provided, only skills carrying that tag (case-insensitive) are returned.
 #[tauri::command]
 pub async fn list_skills(
     project_id: Option<String>,
+    tag: Option<String>,
     state: State<'_, AppState>,
 ) -> Result<Vec<Skill>, String> {
     let db = state.db.lock().map_err(|e| format!("DB lock error: {}", e))?;
 
     let mut stmt = if project_id.is_some() {
         db.prepare(
-            "SELECT id, project_id, name, description, content, usage_count, created_at, updated_at
+            "SELECT id, project_id, name, description, content, usage_count, created_at, updated_at, tags
              FROM skills WHERE project_id = ?1 OR project_id IS NULL
              ORDER BY usage_count DESC, name ASC",
         )
     } else {
         db.prepare(
-            "SELECT id, project_id, name, description, content, usage_count, created_at, updated_at
+            "SELECT id, project_id, name, description, content, usage_count, created_at, updated_at, tags
              FROM skills ORDER BY usage_count DESC, name ASC",
         )
     }
@@ -67,10 +86,14 @@ pub async fn list_skills(
     }
     .map_err(|e| format!("Failed to query skills: {}", e))?;
 
-    let skills: Vec<Skill> = rows
+    let mut skills: Vec<Skill> = rows
         .filter_map(|r| r.ok())
         .collect();
 
+    if let Some(tag) = tag.as_deref().map(str::trim).filter(|t| !t.is_empty()) {
+        skills.retain(|s| s.tags.iter().any(|t| t.eq_ignore_ascii_case(tag)));
+    }
+
     Ok(skills)
 }
 
@@ -81,6 +104,7 @@ pub async fn create_skill(
     description: String,
     content: String,
     project_id: Option<String>,
+    tags: Option<Vec<String>>,
     state: State<'_, AppState>,
 ) -> Result<Skill, String> {
     let db = state.db.lock().map_err(|e| format!("DB lock error: {}", e))?;
@@ -88,17 +112,21 @@ pub async fn create_skill(
     let id = Uuid::new_v4().to_string();
     let now = Utc::now();
     let now_str = now.to_rfc3339();
+    let tags = normalize_tags(tags.unwrap_or_default());
+    let tags_json = serde_json::to_string(&tags).unwrap_or_else(|_| "[]".to_string());
 
     db.execute(
-        "INSERT INTO skills (id, project_id, name, description, content, usage_count, created_at, updated_at)
-         VALUES (?1, ?2, ?3, ?4, ?5, 0, ?6, ?7)",
-        rusqlite::params![id, project_id, name, description, content, now_str, now_str],
+        "INSERT INTO skills (id, project_id, name, description, content, usage_count, tags, created_at, updated_at)
+         VALUES (?1, ?2, ?3, ?4, ?5, 0, ?6, ?7, ?8)",
+        rusqlite::params![id, project_id, name, description, content, tags_json, now_str, now_str],
     )
     .map_err(|e| format!("Failed to insert skill: {}", e))?;
 
+    versions::record_version(&db, "skill", &id)?;
+
     // Log activity
     if let Some(ref pid) = project_id {
-        let _ = db::log_activity_db(&db, pid, "skill", &format!("Created skill: {}", &name));
+        events::publish(&db, AppEvent::activity(pid, ActivityType::Skill, &format!("Created skill: {}", &name)));
     }
 
     Ok(Skill {
@@ -108,18 +136,21 @@ pub async fn create_skill(
         content,
         project_id,
         usage_count: 0,
+        tags,
         created_at: now,
         updated_at: now,
     })
 }
 
 /// Update an existing skill's name, description, and content.
+/// Tags are replaced when provided and left unchanged when None.
 #[tauri::command]
 pub async fn update_skill(
     id: String,
     name: String,
     description: String,
     content: String,
+    tags: Option<Vec<String>>,
     state: State<'_, AppState>,
 ) -> Result<Skill, String> {
     let db = state.db.lock().map_err(|e| format!("DB lock error: {}", e))?;
@@ -127,6 +158,9 @@ pub async fn update_skill(
     let now = Utc::now();
     let now_str = now.to_rfc3339();
 
+    // Preserve the pre-update content for skills created before versioning existed
+    versions::ensure_version_baseline(&db, "skill", &id)?;
+
     let rows_affected = db
         .execute(
             "UPDATE skills SET name = ?1, description = ?2, content = ?3, updated_at = ?4 WHERE id = ?5",
@@ -138,10 +172,21 @@ pub async fn update_skill(
         return Err(format!("Skill not found: {}", id));
     }
 
+    if let Some(tags) = tags {
+        let tags_json = serde_json::to_string(&normalize_tags(tags)).unwrap_or_else(|_| "[]".to_string());
+        db.execute(
+            "UPDATE skills SET tags = ?1 WHERE id = ?2",
+            rusqlite::params![tags_json, id],
+        )
+        .map_err(|e| format!("Failed to update skill tags: {}", e))?;
+    }
+
+    versions::record_version(&db, "skill", &id)?;
+
     // Fetch the updated skill
     let skill = db
         .query_row(
-            "SELECT id, project_id, name, description, content, usage_count, created_at, updated_at
+            "SELECT id, project_id, name, description, content, usage_count, created_at, updated_at, tags
              FROM skills WHERE id = ?1",
             [&id],
             map_skill_row,
@@ -176,9 +221,11 @@ pub async fn delete_skill(
         return Err(format!("Skill not found: {}", id));
     }
 
+    let _ = versions::delete_versions(&db, "skill", &id);
+
     // Log activity
     if let Some((name, Some(pid))) = skill_info {
-        let _ = db::log_activity_db(&db, &pid, "skill", &format!("Deleted skill: {}", name));
+        events::publish(&db, AppEvent::activity(&pid, ActivityType::Skill, &format!("Deleted skill: {}", name)));
     }
 
     Ok(())
@@ -209,6 +256,107 @@ pub async fn increment_skill_usage(
     Ok(count)
 }
 
+/// Add and/or remove tags on many skills in one call.
+/// Runs in a single transaction; fails without changes if any skill ID is unknown.
+#[tauri::command]
+pub async fn bulk_update_skills(
+    ids: Vec<String>,
+    add_tags: Option<Vec<String>>,
+    remove_tags: Option<Vec<String>>,
+    state: State<'_, AppState>,
+) -> Result<Vec<Skill>, String> {
+    let db = state.db.lock().map_err(|e| format!("DB lock error: {}", e))?;
+
+    let add_tags = normalize_tags(add_tags.unwrap_or_default());
+    let remove_tags = normalize_tags(remove_tags.unwrap_or_default());
+    let now_str = Utc::now().to_rfc3339();
+
+    let tx = db
+        .unchecked_transaction()
+        .map_err(|e| format!("Failed to start transaction: {}", e))?;
+
+    let mut updated = Vec::with_capacity(ids.len());
+    for id in &ids {
+        let mut skill = tx
+            .query_row(
+                "SELECT id, project_id, name, description, content, usage_count, created_at, updated_at, tags
+                 FROM skills WHERE id = ?1",
+                [id],
+                map_skill_row,
+            )
+            .map_err(|_| format!("Skill not found: {}", id))?;
+
+        skill.tags = apply_tag_changes(&skill.tags, &add_tags, &remove_tags);
+        let tags_json = serde_json::to_string(&skill.tags).unwrap_or_else(|_| "[]".to_string());
+
+        tx.execute(
+            "UPDATE skills SET tags = ?1, updated_at = ?2 WHERE id = ?3",
+            rusqlite::params![tags_json, now_str, id],
+        )
+        .map_err(|e| format!("Failed to update skill: {}", e))?;
+
+        updated.push(skill);
+    }
+
+    tx.commit()
+        .map_err(|e| format!("Failed to commit bulk update: {}", e))?;
+
+    // Log one activity per affected project
+    let mut project_ids: Vec<&String> = updated.iter().filter_map(|s| s.project_id.as_ref()).collect();
+    project_ids.sort();
+    project_ids.dedup();
+    for pid in project_ids {
+        let count = updated.iter().filter(|s| s.project_id.as_ref() == Some(pid)).count();
+        events::publish(&db, AppEvent::activity(pid, ActivityType::Skill, &format!("Bulk updated tags on {} skills", count)));
+    }
+
+    Ok(updated)
+}
+
+/// Delete many skills in one call. Returns the number of skills deleted.
+/// Unknown IDs are skipped rather than treated as errors.
+#[tauri::command]
+pub async fn bulk_delete_skills(
+    ids: Vec<String>,
+    state: State<'_, AppState>,
+) -> Result<u32, String> {
+    let db = state.db.lock().map_err(|e| format!("DB lock error: {}", e))?;
+
+    let tx = db
+        .unchecked_transaction()
+        .map_err(|e| format!("Failed to start transaction: {}", e))?;
+
+    let mut deleted: Vec<Option<String>> = Vec::new();
+    for id in &ids {
+        let project_id: Option<Option<String>> = tx
+            .query_row("SELECT project_id FROM skills WHERE id = ?1", [id], |row| row.get(0))
+            .ok();
+
+        let rows_affected = tx
+            .execute("DELETE FROM skills WHERE id = ?1", [id])
+            .map_err(|e| format!("Failed to delete skill: {}", e))?;
+
+        if rows_affected > 0 {
+            versions::delete_versions(&tx, "skill", id)?;
+            deleted.push(project_id.flatten());
+        }
+    }
+
+    tx.commit()
+        .map_err(|e| format!("Failed to commit bulk delete: {}", e))?;
+
+    // Log one activity per affected project
+    let mut project_ids: Vec<&String> = deleted.iter().flatten().collect();
+    project_ids.sort();
+    project_ids.dedup();
+    for pid in project_ids {
+        let count = deleted.iter().filter(|p| p.as_ref() == Some(pid)).count();
+        events::publish(&db, AppEvent::activity(pid, ActivityType::Skill, &format!("Bulk deleted {} skills", count)));
+    }
+
+    Ok(deleted.len() as u32)
+}
+
 /// Detect patterns in a project that could become reusable skills.
 /// Analyzes project structure, tech stack, and common file patterns.
 #[tauri::command]
@@ -262,6 +410,54 @@ pub async fn detect_patterns(
     Ok(patterns)
 }
 
+/// Find skills and agents (stored and deployed under .claude/) whose instructions
+/// contradict the project's CLAUDE.md, e.g. "use yarn" against "use pnpm".
+/// `use_ai` defaults to true; without an API key only the rule check runs.
+#[tauri::command]
+pub async fn detect_skill_conflicts(
+    project_id: String,
+    use_ai: Option<bool>,
+    state: State<'_, AppState>,
+) -> Result<Vec<SkillConflict>, String> {
+    let (project, sources, api_key) = {
+        let db = state.db.lock().map_err(|e| format!("DB lock error: {}", e))?;
+        let project = load_project(&db, &project_id)?;
+        let sources = skill_conflicts::collect_sources(&db, &project.id, &project.path)?;
+        (project, sources, ai::get_api_key(&db).ok())
+    };
+
+    let claude_md = safe_read::read_text(std::path::Path::new(&project.path).join("CLAUDE.md"))
+        .map_err(|e| format!("Failed to read CLAUDE.md (generate one first): {}", e))?;
+    let conflicts = skill_conflicts::rule_conflicts(&claude_md, &sources);
+
+    let excludes = ai::load_ai_excludes(&project.path);
+    let api_key = match api_key {
+        Some(key) if use_ai.unwrap_or(true) && !ai::is_ai_excluded(&excludes, "CLAUDE.md") => key,
+        _ => return Ok(conflicts),
+    };
+    let ai_sources: Vec<_> = sources
+        .into_iter()
+        .filter(|s| s.path.as_deref().is_none_or(|p| !ai::is_ai_excluded(&excludes, p)))
+        .collect();
+    if ai_sources.is_empty() {
+        return Ok(conflicts);
+    }
+
+    let prompt = skill_conflicts::ai_prompt(&claude_md, &ai_sources);
+    let call = ai::call_claude(&state.http_client, &api_key, skill_conflicts::AI_SYSTEM_PROMPT, &prompt);
+    let queued = ai_queue::run(AiJobKind::Interactive, "skill conflicts", call);
+    let ai_conflicts = ai_audit::scoped(&project.id, "skill conflicts", queued)
+        .await
+        .and_then(|response| skill_conflicts::parse_ai_conflicts(&response, &ai_sources));
+    match ai_conflicts {
+        Ok(found) => Ok(skill_conflicts::merge_conflicts(conflicts, found)),
+        Err(e) => {
+            tracing::warn!(error = %e, "AI skill conflict check failed; returning rule-based conflicts");
+            Ok(conflicts)
+        }
+    }
+}
+
 // ---------------------------------------------------------------------------
 // Pattern detection heuristics
 // ---------------------------------------------------------------------------
@@ -942,6 +1138,29 @@ fn count_files_in_dir(dir: &std::path::Path) -> usize {
     count
 }
 
+/// Trim, drop empty, and de-duplicate (case-insensitive) a list of tags, preserving order.
+fn normalize_tags(tags: Vec<String>) -> Vec<String> {
+    let mut result: Vec<String> = Vec::new();
+    for tag in tags {
+        let trimmed = tag.trim();
+        if !trimmed.is_empty() && !result.iter().any(|t| t.eq_ignore_ascii_case(trimmed)) {
+            result.push(trimmed.to_string());
+        }
+    }
+    result
+}
+
+/// Apply tag additions and removals to an existing tag list.
+fn apply_tag_changes(current: &[String], add: &[String], remove: &[String]) -> Vec<String> {
+    let mut tags: Vec<String> = current
+        .iter()
+        .filter(|t| !remove.iter().any(|r| r.eq_ignore_ascii_case(t)))
+        .cloned()
+        .collect();
+    tags.extend(add.iter().cloned());
+    normalize_tags(tags)
+}
+
 // ---------------------------------------------------------------------------
 // Row mapping helper
 // ---------------------------------------------------------------------------
@@ -958,6 +1177,11 @@ fn map_skill_row(row: &rusqlite::Row<'_>) -> rusqlite::Result<Skill> {
         .map(|dt| dt.with_timezone(&Utc))
         .unwrap_or_else(|_| Utc::now());
 
+    let tags_json: Option<String> = row.get(8)?;
+    let tags: Vec<String> = tags_json
+        .and_then(|s| serde_json::from_str(&s).ok())
+        .unwrap_or_default();
+
     Ok(Skill {
         id: row.get(0)?,
         project_id: row.get(1)?,
@@ -965,7 +1189,31 @@ fn map_skill_row(row: &rusqlite::Row<'_>) -> rusqlite::Result<Skill> {
         description: row.get(3)?,
         content: row.get(4)?,
         usage_count: row.get(5)?,
+        tags,
         created_at,
         updated_at,
     })
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_normalize_tags() {
+        let tags = normalize_tags(vec![
+            " react ".to_string(),
+            "React".to_string(),
+            "".to_string(),
+            "testing".to_string(),
+        ]);
+        assert_eq!(tags, vec!["react".to_string(), "testing".to_string()]);
+    }
+
+    #[test]
+    fn test_apply_tag_changes() {
+        let current = vec!["react".to_string(), "legacy".to_string()];
+        let tags = apply_tag_changes(&current, &["team".to_string()], &["LEGACY".to_string()]);
+        assert_eq!(tags, vec!["react".to_string(), "team".to_string()]);
+    }
+}
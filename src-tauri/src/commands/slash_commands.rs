@@ -0,0 +1,319 @@
+//! @module commands/slash_commands
+//! @description Tauri IPC commands for deploying skills and saved prompts as Claude Code slash commands
+//!
+//! PURPOSE:
+//! - Write selected skills/prompts to a project's .claude/commands/ directory
+//! - Report drift between deployed command files and their Jumpstart sources
+//!
+//! DEPENDENCIES:
+//! - tauri - Command macro and State
+//! - db::AppState - Database connection (skills, prompt_analyses, projects)
+//! - core::slash_commands - Rendering, markers, and drift checks
+//! - core::claude_backup - .claude snapshot before the first file is written
+//! - models::slash_command - SlashCommandDeployResult, SlashCommandDrift types
+//!
+//! EXPORTS:
+//! - deploy_slash_commands - Generate/refresh command files for the given skill or prompt ids
+//! - check_slash_command_drift - Compare deployed command files against their sources
+//!
+//! PATTERNS:
+//! - ids may be skill ids or saved prompt (prompt_analyses) ids; skills are looked up first
+//! - Files not generated by Jumpstart are never overwritten
+//! - Files edited in the project are only replaced when overwrite_modified is true
+//! - Renaming a skill moves its command file on the next deploy
+//! - .claude/ is backed up once per deploy, before the first write (nothing is written if that fails)
+//!
+//! CLAUDE NOTES:
+//! - Saved prompts use enhanced_prompt when present; their name is the first words of the prompt
+//! - Deploying logs a "skill" activity for the project
+
+use std::fs;
+use std::path::Path;
+
+use rusqlite::{Connection, OptionalExtension};
+use tauri::State;
+
+use crate::core::claude_backup;
+use crate::core::slash_commands::{self, DeployedCommand, SlashCommandSource};
+use crate::core::events::{self, AppEvent};
+use crate::db::AppState;
+use crate::models::activity::ActivityType;
+use crate::models::slash_command::{SlashCommandDeployResult, SlashCommandDrift};
+
+/// Words of a saved prompt used as its command name.
+const PROMPT_NAME_WORDS: usize = 6;
+
+/// Generate `.claude/commands/<name>.md` for each skill or saved prompt id.
+#[tauri::command]
+pub async fn deploy_slash_commands(
+    project_id: String,
+    ids: Vec<String>,
+    overwrite_modified: Option<bool>,
+    state: State<'_, AppState>,
+) -> Result<Vec<SlashCommandDeployResult>, String> {
+    let db = state.db.lock().map_err(|e| format!("DB lock error: {}", e))?;
+    let project_path = project_path(&db, &project_id)?;
+
+    let results = deploy_to_project(&db, Path::new(&project_path), &ids, overwrite_modified.unwrap_or(false))?;
+
+    let written = results
+        .iter()
+        .filter(|r| r.status == "created" || r.status == "updated")
+        .count();
+    if written > 0 {
+        events::publish(
+            &db,
+            AppEvent::activity(
+                &project_id,
+                ActivityType::Skill,
+                &format!("Deployed {} slash command{}", written, if written == 1 { "" } else { "s" }),
+            ),
+        );
+    }
+    Ok(results)
+}
+
+/// Report the sync state of every Jumpstart-generated command in a project.
+#[tauri::command]
+pub async fn check_slash_command_drift(
+    project_id: String,
+    state: State<'_, AppState>,
+) -> Result<Vec<SlashCommandDrift>, String> {
+    let db = state.db.lock().map_err(|e| format!("DB lock error: {}", e))?;
+    let project_path = project_path(&db, &project_id)?;
+    Ok(drift_for_project(&db, Path::new(&project_path)))
+}
+
+fn project_path(db: &Connection, project_id: &str) -> Result<String, String> {
+    db.query_row("SELECT path FROM projects WHERE id = ?1", [project_id], |row| row.get(0))
+        .map_err(|e| format!("Project not found: {}", e))
+}
+
+/// Load a skill or saved prompt as a slash command source.
+fn load_source(db: &Connection, id: &str) -> Result<Option<SlashCommandSource>, String> {
+    let skill = db
+        .query_row(
+            "SELECT name, description, content FROM skills WHERE id = ?1",
+            [id],
+            |row| {
+                Ok(SlashCommandSource {
+                    source_type: "skill".to_string(),
+                    source_id: id.to_string(),
+                    name: row.get(0)?,
+                    description: row.get(1)?,
+                    body: row.get(2)?,
+                })
+            },
+        )
+        .optional()
+        .map_err(|e| format!("Failed to read skill: {}", e))?;
+    if skill.is_some() {
+        return Ok(skill);
+    }
+
+    let prompt: Option<(String, Option<String>)> = db
+        .query_row(
+            "SELECT prompt, enhanced_prompt FROM prompt_analyses WHERE id = ?1",
+            [id],
+            |row| Ok((row.get(0)?, row.get(1)?)),
+        )
+        .optional()
+        .map_err(|e| format!("Failed to read saved prompt: {}", e))?;
+
+    Ok(prompt.map(|(prompt, enhanced)| {
+        let first_line = prompt.lines().find(|l| !l.trim().is_empty()).unwrap_or("prompt").trim();
+        SlashCommandSource {
+            source_type: "prompt".to_string(),
+            source_id: id.to_string(),
+            name: first_line.split_whitespace().take(PROMPT_NAME_WORDS).collect::<Vec<_>>().join(" "),
+            description: first_line.chars().take(120).collect(),
+            body: enhanced.filter(|e| !e.trim().is_empty()).unwrap_or(prompt),
+        }
+    }))
+}
+
+fn skipped(id: &str, source: Option<&SlashCommandSource>, message: &str) -> SlashCommandDeployResult {
+    SlashCommandDeployResult {
+        source_id: id.to_string(),
+        source_type: source.map(|s| s.source_type.clone()),
+        command_name: source.map(|s| slash_commands::command_name(&s.name)),
+        file_path: None,
+        status: "skipped".to_string(),
+        message: Some(message.to_string()),
+    }
+}
+
+fn deploy_to_project(
+    db: &Connection,
+    project_path: &Path,
+    ids: &[String],
+    overwrite_modified: bool,
+) -> Result<Vec<SlashCommandDeployResult>, String> {
+    let commands_dir = project_path.join(slash_commands::COMMANDS_DIR);
+    let deployed = slash_commands::scan_deployed(project_path);
+    let mut results = Vec::new();
+    let mut backed_up = false;
+
+    for id in ids {
+        let Some(source) = load_source(db, id)? else {
+            results.push(skipped(id, None, "No skill or saved prompt with this id"));
+            continue;
+        };
+        let name = slash_commands::command_name(&source.name);
+        let path = commands_dir.join(format!("{}.md", name));
+        let rendered = slash_commands::render_command(&source);
+        let is_same_source =
+            |d: &DeployedCommand| d.source_type == source.source_type && d.source_id == source.source_id;
+
+        let status = match slash_commands::parse_deployed(&path) {
+            None if path.exists() => {
+                results.push(skipped(id, Some(&source), "A command with this name exists and was not generated by Jumpstart"));
+                continue;
+            }
+            None => "created",
+            Some(existing) if !is_same_source(&existing) => {
+                results.push(skipped(id, Some(&source), "A command with this name was generated from another skill or prompt"));
+                continue;
+            }
+            Some(existing) if existing.content == rendered => "unchanged",
+            Some(existing) if slash_commands::is_modified(&existing) && !overwrite_modified => {
+                results.push(skipped(id, Some(&source), "The command file was edited in the project; deploy with overwrite to replace it"));
+                continue;
+            }
+            Some(_) => "updated",
+        };
+
+        if status != "unchanged" {
+            if !backed_up {
+                claude_backup::snapshot(&project_path.to_string_lossy(), "Before deploying slash commands")?;
+                backed_up = true;
+            }
+            fs::create_dir_all(&commands_dir).map_err(|e| format!("Failed to create {}: {}", commands_dir.display(), e))?;
+            fs::write(&path, &rendered).map_err(|e| format!("Failed to write {}: {}", path.display(), e))?;
+        }
+
+        // The source was renamed: remove its old, unedited command file
+        for old in deployed.iter().filter(|d| is_same_source(d) && d.command_name != name) {
+            if !slash_commands::is_modified(old) {
+                let _ = fs::remove_file(&old.file_path);
+            }
+        }
+
+        results.push(SlashCommandDeployResult {
+            source_id: id.clone(),
+            source_type: Some(source.source_type.clone()),
+            command_name: Some(name),
+            file_path: Some(path.to_string_lossy().to_string()),
+            status: status.to_string(),
+            message: None,
+        });
+    }
+
+    Ok(results)
+}
+
+fn drift_for_project(db: &Connection, project_path: &Path) -> Vec<SlashCommandDrift> {
+    slash_commands::scan_deployed(project_path)
+        .into_iter()
+        .map(|deployed| {
+            let source = load_source(db, &deployed.source_id)
+                .ok()
+                .flatten()
+                .filter(|s| s.source_type == deployed.source_type);
+
+            let status = match &source {
+                None => "orphaned",
+                Some(_) if slash_commands::is_modified(&deployed) => "modified",
+                Some(s)
+                    if slash_commands::command_name(&s.name) != deployed.command_name
+                        || slash_commands::render_command(s) != deployed.content =>
+                {
+                    "outdated"
+                }
+                Some(_) => "in_sync",
+            };
+
+            SlashCommandDrift {
+                command_name: deployed.command_name.clone(),
+                file_path: deployed.file_path.to_string_lossy().to_string(),
+                source_type: deployed.source_type.clone(),
+                source_id: deployed.source_id.clone(),
+                source_name: source.map(|s| s.name),
+                status: status.to_string(),
+            }
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_support::memory_db;
+
+    fn setup_db() -> Connection {
+        let conn = memory_db();
+        conn.execute(
+            "INSERT INTO skills (id, project_id, name, description, content, usage_count, tags, created_at, updated_at)
+             VALUES ('s1', NULL, 'Review Checklist', 'Review a change', 'Check tests and docs.', 0, '[]', '2025-01-01T00:00:00Z', '2025-01-01T00:00:00Z')",
+            [],
+        )
+        .unwrap();
+        conn.execute(
+            "INSERT INTO prompt_analyses (id, project_id, prompt, created_at) VALUES ('pa1', NULL, 'Add pagination to the users endpoint', '2025-01-01T00:00:00Z')",
+            [],
+        )
+        .unwrap();
+        conn
+    }
+
+    fn statuses(results: &[SlashCommandDeployResult]) -> Vec<&str> {
+        results.iter().map(|r| r.status.as_str()).collect()
+    }
+
+    #[test]
+    fn test_deploy_and_drift() {
+        let conn = setup_db();
+        let dir = tempfile::tempdir().unwrap();
+        let ids = vec!["s1".to_string(), "pa1".to_string(), "missing".to_string()];
+
+        let results = deploy_to_project(&conn, dir.path(), &ids, false).unwrap();
+        assert_eq!(statuses(&results), vec!["created", "created", "skipped"]);
+        assert!(dir.path().join(".claude/commands/review-checklist.md").exists());
+        assert!(dir.path().join(".claude/commands/add-pagination-to-the-users-endpoint.md").exists());
+        assert_eq!(statuses(&deploy_to_project(&conn, dir.path(), &ids[..1], false).unwrap()), vec!["unchanged"]);
+
+        let drift: Vec<String> = drift_for_project(&conn, dir.path()).into_iter().map(|d| d.status).collect();
+        assert_eq!(drift, vec!["in_sync", "in_sync"]);
+
+        // Source edited in Jumpstart -> outdated; renamed -> old file removed on deploy
+        conn.execute("UPDATE skills SET content = 'Check tests.', name = 'PR Review' WHERE id = 's1'", []).unwrap();
+        conn.execute("DELETE FROM prompt_analyses WHERE id = 'pa1'", []).unwrap();
+        let drift: Vec<String> = drift_for_project(&conn, dir.path()).into_iter().map(|d| d.status).collect();
+        assert_eq!(drift, vec!["orphaned", "outdated"]);
+
+        assert_eq!(statuses(&deploy_to_project(&conn, dir.path(), &ids[..1], false).unwrap()), vec!["created"]);
+        assert!(!dir.path().join(".claude/commands/review-checklist.md").exists());
+
+        // Edited in the project -> modified, and not overwritten without the flag
+        let path = dir.path().join(".claude/commands/pr-review.md");
+        let edited = fs::read_to_string(&path).unwrap().replace("Check tests.", "Check tests twice.");
+        fs::write(&path, edited).unwrap();
+        conn.execute("UPDATE skills SET content = 'Check everything.' WHERE id = 's1'", []).unwrap();
+        assert_eq!(drift_for_project(&conn, dir.path())[1].status, "modified");
+        assert_eq!(statuses(&deploy_to_project(&conn, dir.path(), &ids[..1], false).unwrap()), vec!["skipped"]);
+        assert_eq!(statuses(&deploy_to_project(&conn, dir.path(), &ids[..1], true).unwrap()), vec!["updated"]);
+    }
+
+    #[test]
+    fn test_deploy_never_overwrites_user_commands() {
+        let conn = setup_db();
+        let dir = tempfile::tempdir().unwrap();
+        fs::create_dir_all(dir.path().join(".claude/commands")).unwrap();
+        fs::write(dir.path().join(".claude/commands/review-checklist.md"), "mine").unwrap();
+
+        let results = deploy_to_project(&conn, dir.path(), &["s1".to_string()], true).unwrap();
+        assert_eq!(statuses(&results), vec!["skipped"]);
+        assert_eq!(fs::read_to_string(dir.path().join(".claude/commands/review-checklist.md")).unwrap(), "mine");
+        assert!(drift_for_project(&conn, dir.path()).is_empty());
+    }
+}
@@ -0,0 +1,235 @@
+//! @module commands/sync
+//! @description Tauri IPC commands for encrypted settings and knowledge sync through a user-provided remote
+//!
+//! PURPOSE:
+//! - Configure the sync remote (S3, WebDAV, or git), its secret, and the snapshot passphrase
+//! - Pull the encrypted snapshot, merge it into local SQLite, and push the merged snapshot back
+//!
+//! DEPENDENCIES:
+//! - tauri - Command macro and State
+//! - db::AppState - Local database and shared HTTP client
+//! - db::shared - Shared table layout, local row helpers, and sync planning
+//! - core::sync - Settings, snapshot encryption, settings merge, and remote transfers
+//! - commands::imports - Merge engine for skills, agents, team templates, and setting conflicts
+//! - models::sync - SyncConfig, SyncRemote, SyncSnapshot, SyncReport types
+//!
+//! EXPORTS:
+//! - get_sync_config - Current remote, secret/passphrase presence, and last sync time
+//! - set_sync_config - Store or clear the remote, secret, and passphrase
+//! - run_sync - Pull, merge, and push the encrypted snapshot (conflicts are staged)
+//!
+//! PATTERNS:
+//! - Same flow as sync_shared_db: the DB lock is never held across a network call
+//! - set_sync_config keeps the stored secret/passphrase when the argument is None; "" clears it
+//!
+//! CLAUDE NOTES:
+//! - Every machine must use the same passphrase; it cannot be recovered from the remote
+//! - Conflicts (rows and settings) are resolved with resolve_import_conflicts; until then the
+//!   snapshot keeps the remote version so the other machine's change is not lost
+//! - The sync start time is stored as the last sync so edits made during a sync count as new
+
+use std::collections::HashMap;
+
+use chrono::Utc;
+use rusqlite::types::Value;
+use serde_json::{Map, Value as JsonValue};
+use tauri::State;
+
+use crate::commands::imports;
+use crate::core::merge;
+use crate::core::sync::{self as sync_core, SyncSettings, SNAPSHOT_VERSION};
+use crate::db::shared::{self, SHARED_TABLES};
+use crate::db::AppState;
+use crate::models::shared_db::SharedTableSync;
+use crate::models::sync::{SyncConfig, SyncRemote, SyncReport, SyncSnapshot};
+
+/// Return the sync configuration (never the secret or passphrase themselves).
+#[tauri::command]
+pub async fn get_sync_config(state: State<'_, AppState>) -> Result<SyncConfig, String> {
+    let db = state.db.lock().map_err(|e| format!("DB lock error: {}", e))?;
+    let settings = sync_core::load_settings(&db)?;
+    Ok(to_config(&db, &settings))
+}
+
+/// Configure the sync remote. A None remote turns sync off; secret and passphrase
+/// keep their stored values when None and are cleared by "".
+#[tauri::command]
+pub async fn set_sync_config(
+    remote: Option<SyncRemote>,
+    secret: Option<String>,
+    passphrase: Option<String>,
+    state: State<'_, AppState>,
+) -> Result<SyncConfig, String> {
+    if let Some(remote) = &remote {
+        sync_core::validate_remote(remote)?;
+    }
+    if passphrase.as_deref().is_some_and(|p| !p.is_empty() && p.chars().count() < 12) {
+        return Err("The sync passphrase must be at least 12 characters".to_string());
+    }
+
+    let db = state.db.lock().map_err(|e| format!("DB lock error: {}", e))?;
+    let existing = sync_core::load_settings(&db)?;
+    let settings = SyncSettings {
+        remote,
+        secret: secret.or(existing.secret),
+        passphrase: passphrase.or(existing.passphrase),
+    };
+    sync_core::save_settings(&db, &settings)?;
+
+    Ok(to_config(&db, &settings))
+}
+
+/// Sync with the configured remote. Skills, agents, and team templates go through the
+/// import merge engine; learnings merge by id with last-write-wins; settings merge
+/// three-way against the last sync. The merged snapshot is sealed and pushed back.
+#[tauri::command]
+pub async fn run_sync(state: State<'_, AppState>) -> Result<SyncReport, String> {
+    let started_at = Utc::now().to_rfc3339();
+    let settings = {
+        let db = state.db.lock().map_err(|e| format!("DB lock error: {}", e))?;
+        sync_core::load_settings(&db)?
+    };
+    let remote = settings.remote.ok_or("No sync remote is configured")?;
+    let passphrase = settings.passphrase.ok_or("Set a sync passphrase before syncing")?;
+    let secret = settings.secret.as_deref();
+
+    let fetched = sync_core::fetch(&state.http_client, &remote, secret).await?;
+    let incoming = match &fetched {
+        Some(object) => {
+            let plaintext = sync_core::open(&object.data, &passphrase)?;
+            let snapshot: SyncSnapshot =
+                serde_json::from_slice(&plaintext).map_err(|e| format!("Invalid sync snapshot: {}", e))?;
+            if snapshot.version > SNAPSHOT_VERSION {
+                return Err(format!(
+                    "The remote snapshot was written by a newer version of Project Jumpstart (format {})",
+                    snapshot.version
+                ));
+            }
+            snapshot
+        }
+        None => SyncSnapshot::default(),
+    };
+
+    let mut tables = Vec::new();
+    let mut outgoing = SyncSnapshot {
+        version: SNAPSHOT_VERSION,
+        created_at: started_at.clone(),
+        device: sync_core::device_name(),
+        ..Default::default()
+    };
+    let settings_merge = {
+        let db = state.db.lock().map_err(|e| format!("DB lock error: {}", e))?;
+        let last_synced_at = sync_core::load_last_sync(&db);
+
+        for table in SHARED_TABLES {
+            let remote_rows: Vec<Vec<Value>> = match incoming.tables.get(table.name) {
+                Some(rows) => rows
+                    .iter()
+                    .map(|obj| imports::row_from_json(table, obj))
+                    .collect::<Result<_, _>>()?,
+                None => Vec::new(),
+            };
+            let local = shared::read_local_rows(&db, table)?;
+            let plan = shared::plan_sync(table, local, remote_rows.clone());
+
+            let (pulled, conflicts, push) = if merge::is_mergeable(table) {
+                let (summary, blocked) =
+                    imports::merge_incoming(&db, table, remote_rows.clone(), last_synced_at.as_deref(), "sync")?;
+                let push: Vec<_> = plan
+                    .push
+                    .into_iter()
+                    .filter(|row| !blocked.contains(shared::text_at(row, 0)))
+                    .collect();
+                (summary.added + summary.updated, summary.conflicts, push)
+            } else {
+                (shared::apply_local_rows(&db, table, &plan.pull)?, 0, plan.push)
+            };
+
+            tables.push(SharedTableSync {
+                table: table.name.to_string(),
+                pushed: push.len() as u32,
+                pulled,
+                conflicts,
+            });
+            outgoing
+                .tables
+                .insert(table.name.to_string(), overlay_rows(table, remote_rows, push));
+        }
+
+        let local_settings = sync_core::local_settings(&db)?;
+        let base = sync_core::load_base_settings(&db);
+        let pending = imports::pending_setting_keys(&db)?;
+        let remote_settings = incoming
+            .settings
+            .iter()
+            .filter(|(key, value)| sync_core::is_syncable_setting(key, value))
+            .map(|(key, value)| (key.clone(), value.clone()))
+            .collect();
+        let settings_merge = sync_core::merge_settings(&local_settings, &remote_settings, base.as_ref(), &pending);
+        for (key, value) in &settings_merge.pull {
+            match value {
+                Some(value) => db.execute(
+                    "INSERT OR REPLACE INTO settings (key, value) VALUES (?1, ?2)",
+                    rusqlite::params![key, value],
+                ),
+                None => db.execute("DELETE FROM settings WHERE key = ?1", [key]),
+            }
+            .map_err(|e| format!("Failed to apply synced setting '{}': {}", key, e))?;
+        }
+        if !settings_merge.pull.is_empty() {
+            crate::core::ai::load_token_limits(&db);
+        }
+        for (key, value) in &settings_merge.conflicts {
+            imports::stage_setting_conflict(&db, key, value.as_deref(), &incoming.created_at, "sync")?;
+        }
+        outgoing.settings = settings_merge.outgoing.clone();
+        settings_merge
+    };
+
+    let plaintext = serde_json::to_vec(&outgoing).map_err(|e| format!("Failed to serialize sync snapshot: {}", e))?;
+    let sealed = sync_core::seal(&plaintext, &passphrase)?;
+    let read_version = fetched.as_ref().and_then(|object| object.version.as_deref());
+    sync_core::store(&state.http_client, &remote, secret, sealed, read_version).await?;
+
+    let db = state.db.lock().map_err(|e| format!("DB lock error: {}", e))?;
+    sync_core::record_sync(&db, &started_at, &outgoing.settings)?;
+
+    Ok(SyncReport {
+        remote: remote.kind,
+        tables,
+        settings_pulled: settings_merge.pull.len() as u32,
+        settings_pushed: settings_merge.pushed,
+        setting_conflicts: settings_merge.conflicts.len() as u32,
+        synced_at: started_at,
+    })
+}
+
+/// Remote rows with the pushed local rows replacing (or adding to) them by id.
+fn overlay_rows(
+    table: &shared::SharedTable,
+    remote: Vec<Vec<Value>>,
+    push: Vec<Vec<Value>>,
+) -> Vec<Map<String, JsonValue>> {
+    let mut order = Vec::new();
+    let mut rows: HashMap<String, Vec<Value>> = HashMap::new();
+    for row in remote.into_iter().chain(push) {
+        let id = shared::text_at(&row, 0).to_string();
+        if rows.insert(id.clone(), row).is_none() {
+            order.push(id);
+        }
+    }
+    order
+        .iter()
+        .filter_map(|id| rows.get(id))
+        .map(|row| imports::row_to_json(table, row))
+        .collect()
+}
+
+fn to_config(db: &rusqlite::Connection, settings: &SyncSettings) -> SyncConfig {
+    SyncConfig {
+        remote: settings.remote.clone(),
+        has_secret: settings.secret.is_some(),
+        has_passphrase: settings.passphrase.is_some(),
+        last_synced_at: sync_core::load_last_sync(db),
+    }
+}
@@ -9,7 +9,9 @@
 //! DEPENDENCIES:
 //! - tauri - Command macro and State
 //! - db::AppState - Database connection state
-//! - models::team_template - TeamTemplate, TeammateDef, TeamTaskDef, TeamHookDef, ProjectContext
+//! - models::team_template - TeamTemplate, TeammateDef, TeamTaskDef, TeamHookDef, ProjectContext, variable report types
+//! - core::test_runner - Detected test command for {{test_command}}
+//! - regex - {{variable}} placeholder matching
 //! - chrono - Timestamp generation
 //! - uuid - Unique ID generation
 //!
@@ -20,6 +22,8 @@
 //! - delete_team_template - Delete a template by ID
 //! - increment_team_template_usage - Bump usage count
 //! - generate_team_deploy_output - Generate deploy output string (with optional project context)
+//! - validate_team_template_variables - List resolved and unresolved {{variables}} before deploying
+//! - TEMPLATE_VARIABLES - Variable names templates may use
 //! - build_context_block - Generate "## Project Context" markdown block
 //! - apply_context_substitutions - Replace generic tech phrases with project-specific values
 //! - resolve_test_command - Map test framework name to CLI command
@@ -32,6 +36,9 @@
 //! - generate_team_deploy_output uses pure string templating, no AI
 //! - Deploy output matches real Claude Code Agent Teams behavior (natural language prompts)
 //! - When project context is provided, output is personalized with tech stack details
+//! - {{variable}} placeholders are filled from the project record (plus the detected test command)
+//!   when project_id is given, otherwise from the passed project context; generate_team_deploy_output
+//!   refuses to render while any placeholder is unresolved, so nothing half-filled gets written
 //!
 //! CLAUDE NOTES:
 //! - Mirrors agents.rs command pattern exactly
@@ -40,13 +47,36 @@
 //! - The lead agent uses TeammateTool.spawnTeam internally to create teammates
 //! - Tasks use TaskCreate/TaskUpdate with addBlockedBy for dependencies
 //! - Communication: write (to one teammate), broadcast (to all)
+//! - {{testCommand}} is the legacy spelling of {{test_command}} and resolves the same way
+
+use std::collections::BTreeMap;
 
 use chrono::Utc;
+use regex::Regex;
+use rusqlite::Connection;
 use tauri::State;
 use uuid::Uuid;
 
-use crate::db::{self, AppState};
-use crate::models::team_template::{TeamTemplate, TeammateDef, TeamTaskDef, TeamHookDef, ProjectContext};
+use crate::core::events::{self, AppEvent};
+use crate::core::test_runner;
+use crate::db::AppState;
+use crate::models::activity::ActivityType;
+use crate::models::team_template::{
+    ProjectContext, TeamHookDef, TeamTaskDef, TeamTemplate, TeammateDef, TemplateVariable, TemplateVariableReport,
+    UnresolvedTemplateVariable,
+};
+
+/// Variable names a template may use as {{name}}.
+pub const TEMPLATE_VARIABLES: &[&str] = &[
+    "project_name",
+    "language",
+    "framework",
+    "test_framework",
+    "test_command",
+    "build_tool",
+    "styling",
+    "database",
+];
 
 /// List all team templates for a project (or global if project_id is None).
 #[tauri::command]
@@ -135,7 +165,7 @@ pub async fn create_team_template(
 
     // Log activity
     if let Some(ref pid) = project_id {
-        let _ = db::log_activity_db(&db, pid, "team", &format!("Created team template: {}", &name));
+        events::publish(&db, AppEvent::activity(pid, ActivityType::Team, &format!("Created team template: {}", &name)));
     }
 
     Ok(TeamTemplate {
@@ -242,7 +272,7 @@ pub async fn delete_team_template(id: String, state: State<'_, AppState>) -> Res
     }
 
     if let Some((name, Some(pid))) = template_info {
-        let _ = db::log_activity_db(&db, &pid, "team", &format!("Deleted team template: {}", name));
+        events::publish(&db, AppEvent::activity(&pid, ActivityType::Team, &format!("Deleted team template: {}", name)));
     }
 
     Ok(())
@@ -270,46 +300,239 @@ pub async fn increment_team_template_usage(id: String, state: State<'_, AppState
     Ok(count)
 }
 
+/// Template fields sent by the UI for deployment.
+#[derive(Debug, Clone, serde::Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct DeployTemplate {
+    name: String,
+    description: String,
+    orchestration_pattern: String,
+    teammates: Vec<TeammateDef>,
+    tasks: Vec<TeamTaskDef>,
+    hooks: Vec<TeamHookDef>,
+    lead_spawn_instructions: String,
+}
+
+impl DeployTemplate {
+    /// Every text field that may contain variables, with a label for where it is.
+    fn texts(&self) -> Vec<(String, &str)> {
+        let mut texts = vec![
+            ("name".to_string(), self.name.as_str()),
+            ("description".to_string(), self.description.as_str()),
+            ("lead instructions".to_string(), self.lead_spawn_instructions.as_str()),
+        ];
+        for t in &self.teammates {
+            texts.push((format!("teammate {} role", t.role), t.role.as_str()));
+            texts.push((format!("teammate {} description", t.role), t.description.as_str()));
+            texts.push((format!("teammate {} spawn prompt", t.role), t.spawn_prompt.as_str()));
+        }
+        for t in &self.tasks {
+            texts.push((format!("task {} title", t.id), t.title.as_str()));
+            texts.push((format!("task {} description", t.id), t.description.as_str()));
+        }
+        for h in &self.hooks {
+            texts.push((format!("{} hook command", h.event), h.command.as_str()));
+            texts.push((format!("{} hook description", h.event), h.description.as_str()));
+        }
+        texts
+    }
+
+    /// Replace resolved variables in every text field.
+    fn substitute(&mut self, vars: &BTreeMap<String, String>) {
+        let sub = |text: &mut String| *text = substitute_variables(text, vars);
+        sub(&mut self.name);
+        sub(&mut self.description);
+        sub(&mut self.lead_spawn_instructions);
+        for t in &mut self.teammates {
+            sub(&mut t.role);
+            sub(&mut t.description);
+            sub(&mut t.spawn_prompt);
+        }
+        for t in &mut self.tasks {
+            sub(&mut t.title);
+            sub(&mut t.description);
+        }
+        for h in &mut self.hooks {
+            sub(&mut h.command);
+            sub(&mut h.description);
+        }
+    }
+}
+
 /// Generate deploy output for a team template.
 /// Format: "prompt" (paste-ready lead prompt), "script" (shell script), or "config" (directory config)
-/// Optionally accepts project context JSON to personalize output with the project's tech stack.
+/// With project_id, {{variables}} and the context block come from the project record; otherwise
+/// the optional project context JSON is used. Fails if any {{variable}} stays unresolved.
 #[tauri::command]
 pub async fn generate_team_deploy_output(
     template_json: String,
     format: String,
     project_context_json: Option<String>,
-    _state: State<'_, AppState>,
+    project_id: Option<String>,
+    state: State<'_, AppState>,
 ) -> Result<String, String> {
-    #[derive(serde::Deserialize)]
-    #[serde(rename_all = "camelCase")]
-    struct TemplateInput {
-        name: String,
-        description: String,
-        orchestration_pattern: String,
-        teammates: Vec<TeammateDef>,
-        tasks: Vec<TeamTaskDef>,
-        hooks: Vec<TeamHookDef>,
-        lead_spawn_instructions: String,
-    }
-
-    let template: TemplateInput =
+    let mut template: DeployTemplate =
         serde_json::from_str(&template_json).map_err(|e| format!("Invalid template JSON: {}", e))?;
 
-    let ctx: Option<ProjectContext> = match project_context_json {
-        Some(ref json) if !json.is_empty() => {
-            serde_json::from_str(json).ok()
-        }
-        _ => None,
+    let (ctx, test_command) = {
+        let db = state.db.lock().map_err(|e| format!("DB lock error: {}", e))?;
+        deploy_context(&db, project_id.as_deref(), project_context_json.as_deref())?
     };
+    let vars = template_variables(ctx.as_ref(), test_command.as_deref());
+    let unresolved = unresolved_variables(&template, &vars);
+    if !unresolved.is_empty() {
+        let names: Vec<String> = unresolved
+            .iter()
+            .map(|v| format!("{{{{{}}}}} ({})", v.name, v.locations.join(", ")))
+            .collect();
+        return Err(format!("Unresolved template variables: {}", names.join("; ")));
+    }
+    template.substitute(&vars);
 
+    let t = &template;
     match format.as_str() {
-        "prompt" => Ok(generate_prompt_output(&template.name, &template.description, &template.orchestration_pattern, &template.teammates, &template.tasks, &template.hooks, &template.lead_spawn_instructions, ctx.as_ref())),
-        "script" => Ok(generate_script_output(&template.name, &template.description, &template.orchestration_pattern, &template.teammates, &template.tasks, &template.hooks, &template.lead_spawn_instructions, ctx.as_ref())),
-        "config" => Ok(generate_config_output(&template.name, &template.description, &template.orchestration_pattern, &template.teammates, &template.tasks, &template.hooks, &template.lead_spawn_instructions, ctx.as_ref())),
+        "prompt" => Ok(generate_prompt_output(&t.name, &t.description, &t.orchestration_pattern, &t.teammates, &t.tasks, &t.hooks, &t.lead_spawn_instructions, ctx.as_ref())),
+        "script" => Ok(generate_script_output(&t.name, &t.description, &t.orchestration_pattern, &t.teammates, &t.tasks, &t.hooks, &t.lead_spawn_instructions, ctx.as_ref())),
+        "config" => Ok(generate_config_output(&t.name, &t.description, &t.orchestration_pattern, &t.teammates, &t.tasks, &t.hooks, &t.lead_spawn_instructions, ctx.as_ref())),
         _ => Err(format!("Unknown format: {}", format)),
     }
 }
 
+/// List the {{variables}} a template uses, their values for the project, and any that are
+/// unresolved. Run before deploying; nothing is written.
+#[tauri::command]
+pub async fn validate_team_template_variables(
+    template_json: String,
+    project_context_json: Option<String>,
+    project_id: Option<String>,
+    state: State<'_, AppState>,
+) -> Result<TemplateVariableReport, String> {
+    let template: DeployTemplate =
+        serde_json::from_str(&template_json).map_err(|e| format!("Invalid template JSON: {}", e))?;
+    let (ctx, test_command) = {
+        let db = state.db.lock().map_err(|e| format!("DB lock error: {}", e))?;
+        deploy_context(&db, project_id.as_deref(), project_context_json.as_deref())?
+    };
+    let vars = template_variables(ctx.as_ref(), test_command.as_deref());
+
+    let mut used: Vec<String> = Vec::new();
+    for (_, text) in template.texts() {
+        for name in find_variables(text) {
+            if !used.contains(&name) {
+                used.push(name);
+            }
+        }
+    }
+    let resolved = used
+        .iter()
+        .filter_map(|name| vars.get(name).map(|value| TemplateVariable { name: name.clone(), value: value.clone() }))
+        .collect();
+    let unresolved = unresolved_variables(&template, &vars);
+    Ok(TemplateVariableReport { ready: unresolved.is_empty(), resolved, unresolved })
+}
+
+/// Project context and detected test command for deployment. The project record wins over
+/// the UI-provided context JSON.
+fn deploy_context(
+    db: &Connection,
+    project_id: Option<&str>,
+    project_context_json: Option<&str>,
+) -> Result<(Option<ProjectContext>, Option<String>), String> {
+    let Some(pid) = project_id else {
+        let ctx = project_context_json.filter(|j| !j.is_empty()).and_then(|j| serde_json::from_str(j).ok());
+        return Ok((ctx, None));
+    };
+    let non_empty = |v: Option<String>| v.filter(|s| !s.trim().is_empty());
+    let (path, ctx) = db
+        .query_row(
+            "SELECT path, name, language, framework, testing, styling, database_tech FROM projects WHERE id = ?1",
+            [pid],
+            |row| {
+                Ok((
+                    row.get::<_, String>(0)?,
+                    ProjectContext {
+                        name: non_empty(row.get(1)?),
+                        language: non_empty(row.get(2)?),
+                        framework: non_empty(row.get(3)?),
+                        test_framework: non_empty(row.get(4)?),
+                        build_tool: None,
+                        styling: non_empty(row.get(5)?),
+                        database: non_empty(row.get(6)?),
+                    },
+                ))
+            },
+        )
+        .map_err(|_| format!("Project not found: {}", pid))?;
+    let test_command = test_runner::detect_test_framework(&path).map(|f| f.command);
+    Ok((Some(ctx), test_command))
+}
+
+/// Values for TEMPLATE_VARIABLES from project context. Missing values are left out.
+/// {{test_command}} prefers the detected command, then the test framework's usual command.
+fn template_variables(ctx: Option<&ProjectContext>, test_command: Option<&str>) -> BTreeMap<String, String> {
+    let mut vars = BTreeMap::new();
+    let mut put = |name: &str, value: Option<&str>| {
+        if let Some(v) = value.map(str::trim).filter(|v| !v.is_empty()) {
+            vars.insert(name.to_string(), v.to_string());
+        }
+    };
+    if let Some(c) = ctx {
+        put("project_name", c.name.as_deref());
+        put("language", c.language.as_deref());
+        put("framework", c.framework.as_deref());
+        put("test_framework", c.test_framework.as_deref());
+        put("build_tool", c.build_tool.as_deref());
+        put("styling", c.styling.as_deref());
+        put("database", c.database.as_deref());
+    }
+    let test_cmd = test_command
+        .map(str::to_string)
+        .or_else(|| ctx.and_then(|c| c.test_framework.as_deref()).map(|tf| resolve_test_command(tf).to_string()));
+    put("test_command", test_cmd.as_deref());
+    if let Some(cmd) = vars.get("test_command").cloned() {
+        vars.insert("testCommand".to_string(), cmd);
+    }
+    vars
+}
+
+fn variable_regex() -> Regex {
+    Regex::new(r"\{\{\s*([A-Za-z_][A-Za-z0-9_]*)\s*\}\}").expect("valid variable regex")
+}
+
+/// Variable names used in text, in order of first use.
+fn find_variables(text: &str) -> Vec<String> {
+    let mut names: Vec<String> = Vec::new();
+    for cap in variable_regex().captures_iter(text) {
+        let name = cap[1].to_string();
+        if !names.contains(&name) {
+            names.push(name);
+        }
+    }
+    names
+}
+
+/// Replace {{name}} (spaces inside the braces allowed) for every variable in vars.
+/// Unknown or unresolved placeholders are left as written.
+fn substitute_variables(text: &str, vars: &BTreeMap<String, String>) -> String {
+    variable_regex()
+        .replace_all(text, |cap: &regex::Captures| vars.get(&cap[1]).cloned().unwrap_or_else(|| cap[0].to_string()))
+        .into_owned()
+}
+
+/// Placeholders with no value, with every location they appear in.
+fn unresolved_variables(template: &DeployTemplate, vars: &BTreeMap<String, String>) -> Vec<UnresolvedTemplateVariable> {
+    let mut unresolved: Vec<UnresolvedTemplateVariable> = Vec::new();
+    for (location, text) in template.texts() {
+        for name in find_variables(text).into_iter().filter(|n| !vars.contains_key(n)) {
+            match unresolved.iter_mut().find(|u| u.name == name) {
+                Some(u) => u.locations.push(location.clone()),
+                None => unresolved.push(UnresolvedTemplateVariable { name, locations: vec![location.clone()] }),
+            }
+        }
+    }
+    unresolved
+}
+
 // ---------------------------------------------------------------------------
 // Output generators (pure string templating)
 //
@@ -1057,6 +1280,66 @@ mod tests {
         assert!(result.contains("{{testCommand}}"));
     }
 
+    fn make_template(spawn_prompt: &str, hook_command: &str) -> DeployTemplate {
+        DeployTemplate {
+            name: "{{project_name}} Team".to_string(),
+            description: "Build things".to_string(),
+            orchestration_pattern: "leader".to_string(),
+            teammates: vec![TeammateDef {
+                role: "Dev".to_string(),
+                description: "Developer".to_string(),
+                spawn_prompt: spawn_prompt.to_string(),
+            }],
+            tasks: vec![],
+            hooks: vec![TeamHookDef {
+                event: "PostToolUse".to_string(),
+                command: hook_command.to_string(),
+                description: "Run tests".to_string(),
+            }],
+            lead_spawn_instructions: "Lead it".to_string(),
+        }
+    }
+
+    #[test]
+    fn test_template_variables_resolve_and_substitute() {
+        let ctx = make_ctx();
+        let vars = template_variables(Some(&ctx), Some("pnpm vitest run"));
+        assert_eq!(vars["project_name"], "My App");
+        assert_eq!(vars["test_command"], "pnpm vitest run");
+        assert_eq!(vars["testCommand"], "pnpm vitest run");
+
+        let fallback = template_variables(Some(&ctx), None);
+        assert_eq!(fallback["test_command"], "npx vitest run");
+
+        let mut template = make_template("Write {{ language }} code, then run {{test_command}}", "{{testCommand}}");
+        assert!(unresolved_variables(&template, &vars).is_empty());
+        template.substitute(&vars);
+        assert_eq!(template.name, "My App Team");
+        assert_eq!(template.teammates[0].spawn_prompt, "Write TypeScript code, then run pnpm vitest run");
+        assert_eq!(template.hooks[0].command, "pnpm vitest run");
+    }
+
+    #[test]
+    fn test_unresolved_variables_list_locations() {
+        let ctx = ProjectContext {
+            name: Some("My App".to_string()),
+            language: None,
+            framework: None,
+            test_framework: None,
+            build_tool: None,
+            styling: None,
+            database: None,
+        };
+        let vars = template_variables(Some(&ctx), None);
+        let template = make_template("Use {{language}} and {{test_command}}; ask {{owner}}", "{{test_command}}");
+        let unresolved = unresolved_variables(&template, &vars);
+
+        let names: Vec<&str> = unresolved.iter().map(|u| u.name.as_str()).collect();
+        assert_eq!(names, vec!["language", "test_command", "owner"]);
+        assert_eq!(unresolved[1].locations, vec!["teammate Dev spawn prompt", "PostToolUse hook command"]);
+        assert_eq!(find_variables("{{a}} {{ b }} {{a}} {not}"), vec!["a", "b"]);
+    }
+
     #[test]
     fn test_generate_prompt_output_includes_context() {
         let ctx = make_ctx();
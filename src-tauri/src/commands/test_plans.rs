@@ -12,8 +12,13 @@
 //! - db::AppState - Database connection state
 //! - models::test_plan - Test plan data types
 //! - core::test_runner - Test framework detection and execution
+//! - core::monitor - Window-scoped "test-run-progress" events
 //! - chrono - Timestamp generation
 //! - uuid - Unique ID generation
+//! - core::ai_queue - Test suggestion calls queued behind interactive work
+//! - core::ai_audit - Test suggestion calls count toward the project's audit log
+//! - core::test_env - Per-plan env vars, .env file loading, and secret masking
+//! - core::command_guard - Command validation for dry runs
 //!
 //! EXPORTS:
 //! - list_test_plans - List all test plans for a project
@@ -25,7 +30,11 @@
 //! - create_test_case - Create a new test case
 //! - update_test_case - Update an existing test case
 //! - delete_test_case - Delete a test case
-//! - run_test_plan - Execute tests for a plan
+//! - run_test_plan - Execute tests for a plan (with its environment; secrets masked in stored output)
+//! - get_test_plan_env / save_test_plan_env - Per-plan env vars and .env file loading
+//! - dry_run_test_command - Resolved command and environment without running tests
+//! - cancel_test_run - Stop a running test run (its process is killed and the run marked cancelled)
+//! - get_test_plan_timeout / set_test_plan_timeout - Per-plan test run timeout in seconds
 //! - get_test_runs - Get test run history for a plan
 //! - detect_test_framework - Detect test framework for a project
 //! - generate_test_suggestions - AI-powered test case generation
@@ -33,15 +42,21 @@
 //! - update_tdd_session - Update TDD session phase/status
 //! - get_tdd_session - Get current TDD session
 //! - list_tdd_sessions - List TDD sessions for a project
+//! - get_tdd_analytics - TDD cycle times, cycles per session, and weekly trends
 //! - check_test_staleness - Detect stale tests by comparing source vs test modification
 //! - generate_subagent_config - Generate Claude Code subagent markdown
 //! - generate_hooks_config - Generate PostToolUse hooks JSON
+//! - export_test_plan - Export a plan as round-trippable JSON or a Markdown report
+//! - import_test_plan - Create a plan and its cases from exported JSON
+//! - find_corresponding_test_file - Test file for a source file ("__inline__" for Rust inline tests)
 //!
 //! PATTERNS:
 //! - All commands use AppState for DB access
 //! - Test plans are scoped to a project_id
 //! - Test runs track historical execution results
 //! - TDD sessions guide users through red/green/refactor cycle
+//! - run_test_plan emits "test-run-progress" at start and finish to the main window and
+//!   the plan's monitor window ("monitor-test_plan-<plan_id>")
 //!
 //! CLAUDE NOTES:
 //! - TestPlanStatus: draft, active, archived
@@ -49,19 +64,47 @@
 //! - TestPriority: low, medium, high, critical
 //! - TDDPhase: red (failing test), green (minimal pass), refactor (cleanup)
 //! - AI suggestions require API key from settings
+//! - Exported JSON holds definitions only (see TestPlanExport); the Markdown report adds case
+//!   status and the latest run for PR descriptions and wikis
+//! - import_test_plan always creates a new plan; cases start as pending
+//! - Test env: the plan's vars override the env file (default .env.test, off unless enabled);
+//!   secret values never leave the backend unmasked
+//! - Running runs keep a cancel flag in AppState.test_runs (run_id -> flag) until they finish;
+//!   a run that hits its plan's timeout is stored as timed_out, a cancelled one as cancelled
+
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::Arc;
+use std::time::Duration;
 
 use chrono::Utc;
-use tauri::State;
+use tauri::{AppHandle, Runtime, State};
 use uuid::Uuid;
 
-use crate::db::{self, AppState};
+use crate::core::ai_queue;
+use crate::core::command_guard;
+use crate::core::events::{self, AppEvent};
+use crate::db::AppState;
+use crate::core::monitor::{self, MonitorKind, TestRunProgress};
+use crate::core::proc::ProcLimits;
+use crate::core::tdd_analytics;
+use crate::core::test_env;
 use crate::core::test_runner::{self};
+use crate::models::activity::ActivityType;
+use crate::models::ai_queue::AiJobKind;
 use crate::models::test_plan::{
-    GeneratedTestSuggestion, TDDPhase, TDDPhaseStatus, TDDSession, TestCase,
+    GeneratedTestSuggestion, TDDAnalytics, TDDPhase, TDDPhaseEvent, TDDPhaseStatus, TDDSession, TestCase,
     TestCaseStatus, TestFrameworkInfo, TestPlan, TestPlanStatus, TestPlanSummary, TestPriority,
-    TestRun, TestRunStatus, TestStalenessReport, TestStalenessResult, TestType,
+    TestCaseExport, TestCommandPreview, TestPlanEnv, TestPlanExport, TestRun, TestRunStatus, TestStalenessReport,
+    TestStalenessResult, TestType, TEST_PLAN_EXPORT_VERSION,
 };
 
+/// Settings key prefix for per-plan test timeouts ("test_plans.timeout_secs.<plan_id>").
+const TEST_TIMEOUT_SETTING: &str = "test_plans.timeout_secs";
+
+/// Allowed per-plan timeout range in seconds.
+const MIN_TEST_TIMEOUT_SECS: u32 = 10;
+const MAX_TEST_TIMEOUT_SECS: u32 = 4 * 60 * 60;
+
 // =============================================================================
 // Test Discovery
 // =============================================================================
@@ -219,7 +262,7 @@ pub async fn create_test_plan(
     .map_err(|e| format!("Failed to create test plan: {}", e))?;
 
     // Log activity
-    let _ = db::log_activity_db(&db, &project_id, "test_plan", &format!("Created test plan: {}", &name));
+    events::publish(&db, AppEvent::activity(&project_id, ActivityType::Test, &format!("Created test plan: {}", &name)));
 
     Ok(TestPlan {
         id,
@@ -308,6 +351,9 @@ pub async fn delete_test_plan(id: String, state: State<'_, AppState>) -> Result<
     db.execute("DELETE FROM test_runs WHERE plan_id = ?1", [&id])
         .map_err(|e| format!("Failed to delete test runs: {}", e))?;
 
+    db.execute("DELETE FROM test_plan_env WHERE plan_id = ?1", [&id])
+        .map_err(|e| format!("Failed to delete test environment: {}", e))?;
+
     // Delete test cases
     db.execute("DELETE FROM test_cases WHERE plan_id = ?1", [&id])
         .map_err(|e| format!("Failed to delete test cases: {}", e))?;
@@ -323,7 +369,7 @@ pub async fn delete_test_plan(id: String, state: State<'_, AppState>) -> Result<
 
     // Log activity
     if let Some((name, project_id)) = plan_info {
-        let _ = db::log_activity_db(&db, &project_id, "test_plan", &format!("Deleted test plan: {}", name));
+        events::publish(&db, AppEvent::activity(&project_id, ActivityType::Test, &format!("Deleted test plan: {}", name)));
     }
 
     Ok(())
@@ -495,12 +541,145 @@ pub async fn detect_project_test_framework(
     Ok(test_runner::detect_test_framework(&project_path))
 }
 
-/// Run tests for a test plan.
+/// A plan's test environment, secret values masked.
+#[tauri::command]
+pub async fn get_test_plan_env(plan_id: String, state: State<'_, AppState>) -> Result<TestPlanEnv, String> {
+    let db = state.db.lock().map_err(|e| format!("DB lock error: {}", e))?;
+    Ok(test_env::masked(&test_env::load_plan_env(&db, &plan_id)?))
+}
+
+/// Save a plan's test environment. Secrets left as the mask keep their stored value.
+#[tauri::command]
+pub async fn save_test_plan_env(env: TestPlanEnv, state: State<'_, AppState>) -> Result<TestPlanEnv, String> {
+    let db = state.db.lock().map_err(|e| format!("DB lock error: {}", e))?;
+    db.query_row("SELECT 1 FROM test_plans WHERE id = ?1", [&env.plan_id], |_| Ok(()))
+        .map_err(|_| format!("Test plan not found: {}", env.plan_id))?;
+    test_env::save_plan_env(&db, &env)?;
+    Ok(test_env::masked(&test_env::load_plan_env(&db, &env.plan_id)?))
+}
+
+/// Resolve what run_test_plan would execute (framework, command, env with secrets
+/// masked, warnings) without running anything.
+#[tauri::command]
+pub async fn dry_run_test_command(
+    plan_id: String,
+    project_path: String,
+    with_coverage: bool,
+    state: State<'_, AppState>,
+) -> Result<TestCommandPreview, String> {
+    let framework = test_runner::detect_test_framework(&project_path)
+        .ok_or_else(|| "No test framework detected".to_string())?;
+    let env = {
+        let db = state.db.lock().map_err(|e| format!("DB lock error: {}", e))?;
+        test_env::load_plan_env(&db, &plan_id)?
+    };
+    let resolved = test_env::resolve_env(&project_path, &env)?;
+
+    let command = test_runner::test_command(&framework, with_coverage).to_string();
+    let mut warnings = resolved.warnings.clone();
+    if let Err(e) = command_guard::validate_command(&project_path, &command) {
+        warnings.push(e);
+    }
+
+    Ok(TestCommandPreview {
+        framework: framework.name,
+        command,
+        working_dir: project_path,
+        env: resolved.preview(),
+        env_file: resolved.env_file,
+        warnings,
+    })
+}
+
+/// A plan's test timeout in seconds (ProcLimits::TESTS when unset).
+fn plan_timeout_secs(db: &rusqlite::Connection, plan_id: &str) -> u32 {
+    db.query_row(
+        "SELECT value FROM settings WHERE key = ?1",
+        [format!("{}.{}", TEST_TIMEOUT_SETTING, plan_id)],
+        |row| row.get::<_, String>(0),
+    )
+    .ok()
+    .and_then(|v| v.parse().ok())
+    .unwrap_or(ProcLimits::TESTS.timeout.as_secs() as u32)
+}
+
+/// Resolve a plan's environment and record its run as 'running'.
+/// Returns the environment and the plan's timeout in seconds.
+fn start_test_run(
+    state: &AppState,
+    run_id: &str,
+    plan_id: &str,
+    project_path: &str,
+    started_at: &str,
+) -> Result<(test_env::ResolvedEnv, u32), String> {
+    let db = state.db.lock().map_err(|e| format!("DB lock error: {}", e))?;
+    let env = test_env::resolve_env(project_path, &test_env::load_plan_env(&db, plan_id)?)?;
+    db.execute(
+        "INSERT INTO test_runs (id, plan_id, status, started_at)
+         VALUES (?1, ?2, 'running', ?3)",
+        rusqlite::params![run_id, plan_id, started_at],
+    )
+    .map_err(|e| format!("Failed to create test run: {}", e))?;
+    Ok((env, plan_timeout_secs(&db, plan_id)))
+}
+
+/// Get a plan's test run timeout in seconds.
+#[tauri::command]
+pub async fn get_test_plan_timeout(plan_id: String, state: State<'_, AppState>) -> Result<u32, String> {
+    let db = state.db.lock().map_err(|e| format!("DB lock error: {}", e))?;
+    Ok(plan_timeout_secs(&db, &plan_id))
+}
+
+/// Set a plan's test run timeout in seconds. None restores the default.
+#[tauri::command]
+pub async fn set_test_plan_timeout(
+    plan_id: String,
+    timeout_secs: Option<u32>,
+    state: State<'_, AppState>,
+) -> Result<u32, String> {
+    let db = state.db.lock().map_err(|e| format!("DB lock error: {}", e))?;
+    let key = format!("{}.{}", TEST_TIMEOUT_SETTING, plan_id);
+    match timeout_secs {
+        Some(secs) => {
+            if !(MIN_TEST_TIMEOUT_SECS..=MAX_TEST_TIMEOUT_SECS).contains(&secs) {
+                return Err(format!(
+                    "Timeout must be between {} and {} seconds",
+                    MIN_TEST_TIMEOUT_SECS, MAX_TEST_TIMEOUT_SECS
+                ));
+            }
+            db.execute(
+                "INSERT OR REPLACE INTO settings (key, value) VALUES (?1, ?2)",
+                rusqlite::params![key, secs.to_string()],
+            )
+            .map_err(|e| format!("Failed to save test timeout: {}", e))?;
+        }
+        None => {
+            db.execute("DELETE FROM settings WHERE key = ?1", [&key])
+                .map_err(|e| format!("Failed to clear test timeout: {}", e))?;
+        }
+    }
+    Ok(plan_timeout_secs(&db, &plan_id))
+}
+
+/// Cancel a running test run. The test process is killed; run_test_plan records the run as cancelled.
+#[tauri::command]
+pub async fn cancel_test_run(run_id: String, state: State<'_, AppState>) -> Result<(), String> {
+    let runs = state.test_runs.lock().map_err(|e| format!("Test run lock error: {}", e))?;
+    let cancel = runs
+        .get(&run_id)
+        .ok_or_else(|| format!("Test run is not running: {}", run_id))?;
+    cancel.store(true, Ordering::SeqCst);
+    Ok(())
+}
+
+/// Run tests for a test plan. The run can be stopped with cancel_test_run and is
+/// killed after the plan's timeout.
 #[tauri::command]
-pub async fn run_test_plan(
+pub async fn run_test_plan<R: Runtime>(
     plan_id: String,
     project_path: String,
     with_coverage: bool,
+    app: AppHandle<R>,
     state: State<'_, AppState>,
 ) -> Result<TestRun, String> {
     // Detect framework
@@ -512,18 +691,52 @@ pub async fn run_test_plan(
     let now = Utc::now();
     let now_str = now.to_rfc3339();
 
-    {
-        let db = state.db.lock().map_err(|e| format!("DB lock error: {}", e))?;
-        db.execute(
-            "INSERT INTO test_runs (id, plan_id, status, started_at)
-             VALUES (?1, ?2, 'running', ?3)",
-            rusqlite::params![run_id, plan_id, now_str],
-        )
-        .map_err(|e| format!("Failed to create test run: {}", e))?;
-    }
+    // Register the run so cancel_test_run can stop it. This happens before the 'running'
+    // row is inserted, so a failure here cannot leave a run that never finishes.
+    let cancel = Arc::new(AtomicBool::new(false));
+    state
+        .test_runs
+        .lock()
+        .map_err(|e| format!("Test run lock error: {}", e))?
+        .insert(run_id.clone(), cancel.clone());
+
+    let (env, timeout_secs) = match start_test_run(&state, &run_id, &plan_id, &project_path, &now_str) {
+        Ok(started) => started,
+        Err(e) => {
+            if let Ok(mut runs) = state.test_runs.lock() {
+                runs.remove(&run_id);
+            }
+            return Err(e);
+        }
+    };
+    let secrets = env.secrets();
+
+    let emit_progress = |status: &str, passed: Option<u32>, failed: Option<u32>, error: Option<String>| {
+        let progress = TestRunProgress {
+            plan_id: plan_id.clone(),
+            run_id: run_id.clone(),
+            status: status.to_string(),
+            passed,
+            failed,
+            error,
+        };
+        monitor::emit_scoped(&app, MonitorKind::TestPlan, &plan_id, monitor::TEST_RUN_PROGRESS_EVENT, progress);
+    };
+    emit_progress("running", None, None, None);
 
     // Run tests (this can take a while)
-    let result = test_runner::run_tests(&project_path, &framework, with_coverage);
+    let limits = ProcLimits::new(Duration::from_secs(timeout_secs as u64), ProcLimits::TESTS.max_output_bytes);
+    let result = test_runner::run_tests(&project_path, &framework, with_coverage, &env.pairs(), limits, &cancel);
+    if let Ok(mut runs) = state.test_runs.lock() {
+        runs.remove(&run_id);
+    }
+    let result = result
+        .map(|r| test_runner::TestExecutionResult {
+            stdout: test_env::mask_secrets(&r.stdout, &secrets),
+            stderr: test_env::mask_secrets(&r.stderr, &secrets),
+            ..r
+        })
+        .map_err(|e| test_env::mask_secrets(&e, &secrets));
 
     // Update the run record with results
     let db = state.db.lock().map_err(|e| format!("DB lock error: {}", e))?;
@@ -532,7 +745,15 @@ pub async fn run_test_plan(
 
     match result {
         Ok(exec_result) => {
-            let status = if exec_result.success { "passed" } else { "failed" };
+            let status = if exec_result.cancelled {
+                "cancelled"
+            } else if exec_result.timed_out {
+                "timed_out"
+            } else if exec_result.success {
+                "passed"
+            } else {
+                "failed"
+            };
 
             db.execute(
                 "UPDATE test_runs SET status = ?1, total_tests = ?2, passed_tests = ?3, failed_tests = ?4,
@@ -553,6 +774,7 @@ pub async fn run_test_plan(
                 ],
             )
             .map_err(|e| format!("Failed to update test run: {}", e))?;
+            emit_progress(status, Some(exec_result.passed), Some(exec_result.failed), None);
 
             // Update test case statuses based on results
             for test_result in &exec_result.test_results {
@@ -573,11 +795,15 @@ pub async fn run_test_plan(
                 [&plan_id],
                 |row| row.get(0),
             ) {
-                let msg = format!(
-                    "Test run completed: {} passed, {} failed",
-                    exec_result.passed, exec_result.failed
-                );
-                let _ = db::log_activity_db(&db, &project_id, "test_run", &msg);
+                let msg = match status {
+                    "cancelled" => "Test run cancelled".to_string(),
+                    "timed_out" => format!("Test run timed out after {}s", timeout_secs),
+                    _ => format!(
+                        "Test run completed: {} passed, {} failed",
+                        exec_result.passed, exec_result.failed
+                    ),
+                };
+                events::publish(&db, AppEvent::activity(&project_id, ActivityType::Test, &msg));
             }
 
             // Return the completed run
@@ -599,6 +825,7 @@ pub async fn run_test_plan(
                 rusqlite::params![e, completed_str, run_id],
             )
             .ok();
+            emit_progress("failed", None, None, Some(e.clone()));
 
             Err(format!("Test execution failed: {}", e))
         }
@@ -632,6 +859,238 @@ pub async fn get_test_runs(
     Ok(runs)
 }
 
+// =============================================================================
+// Test Plan Export / Import
+// =============================================================================
+
+/// Export a test plan. `format` is "json" (re-importable with import_test_plan)
+/// or "markdown" (a report for PRs and wikis).
+#[tauri::command]
+pub async fn export_test_plan(
+    plan_id: String,
+    format: String,
+    state: State<'_, AppState>,
+) -> Result<String, String> {
+    let db = state.db.lock().map_err(|e| format!("DB lock error: {}", e))?;
+    export_test_plan_db(&db, &plan_id, &format)
+}
+
+/// Import a test plan exported as JSON into a project. Returns the new plan.
+#[tauri::command]
+pub async fn import_test_plan(
+    project_id: String,
+    json: String,
+    state: State<'_, AppState>,
+) -> Result<TestPlan, String> {
+    let db = state.db.lock().map_err(|e| format!("DB lock error: {}", e))?;
+    let plan = import_test_plan_db(&db, &project_id, &json)?;
+    events::publish(&db, AppEvent::activity(&project_id, ActivityType::Test, &format!("Imported test plan: {}", plan.name)));
+    Ok(plan)
+}
+
+fn export_test_plan_db(db: &rusqlite::Connection, plan_id: &str, format: &str) -> Result<String, String> {
+    let plan: TestPlan = db
+        .query_row(
+            "SELECT id, project_id, name, description, status, target_coverage, created_at, updated_at
+             FROM test_plans WHERE id = ?1",
+            [plan_id],
+            map_test_plan_row,
+        )
+        .map_err(|e| format!("Test plan not found: {}", e))?;
+
+    let mut stmt = db
+        .prepare(
+            "SELECT id, plan_id, name, description, file_path, test_type, priority, status, last_run_at, created_at, updated_at
+             FROM test_cases WHERE plan_id = ?1
+             ORDER BY
+                CASE priority WHEN 'critical' THEN 1 WHEN 'high' THEN 2 WHEN 'medium' THEN 3 ELSE 4 END,
+                name ASC",
+        )
+        .map_err(|e| format!("Failed to prepare query: {}", e))?;
+    let cases: Vec<TestCase> = stmt
+        .query_map([plan_id], map_test_case_row)
+        .map_err(|e| format!("Failed to query test cases: {}", e))?
+        .filter_map(|r| r.ok())
+        .collect();
+
+    match format {
+        "json" => {
+            let export = TestPlanExport {
+                version: TEST_PLAN_EXPORT_VERSION,
+                name: plan.name,
+                description: plan.description,
+                status: plan.status,
+                target_coverage: plan.target_coverage,
+                cases: cases
+                    .into_iter()
+                    .map(|c| TestCaseExport {
+                        name: c.name,
+                        description: c.description,
+                        file_path: c.file_path,
+                        test_type: c.test_type,
+                        priority: c.priority,
+                    })
+                    .collect(),
+            };
+            serde_json::to_string_pretty(&export).map_err(|e| format!("Failed to serialize test plan: {}", e))
+        }
+        "markdown" => {
+            let last_run = db
+                .query_row(
+                    "SELECT id, plan_id, status, total_tests, passed_tests, failed_tests, skipped_tests,
+                            duration_ms, coverage_percent, stdout, stderr, started_at, completed_at
+                     FROM test_runs WHERE plan_id = ?1 AND status != 'running'
+                     ORDER BY started_at DESC LIMIT 1",
+                    [plan_id],
+                    map_test_run_row,
+                )
+                .ok();
+            Ok(render_test_plan_markdown(&plan, &cases, last_run.as_ref()))
+        }
+        other => Err(format!("Unsupported export format: {} (use \"json\" or \"markdown\")", other)),
+    }
+}
+
+fn import_test_plan_db(db: &rusqlite::Connection, project_id: &str, json: &str) -> Result<TestPlan, String> {
+    let export: TestPlanExport =
+        serde_json::from_str(json).map_err(|e| format!("Invalid test plan file: {}", e))?;
+    if export.version > TEST_PLAN_EXPORT_VERSION {
+        return Err(format!(
+            "Test plan file version {} is newer than this app supports ({}).",
+            export.version, TEST_PLAN_EXPORT_VERSION
+        ));
+    }
+    let name = export.name.trim().to_string();
+    if name.is_empty() {
+        return Err("Test plan name cannot be empty.".to_string());
+    }
+    if let Some(case) = export.cases.iter().find(|c| c.name.trim().is_empty()) {
+        return Err(format!("Test case name cannot be empty (file: {:?}).", case.file_path));
+    }
+
+    let id = Uuid::new_v4().to_string();
+    let now = Utc::now();
+    let now_str = now.to_rfc3339();
+    let target_coverage = export.target_coverage.min(100);
+
+    let tx = db
+        .unchecked_transaction()
+        .map_err(|e| format!("Failed to start transaction: {}", e))?;
+    tx.execute(
+        "INSERT INTO test_plans (id, project_id, name, description, status, target_coverage, created_at, updated_at)
+         VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7, ?8)",
+        rusqlite::params![
+            id,
+            project_id,
+            name,
+            export.description,
+            export.status.to_string(),
+            target_coverage,
+            now_str,
+            now_str
+        ],
+    )
+    .map_err(|e| format!("Failed to create test plan: {}", e))?;
+
+    for case in &export.cases {
+        tx.execute(
+            "INSERT INTO test_cases (id, plan_id, name, description, file_path, test_type, priority, status, created_at, updated_at)
+             VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7, 'pending', ?8, ?9)",
+            rusqlite::params![
+                Uuid::new_v4().to_string(),
+                id,
+                case.name.trim(),
+                case.description,
+                case.file_path,
+                case.test_type.to_string(),
+                case.priority.to_string(),
+                now_str,
+                now_str
+            ],
+        )
+        .map_err(|e| format!("Failed to create test case: {}", e))?;
+    }
+    tx.commit().map_err(|e| format!("Failed to import test plan: {}", e))?;
+
+    Ok(TestPlan {
+        id,
+        project_id: project_id.to_string(),
+        name,
+        description: export.description,
+        status: export.status,
+        target_coverage,
+        created_at: now,
+        updated_at: now,
+    })
+}
+
+/// Escape text for a Markdown table cell (pipes and line breaks).
+fn md_cell(text: &str) -> String {
+    text.replace('|', "\\|").replace(['\r', '\n'], " ")
+}
+
+/// Markdown report of a plan: summary, latest run, case table, and case descriptions.
+fn render_test_plan_markdown(plan: &TestPlan, cases: &[TestCase], last_run: Option<&TestRun>) -> String {
+    let count = |status: TestCaseStatus| cases.iter().filter(|c| c.status == status).count();
+    let mut md = format!("# Test Plan: {}\n\n", plan.name);
+    if !plan.description.trim().is_empty() {
+        md.push_str(&format!("{}\n\n", plan.description.trim()));
+    }
+    md.push_str(&format!("- **Status:** {}\n", plan.status));
+    md.push_str(&format!("- **Target coverage:** {}%\n", plan.target_coverage));
+    md.push_str(&format!(
+        "- **Cases:** {} ({} passing, {} failing, {} pending, {} skipped)\n",
+        cases.len(),
+        count(TestCaseStatus::Passing),
+        count(TestCaseStatus::Failing),
+        count(TestCaseStatus::Pending),
+        count(TestCaseStatus::Skipped),
+    ));
+    if let Some(run) = last_run {
+        let coverage = run
+            .coverage_percent
+            .map(|c| format!(", {:.1}% coverage", c))
+            .unwrap_or_default();
+        md.push_str(&format!(
+            "- **Last run:** {} on {}: {}/{} passed, {} failed, {} skipped{}\n",
+            run.status,
+            run.started_at.format("%Y-%m-%d %H:%M UTC"),
+            run.passed_tests,
+            run.total_tests,
+            run.failed_tests,
+            run.skipped_tests,
+            coverage,
+        ));
+    }
+
+    if cases.is_empty() {
+        md.push_str("\nNo test cases yet.\n");
+        return md;
+    }
+
+    md.push_str("\n## Cases\n\n| Priority | Name | Type | Status | File |\n|---|---|---|---|---|\n");
+    for case in cases {
+        let file = case.file_path.as_deref().map(|f| format!("`{}`", md_cell(f))).unwrap_or_default();
+        md.push_str(&format!(
+            "| {} | {} | {} | {} | {} |\n",
+            case.priority,
+            md_cell(&case.name),
+            case.test_type,
+            case.status,
+            file
+        ));
+    }
+
+    let described: Vec<&TestCase> = cases.iter().filter(|c| !c.description.trim().is_empty()).collect();
+    if !described.is_empty() {
+        md.push_str("\n## Case Details\n");
+        for case in described {
+            md.push_str(&format!("\n### {}\n\n{}\n", case.name, case.description.trim()));
+        }
+    }
+    md
+}
+
 // =============================================================================
 // AI Test Generation
 // =============================================================================
@@ -650,10 +1109,11 @@ pub async fn generate_test_suggestions(
     };
     // DB lock released here at end of block
 
-    // Read file contents to analyze
+    // Read file contents to analyze, skipping files on the "never send to AI" list
+    let excludes = crate::core::ai::load_ai_excludes(&project_path);
     let mut file_contents = String::new();
     if let Some(paths) = file_paths {
-        for path in paths.iter().take(5) {
+        for path in paths.iter().filter(|p| !crate::core::ai::is_ai_excluded(&excludes, p)).take(5) {
             // Limit to 5 files
             let full_path = std::path::Path::new(&project_path).join(path);
             if let Ok(content) = std::fs::read_to_string(&full_path) {
@@ -668,7 +1128,11 @@ pub async fn generate_test_suggestions(
             .output()
         {
             let changed_files = String::from_utf8_lossy(&output.stdout);
-            for path in changed_files.lines().take(5) {
+            for path in changed_files
+                .lines()
+                .filter(|p| !crate::core::ai::is_ai_excluded(&excludes, p))
+                .take(5)
+            {
                 let full_path = std::path::Path::new(&project_path).join(path);
                 if let Ok(content) = std::fs::read_to_string(&full_path) {
                     file_contents.push_str(&format!("\n\n--- {} ---\n{}", path, content));
@@ -715,7 +1179,9 @@ Return 3-5 high-quality test suggestions."#,
     );
 
     let system_prompt = "You are a test-driven development expert. Generate specific, actionable test case suggestions based on code analysis. Return only valid JSON.";
-    let response = crate::core::ai::call_claude(&state.http_client, &api_key, system_prompt, &prompt).await?;
+    let call = crate::core::ai::call_claude(&state.http_client, &api_key, system_prompt, &prompt);
+    let queued = ai_queue::run(AiJobKind::TestSuggestions, "test suggestions", call);
+    let response = crate::core::ai_audit::scoped(&project_path, "test suggestions", queued).await?;
 
     // Parse the response
     parse_test_suggestions(&response)
@@ -772,16 +1238,18 @@ pub async fn create_tdd_session(
 
     // Generate initial prompts
     let red_prompt = generate_red_prompt(&feature_name);
+    let phase_history = vec![TDDPhaseEvent { phase: TDDPhase::Red, at: now }];
+    let history_json = serde_json::to_string(&phase_history).unwrap_or_else(|_| "[]".to_string());
 
     db.execute(
-        "INSERT INTO tdd_sessions (id, project_id, feature_name, test_file_path, current_phase, phase_status, red_prompt, created_at, updated_at)
-         VALUES (?1, ?2, ?3, ?4, 'red', 'active', ?5, ?6, ?7)",
-        rusqlite::params![id, project_id, feature_name, test_file_path, red_prompt, now_str, now_str],
+        "INSERT INTO tdd_sessions (id, project_id, feature_name, test_file_path, current_phase, phase_status, red_prompt, phase_history, created_at, updated_at)
+         VALUES (?1, ?2, ?3, ?4, 'red', 'active', ?5, ?6, ?7, ?8)",
+        rusqlite::params![id, project_id, feature_name, test_file_path, red_prompt, history_json, now_str, now_str],
     )
     .map_err(|e| format!("Failed to create TDD session: {}", e))?;
 
     // Log activity
-    let _ = db::log_activity_db(&db, &project_id, "tdd", &format!("Started TDD session: {}", &feature_name));
+    events::publish(&db, AppEvent::activity(&project_id, ActivityType::Test, &format!("Started TDD session: {}", &feature_name)));
 
     Ok(TDDSession {
         id,
@@ -799,10 +1267,12 @@ pub async fn create_tdd_session(
         created_at: now,
         updated_at: now,
         completed_at: None,
+        phase_history,
     })
 }
 
 /// Update TDD session phase and status.
+/// Moving to a new phase records it in phase_history; moving back to red starts a new cycle.
 #[tauri::command]
 pub async fn update_tdd_session(
     id: String,
@@ -818,7 +1288,7 @@ pub async fn update_tdd_session(
         .query_row(
             "SELECT id, project_id, feature_name, test_file_path, current_phase, phase_status,
                     red_prompt, red_output, green_prompt, green_output, refactor_prompt, refactor_output,
-                    created_at, updated_at, completed_at
+                    created_at, updated_at, completed_at, phase_history
              FROM tdd_sessions WHERE id = ?1",
             [&id],
             map_tdd_session_row,
@@ -853,20 +1323,28 @@ pub async fn update_tdd_session(
         .map_err(|e| format!("Failed to update output: {}", e))?;
     }
 
-    // If advancing phase, generate next prompt
+    // If changing phase, generate its prompt and record when it started
     if phase.is_some() && new_phase != current.current_phase {
         let (prompt_column, prompt_content) = match new_phase {
-            TDDPhase::Green => ("green_prompt", Some(generate_green_prompt(&current.feature_name))),
-            TDDPhase::Refactor => ("refactor_prompt", Some(generate_refactor_prompt(&current.feature_name))),
-            TDDPhase::Red => ("red_prompt", None),
+            TDDPhase::Green => ("green_prompt", generate_green_prompt(&current.feature_name)),
+            TDDPhase::Refactor => ("refactor_prompt", generate_refactor_prompt(&current.feature_name)),
+            TDDPhase::Red => ("red_prompt", generate_red_prompt(&current.feature_name)),
         };
 
-        if let Some(content) = prompt_content {
-            db.execute(
-                &format!("UPDATE tdd_sessions SET {} = ?1, current_phase = ?2, phase_status = ?3, updated_at = ?4 WHERE id = ?5", prompt_column),
-                rusqlite::params![content, new_phase.to_string(), new_status.to_string(), now_str, id],
-            )
-            .map_err(|e| format!("Failed to update phase: {}", e))?;
+        let mut history = current.phase_history.clone();
+        history.push(TDDPhaseEvent { phase: new_phase.clone(), at: now });
+        let history_json = serde_json::to_string(&history).unwrap_or_else(|_| "[]".to_string());
+
+        db.execute(
+            &format!("UPDATE tdd_sessions SET {} = ?1, current_phase = ?2, phase_status = ?3, phase_history = ?4, updated_at = ?5 WHERE id = ?6", prompt_column),
+            rusqlite::params![prompt_content, new_phase.to_string(), new_status.to_string(), history_json, now_str, id],
+        )
+        .map_err(|e| format!("Failed to update phase: {}", e))?;
+
+        // A new red phase reopens a completed session
+        if new_phase == TDDPhase::Red {
+            db.execute("UPDATE tdd_sessions SET completed_at = NULL WHERE id = ?1", [&id])
+                .map_err(|e| format!("Failed to update session: {}", e))?;
         }
     } else {
         db.execute(
@@ -892,7 +1370,7 @@ pub async fn update_tdd_session(
     db.query_row(
         "SELECT id, project_id, feature_name, test_file_path, current_phase, phase_status,
                 red_prompt, red_output, green_prompt, green_output, refactor_prompt, refactor_output,
-                created_at, updated_at, completed_at
+                created_at, updated_at, completed_at, phase_history
          FROM tdd_sessions WHERE id = ?1",
         [&id],
         map_tdd_session_row,
@@ -908,7 +1386,7 @@ pub async fn get_tdd_session(id: String, state: State<'_, AppState>) -> Result<T
     db.query_row(
         "SELECT id, project_id, feature_name, test_file_path, current_phase, phase_status,
                 red_prompt, red_output, green_prompt, green_output, refactor_prompt, refactor_output,
-                created_at, updated_at, completed_at
+                created_at, updated_at, completed_at, phase_history
          FROM tdd_sessions WHERE id = ?1",
         [&id],
         map_tdd_session_row,
@@ -929,13 +1407,13 @@ pub async fn list_tdd_sessions(
     let query = if include_completed {
         "SELECT id, project_id, feature_name, test_file_path, current_phase, phase_status,
                 red_prompt, red_output, green_prompt, green_output, refactor_prompt, refactor_output,
-                created_at, updated_at, completed_at
+                created_at, updated_at, completed_at, phase_history
          FROM tdd_sessions WHERE project_id = ?1
          ORDER BY updated_at DESC"
     } else {
         "SELECT id, project_id, feature_name, test_file_path, current_phase, phase_status,
                 red_prompt, red_output, green_prompt, green_output, refactor_prompt, refactor_output,
-                created_at, updated_at, completed_at
+                created_at, updated_at, completed_at, phase_history
          FROM tdd_sessions WHERE project_id = ?1 AND completed_at IS NULL
          ORDER BY updated_at DESC"
     };
@@ -950,6 +1428,31 @@ pub async fn list_tdd_sessions(
     Ok(sessions)
 }
 
+/// Cycle times (red→green, green→refactor), cycles per session, and weekly trends
+/// across all of a project's TDD sessions.
+#[tauri::command]
+pub async fn get_tdd_analytics(project_id: String, state: State<'_, AppState>) -> Result<TDDAnalytics, String> {
+    let db = state.db.lock().map_err(|e| format!("DB lock error: {}", e))?;
+    tdd_analytics_db(&db, &project_id)
+}
+
+fn tdd_analytics_db(db: &rusqlite::Connection, project_id: &str) -> Result<TDDAnalytics, String> {
+    let mut stmt = db
+        .prepare(
+            "SELECT id, project_id, feature_name, test_file_path, current_phase, phase_status,
+                    red_prompt, red_output, green_prompt, green_output, refactor_prompt, refactor_output,
+                    created_at, updated_at, completed_at, phase_history
+             FROM tdd_sessions WHERE project_id = ?1",
+        )
+        .map_err(|e| format!("Failed to prepare query: {}", e))?;
+    let sessions: Vec<TDDSession> = stmt
+        .query_map([project_id], map_tdd_session_row)
+        .map_err(|e| format!("Failed to query TDD sessions: {}", e))?
+        .filter_map(|r| r.ok())
+        .collect();
+    Ok(tdd_analytics::build_analytics(project_id, &sessions))
+}
+
 // =============================================================================
 // Test Staleness Detection
 // =============================================================================
@@ -1046,14 +1549,16 @@ pub async fn check_test_staleness(
 
 /// Check if a file path is a material source file (not test, config, etc.)
 fn is_material_source_file(path: &str) -> bool {
-    let source_exts = [".ts", ".tsx", ".js", ".jsx", ".rs", ".py", ".go"];
+    let source_exts = [".ts", ".tsx", ".js", ".jsx", ".rs", ".py", ".go", ".php", ".rb", ".java", ".kt"];
     let has_source_ext = source_exts.iter().any(|ext| path.ends_with(ext));
     if !has_source_ext {
         return false;
     }
 
     // Exclude test files
-    let test_patterns = [".test.", ".spec.", "_test.", "test_", "__tests__", ".stories."];
+    let test_patterns = [
+        ".test.", ".spec.", "_test.", "test_", "__tests__", ".stories.", "_spec.", "Test.", "Tests.",
+    ];
     if test_patterns.iter().any(|pat| path.contains(pat)) {
         return false;
     }
@@ -1069,7 +1574,7 @@ fn is_material_source_file(path: &str) -> bool {
 
 /// Find the corresponding test file for a given source file.
 /// Returns Some("__inline__") for Rust files with inline tests.
-fn find_corresponding_test_file(source: &str, project_path: &str) -> Option<String> {
+pub fn find_corresponding_test_file(source: &str, project_path: &str) -> Option<String> {
     let path = std::path::Path::new(source);
     let dir = path.parent().map(|p| p.to_string_lossy().to_string()).unwrap_or_default();
     let file_name = path.file_name()?.to_string_lossy().to_string();
@@ -1146,6 +1651,43 @@ fn find_corresponding_test_file(source: &str, project_path: &str) -> Option<Stri
             }
             None
         }
+        "php" | "java" | "kt" => {
+            // PHPUnit / JUnit: NameTest.ext beside the source, or mirrored under the test
+            // root (src/Foo.php -> tests/FooTest.php, src/main/java/x/Foo.java -> src/test/java/x/FooTest.java)
+            let test_name = format!("{}Test.{}", stem, ext);
+            let mirrored = [("src/main/", "src/test/"), ("src/", "tests/"), ("app/", "tests/Unit/"), ("app/", "tests/Feature/")]
+                .iter()
+                .filter_map(|(from, to)| source.strip_prefix(from).map(|rest| format!("{}{}", to, rest)))
+                .filter_map(|mirror| {
+                    let parent = std::path::Path::new(&mirror).parent()?.to_string_lossy().to_string();
+                    Some(format!("{}/{}", parent, test_name))
+                });
+            let beside = if dir.is_empty() { test_name.clone() } else { format!("{}/{}", dir, test_name) };
+            std::iter::once(beside)
+                .chain(mirrored)
+                .chain(std::iter::once(format!("tests/{}", test_name)))
+                .find(|candidate| std::path::Path::new(project_path).join(candidate).exists())
+        }
+        "rb" => {
+            // RSpec: spec/<path without lib/ or app/>/name_spec.rb
+            let spec_name = format!("{}_spec.rb", stem);
+            let relative_dir = ["lib", "app"]
+                .iter()
+                .find_map(|root| {
+                    if dir == *root {
+                        Some(String::new())
+                    } else {
+                        dir.strip_prefix(&format!("{}/", root)).map(str::to_string)
+                    }
+                })
+                .unwrap_or_else(|| dir.clone());
+            let candidate = if relative_dir.is_empty() {
+                format!("spec/{}", spec_name)
+            } else {
+                format!("spec/{}/{}", relative_dir, spec_name)
+            };
+            std::path::Path::new(project_path).join(&candidate).exists().then_some(candidate)
+        }
         _ => None,
     }
 }
@@ -1509,6 +2051,7 @@ fn map_test_run_row(row: &rusqlite::Row<'_>) -> rusqlite::Result<TestRun> {
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::test_support::with_project;
 
     // =========================================================================
     // is_material_source_file tests
@@ -1643,6 +2186,35 @@ mod tests {
         assert_eq!(result, Some("cmd/server_test.go".to_string()));
     }
 
+    #[test]
+    fn test_find_jvm_php_and_rspec_test_files() {
+        let dir = tempfile::tempdir().unwrap();
+        let project = dir.path();
+        let files = [
+            "src/main/java/com/acme/Parser.java",
+            "src/test/java/com/acme/ParserTest.java",
+            "src/Cart.php",
+            "tests/CartTest.php",
+            "lib/billing/invoice.rb",
+            "spec/billing/invoice_spec.rb",
+        ];
+        for file in files {
+            std::fs::create_dir_all(project.join(file).parent().unwrap()).unwrap();
+            std::fs::write(project.join(file), "").unwrap();
+        }
+        let project = project.to_str().unwrap();
+
+        assert_eq!(
+            find_corresponding_test_file("src/main/java/com/acme/Parser.java", project),
+            Some("src/test/java/com/acme/ParserTest.java".to_string())
+        );
+        assert_eq!(find_corresponding_test_file("src/Cart.php", project), Some("tests/CartTest.php".to_string()));
+        assert_eq!(
+            find_corresponding_test_file("lib/billing/invoice.rb", project),
+            Some("spec/billing/invoice_spec.rb".to_string())
+        );
+    }
+
     #[test]
     fn test_no_test_file_found() {
         let dir = tempfile::tempdir().unwrap();
@@ -1683,6 +2255,112 @@ mod tests {
         assert!(json.contains("\"isStale\":true"));
         assert!(json.contains("\"sourceFile\":\"src/App.tsx\""));
     }
+
+    // =========================================================================
+    // Export / import tests
+    // =========================================================================
+
+    fn sample_export() -> String {
+        r#"{
+            "version": 1,
+            "name": "Checkout flow",
+            "description": "Covers cart and payment",
+            "status": "active",
+            "targetCoverage": 90,
+            "cases": [
+                {"name": "applies discount", "testType": "unit", "priority": "medium"},
+                {"name": "pays | refunds", "description": "Stripe sandbox only", "filePath": "src/pay.test.ts",
+                 "testType": "e2e", "priority": "critical"}
+            ]
+        }"#
+        .to_string()
+    }
+
+    #[test]
+    fn test_import_export_json_round_trip() {
+        let db = with_project();
+        let plan = import_test_plan_db(&db, "p1", &sample_export()).unwrap();
+        assert_eq!(plan.name, "Checkout flow");
+        assert_eq!(plan.target_coverage, 90);
+
+        let json = export_test_plan_db(&db, &plan.id, "json").unwrap();
+        let export: TestPlanExport = serde_json::from_str(&json).unwrap();
+        assert_eq!(export.version, TEST_PLAN_EXPORT_VERSION);
+        assert_eq!(export.status, TestPlanStatus::Active);
+        let names: Vec<&str> = export.cases.iter().map(|c| c.name.as_str()).collect();
+        assert_eq!(names, vec!["pays | refunds", "applies discount"], "critical cases first");
+        assert_eq!(export.cases[0].file_path.as_deref(), Some("src/pay.test.ts"));
+
+        let copy = import_test_plan_db(&db, "p1", &json).unwrap();
+        assert_ne!(copy.id, plan.id);
+        let again: TestPlanExport = serde_json::from_str(&export_test_plan_db(&db, &copy.id, "json").unwrap()).unwrap();
+        assert_eq!(again, export);
+    }
+
+    #[test]
+    fn test_import_rejects_invalid_files() {
+        let db = with_project();
+        assert!(import_test_plan_db(&db, "p1", "not json").is_err());
+        assert!(import_test_plan_db(&db, "p1", r#"{"version": 99, "name": "Future"}"#).is_err());
+        assert!(import_test_plan_db(&db, "p1", r#"{"version": 1, "name": "  "}"#).is_err());
+        assert!(import_test_plan_db(&db, "missing", &sample_export()).is_err());
+        let count: i64 = db.query_row("SELECT COUNT(*) FROM test_plans", [], |r| r.get(0)).unwrap();
+        assert_eq!(count, 0);
+    }
+
+    #[test]
+    fn test_export_markdown() {
+        let db = with_project();
+        let plan = import_test_plan_db(&db, "p1", &sample_export()).unwrap();
+        db.execute(
+            "INSERT INTO test_runs (id, plan_id, status, total_tests, passed_tests, failed_tests, skipped_tests, coverage_percent, started_at)
+             VALUES ('r1', ?1, 'failed', 10, 8, 2, 0, 81.5, '2026-03-01T12:00:00Z')",
+            [&plan.id],
+        )
+        .unwrap();
+
+        let md = export_test_plan_db(&db, &plan.id, "markdown").unwrap();
+        assert!(md.starts_with("# Test Plan: Checkout flow\n\nCovers cart and payment\n"));
+        assert!(md.contains("- **Cases:** 2 (0 passing, 0 failing, 2 pending, 0 skipped)"));
+        assert!(md.contains("- **Last run:** failed on 2026-03-01 12:00 UTC: 8/10 passed, 2 failed, 0 skipped, 81.5% coverage"));
+        assert!(md.contains("| critical | pays \\| refunds | e2e | pending | `src/pay.test.ts` |"));
+        assert!(md.contains("### pays | refunds\n\nStripe sandbox only"));
+        assert!(export_test_plan_db(&db, &plan.id, "pdf").is_err());
+    }
+
+    #[tokio::test(flavor = "multi_thread", worker_threads = 2)]
+    async fn test_run_test_plan_end_to_end() {
+        use crate::test_support::{assert_snapshot, TestEnv};
+
+        let env = TestEnv::new();
+        let (project_id, project_path) = env.add_project("shop");
+        env.write_file("shop/phpunit.xml", "<phpunit/>");
+        env.write_executable(
+            "shop/vendor/bin/phpunit",
+            "echo 'PHPUnit 10.5.0'\necho\necho 'OK (3 tests, 5 assertions)'",
+        );
+        let plan_id = "plan-1".to_string();
+        env.db()
+            .execute(
+                "INSERT INTO test_plans (id, project_id, name, created_at, updated_at) VALUES (?1, ?2, 'Checkout', ?3, ?3)",
+                rusqlite::params![plan_id, project_id, Utc::now().to_rfc3339()],
+            )
+            .unwrap();
+
+        let run = run_test_plan(plan_id.clone(), project_path, false, env.handle(), env.state()).await.unwrap();
+        assert_eq!(run.status, TestRunStatus::Passed);
+        assert_eq!(run.total_tests, 3);
+        assert!(env.state().test_runs.lock().unwrap().is_empty(), "the cancel flag is released");
+
+        let activity: String = env
+            .db()
+            .query_row("SELECT message FROM activities WHERE project_id = ?1", [&project_id], |row| row.get(0))
+            .unwrap();
+        assert_eq!(activity, "Test run completed: 3 passed, 0 failed");
+        let mut snapshot = env.redact(&run);
+        snapshot["durationMs"] = serde_json::json!("<duration>");
+        assert_snapshot("run_test_plan", &snapshot);
+    }
 }
 
 fn map_tdd_session_row(row: &rusqlite::Row<'_>) -> rusqlite::Result<TDDSession> {
@@ -1691,6 +2369,7 @@ fn map_tdd_session_row(row: &rusqlite::Row<'_>) -> rusqlite::Result<TDDSession>
     let created_str: String = row.get(12)?;
     let updated_str: String = row.get(13)?;
     let completed_str: Option<String> = row.get(14)?;
+    let history_str: Option<String> = row.get(15)?;
 
     let current_phase: TDDPhase = phase_str.parse().unwrap_or(TDDPhase::Red);
     let phase_status: TDDPhaseStatus = status_str.parse().unwrap_or(TDDPhaseStatus::Pending);
@@ -1723,5 +2402,8 @@ fn map_tdd_session_row(row: &rusqlite::Row<'_>) -> rusqlite::Result<TDDSession>
         created_at,
         updated_at,
         completed_at,
+        phase_history: history_str
+            .and_then(|s| serde_json::from_str(&s).ok())
+            .unwrap_or_default(),
     })
 }
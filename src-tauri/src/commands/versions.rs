@@ -0,0 +1,469 @@
+//! @module commands/versions
+//! @description Tauri IPC commands for skill and agent version history
+//!
+//! PURPOSE:
+//! - Snapshot skills and agents into skill_versions/agent_versions on every write
+//! - List stored versions for a skill or agent
+//! - Diff two versions (changed fields + line diff of content)
+//! - Roll a skill or agent back to a previous version
+//!
+//! DEPENDENCIES:
+//! - tauri - Command macro and State
+//! - db::AppState - Database connection state
+//! - models::version - EntityVersion, VersionDiff, DiffLine types
+//! - rusqlite - Direct connection access for snapshot helpers
+//! - chrono - Timestamp generation
+//! - uuid - Version row ID generation
+//!
+//! EXPORTS:
+//! - list_versions - List versions for a skill or agent (newest first)
+//! - diff_versions - Compare two versions of a skill or agent
+//! - rollback_to_version - Restore a skill or agent to a previous version
+//! - record_version - Snapshot the current row into its versions table (used by skills/agents commands)
+//! - ensure_version_baseline - Snapshot the current row if no versions exist yet
+//! - delete_versions - Remove all versions for a deleted skill or agent
+//! - diff_lines - LCS line diff (also used by README generation)
+//!
+//! PATTERNS:
+//! - entity_type is "skill" or "agent"; any other value is rejected
+//! - Snapshots are copied with INSERT ... SELECT so the stored row matches the DB exactly
+//! - Rollback copies the old snapshot back and records it as a new version (rollbacks are undoable)
+//! - Versions are pruned to the most recent MAX_VERSIONS_PER_ENTITY per entity
+//!
+//! CLAUDE NOTES:
+//! - update_skill/update_agent call ensure_version_baseline before updating so rows created
+//!   before versioning existed still get their original content preserved
+//! - Agent versions store workflow, tools, trigger_patterns as the same JSON text as agents
+
+use chrono::Utc;
+use rusqlite::Connection;
+use tauri::State;
+use uuid::Uuid;
+
+use crate::core::events::{self, AppEvent};
+use crate::db::AppState;
+use crate::models::activity::ActivityType;
+use crate::models::version::{DiffLine, EntityVersion, VersionDiff};
+
+/// Maximum number of versions kept per skill or agent (prevents DB bloat)
+const MAX_VERSIONS_PER_ENTITY: i64 = 50;
+
+/// Table layout for a versioned entity type.
+struct VersionTable {
+    source: &'static str,
+    versions: &'static str,
+    foreign_key: &'static str,
+    columns: &'static [&'static str],
+    content_column: &'static str,
+    activity_type: ActivityType,
+}
+
+const SKILL_TABLE: VersionTable = VersionTable {
+    source: "skills",
+    versions: "skill_versions",
+    foreign_key: "skill_id",
+    columns: &["name", "description", "content"],
+    content_column: "content",
+    activity_type: ActivityType::Skill,
+};
+
+const AGENT_TABLE: VersionTable = VersionTable {
+    source: "agents",
+    versions: "agent_versions",
+    foreign_key: "agent_id",
+    columns: &[
+        "name",
+        "description",
+        "tier",
+        "category",
+        "instructions",
+        "workflow",
+        "tools",
+        "trigger_patterns",
+    ],
+    content_column: "instructions",
+    activity_type: ActivityType::Agent,
+};
+
+fn version_table(entity_type: &str) -> Result<&'static VersionTable, String> {
+    match entity_type {
+        "skill" => Ok(&SKILL_TABLE),
+        "agent" => Ok(&AGENT_TABLE),
+        other => Err(format!("Unsupported entity type: {} (expected 'skill' or 'agent')", other)),
+    }
+}
+
+/// Snapshot the current state of a skill or agent as a new version.
+/// Returns the new version number.
+pub fn record_version(db: &Connection, entity_type: &str, entity_id: &str) -> Result<u32, String> {
+    let table = version_table(entity_type)?;
+    let columns = table.columns.join(", ");
+
+    let next_version: u32 = db
+        .query_row(
+            &format!(
+                "SELECT COALESCE(MAX(version), 0) + 1 FROM {} WHERE {} = ?1",
+                table.versions, table.foreign_key
+            ),
+            [entity_id],
+            |row| row.get(0),
+        )
+        .map_err(|e| format!("Failed to compute next version: {}", e))?;
+
+    let inserted = db
+        .execute(
+            &format!(
+                "INSERT INTO {versions} (id, {fk}, version, {columns}, created_at)
+                 SELECT ?1, id, ?2, {columns}, ?3 FROM {source} WHERE id = ?4",
+                versions = table.versions,
+                fk = table.foreign_key,
+                columns = columns,
+                source = table.source,
+            ),
+            rusqlite::params![Uuid::new_v4().to_string(), next_version, Utc::now().to_rfc3339(), entity_id],
+        )
+        .map_err(|e| format!("Failed to record version: {}", e))?;
+
+    if inserted == 0 {
+        return Err(format!("{} not found: {}", capitalize(entity_type), entity_id));
+    }
+
+    // Prune old versions (keep only the most recent N per entity)
+    let _ = db.execute(
+        &format!(
+            "DELETE FROM {versions} WHERE {fk} = ?1 AND id NOT IN (
+                SELECT id FROM {versions} WHERE {fk} = ?1 ORDER BY version DESC LIMIT ?2
+            )",
+            versions = table.versions,
+            fk = table.foreign_key,
+        ),
+        rusqlite::params![entity_id, MAX_VERSIONS_PER_ENTITY],
+    );
+
+    Ok(next_version)
+}
+
+/// Snapshot the current state of a skill or agent if it has no versions yet.
+/// Called before updates so pre-existing rows keep their original content.
+pub fn ensure_version_baseline(db: &Connection, entity_type: &str, entity_id: &str) -> Result<(), String> {
+    let table = version_table(entity_type)?;
+    let count: u32 = db
+        .query_row(
+            &format!("SELECT COUNT(*) FROM {} WHERE {} = ?1", table.versions, table.foreign_key),
+            [entity_id],
+            |row| row.get(0),
+        )
+        .map_err(|e| format!("Failed to count versions: {}", e))?;
+
+    if count == 0 {
+        record_version(db, entity_type, entity_id)?;
+    }
+    Ok(())
+}
+
+/// Remove all stored versions for a skill or agent.
+pub fn delete_versions(db: &Connection, entity_type: &str, entity_id: &str) -> Result<(), String> {
+    let table = version_table(entity_type)?;
+    db.execute(
+        &format!("DELETE FROM {} WHERE {} = ?1", table.versions, table.foreign_key),
+        [entity_id],
+    )
+    .map_err(|e| format!("Failed to delete versions: {}", e))?;
+    Ok(())
+}
+
+/// List all stored versions of a skill or agent, newest first.
+#[tauri::command]
+pub async fn list_versions(
+    entity_type: String,
+    entity_id: String,
+    state: State<'_, AppState>,
+) -> Result<Vec<EntityVersion>, String> {
+    let table = version_table(&entity_type)?;
+    let db = state.db.lock().map_err(|e| format!("DB lock error: {}", e))?;
+
+    let mut stmt = db
+        .prepare(&format!(
+            "SELECT id, {fk}, version, name, description, {content}, created_at
+             FROM {versions} WHERE {fk} = ?1 ORDER BY version DESC",
+            fk = table.foreign_key,
+            content = table.content_column,
+            versions = table.versions,
+        ))
+        .map_err(|e| format!("Failed to prepare query: {}", e))?;
+
+    let versions = stmt
+        .query_map([&entity_id], |row| map_version_row(row, &entity_type))
+        .map_err(|e| format!("Failed to query versions: {}", e))?
+        .filter_map(|r| r.ok())
+        .collect();
+
+    Ok(versions)
+}
+
+/// Compare two versions of a skill or agent.
+/// Returns the list of changed fields and a line diff of the content.
+#[tauri::command]
+pub async fn diff_versions(
+    entity_type: String,
+    entity_id: String,
+    from_version: u32,
+    to_version: u32,
+    state: State<'_, AppState>,
+) -> Result<VersionDiff, String> {
+    let table = version_table(&entity_type)?;
+    let db = state.db.lock().map_err(|e| format!("DB lock error: {}", e))?;
+
+    let from_fields = load_version_fields(&db, table, &entity_id, from_version)?;
+    let to_fields = load_version_fields(&db, table, &entity_id, to_version)?;
+
+    let changed_fields = table
+        .columns
+        .iter()
+        .zip(from_fields.iter().zip(to_fields.iter()))
+        .filter(|(_, (a, b))| a != b)
+        .map(|(name, _)| name.to_string())
+        .collect();
+
+    let content_index = table
+        .columns
+        .iter()
+        .position(|c| *c == table.content_column)
+        .unwrap_or(0);
+    let from_content = from_fields[content_index].as_deref().unwrap_or("");
+    let to_content = to_fields[content_index].as_deref().unwrap_or("");
+
+    Ok(VersionDiff {
+        entity_type,
+        entity_id,
+        from_version,
+        to_version,
+        changed_fields,
+        lines: diff_lines(from_content, to_content),
+    })
+}
+
+/// Restore a skill or agent to a previous version.
+/// The restored state is recorded as a new version so the rollback can itself be undone.
+#[tauri::command]
+pub async fn rollback_to_version(
+    entity_type: String,
+    entity_id: String,
+    version: u32,
+    state: State<'_, AppState>,
+) -> Result<EntityVersion, String> {
+    let table = version_table(&entity_type)?;
+    let db = state.db.lock().map_err(|e| format!("DB lock error: {}", e))?;
+
+    // Make sure the target version exists before touching the live row
+    load_version_fields(&db, table, &entity_id, version)?;
+
+    let assignments = table
+        .columns
+        .iter()
+        .map(|c| {
+            format!(
+                "{col} = (SELECT {col} FROM {versions} WHERE {fk} = ?1 AND version = ?2)",
+                col = c,
+                versions = table.versions,
+                fk = table.foreign_key,
+            )
+        })
+        .collect::<Vec<_>>()
+        .join(", ");
+
+    let rows_affected = db
+        .execute(
+            &format!("UPDATE {} SET {}, updated_at = ?3 WHERE id = ?1", table.source, assignments),
+            rusqlite::params![entity_id, version, Utc::now().to_rfc3339()],
+        )
+        .map_err(|e| format!("Failed to roll back {}: {}", entity_type, e))?;
+
+    if rows_affected == 0 {
+        return Err(format!("{} not found: {}", capitalize(&entity_type), entity_id));
+    }
+
+    let new_version = record_version(&db, &entity_type, &entity_id)?;
+
+    let restored = db
+        .query_row(
+            &format!(
+                "SELECT id, {fk}, version, name, description, {content}, created_at
+                 FROM {versions} WHERE {fk} = ?1 AND version = ?2",
+                fk = table.foreign_key,
+                content = table.content_column,
+                versions = table.versions,
+            ),
+            rusqlite::params![entity_id, new_version],
+            |row| map_version_row(row, &entity_type),
+        )
+        .map_err(|e| format!("Failed to fetch restored version: {}", e))?;
+
+    // Log activity
+    let project_id: Option<String> = db
+        .query_row(
+            &format!("SELECT project_id FROM {} WHERE id = ?1", table.source),
+            [&entity_id],
+            |row| row.get(0),
+        )
+        .ok()
+        .flatten();
+    if let Some(pid) = project_id {
+        events::publish(
+            &db,
+            AppEvent::activity(
+                &pid,
+                table.activity_type,
+                &format!("Rolled back {} '{}' to version {}", entity_type, restored.name, version),
+            ),
+        );
+    }
+
+    Ok(restored)
+}
+
+/// Load the versioned column values for a specific version, in `table.columns` order.
+fn load_version_fields(
+    db: &Connection,
+    table: &VersionTable,
+    entity_id: &str,
+    version: u32,
+) -> Result<Vec<Option<String>>, String> {
+    db.query_row(
+        &format!(
+            "SELECT {} FROM {} WHERE {} = ?1 AND version = ?2",
+            table.columns.join(", "),
+            table.versions,
+            table.foreign_key
+        ),
+        rusqlite::params![entity_id, version],
+        |row| {
+            (0..table.columns.len())
+                .map(|i| row.get::<_, Option<String>>(i))
+                .collect::<rusqlite::Result<Vec<_>>>()
+        },
+    )
+    .map_err(|e| match e {
+        rusqlite::Error::QueryReturnedNoRows => format!("Version {} not found", version),
+        other => format!("Failed to load version {}: {}", version, other),
+    })
+}
+
+/// Compute a line-level diff between two texts using a longest-common-subsequence table.
+pub(crate) fn diff_lines(old: &str, new: &str) -> Vec<DiffLine> {
+    let a: Vec<&str> = old.lines().collect();
+    let b: Vec<&str> = new.lines().collect();
+
+    // lcs[i][j] = length of LCS of a[i..] and b[j..]
+    let mut lcs = vec![vec![0u32; b.len() + 1]; a.len() + 1];
+    for i in (0..a.len()).rev() {
+        for j in (0..b.len()).rev() {
+            lcs[i][j] = if a[i] == b[j] {
+                lcs[i + 1][j + 1] + 1
+            } else {
+                lcs[i + 1][j].max(lcs[i][j + 1])
+            };
+        }
+    }
+
+    let line = |kind: &str, text: &str| DiffLine {
+        kind: kind.to_string(),
+        text: text.to_string(),
+    };
+
+    let mut result = Vec::new();
+    let (mut i, mut j) = (0, 0);
+    while i < a.len() && j < b.len() {
+        if a[i] == b[j] {
+            result.push(line("unchanged", a[i]));
+            i += 1;
+            j += 1;
+        } else if lcs[i + 1][j] >= lcs[i][j + 1] {
+            result.push(line("removed", a[i]));
+            i += 1;
+        } else {
+            result.push(line("added", b[j]));
+            j += 1;
+        }
+    }
+    result.extend(a[i..].iter().map(|t| line("removed", t)));
+    result.extend(b[j..].iter().map(|t| line("added", t)));
+    result
+}
+
+fn capitalize(s: &str) -> String {
+    let mut chars = s.chars();
+    match chars.next() {
+        Some(first) => first.to_uppercase().collect::<String>() + chars.as_str(),
+        None => String::new(),
+    }
+}
+
+// ---------------------------------------------------------------------------
+// Row mapping helper
+// ---------------------------------------------------------------------------
+
+fn map_version_row(row: &rusqlite::Row<'_>, entity_type: &str) -> rusqlite::Result<EntityVersion> {
+    Ok(EntityVersion {
+        id: row.get(0)?,
+        entity_type: entity_type.to_string(),
+        entity_id: row.get(1)?,
+        version: row.get(2)?,
+        name: row.get(3)?,
+        description: row.get(4)?,
+        content: row.get(5)?,
+        created_at: row.get(6)?,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_support::memory_db;
+
+    fn setup_db() -> Connection {
+        let conn = memory_db();
+        conn.execute(
+            "INSERT INTO skills (id, project_id, name, description, content, usage_count, created_at, updated_at)
+             VALUES ('s1', NULL, 'Skill', 'desc', 'line one\nline two', 0, '2025-01-01T00:00:00Z', '2025-01-01T00:00:00Z')",
+            [],
+        )
+        .unwrap();
+        conn
+    }
+
+    #[test]
+    fn test_record_version_increments() {
+        let db = setup_db();
+        assert_eq!(record_version(&db, "skill", "s1").unwrap(), 1);
+        db.execute("UPDATE skills SET content = 'changed' WHERE id = 's1'", []).unwrap();
+        assert_eq!(record_version(&db, "skill", "s1").unwrap(), 2);
+
+        let fields = load_version_fields(&db, &SKILL_TABLE, "s1", 2).unwrap();
+        assert_eq!(fields[2].as_deref(), Some("changed"));
+        assert!(record_version(&db, "skill", "missing").is_err());
+        assert!(record_version(&db, "prompt", "s1").is_err());
+    }
+
+    #[test]
+    fn test_ensure_version_baseline_only_once() {
+        let db = setup_db();
+        ensure_version_baseline(&db, "skill", "s1").unwrap();
+        ensure_version_baseline(&db, "skill", "s1").unwrap();
+        let count: u32 = db
+            .query_row("SELECT COUNT(*) FROM skill_versions WHERE skill_id = 's1'", [], |r| r.get(0))
+            .unwrap();
+        assert_eq!(count, 1);
+    }
+
+    #[test]
+    fn test_diff_lines() {
+        let diff = diff_lines("a\nb\nc", "a\nc\nd");
+        let kinds: Vec<(&str, &str)> = diff.iter().map(|l| (l.kind.as_str(), l.text.as_str())).collect();
+        assert_eq!(
+            kinds,
+            vec![("unchanged", "a"), ("removed", "b"), ("unchanged", "c"), ("added", "d")]
+        );
+        assert!(diff_lines("", "").is_empty());
+    }
+}
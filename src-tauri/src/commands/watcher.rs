@@ -4,30 +4,55 @@
 //! PURPOSE:
 //! - Start watching a project directory for source file changes
 //! - Stop watching when project changes or app closes
+//! - Report whether the watcher runs natively or polls, and why
 //!
 //! DEPENDENCIES:
 //! - tauri - Command macro, State, AppHandle
 //! - core::watcher - ProjectWatcher for actual file watching
+//! - core::health - DocHealthCache for incremental health
+//! - commands::claude_md - compute_health_score for the recomputed score
+//! - commands::doc_proposals - propose_docs for the approval queue
 //! - db::AppState - Shared state holding the watcher instance
 //!
 //! EXPORTS:
 //! - start_file_watcher - Start watching a project directory
 //! - stop_file_watcher - Stop the current watcher
+//! - get_watcher_status - Active mode (native/polling), poll interval, fallback reason, errors
+//! - HealthScorePayload - Payload of the "health-score-updated" event
 //!
 //! PATTERNS:
 //! - Only one watcher runs at a time (stored in AppState)
 //! - Starting a new watcher automatically stops the previous one
 //! - The watcher emits "file-changed" events to the frontend
+//! - Starting a watcher builds the DocHealthCache; each debounced batch updates only the
+//!   touched files and emits "health-score-updated" when the score inputs changed
+//! - With watcher.doc_proposals = "true", changed files needing docs are queued as doc
+//!   proposals and "doc-proposals-queued" is emitted
 //!
 //! CLAUDE NOTES:
 //! - The watcher is stored as Option<ProjectWatcher> in AppState
 //! - Dropping the previous watcher automatically cleans up its resources
 //! - start_file_watcher requires both the project path and a Tauri AppHandle
+//! - Settings: watcher.mode ("auto" | "native" | "polling", default auto) and
+//!   watcher.poll_interval_ms (default 2000); both apply on the next start_file_watcher
+//! - watcher.doc_proposals is read on every batch, so toggling it needs no restart
 
-use tauri::{AppHandle, State};
+use serde::Serialize;
+use tauri::{AppHandle, Emitter, Manager, State};
 
-use crate::core::watcher::ProjectWatcher;
+use crate::commands::{claude_md, doc_proposals};
+use crate::core::health::DocHealthCache;
+use crate::core::watcher::{ProjectWatcher, WatchOptions, WatcherStatus};
 use crate::db::AppState;
+use crate::models::project::HealthScore;
+
+/// Payload of the "health-score-updated" event.
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct HealthScorePayload {
+    pub project_path: String,
+    pub score: HealthScore,
+}
 
 /// Start watching a project directory for file changes.
 /// Stops any existing watcher before starting a new one.
@@ -46,7 +71,23 @@ pub async fn start_file_watcher(
         *watcher_guard = None;
     }
 
-    let new_watcher = ProjectWatcher::start(app_handle, project_path)?;
+    // Full scan once; the watcher keeps it current from here on
+    let cache = DocHealthCache::build(&project_path);
+    {
+        let mut cache_guard = state
+            .health_cache
+            .lock()
+            .map_err(|e| format!("Failed to lock health cache: {}", e))?;
+        *cache_guard = Some(cache);
+    }
+
+    let options = load_watch_options(&state)?;
+    let handle = app_handle.clone();
+    let watched_path = project_path.clone();
+    let new_watcher = ProjectWatcher::start(app_handle, project_path, options, move |paths| {
+        refresh_health(&handle, &watched_path, &paths);
+        queue_proposals(&handle, &watched_path, &paths);
+    })?;
 
     {
         let mut watcher_guard = state
@@ -59,6 +100,84 @@ pub async fn start_file_watcher(
     Ok(())
 }
 
+/// Watcher mode and poll interval from settings.
+fn load_watch_options(state: &AppState) -> Result<WatchOptions, String> {
+    let db = state.db.lock().map_err(|e| format!("DB lock error: {}", e))?;
+    let setting = |key: &str| -> Option<String> {
+        db.query_row("SELECT value FROM settings WHERE key = ?1", [key], |row| row.get(0))
+            .ok()
+    };
+    Ok(WatchOptions::from_settings(
+        setting("watcher.mode").as_deref(),
+        setting("watcher.poll_interval_ms").as_deref(),
+    ))
+}
+
+/// Apply a batch of changed paths to the health cache and, if anything that
+/// feeds the score changed, emit the recomputed score.
+fn refresh_health(app_handle: &AppHandle, project_path: &str, paths: &[std::path::PathBuf]) {
+    let state = app_handle.state::<AppState>();
+    let docs_changed = match state.health_cache.lock() {
+        Ok(mut guard) => match guard.as_mut().filter(|c| c.project_path() == project_path) {
+            Some(cache) => {
+                let mut changed = false;
+                for path in paths {
+                    changed |= cache.update_file(path);
+                }
+                changed
+            }
+            None => return,
+        },
+        Err(_) => return,
+    };
+
+    // CLAUDE.md, MCP configs, hooks, and CI files are read fresh on every calculation
+    let other_inputs_changed = paths.iter().any(|p| {
+        let name = p.file_name().and_then(|n| n.to_str()).unwrap_or("");
+        name == "CLAUDE.md"
+            || name == ".mcp.json"
+            || name == "mcp_servers.json"
+            || p.components().any(|c| c.as_os_str() == "workflows" || c.as_os_str() == "hooks")
+    });
+
+    if docs_changed || other_inputs_changed {
+        if let Ok((_, score)) = claude_md::compute_health_score(&state, project_path) {
+            let _ = app_handle.emit(
+                "health-score-updated",
+                HealthScorePayload {
+                    project_path: project_path.to_string(),
+                    score,
+                },
+            );
+        }
+    }
+}
+
+/// Queue doc proposals for changed files when the watcher.doc_proposals setting is on.
+fn queue_proposals(app_handle: &AppHandle, project_path: &str, paths: &[std::path::PathBuf]) {
+    let state = app_handle.state::<AppState>();
+    let Ok(db) = state.db.lock() else {
+        return;
+    };
+    let enabled: Option<String> = db
+        .query_row(
+            "SELECT value FROM settings WHERE key = ?1",
+            [doc_proposals::DOC_PROPOSALS_SETTING],
+            |row| row.get(0),
+        )
+        .ok();
+    if enabled.as_deref() != Some("true") {
+        return;
+    }
+
+    let files: Vec<String> = paths.iter().map(|p| p.to_string_lossy().to_string()).collect();
+    if let Ok(queued) = doc_proposals::propose_docs(&db, project_path, &files, "watcher") {
+        if queued > 0 {
+            let _ = app_handle.emit("doc-proposals-queued", project_path.to_string());
+        }
+    }
+}
+
 /// Stop the current file watcher.
 #[tauri::command]
 pub async fn stop_file_watcher(state: State<'_, AppState>) -> Result<(), String> {
@@ -67,5 +186,26 @@ pub async fn stop_file_watcher(state: State<'_, AppState>) -> Result<(), String>
         .lock()
         .map_err(|e| format!("Failed to lock watcher: {}", e))?;
     *watcher_guard = None;
+    drop(watcher_guard);
+
+    let mut cache_guard = state
+        .health_cache
+        .lock()
+        .map_err(|e| format!("Failed to lock health cache: {}", e))?;
+    *cache_guard = None;
     Ok(())
 }
+
+/// Report the running watcher's mode (native or polling), poll interval, why polling
+/// was chosen, and any errors it reported.
+#[tauri::command]
+pub async fn get_watcher_status(state: State<'_, AppState>) -> Result<WatcherStatus, String> {
+    let watcher_guard = state
+        .watcher
+        .lock()
+        .map_err(|e| format!("Failed to lock watcher: {}", e))?;
+    Ok(watcher_guard
+        .as_ref()
+        .map(ProjectWatcher::status)
+        .unwrap_or_else(WatcherStatus::idle))
+}
@@ -0,0 +1,108 @@
+//! @module commands/windows
+//! @description Tauri IPC commands for opening and managing detachable monitor windows
+//!
+//! PURPOSE:
+//! - Open a dedicated window that monitors one RALPH loop or test plan
+//! - List and close open monitor windows
+//!
+//! DEPENDENCIES:
+//! - tauri - AppHandle, Manager, WebviewWindowBuilder
+//! - core::monitor - Label convention and MonitorKind
+//! - models::monitor - MonitorWindow type
+//!
+//! EXPORTS:
+//! - open_monitor_window - Open (or focus) the monitor window for a loop or test plan
+//! - close_monitor_window - Close a monitor window if open
+//! - list_monitor_windows - List open monitor windows
+//!
+//! PATTERNS:
+//! - kind is "ralph" or "test_plan"; target_id is the loop id or plan id
+//! - Opening an already-open monitor focuses it instead of creating a duplicate
+//! - Progress events reach monitor windows via core::monitor::emit_scoped
+//!
+//! CLAUDE NOTES:
+//! - Commands registered in lib.rs invoke_handler
+//! - The frontend route "#/monitor/<kind>/<target_id>" renders the monitor view
+
+use tauri::{AppHandle, Manager, WebviewUrl, WebviewWindowBuilder};
+
+use crate::core::monitor::{self, MonitorKind};
+use crate::models::monitor::MonitorWindow;
+
+/// Open a dedicated monitor window for a RALPH loop or test plan.
+/// If the window is already open it is focused and returned.
+#[tauri::command]
+pub async fn open_monitor_window(
+    kind: String,
+    target_id: String,
+    title: Option<String>,
+    app: AppHandle,
+) -> Result<MonitorWindow, String> {
+    let monitor_kind = MonitorKind::parse(&kind)
+        .ok_or_else(|| format!("Unknown monitor kind '{}'; expected 'ralph' or 'test_plan'", kind))?;
+    let label = monitor::monitor_label(monitor_kind, &target_id);
+
+    if let Some(existing) = app.get_webview_window(&label) {
+        let _ = existing.unminimize();
+        existing
+            .set_focus()
+            .map_err(|e| format!("Failed to focus monitor window: {}", e))?;
+    } else {
+        let default_title = match monitor_kind {
+            MonitorKind::RalphLoop => "RALPH Loop Monitor",
+            MonitorKind::TestPlan => "Test Run Monitor",
+        };
+        let url = format!("index.html#/monitor/{}/{}", monitor_kind.as_str(), target_id);
+        WebviewWindowBuilder::new(&app, &label, WebviewUrl::App(url.into()))
+            .title(title.unwrap_or_else(|| default_title.to_string()))
+            .inner_size(760.0, 620.0)
+            .min_inner_size(480.0, 360.0)
+            .build()
+            .map_err(|e| format!("Failed to open monitor window: {}", e))?;
+    }
+
+    Ok(MonitorWindow {
+        label,
+        kind: monitor_kind.as_str().to_string(),
+        target_id,
+    })
+}
+
+/// Close the monitor window for a target. Returns false if it was not open.
+#[tauri::command]
+pub async fn close_monitor_window(
+    kind: String,
+    target_id: String,
+    app: AppHandle,
+) -> Result<bool, String> {
+    let monitor_kind = MonitorKind::parse(&kind)
+        .ok_or_else(|| format!("Unknown monitor kind '{}'; expected 'ralph' or 'test_plan'", kind))?;
+
+    match app.get_webview_window(&monitor::monitor_label(monitor_kind, &target_id)) {
+        Some(window) => {
+            window
+                .close()
+                .map_err(|e| format!("Failed to close monitor window: {}", e))?;
+            Ok(true)
+        }
+        None => Ok(false),
+    }
+}
+
+/// List all open monitor windows.
+#[tauri::command]
+pub async fn list_monitor_windows(app: AppHandle) -> Result<Vec<MonitorWindow>, String> {
+    let mut windows: Vec<MonitorWindow> = app
+        .webview_windows()
+        .keys()
+        .filter_map(|label| {
+            monitor::parse_monitor_label(label).map(|(kind, target_id)| MonitorWindow {
+                label: label.clone(),
+                kind: kind.as_str().to_string(),
+                target_id,
+            })
+        })
+        .collect();
+    windows.sort_by(|a, b| a.label.cmp(&b.label));
+    Ok(windows)
+}
@@ -5,36 +5,87 @@
 //! - Provide a single function to call the Claude API
 //! - Handle request construction, authentication, and response parsing
 //! - Read the API key from the settings table
+//! - Keep files matching a project's "never send to AI" globs out of API requests
 //!
 //! DEPENDENCIES:
 //! - reqwest - HTTP client for API calls
 //! - serde_json - JSON request/response handling
-//! - rusqlite - Database access for API key retrieval
+//! - rusqlite - Database access for API key retrieval and stored token limits
+//! - models::ai_limits - AiTokenLimits
+//! - core::ai_audit - Record calls made inside a project's audit scope
 //!
 //! EXPORTS:
 //! - MODEL - The Claude model ID string (single source of truth for all callers)
+//! - ANTHROPIC_VERSION - anthropic-version header sent with every API request
+//! - API_URL_ENV - Environment variable that redirects call_claude/call_claude_long (test builds only)
 //! - call_claude - Send a prompt to the Claude API and return the text response (4096 max_tokens)
 //! - call_claude_long - Same as call_claude but with 8192 max_tokens for large code output
 //! - get_api_key - Read and decrypt the Anthropic API key from the settings table
+//! - TOKEN_LIMITS_SETTING_PREFIX - Settings key prefix for a model's token limits
+//! - default_token_limits / token_limits / set_token_limits / validate_token_limits - Per-model prompt budgets
+//! - load_token_limits - Apply MODEL's stored token limits at startup
+//! - estimate_tokens - Heuristic Claude token count for prompt text
+//! - truncate_to_tokens / truncate_tail_to_tokens - Keep the start / end of text within a token budget
+//! - split_by_tokens - Group consecutive lines or messages into ranges within a token budget
+//! - AI_EXCLUDE_FILE - Project-relative path of the "never send to AI" glob list
+//! - load_ai_excludes / save_ai_excludes - Read and write a project's exclusion globs
+//! - glob_matches - Match a project-relative path against one exclusion glob
+//! - is_ai_excluded - Whether a project-relative path matches any exclusion glob
+//! - ensure_ai_allowed - Err for a file that must not be sent to the API
+//! - excluded_references - Excluded paths mentioned in free text (RALPH prompts)
 //!
 //! PATTERNS:
 //! - call_claude is async and returns Result<String, String>
 //! - API key is stored as "anthropic_api_key" in the settings table
 //! - Model used: claude-sonnet-4-5-20250929
 //! - Errors are mapped to descriptive strings for IPC
+//! - Exclusion globs follow .gitignore basics: a glob without "/" matches any path
+//!   component ("*.env*", "secrets"), one with "/" is anchored at the project root,
+//!   "**" spans directories, and a match on a directory covers everything below it
 //!
 //! CLAUDE NOTES:
 //! - The API key is stored encrypted in SQLite settings table (prefixed with "enc:")
 //! - get_api_key automatically decrypts the key before returning
 //! - max_tokens defaults to 4096 for generation requests (call_claude_long uses 8192)
 //! - Response format: { content: [{ type: "text", text: "..." }] }
+//! - Callers wrap call_claude/call_claude_long in core::ai_queue::run so calls share the app-wide limit
+//! - Project work wraps its calls in core::ai_audit::scoped so opted-in projects log every exchange
+//! - Callers that put file content into a prompt must check ensure_ai_allowed (or
+//!   is_ai_excluded) first; excluded files get template-based docs only
+//! - Exclusions live in <project>/.claude/ai-exclude so the git hooks can read them
+//! - Prompt content is sized with estimate_tokens against token_limits(), never by char count;
+//!   analyzer (doc files and chunks), RALPH issue extraction, and session analysis use it
+//! - Token limits are kept in memory like the AI queue limit: loaded at startup, replaced by
+//!   set_token_limits when commands::ai_queue saves new ones
 
+use std::fs;
+use std::ops::Range;
+use std::path::Path;
+use std::sync::{OnceLock, RwLock};
+
+use regex::Regex;
 use rusqlite::Connection;
 use serde_json::json;
 
+use crate::core::ai_audit;
+use crate::models::ai_limits::AiTokenLimits;
+
 pub const MODEL: &str = "claude-sonnet-4-5-20250929";
 const API_URL: &str = "https://api.anthropic.com/v1/messages";
-const ANTHROPIC_VERSION: &str = "2023-06-01";
+
+/// Environment variable that replaces API_URL with a mock server (test builds only).
+#[cfg(test)]
+pub const API_URL_ENV: &str = "JUMPSTART_ANTHROPIC_API_URL";
+
+/// Messages endpoint: the Anthropic API, or JUMPSTART_ANTHROPIC_API_URL in test builds.
+fn api_url() -> String {
+    #[cfg(test)]
+    if let Some(url) = std::env::var(API_URL_ENV).ok().filter(|url| !url.trim().is_empty()) {
+        return url;
+    }
+    API_URL.to_string()
+}
+pub const ANTHROPIC_VERSION: &str = "2023-06-01";
 
 /// Call the Claude API with a system prompt and user prompt.
 /// Returns the text content from the first response block.
@@ -44,47 +95,9 @@ pub async fn call_claude(
     system: &str,
     prompt: &str,
 ) -> Result<String, String> {
-    let body = json!({
-        "model": MODEL,
-        "max_tokens": 4096,
-        "system": system,
-        "messages": [
-            {
-                "role": "user",
-                "content": prompt
-            }
-        ]
-    });
-
-    let response = client
-        .post(API_URL)
-        .header("x-api-key", api_key)
-        .header("anthropic-version", ANTHROPIC_VERSION)
-        .header("content-type", "application/json")
-        .json(&body)
-        .send()
-        .await
-        .map_err(|e| format!("API request failed: {}", e))?;
-
-    let status = response.status();
-    let response_text = response
-        .text()
-        .await
-        .map_err(|e| format!("Failed to read API response: {}", e))?;
-
-    if !status.is_success() {
-        return Err(format!("API returned status {}: {}", status, response_text));
-    }
-
-    let parsed: serde_json::Value = serde_json::from_str(&response_text)
-        .map_err(|e| format!("Failed to parse API response: {}", e))?;
-
-    parsed["content"]
-        .as_array()
-        .and_then(|arr| arr.first())
-        .and_then(|block| block["text"].as_str())
-        .map(|s| s.to_string())
-        .ok_or_else(|| "API response did not contain expected text content".to_string())
+    let result = send_message(client, api_key, system, prompt, 4096).await;
+    ai_audit::record_scoped_call(MODEL, system, prompt, &result);
+    result
 }
 
 /// Call the Claude API with a higher token limit (8192) for large code output.
@@ -94,10 +107,22 @@ pub async fn call_claude_long(
     api_key: &str,
     system: &str,
     prompt: &str,
+) -> Result<String, String> {
+    let result = send_message(client, api_key, system, prompt, 8192).await;
+    ai_audit::record_scoped_call(MODEL, system, prompt, &result);
+    result
+}
+
+async fn send_message(
+    client: &reqwest::Client,
+    api_key: &str,
+    system: &str,
+    prompt: &str,
+    max_tokens: u32,
 ) -> Result<String, String> {
     let body = json!({
         "model": MODEL,
-        "max_tokens": 8192,
+        "max_tokens": max_tokens,
         "system": system,
         "messages": [
             {
@@ -108,7 +133,7 @@ pub async fn call_claude_long(
     });
 
     let response = client
-        .post(API_URL)
+        .post(api_url())
         .header("x-api-key", api_key)
         .header("anthropic-version", ANTHROPIC_VERSION)
         .header("content-type", "application/json")
@@ -159,10 +184,410 @@ pub fn get_api_key(db: &Connection) -> Result<String, String> {
     }
 }
 
+/// Settings key prefix for a model's token limits (JSON AiTokenLimits), e.g. "ai.token_limits.<model>".
+pub const TOKEN_LIMITS_SETTING_PREFIX: &str = "ai.token_limits.";
+
+/// Tokens every budget leaves free for the system prompt and the longest response (call_claude_long).
+const RESERVED_TOKENS: usize = 8192 + 4_000;
+
+/// Smallest budget accepted for any prompt section.
+const MIN_BUDGET_TOKENS: usize = 200;
+
+/// Tokens reserved for the "[... truncated ~N tokens]" marker.
+const TRUNCATION_MARKER_TOKENS: usize = 16;
+
+/// Default token limits for a model.
+pub fn default_token_limits(model: &str) -> AiTokenLimits {
+    AiTokenLimits {
+        model: model.to_string(),
+        ..AiTokenLimits::default()
+    }
+}
+
+fn limits_cell() -> &'static RwLock<AiTokenLimits> {
+    static LIMITS: OnceLock<RwLock<AiTokenLimits>> = OnceLock::new();
+    LIMITS.get_or_init(|| RwLock::new(default_token_limits(MODEL)))
+}
+
+/// Token limits in effect for MODEL.
+pub fn token_limits() -> AiTokenLimits {
+    limits_cell()
+        .read()
+        .map(|l| l.clone())
+        .unwrap_or_else(|_| default_token_limits(MODEL))
+}
+
+/// Reject budgets that are too small to be useful or leave no room in the context window.
+pub fn validate_token_limits(limits: &AiTokenLimits) -> Result<(), String> {
+    let max = limits.context_tokens.saturating_sub(RESERVED_TOKENS);
+    let budgets = [
+        ("Doc file", limits.doc_file_tokens),
+        ("Doc chunk", limits.doc_chunk_tokens),
+        ("Issue output", limits.issue_output_tokens),
+        ("Session transcript", limits.session_transcript_tokens),
+    ];
+    for (label, tokens) in budgets {
+        if tokens < MIN_BUDGET_TOKENS || tokens > max {
+            return Err(format!(
+                "{} budget must be between {} and {} tokens for a {}-token context window.",
+                label, MIN_BUDGET_TOKENS, max, limits.context_tokens
+            ));
+        }
+    }
+    Ok(())
+}
+
+/// Replace the limits in effect for MODEL (validated first).
+pub fn set_token_limits(mut limits: AiTokenLimits) -> Result<AiTokenLimits, String> {
+    validate_token_limits(&limits)?;
+    limits.model = MODEL.to_string();
+    let mut current = limits_cell().write().map_err(|e| format!("Token limits lock error: {}", e))?;
+    *current = limits.clone();
+    Ok(limits)
+}
+
+/// Apply MODEL's stored token limits. Called once at startup.
+pub fn load_token_limits(db: &Connection) {
+    let stored: Option<AiTokenLimits> = db
+        .query_row(
+            "SELECT value FROM settings WHERE key = ?1",
+            [format!("{}{}", TOKEN_LIMITS_SETTING_PREFIX, MODEL)],
+            |row| row.get::<_, String>(0),
+        )
+        .ok()
+        .and_then(|json| serde_json::from_str(&json).ok());
+    if let Some(limits) = stored {
+        if let Err(e) = set_token_limits(limits) {
+            tracing::warn!(error = %e, "Ignoring stored AI token limits");
+        }
+    }
+}
+
+/// Estimated Claude token count. Runs of ASCII letters/digits count one token per 4 chars,
+/// every other non-space char (punctuation, non-ASCII) counts one, and so does each line
+/// break and each 4 chars of indentation. Errs high for code, which is the safe direction.
+pub fn estimate_tokens(text: &str) -> usize {
+    let (mut tokens, mut word, mut spaces) = (0usize, 0usize, 0usize);
+    for c in text.chars() {
+        if c.is_ascii_alphanumeric() || c == '_' {
+            if spaces > 1 {
+                tokens += spaces / 4;
+            }
+            spaces = 0;
+            word += 1;
+            continue;
+        }
+        tokens += word.div_ceil(4);
+        word = 0;
+        if c == ' ' || c == '\t' {
+            spaces += 1;
+            continue;
+        }
+        // A single space merges into the next token; longer runs are indentation
+        if spaces > 1 {
+            tokens += spaces / 4;
+        }
+        spaces = 0;
+        tokens += 1;
+    }
+    tokens + word.div_ceil(4) + if spaces > 1 { spaces / 4 } else { 0 }
+}
+
+/// Byte length of the longest prefix of `text` estimated within `max_tokens`.
+fn fitting_prefix_len(text: &str, max_tokens: usize) -> usize {
+    let bounds: Vec<usize> = text.char_indices().map(|(i, _)| i).chain(std::iter::once(text.len())).collect();
+    let (mut lo, mut hi) = (0, bounds.len() - 1);
+    while lo < hi {
+        let mid = (lo + hi).div_ceil(2);
+        if estimate_tokens(&text[..bounds[mid]]) <= max_tokens {
+            lo = mid;
+        } else {
+            hi = mid - 1;
+        }
+    }
+    bounds[lo]
+}
+
+/// Byte offset where the longest suffix of `text` estimated within `max_tokens` starts.
+fn fitting_suffix_start(text: &str, max_tokens: usize) -> usize {
+    let bounds: Vec<usize> = text.char_indices().map(|(i, _)| i).chain(std::iter::once(text.len())).collect();
+    let (mut lo, mut hi) = (0, bounds.len() - 1);
+    while lo < hi {
+        let mid = (lo + hi) / 2;
+        if estimate_tokens(&text[bounds[mid]..]) <= max_tokens {
+            hi = mid;
+        } else {
+            lo = mid + 1;
+        }
+    }
+    bounds[lo]
+}
+
+/// Keep the start of `text` within `max_tokens`, cut at a line break when one is near the
+/// end, followed by a marker saying how much was dropped. Short text is returned unchanged.
+pub fn truncate_to_tokens(text: &str, max_tokens: usize) -> String {
+    let total = estimate_tokens(text);
+    if total <= max_tokens {
+        return text.to_string();
+    }
+    let mut cut = fitting_prefix_len(text, max_tokens.saturating_sub(TRUNCATION_MARKER_TOKENS));
+    if let Some(newline) = text[..cut].rfind('\n').filter(|&n| n >= cut / 2) {
+        cut = newline;
+    }
+    format!(
+        "{}\n[... truncated ~{} tokens]",
+        &text[..cut],
+        total.saturating_sub(estimate_tokens(&text[..cut]))
+    )
+}
+
+/// Keep the end of `text` within `max_tokens` (where tool output puts its errors and summary),
+/// starting at a line break when one is near, after a marker saying how much was dropped.
+pub fn truncate_tail_to_tokens(text: &str, max_tokens: usize) -> String {
+    let total = estimate_tokens(text);
+    if total <= max_tokens {
+        return text.to_string();
+    }
+    let mut start = fitting_suffix_start(text, max_tokens.saturating_sub(TRUNCATION_MARKER_TOKENS));
+    if let Some(newline) = text[start..].find('\n').filter(|&n| n <= (text.len() - start) / 2) {
+        start += newline + 1;
+    }
+    format!(
+        "[... truncated ~{} tokens]\n{}",
+        total.saturating_sub(estimate_tokens(&text[start..])),
+        &text[start..]
+    )
+}
+
+/// Group consecutive pieces (lines, messages) into ranges whose estimated size, counting one
+/// token per separator, stays within `max_tokens`. A piece larger than the budget gets a range
+/// of its own; callers truncate it if needed.
+pub fn split_by_tokens(pieces: &[&str], max_tokens: usize) -> Vec<Range<usize>> {
+    let mut ranges = Vec::new();
+    let (mut start, mut used) = (0, 0);
+    for (i, piece) in pieces.iter().enumerate() {
+        let tokens = estimate_tokens(piece) + 1;
+        if used + tokens > max_tokens && i > start {
+            ranges.push(start..i);
+            start = i;
+            used = 0;
+        }
+        used += tokens;
+    }
+    if start < pieces.len() {
+        ranges.push(start..pieces.len());
+    }
+    ranges
+}
+
+/// Project-relative file listing globs whose files are never sent to the API.
+pub const AI_EXCLUDE_FILE: &str = ".claude/ai-exclude";
+
+/// Read a project's "never send to AI" globs (empty when the file is missing).
+pub fn load_ai_excludes(project_path: &str) -> Vec<String> {
+    fs::read_to_string(Path::new(project_path).join(AI_EXCLUDE_FILE))
+        .map(|content| {
+            content
+                .lines()
+                .map(|l| l.trim().trim_start_matches("./").to_string())
+                .filter(|l| !l.is_empty() && !l.starts_with('#'))
+                .collect()
+        })
+        .unwrap_or_default()
+}
+
+/// Replace a project's "never send to AI" globs.
+pub fn save_ai_excludes(project_path: &str, globs: &[String]) -> Result<(), String> {
+    let path = Path::new(project_path).join(AI_EXCLUDE_FILE);
+    if let Some(parent) = path.parent() {
+        fs::create_dir_all(parent)
+            .map_err(|e| format!("Failed to create {}: {}", parent.display(), e))?;
+    }
+
+    let mut content = String::from(
+        "# Files Project Jumpstart never sends to the AI (template docs only).\n\
+         # One glob per line, e.g. secrets/ or *.env*\n",
+    );
+    for glob in globs {
+        let glob = glob.trim().trim_start_matches("./");
+        if !glob.is_empty() {
+            content.push_str(glob);
+            content.push('\n');
+        }
+    }
+
+    fs::write(&path, content).map_err(|e| format!("Failed to write {}: {}", path.display(), e))
+}
+
+fn glob_regex(glob: &str) -> Option<Regex> {
+    let glob = glob.trim().trim_start_matches("./").trim_end_matches('/');
+    if glob.is_empty() {
+        return None;
+    }
+    let anchored = glob.contains('/');
+    let chars: Vec<char> = glob.trim_start_matches('/').chars().collect();
+
+    let mut re = String::from(if anchored { "^" } else { "^(?:.*/)?" });
+    let mut i = 0;
+    while i < chars.len() {
+        match chars[i] {
+            '*' if chars.get(i + 1) == Some(&'*') => {
+                if chars.get(i + 2) == Some(&'/') {
+                    re.push_str("(?:.*/)?");
+                    i += 3;
+                } else {
+                    re.push_str(".*");
+                    i += 2;
+                }
+                continue;
+            }
+            '*' => re.push_str("[^/]*"),
+            '?' => re.push_str("[^/]"),
+            c => re.push_str(&regex::escape(&c.to_string())),
+        }
+        i += 1;
+    }
+    re.push_str("(?:/.*)?$");
+    Regex::new(&re).ok()
+}
+
+/// Whether a project-relative path (forward slashes) matches an exclusion glob.
+pub fn glob_matches(glob: &str, rel_path: &str) -> bool {
+    let rel_path = rel_path.trim_start_matches("./").trim_end_matches('/');
+    glob_regex(glob).is_some_and(|re| re.is_match(rel_path))
+}
+
+/// Whether a project-relative path matches any of the globs.
+pub fn is_ai_excluded(globs: &[String], rel_path: &str) -> bool {
+    globs.iter().any(|g| glob_matches(g, rel_path))
+}
+
+/// Err when `file_path` (absolute or project-relative) matches the project's
+/// exclusion globs, so callers fall back to local, template-based handling.
+pub fn ensure_ai_allowed(project_path: &str, file_path: &str) -> Result<(), String> {
+    let globs = load_ai_excludes(project_path);
+    if globs.is_empty() {
+        return Ok(());
+    }
+    let normalized = file_path.replace('\\', "/");
+    let root = format!("{}/", project_path.replace('\\', "/").trim_end_matches('/'));
+    let rel = normalized.strip_prefix(&root).unwrap_or(&normalized);
+    if is_ai_excluded(&globs, rel) {
+        return Err(format!("{} is excluded from AI by {}", rel, AI_EXCLUDE_FILE));
+    }
+    Ok(())
+}
+
+/// Path-like tokens in `text` that match the globs, deduplicated in order of appearance.
+pub fn excluded_references(text: &str, globs: &[String]) -> Vec<String> {
+    if globs.is_empty() {
+        return Vec::new();
+    }
+    let mut found: Vec<String> = Vec::new();
+    let tokens = text.split(|c: char| c.is_whitespace() || matches!(c, '`' | '"' | '\'' | '(' | ')' | ',' | '[' | ']'));
+    for token in tokens {
+        let token = token
+            .trim_end_matches(['.', ':', ';', '!', '?'])
+            .trim_start_matches("./")
+            .trim_end_matches('/');
+        if token.is_empty() || !(token.contains('/') || token.contains('.')) {
+            continue;
+        }
+        if is_ai_excluded(globs, token) && !found.iter().any(|f| f == token) {
+            found.push(token.to_string());
+        }
+    }
+    found
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
 
+    #[test]
+    fn test_estimate_tokens() {
+        assert_eq!(estimate_tokens(""), 0);
+        assert_eq!(estimate_tokens("hello world"), 4);
+        assert_eq!(estimate_tokens("fn main() {}"), 6);
+        assert_eq!(estimate_tokens("        let x = 1;\n"), 8);
+        assert_eq!(estimate_tokens("日本語"), 3);
+    }
+
+    #[test]
+    fn test_truncate_to_tokens_keeps_start_or_end() {
+        let text: String = (0..200).map(|i| format!("line {} of the output\n", i)).collect();
+        assert_eq!(truncate_to_tokens("short", 100), "short");
+
+        let head = truncate_to_tokens(&text, 100);
+        assert!(estimate_tokens(&head) <= 100, "{}", estimate_tokens(&head));
+        assert!(head.starts_with("line 0 of"));
+        assert!(head.contains("[... truncated ~"));
+
+        let tail = truncate_tail_to_tokens(&text, 100);
+        assert!(estimate_tokens(&tail) <= 100);
+        assert!(tail.starts_with("[... truncated ~"));
+        assert!(tail.ends_with("line 199 of the output\n"));
+        assert!(tail.lines().nth(1).unwrap().starts_with("line "), "starts at a line break");
+
+        // Never splits a multi-byte character
+        let wide = "é".repeat(500);
+        assert!(truncate_to_tokens(&wide, 50).starts_with("éé"));
+        assert!(truncate_tail_to_tokens(&wide, 50).ends_with("éé"));
+    }
+
+    #[test]
+    fn test_split_by_tokens() {
+        let pieces = ["aaaa aaaa", "bbbb", "cccc cccc cccc cccc cccc cccc", "dd"];
+        assert_eq!(split_by_tokens(&pieces, 5), vec![0..2, 2..3, 3..4]);
+        assert_eq!(split_by_tokens(&pieces, 1000), vec![0..4]);
+        assert!(split_by_tokens(&[], 10).is_empty());
+    }
+
+    #[test]
+    fn test_token_limits_validation() {
+        let limits = default_token_limits(MODEL);
+        assert!(validate_token_limits(&limits).is_ok());
+        assert!(validate_token_limits(&AiTokenLimits { doc_chunk_tokens: 50, ..limits.clone() }).is_err());
+        assert!(validate_token_limits(&AiTokenLimits { context_tokens: 8_000, ..limits }).is_err());
+    }
+
+    #[test]
+    fn test_glob_matches() {
+        assert!(glob_matches("secrets/", "secrets/prod.json"));
+        assert!(glob_matches("secrets", "config/secrets/key.pem"));
+        assert!(glob_matches("*.env*", ".env.local"));
+        assert!(glob_matches("*.env*", "app/config/.env"));
+        assert!(glob_matches("src/algo/**", "src/algo/pricing/model.rs"));
+        assert!(glob_matches("src/**/*.key", "src/a/b/c.key"));
+        assert!(glob_matches("src/**/*.key", "src/c.key"));
+        assert!(glob_matches("/src/algo", "src/algo/x.py"));
+        assert!(!glob_matches("src/algo", "lib/src/algo/x.py"));
+        assert!(!glob_matches("*.env*", "src/environment.ts"));
+        assert!(!glob_matches("secrets/", "src/secretsauce.rs"));
+        assert!(!glob_matches("", "anything"));
+    }
+
+    #[test]
+    fn test_ai_excludes_roundtrip_and_ensure() {
+        let dir = tempfile::tempdir().unwrap();
+        let root = dir.path().to_str().unwrap();
+        assert!(ensure_ai_allowed(root, &format!("{}/secrets/a.txt", root)).is_ok());
+
+        save_ai_excludes(root, &["./secrets/".to_string(), " ".to_string(), "*.env*".to_string()]).unwrap();
+        assert_eq!(load_ai_excludes(root), vec!["secrets/", "*.env*"]);
+        assert!(ensure_ai_allowed(root, &format!("{}/secrets/a.txt", root)).is_err());
+        assert!(ensure_ai_allowed(root, ".env").is_err());
+        assert!(ensure_ai_allowed(root, &format!("{}/src/main.rs", root)).is_ok());
+    }
+
+    #[test]
+    fn test_excluded_references() {
+        let globs = vec!["secrets/".to_string(), "*.env*".to_string()];
+        let prompt = "Read `secrets/api.json` and .env.production, then update src/app.ts. Also check secrets/api.json.";
+        assert_eq!(excluded_references(prompt, &globs), vec!["secrets/api.json", ".env.production"]);
+        assert!(excluded_references(prompt, &[]).is_empty());
+    }
+
     #[test]
     fn test_parse_api_response() {
         let response_json = r#"{
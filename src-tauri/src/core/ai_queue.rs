@@ -0,0 +1,381 @@
+//! @module core/ai_queue
+//! @description Shared priority queue that gates every Claude API call
+//!
+//! PURPOSE:
+//! - Limit how many AI calls run at once across the whole app
+//! - Start the highest-priority waiting call first (interactive before background)
+//! - Keep one slot free for interactive calls so batch work cannot starve them
+//! - Pause and resume non-interactive work; report queued/running jobs
+//!
+//! DEPENDENCIES:
+//! - rusqlite - Concurrency limit stored in settings
+//! - tokio - Notify to wake waiters when a slot frees
+//! - models::ai_queue - AiJob, AiJobKind, AiPriority, AiQueueStatus
+//!
+//! EXPORTS:
+//! - MAX_CONCURRENT_SETTING - Settings key for the concurrency limit
+//! - AiQueue - Queue state plus wake-up notifier
+//! - AiPermit - Slot held while an AI call runs (released on drop)
+//! - queue - The app-wide queue
+//! - run - Await a slot, then run the future holding it
+//! - load_settings - Apply the stored concurrency limit at startup
+//!
+//! PATTERNS:
+//! - Wrap the API call, not the surrounding work: ai_queue::run(kind, label, ai::call_claude(..))
+//! - Ordering is (priority, enqueue order); changing a queued job's priority re-sorts it
+//! - Pausing holds queued non-interactive jobs; running jobs finish normally
+//!
+//! CLAUDE NOTES:
+//! - A permit dropped while still queued (cancelled future) removes its job, so
+//!   abandoned callers never block the queue
+//! - The pause flag is in memory only; the app always starts unpaused
+
+use rusqlite::Connection;
+use std::future::Future;
+use std::sync::{Mutex, OnceLock};
+use tokio::sync::Notify;
+
+use crate::models::ai_queue::{AiJob, AiJobKind, AiJobState, AiPriority, AiQueueStatus};
+
+/// Settings key for the maximum number of concurrent AI calls.
+pub const MAX_CONCURRENT_SETTING: &str = "ai_queue.max_concurrent";
+
+/// Concurrency limit when the setting is unset.
+pub const DEFAULT_MAX_CONCURRENT: u32 = 3;
+
+/// Upper bound accepted for the concurrency limit.
+pub const MAX_CONCURRENT_LIMIT: u32 = 8;
+
+/// Slots non-interactive jobs may never take (when the limit allows it).
+const RESERVED_INTERACTIVE_SLOTS: u32 = 1;
+
+struct Entry {
+    job: AiJob,
+    seq: u64,
+}
+
+struct QueueState {
+    paused: bool,
+    max_concurrent: u32,
+    next_seq: u64,
+    entries: Vec<Entry>,
+}
+
+impl QueueState {
+    fn new(max_concurrent: u32) -> Self {
+        QueueState {
+            paused: false,
+            max_concurrent,
+            next_seq: 0,
+            entries: Vec::new(),
+        }
+    }
+
+    fn enqueue(&mut self, kind: AiJobKind, label: &str) -> String {
+        let id = uuid::Uuid::new_v4().to_string();
+        self.next_seq += 1;
+        self.entries.push(Entry {
+            job: AiJob {
+                id: id.clone(),
+                kind,
+                priority: kind.default_priority(),
+                label: label.to_string(),
+                state: AiJobState::Queued,
+                queued_at: chrono::Utc::now().to_rfc3339(),
+                started_at: None,
+            },
+            seq: self.next_seq,
+        });
+        id
+    }
+
+    fn running(&self) -> u32 {
+        self.entries.iter().filter(|e| e.job.state == AiJobState::Running).count() as u32
+    }
+
+    /// Whether a queued job with this priority fits right now, ignoring order.
+    fn fits(&self, priority: AiPriority) -> bool {
+        if self.running() >= self.max_concurrent {
+            return false;
+        }
+        if priority == AiPriority::Interactive {
+            return true;
+        }
+        if self.paused {
+            return false;
+        }
+        let shared_slots = self.max_concurrent.saturating_sub(RESERVED_INTERACTIVE_SLOTS).max(1);
+        let running_shared = self
+            .entries
+            .iter()
+            .filter(|e| e.job.state == AiJobState::Running && e.job.priority != AiPriority::Interactive)
+            .count() as u32;
+        running_shared < shared_slots
+    }
+
+    /// The queued job that should start next, if any can start.
+    fn next_to_start(&self) -> Option<&str> {
+        self.entries
+            .iter()
+            .filter(|e| e.job.state == AiJobState::Queued && self.fits(e.job.priority))
+            .min_by_key(|e| (e.job.priority, e.seq))
+            .map(|e| e.job.id.as_str())
+    }
+
+    /// Mark `id` running if it is next in line.
+    fn try_start(&mut self, id: &str) -> bool {
+        if self.next_to_start() != Some(id) {
+            return false;
+        }
+        if let Some(entry) = self.entries.iter_mut().find(|e| e.job.id == id) {
+            entry.job.state = AiJobState::Running;
+            entry.job.started_at = Some(chrono::Utc::now().to_rfc3339());
+            return true;
+        }
+        false
+    }
+
+    fn remove(&mut self, id: &str) {
+        self.entries.retain(|e| e.job.id != id);
+    }
+
+    fn set_priority(&mut self, id: &str, priority: AiPriority) -> Result<(), String> {
+        let entry = self
+            .entries
+            .iter_mut()
+            .find(|e| e.job.id == id)
+            .ok_or_else(|| format!("AI job {} is not in the queue", id))?;
+        if entry.job.state != AiJobState::Queued {
+            return Err("Only queued AI jobs can be re-prioritized".to_string());
+        }
+        entry.job.priority = priority;
+        Ok(())
+    }
+
+    fn status(&self) -> AiQueueStatus {
+        let mut running: Vec<&Entry> = self.entries.iter().filter(|e| e.job.state == AiJobState::Running).collect();
+        running.sort_by_key(|e| e.seq);
+        let mut queued: Vec<&Entry> = self.entries.iter().filter(|e| e.job.state == AiJobState::Queued).collect();
+        queued.sort_by_key(|e| (e.job.priority, e.seq));
+
+        AiQueueStatus {
+            paused: self.paused,
+            max_concurrent: self.max_concurrent,
+            running: running.len() as u32,
+            queued: queued.len() as u32,
+            jobs: running.into_iter().chain(queued).map(|e| e.job.clone()).collect(),
+        }
+    }
+}
+
+/// Queue state plus the notifier waiters sleep on.
+pub struct AiQueue {
+    state: Mutex<QueueState>,
+    notify: Notify,
+}
+
+/// A job's place in the queue. Holding a started permit occupies a slot;
+/// dropping it (started or not) removes the job and wakes waiters.
+pub struct AiPermit {
+    queue: &'static AiQueue,
+    id: String,
+}
+
+impl Drop for AiPermit {
+    fn drop(&mut self) {
+        if let Ok(mut state) = self.queue.state.lock() {
+            state.remove(&self.id);
+        }
+        self.queue.notify.notify_waiters();
+    }
+}
+
+impl AiQueue {
+    fn new(max_concurrent: u32) -> Self {
+        AiQueue {
+            state: Mutex::new(QueueState::new(max_concurrent)),
+            notify: Notify::new(),
+        }
+    }
+
+    /// Queue a job and wait until it may start.
+    pub async fn acquire(&'static self, kind: AiJobKind, label: &str) -> AiPermit {
+        let id = match self.state.lock() {
+            Ok(mut state) => state.enqueue(kind, label),
+            // A poisoned queue must not block AI calls; run unscheduled
+            Err(_) => return AiPermit { queue: self, id: String::new() },
+        };
+        let permit = AiPermit { queue: self, id };
+
+        loop {
+            let notified = self.notify.notified();
+            tokio::pin!(notified);
+            // Register before checking so a release between the check and the await is not missed
+            notified.as_mut().enable();
+
+            let started = self.state.lock().map(|mut s| s.try_start(&permit.id)).unwrap_or(true);
+            if started {
+                // Another slot may still be free for the next waiter
+                self.notify.notify_waiters();
+                return permit;
+            }
+            notified.await;
+        }
+    }
+
+    pub fn status(&self) -> Result<AiQueueStatus, String> {
+        let state = self.state.lock().map_err(|e| format!("AI queue lock error: {}", e))?;
+        Ok(state.status())
+    }
+
+    pub fn set_paused(&self, paused: bool) -> Result<AiQueueStatus, String> {
+        let status = {
+            let mut state = self.state.lock().map_err(|e| format!("AI queue lock error: {}", e))?;
+            state.paused = paused;
+            state.status()
+        };
+        self.notify.notify_waiters();
+        Ok(status)
+    }
+
+    /// Change the concurrency limit (clamped to 1..=MAX_CONCURRENT_LIMIT).
+    pub fn set_max_concurrent(&self, max_concurrent: u32) -> Result<AiQueueStatus, String> {
+        let status = {
+            let mut state = self.state.lock().map_err(|e| format!("AI queue lock error: {}", e))?;
+            state.max_concurrent = max_concurrent.clamp(1, MAX_CONCURRENT_LIMIT);
+            state.status()
+        };
+        self.notify.notify_waiters();
+        Ok(status)
+    }
+
+    /// Change the priority of a queued job.
+    pub fn set_priority(&self, job_id: &str, priority: AiPriority) -> Result<AiQueueStatus, String> {
+        let status = {
+            let mut state = self.state.lock().map_err(|e| format!("AI queue lock error: {}", e))?;
+            state.set_priority(job_id, priority)?;
+            state.status()
+        };
+        self.notify.notify_waiters();
+        Ok(status)
+    }
+}
+
+/// The app-wide AI queue.
+pub fn queue() -> &'static AiQueue {
+    static QUEUE: OnceLock<AiQueue> = OnceLock::new();
+    QUEUE.get_or_init(|| AiQueue::new(DEFAULT_MAX_CONCURRENT))
+}
+
+/// Wait for a slot for `kind`, then run `call` while holding it.
+pub async fn run<T>(kind: AiJobKind, label: &str, call: impl Future<Output = T>) -> T {
+    let _permit = queue().acquire(kind, label).await;
+    call.await
+}
+
+/// Apply the stored concurrency limit. Called once at startup.
+pub fn load_settings(db: &Connection) {
+    let stored = db
+        .query_row(
+            "SELECT value FROM settings WHERE key = ?1",
+            [MAX_CONCURRENT_SETTING],
+            |row| row.get::<_, String>(0),
+        )
+        .ok()
+        .and_then(|v| v.trim().parse::<u32>().ok());
+    if let Some(limit) = stored {
+        let _ = queue().set_max_concurrent(limit);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_interactive_jumps_ahead_of_earlier_background() {
+        let mut state = QueueState::new(1);
+        let doc = state.enqueue(AiJobKind::DocGeneration, "doc a");
+        assert!(state.try_start(&doc));
+
+        let later_doc = state.enqueue(AiJobKind::DocGeneration, "doc b");
+        let prompt = state.enqueue(AiJobKind::Interactive, "prompt");
+        assert!(!state.try_start(&prompt), "no free slot while the doc job runs");
+
+        state.remove(&doc);
+        assert!(!state.try_start(&later_doc));
+        assert!(state.try_start(&prompt));
+    }
+
+    #[test]
+    fn test_same_priority_is_fifo() {
+        let mut state = QueueState::new(1);
+        let first = state.enqueue(AiJobKind::SessionAnalysis, "first");
+        let second = state.enqueue(AiJobKind::TestSuggestions, "second");
+        assert!(!state.try_start(&second));
+        assert!(state.try_start(&first));
+    }
+
+    #[test]
+    fn test_slot_reserved_for_interactive() {
+        let mut state = QueueState::new(2);
+        let a = state.enqueue(AiJobKind::DocGeneration, "a");
+        let b = state.enqueue(AiJobKind::DocGeneration, "b");
+        assert!(state.try_start(&a));
+        assert!(!state.try_start(&b), "the last slot is kept for interactive work");
+
+        let prompt = state.enqueue(AiJobKind::Interactive, "prompt");
+        assert!(state.try_start(&prompt));
+    }
+
+    #[test]
+    fn test_single_slot_still_serves_background() {
+        let mut state = QueueState::new(1);
+        let doc = state.enqueue(AiJobKind::DocGeneration, "doc");
+        assert!(state.try_start(&doc));
+    }
+
+    #[test]
+    fn test_pause_holds_background_but_not_interactive() {
+        let mut state = QueueState::new(3);
+        state.paused = true;
+        let doc = state.enqueue(AiJobKind::DocGeneration, "doc");
+        let prompt = state.enqueue(AiJobKind::Interactive, "prompt");
+        assert!(!state.try_start(&doc));
+        assert!(state.try_start(&prompt));
+
+        state.paused = false;
+        assert!(state.try_start(&doc));
+    }
+
+    #[test]
+    fn test_set_priority_only_for_queued_jobs() {
+        let mut state = QueueState::new(1);
+        let running = state.enqueue(AiJobKind::SessionAnalysis, "running");
+        assert!(state.try_start(&running));
+        assert!(state.set_priority(&running, AiPriority::Background).is_err());
+        assert!(state.set_priority("missing", AiPriority::Normal).is_err());
+
+        let a = state.enqueue(AiJobKind::TestSuggestions, "a");
+        let b = state.enqueue(AiJobKind::DocGeneration, "b");
+        state.set_priority(&b, AiPriority::Interactive).unwrap();
+        state.remove(&running);
+        assert!(!state.try_start(&a));
+        assert!(state.try_start(&b));
+    }
+
+    #[test]
+    fn test_status_lists_running_then_queued_in_start_order() {
+        let mut state = QueueState::new(1);
+        let running = state.enqueue(AiJobKind::Interactive, "running");
+        assert!(state.try_start(&running));
+        state.enqueue(AiJobKind::DocGeneration, "doc");
+        state.enqueue(AiJobKind::SessionAnalysis, "session");
+
+        let status = state.status();
+        assert_eq!(status.running, 1);
+        assert_eq!(status.queued, 2);
+        let labels: Vec<&str> = status.jobs.iter().map(|j| j.label.as_str()).collect();
+        assert_eq!(labels, vec!["running", "session", "doc"]);
+    }
+}
@@ -11,6 +11,14 @@
 //! DEPENDENCIES:
 //! - models::module_doc - ModuleStatus, ModuleDoc types
 //! - core::ai - Claude API caller for AI-powered doc generation
+//! - core::ai_queue - Prioritized slot for each Claude call
+//! - core::ai_audit - Attribute doc generation calls to the project's audit log
+//! - core::notebook - Jupyter notebook flattening and header cell edits
+//! - core::sql_schema - CREATE statement detection for .sql exports
+//! - core::scanner - Archetype detection for archetype-specific inference tables
+//! - core::plugins - Custom analyzer plugins for extensions not handled here
+//! - core::safe_read - Lossy, size-capped reads for read_source
+//! - core::patch - Unified diffs applied through the configured engine for header writes
 //! - std::path - File path operations
 //! - std::fs - File system reading
 //!
@@ -19,18 +27,41 @@
 //! - parse_doc_header - Extract ModuleDoc from file content
 //! - generate_module_doc_for_file - Generate a ModuleDoc template for a file
 //! - generate_module_doc_with_ai - Generate a ModuleDoc using the Claude API
+//! - split_into_chunks - Split large file content at top-level declarations
+//! - ContentChunk - A line range of a file produced by split_into_chunks
 //! - apply_doc_to_file - Prepend or replace doc header in a file
+//! - render_doc_into - File content with a ModuleDoc applied as its header (no write)
+//! - sync_exports_section - Rewrite only the EXPORTS bullets of a header to match detected exports
+//! - sync_exports_in_file - Apply sync_exports_section to a file on disk
 //! - detect_exports - Pattern-based export detection for a file's content
 //! - detect_imports - Pattern-based import detection for a file's content
+//! - read_source - Read a file as analyzable text (notebooks flattened to header + code; lossy UTF-8, size-capped)
+//! - max_source_bytes - On-disk size limit for doc generation/sync (higher for notebooks)
 //! - is_documentable - Check if a filename should have documentation
+//! - is_generated_content - Detect @generated / DO NOT EDIT markers and minified code
+//! - is_ignored_content - Detect the in-file IGNORE_MARKER opt-out
+//! - IGNORE_MARKER - In-file marker that excludes a file from auto-docs, hooks, and freshness
+//! - should_track_file - Combine filename rules, generated detection, ignore marker, and overrides
+//! - load_generated_overrides / save_generated_overrides - Per-project override list
+//! - GENERATED_OVERRIDES_FILE - Project-relative path of the override list
+//! - DOC_EXTENSIONS - Extensions handled by the built-in analyzer
+//! - has_doc_extension - Built-in or plugin-handled extension check for a filename
 //!
 //! PATTERNS:
 //! - Uses pattern-based detection (regex-like string matching), not tree-sitter AST
 //! - Skips node_modules, target, dist, build, .git, __pycache__ directories
-//! - Recognizes .ts, .tsx, .js, .jsx, .rs, .py, .go, .java, .kt, .swift extensions
-//! - Doc status: "current" (fresh), "outdated" (stale docs), "missing" (no header)
+//! - Skips generated/vendored files (.pb.go, .generated.ts, .min.js, @generated,
+//!   DO NOT EDIT, minified) unless listed in .claude/generated-overrides
+//! - Files with a `jumpstart:ignore` comment in their first 20 lines are reported with
+//!   status "ignored" and never documented, hooked, or flagged stale; overrides do not apply
+//! - Recognizes .ts, .tsx, .js, .jsx, .rs, .py, .go, .java, .kt, .swift, .ipynb, .tf, .sql extensions,
+//!   plus any extension claimed by an installed analyzer plugin
+//! - Doc status: "current" (fresh), "outdated" (stale docs), "missing" (no header),
+//!   "ignored" (IGNORE_MARKER opt-out)
 //! - Phase 5 freshness detection is integrated via core::freshness
-//! - AI generation truncates file content to ~8k chars to stay within prompt limits
+//! - AI generation sends files within the doc_file_tokens budget (core::ai::token_limits)
+//!   whole; larger files are split by top-level declarations into doc_chunk_tokens chunks,
+//!   each chunk is summarized, and the header is generated from the summaries (chunk-and-summarize)
 //!
 //! CLAUDE NOTES:
 //! - TypeScript/JS doc headers use /** ... */ with @module/@description (JSDoc)
@@ -40,16 +71,45 @@
 //! - Java doc headers use /** ... */ with @module/@description (Javadoc)
 //! - Kotlin doc headers use /** ... */ with @module/@description (KDoc)
 //! - Swift doc headers use /// with @module/@description (Swift markup)
+//! - Terraform (.tf) doc headers use # with @module/@description (HCL comments); exports are
+//!   var.<name> for variable blocks and output.<name> for outputs, imports are module sources
+//! - SQL (.sql) doc headers use -- with @module/@description; exports are the tables, views,
+//!   functions, and procedures the file creates ("users (table)"); migrations/ stays skipped
+//! - Notebook (.ipynb) headers are the first markdown cell; code cells are analyzed as Python
+//!   via read_source (see core::notebook)
 //! - The header_area is the first 40 lines of a file
+//! - Scans skip files read_source rejects (over max_source_bytes, binary) instead of failing;
+//!   apply_doc_to_file and export sync read strictly so lossy text is never written back
 //! - Exports detection is approximate — pattern-based, not tree-sitter
 //! - walk_for_modules delegates to freshness::check_file_freshness for accurate status
+//! - Export sync keeps existing entry descriptions and only touches EXPORTS bullet lines
 //! - generate_module_doc_with_ai parses structured JSON from AI response into ModuleDoc
+//! - Template inference looks up the project's archetype table (Django, Rails, Spring, Go
+//!   service, CLI tool) first; React/Tauri path rules (hooks, components, stores, commands)
+//!   only apply to frontend and generic projects. Rails tables cover app/javascript only
+//!   because .rb files are not documented
+//! - Template export descriptions match whole identifier words ("Settings" is not "set" + "tings"):
+//!   EXPORT_PREFIX_RULES (verbs) for function-like names, EXPORT_SUFFIX_RULES (nouns) for
+//!   type-like names, after dropping the language's EXPORT_STOP_WORDS ("try_", "Async", "I")
+//! - Plugin-handled files: detect_exports/detect_imports come from the plugin (when it declares
+//!   the capability) and headers are line comments with the manifest's commentPrefix
 
 use crate::core::ai;
-use crate::models::module_doc::{ModuleDoc, ModuleStatus};
+use crate::core::ai_audit;
+use crate::core::ai_queue;
+use crate::core::notebook;
+use crate::core::patch::{self, PatchEngine};
+use crate::core::plugins;
+use crate::core::safe_read;
+use crate::core::scanner::{self, Archetype};
+use crate::models::ai_queue::AiJobKind;
+use crate::models::module_doc::{ExportSyncResult, ModuleDoc, ModuleStatus};
 use std::fs;
 use std::path::Path;
 
+/// Upper bound on summarization calls per file; chunks grow to stay under it.
+const AI_MAX_CHUNKS: usize = 16;
+
 /// Directories to skip when scanning for modules.
 const IGNORE_DIRS: &[&str] = &[
     "node_modules",
@@ -79,9 +139,9 @@ const IGNORE_DIRS: &[&str] = &[
     "vendor",
 ];
 
-/// Extensions that should have documentation headers.
-const DOC_EXTENSIONS: &[&str] = &[
-    ".ts", ".tsx", ".js", ".jsx", ".rs", ".py", ".go", ".java", ".kt", ".swift",
+/// Extensions that should have documentation headers (analyzer plugins add more).
+pub const DOC_EXTENSIONS: &[&str] = &[
+    ".ts", ".tsx", ".js", ".jsx", ".rs", ".py", ".go", ".java", ".kt", ".swift", ".ipynb", ".tf", ".sql",
 ];
 
 /// Files to skip even if they have a documentable extension.
@@ -97,6 +157,9 @@ const SKIP_FILES: &[&str] = &[
     "build.rs",
     "setup.ts",
     "setup.js",
+    "versions.tf",
+    "providers.tf",
+    "backend.tf",
 ];
 
 /// File name patterns to skip (checked with contains/ends_with).
@@ -112,9 +175,33 @@ const SKIP_PATTERNS: &[&str] = &[
     ".mock.tsx",
     ".fixture.ts",
     ".generated.ts",
+    ".generated.tsx",
     ".gen.ts",
+    ".gen.go",
+    ".pb.go",
+    "_pb2.py",
+    ".min.js",
+    ".bundle.js",
 ];
 
+/// Largest source file read for doc generation and export sync.
+const MAX_SOURCE_BYTES: u64 = 2_000_000;
+
+/// Notebooks carry cell outputs (plots, tables) that are never read, so they
+/// get a much higher raw size limit.
+const MAX_NOTEBOOK_BYTES: u64 = 50_000_000;
+
+/// Markers in the first lines of a file that identify generated code.
+const GENERATED_MARKERS: &[&str] = &["@generated", "DO NOT EDIT"];
+
+/// In-file opt-out from auto-doc generation, hooks, and freshness flagging
+/// (e.g. `// jumpstart:ignore` or `# jumpstart:ignore` in the first 20 lines).
+pub const IGNORE_MARKER: &str = "jumpstart:ignore";
+
+/// Project-relative file listing paths that should be documented even though
+/// they look generated (one path per line, `#` comments allowed).
+pub const GENERATED_OVERRIDES_FILE: &str = ".claude/generated-overrides";
+
 // ---------------------------------------------------------------------------
 // Public API
 // ---------------------------------------------------------------------------
@@ -131,13 +218,36 @@ pub fn scan_all_modules(project_path: &str) -> Result<Vec<ModuleStatus>, String>
     }
 
     let mut results = Vec::new();
-    walk_for_modules(path, project_path, &mut results, 0);
+    let overrides = load_generated_overrides(project_path);
+    walk_for_modules(path, project_path, &overrides, &mut results, 0);
 
     // Sort by path for consistent display
     results.sort_by(|a, b| a.path.cmp(&b.path));
     Ok(results)
 }
 
+/// Read a source file as the text the analyzer works on. Notebooks are
+/// flattened to their header cell plus code cells; other files are returned as-is.
+/// Invalid UTF-8 is decoded lossily; files over max_source_bytes and binary files are errors.
+pub fn read_source(file_path: &str) -> Result<String, String> {
+    let content = safe_read::read_text_limited(file_path, max_source_bytes(file_path))?;
+    if notebook::is_notebook(file_path) {
+        return notebook::flatten(&content)
+            .map(|nb| nb.text())
+            .map_err(|e| format!("{}: {}", file_path, e));
+    }
+    Ok(content)
+}
+
+/// Size limit (bytes on disk) for reading a file for doc generation or sync.
+pub fn max_source_bytes(file_path: &str) -> u64 {
+    if notebook::is_notebook(file_path) {
+        MAX_NOTEBOOK_BYTES
+    } else {
+        MAX_SOURCE_BYTES
+    }
+}
+
 /// Parse a file's content and extract its documentation header as a ModuleDoc.
 /// Returns None if no valid doc header is found.
 pub fn parse_doc_header(content: &str) -> Option<ModuleDoc> {
@@ -181,12 +291,11 @@ pub fn generate_module_doc_for_file(
 ) -> Result<ModuleDoc, String> {
     // Guard against extremely large files (>2MB) to prevent OOM
     let file_size = fs::metadata(file_path).map(|m| m.len()).unwrap_or(0);
-    if file_size > 2_000_000 {
+    if file_size > max_source_bytes(file_path) {
         return Err(format!("File too large to generate docs ({} bytes): {}", file_size, file_path));
     }
 
-    let content = fs::read_to_string(file_path)
-        .map_err(|e| format!("Failed to read {}: {}", file_path, e))?;
+    let content = read_source(file_path)?;
 
     let rel_path = make_relative_path(file_path, project_path);
     let ext = Path::new(file_path)
@@ -204,11 +313,12 @@ pub fn generate_module_doc_for_file(
         .trim_end_matches(&format!(".{}", ext))
         .to_string();
 
-    // Smart inference based on file location and content
-    let description = infer_description(&rel_path, &exports, &content);
-    let purpose = infer_purpose(&rel_path, &exports, &content);
-    let patterns = infer_patterns(&rel_path, ext, &exports, &content);
-    let claude_notes = infer_claude_notes(&rel_path, ext, &exports, &imports);
+    // Smart inference based on project archetype, file location, and content
+    let archetype = scanner::detect_archetype(Path::new(project_path));
+    let description = infer_description(archetype, &rel_path, &exports, &content);
+    let purpose = infer_purpose(archetype, &rel_path, &exports, &content);
+    let patterns = infer_patterns(archetype, &rel_path, ext, &exports, &content);
+    let claude_notes = infer_claude_notes(archetype, &rel_path, ext, &exports, &imports);
 
     Ok(ModuleDoc {
         module_path,
@@ -238,6 +348,23 @@ pub async fn generate_module_doc_with_ai(
     client: &reqwest::Client,
     api_key: &str,
 ) -> Result<ModuleDoc, String> {
+    let generate = module_doc_with_ai(file_path, project_path, content, exports, imports, client, api_key);
+    ai_audit::scoped(project_path, "doc generation", generate).await
+}
+
+async fn module_doc_with_ai(
+    file_path: &str,
+    project_path: &str,
+    content: &str,
+    exports: &[String],
+    imports: &[String],
+    client: &reqwest::Client,
+    api_key: &str,
+) -> Result<ModuleDoc, String> {
+    // Files matching the project's .claude/ai-exclude globs never leave the machine;
+    // callers fall back to generate_module_doc_for_file on Err.
+    ai::ensure_ai_allowed(project_path, file_path)?;
+
     let rel_path = make_relative_path(file_path, project_path);
     let ext = Path::new(file_path)
         .extension()
@@ -250,8 +377,19 @@ pub async fn generate_module_doc_with_ai(
         .trim_end_matches(&format!(".{}", ext))
         .to_string();
 
-    // Truncate content to ~12k chars to provide more context while staying within limits
-    let truncated_content: String = content.chars().take(12000).collect();
+    // Small files go to the model whole; large files are summarized chunk by chunk
+    // so declarations near the bottom of the file are not lost to truncation
+    let content_section = if ai::estimate_tokens(content) <= ai::token_limits().doc_file_tokens {
+        format!("File content:\n```\n{}\n```", content)
+    } else {
+        let summaries = summarize_chunks(content, ext, &module_path, client, api_key).await;
+        format!(
+            "The file is too large to include in full ({} lines). \
+            Summaries of each section, in order:\n\n{}",
+            content.lines().count(),
+            summaries
+        )
+    };
 
     let system = r#"You are a technical documentation generator. Analyze source code and produce JSON documentation.
 
@@ -302,15 +440,20 @@ OUTPUT: Return ONLY valid JSON, no markdown fences or explanation.
         File extension: .{}\n\
         Detected exports: {}\n\
         Detected imports: {}\n\n\
-        File content:\n```\n{}\n```",
+        {}",
         module_path,
         ext,
         exports.join(", "),
         imports.join(", "),
-        truncated_content,
+        content_section,
     );
 
-    let response = ai::call_claude(client, api_key, system, &prompt).await?;
+    let response = ai_queue::run(
+        AiJobKind::DocGeneration,
+        file_path,
+        ai::call_claude(client, api_key, system, &prompt),
+    )
+    .await?;
 
     // Strip markdown code fences if present (AI sometimes wraps in ```json ... ```)
     let cleaned_response = response
@@ -371,41 +514,423 @@ OUTPUT: Return ONLY valid JSON, no markdown fences or explanation.
     }
 }
 
-/// Apply a ModuleDoc as a documentation header to a file.
+/// A contiguous slice of a source file, split at top-level declarations.
+#[derive(Debug, Clone, PartialEq)]
+pub struct ContentChunk {
+    /// 1-based first line of the chunk
+    pub start_line: usize,
+    /// 1-based last line of the chunk (inclusive)
+    pub end_line: usize,
+    pub text: String,
+}
+
+/// Split file content into chunks of roughly `max_tokens` (core::ai::estimate_tokens),
+/// breaking only at top-level declarations where possible. A single declaration larger
+/// than `max_tokens` is split on line boundaries.
+pub fn split_into_chunks(content: &str, ext: &str, max_tokens: usize) -> Vec<ContentChunk> {
+    let lines: Vec<&str> = content.lines().collect();
+    if lines.is_empty() {
+        return Vec::new();
+    }
+
+    // Segment the file at each top-level declaration; leading comments and
+    // attributes stay attached to the declaration that follows them
+    let mut boundaries: Vec<usize> = vec![0];
+    let mut pending_start: Option<usize> = None;
+    for (i, line) in lines.iter().enumerate().skip(1) {
+        if line.trim().is_empty() || line.starts_with(char::is_whitespace) {
+            pending_start = None;
+            continue;
+        }
+        if is_declaration_prelude(line) {
+            pending_start.get_or_insert(i);
+        } else if is_top_level_declaration(line, ext) {
+            boundaries.push(pending_start.take().unwrap_or(i));
+        } else {
+            pending_start = None;
+        }
+    }
+    boundaries.dedup();
+
+    let mut segments: Vec<(usize, usize)> = Vec::new();
+    for (idx, &start) in boundaries.iter().enumerate() {
+        let end = boundaries.get(idx + 1).copied().unwrap_or(lines.len());
+        segments.push((start, end));
+    }
+
+    let mut chunks = Vec::new();
+    let mut current: Option<(usize, usize, usize)> = None; // (start, end, tokens)
+    let flush = |chunk: (usize, usize, usize), chunks: &mut Vec<ContentChunk>| {
+        chunks.push(ContentChunk {
+            start_line: chunk.0 + 1,
+            end_line: chunk.1,
+            text: lines[chunk.0..chunk.1].join("\n"),
+        });
+    };
+
+    for (start, end) in segments {
+        let seg_tokens: usize = lines[start..end].iter().map(|l| ai::estimate_tokens(l) + 1).sum();
+
+        if seg_tokens > max_tokens {
+            if let Some(c) = current.take() {
+                flush(c, &mut chunks);
+            }
+            // Oversized declaration: split on line boundaries
+            let mut parts = ai::split_by_tokens(&lines[start..end], max_tokens);
+            let last = parts.pop().unwrap_or(0..end - start);
+            for part in parts {
+                flush((start + part.start, start + part.end, 0), &mut chunks);
+            }
+            let last_tokens = lines[start + last.start..end].iter().map(|l| ai::estimate_tokens(l) + 1).sum();
+            current = Some((start + last.start, end, last_tokens));
+            continue;
+        }
+
+        current = match current {
+            Some((s, e, tokens)) if tokens + seg_tokens <= max_tokens => Some((s, end.max(e), tokens + seg_tokens)),
+            Some(c) => {
+                flush(c, &mut chunks);
+                Some((start, end, seg_tokens))
+            }
+            None => Some((start, end, seg_tokens)),
+        };
+    }
+    if let Some(c) = current {
+        flush(c, &mut chunks);
+    }
+
+    chunks
+}
+
+/// Lines that belong to the declaration below them (doc comments, attributes, decorators).
+fn is_declaration_prelude(line: &str) -> bool {
+    let t = line.trim_start();
+    t.starts_with("///")
+        || t.starts_with("/**")
+        || t.starts_with("#[")
+        || t.starts_with('@')
+        || (t.starts_with("//") && !t.starts_with("//!"))
+}
+
+/// Whether an unindented line starts a top-level declaration for this language.
+fn is_top_level_declaration(line: &str, ext: &str) -> bool {
+    let keywords: &[&str] = match ext {
+        "rs" => &[
+            "pub ", "pub(", "fn ", "async fn ", "struct ", "enum ", "impl", "trait ",
+            "mod ", "const ", "static ", "type ", "macro_rules!",
+        ],
+        "py" | "ipynb" => &["def ", "async def ", "class "],
+        "tf" => &[
+            "resource ", "data ", "module ", "variable ", "output ", "locals ", "provider ",
+            "terraform ",
+        ],
+        "sql" => &["CREATE ", "create ", "ALTER ", "alter ", "INSERT ", "insert "],
+        "go" => &["func ", "type ", "var ", "const "],
+        "java" | "kt" | "swift" => &[
+            "public ", "private ", "protected ", "internal ", "open ", "final ",
+            "abstract ", "class ", "interface ", "object ", "fun ", "func ", "struct ",
+            "enum ", "extension ", "protocol ", "data class ",
+        ],
+        _ => &[
+            "export ", "function ", "async function ", "class ", "interface ",
+            "type ", "const ", "let ", "enum ", "abstract class ",
+        ],
+    };
+    keywords.iter().any(|k| line.starts_with(k))
+}
+
+/// Summarize each chunk of a large file with the Claude API and join the
+/// results in file order. Chunks whose summary call fails fall back to a list
+/// of their declaration lines so the final prompt still covers the whole file.
+async fn summarize_chunks(
+    content: &str,
+    ext: &str,
+    module_path: &str,
+    client: &reqwest::Client,
+    api_key: &str,
+) -> String {
+    let total_tokens = ai::estimate_tokens(content);
+    let chunk_tokens = ai::token_limits().doc_chunk_tokens.max(total_tokens.div_ceil(AI_MAX_CHUNKS));
+    let chunks = split_into_chunks(content, ext, chunk_tokens);
+
+    let system = "You summarize one section of a larger source file so that documentation \
+        can be written for the whole file. List each declaration in the section with its \
+        kind (function, type, class, const, impl, etc.), whether it is exported/public, and \
+        one line on what it does. Mention notable side effects, I/O, or invariants. \
+        Plain text bullets only, at most 200 words.";
+
+    let mut summaries = Vec::with_capacity(chunks.len());
+    for (i, chunk) in chunks.iter().enumerate() {
+        let prompt = format!(
+            "File: {} (.{})\nSection {} of {} (lines {}-{}):\n```\n{}\n```",
+            module_path,
+            ext,
+            i + 1,
+            chunks.len(),
+            chunk.start_line,
+            chunk.end_line,
+            chunk.text,
+        );
+        let call = ai::call_claude(client, api_key, system, &prompt);
+        let summary = match ai_queue::run(AiJobKind::DocGeneration, module_path, call).await {
+            Ok(text) => text.trim().to_string(),
+            Err(_) => chunk
+                .text
+                .lines()
+                .filter(|l| is_top_level_declaration(l, ext))
+                .map(|l| format!("- {}", l.trim_end_matches('{').trim()))
+                .collect::<Vec<_>>()
+                .join("\n"),
+        };
+        summaries.push(format!(
+            "### Lines {}-{}\n{}",
+            chunk.start_line, chunk.end_line, summary
+        ));
+    }
+
+    summaries.join("\n\n")
+}
+
+/// Apply a ModuleDoc as a documentation header to a file inside `project_path`.
 /// If the file already has a doc header, it is replaced. Otherwise, the header is prepended.
-pub fn apply_doc_to_file(file_path: &str, doc: &ModuleDoc) -> Result<(), String> {
+/// The change is written as a unified diff through the configured patch engine.
+pub fn apply_doc_to_file(
+    engine: PatchEngine,
+    project_path: &Path,
+    file_path: &str,
+    doc: &ModuleDoc,
+) -> Result<(), String> {
+    let rel = patch::relative_path(project_path, file_path)
+        .ok_or_else(|| format!("{} is outside the project", file_path))?;
+
     // Guard against extremely large files (>2MB) to prevent OOM
     let file_size = fs::metadata(file_path).map(|m| m.len()).unwrap_or(0);
-    if file_size > 2_000_000 {
+    if file_size > max_source_bytes(file_path) {
         return Err(format!("File too large to apply docs ({} bytes): {}", file_size, file_path));
     }
 
+    // Strict UTF-8: a lossy decode must never be written back over the file
     let content = fs::read_to_string(file_path)
         .map_err(|e| format!("Failed to read {}: {}", file_path, e))?;
+    let new_content = render_doc_into(&content, file_path, doc)?;
+
+    patch::replace_file(engine, project_path, &rel, &content, &new_content)?;
+    Ok(())
+}
 
+/// File content with `doc` applied as its header (replacing an existing header),
+/// without writing anything. Used by apply_doc_to_file and doc proposal previews.
+pub fn render_doc_into(content: &str, file_path: &str, doc: &ModuleDoc) -> Result<String, String> {
     let ext = Path::new(file_path)
         .extension()
         .and_then(|e| e.to_str())
         .unwrap_or("");
 
     let header = format_doc_header(doc, ext);
-    let new_content = if has_doc_header(&content) {
-        replace_doc_header(&content, &header, ext)
+    if ext == notebook::NOTEBOOK_EXTENSION {
+        notebook::set_header_cell(content, &header)
+    } else if has_doc_header(content) {
+        Ok(replace_doc_header(content, &header, ext))
     } else {
-        format!("{}\n{}", header, content)
+        Ok(format!("{}\n{}", header, content))
+    }
+}
+
+/// Update only the EXPORTS section of a file's doc header so it matches the
+/// exports detected in code. Entries for exports that still exist keep their
+/// descriptions; removed exports are dropped and new ones are appended with an
+/// inferred description. All other header lines are left untouched.
+///
+/// Returns the new content and a summary. If the header has no EXPORTS section,
+/// one is inserted before PATTERNS or CLAUDE NOTES.
+pub fn sync_exports_section(
+    content: &str,
+    ext: &str,
+    file_path: &str,
+) -> Result<(String, ExportSyncResult), String> {
+    if !has_doc_header(content) {
+        return Err(format!("No doc header found in {}", file_path));
+    }
+
+    let lines: Vec<&str> = content.lines().collect();
+    let detected = detect_exports(content, ext);
+
+    // Locate the EXPORTS heading and its bullet lines within the header area
+    let mut exports_line: Option<usize> = None;
+    let mut item_lines: Vec<usize> = Vec::new();
+    for (i, line) in lines.iter().enumerate().take(60) {
+        let raw = line.trim();
+        let text = strip_comment_prefix(line);
+
+        if exports_line.is_none() {
+            if text.starts_with("EXPORTS:") {
+                exports_line = Some(i);
+            }
+            continue;
+        }
+
+        if text.starts_with("- ") {
+            item_lines.push(i);
+            continue;
+        }
+        // Stop at the next section heading or the end of the header comment
+        let next_section = text.ends_with(':') && text.chars().next().is_some_and(|c| c.is_uppercase());
+        let header_closed = raw == "*/"
+            || raw.contains("\"\"\"")
+            || (ext != "py" && !raw.is_empty() && !is_comment_line(raw));
+        if next_section || header_closed {
+            break;
+        }
+    }
+
+    let existing: Vec<String> = item_lines
+        .iter()
+        .map(|&i| strip_comment_prefix(lines[i]).trim_start_matches("- ").to_string())
+        .collect();
+
+    let base_of = |item: &str| -> String {
+        let name = super::freshness::extract_export_names(&[item.to_string()])
+            .into_iter()
+            .next()
+            .unwrap_or_default();
+        super::freshness::strip_paren_suffix(&name).to_lowercase()
     };
 
-    fs::write(file_path, new_content)
-        .map_err(|e| format!("Failed to write {}: {}", file_path, e))?;
+    let mut kept: Vec<String> = Vec::new();
+    let mut removed: Vec<String> = Vec::new();
+    for item in &existing {
+        let base = base_of(item);
+        if detected
+            .iter()
+            .any(|d| super::freshness::strip_paren_suffix(d).to_lowercase() == base)
+        {
+            kept.push(item.clone());
+        } else {
+            removed.push(item.clone());
+        }
+    }
+
+    let mut added: Vec<String> = Vec::new();
+    for export in &detected {
+        let base = super::freshness::strip_paren_suffix(export).to_lowercase();
+        if !existing.iter().any(|e| base_of(e) == base) {
+            added.push(infer_export_description(export, file_path));
+        }
+    }
 
-    Ok(())
+    let mut result = ExportSyncResult {
+        file_path: file_path.to_string(),
+        added: added.clone(),
+        removed,
+        updated: false,
+    };
+    if result.added.is_empty() && result.removed.is_empty() {
+        return Ok((content.to_string(), result));
+    }
+
+    let mut new_items = kept;
+    new_items.extend(added);
+
+    let mut out: Vec<String> = Vec::with_capacity(lines.len() + new_items.len() + 2);
+    match exports_line {
+        Some(heading) => {
+            let prefix = match item_lines.first() {
+                Some(&first) => line_prefix(lines[first], "- "),
+                None => line_prefix(lines[heading], "EXPORTS:"),
+            };
+            let (start, end) = match (item_lines.first(), item_lines.last()) {
+                (Some(&first), Some(&last)) => (first, last + 1),
+                _ => (heading + 1, heading + 1),
+            };
+            out.extend(lines[..start].iter().map(|l| l.to_string()));
+            out.extend(new_items.iter().map(|item| format!("{}- {}", prefix, item)));
+            out.extend(lines[end..].iter().map(|l| l.to_string()));
+        }
+        None => {
+            let anchor = lines.iter().take(60).position(|l| {
+                let text = strip_comment_prefix(l);
+                text.starts_with("PATTERNS:") || text.starts_with("CLAUDE NOTES:")
+            });
+            let anchor = anchor.ok_or_else(|| {
+                format!("No EXPORTS section or insertion point in {}; regenerate the header", file_path)
+            })?;
+            let heading_prefix = line_prefix(lines[anchor], strip_comment_prefix(lines[anchor]));
+            out.extend(lines[..anchor].iter().map(|l| l.to_string()));
+            out.push(format!("{}EXPORTS:", heading_prefix));
+            out.extend(new_items.iter().map(|item| format!("{}- {}", heading_prefix, item)));
+            out.push(heading_prefix.trim_end().to_string());
+            out.extend(lines[anchor..].iter().map(|l| l.to_string()));
+        }
+    }
+
+    let mut new_content = out.join("\n");
+    if content.ends_with('\n') {
+        new_content.push('\n');
+    }
+    result.updated = true;
+    Ok((new_content, result))
+}
+
+/// Sync the EXPORTS section of a file on disk with its detected exports.
+/// The file is only rewritten when exports were added or removed.
+pub fn sync_exports_in_file(file_path: &str) -> Result<ExportSyncResult, String> {
+    // Guard against extremely large files (>2MB) to prevent OOM
+    let file_size = fs::metadata(file_path).map(|m| m.len()).unwrap_or(0);
+    if file_size > max_source_bytes(file_path) {
+        return Err(format!("File too large to sync exports ({} bytes): {}", file_size, file_path));
+    }
+
+    let content = fs::read_to_string(file_path)
+        .map_err(|e| format!("Failed to read {}: {}", file_path, e))?;
+
+    let ext = Path::new(file_path)
+        .extension()
+        .and_then(|e| e.to_str())
+        .unwrap_or("");
+
+    if ext == notebook::NOTEBOOK_EXTENSION {
+        return sync_notebook_exports(file_path, &content);
+    }
+
+    let (new_content, result) = sync_exports_section(&content, ext, file_path)?;
+    if result.updated {
+        fs::write(file_path, new_content)
+            .map_err(|e| format!("Failed to write {}: {}", file_path, e))?;
+    }
+
+    Ok(result)
+}
+
+/// Sync a notebook's header cell: the flattened header + code is synced like a
+/// source file and the rewritten header text goes back into the first cell.
+fn sync_notebook_exports(file_path: &str, raw: &str) -> Result<ExportSyncResult, String> {
+    let flat = notebook::flatten(raw).map_err(|e| format!("{}: {}", file_path, e))?;
+    if flat.header.is_none() {
+        return Err(format!("No doc header found in {}", file_path));
+    }
+
+    let (new_text, result) = sync_exports_section(&flat.text(), notebook::NOTEBOOK_EXTENSION, file_path)?;
+    if result.updated {
+        let header = new_text.strip_suffix(flat.code.as_str()).unwrap_or(&new_text);
+        let new_content = notebook::set_header_cell(raw, header)?;
+        fs::write(file_path, new_content)
+            .map_err(|e| format!("Failed to write {}: {}", file_path, e))?;
+    }
+
+    Ok(result)
 }
 
 // ---------------------------------------------------------------------------
 // File walking
 // ---------------------------------------------------------------------------
 
-fn walk_for_modules(dir: &Path, project_path: &str, results: &mut Vec<ModuleStatus>, depth: usize) {
+fn walk_for_modules(
+    dir: &Path,
+    project_path: &str,
+    overrides: &[String],
+    results: &mut Vec<ModuleStatus>,
+    depth: usize,
+) {
     const MAX_DEPTH: usize = 10;
     const MAX_FILES: usize = 2000;
     if depth > MAX_DEPTH || results.len() >= MAX_FILES {
@@ -436,20 +961,43 @@ fn walk_for_modules(dir: &Path, project_path: &str, results: &mut Vec<ModuleStat
 
         if path.is_dir() {
             if !IGNORE_DIRS.contains(&name.as_str()) {
-                walk_for_modules(&path, project_path, results, depth + 1);
+                walk_for_modules(&path, project_path, overrides, results, depth + 1);
             }
-        } else if is_documentable(&name) {
+        } else if has_doc_extension(&name) {
             let abs_path = path.to_string_lossy().to_string();
+            let rel_path = make_relative_path(&abs_path, project_path);
+            if !is_documentable(&name) && !overrides.contains(&rel_path) {
+                continue;
+            }
+            // Oversized and binary files are skipped rather than failing the scan
+            let Ok(content) = read_source(&abs_path) else {
+                continue;
+            };
 
-            // Skip tiny files (<10 lines) — re-exports, barrel files, etc.
-            let line_count = fs::read_to_string(&abs_path)
-                .map(|c| c.lines().count())
-                .unwrap_or(0);
-            if line_count < 10 {
+            // Opted out in-file: listed so the UI can show it, never documented or flagged
+            if is_ignored_content(&content) {
+                results.push(ModuleStatus {
+                    path: rel_path,
+                    status: "ignored".to_string(),
+                    freshness_score: 100,
+                    changes: None,
+                    suggested_doc: None,
+                    quality: None,
+                    analyzer: plugins::for_file(&name).map(|p| p.name),
+                    change_authors: None,
+                });
                 continue;
             }
 
-            let rel_path = make_relative_path(&abs_path, project_path);
+            // Skip generated/vendored files unless overridden
+            if !should_track_file(&name, &rel_path, &content, overrides) {
+                continue;
+            }
+
+            // Skip tiny files (<10 lines) — re-exports, barrel files, etc.
+            if content.lines().count() < 10 {
+                continue;
+            }
 
             // Delegate to freshness engine for accurate status/score
             let freshness = super::freshness::check_file_freshness(&abs_path, project_path);
@@ -464,6 +1012,9 @@ fn walk_for_modules(dir: &Path, project_path: &str, results: &mut Vec<ModuleStat
                     Some(freshness.changes)
                 },
                 suggested_doc: None,
+                quality: None,
+                analyzer: plugins::for_file(&name).map(|p| p.name),
+                change_authors: None,
             });
         }
     }
@@ -484,7 +1035,86 @@ pub fn is_documentable(name: &str) -> bool {
     if SKIP_PATTERNS.iter().any(|pat| name.ends_with(pat)) {
         return false;
     }
-    DOC_EXTENSIONS.iter().any(|ext| name.ends_with(ext))
+    has_doc_extension(name)
+}
+
+/// Whether a filename has a documentable extension: built in, or claimed by an analyzer plugin.
+pub fn has_doc_extension(name: &str) -> bool {
+    DOC_EXTENSIONS.iter().any(|ext| name.ends_with(ext)) || plugins::for_file(name).is_some()
+}
+
+/// Check whether file content looks generated or vendored: a `@generated` or
+/// `DO NOT EDIT` marker near the top, or minified code (very long lines).
+pub fn is_generated_content(content: &str) -> bool {
+    if content
+        .lines()
+        .take(20)
+        .any(|line| GENERATED_MARKERS.iter().any(|m| line.contains(m)))
+    {
+        return true;
+    }
+
+    // Minified bundles: a few very long lines rather than normal source
+    let line_count = content.lines().count().max(1);
+    let longest = content.lines().map(|l| l.len()).max().unwrap_or(0);
+    longest > 1_000 && content.len() / line_count > 300
+}
+
+/// Check whether a file opts out of auto-docs with IGNORE_MARKER in its first 20 lines
+/// (the same window the enforcement hooks read).
+pub fn is_ignored_content(content: &str) -> bool {
+    content.lines().take(20).any(|line| line.contains(IGNORE_MARKER))
+}
+
+/// Decide whether a file belongs in doc coverage and freshness tracking.
+/// Combines the filename rules of `is_documentable` with generated-content
+/// detection; paths in the project's override list are tracked unless the
+/// file itself carries IGNORE_MARKER.
+pub fn should_track_file(name: &str, rel_path: &str, content: &str, overrides: &[String]) -> bool {
+    if is_ignored_content(content) {
+        return false;
+    }
+    if overrides.iter().any(|o| o == rel_path) {
+        return !name.starts_with('.') && has_doc_extension(name);
+    }
+    is_documentable(name) && !is_generated_content(content)
+}
+
+/// Load the per-project list of paths exempt from generated-file detection.
+pub fn load_generated_overrides(project_path: &str) -> Vec<String> {
+    let path = Path::new(project_path).join(GENERATED_OVERRIDES_FILE);
+    fs::read_to_string(path)
+        .map(|content| {
+            content
+                .lines()
+                .map(|l| l.trim().trim_start_matches("./").to_string())
+                .filter(|l| !l.is_empty() && !l.starts_with('#'))
+                .collect()
+        })
+        .unwrap_or_default()
+}
+
+/// Write the per-project override list, replacing any existing entries.
+pub fn save_generated_overrides(project_path: &str, paths: &[String]) -> Result<(), String> {
+    let path = Path::new(project_path).join(GENERATED_OVERRIDES_FILE);
+    if let Some(parent) = path.parent() {
+        fs::create_dir_all(parent)
+            .map_err(|e| format!("Failed to create {}: {}", parent.display(), e))?;
+    }
+
+    let mut content = String::from(
+        "# Files documented by Project Jumpstart even though they look generated.\n\
+         # One project-relative path per line.\n",
+    );
+    for p in paths {
+        let p = p.trim().trim_start_matches("./");
+        if !p.is_empty() {
+            content.push_str(p);
+            content.push('\n');
+        }
+    }
+
+    fs::write(&path, content).map_err(|e| format!("Failed to write {}: {}", path.display(), e))
 }
 
 // ---------------------------------------------------------------------------
@@ -506,6 +1136,7 @@ fn extract_field(header: &str, field: &str) -> String {
             .trim_start_matches("*")
             .trim_start_matches("//!")
             .trim_start_matches("//")
+            .trim_start_matches("--")
             .trim_start_matches('#')
             .trim();
 
@@ -533,6 +1164,7 @@ fn extract_list_section(content: &str, section_name: &str) -> Vec<String> {
             .trim_start_matches("*")
             .trim_start_matches("//!")
             .trim_start_matches("//")
+            .trim_start_matches("--")
             .trim_start_matches('#')
             .trim();
 
@@ -558,6 +1190,34 @@ fn extract_list_section(content: &str, section_name: &str) -> Vec<String> {
     items
 }
 
+/// Strip comment markers from a header line, leaving the section text.
+fn strip_comment_prefix(line: &str) -> &str {
+    line.trim()
+        .trim_start_matches("/**")
+        .trim_start_matches("*/")
+        .trim_start_matches("*")
+        .trim_start_matches("//!")
+        .trim_start_matches("//")
+        .trim_start_matches("--")
+        .trim_start_matches('#')
+        .trim()
+}
+
+/// Whether a trimmed line is part of a comment block.
+fn is_comment_line(trimmed: &str) -> bool {
+    trimmed.starts_with('*') || trimmed.starts_with("/**") || trimmed.starts_with("//") || trimmed.starts_with('#')
+        || trimmed.starts_with("--")
+}
+
+/// Everything on a line before `marker` (e.g. " * " for "* - item"), used to
+/// write new lines with the same comment style and indentation.
+fn line_prefix(line: &str, marker: &str) -> String {
+    match line.find(marker) {
+        Some(pos) => line[..pos].to_string(),
+        None => String::new(),
+    }
+}
+
 // ---------------------------------------------------------------------------
 // Export / import detection (pattern-based)
 // ---------------------------------------------------------------------------
@@ -663,7 +1323,7 @@ pub fn detect_exports(content: &str, ext: &str) -> Vec<String> {
                 }
             }
         }
-        "py" => {
+        "py" | "ipynb" => {
             for line in content.lines() {
                 let trimmed = line.trim();
 
@@ -858,7 +1518,27 @@ pub fn detect_exports(content: &str, ext: &str) -> Vec<String> {
                 }
             }
         }
-        _ => {}
+        "tf" => {
+            for line in content.lines() {
+                // Only top-level blocks; nested blocks are indented
+                if let Some(name) = hcl_block_label(line, "variable") {
+                    exports.push(format!("var.{}", name));
+                } else if let Some(name) = hcl_block_label(line, "output") {
+                    exports.push(format!("output.{}", name));
+                }
+            }
+        }
+        "sql" => {
+            for (kind, name) in super::sql_schema::created_objects(content) {
+                exports.push(format!("{} ({})", name, kind));
+            }
+        }
+        _ => {
+            // Extensions claimed by an analyzer plugin (empty unless it declares "exports")
+            if let Some((_, analysis)) = plugins::analysis_for(ext, content) {
+                exports = analysis.exports;
+            }
+        }
     }
 
     exports
@@ -901,7 +1581,7 @@ pub fn detect_imports(content: &str, ext: &str) -> Vec<String> {
                 }
             }
         }
-        "py" => {
+        "py" | "ipynb" => {
             for line in content.lines() {
                 let trimmed = line.trim();
                 if (trimmed.starts_with("from ") || trimmed.starts_with("import "))
@@ -970,12 +1650,52 @@ pub fn detect_imports(content: &str, ext: &str) -> Vec<String> {
                 }
             }
         }
-        _ => {}
+        "tf" => {
+            // module "name" { source = "..." }: the source is the dependency
+            let mut in_module = false;
+            for line in content.lines() {
+                if hcl_block_label(line, "module").is_some() {
+                    in_module = true;
+                } else if line.starts_with('}') {
+                    in_module = false;
+                } else if in_module {
+                    let trimmed = line.trim();
+                    if let Some(value) = trimmed.strip_prefix("source").map(str::trim_start) {
+                        if let Some(value) = value.strip_prefix('=') {
+                            let source = value.trim().trim_matches('"');
+                            if !source.is_empty() {
+                                imports.push(source.to_string());
+                            }
+                            in_module = false;
+                        }
+                    }
+                }
+            }
+        }
+        _ => {
+            // Extensions claimed by an analyzer plugin (empty unless it declares "imports")
+            if let Some((_, analysis)) = plugins::analysis_for(ext, content) {
+                imports = analysis.imports;
+            }
+        }
     }
 
     imports
 }
 
+/// Label of a top-level HCL block of `kind`, e.g. `variable "region" {` -> "region".
+fn hcl_block_label(line: &str, kind: &str) -> Option<String> {
+    let rest = line.strip_prefix(kind)?.trim_start();
+    let label = rest.strip_prefix('"')?;
+    let end = label.find('"')?;
+    let name = &label[..end];
+    if name.is_empty() {
+        None
+    } else {
+        Some(name.to_string())
+    }
+}
+
 // ---------------------------------------------------------------------------
 // Doc header formatting
 // ---------------------------------------------------------------------------
@@ -989,7 +1709,12 @@ fn format_doc_header(doc: &ModuleDoc, ext: &str) -> String {
         "java" => format_java_doc_header(doc),
         "kt" => format_kotlin_doc_header(doc),
         "swift" => format_swift_doc_header(doc),
-        _ => format_ts_doc_header(doc), // fallback
+        "ipynb" => format_notebook_doc_header(doc),
+        "tf" | "sql" => format_line_doc_header(doc, if ext == "tf" { "#" } else { "--" }),
+        _ => match plugins::for_extension(ext) {
+            Some(plugin) => format_line_doc_header(doc, &plugin.comment_prefix),
+            None => format_ts_doc_header(doc), // fallback
+        },
     }
 }
 
@@ -1307,6 +2032,59 @@ fn format_swift_doc_header(doc: &ModuleDoc) -> String {
     lines.join("\n")
 }
 
+/// Headers written as a run of line comments: `#` (HCL), `--` (SQL), or an analyzer
+/// plugin's commentPrefix.
+fn format_line_doc_header(doc: &ModuleDoc, marker: &str) -> String {
+    let mut lines = Vec::new();
+    lines.push(format!("{} @module {}", marker, doc.module_path));
+    lines.push(format!("{} @description {}", marker, doc.description));
+    lines.push(marker.to_string());
+
+    let sections = [
+        ("PURPOSE:", &doc.purpose),
+        ("DEPENDENCIES:", &doc.dependencies),
+        ("EXPORTS:", &doc.exports),
+        ("PATTERNS:", &doc.patterns),
+        ("CLAUDE NOTES:", &doc.claude_notes),
+    ];
+    for (title, items) in sections {
+        if !items.is_empty() {
+            lines.push(format!("{} {}", marker, title));
+            lines.extend(items.iter().map(|item| format!("{} - {}", marker, item)));
+            lines.push(marker.to_string());
+        }
+    }
+    if lines.last().is_some_and(|l| l == marker) {
+        lines.pop();
+    }
+
+    lines.join("\n")
+}
+
+/// Notebook headers are plain markdown lines in the first cell (no comment markers).
+fn format_notebook_doc_header(doc: &ModuleDoc) -> String {
+    let mut lines = Vec::new();
+    lines.push(format!("@module {}", doc.module_path));
+    lines.push(format!("@description {}", doc.description));
+
+    let sections = [
+        ("PURPOSE:", &doc.purpose),
+        ("DEPENDENCIES:", &doc.dependencies),
+        ("EXPORTS:", &doc.exports),
+        ("PATTERNS:", &doc.patterns),
+        ("CLAUDE NOTES:", &doc.claude_notes),
+    ];
+    for (title, items) in sections {
+        if !items.is_empty() {
+            lines.push(String::new());
+            lines.push(title.to_string());
+            lines.extend(items.iter().map(|item| format!("- {}", item)));
+        }
+    }
+
+    lines.join("\n")
+}
+
 /// Replace an existing doc header in a file with a new one.
 fn replace_doc_header(content: &str, new_header: &str, ext: &str) -> String {
     let lines: Vec<&str> = content.lines().collect();
@@ -1362,7 +2140,26 @@ fn replace_doc_header(content: &str, new_header: &str, ext: &str) -> String {
             }
             end
         }
-        _ => 0,
+        _ => {
+            // Find last consecutive # (HCL), -- (SQL), or plugin commentPrefix line
+            let marker = match ext {
+                "tf" => Some("#".to_string()),
+                "sql" => Some("--".to_string()),
+                _ => plugins::for_extension(ext).map(|p| p.comment_prefix),
+            };
+            let mut last_doc = 0;
+            if let Some(marker) = marker {
+                for (i, line) in lines.iter().enumerate() {
+                    let trimmed = line.trim();
+                    if trimmed.starts_with(&marker) {
+                        last_doc = i + 1;
+                    } else if !trimmed.is_empty() {
+                        break;
+                    }
+                }
+            }
+            last_doc
+        }
     };
 
     if header_end == 0 {
@@ -1440,31 +2237,330 @@ fn extract_word_after(line: &str, prefix: &str) -> Option<String> {
     }
 }
 
+// ---------------------------------------------------------------------------
+// Archetype inference tables
+// ---------------------------------------------------------------------------
+
+/// The role a file plays in an archetype's layout, for template doc inference.
+/// `{name}` in any text is replaced with the file's subject in words.
+struct RoleRule {
+    /// "/dir/" matches anywhere in the path (subject: file stem); "/file.py" matches
+    /// that file name (subject: enclosing directory); "Suffix.java" matches the end
+    /// of the file name (subject: file stem without the suffix)
+    markers: &'static [&'static str],
+    description: &'static str,
+    purposes: &'static [&'static str],
+    patterns: &'static [&'static str],
+    note: &'static str,
+}
+
+const DJANGO_RULES: &[RoleRule] = &[
+    RoleRule {
+        markers: &["/models.py", "/models/"],
+        description: "Django models for {name}",
+        purposes: &["Define database tables as Django model classes", "Declare fields, relations, and Meta options"],
+        patterns: &["Query through the model's objects manager", "Run makemigrations after changing fields"],
+        note: "Field changes need a migration (python manage.py makemigrations)",
+    },
+    RoleRule {
+        markers: &["/views.py", "/views/"],
+        description: "Django views for {name}",
+        purposes: &["Handle HTTP requests and return responses", "Render templates or serialize response data"],
+        patterns: &["Route views from the app's urls.py", "Keep business logic in models or services, not views"],
+        note: "Views are wired up in urls.py - update routes when renaming",
+    },
+    RoleRule {
+        markers: &["/urls.py"],
+        description: "URL routes for {name}",
+        purposes: &["Map URL patterns to views"],
+        patterns: &["Include app routes from the project urls.py with include()"],
+        note: "Route names are used by reverse() and {% url %} - rename carefully",
+    },
+    RoleRule {
+        markers: &["/serializers.py", "/serializers/"],
+        description: "Django REST Framework serializers for {name}",
+        purposes: &["Convert model instances to and from JSON", "Validate incoming request data"],
+        patterns: &["Call is_valid() before reading validated_data"],
+        note: "Serializer fields define the API contract - changes affect clients",
+    },
+    RoleRule {
+        markers: &["/admin.py"],
+        description: "Django admin configuration for {name}",
+        purposes: &["Register models with the Django admin site", "Customize admin lists, filters, and search"],
+        patterns: &["Use @admin.register(Model) on ModelAdmin classes"],
+        note: "The admin is for staff users only, not a public interface",
+    },
+    RoleRule {
+        markers: &["/forms.py", "/forms/"],
+        description: "Django forms for {name}",
+        purposes: &["Validate and clean user-submitted data"],
+        patterns: &["Check form.is_valid() before reading cleaned_data"],
+        note: "Validation runs in clean() and clean_<field>() methods",
+    },
+    RoleRule {
+        markers: &["/management/commands/"],
+        description: "Django management command for {name}",
+        purposes: &["Run {name} from the command line through manage.py"],
+        patterns: &["Invoke with python manage.py <command>", "Define arguments in add_arguments()"],
+        note: "Management commands often run from cron - keep them idempotent",
+    },
+    RoleRule {
+        markers: &["/tasks.py"],
+        description: "Celery tasks for {name}",
+        purposes: &["Run background jobs outside the request cycle"],
+        patterns: &["Queue tasks with .delay() or .apply_async()"],
+        note: "Pass IDs, not model instances - task arguments must be serializable",
+    },
+    RoleRule {
+        markers: &["/settings.py", "/settings/"],
+        description: "Django settings for {name}",
+        purposes: &["Configure installed apps, middleware, and databases"],
+        patterns: &["Read secrets from environment variables, not literals"],
+        note: "Never commit production secrets to settings",
+    },
+];
+
+const RAILS_RULES: &[RoleRule] = &[
+    RoleRule {
+        markers: &["/app/javascript/controllers/"],
+        description: "Stimulus controller for {name}",
+        purposes: &["Attach behavior to server-rendered HTML via data-controller", "Read declared targets and values"],
+        patterns: &["Reference targets with this.<name>Target", "Register new controllers in controllers/index.js"],
+        note: "Rails renders the HTML - keep state in data attributes, not in JavaScript",
+    },
+    RoleRule {
+        markers: &["/app/javascript/channels/"],
+        description: "Action Cable channel client for {name}",
+        purposes: &["Subscribe to the {name} channel", "Handle messages broadcast by the server"],
+        patterns: &["Create subscriptions with consumer.subscriptions.create()"],
+        note: "The matching server channel lives in app/channels",
+    },
+    RoleRule {
+        markers: &["/app/javascript/"],
+        description: "Frontend code for {name} on Rails-rendered pages",
+        purposes: &["Add client-side behavior to Rails views"],
+        patterns: &["Import from app/javascript/application.js"],
+        note: "Served through importmap or jsbundling - check config/importmap.rb when adding packages",
+    },
+];
+
+const SPRING_RULES: &[RoleRule] = &[
+    RoleRule {
+        markers: &["Controller.java", "Controller.kt", "/controller/", "/controllers/", "/web/"],
+        description: "Spring REST controller for {name}",
+        purposes: &["Map HTTP endpoints to service calls", "Validate request bodies and path variables"],
+        patterns: &["Annotate with @RestController and @RequestMapping", "Delegate business logic to a @Service"],
+        note: "Keep controllers thin - transactions belong in services",
+    },
+    RoleRule {
+        markers: &["ServiceImpl.java", "Service.java", "Service.kt", "/service/", "/services/"],
+        description: "Spring service for {name} business logic",
+        purposes: &["Implement {name} business rules", "Coordinate repositories within transactions"],
+        patterns: &["Inject dependencies through the constructor", "Mark write methods @Transactional"],
+        note: "Services own transaction boundaries",
+    },
+    RoleRule {
+        markers: &["Repository.java", "Repository.kt", "/repository/", "/repositories/"],
+        description: "Spring Data repository for {name}",
+        purposes: &["Query and persist {name} entities"],
+        patterns: &["Derive queries from method names or declare them with @Query"],
+        note: "Derived query methods are parsed from their names - renaming changes the SQL",
+    },
+    RoleRule {
+        markers: &["/entity/", "/entities/", "/model/", "/domain/"],
+        description: "JPA entity for {name}",
+        purposes: &["Map the {name} table to a class", "Declare relations between entities"],
+        patterns: &["Annotate with @Entity and @Id", "Change the schema through a migration (Flyway/Liquibase)"],
+        note: "Lazy relations accessed outside a transaction throw LazyInitializationException",
+    },
+    RoleRule {
+        markers: &["/dto/"],
+        description: "Data transfer objects for {name}",
+        purposes: &["Define request and response shapes for the API"],
+        patterns: &["Map entities to DTOs in the service layer"],
+        note: "DTOs are the API contract - never expose entities directly",
+    },
+    RoleRule {
+        markers: &["Configuration.java", "Config.java", "Config.kt", "/config/", "/configuration/"],
+        description: "Spring configuration for {name}",
+        purposes: &["Declare beans and settings for {name}"],
+        patterns: &["Annotate with @Configuration and expose @Bean methods"],
+        note: "Bean changes affect the whole application context",
+    },
+];
+
+const GO_SERVICE_RULES: &[RoleRule] = &[
+    RoleRule {
+        markers: &["/main.go"],
+        description: "Entry point for the {name} service",
+        purposes: &["Load configuration and wire dependencies", "Start the server and handle graceful shutdown"],
+        patterns: &["Keep main small - construct dependencies and call Run"],
+        note: "Shutdown must drain in-flight requests before exiting",
+    },
+    RoleRule {
+        markers: &["_handler.go", "/handlers/", "/handler/", "/api/", "/transport/"],
+        description: "HTTP handlers for {name}",
+        purposes: &["Decode and validate {name} requests", "Call services and encode JSON responses"],
+        patterns: &["Register handlers on the router in the server setup", "Pass r.Context() down to services"],
+        note: "Handlers should not touch the database directly",
+    },
+    RoleRule {
+        markers: &["_service.go", "/services/", "/service/", "/usecase/"],
+        description: "Service layer for {name}",
+        purposes: &["Implement {name} business logic", "Coordinate repositories and external clients"],
+        patterns: &["Accept context.Context as the first argument", "Depend on interfaces, not concrete stores"],
+        note: "Wrap errors with fmt.Errorf(\"...: %w\", err) so callers can use errors.Is",
+    },
+    RoleRule {
+        markers: &["_repository.go", "/repository/", "/store/", "/storage/", "/db/"],
+        description: "Data access for {name}",
+        purposes: &["Query and persist {name} records"],
+        patterns: &["Use parameterized queries; never build SQL with string formatting"],
+        note: "Close rows and check rows.Err() after iterating",
+    },
+    RoleRule {
+        markers: &["/middleware/"],
+        description: "HTTP middleware for {name}",
+        purposes: &["Wrap handlers with {name} behavior"],
+        patterns: &["Return func(http.Handler) http.Handler and chain on the router"],
+        note: "Middleware order matters - check the router setup when adding one",
+    },
+    RoleRule {
+        markers: &["/models/", "/model/", "/domain/"],
+        description: "Domain types for {name}",
+        purposes: &["Define {name} types shared across layers"],
+        patterns: &["Keep domain types free of transport and storage tags where possible"],
+        note: "JSON tags on these types are part of the API contract",
+    },
+    RoleRule {
+        markers: &["/config/"],
+        description: "Configuration loading for {name}",
+        purposes: &["Read settings from environment variables and files"],
+        patterns: &["Load once at startup and pass the config down explicitly"],
+        note: "Fail fast on missing required settings",
+    },
+];
+
+const CLI_TOOL_RULES: &[RoleRule] = &[
+    RoleRule {
+        markers: &["/__main__.py", "/cli.py"],
+        description: "Command-line entry point for {name}",
+        purposes: &["Parse arguments and dispatch to subcommands"],
+        patterns: &["Run with python -m <package> or the installed console script"],
+        note: "Exit codes are part of the CLI contract - return non-zero on failure",
+    },
+    RoleRule {
+        markers: &["/cmd/", "/commands/", "/subcommands/", "/cli/"],
+        description: "CLI command for {name}",
+        purposes: &["Parse {name} arguments and flags", "Run the {name} subcommand"],
+        patterns: &["Register the command with the root command"],
+        note: "Flags and output format are user-facing - treat them as a stable interface",
+    },
+    RoleRule {
+        markers: &["/output/", "/format/", "/render/", "/printer/"],
+        description: "Terminal output formatting for {name}",
+        purposes: &["Format results for humans and machine-readable output"],
+        patterns: &["Write results to stdout and diagnostics to stderr"],
+        note: "Scripts may parse this output - keep machine-readable formats stable",
+    },
+    RoleRule {
+        markers: &["/config/", "/config.rs", "/config.go", "/config.py"],
+        description: "Configuration loading for {name}",
+        purposes: &["Merge config files, environment variables, and flags"],
+        patterns: &["Flags override environment variables, which override config files"],
+        note: "Document new settings in the --help text",
+    },
+];
+
+/// Suffixes stripped from a file stem to get its subject ("UserController" -> "user").
+const ROLE_SUFFIXES: &[&str] = &[
+    "ServiceImpl", "Controller", "Service", "Repository", "Configuration", "Config",
+    "_controller", "_handler", "_service", "_repository", "_channel",
+];
+
+fn archetype_rules(archetype: Archetype) -> &'static [RoleRule] {
+    match archetype {
+        Archetype::Django => DJANGO_RULES,
+        Archetype::Rails => RAILS_RULES,
+        Archetype::Spring => SPRING_RULES,
+        Archetype::GoService => GO_SERVICE_RULES,
+        Archetype::CliTool => CLI_TOOL_RULES,
+        Archetype::Frontend | Archetype::Generic => &[],
+    }
+}
+
+/// Whether the React/Tauri path rules (hooks, components, stores, commands) apply.
+fn uses_frontend_layout(archetype: Archetype) -> bool {
+    matches!(archetype, Archetype::Frontend | Archetype::Generic)
+}
+
+/// The archetype rule for a file and the file's subject in words, if any rule matches.
+fn archetype_role(archetype: Archetype, rel_path: &str) -> Option<(&'static RoleRule, String)> {
+    let path = format!("/{}", rel_path);
+    let file = Path::new(rel_path);
+    let stem = file.file_stem().and_then(|s| s.to_str()).unwrap_or("module");
+
+    for rule in archetype_rules(archetype) {
+        for marker in rule.markers {
+            let name = if marker.ends_with('/') {
+                if !path.contains(marker) {
+                    continue;
+                }
+                stem
+            } else if marker.starts_with('/') {
+                if !path.ends_with(marker) {
+                    continue;
+                }
+                file.parent()
+                    .and_then(|p| p.file_name())
+                    .and_then(|n| n.to_str())
+                    .unwrap_or(stem)
+            } else {
+                if !path.ends_with(marker) {
+                    continue;
+                }
+                stem
+            };
+            let subject = ROLE_SUFFIXES
+                .iter()
+                .find_map(|suffix| name.strip_suffix(suffix).filter(|rest| !rest.is_empty()))
+                .unwrap_or(name);
+            return Some((rule, camel_to_words(&subject.replace(['_', '-'], " "))));
+        }
+    }
+    None
+}
+
 // ---------------------------------------------------------------------------
 // Smart inference functions for template-based doc generation
 // ---------------------------------------------------------------------------
 
-/// Infer a meaningful description from file path, exports, and content.
-fn infer_description(rel_path: &str, exports: &[String], content: &str) -> String {
+/// Infer a meaningful description from archetype, file path, exports, and content.
+fn infer_description(archetype: Archetype, rel_path: &str, exports: &[String], content: &str) -> String {
     let file_stem = Path::new(rel_path)
         .file_stem()
         .and_then(|s| s.to_str())
         .unwrap_or("module");
 
+    if let Some((rule, subject)) = archetype_role(archetype, rel_path) {
+        return rule.description.replace("{name}", &subject);
+    }
+    let frontend = uses_frontend_layout(archetype);
+
     // Check for specific file patterns
-    if rel_path.contains("/hooks/") || file_stem.starts_with("use") {
+    if frontend && (rel_path.contains("/hooks/") || file_stem.starts_with("use")) {
         let hook_name = file_stem.strip_prefix("use").unwrap_or(file_stem);
         return format!("Custom React hook for {} state and actions", camel_to_words(hook_name));
     }
 
-    if rel_path.contains("/components/") {
+    if frontend && rel_path.contains("/components/") {
         if exports.len() == 1 {
             return format!("{} UI component", exports[0]);
         }
         return format!("{} UI component with related utilities", pascal_to_words(file_stem));
     }
 
-    if rel_path.contains("/stores/") || file_stem.ends_with("Store") {
+    if frontend && (rel_path.contains("/stores/") || file_stem.ends_with("Store")) {
         let store_name = file_stem.strip_suffix("Store").unwrap_or(file_stem);
         return format!("Zustand store for {} state management", camel_to_words(store_name));
     }
@@ -1473,11 +2569,11 @@ fn infer_description(rel_path: &str, exports: &[String], content: &str) -> Strin
         return format!("Utility functions for {}", camel_to_words(file_stem));
     }
 
-    if rel_path.contains("/types/") {
+    if frontend && rel_path.contains("/types/") {
         return format!("TypeScript type definitions for {}", camel_to_words(file_stem));
     }
 
-    if rel_path.contains("/commands/") {
+    if frontend && rel_path.contains("/commands/") {
         return format!("Tauri IPC command handlers for {}", camel_to_words(file_stem));
     }
 
@@ -1515,16 +2611,21 @@ fn infer_description(rel_path: &str, exports: &[String], content: &str) -> Strin
     }
 }
 
-/// Infer purpose bullet points from file location and exports.
-fn infer_purpose(rel_path: &str, exports: &[String], content: &str) -> Vec<String> {
+/// Infer purpose bullet points from archetype, file location, and exports.
+fn infer_purpose(archetype: Archetype, rel_path: &str, exports: &[String], content: &str) -> Vec<String> {
     let mut purposes = Vec::new();
     let file_stem = Path::new(rel_path)
         .file_stem()
         .and_then(|s| s.to_str())
         .unwrap_or("module");
+    let frontend = uses_frontend_layout(archetype);
 
+    // Archetype-specific purposes
+    if let Some((rule, subject)) = archetype_role(archetype, rel_path) {
+        purposes.extend(rule.purposes.iter().map(|p| p.replace("{name}", &subject)));
+    }
     // Hook-specific purposes
-    if rel_path.contains("/hooks/") || file_stem.starts_with("use") {
+    else if frontend && (rel_path.contains("/hooks/") || file_stem.starts_with("use")) {
         purposes.push("Encapsulate related state logic".to_string());
         if content.contains("useState") {
             purposes.push("Manage local component state".to_string());
@@ -1540,7 +2641,7 @@ fn infer_purpose(rel_path: &str, exports: &[String], content: &str) -> Vec<Strin
         }
     }
     // Component-specific purposes
-    else if rel_path.contains("/components/") {
+    else if frontend && rel_path.contains("/components/") {
         purposes.push(format!("Render {} UI elements", pascal_to_words(file_stem)));
         if content.contains("onClick") || content.contains("onSubmit") || content.contains("onChange") {
             purposes.push("Handle user interactions".to_string());
@@ -1550,7 +2651,7 @@ fn infer_purpose(rel_path: &str, exports: &[String], content: &str) -> Vec<Strin
         }
     }
     // Store-specific purposes
-    else if rel_path.contains("/stores/") || file_stem.ends_with("Store") {
+    else if frontend && (rel_path.contains("/stores/") || file_stem.ends_with("Store")) {
         purposes.push("Provide global state container".to_string());
         purposes.push("Expose actions for state mutations".to_string());
         if content.contains("persist") {
@@ -1558,7 +2659,7 @@ fn infer_purpose(rel_path: &str, exports: &[String], content: &str) -> Vec<Strin
         }
     }
     // Command-specific purposes (Tauri)
-    else if rel_path.contains("/commands/") {
+    else if frontend && rel_path.contains("/commands/") {
         purposes.push("Handle IPC calls from frontend".to_string());
         if content.contains("State<") {
             purposes.push("Access shared application state".to_string());
@@ -1575,7 +2676,7 @@ fn infer_purpose(rel_path: &str, exports: &[String], content: &str) -> Vec<Strin
         }
     }
     // Type definition purposes
-    else if rel_path.contains("/types/") {
+    else if frontend && rel_path.contains("/types/") {
         purposes.push("Define TypeScript interfaces and types".to_string());
         purposes.push("Ensure type safety across the codebase".to_string());
     }
@@ -1601,21 +2702,26 @@ fn infer_purpose(rel_path: &str, exports: &[String], content: &str) -> Vec<Strin
     purposes
 }
 
-/// Infer usage patterns from file type and content.
-fn infer_patterns(rel_path: &str, ext: &str, exports: &[String], content: &str) -> Vec<String> {
+/// Infer usage patterns from archetype, file type, and content.
+fn infer_patterns(archetype: Archetype, rel_path: &str, ext: &str, exports: &[String], content: &str) -> Vec<String> {
     let mut patterns = Vec::new();
     let file_stem = Path::new(rel_path)
         .file_stem()
         .and_then(|s| s.to_str())
         .unwrap_or("module");
+    let frontend = uses_frontend_layout(archetype);
 
+    // Archetype patterns
+    if let Some((rule, subject)) = archetype_role(archetype, rel_path) {
+        patterns.extend(rule.patterns.iter().map(|p| p.replace("{name}", &subject)));
+    }
     // Hook patterns
-    if rel_path.contains("/hooks/") || file_stem.starts_with("use") {
+    else if frontend && (rel_path.contains("/hooks/") || file_stem.starts_with("use")) {
         patterns.push(format!("Call {}() at the top of functional components", file_stem));
         patterns.push("Destructure returned values for state and actions".to_string());
     }
     // Component patterns
-    else if rel_path.contains("/components/") {
+    else if frontend && rel_path.contains("/components/") {
         if let Some(main_export) = exports.first() {
             patterns.push(format!("Import and render <{} /> in parent components", main_export));
         }
@@ -1624,14 +2730,14 @@ fn infer_patterns(rel_path: &str, ext: &str, exports: &[String], content: &str)
         }
     }
     // Store patterns
-    else if rel_path.contains("/stores/") || file_stem.ends_with("Store") {
+    else if frontend && (rel_path.contains("/stores/") || file_stem.ends_with("Store")) {
         if let Some(main_export) = exports.first() {
             patterns.push(format!("Use {}(selector) to subscribe to specific state slices", main_export));
         }
         patterns.push("Call actions directly from the store hook".to_string());
     }
     // Tauri command patterns
-    else if rel_path.contains("/commands/") {
+    else if frontend && rel_path.contains("/commands/") {
         patterns.push("Commands are async and return Result<T, String>".to_string());
         patterns.push("Use State<AppState> for shared application state".to_string());
         patterns.push("Map errors to strings with .map_err(|e| e.to_string())".to_string());
@@ -1654,7 +2760,7 @@ fn infer_patterns(rel_path: &str, ext: &str, exports: &[String], content: &str)
                 patterns.push("Await async functions or spawn as tasks".to_string());
             }
         }
-        "py" => {
+        "py" | "ipynb" => {
             if content.contains("async def") {
                 patterns.push("Use await when calling async functions".to_string());
             }
@@ -1672,20 +2778,23 @@ fn infer_patterns(rel_path: &str, ext: &str, exports: &[String], content: &str)
     patterns
 }
 
-/// Infer Claude-specific notes from code characteristics.
-fn infer_claude_notes(rel_path: &str, ext: &str, exports: &[String], imports: &[String]) -> Vec<String> {
+/// Infer Claude-specific notes from archetype and code characteristics.
+fn infer_claude_notes(archetype: Archetype, rel_path: &str, ext: &str, exports: &[String], imports: &[String]) -> Vec<String> {
     let mut notes = Vec::new();
     let file_stem = Path::new(rel_path)
         .file_stem()
         .and_then(|s| s.to_str())
         .unwrap_or("module");
+    let frontend = uses_frontend_layout(archetype);
 
     // Note about file location
-    if rel_path.contains("/components/") {
+    if let Some((rule, subject)) = archetype_role(archetype, rel_path) {
+        notes.push(rule.note.replace("{name}", &subject));
+    } else if frontend && rel_path.contains("/components/") {
         notes.push("This is a React component - prefer composition over prop drilling".to_string());
-    } else if rel_path.contains("/hooks/") {
+    } else if frontend && rel_path.contains("/hooks/") {
         notes.push("Custom hooks should follow Rules of Hooks".to_string());
-    } else if rel_path.contains("/commands/") {
+    } else if frontend && rel_path.contains("/commands/") {
         notes.push("Tauri commands are registered in lib.rs invoke_handler".to_string());
     } else if rel_path.contains("/core/") {
         notes.push("Core modules should be framework-agnostic when possible".to_string());
@@ -1704,7 +2813,7 @@ fn infer_claude_notes(rel_path: &str, ext: &str, exports: &[String], imports: &[
     // Language-specific notes
     match ext {
         "ts" | "tsx" => {
-            if file_stem.starts_with("use") {
+            if frontend && file_stem.starts_with("use") {
                 notes.push("Hook names must start with 'use' for React rules".to_string());
             }
         }
@@ -1714,6 +2823,15 @@ fn infer_claude_notes(rel_path: &str, ext: &str, exports: &[String], imports: &[
         "py" => {
             notes.push("Follow PEP 8 style guidelines".to_string());
         }
+        "ipynb" => {
+            notes.push("Run cells top to bottom; move reusable logic into .py modules".to_string());
+        }
+        "tf" => {
+            notes.push("Run terraform fmt and terraform validate before committing".to_string());
+        }
+        "sql" => {
+            notes.push("Change the schema through a new migration, not by editing applied ones".to_string());
+        }
         _ => {}
     }
 
@@ -1760,91 +2878,231 @@ fn infer_dependency_description(import_path: &str) -> String {
     format!("{} - Imported dependency", import_path)
 }
 
-/// Infer a description for an export based on naming conventions.
-fn infer_export_description(export_name: &str, rel_path: &str) -> String {
-    let name_lower = export_name.to_lowercase();
+/// A naming rule for export descriptions. `{}` in `description` is replaced with the rest of
+/// the name in words; `bare` is used when the matched word is the whole name.
+struct ExportRule {
+    words: &'static [&'static str],
+    description: &'static str,
+    bare: &'static str,
+}
 
-    // Hook exports
-    if export_name.starts_with("use") {
-        let hook_purpose = export_name.strip_prefix("use").unwrap_or(export_name);
-        return format!("{} - React hook for {} state/actions", export_name, camel_to_words(hook_purpose));
-    }
+/// Verb rules matched against the first word of function-like names ("getUser", "parse_args").
+const EXPORT_PREFIX_RULES: &[ExportRule] = &[
+    ExportRule { words: &["get"], description: "Retrieves {}", bare: "Getter" },
+    ExportRule { words: &["set"], description: "Updates {}", bare: "Setter" },
+    ExportRule { words: &["create", "make"], description: "Creates new {}", bare: "Factory function" },
+    ExportRule { words: &["new"], description: "Creates new {}", bare: "Constructor" },
+    ExportRule { words: &["build"], description: "Builds {}", bare: "Builder function" },
+    ExportRule { words: &["delete", "remove"], description: "Removes {}", bare: "Removes item" },
+    ExportRule { words: &["update"], description: "Modifies {}", bare: "Modifies data" },
+    ExportRule { words: &["handle"], description: "Event handler for {}", bare: "Event handler" },
+    ExportRule { words: &["on"], description: "Callback for {} events", bare: "Event callback" },
+    ExportRule { words: &["is"], description: "Checks whether {}", bare: "Boolean check" },
+    ExportRule { words: &["has"], description: "Checks for {}", bare: "Boolean check" },
+    ExportRule { words: &["can"], description: "Checks whether it can {}", bare: "Boolean check" },
+    ExportRule { words: &["should"], description: "Decides whether to {}", bare: "Boolean check" },
+    ExportRule { words: &["calculate", "compute"], description: "Calculates {}", bare: "Calculates derived value" },
+    ExportRule { words: &["parse"], description: "Parses {}", bare: "Parses input data" },
+    ExportRule { words: &["format"], description: "Formats {}", bare: "Formats output data" },
+    ExportRule { words: &["validate"], description: "Validates {}", bare: "Validates input" },
+    ExportRule { words: &["fetch"], description: "Fetches {}", bare: "Async data fetching" },
+    ExportRule { words: &["load"], description: "Loads {}", bare: "Loads data" },
+    ExportRule { words: &["save", "store", "persist"], description: "Saves {}", bare: "Persists data" },
+    ExportRule { words: &["read"], description: "Reads {}", bare: "Reads data" },
+    ExportRule { words: &["write"], description: "Writes {}", bare: "Writes data" },
+    ExportRule { words: &["render"], description: "Renders {}", bare: "Render function" },
+    ExportRule { words: &["init", "initialize", "setup"], description: "Sets up {}", bare: "Initialization" },
+    ExportRule { words: &["reset", "clear"], description: "Resets {}", bare: "Resets state" },
+    ExportRule { words: &["find", "search", "lookup"], description: "Finds {}", bare: "Lookup function" },
+    ExportRule { words: &["list"], description: "Lists {}", bare: "Lists items" },
+    ExportRule { words: &["check"], description: "Checks {}", bare: "Runs a check" },
+    ExportRule { words: &["register"], description: "Registers {}", bare: "Registration function" },
+    ExportRule { words: &["generate"], description: "Generates {}", bare: "Generator function" },
+    ExportRule { words: &["detect"], description: "Detects {}", bare: "Detection function" },
+    ExportRule { words: &["extract"], description: "Extracts {}", bare: "Extracts data" },
+    ExportRule { words: &["apply"], description: "Applies {}", bare: "Applies changes" },
+    ExportRule { words: &["convert"], description: "Converts {}", bare: "Conversion function" },
+    ExportRule { words: &["to"], description: "Converts to {}", bare: "Conversion function" },
+    ExportRule { words: &["into"], description: "Converts into {}", bare: "Conversion function" },
+    ExportRule { words: &["from"], description: "Builds from {}", bare: "Conversion function" },
+    ExportRule { words: &["as"], description: "Views as {}", bare: "Conversion function" },
+    ExportRule { words: &["with"], description: "Builder option for {}", bare: "Builder option" },
+    ExportRule { words: &["send"], description: "Sends {}", bare: "Sends a message" },
+    ExportRule { words: &["emit", "publish", "dispatch"], description: "Emits {}", bare: "Emits an event" },
+    ExportRule { words: &["run", "execute"], description: "Runs {}", bare: "Runs a task" },
+    ExportRule { words: &["start"], description: "Starts {}", bare: "Starts a process" },
+    ExportRule { words: &["stop"], description: "Stops {}", bare: "Stops a process" },
+];
 
-    // Interface/Type exports
-    if export_name.ends_with("Props") {
-        let component = export_name.strip_suffix("Props").unwrap_or(export_name);
-        return format!("{} - Props interface for {} component", export_name, component);
-    }
-    if export_name.ends_with("State") {
-        let domain = export_name.strip_suffix("State").unwrap_or(export_name);
-        return format!("{} - State shape for {}", export_name, domain);
-    }
-    if export_name.ends_with("Config") || export_name.ends_with("Options") {
-        return format!("{} - Configuration type", export_name);
-    }
+/// Noun rules matched against the last word of type-like names ("UserProps", "AppConfig").
+const EXPORT_SUFFIX_RULES: &[ExportRule] = &[
+    ExportRule { words: &["props"], description: "Props interface for {} component", bare: "Props interface" },
+    ExportRule { words: &["state"], description: "State shape for {}", bare: "State shape" },
+    ExportRule {
+        words: &["config", "configuration", "options", "settings", "preferences"],
+        description: "Configuration for {}",
+        bare: "Configuration type",
+    },
+    ExportRule { words: &["error", "exception"], description: "Error type for {}", bare: "Error type" },
+    ExportRule { words: &["context"], description: "Context for {}", bare: "Context type" },
+    ExportRule { words: &["provider"], description: "Provider for {}", bare: "Provider" },
+    ExportRule { words: &["store"], description: "Store for {} state", bare: "State store" },
+    ExportRule { words: &["service"], description: "Service for {}", bare: "Service" },
+    ExportRule { words: &["client"], description: "Client for {}", bare: "Client" },
+    ExportRule { words: &["handler"], description: "Handler for {}", bare: "Handler" },
+    ExportRule { words: &["request"], description: "Request payload for {}", bare: "Request payload" },
+    ExportRule { words: &["response"], description: "Response payload for {}", bare: "Response payload" },
+    ExportRule { words: &["event"], description: "Event payload for {}", bare: "Event payload" },
+    ExportRule { words: &["result"], description: "Result of {}", bare: "Result type" },
+    ExportRule { words: &["builder"], description: "Builder for {}", bare: "Builder" },
+    ExportRule { words: &["manager"], description: "Manager for {}", bare: "Manager" },
+    ExportRule { words: &["schema"], description: "Schema for {}", bare: "Schema" },
+];
 
-    // Constants
-    if export_name.chars().all(|c| c.is_uppercase() || c == '_') {
-        return format!("{} - Constant value", export_name);
-    }
+/// Words skipped at the start or end of export names in a language, so rules see the
+/// meaningful words ("tryFrom" -> "from", "IUserProps" -> "user props", "fetch_async" -> "fetch").
+struct ExportStopWords {
+    extensions: &'static [&'static str],
+    leading: &'static [&'static str],
+    trailing: &'static [&'static str],
+}
 
-    // Common function patterns
-    if name_lower.starts_with("get") {
-        let target = export_name.strip_prefix("get").unwrap_or(export_name);
-        return format!("{} - Retrieves {}", export_name, camel_to_words(target));
-    }
-    if name_lower.starts_with("set") {
-        let target = export_name.strip_prefix("set").unwrap_or(export_name);
-        return format!("{} - Updates {}", export_name, camel_to_words(target));
-    }
-    if name_lower.starts_with("create") {
-        let target = export_name.strip_prefix("create").unwrap_or(export_name);
-        return format!("{} - Creates new {}", export_name, camel_to_words(target));
-    }
-    if name_lower.starts_with("delete") || name_lower.starts_with("remove") {
-        return format!("{} - Removes item", export_name);
+const EXPORT_STOP_WORDS: &[ExportStopWords] = &[
+    ExportStopWords { extensions: &["ts", "tsx", "js", "jsx"], leading: &["i", "do"], trailing: &["async"] },
+    ExportStopWords { extensions: &["rs"], leading: &["try"], trailing: &["impl", "fn"] },
+    ExportStopWords { extensions: &["py", "ipynb"], leading: &["async"], trailing: &["async"] },
+    ExportStopWords { extensions: &["go"], leading: &["must"], trailing: &["func"] },
+    ExportStopWords { extensions: &["java", "kt"], leading: &[], trailing: &["impl"] },
+];
+
+/// Split an identifier into words: camelCase, PascalCase, snake_case, kebab-case, and
+/// acronyms ("HTTPClient" -> ["HTTP", "client"]). Acronyms keep their case; other words
+/// are lowercased.
+fn identifier_words(name: &str) -> Vec<String> {
+    let chars: Vec<char> = name.chars().collect();
+    let mut words: Vec<String> = Vec::new();
+    let mut current = String::new();
+    for (i, &c) in chars.iter().enumerate() {
+        if !c.is_alphanumeric() {
+            if !current.is_empty() {
+                words.push(std::mem::take(&mut current));
+            }
+            continue;
+        }
+        if c.is_uppercase() && !current.is_empty() {
+            let prev = chars[i - 1];
+            let next_lower = chars.get(i + 1).is_some_and(|n| n.is_lowercase());
+            if prev.is_lowercase() || prev.is_ascii_digit() || (prev.is_uppercase() && next_lower) {
+                words.push(std::mem::take(&mut current));
+            }
+        }
+        current.push(c);
     }
-    if name_lower.starts_with("update") {
-        let target = export_name.strip_prefix("update").unwrap_or(export_name);
-        return format!("{} - Modifies {}", export_name, camel_to_words(target));
+    if !current.is_empty() {
+        words.push(current);
     }
-    if name_lower.starts_with("handle") {
-        let event = export_name.strip_prefix("handle").unwrap_or(export_name);
-        return format!("{} - Event handler for {}", export_name, camel_to_words(event));
+    words
+        .into_iter()
+        .map(|w| {
+            let acronym = w.chars().count() > 1 && w.chars().all(|c| c.is_uppercase() || c.is_ascii_digit());
+            if acronym {
+                w
+            } else {
+                w.to_lowercase()
+            }
+        })
+        .collect()
+}
+
+/// The rule whose words include `word` (case-insensitive), if any.
+fn find_export_rule(rules: &'static [ExportRule], word: &str) -> Option<&'static ExportRule> {
+    let word = word.to_lowercase();
+    rules.iter().find(|rule| rule.words.contains(&word.as_str()))
+}
+
+fn apply_export_rule(rule: &ExportRule, rest: &[String]) -> String {
+    if rest.is_empty() {
+        rule.bare.to_string()
+    } else {
+        rule.description.replace("{}", &rest.join(" "))
     }
-    if name_lower.starts_with("on") && export_name.len() > 2 {
-        let event = &export_name[2..];
-        return format!("{} - Callback for {} events", export_name, camel_to_words(event));
+}
+
+/// Infer a description for an export from its name, using the naming conventions of the
+/// file's language. Verb rules only apply to function-like names (or any name in Go, where
+/// exported functions are capitalized); noun rules only apply to type-like names.
+fn infer_export_description(export_name: &str, rel_path: &str) -> String {
+    let ext = Path::new(rel_path).extension().and_then(|e| e.to_str()).unwrap_or("");
+    let javascript = matches!(ext, "ts" | "tsx" | "js" | "jsx");
+
+    // Declarative exports: "users (table)" in SQL, "var.region" / "output.vpc_id" in Terraform
+    if let Some((name, kind)) = export_name.strip_suffix(')').and_then(|n| n.split_once(" (")) {
+        return format!("{} - Database {}", name, kind);
     }
-    if name_lower.starts_with("is") || name_lower.starts_with("has") || name_lower.starts_with("can") {
-        return format!("{} - Boolean check", export_name);
+    if export_name.starts_with("var.") {
+        return format!("{} - Input variable", export_name);
     }
-    if name_lower.starts_with("calculate") || name_lower.starts_with("compute") {
-        return format!("{} - Calculates derived value", export_name);
+    if export_name.starts_with("output.") {
+        return format!("{} - Module output", export_name);
     }
-    if name_lower.starts_with("parse") {
-        return format!("{} - Parses input data", export_name);
+
+    // Python private/dunder names describe the same thing without the underscores
+    let name = export_name.trim_matches('_');
+
+    // Constants
+    if name.chars().any(|c| c.is_alphabetic())
+        && name.chars().all(|c| c.is_uppercase() || c.is_ascii_digit() || c == '_')
+        && name.len() > 1
+    {
+        return format!("{} - Constant value", export_name);
     }
-    if name_lower.starts_with("format") {
-        return format!("{} - Formats output data", export_name);
+
+    // React hooks: "use" followed by a capitalized word ("useAuth", not "user")
+    if javascript {
+        if let Some(hook) = name.strip_prefix("use").filter(|r| r.starts_with(|c: char| c.is_uppercase())) {
+            return format!("{} - React hook for {} state/actions", export_name, identifier_words(hook).join(" "));
+        }
     }
-    if name_lower.starts_with("validate") {
-        return format!("{} - Validates input", export_name);
+
+    let mut words = identifier_words(name);
+    if let Some(stop) = EXPORT_STOP_WORDS.iter().find(|s| s.extensions.contains(&ext)) {
+        if words.len() > 1 && stop.leading.contains(&words[0].to_lowercase().as_str()) {
+            words.remove(0);
+        }
+        if words.len() > 1 && stop.trailing.contains(&words[words.len() - 1].to_lowercase().as_str()) {
+            words.pop();
+        }
     }
-    if name_lower.starts_with("fetch") || name_lower.starts_with("load") {
-        return format!("{} - Async data fetching", export_name);
+    let Some(first) = words.first() else {
+        return format!("{} - Exported function/value", export_name);
+    };
+
+    let type_like = name.starts_with(|c: char| c.is_uppercase());
+    if !type_like || ext == "go" {
+        if let Some(rule) = find_export_rule(EXPORT_PREFIX_RULES, first) {
+            return format!("{} - {}", export_name, apply_export_rule(rule, &words[1..]));
+        }
     }
-    if name_lower.starts_with("save") || name_lower.starts_with("store") {
-        return format!("{} - Persists data", export_name);
+    if !type_like {
+        return format!("{} - Exported function/value", export_name);
     }
 
-    // Check if it's likely a component (PascalCase in components folder)
-    if rel_path.contains("/components/") && export_name.chars().next().is_some_and(|c| c.is_uppercase()) {
+    let (last, rest) = words.split_last().unwrap_or((first, &[]));
+    if let Some(rule) = find_export_rule(EXPORT_SUFFIX_RULES, last) {
+        return format!("{} - {}", export_name, apply_export_rule(rule, rest));
+    }
+    if matches!(ext, "tsx" | "jsx") || (javascript && rel_path.contains("/components/")) {
         return format!("{} - React component", export_name);
     }
 
-    // Default
-    format!("{} - Exported function/value", export_name)
+    let joined = words.join(" ");
+    let mut chars = joined.chars();
+    let subject: String = chars.next().map(|c| c.to_uppercase().chain(chars).collect()).unwrap_or_default();
+    match ext {
+        "go" => format!("{} - Exported function/value", export_name),
+        "py" | "ipynb" | "java" | "kt" | "swift" => format!("{} - {} class", export_name, subject),
+        _ => format!("{} - {} type", export_name, subject),
+    }
 }
 
 /// Convert camelCase or PascalCase to lowercase words.
@@ -2126,4 +3384,338 @@ import React from "react";
         assert!(!is_documentable("build.rs"));
         assert!(!is_documentable("setup.ts"));
     }
+
+    #[test]
+    fn test_sync_exports_section_preserves_other_sections() {
+        let content = r#"/**
+ * @module lib/api
+ * @description API helpers
+ *
+ * EXPORTS:
+ * - fetchUser - Loads the current user
+ * - oldHelper - No longer exists
+ *
+ * PATTERNS:
+ * - All calls are async
+ */
+
+export async function fetchUser() {}
+export function parseResponse() {}
+"#;
+
+        let (updated, result) = sync_exports_section(content, "ts", "/p/lib/api.ts").unwrap();
+        assert!(result.updated);
+        assert_eq!(result.removed, vec!["oldHelper - No longer exists"]);
+        assert_eq!(result.added, vec!["parseResponse - Parses input data"]);
+        assert!(updated.contains(" * - fetchUser - Loads the current user\n * - parseResponse - Parses input data\n *\n * PATTERNS:"));
+        assert!(!updated.contains("oldHelper"));
+        assert!(updated.contains(" * - All calls are async"));
+        assert!(updated.ends_with("export function parseResponse() {}\n"));
+
+        // Already in sync: no change
+        let (again, second) = sync_exports_section(&updated, "ts", "/p/lib/api.ts").unwrap();
+        assert!(!second.updated);
+        assert_eq!(again, updated);
+    }
+
+    #[test]
+    fn test_sync_exports_section_inserts_missing_section() {
+        let content = "//! @module core/math\n//! @description Math helpers\n//!\n//! PATTERNS:\n//! - Pure functions\n\npub fn add_one(x: u32) -> u32 { x + 1 }\n";
+
+        let (updated, result) = sync_exports_section(content, "rs", "/p/core/math.rs").unwrap();
+        assert!(result.updated);
+        assert_eq!(result.added.len(), 1);
+        assert!(updated.contains("//! EXPORTS:\n//! - add_one - "));
+        assert!(updated.contains("//!\n//! PATTERNS:\n//! - Pure functions"));
+    }
+
+    #[test]
+    fn test_split_into_chunks_breaks_at_declarations() {
+        let mut content = String::from("use std::fs;\n\n");
+        for i in 0..6 {
+            content.push_str(&format!("/// Doc for f{}\npub fn f{}() {{\n    let x = {};\n}}\n\n", i, i, "1".repeat(40)));
+        }
+
+        let chunks = split_into_chunks(&content, "rs", 40);
+        assert!(chunks.len() > 1);
+        // Every chunk after the first starts at a declaration's doc comment
+        for chunk in &chunks[1..] {
+            assert!(chunk.text.starts_with("/// Doc for f"), "bad boundary: {:?}", chunk.text);
+        }
+        // Chunks cover the whole file in order, without gaps
+        assert_eq!(chunks[0].start_line, 1);
+        for pair in chunks.windows(2) {
+            assert_eq!(pair[1].start_line, pair[0].end_line + 1);
+        }
+        assert_eq!(chunks.last().unwrap().end_line, content.lines().count());
+    }
+
+    #[test]
+    fn test_split_into_chunks_splits_oversized_declaration() {
+        let body: String = (0..50).map(|i| format!("    let v{} = {};\n", i, i)).collect();
+        let content = format!("export function big() {{\n{}}}\n", body);
+
+        let chunks = split_into_chunks(&content, "ts", 60);
+        assert!(chunks.len() > 1);
+        assert!(chunks.iter().all(|c| ai::estimate_tokens(&c.text) <= 60));
+    }
+
+    #[test]
+    fn test_generated_file_detection() {
+        assert!(!is_documentable("service.pb.go"));
+        assert!(!is_documentable("vendor.min.js"));
+        assert!(!is_documentable("schema.generated.tsx"));
+
+        assert!(is_generated_content("// Code generated by protoc-gen-go. DO NOT EDIT.\npackage api\n"));
+        assert!(is_generated_content("/* @generated */\nexport const x = 1;\n"));
+        let minified = format!("!function(){{{}}}();", "var a=1;".repeat(300));
+        assert!(is_generated_content(&minified));
+        assert!(!is_generated_content("export function add(a, b) {\n  return a + b;\n}\n"));
+
+        let generated = "// DO NOT EDIT\nexport const routes = [];\n";
+        assert!(!should_track_file("routes.ts", "src/routes.ts", generated, &[]));
+        let overrides = vec!["src/routes.ts".to_string()];
+        assert!(should_track_file("routes.ts", "src/routes.ts", generated, &overrides));
+    }
+
+    #[test]
+    fn test_ignore_marker() {
+        let ignored = "// jumpstart:ignore\nexport const secretSauce = 1;\n";
+        assert!(is_ignored_content(ignored));
+        assert!(is_ignored_content("#!/usr/bin/env python\n# jumpstart:ignore - hand-tuned\n"));
+        assert!(!is_ignored_content(&format!("{}// jumpstart:ignore\n", "let a = 1;\n".repeat(25))));
+        // The in-file marker wins over the generated-overrides list
+        let overrides = vec!["src/sauce.ts".to_string()];
+        assert!(!should_track_file("sauce.ts", "src/sauce.ts", ignored, &overrides));
+
+        let dir = tempfile::tempdir().unwrap();
+        let src = dir.path().join("src");
+        std::fs::create_dir_all(&src).unwrap();
+        let body = "export function f() {\n  return 1;\n}\n".repeat(4);
+        std::fs::write(src.join("sauce.ts"), format!("// jumpstart:ignore\n{}", body)).unwrap();
+        std::fs::write(src.join("plain.ts"), &body).unwrap();
+        let modules = scan_all_modules(&dir.path().to_string_lossy()).unwrap();
+        let status = |p: &str| modules.iter().find(|m| m.path == p).map(|m| m.status.clone());
+        assert_eq!(status("src/sauce.ts").as_deref(), Some("ignored"));
+        assert_eq!(status("src/plain.ts").as_deref(), Some("missing"));
+    }
+
+    #[test]
+    fn test_scan_survives_non_utf8_and_huge_files() {
+        let dir = tempfile::tempdir().unwrap();
+        let src = dir.path().join("src");
+        std::fs::create_dir_all(&src).unwrap();
+        let mut latin1 = b"// r\xe9sum\xe9 helpers\n".to_vec();
+        latin1.extend("export function f() {\n  return 1;\n}\n".repeat(4).as_bytes());
+        std::fs::write(src.join("resume.ts"), latin1).unwrap();
+        std::fs::write(src.join("huge.ts"), "export const x = 1;\n".repeat(150_000)).unwrap();
+
+        let modules = scan_all_modules(&dir.path().to_string_lossy()).unwrap();
+        let paths: Vec<&str> = modules.iter().map(|m| m.path.as_str()).collect();
+        assert_eq!(paths, vec!["src/resume.ts"]);
+        assert!(read_source(&src.join("resume.ts").to_string_lossy()).unwrap().contains("r\u{fffd}sum"));
+    }
+
+    #[test]
+    fn test_generated_overrides_roundtrip() {
+        let dir = tempfile::tempdir().unwrap();
+        let project = dir.path().to_string_lossy().to_string();
+        assert!(load_generated_overrides(&project).is_empty());
+
+        save_generated_overrides(&project, &["./src/api.pb.go".to_string(), " ".to_string()]).unwrap();
+        assert_eq!(load_generated_overrides(&project), vec!["src/api.pb.go"]);
+    }
+
+    #[test]
+    fn test_notebook_doc_roundtrip() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("churn.ipynb");
+        let file = path.to_string_lossy().to_string();
+        std::fs::write(
+            &path,
+            r#"{"cells": [{"cell_type": "code", "metadata": {}, "execution_count": null, "outputs": [],
+                "source": ["import pandas as pd\n", "from lib import features\n", "\n", "def load_data(path):\n", "    return pd.read_csv(path)"]}],
+               "metadata": {}, "nbformat": 4, "nbformat_minor": 5}"#,
+        )
+        .unwrap();
+
+        assert!(is_documentable("churn.ipynb"));
+        let source = read_source(&file).unwrap();
+        assert_eq!(detect_exports(&source, "ipynb"), vec!["load_data"]);
+        assert_eq!(detect_imports(&source, "ipynb"), vec!["pandas", "lib"]);
+        assert!(parse_doc_header(&source).is_none());
+
+        let doc = ModuleDoc {
+            module_path: "churn".to_string(),
+            description: "Churn exploration".to_string(),
+            purpose: vec!["Explore churn".to_string()],
+            dependencies: vec![],
+            exports: vec!["old_fn - Removed helper".to_string()],
+            patterns: vec![],
+            claude_notes: vec![],
+        };
+        apply_doc_to_file(PatchEngine::Builtin, dir.path(), &file, &doc).unwrap();
+        let parsed = parse_doc_header(&read_source(&file).unwrap()).unwrap();
+        assert_eq!(parsed.description, "Churn exploration");
+
+        let result = sync_exports_in_file(&file).unwrap();
+        assert!(result.updated);
+        assert_eq!(result.removed, vec!["old_fn - Removed helper"]);
+        let synced = read_source(&file).unwrap();
+        let exports = parse_doc_header(&synced).unwrap().exports;
+        assert_eq!(exports.len(), 1);
+        assert!(exports[0].starts_with("load_data"));
+        assert!(synced.ends_with("    return pd.read_csv(path)"));
+    }
+
+    #[test]
+    fn test_terraform_module_docs() {
+        let content = r#"variable "region" {
+  type    = string
+  default = "us-east-1"
+}
+
+module "vpc" {
+  source  = "./modules/vpc"
+  region  = var.region
+}
+
+module "s3" {
+  source = "terraform-aws-modules/s3-bucket/aws"
+}
+
+resource "aws_instance" "web" {
+  ami = "ami-123"
+}
+
+output "vpc_id" {
+  value = module.vpc.id
+}
+"#;
+        assert!(is_documentable("main.tf"));
+        assert!(!is_documentable("versions.tf"));
+        assert_eq!(detect_exports(content, "tf"), vec!["var.region", "output.vpc_id"]);
+        assert_eq!(
+            detect_imports(content, "tf"),
+            vec!["./modules/vpc", "terraform-aws-modules/s3-bucket/aws"]
+        );
+
+        let doc = ModuleDoc {
+            module_path: "infra/main".to_string(),
+            description: "Network and compute".to_string(),
+            purpose: vec!["Provision the VPC".to_string()],
+            dependencies: vec!["./modules/vpc - VPC module".to_string()],
+            exports: vec!["var.region - AWS region".to_string(), "output.vpc_id - VPC ID".to_string()],
+            patterns: vec![],
+            claude_notes: vec![],
+        };
+        let header = format_doc_header(&doc, "tf");
+        assert!(header.lines().all(|l| l.starts_with('#')));
+        let parsed = parse_doc_header(&format!("{}\n{}", header, content)).unwrap();
+        assert_eq!(parsed.module_path, "infra/main");
+        assert_eq!(parsed.exports.len(), 2);
+        assert_eq!(parsed.dependencies, vec!["./modules/vpc - VPC module"]);
+
+        let replaced = replace_doc_header(&format!("{}\n\n{}", header, content), "# @module new", "tf");
+        assert!(replaced.starts_with("# @module new\n\nvariable \"region\""));
+    }
+    #[test]
+    fn test_sql_module_docs() {
+        let content = "CREATE TABLE users (id INTEGER PRIMARY KEY, email TEXT);\n\
+                       CREATE OR REPLACE VIEW active_users AS SELECT * FROM users;\n\
+                       CREATE INDEX idx_email ON users (email);\n";
+        assert!(is_documentable("schema.sql"));
+        assert_eq!(detect_exports(content, "sql"), vec!["users (table)", "active_users (view)"]);
+
+        let doc = ModuleDoc {
+            module_path: "db/schema".to_string(),
+            description: "Core tables".to_string(),
+            purpose: vec![],
+            dependencies: vec![],
+            exports: vec!["users - Registered users".to_string(), "active_users - Users with a name".to_string()],
+            patterns: vec![],
+            claude_notes: vec!["Keep in sync with migrations".to_string()],
+        };
+        let header = format_doc_header(&doc, "sql");
+        assert!(header.lines().all(|l| l.starts_with("--")));
+        let with_header = format!("{}\n\n{}", header, content);
+        let parsed = parse_doc_header(&with_header).unwrap();
+        assert_eq!(parsed.module_path, "db/schema");
+        assert_eq!(parsed.exports, vec!["users - Registered users", "active_users - Users with a name"]);
+        assert_eq!(parsed.claude_notes, vec!["Keep in sync with migrations"]);
+        assert_eq!(super::super::freshness::doc_header_line_count(&with_header), header.lines().count());
+
+        let replaced = replace_doc_header(&with_header, "-- @module new", "sql");
+        assert!(replaced.starts_with("-- @module new\n\nCREATE TABLE users"));
+    }
+
+    #[test]
+    fn test_infer_export_description() {
+        let cases = [
+            // Verb prefixes match whole words, so nouns that start like verbs are not verbs
+            ("Settings", "src/lib/config.ts", "Settings - Configuration type"),
+            ("settingsPanel", "src/lib/ui.ts", "settingsPanel - Exported function/value"),
+            ("getUserName", "src/api.ts", "getUserName - Retrieves user name"),
+            ("setTheme", "src/theme.ts", "setTheme - Updates theme"),
+            ("onlineUsers", "src/presence.ts", "onlineUsers - Exported function/value"),
+            ("onClick", "src/button.ts", "onClick - Callback for click events"),
+            ("isValidPath", "src/paths.ts", "isValidPath - Checks whether valid path"),
+            ("fetchHTTPResponse", "src/net.ts", "fetchHTTPResponse - Fetches HTTP response"),
+            ("fetchDataAsync", "src/net.ts", "fetchDataAsync - Fetches data"),
+            // Hooks need a capitalized word after "use"
+            ("useAuth", "src/hooks/useAuth.ts", "useAuth - React hook for auth state/actions"),
+            ("userCount", "src/stats.ts", "userCount - Exported function/value"),
+            // Type-like names use noun suffixes
+            ("IUserProps", "src/types.ts", "IUserProps - Props interface for user component"),
+            ("AppConfig", "src/config.ts", "AppConfig - Configuration for app"),
+            ("UpdateBanner", "src/components/UpdateBanner.tsx", "UpdateBanner - React component"),
+            ("MAX_SIZE", "src/limits.rs", "MAX_SIZE - Constant value"),
+            // snake_case languages and their stop words
+            ("calculate_health", "src/health.rs", "calculate_health - Calculates health"),
+            ("try_from_str", "src/parse.rs", "try_from_str - Builds from str"),
+            ("new", "src/model.rs", "new - Constructor"),
+            ("ScanError", "src/scan.rs", "ScanError - Error type for scan"),
+            ("HealthMonitor", "app/monitor.py", "HealthMonitor - Health monitor class"),
+            ("__init__", "app/monitor.py", "__init__ - Initialization"),
+            ("async_fetch_data", "app/net.py", "async_fetch_data - Fetches data"),
+            // Go exports are capitalized functions or types
+            ("GetUser", "internal/users.go", "GetUser - Retrieves user"),
+            ("UserService", "internal/users.go", "UserService - Service for user"),
+            // Declarative exports
+            ("users (table)", "db/schema.sql", "users - Database table"),
+            ("var.region", "infra/main.tf", "var.region - Input variable"),
+        ];
+        for (name, path, expected) in cases {
+            assert_eq!(infer_export_description(name, path), expected, "{} in {}", name, path);
+        }
+        assert_eq!(identifier_words("parseHTTPHeaders_v2"), vec!["parse", "HTTP", "headers", "v2"]);
+    }
+
+    #[test]
+    fn test_archetype_inference_tables() {
+        let none: Vec<String> = vec![];
+        assert_eq!(infer_description(Archetype::Django, "blog/views.py", &none, ""), "Django views for blog");
+        assert_eq!(
+            infer_description(Archetype::Spring, "src/main/java/com/acme/web/UserController.java", &none, ""),
+            "Spring REST controller for user"
+        );
+        assert_eq!(
+            infer_description(Archetype::GoService, "internal/handlers/order_handler.go", &none, ""),
+            "HTTP handlers for order"
+        );
+        assert_eq!(infer_description(Archetype::GoService, "cmd/api/main.go", &none, ""), "Entry point for the api service");
+        assert_eq!(
+            infer_description(Archetype::Rails, "app/javascript/controllers/hello_controller.js", &none, ""),
+            "Stimulus controller for hello"
+        );
+        assert_eq!(infer_purpose(Archetype::CliTool, "cmd/sync.go", &none, "")[0], "Parse sync arguments and flags");
+
+        // React/Tauri rules only apply to frontend (and unknown) layouts
+        let exports = vec!["Card".to_string()];
+        assert_eq!(infer_description(Archetype::Frontend, "src/components/Card.tsx", &exports, ""), "Card UI component");
+        assert_ne!(infer_description(Archetype::Django, "app/components/card.py", &exports, ""), "Card UI component");
+        assert!(!infer_claude_notes(Archetype::CliTool, "src/components/card.rs", "rs", &none, &none)
+            .iter()
+            .any(|n| n.contains("React")));
+    }
 }
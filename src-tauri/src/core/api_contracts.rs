@@ -0,0 +1,937 @@
+//! @module core/api_contracts
+//! @description Find API schemas (OpenAPI/GraphQL), fingerprint them, and detect contract drift
+//!
+//! PURPOSE:
+//! - Discover OpenAPI/Swagger (YAML or JSON) and GraphQL SDL files in a project
+//! - Fingerprint each schema operation and type so changes can be compared over time
+//! - Find route handlers and GraphQL resolvers in source files
+//! - Flag routes missing from the schema, schema operations without handlers, and handlers
+//!   that changed while neither the schema nor their doc header did
+//!
+//! DEPENDENCIES:
+//! - rusqlite - api_contract_baselines table
+//! - sha2 - Stable fingerprints
+//! - serde_json - OpenAPI JSON parsing
+//! - core::freshness - Scan scope and doc header length
+//! - models::api_contracts - ApiSchemaSummary, ApiContractDrift
+//! - core::safe_read - Lossy, size-capped reads of schema and handler files
+//!
+//! EXPORTS:
+//! - FORMAT_OPENAPI / FORMAT_GRAPHQL - Schema format names
+//! - DRIFT_UNDOCUMENTED_ROUTE / DRIFT_MISSING_HANDLER / DRIFT_HANDLER_CHANGED - Drift kinds
+//! - ApiOperation - Operation key and fingerprint
+//! - SchemaFile - A parsed schema file
+//! - HandlerFile - A source file with routes or resolvers, with code/doc fingerprints
+//! - ContractSnapshot - All schemas and handlers of a project
+//! - BaselineEntry - A stored fingerprint
+//! - openapi_operations - Operations of an OpenAPI document (YAML or JSON)
+//! - graphql_operations - Root fields and named types of a GraphQL schema
+//! - routes_in_source - (method, path) routes declared in a source file
+//! - scan_contracts - Build a ContractSnapshot for a project
+//! - detect_drift - Compare a snapshot with its routes and the stored baseline
+//! - load_baseline / save_baseline - Read and replace the stored fingerprints
+//!
+//! PATTERNS:
+//! - Paths are normalized before matching: parameters (:id, {id}, <int:id>) become {}
+//! - A code route matches a schema operation when the methods agree (or the route
+//!   accepts any method) and the schema path ends with the route path, so routers
+//!   mounted under a prefix still match
+//! - "handler_changed" compares against the baseline: a handler whose code fingerprint
+//!   moved while its doc header and every schema of the same format stayed put
+//!
+//! CLAUDE NOTES:
+//! - The first check records the baseline, so handler_changed only appears after
+//!   later edits; accepting drift re-records the baseline
+//! - Route detection is pattern-based (Express/Fastify, FastAPI/Flask, Spring, Go, axum,
+//!   actix); HTTP client calls are excluded by requiring a router-like receiver
+//! - Test files are never handler files (supertest calls look like routes)
+
+use std::collections::HashMap;
+use std::fs;
+use std::path::Path;
+
+use chrono::Utc;
+use rusqlite::Connection;
+use sha2::{Digest, Sha256};
+
+use crate::core::{freshness, safe_read};
+use crate::models::api_contracts::{ApiContractDrift, ApiSchemaSummary};
+
+pub const FORMAT_OPENAPI: &str = "openapi";
+pub const FORMAT_GRAPHQL: &str = "graphql";
+
+pub const DRIFT_UNDOCUMENTED_ROUTE: &str = "undocumented_route";
+pub const DRIFT_MISSING_HANDLER: &str = "missing_handler";
+pub const DRIFT_HANDLER_CHANGED: &str = "handler_changed";
+
+/// api_contract_baselines.kind values.
+const KIND_SCHEMA: &str = "schema";
+const KIND_HANDLER: &str = "handler";
+
+/// Route method for handlers that accept any HTTP method.
+const ANY_METHOD: &str = "ANY";
+
+const HTTP_METHODS: &[&str] = &["get", "post", "put", "patch", "delete", "head", "options"];
+
+/// Source extensions searched for routes and resolvers.
+const HANDLER_EXTENSIONS: &[&str] = &["ts", "tsx", "js", "jsx", "mjs", "py", "go", "java", "kt", "rs"];
+
+/// Files larger than this are not scanned.
+const MAX_FILE_BYTES: u64 = 1024 * 1024;
+
+/// Receivers whose .get()/.post() calls register routes (matched on the last identifier).
+const ROUTER_RECEIVER_SUFFIXES: &[&str] = &[
+    "app", "router", "server", "api", "routes", "fastify", "bp", "blueprint", "group",
+];
+const ROUTER_RECEIVERS: &[&str] = &["r", "g", "e", "mux", "v1", "v2"];
+
+/// A schema operation (or type) and the fingerprint of its definition.
+#[derive(Debug, Clone, PartialEq)]
+pub struct ApiOperation {
+    pub key: String,
+    pub fingerprint: String,
+}
+
+/// A parsed schema file.
+#[derive(Debug, Clone)]
+pub struct SchemaFile {
+    pub path: String,
+    pub format: &'static str,
+    pub operations: Vec<ApiOperation>,
+    /// Fingerprint over all operation fingerprints (formatting-insensitive)
+    pub fingerprint: String,
+}
+
+/// A source file that declares HTTP routes or GraphQL resolvers.
+#[derive(Debug, Clone)]
+pub struct HandlerFile {
+    pub path: String,
+    /// Schema format this file implements (FORMAT_OPENAPI for routes, FORMAT_GRAPHQL for resolvers)
+    pub format: &'static str,
+    /// (METHOD, normalized path) for route files
+    pub routes: Vec<(String, String)>,
+    pub code_fingerprint: String,
+    pub doc_fingerprint: String,
+}
+
+/// Schemas and handlers of a project at one point in time.
+#[derive(Debug, Clone, Default)]
+pub struct ContractSnapshot {
+    pub schemas: Vec<SchemaFile>,
+    pub handlers: Vec<HandlerFile>,
+}
+
+impl ContractSnapshot {
+    pub fn schema_summaries(&self) -> Vec<ApiSchemaSummary> {
+        self.schemas
+            .iter()
+            .map(|s| ApiSchemaSummary {
+                path: s.path.clone(),
+                format: s.format.to_string(),
+                operations: s.operations.iter().map(|o| o.key.clone()).collect(),
+            })
+            .collect()
+    }
+}
+
+/// A fingerprint stored in api_contract_baselines.
+#[derive(Debug, Clone, PartialEq)]
+pub struct BaselineEntry {
+    pub path: String,
+    pub kind: String,
+    pub fingerprint: String,
+    pub doc_fingerprint: String,
+    pub recorded_at: String,
+}
+
+fn fingerprint(text: &str) -> String {
+    format!("{:x}", Sha256::digest(text.as_bytes()))
+}
+
+/// Collapse whitespace so re-indentation does not change a fingerprint.
+fn normalize_ws(text: &str) -> String {
+    text.split_whitespace().collect::<Vec<_>>().join(" ")
+}
+
+// ---------------------------------------------------------------------------
+// Schema discovery and parsing
+// ---------------------------------------------------------------------------
+
+/// Schema format of a file, by name and (for YAML/JSON) content.
+fn schema_format(name: &str, content: &str) -> Option<&'static str> {
+    let lower = name.to_ascii_lowercase();
+    if lower.ends_with(".graphql") || lower.ends_with(".gql") {
+        return Some(FORMAT_GRAPHQL);
+    }
+    let is_data = [".yaml", ".yml", ".json"].iter().any(|e| lower.ends_with(e));
+    if !is_data {
+        return None;
+    }
+    let head: String = content.chars().take(2000).collect();
+    let declares_openapi = head.lines().any(|l| {
+        let t = l.trim_start_matches(|c: char| c == '{' || c.is_whitespace()).trim_start_matches('"');
+        t.starts_with("openapi") || t.starts_with("swagger")
+    });
+    if declares_openapi && (head.contains("paths") || lower.contains("openapi") || lower.contains("swagger")) {
+        Some(FORMAT_OPENAPI)
+    } else {
+        None
+    }
+}
+
+/// Replace path parameters with "{}" and drop trailing slashes.
+fn normalize_path(path: &str) -> String {
+    let segments: Vec<&str> = path
+        .split('/')
+        .filter(|s| !s.is_empty())
+        .map(|s| if s.starts_with([':', '{', '<', '*']) { "{}" } else { s })
+        .collect();
+    format!("/{}", segments.join("/"))
+}
+
+/// Operations ("GET /users/{}") of an OpenAPI document in YAML or JSON.
+pub fn openapi_operations(content: &str) -> Vec<ApiOperation> {
+    if content.trim_start().starts_with('{') {
+        return openapi_json_operations(content);
+    }
+
+    let lines: Vec<&str> = content.lines().collect();
+    let indent = |l: &str| l.len() - l.trim_start().len();
+    let is_content = |l: &str| !l.trim().is_empty() && !l.trim_start().starts_with('#');
+
+    let Some(start) = lines.iter().position(|l| l.trim_end() == "paths:") else {
+        return Vec::new();
+    };
+    let end = lines[start + 1..]
+        .iter()
+        .position(|l| is_content(l) && indent(l) == 0)
+        .map(|i| start + 1 + i)
+        .unwrap_or(lines.len());
+
+    let mut operations = Vec::new();
+    let mut path_indent = None;
+    let mut current_path: Option<String> = None;
+    let mut i = start + 1;
+    while i < end {
+        let line = lines[i];
+        if !is_content(line) {
+            i += 1;
+            continue;
+        }
+        let level = indent(line);
+        let key = line.trim().trim_end_matches(':').trim_matches(|c| c == '"' || c == '\'');
+        let path_level = *path_indent.get_or_insert(level);
+
+        if level == path_level {
+            current_path = key.starts_with('/').then(|| normalize_path(key));
+            i += 1;
+            continue;
+        }
+        if let Some(path) = &current_path {
+            if line.trim_end().ends_with(':') && HTTP_METHODS.contains(&key) {
+                let body_end = lines[i + 1..end]
+                    .iter()
+                    .position(|l| is_content(l) && indent(l) <= level)
+                    .map(|j| i + 1 + j)
+                    .unwrap_or(end);
+                let body: Vec<String> = lines[i + 1..body_end]
+                    .iter()
+                    .copied()
+                    .filter(|l| is_content(l))
+                    .map(normalize_ws)
+                    .collect();
+                operations.push(ApiOperation {
+                    key: format!("{} {}", key.to_ascii_uppercase(), path),
+                    fingerprint: fingerprint(&body.join("\n")),
+                });
+                i = body_end;
+                continue;
+            }
+        }
+        i += 1;
+    }
+
+    operations
+}
+
+fn openapi_json_operations(content: &str) -> Vec<ApiOperation> {
+    let Ok(doc) = serde_json::from_str::<serde_json::Value>(content) else {
+        return Vec::new();
+    };
+    let Some(paths) = doc.get("paths").and_then(|p| p.as_object()) else {
+        return Vec::new();
+    };
+    let mut operations = Vec::new();
+    for (path, item) in paths {
+        let Some(item) = item.as_object() else { continue };
+        for (method, op) in item {
+            if HTTP_METHODS.contains(&method.as_str()) {
+                operations.push(ApiOperation {
+                    key: format!("{} {}", method.to_ascii_uppercase(), normalize_path(path)),
+                    fingerprint: fingerprint(&op.to_string()),
+                });
+            }
+        }
+    }
+    operations
+}
+
+/// Root fields ("Query.user", "Mutation.createUser") and named types ("type User")
+/// of a GraphQL schema. Comments and descriptions do not affect fingerprints.
+pub fn graphql_operations(content: &str) -> Vec<ApiOperation> {
+    let mut cleaned = Vec::new();
+    let mut in_description = false;
+    for line in content.lines() {
+        let t = line.trim();
+        if in_description {
+            in_description = !t.contains("\"\"\"");
+            continue;
+        }
+        if let Some(rest) = t.strip_prefix("\"\"\"") {
+            // A one-line """...""" description closes itself
+            in_description = !rest.contains("\"\"\"");
+            continue;
+        }
+        if t.starts_with('#') || (t.starts_with('"') && t.ends_with('"')) || t.is_empty() {
+            continue;
+        }
+        cleaned.push(t);
+    }
+
+    let mut operations = Vec::new();
+    let mut i = 0;
+    while i < cleaned.len() {
+        let Some((keyword, name, rest)) = graphql_definition(cleaned[i]) else {
+            i += 1;
+            continue;
+        };
+        i += 1;
+
+        let mut body = Vec::new();
+        match rest.split_once('{') {
+            Some((_, inline)) => match inline.split_once('}') {
+                Some((inside, _)) => body.push(inside),
+                None => {
+                    body.push(inline);
+                    while i < cleaned.len() {
+                        let line = cleaned[i];
+                        i += 1;
+                        if let Some((inside, _)) = line.split_once('}') {
+                            body.push(inside);
+                            break;
+                        }
+                        body.push(line);
+                    }
+                }
+            },
+            None => {
+                // scalar X, union X = A | B (members may continue on "|" lines)
+                body.push(rest);
+                while i < cleaned.len() && cleaned[i].starts_with('|') {
+                    body.push(cleaned[i]);
+                    i += 1;
+                }
+            }
+        }
+        let body = body.join("\n");
+
+        if keyword == "type" && matches!(name, "Query" | "Mutation" | "Subscription") {
+            for field in graphql_fields(&body) {
+                let field_name = field.split(['(', ':']).next().unwrap_or("").trim();
+                if !field_name.is_empty() {
+                    operations.push(ApiOperation {
+                        key: format!("{}.{}", name, field_name),
+                        fingerprint: fingerprint(&normalize_ws(&field)),
+                    });
+                }
+            }
+        } else {
+            operations.push(ApiOperation {
+                key: format!("{} {}", keyword, name),
+                fingerprint: fingerprint(&normalize_ws(&format!("{} {}", rest.split('{').next().unwrap_or(""), body))),
+            });
+        }
+    }
+
+    operations
+}
+
+/// "<keyword> <Name> <rest>" of an SDL definition line ("extend" is ignored).
+fn graphql_definition(line: &str) -> Option<(&'static str, &str, &str)> {
+    const KEYWORDS: &[&str] = &["type", "input", "enum", "interface", "union", "scalar"];
+    let line = line.strip_prefix("extend ").unwrap_or(line);
+    KEYWORDS.iter().find_map(|keyword| {
+        let after = line.strip_prefix(keyword)?.strip_prefix(' ')?.trim_start();
+        let len = after.find(|c: char| !(c.is_alphanumeric() || c == '_')).unwrap_or(after.len());
+        (len > 0).then(|| (*keyword, &after[..len], &after[len..]))
+    })
+}
+
+/// Field definitions of a type body, joining fields whose arguments span lines.
+fn graphql_fields(body: &str) -> Vec<String> {
+    let mut fields = Vec::new();
+    let mut current = String::new();
+    let mut depth = 0i32;
+    for line in body.lines().map(str::trim).filter(|l| !l.is_empty()) {
+        if !current.is_empty() {
+            current.push(' ');
+        }
+        current.push_str(line);
+        depth += line.matches('(').count() as i32 - line.matches(')').count() as i32;
+        if depth <= 0 {
+            fields.push(std::mem::take(&mut current));
+            depth = 0;
+        }
+    }
+    if !current.is_empty() {
+        fields.push(current);
+    }
+    fields
+}
+
+// ---------------------------------------------------------------------------
+// Route and resolver detection
+// ---------------------------------------------------------------------------
+
+/// First string literal in `s` (single, double, or backtick quotes).
+fn first_string_literal(s: &str) -> Option<&str> {
+    let start = s.find(['"', '\'', '`'])?;
+    let quote = s[start..].chars().next()?;
+    let inner = &s[start + 1..];
+    let end = inner.find(quote)?;
+    Some(&inner[..end])
+}
+
+/// Whether the identifier chain before a method call looks like a router.
+fn is_router_receiver(before: &str) -> bool {
+    let ident: String = before
+        .chars()
+        .rev()
+        .take_while(|c| c.is_alphanumeric() || *c == '_')
+        .collect::<Vec<_>>()
+        .into_iter()
+        .rev()
+        .collect();
+    let lower = ident.to_ascii_lowercase();
+    !lower.is_empty()
+        && (ROUTER_RECEIVERS.contains(&lower.as_str()) || ROUTER_RECEIVER_SUFFIXES.iter().any(|s| lower.ends_with(s)))
+}
+
+/// (METHOD, normalized path) routes declared on one line.
+fn routes_in_line(line: &str) -> Vec<(String, String)> {
+    let t = line.trim();
+    let mut routes = Vec::new();
+    let mut push = |method: &str, path: &str| {
+        if path.starts_with('/') {
+            routes.push((method.to_ascii_uppercase(), normalize_path(path)));
+        }
+    };
+
+    // Spring: @GetMapping("/x"), @PostMapping(value = "/x")
+    for method in HTTP_METHODS {
+        let annotation = format!("@{}{}Mapping(", method[..1].to_ascii_uppercase(), &method[1..]);
+        if let Some(pos) = t.find(&annotation) {
+            if let Some(path) = first_string_literal(&t[pos + annotation.len()..]) {
+                push(method, path);
+            }
+        }
+    }
+
+    // actix: #[get("/x")]
+    for method in HTTP_METHODS {
+        if let Some(rest) = t.strip_prefix(&format!("#[{}(", method)) {
+            if let Some(path) = first_string_literal(rest) {
+                push(method, path);
+            }
+        }
+    }
+
+    // Express/Fastify/FastAPI/gin/chi: receiver.get("/x", ...), @app.get("/x")
+    for method in HTTP_METHODS {
+        let capitalized = format!("{}{}", method[..1].to_ascii_uppercase(), &method[1..]);
+        for variant in [method.to_string(), method.to_ascii_uppercase(), capitalized] {
+            let call = format!(".{}(", variant);
+            let mut search = 0;
+            while let Some(pos) = t[search..].find(&call).map(|p| p + search) {
+                if is_router_receiver(&t[..pos]) {
+                    if let Some(path) = first_string_literal(&t[pos + call.len()..]) {
+                        push(method, path);
+                    }
+                }
+                search = pos + call.len();
+            }
+        }
+    }
+
+    // Flask @app.route("/x", methods=[...]) and axum .route("/x", get(h).post(h))
+    if let Some(pos) = t.find(".route(") {
+        let args = &t[pos + ".route(".len()..];
+        if let Some(path) = first_string_literal(args) {
+            let after_path = &args[args.find(path).map(|p| p + path.len() + 1).unwrap_or(0)..];
+            let mut methods: Vec<&str> = Vec::new();
+            if let Some(list) = after_path.find("methods").map(|p| &after_path[p..]) {
+                let list = list.to_ascii_lowercase();
+                methods.extend(
+                    HTTP_METHODS
+                        .iter()
+                        .filter(|m| list.contains(&format!("\"{}\"", m)) || list.contains(&format!("'{}'", m))),
+                );
+            } else {
+                methods.extend(HTTP_METHODS.iter().filter(|m| after_path.contains(&format!("{}(", m))));
+            }
+            if methods.is_empty() {
+                methods.push(if t.starts_with('@') { "get" } else { ANY_METHOD });
+            }
+            for method in methods {
+                push(method, path);
+            }
+        }
+    }
+
+    // Go net/http: mux.HandleFunc("/x", h)
+    for call in ["HandleFunc(", ".Handle("] {
+        if let Some(pos) = t.find(call) {
+            if let Some(path) = first_string_literal(&t[pos + call.len()..]) {
+                let method = t.find(".Methods(").and_then(|p| first_string_literal(&t[p..])).unwrap_or(ANY_METHOD);
+                push(method, path);
+            }
+        }
+    }
+
+    routes.sort();
+    routes.dedup();
+    routes
+}
+
+/// Routes declared in a source file.
+pub fn routes_in_source(content: &str) -> Vec<(String, String)> {
+    let mut routes: Vec<(String, String)> = content.lines().flat_map(routes_in_line).collect();
+    routes.sort();
+    routes.dedup();
+    routes
+}
+
+/// Whether a source file implements GraphQL resolvers.
+fn is_resolver_source(name: &str, content: &str) -> bool {
+    name.to_ascii_lowercase().contains("resolver")
+        || content.contains("@Resolver(")
+        || content.contains("@QueryMapping")
+        || content.contains("@strawberry.type")
+        || (content.contains("Query: {") || content.contains("Mutation: {")) && content.contains("resolvers")
+}
+
+fn is_test_file(name: &str) -> bool {
+    let lower = name.to_ascii_lowercase();
+    lower.contains(".test.") || lower.contains(".spec.") || lower.contains("_test.") || lower.starts_with("test_")
+}
+
+/// Walk the project for schema files and handler files.
+pub fn scan_contracts(project_path: &str) -> ContractSnapshot {
+    let root = Path::new(project_path);
+    let mut files = Vec::new();
+    walk(root, root, &mut files);
+    files.sort();
+
+    let mut snapshot = ContractSnapshot::default();
+    for rel in files {
+        let name = rel.rsplit('/').next().unwrap_or(&rel).to_string();
+        let Ok(content) = safe_read::read_text(root.join(&rel)) else {
+            continue;
+        };
+
+        if let Some(format) = schema_format(&name, &content) {
+            let operations = if format == FORMAT_OPENAPI {
+                openapi_operations(&content)
+            } else {
+                graphql_operations(&content)
+            };
+            let combined: Vec<String> = operations.iter().map(|o| format!("{}={}", o.key, o.fingerprint)).collect();
+            snapshot.schemas.push(SchemaFile {
+                path: rel,
+                format,
+                fingerprint: fingerprint(&combined.join("\n")),
+                operations,
+            });
+            continue;
+        }
+
+        let ext = name.rsplit('.').next().unwrap_or("");
+        if !HANDLER_EXTENSIONS.contains(&ext) || is_test_file(&name) {
+            continue;
+        }
+        let routes = routes_in_source(&content);
+        let format = if !routes.is_empty() {
+            FORMAT_OPENAPI
+        } else if is_resolver_source(&name, &content) {
+            FORMAT_GRAPHQL
+        } else {
+            continue;
+        };
+
+        let header_lines = freshness::doc_header_line_count(&content);
+        let lines: Vec<&str> = content.lines().collect();
+        snapshot.handlers.push(HandlerFile {
+            path: rel,
+            format,
+            routes,
+            doc_fingerprint: fingerprint(&lines[..header_lines].join("\n")),
+            code_fingerprint: fingerprint(&normalize_ws(&lines[header_lines..].join("\n"))),
+        });
+    }
+
+    snapshot
+}
+
+fn walk(root: &Path, dir: &Path, files: &mut Vec<String>) {
+    let Ok(entries) = fs::read_dir(dir) else {
+        return;
+    };
+    for entry in entries.flatten() {
+        let path = entry.path();
+        let Ok(rel) = path.strip_prefix(root) else {
+            continue;
+        };
+        let rel = rel.to_string_lossy().replace('\\', "/");
+        if path.is_dir() {
+            // in_scan_scope checks directories via a placeholder file name
+            if freshness::in_scan_scope(&format!("{}/_", rel)) {
+                walk(root, &path, files);
+            }
+        } else if freshness::in_scan_scope(&rel) && entry.metadata().is_ok_and(|m| m.len() <= MAX_FILE_BYTES) {
+            files.push(rel);
+        }
+    }
+}
+
+// ---------------------------------------------------------------------------
+// Drift detection
+// ---------------------------------------------------------------------------
+
+/// Whether a code route implements a schema operation key ("GET /users/{}").
+fn route_matches(method: &str, path: &str, operation_key: &str) -> bool {
+    let Some((op_method, op_path)) = operation_key.split_once(' ') else {
+        return false;
+    };
+    if method != ANY_METHOD && method != op_method {
+        return false;
+    }
+    if path == "/" {
+        return op_path == "/";
+    }
+    let route: Vec<&str> = path.split('/').filter(|s| !s.is_empty()).collect();
+    let op: Vec<&str> = op_path.split('/').filter(|s| !s.is_empty()).collect();
+    op.ends_with(&route)
+}
+
+/// Compare a snapshot's routes with its OpenAPI operations, and its handlers
+/// with the stored baseline. An empty baseline only yields route findings.
+pub fn detect_drift(snapshot: &ContractSnapshot, baseline: &[BaselineEntry]) -> Vec<ApiContractDrift> {
+    let mut drift = Vec::new();
+
+    let openapi_ops: Vec<(&str, &str)> = snapshot
+        .schemas
+        .iter()
+        .filter(|s| s.format == FORMAT_OPENAPI)
+        .flat_map(|s| s.operations.iter().map(move |o| (s.path.as_str(), o.key.as_str())))
+        .collect();
+
+    if !openapi_ops.is_empty() {
+        let routes: Vec<(&str, &str, &str)> = snapshot
+            .handlers
+            .iter()
+            .flat_map(|h| h.routes.iter().map(move |(m, p)| (h.path.as_str(), m.as_str(), p.as_str())))
+            .collect();
+
+        for (file, method, path) in &routes {
+            if !openapi_ops.iter().any(|(_, key)| route_matches(method, path, key)) {
+                drift.push(ApiContractDrift {
+                    kind: DRIFT_UNDOCUMENTED_ROUTE.to_string(),
+                    path: file.to_string(),
+                    operation: Some(format!("{} {}", method, path)),
+                    detail: format!("{} {} is handled in code but not described in any OpenAPI schema", method, path),
+                });
+            }
+        }
+
+        if !routes.is_empty() {
+            for (schema, key) in &openapi_ops {
+                if !routes.iter().any(|(_, m, p)| route_matches(m, p, key)) {
+                    drift.push(ApiContractDrift {
+                        kind: DRIFT_MISSING_HANDLER.to_string(),
+                        path: schema.to_string(),
+                        operation: Some(key.to_string()),
+                        detail: format!("{} is in the schema but no route handler was found", key),
+                    });
+                }
+            }
+        }
+    }
+
+    let baseline: HashMap<(&str, &str), &BaselineEntry> =
+        baseline.iter().map(|b| ((b.kind.as_str(), b.path.as_str()), b)).collect();
+    let schema_changed = |format: &str| {
+        snapshot.schemas.iter().filter(|s| s.format == format).any(|s| {
+            baseline
+                .get(&(KIND_SCHEMA, s.path.as_str()))
+                .is_none_or(|b| b.fingerprint != s.fingerprint)
+        })
+    };
+    for handler in &snapshot.handlers {
+        let Some(before) = baseline.get(&(KIND_HANDLER, handler.path.as_str())) else {
+            continue;
+        };
+        let has_schema = snapshot.schemas.iter().any(|s| s.format == handler.format);
+        if has_schema
+            && before.fingerprint != handler.code_fingerprint
+            && before.doc_fingerprint == handler.doc_fingerprint
+            && !schema_changed(handler.format)
+        {
+            let what = if handler.format == FORMAT_GRAPHQL { "Resolvers" } else { "Route handlers" };
+            drift.push(ApiContractDrift {
+                kind: DRIFT_HANDLER_CHANGED.to_string(),
+                path: handler.path.clone(),
+                operation: None,
+                detail: format!(
+                    "{} changed since {} but neither the {} schema nor this file's docs were updated",
+                    what,
+                    before.recorded_at,
+                    if handler.format == FORMAT_GRAPHQL { "GraphQL" } else { "OpenAPI" }
+                ),
+            });
+        }
+    }
+
+    drift
+}
+
+/// Stored fingerprints for a project.
+pub fn load_baseline(db: &Connection, project_id: &str) -> Result<Vec<BaselineEntry>, String> {
+    let mut stmt = db
+        .prepare(
+            "SELECT path, kind, fingerprint, doc_fingerprint, recorded_at
+             FROM api_contract_baselines WHERE project_id = ?1 ORDER BY path",
+        )
+        .map_err(|e| format!("Failed to query API contract baseline: {}", e))?;
+
+    let entries = stmt
+        .query_map([project_id], |row| {
+            Ok(BaselineEntry {
+                path: row.get(0)?,
+                kind: row.get(1)?,
+                fingerprint: row.get(2)?,
+                doc_fingerprint: row.get(3)?,
+                recorded_at: row.get(4)?,
+            })
+        })
+        .map_err(|e| format!("Failed to read API contract baseline: {}", e))?
+        .filter_map(|r| r.ok())
+        .collect();
+
+    Ok(entries)
+}
+
+/// Replace the project's stored fingerprints with the snapshot's. Returns the timestamp.
+pub fn save_baseline(db: &Connection, project_id: &str, snapshot: &ContractSnapshot) -> Result<String, String> {
+    let now = Utc::now().to_rfc3339();
+    db.execute("DELETE FROM api_contract_baselines WHERE project_id = ?1", [project_id])
+        .map_err(|e| format!("Failed to clear API contract baseline: {}", e))?;
+
+    let rows = snapshot
+        .schemas
+        .iter()
+        .map(|s| (s.path.as_str(), KIND_SCHEMA, s.fingerprint.as_str(), ""))
+        .chain(
+            snapshot
+                .handlers
+                .iter()
+                .map(|h| (h.path.as_str(), KIND_HANDLER, h.code_fingerprint.as_str(), h.doc_fingerprint.as_str())),
+        );
+    for (path, kind, fp, doc_fp) in rows {
+        db.execute(
+            "INSERT INTO api_contract_baselines (project_id, path, kind, fingerprint, doc_fingerprint, recorded_at)
+             VALUES (?1, ?2, ?3, ?4, ?5, ?6)",
+            rusqlite::params![project_id, path, kind, fp, doc_fp, now],
+        )
+        .map_err(|e| format!("Failed to save API contract baseline: {}", e))?;
+    }
+
+    Ok(now)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_support::with_project;
+
+    const OPENAPI_YAML: &str = r#"openapi: 3.0.0
+info:
+  title: Users
+paths:
+  /users:
+    get:
+      summary: List users
+      responses:
+        "200":
+          description: OK
+    post:
+      summary: Create a user
+  "/users/{id}":
+    parameters:
+      - name: id
+    delete:
+      summary: Delete a user
+components:
+  schemas:
+    User:
+      type: object
+"#;
+
+    const SCHEMA_GRAPHQL: &str = r#"# Root types
+"""
+Queries
+"""
+type Query {
+  user(id: ID!): User
+  users(
+    first: Int
+  ): [User!]!
+}
+
+type Mutation { createUser(name: String!): User }
+
+type User {
+  id: ID!
+  "display name"
+  name: String
+}
+
+union SearchResult = User
+  | Post
+"#;
+
+    #[test]
+    fn test_openapi_operations_yaml_and_json() {
+        let keys: Vec<String> = openapi_operations(OPENAPI_YAML).into_iter().map(|o| o.key).collect();
+        assert_eq!(keys, vec!["GET /users", "POST /users", "DELETE /users/{}"]);
+
+        let json = r#"{"openapi": "3.1.0", "paths": {"/pets/{petId}": {"get": {"operationId": "showPet"}}}}"#;
+        let ops = openapi_operations(json);
+        assert_eq!(ops.len(), 1);
+        assert_eq!(ops[0].key, "GET /pets/{}");
+
+        // Reformatting the document does not change fingerprints; editing an operation does
+        let reindented = OPENAPI_YAML.replace("summary: List users", "summary:   List users");
+        assert_eq!(openapi_operations(&reindented)[0].fingerprint, openapi_operations(OPENAPI_YAML)[0].fingerprint);
+        let edited = OPENAPI_YAML.replace("List users", "List all users");
+        assert_ne!(openapi_operations(&edited)[0].fingerprint, openapi_operations(OPENAPI_YAML)[0].fingerprint);
+    }
+
+    #[test]
+    fn test_graphql_operations() {
+        let ops = graphql_operations(SCHEMA_GRAPHQL);
+        let keys: Vec<&str> = ops.iter().map(|o| o.key.as_str()).collect();
+        assert_eq!(
+            keys,
+            vec!["Query.user", "Query.users", "Mutation.createUser", "type User", "union SearchResult"]
+        );
+
+        // Descriptions and comments are documentation, not contract
+        let described = SCHEMA_GRAPHQL.replace("\"display name\"", "\"the user's display name\"");
+        assert_eq!(graphql_operations(&described), ops);
+        let changed = SCHEMA_GRAPHQL.replace("name: String\n", "name: String!\n");
+        assert_ne!(graphql_operations(&changed)[3], ops[3]);
+    }
+
+    #[test]
+    fn test_routes_in_source() {
+        let express = r#"
+router.get('/users', list);
+app.post("/users", create);
+router.route('/users/:id').delete(remove);
+const res = await axios.get('/users');
+"#;
+        assert_eq!(
+            routes_in_source(express),
+            vec![
+                ("DELETE".to_string(), "/users/{}".to_string()),
+                ("GET".to_string(), "/users".to_string()),
+                ("POST".to_string(), "/users".to_string()),
+            ]
+        );
+
+        let python = "@app.get(\"/items/{item_id}\")\n@bp.route('/health')\n@app.route('/login', methods=['GET', 'POST'])\n";
+        assert_eq!(
+            routes_in_source(python),
+            vec![
+                ("GET".to_string(), "/health".to_string()),
+                ("GET".to_string(), "/items/{}".to_string()),
+                ("GET".to_string(), "/login".to_string()),
+                ("POST".to_string(), "/login".to_string()),
+            ]
+        );
+
+        let others = "@GetMapping(\"/orders/{id}\")\n#[post(\"/orders\")]\n.route(\"/orders/:id\", get(show).put(update))\nhttp.HandleFunc(\"/ping\", ping)\n";
+        assert_eq!(
+            routes_in_source(others),
+            vec![
+                ("ANY".to_string(), "/ping".to_string()),
+                ("GET".to_string(), "/orders/{}".to_string()),
+                ("POST".to_string(), "/orders".to_string()),
+                ("PUT".to_string(), "/orders/{}".to_string()),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_detect_drift_routes_and_baseline() {
+        let dir = tempfile::tempdir().unwrap();
+        let root = dir.path();
+        fs::create_dir_all(root.join("api")).unwrap();
+        fs::create_dir_all(root.join("src/routes")).unwrap();
+        fs::write(root.join("api/openapi.yaml"), OPENAPI_YAML).unwrap();
+        fs::write(
+            root.join("src/routes/users.ts"),
+            "/**\n * @module routes/users\n */\nrouter.get('/', list);\nrouter.get('/users', list);\nrouter.post('/users', create);\nrouter.get('/admin', admin);\n",
+        )
+        .unwrap();
+        fs::write(root.join("src/routes/users.test.ts"), "request(app).get('/nope');\n").unwrap();
+        let path = root.to_str().unwrap();
+
+        let snapshot = scan_contracts(path);
+        assert_eq!(snapshot.schemas.len(), 1);
+        assert_eq!(snapshot.schemas[0].format, FORMAT_OPENAPI);
+        assert_eq!(snapshot.handlers.len(), 1);
+
+        let drift = detect_drift(&snapshot, &[]);
+        let found: Vec<(&str, Option<&str>)> = drift.iter().map(|d| (d.kind.as_str(), d.operation.as_deref())).collect();
+        assert_eq!(
+            found,
+            vec![
+                (DRIFT_UNDOCUMENTED_ROUTE, Some("GET /")),
+                (DRIFT_UNDOCUMENTED_ROUTE, Some("GET /admin")),
+                (DRIFT_MISSING_HANDLER, Some("DELETE /users/{}")),
+            ]
+        );
+
+        let conn = with_project();
+        save_baseline(&conn, "p1", &snapshot).unwrap();
+        let baseline = load_baseline(&conn, "p1").unwrap();
+        assert_eq!(baseline.len(), 2);
+
+        // Handler code changes alone: drift
+        let handler = root.join("src/routes/users.ts");
+        let original = fs::read_to_string(&handler).unwrap();
+        fs::write(&handler, original.replace("create);", "createUser);")).unwrap();
+        let changed = detect_drift(&scan_contracts(path), &baseline);
+        assert!(changed.iter().any(|d| d.kind == DRIFT_HANDLER_CHANGED && d.path == "src/routes/users.ts"));
+
+        // ...unless its doc header changed too
+        fs::write(&handler, original.replace("create);", "createUser);").replace("routes/users", "routes/users (v2)")).unwrap();
+        assert!(!detect_drift(&scan_contracts(path), &baseline).iter().any(|d| d.kind == DRIFT_HANDLER_CHANGED));
+
+        // ...or the schema changed
+        fs::write(&handler, original.replace("create);", "createUser);")).unwrap();
+        fs::write(root.join("api/openapi.yaml"), OPENAPI_YAML.replace("Create a user", "Create one user")).unwrap();
+        assert!(!detect_drift(&scan_contracts(path), &baseline).iter().any(|d| d.kind == DRIFT_HANDLER_CHANGED));
+    }
+}
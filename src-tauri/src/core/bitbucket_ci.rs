@@ -0,0 +1,237 @@
+//! @module core/bitbucket_ci
+//! @description Bitbucket adapter for the forge integration: doc-check steps from Bitbucket Pipelines
+//!
+//! PURPOSE:
+//! - Fetch recent pipelines of a Bitbucket Cloud repository with an access token
+//! - Keep pipelines that ran the doc-check step the CI snippet installs, as CiRuns
+//!
+//! DEPENDENCIES:
+//! - reqwest - Bitbucket Cloud REST API (2.0) calls
+//! - serde - Pipeline and step deserialization
+//! - core::git_forge - GitForge trait, ForgeFuture, CiRun
+//! - models::git_forge - ForgeProvider
+//!
+//! EXPORTS:
+//! - BITBUCKET_TOKEN_SETTING - Settings key of the (encrypted) Bitbucket token
+//! - DOC_CHECK_STEP - Step name the Bitbucket Pipelines snippet defines
+//! - Pipeline / PipelineStep - The fields of pipelines and steps we use
+//! - BitbucketForge - GitForge implementation for bitbucket.org repositories
+//! - fetch_doc_check_runs - Recent pipelines with their doc-check step, from the API
+//! - ci_run - A pipeline and its doc-check step as a CiRun
+//!
+//! PATTERNS:
+//! - Pipelines are not named like GitHub workflows, so the conclusion comes from the
+//!   pipeline's DOC_CHECK_STEP; pipelines without that step are dropped
+//! - Steps are only fetched for finished pipelines that did not succeed (one request each);
+//!   a successful pipeline implies a successful doc check
+//! - CiRun ids are "bitbucket-<build_number>"
+//!
+//! CLAUDE NOTES:
+//! - The token is a repository or workspace access token with pipeline read scope, sent
+//!   as a Bearer token
+//! - Pipeline uuids come wrapped in braces and are URL-encoded in step requests
+
+use serde::Deserialize;
+
+use crate::core::git_forge::{CiRun, ForgeFuture, GitForge};
+use crate::models::git_forge::ForgeProvider;
+
+/// Settings key of the Bitbucket token (stored encrypted).
+pub const BITBUCKET_TOKEN_SETTING: &str = "bitbucket_token";
+
+/// Step name written by the Bitbucket Pipelines snippet.
+pub const DOC_CHECK_STEP: &str = "Documentation check";
+
+const API_URL: &str = "https://api.bitbucket.org/2.0";
+
+/// Pipelines requested per sync
+const PIPELINES_PER_SYNC: u32 = 30;
+
+/// The fields of a Bitbucket pipeline we use.
+#[derive(Debug, Clone, Deserialize)]
+pub struct Pipeline {
+    pub uuid: String,
+    pub build_number: u64,
+    pub state: State,
+    pub target: Option<Target>,
+    pub created_on: String,
+    pub completed_on: Option<String>,
+}
+
+#[derive(Debug, Clone, Deserialize)]
+pub struct PipelineStep {
+    pub name: Option<String>,
+    pub state: State,
+}
+
+/// Pipeline or step state: name COMPLETED / IN_PROGRESS / PENDING, result SUCCESSFUL /
+/// FAILED / ERROR / STOPPED / ... once completed.
+#[derive(Debug, Clone, Deserialize)]
+pub struct State {
+    pub name: String,
+    pub result: Option<NamedValue>,
+}
+
+#[derive(Debug, Clone, Deserialize)]
+pub struct NamedValue {
+    pub name: String,
+}
+
+#[derive(Debug, Clone, Deserialize)]
+pub struct Target {
+    pub ref_name: Option<String>,
+    pub commit: Option<Commit>,
+}
+
+#[derive(Debug, Clone, Deserialize)]
+pub struct Commit {
+    pub hash: String,
+}
+
+#[derive(Debug, Deserialize)]
+struct Page<T> {
+    values: Vec<T>,
+}
+
+/// Bitbucket adapter: doc-check steps from Bitbucket Pipelines.
+pub struct BitbucketForge {
+    workspace: String,
+    repo: String,
+    token: String,
+}
+
+impl BitbucketForge {
+    pub fn new(workspace: &str, repo: &str, token: &str) -> Self {
+        BitbucketForge { workspace: workspace.to_string(), repo: repo.to_string(), token: token.to_string() }
+    }
+}
+
+impl GitForge for BitbucketForge {
+    fn provider(&self) -> ForgeProvider {
+        ForgeProvider::Bitbucket
+    }
+
+    fn repository(&self) -> String {
+        format!("{}/{}", self.workspace, self.repo)
+    }
+
+    fn fetch_doc_check_runs<'a>(&'a self, client: &'a reqwest::Client) -> ForgeFuture<'a, Vec<CiRun>> {
+        Box::pin(fetch_doc_check_runs(client, &self.token, &self.workspace, &self.repo))
+    }
+}
+
+async fn get_json<T: for<'de> Deserialize<'de>>(client: &reqwest::Client, token: &str, url: &str) -> Result<T, String> {
+    let response = client
+        .get(url)
+        .bearer_auth(token)
+        .header("User-Agent", "project-jumpstart")
+        .send()
+        .await
+        .map_err(|e| format!("Bitbucket request failed: {}", e))?;
+
+    match response.status().as_u16() {
+        200 => {}
+        401 => return Err("Bitbucket rejected the token (401). Check it in Settings.".to_string()),
+        403 => return Err("Bitbucket token cannot read pipelines (403)".to_string()),
+        404 => return Err("Bitbucket repository not found (or Pipelines is not enabled)".to_string()),
+        status => return Err(format!("Bitbucket API error ({})", status)),
+    }
+    response
+        .json()
+        .await
+        .map_err(|e| format!("Failed to parse Bitbucket response: {}", e))
+}
+
+/// Recent pipelines that ran the doc-check step, newest first.
+pub async fn fetch_doc_check_runs(
+    client: &reqwest::Client,
+    token: &str,
+    workspace: &str,
+    repo: &str,
+) -> Result<Vec<CiRun>, String> {
+    let base = format!("{}/repositories/{}/{}/pipelines", API_URL, workspace, repo);
+    let url = format!("{}/?sort=-created_on&pagelen={}", base, PIPELINES_PER_SYNC);
+    let pipelines: Page<Pipeline> = get_json(client, token, &url).await?;
+
+    let mut runs = Vec::new();
+    for pipeline in pipelines.values {
+        let finished = pipeline.state.name == "COMPLETED";
+        let succeeded = pipeline.state.result.as_ref().is_some_and(|r| r.name == "SUCCESSFUL");
+        let step = if finished && !succeeded {
+            let url = format!("{}/{}/steps/", base, pipeline.uuid.replace('{', "%7B").replace('}', "%7D"));
+            let steps: Page<PipelineStep> = get_json(client, token, &url).await?;
+            match steps.values.into_iter().find(|s| s.name.as_deref() == Some(DOC_CHECK_STEP)) {
+                Some(step) => Some(step),
+                None => continue,
+            }
+        } else {
+            None
+        };
+        runs.push(ci_run(workspace, repo, pipeline, step.as_ref()));
+    }
+    Ok(runs)
+}
+
+/// A pipeline as a CiRun. The conclusion is the doc-check step's result when given,
+/// otherwise the pipeline's own.
+pub fn ci_run(workspace: &str, repo: &str, pipeline: Pipeline, step: Option<&PipelineStep>) -> CiRun {
+    let state = step.map(|s| &s.state).unwrap_or(&pipeline.state);
+    let completed = state.name == "COMPLETED";
+    let conclusion = state.result.as_ref().filter(|_| completed).map(|r| {
+        match r.name.as_str() {
+            "SUCCESSFUL" => "success",
+            "FAILED" | "ERROR" => "failure",
+            "STOPPED" => "cancelled",
+            other => other,
+        }
+        .to_lowercase()
+    });
+    let target = pipeline.target.as_ref();
+    CiRun {
+        id: format!("bitbucket-{}", pipeline.build_number),
+        completed,
+        conclusion,
+        branch: target.and_then(|t| t.ref_name.clone()),
+        sha: target.and_then(|t| t.commit.as_ref()).map(|c| c.hash.clone()).unwrap_or_default(),
+        url: format!("https://bitbucket.org/{}/{}/pipelines/results/{}", workspace, repo, pipeline.build_number),
+        title: None,
+        updated_at: pipeline.completed_on.unwrap_or(pipeline.created_on),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_pipelines_become_ci_runs() {
+        let page: Page<Pipeline> = serde_json::from_str(
+            r#"{"values": [
+                {"uuid": "{1}", "build_number": 12,
+                 "state": {"name": "COMPLETED", "result": {"name": "FAILED"}},
+                 "target": {"ref_name": "main", "commit": {"hash": "abcdef1234567"}},
+                 "created_on": "2025-03-01T09:58:00Z", "completed_on": "2025-03-01T10:00:00Z"},
+                {"uuid": "{2}", "build_number": 13, "state": {"name": "IN_PROGRESS"},
+                 "target": null, "created_on": "2025-03-01T11:00:00Z"}
+            ]}"#,
+        )
+        .unwrap();
+        let step: PipelineStep =
+            serde_json::from_str(r#"{"name": "Documentation check", "state": {"name": "COMPLETED", "result": {"name": "ERROR"}}}"#)
+                .unwrap();
+        let mut pipelines = page.values.into_iter();
+
+        let failed = ci_run("ws", "app", pipelines.next().unwrap(), Some(&step));
+        assert_eq!(failed.id, "bitbucket-12");
+        assert!(failed.completed);
+        assert_eq!(failed.conclusion.as_deref(), Some("failure"));
+        assert_eq!(failed.branch.as_deref(), Some("main"));
+        assert_eq!(failed.url, "https://bitbucket.org/ws/app/pipelines/results/12");
+        assert_eq!(failed.updated_at, "2025-03-01T10:00:00Z");
+
+        let running = ci_run("ws", "app", pipelines.next().unwrap(), None);
+        assert!(!running.completed);
+        assert_eq!(running.conclusion, None);
+        assert_eq!(running.sha, "");
+    }
+}
@@ -0,0 +1,434 @@
+//! @module core/bootstrap
+//! @description Heuristic half of the CLAUDE.md bootstrap: clustering, digests, prompts, estimates, and run storage
+//!
+//! PURPOSE:
+//! - Group a project's source files into directory clusters at a chosen depth
+//! - Build a local digest of each cluster (file names, exports, imports, existing header descriptions)
+//! - Build the AI prompts for cluster summaries and the architecture overview, with heuristic fallbacks
+//! - Estimate AI calls, tokens, and cost before anything is sent
+//! - Store and load resumable bootstrap runs; write nested CLAUDE.md files without overwriting
+//!
+//! DEPENDENCIES:
+//! - core::analyzer - Source file walk, export/import detection, header parsing
+//! - core::ai - Token estimates, truncation, and "never send to AI" globs
+//! - core::generator - Template CLAUDE.md used when the overview cannot come from AI
+//! - rusqlite - doc_bootstraps table
+//! - models::bootstrap - BootstrapCluster, BootstrapEstimate, BootstrapRun
+//! - models::project - Project
+//!
+//! EXPORTS:
+//! - MIN_DEPTH / MAX_DEPTH - Accepted clustering depth range
+//! - CLUSTER_SYSTEM_PROMPT / OVERVIEW_SYSTEM_PROMPT - System prompts for the two AI stages
+//! - plan - Scan, cluster, and estimate a bootstrap (no AI, nothing written)
+//! - cluster_files - Group project-relative files into directory clusters
+//! - cluster_digest - Local per-file digest of a cluster, within the doc token budget
+//! - cluster_prompt / heuristic_summary - AI prompt and local fallback for one cluster
+//! - overview_prompt / heuristic_overview - AI prompt and local fallback for the top-level overview
+//! - estimate - AI calls, tokens, and USD for a set of clusters
+//! - write_cluster - Create <dir>/CLAUDE.md unless one exists
+//! - save_run / latest_run - doc_bootstraps persistence
+//!
+//! PATTERNS:
+//! - A cluster key is the file's first `depth` directories; clusters with fewer than
+//!   MIN_CLUSTER_FILES files fold into their parent, and root-level files go to the overview only
+//! - Files matching .claude/ai-exclude are counted but never named in a digest
+//! - Cost uses list prices for ai::MODEL; it is an estimate shown before the user confirms
+//!
+//! CLAUDE NOTES:
+//! - Existing CLAUDE.md files (nested or root) are never overwritten; the outcome records it
+//! - More than MAX_CLUSTERS clusters retries one level shallower so a run stays reviewable
+
+use std::collections::BTreeMap;
+use std::path::Path;
+
+use chrono::Utc;
+use rusqlite::{Connection, OptionalExtension};
+
+use crate::core::{ai, analyzer, generator};
+use crate::models::bootstrap::{BootstrapCluster, BootstrapEstimate, BootstrapRun};
+use crate::models::project::Project;
+
+pub const MIN_DEPTH: u32 = 1;
+pub const MAX_DEPTH: u32 = 4;
+
+/// Clusters smaller than this fold into their parent directory.
+const MIN_CLUSTER_FILES: usize = 2;
+/// Most clusters one run produces before clustering one level shallower.
+const MAX_CLUSTERS: usize = 60;
+/// Expected response size of one cluster summary.
+const SUMMARY_OUTPUT_TOKENS: u32 = 700;
+/// Expected response size of the overview.
+const OVERVIEW_OUTPUT_TOKENS: u32 = 1_500;
+/// Cluster summary excerpt sent to the overview stage.
+const OVERVIEW_TOKENS_PER_CLUSTER: usize = 250;
+/// Exports and imports listed per file in a digest.
+const DIGEST_NAMES_PER_FILE: usize = 8;
+/// USD per million tokens for ai::MODEL (input, output).
+const INPUT_USD_PER_MTOK: f64 = 3.0;
+const OUTPUT_USD_PER_MTOK: f64 = 15.0;
+
+pub const CLUSTER_SYSTEM_PROMPT: &str = "You write nested CLAUDE.md files: short, directory-level guides that \
+    help AI coding assistants work in one part of a codebase. You are given the directory's source files with \
+    their exports, imports, and any existing doc header descriptions. Write Markdown with: an H1 of the \
+    directory path; a 1-2 sentence purpose; \"## Key Files\" (one line per important file: what it is for); \
+    \"## Conventions\" (patterns visible in names, exports, and imports); \"## Notes\" (relationships to other \
+    directories, gotchas). Stay under 60 lines. Only state what the file list supports; never invent APIs. \
+    Return only the Markdown.";
+
+pub const OVERVIEW_SYSTEM_PROMPT: &str = "You write the top-level CLAUDE.md for a codebase that had no \
+    documentation. You are given the project's stack and a summary of each directory (each already has its own \
+    nested CLAUDE.md). Write Markdown with: an H1 project name; \"## Overview\" (2-3 sentences on what the \
+    project does); \"## Architecture\" (how the directories relate and how data flows between them); \
+    \"## Module Map\" (one bullet per directory: `- [dir/](dir/CLAUDE.md) - one line`); \"## Conventions\"; \
+    \"## Notes\". Reference real directory names only. Return only the Markdown.";
+
+/// Scan the project, cluster its files, and estimate the run. Nothing is sent or written.
+pub fn plan(project: &Project, depth: u32, uses_ai: bool) -> Result<BootstrapRun, String> {
+    let depth = depth.clamp(MIN_DEPTH, MAX_DEPTH);
+    let files: Vec<String> = analyzer::scan_all_modules(&project.path)?
+        .into_iter()
+        .filter(|module| module.status != "ignored")
+        .map(|module| module.path)
+        .collect();
+    let (mut clusters, root_files) = cluster_files(&files, depth);
+
+    let excludes = ai::load_ai_excludes(&project.path);
+    for cluster in &mut clusters {
+        let prompt = cluster_prompt(project, cluster, &cluster_digest(&project.path, cluster, &excludes));
+        cluster.prompt_tokens = ai::estimate_tokens(&format!("{}{}", CLUSTER_SYSTEM_PROMPT, prompt)) as u32;
+    }
+
+    let now = Utc::now().to_rfc3339();
+    Ok(BootstrapRun {
+        id: String::new(),
+        project_id: project.id.clone(),
+        depth,
+        stage: "planned".to_string(),
+        estimate: estimate(&clusters, root_files.len() as u32, uses_ai),
+        clusters,
+        root_files,
+        overview: None,
+        overview_path: None,
+        error: None,
+        created_at: now.clone(),
+        updated_at: now,
+    })
+}
+
+/// Group project-relative files into clusters keyed by their first `depth` directories.
+/// Returns the clusters (sorted by dir) and the files that belong to the root.
+pub fn cluster_files(files: &[String], depth: u32) -> (Vec<BootstrapCluster>, Vec<String>) {
+    let mut groups: BTreeMap<String, Vec<String>> = BTreeMap::new();
+    let mut root_files = Vec::new();
+    for file in files {
+        let dirs: Vec<&str> = file.split('/').collect();
+        let dirs = &dirs[..dirs.len() - 1];
+        if dirs.is_empty() {
+            root_files.push(file.clone());
+        } else {
+            let key = dirs[..dirs.len().min(depth as usize)].join("/");
+            groups.entry(key).or_default().push(file.clone());
+        }
+    }
+
+    // Fold small clusters upward, deepest first, so folds can cascade
+    for level in (1..=depth as usize).rev() {
+        let small: Vec<String> = groups
+            .iter()
+            .filter(|(dir, files)| dir.split('/').count() == level && files.len() < MIN_CLUSTER_FILES)
+            .map(|(dir, _)| dir.clone())
+            .collect();
+        for dir in small {
+            let files = groups.remove(&dir).unwrap_or_default();
+            match dir.rsplit_once('/') {
+                Some((parent, _)) => groups.entry(parent.to_string()).or_default().extend(files),
+                None => root_files.extend(files),
+            }
+        }
+    }
+
+    if groups.len() > MAX_CLUSTERS && depth > MIN_DEPTH {
+        return cluster_files(files, depth - 1);
+    }
+
+    root_files.sort();
+    let clusters = groups
+        .into_iter()
+        .map(|(dir, mut files)| {
+            files.sort();
+            BootstrapCluster {
+                dir,
+                files,
+                ..Default::default()
+            }
+        })
+        .collect();
+    (clusters, root_files)
+}
+
+/// One line per file: path within the cluster, header description, exports, imports.
+/// Excluded files are only counted. Truncated to the doc_file_tokens budget.
+pub fn cluster_digest(project_path: &str, cluster: &BootstrapCluster, excludes: &[String]) -> String {
+    let mut lines = Vec::new();
+    let mut excluded = 0;
+    for file in &cluster.files {
+        if ai::is_ai_excluded(excludes, file) {
+            excluded += 1;
+            continue;
+        }
+        let name = file.strip_prefix(&format!("{}/", cluster.dir)).unwrap_or(file);
+        let abs = Path::new(project_path).join(file).to_string_lossy().to_string();
+        let content = std::fs::metadata(&abs)
+            .ok()
+            .filter(|m| m.len() <= analyzer::max_source_bytes(&abs))
+            .and_then(|_| analyzer::read_source(&abs).ok())
+            .unwrap_or_default();
+        let ext = Path::new(file).extension().and_then(|e| e.to_str()).unwrap_or("");
+
+        let mut line = format!("- `{}`", name);
+        if let Some(doc) = analyzer::parse_doc_header(&content).filter(|d| !d.description.is_empty()) {
+            line.push_str(&format!(": {}", doc.description));
+        }
+        let exports = analyzer::detect_exports(&content, ext);
+        if !exports.is_empty() {
+            line.push_str(&format!(" Exports: {}.", list_names(&exports)));
+        }
+        let imports = analyzer::detect_imports(&content, ext);
+        if !imports.is_empty() {
+            line.push_str(&format!(" Imports: {}.", list_names(&imports)));
+        }
+        lines.push(line);
+    }
+    if excluded > 0 {
+        lines.push(format!("- ({} files excluded from AI)", excluded));
+    }
+    ai::truncate_to_tokens(&lines.join("\n"), ai::token_limits().doc_file_tokens)
+}
+
+fn list_names(names: &[String]) -> String {
+    let mut list = names.iter().take(DIGEST_NAMES_PER_FILE).cloned().collect::<Vec<_>>().join(", ");
+    if names.len() > DIGEST_NAMES_PER_FILE {
+        list.push_str(&format!(" (+{} more)", names.len() - DIGEST_NAMES_PER_FILE));
+    }
+    list
+}
+
+fn stack_line(project: &Project) -> String {
+    let mut stack = vec![project.language.clone()];
+    stack.extend(project.framework.clone());
+    stack.extend(project.database.clone());
+    stack.retain(|s| !s.is_empty());
+    stack.join(", ")
+}
+
+pub fn cluster_prompt(project: &Project, cluster: &BootstrapCluster, digest: &str) -> String {
+    format!(
+        "Project: {} ({})\nDirectory: {}/ ({} source files)\n\nFiles:\n{}\n\nWrite the CLAUDE.md for this directory.",
+        project.name,
+        stack_line(project),
+        cluster.dir,
+        cluster.files.len(),
+        digest
+    )
+}
+
+/// Local stand-in for an AI cluster summary, built from the digest.
+pub fn heuristic_summary(cluster: &BootstrapCluster, digest: &str) -> String {
+    format!(
+        "# {}\n\n{} source files. Generated from file names, exports, and imports; review and expand.\n\n## Key Files\n\n{}\n",
+        cluster.dir,
+        cluster.files.len(),
+        digest
+    )
+}
+
+/// The summary without its H1, cut to the overview's per-cluster budget.
+fn summary_excerpt(summary: &str) -> String {
+    let body: Vec<&str> = summary.lines().filter(|l| !l.starts_with("# ")).collect();
+    ai::truncate_to_tokens(body.join("\n").trim(), OVERVIEW_TOKENS_PER_CLUSTER)
+}
+
+pub fn overview_prompt(project: &Project, clusters: &[BootstrapCluster], root_files: &[String]) -> String {
+    let mut prompt = format!("Project: {}\nStack: {}\n", project.name, stack_line(project));
+    if !project.description.is_empty() {
+        prompt.push_str(&format!("Description: {}\n", project.description));
+    }
+    if !root_files.is_empty() {
+        prompt.push_str(&format!("Root files: {}\n", root_files.join(", ")));
+    }
+    prompt.push_str("\nDirectory summaries:\n");
+    for cluster in clusters {
+        prompt.push_str(&format!(
+            "\n### {}/ ({} files)\n{}\n",
+            cluster.dir,
+            cluster.files.len(),
+            summary_excerpt(cluster.summary.as_deref().unwrap_or(""))
+        ));
+    }
+    prompt
+}
+
+/// Template CLAUDE.md (core::generator) with a module map linking the nested files.
+pub fn heuristic_overview(project: &Project, clusters: &[BootstrapCluster]) -> String {
+    let mut out = generator::generate_claude_md_content(project);
+    out.push_str("\n---\n\n## Module Map\n\n");
+    for cluster in clusters {
+        out.push_str(&format!(
+            "- [{}/]({}/CLAUDE.md) - {} files\n",
+            cluster.dir,
+            cluster.dir,
+            cluster.files.len()
+        ));
+    }
+    out
+}
+
+/// AI calls, tokens, and approximate cost of summarizing `clusters` plus the overview.
+pub fn estimate(clusters: &[BootstrapCluster], root_files: u32, uses_ai: bool) -> BootstrapEstimate {
+    let files = clusters.iter().map(|c| c.files.len() as u32).sum::<u32>() + root_files;
+    if !uses_ai {
+        return BootstrapEstimate {
+            uses_ai,
+            clusters: clusters.len() as u32,
+            files,
+            ..Default::default()
+        };
+    }
+    let overview_input = ai::estimate_tokens(OVERVIEW_SYSTEM_PROMPT) as u32
+        + clusters.len() as u32 * OVERVIEW_TOKENS_PER_CLUSTER as u32
+        + 300;
+    let input_tokens = clusters.iter().map(|c| c.prompt_tokens).sum::<u32>() + overview_input;
+    let output_tokens = clusters.len() as u32 * SUMMARY_OUTPUT_TOKENS + OVERVIEW_OUTPUT_TOKENS;
+    let usd = input_tokens as f64 / 1e6 * INPUT_USD_PER_MTOK + output_tokens as f64 / 1e6 * OUTPUT_USD_PER_MTOK;
+    BootstrapEstimate {
+        uses_ai,
+        clusters: clusters.len() as u32,
+        files,
+        ai_calls: clusters.len() as u32 + 1,
+        input_tokens,
+        output_tokens,
+        estimated_usd: (usd * 100.0).ceil() / 100.0,
+    }
+}
+
+/// Write <dir>/CLAUDE.md from the cluster summary. Returns "created" or "kept_existing".
+pub fn write_cluster(project_path: &str, cluster: &BootstrapCluster) -> Result<&'static str, String> {
+    let path = Path::new(project_path).join(&cluster.dir).join("CLAUDE.md");
+    if path.exists() {
+        return Ok("kept_existing");
+    }
+    let summary = cluster.summary.as_deref().ok_or("Cluster has no summary yet")?;
+    std::fs::write(&path, format!("{}\n", summary.trim_end()))
+        .map_err(|e| format!("Failed to write {}: {}", path.display(), e))?;
+    Ok("created")
+}
+
+pub fn save_run(db: &Connection, run: &BootstrapRun) -> Result<(), String> {
+    let clusters = serde_json::to_string(&run.clusters).map_err(|e| format!("Failed to serialize clusters: {}", e))?;
+    let root_files =
+        serde_json::to_string(&run.root_files).map_err(|e| format!("Failed to serialize root files: {}", e))?;
+    let estimate = serde_json::to_string(&run.estimate).map_err(|e| format!("Failed to serialize estimate: {}", e))?;
+    db.execute(
+        "INSERT OR REPLACE INTO doc_bootstraps
+         (id, project_id, depth, stage, clusters, root_files, estimate, overview, overview_path, error, created_at, updated_at)
+         VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7, ?8, ?9, ?10, ?11, ?12)",
+        rusqlite::params![
+            run.id,
+            run.project_id,
+            run.depth,
+            run.stage,
+            clusters,
+            root_files,
+            estimate,
+            run.overview,
+            run.overview_path,
+            run.error,
+            run.created_at,
+            run.updated_at
+        ],
+    )
+    .map_err(|e| format!("Failed to save bootstrap run: {}", e))?;
+    Ok(())
+}
+
+/// The project's most recent bootstrap run.
+pub fn latest_run(db: &Connection, project_id: &str) -> Result<Option<BootstrapRun>, String> {
+    db.query_row(
+        "SELECT id, project_id, depth, stage, clusters, root_files, estimate, overview, overview_path, error, created_at, updated_at
+         FROM doc_bootstraps WHERE project_id = ?1 ORDER BY created_at DESC LIMIT 1",
+        [project_id],
+        |row| {
+            let clusters: String = row.get(4)?;
+            let root_files: String = row.get(5)?;
+            let estimate: String = row.get(6)?;
+            Ok(BootstrapRun {
+                id: row.get(0)?,
+                project_id: row.get(1)?,
+                depth: row.get(2)?,
+                stage: row.get(3)?,
+                clusters: serde_json::from_str(&clusters).unwrap_or_default(),
+                root_files: serde_json::from_str(&root_files).unwrap_or_default(),
+                estimate: serde_json::from_str(&estimate).unwrap_or_default(),
+                overview: row.get(7)?,
+                overview_path: row.get(8)?,
+                error: row.get(9)?,
+                created_at: row.get(10)?,
+                updated_at: row.get(11)?,
+            })
+        },
+    )
+    .optional()
+    .map_err(|e| format!("Failed to load bootstrap run: {}", e))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn paths(list: &[&str]) -> Vec<String> {
+        list.iter().map(|s| s.to_string()).collect()
+    }
+
+    #[test]
+    fn test_cluster_files_folds_small_directories() {
+        let files = paths(&[
+            "main.rs",
+            "src/api/users.rs",
+            "src/api/orders.rs",
+            "src/api/v2/hooks.rs",
+            "src/db/pool.rs",
+            "src/db/migrate.rs",
+            "src/util.rs",
+            "scripts/deploy.py",
+        ]);
+        let (clusters, root) = cluster_files(&files, 2);
+        let dirs: Vec<&str> = clusters.iter().map(|c| c.dir.as_str()).collect();
+        // src/api/v2 is cut to depth 2; one-file "src" and "scripts" fold into the root
+        assert_eq!(dirs, vec!["src/api", "src/db"]);
+        assert_eq!(clusters[0].files, paths(&["src/api/orders.rs", "src/api/users.rs", "src/api/v2/hooks.rs"]));
+        assert_eq!(root, paths(&["main.rs", "scripts/deploy.py", "src/util.rs"]));
+
+        // A one-file subdirectory folds into its parent cluster
+        let (clusters, _) = cluster_files(&paths(&["lib/a.rs", "lib/b.rs", "lib/x/c.rs"]), 2);
+        assert_eq!(clusters.len(), 1);
+        assert_eq!(clusters[0].files, paths(&["lib/a.rs", "lib/b.rs", "lib/x/c.rs"]));
+    }
+
+    #[test]
+    fn test_estimate_without_ai_is_free() {
+        let clusters = vec![BootstrapCluster {
+            dir: "src".to_string(),
+            files: paths(&["src/a.rs", "src/b.rs"]),
+            prompt_tokens: 1_000,
+            ..Default::default()
+        }];
+        let free = estimate(&clusters, 1, false);
+        assert_eq!((free.files, free.ai_calls, free.input_tokens), (3, 0, 0));
+        assert_eq!(free.estimated_usd, 0.0);
+
+        let paid = estimate(&clusters, 1, true);
+        assert_eq!(paid.ai_calls, 2);
+        assert_eq!(paid.output_tokens, SUMMARY_OUTPUT_TOKENS + OVERVIEW_OUTPUT_TOKENS);
+        assert!(paid.input_tokens > 1_000 && paid.estimated_usd > 0.0);
+    }
+}
@@ -0,0 +1,210 @@
+//! @module core/claude_backup
+//! @description Timestamped snapshots of a project's .claude directory and restore
+//!
+//! PURPOSE:
+//! - Copy .claude/ into .jumpstart/backups/<id>/ before Jumpstart writes into it
+//! - List a project's backups and restore one over the current .claude/
+//! - Keep only the newest MAX_BACKUPS snapshots per project
+//!
+//! DEPENDENCIES:
+//! - chrono - Backup ids and timestamps
+//! - uuid - Id suffix so two snapshots in the same millisecond do not collide
+//! - models::claude_backup - ClaudeDirBackup
+//!
+//! EXPORTS:
+//! - BACKUPS_DIR - Project-relative directory holding the backups
+//! - MAX_BACKUPS - Snapshots kept per project
+//! - snapshot - Back up .claude/ (None when there is nothing to back up)
+//! - list_backups - A project's backups, newest first
+//! - find_backup - One backup by id
+//! - restore - Replace .claude/ with a backup (snapshotting the current state first)
+//!
+//! PATTERNS:
+//! - Layout: .jumpstart/backups/<id>/manifest.json + .jumpstart/backups/<id>/claude/...
+//! - .jumpstart/backups/.gitignore ignores everything so backups are never committed
+//! - Callers snapshot before their first write and abort the write when the snapshot fails
+//!
+//! CLAUDE NOTES:
+//! - Symlinks inside .claude are skipped (not followed) when copying
+//! - Restoring removes files created after the backup; the pre-restore snapshot keeps them
+
+use std::fs;
+use std::path::{Path, PathBuf};
+
+use chrono::Utc;
+
+use crate::models::claude_backup::ClaudeDirBackup;
+
+/// Project-relative directory holding the backups.
+pub const BACKUPS_DIR: &str = ".jumpstart/backups";
+
+/// Snapshots kept per project; older ones are deleted after each snapshot.
+pub const MAX_BACKUPS: usize = 20;
+
+const MANIFEST_FILE: &str = "manifest.json";
+const CONTENT_DIR: &str = "claude";
+
+fn backups_root(project_path: &str) -> PathBuf {
+    Path::new(project_path).join(BACKUPS_DIR)
+}
+
+fn is_valid_id(id: &str) -> bool {
+    !id.is_empty() && id.chars().all(|c| c.is_ascii_alphanumeric() || c == '-')
+}
+
+/// Copy `src` into `dst` recursively. Returns (files, bytes) copied.
+fn copy_dir(src: &Path, dst: &Path) -> Result<(u32, u64), String> {
+    fs::create_dir_all(dst).map_err(|e| format!("Failed to create {}: {}", dst.display(), e))?;
+    let entries = fs::read_dir(src).map_err(|e| format!("Failed to read {}: {}", src.display(), e))?;
+    let (mut files, mut bytes) = (0, 0);
+    for entry in entries.flatten() {
+        let Ok(file_type) = entry.file_type() else { continue };
+        let target = dst.join(entry.file_name());
+        if file_type.is_dir() {
+            let (f, b) = copy_dir(&entry.path(), &target)?;
+            files += f;
+            bytes += b;
+        } else if file_type.is_file() {
+            bytes += fs::copy(entry.path(), &target)
+                .map_err(|e| format!("Failed to copy {}: {}", entry.path().display(), e))?;
+            files += 1;
+        }
+    }
+    Ok((files, bytes))
+}
+
+/// Back up the project's .claude directory. Returns None when it does not exist or is empty.
+pub fn snapshot(project_path: &str, reason: &str) -> Result<Option<ClaudeDirBackup>, String> {
+    let claude_dir = Path::new(project_path).join(".claude");
+    let has_content = fs::read_dir(&claude_dir).map(|mut e| e.next().is_some()).unwrap_or(false);
+    if !has_content {
+        return Ok(None);
+    }
+
+    let root = backups_root(project_path);
+    fs::create_dir_all(&root).map_err(|e| format!("Failed to create {}: {}", root.display(), e))?;
+    let gitignore = root.join(".gitignore");
+    if !gitignore.exists() {
+        let _ = fs::write(&gitignore, "*\n");
+    }
+
+    let now = Utc::now();
+    let suffix = uuid::Uuid::new_v4().simple().to_string();
+    let id = format!("{}-{}", now.format("%Y%m%d-%H%M%S-%3f"), &suffix[..6]);
+    let dir = root.join(&id);
+    let (file_count, total_bytes) = match copy_dir(&claude_dir, &dir.join(CONTENT_DIR)) {
+        Ok(counts) => counts,
+        Err(e) => {
+            let _ = fs::remove_dir_all(&dir);
+            return Err(format!("Failed to back up .claude: {}", e));
+        }
+    };
+
+    let backup = ClaudeDirBackup {
+        id,
+        reason: reason.to_string(),
+        created_at: now.to_rfc3339(),
+        file_count,
+        total_bytes,
+    };
+    let manifest = serde_json::to_string_pretty(&backup).map_err(|e| format!("Failed to serialize backup: {}", e))?;
+    fs::write(dir.join(MANIFEST_FILE), manifest).map_err(|e| format!("Failed to write backup manifest: {}", e))?;
+
+    prune(project_path);
+    Ok(Some(backup))
+}
+
+/// A project's backups, newest first. Directories without a readable manifest are ignored.
+pub fn list_backups(project_path: &str) -> Vec<ClaudeDirBackup> {
+    let Ok(entries) = fs::read_dir(backups_root(project_path)) else {
+        return Vec::new();
+    };
+    let mut backups: Vec<ClaudeDirBackup> = entries
+        .flatten()
+        .filter_map(|e| fs::read_to_string(e.path().join(MANIFEST_FILE)).ok())
+        .filter_map(|json| serde_json::from_str(&json).ok())
+        .collect();
+    backups.sort_by(|a, b| b.id.cmp(&a.id));
+    backups
+}
+
+/// One backup by id.
+pub fn find_backup(project_path: &str, id: &str) -> Option<ClaudeDirBackup> {
+    if !is_valid_id(id) {
+        return None;
+    }
+    let json = fs::read_to_string(backups_root(project_path).join(id).join(MANIFEST_FILE)).ok()?;
+    serde_json::from_str(&json).ok()
+}
+
+/// Replace .claude/ with the backup's contents. The current .claude/ is snapshotted first.
+/// Returns the restored backup.
+pub fn restore(project_path: &str, id: &str) -> Result<ClaudeDirBackup, String> {
+    let backup = find_backup(project_path, id).ok_or_else(|| format!("Backup not found: {}", id))?;
+    snapshot(project_path, &format!("Before restoring backup {}", id))?;
+
+    let claude_dir = Path::new(project_path).join(".claude");
+    if claude_dir.exists() {
+        fs::remove_dir_all(&claude_dir).map_err(|e| format!("Failed to clear .claude: {}", e))?;
+    }
+    copy_dir(&backups_root(project_path).join(id).join(CONTENT_DIR), &claude_dir)?;
+    Ok(backup)
+}
+
+/// Delete all but the newest MAX_BACKUPS backups.
+fn prune(project_path: &str) {
+    let root = backups_root(project_path);
+    for old in list_backups(project_path).iter().skip(MAX_BACKUPS) {
+        if let Err(e) = fs::remove_dir_all(root.join(&old.id)) {
+            tracing::warn!(error = %e, backup = %old.id, "Failed to prune .claude backup");
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_snapshot_and_restore() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().to_string_lossy().to_string();
+        assert!(snapshot(&path, "Before deploy").unwrap().is_none(), "no .claude yet");
+
+        fs::create_dir_all(dir.path().join(".claude/commands")).unwrap();
+        fs::write(dir.path().join(".claude/commands/review.md"), "hand-tuned").unwrap();
+        fs::write(dir.path().join(".claude/ai-exclude"), "secrets/\n").unwrap();
+        let backup = snapshot(&path, "Before deploying slash commands").unwrap().unwrap();
+        assert_eq!(backup.file_count, 2);
+        assert!(dir.path().join(BACKUPS_DIR).join(".gitignore").exists());
+
+        fs::write(dir.path().join(".claude/commands/review.md"), "overwritten").unwrap();
+        fs::write(dir.path().join(".claude/commands/new.md"), "new").unwrap();
+        std::thread::sleep(std::time::Duration::from_millis(2));
+        let restored = restore(&path, &backup.id).unwrap();
+        assert_eq!(restored.reason, "Before deploying slash commands");
+        assert_eq!(fs::read_to_string(dir.path().join(".claude/commands/review.md")).unwrap(), "hand-tuned");
+        assert!(!dir.path().join(".claude/commands/new.md").exists());
+
+        let backups = list_backups(&path);
+        assert_eq!(backups.len(), 2);
+        assert!(backups[0].reason.starts_with("Before restoring backup"));
+        assert!(restore(&path, "../etc").is_err());
+    }
+
+    #[test]
+    fn test_prune_keeps_newest() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().to_string_lossy().to_string();
+        fs::create_dir_all(dir.path().join(".claude")).unwrap();
+        fs::write(dir.path().join(".claude/settings.json"), "{}").unwrap();
+        let first = snapshot(&path, "first").unwrap().unwrap();
+        std::thread::sleep(std::time::Duration::from_millis(2));
+        for _ in 0..MAX_BACKUPS {
+            snapshot(&path, "again").unwrap();
+        }
+        let backups = list_backups(&path);
+        assert_eq!(backups.len(), MAX_BACKUPS);
+        assert!(backups.iter().all(|b| b.id != first.id));
+    }
+}
@@ -0,0 +1,360 @@
+//! @module core/command_guard
+//! @description Validation and per-project allowlisting for commands taken from PRDs and templates
+//!
+//! PURPOSE:
+//! - Split a command line into argv without a shell, rejecting shell metacharacters
+//! - Keep the program and path arguments inside the project directory
+//! - Track first-time commands as pending until the user approves them
+//! - Run validated commands in the project directory
+//!
+//! DEPENDENCIES:
+//! - rusqlite - command_approvals table
+//! - core::proc - Command execution (no shell) with timeouts and output limits
+//! - models::command_guard - CommandApproval type
+//!
+//! EXPORTS:
+//! - STATUS_PENDING / STATUS_APPROVED / STATUS_DENIED - command_approvals.status values
+//! - parse_command - Split a command line into argv, rejecting shell syntax
+//! - validate_command - parse_command plus working-directory containment
+//! - check_command - Validate and look up (or register) a command's approval
+//! - require_approved - Fail unless every command is approved for the project
+//! - run_validated - Validate and run a command in the project directory
+//! - run_validated_env - run_validated with extra environment variables and a cancel flag
+//! - list_approvals / set_approval / remove_approval - Allowlist CRUD
+//!
+//! PATTERNS:
+//! - Commands are stored normalized (argv joined by single spaces) so spacing
+//!   differences do not create duplicate approvals
+//! - Approval is checked when a loop starts (where the DB is available); the
+//!   background executor only re-runs the syntax and containment checks
+//!
+//! CLAUDE NOTES:
+//! - Commands never go through sh/cmd, so quoting is not supported; a PRD that
+//!   needs pipes or redirection should point at a script inside the project
+//! - Built-in test framework commands (core::test_runner) skip approval but are
+//!   still validated
+//! - A denied command stays denied until removed or re-approved
+
+use std::path::{Component, Path, PathBuf};
+use std::process::Command;
+use std::sync::atomic::AtomicBool;
+
+use chrono::Utc;
+use rusqlite::{Connection, OptionalExtension};
+
+use crate::core::proc::{self, ProcLimits, ProcOutput};
+use crate::models::command_guard::CommandApproval;
+
+pub const STATUS_PENDING: &str = "pending";
+pub const STATUS_APPROVED: &str = "approved";
+pub const STATUS_DENIED: &str = "denied";
+
+/// Characters that only mean something to a shell. Commands run without one,
+/// so their presence signals an attempt at chaining, substitution, or redirection.
+const SHELL_METACHARACTERS: &[char] = &[
+    ';', '|', '&', '$', '`', '<', '>', '(', ')', '{', '}', '\\', '"', '\'', '\n', '\r',
+];
+
+/// Result of looking a command up in the project's allowlist.
+#[derive(Debug, Clone, PartialEq)]
+pub enum CommandDecision {
+    Approved(Vec<String>),
+    Pending,
+    Denied,
+}
+
+/// Split a command line on whitespace. Rejects empty commands and shell syntax.
+pub fn parse_command(command: &str) -> Result<Vec<String>, String> {
+    if let Some(c) = command.chars().find(|c| SHELL_METACHARACTERS.contains(c)) {
+        return Err(format!(
+            "Command `{}` contains shell metacharacter '{}'; commands run without a shell",
+            command.trim(),
+            c.escape_default()
+        ));
+    }
+    let argv: Vec<String> = command.split_whitespace().map(String::from).collect();
+    if argv.is_empty() {
+        return Err("Empty command".to_string());
+    }
+    Ok(argv)
+}
+
+/// Parse a command and check that the program and any path-like arguments
+/// stay inside the project directory.
+pub fn validate_command(project_path: &str, command: &str) -> Result<Vec<String>, String> {
+    let argv = parse_command(command)?;
+    let root = Path::new(project_path)
+        .canonicalize()
+        .map_err(|e| format!("Invalid project directory {}: {}", project_path, e))?;
+
+    for (i, arg) in argv.iter().enumerate() {
+        // --flag=value: only the value can be a path
+        let candidate = match arg.split_once('=') {
+            Some((flag, value)) if flag.starts_with('-') => value,
+            _ => arg.as_str(),
+        };
+        let is_path = candidate.contains('/') || candidate == ".." || (i == 0 && candidate.starts_with('.'));
+        if is_path && !resolve(&root, candidate).starts_with(&root) {
+            return Err(format!("`{}` points outside the project directory", arg));
+        }
+    }
+    Ok(argv)
+}
+
+/// Resolve a path against the project root: symlinks are followed when the
+/// path exists, otherwise `..` components are removed lexically.
+fn resolve(root: &Path, candidate: &str) -> PathBuf {
+    let joined = root.join(candidate);
+    if let Ok(real) = joined.canonicalize() {
+        return real;
+    }
+    let mut out = PathBuf::new();
+    for component in joined.components() {
+        match component {
+            Component::ParentDir => {
+                out.pop();
+            }
+            Component::CurDir => {}
+            other => out.push(other),
+        }
+    }
+    out
+}
+
+/// Validate a command and look up its approval. Unknown commands are recorded
+/// as pending (with `source`) so the user can approve them.
+pub fn check_command(
+    db: &Connection,
+    project_id: &str,
+    project_path: &str,
+    command: &str,
+    source: &str,
+) -> Result<CommandDecision, String> {
+    let argv = validate_command(project_path, command)?;
+    let normalized = argv.join(" ");
+
+    let status: Option<String> = db
+        .query_row(
+            "SELECT status FROM command_approvals WHERE project_id = ?1 AND command = ?2",
+            rusqlite::params![project_id, normalized],
+            |row| row.get(0),
+        )
+        .optional()
+        .map_err(|e| format!("Failed to query command approvals: {}", e))?;
+
+    match status.as_deref() {
+        Some(STATUS_APPROVED) => Ok(CommandDecision::Approved(argv)),
+        Some(STATUS_DENIED) => Ok(CommandDecision::Denied),
+        Some(_) => Ok(CommandDecision::Pending),
+        None => {
+            db.execute(
+                "INSERT INTO command_approvals (id, project_id, command, status, source, requested_at)
+                 VALUES (?1, ?2, ?3, ?4, ?5, ?6)",
+                rusqlite::params![
+                    uuid::Uuid::new_v4().to_string(),
+                    project_id,
+                    normalized,
+                    STATUS_PENDING,
+                    source,
+                    Utc::now().to_rfc3339(),
+                ],
+            )
+            .map_err(|e| format!("Failed to record command approval: {}", e))?;
+            Ok(CommandDecision::Pending)
+        }
+    }
+}
+
+/// Check every command; fails with the full list of commands still awaiting
+/// approval (all of them are registered, not just the first).
+pub fn require_approved(
+    db: &Connection,
+    project_id: &str,
+    project_path: &str,
+    commands: &[String],
+    source: &str,
+) -> Result<(), String> {
+    let mut pending = Vec::new();
+    for command in commands.iter().filter(|c| !c.trim().is_empty()) {
+        match check_command(db, project_id, project_path, command, source)? {
+            CommandDecision::Approved(_) => {}
+            CommandDecision::Pending => pending.push(format!("`{}`", command.trim())),
+            CommandDecision::Denied => {
+                return Err(format!("Command `{}` was denied for this project", command.trim()))
+            }
+        }
+    }
+    if pending.is_empty() {
+        Ok(())
+    } else {
+        Err(format!("Commands need approval before they can run: {}", pending.join(", ")))
+    }
+}
+
+/// Validate a command and run it (without a shell) in the project directory.
+pub fn run_validated(project_path: &str, command: &str, limits: ProcLimits) -> Result<ProcOutput, String> {
+    run_validated_env(project_path, command, &[], limits, &AtomicBool::new(false))
+}
+
+/// run_validated with extra environment variables (added to the inherited environment)
+/// and a cancel flag that kills the process when set.
+pub fn run_validated_env(
+    project_path: &str,
+    command: &str,
+    env: &[(String, String)],
+    limits: ProcLimits,
+    cancel: &AtomicBool,
+) -> Result<ProcOutput, String> {
+    let argv = validate_command(project_path, command)?;
+    proc::run_cancellable(
+        Command::new(&argv[0])
+            .args(&argv[1..])
+            .current_dir(project_path)
+            .envs(env.iter().map(|(k, v)| (k, v))),
+        limits,
+        cancel,
+    )
+    .map_err(|e| format!("Failed to run `{}`: {}", command.trim(), e))
+}
+
+/// All commands recorded for a project, pending first, then by command.
+pub fn list_approvals(db: &Connection, project_id: &str) -> Result<Vec<CommandApproval>, String> {
+    let mut stmt = db
+        .prepare(
+            "SELECT id, project_id, command, status, source, requested_at, decided_at
+             FROM command_approvals WHERE project_id = ?1
+             ORDER BY CASE status WHEN 'pending' THEN 0 ELSE 1 END, command",
+        )
+        .map_err(|e| format!("Failed to query command approvals: {}", e))?;
+
+    let approvals = stmt
+        .query_map([project_id], |row| {
+            Ok(CommandApproval {
+                id: row.get(0)?,
+                project_id: row.get(1)?,
+                command: row.get(2)?,
+                status: row.get(3)?,
+                source: row.get(4)?,
+                requested_at: row.get(5)?,
+                decided_at: row.get(6)?,
+            })
+        })
+        .map_err(|e| format!("Failed to read command approvals: {}", e))?
+        .filter_map(|r| r.ok())
+        .collect();
+
+    Ok(approvals)
+}
+
+/// Approve or deny a command, adding it to the allowlist if it was never seen.
+pub fn set_approval(
+    db: &Connection,
+    project_id: &str,
+    project_path: &str,
+    command: &str,
+    approved: bool,
+) -> Result<(), String> {
+    let normalized = validate_command(project_path, command)?.join(" ");
+    let status = if approved { STATUS_APPROVED } else { STATUS_DENIED };
+    let now = Utc::now().to_rfc3339();
+    db.execute(
+        "INSERT INTO command_approvals (id, project_id, command, status, source, requested_at, decided_at)
+         VALUES (?1, ?2, ?3, ?4, 'user', ?5, ?5)
+         ON CONFLICT(project_id, command) DO UPDATE SET status = excluded.status, decided_at = excluded.decided_at",
+        rusqlite::params![uuid::Uuid::new_v4().to_string(), project_id, normalized, status, now],
+    )
+    .map_err(|e| format!("Failed to save command approval: {}", e))?;
+    Ok(())
+}
+
+/// Forget a command; it will need approval again the next time it is used.
+pub fn remove_approval(db: &Connection, project_id: &str, command: &str) -> Result<bool, String> {
+    let normalized = command.split_whitespace().collect::<Vec<_>>().join(" ");
+    let removed = db
+        .execute(
+            "DELETE FROM command_approvals WHERE project_id = ?1 AND command = ?2",
+            rusqlite::params![project_id, normalized],
+        )
+        .map_err(|e| format!("Failed to remove command approval: {}", e))?;
+    Ok(removed > 0)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_support::with_project;
+
+    #[test]
+    fn test_parse_command_rejects_shell_syntax() {
+        assert_eq!(parse_command("pnpm  test --run").unwrap(), vec!["pnpm", "test", "--run"]);
+        for bad in [
+            "pnpm test; rm -rf /",
+            "cargo test && curl x",
+            "echo $(whoami)",
+            "cat `id`",
+            "npm test > out.txt",
+            "pytest 'a b'",
+            "pnpm test\nrm -rf .",
+        ] {
+            assert!(parse_command(bad).is_err(), "{} should be rejected", bad);
+        }
+        assert!(parse_command("   ").is_err());
+    }
+
+    #[test]
+    fn test_validate_command_containment() {
+        let dir = tempfile::tempdir().unwrap();
+        std::fs::create_dir(dir.path().join("scripts")).unwrap();
+        let root = dir.path().to_str().unwrap();
+
+        assert!(validate_command(root, "go test ./...").is_ok());
+        assert!(validate_command(root, "./scripts/check.sh --out=reports/x.json").is_ok());
+        assert!(validate_command(root, "pnpm vitest run --reporter=json").is_ok());
+
+        assert!(validate_command(root, "/bin/rm -rf src").is_err());
+        assert!(validate_command(root, "cat ../secrets.txt").is_err());
+        assert!(validate_command(root, "pytest --rootdir=/etc").is_err());
+        assert!(validate_command(root, "./scripts/../../evil.sh").is_err());
+        assert!(validate_command(root, "ls ..").is_err());
+    }
+
+    #[test]
+    fn test_first_time_command_needs_approval() {
+        let db = with_project();
+        let dir = tempfile::tempdir().unwrap();
+        let root = dir.path().to_str().unwrap();
+        let commands = vec!["pnpm   test".to_string(), "pnpm tsc --noEmit".to_string()];
+
+        let err = require_approved(&db, "p1", root, &commands, "prd").unwrap_err();
+        assert!(err.contains("`pnpm   test`") && err.contains("`pnpm tsc --noEmit`"));
+
+        let approvals = list_approvals(&db, "p1").unwrap();
+        assert_eq!(approvals.len(), 2);
+        assert!(approvals.iter().all(|a| a.status == STATUS_PENDING && a.source == "prd"));
+        assert!(approvals.iter().any(|a| a.command == "pnpm test"));
+
+        set_approval(&db, "p1", root, "pnpm test", true).unwrap();
+        set_approval(&db, "p1", root, "pnpm tsc --noEmit", true).unwrap();
+        require_approved(&db, "p1", root, &commands, "prd").unwrap();
+        assert_eq!(
+            check_command(&db, "p1", root, "pnpm test", "prd").unwrap(),
+            CommandDecision::Approved(vec!["pnpm".to_string(), "test".to_string()])
+        );
+
+        set_approval(&db, "p1", root, "pnpm test", false).unwrap();
+        assert!(require_approved(&db, "p1", root, &commands, "prd").unwrap_err().contains("denied"));
+
+        assert!(remove_approval(&db, "p1", "pnpm  test").unwrap());
+        assert_eq!(check_command(&db, "p1", root, "pnpm test", "prd").unwrap(), CommandDecision::Pending);
+    }
+
+    #[test]
+    fn test_invalid_command_is_not_recorded() {
+        let db = with_project();
+        let dir = tempfile::tempdir().unwrap();
+        let root = dir.path().to_str().unwrap();
+        let commands = vec!["pnpm test | tee log".to_string()];
+        assert!(require_approved(&db, "p1", root, &commands, "prd").unwrap_err().contains("metacharacter"));
+        assert!(list_approvals(&db, "p1").unwrap().is_empty());
+    }
+}
@@ -0,0 +1,504 @@
+//! @module core/control
+//! @description Local control channel letting the jumpstart-cli companion drive the running app
+//!
+//! PURPOSE:
+//! - Listen on 127.0.0.1 and publish the port and a per-session token in control.json
+//! - Serve list_ralph_loops, start_ralph_loop, and follow_ralph_loop for CLI clients
+//! - Serve get_module_doc, search_module_docs, and get_project_conventions for the
+//!   jumpstart-mcp server
+//! - Stream a loop's iteration and activity events to followers until it stops running
+//!
+//! DEPENDENCIES:
+//! - std::net - TcpListener bound to loopback
+//! - tauri - AppHandle for AppState and the RALPH commands
+//! - commands::ralph - analyze_ralph_prompt, start_ralph_loop, list_ralph_loops
+//! - commands::project - load_project for the stack in get_project_conventions
+//! - core::analyzer - Doc header parsing for get_module_doc
+//! - core::doc_index - Ranked module search for search_module_docs
+//! - core::readme - CLAUDE.md sections for get_project_conventions
+//! - core::conventions - Which CLAUDE.md sections count as conventions
+//! - core::ai - AI exclude globs (excluded files are never served)
+//! - core::events - ControlSubscriber forwards bus events to followers
+//! - models::control - ControlEndpoint, ControlRequest
+//!
+//! EXPORTS:
+//! - CONTROL_FILE - Endpoint file name in ~/.project-jumpstart
+//! - start - Bind the listener, write control.json, and register the follower subscriber
+//! - resolve_project - Project id for an id, name, or path (a path inside a project matches it)
+//! - is_follow_event - Whether a bus event belongs to the followed loop
+//! - project_relative - Project-relative path for a path given by a client, if inside the project
+//! - module_doc - Parsed doc header of one project file
+//! - project_conventions - Stack and convention sections of CLAUDE.md for a project
+//! - ControlSubscriber - Event bus subscriber feeding follow_ralph_loop connections
+//!
+//! PATTERNS:
+//! - One request per connection, one thread per connection; every request must carry the token
+//! - Commands run through the same #[tauri::command] functions the UI calls, via block_on
+//!
+//! CLAUDE NOTES:
+//! - Loopback only; the token keeps other local users and browsers out (control.json is 0600)
+//! - A follower ends when the loop leaves "running" (completed, failed, paused, needs_review)
+//!   or when the client disconnects
+//! - Activity events are matched by project, so a second loop in the same project can
+//!   interleave its activity lines
+//! - Module doc commands refuse paths outside the project and files matched by the AI
+//!   exclude list, since their output goes straight into a Claude Code session
+
+use std::fs;
+use std::io::{BufRead, BufReader, Read, Write};
+use std::net::{TcpListener, TcpStream};
+use std::path::{Component, Path, PathBuf};
+use std::sync::mpsc::{self, RecvTimeoutError, Sender};
+use std::sync::{Mutex, OnceLock};
+use std::time::Duration;
+
+use rusqlite::Connection;
+use serde_json::{json, Value};
+use tauri::{AppHandle, Manager};
+
+use crate::commands::{project, ralph};
+use crate::core::events::{self, AppEvent, Subscriber};
+use crate::core::{ai, analyzer, conventions, doc_index, readme};
+use crate::db::AppState;
+use crate::models::activity::ActivityType;
+use crate::models::control::{ControlEndpoint, ControlRequest};
+use crate::models::project::Project;
+
+/// Endpoint file in ~/.project-jumpstart.
+pub const CONTROL_FILE: &str = "control.json";
+
+/// How often a follower re-checks the loop's status when no events arrive.
+const FOLLOW_POLL: Duration = Duration::from_secs(2);
+
+/// Largest request line accepted.
+const MAX_REQUEST_BYTES: u64 = 256 * 1024;
+
+/// search_module_docs results when the client gives no limit, and the most it may ask for.
+const DEFAULT_SEARCH_RESULTS: usize = 10;
+const MAX_SEARCH_RESULTS: usize = 50;
+
+fn followers() -> &'static Mutex<Vec<Sender<AppEvent>>> {
+    static FOLLOWERS: OnceLock<Mutex<Vec<Sender<AppEvent>>>> = OnceLock::new();
+    FOLLOWERS.get_or_init(|| Mutex::new(Vec::new()))
+}
+
+/// Forwards every bus event to connected followers, dropping disconnected ones.
+pub struct ControlSubscriber;
+
+impl Subscriber for ControlSubscriber {
+    fn handle(&self, _db: &Connection, event: &AppEvent) {
+        if let Ok(mut senders) = followers().lock() {
+            senders.retain(|tx| tx.send(event.clone()).is_ok());
+        }
+    }
+}
+
+fn write_endpoint(path: &Path, endpoint: &ControlEndpoint) -> Result<(), String> {
+    let json = serde_json::to_string_pretty(endpoint).map_err(|e| format!("Failed to serialize endpoint: {}", e))?;
+
+    #[cfg(unix)]
+    {
+        use std::fs::OpenOptions;
+        use std::os::unix::fs::OpenOptionsExt;
+
+        let mut file = OpenOptions::new()
+            .create(true)
+            .write(true)
+            .truncate(true)
+            .mode(0o600)
+            .open(path)
+            .map_err(|e| format!("Failed to create {}: {}", CONTROL_FILE, e))?;
+        file.write_all(json.as_bytes())
+            .map_err(|e| format!("Failed to write {}: {}", CONTROL_FILE, e))?;
+    }
+
+    #[cfg(not(unix))]
+    {
+        fs::write(path, json).map_err(|e| format!("Failed to write {}: {}", CONTROL_FILE, e))?;
+    }
+    Ok(())
+}
+
+/// Bind the control listener, write control.json, and start accepting CLI connections.
+pub fn start(app: &AppHandle) -> Result<(), String> {
+    let dir: PathBuf = dirs::home_dir()
+        .ok_or("Could not determine home directory")?
+        .join(".project-jumpstart");
+    fs::create_dir_all(&dir).map_err(|e| format!("Failed to create data directory: {}", e))?;
+
+    let listener = TcpListener::bind("127.0.0.1:0").map_err(|e| format!("Failed to bind control port: {}", e))?;
+    let port = listener
+        .local_addr()
+        .map_err(|e| format!("Failed to read control port: {}", e))?
+        .port();
+    let token = uuid::Uuid::new_v4().simple().to_string();
+    write_endpoint(
+        &dir.join(CONTROL_FILE),
+        &ControlEndpoint {
+            port,
+            token: token.clone(),
+            pid: std::process::id(),
+        },
+    )?;
+
+    events::subscribe(Box::new(ControlSubscriber));
+
+    let app = app.clone();
+    std::thread::spawn(move || {
+        for stream in listener.incoming().flatten() {
+            let app = app.clone();
+            let token = token.clone();
+            std::thread::spawn(move || handle_connection(&app, &token, stream));
+        }
+    });
+    tracing::info!(port, "Control channel listening");
+    Ok(())
+}
+
+fn send_line(stream: &mut TcpStream, value: &Value) -> bool {
+    writeln!(stream, "{}", value).and_then(|_| stream.flush()).is_ok()
+}
+
+fn handle_connection(app: &AppHandle, token: &str, mut stream: TcpStream) {
+    let mut line = String::new();
+    let read = stream
+        .try_clone()
+        .map(|s| BufReader::new(s).take(MAX_REQUEST_BYTES).read_line(&mut line));
+    if !matches!(read, Ok(Ok(n)) if n > 0) {
+        return;
+    }
+
+    let request: ControlRequest = match serde_json::from_str(line.trim()) {
+        Ok(request) => request,
+        Err(e) => {
+            send_line(&mut stream, &json!({ "ok": false, "error": format!("Invalid request: {}", e) }));
+            return;
+        }
+    };
+    if request.token != token {
+        send_line(&mut stream, &json!({ "ok": false, "error": "Invalid token" }));
+        return;
+    }
+
+    if request.command == "follow_ralph_loop" {
+        match request.args.get("loopId").and_then(Value::as_str) {
+            Some(loop_id) => follow_loop(app, loop_id, &mut stream),
+            None => {
+                send_line(&mut stream, &json!({ "ok": false, "error": "loopId is required" }));
+            }
+        }
+        return;
+    }
+
+    let response = match dispatch(app, &request.command, &request.args) {
+        Ok(data) => json!({ "ok": true, "data": data }),
+        Err(e) => json!({ "ok": false, "error": e }),
+    };
+    send_line(&mut stream, &response);
+}
+
+/// Project id for `spec`: an exact id or name, else the project whose path equals or contains it
+/// (the deepest match wins).
+pub fn resolve_project(db: &Connection, spec: &str) -> Result<String, String> {
+    let spec = spec.trim().trim_end_matches('/');
+    let projects: Vec<(String, String, String)> = {
+        let mut stmt = db
+            .prepare("SELECT id, name, path FROM projects")
+            .map_err(|e| format!("Failed to query projects: {}", e))?;
+        let rows = stmt
+            .query_map([], |row| Ok((row.get(0)?, row.get(1)?, row.get(2)?)))
+            .map_err(|e| format!("Failed to read projects: {}", e))?
+            .filter_map(|r| r.ok())
+            .collect();
+        rows
+    };
+
+    if let Some((id, _, _)) = projects.iter().find(|(id, name, _)| id == spec || name == spec) {
+        return Ok(id.clone());
+    }
+    projects
+        .iter()
+        .filter(|(_, _, path)| {
+            let path = path.trim_end_matches('/');
+            spec == path || spec.starts_with(&format!("{}/", path))
+        })
+        .max_by_key(|(_, _, path)| path.len())
+        .map(|(id, _, _)| id.clone())
+        .ok_or_else(|| format!("No project matches '{}'", spec))
+}
+
+fn str_arg<'a>(args: &'a Value, key: &str) -> Result<&'a str, String> {
+    args.get(key)
+        .and_then(Value::as_str)
+        .filter(|s| !s.trim().is_empty())
+        .ok_or_else(|| format!("{} is required", key))
+}
+
+fn dispatch(app: &AppHandle, command: &str, args: &Value) -> Result<Value, String> {
+    let state = app.state::<AppState>();
+    let project_id = {
+        let db = state.db.lock().map_err(|e| format!("DB lock error: {}", e))?;
+        resolve_project(&db, str_arg(args, "project")?)?
+    };
+
+    match command {
+        "list_ralph_loops" => {
+            let loops = tauri::async_runtime::block_on(ralph::list_ralph_loops(project_id, app.state()))?;
+            serde_json::to_value(loops).map_err(|e| e.to_string())
+        }
+        "start_ralph_loop" => {
+            let prompt = str_arg(args, "prompt")?.to_string();
+            let max_deleted_percent = args.get("maxDeletedPercent").and_then(Value::as_u64).map(|p| p as u32);
+            let resume_session = args.get("resumeSession").and_then(Value::as_bool);
+            let max_duration_minutes = args.get("maxDurationMinutes").and_then(Value::as_u64).map(|m| m as u32);
+            let analysis = tauri::async_runtime::block_on(ralph::analyze_ralph_prompt(
+                prompt.clone(),
+                Some(project_id.clone()),
+                app.state(),
+            ))?;
+            let started = tauri::async_runtime::block_on(ralph::start_ralph_loop(
+                project_id,
+                prompt,
+                None,
+                analysis.quality_score,
+                max_deleted_percent,
+                resume_session,
+                max_duration_minutes,
+                app.clone(),
+                app.state(),
+            ))?;
+            serde_json::to_value(started).map_err(|e| e.to_string())
+        }
+        "get_module_doc" => {
+            let project_path = {
+                let db = state.db.lock().map_err(|e| format!("DB lock error: {}", e))?;
+                project::load_project(&db, &project_id)?.path
+            };
+            module_doc(&project_path, str_arg(args, "path")?)
+        }
+        "search_module_docs" => {
+            let query = str_arg(args, "query")?;
+            let limit = args
+                .get("limit")
+                .and_then(Value::as_u64)
+                .map_or(DEFAULT_SEARCH_RESULTS, |l| (l as usize).clamp(1, MAX_SEARCH_RESULTS));
+            let db = state.db.lock().map_err(|e| format!("DB lock error: {}", e))?;
+            let project_path = project::load_project(&db, &project_id)?.path;
+            let hits = doc_index::suggest_files(&db, &project_id, &project_path, query, limit)?;
+            Ok(Value::Array(
+                hits.into_iter()
+                    .map(|hit| {
+                        let description = doc_index::indexed_description(&db, &project_id, &hit.path);
+                        json!({ "path": hit.path, "score": hit.score, "reasons": hit.reasons, "description": description })
+                    })
+                    .collect(),
+            ))
+        }
+        "get_project_conventions" => {
+            let project = {
+                let db = state.db.lock().map_err(|e| format!("DB lock error: {}", e))?;
+                project::load_project(&db, &project_id)?
+            };
+            Ok(project_conventions(&project))
+        }
+        other => Err(format!("Unknown command: {}", other)),
+    }
+}
+
+/// Project-relative path ("/"-separated) for `path`, which is relative to the project or
+/// absolute inside it. None when it leaves the project or names the project itself.
+pub fn project_relative(project_path: &str, path: &str) -> Option<String> {
+    let path = Path::new(path.trim());
+    let rel = if path.is_absolute() {
+        path.strip_prefix(project_path).ok()?
+    } else {
+        path
+    };
+    let mut parts = Vec::new();
+    for component in rel.components() {
+        match component {
+            Component::Normal(part) => parts.push(part.to_string_lossy().to_string()),
+            Component::CurDir => {}
+            _ => return None,
+        }
+    }
+    (!parts.is_empty()).then(|| parts.join("/"))
+}
+
+/// The parsed doc header of a project file as {path, documented, doc}; doc is null for a
+/// file without a header.
+pub fn module_doc(project_path: &str, path: &str) -> Result<Value, String> {
+    let rel = project_relative(project_path, path).ok_or_else(|| format!("{} is not inside the project", path))?;
+    if ai::is_ai_excluded(&ai::load_ai_excludes(project_path), &rel) {
+        return Err(format!("{} is excluded from AI context ({})", rel, ai::AI_EXCLUDE_FILE));
+    }
+    let full = Path::new(project_path).join(&rel);
+    if !full.is_file() {
+        return Err(format!("File not found: {}", rel));
+    }
+    let doc = analyzer::parse_doc_header(&analyzer::read_source(&full.to_string_lossy())?);
+    Ok(json!({ "path": rel, "documented": doc.is_some(), "doc": doc }))
+}
+
+/// A project's stack and the convention sections of its CLAUDE.md (null when there are none).
+pub fn project_conventions(project: &Project) -> Value {
+    let claude_md = fs::read_to_string(Path::new(&project.path).join("CLAUDE.md")).ok();
+    json!({
+        "project": project.name,
+        "stack": {
+            "language": project.language,
+            "framework": project.framework,
+            "database": project.database,
+            "testing": project.testing,
+            "styling": project.styling,
+        },
+        "conventions": claude_md.as_deref().and_then(|c| readme::claude_md_sections(c, &conventions::CONVENTION_SECTIONS)),
+    })
+}
+
+/// Whether a bus event belongs to the followed loop (its iterations, or RALPH activity in its project).
+pub fn is_follow_event(event: &AppEvent, loop_id: &str, project_id: &str) -> bool {
+    match event {
+        AppEvent::LoopIterationFinished { loop_id: id, .. } => id == loop_id,
+        AppEvent::Activity {
+            project_id: pid,
+            activity_type,
+            ..
+        } => pid == project_id && *activity_type == ActivityType::Ralph,
+        _ => false,
+    }
+}
+
+fn loop_state(app: &AppHandle, loop_id: &str) -> Result<(String, String, Option<String>), String> {
+    let state = app.state::<AppState>();
+    let db = state.db.lock().map_err(|e| format!("DB lock error: {}", e))?;
+    db.query_row(
+        "SELECT project_id, status, outcome FROM ralph_loops WHERE id = ?1",
+        [loop_id],
+        |row| Ok((row.get(0)?, row.get(1)?, row.get(2)?)),
+    )
+    .map_err(|_| format!("Loop not found: {}", loop_id))
+}
+
+/// Stream the loop's events until it stops running or the client goes away.
+fn follow_loop(app: &AppHandle, loop_id: &str, stream: &mut TcpStream) {
+    let (tx, rx) = mpsc::channel();
+    if let Ok(mut senders) = followers().lock() {
+        senders.push(tx);
+    }
+
+    let (project_id, status, _) = match loop_state(app, loop_id) {
+        Ok(state) => state,
+        Err(e) => {
+            send_line(stream, &json!({ "ok": false, "error": e }));
+            return;
+        }
+    };
+    if !send_line(stream, &json!({ "ok": true, "data": { "loopId": loop_id, "status": status } })) {
+        return;
+    }
+
+    loop {
+        match rx.recv_timeout(FOLLOW_POLL) {
+            Ok(event) => {
+                if is_follow_event(&event, loop_id, &project_id) && !send_line(stream, &json!({ "event": event })) {
+                    return;
+                }
+            }
+            Err(RecvTimeoutError::Timeout) => {}
+            Err(RecvTimeoutError::Disconnected) => return,
+        }
+        match loop_state(app, loop_id) {
+            Ok((_, status, outcome)) if status != "running" && status != "idle" => {
+                send_line(stream, &json!({ "done": true, "status": status, "outcome": outcome }));
+                return;
+            }
+            Ok(_) => {}
+            Err(e) => {
+                send_line(stream, &json!({ "done": true, "error": e }));
+                return;
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_support::memory_db;
+
+    #[test]
+    fn test_resolve_project() {
+        let db = memory_db();
+        db.execute_batch(
+            "INSERT INTO projects (id, name, path, created_at) VALUES ('p1', 'web', '/work/web', '2025-01-01T00:00:00Z');
+             INSERT INTO projects (id, name, path, created_at) VALUES ('p2', 'api', '/work/web/api/', '2025-01-01T00:00:00Z');",
+        )
+        .unwrap();
+
+        assert_eq!(resolve_project(&db, "p2").unwrap(), "p2");
+        assert_eq!(resolve_project(&db, "web").unwrap(), "p1");
+        assert_eq!(resolve_project(&db, "/work/web/src").unwrap(), "p1");
+        assert_eq!(resolve_project(&db, "/work/web/api/src/").unwrap(), "p2");
+        assert!(resolve_project(&db, "/work/webapp").is_err());
+    }
+
+    #[test]
+    fn test_is_follow_event() {
+        let iteration = AppEvent::LoopIterationFinished {
+            project_id: "p1".into(),
+            loop_id: "l1".into(),
+            iteration: 1,
+            story_index: None,
+            status: "passed".into(),
+            issues_count: 0,
+            files_changed: 2,
+            failure_type: None,
+        };
+        assert!(is_follow_event(&iteration, "l1", "p1"));
+        assert!(!is_follow_event(&iteration, "l2", "p1"));
+        assert!(is_follow_event(&AppEvent::activity("p1", ActivityType::Ralph, "Loop completed"), "l1", "p1"));
+        assert!(!is_follow_event(&AppEvent::activity("p1", ActivityType::Test, "Tests ran"), "l1", "p1"));
+        assert!(!is_follow_event(&AppEvent::activity("p2", ActivityType::Ralph, "Loop completed"), "l1", "p1"));
+    }
+
+    #[test]
+    fn test_project_relative() {
+        assert_eq!(project_relative("/work/web", "src/app.ts").as_deref(), Some("src/app.ts"));
+        assert_eq!(project_relative("/work/web/", "/work/web/src/app.ts").as_deref(), Some("src/app.ts"));
+        assert_eq!(project_relative("/work/web", "./src/./app.ts").as_deref(), Some("src/app.ts"));
+        assert_eq!(project_relative("/work/web", "../api/main.rs"), None);
+        assert_eq!(project_relative("/work/web", "/work/webapp/main.rs"), None);
+        assert_eq!(project_relative("/work/web", "/work/web"), None);
+    }
+
+    #[test]
+    fn test_module_doc() {
+        let dir = tempfile::tempdir().unwrap();
+        let root = dir.path().to_string_lossy().to_string();
+        fs::create_dir_all(dir.path().join("src")).unwrap();
+        fs::create_dir_all(dir.path().join(".claude")).unwrap();
+        fs::write(
+            dir.path().join("src/math.rs"),
+            "//! @module core/math\n//! @description Math helpers\n//!\n//! PATTERNS:\n//! - Pure functions\n\npub fn add_one(x: u32) -> u32 { x + 1 }\n",
+        )
+        .unwrap();
+        fs::write(dir.path().join("src/plain.rs"), "pub fn two() -> u32 { 2 }\n").unwrap();
+        fs::write(dir.path().join("src/secret.rs"), "//! @module secret\n").unwrap();
+        fs::write(dir.path().join(ai::AI_EXCLUDE_FILE), "src/secret.rs\n").unwrap();
+
+        let doc = module_doc(&root, "src/math.rs").unwrap();
+        assert_eq!(doc["documented"], json!(true));
+        assert_eq!(doc["doc"]["modulePath"], json!("core/math"));
+        assert_eq!(doc["doc"]["description"], json!("Math helpers"));
+
+        let plain = module_doc(&root, &format!("{}/src/plain.rs", root)).unwrap();
+        assert_eq!(plain["path"], json!("src/plain.rs"));
+        assert_eq!(plain["documented"], json!(false));
+        assert!(plain["doc"].is_null());
+
+        assert!(module_doc(&root, "src/secret.rs").unwrap_err().contains("excluded"));
+        assert!(module_doc(&root, "src/missing.rs").is_err());
+        assert!(module_doc(&root, "../outside.rs").is_err());
+    }
+}
@@ -0,0 +1,563 @@
+//! @module core/conventions
+//! @description CLAUDE.md conventions as structured rules, stored per project and checked against the code
+//!
+//! PURPOSE:
+//! - Parse CLAUDE.md convention sections into naming, directory, and forbidden-pattern rules
+//! - Store a project's rules in the conventions table, keeping stats for unchanged rules
+//! - Scan the codebase for violations (file names, file placement, forbidden text)
+//! - Summarize violations for the health score and RALPH prompt context
+//!
+//! DEPENDENCIES:
+//! - rusqlite - conventions table
+//! - regex - Backticked tokens, negations, forbidden-text matching
+//! - uuid - Ids for new rules
+//! - core::readme - CLAUDE.md section extraction
+//! - core::ai - Project-relative glob matching for rule scopes
+//! - core::analyzer - Source file extensions and generated-file detection
+//! - core::test_runner - Test file names (skipped by forbidden-text rules)
+//! - core::safe_read - Size-capped reads of CLAUDE.md and source files
+//! - models::convention - Convention, ConventionKind, ConventionViolation
+//! - models::project - QuickWin
+//!
+//! EXPORTS:
+//! - CONVENTION_SECTIONS - CLAUDE.md "## " sections that hold conventions
+//! - parse_conventions - Rules from CLAUDE.md text (ids empty until stored)
+//! - sync_conventions - Re-parse the project's CLAUDE.md and store its rules
+//! - load_conventions - Stored rules for a project
+//! - check - Violations of the given rules, filling each rule's count and samples
+//! - record_results - Store each rule's violation count and samples
+//! - violation_totals - (violations, rules violated) from the last check
+//! - quick_win - Health quick win for outstanding violations
+//! - ralph_candidates - Rules (with violation hints) offered to RALPH as learnings
+//!
+//! PATTERNS:
+//! - Only bullet/plain lines are parsed; fenced code blocks are skipped
+//! - Forbidden: a negation (never, don't, avoid, no, must not, ...) followed by backticked code
+//!   ("Never use `console.log`"); path, glob, and extension tokens are not treated as code
+//! - Naming: a line about files naming exactly one case style ("File naming: `kebab-case.tsx`");
+//!   a backticked extension or directory narrows the scope
+//! - Directory: a backticked directory plus a backticked file glob ("Tests go in `__tests__/`
+//!   (`*.test.ts`)"); matching files outside any such directory are violations
+//! - A rule is identified by (kind, pattern, scope); re-syncing keeps the id and last counts
+//!
+//! CLAUDE NOTES:
+//! - Checks are pattern-based and cheap, so expect some false positives from free-form prose
+//! - Forbidden text skips test files and comment lines; word-like tokens match on word boundaries
+//! - index/mod/main/lib/__init__ and "_", "+", "[" prefixed stems are exempt from naming rules
+//! - At most MAX_REPORTED_PER_RULE violations per rule are returned; counts include all of them
+
+use std::collections::{HashMap, HashSet};
+use std::fs;
+use std::path::Path;
+
+use regex::Regex;
+use rusqlite::Connection;
+use uuid::Uuid;
+
+use crate::core::{ai, analyzer, readme, safe_read, test_runner};
+use crate::models::convention::{Convention, ConventionKind, ConventionViolation};
+use crate::models::project::QuickWin;
+
+/// CLAUDE.md sections that hold conventions ("## " titles, case-insensitive).
+pub const CONVENTION_SECTIONS: [&str; 7] = [
+    "Code Patterns",
+    "Conventions",
+    "Code Style",
+    "Coding Standards",
+    "Architectural Decisions",
+    "Module Documentation Format",
+    "CLAUDE NOTES",
+];
+
+/// Case styles: (name stored as the pattern, phrases that name it, stem regex).
+const CASE_STYLES: &[(&str, &[&str], &str)] = &[
+    ("kebab-case", &["kebab-case", "kebab case"], r"^[a-z0-9]+(?:-[a-z0-9]+)*$"),
+    ("snake_case", &["snake_case", "snake case"], r"^[a-z0-9]+(?:_[a-z0-9]+)*$"),
+    ("camelCase", &["camelcase", "camel case"], r"^[a-z][a-zA-Z0-9]*$"),
+    ("PascalCase", &["pascalcase", "pascal case"], r"^[A-Z][a-zA-Z0-9]*$"),
+];
+
+/// File stems that follow framework conventions rather than the project's naming rule.
+const EXEMPT_STEMS: &[&str] = &["index", "mod", "main", "lib", "__init__", "__main__", "setup", "conftest"];
+
+/// Directories never scanned (dependencies, build output, virtualenvs).
+const SKIP_DIRS: &[&str] = &[
+    "node_modules",
+    "target",
+    "dist",
+    "build",
+    "coverage",
+    "vendor",
+    "__pycache__",
+    "venv",
+];
+
+const MAX_FILES: usize = 5_000;
+const MAX_DEPTH: usize = 12;
+const MAX_REPORTED_PER_RULE: usize = 100;
+const MAX_SAMPLES: usize = 5;
+
+/// Rules from CLAUDE.md text. Ids are empty until the rules are stored.
+pub fn parse_conventions(claude_md: &str) -> Vec<Convention> {
+    let Some(body) = readme::claude_md_sections(claude_md, &CONVENTION_SECTIONS) else {
+        return Vec::new();
+    };
+    let token_re = Regex::new(r"`([^`]+)`").unwrap();
+    let negation_re = Regex::new(r"(?i)\b(?:never|don't|do not|avoid|must not|no|forbidden|not allowed)\b").unwrap();
+
+    let mut conventions: Vec<Convention> = Vec::new();
+    let mut in_fence = false;
+    for line in body.lines() {
+        let trimmed = line.trim();
+        if trimmed.starts_with("```") {
+            in_fence = !in_fence;
+            continue;
+        }
+        if in_fence || trimmed.is_empty() || trimmed.starts_with('#') || trimmed.starts_with('|') {
+            continue;
+        }
+        let rule = strip_bullet(trimmed);
+        let lower = rule.to_lowercase();
+        let tokens: Vec<(usize, &str)> = token_re
+            .captures_iter(rule)
+            .filter_map(|c| c.get(1).map(|m| (m.start(), m.as_str().trim())))
+            .collect();
+        let styles: Vec<&str> = CASE_STYLES
+            .iter()
+            .filter(|(_, phrases, _)| phrases.iter().any(|p| lower.contains(p)))
+            .map(|(name, _, _)| *name)
+            .collect();
+        let dir = tokens.iter().map(|(_, t)| *t).find(|t| is_dir_token(t)).map(clean_dir);
+
+        let mut push = |kind: ConventionKind, pattern: String, scope: Option<String>| {
+            if !conventions.iter().any(|c| c.kind == kind && c.pattern == pattern && c.scope == scope) {
+                conventions.push(Convention {
+                    id: String::new(),
+                    kind,
+                    rule: rule.to_string(),
+                    pattern,
+                    scope,
+                    violation_count: 0,
+                    sample_violations: Vec::new(),
+                    checked_at: None,
+                });
+            }
+        };
+
+        if let Some(negation) = negation_re.find(rule) {
+            for (_, token) in tokens.iter().filter(|(start, _)| *start > negation.start()) {
+                if is_code_token(token) {
+                    push(ConventionKind::Forbidden, token.to_string(), None);
+                }
+            }
+            continue;
+        }
+
+        if styles.len() == 1 && (lower.contains("file") || lower.contains("naming")) {
+            let ext = tokens.iter().find_map(|(_, t)| extension_of(t));
+            let scope = match (&dir, ext) {
+                (Some(dir), Some(ext)) => Some(format!("{}/**/*.{}", dir, ext)),
+                (Some(dir), None) => Some(dir.clone()),
+                (None, Some(ext)) => Some(format!("*.{}", ext)),
+                (None, None) => None,
+            };
+            push(ConventionKind::Naming, styles[0].to_string(), scope);
+            continue;
+        }
+
+        let glob = tokens.iter().map(|(_, t)| *t).find(|t| t.contains('*') && !t.contains('/'));
+        if let (Some(dir), Some(glob), true) = (dir, glob, styles.is_empty()) {
+            push(ConventionKind::Directory, dir, Some(glob.to_string()));
+        }
+    }
+    conventions
+}
+
+fn strip_bullet(line: &str) -> &str {
+    let line = line.trim_start_matches(['-', '*', '+']).trim_start();
+    let digits = line.len() - line.trim_start_matches(|c: char| c.is_ascii_digit()).len();
+    if digits > 0 && line[digits..].starts_with(['.', ')']) {
+        line[digits + 1..].trim_start()
+    } else {
+        line
+    }
+}
+
+/// "src/components/", "__tests__/", "app/models" (a path without a file extension).
+fn is_dir_token(token: &str) -> bool {
+    !token.contains('*')
+        && !token.contains(' ')
+        && (token.ends_with('/') || (token.contains('/') && extension_of(token).is_none()))
+}
+
+fn clean_dir(token: &str) -> String {
+    token.trim_start_matches("./").trim_matches('/').to_string()
+}
+
+/// "tsx" from "kebab-case.tsx", "*.tsx", or ".tsx".
+fn extension_of(token: &str) -> Option<String> {
+    let name = token.rsplit('/').next().unwrap_or(token);
+    let (_, ext) = name.rsplit_once('.')?;
+    (!ext.is_empty() && ext.len() <= 5 && ext.chars().all(|c| c.is_ascii_alphanumeric())).then(|| ext.to_lowercase())
+}
+
+/// Backticked text that reads as code rather than a path, glob, extension, or case style.
+fn is_code_token(token: &str) -> bool {
+    let len = token.chars().count();
+    let extension = token.starts_with('.') && token[1..].chars().all(|c| c.is_ascii_alphanumeric());
+    (2..=60).contains(&len)
+        && !extension
+        && !token.contains('/')
+        && !token.contains('*')
+        && !CASE_STYLES.iter().any(|(_, phrases, _)| phrases.contains(&token.to_lowercase().as_str()))
+}
+
+/// Re-parse the project's CLAUDE.md and store its rules. Rules that still exist keep their
+/// id and last check result; rules no longer in CLAUDE.md are removed.
+pub fn sync_conventions(db: &Connection, project_id: &str, project_path: &str) -> Result<Vec<Convention>, String> {
+    let claude_md = safe_read::read_text(Path::new(project_path).join("CLAUDE.md")).unwrap_or_default();
+    let existing = load_conventions(db, project_id)?;
+    let now = chrono::Utc::now().to_rfc3339();
+
+    let conventions: Vec<Convention> = parse_conventions(&claude_md)
+        .into_iter()
+        .map(|parsed| {
+            match existing
+                .iter()
+                .find(|e| e.kind == parsed.kind && e.pattern == parsed.pattern && e.scope == parsed.scope)
+            {
+                Some(previous) => Convention { rule: parsed.rule, ..previous.clone() },
+                None => Convention { id: Uuid::new_v4().to_string(), ..parsed },
+            }
+        })
+        .collect();
+
+    db.execute("DELETE FROM conventions WHERE project_id = ?1", [project_id])
+        .map_err(|e| format!("Failed to clear conventions: {}", e))?;
+    for c in &conventions {
+        db.execute(
+            "INSERT INTO conventions (id, project_id, kind, rule, pattern, scope, violation_count, sample_violations, checked_at, created_at)
+             VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7, ?8, ?9, ?10)",
+            rusqlite::params![
+                c.id,
+                project_id,
+                c.kind.as_str(),
+                c.rule,
+                c.pattern,
+                c.scope,
+                c.violation_count,
+                serde_json::to_string(&c.sample_violations).unwrap_or_else(|_| "[]".to_string()),
+                c.checked_at,
+                now,
+            ],
+        )
+        .map_err(|e| format!("Failed to save convention: {}", e))?;
+    }
+    Ok(conventions)
+}
+
+/// Stored rules for a project, in CLAUDE.md order.
+pub fn load_conventions(db: &Connection, project_id: &str) -> Result<Vec<Convention>, String> {
+    let mut stmt = db
+        .prepare(
+            "SELECT id, kind, rule, pattern, scope, violation_count, sample_violations, checked_at
+             FROM conventions WHERE project_id = ?1 ORDER BY rowid",
+        )
+        .map_err(|e| format!("Query prepare error: {}", e))?;
+    let rows = stmt
+        .query_map([project_id], |row| {
+            let kind: String = row.get(1)?;
+            let samples: String = row.get(6)?;
+            Ok((
+                kind,
+                Convention {
+                    id: row.get(0)?,
+                    kind: ConventionKind::Forbidden,
+                    rule: row.get(2)?,
+                    pattern: row.get(3)?,
+                    scope: row.get(4)?,
+                    violation_count: row.get(5)?,
+                    sample_violations: serde_json::from_str(&samples).unwrap_or_default(),
+                    checked_at: row.get(7)?,
+                },
+            ))
+        })
+        .map_err(|e| format!("Query error: {}", e))?;
+
+    let mut conventions = Vec::new();
+    for row in rows {
+        let (kind, convention) = row.map_err(|e| format!("Row mapping error: {}", e))?;
+        if let Some(kind) = ConventionKind::parse(&kind) {
+            conventions.push(Convention { kind, ..convention });
+        }
+    }
+    Ok(conventions)
+}
+
+/// Check the project's source files against `conventions`. Sets each rule's violation_count
+/// and sample_violations; returns the violations (capped per rule) and the number of files checked.
+pub fn check(project_path: &str, conventions: &mut [Convention]) -> (Vec<ConventionViolation>, u32) {
+    let mut files = Vec::new();
+    collect_files(Path::new(project_path), "", 0, &mut files);
+
+    let naming: HashMap<&str, Regex> = CASE_STYLES
+        .iter()
+        .filter_map(|(name, _, re)| Regex::new(re).ok().map(|re| (*name, re)))
+        .collect();
+    let forbidden: Vec<Option<Regex>> = conventions
+        .iter()
+        .map(|c| (c.kind == ConventionKind::Forbidden).then(|| forbidden_regex(&c.pattern)).flatten())
+        .collect();
+
+    let mut found: Vec<Vec<ConventionViolation>> = vec![Vec::new(); conventions.len()];
+    for rel in &files {
+        let name = rel.rsplit('/').next().unwrap_or(rel);
+        let mut content: Option<String> = None;
+        for (i, c) in conventions.iter().enumerate() {
+            if c.scope.as_deref().is_some_and(|scope| c.kind != ConventionKind::Directory && !ai::glob_matches(scope, rel)) {
+                continue;
+            }
+            let violation = |line: Option<u32>, detail: String| ConventionViolation {
+                convention_id: c.id.clone(),
+                kind: c.kind,
+                rule: c.rule.clone(),
+                file: rel.clone(),
+                line,
+                detail,
+            };
+            match c.kind {
+                ConventionKind::Naming => {
+                    let stem = name.split('.').next().unwrap_or(name);
+                    let exempt = stem.is_empty()
+                        || EXEMPT_STEMS.contains(&stem)
+                        || stem.starts_with(['_', '+', '[']);
+                    if !exempt && naming.get(c.pattern.as_str()).is_some_and(|re| !re.is_match(stem)) {
+                        found[i].push(violation(None, format!("{} is not {}", name, c.pattern)));
+                    }
+                }
+                ConventionKind::Directory => {
+                    let in_dir = rel.starts_with(&format!("{}/", c.pattern)) || rel.contains(&format!("/{}/", c.pattern));
+                    if c.scope.as_deref().is_some_and(|glob| ai::glob_matches(glob, name)) && !in_dir {
+                        found[i].push(violation(None, format!("{} is outside {}/", name, c.pattern)));
+                    }
+                }
+                ConventionKind::Forbidden => {
+                    let Some(re) = &forbidden[i] else { continue };
+                    if test_runner::is_test_file(name) {
+                        continue;
+                    }
+                    let text = content.get_or_insert_with(|| {
+                        safe_read::read_text(Path::new(project_path).join(rel))
+                            .ok()
+                            .filter(|t| !analyzer::is_generated_content(t))
+                            .unwrap_or_default()
+                    });
+                    for (n, line) in text.lines().enumerate() {
+                        if !is_comment(line) && re.is_match(line) {
+                            found[i].push(violation(Some(n as u32 + 1), line.trim().chars().take(200).collect()));
+                        }
+                    }
+                }
+            }
+        }
+    }
+
+    let mut violations = Vec::new();
+    for (c, mut hits) in conventions.iter_mut().zip(found) {
+        c.violation_count = hits.len() as u32;
+        c.sample_violations = hits
+            .iter()
+            .take(MAX_SAMPLES)
+            .map(|v| match v.line {
+                Some(line) => format!("{}:{}", v.file, line),
+                None => v.file.clone(),
+            })
+            .collect();
+        hits.truncate(MAX_REPORTED_PER_RULE);
+        violations.extend(hits);
+    }
+    (violations, files.len() as u32)
+}
+
+fn collect_files(dir: &Path, rel_dir: &str, depth: usize, files: &mut Vec<String>) {
+    if depth > MAX_DEPTH {
+        return;
+    }
+    let Ok(entries) = fs::read_dir(dir) else {
+        return;
+    };
+    let mut entries: Vec<_> = entries.flatten().collect();
+    entries.sort_by_key(|e| e.file_name());
+    for entry in entries {
+        if files.len() >= MAX_FILES {
+            return;
+        }
+        let name = entry.file_name().to_string_lossy().to_string();
+        let Ok(file_type) = entry.file_type() else { continue };
+        if name.starts_with('.') || file_type.is_symlink() {
+            continue;
+        }
+        let rel = if rel_dir.is_empty() { name.clone() } else { format!("{}/{}", rel_dir, name) };
+        if file_type.is_dir() {
+            if !SKIP_DIRS.contains(&name.as_str()) {
+                collect_files(&entry.path(), &rel, depth + 1, files);
+            }
+        } else if analyzer::has_doc_extension(&name) {
+            files.push(rel);
+        }
+    }
+}
+
+/// Literal match, on word boundaries where the token starts or ends with a word character.
+fn forbidden_regex(token: &str) -> Option<Regex> {
+    let word = |c: Option<char>| c.is_some_and(|c| c.is_alphanumeric() || c == '_');
+    let pattern = format!(
+        "{}{}{}",
+        if word(token.chars().next()) { r"\b" } else { "" },
+        regex::escape(token),
+        if word(token.chars().last()) { r"\b" } else { "" },
+    );
+    Regex::new(&pattern).ok()
+}
+
+fn is_comment(line: &str) -> bool {
+    let trimmed = line.trim_start();
+    ["//", "#", "*", "/*", "--", "<!--"].iter().any(|marker| trimmed.starts_with(marker))
+}
+
+/// Store each rule's violation count and samples from a check.
+pub fn record_results(db: &Connection, conventions: &[Convention], checked_at: &str) -> Result<(), String> {
+    for c in conventions {
+        db.execute(
+            "UPDATE conventions SET violation_count = ?1, sample_violations = ?2, checked_at = ?3 WHERE id = ?4",
+            rusqlite::params![
+                c.violation_count,
+                serde_json::to_string(&c.sample_violations).unwrap_or_else(|_| "[]".to_string()),
+                checked_at,
+                c.id,
+            ],
+        )
+        .map_err(|e| format!("Failed to record convention check: {}", e))?;
+    }
+    Ok(())
+}
+
+/// (total violations, rules with violations) from the last check; None before the first check.
+pub fn violation_totals(db: &Connection, project_id: &str) -> Option<(u32, u32)> {
+    let (checked, violations, rules) = db
+        .query_row(
+            "SELECT COUNT(*), COALESCE(SUM(violation_count), 0), COALESCE(SUM(violation_count > 0), 0)
+             FROM conventions WHERE project_id = ?1 AND checked_at IS NOT NULL",
+            [project_id],
+            |row| Ok((row.get::<_, u32>(0)?, row.get::<_, u32>(1)?, row.get::<_, u32>(2)?)),
+        )
+        .ok()?;
+    (checked > 0).then_some((violations, rules))
+}
+
+/// Health quick win for outstanding violations (None when there are none).
+pub fn quick_win(violations: u32, rules: u32) -> Option<QuickWin> {
+    (violations > 0).then(|| QuickWin {
+        title: "Fix CLAUDE.md convention violations".to_string(),
+        description: format!(
+            "{} violation{} of {} CLAUDE.md convention{}. Code that contradicts CLAUDE.md teaches Claude the wrong pattern.",
+            violations,
+            if violations == 1 { "" } else { "s" },
+            rules,
+            if rules == 1 { "" } else { "s" },
+        ),
+        impact: violations.min(10),
+        effort: "medium".to_string(),
+    })
+}
+
+/// Stored rules phrased for RALPH's learning candidates; violated rules name where they are broken.
+pub fn ralph_candidates(db: &Connection, project_id: &str) -> Vec<String> {
+    let conventions = load_conventions(db, project_id).unwrap_or_default();
+    let mut seen = HashSet::new();
+    conventions
+        .into_iter()
+        .filter(|c| seen.insert(c.rule.clone()))
+        .map(|c| match c.sample_violations.first() {
+            Some(sample) if c.violation_count > 0 => format!(
+                "Convention: {} (currently broken in {} place{}, e.g. {}; don't copy those)",
+                c.rule,
+                c.violation_count,
+                if c.violation_count == 1 { "" } else { "s" },
+                sample
+            ),
+            _ => format!("Convention: {}", c.rule),
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const CLAUDE_MD: &str = "# App\n\n## Code Patterns\n\
+        - File naming: `kebab-case.tsx` for components in `src/components/`\n\
+        - Never use `console.log` or `any` in production code\n\
+        - Don't modify files in `/generated`\n\
+        - Tests go in `__tests__/` (`*.test.ts`)\n\
+        - Components are PascalCase, hooks are camelCase\n\
+        ```\n- Never use `eval` (inside a fence)\n```\n\n\
+        ## Commands\n- Never run `rm -rf`\n";
+
+    #[test]
+    fn test_parse_conventions() {
+        let conventions = parse_conventions(CLAUDE_MD);
+        let summary: Vec<(ConventionKind, &str, Option<&str>)> =
+            conventions.iter().map(|c| (c.kind, c.pattern.as_str(), c.scope.as_deref())).collect();
+        assert_eq!(
+            summary,
+            vec![
+                (ConventionKind::Naming, "kebab-case", Some("src/components/**/*.tsx")),
+                (ConventionKind::Forbidden, "console.log", None),
+                (ConventionKind::Forbidden, "any", None),
+                (ConventionKind::Directory, "__tests__", Some("*.test.ts")),
+            ]
+        );
+        assert_eq!(conventions[1].rule, "Never use `console.log` or `any` in production code");
+        assert!(parse_conventions("# App\n\nNo sections here").is_empty());
+    }
+
+    #[test]
+    fn test_check_conventions() {
+        let dir = tempfile::tempdir().unwrap();
+        let root = dir.path();
+        let write = |rel: &str, content: &str| {
+            let path = root.join(rel);
+            fs::create_dir_all(path.parent().unwrap()).unwrap();
+            fs::write(path, content).unwrap();
+        };
+        write("src/components/user-card.tsx", "export const UserCard = () => null;\n");
+        write("src/components/UserList.tsx", "// console.log is banned\nconsole.log(list);\n");
+        write("src/components/index.tsx", "export * from './user-card';\n");
+        write("src/utils/format.ts", "const many = 1;\nfunction f(x: any) {}\n");
+        write("src/utils/format.test.ts", "console.log('ok');\n");
+        write("src/__tests__/card.test.ts", "");
+        write("node_modules/pkg/index.ts", "console.log(1);\n");
+
+        let mut conventions = parse_conventions(CLAUDE_MD);
+        let (violations, files_checked) = check(root.to_str().unwrap(), &mut conventions);
+        assert_eq!(files_checked, 6);
+
+        let located: Vec<(ConventionKind, &str, Option<u32>)> =
+            violations.iter().map(|v| (v.kind, v.file.as_str(), v.line)).collect();
+        assert_eq!(
+            located,
+            vec![
+                (ConventionKind::Naming, "src/components/UserList.tsx", None),
+                (ConventionKind::Forbidden, "src/components/UserList.tsx", Some(2)),
+                (ConventionKind::Forbidden, "src/utils/format.ts", Some(2)),
+                (ConventionKind::Directory, "src/utils/format.test.ts", None),
+            ]
+        );
+        assert_eq!(conventions[1].violation_count, 1);
+        assert_eq!(conventions[1].sample_violations, vec!["src/components/UserList.tsx:2".to_string()]);
+
+        assert_eq!(quick_win(4, 3).unwrap().impact, 4);
+        assert!(quick_win(0, 0).is_none());
+    }
+}
@@ -0,0 +1,371 @@
+//! @module core/digest
+//! @description Per-project weekly digests compiled from the activity, loop, test, health, and learning tables
+//!
+//! PURPOSE:
+//! - Compute the Monday-to-Monday (UTC) week boundaries digests cover
+//! - Collect a project's weekly numbers and render them as Markdown
+//! - Store digests (one per project and week) and generate the ones that are due
+//!
+//! DEPENDENCIES:
+//! - chrono - Week boundaries
+//! - rusqlite - Source tables and the weekly_digests table
+//! - core::events - DigestGenerated event (notification + webhook delivery)
+//! - models::digest - DigestStats, WeeklyDigest
+//!
+//! EXPORTS:
+//! - ENABLED_SETTING - "digest.enabled"; "false" stops scheduled digests
+//! - is_enabled - Whether scheduled digests are on (default on)
+//! - week_start_of / last_completed_week - Week boundaries
+//! - collect_stats - A project's numbers for one week
+//! - render_markdown - Markdown body of a digest
+//! - generate - Collect, render, and store (or replace) a project's digest for a week
+//! - generate_due - Digests for the last completed week that are missing (used by the scheduler)
+//! - list - Stored digests for a project, newest week first
+//!
+//! PATTERNS:
+//! - Timestamps are compared as RFC 3339 strings, like every other created_at in the database
+//! - generate publishes AppEvent::DigestGenerated; posting to the events webhook follows the
+//!   events.webhook_url / events.webhook_events settings like any other event
+//!
+//! CLAUDE NOTES:
+//! - Scheduled generation skips weeks where nothing happened; generate (manual) always stores one
+//! - Regenerating a week keeps the digest id and replaces its stats and Markdown
+
+use chrono::{DateTime, Datelike, Duration, TimeZone, Utc};
+use rusqlite::{Connection, OptionalExtension};
+
+use crate::core::events::{self, AppEvent};
+use crate::models::digest::{DigestStats, WeeklyDigest};
+
+pub const ENABLED_SETTING: &str = "digest.enabled";
+
+/// New learnings listed in a digest.
+const TOP_LEARNINGS: u32 = 5;
+
+/// Scheduled digests are on unless digest.enabled is "false".
+pub fn is_enabled(db: &Connection) -> bool {
+    let value: Option<String> = db
+        .query_row("SELECT value FROM settings WHERE key = ?1", [ENABLED_SETTING], |row| row.get(0))
+        .ok();
+    value.as_deref() != Some("false")
+}
+
+/// Monday 00:00 UTC of the week containing `at`.
+pub fn week_start_of(at: DateTime<Utc>) -> DateTime<Utc> {
+    let date = at.date_naive() - Duration::days(at.weekday().num_days_from_monday() as i64);
+    Utc.from_utc_datetime(&date.and_hms_opt(0, 0, 0).unwrap_or_default())
+}
+
+/// Start of the most recent week that has fully ended.
+pub fn last_completed_week(now: DateTime<Utc>) -> DateTime<Utc> {
+    week_start_of(now) - Duration::weeks(1)
+}
+
+/// A project's numbers for the week starting at `week_start`.
+pub fn collect_stats(db: &Connection, project_id: &str, week_start: DateTime<Utc>) -> Result<DigestStats, String> {
+    let start = week_start.to_rfc3339();
+    let end = (week_start + Duration::weeks(1)).to_rfc3339();
+    let params = rusqlite::params![project_id, start, end];
+    let count = |sql: &str| -> Result<u32, String> {
+        db.query_row(sql, params, |row| row.get(0))
+            .map_err(|e| format!("Failed to collect digest stats: {}", e))
+    };
+
+    let docs_generated = count(
+        "SELECT COUNT(*) FROM activities
+         WHERE project_id = ?1 AND activity_type = 'generate' AND created_at >= ?2 AND created_at < ?3",
+    )?;
+    let (loops_run, loops_completed, loops_failed) = db
+        .query_row(
+            "SELECT COUNT(*), COALESCE(SUM(status = 'completed'), 0), COALESCE(SUM(status = 'failed'), 0)
+             FROM ralph_loops WHERE project_id = ?1 AND created_at >= ?2 AND created_at < ?3",
+            params,
+            |row| Ok((row.get(0)?, row.get(1)?, row.get(2)?)),
+        )
+        .map_err(|e| format!("Failed to collect loop stats: {}", e))?;
+    let (test_runs, test_runs_passed, test_runs_failed) = db
+        .query_row(
+            "SELECT COUNT(*), COALESCE(SUM(r.status = 'passed'), 0), COALESCE(SUM(r.status = 'failed'), 0)
+             FROM test_runs r JOIN test_plans p ON p.id = r.plan_id
+             WHERE p.project_id = ?1 AND r.started_at >= ?2 AND r.started_at < ?3",
+            params,
+            |row| Ok((row.get(0)?, row.get(1)?, row.get(2)?)),
+        )
+        .map_err(|e| format!("Failed to collect test stats: {}", e))?;
+
+    let health_before: Option<u32> = db
+        .query_row(
+            "SELECT health_score FROM health_snapshots WHERE project_id = ?1 AND created_at < ?2
+             ORDER BY created_at DESC LIMIT 1",
+            rusqlite::params![project_id, start],
+            |row| row.get(0),
+        )
+        .optional()
+        .map_err(|e| format!("Failed to collect health stats: {}", e))?;
+    let health_first: Option<u32> = db
+        .query_row(
+            "SELECT health_score FROM health_snapshots WHERE project_id = ?1 AND created_at >= ?2 AND created_at < ?3
+             ORDER BY created_at ASC LIMIT 1",
+            params,
+            |row| row.get(0),
+        )
+        .optional()
+        .map_err(|e| format!("Failed to collect health stats: {}", e))?;
+    let health_end: Option<u32> = db
+        .query_row(
+            "SELECT health_score FROM health_snapshots WHERE project_id = ?1 AND created_at < ?2
+             ORDER BY created_at DESC LIMIT 1",
+            rusqlite::params![project_id, end],
+            |row| row.get(0),
+        )
+        .optional()
+        .map_err(|e| format!("Failed to collect health stats: {}", e))?;
+
+    let new_learnings = count(
+        "SELECT COUNT(*) FROM learnings WHERE project_id = ?1 AND created_at >= ?2 AND created_at < ?3",
+    )?;
+    let mut stmt = db
+        .prepare(
+            "SELECT content FROM learnings WHERE project_id = ?1 AND created_at >= ?2 AND created_at < ?3
+             ORDER BY CASE confidence WHEN 'high' THEN 0 WHEN 'medium' THEN 1 ELSE 2 END, created_at DESC
+             LIMIT ?4",
+        )
+        .map_err(|e| format!("Failed to query learnings: {}", e))?;
+    let top_learnings = stmt
+        .query_map(rusqlite::params![project_id, start, end, TOP_LEARNINGS], |row| row.get(0))
+        .map_err(|e| format!("Failed to read learnings: {}", e))?
+        .filter_map(|r| r.ok())
+        .collect();
+
+    Ok(DigestStats {
+        docs_generated,
+        loops_run,
+        loops_completed,
+        loops_failed,
+        test_runs,
+        test_runs_passed,
+        test_runs_failed,
+        health_start: health_before.or(health_first),
+        health_end,
+        new_learnings,
+        top_learnings,
+    })
+}
+
+/// Markdown body of a digest.
+pub fn render_markdown(project_name: &str, week_start: DateTime<Utc>, stats: &DigestStats) -> String {
+    let last_day = (week_start + Duration::days(6)).format("%Y-%m-%d");
+    let mut out = format!(
+        "# Weekly digest: {}\n\n_{} to {}_\n\n",
+        project_name,
+        week_start.format("%Y-%m-%d"),
+        last_day
+    );
+    out.push_str(&format!("- Docs generated: {}\n", stats.docs_generated));
+    out.push_str(&format!(
+        "- RALPH loops: {} run ({} completed, {} failed)\n",
+        stats.loops_run, stats.loops_completed, stats.loops_failed
+    ));
+    out.push_str(&format!(
+        "- Test runs: {} ({} passed, {} failed)\n",
+        stats.test_runs, stats.test_runs_passed, stats.test_runs_failed
+    ));
+    match (stats.health_start, stats.health_end, stats.health_delta()) {
+        (Some(from), Some(to), Some(delta)) => {
+            out.push_str(&format!("- Health: {} -> {} ({:+})\n", from, to, delta))
+        }
+        _ => out.push_str("- Health: no snapshots yet\n"),
+    }
+    out.push_str(&format!("- New learnings: {}\n", stats.new_learnings));
+
+    if !stats.top_learnings.is_empty() {
+        out.push_str("\n## Top new learnings\n\n");
+        for learning in &stats.top_learnings {
+            out.push_str(&format!("- {}\n", learning.lines().next().unwrap_or("").trim()));
+        }
+    }
+    out
+}
+
+/// Collect, render, and store a project's digest for the week starting at `week_start`,
+/// replacing an existing digest for that week. Publishes AppEvent::DigestGenerated.
+pub fn generate(db: &Connection, project_id: &str, week_start: DateTime<Utc>) -> Result<WeeklyDigest, String> {
+    let stats = collect_stats(db, project_id, week_start)?;
+    store(db, project_id, week_start, stats)
+}
+
+/// Generate the last completed week's digest for every project that does not have one
+/// and had any activity that week. Does nothing when digests are disabled.
+pub fn generate_due(db: &Connection, now: DateTime<Utc>) -> Result<Vec<WeeklyDigest>, String> {
+    if !is_enabled(db) {
+        return Ok(Vec::new());
+    }
+    let week_start = last_completed_week(now);
+    let mut stmt = db
+        .prepare(
+            "SELECT id FROM projects WHERE id NOT IN (SELECT project_id FROM weekly_digests WHERE week_start = ?1)",
+        )
+        .map_err(|e| format!("Failed to query projects: {}", e))?;
+    let project_ids: Vec<String> = stmt
+        .query_map([week_start.to_rfc3339()], |row| row.get(0))
+        .map_err(|e| format!("Failed to read projects: {}", e))?
+        .filter_map(|r| r.ok())
+        .collect();
+
+    let mut digests = Vec::new();
+    for project_id in project_ids {
+        let stats = collect_stats(db, &project_id, week_start)?;
+        if !stats.is_empty() {
+            digests.push(store(db, &project_id, week_start, stats)?);
+        }
+    }
+    Ok(digests)
+}
+
+/// Stored digests for a project, newest week first.
+pub fn list(db: &Connection, project_id: &str, limit: u32) -> Result<Vec<WeeklyDigest>, String> {
+    let mut stmt = db
+        .prepare(
+            "SELECT id, project_id, week_start, week_end, stats, markdown, created_at FROM weekly_digests
+             WHERE project_id = ?1 ORDER BY week_start DESC LIMIT ?2",
+        )
+        .map_err(|e| format!("Failed to query digests: {}", e))?;
+    let digests = stmt
+        .query_map(rusqlite::params![project_id, limit], |row| {
+            let stats: String = row.get(4)?;
+            Ok(WeeklyDigest {
+                id: row.get(0)?,
+                project_id: row.get(1)?,
+                week_start: row.get(2)?,
+                week_end: row.get(3)?,
+                stats: serde_json::from_str(&stats).unwrap_or_default(),
+                markdown: row.get(5)?,
+                created_at: row.get(6)?,
+            })
+        })
+        .map_err(|e| format!("Failed to read digests: {}", e))?
+        .filter_map(|r| r.ok())
+        .collect();
+    Ok(digests)
+}
+
+fn store(db: &Connection, project_id: &str, week_start: DateTime<Utc>, stats: DigestStats) -> Result<WeeklyDigest, String> {
+    let project_name: String = db
+        .query_row("SELECT name FROM projects WHERE id = ?1", [project_id], |row| row.get(0))
+        .map_err(|e| format!("Project not found: {}", e))?;
+    let markdown = render_markdown(&project_name, week_start, &stats);
+    let start = week_start.to_rfc3339();
+    let existing: Option<String> = db
+        .query_row(
+            "SELECT id FROM weekly_digests WHERE project_id = ?1 AND week_start = ?2",
+            [project_id, &start],
+            |row| row.get(0),
+        )
+        .optional()
+        .map_err(|e| format!("Failed to query digests: {}", e))?;
+
+    let digest = WeeklyDigest {
+        id: existing.unwrap_or_else(|| uuid::Uuid::new_v4().to_string()),
+        project_id: project_id.to_string(),
+        week_start: start,
+        week_end: (week_start + Duration::weeks(1)).to_rfc3339(),
+        stats,
+        markdown,
+        created_at: Utc::now().to_rfc3339(),
+    };
+    let stats_json =
+        serde_json::to_string(&digest.stats).map_err(|e| format!("Failed to serialize digest stats: {}", e))?;
+    db.execute(
+        "INSERT OR REPLACE INTO weekly_digests (id, project_id, week_start, week_end, stats, markdown, created_at)
+         VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7)",
+        rusqlite::params![
+            digest.id,
+            digest.project_id,
+            digest.week_start,
+            digest.week_end,
+            stats_json,
+            digest.markdown,
+            digest.created_at
+        ],
+    )
+    .map_err(|e| format!("Failed to save digest: {}", e))?;
+
+    events::publish(
+        db,
+        AppEvent::DigestGenerated {
+            project_id: digest.project_id.clone(),
+            digest_id: digest.id.clone(),
+            week_start: digest.week_start.clone(),
+            markdown: digest.markdown.clone(),
+        },
+    );
+    Ok(digest)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_support::memory_db;
+
+    fn at(s: &str) -> DateTime<Utc> {
+        DateTime::parse_from_rfc3339(s).unwrap().with_timezone(&Utc)
+    }
+
+    #[test]
+    fn test_week_boundaries() {
+        // 2026-10-15 is a Thursday
+        assert_eq!(week_start_of(at("2026-10-15T18:30:00Z")), at("2026-10-12T00:00:00Z"));
+        assert_eq!(week_start_of(at("2026-10-12T00:00:00Z")), at("2026-10-12T00:00:00Z"));
+        assert_eq!(last_completed_week(at("2026-10-18T23:59:00Z")), at("2026-10-05T00:00:00Z"));
+    }
+
+    #[test]
+    fn test_generate_due_collects_week_and_skips_quiet_projects() {
+        let db = memory_db();
+        let now = "2026-10-01T00:00:00+00:00";
+        for (id, name) in [("p1", "Busy"), ("p2", "Quiet")] {
+            db.execute(
+                "INSERT INTO projects (id, name, path, created_at) VALUES (?1, ?2, ?3, ?4)",
+                rusqlite::params![id, name, format!("/tmp/{}", id), now],
+            )
+            .unwrap();
+        }
+        let in_week = "2026-10-07T10:00:00+00:00";
+        db.execute_batch(&format!(
+            "INSERT INTO activities (id, project_id, activity_type, message, created_at) VALUES
+                ('a1', 'p1', 'generate', 'Applied docs', '{in_week}'),
+                ('a2', 'p1', 'generate', 'Applied docs', '2026-09-30T10:00:00+00:00');
+             INSERT INTO ralph_loops (id, project_id, prompt, status, created_at) VALUES
+                ('l1', 'p1', 'x', 'completed', '{in_week}'), ('l2', 'p1', 'y', 'failed', '{in_week}');
+             INSERT INTO health_snapshots (id, project_id, commit_sha, health_score, created_at) VALUES
+                ('h1', 'p1', 'a', 60, '2026-10-01T00:00:00+00:00'), ('h2', 'p1', 'b', 72, '{in_week}');
+             INSERT INTO learnings (id, project_id, content, confidence, created_at, updated_at) VALUES
+                ('n1', 'p1', 'Run migrations before tests', 'medium', '{in_week}', '{in_week}'),
+                ('n2', 'p1', 'Use pnpm', 'high', '{in_week}', '{in_week}');"
+        ))
+        .unwrap();
+
+        let digests = generate_due(&db, at("2026-10-14T09:00:00Z")).unwrap();
+        assert_eq!(digests.len(), 1);
+        let digest = &digests[0];
+        assert_eq!(digest.project_id, "p1");
+        assert_eq!(digest.stats.docs_generated, 1);
+        assert_eq!((digest.stats.loops_run, digest.stats.loops_completed, digest.stats.loops_failed), (2, 1, 1));
+        assert_eq!(digest.stats.health_delta(), Some(12));
+        assert_eq!(digest.stats.top_learnings, vec!["Use pnpm", "Run migrations before tests"]);
+        assert!(digest.markdown.contains("# Weekly digest: Busy"));
+        assert!(digest.markdown.contains("- Health: 60 -> 72 (+12)"));
+
+        // Already generated: nothing due; disabled: nothing generated
+        assert!(generate_due(&db, at("2026-10-15T09:00:00Z")).unwrap().is_empty());
+        db.execute("DELETE FROM weekly_digests", []).unwrap();
+        db.execute("INSERT INTO settings (key, value) VALUES (?1, 'false')", [ENABLED_SETTING]).unwrap();
+        assert!(generate_due(&db, at("2026-10-14T09:00:00Z")).unwrap().is_empty());
+
+        // Regenerating keeps the id
+        let first = generate(&db, "p1", at("2026-10-05T00:00:00Z")).unwrap();
+        let again = generate(&db, "p1", at("2026-10-05T00:00:00Z")).unwrap();
+        assert_eq!(first.id, again.id);
+        assert_eq!(list(&db, "p1", 10).unwrap().len(), 1);
+    }
+}
@@ -0,0 +1,185 @@
+//! @module core/doc_goals
+//! @description Check per-project documentation goals (coverage, stale age, health score)
+//!
+//! PURPOSE:
+//! - Validate DocGoals before they are saved
+//! - Measure how long outdated doc headers have gone without an update (git history)
+//! - Compare doc coverage, stale age, and health score against a project's goals
+//! - Derive the CI fail-under threshold from the goals
+//!
+//! DEPENDENCIES:
+//! - core::freshness - Doc header length and header git history
+//! - models::health_history - DocGoals, DocGoalCheck, DocGoalsReport
+//! - chrono - Age of the last header change
+//! - core::safe_read - Lossy, size-capped reads of outdated files
+//!
+//! EXPORTS:
+//! - validate_goals - Reject out-of-range targets and CI enforcement without a coverage target
+//! - stale_doc_ages - (path, days since the doc header changed) for outdated files, oldest first
+//! - evaluate_goals - Check every set goal; None when the project has no goals
+//! - ci_fail_under - Coverage threshold the CI check enforces, if any
+//!
+//! PATTERNS:
+//! - A stale_days goal is missed when any outdated file's header is older than max_stale_days
+//! - Coverage is compared at one decimal place so 89.96% does not miss a 90% goal
+//!
+//! CLAUDE NOTES:
+//! - Stale age is measured from the last commit touching the header lines, falling back to
+//!   the last commit touching the file; files without git history are never counted stale
+//! - The CI check can only enforce coverage (a header grep); stale age needs the freshness scan
+//!   and git history, so it is checked in the app and recorded in health snapshots only
+
+use std::path::Path;
+
+use chrono::{DateTime, Utc};
+
+use crate::core::{freshness, safe_read};
+use crate::models::health_history::{DocGoalCheck, DocGoals, DocGoalsReport};
+
+/// Reject targets outside their ranges. Returns the goals unchanged when valid.
+pub fn validate_goals(goals: DocGoals) -> Result<DocGoals, String> {
+    if let Some(coverage) = goals.min_doc_coverage {
+        if !(0.0..=100.0).contains(&coverage) {
+            return Err("Doc coverage goal must be between 0 and 100.".to_string());
+        }
+    }
+    if goals.min_health_score.is_some_and(|s| s > 100) {
+        return Err("Health score goal must be between 0 and 100.".to_string());
+    }
+    if goals.max_stale_days == Some(0) {
+        return Err("Stale days goal must be at least 1 day.".to_string());
+    }
+    if goals.enforce_in_ci && goals.min_doc_coverage.is_none() {
+        return Err("CI enforcement needs a doc coverage goal.".to_string());
+    }
+    Ok(goals)
+}
+
+/// Days since each outdated file's doc header last changed in git, oldest first.
+/// `outdated` holds project-relative paths.
+pub fn stale_doc_ages(project_path: &str, outdated: &[String], now: DateTime<Utc>) -> Vec<(String, i64)> {
+    let mut ages: Vec<(String, i64)> = outdated
+        .iter()
+        .filter_map(|rel| {
+            let full = Path::new(project_path).join(rel);
+            let content = safe_read::read_text(&full).ok()?;
+            let header_lines = freshness::doc_header_line_count(&content);
+            let history = freshness::header_git_history(&full.to_string_lossy(), header_lines);
+            let changed_at = history.header_changed_at.or(history.file_changed_at)?;
+            let changed_at = DateTime::parse_from_rfc3339(&changed_at).ok()?;
+            let days = (now - changed_at.with_timezone(&Utc)).num_days().max(0);
+            Some((rel.clone(), days))
+        })
+        .collect();
+    ages.sort_by(|a, b| b.1.cmp(&a.1).then_with(|| a.0.cmp(&b.0)));
+    ages
+}
+
+/// Check each goal that is set. `stale` is the output of stale_doc_ages
+/// (ignored when no stale_days goal is set). None when no goal is set.
+pub fn evaluate_goals(
+    goals: &DocGoals,
+    doc_coverage: f64,
+    health_score: u32,
+    stale: &[(String, i64)],
+) -> Option<DocGoalsReport> {
+    if goals.is_empty() {
+        return None;
+    }
+
+    let mut checks = Vec::new();
+    if let Some(target) = goals.min_doc_coverage {
+        let actual = (doc_coverage * 10.0).round() / 10.0;
+        checks.push(DocGoalCheck {
+            goal: "doc_coverage".to_string(),
+            target,
+            actual,
+            met: actual >= target,
+            files: Vec::new(),
+        });
+    }
+    if let Some(max_days) = goals.max_stale_days {
+        let files: Vec<String> = stale
+            .iter()
+            .filter(|(_, days)| *days > max_days as i64)
+            .map(|(path, _)| path.clone())
+            .collect();
+        checks.push(DocGoalCheck {
+            goal: "stale_days".to_string(),
+            target: max_days as f64,
+            actual: stale.iter().map(|(_, days)| *days).max().unwrap_or(0) as f64,
+            met: files.is_empty(),
+            files,
+        });
+    }
+    if let Some(target) = goals.min_health_score {
+        checks.push(DocGoalCheck {
+            goal: "health_score".to_string(),
+            target: target as f64,
+            actual: health_score as f64,
+            met: health_score >= target,
+            files: Vec::new(),
+        });
+    }
+
+    let met = checks.iter().all(|c| c.met);
+    Some(DocGoalsReport { checks, met })
+}
+
+/// Coverage percentage the generated CI check must reach, when CI enforcement is on.
+pub fn ci_fail_under(goals: &DocGoals) -> Option<f64> {
+    if goals.enforce_in_ci {
+        goals.min_doc_coverage
+    } else {
+        None
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_evaluate_goals() {
+        assert!(evaluate_goals(&DocGoals::default(), 50.0, 40, &[]).is_none());
+
+        let goals = DocGoals {
+            min_doc_coverage: Some(90.0),
+            max_stale_days: Some(30),
+            min_health_score: Some(70),
+            enforce_in_ci: false,
+        };
+        let stale = vec![("src/old.rs".to_string(), 45), ("src/new.rs".to_string(), 3)];
+        let report = evaluate_goals(&goals, 89.96, 72, &stale).unwrap();
+        assert!(!report.met);
+        assert_eq!(report.checks.len(), 3);
+        assert!(report.checks[0].met, "89.96% rounds to 90.0%");
+        assert!(!report.checks[1].met);
+        assert_eq!(report.checks[1].actual, 45.0);
+        assert_eq!(report.checks[1].files, vec!["src/old.rs"]);
+        assert!(report.checks[2].met);
+
+        let report = evaluate_goals(&goals, 95.0, 80, &stale[1..]).unwrap();
+        assert!(report.met);
+    }
+
+    #[test]
+    fn test_validate_goals() {
+        let valid = DocGoals {
+            min_doc_coverage: Some(90.0),
+            enforce_in_ci: true,
+            ..DocGoals::default()
+        };
+        assert_eq!(validate_goals(valid.clone()).unwrap(), valid);
+        assert_eq!(ci_fail_under(&valid), Some(90.0));
+
+        let no_target = DocGoals {
+            enforce_in_ci: true,
+            ..DocGoals::default()
+        };
+        assert!(validate_goals(no_target).is_err());
+        assert!(validate_goals(DocGoals { min_doc_coverage: Some(120.0), ..DocGoals::default() }).is_err());
+        assert!(validate_goals(DocGoals { max_stale_days: Some(0), ..DocGoals::default() }).is_err());
+        assert_eq!(ci_fail_under(&DocGoals { min_doc_coverage: Some(90.0), ..DocGoals::default() }), None);
+    }
+}
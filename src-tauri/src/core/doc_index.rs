@@ -0,0 +1,374 @@
+//! @module core/doc_index
+//! @description Full-text index over module doc headers and prompt-to-file relevance ranking
+//!
+//! PURPOSE:
+//! - Index each documentable file's header (module, description, exports, notes) in FTS5
+//! - Rebuild a project's index when it is older than INDEX_TTL_SECS
+//! - Rank project files for a free-text prompt from FTS matches plus path heuristics
+//!
+//! DEPENDENCIES:
+//! - rusqlite - module_doc_index (FTS5) and the settings table for build times
+//! - core::analyzer - File walk, header parsing, and export detection for undocumented files
+//! - core::ai - .claude/ai-exclude globs (excluded files are never suggested)
+//! - core::test_runner - Test file detection (down-weighted unless the prompt is about tests)
+//! - models::ralph - FileSuggestion
+//!
+//! EXPORTS:
+//! - INDEX_TTL_SECS - Age after which ensure_fresh rebuilds a project's index
+//! - split_identifier - "TestPlanEnv" / "test_plan_env" -> ["test", "plan", "env"]
+//! - prompt_terms - Lowercase search terms from free text (stop words dropped)
+//! - rebuild - Re-index every documentable file of a project
+//! - ensure_fresh - Rebuild when the index is missing or stale
+//! - search - FTS matches for terms as (path, bm25) pairs, best first
+//! - rank_files - Combine FTS ranks and path heuristics into FileSuggestions
+//! - suggest_files - ensure_fresh + search + rank_files for a prompt
+//! - indexed_description - A file's @description as of the last index build
+//!
+//! PATTERNS:
+//! - Score is 0-100: up to 60 from the FTS rank (relative to the best hit), +40 when the prompt
+//!   names the file, +20/+10 when all/some of the file name's words are terms, +5 for a
+//!   matching directory; test files score x0.8 unless the prompt mentions tests
+//! - Files without a header are indexed by path and detected exports only
+//!
+//! CLAUDE NOTES:
+//! - FTS queries quote every term and use prefix matching ("term"*), so user text cannot
+//!   inject FTS syntax
+//! - Build time is stored in settings as "doc_index.built_at.<project_id>"
+
+use std::collections::{HashMap, HashSet};
+use std::path::Path;
+
+use chrono::{DateTime, Utc};
+use rusqlite::Connection;
+
+use crate::core::{ai, analyzer, test_runner};
+use crate::models::ralph::FileSuggestion;
+
+/// Index age (seconds) after which ensure_fresh rebuilds it.
+pub const INDEX_TTL_SECS: i64 = 600;
+
+const BUILT_AT_SETTING: &str = "doc_index.built_at";
+
+/// FTS hits considered before ranking.
+const SEARCH_LIMIT: u32 = 200;
+
+const STOP_WORDS: &[&str] = &[
+    "the", "and", "for", "with", "that", "this", "from", "into", "onto", "when", "then", "than", "there", "their",
+    "should", "would", "could", "will", "can", "not", "all", "any", "are", "was", "were", "been", "have", "has",
+    "but", "its", "also", "via", "using", "use", "make", "add", "fix", "update", "change", "new", "please", "need",
+    "needs", "want", "like", "some", "each", "our", "your", "you", "they", "them", "out", "get", "set", "too",
+];
+
+/// Split an identifier or path segment into lowercase words (camelCase, snake_case, kebab-case).
+pub fn split_identifier(ident: &str) -> Vec<String> {
+    let mut words = Vec::new();
+    let mut current = String::new();
+    let chars: Vec<char> = ident.chars().collect();
+    for (i, &c) in chars.iter().enumerate() {
+        if !c.is_alphanumeric() {
+            if !current.is_empty() {
+                words.push(std::mem::take(&mut current));
+            }
+            continue;
+        }
+        let boundary = c.is_uppercase()
+            && !current.is_empty()
+            && (chars[i - 1].is_lowercase() || chars.get(i + 1).is_some_and(|n| n.is_lowercase()));
+        if boundary {
+            words.push(std::mem::take(&mut current));
+        }
+        current.extend(c.to_lowercase());
+    }
+    if !current.is_empty() {
+        words.push(current);
+    }
+    words
+}
+
+/// Lowercase search terms (3+ characters, stop words dropped, identifiers split), in order, deduplicated.
+pub fn prompt_terms(text: &str) -> Vec<String> {
+    let mut seen = HashSet::new();
+    text.split_whitespace()
+        .flat_map(split_identifier)
+        .filter(|w| w.chars().count() >= 3 && !STOP_WORDS.contains(&w.as_str()))
+        .filter(|w| seen.insert(w.clone()))
+        .collect()
+}
+
+fn words_of(items: &[String]) -> String {
+    items
+        .iter()
+        .flat_map(|item| {
+            let name = item.split(" - ").next().unwrap_or(item);
+            let mut words = split_identifier(name);
+            words.push(item.clone());
+            words
+        })
+        .collect::<Vec<_>>()
+        .join(" ")
+}
+
+/// One indexed file: (path, module, description, exports, notes).
+type IndexRow = (String, String, String, String, String);
+
+fn index_rows(project_path: &str) -> Result<Vec<IndexRow>, String> {
+    let modules = analyzer::scan_all_modules(project_path)?;
+    let mut rows = Vec::with_capacity(modules.len());
+    for module in modules {
+        let full = Path::new(project_path).join(&module.path);
+        let full = full.to_string_lossy();
+        let too_big = std::fs::metadata(full.as_ref()).map(|m| m.len() > analyzer::max_source_bytes(&full)).unwrap_or(true);
+        let content = if too_big { None } else { analyzer::read_source(&full).ok() };
+
+        let row = match content.as_deref().and_then(analyzer::parse_doc_header) {
+            Some(doc) => {
+                let mut notes = doc.purpose.clone();
+                notes.extend(doc.patterns.iter().cloned());
+                notes.extend(doc.claude_notes.iter().cloned());
+                (module.path.clone(), doc.module_path.clone(), doc.description.clone(), words_of(&doc.exports), notes.join(" "))
+            }
+            None => {
+                let ext = Path::new(&module.path).extension().and_then(|e| e.to_str()).unwrap_or("");
+                let exports = content.as_deref().map(|c| analyzer::detect_exports(c, ext)).unwrap_or_default();
+                (module.path.clone(), String::new(), String::new(), words_of(&exports), String::new())
+            }
+        };
+        rows.push(row);
+    }
+    Ok(rows)
+}
+
+/// Re-index every documentable file in a project. Returns the number of files indexed.
+pub fn rebuild(db: &Connection, project_id: &str, project_path: &str) -> Result<u32, String> {
+    let rows = index_rows(project_path)?;
+    db.execute("DELETE FROM module_doc_index WHERE project_id = ?1", [project_id])
+        .map_err(|e| format!("Failed to clear doc index: {}", e))?;
+    for (path, module, description, exports, notes) in &rows {
+        let path_words = split_identifier(path).join(" ");
+        db.execute(
+            "INSERT INTO module_doc_index (project_id, file_path, module, description, exports, notes)
+             VALUES (?1, ?2, ?3, ?4, ?5, ?6)",
+            rusqlite::params![project_id, path, format!("{} {}", module, path_words), description, exports, notes],
+        )
+        .map_err(|e| format!("Failed to index {}: {}", path, e))?;
+    }
+    db.execute(
+        "INSERT OR REPLACE INTO settings (key, value) VALUES (?1, ?2)",
+        rusqlite::params![format!("{}.{}", BUILT_AT_SETTING, project_id), Utc::now().to_rfc3339()],
+    )
+    .map_err(|e| format!("Failed to record doc index time: {}", e))?;
+    Ok(rows.len() as u32)
+}
+
+/// Rebuild the project's index when it has never been built or is older than INDEX_TTL_SECS.
+pub fn ensure_fresh(db: &Connection, project_id: &str, project_path: &str) -> Result<(), String> {
+    let built_at: Option<String> = db
+        .query_row(
+            "SELECT value FROM settings WHERE key = ?1",
+            [format!("{}.{}", BUILT_AT_SETTING, project_id)],
+            |row| row.get(0),
+        )
+        .ok();
+    let fresh = built_at
+        .and_then(|at| DateTime::parse_from_rfc3339(&at).ok())
+        .is_some_and(|at| (Utc::now() - at.with_timezone(&Utc)).num_seconds() < INDEX_TTL_SECS);
+    if !fresh {
+        rebuild(db, project_id, project_path)?;
+    }
+    Ok(())
+}
+
+/// FTS matches for `terms` in a project's index as (path, bm25), best (lowest bm25) first.
+pub fn search(db: &Connection, project_id: &str, terms: &[String], limit: u32) -> Result<Vec<(String, f64)>, String> {
+    if terms.is_empty() {
+        return Ok(Vec::new());
+    }
+    let query = terms
+        .iter()
+        .map(|t| format!("\"{}\"*", t.replace('"', "")))
+        .collect::<Vec<_>>()
+        .join(" OR ");
+    let mut stmt = db
+        .prepare(
+            "SELECT file_path, bm25(module_doc_index, 0.0, 3.0, 4.0, 3.0, 2.0, 1.0) AS rank
+             FROM module_doc_index WHERE module_doc_index MATCH ?1 AND project_id = ?2
+             ORDER BY rank LIMIT ?3",
+        )
+        .map_err(|e| format!("Failed to prepare doc search: {}", e))?;
+    let hits = stmt
+        .query_map(rusqlite::params![query, project_id, limit], |row| Ok((row.get(0)?, row.get(1)?)))
+        .map_err(|e| format!("Failed to search docs: {}", e))?
+        .filter_map(|r| r.ok())
+        .collect();
+    Ok(hits)
+}
+
+/// Rank `paths` for a prompt. `fts_hits` are search results; `terms` come from prompt_terms.
+pub fn rank_files(prompt: &str, terms: &[String], paths: &[String], fts_hits: &[(String, f64)]) -> Vec<FileSuggestion> {
+    let prompt_lower = prompt.to_lowercase();
+    let term_set: HashSet<&str> = terms.iter().map(String::as_str).collect();
+    let about_tests = term_set.iter().any(|t| t.starts_with("test") || t.starts_with("spec"));
+    let best = fts_hits.iter().map(|(_, r)| *r).fold(0.0_f64, f64::min);
+    let fts: HashMap<&str, f64> = fts_hits.iter().map(|(p, r)| (p.as_str(), *r)).collect();
+
+    let mut suggestions: Vec<FileSuggestion> = paths
+        .iter()
+        .filter_map(|path| {
+            let mut score = 0.0;
+            let mut reasons = Vec::new();
+
+            if let Some(rank) = fts.get(path.as_str()) {
+                score += if best < 0.0 { 60.0 * (rank / best).clamp(0.0, 1.0) } else { 30.0 };
+                reasons.push("Module doc matches the prompt".to_string());
+            }
+
+            let file_name = Path::new(path).file_name().and_then(|n| n.to_str()).unwrap_or(path);
+            if prompt_lower.contains(&path.to_lowercase()) || prompt_lower.contains(&file_name.to_lowercase()) {
+                score += 40.0;
+                reasons.push("Named in the prompt".to_string());
+            }
+
+            let stem = Path::new(path).file_stem().and_then(|n| n.to_str()).unwrap_or("");
+            let stem_words: Vec<String> = split_identifier(stem).into_iter().filter(|w| w.len() >= 3).collect();
+            let matched = stem_words.iter().filter(|w| term_set.contains(w.as_str())).count();
+            if !stem_words.is_empty() && matched == stem_words.len() {
+                score += 20.0;
+                reasons.push("File name matches".to_string());
+            } else if matched > 0 {
+                score += 10.0;
+                reasons.push("File name partly matches".to_string());
+            }
+
+            let dir_match = Path::new(path)
+                .parent()
+                .map(|p| p.iter().filter_map(|s| s.to_str()).flat_map(split_identifier).any(|w| term_set.contains(w.as_str())))
+                .unwrap_or(false);
+            if dir_match {
+                score += 5.0;
+                reasons.push("In a matching directory".to_string());
+            }
+
+            if score <= 0.0 {
+                return None;
+            }
+            if !about_tests && test_runner::is_test_file(file_name) {
+                score *= 0.8;
+            }
+            Some(FileSuggestion {
+                path: path.clone(),
+                score: score.min(100.0).round() as u32,
+                reasons,
+            })
+        })
+        .collect();
+
+    suggestions.sort_by(|a, b| b.score.cmp(&a.score).then_with(|| a.path.cmp(&b.path)));
+    suggestions
+}
+
+/// Files most likely relevant to `prompt`, best first, at most `limit`. AI-excluded files are skipped.
+pub fn suggest_files(
+    db: &Connection,
+    project_id: &str,
+    project_path: &str,
+    prompt: &str,
+    limit: usize,
+) -> Result<Vec<FileSuggestion>, String> {
+    ensure_fresh(db, project_id, project_path)?;
+    let terms = prompt_terms(prompt);
+    let hits = search(db, project_id, &terms, SEARCH_LIMIT)?;
+
+    let excludes = ai::load_ai_excludes(project_path);
+    let paths: Vec<String> = {
+        let mut stmt = db
+            .prepare("SELECT file_path FROM module_doc_index WHERE project_id = ?1")
+            .map_err(|e| format!("Failed to list indexed files: {}", e))?;
+        let rows = stmt
+            .query_map([project_id], |row| row.get::<_, String>(0))
+            .map_err(|e| format!("Failed to list indexed files: {}", e))?
+            .filter_map(|r| r.ok())
+            .filter(|p| !ai::is_ai_excluded(&excludes, p))
+            .collect();
+        rows
+    };
+
+    let mut ranked = rank_files(prompt, &terms, &paths, &hits);
+    ranked.truncate(limit);
+    Ok(ranked)
+}
+
+/// A file's @description as of the last index build; None when it has no header (or
+/// is not indexed).
+pub fn indexed_description(db: &Connection, project_id: &str, path: &str) -> Option<String> {
+    db.query_row(
+        "SELECT description FROM module_doc_index WHERE project_id = ?1 AND file_path = ?2",
+        [project_id, path],
+        |row| row.get::<_, String>(0),
+    )
+    .ok()
+    .filter(|d| !d.is_empty())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_support::memory_db;
+    use std::fs;
+
+    #[test]
+    fn test_split_identifier_and_terms() {
+        assert_eq!(split_identifier("TestPlanEnv"), vec!["test", "plan", "env"]);
+        assert_eq!(split_identifier("parseHTTPResponse"), vec!["parse", "http", "response"]);
+        assert_eq!(split_identifier("src/test_plans.rs"), vec!["src", "test", "plans", "rs"]);
+        assert_eq!(
+            prompt_terms("Fix the loginForm validation and add the login tests"),
+            vec!["login", "form", "validation", "tests"]
+        );
+    }
+
+    #[test]
+    fn test_rank_files_combines_fts_and_paths() {
+        let paths: Vec<String> = ["src/auth/login.ts", "src/auth/login.test.ts", "src/utils/math.ts", "src/api/session.ts"]
+            .iter()
+            .map(|p| p.to_string())
+            .collect();
+        let hits = vec![("src/api/session.ts".to_string(), -4.0), ("src/auth/login.ts".to_string(), -2.0)];
+        let prompt = "Fix login redirect after the session expires";
+        let ranked = rank_files(prompt, &prompt_terms(prompt), &paths, &hits);
+
+        let order: Vec<&str> = ranked.iter().map(|s| s.path.as_str()).collect();
+        assert_eq!(order, vec!["src/api/session.ts", "src/auth/login.ts", "src/auth/login.test.ts"]);
+        assert!(ranked[1].reasons.contains(&"File name matches".to_string()));
+
+        let named = rank_files("Refactor src/utils/math.ts", &prompt_terms("Refactor src/utils/math.ts"), &paths, &[]);
+        assert_eq!(named[0].path, "src/utils/math.ts");
+        assert!(named[0].reasons.contains(&"Named in the prompt".to_string()));
+    }
+
+    #[test]
+    fn test_suggest_files_from_index() {
+        let dir = tempfile::tempdir().unwrap();
+        fs::create_dir_all(dir.path().join("src")).unwrap();
+        fs::create_dir_all(dir.path().join(".claude")).unwrap();
+        fs::write(
+            dir.path().join("src/billing.ts"),
+            "/**\n * @module src/billing\n * @description Stripe invoice creation and refunds\n *\n * EXPORTS:\n * - createInvoice - Create an invoice\n */\nexport function createInvoice() {}\n",
+        )
+        .unwrap();
+        fs::write(dir.path().join("src/secret.ts"), "export function refundInvoice() {}\n").unwrap();
+        fs::write(dir.path().join("src/other.ts"), "export function unrelated() {}\n").unwrap();
+        fs::write(dir.path().join(".claude/ai-exclude"), "src/secret.ts\n").unwrap();
+        let project_path = dir.path().to_string_lossy().to_string();
+
+        let db = memory_db();
+        let suggestions = suggest_files(&db, "p1", &project_path, "Refunds should create a credit invoice", 5).unwrap();
+        let paths: Vec<&str> = suggestions.iter().map(|s| s.path.as_str()).collect();
+        assert_eq!(paths, vec!["src/billing.ts"]);
+        assert!(suggestions[0].score >= 60);
+        assert_eq!(
+            indexed_description(&db, "p1", "src/billing.ts").as_deref(),
+            Some("Stripe invoice creation and refunds")
+        );
+        assert_eq!(indexed_description(&db, "p1", "src/other.ts"), None);
+    }
+}
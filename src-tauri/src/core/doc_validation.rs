@@ -0,0 +1,273 @@
+//! @module core/doc_validation
+//! @description Cross-check doc header DEPENDENCIES and EXPORTS against detected imports and exports
+//!
+//! PURPOSE:
+//! - Flag phantom dependencies (documented but not imported) and undocumented imports
+//! - Flag phantom exports (documented but gone) and undocumented exports
+//! - Collect lint findings from analyzer plugins (in-house languages and DSLs)
+//! - Roll per-file findings up into project-level lint counts
+//!
+//! DEPENDENCIES:
+//! - core::analyzer - Header parsing and import/export detection
+//! - core::freshness - Documented files with their freshness status, entry name extraction
+//! - core::plugins - Lint findings from the analyzer plugin for a file's extension
+//! - models::module_doc - ModuleDoc, DocFileValidation, DocValidationReport
+//!
+//! EXPORTS:
+//! - validate_file - Findings for one documented file
+//! - validate_project - Findings for every documented file, with totals
+//! - expand_rust_use - Split a `use` tree like "core::{a, b}" into full paths
+//!
+//! PATTERNS:
+//! - Only documented entries that look project-local are checked for phantoms; external
+//!   crates/packages ("tauri", "react") are never detected as imports so they are skipped
+//! - An entry is project-local when it starts with "./", "../", "@/" or "crate::", or its first
+//!   segment is the first segment of an import detected anywhere in the project
+//! - Rust use trees are expanded before matching so "core::freshness" matches
+//!   "core::{analyzer, freshness}"
+//!
+//! CLAUDE NOTES:
+//! - Files without a header ("missing") are skipped; they have nothing to validate
+//! - Matching is by containment, like the freshness signals, so "db::AppState" matches "db::AppState"
+//!   and "core::events" matches "core::events::AppEvent"
+
+use std::collections::HashSet;
+use std::path::Path;
+
+use crate::core::{analyzer, freshness, plugins};
+use crate::models::module_doc::{DocFileValidation, DocValidationReport, ModuleDoc};
+
+/// Findings for one documented file. `local_roots` are first path segments of
+/// imports seen across the project (see collect_local_roots).
+pub fn validate_file(
+    rel_path: &str,
+    status: &str,
+    content: &str,
+    doc: &ModuleDoc,
+    local_roots: &HashSet<String>,
+) -> DocFileValidation {
+    let ext = Path::new(rel_path).extension().and_then(|e| e.to_str()).unwrap_or("");
+    let imports = expanded_imports(content, ext);
+    let documented_deps = freshness::extract_dependency_paths(&doc.dependencies);
+
+    let phantom_dependencies = documented_deps
+        .iter()
+        .filter(|dep| is_local(dep, local_roots))
+        .filter(|dep| !imports.iter().any(|i| matches(i, dep)))
+        .cloned()
+        .collect();
+    let mut seen = HashSet::new();
+    let undocumented_dependencies = imports
+        .iter()
+        .filter(|i| !documented_deps.iter().any(|d| matches(i, d)))
+        .filter(|i| seen.insert(i.as_str()))
+        .cloned()
+        .collect();
+
+    let actual_exports = analyzer::detect_exports(content, ext);
+    let documented_exports = freshness::extract_export_names(&doc.exports);
+    let same = |a: &str, b: &str| freshness::strip_paren_suffix(a).eq_ignore_ascii_case(freshness::strip_paren_suffix(b));
+    let phantom_exports = documented_exports
+        .iter()
+        .filter(|d| !actual_exports.iter().any(|a| same(a, d)))
+        .cloned()
+        .collect();
+    let undocumented_exports = actual_exports
+        .iter()
+        .filter(|a| !documented_exports.iter().any(|d| same(a, d)))
+        .cloned()
+        .collect();
+
+    let plugin_findings = plugins::analysis_for(ext, content)
+        .map(|(plugin, analysis)| {
+            analysis
+                .lint
+                .iter()
+                .map(|f| match f.line {
+                    Some(line) => format!("{}: {} (line {})", plugin.name, f.message, line),
+                    None => format!("{}: {}", plugin.name, f.message),
+                })
+                .collect()
+        })
+        .unwrap_or_default();
+
+    DocFileValidation {
+        path: rel_path.to_string(),
+        status: status.to_string(),
+        phantom_dependencies,
+        undocumented_dependencies,
+        phantom_exports,
+        undocumented_exports,
+        plugin_findings,
+    }
+}
+
+/// Validate every documented file in the project. Files with findings come
+/// first, outdated before current.
+pub fn validate_project(project_id: &str, project_path: &str) -> Result<DocValidationReport, String> {
+    let documented: Vec<(String, String, String, ModuleDoc)> = freshness::check_project_freshness(project_path)?
+        .into_iter()
+        .filter(|m| m.status != "missing")
+        .filter_map(|m| {
+            let content = analyzer::read_source(&Path::new(project_path).join(&m.path).to_string_lossy()).ok()?;
+            let doc = analyzer::parse_doc_header(&content)?;
+            Some((m.path, m.status, content, doc))
+        })
+        .collect();
+
+    let local_roots = collect_local_roots(documented.iter().map(|(path, _, content, _)| (path.as_str(), content.as_str())));
+    let mut files: Vec<DocFileValidation> = documented
+        .iter()
+        .map(|(path, status, content, doc)| validate_file(path, status, content, doc, &local_roots))
+        .collect();
+    let files_checked = files.len() as u32;
+    files.retain(|f| f.issue_count() > 0);
+    files.sort_by(|a, b| (a.status != "outdated").cmp(&(b.status != "outdated")).then_with(|| a.path.cmp(&b.path)));
+
+    let count = |f: fn(&DocFileValidation) -> usize| files.iter().map(f).sum::<usize>() as u32;
+    Ok(DocValidationReport {
+        project_id: project_id.to_string(),
+        files_checked,
+        files_with_issues: files.len() as u32,
+        phantom_dependencies: count(|f| f.phantom_dependencies.len()),
+        undocumented_dependencies: count(|f| f.undocumented_dependencies.len()),
+        phantom_exports: count(|f| f.phantom_exports.len()),
+        undocumented_exports: count(|f| f.undocumented_exports.len()),
+        plugin_findings: count(|f| f.plugin_findings.len()),
+        files,
+    })
+}
+
+/// Split a Rust use tree into full paths: "core::{analyzer, freshness}" ->
+/// ["core::analyzer", "core::freshness"], "db::{self, AppState}" -> ["db", "db::AppState"].
+pub fn expand_rust_use(import: &str) -> Vec<String> {
+    let Some(open) = import.find('{') else {
+        return vec![import.trim().to_string()];
+    };
+    let prefix = import[..open].trim_end_matches("::");
+    let inner = import[open + 1..].trim_end_matches('}');
+    inner
+        .split(',')
+        .map(str::trim)
+        .filter(|item| !item.is_empty())
+        .map(|item| {
+            let item = item.split(" as ").next().unwrap_or(item).trim();
+            if item == "self" {
+                prefix.to_string()
+            } else {
+                format!("{}::{}", prefix, item.trim_end_matches("::{self}"))
+            }
+        })
+        .collect()
+}
+
+fn expand_if_rust(import: &str, ext: &str) -> Vec<String> {
+    if ext == "rs" {
+        expand_rust_use(import)
+    } else {
+        vec![import.to_string()]
+    }
+}
+
+fn expanded_imports(content: &str, ext: &str) -> Vec<String> {
+    analyzer::detect_imports(content, ext)
+        .iter()
+        .flat_map(|i| expand_if_rust(i, ext))
+        .collect()
+}
+
+fn matches(import: &str, documented: &str) -> bool {
+    let documented = normalize(documented);
+    let import = normalize(import);
+    !documented.is_empty() && (import.contains(documented) || documented.contains(import))
+}
+
+fn normalize(entry: &str) -> &str {
+    entry.trim().trim_start_matches("crate::").trim_start_matches("@/")
+}
+
+/// First path segment of an import or documented entry ("core" for "core::analyzer",
+/// "components" for "@/components/Button", "utils" for "../utils/format").
+fn first_segment(entry: &str) -> &str {
+    let trimmed = normalize(entry).trim_start_matches("./").trim_start_matches("../");
+    trimmed.split([':', '/', '.']).next().unwrap_or("")
+}
+
+fn is_local(dep: &str, local_roots: &HashSet<String>) -> bool {
+    let dep = dep.trim();
+    dep.starts_with("./")
+        || dep.starts_with("../")
+        || dep.starts_with("@/")
+        || dep.starts_with("crate::")
+        || local_roots.contains(first_segment(dep))
+}
+
+fn collect_local_roots<'a>(files: impl Iterator<Item = (&'a str, &'a str)>) -> HashSet<String> {
+    files
+        .flat_map(|(path, content)| {
+            let ext = Path::new(path).extension().and_then(|e| e.to_str()).unwrap_or("");
+            analyzer::detect_imports(content, ext)
+        })
+        .map(|i| first_segment(&i).to_string())
+        .filter(|s| !s.is_empty())
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn doc(deps: &[&str], exports: &[&str]) -> ModuleDoc {
+        ModuleDoc {
+            module_path: "commands/example".to_string(),
+            description: "Example".to_string(),
+            purpose: vec!["Example".to_string()],
+            dependencies: deps.iter().map(|s| s.to_string()).collect(),
+            exports: exports.iter().map(|s| s.to_string()).collect(),
+            patterns: vec![],
+            claude_notes: vec![],
+        }
+    }
+
+    #[test]
+    fn test_expand_rust_use() {
+        assert_eq!(expand_rust_use("core::{analyzer, freshness}"), vec!["core::analyzer", "core::freshness"]);
+        assert_eq!(expand_rust_use("db::{self, AppState}"), vec!["db", "db::AppState"]);
+        assert_eq!(expand_rust_use("models::project::Project"), vec!["models::project::Project"]);
+    }
+
+    #[test]
+    fn test_validate_file_rust() {
+        let content = "use tauri::State;\nuse crate::core::{analyzer, freshness};\nuse crate::db::AppState;\n\npub fn scan() {}\n";
+        let doc = doc(
+            &["tauri - Command macro", "core::analyzer - Parsing", "models::project - Gone", "db::AppState - State"],
+            &["scan - Scan files", "removed_fn - No longer here"],
+        );
+        let roots: HashSet<String> = ["core", "db", "models"].iter().map(|s| s.to_string()).collect();
+        let result = validate_file("src/commands/example.rs", "current", content, &doc, &roots);
+
+        // "tauri" is external and never detected, so it is not a phantom
+        assert_eq!(result.phantom_dependencies, vec!["models::project"]);
+        assert_eq!(result.undocumented_dependencies, vec!["core::freshness"]);
+        assert_eq!(result.phantom_exports, vec!["removed_fn"]);
+        assert!(result.undocumented_exports.is_empty());
+    }
+
+    #[test]
+    fn test_validate_project_counts() {
+        let dir = tempfile::tempdir().unwrap();
+        let root = dir.path();
+        std::fs::create_dir_all(root.join("src/utils")).unwrap();
+        std::fs::write(
+            root.join("src/utils/format.ts"),
+            "/**\n * @module utils/format\n * @description Formatting\n *\n * PURPOSE:\n * - Format values\n *\n * DEPENDENCIES:\n * - ./dates - Date helpers\n *\n * EXPORTS:\n * - formatDate - Format a date\n */\n\nimport { parse } from \"./parse\";\n\nexport function formatDate() {}\n",
+        )
+        .unwrap();
+
+        let report = validate_project("p1", root.to_str().unwrap()).unwrap();
+        assert_eq!(report.files_checked, 1);
+        assert_eq!(report.phantom_dependencies, 1);
+        assert_eq!(report.undocumented_dependencies, 1);
+        assert_eq!(report.files[0].undocumented_dependencies, vec!["./parse"]);
+    }
+}
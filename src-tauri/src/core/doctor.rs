@@ -0,0 +1,509 @@
+//! @module core/doctor
+//! @description Self-diagnostics: database, tools, secrets, background jobs, and project paths
+//!
+//! PURPOSE:
+//! - Check database integrity and that every migration ran
+//! - Check git and jq (used by the generated hooks) are installed
+//! - Check stored secrets can be decrypted on this machine and the API key is accepted
+//! - Find background jobs left "running" by a previous app session, and projects whose folder is gone
+//! - Run the one-click fixes offered by the checks
+//!
+//! DEPENDENCIES:
+//! - rusqlite - PRAGMA quick_check, user_version, and job/project queries
+//! - db::schema / db::run_migrations - Schema version, migrated columns, and the migration fix
+//! - core::crypto - Decrypting stored "enc:" settings
+//! - core::proc - git --version / jq --version with a short timeout
+//! - core::ralph_preflight - Claude CLI and disk space checks (converted with from_preflight)
+//! - models::doctor - DoctorCheck
+//!
+//! EXPORTS:
+//! - FIX_RUN_MIGRATIONS / FIX_REINDEX_DB / FIX_FAIL_ORPHANED_JOBS / FIX_CLEAR_UNDECRYPTABLE_SECRETS - fix_action ids
+//! - mark_app_started - Record when this app session started (call once at startup)
+//! - from_preflight - Convert a RALPH preflight check into a DoctorCheck
+//! - check_integrity / check_schema / check_tool / check_key_storage / check_project_paths - Individual checks
+//! - api_key_check - Classify the API key probe's HTTP status (or error)
+//! - undecryptable_settings - Settings whose encrypted value no longer decrypts
+//! - OrphanedJobs / orphaned_jobs / check_orphaned_jobs - Jobs left running by an earlier session
+//! - apply_fix - Run a fix_action
+//!
+//! PATTERNS:
+//! - Statuses match RalphPreflightCheck: "pass" | "warn" | "fail" | "skipped"
+//! - Checks never return errors; a check that cannot run reports "warn" or "skipped" with the reason
+//!
+//! CLAUDE NOTES:
+//! - RALPH loops and rule actions run on threads of the app process, so anything still "running"
+//!   that started before this session began is orphaned; test runs are orphaned when they have no
+//!   cancel flag in AppState.test_runs
+//! - The machine ID derives the secret encryption key; when it changes (new machine, restored
+//!   backup) old "enc:" values cannot be decrypted and must be re-entered
+
+use std::collections::HashSet;
+use std::path::Path;
+use std::process::Command;
+use std::sync::OnceLock;
+use std::time::Duration;
+
+use chrono::{DateTime, Utc};
+use rusqlite::Connection;
+
+use crate::core::crypto;
+use crate::core::proc::{self, ProcLimits};
+use crate::db::{self, schema};
+use crate::models::doctor::DoctorCheck;
+use crate::models::ralph::RalphPreflightCheck;
+
+pub const FIX_RUN_MIGRATIONS: &str = "run_migrations";
+pub const FIX_REINDEX_DB: &str = "reindex_db";
+pub const FIX_FAIL_ORPHANED_JOBS: &str = "fail_orphaned_jobs";
+pub const FIX_CLEAR_UNDECRYPTABLE_SECRETS: &str = "clear_undecryptable_secrets";
+
+/// git --version, jq --version.
+const CHECK_LIMITS: ProcLimits = ProcLimits::new(Duration::from_secs(15), 64 * 1024);
+
+/// Outcome written to loops and rule runs closed by the fail_orphaned_jobs fix.
+const ORPHANED_OUTCOME: &str = "Interrupted: the app closed while this was running";
+
+static APP_STARTED: OnceLock<DateTime<Utc>> = OnceLock::new();
+
+/// Record when this app session started. Later calls keep the first time.
+pub fn mark_app_started() {
+    let _ = APP_STARTED.set(Utc::now());
+}
+
+fn app_started() -> DateTime<Utc> {
+    *APP_STARTED.get_or_init(Utc::now)
+}
+
+fn check(id: &str, label: &str, status: &str, detail: String, fix: Option<&str>, fix_action: Option<&str>) -> DoctorCheck {
+    DoctorCheck {
+        id: id.to_string(),
+        label: label.to_string(),
+        status: status.to_string(),
+        detail,
+        fix: fix.map(str::to_string),
+        fix_action: fix_action.map(str::to_string),
+    }
+}
+
+/// A RALPH preflight check as a doctor check (no one-click fix).
+pub fn from_preflight(c: RalphPreflightCheck) -> DoctorCheck {
+    DoctorCheck {
+        id: c.id,
+        label: c.label,
+        status: c.status,
+        detail: c.detail,
+        fix: c.fix,
+        fix_action: None,
+    }
+}
+
+/// PRAGMA quick_check on the app database.
+pub fn check_integrity(db: &Connection) -> DoctorCheck {
+    let label = "Database integrity";
+    let rows: Result<Vec<String>, rusqlite::Error> = db.prepare("PRAGMA quick_check").and_then(|mut stmt| {
+        let rows = stmt.query_map([], |row| row.get(0))?.collect();
+        rows
+    });
+    match rows {
+        Ok(rows) if rows.len() == 1 && rows[0] == "ok" => {
+            check("db_integrity", label, "pass", "No problems found".to_string(), None, None)
+        }
+        Ok(rows) => check(
+            "db_integrity",
+            label,
+            "fail",
+            rows.iter().take(3).cloned().collect::<Vec<_>>().join("; "),
+            Some("Rebuild the indexes; if problems remain, restore ~/.project-jumpstart/jumpstart.db from a backup."),
+            Some(FIX_REINDEX_DB),
+        ),
+        Err(e) => check(
+            "db_integrity",
+            label,
+            "fail",
+            format!("Integrity check could not run: {}", e),
+            Some("Restore ~/.project-jumpstart/jumpstart.db from a backup."),
+            None,
+        ),
+    }
+}
+
+/// Schema version and migrated columns.
+pub fn check_schema(db: &Connection) -> DoctorCheck {
+    let label = "Database schema";
+    let version = schema::schema_version(db).unwrap_or(0);
+    let missing: Vec<String> = schema::MIGRATED_COLUMNS
+        .iter()
+        .filter(|(table, column)| db.prepare(&format!("SELECT {} FROM {} LIMIT 0", column, table)).is_err())
+        .map(|(table, column)| format!("{}.{}", table, column))
+        .collect();
+
+    if !missing.is_empty() || version < schema::SCHEMA_VERSION {
+        let mut detail = format!("Schema version {} (expected {})", version, schema::SCHEMA_VERSION);
+        if !missing.is_empty() {
+            detail.push_str(&format!("; missing columns: {}", missing.join(", ")));
+        }
+        return check(
+            "schema_version",
+            label,
+            "fail",
+            detail,
+            Some("Run the database migrations."),
+            Some(FIX_RUN_MIGRATIONS),
+        );
+    }
+    if version > schema::SCHEMA_VERSION {
+        return check(
+            "schema_version",
+            label,
+            "warn",
+            format!("Schema version {} is newer than this app ({})", version, schema::SCHEMA_VERSION),
+            Some("Update Project Jumpstart; an older app may not read newer data correctly."),
+            None,
+        );
+    }
+    check("schema_version", label, "pass", format!("Schema version {}", version), None, None)
+}
+
+/// `<program> --version`. A missing tool gets `missing_status` ("fail" or "warn").
+pub fn check_tool(id: &str, label: &str, program: &str, missing_status: &str, fix: &str) -> DoctorCheck {
+    match proc::run(Command::new(program).arg("--version"), CHECK_LIMITS) {
+        Ok(out) if out.success() => {
+            let stdout = String::from_utf8_lossy(&out.stdout);
+            let version = stdout.lines().map(str::trim).find(|l| !l.is_empty()).unwrap_or(program);
+            check(id, label, "pass", version.to_string(), None, None)
+        }
+        Ok(_) => check(id, label, missing_status, format!("`{} --version` failed", program), Some(fix), None),
+        Err(_) => check(id, label, missing_status, format!("{} was not found on PATH", program), Some(fix), None),
+    }
+}
+
+/// API key probe result: the HTTP status of an authenticated request, or the request error.
+pub fn api_key_check(status: Result<u16, String>) -> DoctorCheck {
+    let label = "API key";
+    match status {
+        Ok(200..=299) => check("api_key", label, "pass", "Accepted by the Anthropic API".to_string(), None, None),
+        Ok(401) | Ok(403) => check(
+            "api_key",
+            label,
+            "fail",
+            "The Anthropic API rejected the key".to_string(),
+            Some("Create a new key in the Anthropic Console and save it in Settings."),
+            None,
+        ),
+        Ok(code) => check(
+            "api_key",
+            label,
+            "warn",
+            format!("The Anthropic API answered HTTP {}", code),
+            Some("Try again later; the API may be unavailable."),
+            None,
+        ),
+        Err(e) => check(
+            "api_key",
+            label,
+            "warn",
+            format!("Could not reach the Anthropic API: {}", e),
+            Some("Check your network connection or proxy settings."),
+            None,
+        ),
+    }
+}
+
+/// Keys of settings whose "enc:" value does not decrypt with this machine's key.
+pub fn undecryptable_settings(db: &Connection) -> Vec<String> {
+    let rows: Vec<(String, String)> = match db.prepare("SELECT key, value FROM settings WHERE value LIKE 'enc:%'") {
+        Ok(mut stmt) => stmt
+            .query_map([], |row| Ok((row.get(0)?, row.get(1)?)))
+            .map(|rows| rows.filter_map(|r| r.ok()).collect())
+            .unwrap_or_default(),
+        Err(_) => Vec::new(),
+    };
+    rows.into_iter()
+        .filter(|(_, value)| crypto::decrypt(&value["enc:".len()..]).is_err())
+        .map(|(key, _)| key)
+        .collect()
+}
+
+/// Secret encryption: machine ID available, encryption round-trips, stored secrets decrypt.
+pub fn check_key_storage(db: &Connection) -> DoctorCheck {
+    let label = "Secret storage";
+    let round_trip = crypto::encrypt("jumpstart-doctor").and_then(|enc| crypto::decrypt(&enc));
+    if !matches!(round_trip.as_deref(), Ok("jumpstart-doctor")) {
+        return check(
+            "key_storage",
+            label,
+            "fail",
+            "Secrets cannot be encrypted on this machine".to_string(),
+            Some("Report this with the app logs attached."),
+            None,
+        );
+    }
+
+    let broken = undecryptable_settings(db);
+    if !broken.is_empty() {
+        return check(
+            "key_storage",
+            label,
+            "fail",
+            format!("{} stored secret(s) cannot be decrypted on this machine: {}", broken.len(), broken.join(", ")),
+            Some("Clear them, then re-enter the keys in Settings."),
+            Some(FIX_CLEAR_UNDECRYPTABLE_SECRETS),
+        );
+    }
+    if machine_uid::get().is_err() {
+        return check(
+            "key_storage",
+            label,
+            "warn",
+            "No machine ID; secrets are encrypted with a fallback key".to_string(),
+            Some("Secrets still work, but are protected less strongly than usual."),
+            None,
+        );
+    }
+    check("key_storage", label, "pass", "Machine-bound encryption available".to_string(), None, None)
+}
+
+/// Background jobs left "running" by an earlier app session.
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct OrphanedJobs {
+    pub loops: Vec<String>,
+    pub test_runs: Vec<String>,
+    pub rule_runs: Vec<String>,
+}
+
+impl OrphanedJobs {
+    fn total(&self) -> usize {
+        self.loops.len() + self.test_runs.len() + self.rule_runs.len()
+    }
+}
+
+/// Ids of `table` rows in `status` whose `time_column` is before `before` (or missing).
+fn started_before(db: &Connection, table: &str, status: &str, time_column: &str, before: DateTime<Utc>) -> Vec<String> {
+    let sql = format!("SELECT id, {} FROM {} WHERE status = ?1", time_column, table);
+    let rows: Vec<(String, Option<String>)> = match db.prepare(&sql) {
+        Ok(mut stmt) => stmt
+            .query_map([status], |row| Ok((row.get(0)?, row.get(1)?)))
+            .map(|rows| rows.filter_map(|r| r.ok()).collect())
+            .unwrap_or_default(),
+        Err(_) => Vec::new(),
+    };
+    rows.into_iter()
+        .filter(|(_, at)| {
+            at.as_deref()
+                .and_then(|at| DateTime::parse_from_rfc3339(at).ok())
+                .is_none_or(|at| at.with_timezone(&Utc) < before)
+        })
+        .map(|(id, _)| id)
+        .collect()
+}
+
+/// Running loops and rule runs started before this session, and running test runs without a
+/// live cancel flag.
+pub fn orphaned_jobs(db: &Connection, live_test_runs: &HashSet<String>) -> OrphanedJobs {
+    let started = app_started();
+    let test_runs = match db.prepare("SELECT id FROM test_runs WHERE status = 'running'") {
+        Ok(mut stmt) => stmt
+            .query_map([], |row| row.get::<_, String>(0))
+            .map(|rows| rows.filter_map(|r| r.ok()).filter(|id| !live_test_runs.contains(id)).collect())
+            .unwrap_or_default(),
+        Err(_) => Vec::new(),
+    };
+    OrphanedJobs {
+        loops: started_before(db, "ralph_loops", "running", "started_at", started),
+        test_runs,
+        rule_runs: started_before(db, "rule_runs", "started", "fired_at", started),
+    }
+}
+
+pub fn check_orphaned_jobs(orphans: &OrphanedJobs) -> DoctorCheck {
+    let label = "Background jobs";
+    if orphans.total() == 0 {
+        return check("orphaned_jobs", label, "pass", "No interrupted jobs".to_string(), None, None);
+    }
+    check(
+        "orphaned_jobs",
+        label,
+        "warn",
+        format!(
+            "Left running by an earlier session: {} RALPH loop(s), {} test run(s), {} rule run(s)",
+            orphans.loops.len(),
+            orphans.test_runs.len(),
+            orphans.rule_runs.len()
+        ),
+        Some("Mark them as interrupted so they stop showing as running."),
+        Some(FIX_FAIL_ORPHANED_JOBS),
+    )
+}
+
+/// Projects whose folder no longer exists.
+pub fn check_project_paths(db: &Connection) -> DoctorCheck {
+    let label = "Project folders";
+    let projects: Vec<(String, String)> = match db.prepare("SELECT name, path FROM projects ORDER BY name") {
+        Ok(mut stmt) => stmt
+            .query_map([], |row| Ok((row.get(0)?, row.get(1)?)))
+            .map(|rows| rows.filter_map(|r| r.ok()).collect())
+            .unwrap_or_default(),
+        Err(e) => {
+            return check("project_paths", label, "skipped", format!("Could not list projects: {}", e), None, None);
+        }
+    };
+    let broken: Vec<String> = projects
+        .iter()
+        .filter(|(_, path)| !Path::new(path).is_dir())
+        .map(|(name, path)| format!("{} ({})", name, path))
+        .collect();
+    if broken.is_empty() {
+        return check(
+            "project_paths",
+            label,
+            "pass",
+            format!("All {} project folder(s) found", projects.len()),
+            None,
+            None,
+        );
+    }
+    check(
+        "project_paths",
+        label,
+        "warn",
+        format!("Missing: {}", broken.join(", ")),
+        Some("Update each project's location from its settings, or remove the project."),
+        None,
+    )
+}
+
+/// Run a fix_action. Returns a short description of what changed.
+pub fn apply_fix(db: &Connection, action: &str, live_test_runs: &HashSet<String>) -> Result<String, String> {
+    match action {
+        FIX_RUN_MIGRATIONS => {
+            db::run_migrations(db)?;
+            Ok(format!("Database migrated to schema version {}", schema::SCHEMA_VERSION))
+        }
+        FIX_REINDEX_DB => {
+            db.execute_batch("REINDEX;").map_err(|e| format!("Failed to rebuild indexes: {}", e))?;
+            Ok("Rebuilt database indexes".to_string())
+        }
+        FIX_FAIL_ORPHANED_JOBS => {
+            let orphans = orphaned_jobs(db, live_test_runs);
+            let now = Utc::now().to_rfc3339();
+            for id in &orphans.loops {
+                db.execute(
+                    "UPDATE ralph_loops SET status = 'failed', outcome = ?1, completed_at = ?2 WHERE id = ?3 AND status = 'running'",
+                    rusqlite::params![ORPHANED_OUTCOME, now, id],
+                )
+                .map_err(|e| format!("Failed to update loop: {}", e))?;
+            }
+            for id in &orphans.test_runs {
+                db.execute(
+                    "UPDATE test_runs SET status = 'cancelled', completed_at = ?1 WHERE id = ?2 AND status = 'running'",
+                    rusqlite::params![now, id],
+                )
+                .map_err(|e| format!("Failed to update test run: {}", e))?;
+            }
+            for id in &orphans.rule_runs {
+                db.execute(
+                    "UPDATE rule_runs SET status = 'failed', output = ?1, finished_at = ?2 WHERE id = ?3 AND status = 'started'",
+                    rusqlite::params![ORPHANED_OUTCOME, now, id],
+                )
+                .map_err(|e| format!("Failed to update rule run: {}", e))?;
+            }
+            Ok(format!("Marked {} interrupted job(s)", orphans.total()))
+        }
+        FIX_CLEAR_UNDECRYPTABLE_SECRETS => {
+            let keys = undecryptable_settings(db);
+            for key in &keys {
+                db.execute("DELETE FROM settings WHERE key = ?1", [key])
+                    .map_err(|e| format!("Failed to clear {}: {}", key, e))?;
+            }
+            Ok(format!("Cleared {} secret(s); re-enter them in Settings", keys.len()))
+        }
+        other => Err(format!("Unknown doctor fix: {}", other)),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_support::memory_db;
+
+    fn migrated_db() -> Connection {
+        let db = memory_db();
+        db::run_migrations(&db).unwrap();
+        db
+    }
+
+    #[test]
+    fn test_check_schema_and_migration_fix() {
+        let db = migrated_db();
+        assert_eq!(check_schema(&db).status, "pass");
+
+        db.execute_batch("PRAGMA user_version = 3;").unwrap();
+        let stale = check_schema(&db);
+        assert_eq!(stale.status, "fail");
+        assert_eq!(stale.fix_action.as_deref(), Some(FIX_RUN_MIGRATIONS));
+
+        apply_fix(&db, FIX_RUN_MIGRATIONS, &HashSet::new()).unwrap();
+        assert_eq!(check_schema(&db).status, "pass");
+        assert_eq!(check_integrity(&db).status, "pass");
+    }
+
+    #[test]
+    fn test_orphaned_jobs_and_fix() {
+        mark_app_started();
+        let db = migrated_db();
+        db.execute_batch(
+            "INSERT INTO projects (id, name, path, created_at) VALUES ('p1', 'Test', '/nonexistent/jumpstart-doctor', '2025-01-01T00:00:00Z');
+             INSERT INTO ralph_loops (id, project_id, prompt, status, started_at, created_at)
+             VALUES ('old', 'p1', 'Fix', 'running', '2025-01-01T00:00:00Z', '2025-01-01T00:00:00Z');
+             INSERT INTO test_plans (id, project_id, name, created_at, updated_at) VALUES ('plan1', 'p1', 'Plan', '2025-01-01T00:00:00Z', '2025-01-01T00:00:00Z');
+             INSERT INTO test_runs (id, plan_id, status, started_at) VALUES ('live', 'plan1', 'running', '2025-01-01T00:00:00Z');
+             INSERT INTO test_runs (id, plan_id, status, started_at) VALUES ('dead', 'plan1', 'running', '2025-01-01T00:00:00Z');",
+        )
+        .unwrap();
+        db.execute(
+            "INSERT INTO ralph_loops (id, project_id, prompt, status, started_at, created_at) VALUES ('new', 'p1', 'Fix', 'running', ?1, ?1)",
+            [Utc::now().to_rfc3339()],
+        )
+        .unwrap();
+
+        let live: HashSet<String> = ["live".to_string()].into_iter().collect();
+        let orphans = orphaned_jobs(&db, &live);
+        assert_eq!(orphans.loops, vec!["old".to_string()]);
+        assert_eq!(orphans.test_runs, vec!["dead".to_string()]);
+        assert_eq!(check_orphaned_jobs(&orphans).status, "warn");
+
+        apply_fix(&db, FIX_FAIL_ORPHANED_JOBS, &live).unwrap();
+        assert_eq!(orphaned_jobs(&db, &live), OrphanedJobs::default());
+        let status: String = db.query_row("SELECT status FROM ralph_loops WHERE id = 'new'", [], |r| r.get(0)).unwrap();
+        assert_eq!(status, "running");
+
+        assert_eq!(check_project_paths(&db).status, "warn");
+    }
+
+    #[test]
+    fn test_undecryptable_settings_are_cleared() {
+        let db = migrated_db();
+        let good = format!("enc:{}", crypto::encrypt("sk-ant-good").unwrap());
+        db.execute(
+            "INSERT INTO settings (key, value) VALUES ('anthropic_api_key', ?1), ('github_token', 'enc:not-base64!')",
+            [good],
+        )
+        .unwrap();
+
+        assert_eq!(undecryptable_settings(&db), vec!["github_token".to_string()]);
+        let storage = check_key_storage(&db);
+        assert_eq!(storage.fix_action.as_deref(), Some(FIX_CLEAR_UNDECRYPTABLE_SECRETS));
+
+        apply_fix(&db, FIX_CLEAR_UNDECRYPTABLE_SECRETS, &HashSet::new()).unwrap();
+        assert!(undecryptable_settings(&db).is_empty());
+        assert!(apply_fix(&db, "format_disk", &HashSet::new()).is_err());
+    }
+
+    #[test]
+    fn test_api_key_check() {
+        assert_eq!(api_key_check(Ok(200)).status, "pass");
+        assert_eq!(api_key_check(Ok(401)).status, "fail");
+        assert_eq!(api_key_check(Ok(529)).status, "warn");
+        assert_eq!(api_key_check(Err("timed out".into())).status, "warn");
+    }
+}
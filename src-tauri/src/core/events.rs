@@ -0,0 +1,429 @@
+//! @module core/events
+//! @description Internal event bus that fans structured app events out to subscribers
+//!
+//! PURPOSE:
+//! - Define AppEvent, the structured events commands and core modules publish
+//! - Deliver each published event to every registered subscriber
+//! - Provide the built-in subscribers: activity log, notifications, webhooks, Tauri events
+//!
+//! DEPENDENCIES:
+//! - rusqlite - Connection passed to subscribers (activity rows, settings lookups)
+//! - tauri - AppHandle and Emitter for frontend events
+//! - reqwest - Webhook delivery
+//! - db::log_activity_db - Activity feed insert
+//! - models::activity - ActivityType
+//! - core::rules - RulesSubscriber (automation rules)
+//!
+//! EXPORTS:
+//! - AppEvent - Structured event (scan_completed, loop_iteration_finished, doc_applied,
+//!   digest_generated, health_alert, activity)
+//! - Subscriber - Trait implemented by event consumers
+//! - EventBus - Ordered list of subscribers
+//! - ActivityLogSubscriber - Writes the event's activity feed entry
+//! - TauriEventSubscriber - Emits every event to the frontend as "app-event"
+//! - NotificationSubscriber - Emits "notification" for events worth surfacing to the user
+//! - WebhookSubscriber - POSTs events to the URL in the events.webhook_url setting
+//! - publish - Publish an event on the global bus
+//! - subscribe - Register a subscriber on the global bus
+//! - install_app_subscribers - Register the notification, webhook, Tauri, and rules subscribers at startup
+//! - APP_EVENT, NOTIFICATION_EVENT - Tauri event names
+//!
+//! PATTERNS:
+//! - Publishers call events::publish(&db, event) with the DB lock they already hold;
+//!   subscribers use that connection and must never lock AppState.db themselves
+//! - The global bus always starts with ActivityLogSubscriber, so activity logging
+//!   works in tests and before the Tauri app is set up
+//! - Subscribers are best-effort: failures are swallowed and never reach the publisher
+//! - Webhooks are delivered on the async runtime so publishing never waits on the network
+//!
+//! CLAUDE NOTES:
+//! - Prefer publish over db::log_activity_db in commands; AppEvent::activity covers
+//!   one-off activity messages that don't warrant their own variant
+//! - Settings: notifications.enabled (default on), events.webhook_url (unset = off),
+//!   events.webhook_events (comma-separated event names, empty = all)
+//! - Events serialize as {"type": "<name>", ...fields in camelCase}
+
+use rusqlite::Connection;
+use serde::Serialize;
+use std::path::Path;
+use std::sync::{OnceLock, RwLock};
+use tauri::{AppHandle, Emitter};
+
+use crate::db;
+use crate::models::activity::ActivityType;
+
+/// Tauri event carrying every published AppEvent.
+pub const APP_EVENT: &str = "app-event";
+/// Tauri event carrying a NotificationPayload.
+pub const NOTIFICATION_EVENT: &str = "notification";
+
+/// A structured event published by commands and core modules.
+#[derive(Debug, Clone, Serialize)]
+#[serde(tag = "type", rename_all = "snake_case")]
+pub enum AppEvent {
+    /// A project's files were scanned for doc status
+    #[serde(rename_all = "camelCase")]
+    ScanCompleted {
+        project_id: Option<String>,
+        project_path: String,
+        total: u32,
+        missing: u32,
+        outdated: u32,
+    },
+    /// A RALPH loop finished one iteration (or one PRD story attempt)
+    #[serde(rename_all = "camelCase")]
+    LoopIterationFinished {
+        project_id: String,
+        loop_id: String,
+        iteration: u32,
+        story_index: Option<u32>,
+        /// "passed" | "issues" | "failed" | "needs_review"
+        status: String,
+        issues_count: u32,
+        files_changed: u32,
+        /// Mistake category of a failed iteration ("type_error", "syntax_error", ...)
+        failure_type: Option<String>,
+    },
+    /// A doc header was written into a source file
+    #[serde(rename_all = "camelCase")]
+    DocApplied { project_id: String, file_path: String },
+    /// A weekly digest was generated (scheduled or on demand)
+    #[serde(rename_all = "camelCase")]
+    DigestGenerated {
+        project_id: String,
+        digest_id: String,
+        week_start: String,
+        markdown: String,
+    },
+    /// A health metric crossed (active) or recovered from (inactive) a project alert threshold
+    #[serde(rename_all = "camelCase")]
+    HealthAlert {
+        project_id: String,
+        /// "health_score" | "stale_files" | "failing_tests"
+        metric: String,
+        threshold: u32,
+        value: u32,
+        active: bool,
+        message: String,
+    },
+    /// Any other activity feed entry
+    #[serde(rename_all = "camelCase")]
+    Activity {
+        project_id: String,
+        activity_type: ActivityType,
+        message: String,
+    },
+}
+
+impl AppEvent {
+    /// Shorthand for AppEvent::Activity.
+    pub fn activity(project_id: &str, activity_type: ActivityType, message: &str) -> Self {
+        AppEvent::Activity {
+            project_id: project_id.to_string(),
+            activity_type,
+            message: message.to_string(),
+        }
+    }
+
+    /// Stable snake_case event name (matches the serialized "type").
+    pub fn name(&self) -> &'static str {
+        match self {
+            AppEvent::ScanCompleted { .. } => "scan_completed",
+            AppEvent::LoopIterationFinished { .. } => "loop_iteration_finished",
+            AppEvent::DocApplied { .. } => "doc_applied",
+            AppEvent::DigestGenerated { .. } => "digest_generated",
+            AppEvent::HealthAlert { .. } => "health_alert",
+            AppEvent::Activity { .. } => "activity",
+        }
+    }
+
+    /// The project the event belongs to, when known.
+    pub fn project_id(&self) -> Option<&str> {
+        match self {
+            AppEvent::ScanCompleted { project_id, .. } => project_id.as_deref(),
+            AppEvent::LoopIterationFinished { project_id, .. }
+            | AppEvent::DocApplied { project_id, .. }
+            | AppEvent::DigestGenerated { project_id, .. }
+            | AppEvent::HealthAlert { project_id, .. }
+            | AppEvent::Activity { project_id, .. } => Some(project_id),
+        }
+    }
+
+    /// The activity feed entry for this event, if it gets one.
+    /// Scans and passing iterations are too frequent for the feed.
+    pub fn activity_entry(&self) -> Option<(ActivityType, String)> {
+        match self {
+            AppEvent::ScanCompleted { .. } => None,
+            AppEvent::LoopIterationFinished { .. } => None,
+            AppEvent::DocApplied { file_path, .. } => {
+                let name = Path::new(file_path).file_name().and_then(|n| n.to_str()).unwrap_or("file");
+                Some((ActivityType::Generate, format!("Applied docs to {}", name)))
+            }
+            AppEvent::DigestGenerated { week_start, .. } => Some((
+                ActivityType::Info,
+                format!("Weekly digest generated for the week of {}", week_start.get(..10).unwrap_or(week_start)),
+            )),
+            AppEvent::HealthAlert { message, .. } => Some((ActivityType::Health, message.clone())),
+            AppEvent::Activity { activity_type, message, .. } => Some((*activity_type, message.clone())),
+        }
+    }
+
+    /// A user-facing notification for this event, if it deserves one.
+    pub fn notification(&self) -> Option<String> {
+        match self {
+            AppEvent::LoopIterationFinished { iteration, status, .. } => match status.as_str() {
+                "failed" => Some(format!("RALPH iteration {} failed", iteration)),
+                "needs_review" => Some(format!("RALPH loop paused for review after iteration {}", iteration)),
+                _ => None,
+            },
+            AppEvent::DigestGenerated { .. } => Some("Your weekly digest is ready".to_string()),
+            AppEvent::HealthAlert { active: true, message, .. } => Some(message.clone()),
+            _ => None,
+        }
+    }
+}
+
+/// A consumer of published events.
+pub trait Subscriber: Send + Sync {
+    /// Handle one event. `db` is the publisher's locked connection.
+    fn handle(&self, db: &Connection, event: &AppEvent);
+}
+
+/// An ordered list of subscribers; events are delivered in registration order.
+pub struct EventBus {
+    subscribers: RwLock<Vec<Box<dyn Subscriber>>>,
+}
+
+impl EventBus {
+    /// A bus with no subscribers.
+    pub fn new() -> Self {
+        EventBus {
+            subscribers: RwLock::new(Vec::new()),
+        }
+    }
+
+    pub fn subscribe(&self, subscriber: Box<dyn Subscriber>) {
+        if let Ok(mut subscribers) = self.subscribers.write() {
+            subscribers.push(subscriber);
+        }
+    }
+
+    pub fn publish(&self, db: &Connection, event: &AppEvent) {
+        if let Ok(subscribers) = self.subscribers.read() {
+            for subscriber in subscribers.iter() {
+                subscriber.handle(db, event);
+            }
+        }
+    }
+}
+
+impl Default for EventBus {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/// The global bus, created with the activity log subscriber.
+fn bus() -> &'static EventBus {
+    static BUS: OnceLock<EventBus> = OnceLock::new();
+    BUS.get_or_init(|| {
+        let bus = EventBus::new();
+        bus.subscribe(Box::new(ActivityLogSubscriber));
+        bus
+    })
+}
+
+/// Publish an event on the global bus.
+pub fn publish(db: &Connection, event: AppEvent) {
+    bus().publish(db, &event);
+}
+
+/// Register a subscriber on the global bus.
+pub fn subscribe(subscriber: Box<dyn Subscriber>) {
+    bus().subscribe(subscriber);
+}
+
+/// Register the subscribers that need the running app. Call once from setup.
+pub fn install_app_subscribers(app: &AppHandle, http_client: reqwest::Client) {
+    subscribe(Box::new(TauriEventSubscriber { app: app.clone() }));
+    subscribe(Box::new(NotificationSubscriber { app: app.clone() }));
+    subscribe(Box::new(WebhookSubscriber { http_client }));
+    subscribe(Box::new(crate::core::rules::RulesSubscriber));
+}
+
+fn setting(db: &Connection, key: &str) -> Option<String> {
+    db.query_row("SELECT value FROM settings WHERE key = ?1", [key], |row| row.get(0))
+        .ok()
+}
+
+/// Writes the event's activity feed entry.
+pub struct ActivityLogSubscriber;
+
+impl Subscriber for ActivityLogSubscriber {
+    fn handle(&self, db: &Connection, event: &AppEvent) {
+        if let (Some(project_id), Some((activity_type, message))) = (event.project_id(), event.activity_entry()) {
+            let _ = db::log_activity_db(db, project_id, activity_type, &message);
+        }
+    }
+}
+
+/// Emits every event to the frontend as "app-event".
+pub struct TauriEventSubscriber {
+    app: AppHandle,
+}
+
+impl Subscriber for TauriEventSubscriber {
+    fn handle(&self, _db: &Connection, event: &AppEvent) {
+        let _ = self.app.emit(APP_EVENT, event);
+    }
+}
+
+/// Payload of the "notification" event.
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct NotificationPayload {
+    pub event: String,
+    pub project_id: Option<String>,
+    pub message: String,
+}
+
+/// Emits "notification" for events that deserve one, unless notifications.enabled is "false".
+pub struct NotificationSubscriber {
+    app: AppHandle,
+}
+
+impl Subscriber for NotificationSubscriber {
+    fn handle(&self, db: &Connection, event: &AppEvent) {
+        let Some(message) = event.notification() else {
+            return;
+        };
+        if setting(db, "notifications.enabled").as_deref() == Some("false") {
+            return;
+        }
+        let _ = self.app.emit(
+            NOTIFICATION_EVENT,
+            NotificationPayload {
+                event: event.name().to_string(),
+                project_id: event.project_id().map(str::to_string),
+                message,
+            },
+        );
+    }
+}
+
+/// POSTs events as JSON to the events.webhook_url setting.
+pub struct WebhookSubscriber {
+    http_client: reqwest::Client,
+}
+
+/// Body of a webhook delivery.
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+struct WebhookBody<'a> {
+    event: &'static str,
+    sent_at: String,
+    data: &'a AppEvent,
+}
+
+impl Subscriber for WebhookSubscriber {
+    fn handle(&self, db: &Connection, event: &AppEvent) {
+        let Some(url) = setting(db, "events.webhook_url").filter(|u| !u.trim().is_empty()) else {
+            return;
+        };
+        if !webhook_wants(setting(db, "events.webhook_events").as_deref(), event.name()) {
+            return;
+        }
+        let body = WebhookBody {
+            event: event.name(),
+            sent_at: chrono::Utc::now().to_rfc3339(),
+            data: event,
+        };
+        let Ok(json) = serde_json::to_value(&body) else {
+            return;
+        };
+        let client = self.http_client.clone();
+        tauri::async_runtime::spawn(async move {
+            if let Err(e) = client.post(url.trim()).json(&json).send().await {
+                tracing::warn!(error = %e, "Webhook delivery failed");
+            }
+        });
+    }
+}
+
+/// Whether an events.webhook_events filter (comma-separated names, empty = all) includes `name`.
+fn webhook_wants(filter: Option<&str>, name: &str) -> bool {
+    match filter.map(str::trim).filter(|f| !f.is_empty()) {
+        Some(filter) => filter.split(',').any(|n| n.trim() == name),
+        None => true,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_support::with_project;
+    use std::sync::{Arc, Mutex};
+
+    struct Recorder(Arc<Mutex<Vec<String>>>);
+
+    impl Subscriber for Recorder {
+        fn handle(&self, _db: &Connection, event: &AppEvent) {
+            self.0.lock().unwrap().push(event.name().to_string());
+        }
+    }
+
+    #[test]
+    fn test_bus_delivers_to_subscribers_in_order() {
+        let conn = with_project();
+
+        let seen = Arc::new(Mutex::new(Vec::new()));
+        let bus = EventBus::new();
+        bus.subscribe(Box::new(ActivityLogSubscriber));
+        bus.subscribe(Box::new(Recorder(seen.clone())));
+
+        bus.publish(&conn, &AppEvent::DocApplied { project_id: "p1".into(), file_path: "/tmp/p1/src/a.ts".into() });
+        bus.publish(
+            &conn,
+            &AppEvent::ScanCompleted { project_id: Some("p1".into()), project_path: "/tmp/p1".into(), total: 3, missing: 1, outdated: 0 },
+        );
+
+        assert_eq!(*seen.lock().unwrap(), vec!["doc_applied", "scan_completed"]);
+        // Only the doc_applied event has a feed entry
+        let messages: Vec<String> = conn
+            .prepare("SELECT message FROM activities WHERE project_id = 'p1'")
+            .unwrap()
+            .query_map([], |row| row.get(0))
+            .unwrap()
+            .filter_map(|r| r.ok())
+            .collect();
+        assert_eq!(messages, vec!["Applied docs to a.ts"]);
+    }
+
+    #[test]
+    fn test_event_serialization_and_notifications() {
+        let event = AppEvent::LoopIterationFinished {
+            project_id: "p1".into(),
+            loop_id: "l1".into(),
+            iteration: 2,
+            story_index: None,
+            status: "failed".into(),
+            issues_count: 0,
+            files_changed: 1,
+            failure_type: Some("type_error".into()),
+        };
+        let json = serde_json::to_value(&event).unwrap();
+        assert_eq!(json["type"], "loop_iteration_finished");
+        assert_eq!(json["loopId"], "l1");
+        assert_eq!(json["failureType"], "type_error");
+        assert_eq!(event.notification().as_deref(), Some("RALPH iteration 2 failed"));
+        assert!(AppEvent::activity("p1", ActivityType::Skill, "Created skill: x").notification().is_none());
+    }
+
+    #[test]
+    fn test_webhook_wants() {
+        assert!(webhook_wants(None, "doc_applied"));
+        assert!(webhook_wants(Some(" "), "doc_applied"));
+        assert!(webhook_wants(Some("scan_completed, doc_applied"), "doc_applied"));
+        assert!(!webhook_wants(Some("scan_completed"), "activity"));
+    }
+}
@@ -6,23 +6,52 @@
 //! - Calculate freshness scores (0-100) based on weighted staleness signals
 //! - Generate human-readable change descriptions for stale files
 //! - Provide batch freshness checking for all project files
+//! - Apply per-directory freshness policies (max days a header may lag a code change)
 //!
 //! DEPENDENCIES:
 //! - core::analyzer - parse_doc_header, detect_exports, detect_imports for comparison
-//! - models::module_doc - ModuleStatus, ModuleDoc types
+//! - core::notebook - Notebooks are skipped for change authors (no stable header line range)
+//! - core::plugins - Freshness signals from the analyzer plugin for a file's extension
+//! - core::ai - glob_matches for freshness policy patterns
+//! - core::proc - Time and output limits for the git calls
+//! - models::module_doc - ModuleStatus, ModuleDoc, ChangeAuthor, FreshnessPolicy types
+//! - rusqlite - Freshness policies in the settings table
 //! - std::path, std::fs - File system operations
+//! - std::process::Command - git log for header history and change authors
 //!
 //! EXPORTS:
 //! - check_file_freshness - Check freshness of a single file, returns FreshnessResult
+//! - check_content_freshness - Same check on content already in memory (e.g. from a git revision)
 //! - check_project_freshness - Check all files in a project, returns Vec<ModuleStatus> with freshness
 //! - FreshnessResult - Freshness score, status, and change details for one file
 //! - StalenessSignal - Individual staleness signal with weight and description
+//! - SignalType - Staleness signal kinds (as_str gives the snake_case IPC name)
+//! - OUTDATED_BELOW - Freshness score under which a documented file is "outdated"
+//! - HeaderGitHistory - Git commit info for a doc header vs the rest of the file
+//! - doc_header_line_count - Number of lines in the leading doc header comment
+//! - header_git_history - Last header commit and commits since, via git log -L
+//! - change_authors_since - Per-author commits and lines changed after a commit, via git log --numstat
+//! - attach_change_authors - Add change authors to outdated modules (used by get_stale_files)
+//! - in_scan_scope - Whether a relative path is covered by check_project_freshness
+//! - extract_export_names - Parse export names from EXPORTS section lines
+//! - strip_paren_suffix - Drop a trailing "(default)"-style suffix from an export name
+//! - extract_dependency_paths - Parse dependency paths from DEPENDENCIES section lines
+//! - POLICY_SETTING_PREFIX - Settings key prefix of a project's freshness policies
+//! - load_policies - A project's freshness policies (empty when unset)
+//! - policy_for - The first policy whose pattern matches a relative path
+//! - first_change_since - Date of the oldest commit to a file after a given commit
+//! - overdue_days - Age in days of an unreflected code change, when it exceeds a policy
+//! - apply_policies - Mark documented files outdated when their policy deadline has passed
 //!
 //! PATTERNS:
 //! - Freshness score starts at 100 and is reduced by staleness signals
 //! - Signals are weighted: missing/extra exports (high), import changes (medium)
+//! - Files handled by an analyzer plugin also get its freshness signals (plugin_signal)
 //! - Score >= 80 → "current", score >= 40 → "outdated", score < 40 → "outdated" (critical)
 //! - Files without doc headers always have freshness_score = 0, status = "missing"
+//! - Policies are checked in order and the first matching pattern wins, so list specific
+//!   directories before broad ones; files matching no policy have no deadline
+//! - A file past its policy deadline is "outdated" with its score capped below OUTDATED_BELOW
 //!
 //! CLAUDE NOTES:
 //! - Uses pattern-based detection from analyzer.rs (not tree-sitter yet)
@@ -30,11 +59,24 @@
 //! - Actual exports come from detect_exports() scanning the code
 //! - The "description" field in changes is human-readable for the UI
 //! - This is Phase 5's core engine; Phase 4 only had current/missing
-
-use crate::core::analyzer;
-use crate::models::module_doc::ModuleStatus;
+//! - Project scans skip generated/vendored files via analyzer::should_track_file
+//! - Files are read via analyzer::read_source, so notebooks are scored on header cell + code
+//! - Git history is informational only (used by explain_freshness); it never changes the score
+//! - Change authors only count committed changes after the header's last commit; merges are
+//!   skipped so a merge doesn't credit whoever merged it
+//! - Policy deadlines run from the oldest commit after the header's last commit; uncommitted
+//!   edits and files whose header was never committed never miss a deadline
+//! - apply_policies runs git per matching documented file, so it is applied by get_stale_files
+//!   rather than inside check_project_freshness
+
+use crate::core::proc::{self, ProcLimits};
+use crate::core::{ai, analyzer, notebook, plugins};
+use crate::models::module_doc::{ChangeAuthor, FreshnessPolicy, ModuleStatus};
+use chrono::{DateTime, Utc};
+use rusqlite::Connection;
 use std::fs;
 use std::path::Path;
+use std::process::Command;
 
 /// Result of checking freshness for a single file.
 #[derive(Debug, Clone)]
@@ -55,6 +97,19 @@ pub struct StalenessSignal {
     pub description: String,
 }
 
+/// Git history for a file's doc header versus the rest of the file.
+#[derive(Debug, Clone, Default)]
+pub struct HeaderGitHistory {
+    /// Short hash of the last commit that touched the doc header lines
+    pub header_commit: Option<String>,
+    /// Commit date (RFC 3339) of header_commit
+    pub header_changed_at: Option<String>,
+    /// Commit date (RFC 3339) of the last commit touching the file at all
+    pub file_changed_at: Option<String>,
+    /// Number of commits to the file since the header last changed
+    pub commits_since_header_change: Option<u32>,
+}
+
 /// Types of staleness signals.
 #[derive(Debug, Clone, PartialEq)]
 pub enum SignalType {
@@ -70,6 +125,23 @@ pub enum SignalType {
     PlaceholderDescription,
     /// Doc header has no purpose section or it's empty
     MissingPurpose,
+    /// Reported by the analyzer plugin handling the file's extension
+    PluginSignal,
+}
+
+impl SignalType {
+    /// Stable snake_case identifier for IPC and logs.
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            SignalType::UndocumentedExport => "undocumented_export",
+            SignalType::RemovedExport => "removed_export",
+            SignalType::NewDependency => "new_dependency",
+            SignalType::RemovedDependency => "removed_dependency",
+            SignalType::PlaceholderDescription => "placeholder_description",
+            SignalType::MissingPurpose => "missing_purpose",
+            SignalType::PluginSignal => "plugin_signal",
+        }
+    }
 }
 
 // Signal weights — higher = more impact on freshness
@@ -82,6 +154,12 @@ const WEIGHT_REMOVED_DEPENDENCY: u32 = 2;
 const WEIGHT_PLACEHOLDER_DESC: u32 = 15;
 const WEIGHT_MISSING_PURPOSE: u32 = 12;
 
+/// Documented files scoring below this are "outdated".
+pub const OUTDATED_BELOW: u32 = 60;
+
+/// Settings key prefix of a project's freshness policies (JSON Vec<FreshnessPolicy>).
+pub const POLICY_SETTING_PREFIX: &str = "freshness_policies.";
+
 // ---------------------------------------------------------------------------
 // Public API
 // ---------------------------------------------------------------------------
@@ -90,7 +168,7 @@ const WEIGHT_MISSING_PURPOSE: u32 = 12;
 /// Returns a FreshnessResult with score, status, and change details.
 /// If the file has no doc header, returns score=0, status="missing".
 pub fn check_file_freshness(file_path: &str, _project_path: &str) -> FreshnessResult {
-    let content = match fs::read_to_string(file_path) {
+    let content = match analyzer::read_source(file_path) {
         Ok(c) => c,
         Err(_) => {
             return FreshnessResult {
@@ -102,7 +180,17 @@ pub fn check_file_freshness(file_path: &str, _project_path: &str) -> FreshnessRe
         }
     };
 
-    let doc = match analyzer::parse_doc_header(&content) {
+    let ext = Path::new(file_path)
+        .extension()
+        .and_then(|e| e.to_str())
+        .unwrap_or("");
+    check_content_freshness(&content, ext)
+}
+
+/// Score already-read file content (e.g. a file at a git revision) with the same
+/// signals as check_file_freshness. `ext` is the file extension without the dot.
+pub fn check_content_freshness(content: &str, ext: &str) -> FreshnessResult {
+    let doc = match analyzer::parse_doc_header(content) {
         Some(d) => d,
         None => {
             return FreshnessResult {
@@ -114,15 +202,10 @@ pub fn check_file_freshness(file_path: &str, _project_path: &str) -> FreshnessRe
         }
     };
 
-    let ext = Path::new(file_path)
-        .extension()
-        .and_then(|e| e.to_str())
-        .unwrap_or("");
-
     let mut signals = Vec::new();
 
     // --- Signal: Compare documented exports vs actual exports ---
-    let actual_exports = analyzer::detect_exports(&content, ext);
+    let actual_exports = analyzer::detect_exports(content, ext);
     let documented_exports = extract_export_names(&doc.exports);
 
     // Exports in code but not documented
@@ -154,7 +237,7 @@ pub fn check_file_freshness(file_path: &str, _project_path: &str) -> FreshnessRe
     }
 
     // --- Signal: Compare documented dependencies vs actual imports ---
-    let actual_imports = analyzer::detect_imports(&content, ext);
+    let actual_imports = analyzer::detect_imports(content, ext);
     let documented_deps = extract_dependency_paths(&doc.dependencies);
 
     // Imports in code but not in documented dependencies
@@ -202,13 +285,24 @@ pub fn check_file_freshness(file_path: &str, _project_path: &str) -> FreshnessRe
         });
     }
 
+    // --- Signals: analyzer plugin for in-house languages (weights clamped by core::plugins) ---
+    if let Some((plugin, analysis)) = plugins::analysis_for(ext, content) {
+        for finding in analysis.freshness {
+            signals.push(StalenessSignal {
+                signal_type: SignalType::PluginSignal,
+                weight: finding.weight,
+                description: format!("{}: {}", plugin.name, finding.message),
+            });
+        }
+    }
+
     // Calculate score
     let total_penalty: u32 = signals.iter().map(|s| s.weight).sum();
     let score = 100u32.saturating_sub(total_penalty);
 
     let status = if signals.is_empty() {
         "current".to_string()
-    } else if score >= 60 {
+    } else if score >= OUTDATED_BELOW {
         // Score >= 60 is "current" - be lenient because AI docs may not perfectly match
         // the export detector's heuristics (e.g., interfaces vs types, default exports)
         "current".to_string()
@@ -235,34 +329,325 @@ pub fn check_project_freshness(project_path: &str) -> Result<Vec<ModuleStatus>,
     }
 
     let mut results = Vec::new();
-    walk_with_freshness(path, project_path, &mut results, 0);
+    let overrides = analyzer::load_generated_overrides(project_path);
+    walk_with_freshness(path, project_path, &overrides, &mut results, 0);
     results.sort_by(|a, b| a.path.cmp(&b.path));
     Ok(results)
 }
 
+/// Count the lines making up the leading doc header comment (0 if none).
+/// Handles //, /* */, # and Python triple-quoted docstring headers.
+pub fn doc_header_line_count(content: &str) -> usize {
+    let lines: Vec<&str> = content.lines().collect();
+    let first = match lines.iter().position(|l| !l.trim().is_empty()) {
+        Some(i) => i,
+        None => return 0,
+    };
+
+    let first_trimmed = lines[first].trim_start();
+    if first_trimmed.starts_with("\"\"\"") || first_trimmed.starts_with("'''") {
+        let quote = &first_trimmed[..3];
+        if first_trimmed.len() > 3 && first_trimmed[3..].contains(quote) {
+            return first + 1;
+        }
+        for (i, line) in lines.iter().enumerate().skip(first + 1) {
+            if line.contains(quote) {
+                return i + 1;
+            }
+        }
+        return lines.len();
+    }
+
+    let mut in_block = false;
+    let mut count = first;
+    for line in &lines[first..] {
+        let trimmed = line.trim_start();
+        if in_block {
+            count += 1;
+            if trimmed.contains("*/") {
+                in_block = false;
+            }
+            continue;
+        }
+        if trimmed.starts_with("/*") {
+            count += 1;
+            in_block = !trimmed.contains("*/");
+        } else if trimmed.starts_with("//") || trimmed.starts_with("--") || (trimmed.starts_with('#') && !trimmed.starts_with("#[") && !trimmed.starts_with("#!")) {
+            count += 1;
+        } else {
+            break;
+        }
+    }
+
+    if count == first { 0 } else { count }
+}
+
+/// Look up git history for a file's doc header (first `header_lines` lines).
+/// Returns an empty history when the file is not in a git repository or has no commits.
+pub fn header_git_history(file_path: &str, header_lines: usize) -> HeaderGitHistory {
+    let path = Path::new(file_path);
+    let (dir, name) = match (path.parent(), path.file_name().and_then(|n| n.to_str())) {
+        (Some(d), Some(n)) => (d, n),
+        _ => return HeaderGitHistory::default(),
+    };
+
+    let git = |args: &[&str]| -> Option<String> {
+        let output = proc::run(Command::new("git").args(args).current_dir(dir), ProcLimits::GIT).ok()?;
+        if !output.success() {
+            return None;
+        }
+        let text = String::from_utf8_lossy(&output.stdout).trim().to_string();
+        if text.is_empty() { None } else { Some(text) }
+    };
+
+    let file_changed_at = git(&["log", "-1", "--format=%cI", "--", name]);
+    if file_changed_at.is_none() || header_lines == 0 {
+        return HeaderGitHistory {
+            file_changed_at,
+            ..Default::default()
+        };
+    }
+
+    let range = format!("1,{}:{}", header_lines, name);
+    let header_log = git(&["log", "-1", "-s", "--format=%h%x09%cI", "-L", &range]);
+    let (header_commit, header_changed_at) = match header_log
+        .as_deref()
+        .and_then(|l| l.lines().next())
+        .and_then(|l| l.split_once('\t'))
+    {
+        Some((hash, date)) => (Some(hash.to_string()), Some(date.to_string())),
+        None => (None, None),
+    };
+
+    let commits_since_header_change = header_commit.as_ref().and_then(|hash| {
+        git(&["rev-list", "--count", &format!("{}..HEAD", hash), "--", name])
+            .and_then(|c| c.parse().ok())
+    });
+
+    HeaderGitHistory {
+        header_commit,
+        header_changed_at,
+        file_changed_at,
+        commits_since_header_change,
+    }
+}
+
+/// Authors of the commits to a file after `since_commit` (the last header change),
+/// with lines each added and removed, most lines changed first. Uses .mailmap names.
+/// Returns an empty list outside a git repository.
+pub fn change_authors_since(file_path: &str, since_commit: &str) -> Vec<ChangeAuthor> {
+    let path = Path::new(file_path);
+    let (Some(dir), Some(name)) = (path.parent(), path.file_name().and_then(|n| n.to_str())) else {
+        return Vec::new();
+    };
+    let range = format!("{}..HEAD", since_commit);
+    let output = proc::run(
+        Command::new("git")
+            .args(["log", "--no-merges", "--numstat", "--format=%x1e%aN%x09%aE", &range, "--", name])
+            .current_dir(dir),
+        ProcLimits::GIT,
+    );
+    match output {
+        Ok(o) if o.success() => parse_author_numstat(&String::from_utf8_lossy(&o.stdout)),
+        _ => Vec::new(),
+    }
+}
+
+/// Parse `git log --numstat --format=%x1e%aN%x09%aE` output into per-author totals.
+/// Authors are grouped by email (case-insensitive); binary diffs count as commits only.
+fn parse_author_numstat(log: &str) -> Vec<ChangeAuthor> {
+    let mut authors: Vec<ChangeAuthor> = Vec::new();
+    let mut current: Option<usize> = None;
+    for line in log.lines() {
+        if let Some(header) = line.strip_prefix('\u{1e}') {
+            let (name, email) = header.split_once('\t').unwrap_or((header, ""));
+            let index = match authors.iter().position(|a| a.email.eq_ignore_ascii_case(email)) {
+                Some(i) => i,
+                None => {
+                    authors.push(ChangeAuthor {
+                        name: name.to_string(),
+                        email: email.to_string(),
+                        commits: 0,
+                        lines_added: 0,
+                        lines_removed: 0,
+                    });
+                    authors.len() - 1
+                }
+            };
+            authors[index].commits += 1;
+            current = Some(index);
+        } else if let Some(index) = current {
+            let mut fields = line.split('\t');
+            if let (Some(added), Some(removed)) = (fields.next(), fields.next()) {
+                authors[index].lines_added += added.parse::<u32>().unwrap_or(0);
+                authors[index].lines_removed += removed.parse::<u32>().unwrap_or(0);
+            }
+        }
+    }
+    authors.sort_by(|a, b| b.lines_changed().cmp(&a.lines_changed()).then_with(|| a.name.cmp(&b.name)));
+    authors
+}
+
+/// Fill `change_authors` on outdated modules from git history and add a
+/// "Code changed since the header by ..." line to their changes.
+/// Files outside git, notebooks, and files whose header was never committed are left as-is.
+pub fn attach_change_authors(project_path: &str, modules: &mut [ModuleStatus]) {
+    for module in modules.iter_mut().filter(|m| m.status == "outdated") {
+        let full = Path::new(project_path).join(&module.path);
+        let full = full.to_string_lossy();
+        if notebook::is_notebook(&full) {
+            continue;
+        }
+        let Ok(content) = analyzer::read_source(&full) else {
+            continue;
+        };
+        let history = header_git_history(&full, doc_header_line_count(&content));
+        let Some(commit) = history.header_commit else {
+            continue;
+        };
+        let authors = change_authors_since(&full, &commit);
+        if authors.is_empty() {
+            continue;
+        }
+        module.changes.get_or_insert_with(Vec::new).push(describe_change_authors(&authors));
+        module.change_authors = Some(authors);
+    }
+}
+
+/// One-line summary of who changed the code, e.g. "Code changed since the header by Ana (+40/-3), Li (+2/-0)".
+fn describe_change_authors(authors: &[ChangeAuthor]) -> String {
+    const SHOWN: usize = 3;
+    let mut names: Vec<String> = authors
+        .iter()
+        .take(SHOWN)
+        .map(|a| format!("{} (+{}/-{})", a.name, a.lines_added, a.lines_removed))
+        .collect();
+    if authors.len() > SHOWN {
+        names.push(format!("{} more", authors.len() - SHOWN));
+    }
+    format!("Code changed since the header by {}", names.join(", "))
+}
+
 // ---------------------------------------------------------------------------
-// File walking with freshness
+// Per-directory freshness policies
 // ---------------------------------------------------------------------------
 
-fn walk_with_freshness(dir: &Path, project_path: &str, results: &mut Vec<ModuleStatus>, depth: usize) {
-    const MAX_DEPTH: usize = 10;
-    if depth > MAX_DEPTH {
+/// A project's freshness policies, in priority order. Empty when unset or unreadable.
+pub fn load_policies(db: &Connection, project_id: &str) -> Vec<FreshnessPolicy> {
+    db.query_row(
+        "SELECT value FROM settings WHERE key = ?1",
+        [format!("{}{}", POLICY_SETTING_PREFIX, project_id)],
+        |row| row.get::<_, String>(0),
+    )
+    .ok()
+    .and_then(|json| serde_json::from_str(&json).ok())
+    .unwrap_or_default()
+}
+
+/// The first policy whose pattern matches a project-relative path.
+pub fn policy_for<'a>(policies: &'a [FreshnessPolicy], rel_path: &str) -> Option<&'a FreshnessPolicy> {
+    policies.iter().find(|p| ai::glob_matches(&p.pattern, rel_path))
+}
+
+/// Commit date (RFC 3339) of the oldest commit to a file after `since_commit`.
+/// None when nothing was committed since, or outside a git repository.
+pub fn first_change_since(file_path: &str, since_commit: &str) -> Option<String> {
+    let path = Path::new(file_path);
+    let (dir, name) = (path.parent()?, path.file_name()?.to_str()?);
+    let output = proc::run(
+        Command::new("git")
+            .args(["log", "--reverse", "--format=%cI", &format!("{}..HEAD", since_commit), "--", name])
+            .current_dir(dir),
+        ProcLimits::GIT,
+    )
+    .ok()
+    .filter(|o| o.success())?;
+    String::from_utf8_lossy(&output.stdout).lines().next().map(str::to_string)
+}
+
+/// Whole days since `changed_at` (RFC 3339) when that is more than `max_days`, else None.
+pub fn overdue_days(changed_at: &str, max_days: u32, now: DateTime<Utc>) -> Option<i64> {
+    let changed_at = DateTime::parse_from_rfc3339(changed_at).ok()?;
+    let age = (now - changed_at.with_timezone(&Utc)).num_days();
+    (age > i64::from(max_days)).then_some(age)
+}
+
+/// Mark documented files outdated when code was committed after their header's last change
+/// and the first such commit is older than their policy allows. The score is capped below
+/// OUTDATED_BELOW and the deadline is added to changes.
+pub fn apply_policies(project_path: &str, policies: &[FreshnessPolicy], modules: &mut [ModuleStatus]) {
+    if policies.is_empty() {
         return;
     }
+    let now = Utc::now();
+    for module in modules.iter_mut().filter(|m| m.status == "current" || m.status == "outdated") {
+        let Some(policy) = policy_for(policies, &module.path) else {
+            continue;
+        };
+        let full = Path::new(project_path).join(&module.path);
+        let full = full.to_string_lossy();
+        let Ok(content) = analyzer::read_source(&full) else {
+            continue;
+        };
+        let Some(commit) = header_git_history(&full, doc_header_line_count(&content)).header_commit else {
+            continue;
+        };
+        let Some(days) = first_change_since(&full, &commit).and_then(|at| overdue_days(&at, policy.max_days, now)) else {
+            continue;
+        };
+        module.status = "outdated".to_string();
+        module.freshness_score = module.freshness_score.min(OUTDATED_BELOW - 1);
+        module.changes.get_or_insert_with(Vec::new).push(format!(
+            "Code changed {} days ago without a header update ({} requires updates within {} days)",
+            days, policy.pattern, policy.max_days
+        ));
+    }
+}
 
-    let ignore_dirs = [
-        "node_modules",
-        "target",
-        ".git",
-        "dist",
-        "build",
-        ".next",
-        "__pycache__",
-        ".venv",
-        "venv",
-        "coverage",
-        ".turbo",
-    ];
+// ---------------------------------------------------------------------------
+// File walking with freshness
+// ---------------------------------------------------------------------------
+
+/// Maximum directory depth of a project freshness scan.
+const MAX_SCAN_DEPTH: usize = 10;
+
+/// Directories skipped by the project freshness scan.
+const IGNORED_DIRS: &[&str] = &[
+    "node_modules",
+    "target",
+    ".git",
+    "dist",
+    "build",
+    ".next",
+    "__pycache__",
+    ".venv",
+    "venv",
+    "coverage",
+    ".turbo",
+];
+
+/// Whether a project-relative path lies where check_project_freshness looks
+/// (not hidden, not under an ignored directory, within the depth limit).
+pub fn in_scan_scope(rel_path: &str) -> bool {
+    let parts: Vec<&str> = rel_path.split('/').filter(|p| !p.is_empty()).collect();
+    let Some((name, dirs)) = parts.split_last() else {
+        return false;
+    };
+    dirs.len() <= MAX_SCAN_DEPTH
+        && !name.starts_with('.')
+        && dirs.iter().all(|d| !d.starts_with('.') && !IGNORED_DIRS.contains(d))
+}
+
+fn walk_with_freshness(
+    dir: &Path,
+    project_path: &str,
+    overrides: &[String],
+    results: &mut Vec<ModuleStatus>,
+    depth: usize,
+) {
+    if depth > MAX_SCAN_DEPTH {
+        return;
+    }
 
     let entries = match fs::read_dir(dir) {
         Ok(e) => e,
@@ -278,12 +663,23 @@ fn walk_with_freshness(dir: &Path, project_path: &str, results: &mut Vec<ModuleS
         }
 
         if path.is_dir() {
-            if !ignore_dirs.contains(&name.as_str()) {
-                walk_with_freshness(&path, project_path, results, depth + 1);
+            if !IGNORED_DIRS.contains(&name.as_str()) {
+                walk_with_freshness(&path, project_path, overrides, results, depth + 1);
             }
-        } else if analyzer::is_documentable(&name) {
+        } else {
             let abs_path = path.to_string_lossy().to_string();
             let rel_path = make_relative(&abs_path, project_path);
+            if !analyzer::is_documentable(&name) && !overrides.contains(&rel_path) {
+                continue;
+            }
+            // Generated/vendored files are excluded unless overridden; oversized and
+            // binary files are skipped
+            let Ok(content) = analyzer::read_source(&abs_path) else {
+                continue;
+            };
+            if !analyzer::should_track_file(&name, &rel_path, &content, overrides) {
+                continue;
+            }
 
             let freshness = check_file_freshness(&abs_path, project_path);
 
@@ -297,6 +693,9 @@ fn walk_with_freshness(dir: &Path, project_path: &str, results: &mut Vec<ModuleS
                     Some(freshness.changes)
                 },
                 suggested_doc: None,
+                quality: None,
+                analyzer: plugins::for_file(&name).map(|p| p.name),
+                change_authors: None,
             });
         }
     }
@@ -308,7 +707,7 @@ fn walk_with_freshness(dir: &Path, project_path: &str, results: &mut Vec<ModuleS
 
 /// Strip parenthetical suffix from export names.
 /// E.g., "App (default)" -> "App", "useState (hook)" -> "useState"
-fn strip_paren_suffix(name: &str) -> &str {
+pub fn strip_paren_suffix(name: &str) -> &str {
     if let Some(paren_pos) = name.find(" (") {
         name[..paren_pos].trim()
     } else {
@@ -318,7 +717,7 @@ fn strip_paren_suffix(name: &str) -> &str {
 
 /// Extract export names from the EXPORTS section lines.
 /// Lines are typically "functionName - description" format.
-fn extract_export_names(exports_lines: &[String]) -> Vec<String> {
+pub fn extract_export_names(exports_lines: &[String]) -> Vec<String> {
     exports_lines
         .iter()
         .map(|line| {
@@ -337,7 +736,7 @@ fn extract_export_names(exports_lines: &[String]) -> Vec<String> {
 
 /// Extract dependency paths from the DEPENDENCIES section lines.
 /// Lines are typically "path - why needed" format.
-fn extract_dependency_paths(deps_lines: &[String]) -> Vec<String> {
+pub fn extract_dependency_paths(deps_lines: &[String]) -> Vec<String> {
     deps_lines
         .iter()
         .map(|line| {
@@ -551,4 +950,63 @@ export function myFunc() {}
             "src/App.tsx"
         );
     }
+
+    #[test]
+    fn test_doc_header_line_count() {
+        assert_eq!(doc_header_line_count("/**\n * @module x\n */\n\nexport const a = 1;\n"), 3);
+        assert_eq!(doc_header_line_count("//! @module x\n//! doc\n\nuse std::fs;\n"), 2);
+        assert_eq!(doc_header_line_count("\"\"\"\nModule doc\n\"\"\"\nimport os\n"), 3);
+        assert_eq!(doc_header_line_count("# comment\n#[derive(Debug)]\n"), 1);
+        assert_eq!(doc_header_line_count("export const a = 1;\n"), 0);
+    }
+
+    #[test]
+    fn test_parse_author_numstat() {
+        let log = "\u{1e}Ana\tana@example.com\n\n30\t2\tsrc/api.ts\n\
+                   \u{1e}Li\tli@example.com\n\n1\t1\tsrc/api.ts\n\
+                   \u{1e}Ana B\tANA@example.com\n\n10\t1\tsrc/api.ts\n\
+                   \u{1e}Li\tli@example.com\n\n-\t-\tsrc/api.ts\n";
+        let authors = parse_author_numstat(log);
+        assert_eq!(authors.len(), 2);
+        assert_eq!(authors[0].name, "Ana");
+        assert_eq!((authors[0].commits, authors[0].lines_added, authors[0].lines_removed), (2, 40, 3));
+        assert_eq!((authors[1].commits, authors[1].lines_changed()), (2, 2));
+        assert_eq!(
+            describe_change_authors(&authors),
+            "Code changed since the header by Ana (+40/-3), Li (+1/-1)"
+        );
+        assert!(parse_author_numstat("").is_empty());
+    }
+
+    #[test]
+    fn test_header_git_history_outside_repo() {
+        let dir = std::env::temp_dir().join("freshness_test_no_git");
+        let _ = fs::create_dir_all(&dir);
+        let file_path = dir.join("nogit.ts");
+        fs::write(&file_path, "// header\nexport const a = 1;\n").unwrap();
+
+        let history = header_git_history(file_path.to_str().unwrap(), 1);
+        assert!(history.header_commit.is_none());
+        assert!(history.commits_since_header_change.is_none());
+
+        let _ = fs::remove_dir_all(&dir);
+    }
+
+    #[test]
+    fn test_policy_for_and_overdue_days() {
+        let policies = vec![
+            FreshnessPolicy { pattern: "src/core/**".to_string(), max_days: 7 },
+            FreshnessPolicy { pattern: "src/**".to_string(), max_days: 30 },
+            FreshnessPolicy { pattern: "scripts/**".to_string(), max_days: 90 },
+        ];
+        assert_eq!(policy_for(&policies, "src/core/freshness.rs").map(|p| p.max_days), Some(7));
+        assert_eq!(policy_for(&policies, "src/commands/ralph.rs").map(|p| p.max_days), Some(30));
+        assert_eq!(policy_for(&policies, "scripts/release.sh").map(|p| p.max_days), Some(90));
+        assert!(policy_for(&policies, "docs/guide.md").is_none());
+
+        let now = DateTime::parse_from_rfc3339("2025-03-20T12:00:00Z").unwrap().with_timezone(&Utc);
+        assert_eq!(overdue_days("2025-03-10T12:00:00+00:00", 7, now), Some(10));
+        assert_eq!(overdue_days("2025-03-15T12:00:00+00:00", 7, now), None);
+        assert_eq!(overdue_days("not a date", 7, now), None);
+    }
 }
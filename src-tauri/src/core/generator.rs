@@ -10,11 +10,16 @@
 //! DEPENDENCIES:
 //! - models::project - Project struct for project data
 //! - core::ai - Claude API caller for AI-powered generation
+//! - core::ai_queue - Prioritized slot for each Claude call
+//! - core::ai_audit - Attribute the call to the project's audit log
 //! - reqwest - HTTP client (passed through for API calls)
+//! - core::safe_read - Lossy, size-capped reads of sampled files
+//! - core::package_manager - Package manager for the Commands section and tech stack
 //!
 //! EXPORTS:
 //! - generate_claude_md_content - Template-based CLAUDE.md generation (fallback)
 //! - generate_claude_md_with_ai - AI-powered CLAUDE.md generation
+//! - setup_commands - Install/dev/build/test commands for a language, framework, test runner, and package manager
 //!
 //! PATTERNS:
 //! - Template sections are built with helper functions
@@ -25,9 +30,15 @@
 //! - generate_claude_md_content is the synchronous template fallback
 //! - generate_claude_md_with_ai uses the Anthropic API for richer output
 //! - AI prompt includes project name, language, framework, and source file listing
+//! - Key file samples skip anything matching the project's .claude/ai-exclude globs
 //! - The generated content includes: overview, tech stack, structure, commands, patterns, notes
 
 use crate::core::ai;
+use crate::core::ai_audit;
+use crate::core::ai_queue;
+use crate::core::package_manager::{self, PackageManager};
+use crate::core::safe_read;
+use crate::models::ai_queue::AiJobKind;
 use crate::models::project::Project;
 
 /// Generate a complete CLAUDE.md file from project configuration data.
@@ -163,6 +174,7 @@ pub async fn generate_claude_md_with_ai(
         - Framework: {}\n\
         - Database: {}\n\
         - Testing: {}\n\
+        - Package Manager: {}\n\
         - Styling: {}\n\
         - Type: {}\n\
         - Description: {}\n\n\
@@ -183,6 +195,7 @@ pub async fn generate_claude_md_with_ai(
         project.framework.as_deref().unwrap_or("None"),
         project.database.as_deref().unwrap_or("None"),
         project.testing.as_deref().unwrap_or("None"),
+        project_package_manager(project).map(|pm| pm.as_str()).unwrap_or("None"),
         project.styling.as_deref().unwrap_or("None"),
         project.project_type,
         if project.description.is_empty() { "Not provided" } else { &project.description },
@@ -191,7 +204,12 @@ pub async fn generate_claude_md_with_ai(
         file_samples,
     );
 
-    ai::call_claude(client, api_key, system, &prompt).await
+    let call = ai_queue::run(
+        AiJobKind::Interactive,
+        "CLAUDE.md generation",
+        ai::call_claude(client, api_key, system, &prompt),
+    );
+    ai_audit::scoped(&project.path, "CLAUDE.md generation", call).await
 }
 
 /// Collect contents of key files for AI context.
@@ -199,6 +217,7 @@ pub async fn generate_claude_md_with_ai(
 fn collect_key_file_contents(project_path: &str) -> String {
     let mut samples = Vec::new();
     let root = std::path::Path::new(project_path);
+    let excludes = ai::load_ai_excludes(project_path);
 
     // Priority files to sample (in order)
     let priority_files = [
@@ -247,14 +266,14 @@ fn collect_key_file_contents(project_path: &str) -> String {
             break;
         }
 
+        if ai::is_ai_excluded(&excludes, rel_path) {
+            continue;
+        }
+
         let full_path = root.join(rel_path);
         if full_path.exists() {
-            // Skip files larger than 1MB to avoid OOM on minified bundles
-            let file_size = std::fs::metadata(&full_path).map(|m| m.len()).unwrap_or(0);
-            if file_size > 1_000_000 {
-                continue;
-            }
-            if let Ok(content) = std::fs::read_to_string(&full_path) {
+            // Files over 1MB (minified bundles) are refused by the capped read
+            if let Ok(content) = safe_read::read_text_limited(&full_path, 1_000_000) {
                 let truncated: String = content.chars().take(*max_chars).collect();
                 let was_truncated = content.len() > *max_chars;
 
@@ -277,14 +296,13 @@ fn collect_key_file_contents(project_path: &str) -> String {
                 if total_chars >= MAX_TOTAL_CHARS {
                     break;
                 }
-                // Skip files larger than 1MB
-                let file_size = std::fs::metadata(type_file).map(|m| m.len()).unwrap_or(0);
-                if file_size > 1_000_000 {
+                let rel = type_file.strip_prefix(root).unwrap_or(type_file);
+                if ai::is_ai_excluded(&excludes, &rel.to_string_lossy().replace('\\', "/")) {
                     continue;
                 }
-                if let Ok(content) = std::fs::read_to_string(type_file) {
+                // Files over 1MB are refused by the capped read
+                if let Ok(content) = safe_read::read_text_limited(type_file, 1_000_000) {
                     let truncated: String = content.chars().take(2000).collect();
-                    let rel = type_file.strip_prefix(root).unwrap_or(type_file);
                     samples.push(format!(
                         "### {} (types)\n```\n{}\n```\n",
                         rel.display(),
@@ -437,6 +455,10 @@ fn generate_tech_stack(project: &Project) -> String {
         rows.push(format!("| **Styling** | {} |", style));
     }
 
+    if let Some(pm) = project_package_manager(project) {
+        rows.push(format!("| **Package Manager** | {} |", pm.as_str()));
+    }
+
     if !project.project_type.is_empty() {
         rows.push(format!("| **Type** | {} |", project.project_type));
     }
@@ -740,18 +762,45 @@ fn generate_decisions(project: &Project) -> String {
     )
 }
 
+/// The project's stored package manager, else the one its files or language imply.
+fn project_package_manager(project: &Project) -> Option<PackageManager> {
+    package_manager::resolve(project.package_manager.as_deref(), &project.path, &project.language)
+}
+
 fn generate_commands(project: &Project) -> String {
-    let commands = match project.language.as_str() {
+    let commands = setup_commands(
+        &project.language,
+        project.framework.as_deref(),
+        project.testing.as_deref(),
+        project_package_manager(project),
+    );
+
+    format!(
+        "## Commands\n\n```bash\n{}\n```\n",
+        commands.join("\n")
+    )
+}
+
+/// Install/dev/build/test commands (with trailing `# comments`) for a detected stack.
+/// Shared by the CLAUDE.md Commands section and README generation.
+/// JS commands fall back to pnpm and Python commands to pip when `package_manager` is None.
+pub fn setup_commands(
+    language: &str,
+    framework: Option<&str>,
+    testing: Option<&str>,
+    package_manager: Option<PackageManager>,
+) -> Vec<String> {
+    let js_pm = package_manager.filter(PackageManager::is_javascript).unwrap_or(PackageManager::Pnpm);
+    match language {
         "TypeScript" | "JavaScript" => {
-            let pm = "pnpm"; // Default to pnpm per project conventions
             let mut cmds = vec![
-                format!("{} install              # Install dependencies", pm),
-                format!("{} dev                  # Start development server", pm),
-                format!("{} build                # Build for production", pm),
-                format!("{} lint                 # Run linter", pm),
+                commented(&js_pm.install(), "Install dependencies"),
+                commented(&js_pm.run_script("dev"), "Start development server"),
+                commented(&js_pm.run_script("build"), "Build for production"),
+                commented(&js_pm.run_script("lint"), "Run linter"),
             ];
-            if let Some(ref test) = project.testing {
-                cmds.push(format!("{} test                 # Run {} tests", pm, test));
+            if let Some(test) = testing {
+                cmds.push(commented(&js_pm.run_script("test"), &format!("Run {} tests", test)));
             }
             cmds
         }
@@ -762,33 +811,32 @@ fn generate_commands(project: &Project) -> String {
                 "cargo test              # Run tests".to_string(),
                 "cargo clippy            # Run linter".to_string(),
             ];
-            if project.framework.as_deref() == Some("Tauri") {
-                cmds.push("pnpm tauri dev          # Start Tauri development".to_string());
-                cmds.push("pnpm tauri build        # Build distributable app".to_string());
+            if framework == Some("Tauri") {
+                cmds.push(commented(&js_pm.run_script("tauri dev"), "Start Tauri development"));
+                cmds.push(commented(&js_pm.run_script("tauri build"), "Build distributable app"));
             }
             cmds
         }
         "Python" => {
-            let mut cmds = vec![
-                "pip install -r requirements.txt  # Install dependencies".to_string(),
-            ];
-            if let Some(ref fw) = project.framework {
-                match fw.as_str() {
+            let pm = package_manager.filter(|pm| !pm.is_javascript()).unwrap_or(PackageManager::Pip);
+            let mut cmds = vec![commented(&pm.install(), "Install dependencies")];
+            if let Some(fw) = framework {
+                match fw {
                     "Django" => {
-                        cmds.push("python manage.py runserver  # Start dev server".to_string());
-                        cmds.push("python manage.py test       # Run tests".to_string());
+                        cmds.push(commented(&pm.exec("python manage.py runserver"), "Start dev server"));
+                        cmds.push(commented(&pm.exec("python manage.py test"), "Run tests"));
                     }
                     "FastAPI" => {
-                        cmds.push("uvicorn main:app --reload   # Start dev server".to_string());
+                        cmds.push(commented(&pm.exec("uvicorn main:app --reload"), "Start dev server"));
                     }
                     "Flask" => {
-                        cmds.push("flask run                   # Start dev server".to_string());
+                        cmds.push(commented(&pm.exec("flask run"), "Start dev server"));
                     }
                     _ => {}
                 }
             }
-            if let Some(ref test) = project.testing {
-                cmds.push(format!("{}                          # Run tests", test));
+            if let Some(test) = testing {
+                cmds.push(commented(&pm.exec(test), "Run tests"));
             }
             cmds
         }
@@ -803,12 +851,12 @@ fn generate_commands(project: &Project) -> String {
         _ => {
             vec!["# Add your project commands here".to_string()]
         }
-    };
+    }
+}
 
-    format!(
-        "## Commands\n\n```bash\n{}\n```\n",
-        commands.join("\n")
-    )
+/// A command padded to a column with its trailing `# comment`.
+fn commented(command: &str, comment: &str) -> String {
+    format!("{:<24} # {}", command, comment)
 }
 
 fn generate_patterns(project: &Project) -> String {
@@ -1002,6 +1050,10 @@ mod tests {
             stack_extras: None,
             health_score: 0,
             created_at: Utc::now(),
+            location_type: "local".to_string(),
+            remote_host: None,
+            remote_path: None,
+            package_manager: None,
         };
 
         let content = generate_claude_md_content(&project);
@@ -1032,6 +1084,10 @@ mod tests {
             stack_extras: None,
             health_score: 0,
             created_at: Utc::now(),
+            location_type: "local".to_string(),
+            remote_host: None,
+            remote_path: None,
+            package_manager: None,
         };
 
         let content = generate_claude_md_content(&project);
@@ -1065,6 +1121,10 @@ mod tests {
             }),
             health_score: 0,
             created_at: Utc::now(),
+            location_type: "local".to_string(),
+            remote_host: None,
+            remote_path: None,
+            package_manager: None,
         };
 
         let content = generate_claude_md_content(&project);
@@ -1080,4 +1140,23 @@ mod tests {
         assert!(content.contains("Monitoring"));
         assert!(content.contains("Email"));
     }
+
+    #[test]
+    fn test_setup_commands_use_package_manager() {
+        let npm = setup_commands("TypeScript", Some("React"), Some("Vitest"), Some(PackageManager::Npm));
+        assert!(npm[0].starts_with("npm install "));
+        assert!(npm[1].starts_with("npm run dev "));
+        assert!(npm.last().unwrap().starts_with("npm test "));
+
+        let default = setup_commands("TypeScript", None, None, None);
+        assert!(default[0].starts_with("pnpm install "));
+
+        let tauri = setup_commands("Rust", Some("Tauri"), None, Some(PackageManager::Bun));
+        assert!(tauri.iter().any(|c| c.starts_with("bun run tauri dev ")));
+
+        let poetry = setup_commands("Python", Some("Django"), Some("pytest"), Some(PackageManager::Poetry));
+        assert!(poetry[0].starts_with("poetry install "));
+        assert!(poetry[1].starts_with("poetry run python manage.py runserver "));
+        assert!(poetry.last().unwrap().starts_with("poetry run pytest "));
+    }
 }
@@ -0,0 +1,337 @@
+//! @module core/git_forge
+//! @description Forge-neutral CI integration: the GitForge trait and per-project provider selection
+//!
+//! PURPOSE:
+//! - Define the GitForge trait that GitHub, GitLab, and Bitbucket adapters implement
+//! - Pick a project's forge from its settings or its origin remote host
+//! - Turn failed doc-check CI runs from any forge into enforcement events
+//!
+//! DEPENDENCIES:
+//! - reqwest - HTTP client handed to adapters
+//! - rusqlite - Forge settings, tokens, and event inserts
+//! - core::crypto - Decrypt stored forge tokens
+//! - core::proc - git remote lookup with a timeout
+//! - core::github_ci / gitlab_ci / bitbucket_ci - The adapters
+//! - models::git_forge - ForgeProvider, ForgeConfig
+//! - models::enforcement - EnforcementEvent
+//!
+//! EXPORTS:
+//! - FORGE_SETTING_PREFIX - Settings key prefix ("git_forge.<project_id>")
+//! - ForgeFuture - Boxed future returned by GitForge methods
+//! - GitForge - Adapter trait: provider, repository label, recent doc-check runs
+//! - CiRun - A doc-check CI run in forge-neutral form
+//! - load_config - A project's ForgeConfig (defaults when unset)
+//! - load_token - Decrypted token for a provider from settings
+//! - origin_url - The project's origin remote URL
+//! - parse_remote - (host, path) from an https/ssh remote URL
+//! - detect_provider - Provider for a remote host, if recognizable
+//! - forge_for_project - The configured or detected adapter, with its token
+//! - failure_event - Enforcement event for a failed run (None for other runs)
+//! - record_events - Insert events, skipping runs already recorded
+//!
+//! PATTERNS:
+//! - Adapters return futures as ForgeFuture so they can live behind Box<dyn GitForge>
+//! - Event ids are "ci-<project_id>-<run id>"; GitHub run ids are bare numbers (as before
+//!   the other forges existed), GitLab and Bitbucket ids are prefixed "gitlab-" / "bitbucket-"
+//! - Only completed runs with conclusion failure/timed_out become events ("block")
+//!
+//! CLAUDE NOTES:
+//! - Detection: github.com -> GitHub, bitbucket.org -> Bitbucket, any host containing
+//!   "gitlab" -> GitLab. Other self-hosted hosts need ForgeConfig.provider set
+//! - Each provider has its own token setting (ForgeProvider::token_setting); all are
+//!   encrypted by save_setting and never leave the machine on sync
+//! - New forge features (PR creation, issue import) belong on GitForge, not in a
+//!   provider module, so every provider gets them
+
+use chrono::{DateTime, Utc};
+use rusqlite::Connection;
+use std::future::Future;
+use std::pin::Pin;
+use std::process::Command;
+
+use crate::core::crypto;
+use crate::core::proc::{self, ProcLimits};
+use crate::core::{bitbucket_ci, github_ci, gitlab_ci};
+use crate::models::enforcement::EnforcementEvent;
+use crate::models::git_forge::{ForgeConfig, ForgeProvider};
+
+/// Settings key prefix for per-project forge settings (JSON ForgeConfig).
+pub const FORGE_SETTING_PREFIX: &str = "git_forge.";
+
+/// Future returned by GitForge methods.
+pub type ForgeFuture<'a, T> = Pin<Box<dyn Future<Output = Result<T, String>> + Send + 'a>>;
+
+/// A hosted git service the app can query for a project.
+pub trait GitForge: Send + Sync {
+    fn provider(&self) -> ForgeProvider;
+
+    /// Repository as shown to the user ("owner/repo", "group/sub/repo", "workspace/repo").
+    fn repository(&self) -> String;
+
+    /// Recent runs of the doc check installed by the CI snippet, newest first.
+    fn fetch_doc_check_runs<'a>(&'a self, client: &'a reqwest::Client) -> ForgeFuture<'a, Vec<CiRun>>;
+}
+
+/// A doc-check CI run, whatever forge it came from.
+#[derive(Debug, Clone, PartialEq)]
+pub struct CiRun {
+    /// Unique per project across forges; becomes part of the event id
+    pub id: String,
+    pub completed: bool,
+    /// For completed runs: "success" | "failure" | "timed_out" | "cancelled" | other
+    pub conclusion: Option<String>,
+    pub branch: Option<String>,
+    pub sha: String,
+    pub url: String,
+    pub title: Option<String>,
+    /// RFC 3339 (or the forge's own format if it could not be parsed)
+    pub updated_at: String,
+}
+
+impl ForgeProvider {
+    /// Settings key of the provider's (encrypted) API token.
+    pub fn token_setting(&self) -> &'static str {
+        match self {
+            ForgeProvider::Github => github_ci::GITHUB_TOKEN_SETTING,
+            ForgeProvider::Gitlab => gitlab_ci::GITLAB_TOKEN_SETTING,
+            ForgeProvider::Bitbucket => bitbucket_ci::BITBUCKET_TOKEN_SETTING,
+        }
+    }
+}
+
+/// A project's forge settings; defaults (detect everything) when unset or unreadable.
+pub fn load_config(db: &Connection, project_id: &str) -> ForgeConfig {
+    db.query_row(
+        "SELECT value FROM settings WHERE key = ?1",
+        [format!("{}{}", FORGE_SETTING_PREFIX, project_id)],
+        |row| row.get::<_, String>(0),
+    )
+    .ok()
+    .and_then(|json| serde_json::from_str(&json).ok())
+    .unwrap_or_default()
+}
+
+/// Decrypted API token for `provider` from settings.
+pub fn load_token(db: &Connection, provider: ForgeProvider) -> Result<String, String> {
+    let value: String = db
+        .query_row("SELECT value FROM settings WHERE key = ?1", [provider.token_setting()], |row| row.get(0))
+        .ok()
+        .filter(|v: &String| !v.is_empty())
+        .ok_or_else(|| format!("{} token not configured. Set it in Settings.", provider.label()))?;
+    match value.strip_prefix("enc:") {
+        Some(enc) => crypto::decrypt(enc).map_err(|e| format!("Failed to decrypt {} token: {}", provider.label(), e)),
+        None => Ok(value),
+    }
+}
+
+/// URL of the project's origin remote.
+pub fn origin_url(project_path: &str) -> Result<String, String> {
+    let output = proc::run(
+        Command::new("git").args(["remote", "get-url", "origin"]).current_dir(project_path),
+        ProcLimits::GIT,
+    )
+    .map_err(|e| format!("Failed to run git: {}", e))?;
+    if !output.status.success() {
+        return Err("Project has no origin remote".to_string());
+    }
+    Ok(String::from_utf8_lossy(&output.stdout).trim().to_string())
+}
+
+/// (host, repository path) from https://host/path(.git), git@host:path(.git), or
+/// ssh://git@host[:port]/path(.git). The path keeps every namespace segment.
+pub fn parse_remote(url: &str) -> Option<(String, String)> {
+    let (host, path) = if let Some(rest) = url.strip_prefix("https://").or_else(|| url.strip_prefix("http://")) {
+        rest.split_once('/')?
+    } else if let Some(rest) = url.strip_prefix("ssh://") {
+        let (host, path) = rest.split_once('/')?;
+        (host.split(':').next().unwrap_or(host), path)
+    } else {
+        let (host, path) = url.split_once(':')?;
+        if host.contains('/') {
+            return None;
+        }
+        (host, path)
+    };
+    let host = host.rsplit('@').next().unwrap_or(host).to_lowercase();
+    let path = path.trim_matches('/');
+    let path = path.strip_suffix(".git").unwrap_or(path);
+    if host.is_empty() || !path.contains('/') || path.split('/').any(str::is_empty) {
+        return None;
+    }
+    Some((host, path.to_string()))
+}
+
+/// The provider a remote host belongs to, if the host name gives it away.
+pub fn detect_provider(host: &str) -> Option<ForgeProvider> {
+    match host {
+        "github.com" => Some(ForgeProvider::Github),
+        "bitbucket.org" => Some(ForgeProvider::Bitbucket),
+        h if h.contains("gitlab") => Some(ForgeProvider::Gitlab),
+        _ => None,
+    }
+}
+
+/// The project's forge adapter: provider from its ForgeConfig or the origin host,
+/// repository from the origin remote, token from settings.
+pub fn forge_for_project(db: &Connection, project_id: &str, project_path: &str) -> Result<Box<dyn GitForge>, String> {
+    let config = load_config(db, project_id);
+    let url = origin_url(project_path)?;
+    let (host, path) = parse_remote(&url).ok_or_else(|| format!("Unrecognized origin remote: {}", url))?;
+    let provider = config
+        .provider
+        .or_else(|| detect_provider(&host))
+        .ok_or_else(|| format!("Cannot tell which forge hosts {}; choose one in the project's forge settings", host))?;
+    let token = load_token(db, provider)?;
+
+    Ok(match provider {
+        ForgeProvider::Github => {
+            let (owner, repo) = path
+                .split_once('/')
+                .filter(|(_, repo)| !repo.contains('/'))
+                .ok_or_else(|| format!("Origin is not a GitHub repository: {}", url))?;
+            Box::new(github_ci::GitHubForge::new(owner, repo, &token))
+        }
+        ForgeProvider::Gitlab => {
+            let api_url = config.api_url.unwrap_or_else(|| format!("https://{}/api/v4", host));
+            Box::new(gitlab_ci::GitLabForge::new(&api_url, &path, &token))
+        }
+        ForgeProvider::Bitbucket => {
+            let (workspace, repo) = path
+                .split_once('/')
+                .filter(|(_, repo)| !repo.contains('/'))
+                .ok_or_else(|| format!("Origin is not a Bitbucket repository: {}", url))?;
+            Box::new(bitbucket_ci::BitbucketForge::new(workspace, repo, &token))
+        }
+    })
+}
+
+/// Enforcement event for a failed run; None for passing, cancelled, or unfinished runs.
+pub fn failure_event(project_id: &str, run: &CiRun) -> Option<EnforcementEvent> {
+    if !run.completed {
+        return None;
+    }
+    let conclusion = run.conclusion.as_deref()?;
+    if conclusion != "failure" && conclusion != "timed_out" {
+        return None;
+    }
+
+    let short_sha: String = run.sha.chars().take(7).collect();
+    let branch = run.branch.as_deref().unwrap_or("unknown branch");
+    let title = run.title.as_deref().unwrap_or("doc check");
+    let outcome = if conclusion == "timed_out" { "timed out" } else { "failed" };
+    let created_at = DateTime::parse_from_rfc3339(&run.updated_at)
+        .map(|t| t.with_timezone(&Utc).to_rfc3339())
+        .unwrap_or_else(|_| run.updated_at.clone());
+
+    Some(EnforcementEvent {
+        id: format!("ci-{}-{}", project_id, run.id),
+        project_id: project_id.to_string(),
+        event_type: "block".to_string(),
+        source: "ci".to_string(),
+        message: format!("CI doc check {} on {} ({}): {} {}", outcome, branch, short_sha, title, run.url),
+        file_path: None,
+        created_at,
+    })
+}
+
+/// Insert events, ignoring ones already recorded. Returns how many were new.
+pub fn record_events(db: &Connection, events: &[EnforcementEvent]) -> Result<u32, String> {
+    let mut inserted = 0;
+    for event in events {
+        inserted += db
+            .execute(
+                "INSERT OR IGNORE INTO enforcement_events (id, project_id, event_type, source, message, file_path, created_at)
+                 VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7)",
+                rusqlite::params![
+                    event.id,
+                    event.project_id,
+                    event.event_type,
+                    event.source,
+                    event.message,
+                    event.file_path,
+                    event.created_at
+                ],
+            )
+            .map_err(|e| format!("Failed to record CI event: {}", e))? as u32;
+    }
+    Ok(inserted)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_support::memory_db;
+
+    fn run(id: &str, completed: bool, conclusion: Option<&str>) -> CiRun {
+        CiRun {
+            id: id.to_string(),
+            completed,
+            conclusion: conclusion.map(str::to_string),
+            branch: Some("main".to_string()),
+            sha: "abcdef1234567".to_string(),
+            url: format!("https://github.com/o/r/actions/runs/{}", id),
+            title: Some("Add login".to_string()),
+            updated_at: "2025-03-01T10:00:00Z".to_string(),
+        }
+    }
+
+    #[test]
+    fn test_parse_remote_and_detect_provider() {
+        let parsed = |url: &str| parse_remote(url).map(|(h, p)| format!("{} {}", h, p));
+        assert_eq!(parsed("https://github.com/octo/app.git").as_deref(), Some("github.com octo/app"));
+        assert_eq!(parsed("https://github.com/octo/app").as_deref(), Some("github.com octo/app"));
+        assert_eq!(parsed("ssh://git@github.com/octo/app.git").as_deref(), Some("github.com octo/app"));
+        assert_eq!(parsed("git@gitlab.com:group/sub/app.git").as_deref(), Some("gitlab.com group/sub/app"));
+        assert_eq!(
+            parsed("ssh://git@gitlab.example.com:2222/team/app.git").as_deref(),
+            Some("gitlab.example.com team/app")
+        );
+        assert_eq!(parsed("https://user@bitbucket.org/ws/app.git").as_deref(), Some("bitbucket.org ws/app"));
+        assert_eq!(parsed("https://github.com/octo"), None);
+        assert_eq!(parsed("/srv/git/app.git"), None);
+
+        assert_eq!(detect_provider("github.com"), Some(ForgeProvider::Github));
+        assert_eq!(detect_provider("gitlab.example.com"), Some(ForgeProvider::Gitlab));
+        assert_eq!(detect_provider("bitbucket.org"), Some(ForgeProvider::Bitbucket));
+        assert_eq!(detect_provider("git.example.com"), None);
+    }
+
+    #[test]
+    fn test_failure_events_are_recorded_once() {
+        let conn = memory_db();
+
+        let runs = vec![
+            run("1", true, Some("failure")),
+            run("2", true, Some("success")),
+            run("3", false, None),
+            run("gitlab-4", true, Some("timed_out")),
+        ];
+        let events: Vec<EnforcementEvent> = runs.iter().filter_map(|r| failure_event("p1", r)).collect();
+        assert_eq!(events.len(), 2);
+        assert_eq!(events[0].id, "ci-p1-1");
+        assert_eq!(events[1].id, "ci-p1-gitlab-4");
+        assert_eq!(events[0].source, "ci");
+        assert!(events[0].message.contains("failed on main (abcdef1)"));
+        assert!(events[1].message.contains("timed out"));
+        assert_eq!(events[0].created_at, "2025-03-01T10:00:00+00:00");
+
+        assert_eq!(record_events(&conn, &events).unwrap(), 2);
+        assert_eq!(record_events(&conn, &events).unwrap(), 0);
+    }
+
+    #[test]
+    fn test_load_config_defaults_and_stored() {
+        let conn = memory_db();
+        assert_eq!(load_config(&conn, "p1"), ForgeConfig::default());
+
+        conn.execute(
+            "INSERT INTO settings (key, value) VALUES ('git_forge.p1', '{\"provider\":\"gitlab\",\"apiUrl\":\"https://git.example.com/api/v4\"}')",
+            [],
+        )
+        .unwrap();
+        let config = load_config(&conn, "p1");
+        assert_eq!(config.provider, Some(ForgeProvider::Gitlab));
+        assert_eq!(config.api_url.as_deref(), Some("https://git.example.com/api/v4"));
+        assert!(load_token(&conn, ForgeProvider::Gitlab).unwrap_err().contains("GitLab token not configured"));
+    }
+}
@@ -0,0 +1,168 @@
+//! @module core/git_policy
+//! @description Per-project git permission checks with an ask-and-wait confirmation flow
+//!
+//! PURPOSE:
+//! - Load a project's GitPolicy from settings
+//! - Decide whether a git mutation may run: allowed, denied, or after the user confirms
+//! - Track pending confirmations until the frontend answers or they time out
+//!
+//! DEPENDENCIES:
+//! - rusqlite - Settings lookup
+//! - tauri - AppHandle and Emitter for the confirmation event
+//! - tokio - oneshot channel and timeout for awaiting the answer
+//! - models::git_policy - GitPolicy, GitPermission, GitOperation, GitPermissionRequest
+//!
+//! EXPORTS:
+//! - POLICY_SETTING_PREFIX - Settings key prefix ("git_policy.<project_id>")
+//! - PERMISSION_REQUEST_EVENT - Tauri event carrying a GitPermissionRequest
+//! - load_policy - A project's policy (defaults when unset)
+//! - authorize - Resolve a permission to allowed/denied, asking the user for "ask"
+//! - answer - Deliver the user's answer to a pending request
+//!
+//! PATTERNS:
+//! - Callers read the permission with load_policy while holding their connection, then
+//!   await authorize without it (a &Connection held across .await is not Send)
+//! - An unanswered request is denied after ASK_TIMEOUT so unattended loops keep moving
+//!
+//! CLAUDE NOTES:
+//! - Pending requests live in memory only; restarting the app drops them (and denies)
+//! - Denials return an Err message suitable for loop outcomes
+
+use rusqlite::Connection;
+use std::collections::HashMap;
+use std::sync::{Mutex, OnceLock};
+use std::time::Duration;
+use tauri::{AppHandle, Emitter, Runtime};
+use tokio::sync::oneshot;
+
+use crate::models::git_policy::{GitOperation, GitPermission, GitPermissionRequest, GitPolicy};
+
+/// Settings key prefix for per-project policies (JSON GitPolicy).
+pub const POLICY_SETTING_PREFIX: &str = "git_policy.";
+
+/// Tauri event emitted when a git operation needs confirmation.
+pub const PERMISSION_REQUEST_EVENT: &str = "git-permission-request";
+
+/// How long an "ask" waits for an answer before denying.
+const ASK_TIMEOUT: Duration = Duration::from_secs(300);
+
+fn pending() -> &'static Mutex<HashMap<String, oneshot::Sender<bool>>> {
+    static PENDING: OnceLock<Mutex<HashMap<String, oneshot::Sender<bool>>>> = OnceLock::new();
+    PENDING.get_or_init(|| Mutex::new(HashMap::new()))
+}
+
+/// A project's git policy from settings; defaults when unset or unreadable.
+pub fn load_policy(db: &Connection, project_id: &str) -> GitPolicy {
+    db.query_row(
+        "SELECT value FROM settings WHERE key = ?1",
+        [format!("{}{}", POLICY_SETTING_PREFIX, project_id)],
+        |row| row.get::<_, String>(0),
+    )
+    .ok()
+    .and_then(|json| serde_json::from_str(&json).ok())
+    .unwrap_or_default()
+}
+
+/// Resolve `permission` for `operation`. "ask" emits a GitPermissionRequest and
+/// waits for answer() (denied after ASK_TIMEOUT). Err explains a denial.
+pub async fn authorize<R: Runtime>(
+    app: &AppHandle<R>,
+    permission: GitPermission,
+    project_id: &str,
+    operation: GitOperation,
+    detail: &str,
+) -> Result<(), String> {
+    match permission {
+        GitPermission::Yes => return Ok(()),
+        GitPermission::No => return Err(denied(operation, "the project's git policy")),
+        GitPermission::Ask => {}
+    }
+
+    let (request, rx) = register_request(project_id, operation, detail);
+    let request_id = request.request_id.clone();
+    if app.emit(PERMISSION_REQUEST_EVENT, request).is_err() {
+        forget(&request_id);
+        return Err(denied(operation, "no confirmation window"));
+    }
+
+    match tokio::time::timeout(ASK_TIMEOUT, rx).await {
+        Ok(Ok(true)) => Ok(()),
+        Ok(_) => Err(denied(operation, "the user")),
+        Err(_) => {
+            forget(&request_id);
+            Err(denied(operation, "timeout waiting for confirmation"))
+        }
+    }
+}
+
+/// Deliver the user's answer to a pending request.
+pub fn answer(request_id: &str, allow: bool) -> Result<(), String> {
+    let sender = pending()
+        .lock()
+        .map_err(|e| format!("Failed to lock git permission requests: {}", e))?
+        .remove(request_id)
+        .ok_or_else(|| "Git permission request not found or already answered".to_string())?;
+    sender
+        .send(allow)
+        .map_err(|_| "Git permission request is no longer waiting".to_string())
+}
+
+fn register_request(
+    project_id: &str,
+    operation: GitOperation,
+    detail: &str,
+) -> (GitPermissionRequest, oneshot::Receiver<bool>) {
+    let (tx, rx) = oneshot::channel();
+    let request = GitPermissionRequest {
+        request_id: uuid::Uuid::new_v4().to_string(),
+        project_id: project_id.to_string(),
+        operation,
+        detail: detail.to_string(),
+        requested_at: chrono::Utc::now().to_rfc3339(),
+    };
+    if let Ok(mut pending) = pending().lock() {
+        pending.insert(request.request_id.clone(), tx);
+    }
+    (request, rx)
+}
+
+fn forget(request_id: &str) {
+    if let Ok(mut pending) = pending().lock() {
+        pending.remove(request_id);
+    }
+}
+
+fn denied(operation: GitOperation, by: &str) -> String {
+    format!("Not allowed to {} (denied by {})", operation.label(), by)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_support::memory_db;
+
+    #[test]
+    fn test_load_policy_defaults_and_partial_json() {
+        let conn = memory_db();
+        assert_eq!(load_policy(&conn, "p1"), GitPolicy::default());
+
+        conn.execute(
+            "INSERT INTO settings (key, value) VALUES ('git_policy.p1', '{\"allowCommit\":\"no\"}')",
+            [],
+        )
+        .unwrap();
+        let policy = load_policy(&conn, "p1");
+        assert_eq!(policy.permission(GitOperation::Commit), GitPermission::No);
+        assert_eq!(policy.permission(GitOperation::Branch), GitPermission::Yes);
+        assert_eq!(policy.permission(GitOperation::Push), GitPermission::Ask);
+    }
+
+    #[test]
+    fn test_answer_resolves_pending_request_once() {
+        let (request, mut rx) = register_request("p1", GitOperation::Push, "origin main");
+        assert!(answer(&request.request_id, true).is_ok());
+        assert_eq!(rx.try_recv(), Ok(true));
+        assert!(answer(&request.request_id, true).is_err());
+        assert!(answer("unknown", false).is_err());
+    }
+}
@@ -0,0 +1,183 @@
+//! @module core/github_ci
+//! @description GitHub adapter for the forge integration: doc-check runs from GitHub Actions
+//!
+//! PURPOSE:
+//! - Fetch recent runs of the generated doc-check workflow with a GitHub token
+//! - Convert them to forge-neutral CiRuns for git_forge::failure_event
+//!
+//! DEPENDENCIES:
+//! - reqwest - GitHub REST API calls
+//! - core::git_forge - GitForge trait, ForgeFuture, CiRun
+//! - models::git_forge - ForgeProvider
+//!
+//! EXPORTS:
+//! - GITHUB_TOKEN_SETTING - Settings key of the (encrypted) GitHub token
+//! - DOC_CHECK_WORKFLOW - Workflow file the CI snippet installs
+//! - WorkflowRun - The fields of a GitHub workflow run we use
+//! - GitHubForge - GitForge implementation for github.com repositories
+//! - fetch_doc_check_runs - Recent doc-check runs from the GitHub API
+//! - ci_run - WorkflowRun as a CiRun
+//!
+//! PATTERNS:
+//! - CiRun ids are the bare run ids, so event ids stay "ci-<project_id>-<run_id>"
+//! - The event time is the run's last update, normalized to RFC 3339 by failure_event
+//!
+//! CLAUDE NOTES:
+//! - The token needs "actions: read" (fine-grained) or "repo" scope for private repos
+//! - A missing workflow (never pushed) comes back as 404 and is reported as such
+//! - Remote parsing and token loading live in core::git_forge (shared by all forges)
+
+use serde::Deserialize;
+
+use crate::core::git_forge::{CiRun, ForgeFuture, GitForge};
+use crate::models::git_forge::ForgeProvider;
+
+/// Settings key of the GitHub token (stored encrypted).
+pub const GITHUB_TOKEN_SETTING: &str = "github_token";
+
+/// Workflow file written by the GitHub Actions CI snippet.
+pub const DOC_CHECK_WORKFLOW: &str = "doc-check.yml";
+
+/// Runs requested per sync
+const RUNS_PER_SYNC: u32 = 30;
+
+/// The fields of a GitHub workflow run we use.
+#[derive(Debug, Clone, Deserialize)]
+pub struct WorkflowRun {
+    pub id: u64,
+    pub status: Option<String>,
+    pub conclusion: Option<String>,
+    pub head_branch: Option<String>,
+    pub head_sha: String,
+    pub html_url: String,
+    pub display_title: Option<String>,
+    pub updated_at: String,
+}
+
+#[derive(Debug, Deserialize)]
+struct WorkflowRunsResponse {
+    workflow_runs: Vec<WorkflowRun>,
+}
+
+/// GitHub adapter: doc-check workflow runs from GitHub Actions.
+pub struct GitHubForge {
+    owner: String,
+    repo: String,
+    token: String,
+}
+
+impl GitHubForge {
+    pub fn new(owner: &str, repo: &str, token: &str) -> Self {
+        GitHubForge { owner: owner.to_string(), repo: repo.to_string(), token: token.to_string() }
+    }
+}
+
+impl GitForge for GitHubForge {
+    fn provider(&self) -> ForgeProvider {
+        ForgeProvider::Github
+    }
+
+    fn repository(&self) -> String {
+        format!("{}/{}", self.owner, self.repo)
+    }
+
+    fn fetch_doc_check_runs<'a>(&'a self, client: &'a reqwest::Client) -> ForgeFuture<'a, Vec<CiRun>> {
+        Box::pin(async move {
+            let runs = fetch_doc_check_runs(client, &self.token, &self.owner, &self.repo).await?;
+            Ok(runs.into_iter().map(ci_run).collect())
+        })
+    }
+}
+
+/// Recent runs of the doc-check workflow, newest first.
+pub async fn fetch_doc_check_runs(
+    client: &reqwest::Client,
+    token: &str,
+    owner: &str,
+    repo: &str,
+) -> Result<Vec<WorkflowRun>, String> {
+    let url = format!(
+        "https://api.github.com/repos/{}/{}/actions/workflows/{}/runs?per_page={}",
+        owner, repo, DOC_CHECK_WORKFLOW, RUNS_PER_SYNC
+    );
+    let response = client
+        .get(&url)
+        .bearer_auth(token)
+        .header("Accept", "application/vnd.github+json")
+        .header("X-GitHub-Api-Version", "2022-11-28")
+        .header("User-Agent", "project-jumpstart")
+        .send()
+        .await
+        .map_err(|e| format!("GitHub request failed: {}", e))?;
+
+    match response.status().as_u16() {
+        200 => {}
+        401 => return Err("GitHub rejected the token (401). Check it in Settings.".to_string()),
+        404 => {
+            return Err(format!(
+                "No {} workflow found in {}/{} (or the token cannot read Actions)",
+                DOC_CHECK_WORKFLOW, owner, repo
+            ))
+        }
+        status => return Err(format!("GitHub API error ({})", status)),
+    }
+
+    let body: WorkflowRunsResponse = response
+        .json()
+        .await
+        .map_err(|e| format!("Failed to parse GitHub response: {}", e))?;
+    Ok(body.workflow_runs)
+}
+
+/// A workflow run in forge-neutral form. Ids stay bare numbers so events recorded
+/// before GitLab and Bitbucket support keep deduplicating.
+pub fn ci_run(run: WorkflowRun) -> CiRun {
+    CiRun {
+        id: run.id.to_string(),
+        completed: run.status.as_deref() == Some("completed"),
+        conclusion: run.conclusion,
+        branch: run.head_branch,
+        sha: run.head_sha,
+        url: run.html_url,
+        title: run.display_title,
+        updated_at: run.updated_at,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::core::git_forge;
+    use crate::models::enforcement::EnforcementEvent;
+
+    fn run(id: u64, status: &str, conclusion: Option<&str>) -> WorkflowRun {
+        WorkflowRun {
+            id,
+            status: Some(status.to_string()),
+            conclusion: conclusion.map(str::to_string),
+            head_branch: Some("main".to_string()),
+            head_sha: "abcdef1234567".to_string(),
+            html_url: format!("https://github.com/o/r/actions/runs/{}", id),
+            display_title: Some("Add login".to_string()),
+            updated_at: "2025-03-01T10:00:00Z".to_string(),
+        }
+    }
+
+    #[test]
+    fn test_workflow_runs_become_failure_events() {
+        let runs = vec![
+            run(1, "completed", Some("failure")),
+            run(2, "completed", Some("success")),
+            run(3, "in_progress", None),
+            run(4, "completed", Some("timed_out")),
+        ];
+        let events: Vec<EnforcementEvent> =
+            runs.into_iter().map(ci_run).filter_map(|r| git_forge::failure_event("p1", &r)).collect();
+        assert_eq!(events.len(), 2);
+        assert_eq!(events[0].id, "ci-p1-1");
+        assert_eq!(events[0].source, "ci");
+        assert!(events[0].message.contains("failed on main (abcdef1)"));
+        assert!(events[1].message.contains("timed out"));
+        assert_eq!(events[0].created_at, "2025-03-01T10:00:00+00:00");
+    }
+}
@@ -0,0 +1,192 @@
+//! @module core/gitlab_ci
+//! @description GitLab adapter for the forge integration: doc-check jobs from GitLab CI
+//!
+//! PURPOSE:
+//! - Fetch recent jobs of a GitLab project (gitlab.com or self-hosted) with a token
+//! - Keep the doc-check jobs the CI snippet installs and convert them to CiRuns
+//!
+//! DEPENDENCIES:
+//! - reqwest - GitLab REST API (v4) calls
+//! - serde - Job deserialization
+//! - core::git_forge - GitForge trait, ForgeFuture, CiRun
+//! - models::git_forge - ForgeProvider
+//!
+//! EXPORTS:
+//! - GITLAB_TOKEN_SETTING - Settings key of the (encrypted) GitLab token
+//! - DOC_CHECK_JOB - Job name the GitLab CI snippet defines
+//! - Job - The fields of a GitLab CI job we use
+//! - GitLabForge - GitForge implementation for GitLab projects
+//! - fetch_doc_check_jobs - Recent doc-check jobs from the GitLab API
+//! - ci_run - Job as a CiRun
+//!
+//! PATTERNS:
+//! - The project is addressed by its URL-encoded full path ("group%2Fsub%2Frepo"), so
+//!   nested groups work without a numeric id lookup
+//! - CiRun ids are "gitlab-<job_id>"
+//! - Status mapping: success -> success, failed -> failure (timed_out when the failure
+//!   reason is a timeout), canceled/skipped -> cancelled, anything else is unfinished
+//!
+//! CLAUDE NOTES:
+//! - The token is a personal, group, or project access token with read_api scope, sent as
+//!   PRIVATE-TOKEN
+//! - The jobs endpoint has no name filter; JOBS_PER_SYNC jobs are read and filtered here
+
+use serde::Deserialize;
+
+use crate::core::git_forge::{CiRun, ForgeFuture, GitForge};
+use crate::models::git_forge::ForgeProvider;
+
+/// Settings key of the GitLab token (stored encrypted).
+pub const GITLAB_TOKEN_SETTING: &str = "gitlab_token";
+
+/// Job name written by the GitLab CI snippet.
+pub const DOC_CHECK_JOB: &str = "doc-check";
+
+/// Jobs requested per sync (all jobs, not only doc checks)
+const JOBS_PER_SYNC: u32 = 100;
+
+/// The fields of a GitLab CI job we use.
+#[derive(Debug, Clone, Deserialize)]
+pub struct Job {
+    pub id: u64,
+    pub name: String,
+    pub status: String,
+    #[serde(rename = "ref")]
+    pub git_ref: Option<String>,
+    pub web_url: String,
+    pub failure_reason: Option<String>,
+    pub finished_at: Option<String>,
+    pub created_at: String,
+    pub commit: Option<JobCommit>,
+}
+
+#[derive(Debug, Clone, Deserialize)]
+pub struct JobCommit {
+    pub id: String,
+    pub title: Option<String>,
+}
+
+/// GitLab adapter: doc-check jobs from GitLab CI.
+pub struct GitLabForge {
+    api_url: String,
+    project_path: String,
+    token: String,
+}
+
+impl GitLabForge {
+    /// `api_url` is the v4 API base, e.g. "https://gitlab.com/api/v4".
+    pub fn new(api_url: &str, project_path: &str, token: &str) -> Self {
+        GitLabForge {
+            api_url: api_url.trim_end_matches('/').to_string(),
+            project_path: project_path.to_string(),
+            token: token.to_string(),
+        }
+    }
+}
+
+impl GitForge for GitLabForge {
+    fn provider(&self) -> ForgeProvider {
+        ForgeProvider::Gitlab
+    }
+
+    fn repository(&self) -> String {
+        self.project_path.clone()
+    }
+
+    fn fetch_doc_check_runs<'a>(&'a self, client: &'a reqwest::Client) -> ForgeFuture<'a, Vec<CiRun>> {
+        Box::pin(async move {
+            let jobs = fetch_doc_check_jobs(client, &self.api_url, &self.token, &self.project_path).await?;
+            Ok(jobs.into_iter().map(ci_run).collect())
+        })
+    }
+}
+
+/// Recent doc-check jobs of `project_path`, newest first.
+pub async fn fetch_doc_check_jobs(
+    client: &reqwest::Client,
+    api_url: &str,
+    token: &str,
+    project_path: &str,
+) -> Result<Vec<Job>, String> {
+    let url = format!(
+        "{}/projects/{}/jobs?per_page={}",
+        api_url,
+        project_path.replace('/', "%2F"),
+        JOBS_PER_SYNC
+    );
+    let response = client
+        .get(&url)
+        .header("PRIVATE-TOKEN", token)
+        .header("User-Agent", "project-jumpstart")
+        .send()
+        .await
+        .map_err(|e| format!("GitLab request failed: {}", e))?;
+
+    match response.status().as_u16() {
+        200 => {}
+        401 => return Err("GitLab rejected the token (401). Check it in Settings.".to_string()),
+        403 => return Err("GitLab token lacks read_api access to this project (403)".to_string()),
+        404 => return Err(format!("GitLab project {} not found (or the token cannot see it)", project_path)),
+        status => return Err(format!("GitLab API error ({})", status)),
+    }
+
+    let jobs: Vec<Job> = response
+        .json()
+        .await
+        .map_err(|e| format!("Failed to parse GitLab response: {}", e))?;
+    Ok(jobs.into_iter().filter(|job| job.name == DOC_CHECK_JOB).collect())
+}
+
+/// A job in forge-neutral form.
+pub fn ci_run(job: Job) -> CiRun {
+    let conclusion = match job.status.as_str() {
+        "success" => Some("success"),
+        "failed" if job.failure_reason.as_deref() == Some("job_execution_timeout") => Some("timed_out"),
+        "failed" => Some("failure"),
+        "canceled" | "skipped" => Some("cancelled"),
+        _ => None,
+    };
+    CiRun {
+        id: format!("gitlab-{}", job.id),
+        completed: conclusion.is_some(),
+        conclusion: conclusion.map(str::to_string),
+        branch: job.git_ref,
+        sha: job.commit.as_ref().map(|c| c.id.clone()).unwrap_or_default(),
+        url: job.web_url,
+        title: job.commit.and_then(|c| c.title),
+        updated_at: job.finished_at.unwrap_or(job.created_at),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_jobs_become_ci_runs() {
+        let jobs: Vec<Job> = serde_json::from_str(
+            r#"[
+                {"id": 7, "name": "doc-check", "status": "failed", "ref": "feature/x",
+                 "web_url": "https://gitlab.com/g/r/-/jobs/7", "failure_reason": "script_failure",
+                 "finished_at": "2025-03-01T10:00:00.000Z", "created_at": "2025-03-01T09:58:00.000Z",
+                 "commit": {"id": "abcdef1234567", "title": "Add login"}},
+                {"id": 8, "name": "doc-check", "status": "failed", "ref": "main",
+                 "web_url": "https://gitlab.com/g/r/-/jobs/8", "failure_reason": "job_execution_timeout",
+                 "finished_at": null, "created_at": "2025-03-01T09:00:00.000Z", "commit": null},
+                {"id": 9, "name": "doc-check", "status": "running", "ref": "main",
+                 "web_url": "https://gitlab.com/g/r/-/jobs/9", "created_at": "2025-03-01T11:00:00.000Z"}
+            ]"#,
+        )
+        .unwrap();
+        let runs: Vec<CiRun> = jobs.into_iter().map(ci_run).collect();
+
+        assert_eq!(runs[0].id, "gitlab-7");
+        assert_eq!(runs[0].conclusion.as_deref(), Some("failure"));
+        assert_eq!(runs[0].branch.as_deref(), Some("feature/x"));
+        assert_eq!(runs[0].sha, "abcdef1234567");
+        assert_eq!(runs[0].updated_at, "2025-03-01T10:00:00.000Z");
+        assert_eq!(runs[1].conclusion.as_deref(), Some("timed_out"));
+        assert_eq!(runs[1].updated_at, "2025-03-01T09:00:00.000Z");
+        assert!(!runs[2].completed);
+    }
+}
@@ -6,16 +6,25 @@
 //! - Break down scores by component (CLAUDE.md, modules, freshness, skills, context, enforcement)
 //! - Identify quick wins for score improvement
 //! - Determine context rot risk level
+//! - Score individual files (freshness, doc lint, test linkage, churn) for worst-first sorting
 //!
 //! DEPENDENCIES:
 //! - models::project - HealthScore, HealthComponents, QuickWin types
 //! - core::freshness - Freshness scoring engine
 //! - std::path::Path - File system checks
+//! - core::doc_validation - Per-file DEPENDENCIES/EXPORTS lint for module quality
+//! - core::proc - git log for per-file churn
+//! - commands::test_plans - Test file lookup for module quality
 //!
 //! EXPORTS:
 //! - calculate_health - Calculate full health score for a project path (without test metrics)
 //! - calculate_health_with_tests - Calculate health score with optional test coverage and pass rate
+//! - calculate_health_cached - Same score computed from a DocHealthCache (no tree walk)
+//! - DocHealthCache - Per-file doc coverage, freshness, and header tokens, updatable per file;
+//!   also reports coverage and outdated files for doc goals
 //! - estimate_tokens - Estimate token count for a string (chars / 4 approximation)
+//! - module_quality - Composite 0-100 quality score for one file from its signals
+//! - attach_module_quality - Fill ModuleStatus.quality for scanned modules
 //!
 //! PATTERNS:
 //! - Component weights must sum to 100
@@ -33,12 +42,22 @@
 //! - Context rot risk is based on doc scores only (CLAUDE.md + modules + freshness)
 //! - Risk thresholds: low (>=70% of doc max), medium (40-69%), high (<40%)
 //! - Quick wins include TDD subagent setup when test framework detected but no subagent exists
+//! - Module docs, freshness, and context header tokens all come from one DocHealthCache walk;
+//!   the watcher keeps a cache for the watched project and calls update_file per change
 //! - Quick wins include Claude Code hooks setup when test framework detected but no hooks configured
+//! - Module quality weights: Freshness=40, Lint=20, Tests=25, Churn=15 (churn = commits in the
+//!   last 90 days; busy files lose points because their docs drift fastest)
 
-use crate::commands::enforcement;
+use crate::commands::{enforcement, test_plans};
+use crate::core::doc_validation;
 use crate::core::freshness;
+use crate::core::proc::{self, ProcLimits};
+use crate::core::test_runner;
+use crate::models::module_doc::{ModuleQuality, ModuleStatus};
 use crate::models::project::{HealthComponents, HealthScore, QuickWin};
+use std::collections::HashMap;
 use std::path::Path;
+use std::process::Command;
 
 // Weights adjusted to accommodate performance component (total must = 100)
 const WEIGHT_CLAUDE_MD: u32 = 20;
@@ -50,6 +69,18 @@ const WEIGHT_ENFORCEMENT: u32 = 7;
 const WEIGHT_TESTS: u32 = 10;
 const WEIGHT_PERFORMANCE: u32 = 12;
 
+// Per-file module quality weights (total must = 100)
+const QUALITY_WEIGHT_FRESHNESS: u32 = 40;
+const QUALITY_WEIGHT_LINT: u32 = 20;
+const QUALITY_WEIGHT_TESTS: u32 = 25;
+const QUALITY_WEIGHT_CHURN: u32 = 15;
+
+/// Lint points lost per phantom/undocumented header entry.
+const LINT_PENALTY: u32 = 5;
+
+/// git --since value for the churn window.
+const CHURN_SINCE: &str = "90 days ago";
+
 /// Calculate the full health score for a project at the given path.
 /// `skill_count` is the number of skills created for the project (from DB).
 /// `test_coverage` is the latest test coverage percentage (0-100, from test runs).
@@ -61,6 +92,7 @@ pub fn calculate_health(project_path: &str, skill_count: u32) -> HealthScore {
 }
 
 /// Calculate health score with optional test metrics and performance score.
+#[allow(dead_code)]
 pub fn calculate_health_with_tests(
     project_path: &str,
     skill_count: u32,
@@ -69,13 +101,35 @@ pub fn calculate_health_with_tests(
     performance_score: Option<u32>,
     discovered_test_count: Option<u32>,
 ) -> HealthScore {
+    let docs = DocHealthCache::build(project_path);
+    calculate_health_cached(
+        &docs,
+        skill_count,
+        test_coverage,
+        test_pass_rate,
+        performance_score,
+        discovered_test_count,
+    )
+}
+
+/// Calculate the health score using cached per-file documentation data.
+/// Only the cheap components (CLAUDE.md, MCP config, enforcement) touch the disk.
+pub fn calculate_health_cached(
+    docs: &DocHealthCache,
+    skill_count: u32,
+    test_coverage: Option<f64>,
+    test_pass_rate: Option<f64>,
+    performance_score: Option<u32>,
+    discovered_test_count: Option<u32>,
+) -> HealthScore {
+    let project_path = docs.project_path();
     let path = Path::new(project_path);
 
     let claude_md_score = calculate_claude_md_score(path);
-    let module_docs_stats = calculate_module_docs_stats(path);
-    let freshness_score = calculate_freshness_score(project_path);
+    let module_docs_stats = docs.module_docs_stats();
+    let freshness_score = docs.freshness_score();
     let skills_score = calculate_skills_score(skill_count);
-    let context_score = calculate_context_score(path);
+    let context_score = calculate_context_score(path, docs.header_tokens);
     let enforcement_score = enforcement::calculate_enforcement_score(project_path);
     let tests_score = calculate_tests_score(test_coverage, test_pass_rate, discovered_test_count);
     let perf_score = calculate_performance_score(performance_score);
@@ -135,6 +189,8 @@ pub fn calculate_health_with_tests(
         quick_wins,
         context_rot_risk,
         discovered_test_count,
+        goals: None,
+        convention_violations: None,
     }
 }
 
@@ -143,6 +199,86 @@ pub fn estimate_tokens(content: &str) -> u32 {
     (content.len() as f64 / 4.0).ceil() as u32
 }
 
+/// Composite quality score for one file (0-100).
+/// `freshness_score` is the file's doc freshness (0 when missing), `lint_violations` its
+/// phantom/undocumented header entries, `recent_commits` its commits in the churn window.
+pub fn module_quality(freshness_score: u32, lint_violations: u32, has_tests: bool, recent_commits: u32) -> ModuleQuality {
+    let freshness = (freshness_score.min(100) as f64 / 100.0 * QUALITY_WEIGHT_FRESHNESS as f64).round() as u32;
+    let lint = QUALITY_WEIGHT_LINT.saturating_sub(lint_violations.saturating_mul(LINT_PENALTY));
+    let tests = if has_tests { QUALITY_WEIGHT_TESTS } else { 0 };
+    let churn = match recent_commits {
+        0..=2 => QUALITY_WEIGHT_CHURN,
+        3..=5 => 10,
+        6..=10 => 5,
+        _ => 0,
+    };
+
+    ModuleQuality {
+        score: freshness + lint + tests + churn,
+        freshness_score: freshness,
+        lint_score: lint,
+        test_score: tests,
+        churn_score: churn,
+        lint_violations,
+        has_tests,
+        recent_commits,
+    }
+}
+
+/// Fill `quality` for every module of a scan. Lint comes from one doc_validation pass and
+/// churn from one `git log`; a project outside git gets full churn points.
+pub fn attach_module_quality(project_path: &str, modules: &mut [ModuleStatus]) {
+    let lint: HashMap<String, u32> = doc_validation::validate_project("", project_path)
+        .map(|report| {
+            report
+                .files
+                .iter()
+                .map(|f| (f.path.clone(), f.issue_count() as u32))
+                .collect()
+        })
+        .unwrap_or_default();
+    let churn = recent_commit_counts(project_path);
+
+    for module in modules.iter_mut().filter(|m| m.status != "ignored") {
+        let name = Path::new(&module.path)
+            .file_name()
+            .map(|n| n.to_string_lossy().to_string())
+            .unwrap_or_default();
+        let has_tests = test_runner::is_test_file(&name)
+            || test_plans::find_corresponding_test_file(&module.path, project_path).is_some();
+        module.quality = Some(module_quality(
+            module.freshness_score,
+            lint.get(&module.path).copied().unwrap_or(0),
+            has_tests,
+            churn.get(&module.path).copied().unwrap_or(0),
+        ));
+    }
+}
+
+/// Commits per project-relative path within the churn window.
+fn recent_commit_counts(project_path: &str) -> HashMap<String, u32> {
+    let since = format!("--since={}", CHURN_SINCE);
+    let output = proc::run(
+        Command::new("git")
+            .args(["log", &since, "--name-only", "--relative", "--format="])
+            .current_dir(project_path),
+        ProcLimits::GIT,
+    );
+    match output {
+        Ok(out) if out.success() => count_changed_paths(&String::from_utf8_lossy(&out.stdout)),
+        _ => HashMap::new(),
+    }
+}
+
+/// Count path lines in `git log --name-only --format=` output (one line per file per commit).
+fn count_changed_paths(log: &str) -> HashMap<String, u32> {
+    let mut counts = HashMap::new();
+    for line in log.lines().map(str::trim).filter(|l| !l.is_empty()) {
+        *counts.entry(line.to_string()).or_insert(0) += 1;
+    }
+    counts
+}
+
 /// Score the performance component (0-12 points).
 /// Based on the latest performance analysis overall score (0-100).
 /// Scales linearly: full health weight at perf score >= 80.
@@ -245,40 +381,12 @@ fn calculate_tests_score(
     run_score.max(discovery_score).min(WEIGHT_TESTS)
 }
 
-/// Score the freshness component (0-12 points).
-/// Uses the freshness engine to calculate average freshness across documented files.
-fn calculate_freshness_score(project_path: &str) -> u32 {
-    let modules = match freshness::check_project_freshness(project_path) {
-        Ok(m) => m,
-        Err(_) => return 0,
-    };
-
-    if modules.is_empty() {
-        return 0;
-    }
-
-    // Only consider files that have documentation (not "missing")
-    let documented: Vec<&crate::models::module_doc::ModuleStatus> = modules
-        .iter()
-        .filter(|m| m.status != "missing")
-        .collect();
-
-    if documented.is_empty() {
-        return 0;
-    }
-
-    let avg_freshness: f64 =
-        documented.iter().map(|m| m.freshness_score as f64).sum::<f64>() / documented.len() as f64;
-
-    let raw_score = (avg_freshness / 100.0 * WEIGHT_FRESHNESS as f64).round() as u32;
-    raw_score.min(WEIGHT_FRESHNESS)
-}
-
 /// Score the context efficiency component (0-7 points).
 /// Estimates persistent token usage (CLAUDE.md + doc headers + MCP configs) against the
 /// 200k context budget. Lower usage means more headroom for conversations.
 /// Scoring: <25% usage → 7pts, 25-50% → 5pts, 50-75% → 3pts, >75% → 1pt.
-fn calculate_context_score(project_path: &Path) -> u32 {
+/// `header_tokens` is the doc header total from DocHealthCache.
+fn calculate_context_score(project_path: &Path, header_tokens: u32) -> u32 {
     const CONTEXT_BUDGET: u32 = 200_000;
 
     if !project_path.exists() {
@@ -296,7 +404,7 @@ fn calculate_context_score(project_path: &Path) -> u32 {
     }
 
     // Doc header tokens from source files across the project
-    persistent_tokens += estimate_dir_header_tokens(project_path);
+    persistent_tokens += header_tokens;
 
     // MCP config tokens
     let mcp_json = project_path.join(".mcp.json");
@@ -333,42 +441,6 @@ fn calculate_context_score(project_path: &Path) -> u32 {
     score.min(WEIGHT_CONTEXT)
 }
 
-/// Recursively estimate tokens from documentation headers in source files.
-fn estimate_dir_header_tokens(dir: &Path) -> u32 {
-    let mut tokens: u32 = 0;
-    let entries = match std::fs::read_dir(dir) {
-        Ok(e) => e,
-        Err(_) => return 0,
-    };
-
-    for entry in entries.flatten() {
-        let path = entry.path();
-        let name = entry.file_name().to_string_lossy().to_string();
-
-        if name.starts_with('.')
-            || name == "node_modules"
-            || name == "target"
-            || name == "dist"
-            || name == "build"
-        {
-            continue;
-        }
-
-        if path.is_dir() {
-            tokens += estimate_dir_header_tokens(&path);
-        } else if is_documentable_file(&name) {
-            if let Ok(content) = std::fs::read_to_string(&path) {
-                let header: String = content.lines().take(30).collect::<Vec<_>>().join("\n");
-                if header.contains("@module") || header.contains("@description") {
-                    tokens += estimate_tokens(&header);
-                }
-            }
-        }
-    }
-
-    tokens
-}
-
 /// Count MCP server entries in a JSON config string.
 fn count_mcp_servers(content: &str) -> u32 {
     if let Ok(value) = serde_json::from_str::<serde_json::Value>(content) {
@@ -395,74 +467,6 @@ struct ModuleDocStats {
     undocumented_files: u32,
 }
 
-/// Score the module documentation component (0-20 points).
-/// Scans the entire project tree for source files with documentation headers.
-/// Returns both the score and file counts for use in quick win messages.
-fn calculate_module_docs_stats(project_path: &Path) -> ModuleDocStats {
-    if !project_path.exists() {
-        return ModuleDocStats {
-            score: 0,
-            total_files: 0,
-            documented_files: 0,
-            undocumented_files: 0,
-        };
-    }
-
-    let mut total_files = 0u32;
-    let mut documented_files = 0u32;
-
-    count_documented_files(project_path, &mut total_files, &mut documented_files);
-
-    let undocumented_files = total_files.saturating_sub(documented_files);
-
-    if total_files == 0 {
-        return ModuleDocStats {
-            score: 0,
-            total_files: 0,
-            documented_files: 0,
-            undocumented_files: 0,
-        };
-    }
-
-    let coverage = (documented_files as f64) / (total_files as f64);
-    let raw_score = (coverage * WEIGHT_MODULE_DOCS as f64).round() as u32;
-    let score = raw_score.min(WEIGHT_MODULE_DOCS);
-
-    ModuleDocStats {
-        score,
-        total_files,
-        documented_files,
-        undocumented_files,
-    }
-}
-
-/// Recursively count source files and check for documentation headers.
-fn count_documented_files(dir: &Path, total: &mut u32, documented: &mut u32) {
-    let entries = match std::fs::read_dir(dir) {
-        Ok(e) => e,
-        Err(_) => return,
-    };
-
-    for entry in entries.flatten() {
-        let path = entry.path();
-        let name = entry.file_name().to_string_lossy().to_string();
-
-        // Skip hidden dirs and common non-source dirs
-        if name.starts_with('.') || name == "node_modules" || name == "target" || name == "dist" || name == "build" {
-            continue;
-        }
-
-        if path.is_dir() {
-            count_documented_files(&path, total, documented);
-        } else if is_documentable_file(&name) {
-            *total += 1;
-            if has_doc_header(&path) {
-                *documented += 1;
-            }
-        }
-    }
-}
-
 /// Check if a file is a source file that should have documentation.
 fn is_documentable_file(name: &str) -> bool {
     // Delegate to analyzer's canonical implementation
@@ -471,12 +475,7 @@ fn is_documentable_file(name: &str) -> bool {
 
 /// Check if a file has a documentation header.
 /// Looks for `@module` or `//! @module` patterns in the first 30 lines.
-fn has_doc_header(path: &Path) -> bool {
-    let content = match std::fs::read_to_string(path) {
-        Ok(c) => c,
-        Err(_) => return false,
-    };
-
+fn has_doc_header(content: &str) -> bool {
     let header_area: String = content.lines().take(30).collect::<Vec<_>>().join("\n");
     header_area.contains("@module") || header_area.contains("@description")
 }
@@ -770,10 +769,281 @@ fn generate_quick_wins(
     wins
 }
 
+/// One file's contribution to the documentation-driven components.
+#[derive(Debug, Clone, Default, PartialEq)]
+struct FileHealth {
+    /// Counted toward module doc coverage (documentable and not generated)
+    tracked: bool,
+    documented: bool,
+    /// Freshness score when the file is in the freshness scan and has a doc header
+    freshness: Option<u32>,
+    /// Doc header tokens counted toward the context component
+    header_tokens: u32,
+}
+
+/// Per-file documentation health for one project. Built with one tree walk,
+/// then kept current file-by-file from watcher events so the module docs,
+/// freshness, and context components never need a full rescan.
+#[derive(Debug, Clone)]
+pub struct DocHealthCache {
+    project_path: String,
+    overrides: Vec<String>,
+    files: HashMap<String, FileHealth>,
+    total_files: u32,
+    documented_files: u32,
+    freshness_sum: u64,
+    freshness_count: u32,
+    header_tokens: u32,
+}
+
+/// Directories skipped by the module docs and context walks.
+const SKIPPED_DIRS: &[&str] = &["node_modules", "target", "dist", "build"];
+
+impl DocHealthCache {
+    /// Walk the project once and record every file's contribution.
+    pub fn build(project_path: &str) -> Self {
+        let mut cache = DocHealthCache {
+            project_path: project_path.to_string(),
+            overrides: super::analyzer::load_generated_overrides(project_path),
+            files: HashMap::new(),
+            total_files: 0,
+            documented_files: 0,
+            freshness_sum: 0,
+            freshness_count: 0,
+            header_tokens: 0,
+        };
+        let root = Path::new(project_path);
+        if root.exists() {
+            cache.walk(root);
+        }
+        cache
+    }
+
+    pub fn project_path(&self) -> &str {
+        &self.project_path
+    }
+
+    fn walk(&mut self, dir: &Path) {
+        let entries = match std::fs::read_dir(dir) {
+            Ok(e) => e,
+            Err(_) => return,
+        };
+        for entry in entries.flatten() {
+            let path = entry.path();
+            let name = entry.file_name().to_string_lossy().to_string();
+            if name.starts_with('.') || SKIPPED_DIRS.contains(&name.as_str()) {
+                continue;
+            }
+            if path.is_dir() {
+                self.walk(&path);
+            } else if let Some(rel) = self.relative(&path) {
+                if let Some(health) = self.evaluate(&rel, &path) {
+                    self.add(&health);
+                    self.files.insert(rel, health);
+                }
+            }
+        }
+    }
+
+    fn relative(&self, path: &Path) -> Option<String> {
+        path.strip_prefix(&self.project_path)
+            .ok()
+            .map(|p| p.to_string_lossy().replace('\\', "/"))
+            .filter(|p| !p.is_empty())
+    }
+
+    /// Compute a file's contribution, or None when it contributes nothing.
+    fn evaluate(&self, rel: &str, path: &Path) -> Option<FileHealth> {
+        let parts: Vec<&str> = rel.split('/').collect();
+        let (name, dirs) = parts.split_last()?;
+        if name.starts_with('.') || dirs.iter().any(|d| d.starts_with('.') || SKIPPED_DIRS.contains(d)) {
+            return None;
+        }
+        let documentable = is_documentable_file(name);
+        if !documentable && !self.overrides.iter().any(|o| o == rel) {
+            return None;
+        }
+
+        // Oversized and binary files are left out of doc health
+        let content = super::analyzer::read_source(&path.to_string_lossy()).ok()?;
+        let mut health = FileHealth::default();
+
+        if documentable {
+            let header: String = content.lines().take(30).collect::<Vec<_>>().join("\n");
+            if header.contains("@module") || header.contains("@description") {
+                health.header_tokens = estimate_tokens(&header);
+            }
+        }
+        if super::analyzer::should_track_file(name, rel, &content, &self.overrides) {
+            health.tracked = true;
+            health.documented = has_doc_header(&content);
+            if freshness::in_scan_scope(rel) {
+                let result = freshness::check_file_freshness(&path.to_string_lossy(), &self.project_path);
+                if result.status != "missing" {
+                    health.freshness = Some(result.score);
+                }
+            }
+        }
+
+        if health == FileHealth::default() {
+            None
+        } else {
+            Some(health)
+        }
+    }
+
+    fn add(&mut self, health: &FileHealth) {
+        if health.tracked {
+            self.total_files += 1;
+            if health.documented {
+                self.documented_files += 1;
+            }
+        }
+        if let Some(score) = health.freshness {
+            self.freshness_sum += score as u64;
+            self.freshness_count += 1;
+        }
+        self.header_tokens += health.header_tokens;
+    }
+
+    fn subtract(&mut self, health: &FileHealth) {
+        if health.tracked {
+            self.total_files -= 1;
+            if health.documented {
+                self.documented_files -= 1;
+            }
+        }
+        if let Some(score) = health.freshness {
+            self.freshness_sum -= score as u64;
+            self.freshness_count -= 1;
+        }
+        self.header_tokens -= health.header_tokens;
+    }
+
+    /// Re-evaluate one changed, created, or removed path. A removed directory
+    /// drops every file under it; editing the generated-overrides list
+    /// rebuilds the cache. Returns true when any contribution changed.
+    pub fn update_file(&mut self, path: &Path) -> bool {
+        let Some(rel) = self.relative(path) else {
+            return false;
+        };
+        if rel == super::analyzer::GENERATED_OVERRIDES_FILE {
+            *self = DocHealthCache::build(&self.project_path);
+            return true;
+        }
+
+        if !path.exists() {
+            let prefix = format!("{}/", rel);
+            let removed: Vec<String> = self
+                .files
+                .keys()
+                .filter(|k| **k == rel || k.starts_with(&prefix))
+                .cloned()
+                .collect();
+            for key in &removed {
+                if let Some(old) = self.files.remove(key) {
+                    self.subtract(&old);
+                }
+            }
+            return !removed.is_empty();
+        }
+        if path.is_dir() {
+            return false;
+        }
+
+        let new = self.evaluate(&rel, path);
+        let old = self.files.remove(&rel);
+        let changed = old != new;
+        if let Some(old) = &old {
+            self.subtract(old);
+        }
+        if let Some(new) = new {
+            self.add(&new);
+            self.files.insert(rel, new);
+        }
+        changed
+    }
+
+    fn module_docs_stats(&self) -> ModuleDocStats {
+        if self.total_files == 0 {
+            return ModuleDocStats {
+                score: 0,
+                total_files: 0,
+                documented_files: 0,
+                undocumented_files: 0,
+            };
+        }
+        let coverage = self.documented_files as f64 / self.total_files as f64;
+        let raw_score = (coverage * WEIGHT_MODULE_DOCS as f64).round() as u32;
+        ModuleDocStats {
+            score: raw_score.min(WEIGHT_MODULE_DOCS),
+            total_files: self.total_files,
+            documented_files: self.documented_files,
+            undocumented_files: self.total_files - self.documented_files,
+        }
+    }
+
+    /// Documented files / tracked files (0-100); 0 for a project with no tracked files.
+    pub fn doc_coverage(&self) -> f64 {
+        if self.total_files == 0 {
+            return 0.0;
+        }
+        self.documented_files as f64 / self.total_files as f64 * 100.0
+    }
+
+    /// Project-relative paths of documented files whose freshness is below the
+    /// "outdated" threshold, sorted.
+    pub fn outdated_files(&self) -> Vec<String> {
+        let mut files: Vec<String> = self
+            .files
+            .iter()
+            .filter(|(_, health)| health.freshness.is_some_and(|score| score < freshness::OUTDATED_BELOW))
+            .map(|(path, _)| path.clone())
+            .collect();
+        files.sort();
+        files
+    }
+
+    /// Average freshness of documented files, scaled to the freshness weight.
+    fn freshness_score(&self) -> u32 {
+        if self.freshness_count == 0 {
+            return 0;
+        }
+        let avg_freshness = self.freshness_sum as f64 / self.freshness_count as f64;
+        let raw_score = (avg_freshness / 100.0 * WEIGHT_FRESHNESS as f64).round() as u32;
+        raw_score.min(WEIGHT_FRESHNESS)
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
 
+    #[test]
+    fn test_module_quality_components() {
+        let best = module_quality(100, 0, true, 1);
+        assert_eq!(best.score, 100);
+
+        let worst = module_quality(0, 7, false, 25);
+        assert_eq!((worst.freshness_score, worst.lint_score, worst.test_score, worst.churn_score), (0, 0, 0, 0));
+        assert_eq!(worst.score, 0);
+
+        let mid = module_quality(50, 1, false, 4);
+        assert_eq!((mid.freshness_score, mid.lint_score, mid.churn_score), (20, 15, 10));
+        assert_eq!(mid.score, 45);
+        assert_eq!(mid.lint_violations, 1);
+        assert_eq!(mid.recent_commits, 4);
+    }
+
+    #[test]
+    fn test_count_changed_paths() {
+        let log = "src/a.rs\nsrc/b.rs\n\nsrc/a.rs\n\n";
+        let counts = count_changed_paths(log);
+        assert_eq!(counts.get("src/a.rs"), Some(&2));
+        assert_eq!(counts.get("src/b.rs"), Some(&1));
+        assert_eq!(counts.len(), 2);
+    }
+
     #[test]
     fn test_estimate_tokens() {
         assert_eq!(estimate_tokens(""), 0);
@@ -804,7 +1074,7 @@ mod tests {
 
     #[test]
     fn test_context_score_nonexistent_path() {
-        let score = calculate_context_score(Path::new("/nonexistent/path/12345"));
+        let score = calculate_context_score(Path::new("/nonexistent/path/12345"), 0);
         assert_eq!(score, 0);
     }
 
@@ -895,4 +1165,34 @@ mod tests {
         assert_eq!(calculate_tests_score(Some(0.0), Some(0.0), None), 0);
         assert_eq!(calculate_tests_score(Some(0.0), Some(0.0), Some(0)), 0);
     }
+
+    #[test]
+    fn test_doc_health_cache_incremental_matches_rebuild() {
+        let dir = tempfile::tempdir().unwrap();
+        let root = dir.path();
+        let project = root.to_string_lossy().to_string();
+        std::fs::create_dir_all(root.join("src/util")).unwrap();
+        std::fs::write(root.join("src/a.ts"), "export const a = 1;\n").unwrap();
+        std::fs::write(root.join("src/util/b.ts"), "export const b = 2;\n").unwrap();
+
+        let mut cache = DocHealthCache::build(&project);
+        assert_eq!(cache.module_docs_stats().total_files, 2);
+        assert_eq!(cache.module_docs_stats().documented_files, 0);
+
+        let documented = "/**\n * @module a\n * @description Module a\n *\n * PURPOSE:\n * - Hold a\n *\n * EXPORTS:\n * - a - The value\n */\nexport const a = 1;\n";
+        std::fs::write(root.join("src/a.ts"), documented).unwrap();
+        assert!(cache.update_file(&root.join("src/a.ts")));
+        assert!(!cache.update_file(&root.join("src/a.ts")));
+        assert_eq!(cache.module_docs_stats().documented_files, 1);
+
+        std::fs::remove_dir_all(root.join("src/util")).unwrap();
+        assert!(cache.update_file(&root.join("src/util")));
+
+        let rebuilt = DocHealthCache::build(&project);
+        assert_eq!(cache.module_docs_stats().total_files, rebuilt.module_docs_stats().total_files);
+        assert_eq!(cache.module_docs_stats().score, rebuilt.module_docs_stats().score);
+        assert_eq!(cache.freshness_score(), rebuilt.freshness_score());
+        assert_eq!(cache.header_tokens, rebuilt.header_tokens);
+        assert_eq!(cache.files, rebuilt.files);
+    }
 }
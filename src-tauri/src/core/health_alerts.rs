@@ -0,0 +1,309 @@
+//! @module core/health_alerts
+//! @description Per-project health alert thresholds that fire once when a metric crosses them
+//!
+//! PURPOSE:
+//! - Validate, store, and load a project's alert thresholds
+//! - Compare health score, stale files, and failing tests against the thresholds
+//! - Track each metric's alert state so an alert fires on the crossing, not on every scan
+//! - Publish a health_alert event for each crossing (activity feed + notification)
+//!
+//! DEPENDENCIES:
+//! - rusqlite - settings (thresholds), health_alert_state, test_runs
+//! - core::events - health_alert events for the activity log and notifications
+//! - models::health_alert - HealthAlertThresholds, HealthAlertMetric, HealthAlert
+//!
+//! EXPORTS:
+//! - AlertMetrics - Current metric values (failing tests only when a test run exists)
+//! - AlertTransition - A metric crossing into or out of its alert range
+//! - validate_thresholds - Reject out-of-range thresholds
+//! - load_thresholds / store_thresholds - Thresholds in the "health_alerts.<project_id>" setting
+//! - transitions - Crossings given the metrics and which alerts are already active
+//! - check - Evaluate, record state changes, and publish events; returns the transitions
+//! - list_alerts - Tracked alert state for a project
+//! - latest_failed_tests - Failures in the project's latest completed test run
+//!
+//! PATTERNS:
+//! - An alert fires when its metric enters the alert range and stays active (silent) until the
+//!   metric recovers; recovery is recorded in the activity feed but not notified
+//! - Saving thresholds resets the project's alert state, so a threshold that is already
+//!   breached fires once on the next check
+//!
+//! CLAUDE NOTES:
+//! - check runs on every health score computation (15s polls, watcher scans); it returns early
+//!   when the project has no thresholds and only queries test_runs for a failing-tests threshold
+//! - Stale files are documented files whose doc header is outdated (DocHealthCache::outdated_files)
+
+use std::collections::HashSet;
+
+use rusqlite::Connection;
+
+use crate::core::events::{self, AppEvent};
+use crate::models::health_alert::{HealthAlert, HealthAlertMetric, HealthAlertThresholds};
+
+const SETTING_PREFIX: &str = "health_alerts.";
+
+/// Current values of the alerted metrics.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct AlertMetrics {
+    pub health_score: u32,
+    pub stale_files: u32,
+    /// None when the project has no completed test run
+    pub failing_tests: Option<u32>,
+}
+
+impl AlertMetrics {
+    fn value(&self, metric: HealthAlertMetric) -> Option<u32> {
+        match metric {
+            HealthAlertMetric::HealthScore => Some(self.health_score),
+            HealthAlertMetric::StaleFiles => Some(self.stale_files),
+            HealthAlertMetric::FailingTests => self.failing_tests,
+        }
+    }
+}
+
+/// A metric entering (active) or leaving its alert range.
+#[derive(Debug, Clone, PartialEq)]
+pub struct AlertTransition {
+    pub metric: HealthAlertMetric,
+    pub threshold: u32,
+    pub value: u32,
+    pub active: bool,
+}
+
+impl AlertTransition {
+    /// Activity feed / notification text.
+    pub fn message(&self) -> String {
+        match (self.metric, self.active) {
+            (HealthAlertMetric::HealthScore, true) => {
+                format!("Health score dropped to {} (alert below {})", self.value, self.threshold)
+            }
+            (HealthAlertMetric::HealthScore, false) => {
+                format!("Health score recovered to {} (alert below {})", self.value, self.threshold)
+            }
+            (HealthAlertMetric::StaleFiles, true) => {
+                format!("{} stale doc files (alert above {})", self.value, self.threshold)
+            }
+            (HealthAlertMetric::StaleFiles, false) => {
+                format!("Stale doc files back to {} (alert above {})", self.value, self.threshold)
+            }
+            (HealthAlertMetric::FailingTests, true) => {
+                format!("{} failing tests (alert above {})", self.value, self.threshold)
+            }
+            (HealthAlertMetric::FailingTests, false) => {
+                format!("Failing tests back to {} (alert above {})", self.value, self.threshold)
+            }
+        }
+    }
+}
+
+/// Whether a value is in the metric's alert range.
+fn breached(metric: HealthAlertMetric, threshold: u32, value: u32) -> bool {
+    match metric {
+        HealthAlertMetric::HealthScore => value < threshold,
+        HealthAlertMetric::StaleFiles | HealthAlertMetric::FailingTests => value > threshold,
+    }
+}
+
+pub fn validate_thresholds(thresholds: HealthAlertThresholds) -> Result<HealthAlertThresholds, String> {
+    if thresholds.health_below.is_some_and(|t| t == 0 || t > 100) {
+        return Err("Health score alert must be between 1 and 100.".to_string());
+    }
+    Ok(thresholds)
+}
+
+/// A project's thresholds; none when unset or unreadable.
+pub fn load_thresholds(db: &Connection, project_id: &str) -> HealthAlertThresholds {
+    db.query_row(
+        "SELECT value FROM settings WHERE key = ?1",
+        [format!("{}{}", SETTING_PREFIX, project_id)],
+        |row| row.get::<_, String>(0),
+    )
+    .ok()
+    .and_then(|json| serde_json::from_str(&json).ok())
+    .unwrap_or_default()
+}
+
+/// Write a project's (already validated) thresholds and reset its alert state.
+pub fn store_thresholds(db: &Connection, project_id: &str, thresholds: &HealthAlertThresholds) -> Result<(), String> {
+    let json =
+        serde_json::to_string(thresholds).map_err(|e| format!("Failed to serialize alert thresholds: {}", e))?;
+    db.execute(
+        "INSERT OR REPLACE INTO settings (key, value) VALUES (?1, ?2)",
+        rusqlite::params![format!("{}{}", SETTING_PREFIX, project_id), json],
+    )
+    .map_err(|e| format!("Failed to save alert thresholds: {}", e))?;
+    db.execute("DELETE FROM health_alert_state WHERE project_id = ?1", [project_id])
+        .map_err(|e| format!("Failed to reset alert state: {}", e))?;
+    Ok(())
+}
+
+/// Crossings for the metrics that have a threshold and a value. `active` holds the metrics
+/// whose alert is currently firing.
+pub fn transitions(
+    thresholds: &HealthAlertThresholds,
+    metrics: &AlertMetrics,
+    active: &HashSet<HealthAlertMetric>,
+) -> Vec<AlertTransition> {
+    HealthAlertMetric::ALL
+        .into_iter()
+        .filter_map(|metric| {
+            let threshold = thresholds.threshold(metric)?;
+            let value = metrics.value(metric)?;
+            let now_active = breached(metric, threshold, value);
+            (now_active != active.contains(&metric)).then_some(AlertTransition {
+                metric,
+                threshold,
+                value,
+                active: now_active,
+            })
+        })
+        .collect()
+}
+
+/// Check a project's metrics against its thresholds. Records each crossing in
+/// health_alert_state and publishes a health_alert event for it.
+pub fn check(db: &Connection, project_id: &str, health_score: u32, stale_files: u32) -> Result<Vec<AlertTransition>, String> {
+    let thresholds = load_thresholds(db, project_id);
+    if thresholds.is_empty() {
+        return Ok(Vec::new());
+    }
+    let metrics = AlertMetrics {
+        health_score,
+        stale_files,
+        failing_tests: thresholds.failing_tests_above.and_then(|_| latest_failed_tests(db, project_id)),
+    };
+    let active: HashSet<HealthAlertMetric> =
+        list_alerts(db, project_id)?.into_iter().filter(|a| a.active).map(|a| a.metric).collect();
+
+    let found = transitions(&thresholds, &metrics, &active);
+    let now = chrono::Utc::now().to_rfc3339();
+    for t in &found {
+        let message = t.message();
+        db.execute(
+            "INSERT OR REPLACE INTO health_alert_state (project_id, metric, threshold, value, active, message, changed_at)
+             VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7)",
+            rusqlite::params![project_id, t.metric.as_str(), t.threshold, t.value, t.active, message, now],
+        )
+        .map_err(|e| format!("Failed to record alert state: {}", e))?;
+        events::publish(
+            db,
+            AppEvent::HealthAlert {
+                project_id: project_id.to_string(),
+                metric: t.metric.as_str().to_string(),
+                threshold: t.threshold,
+                value: t.value,
+                active: t.active,
+                message,
+            },
+        );
+    }
+    Ok(found)
+}
+
+/// Tracked alert state for a project (metrics that have fired at least once since the
+/// thresholds were saved).
+pub fn list_alerts(db: &Connection, project_id: &str) -> Result<Vec<HealthAlert>, String> {
+    let mut stmt = db
+        .prepare(
+            "SELECT metric, threshold, value, active, message, changed_at
+             FROM health_alert_state WHERE project_id = ?1 ORDER BY changed_at DESC",
+        )
+        .map_err(|e| format!("Query prepare error: {}", e))?;
+    let rows = stmt
+        .query_map([project_id], |row| {
+            Ok((
+                row.get::<_, String>(0)?,
+                row.get::<_, u32>(1)?,
+                row.get::<_, u32>(2)?,
+                row.get::<_, bool>(3)?,
+                row.get::<_, String>(4)?,
+                row.get::<_, String>(5)?,
+            ))
+        })
+        .map_err(|e| format!("Query error: {}", e))?;
+
+    let mut alerts = Vec::new();
+    for row in rows {
+        let (metric, threshold, value, active, message, changed_at) =
+            row.map_err(|e| format!("Row mapping error: {}", e))?;
+        if let Some(metric) = HealthAlertMetric::parse(&metric) {
+            alerts.push(HealthAlert { metric, threshold, value, active, message, changed_at });
+        }
+    }
+    Ok(alerts)
+}
+
+/// Failed tests in the project's latest completed test run.
+pub fn latest_failed_tests(db: &Connection, project_id: &str) -> Option<u32> {
+    db.query_row(
+        "SELECT tr.failed_tests FROM test_runs tr
+         JOIN test_plans tp ON tr.plan_id = tp.id
+         WHERE tp.project_id = ?1 AND tr.status = 'completed'
+         ORDER BY tr.completed_at DESC
+         LIMIT 1",
+        [project_id],
+        |row| row.get(0),
+    )
+    .ok()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_support::with_project;
+
+    #[test]
+    fn test_transitions_fire_once_per_crossing() {
+        let thresholds = HealthAlertThresholds {
+            health_below: Some(60),
+            stale_files_above: Some(5),
+            failing_tests_above: Some(0),
+        };
+        let metrics = AlertMetrics { health_score: 55, stale_files: 5, failing_tests: None };
+        let mut active = HashSet::new();
+
+        let fired = transitions(&thresholds, &metrics, &active);
+        assert_eq!(
+            fired,
+            vec![AlertTransition { metric: HealthAlertMetric::HealthScore, threshold: 60, value: 55, active: true }]
+        );
+        assert_eq!(fired[0].message(), "Health score dropped to 55 (alert below 60)");
+
+        // Still below: no repeat
+        active.insert(HealthAlertMetric::HealthScore);
+        let metrics = AlertMetrics { health_score: 50, stale_files: 6, failing_tests: Some(0) };
+        let fired = transitions(&thresholds, &metrics, &active);
+        assert_eq!(fired.len(), 1);
+        assert_eq!(fired[0].metric, HealthAlertMetric::StaleFiles);
+
+        // Recovered: one cleared transition
+        let metrics = AlertMetrics { health_score: 60, stale_files: 0, failing_tests: Some(0) };
+        let cleared = transitions(&thresholds, &metrics, &active);
+        assert_eq!(cleared.len(), 1);
+        assert!(!cleared[0].active);
+
+        assert!(validate_thresholds(HealthAlertThresholds { health_below: Some(101), ..Default::default() }).is_err());
+    }
+
+    #[test]
+    fn test_check_records_state_and_activity() {
+        let conn = with_project();
+
+        assert!(check(&conn, "p1", 10, 50).unwrap().is_empty());
+        store_thresholds(&conn, "p1", &HealthAlertThresholds { health_below: Some(70), ..Default::default() }).unwrap();
+
+        assert_eq!(check(&conn, "p1", 65, 0).unwrap().len(), 1);
+        assert!(check(&conn, "p1", 62, 0).unwrap().is_empty());
+        assert_eq!(check(&conn, "p1", 80, 0).unwrap().len(), 1);
+
+        let alerts = list_alerts(&conn, "p1").unwrap();
+        assert_eq!(alerts.len(), 1);
+        assert!(!alerts[0].active);
+        assert_eq!(alerts[0].value, 80);
+
+        let activities: u32 = conn
+            .query_row("SELECT COUNT(*) FROM activities WHERE project_id = 'p1'", [], |row| row.get(0))
+            .unwrap();
+        assert_eq!(activities, 2);
+    }
+}
@@ -0,0 +1,242 @@
+//! @module core/health_history
+//! @description Tie documentation health to git commits and detect regressions
+//!
+//! PURPOSE:
+//! - Summarize per-file freshness into doc coverage, average freshness, and degraded files
+//! - Read git HEAD, the files changed in a commit range, and the commits in it
+//! - Find commit ranges after which doc coverage or freshness dropped sharply
+//!
+//! DEPENDENCIES:
+//! - models::module_doc - ModuleStatus (freshness scan output)
+//! - models::health_history - HealthSnapshot, HealthRegression types
+//! - std::process::Command - git rev-parse / diff / log
+//! - core::proc - git commands with a timeout and output cap
+//!
+//! EXPORTS:
+//! - DEFAULT_MIN_DROP - Percentage-point drop that counts as a regression
+//! - DocMetrics - Coverage, freshness, and degraded files for one scan
+//! - doc_metrics - Summarize a freshness scan
+//! - git_head - Current HEAD commit sha
+//! - changed_files - Files changed between two commits
+//! - commits_between - "<short sha> <subject>" lines for from..to
+//! - detect_regressions - Compare consecutive snapshots and report drops
+//!
+//! PATTERNS:
+//! - Only consecutive snapshots at different commits are compared
+//! - A regression is a drop >= min_drop in doc coverage or average freshness
+//! - Files involved = newly degraded files that were also changed in the range;
+//!   when git cannot diff the range (rewritten history) all newly degraded files are reported
+//!
+//! CLAUDE NOTES:
+//! - Paths from the freshness scan are relative to the project root, which is assumed to be
+//!   the git root (same assumption as check_test_staleness)
+//! - Git helpers return None/empty on any failure; regressions still work without git
+
+use std::collections::HashSet;
+use std::path::Path;
+use std::process::Command;
+
+use chrono::Utc;
+
+use crate::core::proc::{self, ProcLimits};
+use crate::models::health_history::{HealthRegression, HealthSnapshot};
+use crate::models::module_doc::ModuleStatus;
+
+/// Percentage-point drop in coverage or freshness that counts as a regression.
+pub const DEFAULT_MIN_DROP: f64 = 10.0;
+
+/// Maximum commits listed per regression.
+const MAX_COMMITS: usize = 50;
+
+/// Documentation metrics for one freshness scan.
+#[derive(Debug, Clone, PartialEq)]
+pub struct DocMetrics {
+    /// Documented files / total files (0-100)
+    pub doc_coverage: f64,
+    /// Average freshness of documented files (0-100)
+    pub avg_freshness: f64,
+    pub total_files: u32,
+    pub documented_files: u32,
+    /// Missing or outdated files, sorted
+    pub degraded_files: Vec<String>,
+}
+
+/// Summarize a freshness scan into coverage, freshness, and degraded files.
+pub fn doc_metrics(modules: &[ModuleStatus]) -> DocMetrics {
+    let documented: Vec<&ModuleStatus> = modules.iter().filter(|m| m.status != "missing").collect();
+    let total_files = modules.len() as u32;
+    let documented_files = documented.len() as u32;
+
+    let doc_coverage = if total_files == 0 {
+        0.0
+    } else {
+        documented_files as f64 / total_files as f64 * 100.0
+    };
+    let avg_freshness = if documented.is_empty() {
+        0.0
+    } else {
+        documented.iter().map(|m| m.freshness_score as f64).sum::<f64>() / documented.len() as f64
+    };
+
+    let mut degraded_files: Vec<String> = modules
+        .iter()
+        .filter(|m| m.status == "missing" || m.status == "outdated")
+        .map(|m| m.path.clone())
+        .collect();
+    degraded_files.sort();
+
+    DocMetrics {
+        doc_coverage,
+        avg_freshness,
+        total_files,
+        documented_files,
+        degraded_files,
+    }
+}
+
+/// Trimmed stdout of a git command, or None if it fails or times out.
+fn git(project_path: &Path, args: &[&str]) -> Option<String> {
+    let output = proc::run(Command::new("git").args(args).current_dir(project_path), ProcLimits::GIT).ok()?;
+    if !output.success() {
+        return None;
+    }
+    Some(String::from_utf8_lossy(&output.stdout).trim().to_string())
+}
+
+/// Current HEAD sha, or None outside a git repo / before the first commit.
+pub fn git_head(project_path: &Path) -> Option<String> {
+    git(project_path, &["rev-parse", "HEAD"]).filter(|s| !s.is_empty())
+}
+
+/// Files changed between two commits, or None if git cannot diff the range.
+pub fn changed_files(project_path: &Path, from: &str, to: &str) -> Option<HashSet<String>> {
+    let out = git(project_path, &["diff", "--name-only", from, to])?;
+    Some(out.lines().map(|l| l.to_string()).collect())
+}
+
+/// "<short sha> <subject>" for commits in from..to, newest first.
+pub fn commits_between(project_path: &Path, from: &str, to: &str) -> Vec<String> {
+    let max = format!("-{}", MAX_COMMITS);
+    let range = format!("{}..{}", from, to);
+    git(project_path, &["log", &max, "--format=%h %s", &range])
+        .map(|out| out.lines().map(|l| l.to_string()).collect())
+        .unwrap_or_default()
+}
+
+/// Compare consecutive snapshots (oldest first) and report sharp drops.
+/// `git_context` resolves (changed files, commits) for a from..to range.
+pub fn detect_regressions<F>(snapshots: &[HealthSnapshot], min_drop: f64, mut git_context: F) -> Vec<HealthRegression>
+where
+    F: FnMut(&str, &str) -> (Option<HashSet<String>>, Vec<String>),
+{
+    let now = Utc::now().to_rfc3339();
+    let mut regressions = Vec::new();
+
+    for pair in snapshots.windows(2) {
+        let (before, after) = (&pair[0], &pair[1]);
+        if before.commit_sha == after.commit_sha {
+            continue;
+        }
+        let coverage_drop = before.doc_coverage - after.doc_coverage;
+        let freshness_drop = before.avg_freshness - after.avg_freshness;
+        if coverage_drop < min_drop && freshness_drop < min_drop {
+            continue;
+        }
+
+        let previously: HashSet<&String> = before.degraded_files.iter().collect();
+        let newly_degraded: Vec<&String> = after.degraded_files.iter().filter(|f| !previously.contains(f)).collect();
+        let (changed, commits) = git_context(&before.commit_sha, &after.commit_sha);
+        let files = match changed {
+            Some(changed) => newly_degraded.into_iter().filter(|f| changed.contains(*f)).cloned().collect(),
+            None => newly_degraded.into_iter().cloned().collect(),
+        };
+
+        regressions.push(HealthRegression {
+            from_commit: before.commit_sha.clone(),
+            to_commit: after.commit_sha.clone(),
+            commits,
+            coverage_before: before.doc_coverage,
+            coverage_after: after.doc_coverage,
+            freshness_before: before.avg_freshness,
+            freshness_after: after.avg_freshness,
+            health_before: before.health_score,
+            health_after: after.health_score,
+            files,
+            detected_at: now.clone(),
+        });
+    }
+
+    regressions
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn module(path: &str, status: &str, freshness: u32) -> ModuleStatus {
+        ModuleStatus {
+            path: path.to_string(),
+            status: status.to_string(),
+            freshness_score: freshness,
+            changes: None,
+            suggested_doc: None,
+            quality: None,
+            analyzer: None,
+            change_authors: None,
+        }
+    }
+
+    fn snapshot(commit: &str, coverage: f64, freshness: f64, degraded: &[&str]) -> HealthSnapshot {
+        HealthSnapshot {
+            id: commit.to_string(),
+            project_id: "p1".to_string(),
+            commit_sha: commit.to_string(),
+            health_score: 50,
+            doc_coverage: coverage,
+            avg_freshness: freshness,
+            total_files: 4,
+            documented_files: 4,
+            degraded_files: degraded.iter().map(|s| s.to_string()).collect(),
+            goals_met: None,
+            created_at: "2025-01-01T00:00:00Z".to_string(),
+        }
+    }
+
+    #[test]
+    fn test_doc_metrics() {
+        let metrics = doc_metrics(&[
+            module("b.rs", "missing", 0),
+            module("a.rs", "outdated", 60),
+            module("c.rs", "current", 100),
+            module("d.rs", "current", 80),
+        ]);
+        assert_eq!(metrics.doc_coverage, 75.0);
+        assert_eq!(metrics.avg_freshness, 80.0);
+        assert_eq!(metrics.degraded_files, vec!["a.rs", "b.rs"]);
+        assert_eq!(doc_metrics(&[]).doc_coverage, 0.0);
+    }
+
+    #[test]
+    fn test_detect_regressions() {
+        let snapshots = vec![
+            snapshot("c1", 100.0, 95.0, &[]),
+            snapshot("c1", 50.0, 95.0, &["x.rs"]), // same commit: not compared
+            snapshot("c2", 95.0, 92.0, &["x.rs"]), // small drop
+            snapshot("c3", 70.0, 90.0, &["x.rs", "new.rs", "other.rs"]),
+            snapshot("c4", 70.0, 75.0, &["x.rs", "new.rs", "other.rs", "stale.rs"]),
+        ];
+
+        let regressions = detect_regressions(&snapshots, DEFAULT_MIN_DROP, |from, to| match (from, to) {
+            ("c2", "c3") => (Some(["new.rs".to_string()].into_iter().collect()), vec!["c3 add module".to_string()]),
+            _ => (None, vec![]),
+        });
+
+        assert_eq!(regressions.len(), 2);
+        assert_eq!((regressions[0].from_commit.as_str(), regressions[0].to_commit.as_str()), ("c2", "c3"));
+        assert_eq!(regressions[0].files, vec!["new.rs"]);
+        assert_eq!(regressions[0].commits, vec!["c3 add module"]);
+        // No diff available: all newly degraded files
+        assert_eq!(regressions[1].files, vec!["stale.rs"]);
+        assert_eq!(regressions[1].freshness_after, 75.0);
+    }
+}
@@ -0,0 +1,406 @@
+//! @module core/issue_rules
+//! @description Local rules engine that extracts structured issues from tool output
+//!
+//! PURPOSE:
+//! - Recognize compiler, linter, and test runner output in a RALPH iteration's text
+//! - Produce issues with tool, kind, file, line, column, and code, without an API call
+//! - Give users without an API key meaningful feedback for the next iteration
+//!
+//! DEPENDENCIES:
+//! - regex - Per-tool line patterns, compiled once
+//!
+//! EXPORTS:
+//! - RuleIssue - One issue found by a rule pack
+//! - MAX_ISSUES - Cap on issues returned for one output
+//! - extract - Run every rule pack over an output
+//!
+//! PATTERNS:
+//! - Rule packs: tsc (both output styles), eslint (stylish), cargo/rustc (diagnostics, test
+//!   failures, panics), pytest (FAILED/ERROR summary lines, traceback locations), go vet /
+//!   go build, and go test
+//! - Multi-line formats are handled with a little state: a cargo diagnostic takes its location
+//!   from the `-->` line after it, eslint rows take their file from the header line above them,
+//!   and go test log lines attach to the preceding `--- FAIL`; pytest traceback locations
+//!   give FAILED summary lines for the same file their line number
+//! - Cargo summary lines ("could not compile", "aborting due to", "generated N warnings") are
+//!   skipped; issues are de-duplicated by (file, line, message)
+//!
+//! CLAUDE NOTES:
+//! - ANSI color codes are stripped first, since most tools color output on a terminal
+//! - kind is "error" | "warning" | "type_error" | "test_failure", matching the issue types the
+//!   AI extractor uses so both feed the same iteration prompt
+//! - To support another tool, add its patterns to Packs and a branch in extract; keep tests
+//!   with a real sample of the tool's output
+
+use std::sync::OnceLock;
+
+use regex::Regex;
+
+/// At most this many issues are returned; later ones are usually cascades.
+pub const MAX_ISSUES: usize = 20;
+
+/// An issue found in tool output.
+#[derive(Debug, Clone, PartialEq)]
+pub struct RuleIssue {
+    /// "tsc" | "eslint" | "cargo" | "pytest" | "go"
+    pub tool: &'static str,
+    /// "error" | "warning" | "type_error" | "test_failure"
+    pub kind: &'static str,
+    pub file: Option<String>,
+    pub line: Option<u32>,
+    pub column: Option<u32>,
+    /// Tool-specific code or rule (TS2322, E0425, no-unused-vars)
+    pub code: Option<String>,
+    pub message: String,
+}
+
+impl RuleIssue {
+    fn new(tool: &'static str, kind: &'static str, message: &str) -> Self {
+        RuleIssue {
+            tool,
+            kind,
+            file: None,
+            line: None,
+            column: None,
+            code: None,
+            message: message.trim().chars().take(300).collect(),
+        }
+    }
+
+    fn at(mut self, file: &str, line: Option<&str>, column: Option<&str>) -> Self {
+        self.file = Some(file.trim_start_matches("./").to_string());
+        self.line = line.and_then(|l| l.parse().ok());
+        self.column = column.and_then(|c| c.parse().ok());
+        self
+    }
+
+    fn code(mut self, code: Option<&str>) -> Self {
+        self.code = code.map(str::to_string);
+        self
+    }
+
+    /// "file:line:col", "file:line", "file", or "" when the issue has no location.
+    pub fn location(&self) -> String {
+        match (&self.file, self.line, self.column) {
+            (Some(f), Some(l), Some(c)) => format!("{}:{}:{}", f, l, c),
+            (Some(f), Some(l), None) => format!("{}:{}", f, l),
+            (Some(f), None, _) => f.clone(),
+            (None, _, _) => String::new(),
+        }
+    }
+
+    /// One-line description: "location: message [code]".
+    pub fn description(&self) -> String {
+        let mut text = match self.location() {
+            loc if loc.is_empty() => self.message.clone(),
+            loc => format!("{}: {}", loc, self.message),
+        };
+        if let Some(code) = &self.code {
+            text.push_str(&format!(" [{}]", code));
+        }
+        text
+    }
+}
+
+struct Packs {
+    ansi: Regex,
+    tsc: Regex,
+    tsc_pretty: Regex,
+    eslint_file: Regex,
+    eslint_row: Regex,
+    cargo_head: Regex,
+    cargo_loc: Regex,
+    cargo_test: Regex,
+    cargo_panic: Regex,
+    pytest_summary: Regex,
+    pytest_loc: Regex,
+    go_loc: Regex,
+    go_fail: Regex,
+    go_log: Regex,
+}
+
+fn packs() -> &'static Packs {
+    static PACKS: OnceLock<Packs> = OnceLock::new();
+    PACKS.get_or_init(|| {
+        let re = |pattern: &str| Regex::new(pattern).expect("valid issue rule pattern");
+        Packs {
+            ansi: re(r"\x1b\[[0-9;]*[A-Za-z]"),
+            tsc: re(r"^(?P<file>[^\s(][^(]*\.[cm]?[jt]sx?)\((?P<line>\d+),(?P<col>\d+)\): error (?P<code>TS\d+): (?P<msg>.+)$"),
+            tsc_pretty: re(r"^(?P<file>\S+\.[cm]?[jt]sx?):(?P<line>\d+):(?P<col>\d+) - error (?P<code>TS\d+): (?P<msg>.+)$"),
+            eslint_file: re(r"^(?:[A-Za-z]:)?[\w./\\@-]+\.(?:[cm]?[jt]sx?|vue|svelte)$"),
+            eslint_row: re(r"^\s+(?P<line>\d+):(?P<col>\d+)\s+(?P<sev>error|warning)\s+(?P<msg>.+?)\s{2,}(?P<rule>[\w@/-]+)$"),
+            cargo_head: re(r"^(?P<sev>error|warning)(?:\[(?P<code>\w+)\])?: (?P<msg>.+)$"),
+            cargo_loc: re(r"^\s*--> (?P<file>[^:]+):(?P<line>\d+):(?P<col>\d+)$"),
+            cargo_test: re(r"^test (?P<test>\S+) \.\.\. FAILED$"),
+            cargo_panic: re(
+                r"^thread '(?P<test>[^']+)' panicked at (?:'(?P<msg>.*)', )?(?P<file>[^:\s]+):(?P<line>\d+):(?P<col>\d+):?$",
+            ),
+            pytest_summary: re(r"^(?P<sev>FAILED|ERROR) (?P<file>[^:\s]+\.py)(?:::(?P<test>\S+))?(?: - (?P<msg>.+))?$"),
+            pytest_loc: re(r"^(?P<file>\S+\.py):(?P<line>\d+): (?P<msg>\w*(?:Error|Exception|Failed)\b.*)$"),
+            go_loc: re(r"^(?:vet: )?(?P<file>[^\s:]+\.go):(?P<line>\d+):(?P<col>\d+): (?P<msg>.+)$"),
+            go_fail: re(r"^\s*--- FAIL: (?P<test>\S+)"),
+            go_log: re(r"^\s+(?P<file>\S+_test\.go):(?P<line>\d+): (?P<msg>.+)$"),
+        }
+    })
+}
+
+/// Cargo lines that summarize other diagnostics rather than report one.
+fn is_cargo_summary(message: &str) -> bool {
+    message.starts_with("could not compile")
+        || message.starts_with("aborting due to")
+        || message.starts_with("test failed")
+        || message.contains(" generated ")
+        || message.starts_with("build failed")
+}
+
+/// Extract issues from tool output with every rule pack. Returns at most MAX_ISSUES,
+/// de-duplicated, in output order.
+pub fn extract(output: &str) -> Vec<RuleIssue> {
+    let p = packs();
+    let text = p.ansi.replace_all(output, "");
+    let mut issues: Vec<RuleIssue> = Vec::new();
+    let mut cargo_pending: Option<usize> = None;
+    let mut panic_pending: Option<usize> = None;
+    let mut go_test: Option<usize> = None;
+    let mut eslint_file: Option<String> = None;
+    // pytest traceback locations, matched to FAILED summary lines by file
+    let mut pytest_locs: Vec<RuleIssue> = Vec::new();
+
+    for raw in text.lines() {
+        let line = raw.trim_end();
+        let cargo_issue = cargo_pending.take();
+
+        if let Some(i) = panic_pending.take() {
+            if line.trim().is_empty() {
+                panic_pending = Some(i);
+            } else if !line.starts_with("note:") {
+                issues[i].message = format!("{}: {}", issues[i].message, line.trim());
+                continue;
+            }
+        }
+
+        if let Some(c) = p.cargo_loc.captures(line) {
+            if let Some(i) = cargo_issue {
+                let issue = issues[i].clone();
+                issues[i] = issue.at(&c["file"], Some(&c["line"]), Some(&c["col"]));
+            }
+            continue;
+        }
+
+        if let Some(c) = p.tsc.captures(line).or_else(|| p.tsc_pretty.captures(line)) {
+            issues.push(
+                RuleIssue::new("tsc", "type_error", &c["msg"])
+                    .at(&c["file"], Some(&c["line"]), Some(&c["col"]))
+                    .code(Some(&c["code"])),
+            );
+            continue;
+        }
+
+        if let Some(c) = p.go_loc.captures(line) {
+            issues.push(RuleIssue::new("go", "error", &c["msg"]).at(&c["file"], Some(&c["line"]), Some(&c["col"])));
+            continue;
+        }
+
+        if let Some(c) = p.go_fail.captures(line) {
+            issues.push(RuleIssue::new("go", "test_failure", &format!("Test {} failed", &c["test"])));
+            go_test = Some(issues.len() - 1);
+            continue;
+        }
+
+        if let Some(c) = p.go_log.captures(line) {
+            if let Some(i) = go_test.take() {
+                let issue = issues[i].clone();
+                let message = format!("{}: {}", issue.message, c["msg"].trim());
+                issues[i] = RuleIssue::new("go", "test_failure", &message).at(&c["file"], Some(&c["line"]), None);
+            }
+            continue;
+        }
+
+        if let Some(c) = p.pytest_summary.captures(line) {
+            let target = match c.name("test") {
+                Some(test) => format!("{}::{}", &c["file"], test.as_str()),
+                None => c["file"].to_string(),
+            };
+            let mut message = match &c["sev"] {
+                "FAILED" => format!("Test {} failed", target),
+                _ => format!("Error collecting {}", target),
+            };
+            if let Some(msg) = c.name("msg") {
+                message = format!("{}: {}", message, msg.as_str());
+            }
+            let mut issue = RuleIssue::new("pytest", "test_failure", &message).at(&c["file"], None, None);
+            if let Some(pos) = pytest_locs.iter().position(|l| l.file == issue.file) {
+                issue.line = pytest_locs.remove(pos).line;
+            }
+            issues.push(issue);
+            continue;
+        }
+
+        if let Some(c) = p.pytest_loc.captures(line) {
+            pytest_locs.push(RuleIssue::new("pytest", "test_failure", &c["msg"]).at(&c["file"], Some(&c["line"]), None));
+            continue;
+        }
+
+        if let Some(c) = p.cargo_test.captures(line) {
+            issues.push(RuleIssue::new("cargo", "test_failure", &format!("Test {} failed", &c["test"])));
+            continue;
+        }
+
+        if let Some(c) = p.cargo_panic.captures(line) {
+            let failed = format!("Test {} failed", &c["test"]);
+            let idx = match issues.iter().position(|i| i.tool == "cargo" && i.message == failed) {
+                Some(idx) => idx,
+                None => {
+                    issues.push(RuleIssue::new("cargo", "test_failure", &failed));
+                    issues.len() - 1
+                }
+            };
+            let issue = issues[idx].clone();
+            issues[idx] = issue.at(&c["file"], Some(&c["line"]), Some(&c["col"]));
+            match c.name("msg") {
+                Some(msg) => issues[idx].message = format!("{}: {}", failed, msg.as_str()),
+                None => panic_pending = Some(idx),
+            }
+            continue;
+        }
+
+        if let Some(c) = p.cargo_head.captures(line) {
+            if !is_cargo_summary(&c["msg"]) {
+                let kind = if &c["sev"] == "error" { "error" } else { "warning" };
+                issues.push(RuleIssue::new("cargo", kind, &c["msg"]).code(c.name("code").map(|m| m.as_str())));
+                cargo_pending = Some(issues.len() - 1);
+            }
+            continue;
+        }
+
+        if p.eslint_file.is_match(line) {
+            eslint_file = Some(line.to_string());
+            continue;
+        }
+        if let (Some(file), Some(c)) = (&eslint_file, p.eslint_row.captures(line)) {
+            let kind = if &c["sev"] == "error" { "error" } else { "warning" };
+            issues.push(
+                RuleIssue::new("eslint", kind, &c["msg"])
+                    .at(file, Some(&c["line"]), Some(&c["col"]))
+                    .code(Some(&c["rule"])),
+            );
+            continue;
+        }
+        if line.trim().is_empty() {
+            eslint_file = None;
+        }
+    }
+
+    // Tracebacks without a matching summary line (pytest -rN, or failures in non-test files)
+    issues.extend(pytest_locs);
+
+    let mut unique: Vec<RuleIssue> = Vec::new();
+    for issue in issues {
+        let duplicate = unique
+            .iter()
+            .any(|u| u.file == issue.file && u.line == issue.line && u.message == issue.message);
+        if !duplicate {
+            unique.push(issue);
+        }
+    }
+    unique.truncate(MAX_ISSUES);
+    unique
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn descriptions(output: &str) -> Vec<String> {
+        extract(output).iter().map(|i| format!("{} {}", i.kind, i.description())).collect()
+    }
+
+    #[test]
+    fn test_tsc_both_styles() {
+        let output = "src/app.ts(12,5): error TS2322: Type 'string' is not assignable to type 'number'.\n\
+                      src/api/client.tsx:3:10 - error TS2307: Cannot find module './types'.\n\n\
+                      Found 2 errors.";
+        assert_eq!(
+            descriptions(output),
+            vec![
+                "type_error src/app.ts:12:5: Type 'string' is not assignable to type 'number'. [TS2322]",
+                "type_error src/api/client.tsx:3:10: Cannot find module './types'. [TS2307]",
+            ]
+        );
+    }
+
+    #[test]
+    fn test_eslint_stylish() {
+        let output = "\n/home/dev/app/src/index.js\n  \
+                      3:7   error    'unused' is assigned a value but never used  no-unused-vars\n  \
+                      10:1  warning  Unexpected console statement                  no-console\n\n\
+                      ✖ 2 problems (1 error, 1 warning)\n";
+        let issues = extract(output);
+        assert_eq!(issues.len(), 2);
+        assert_eq!(issues[0].tool, "eslint");
+        assert_eq!(
+            issues[0].description(),
+            "/home/dev/app/src/index.js:3:7: 'unused' is assigned a value but never used [no-unused-vars]"
+        );
+        assert_eq!((issues[1].kind, issues[1].line), ("warning", Some(10)));
+    }
+
+    #[test]
+    fn test_cargo_diagnostics_and_tests() {
+        let output = "\x1b[1m\x1b[31merror[E0425]\x1b[0m: cannot find value `foo` in this scope\n  \
+                      --> src/main.rs:10:5\n   |\n\
+                      warning: unused variable: `x`\n  --> src/lib.rs:5:9\n\
+                      error: could not compile `app` (bin \"app\") due to 1 previous error\n\
+                      running 2 tests\ntest tests::adds ... FAILED\ntest tests::subs ... ok\n\n\
+                      ---- tests::adds stdout ----\n\
+                      thread 'tests::adds' panicked at src/math.rs:20:9:\n\
+                      assertion `left == right` failed\n";
+        assert_eq!(
+            descriptions(output),
+            vec![
+                "error src/main.rs:10:5: cannot find value `foo` in this scope [E0425]",
+                "warning src/lib.rs:5:9: unused variable: `x`",
+                "test_failure src/math.rs:20:9: Test tests::adds failed: assertion `left == right` failed",
+            ]
+        );
+    }
+
+    #[test]
+    fn test_pytest() {
+        let output = "tests/test_api.py:42: AssertionError\n\
+                      ===== short test summary info =====\n\
+                      FAILED tests/test_api.py::test_login - assert 401 == 200\n\
+                      ERROR tests/test_db.py - ModuleNotFoundError: No module named 'psycopg'\n";
+        let issues = extract(output);
+        assert_eq!(
+            issues.iter().map(|i| i.description()).collect::<Vec<_>>(),
+            vec![
+                "tests/test_api.py:42: Test tests/test_api.py::test_login failed: assert 401 == 200",
+                "tests/test_db.py: Error collecting tests/test_db.py: ModuleNotFoundError: No module named 'psycopg'",
+            ]
+        );
+        assert!(issues.iter().all(|i| i.kind == "test_failure"));
+    }
+
+    #[test]
+    fn test_go_vet_and_go_test() {
+        let output = "# example.com/app\n\
+                      vet: ./main.go:14:2: fmt.Printf format %d has arg name of wrong type string\n\
+                      --- FAIL: TestParse (0.00s)\n    parse_test.go:21: got 3, want 4\nFAIL\n";
+        assert_eq!(
+            descriptions(output),
+            vec![
+                "error main.go:14:2: fmt.Printf format %d has arg name of wrong type string",
+                "test_failure parse_test.go:21: Test TestParse failed: got 3, want 4",
+            ]
+        );
+    }
+
+    #[test]
+    fn test_clean_output_and_cap() {
+        assert!(extract("Compiling app\nFinished dev target(s) in 2.5s\nAll tests passed!").is_empty());
+        let many: String = (1..=30).map(|n| format!("src/a.ts({},1): error TS2304: Cannot find name 'x{}'.\n", n, n)).collect();
+        assert_eq!(extract(&many).len(), MAX_ISSUES);
+        assert_eq!(extract("error: boom\nerror: boom\n").len(), 1);
+    }
+}
@@ -0,0 +1,184 @@
+//! @module core/learnings
+//! @description Staleness checks for learnings promoted into CLAUDE.md and other shared files
+//!
+//! PURPOSE:
+//! - Find the project files a learning refers to
+//! - Measure how much a file changed in git since a date (lines and commits)
+//! - Remove a promoted learning's bullet from a file when it is demoted
+//!
+//! DEPENDENCIES:
+//! - core::proc - git log with ProcLimits::GIT
+//! - models::memory - ReferencedFileChange
+//!
+//! EXPORTS:
+//! - DEFAULT_STALE_MONTHS - Age after which a promoted learning is due for review
+//! - DEFAULT_MIN_CHANGED_LINES - Churn in a referenced file that counts as significant
+//! - referenced_paths - File paths mentioned in a learning that exist (or existed) in the project
+//! - parse_log_numstat - Lines changed and commits from `git log --numstat --format=%H`
+//! - file_change_since - Churn of one file since a date, with a deleted flag
+//! - in_git_history - Whether git has history for a path
+//! - remove_learning_line - Drop the "- <content>" bullet(s) of a learning from file content
+//!
+//! PATTERNS:
+//! - A learning is stale when it was promoted (or last verified) more than N months ago and a
+//!   referenced file has since changed by at least the line threshold or been deleted
+//! - Learnings that mention no files are never reported; there is nothing to compare against
+//!
+//! CLAUDE NOTES:
+//! - promote_learning writes learnings as "- <content>" bullets; remove_learning_line matches
+//!   that form (and "* ") after trimming, so hand-edited bullets are left alone
+//! - Paths are resolved relative to the project root, which is assumed to be the git root
+
+use std::path::Path;
+use std::process::Command;
+
+use crate::core::proc::{self, ProcLimits};
+use crate::models::memory::ReferencedFileChange;
+
+/// Months after promotion (or last verification) before a learning is reviewed.
+pub const DEFAULT_STALE_MONTHS: u32 = 6;
+
+/// Added + deleted lines in a referenced file that count as a significant change.
+pub const DEFAULT_MIN_CHANGED_LINES: u32 = 50;
+
+/// Characters stripped from the ends of a word before it is treated as a path.
+const PATH_TRIM: &[char] = &['`', '\'', '"', '(', ')', '[', ']', ',', ';', ':', '.', '!', '?'];
+
+fn git(project_dir: &Path, args: &[&str]) -> Option<String> {
+    let output = proc::run(Command::new("git").args(args).current_dir(project_dir), ProcLimits::GIT).ok()?;
+    if !output.success() {
+        return None;
+    }
+    Some(String::from_utf8_lossy(&output.stdout).to_string())
+}
+
+/// Whether a word looks like a file name: a short, non-numeric alphanumeric extension.
+fn looks_like_path(word: &str) -> bool {
+    let Some((stem, ext)) = word.rsplit_once('.') else {
+        return false;
+    };
+    !stem.is_empty()
+        && !word.contains("://")
+        && (1..=6).contains(&ext.len())
+        && ext.chars().all(|c| c.is_ascii_alphanumeric())
+        && !ext.chars().all(|c| c.is_ascii_digit())
+}
+
+/// Project-relative paths mentioned in a learning. A path counts when the file exists,
+/// or when `in_history` says git knows it (deleted files are the stalest references).
+pub fn referenced_paths<F>(content: &str, project_dir: &Path, in_history: F) -> Vec<String>
+where
+    F: Fn(&str) -> bool,
+{
+    let mut paths: Vec<String> = Vec::new();
+    for word in content.split_whitespace() {
+        let word = word.trim_matches(PATH_TRIM).trim_start_matches("./");
+        if !looks_like_path(word) || paths.iter().any(|p| p == word) {
+            continue;
+        }
+        if project_dir.join(word).is_file() || (word.contains('/') && in_history(word)) {
+            paths.push(word.to_string());
+        }
+    }
+    paths
+}
+
+/// (lines changed, commits) from `git log --numstat --format=%H` output.
+/// Binary files ("-" counts) add commits but no lines.
+pub fn parse_log_numstat(output: &str) -> (u32, u32) {
+    let mut lines = 0u32;
+    let mut commits = 0u32;
+    for line in output.lines().map(str::trim).filter(|l| !l.is_empty()) {
+        let mut parts = line.split('\t');
+        match (parts.next(), parts.next(), parts.next()) {
+            (Some(added), Some(deleted), Some(_)) => {
+                lines += added.parse::<u32>().unwrap_or(0) + deleted.parse::<u32>().unwrap_or(0);
+            }
+            _ if line.len() == 40 && line.chars().all(|c| c.is_ascii_hexdigit()) => commits += 1,
+            _ => {}
+        }
+    }
+    (lines, commits)
+}
+
+/// How much `path` changed since `since` (RFC 3339).
+/// None when git cannot read the history.
+pub fn file_change_since(project_dir: &Path, path: &str, since: &str) -> Option<ReferencedFileChange> {
+    let since_arg = format!("--since={}", since);
+    let out = git(project_dir, &["log", &since_arg, "--numstat", "--format=%H", "--", path])?;
+    let (lines_changed, commits) = parse_log_numstat(&out);
+    Some(ReferencedFileChange {
+        path: path.to_string(),
+        lines_changed,
+        commits,
+        deleted: !project_dir.join(path).exists(),
+    })
+}
+
+/// Whether git has any history for a path (used for files that no longer exist).
+pub fn in_git_history(project_dir: &Path, path: &str) -> bool {
+    git(project_dir, &["log", "-1", "--format=%H", "--", path]).is_some_and(|out| !out.trim().is_empty())
+}
+
+/// Remove the bullet line(s) holding a learning. Returns the new content and lines removed.
+pub fn remove_learning_line(file_content: &str, learning: &str) -> (String, u32) {
+    let learning = learning.trim();
+    let mut removed = 0;
+    let kept: Vec<&str> = file_content
+        .lines()
+        .filter(|line| {
+            let trimmed = line.trim();
+            let text = trimmed.strip_prefix("- ").or_else(|| trimmed.strip_prefix("* "));
+            let matches = text.is_some_and(|t| t.trim() == learning);
+            if matches {
+                removed += 1;
+            }
+            !matches
+        })
+        .collect();
+
+    let mut out = kept.join("\n");
+    if file_content.ends_with('\n') && !out.is_empty() {
+        out.push('\n');
+    }
+    (out, removed)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::fs;
+
+    #[test]
+    fn test_referenced_paths() {
+        let dir = tempfile::tempdir().unwrap();
+        fs::create_dir_all(dir.path().join("src/db")).unwrap();
+        fs::write(dir.path().join("src/db/pool.rs"), "").unwrap();
+        fs::write(dir.path().join("Cargo.toml"), "").unwrap();
+
+        let content = "Open connections via `src/db/pool.rs` (see Cargo.toml). Old: src/legacy/db.rs, \
+            version 1.2, https://example.com/x.html, e.g. config.yaml";
+        let paths = referenced_paths(content, dir.path(), |p| p == "src/legacy/db.rs");
+        assert_eq!(paths, vec!["src/db/pool.rs", "Cargo.toml", "src/legacy/db.rs"]);
+    }
+
+    #[test]
+    fn test_parse_log_numstat() {
+        let out = "0123456789abcdef0123456789abcdef01234567\n\n10\t5\tsrc/a.rs\n\
+            89abcdef0123456789abcdef0123456789abcdef\n\n-\t-\tlogo.png\n3\t0\tsrc/a.rs\n";
+        assert_eq!(parse_log_numstat(out), (18, 2));
+        assert_eq!(parse_log_numstat(""), (0, 0));
+    }
+
+    #[test]
+    fn test_remove_learning_line() {
+        let content = "# Notes\n\n- Use the pool in src/db/pool.rs\n- Keep PRs small\n*   Use the pool in src/db/pool.rs\n";
+        let (out, removed) = remove_learning_line(content, "Use the pool in src/db/pool.rs");
+        assert_eq!(removed, 2);
+        assert_eq!(out, "# Notes\n\n- Keep PRs small\n");
+
+        let (unchanged, none) = remove_learning_line(content, "Not there");
+        assert_eq!(none, 0);
+        assert_eq!(unchanged, content);
+    }
+}
@@ -0,0 +1,352 @@
+//! @module core/logging
+//! @description Structured application logging to rotating files and the log viewer query
+//!
+//! PURPOSE:
+//! - Install the tracing subscriber: JSON lines to daily-rotated files plus readable stderr output
+//! - Parse the log files back into entries filtered by level, time range, and module
+//!
+//! DEPENDENCIES:
+//! - tracing-subscriber - Registry, EnvFilter, and fmt layers
+//! - tracing-appender - Daily rolling file appender with a non-blocking writer
+//! - chrono - Time range parsing and comparison
+//! - models::logs - LogEntry
+//!
+//! EXPORTS:
+//! - LOG_ENV - Environment variable overriding the log filter (EnvFilter syntax)
+//! - logs_dir - ~/.project-jumpstart/logs
+//! - init - Install the global subscriber (once, at startup)
+//! - LogFilter - Minimum level, time range, and module filter for read_logs
+//! - parse_range - "SINCE..UNTIL" (dates or RFC 3339 timestamps) into a time range
+//! - parse_line - One JSON log line into a LogEntry
+//! - read_logs - Matching entries from the log files, newest first
+//!
+//! PATTERNS:
+//! - Code logs with tracing macros and structured fields:
+//!   tracing::warn!(error = %e, "Failed to ...")
+//! - Files are named jumpstart.YYYY-MM-DD.log; the oldest are deleted past MAX_LOG_FILES
+//!
+//! CLAUDE NOTES:
+//! - The non-blocking writer's guard is kept in a static so buffered lines are flushed for the
+//!   life of the process
+//! - If the log directory cannot be created, logging falls back to stderr only
+//! - Module names drop the crate prefix ("project_jumpstart_lib::commands::ralph" -> "commands::ralph")
+
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::sync::OnceLock;
+
+use chrono::{DateTime, NaiveDate, Utc};
+use tracing_appender::non_blocking::WorkerGuard;
+use tracing_appender::rolling::{RollingFileAppender, Rotation};
+use tracing_subscriber::layer::SubscriberExt;
+use tracing_subscriber::util::SubscriberInitExt;
+use tracing_subscriber::{fmt, EnvFilter};
+
+use crate::models::logs::LogEntry;
+
+/// Environment variable overriding the default filter, e.g. "debug" or "warn,project_jumpstart_lib::core=debug".
+pub const LOG_ENV: &str = "JUMPSTART_LOG";
+
+/// Dependencies log warnings and up; the app logs info and up.
+const DEFAULT_FILTER: &str = "warn,project_jumpstart_lib=info";
+
+const LOG_FILE_PREFIX: &str = "jumpstart";
+const LOG_FILE_SUFFIX: &str = "log";
+
+/// Daily files kept before the oldest is deleted.
+const MAX_LOG_FILES: usize = 14;
+
+const CRATE_PREFIX: &str = "project_jumpstart_lib::";
+
+static FILE_GUARD: OnceLock<WorkerGuard> = OnceLock::new();
+
+/// Directory holding the rotated log files.
+pub fn logs_dir() -> Option<PathBuf> {
+    dirs::home_dir().map(|home| home.join(".project-jumpstart").join("logs"))
+}
+
+/// Install the global tracing subscriber. Safe to call more than once; later calls do nothing.
+pub fn init() {
+    let filter = EnvFilter::try_from_env(LOG_ENV).unwrap_or_else(|_| EnvFilter::new(DEFAULT_FILTER));
+    let stderr_layer = fmt::layer().with_writer(std::io::stderr);
+
+    let file_layer = logs_dir().and_then(|dir| {
+        fs::create_dir_all(&dir).ok()?;
+        let appender = RollingFileAppender::builder()
+            .rotation(Rotation::DAILY)
+            .filename_prefix(LOG_FILE_PREFIX)
+            .filename_suffix(LOG_FILE_SUFFIX)
+            .max_log_files(MAX_LOG_FILES)
+            .build(&dir)
+            .ok()?;
+        let (writer, guard) = tracing_appender::non_blocking(appender);
+        let _ = FILE_GUARD.set(guard);
+        Some(
+            fmt::layer()
+                .json()
+                .with_current_span(false)
+                .with_span_list(false)
+                .with_ansi(false)
+                .with_writer(writer),
+        )
+    });
+
+    let _ = tracing_subscriber::registry()
+        .with(filter)
+        .with(stderr_layer)
+        .with(file_layer)
+        .try_init();
+}
+
+/// Severity rank of a level name (TRACE lowest, ERROR highest).
+fn level_rank(level: &str) -> Option<u8> {
+    match level.to_ascii_uppercase().as_str() {
+        "TRACE" => Some(0),
+        "DEBUG" => Some(1),
+        "INFO" => Some(2),
+        "WARN" | "WARNING" => Some(3),
+        "ERROR" => Some(4),
+        _ => None,
+    }
+}
+
+/// Which entries read_logs returns.
+#[derive(Debug, Clone, Default)]
+pub struct LogFilter {
+    /// Minimum level name ("warn" keeps WARN and ERROR)
+    pub level: Option<String>,
+    pub since: Option<DateTime<Utc>>,
+    pub until: Option<DateTime<Utc>>,
+    /// Module path prefix, with or without the crate name
+    pub module: Option<String>,
+}
+
+impl LogFilter {
+    fn matches(&self, entry: &LogEntry, min_rank: Option<u8>) -> bool {
+        if let Some(min) = min_rank {
+            if level_rank(&entry.level).is_none_or(|rank| rank < min) {
+                return false;
+            }
+        }
+        if self.since.is_some() || self.until.is_some() {
+            let Ok(at) = DateTime::parse_from_rfc3339(&entry.timestamp) else {
+                return false;
+            };
+            let at = at.with_timezone(&Utc);
+            if self.since.is_some_and(|s| at < s) || self.until.is_some_and(|u| at > u) {
+                return false;
+            }
+        }
+        match self.module.as_deref().map(|m| m.trim().trim_start_matches(CRATE_PREFIX)) {
+            Some(module) if !module.is_empty() => {
+                entry.module == module || entry.module.starts_with(&format!("{}::", module))
+            }
+            _ => true,
+        }
+    }
+}
+
+/// Parse one side of a range: an RFC 3339 timestamp or a YYYY-MM-DD date
+/// (start of day for `since`, end of day for `until`).
+fn parse_range_bound(value: &str, end_of_day: bool) -> Result<Option<DateTime<Utc>>, String> {
+    if value.is_empty() {
+        return Ok(None);
+    }
+    if let Ok(at) = DateTime::parse_from_rfc3339(value) {
+        return Ok(Some(at.with_timezone(&Utc)));
+    }
+    let date = NaiveDate::parse_from_str(value, "%Y-%m-%d")
+        .map_err(|_| format!("Invalid time '{}' in range (expected YYYY-MM-DD or RFC 3339)", value))?;
+    let time = if end_of_day {
+        date.and_hms_milli_opt(23, 59, 59, 999)
+    } else {
+        date.and_hms_opt(0, 0, 0)
+    };
+    Ok(time.map(|t| t.and_utc()))
+}
+
+/// Parse a "SINCE..UNTIL" range. Either side may be empty; a bare value means "since".
+pub fn parse_range(range: Option<&str>) -> Result<(Option<DateTime<Utc>>, Option<DateTime<Utc>>), String> {
+    let range = match range.map(str::trim) {
+        Some(r) if !r.is_empty() => r,
+        _ => return Ok((None, None)),
+    };
+    let (since, until) = match range.split_once("..") {
+        Some((s, u)) => (s.trim(), u.trim()),
+        None => (range, ""),
+    };
+    Ok((parse_range_bound(since, false)?, parse_range_bound(until, true)?))
+}
+
+/// Parse one JSON log line written by the file layer. Other lines return None.
+pub fn parse_line(line: &str) -> Option<LogEntry> {
+    let value: serde_json::Value = serde_json::from_str(line.trim()).ok()?;
+    let timestamp = value.get("timestamp")?.as_str()?.to_string();
+    let level = value.get("level")?.as_str()?.to_ascii_uppercase();
+    let target = value.get("target").and_then(|t| t.as_str()).unwrap_or("");
+
+    let mut fields = value.get("fields").cloned().unwrap_or_else(|| serde_json::json!({}));
+    let message = fields
+        .as_object_mut()
+        .and_then(|f| f.remove("message"))
+        .map(|m| match m {
+            serde_json::Value::String(s) => s,
+            other => other.to_string(),
+        })
+        .unwrap_or_default();
+
+    Some(LogEntry {
+        timestamp,
+        level,
+        module: target.trim_start_matches(CRATE_PREFIX).to_string(),
+        message,
+        fields,
+    })
+}
+
+/// The date in a rotated file name ("jumpstart.2025-01-01.log"), if it is one of ours.
+fn log_file_date(path: &Path) -> Option<NaiveDate> {
+    let name = path.file_name()?.to_str()?;
+    let date = name
+        .strip_prefix(&format!("{}.", LOG_FILE_PREFIX))?
+        .strip_suffix(&format!(".{}", LOG_FILE_SUFFIX))?;
+    NaiveDate::parse_from_str(date, "%Y-%m-%d").ok()
+}
+
+/// Entries matching `filter` from the log files in `dir`, newest first, at most `limit`.
+/// The flag is true when more entries matched than were returned.
+pub fn read_logs(dir: &Path, filter: &LogFilter, limit: usize) -> Result<(Vec<LogEntry>, bool), String> {
+    let min_rank = match filter.level.as_deref().map(str::trim) {
+        Some(level) if !level.is_empty() => {
+            Some(level_rank(level).ok_or_else(|| format!("Unknown log level: {}", level))?)
+        }
+        _ => None,
+    };
+
+    let mut files: Vec<(NaiveDate, PathBuf)> = match fs::read_dir(dir) {
+        Ok(entries) => entries
+            .filter_map(|e| e.ok())
+            .map(|e| e.path())
+            .filter_map(|p| log_file_date(&p).map(|d| (d, p)))
+            .collect(),
+        Err(_) => return Ok((Vec::new(), false)),
+    };
+    files.sort_by(|a, b| b.0.cmp(&a.0));
+
+    let mut entries = Vec::new();
+    for (date, path) in files {
+        if filter.since.is_some_and(|s| date < s.date_naive()) {
+            break;
+        }
+        if filter.until.is_some_and(|u| date > u.date_naive()) {
+            continue;
+        }
+        let Ok(content) = fs::read_to_string(&path) else {
+            continue;
+        };
+        for entry in content.lines().rev().filter_map(parse_line) {
+            if !filter.matches(&entry, min_rank) {
+                continue;
+            }
+            if entries.len() == limit {
+                return Ok((entries, true));
+            }
+            entries.push(entry);
+        }
+    }
+    Ok((entries, false))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn line(timestamp: &str, level: &str, target: &str, message: &str) -> String {
+        serde_json::json!({
+            "timestamp": timestamp,
+            "level": level,
+            "fields": { "message": message, "error": "boom" },
+            "target": target,
+        })
+        .to_string()
+    }
+
+    #[test]
+    fn test_parse_line() {
+        let entry = parse_line(&line(
+            "2025-01-02T03:04:05.000001Z",
+            "WARN",
+            "project_jumpstart_lib::commands::ralph",
+            "Failed to open database connection",
+        ))
+        .unwrap();
+        assert_eq!(entry.level, "WARN");
+        assert_eq!(entry.module, "commands::ralph");
+        assert_eq!(entry.message, "Failed to open database connection");
+        assert_eq!(entry.fields, serde_json::json!({ "error": "boom" }));
+
+        assert!(parse_line("not json").is_none());
+        assert!(parse_line("{\"level\":\"INFO\"}").is_none());
+    }
+
+    #[test]
+    fn test_parse_range() {
+        let (since, until) = parse_range(Some("2025-01-01..2025-01-02")).unwrap();
+        assert_eq!(since.unwrap().to_rfc3339(), "2025-01-01T00:00:00+00:00");
+        assert!(until.unwrap().to_rfc3339().starts_with("2025-01-02T23:59:59.999"));
+
+        let (since, until) = parse_range(Some("2025-01-01T12:00:00Z")).unwrap();
+        assert_eq!(since.unwrap().to_rfc3339(), "2025-01-01T12:00:00+00:00");
+        assert!(until.is_none());
+
+        assert_eq!(parse_range(None).unwrap(), (None, None));
+        assert!(parse_range(Some("yesterday..")).is_err());
+    }
+
+    #[test]
+    fn test_read_logs_filters_newest_first() {
+        let dir = tempfile::tempdir().unwrap();
+        let day1 = [
+            line("2025-01-01T10:00:00Z", "INFO", "project_jumpstart_lib::core::events", "first"),
+            line("2025-01-01T11:00:00Z", "ERROR", "project_jumpstart_lib::commands::ralph", "second"),
+        ]
+        .join("\n");
+        let day2 = [
+            line("2025-01-02T09:00:00Z", "WARN", "project_jumpstart_lib::commands::ralph", "third"),
+            "garbage".to_string(),
+            line("2025-01-02T10:00:00Z", "DEBUG", "project_jumpstart_lib::commands::ralph", "fourth"),
+        ]
+        .join("\n");
+        fs::write(dir.path().join("jumpstart.2025-01-01.log"), day1).unwrap();
+        fs::write(dir.path().join("jumpstart.2025-01-02.log"), day2).unwrap();
+        fs::write(dir.path().join("other.txt"), "ignored").unwrap();
+
+        let messages = |filter: &LogFilter, limit: usize| {
+            let (entries, truncated) = read_logs(dir.path(), filter, limit).unwrap();
+            (entries.into_iter().map(|e| e.message).collect::<Vec<_>>(), truncated)
+        };
+
+        assert_eq!(
+            messages(&LogFilter::default(), 10),
+            (vec!["fourth".into(), "third".into(), "second".into(), "first".into()], false)
+        );
+
+        let warnings = LogFilter {
+            level: Some("warn".into()),
+            ..Default::default()
+        };
+        assert_eq!(messages(&warnings, 10), (vec!["third".into(), "second".into()], false));
+        assert_eq!(messages(&warnings, 1), (vec!["third".into()], true));
+
+        let (since, until) = parse_range(Some("2025-01-01")).unwrap();
+        let ralph_day1 = LogFilter {
+            module: Some("commands::ralph".into()),
+            since,
+            until: until.or_else(|| parse_range(Some("..2025-01-01")).unwrap().1),
+            ..Default::default()
+        };
+        assert_eq!(messages(&ralph_day1, 10), (vec!["second".into()], false));
+
+        assert!(read_logs(dir.path(), &LogFilter { level: Some("loud".into()), ..Default::default() }, 10).is_err());
+    }
+}
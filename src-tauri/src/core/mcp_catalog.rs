@@ -0,0 +1,453 @@
+//! @module core/mcp_catalog
+//! @description Curated MCP server catalog and safe edits of a project's .mcp.json
+//!
+//! PURPOSE:
+//! - Provide a curated list of MCP servers with packages, required env, and token cost
+//! - Build a server entry from a catalog item and user-supplied env values
+//! - Merge or remove server entries in .mcp.json without touching anything else
+//!
+//! DEPENDENCIES:
+//! - serde_json - Parse and rewrite .mcp.json
+//! - models::context - McpCatalogEntry, McpEnvVar types
+//! - std::fs - Atomic writes (temp file + rename)
+//!
+//! EXPORTS:
+//! - MCP_CONFIG_FILE - Project-relative path of the MCP config (.mcp.json)
+//! - catalog - All catalog entries
+//! - find_entry - Look up a catalog entry by id
+//! - build_server_entry - JSON entry for .mcp.json from a catalog item + env
+//! - JUMPSTART_SERVER - .mcp.json name of the app's own jumpstart-mcp server
+//! - jumpstart_server_entry - .mcp.json entry launching jumpstart-mcp from the app's directory
+//! - configured_servers - Names of servers configured in a project
+//! - add_server - Insert or replace a server in .mcp.json
+//! - remove_server - Remove a server from .mcp.json
+//!
+//! PATTERNS:
+//! - An unparseable .mcp.json is never overwritten; the edit fails instead
+//! - Other servers and top-level keys are preserved
+//! - Writes go to a temp file that is renamed over .mcp.json
+//! - Env values like "${GITHUB_TOKEN}" are written as-is (Claude Code expands them)
+//! - Secret env vars only accept a "${VAR}" reference; a literal value is rejected so tokens
+//!   never land in .mcp.json (which is usually committed). An omitted secret becomes "${NAME}"
+//!
+//! CLAUDE NOTES:
+//! - Claude Code reads project-scoped servers from .mcp.json at the project root; that is
+//!   the same file get_mcp_status reads first
+//! - Token cost estimates are rough tool-schema sizes used for context budgeting
+//! - serde_json sorts object keys on rewrite; values are unchanged
+//! - The jumpstart-mcp entry holds an absolute path to this install's binary, so it is
+//!   machine-specific; it takes no --project because Claude Code starts it in the project
+
+use std::collections::HashMap;
+use std::fs;
+use std::path::Path;
+
+use serde_json::{json, Map, Value};
+
+use crate::models::context::{McpCatalogEntry, McpEnvVar};
+
+/// Project-relative path of the Claude Code MCP config.
+pub const MCP_CONFIG_FILE: &str = ".mcp.json";
+
+struct CatalogItem {
+    id: &'static str,
+    name: &'static str,
+    description: &'static str,
+    category: &'static str,
+    package: &'static str,
+    command: &'static str,
+    args: &'static [&'static str],
+    /// (name, description, required, secret)
+    env: &'static [(&'static str, &'static str, bool, bool)],
+    token_cost: u32,
+}
+
+const CATALOG: &[CatalogItem] = &[
+    CatalogItem {
+        id: "filesystem",
+        name: "Filesystem",
+        description: "Read and write files in allowed directories",
+        category: "files",
+        package: "@modelcontextprotocol/server-filesystem",
+        command: "npx",
+        args: &["-y", "@modelcontextprotocol/server-filesystem", "."],
+        env: &[],
+        token_cost: 1_800,
+    },
+    CatalogItem {
+        id: "github",
+        name: "GitHub",
+        description: "Issues, pull requests, and repository contents via the GitHub API",
+        category: "source-control",
+        package: "@modelcontextprotocol/server-github",
+        command: "npx",
+        args: &["-y", "@modelcontextprotocol/server-github"],
+        env: &[("GITHUB_PERSONAL_ACCESS_TOKEN", "GitHub token with repo scope", true, true)],
+        token_cost: 4_500,
+    },
+    CatalogItem {
+        id: "postgres",
+        name: "PostgreSQL",
+        description: "Read-only SQL queries and schema inspection",
+        category: "database",
+        package: "@modelcontextprotocol/server-postgres",
+        command: "npx",
+        args: &["-y", "@modelcontextprotocol/server-postgres", "${DATABASE_URL}"],
+        env: &[("DATABASE_URL", "postgres:// connection string", true, true)],
+        token_cost: 600,
+    },
+    CatalogItem {
+        id: "sqlite",
+        name: "SQLite",
+        description: "Query and inspect a local SQLite database",
+        category: "database",
+        package: "mcp-server-sqlite",
+        command: "uvx",
+        args: &["mcp-server-sqlite", "--db-path", "${SQLITE_DB_PATH}"],
+        env: &[("SQLITE_DB_PATH", "Path to the SQLite database file", true, false)],
+        token_cost: 1_200,
+    },
+    CatalogItem {
+        id: "fetch",
+        name: "Fetch",
+        description: "Fetch web pages and convert them to markdown",
+        category: "web",
+        package: "mcp-server-fetch",
+        command: "uvx",
+        args: &["mcp-server-fetch"],
+        env: &[],
+        token_cost: 400,
+    },
+    CatalogItem {
+        id: "brave-search",
+        name: "Brave Search",
+        description: "Web and local search via the Brave Search API",
+        category: "web",
+        package: "@modelcontextprotocol/server-brave-search",
+        command: "npx",
+        args: &["-y", "@modelcontextprotocol/server-brave-search"],
+        env: &[("BRAVE_API_KEY", "Brave Search API key", true, true)],
+        token_cost: 700,
+    },
+    CatalogItem {
+        id: "puppeteer",
+        name: "Puppeteer",
+        description: "Browser automation: navigate, click, screenshot",
+        category: "browser",
+        package: "@modelcontextprotocol/server-puppeteer",
+        command: "npx",
+        args: &["-y", "@modelcontextprotocol/server-puppeteer"],
+        env: &[],
+        token_cost: 1_500,
+    },
+    CatalogItem {
+        id: "memory",
+        name: "Memory",
+        description: "Persistent knowledge graph across sessions",
+        category: "knowledge",
+        package: "@modelcontextprotocol/server-memory",
+        command: "npx",
+        args: &["-y", "@modelcontextprotocol/server-memory"],
+        env: &[("MEMORY_FILE_PATH", "Where the knowledge graph is stored", false, false)],
+        token_cost: 2_000,
+    },
+    CatalogItem {
+        id: "sequential-thinking",
+        name: "Sequential Thinking",
+        description: "Structured step-by-step reasoning tool",
+        category: "reasoning",
+        package: "@modelcontextprotocol/server-sequential-thinking",
+        command: "npx",
+        args: &["-y", "@modelcontextprotocol/server-sequential-thinking"],
+        env: &[],
+        token_cost: 900,
+    },
+    CatalogItem {
+        id: "slack",
+        name: "Slack",
+        description: "Read channels and post messages in a Slack workspace",
+        category: "communication",
+        package: "@modelcontextprotocol/server-slack",
+        command: "npx",
+        args: &["-y", "@modelcontextprotocol/server-slack"],
+        env: &[
+            ("SLACK_BOT_TOKEN", "Bot token (xoxb-...)", true, true),
+            ("SLACK_TEAM_ID", "Workspace ID (T...)", true, false),
+        ],
+        token_cost: 1_600,
+    },
+];
+
+fn to_entry(item: &CatalogItem) -> McpCatalogEntry {
+    McpCatalogEntry {
+        id: item.id.to_string(),
+        name: item.name.to_string(),
+        description: item.description.to_string(),
+        category: item.category.to_string(),
+        package: item.package.to_string(),
+        command: item.command.to_string(),
+        args: item.args.iter().map(|a| a.to_string()).collect(),
+        env: item
+            .env
+            .iter()
+            .map(|(name, description, required, secret)| McpEnvVar {
+                name: name.to_string(),
+                description: description.to_string(),
+                required: *required,
+                secret: *secret,
+            })
+            .collect(),
+        token_cost_estimate: item.token_cost,
+        installed: false,
+    }
+}
+
+/// All catalog entries, in catalog order.
+pub fn catalog() -> Vec<McpCatalogEntry> {
+    CATALOG.iter().map(to_entry).collect()
+}
+
+/// Look up a catalog entry by id (case-insensitive).
+pub fn find_entry(id: &str) -> Option<McpCatalogEntry> {
+    CATALOG.iter().find(|i| i.id.eq_ignore_ascii_case(id.trim())).map(to_entry)
+}
+
+/// .mcp.json name of the app's built-in MCP server.
+pub const JUMPSTART_SERVER: &str = "project-jumpstart";
+
+/// File name of the jumpstart-mcp binary shipped next to the app.
+const JUMPSTART_MCP_BINARY: &str = if cfg!(windows) { "jumpstart-mcp.exe" } else { "jumpstart-mcp" };
+
+/// The .mcp.json entry that runs jumpstart-mcp from `app_dir` (the app executable's directory).
+/// Fails when the binary is not there.
+pub fn jumpstart_server_entry(app_dir: &Path) -> Result<Value, String> {
+    let binary = app_dir.join(JUMPSTART_MCP_BINARY);
+    if !binary.is_file() {
+        return Err(format!("{} was not found next to the app ({})", JUMPSTART_MCP_BINARY, binary.display()));
+    }
+    Ok(json!({ "command": binary.to_string_lossy(), "args": [] }))
+}
+
+/// Whether `value` is exactly one environment variable reference such as "${GITHUB_TOKEN}".
+fn is_env_reference(value: &str) -> bool {
+    value
+        .trim()
+        .strip_prefix("${")
+        .and_then(|rest| rest.strip_suffix('}'))
+        .is_some_and(|name| {
+            name.chars().next().is_some_and(|c| c.is_ascii_alphabetic() || c == '_')
+                && name.chars().all(|c| c.is_ascii_alphanumeric() || c == '_')
+        })
+}
+
+/// Build the .mcp.json entry for a catalog server. Fails if a required env
+/// var is missing; unknown env names are rejected to catch typos. Secret vars
+/// must be "${VAR}" references and default to "${NAME}" when omitted.
+pub fn build_server_entry(entry: &McpCatalogEntry, env: &HashMap<String, String>) -> Result<Value, String> {
+    if let Some(unknown) = env.keys().find(|k| !entry.env.iter().any(|v| &v.name == *k)) {
+        return Err(format!("{} does not use environment variable {}", entry.name, unknown));
+    }
+
+    let given = |name: &str| env.get(name).map(|s| s.trim()).filter(|s| !s.is_empty());
+    if let Some(literal) = entry.env.iter().find(|v| v.secret && given(&v.name).is_some_and(|s| !is_env_reference(s))) {
+        return Err(format!(
+            "{} is a secret and would be stored in plain text in {}; set it in your environment and pass \"${{{}}}\" instead",
+            literal.name, MCP_CONFIG_FILE, literal.name
+        ));
+    }
+
+    let missing: Vec<&str> = entry
+        .env
+        .iter()
+        .filter(|v| v.required && !v.secret && given(&v.name).is_none())
+        .map(|v| v.name.as_str())
+        .collect();
+    if !missing.is_empty() {
+        return Err(format!("{} requires: {}", entry.name, missing.join(", ")));
+    }
+
+    let mut server = json!({ "command": entry.command, "args": entry.args });
+    let env_map: Map<String, Value> = entry
+        .env
+        .iter()
+        .filter_map(|v| match given(&v.name) {
+            Some(value) => Some((v.name.clone(), json!(value))),
+            None if v.secret && v.required => Some((v.name.clone(), json!(format!("${{{}}}", v.name)))),
+            None => None,
+        })
+        .collect();
+    if !env_map.is_empty() {
+        server["env"] = Value::Object(env_map);
+    }
+    Ok(server)
+}
+
+/// Parse .mcp.json as an object (an absent or empty file is an empty object).
+fn read_config(path: &Path) -> Result<Map<String, Value>, String> {
+    let content = match fs::read_to_string(path) {
+        Ok(c) => c,
+        Err(e) if e.kind() == std::io::ErrorKind::NotFound => return Ok(Map::new()),
+        Err(e) => return Err(format!("Failed to read {}: {}", path.display(), e)),
+    };
+    if content.trim().is_empty() {
+        return Ok(Map::new());
+    }
+    match serde_json::from_str::<Value>(&content) {
+        Ok(Value::Object(map)) => Ok(map),
+        Ok(_) => Err(format!("{} is not a JSON object; fix it before adding servers", path.display())),
+        Err(e) => Err(format!("{} is not valid JSON ({}); fix it before adding servers", path.display(), e)),
+    }
+}
+
+fn write_config(path: &Path, config: &Map<String, Value>) -> Result<(), String> {
+    let content = serde_json::to_string_pretty(config).map_err(|e| format!("Failed to serialize MCP config: {}", e))?;
+    let tmp = path.with_extension("json.tmp");
+    fs::write(&tmp, format!("{}\n", content)).map_err(|e| format!("Failed to write {}: {}", tmp.display(), e))?;
+    fs::rename(&tmp, path).map_err(|e| format!("Failed to replace {}: {}", path.display(), e))
+}
+
+/// Names of servers configured in a project's .mcp.json.
+pub fn configured_servers(project_path: &Path) -> Vec<String> {
+    read_config(&project_path.join(MCP_CONFIG_FILE))
+        .ok()
+        .and_then(|c| c.get("mcpServers").and_then(|s| s.as_object()).map(|s| s.keys().cloned().collect()))
+        .unwrap_or_default()
+}
+
+/// Insert or replace a server under "mcpServers". Returns "added",
+/// "replaced", or "unchanged".
+pub fn add_server(project_path: &Path, name: &str, server: Value) -> Result<&'static str, String> {
+    let path = project_path.join(MCP_CONFIG_FILE);
+    let mut config = read_config(&path)?;
+
+    let servers = config
+        .entry("mcpServers")
+        .or_insert_with(|| Value::Object(Map::new()))
+        .as_object_mut()
+        .ok_or_else(|| format!("\"mcpServers\" in {} is not an object", path.display()))?;
+
+    let action = match servers.get(name) {
+        Some(existing) if *existing == server => return Ok("unchanged"),
+        Some(_) => "replaced",
+        None => "added",
+    };
+    servers.insert(name.to_string(), server);
+    write_config(&path, &config)?;
+    Ok(action)
+}
+
+/// Remove a server from "mcpServers". Returns false if it was not configured.
+pub fn remove_server(project_path: &Path, name: &str) -> Result<bool, String> {
+    let path = project_path.join(MCP_CONFIG_FILE);
+    let mut config = read_config(&path)?;
+
+    let removed = config
+        .get_mut("mcpServers")
+        .and_then(|s| s.as_object_mut())
+        .and_then(|s| s.remove(name))
+        .is_some();
+    if removed {
+        write_config(&path, &config)?;
+    }
+    Ok(removed)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_build_server_entry_validates_env() {
+        let sqlite = find_entry("sqlite").unwrap();
+        assert!(build_server_entry(&sqlite, &HashMap::new()).unwrap_err().contains("SQLITE_DB_PATH"));
+
+        let github = find_entry("GitHub").unwrap();
+        let mut env = HashMap::new();
+        env.insert("GITHUB_TOKEN".to_string(), "x".to_string());
+        assert!(build_server_entry(&github, &env).is_err());
+
+        let mut env = HashMap::new();
+        env.insert("GITHUB_PERSONAL_ACCESS_TOKEN".to_string(), "${GITHUB_TOKEN}".to_string());
+        let entry = build_server_entry(&github, &env).unwrap();
+        assert_eq!(entry["command"], "npx");
+        assert_eq!(entry["env"]["GITHUB_PERSONAL_ACCESS_TOKEN"], "${GITHUB_TOKEN}");
+        assert!(build_server_entry(&find_entry("fetch").unwrap(), &HashMap::new()).unwrap().get("env").is_none());
+    }
+
+    #[test]
+    fn test_secret_env_values_stay_out_of_config() {
+        for (server, var) in [
+            ("github", "GITHUB_PERSONAL_ACCESS_TOKEN"),
+            ("slack", "SLACK_BOT_TOKEN"),
+            ("brave-search", "BRAVE_API_KEY"),
+            ("postgres", "DATABASE_URL"),
+        ] {
+            let entry = find_entry(server).unwrap();
+            let mut env = HashMap::new();
+            if server == "slack" {
+                env.insert("SLACK_TEAM_ID".to_string(), "T123".to_string());
+            }
+
+            // Omitted secrets reference the variable of the same name
+            let omitted = build_server_entry(&entry, &env).unwrap();
+            assert_eq!(omitted["env"][var], format!("${{{}}}", var));
+
+            env.insert(var.to_string(), "ghp_literalsecret".to_string());
+            let err = build_server_entry(&entry, &env).unwrap_err();
+            assert!(err.contains(var) && err.contains("plain text"), "{}", err);
+        }
+
+        assert!(is_env_reference("${MY_TOKEN}"));
+        assert!(is_env_reference(" ${_T1} "));
+        assert!(!is_env_reference("${MY_TOKEN}extra"));
+        assert!(!is_env_reference("prefix${MY_TOKEN}"));
+        assert!(!is_env_reference("${1TOKEN}"));
+        assert!(!is_env_reference("${}"));
+    }
+
+    #[test]
+    fn test_add_and_remove_preserve_other_config() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join(MCP_CONFIG_FILE);
+        fs::write(&path, r#"{"mcpServers": {"custom": {"command": "my-server"}}, "other": 1}"#).unwrap();
+
+        let fetch = build_server_entry(&find_entry("fetch").unwrap(), &HashMap::new()).unwrap();
+        assert_eq!(add_server(dir.path(), "fetch", fetch.clone()).unwrap(), "added");
+        assert_eq!(add_server(dir.path(), "fetch", fetch).unwrap(), "unchanged");
+
+        let config: Value = serde_json::from_str(&fs::read_to_string(&path).unwrap()).unwrap();
+        assert_eq!(config["other"], 1);
+        assert_eq!(config["mcpServers"]["custom"]["command"], "my-server");
+        assert_eq!(configured_servers(dir.path()), vec!["custom".to_string(), "fetch".to_string()]);
+
+        assert!(remove_server(dir.path(), "fetch").unwrap());
+        assert!(!remove_server(dir.path(), "fetch").unwrap());
+        assert_eq!(configured_servers(dir.path()), vec!["custom".to_string()]);
+    }
+
+    #[test]
+    fn test_jumpstart_server_entry() {
+        let dir = tempfile::tempdir().unwrap();
+        assert!(jumpstart_server_entry(dir.path()).unwrap_err().contains("not found"));
+
+        let binary = dir.path().join(JUMPSTART_MCP_BINARY);
+        fs::write(&binary, "").unwrap();
+        let entry = jumpstart_server_entry(dir.path()).unwrap();
+        assert_eq!(entry["command"], json!(binary.to_string_lossy()));
+        assert_eq!(entry["args"], json!([]));
+    }
+
+    #[test]
+    fn test_invalid_config_is_not_overwritten() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join(MCP_CONFIG_FILE);
+        fs::write(&path, "{ broken").unwrap();
+
+        assert!(add_server(dir.path(), "fetch", json!({})).is_err());
+        assert_eq!(fs::read_to_string(&path).unwrap(), "{ broken");
+
+        // Missing file is created
+        let fresh = tempfile::tempdir().unwrap();
+        assert_eq!(add_server(fresh.path(), "fetch", json!({"command": "uvx"})).unwrap(), "added");
+    }
+}
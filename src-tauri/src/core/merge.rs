@@ -0,0 +1,200 @@
+//! @module core/merge
+//! @description Classify incoming shared knowledge (skills, agents, team templates) against local rows
+//!
+//! PURPOSE:
+//! - Match incoming rows to local ones by id, then by name
+//! - Classify each incoming row as new, identical, newer, outdated, or conflicting
+//! - Report which fields differ so the UI can show what a decision changes
+//!
+//! DEPENDENCIES:
+//! - db::shared - SharedTable column layout and row/timestamp helpers
+//! - rusqlite::types::Value - Row cell type
+//!
+//! EXPORTS:
+//! - MergeClass - Classification of one incoming row
+//! - MergeCandidate - Incoming row, its matched local row, and its class
+//! - classify_incoming - Classify a batch of incoming rows
+//! - changed_fields - Names of content fields that differ between two rows
+//! - is_mergeable - Whether a shared table has names and so can collide
+//!
+//! PATTERNS:
+//! - Content comparison ignores id, usage_count, created_at, updated_at
+//! - A row matched only by name (different id) is never auto-applied
+//! - With a last sync time, a row edited on both sides since that sync is conflicting
+//!
+//! CLAUDE NOTES:
+//! - Pure functions over rows; staging and applying live in commands/imports.rs
+//! - Name matching is case-insensitive and trims whitespace
+
+use std::collections::HashMap;
+
+use rusqlite::types::Value;
+
+use crate::db::shared::{is_newer, text_at, SharedTable};
+
+/// Columns that do not count as content when comparing two rows.
+const NON_CONTENT_COLUMNS: &[&str] = &["id", "usage_count", "created_at", "updated_at"];
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum MergeClass {
+    /// No local row with this id or name
+    New,
+    /// Local row has the same content
+    Identical,
+    /// Same id; incoming is newer and the local row was not edited since the last sync
+    Newer,
+    /// Same id; the local row is newer (it will be pushed, nothing to import)
+    Outdated,
+    /// Same name with a different id, or the same id edited on both sides
+    Conflicting,
+}
+
+#[derive(Debug, Clone)]
+pub struct MergeCandidate {
+    pub class: MergeClass,
+    pub incoming: Vec<Value>,
+    pub local: Option<Vec<Value>>,
+}
+
+/// Only tables with a name column can collide by name.
+pub fn is_mergeable(table: &SharedTable) -> bool {
+    table.column_index("name").is_some()
+}
+
+fn normalized_name(table: &SharedTable, row: &[Value]) -> String {
+    table
+        .column_index("name")
+        .map(|i| text_at(row, i).trim().to_lowercase())
+        .unwrap_or_default()
+}
+
+/// Names of content columns whose values differ.
+pub fn changed_fields(table: &SharedTable, local: &[Value], incoming: &[Value]) -> Vec<String> {
+    table
+        .columns
+        .iter()
+        .enumerate()
+        .filter(|(_, (c, _))| !NON_CONTENT_COLUMNS.contains(c))
+        .filter(|(i, _)| local.get(*i) != incoming.get(*i))
+        .map(|(_, (c, _))| c.to_string())
+        .collect()
+}
+
+/// Classify incoming rows against the local rows of the same table.
+/// `last_synced_at` is the previous successful sync (None for bundle imports
+/// and first syncs), used to detect rows edited on both sides.
+pub fn classify_incoming(
+    table: &SharedTable,
+    local: &[Vec<Value>],
+    incoming: Vec<Vec<Value>>,
+    last_synced_at: Option<&str>,
+) -> Vec<MergeCandidate> {
+    let updated = table.updated_at_index();
+    let by_id: HashMap<&str, &Vec<Value>> = local.iter().map(|r| (text_at(r, 0), r)).collect();
+    let by_name: HashMap<String, &Vec<Value>> = if is_mergeable(table) {
+        local.iter().map(|r| (normalized_name(table, r), r)).collect()
+    } else {
+        HashMap::new()
+    };
+
+    incoming
+        .into_iter()
+        .map(|row| {
+            let (local_row, same_id) = match by_id.get(text_at(&row, 0)) {
+                Some(l) => (Some(*l), true),
+                None => (by_name.get(&normalized_name(table, &row)).copied(), false),
+            };
+
+            let class = match local_row {
+                None => MergeClass::New,
+                Some(l) if changed_fields(table, l, &row).is_empty() => MergeClass::Identical,
+                Some(_) if !same_id => MergeClass::Conflicting,
+                Some(l) => {
+                    let (ours, theirs) = (text_at(l, updated), text_at(&row, updated));
+                    let edited_both = last_synced_at
+                        .map(|since| is_newer(ours, since) && is_newer(theirs, since))
+                        .unwrap_or(false);
+                    if edited_both {
+                        MergeClass::Conflicting
+                    } else if is_newer(theirs, ours) {
+                        MergeClass::Newer
+                    } else if is_newer(ours, theirs) {
+                        MergeClass::Outdated
+                    } else {
+                        MergeClass::Conflicting
+                    }
+                }
+            };
+
+            MergeCandidate {
+                class,
+                incoming: row,
+                local: local_row.cloned(),
+            }
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::db::shared::SHARED_TABLES;
+
+    fn skill(id: &str, name: &str, content: &str, updated_at: &str) -> Vec<Value> {
+        vec![
+            Value::Text(id.into()),
+            Value::Text(name.into()),
+            Value::Text(String::new()),
+            Value::Text(content.into()),
+            Value::Integer(0),
+            Value::Text("[]".into()),
+            Value::Text("2025-01-01T00:00:00Z".into()),
+            Value::Text(updated_at.into()),
+        ]
+    }
+
+    #[test]
+    fn test_classify_incoming() {
+        let skills = &SHARED_TABLES[0];
+        let local = vec![
+            skill("a", "Same", "x", "2025-01-01T00:00:00Z"),
+            skill("b", "Updated remotely", "old", "2025-01-01T00:00:00Z"),
+            skill("c", "Updated locally", "new", "2025-03-01T00:00:00Z"),
+            skill("d", "Both edited", "mine", "2025-03-01T00:00:00Z"),
+            skill("e", "Review Checklist", "mine", "2025-01-01T00:00:00Z"),
+        ];
+        let incoming = vec![
+            skill("a", "Same", "x", "2025-05-01T00:00:00Z"),
+            skill("b", "Updated remotely", "new", "2025-03-01T00:00:00Z"),
+            skill("c", "Updated locally", "old", "2025-01-01T00:00:00Z"),
+            skill("d", "Both edited", "theirs", "2025-03-02T00:00:00Z"),
+            skill("x", " review checklist ", "theirs", "2025-01-01T00:00:00Z"),
+            skill("y", "Brand new", "z", "2025-01-01T00:00:00Z"),
+        ];
+
+        let classes: Vec<MergeClass> = classify_incoming(skills, &local, incoming, Some("2025-02-01T00:00:00Z"))
+            .into_iter()
+            .map(|c| c.class)
+            .collect();
+        assert_eq!(
+            classes,
+            vec![
+                MergeClass::Identical,
+                MergeClass::Newer,
+                MergeClass::Outdated,
+                MergeClass::Conflicting,
+                MergeClass::Conflicting,
+                MergeClass::New,
+            ]
+        );
+    }
+
+    #[test]
+    fn test_changed_fields_ignores_metadata() {
+        let skills = &SHARED_TABLES[0];
+        let a = skill("a", "Name", "one", "2025-01-01T00:00:00Z");
+        let mut b = skill("b", "Name", "two", "2025-02-01T00:00:00Z");
+        b[4] = Value::Integer(9);
+        assert_eq!(changed_fields(skills, &a, &b), vec!["content".to_string()]);
+    }
+}
@@ -0,0 +1,296 @@
+//! @module core/merge_drift
+//! @description Detect documented files whose code changed in a merge while their header did not
+//!
+//! PURPOSE:
+//! - Find the commit range a merge brought in (hook-recorded ranges, ORIG_HEAD, or merge parents)
+//! - Compare each changed file's header and body before and after the merge
+//! - Store drifted files and mark them outdated in freshness results until the header changes
+//!
+//! DEPENDENCIES:
+//! - core::analyzer - Header parsing and source reading
+//! - core::freshness - Header line counting
+//! - core::proc - git rev-parse/diff/show with ProcLimits::GIT
+//! - rusqlite - merge_drift table
+//! - models::module_doc - ModuleStatus, MergeDriftFile
+//!
+//! EXPORTS:
+//! - PENDING_MERGES_FILE - File in the git dir where the post-merge hook records ranges
+//! - MERGE_DRIFT_CHANGE - Prefix of the change note added to drifted modules
+//! - take_pending_ranges - Read and clear hook-recorded (old, new) ranges
+//! - last_merge_range - Range of the most recent merge when no hook recorded one
+//! - detect_drift - Drifted files for one range
+//! - is_drift - Whether a before/after pair is code-changed-but-header-unchanged
+//! - record_drift - Upsert drifted files for a project
+//! - apply_drift - Mark stored drift in freshness results (and drop resolved rows)
+//!
+//! PATTERNS:
+//! - A file drifts when it had a doc header before and after the merge, the header text is
+//!   identical, and the code below it changed
+//! - Drift is resolved by any header edit: apply_drift compares the current header with the
+//!   one stored at detection
+//!
+//! CLAUDE NOTES:
+//! - Paths are project-relative (git --relative); the project root may be below the git root
+//! - Files added or deleted by the merge are not drift; missing headers already show as "missing"
+
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::process::Command;
+
+use chrono::Utc;
+use rusqlite::Connection;
+
+use crate::core::analyzer;
+use crate::core::freshness;
+use crate::core::proc::{self, ProcLimits};
+use crate::models::module_doc::{MergeDriftFile, ModuleStatus};
+
+/// File in the git dir the post-merge hook appends "<old> <new>" lines to.
+pub const PENDING_MERGES_FILE: &str = "jumpstart-merges";
+
+/// Prefix of the change note on drifted modules.
+pub const MERGE_DRIFT_CHANGE: &str = "Merge drift";
+
+fn git(project_path: &str, args: &[&str]) -> Option<String> {
+    let output = proc::run(Command::new("git").args(args).current_dir(project_path), ProcLimits::GIT).ok()?;
+    if !output.success() {
+        return None;
+    }
+    Some(String::from_utf8_lossy(&output.stdout).to_string())
+}
+
+fn rev_parse(project_path: &str, rev: &str) -> Option<String> {
+    git(project_path, &["rev-parse", "-q", "--verify", rev])
+        .map(|s| s.trim().to_string())
+        .filter(|s| !s.is_empty())
+}
+
+fn pending_file(project_path: &str) -> Option<PathBuf> {
+    let git_dir = git(project_path, &["rev-parse", "--absolute-git-dir"])?;
+    Some(Path::new(git_dir.trim()).join(PENDING_MERGES_FILE))
+}
+
+/// Ranges recorded by the post-merge hook, oldest first. The file is removed.
+pub fn take_pending_ranges(project_path: &str) -> Vec<(String, String)> {
+    let Some(file) = pending_file(project_path) else {
+        return Vec::new();
+    };
+    let Ok(content) = fs::read_to_string(&file) else {
+        return Vec::new();
+    };
+    let _ = fs::remove_file(&file);
+    parse_pending_ranges(&content)
+}
+
+/// "<old> <new>" lines; malformed lines and no-op ranges are skipped.
+fn parse_pending_ranges(content: &str) -> Vec<(String, String)> {
+    content
+        .lines()
+        .filter_map(|line| {
+            let mut parts = line.split_whitespace();
+            let (old, new) = (parts.next()?, parts.next()?);
+            (old != new).then(|| (old.to_string(), new.to_string()))
+        })
+        .collect()
+}
+
+/// The range of the latest merge: ORIG_HEAD..HEAD when ORIG_HEAD is an ancestor of HEAD
+/// (merge or pull), otherwise the first parent of a merge commit at HEAD.
+pub fn last_merge_range(project_path: &str) -> Option<(String, String)> {
+    let head = rev_parse(project_path, "HEAD")?;
+    if let Some(orig) = rev_parse(project_path, "ORIG_HEAD") {
+        let is_ancestor = proc::run(
+            Command::new("git")
+                .args(["merge-base", "--is-ancestor", &orig, &head])
+                .current_dir(project_path),
+            ProcLimits::GIT,
+        )
+        .map(|o| o.success())
+        .unwrap_or(false);
+        if orig != head && is_ancestor {
+            return Some((orig, head));
+        }
+    }
+    let first_parent = rev_parse(project_path, "HEAD^1")?;
+    rev_parse(project_path, "HEAD^2")?;
+    Some((first_parent, head))
+}
+
+/// (header, body) of a source file; None when it has no doc header.
+fn split_header(content: &str) -> Option<(String, String)> {
+    analyzer::parse_doc_header(content)?;
+    let header_lines = freshness::doc_header_line_count(content);
+    let header = content.lines().take(header_lines).collect::<Vec<_>>().join("\n");
+    let body = content.lines().skip(header_lines).collect::<Vec<_>>().join("\n");
+    Some((header, body))
+}
+
+/// Whether `after` changed code relative to `before` while keeping an identical header.
+pub fn is_drift(before: &str, after: &str) -> bool {
+    match (split_header(before), split_header(after)) {
+        (Some((old_header, old_body)), Some((new_header, new_body))) => {
+            old_header == new_header && old_body.trim() != new_body.trim()
+        }
+        _ => false,
+    }
+}
+
+/// Documented files changed between `base` and `head` whose header stayed the same.
+pub fn detect_drift(project_path: &str, base: &str, head: &str) -> Vec<(MergeDriftFile, String)> {
+    let Some(names) = git(project_path, &["diff", "--name-only", "--relative", "--diff-filter=M", base, head]) else {
+        return Vec::new();
+    };
+    let short_head: String = head.chars().take(7).collect();
+    let detected_at = Utc::now().to_rfc3339();
+
+    let mut drifted = Vec::new();
+    for rel in names.lines().map(str::trim).filter(|l| !l.is_empty()) {
+        let name = Path::new(rel).file_name().and_then(|n| n.to_str()).unwrap_or("");
+        if !analyzer::is_documentable(name) || !freshness::in_scan_scope(rel) {
+            continue;
+        }
+        let show = |rev: &str| git(project_path, &["show", &format!("{}:./{}", rev, rel)]);
+        let (Some(before), Some(after)) = (show(base), show(head)) else {
+            continue;
+        };
+        if analyzer::is_ignored_content(&after) || !is_drift(&before, &after) {
+            continue;
+        }
+        let Some((header, _)) = split_header(&after) else {
+            continue;
+        };
+        drifted.push((
+            MergeDriftFile {
+                path: rel.to_string(),
+                merge_commit: short_head.clone(),
+                detected_at: detected_at.clone(),
+            },
+            header,
+        ));
+    }
+    drifted
+}
+
+/// Upsert drifted files (with the header seen at detection) for a project.
+pub fn record_drift(db: &Connection, project_id: &str, drifted: &[(MergeDriftFile, String)]) -> Result<(), String> {
+    for (file, header) in drifted {
+        db.execute(
+            "INSERT OR REPLACE INTO merge_drift (project_id, file_path, header, merge_commit, detected_at)
+             VALUES (?1, ?2, ?3, ?4, ?5)",
+            rusqlite::params![project_id, file.path, header, file.merge_commit, file.detected_at],
+        )
+        .map_err(|e| format!("Failed to record merge drift: {}", e))?;
+    }
+    Ok(())
+}
+
+/// Mark modules with unresolved merge drift as outdated, adding a change note.
+/// Rows whose header has since changed (or whose file is gone) are deleted.
+pub fn apply_drift(db: &Connection, project_id: &str, project_path: &str, modules: &mut [ModuleStatus]) -> Result<(), String> {
+    let rows: Vec<(String, String, String)> = {
+        let mut stmt = db
+            .prepare("SELECT file_path, header, merge_commit FROM merge_drift WHERE project_id = ?1")
+            .map_err(|e| format!("Failed to query merge drift: {}", e))?;
+        let rows = stmt
+            .query_map([project_id], |row| Ok((row.get(0)?, row.get(1)?, row.get(2)?)))
+            .map_err(|e| format!("Failed to read merge drift: {}", e))?
+            .filter_map(|r| r.ok())
+            .collect();
+        rows
+    };
+
+    for (rel, header, merge_commit) in rows {
+        let current = analyzer::read_source(&Path::new(project_path).join(&rel).to_string_lossy())
+            .ok()
+            .and_then(|content| split_header(&content));
+        let unresolved = current.is_some_and(|(current_header, _)| current_header == header);
+        if !unresolved {
+            db.execute(
+                "DELETE FROM merge_drift WHERE project_id = ?1 AND file_path = ?2",
+                [project_id, rel.as_str()],
+            )
+            .map_err(|e| format!("Failed to clear merge drift: {}", e))?;
+            continue;
+        }
+        if let Some(module) = modules.iter_mut().find(|m| m.path == rel) {
+            module.status = "outdated".to_string();
+            module
+                .changes
+                .get_or_insert_with(Vec::new)
+                .push(format!("{}: code changed in merge {} without a header update", MERGE_DRIFT_CHANGE, merge_commit));
+        }
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_support::memory_db;
+
+    const HEADER: &str = "//! @module core/math\n//! @description Math helpers\n//!\n//! EXPORTS:\n//! - add_one - Adds one\n";
+
+    #[test]
+    fn test_is_drift() {
+        let before = format!("{}\npub fn add_one(x: u32) -> u32 {{ x + 1 }}\n", HEADER);
+        let code_changed = format!("{}\npub fn add_one(x: u64) -> u64 {{ x + 1 }}\n", HEADER);
+        let both_changed = code_changed.replace("Math helpers", "Math helpers (u64)");
+        let no_header = "pub fn add_one(x: u64) -> u64 { x + 1 }\n";
+
+        assert!(is_drift(&before, &code_changed));
+        assert!(!is_drift(&before, &both_changed));
+        assert!(!is_drift(&before, &before));
+        assert!(!is_drift(no_header, no_header.replace("u64", "u32").as_str()));
+    }
+
+    #[test]
+    fn test_parse_pending_ranges() {
+        let ranges = parse_pending_ranges("aaa bbb\n\nccc ccc\nonly-one\nddd eee\n");
+        assert_eq!(
+            ranges,
+            vec![("aaa".to_string(), "bbb".to_string()), ("ddd".to_string(), "eee".to_string())]
+        );
+    }
+
+    #[test]
+    fn test_apply_drift_marks_until_header_changes() {
+        let dir = std::env::temp_dir().join(format!("jumpstart-drift-{}", uuid::Uuid::new_v4()));
+        fs::create_dir_all(&dir).unwrap();
+        let project_path = dir.to_string_lossy().to_string();
+        let content = format!("{}\npub fn add_one(x: u64) -> u64 {{ x + 1 }}\n", HEADER);
+        fs::write(dir.join("math.rs"), &content).unwrap();
+
+        let db = memory_db();
+        let header = split_header(&content).unwrap().0;
+        let file = MergeDriftFile {
+            path: "math.rs".to_string(),
+            merge_commit: "abc1234".to_string(),
+            detected_at: "2025-01-01T00:00:00Z".to_string(),
+        };
+        record_drift(&db, "p1", &[(file, header)]).unwrap();
+
+        let module = ModuleStatus {
+            path: "math.rs".to_string(),
+            status: "current".to_string(),
+            freshness_score: 100,
+            changes: None,
+            suggested_doc: None,
+            quality: None,
+            analyzer: None,
+            change_authors: None,
+        };
+        let mut modules = vec![module.clone()];
+        apply_drift(&db, "p1", &project_path, &mut modules).unwrap();
+        assert_eq!(modules[0].status, "outdated");
+        assert!(modules[0].changes.as_ref().unwrap()[0].starts_with("Merge drift: code changed in merge abc1234"));
+
+        fs::write(dir.join("math.rs"), content.replace("Math helpers", "Math helpers for u64")).unwrap();
+        let mut modules = vec![module];
+        apply_drift(&db, "p1", &project_path, &mut modules).unwrap();
+        assert_eq!(modules[0].status, "current");
+        let remaining: u32 = db.query_row("SELECT COUNT(*) FROM merge_drift", [], |row| row.get(0)).unwrap();
+        assert_eq!(remaining, 0);
+
+        let _ = fs::remove_dir_all(&dir);
+    }
+}
@@ -14,6 +14,58 @@
 //! - health - Health score calculation
 //! - crypto - API key encryption/decryption
 //! - test_runner - Test framework detection and execution
+//! - test_env - Per-plan test environment variables, .env loading, and secret masking
+//! - monitor - Window-scoped progress events for detachable monitor windows
+//! - merge - Classify incoming shared knowledge against local rows
+//! - slash_commands - Render Claude Code slash commands and detect drift
+//! - mcp_catalog - Curated MCP servers and safe .mcp.json edits
+//! - health_history - Doc metrics per git commit and regression detection
+//! - command_guard - Validation and allowlisting for commands from PRDs and templates
+//! - proc - Process runner with timeouts, output limits, and kill-on-timeout
+//! - notebook - Jupyter notebook flattening and header cell edits
+//! - sql_schema - SQL statement parsing and the CLAUDE.md database schema overview
+//! - api_contracts - OpenAPI/GraphQL schema fingerprints and contract drift detection
+//! - readme - README section composition from module docs, CLAUDE.md, and the scanner
+//! - worktree - Git working-tree snapshots and per-run changed files
+//! - learnings - Referenced-file churn for promoted learnings and demotion edits
+//! - doc_goals - Per-project doc coverage, stale age, and health score goals
+//! - remote - ssh/rsync executor for projects on remote machines and dev containers
+//! - tdd_analytics - TDD cycle times and trends from session phase history
+//! - subagent_lint - Frontmatter, tool name, and duplicate checks for .claude/agents files
+//! - query_plan - EXPLAIN QUERY PLAN audit and missing-index report for the app database
+//! - ralph_preflight - CLI, login, project, and disk checks run before a RALPH loop starts
+//! - issue_rules - Local regex rules extracting file/line issues from tsc, eslint, cargo, pytest, and go output
+//! - patch - Unified diff parsing, dry runs, and builtin/git application for AI-proposed diffs
+//! - stack_presets - Built-in kickstart stack presets, preset matching, and prompt guidance
+//! - transcript_cache - Parsed session transcripts reused until the JSONL file changes
+//! - plan_prd - Plan-mode plans from session transcripts converted into PRD drafts
+//! - events - Internal event bus with activity, notification, webhook, and Tauri subscribers
+//! - git_policy - Per-project git permissions with ask-and-wait confirmations
+//! - github_ci - GitHub adapter: doc-check runs from GitHub Actions
+//! - doc_validation - Phantom/undocumented DEPENDENCIES and EXPORTS entries across documented files
+//! - ai_queue - Prioritized, concurrency-limited queue gating every Claude API call
+//! - merge_drift - Files whose code changed in a merge while their doc header did not
+//! - rules - Automation rules engine evaluated on the event bus
+//! - logging - tracing subscriber writing rotating JSON log files, and the log viewer query
+//! - doctor - Self-diagnostics checks and their one-click fixes
+//! - control - Loopback control channel for the jumpstart-cli companion (RALPH start/list/follow)
+//! - doc_index - FTS5 index over module doc headers and prompt-to-file relevance ranking
+//! - policy - Organization policy pack parsing and compliance evaluation
+//! - claude_backup - Timestamped .claude directory snapshots taken before Jumpstart writes into it
+//! - plugins - Custom analyzer plugins (subprocess protocol) for in-house languages and DSLs
+//! - sync - Encrypted settings/knowledge snapshots on an S3, WebDAV, or git remote
+//! - ai_audit - Opt-in per-project audit log of AI prompts and responses (redacted)
+//! - digest - Per-project weekly digests (stats, Markdown, due-week generation)
+//! - bootstrap - Directory clustering, digests, prompts, and cost estimates for the CLAUDE.md bootstrap
+//! - pr_health - Doc coverage delta, newly stale docs, and test plan status between two git refs
+//! - safe_read - Lossy, size-capped file reads and streaming line readers for scans
+//! - skill_conflicts - Skill and agent instructions that contradict CLAUDE.md
+//! - git_forge - GitForge trait, per-project forge selection, CI runs as enforcement events
+//! - gitlab_ci - GitLab adapter: doc-check jobs from GitLab CI
+//! - bitbucket_ci - Bitbucket adapter: doc-check steps from Bitbucket Pipelines
+//! - package_manager - npm/pnpm/yarn/bun/uv/poetry/pipenv detection and command building
+//! - conventions - CLAUDE.md naming/directory/forbidden rules and codebase violation checks
+//! - health_alerts - Per-project alert thresholds that fire once per crossing
 //!
 //! PATTERNS:
 //! - Core modules contain business logic, not IPC handling
@@ -34,3 +86,55 @@ pub mod health;
 pub mod crypto;
 pub mod test_runner;
 pub mod performance;
+pub mod monitor;
+pub mod merge;
+pub mod slash_commands;
+pub mod mcp_catalog;
+pub mod health_history;
+pub mod command_guard;
+pub mod proc;
+pub mod notebook;
+pub mod sql_schema;
+pub mod api_contracts;
+pub mod readme;
+pub mod worktree;
+pub mod learnings;
+pub mod doc_goals;
+pub mod remote;
+pub mod tdd_analytics;
+pub mod subagent_lint;
+pub mod query_plan;
+pub mod ralph_preflight;
+pub mod issue_rules;
+pub mod patch;
+pub mod stack_presets;
+pub mod transcript_cache;
+pub mod plan_prd;
+pub mod events;
+pub mod git_policy;
+pub mod github_ci;
+pub mod doc_validation;
+pub mod ai_queue;
+pub mod merge_drift;
+pub mod rules;
+pub mod test_env;
+pub mod logging;
+pub mod doctor;
+pub mod control;
+pub mod doc_index;
+pub mod policy;
+pub mod claude_backup;
+pub mod plugins;
+pub mod sync;
+pub mod ai_audit;
+pub mod digest;
+pub mod bootstrap;
+pub mod pr_health;
+pub mod safe_read;
+pub mod skill_conflicts;
+pub mod git_forge;
+pub mod gitlab_ci;
+pub mod bitbucket_ci;
+pub mod package_manager;
+pub mod conventions;
+pub mod health_alerts;
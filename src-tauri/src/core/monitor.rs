@@ -0,0 +1,213 @@
+//! @module core/monitor
+//! @description Window-scoped event routing for detachable RALPH loop and test run monitors
+//!
+//! PURPOSE:
+//! - Define the label convention for dedicated monitor windows
+//! - Route progress events to the main window and to the monitor window for that target
+//! - Define progress payloads for RALPH loops and test runs
+//!
+//! DEPENDENCIES:
+//! - tauri - AppHandle, Emitter, Manager for window lookup and targeted emission
+//! - serde - Serialization for event payloads
+//!
+//! EXPORTS:
+//! - MAIN_WINDOW_LABEL - Label of the app's main window
+//! - MonitorKind - What a monitor window watches (RALPH loop or test plan)
+//! - monitor_label - Window label for a monitor of a given kind and target
+//! - parse_monitor_label - Recover kind and target from a monitor window label
+//! - emit_scoped - Emit an event to the main window and the matching monitor window only
+//! - RalphLoopProgress - Payload of "ralph-loop-progress" events
+//! - TestRunProgress - Payload of "test-run-progress" events
+//! - PrdStoryProgress - Payload of "ralph-prd-story" events (one per PRD story step)
+//! - RALPH_PROGRESS_EVENT / TEST_RUN_PROGRESS_EVENT / PRD_STORY_EVENT - Event names
+//! - estimate_remaining_secs - Rolling ETA from finished story durations
+//!
+//! PATTERNS:
+//! - Monitor labels are "monitor-<kind>-<target_id>" (e.g. "monitor-ralph-<loop id>")
+//! - Events use emit_to with a window label, never a global broadcast
+//! - Emission is best-effort; failures are ignored so background work never stops
+//! - PRD ETA = mean of the last ETA_WINDOW finished story durations x stories left to run
+//!
+//! CLAUDE NOTES:
+//! - Monitor windows load the frontend at "#/monitor/<kind>/<target_id>"
+//! - capabilities/default.json grants "monitor-*" windows the same core permissions as main
+//! - Test run monitors are keyed by plan_id because the run id is created when the run starts
+
+use serde::Serialize;
+use tauri::{AppHandle, Emitter, Manager, Runtime};
+
+/// Label of the app's main window (Tauri's default for the first configured window).
+pub const MAIN_WINDOW_LABEL: &str = "main";
+
+/// Event emitted whenever a RALPH loop's status or progress changes.
+pub const RALPH_PROGRESS_EVENT: &str = "ralph-loop-progress";
+
+/// Event emitted when a test run starts and when it finishes.
+pub const TEST_RUN_PROGRESS_EVENT: &str = "test-run-progress";
+
+/// Event emitted for each step of a PRD story (started, iteration, validation failed, committed, ...).
+pub const PRD_STORY_EVENT: &str = "ralph-prd-story";
+
+/// Finished stories averaged for the PRD ETA.
+const ETA_WINDOW: usize = 5;
+
+const MONITOR_PREFIX: &str = "monitor-";
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum MonitorKind {
+    RalphLoop,
+    TestPlan,
+}
+
+impl MonitorKind {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            MonitorKind::RalphLoop => "ralph",
+            MonitorKind::TestPlan => "test_plan",
+        }
+    }
+
+    pub fn parse(s: &str) -> Option<MonitorKind> {
+        match s {
+            "ralph" => Some(MonitorKind::RalphLoop),
+            "test_plan" => Some(MonitorKind::TestPlan),
+            _ => None,
+        }
+    }
+}
+
+/// Window label for the monitor of a target. Characters Tauri does not allow
+/// in labels are replaced with '_'.
+pub fn monitor_label(kind: MonitorKind, target_id: &str) -> String {
+    let safe_id: String = target_id
+        .chars()
+        .map(|c| if c.is_ascii_alphanumeric() || c == '-' || c == '_' { c } else { '_' })
+        .collect();
+    format!("{}{}-{}", MONITOR_PREFIX, kind.as_str(), safe_id)
+}
+
+/// Recover the kind and target id from a monitor window label.
+pub fn parse_monitor_label(label: &str) -> Option<(MonitorKind, String)> {
+    let rest = label.strip_prefix(MONITOR_PREFIX)?;
+    [MonitorKind::RalphLoop, MonitorKind::TestPlan]
+        .into_iter()
+        .find_map(|kind| {
+            rest.strip_prefix(kind.as_str())
+                .and_then(|r| r.strip_prefix('-'))
+                .filter(|id| !id.is_empty())
+                .map(|id| (kind, id.to_string()))
+        })
+}
+
+/// Emit an event to the main window and, if open, the monitor window for
+/// this target. Other monitor windows do not receive it.
+pub fn emit_scoped<R: Runtime, S: Serialize + Clone>(
+    app: &AppHandle<R>,
+    kind: MonitorKind,
+    target_id: &str,
+    event: &str,
+    payload: S,
+) {
+    let _ = app.emit_to(MAIN_WINDOW_LABEL, event, payload.clone());
+
+    let label = monitor_label(kind, target_id);
+    if app.get_webview_window(&label).is_some() {
+        let _ = app.emit_to(label.as_str(), event, payload);
+    }
+}
+
+/// Payload for "ralph-loop-progress" events.
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct RalphLoopProgress {
+    pub loop_id: String,
+    pub project_id: String,
+    pub status: String,
+    pub iterations: u32,
+    pub current_story: Option<u32>,
+    pub total_stories: Option<u32>,
+    pub outcome: Option<String>,
+    /// Estimated PRD finish time (RFC 3339), once a story has finished
+    pub eta_at: Option<String>,
+}
+
+/// Payload for "ralph-prd-story" events.
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct PrdStoryProgress {
+    pub loop_id: String,
+    pub project_id: String,
+    /// 0-indexed
+    pub story_index: u32,
+    pub story_id: String,
+    pub story_title: String,
+    pub total_stories: u32,
+    /// "started" | "iteration" | "validation_failed" | "committed" | "failed" | "skipped" |
+    /// "timed_out" (the loop's time limit stopped or skipped the story)
+    pub phase: String,
+    /// Attempt number within the story (1-based) for iteration/validation_failed/committed/failed
+    pub iteration: Option<u32>,
+    pub max_iterations: u32,
+    /// Commit hash for "committed" ("no commit" when git policy denied it)
+    pub commit: Option<String>,
+    pub completed_stories: u32,
+    pub eta_at: Option<String>,
+    pub remaining_secs: Option<u64>,
+    pub timestamp: String,
+}
+
+/// Seconds left for `remaining` stories, from the mean duration of the last ETA_WINDOW
+/// finished stories. None until a story has finished.
+pub fn estimate_remaining_secs(durations_secs: &[u64], remaining: u32) -> Option<u64> {
+    if durations_secs.is_empty() {
+        return None;
+    }
+    let recent = &durations_secs[durations_secs.len().saturating_sub(ETA_WINDOW)..];
+    let mean = recent.iter().sum::<u64>() as f64 / recent.len() as f64;
+    Some((mean * remaining as f64).round() as u64)
+}
+
+/// Payload for "test-run-progress" events.
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct TestRunProgress {
+    pub plan_id: String,
+    pub run_id: String,
+    /// "running" | "passed" | "failed"
+    pub status: String,
+    pub passed: Option<u32>,
+    pub failed: Option<u32>,
+    pub error: Option<String>,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_monitor_label_roundtrip() {
+        let label = monitor_label(MonitorKind::RalphLoop, "1b4e28ba-2fa1-11d2-883f-0016d3cca427");
+        assert_eq!(label, "monitor-ralph-1b4e28ba-2fa1-11d2-883f-0016d3cca427");
+        assert_eq!(
+            parse_monitor_label(&label),
+            Some((MonitorKind::RalphLoop, "1b4e28ba-2fa1-11d2-883f-0016d3cca427".to_string()))
+        );
+
+        let label = monitor_label(MonitorKind::TestPlan, "plan 1");
+        assert_eq!(label, "monitor-test_plan-plan_1");
+        assert_eq!(parse_monitor_label(&label), Some((MonitorKind::TestPlan, "plan_1".to_string())));
+
+        assert_eq!(parse_monitor_label("main"), None);
+        assert_eq!(parse_monitor_label("monitor-ralph-"), None);
+        assert_eq!(parse_monitor_label("monitor-unknown-x"), None);
+    }
+
+    #[test]
+    fn test_estimate_remaining_secs_uses_recent_stories() {
+        assert_eq!(estimate_remaining_secs(&[], 3), None);
+        assert_eq!(estimate_remaining_secs(&[60, 120], 4), Some(360));
+        // Only the last 5 stories count
+        assert_eq!(estimate_remaining_secs(&[1000, 10, 20, 30, 40, 50], 2), Some(60));
+        assert_eq!(estimate_remaining_secs(&[90], 0), Some(0));
+    }
+}
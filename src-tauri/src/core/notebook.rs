@@ -0,0 +1,212 @@
+//! @module core/notebook
+//! @description Jupyter notebook (.ipynb) reading and doc header editing
+//!
+//! PURPOSE:
+//! - Flatten a notebook into analyzable text: the header cell followed by all code cells
+//! - Read the first-markdown-cell doc header
+//! - Insert or replace that header cell while leaving every other cell untouched
+//!
+//! DEPENDENCIES:
+//! - serde_json - nbformat JSON parsing and writing
+//!
+//! EXPORTS:
+//! - NOTEBOOK_EXTENSION - File extension of notebooks ("ipynb")
+//! - is_notebook - Whether a path is a notebook
+//! - FlatNotebook - Header text and concatenated code of a notebook
+//! - flatten - Parse notebook JSON into a FlatNotebook
+//! - set_header_cell - Write a doc header into the first markdown cell
+//!
+//! PATTERNS:
+//! - Doc header convention: the notebook's FIRST cell is a markdown cell holding
+//!   "@module ..." / "@description ..." lines and the usual PURPOSE/EXPORTS/... sections
+//! - Code cells are analyzed as Python; IPython magics (%, %%) and shell escapes (!) are dropped
+//! - Outputs are never read, so large embedded plots do not affect analysis
+//!
+//! CLAUDE NOTES:
+//! - Written notebooks use nbformat's layout (1-space indent, sorted keys, trailing newline)
+//!   so header edits produce small diffs
+//! - A first markdown cell without @module/@description is not a header; a new header
+//!   cell is inserted above it
+
+use serde_json::{json, Value};
+
+/// File extension of Jupyter notebooks.
+pub const NOTEBOOK_EXTENSION: &str = "ipynb";
+
+/// A notebook reduced to the text the analyzer works on.
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct FlatNotebook {
+    /// Text of the first cell when it is a markdown cell
+    pub header: Option<String>,
+    /// Code cells joined by blank lines, without magics
+    pub code: String,
+}
+
+impl FlatNotebook {
+    /// Header cell (if any) followed by the code, as one document.
+    pub fn text(&self) -> String {
+        match &self.header {
+            Some(header) => format!("{}\n\n{}", header.trim_end(), self.code),
+            None => self.code.clone(),
+        }
+    }
+}
+
+/// Whether a file path (or name) is a Jupyter notebook.
+pub fn is_notebook(path: &str) -> bool {
+    path.ends_with(".ipynb")
+}
+
+/// Parse notebook JSON into its header cell and code.
+pub fn flatten(raw: &str) -> Result<FlatNotebook, String> {
+    let notebook: Value = serde_json::from_str(raw).map_err(|e| format!("Invalid notebook JSON: {}", e))?;
+    let cells = notebook
+        .get("cells")
+        .and_then(|c| c.as_array())
+        .ok_or("Notebook has no cells array")?;
+
+    let header = cells
+        .first()
+        .filter(|c| cell_type(c) == "markdown")
+        .map(cell_source);
+
+    let code = cells
+        .iter()
+        .filter(|c| cell_type(c) == "code")
+        .map(|c| {
+            cell_source(c)
+                .lines()
+                .filter(|l| {
+                    let t = l.trim_start();
+                    !t.starts_with('%') && !t.starts_with('!')
+                })
+                .collect::<Vec<_>>()
+                .join("\n")
+        })
+        .filter(|s| !s.trim().is_empty())
+        .collect::<Vec<_>>()
+        .join("\n\n");
+
+    Ok(FlatNotebook { header, code })
+}
+
+/// Write `header` as the notebook's first markdown cell, replacing an existing
+/// header cell or inserting a new one. Returns the new notebook JSON.
+pub fn set_header_cell(raw: &str, header: &str) -> Result<String, String> {
+    let mut notebook: Value = serde_json::from_str(raw).map_err(|e| format!("Invalid notebook JSON: {}", e))?;
+    let cells = notebook
+        .get_mut("cells")
+        .and_then(|c| c.as_array_mut())
+        .ok_or("Notebook has no cells array")?;
+
+    let source = source_lines(header.trim_end());
+    let replace = cells
+        .first()
+        .is_some_and(|c| cell_type(c) == "markdown" && is_header_text(&cell_source(c)));
+    if replace {
+        cells[0]["source"] = source;
+    } else {
+        cells.insert(
+            0,
+            json!({
+                "cell_type": "markdown",
+                "metadata": {},
+                "source": source,
+            }),
+        );
+    }
+
+    to_nbformat_json(&notebook)
+}
+
+fn cell_type(cell: &Value) -> &str {
+    cell.get("cell_type").and_then(|t| t.as_str()).unwrap_or("")
+}
+
+/// Cell source as one string (nbformat allows a string or a list of lines).
+fn cell_source(cell: &Value) -> String {
+    match cell.get("source") {
+        Some(Value::String(s)) => s.clone(),
+        Some(Value::Array(lines)) => lines.iter().filter_map(|l| l.as_str()).collect(),
+        _ => String::new(),
+    }
+}
+
+fn is_header_text(text: &str) -> bool {
+    text.contains("@module") || text.contains("@description")
+}
+
+/// Split text into nbformat source lines (each ends in "\n" except the last).
+fn source_lines(text: &str) -> Value {
+    let lines: Vec<&str> = text.split('\n').collect();
+    let last = lines.len() - 1;
+    Value::Array(
+        lines
+            .iter()
+            .enumerate()
+            .map(|(i, l)| Value::String(if i == last { l.to_string() } else { format!("{}\n", l) }))
+            .collect(),
+    )
+}
+
+fn to_nbformat_json(notebook: &Value) -> Result<String, String> {
+    let mut buf = Vec::new();
+    let formatter = serde_json::ser::PrettyFormatter::with_indent(b" ");
+    let mut serializer = serde_json::Serializer::with_formatter(&mut buf, formatter);
+    serde::Serialize::serialize(notebook, &mut serializer).map_err(|e| format!("Failed to write notebook: {}", e))?;
+    let mut out = String::from_utf8(buf).map_err(|e| format!("Failed to write notebook: {}", e))?;
+    out.push('\n');
+    Ok(out)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const NOTEBOOK: &str = r###"{
+ "cells": [
+  {"cell_type": "markdown", "metadata": {}, "source": ["@module notebooks/churn\n", "@description Churn exploration\n", "\n", "EXPORTS:\n", "- load_data - Load the CSV"]},
+  {"cell_type": "code", "metadata": {}, "execution_count": 1, "outputs": [{"output_type": "stream", "text": ["def fake():\n"]}],
+   "source": ["%matplotlib inline\n", "import pandas as pd\n", "from lib.features import build"]},
+  {"cell_type": "markdown", "metadata": {}, "source": "## Loading"},
+  {"cell_type": "code", "metadata": {}, "execution_count": 2, "outputs": [], "source": "!pip install x\ndef load_data(path):\n    return pd.read_csv(path)"}
+ ],
+ "metadata": {},
+ "nbformat": 4,
+ "nbformat_minor": 5
+}"###;
+
+    #[test]
+    fn test_flatten_notebook() {
+        let flat = flatten(NOTEBOOK).unwrap();
+        assert!(flat.header.as_deref().unwrap().starts_with("@module notebooks/churn\n"));
+        assert_eq!(
+            flat.code,
+            "import pandas as pd\nfrom lib.features import build\n\ndef load_data(path):\n    return pd.read_csv(path)"
+        );
+        assert!(!flat.code.contains("fake"));
+        assert!(flat.text().contains("- load_data - Load the CSV\n\nimport pandas"));
+        assert!(flatten("not json").is_err());
+    }
+
+    #[test]
+    fn test_set_header_cell_replaces_header() {
+        let updated = set_header_cell(NOTEBOOK, "@module notebooks/churn\n@description Updated\n").unwrap();
+        let flat = flatten(&updated).unwrap();
+        assert_eq!(flat.header.as_deref(), Some("@module notebooks/churn\n@description Updated"));
+        assert_eq!(flat.code, flatten(NOTEBOOK).unwrap().code);
+        assert!(updated.starts_with("{\n \"cells\": [\n"));
+        assert!(updated.ends_with("}\n"));
+    }
+
+    #[test]
+    fn test_set_header_cell_inserts_above_plain_markdown() {
+        let raw = r##"{"cells": [{"cell_type": "markdown", "metadata": {}, "source": ["# Title"]}], "metadata": {}, "nbformat": 4, "nbformat_minor": 5}"##;
+        let updated = set_header_cell(raw, "@module nb\n@description New").unwrap();
+        let value: Value = serde_json::from_str(&updated).unwrap();
+        let cells = value["cells"].as_array().unwrap();
+        assert_eq!(cells.len(), 2);
+        assert_eq!(cells[0]["source"], json!(["@module nb\n", "@description New"]));
+        assert_eq!(cells[1]["source"], json!(["# Title"]));
+    }
+}
@@ -0,0 +1,223 @@
+//! @module core/package_manager
+//! @description Package manager detection (npm, pnpm, yarn, bun, uv, poetry, pipenv) and command building
+//!
+//! PURPOSE:
+//! - Detect which package manager a JavaScript or Python project uses
+//! - Resolve a project's package manager from its stored value, its files, or the language default
+//! - Build install, script, and tool commands for that package manager
+//!
+//! DEPENDENCIES:
+//! - std::path - Lockfile lookups
+//! - core::safe_read - Size-capped reads of package.json and pyproject.toml
+//! - serde_json - The package.json "packageManager" field
+//!
+//! EXPORTS:
+//! - PackageManager - npm | pnpm | yarn | bun | uv | poetry | pipenv | pip
+//! - detect - Package manager and the file it was detected from
+//! - default_for - Package manager assumed for a language when nothing is detected
+//! - resolve - Stored value, then detection, then the language default
+//!
+//! PATTERNS:
+//! - Detection priority: package.json "packageManager" > JS lockfiles > Python lockfiles >
+//!   pyproject.toml [tool.poetry] / [tool.uv]
+//! - Projects store the name (as_str) in projects.package_manager; NULL means "detect at use"
+//!
+//! CLAUDE NOTES:
+//! - Undetected JS projects keep the old pnpm default; Python falls back to plain pip
+//! - "bun test" is bun's own runner, so package scripts run as "bun run <script>"
+//! - pip has no runner: exec returns the tool command unchanged
+
+use std::path::Path;
+
+use crate::core::safe_read;
+
+/// A JavaScript or Python package manager.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum PackageManager {
+    Npm,
+    Pnpm,
+    Yarn,
+    Bun,
+    Uv,
+    Poetry,
+    Pipenv,
+    Pip,
+}
+
+/// Lockfiles in detection order, with the manager that writes them.
+const LOCKFILES: &[(&str, PackageManager)] = &[
+    ("pnpm-lock.yaml", PackageManager::Pnpm),
+    ("yarn.lock", PackageManager::Yarn),
+    ("bun.lockb", PackageManager::Bun),
+    ("bun.lock", PackageManager::Bun),
+    ("package-lock.json", PackageManager::Npm),
+    ("npm-shrinkwrap.json", PackageManager::Npm),
+    ("uv.lock", PackageManager::Uv),
+    ("poetry.lock", PackageManager::Poetry),
+    ("Pipfile.lock", PackageManager::Pipenv),
+    ("Pipfile", PackageManager::Pipenv),
+];
+
+impl PackageManager {
+    /// Name as stored on the project and typed on the command line.
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            PackageManager::Npm => "npm",
+            PackageManager::Pnpm => "pnpm",
+            PackageManager::Yarn => "yarn",
+            PackageManager::Bun => "bun",
+            PackageManager::Uv => "uv",
+            PackageManager::Poetry => "poetry",
+            PackageManager::Pipenv => "pipenv",
+            PackageManager::Pip => "pip",
+        }
+    }
+
+    /// Parse a stored or user-supplied name (case-insensitive).
+    pub fn parse(name: &str) -> Option<Self> {
+        let pm = match name.trim().to_lowercase().as_str() {
+            "npm" => PackageManager::Npm,
+            "pnpm" => PackageManager::Pnpm,
+            "yarn" => PackageManager::Yarn,
+            "bun" => PackageManager::Bun,
+            "uv" => PackageManager::Uv,
+            "poetry" => PackageManager::Poetry,
+            "pipenv" => PackageManager::Pipenv,
+            "pip" => PackageManager::Pip,
+            _ => return None,
+        };
+        Some(pm)
+    }
+
+    /// True for the JavaScript package managers.
+    pub fn is_javascript(&self) -> bool {
+        matches!(self, PackageManager::Npm | PackageManager::Pnpm | PackageManager::Yarn | PackageManager::Bun)
+    }
+
+    /// Command that installs the project's dependencies.
+    pub fn install(&self) -> String {
+        match self {
+            PackageManager::Uv => "uv sync".to_string(),
+            PackageManager::Pip => "pip install -r requirements.txt".to_string(),
+            other => format!("{} install", other.as_str()),
+        }
+    }
+
+    /// Command that runs a package.json script ("test", "dev", "tauri dev").
+    pub fn run_script(&self, script: &str) -> String {
+        match self {
+            PackageManager::Npm if script == "test" || script == "start" => format!("npm {}", script),
+            PackageManager::Npm | PackageManager::Bun => format!("{} run {}", self.as_str(), script),
+            PackageManager::Pnpm | PackageManager::Yarn => format!("{} {}", self.as_str(), script),
+            _ => self.exec(script),
+        }
+    }
+
+    /// Command that runs a tool installed in the project ("vitest run", "pytest -q").
+    pub fn exec(&self, command: &str) -> String {
+        match self {
+            PackageManager::Npm => format!("npx {}", command),
+            PackageManager::Pnpm => format!("pnpm {}", command),
+            PackageManager::Yarn => format!("yarn {}", command),
+            PackageManager::Bun => format!("bunx {}", command),
+            PackageManager::Uv => format!("uv run {}", command),
+            PackageManager::Poetry => format!("poetry run {}", command),
+            PackageManager::Pipenv => format!("pipenv run {}", command),
+            PackageManager::Pip => command.to_string(),
+        }
+    }
+}
+
+/// Detect the package manager from the project's files.
+/// Returns the manager and the file it was detected from.
+pub fn detect(project_path: &Path) -> Option<(PackageManager, String)> {
+    if let Ok(content) = safe_read::read_text(project_path.join("package.json")) {
+        let declared = serde_json::from_str::<serde_json::Value>(&content)
+            .ok()
+            .and_then(|pkg| pkg.get("packageManager").and_then(|v| v.as_str()).map(str::to_string));
+        // "pnpm@9.1.0" or "yarn@4.0.2+sha224.abc"
+        if let Some(pm) = declared.and_then(|d| PackageManager::parse(d.split('@').next().unwrap_or_default())) {
+            return Some((pm, "package.json packageManager".to_string()));
+        }
+    }
+
+    for (file, pm) in LOCKFILES {
+        if project_path.join(file).is_file() {
+            return Some((*pm, file.to_string()));
+        }
+    }
+
+    let pyproject = safe_read::read_text(project_path.join("pyproject.toml")).unwrap_or_default();
+    if pyproject.lines().any(|l| l.trim() == "[tool.poetry]") {
+        return Some((PackageManager::Poetry, "pyproject.toml [tool.poetry]".to_string()));
+    }
+    if pyproject.lines().any(|l| l.trim() == "[tool.uv]") {
+        return Some((PackageManager::Uv, "pyproject.toml [tool.uv]".to_string()));
+    }
+    None
+}
+
+/// Package manager assumed for a language when the project shows no signal.
+pub fn default_for(language: &str) -> Option<PackageManager> {
+    match language {
+        "TypeScript" | "JavaScript" => Some(PackageManager::Pnpm),
+        "Python" => Some(PackageManager::Pip),
+        _ => None,
+    }
+}
+
+/// A project's package manager: the stored value, then detection from its files,
+/// then the language default.
+pub fn resolve(stored: Option<&str>, project_path: &str, language: &str) -> Option<PackageManager> {
+    stored
+        .and_then(PackageManager::parse)
+        .or_else(|| detect(Path::new(project_path)).map(|(pm, _)| pm))
+        .or_else(|| default_for(language))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::fs;
+
+    #[test]
+    fn test_detect_priority() {
+        let dir = tempfile::tempdir().unwrap();
+        let root = dir.path();
+        assert!(detect(root).is_none());
+
+        fs::write(root.join("pyproject.toml"), "[project]\nname = \"x\"\n\n[tool.poetry]\n").unwrap();
+        assert_eq!(detect(root).unwrap().0, PackageManager::Poetry);
+        fs::write(root.join("uv.lock"), "").unwrap();
+        assert_eq!(detect(root).unwrap(), (PackageManager::Uv, "uv.lock".to_string()));
+
+        fs::write(root.join("package.json"), r#"{"name": "web"}"#).unwrap();
+        fs::write(root.join("package-lock.json"), "{}").unwrap();
+        assert_eq!(detect(root).unwrap().0, PackageManager::Npm);
+        fs::write(root.join("yarn.lock"), "").unwrap();
+        assert_eq!(detect(root).unwrap().0, PackageManager::Yarn);
+
+        fs::write(root.join("package.json"), r#"{"name": "web", "packageManager": "bun@1.1.0"}"#).unwrap();
+        assert_eq!(detect(root).unwrap(), (PackageManager::Bun, "package.json packageManager".to_string()));
+
+        let path = root.to_str().unwrap();
+        assert_eq!(resolve(Some("PNPM"), path, "TypeScript"), Some(PackageManager::Pnpm));
+        assert_eq!(resolve(None, path, "TypeScript"), Some(PackageManager::Bun));
+        assert_eq!(resolve(None, "/nonexistent/pm", "Python"), Some(PackageManager::Pip));
+        assert_eq!(resolve(None, "/nonexistent/pm", "Go"), None);
+    }
+
+    #[test]
+    fn test_commands() {
+        assert_eq!(PackageManager::Npm.run_script("test"), "npm test");
+        assert_eq!(PackageManager::Npm.run_script("dev"), "npm run dev");
+        assert_eq!(PackageManager::Bun.run_script("test"), "bun run test");
+        assert_eq!(PackageManager::Yarn.run_script("tauri dev"), "yarn tauri dev");
+        assert_eq!(PackageManager::Npm.exec("vitest run"), "npx vitest run");
+        assert_eq!(PackageManager::Bun.exec("jest"), "bunx jest");
+        assert_eq!(PackageManager::Poetry.exec("pytest -q"), "poetry run pytest -q");
+        assert_eq!(PackageManager::Pip.exec("pytest -q"), "pytest -q");
+        assert_eq!(PackageManager::Uv.install(), "uv sync");
+        assert_eq!(PackageManager::Pipenv.install(), "pipenv install");
+    }
+}
@@ -0,0 +1,755 @@
+//! @module core/patch
+//! @description Unified diff parsing, validation, and application for AI-proposed edits
+//!
+//! PURPOSE:
+//! - Resolve an issue's file to a safe project-relative path
+//! - Cut the part of a file an AI needs to propose a fix for a given line
+//! - Normalize an AI-written diff so it touches exactly one file with a/ b/ headers
+//! - Parse unified diffs and apply them in-process with offset search and context fuzz
+//! - Dry-run a diff to report where each hunk lands before anything is written
+//! - Fall back to `git apply` when the git engine is selected
+//!
+//! DEPENDENCIES:
+//! - rusqlite - The patch.engine setting
+//! - core::proc - git apply with ProcLimits::GIT
+//! - uuid - Temporary diff file names
+//!
+//! EXPORTS:
+//! - relative_path - Project-relative form of an issue's file path (None outside the project)
+//! - excerpt - (first line, last line, text) of the file region sent to the AI
+//! - normalize_diff - Clean an AI diff and pin its headers to one file
+//! - PatchEngine / engine / ENGINE_SETTING - builtin (default) or git, from the patch.engine setting
+//! - parse - Unified diff to FilePatch / Hunk / HunkLine
+//! - apply_to_text - Apply one file's hunks to text (new text, max offset, max fuzz)
+//! - dry_run - Validate a diff against the project; PatchReport per file
+//! - check - dry_run without the report
+//! - apply - Apply a diff in a project (every file or none)
+//! - diff_texts - Unified diff between two versions of a file (one hunk)
+//! - replace_file - Write new file text as a diff through the engine (headers, CLAUDE.md sections)
+//!
+//! PATTERNS:
+//! - Hunk header line counts are ignored (recounted from the hunk), like `git apply --recount`;
+//!   models often get them wrong
+//! - Builtin matching: header position, then the nearest matching offset, then up to MAX_FUZZ
+//!   context lines dropped from each hunk end; trailing whitespace is ignored when comparing
+//! - Builtin apply computes every file's new content before writing any of them
+//! - Git diffs are written to a temp file because proc::run gives children a null stdin
+//! - Whole-file rewrites (doc headers, the CLAUDE.md schema overview) use replace_file, so they
+//!   are checked against the file as it was read and go through the configured engine
+//!
+//! CLAUDE NOTES:
+//! - git apply works outside a repository too; inside one, paths are relative to the git root,
+//!   assumed to be the project root (as in core::worktree)
+//! - normalize_diff rewrites ---/+++ headers instead of trusting the model's paths
+//! - Builtin paths go through relative_path, so a diff cannot write outside the project
+//! - Files keep their line endings (CRLF preserved) and trailing-newline state unless a hunk
+//!   at the end of the file changes it
+
+use std::fs;
+use std::path::{Component, Path};
+use std::process::Command;
+
+use rusqlite::Connection;
+
+use crate::core::proc::{self, ProcLimits};
+
+/// Files up to this many lines are sent whole.
+const FULL_FILE_LINES: usize = 400;
+
+/// Lines of context on each side of the issue line for larger files.
+const EXCERPT_CONTEXT: usize = 60;
+
+/// Project-relative path for `file` (relative or absolute inside the project).
+/// None when it escapes the project.
+pub fn relative_path(project_path: &Path, file: &str) -> Option<String> {
+    let file = file.trim().trim_start_matches("./");
+    let path = Path::new(file);
+    let rel = if path.is_absolute() {
+        path.strip_prefix(project_path).ok()?
+    } else {
+        path
+    };
+    if rel.as_os_str().is_empty() || rel.components().any(|c| !matches!(c, Component::Normal(_))) {
+        return None;
+    }
+    Some(rel.to_string_lossy().replace('\\', "/"))
+}
+
+/// The region of `content` to show for an issue at `line`: the whole file when it is
+/// short, otherwise EXCERPT_CONTEXT lines around the line. Lines are 1-based.
+pub fn excerpt(content: &str, line: Option<u32>) -> (usize, usize, String) {
+    let lines: Vec<&str> = content.lines().collect();
+    if lines.len() <= FULL_FILE_LINES {
+        return (1, lines.len(), content.to_string());
+    }
+    let center = line.map(|l| l as usize).unwrap_or(1).clamp(1, lines.len());
+    let start = center.saturating_sub(EXCERPT_CONTEXT).max(1);
+    let end = (center + EXCERPT_CONTEXT).min(lines.len());
+    (start, end, lines[start - 1..end].join("\n"))
+}
+
+/// Strip code fences and chatter around a diff, check it only touches `rel_path`,
+/// and rewrite its file headers to `a/<rel_path>` / `b/<rel_path>`.
+pub fn normalize_diff(text: &str, rel_path: &str) -> Result<String, String> {
+    let lines: Vec<&str> = text.lines().filter(|l| !l.trim_start().starts_with("```")).collect();
+    let start = lines
+        .iter()
+        .position(|l| l.starts_with("--- ") || l.starts_with("diff --git "))
+        .ok_or("Diff has no file header")?;
+
+    let lines = &lines[start..];
+    let mut out = Vec::new();
+    let mut has_hunk = false;
+    for (i, line) in lines.iter().enumerate() {
+        if line.starts_with("diff --git ") || line.starts_with("index ") {
+            continue;
+        }
+        // A ---/+++ pair is a file header; elsewhere "--- x" is a removed "-- x" line
+        let header = (line.starts_with("--- ") && lines.get(i + 1).is_some_and(|n| n.starts_with("+++ ")))
+            || (line.starts_with("+++ ") && i > 0 && lines[i - 1].starts_with("--- "));
+        if !header {
+            has_hunk |= line.starts_with("@@");
+            out.push(line.to_string());
+            continue;
+        }
+        let (marker, side) = if line.starts_with("--- ") { ("---", "a/") } else { ("+++", "b/") };
+        let path = line[4..].split('\t').next().unwrap_or("").trim();
+        if path == "/dev/null" {
+            out.push(line.to_string());
+            continue;
+        }
+        let bare = path.strip_prefix(side).unwrap_or(path);
+        if bare != rel_path {
+            return Err(format!("Diff touches {}, not {}", bare, rel_path));
+        }
+        out.push(format!("{} {}{}", marker, side, rel_path));
+    }
+    if !has_hunk {
+        return Err("Diff has no hunks".to_string());
+    }
+    Ok(out.join("\n") + "\n")
+}
+
+/// How diffs are checked and applied. Stored in the "patch.engine" setting.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum PatchEngine {
+    /// In-process hunk matching with offset search and context fuzz
+    #[default]
+    Builtin,
+    /// `git apply --recount` (exact context only)
+    Git,
+}
+
+impl PatchEngine {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            PatchEngine::Builtin => "builtin",
+            PatchEngine::Git => "git",
+        }
+    }
+
+    pub fn parse(value: &str) -> Option<Self> {
+        match value.trim() {
+            "builtin" => Some(PatchEngine::Builtin),
+            "git" => Some(PatchEngine::Git),
+            _ => None,
+        }
+    }
+}
+
+/// Setting that selects the patch engine ("builtin" or "git").
+pub const ENGINE_SETTING: &str = "patch.engine";
+
+/// Most context lines dropped from each end of a hunk when it does not match as written.
+const MAX_FUZZ: usize = 2;
+
+/// The configured patch engine (builtin when unset or unknown).
+pub fn engine(db: &Connection) -> PatchEngine {
+    db.query_row("SELECT value FROM settings WHERE key = ?1", [ENGINE_SETTING], |row| row.get::<_, String>(0))
+        .ok()
+        .and_then(|v| PatchEngine::parse(&v))
+        .unwrap_or_default()
+}
+
+/// One line of a hunk.
+#[derive(Debug, Clone, PartialEq)]
+pub enum HunkLine {
+    Context(String),
+    Remove(String),
+    Add(String),
+}
+
+/// A hunk: where it claims to start in the old file and its lines. Header line counts are
+/// ignored (recounted from the lines), since models often get them wrong.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Hunk {
+    /// 1-based old-file line from the @@ header (0 for an insertion at the top)
+    pub old_start: usize,
+    pub lines: Vec<HunkLine>,
+    /// "\ No newline at end of file" after the new side's last line
+    pub new_no_newline: bool,
+}
+
+impl Hunk {
+    fn old_lines(&self) -> Vec<&str> {
+        self.lines
+            .iter()
+            .filter_map(|l| match l {
+                HunkLine::Context(t) | HunkLine::Remove(t) => Some(t.as_str()),
+                HunkLine::Add(_) => None,
+            })
+            .collect()
+    }
+
+    fn new_lines(&self) -> Vec<&str> {
+        self.lines
+            .iter()
+            .filter_map(|l| match l {
+                HunkLine::Context(t) | HunkLine::Add(t) => Some(t.as_str()),
+                HunkLine::Remove(_) => None,
+            })
+            .collect()
+    }
+
+    /// The hunk with up to `fuzz` context lines dropped from each end.
+    fn fuzzed(&self, fuzz: usize) -> Hunk {
+        let leading = self.lines.iter().take_while(|l| matches!(l, HunkLine::Context(_))).count();
+        let trailing = self.lines.iter().rev().take_while(|l| matches!(l, HunkLine::Context(_))).count();
+        let (front, back) = if leading == self.lines.len() {
+            (0, 0)
+        } else {
+            (fuzz.min(leading), fuzz.min(trailing))
+        };
+        Hunk {
+            old_start: self.old_start + front,
+            lines: self.lines[front..self.lines.len() - back].to_vec(),
+            new_no_newline: self.new_no_newline && back == 0,
+        }
+    }
+}
+
+/// The hunks for one file. A None path is /dev/null (file created or deleted).
+#[derive(Debug, Clone, PartialEq)]
+pub struct FilePatch {
+    pub old_path: Option<String>,
+    pub new_path: Option<String>,
+    pub hunks: Vec<Hunk>,
+}
+
+/// How one file's hunks were placed by a dry run.
+#[derive(Debug, Clone, PartialEq)]
+pub struct PatchedFile {
+    pub path: String,
+    pub hunks: usize,
+    /// Largest distance (lines) between a hunk's header position and where it matched
+    pub max_offset: usize,
+    /// Largest number of context lines dropped from a hunk end to make it match
+    pub max_fuzz: usize,
+}
+
+/// Result of a dry run: every file the diff touches, in diff order.
+#[derive(Debug, Clone, PartialEq, Default)]
+pub struct PatchReport {
+    pub files: Vec<PatchedFile>,
+}
+
+/// Parse a unified diff into per-file hunks. Lines outside headers and hunks are ignored.
+pub fn parse(diff: &str) -> Result<Vec<FilePatch>, String> {
+    let lines: Vec<&str> = diff.lines().collect();
+    let mut files: Vec<FilePatch> = Vec::new();
+    // Bare empty lines at the end of a hunk are usually blank separators, not context
+    let mut blank_tail = 0;
+    let mut in_hunk = false;
+
+    let header_path = |line: &str| -> Option<String> {
+        let path = line[4..].split('\t').next().unwrap_or("").trim();
+        if path == "/dev/null" {
+            return None;
+        }
+        let path = path.strip_prefix("a/").or_else(|| path.strip_prefix("b/")).unwrap_or(path);
+        Some(path.to_string())
+    };
+    let close_hunk = |files: &mut Vec<FilePatch>, blank_tail: &mut usize| {
+        if let Some(hunk) = files.last_mut().and_then(|f| f.hunks.last_mut()) {
+            let keep = hunk.lines.len() - *blank_tail;
+            hunk.lines.truncate(keep);
+        }
+        *blank_tail = 0;
+    };
+
+    let mut i = 0;
+    while i < lines.len() {
+        let line = lines[i];
+        if line.starts_with("--- ") && lines.get(i + 1).is_some_and(|n| n.starts_with("+++ ")) {
+            close_hunk(&mut files, &mut blank_tail);
+            in_hunk = false;
+            files.push(FilePatch { old_path: header_path(line), new_path: header_path(lines[i + 1]), hunks: Vec::new() });
+            i += 2;
+            continue;
+        }
+        if line.starts_with("@@") {
+            close_hunk(&mut files, &mut blank_tail);
+            let file = files.last_mut().ok_or("Hunk before any file header")?;
+            let old_start = line
+                .trim_start_matches('@')
+                .trim()
+                .strip_prefix('-')
+                .and_then(|r| r.split([',', ' ']).next())
+                .and_then(|n| n.parse::<usize>().ok())
+                .ok_or_else(|| format!("Malformed hunk header: {}", line))?;
+            file.hunks.push(Hunk { old_start, lines: Vec::new(), new_no_newline: false });
+            in_hunk = true;
+            i += 1;
+            continue;
+        }
+        if in_hunk {
+            let hunk = files.last_mut().and_then(|f| f.hunks.last_mut()).ok_or("Hunk without a file")?;
+            match line.chars().next() {
+                Some(' ') => hunk.lines.push(HunkLine::Context(line[1..].to_string())),
+                Some('-') => hunk.lines.push(HunkLine::Remove(line[1..].to_string())),
+                Some('+') => hunk.lines.push(HunkLine::Add(line[1..].to_string())),
+                Some('\\') => {
+                    if matches!(hunk.lines.last(), Some(HunkLine::Add(_)) | Some(HunkLine::Context(_))) {
+                        hunk.new_no_newline = true;
+                    }
+                }
+                None => {
+                    hunk.lines.push(HunkLine::Context(String::new()));
+                    blank_tail += 1;
+                    i += 1;
+                    continue;
+                }
+                _ => in_hunk = false,
+            }
+            blank_tail = 0;
+        }
+        i += 1;
+    }
+    close_hunk(&mut files, &mut blank_tail);
+
+    if files.is_empty() {
+        return Err("Diff has no file header".to_string());
+    }
+    if let Some(f) = files.iter().find(|f| f.hunks.is_empty() || f.hunks.iter().any(|h| h.lines.is_empty())) {
+        return Err(format!("Diff has no hunks for {}", f.new_path.as_deref().or(f.old_path.as_deref()).unwrap_or("?")));
+    }
+    Ok(files)
+}
+
+fn lines_match(file: &[&str], at: usize, old: &[&str]) -> bool {
+    old.iter().enumerate().all(|(k, line)| file[at + k].trim_end() == line.trim_end())
+}
+
+/// Where `old` matches in `file` at or after `from`, nearest to `expected` first.
+fn find_hunk(file: &[&str], old: &[&str], expected: usize, from: usize) -> Option<usize> {
+    if old.len() > file.len() {
+        return None;
+    }
+    let last = file.len() - old.len();
+    if from > last {
+        return None;
+    }
+    let expected = expected.clamp(from, last);
+    let mut positions: Vec<usize> = (from..=last).collect();
+    positions.sort_by_key(|&at| (at.abs_diff(expected), at));
+    positions.into_iter().find(|&at| lines_match(file, at, old))
+}
+
+/// Apply one file's hunks to its content. Hunks are tried where their header says, then at
+/// the nearest offset where they match, then with up to MAX_FUZZ context lines dropped from
+/// each end. Trailing whitespace is ignored when matching. Returns the new content and
+/// (max offset, max fuzz).
+pub fn apply_to_text(content: &str, file: &FilePatch) -> Result<(String, usize, usize), String> {
+    let eol = if content.contains("\r\n") { "\r\n" } else { "\n" };
+    let lines: Vec<&str> = content.lines().collect();
+    let mut trailing_newline = content.ends_with('\n') || content.is_empty();
+
+    let mut out: Vec<String> = Vec::new();
+    let mut cursor = 0;
+    let mut drift: isize = 0;
+    let (mut max_offset, mut max_fuzz) = (0, 0);
+    let name = file.new_path.as_deref().or(file.old_path.as_deref()).unwrap_or("file");
+
+    for (n, hunk) in file.hunks.iter().enumerate() {
+        let placed = (0..=MAX_FUZZ).find_map(|fuzz| {
+            let candidate = if fuzz == 0 { hunk.clone() } else { hunk.fuzzed(fuzz) };
+            if fuzz > 0 && candidate.lines.len() == hunk.lines.len() {
+                return None;
+            }
+            let old = candidate.old_lines();
+            let declared = if old.is_empty() { candidate.old_start } else { candidate.old_start.saturating_sub(1) };
+            let expected = (declared as isize + drift).max(0) as usize;
+            let at = if old.is_empty() {
+                Some(expected.clamp(cursor, lines.len()))
+            } else {
+                find_hunk(&lines, &old, expected, cursor)
+            };
+            at.map(|at| (candidate, at, declared, fuzz))
+        });
+        let Some((hunk, at, declared, fuzz)) = placed else {
+            return Err(format!(
+                "Hunk {} of {} does not match (expected near line {})",
+                n + 1,
+                name,
+                hunk.old_start
+            ));
+        };
+
+        // Context lines keep the file's text (matching ignored trailing whitespace)
+        out.extend(lines[cursor..at].iter().map(|l| l.to_string()));
+        cursor = at;
+        for line in &hunk.lines {
+            match line {
+                HunkLine::Context(_) => {
+                    out.push(lines[cursor].to_string());
+                    cursor += 1;
+                }
+                HunkLine::Remove(_) => cursor += 1,
+                HunkLine::Add(text) => out.push(text.clone()),
+            }
+        }
+        drift = at as isize - declared as isize;
+        max_offset = max_offset.max(drift.unsigned_abs());
+        max_fuzz = max_fuzz.max(fuzz);
+        if cursor == lines.len() {
+            if hunk.new_no_newline {
+                trailing_newline = false;
+            } else if hunk.lines.iter().any(|l| !matches!(l, HunkLine::Context(_))) {
+                trailing_newline = true;
+            }
+        }
+    }
+    out.extend(lines[cursor..].iter().map(|l| l.to_string()));
+
+    let mut text = out.join(eol);
+    if trailing_newline && !out.is_empty() {
+        text.push_str(eol);
+    }
+    Ok((text, max_offset, max_fuzz))
+}
+
+/// Pending writes: relative path and new contents (None = delete).
+type PlannedWrites = Vec<(String, Option<String>)>;
+
+/// New contents for every file in the diff, without writing anything.
+fn builtin_plan(project_path: &Path, diff: &str) -> Result<(PatchReport, PlannedWrites), String> {
+    let mut report = PatchReport::default();
+    let mut writes = Vec::new();
+    for file in parse(diff)? {
+        let target = file.new_path.as_deref().or(file.old_path.as_deref()).unwrap_or_default();
+        let rel = relative_path(project_path, target).ok_or_else(|| format!("Diff path escapes the project: {}", target))?;
+        let full = project_path.join(&rel);
+        let content = match &file.old_path {
+            Some(_) => fs::read_to_string(&full).map_err(|e| format!("Failed to read {}: {}", rel, e))?,
+            None if full.exists() => return Err(format!("{} already exists", rel)),
+            None => String::new(),
+        };
+        let (text, max_offset, max_fuzz) = apply_to_text(&content, &file)?;
+        if file.new_path.is_none() && !text.is_empty() {
+            return Err(format!("Deleting {} would discard lines the diff does not remove", rel));
+        }
+        report.files.push(PatchedFile { path: rel.clone(), hunks: file.hunks.len(), max_offset, max_fuzz });
+        writes.push((rel, file.new_path.is_some().then_some(text)));
+    }
+    Ok((report, writes))
+}
+
+fn git_apply(project_path: &Path, diff: &str, check_only: bool) -> Result<(), String> {
+    let file = std::env::temp_dir().join(format!("jumpstart-patch-{}.diff", uuid::Uuid::new_v4()));
+    fs::write(&file, diff).map_err(|e| format!("Failed to write diff: {}", e))?;
+
+    let mut cmd = Command::new("git");
+    cmd.arg("apply");
+    if check_only {
+        cmd.arg("--check");
+    }
+    cmd.args(["--recount", "--whitespace=nowarn"]).arg(&file).current_dir(project_path);
+    let result = proc::run(&mut cmd, ProcLimits::GIT);
+    let _ = fs::remove_file(&file);
+
+    let output = result.map_err(|e| format!("Failed to run git apply: {}", e))?;
+    if output.success() {
+        Ok(())
+    } else {
+        Err(String::from_utf8_lossy(&output.stderr).trim().to_string())
+    }
+}
+
+/// Validate `diff` against the project's files without changing them, reporting how each
+/// file's hunks were placed (git reports no offsets or fuzz).
+pub fn dry_run(engine: PatchEngine, project_path: &Path, diff: &str) -> Result<PatchReport, String> {
+    match engine {
+        PatchEngine::Builtin => builtin_plan(project_path, diff).map(|(report, _)| report),
+        PatchEngine::Git => {
+            git_apply(project_path, diff, true)?;
+            let files = parse(diff)?
+                .into_iter()
+                .map(|f| PatchedFile {
+                    path: f.new_path.or(f.old_path).unwrap_or_default(),
+                    hunks: f.hunks.len(),
+                    max_offset: 0,
+                    max_fuzz: 0,
+                })
+                .collect();
+            Ok(PatchReport { files })
+        }
+    }
+}
+
+/// Check that `diff` applies in the project, without changing files.
+pub fn check(engine: PatchEngine, project_path: &Path, diff: &str) -> Result<(), String> {
+    dry_run(engine, project_path, diff).map(|_| ())
+}
+
+/// Apply `diff` to the project's files: every file or none.
+pub fn apply(engine: PatchEngine, project_path: &Path, diff: &str) -> Result<(), String> {
+    match engine {
+        PatchEngine::Builtin => {
+            let (_, writes) = builtin_plan(project_path, diff)?;
+            for (rel, content) in writes {
+                let full = project_path.join(&rel);
+                match content {
+                    Some(text) => {
+                        if let Some(parent) = full.parent() {
+                            fs::create_dir_all(parent).map_err(|e| format!("Failed to create {}: {}", rel, e))?;
+                        }
+                        fs::write(&full, text).map_err(|e| format!("Failed to write {}: {}", rel, e))?;
+                    }
+                    None => fs::remove_file(&full).map_err(|e| format!("Failed to delete {}: {}", rel, e))?,
+                }
+            }
+            Ok(())
+        }
+        PatchEngine::Git => git_apply(project_path, diff, false),
+    }
+}
+
+/// Context lines around the changed region in diffs built by diff_texts.
+const DIFF_CONTEXT: usize = 3;
+
+/// Lines of `text` split on '\n' only (a CR stays on its line, so the diff matches the file's
+/// bytes for git apply), and whether the text ends with a newline.
+fn diff_lines(text: &str) -> (Vec<&str>, bool) {
+    if text.is_empty() {
+        return (Vec::new(), true);
+    }
+    let newline = text.ends_with('\n');
+    (text.strip_suffix('\n').unwrap_or(text).split('\n').collect(), newline)
+}
+
+/// Unified diff turning `old` into `new` for the project file `rel_path`: one hunk from the
+/// first to the last changed line, with DIFF_CONTEXT lines of context. None when equal.
+pub fn diff_texts(rel_path: &str, old: &str, new: &str) -> Option<String> {
+    if old == new {
+        return None;
+    }
+    let (old_lines, old_newline) = diff_lines(old);
+    let (new_lines, new_newline) = diff_lines(new);
+    let (n, m) = (old_lines.len(), new_lines.len());
+
+    let mut prefix = old_lines.iter().zip(&new_lines).take_while(|(a, b)| a == b).count();
+    let mut suffix = old_lines[prefix..]
+        .iter()
+        .rev()
+        .zip(new_lines[prefix..].iter().rev())
+        .take_while(|(a, b)| a == b)
+        .count();
+    if old_newline != new_newline {
+        // The last line gains or loses its newline, so it is removed and re-added
+        suffix = 0;
+    }
+    if !old_newline || !new_newline {
+        // A last line without a newline cannot stay as context when lines follow it on the other side
+        prefix = prefix.min(n.saturating_sub(1)).min(m.saturating_sub(1));
+    }
+    let (old_end, new_end) = (n - suffix, m - suffix);
+    let start = prefix.saturating_sub(DIFF_CONTEXT);
+    let trailing = suffix.min(DIFF_CONTEXT);
+    let (old_count, new_count) = (old_end + trailing - start, new_end + trailing - start);
+
+    let range = |start: usize, count: usize| if count == 0 { format!("{},0", start) } else { format!("{},{}", start + 1, count) };
+    let no_newline = "\\ No newline at end of file\n";
+    let mut diff = format!(
+        "--- a/{}\n+++ b/{}\n@@ -{} +{} @@\n",
+        rel_path,
+        rel_path,
+        range(start, old_count),
+        range(start, new_count)
+    );
+    for line in &old_lines[start..prefix] {
+        diff.push_str(&format!(" {}\n", line));
+    }
+    for line in &old_lines[prefix..old_end] {
+        diff.push_str(&format!("-{}\n", line));
+    }
+    if old_end == n && old_end > prefix && !old_newline {
+        diff.push_str(no_newline);
+    }
+    for line in &new_lines[prefix..new_end] {
+        diff.push_str(&format!("+{}\n", line));
+    }
+    if new_end == m && new_end > prefix && !new_newline {
+        diff.push_str(no_newline);
+    }
+    for line in &old_lines[old_end..old_end + trailing] {
+        diff.push_str(&format!(" {}\n", line));
+    }
+    if trailing > 0 && old_end + trailing == n && !old_newline {
+        diff.push_str(no_newline);
+    }
+    Some(diff)
+}
+
+/// Replace a project file's text through the engine: diff `old` (the text the caller read)
+/// against `new`, check, and apply, so a file changed since it was read is not overwritten.
+/// Returns false when there was nothing to change.
+pub fn replace_file(engine: PatchEngine, project_path: &Path, rel_path: &str, old: &str, new: &str) -> Result<bool, String> {
+    let Some(diff) = diff_texts(rel_path, old, new) else {
+        return Ok(false);
+    };
+    check(engine, project_path, &diff)?;
+    apply(engine, project_path, &diff)?;
+    Ok(true)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_relative_path() {
+        let root = Path::new("/home/dev/app");
+        assert_eq!(relative_path(root, "./src/main.rs").as_deref(), Some("src/main.rs"));
+        assert_eq!(relative_path(root, "/home/dev/app/src/a.ts").as_deref(), Some("src/a.ts"));
+        assert_eq!(relative_path(root, "/etc/passwd"), None);
+        assert_eq!(relative_path(root, "../other/x.rs"), None);
+        assert_eq!(relative_path(root, ""), None);
+    }
+
+    #[test]
+    fn test_excerpt() {
+        assert_eq!(excerpt("a\nb", Some(2)), (1, 2, "a\nb".to_string()));
+        let long: String = (1..=1000).map(|n| format!("line {}\n", n)).collect();
+        let (start, end, text) = excerpt(&long, Some(500));
+        assert_eq!((start, end), (440, 560));
+        assert!(text.starts_with("line 440\n") && text.ends_with("line 560"));
+        assert_eq!(excerpt(&long, None).0, 1);
+    }
+
+    #[test]
+    fn test_normalize_diff() {
+        let ai = "Here is the fix:\n```diff\n--- src/lib.rs\n+++ src/lib.rs\n@@ -1,2 +1,2 @@\n-let x = 1;\n+let x = 2;\n y();\n```";
+        assert_eq!(
+            normalize_diff(ai, "src/lib.rs").unwrap(),
+            "--- a/src/lib.rs\n+++ b/src/lib.rs\n@@ -1,2 +1,2 @@\n-let x = 1;\n+let x = 2;\n y();\n"
+        );
+        assert_eq!(
+            normalize_diff("--- a/src/other.rs\n+++ b/src/other.rs\n@@ -1 +1 @@\n-a\n+b", "src/lib.rs").unwrap_err(),
+            "Diff touches src/other.rs, not src/lib.rs"
+        );
+        assert!(normalize_diff("--- a/src/lib.rs\n+++ b/src/lib.rs\n", "src/lib.rs").is_err());
+        assert!(normalize_diff("I could not fix this.", "src/lib.rs").is_err());
+    }
+
+    #[test]
+    fn test_check_and_apply() {
+        let dir = tempfile::tempdir().unwrap();
+        fs::create_dir_all(dir.path().join("src")).unwrap();
+        fs::write(dir.path().join("src/lib.rs"), "fn a() {}\nlet x = 1;\nfn b() {}\n").unwrap();
+
+        // Wrong hunk counts are tolerated by both engines (--recount)
+        let diff = normalize_diff("--- src/lib.rs\n+++ src/lib.rs\n@@ -1,9 +1,9 @@\n fn a() {}\n-let x = 1;\n+let x = 2;\n fn b() {}\n", "src/lib.rs").unwrap();
+        for engine in [PatchEngine::Git, PatchEngine::Builtin] {
+            fs::write(dir.path().join("src/lib.rs"), "fn a() {}\nlet x = 1;\nfn b() {}\n").unwrap();
+            check(engine, dir.path(), &diff).unwrap();
+            apply(engine, dir.path(), &diff).unwrap();
+            assert_eq!(fs::read_to_string(dir.path().join("src/lib.rs")).unwrap(), "fn a() {}\nlet x = 2;\nfn b() {}\n");
+
+            // Applying again no longer matches
+            assert!(check(engine, dir.path(), &diff).is_err());
+        }
+    }
+
+    #[test]
+    fn test_builtin_offset_and_fuzz() {
+        let original: String = (1..=20).map(|n| format!("line {}\n", n)).collect();
+
+        // Header says line 3, the hunk starts at line 9; trailing whitespace differs
+        let file = &parse("--- a/f.txt\n+++ b/f.txt\n@@ -3,3 +3,3 @@\n line 9  \n-line 10\n+line ten\n line 11\n").unwrap()[0];
+        let (text, offset, fuzz) = apply_to_text(&original, file).unwrap();
+        assert!(text.contains("line 9\nline ten\nline 11\n"));
+        assert_eq!((offset, fuzz), (6, 0));
+
+        // Stale outer context matches once it is fuzzed away
+        let file = &parse("--- a/f.txt\n+++ b/f.txt\n@@ -4,5 +4,5 @@\n changed 4\n line 5\n-line 6\n+line six\n line 7\n changed 8\n").unwrap()[0];
+        let (text, _, fuzz) = apply_to_text(&original, file).unwrap();
+        assert!(text.contains("line 5\nline six\nline 7\n"));
+        assert_eq!(fuzz, 1);
+
+        let file = &parse("--- a/f.txt\n+++ b/f.txt\n@@ -1 +1 @@\n-missing\n+x\n").unwrap()[0];
+        assert_eq!(apply_to_text(&original, file).unwrap_err(), "Hunk 1 of f.txt does not match (expected near line 1)");
+    }
+
+    #[test]
+    fn test_diff_texts_round_trip() {
+        let cases = [
+            ("a\nb\nc\n", "a\nB\nc\n"),
+            ("a\nb\nc\n", "header\na\nb\nc\n"),
+            ("1\n2\n3\n4\n5\n6\n7\n8\n9\n", "1\n2\n3\n4\nfive\n6\n7\n8\n9\n"),
+            ("a\nb", "a\nb\n"),
+            ("a\nb\n", "a\nb"),
+            ("a", "a\nb"),
+            ("a\nb", "a"),
+            ("a\nb\nc", "a\nx\nc"),
+            ("", "new\n"),
+            ("x\r\ny\r\n", "x\r\nz\r\n"),
+        ];
+        for (old, new) in cases {
+            let diff = diff_texts("f.txt", old, new).unwrap();
+            let file = &parse(&diff).unwrap()[0];
+            assert_eq!(apply_to_text(old, file).unwrap().0, new, "builtin: {:?} -> {:?}\n{}", old, new, diff);
+        }
+        assert_eq!(diff_texts("f.txt", "same\n", "same\n"), None);
+
+        // git apply accepts the same diffs, CR bytes included
+        let dir = tempfile::tempdir().unwrap();
+        for (old, new) in cases {
+            fs::write(dir.path().join("f.txt"), old).unwrap();
+            assert!(replace_file(PatchEngine::Git, dir.path(), "f.txt", old, new).unwrap());
+            assert_eq!(fs::read_to_string(dir.path().join("f.txt")).unwrap(), new, "git: {:?} -> {:?}", old, new);
+        }
+    }
+
+    #[test]
+    fn test_replace_file_detects_changes_since_read() {
+        let dir = tempfile::tempdir().unwrap();
+        fs::write(dir.path().join("CLAUDE.md"), "# App\nedited meanwhile\n").unwrap();
+        let err = replace_file(PatchEngine::Builtin, dir.path(), "CLAUDE.md", "# App\nold\n", "# App\nnew\n");
+        assert!(err.is_err());
+        assert_eq!(fs::read_to_string(dir.path().join("CLAUDE.md")).unwrap(), "# App\nedited meanwhile\n");
+        assert!(!replace_file(PatchEngine::Builtin, dir.path(), "CLAUDE.md", "x\n", "x\n").unwrap());
+    }
+
+    #[test]
+    fn test_builtin_newlines_and_new_files() {
+        let file = &parse("--- a/f.txt\n+++ b/f.txt\n@@ -1,2 +1,2 @@\n a\n-b\n+c\n\\ No newline at end of file\n").unwrap()[0];
+        assert_eq!(apply_to_text("a\r\nb\r\n", file).unwrap().0, "a\r\nc");
+
+        let dir = tempfile::tempdir().unwrap();
+        fs::write(dir.path().join("keep.txt"), "one\n").unwrap();
+        let diff = "--- /dev/null\n+++ b/docs/new.md\n@@ -0,0 +1,2 @@\n+# New\n+text\n\
+                    --- a/keep.txt\n+++ b/keep.txt\n@@ -1 +1 @@\n-absent\n+two\n";
+        // One file fails, so nothing is written
+        assert!(apply(PatchEngine::Builtin, dir.path(), diff).is_err());
+        assert!(!dir.path().join("docs/new.md").exists());
+
+        let diff = diff.replace("-absent", "-one");
+        let report = dry_run(PatchEngine::Builtin, dir.path(), &diff).unwrap();
+        assert_eq!(report.files.iter().map(|f| f.path.as_str()).collect::<Vec<_>>(), vec!["docs/new.md", "keep.txt"]);
+        apply(PatchEngine::Builtin, dir.path(), &diff).unwrap();
+        assert_eq!(fs::read_to_string(dir.path().join("docs/new.md")).unwrap(), "# New\ntext\n");
+        assert_eq!(fs::read_to_string(dir.path().join("keep.txt")).unwrap(), "two\n");
+
+        assert!(dry_run(PatchEngine::Builtin, dir.path(), "--- a/../x\n+++ b/../x\n@@ -1 +1 @@\n-a\n+b\n").is_err());
+    }
+}
@@ -12,6 +12,7 @@
 //! - std::path::Path - File system traversal
 //! - std::fs - File reading
 //! - uuid - Unique IDs for reviews and issues
+//! - core::safe_read - Lossy, size-capped source reads
 //!
 //! EXPORTS:
 //! - analyze_project - Run full performance analysis on a project path
@@ -28,6 +29,7 @@
 //! - All scores floor at 0
 //! - This is heuristic analysis, not AST-based; uses string/regex matching
 
+use crate::core::safe_read;
 use crate::models::performance::{
     ArchitectureFinding, PerformanceComponents, PerformanceIssue, PerformanceReview,
 };
@@ -149,7 +151,7 @@ fn scan_source_files(dir: &Path, issues: &mut Vec<PerformanceIssue>) {
         if path.is_dir() {
             scan_source_files(&path, issues);
         } else if is_scannable_file(&name) {
-            if let Ok(content) = std::fs::read_to_string(&path) {
+            if let Ok(content) = safe_read::read_text(&path) {
                 let rel_path = path.to_string_lossy().to_string();
                 scan_file_content(&content, &rel_path, &name, issues);
             }
@@ -0,0 +1,333 @@
+//! @module core/plan_prd
+//! @description Plan-mode output from Claude Code transcripts converted into PRD drafts
+//!
+//! PURPOSE:
+//! - Find the plans a session proposed in plan mode (ExitPlanMode tool calls)
+//! - Turn a markdown plan's steps into PRD stories for RALPH PRD mode
+//!
+//! DEPENDENCIES:
+//! - models::ralph - PrdFile, PrdStory types
+//! - serde_json - JSONL transcript parsing
+//! - regex - Numbered steps and test command detection
+//! - core::package_manager - Package manager for default validation commands
+//! - core::safe_read - package.json scripts lookup
+//!
+//! EXPORTS:
+//! - find_plans - Plan markdown from every ExitPlanMode call in a transcript, oldest first
+//! - plan_to_prd - Build a PrdFile draft from plan markdown
+//! - apply_validation_defaults - Test/typecheck commands from the project when the plan names none
+//!
+//! PATTERNS:
+//! - Stories come from the first structure found: top-level numbered steps, then "##"/"###"
+//!   headings, then top-level bullets
+//! - Lines under a step become its description; lines mentioning tests or verification also
+//!   become its acceptance criteria
+//!
+//! CLAUDE NOTES:
+//! - Plan mode ends with an assistant tool_use {"name": "ExitPlanMode", "input": {"plan": "..."}};
+//!   transcript_cache drops tool input, so find_plans reads the raw JSONL
+//! - The draft branch is "plan/<slug of the plan title>" so PRD mode works on its own branch
+//! - The test command is the first backticked pnpm/npm/yarn/cargo/pytest/go test command in the plan;
+//!   a command the plan names is kept even when the project uses another package manager
+
+use std::path::Path;
+
+use regex::Regex;
+
+use crate::core::package_manager::{self, PackageManager};
+use crate::core::safe_read;
+use crate::models::ralph::{PrdFile, PrdStory};
+
+/// Plan markdown from every ExitPlanMode call in a JSONL transcript, oldest first.
+pub fn find_plans(transcript: &str) -> Vec<String> {
+    let mut plans = Vec::new();
+    for line in transcript.lines() {
+        if !line.contains("ExitPlanMode") {
+            continue;
+        }
+        let Ok(json) = serde_json::from_str::<serde_json::Value>(line) else {
+            continue;
+        };
+        let Some(blocks) = json.pointer("/message/content").and_then(|c| c.as_array()) else {
+            continue;
+        };
+        for block in blocks {
+            if block.get("type").and_then(|t| t.as_str()) == Some("tool_use")
+                && block.get("name").and_then(|n| n.as_str()) == Some("ExitPlanMode")
+            {
+                if let Some(plan) = block.pointer("/input/plan").and_then(|p| p.as_str()) {
+                    if !plan.trim().is_empty() {
+                        plans.push(plan.to_string());
+                    }
+                }
+            }
+        }
+    }
+    plans
+}
+
+struct Step {
+    title: String,
+    body: Vec<String>,
+}
+
+/// Build a PRD draft from plan markdown. Err when the plan has no steps.
+pub fn plan_to_prd(plan: &str) -> Result<PrdFile, String> {
+    let title = plan
+        .lines()
+        .map(str::trim)
+        .find(|l| l.starts_with("# "))
+        .map(|l| clean(l.trim_start_matches('#')))
+        .filter(|t| !t.is_empty())
+        .unwrap_or_else(|| "Implementation plan".to_string());
+    let title = title
+        .strip_prefix("Plan:")
+        .map(|t| t.trim().to_string())
+        .unwrap_or(title);
+
+    let numbered = Regex::new(r"^(\d+)[.)]\s+(.+)$").unwrap();
+    let heading = Regex::new(r"^#{2,3}\s+(.+)$").unwrap();
+    let bullet = Regex::new(r"^[-*]\s+(.+)$").unwrap();
+
+    let mut steps = collect_steps(plan, &numbered, 2);
+    if steps.is_empty() {
+        steps = collect_steps(plan, &heading, 1);
+    }
+    if steps.is_empty() {
+        steps = collect_steps(plan, &bullet, 1);
+    }
+    if steps.is_empty() {
+        return Err("The plan has no numbered steps, sections, or bullet points to turn into stories.".to_string());
+    }
+
+    let stories = steps
+        .into_iter()
+        .enumerate()
+        .map(|(i, step)| {
+            let (title, lead) = split_title(&step.title);
+            let mut description: Vec<String> = lead.into_iter().collect();
+            description.extend(step.body.iter().cloned());
+            let criteria: Vec<String> = step
+                .body
+                .iter()
+                .filter(|l| {
+                    let lower = l.to_lowercase();
+                    lower.contains("test") || lower.contains("verify")
+                })
+                .map(|l| l.trim_start_matches(['-', '*', ' ']).to_string())
+                .collect();
+            PrdStory {
+                id: format!("story-{}", i + 1),
+                title: title.clone(),
+                description: if description.is_empty() { title } else { description.join("\n") },
+                acceptance_criteria: if criteria.is_empty() { None } else { Some(criteria.join("; ")) },
+                priority: (i + 1) as u32,
+                completed: false,
+                commit_hash: None,
+                depends_on: Vec::new(),
+                project: None,
+            }
+        })
+        .collect();
+
+    Ok(PrdFile {
+        branch: format!("plan/{}", slugify(&title)),
+        description: intro(plan),
+        name: title,
+        test_command: test_command(plan),
+        typecheck_command: None,
+        max_iterations_per_story: 3,
+        stories,
+        cross_repo_context: None,
+        max_duration_minutes: None,
+    })
+}
+
+/// Steps whose title lines match `marker` (capture group `group`) at the top level.
+/// Everything until the next step or a top-level heading is the step's body.
+fn collect_steps(plan: &str, marker: &Regex, group: usize) -> Vec<Step> {
+    let mut steps: Vec<Step> = Vec::new();
+    let mut in_code = false;
+    for line in plan.lines() {
+        if line.trim_start().starts_with("```") {
+            in_code = !in_code;
+        }
+        let top_level = !line.starts_with([' ', '\t']);
+        if !in_code && top_level {
+            if let Some(caps) = marker.captures(line.trim_end()) {
+                steps.push(Step {
+                    title: clean(&caps[group]),
+                    body: Vec::new(),
+                });
+                continue;
+            }
+            if line.starts_with('#') {
+                // A heading that is not a step closes the current step
+                if steps.last().is_some_and(|s| !s.title.is_empty()) {
+                    steps.push(Step { title: String::new(), body: Vec::new() });
+                }
+                continue;
+            }
+        }
+        if let Some(step) = steps.last_mut() {
+            if !step.title.is_empty() && !line.trim().is_empty() {
+                step.body.push(line.trim().to_string());
+            }
+        }
+    }
+    steps.retain(|s| !s.title.is_empty());
+    steps
+}
+
+/// Text before the first step or section, used as the PRD description.
+fn intro(plan: &str) -> Option<String> {
+    let lines: Vec<&str> = plan
+        .lines()
+        .map(str::trim)
+        .skip_while(|l| l.is_empty() || l.starts_with("# "))
+        .take_while(|l| !l.starts_with('#') && !l.starts_with(|c: char| c.is_ascii_digit()) && !l.starts_with(['-', '*']))
+        .filter(|l| !l.is_empty())
+        .collect();
+    if lines.is_empty() {
+        None
+    } else {
+        Some(lines.join(" "))
+    }
+}
+
+/// "Title - details" / "Title: details" split into a short title and a description lead.
+fn split_title(text: &str) -> (String, Option<String>) {
+    for sep in [" - ", " — ", ": "] {
+        if let Some((head, tail)) = text.split_once(sep) {
+            if !head.trim().is_empty() && head.len() <= 80 && !tail.trim().is_empty() {
+                return (head.trim().to_string(), Some(tail.trim().to_string()));
+            }
+        }
+    }
+    (text.to_string(), None)
+}
+
+fn clean(text: &str) -> String {
+    text.replace("**", "").trim().to_string()
+}
+
+fn slugify(text: &str) -> String {
+    let slug: String = text
+        .to_lowercase()
+        .chars()
+        .map(|c| if c.is_ascii_alphanumeric() { c } else { '-' })
+        .collect();
+    let slug = slug.split('-').filter(|p| !p.is_empty()).collect::<Vec<_>>().join("-");
+    let slug: String = slug.chars().take(40).collect();
+    if slug.is_empty() {
+        "draft".to_string()
+    } else {
+        slug.trim_end_matches('-').to_string()
+    }
+}
+
+/// Fill the test and typecheck commands a plan did not name from the project's files,
+/// using its package manager ("npm test", "uv run pytest", "pnpm tsc --noEmit").
+pub fn apply_validation_defaults(prd: &mut PrdFile, project_path: &str) {
+    let root = Path::new(project_path);
+    let detected = package_manager::detect(root).map(|(pm, _)| pm);
+    let js_pm = detected.filter(PackageManager::is_javascript).unwrap_or(PackageManager::Pnpm);
+
+    if prd.test_command.is_none() {
+        let has_test_script = safe_read::read_text(root.join("package.json"))
+            .ok()
+            .and_then(|c| serde_json::from_str::<serde_json::Value>(&c).ok())
+            .is_some_and(|pkg| pkg.pointer("/scripts/test").is_some());
+        prd.test_command = if has_test_script {
+            Some(js_pm.run_script("test"))
+        } else if root.join("Cargo.toml").exists() {
+            Some("cargo test".to_string())
+        } else if root.join("go.mod").exists() {
+            Some("go test ./...".to_string())
+        } else if root.join("pyproject.toml").exists() || root.join("pytest.ini").exists() {
+            let pm = detected.filter(|pm| !pm.is_javascript()).unwrap_or(PackageManager::Pip);
+            Some(pm.exec("pytest"))
+        } else {
+            None
+        };
+    }
+    if prd.typecheck_command.is_none() && root.join("tsconfig.json").exists() {
+        prd.typecheck_command = Some(js_pm.exec("tsc --noEmit"));
+    }
+}
+
+fn test_command(plan: &str) -> Option<String> {
+    let re = Regex::new(r"`((?:pnpm|npm|yarn|bun) (?:run )?test[^`]*|cargo test[^`]*|pytest[^`]*|go test[^`]*)`").unwrap();
+    re.captures(plan).map(|c| c[1].trim().to_string())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const PLAN: &str = "# Plan: Add CSV export\n\nUsers need to export reports.\n\n## Steps\n\n1. **Add export service** - builds CSV rows\n   - Handle empty reports\n   - Unit test the quoting rules\n2. Wire the Export button\n   ```ts\n   1. not a step\n   ```\n3. Document the feature\n\n## Verification\nRun `pnpm test` and check the download.\n";
+
+    #[test]
+    fn test_find_plans() {
+        let transcript = format!(
+            "{}\n{}\n{}\n",
+            r#"{"type":"user","message":{"role":"user","content":"plan CSV export"}}"#,
+            serde_json::json!({"type": "assistant", "message": {"role": "assistant", "content": [
+                {"type": "text", "text": "Here is the plan"},
+                {"type": "tool_use", "name": "ExitPlanMode", "input": {"plan": PLAN}}
+            ]}}),
+            r#"{"type":"assistant","message":{"role":"assistant","content":[{"type":"tool_use","name":"ExitPlanMode","input":{"plan":"  "}}]}}"#
+        );
+        assert_eq!(find_plans(&transcript), vec![PLAN.to_string()]);
+        assert!(find_plans("not json ExitPlanMode").is_empty());
+    }
+
+    #[test]
+    fn test_plan_to_prd_numbered_steps() {
+        let prd = plan_to_prd(PLAN).unwrap();
+        assert_eq!(prd.name, "Add CSV export");
+        assert_eq!(prd.branch, "plan/add-csv-export");
+        assert_eq!(prd.description.as_deref(), Some("Users need to export reports."));
+        assert_eq!(prd.test_command.as_deref(), Some("pnpm test"));
+
+        let titles: Vec<&str> = prd.stories.iter().map(|s| s.title.as_str()).collect();
+        assert_eq!(titles, vec!["Add export service", "Wire the Export button", "Document the feature"]);
+        let first = &prd.stories[0];
+        assert_eq!(first.id, "story-1");
+        assert!(first.description.starts_with("builds CSV rows\n- Handle empty reports"));
+        assert_eq!(first.acceptance_criteria.as_deref(), Some("Unit test the quoting rules"));
+        // The verification section is not part of the last step
+        assert!(!prd.stories[2].description.contains("pnpm test"));
+        assert_eq!(prd.stories[2].priority, 3);
+    }
+
+    #[test]
+    fn test_plan_to_prd_fallbacks() {
+        let sections = plan_to_prd("## Schema\nAdd the table\n## API\nAdd the endpoint\n").unwrap();
+        assert_eq!(sections.name, "Implementation plan");
+        assert_eq!(sections.stories.len(), 2);
+        assert_eq!(sections.stories[1].description, "Add the endpoint");
+
+        let bullets = plan_to_prd("- Rename the hook\n- Update callers\n").unwrap();
+        assert_eq!(bullets.stories.len(), 2);
+        assert!(plan_to_prd("Just do it.").is_err());
+    }
+
+    #[test]
+    fn test_apply_validation_defaults() {
+        let dir = tempfile::tempdir().unwrap();
+        let root = dir.path();
+        std::fs::write(root.join("package.json"), r#"{"scripts": {"test": "vitest run"}}"#).unwrap();
+        std::fs::write(root.join("package-lock.json"), "{}").unwrap();
+        std::fs::write(root.join("tsconfig.json"), "{}").unwrap();
+
+        let mut prd = plan_to_prd("- Rename the hook\n").unwrap();
+        apply_validation_defaults(&mut prd, root.to_str().unwrap());
+        assert_eq!(prd.test_command.as_deref(), Some("npm test"));
+        assert_eq!(prd.typecheck_command.as_deref(), Some("npx tsc --noEmit"));
+
+        let mut planned = plan_to_prd(PLAN).unwrap();
+        apply_validation_defaults(&mut planned, root.to_str().unwrap());
+        assert_eq!(planned.test_command.as_deref(), Some("pnpm test"));
+    }
+}
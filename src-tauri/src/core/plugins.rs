@@ -0,0 +1,418 @@
+//! @module core/plugins
+//! @description Custom analyzer plugins run as subprocesses for in-house languages and DSLs
+//!
+//! PURPOSE:
+//! - Discover plugin manifests in ~/.project-jumpstart/plugins/<name>/plugin.json
+//! - Validate manifests (extensions, comment style, capabilities, timeout)
+//! - Run a plugin on one file and cache its exports, imports, lint findings, and freshness signals
+//!
+//! DEPENDENCIES:
+//! - core::proc - Subprocess runner with stdin, timeout, and output cap
+//! - core::analyzer - Built-in extensions (never claimable) and doc header parsing
+//! - models::plugin - AnalyzerPlugin, PluginAnalysis, PluginScanReport
+//! - sha2 - Content hash for the analysis cache
+//!
+//! EXPORTS:
+//! - PROTOCOL_VERSION - Version sent in every request
+//! - MANIFEST_FILE - Manifest file name inside a plugin directory
+//! - plugins_dir - Default discovery directory
+//! - discover - Load and validate every manifest under a directory
+//! - validate_manifest - Reject manifests the analyzer could not honor
+//! - load - Discover from plugins_dir and install the result (startup and reload)
+//! - install - Replace the installed plugin set
+//! - report - The last discovery report
+//! - for_extension / for_file - Installed plugin handling an extension or filename
+//! - analyze - Run a plugin on file content (cached by content hash)
+//! - analysis_for - analyze via the plugin for an extension, logging failures
+//! - parse_response - Decode a plugin's stdout, dropping undeclared capabilities
+//!
+//! PATTERNS:
+//! - Protocol v1: the command (relative paths resolve against the plugin dir, which is also its
+//!   working directory) receives one JSON request on stdin:
+//!   {"protocol": 1, "extension": "acme", "capabilities": [...], "content": "...", "doc": ModuleDoc|null}
+//!   and prints one JSON response on stdout:
+//!   {"exports": [], "imports": [], "lint": [{"message", "line"}], "freshness": [{"message", "weight"}]}
+//!   Every response field is optional; a non-zero exit or invalid JSON is a failure
+//! - The installed set lives in a process-wide RwLock (like ai::token_limits); load() runs at startup
+//!   and from reload_analyzer_plugins
+//!
+//! CLAUDE NOTES:
+//! - Built-in extensions (analyzer::DOC_EXTENSIONS) cannot be claimed, and the first plugin (by
+//!   directory name) to claim an extension wins; later claims are reported as errors
+//! - Failed runs are cached as empty results, so a broken plugin costs one timeout per file
+//!   version instead of one per detect_exports/detect_imports/freshness call
+//! - commentPrefix is limited to "#", "//" and "--" because parse_doc_header only strips those
+//! - Freshness weights are clamped to MAX_SIGNAL_WEIGHT so one plugin signal cannot zero a score
+
+use std::collections::HashMap;
+use std::path::{Path, PathBuf};
+use std::process::Command;
+use std::sync::{Arc, Mutex, OnceLock, RwLock};
+use std::time::Duration;
+
+use serde_json::json;
+use sha2::{Digest, Sha256};
+
+use crate::core::analyzer;
+use crate::core::proc::{self, ProcLimits};
+use crate::models::plugin::{AnalyzerPlugin, PluginAnalysis, PluginCapability, PluginScanReport};
+
+/// Version of the request/response protocol sent to plugins.
+pub const PROTOCOL_VERSION: u32 = 1;
+
+/// Manifest file name inside each plugin directory.
+pub const MANIFEST_FILE: &str = "plugin.json";
+
+/// Line-comment markers parse_doc_header understands.
+const COMMENT_PREFIXES: &[&str] = &["#", "//", "--"];
+
+/// Longest timeout a manifest may ask for.
+const MAX_TIMEOUT_MS: u64 = 30_000;
+
+/// Largest plugin response kept.
+const MAX_OUTPUT_BYTES: usize = 1024 * 1024;
+
+/// Largest freshness penalty a single plugin signal may apply.
+const MAX_SIGNAL_WEIGHT: u32 = 50;
+
+/// Cached analyses kept before the cache is cleared.
+const CACHE_CAPACITY: usize = 512;
+
+/// Default discovery directory: ~/.project-jumpstart/plugins.
+pub fn plugins_dir() -> Option<PathBuf> {
+    dirs::home_dir().map(|home| home.join(".project-jumpstart").join("plugins"))
+}
+
+/// Load every `<dir>/<name>/plugin.json`, in directory-name order. Invalid manifests and
+/// extensions already claimed by an earlier plugin are reported in `errors`.
+pub fn discover(dir: &Path) -> PluginScanReport {
+    let mut report = PluginScanReport {
+        plugins_dir: dir.to_string_lossy().to_string(),
+        ..Default::default()
+    };
+    let Ok(entries) = std::fs::read_dir(dir) else {
+        return report;
+    };
+    let mut plugin_dirs: Vec<PathBuf> = entries
+        .flatten()
+        .map(|e| e.path())
+        .filter(|p| p.join(MANIFEST_FILE).is_file())
+        .collect();
+    plugin_dirs.sort();
+
+    for plugin_dir in plugin_dirs {
+        let label = plugin_dir.file_name().unwrap_or_default().to_string_lossy().to_string();
+        let loaded = load_manifest(&plugin_dir).and_then(|plugin| {
+            let taken = plugin.extensions.iter().find_map(|ext| {
+                report
+                    .plugins
+                    .iter()
+                    .find(|p| p.extensions.contains(ext))
+                    .map(|p| format!("{} is already handled by plugin {}", ext, p.name))
+            });
+            match taken {
+                Some(conflict) => Err(conflict),
+                None => Ok(plugin),
+            }
+        });
+        match loaded {
+            Ok(plugin) => report.plugins.push(plugin),
+            Err(e) => report.errors.push(format!("{}: {}", label, e)),
+        }
+    }
+    report
+}
+
+fn load_manifest(plugin_dir: &Path) -> Result<AnalyzerPlugin, String> {
+    let content = std::fs::read_to_string(plugin_dir.join(MANIFEST_FILE))
+        .map_err(|e| format!("Failed to read {}: {}", MANIFEST_FILE, e))?;
+    let mut plugin: AnalyzerPlugin =
+        serde_json::from_str(&content).map_err(|e| format!("Invalid {}: {}", MANIFEST_FILE, e))?;
+    plugin.extensions = plugin
+        .extensions
+        .iter()
+        .map(|ext| format!(".{}", ext.trim().trim_start_matches('.')))
+        .collect();
+    validate_manifest(&plugin)?;
+    plugin.dir = plugin_dir.to_string_lossy().to_string();
+    Ok(plugin)
+}
+
+/// Reject manifests the analyzer could not honor. Extensions are expected with a leading dot.
+pub fn validate_manifest(plugin: &AnalyzerPlugin) -> Result<(), String> {
+    if plugin.name.trim().is_empty() {
+        return Err("name is required".to_string());
+    }
+    if plugin.command.trim().is_empty() {
+        return Err("command is required".to_string());
+    }
+    if plugin.extensions.is_empty() {
+        return Err("extensions must list at least one extension".to_string());
+    }
+    for ext in &plugin.extensions {
+        let bare = ext.strip_prefix('.').unwrap_or(ext);
+        if bare.is_empty() || !bare.chars().all(|c| c.is_ascii_alphanumeric() || c == '_' || c == '-') {
+            return Err(format!("{} is not a single file extension", ext));
+        }
+        if analyzer::DOC_EXTENSIONS.contains(&ext.as_str()) {
+            return Err(format!("{} is handled by the built-in analyzer", ext));
+        }
+    }
+    if !COMMENT_PREFIXES.contains(&plugin.comment_prefix.as_str()) {
+        return Err(format!(
+            "commentPrefix must be one of {} (got {:?})",
+            COMMENT_PREFIXES.join(", "),
+            plugin.comment_prefix
+        ));
+    }
+    if plugin.capabilities.is_empty() {
+        return Err("capabilities must list at least one capability".to_string());
+    }
+    if plugin.timeout_ms == 0 || plugin.timeout_ms > MAX_TIMEOUT_MS {
+        return Err(format!("timeoutMs must be between 1 and {}", MAX_TIMEOUT_MS));
+    }
+    Ok(())
+}
+
+fn registry() -> &'static RwLock<Arc<PluginScanReport>> {
+    static REGISTRY: OnceLock<RwLock<Arc<PluginScanReport>>> = OnceLock::new();
+    REGISTRY.get_or_init(|| RwLock::new(Arc::new(PluginScanReport::default())))
+}
+
+fn cache() -> &'static Mutex<HashMap<(String, String), PluginAnalysis>> {
+    static CACHE: OnceLock<Mutex<HashMap<(String, String), PluginAnalysis>>> = OnceLock::new();
+    CACHE.get_or_init(|| Mutex::new(HashMap::new()))
+}
+
+/// Discover plugins in plugins_dir() and install them. Called at startup and on reload.
+pub fn load() -> PluginScanReport {
+    let report = plugins_dir().map(|dir| discover(&dir)).unwrap_or_default();
+    for error in &report.errors {
+        tracing::warn!(error = %error, "Analyzer plugin rejected");
+    }
+    install(report.clone());
+    report
+}
+
+/// Replace the installed plugins and drop cached analyses.
+pub fn install(report: PluginScanReport) {
+    if let Ok(mut current) = registry().write() {
+        *current = Arc::new(report);
+    }
+    if let Ok(mut cached) = cache().lock() {
+        cached.clear();
+    }
+}
+
+/// The last installed discovery report.
+pub fn report() -> Arc<PluginScanReport> {
+    registry()
+        .read()
+        .map(|r| Arc::clone(&r))
+        .unwrap_or_default()
+}
+
+/// Installed plugin handling `ext` (without the dot, as from Path::extension).
+pub fn for_extension(ext: &str) -> Option<AnalyzerPlugin> {
+    if ext.is_empty() {
+        return None;
+    }
+    report()
+        .plugins
+        .iter()
+        .find(|p| p.extensions.iter().any(|e| &e[1..] == ext))
+        .cloned()
+}
+
+/// Installed plugin handling a filename.
+pub fn for_file(name: &str) -> Option<AnalyzerPlugin> {
+    let ext = Path::new(name).extension()?.to_str()?;
+    for_extension(ext)
+}
+
+/// Run `plugin` on a file's content. Results (including failures, as empty analyses)
+/// are cached by plugin name and content hash.
+pub fn analyze(plugin: &AnalyzerPlugin, ext: &str, content: &str) -> Result<PluginAnalysis, String> {
+    let key = (plugin.name.clone(), format!("{:x}", Sha256::digest(content.as_bytes())));
+    if let Some(hit) = cache().lock().ok().and_then(|c| c.get(&key).cloned()) {
+        return Ok(hit);
+    }
+
+    let result = run_plugin(plugin, ext, content);
+    if let Ok(mut cached) = cache().lock() {
+        if cached.len() >= CACHE_CAPACITY {
+            cached.clear();
+        }
+        cached.insert(key, result.clone().unwrap_or_default());
+    }
+    result
+}
+
+/// analyze with the installed plugin for `ext`, if any. Failures are logged and yield None.
+pub fn analysis_for(ext: &str, content: &str) -> Option<(AnalyzerPlugin, PluginAnalysis)> {
+    let plugin = for_extension(ext)?;
+    match analyze(&plugin, ext, content) {
+        Ok(analysis) => Some((plugin, analysis)),
+        Err(e) => {
+            tracing::warn!(plugin = %plugin.name, error = %e, "Analyzer plugin failed");
+            None
+        }
+    }
+}
+
+fn run_plugin(plugin: &AnalyzerPlugin, ext: &str, content: &str) -> Result<PluginAnalysis, String> {
+    let request = json!({
+        "protocol": PROTOCOL_VERSION,
+        "extension": ext,
+        "capabilities": plugin.capabilities,
+        "content": content,
+        "doc": analyzer::parse_doc_header(content),
+    });
+    let dir = Path::new(&plugin.dir);
+    let program = if plugin.command.contains('/') || plugin.command.contains('\\') {
+        dir.join(&plugin.command)
+    } else {
+        PathBuf::from(&plugin.command)
+    };
+    let output = proc::run_with_input(
+        Command::new(program).args(&plugin.args).current_dir(dir),
+        request.to_string().into_bytes(),
+        ProcLimits::new(Duration::from_millis(plugin.timeout_ms), MAX_OUTPUT_BYTES),
+    )
+    .map_err(|e| format!("Failed to run plugin {}: {}", plugin.name, e))?;
+
+    if !output.success() {
+        let stderr = String::from_utf8_lossy(&output.stderr);
+        let reason = stderr.lines().rev().find(|l| !l.trim().is_empty()).unwrap_or("no output");
+        return Err(format!("Plugin {} failed: {}", plugin.name, reason.trim()));
+    }
+    parse_response(plugin, &String::from_utf8_lossy(&output.stdout))
+}
+
+/// Decode a plugin response. Fields for capabilities the manifest does not declare are
+/// dropped, and freshness weights are clamped to MAX_SIGNAL_WEIGHT.
+pub fn parse_response(plugin: &AnalyzerPlugin, stdout: &str) -> Result<PluginAnalysis, String> {
+    let mut analysis: PluginAnalysis = serde_json::from_str(stdout.trim())
+        .map_err(|e| format!("Plugin {} returned invalid JSON: {}", plugin.name, e))?;
+    if !plugin.has(PluginCapability::Exports) {
+        analysis.exports.clear();
+    }
+    if !plugin.has(PluginCapability::Imports) {
+        analysis.imports.clear();
+    }
+    if !plugin.has(PluginCapability::Lint) {
+        analysis.lint.clear();
+    }
+    if !plugin.has(PluginCapability::Freshness) {
+        analysis.freshness.clear();
+    }
+    for signal in &mut analysis.freshness {
+        signal.weight = signal.weight.min(MAX_SIGNAL_WEIGHT);
+    }
+    Ok(analysis)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn write_manifest(root: &Path, dir: &str, manifest: &str) {
+        std::fs::create_dir_all(root.join(dir)).unwrap();
+        std::fs::write(root.join(dir).join(MANIFEST_FILE), manifest).unwrap();
+    }
+
+    #[test]
+    fn test_discover_validates_and_resolves_conflicts() {
+        let root = tempfile::tempdir().unwrap();
+        write_manifest(root.path(), "a-acme", r#"{"name": "acme", "extensions": ["acme"], "command": "./analyze"}"#);
+        write_manifest(root.path(), "b-dup", r#"{"name": "dup", "extensions": [".acme"], "command": "dup"}"#);
+        write_manifest(root.path(), "c-ts", r#"{"name": "ts", "extensions": [".ts"], "command": "ts"}"#);
+        write_manifest(
+            root.path(),
+            "d-comment",
+            r#"{"name": "lisp", "extensions": [".el"], "command": "el", "commentPrefix": ";;"}"#,
+        );
+        write_manifest(root.path(), "e-broken", "{");
+        std::fs::create_dir_all(root.path().join("f-no-manifest")).unwrap();
+
+        let report = discover(root.path());
+        assert_eq!(report.plugins.len(), 1);
+        let acme = &report.plugins[0];
+        assert_eq!(acme.extensions, vec![".acme"]);
+        assert_eq!(acme.capabilities, vec![PluginCapability::Exports, PluginCapability::Imports]);
+        assert_eq!(acme.comment_prefix, "#");
+        assert!(acme.dir.ends_with("a-acme"));
+
+        assert_eq!(report.errors.len(), 4);
+        assert_eq!(report.errors[0], "b-dup: .acme is already handled by plugin acme");
+        assert_eq!(report.errors[1], "c-ts: .ts is handled by the built-in analyzer");
+        assert!(report.errors[2].starts_with("d-comment: commentPrefix must be one of"));
+        assert!(report.errors[3].starts_with("e-broken: Invalid plugin.json"));
+    }
+
+    #[test]
+    fn test_parse_response_drops_undeclared_capabilities() {
+        let plugin: AnalyzerPlugin = serde_json::from_str(
+            r#"{"name": "acme", "extensions": [".acme"], "command": "x", "capabilities": ["exports", "freshness"]}"#,
+        )
+        .unwrap();
+        let analysis = parse_response(
+            &plugin,
+            r#"{"exports": ["rule Total"], "imports": ["std"], "lint": [{"message": "no owner"}],
+                "freshness": [{"message": "schema bumped", "weight": 90}]}"#,
+        )
+        .unwrap();
+        assert_eq!(analysis.exports, vec!["rule Total"]);
+        assert!(analysis.imports.is_empty());
+        assert!(analysis.lint.is_empty());
+        assert_eq!(analysis.freshness[0].weight, MAX_SIGNAL_WEIGHT);
+        assert!(parse_response(&plugin, "exports: none").is_err());
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn test_installed_plugin_drives_analyzer() {
+        use std::os::unix::fs::PermissionsExt;
+
+        let root = tempfile::tempdir().unwrap();
+        write_manifest(
+            root.path(),
+            "acme",
+            r##"{"name": "acme", "extensions": [".acme"], "command": "./analyze",
+                 "capabilities": ["exports", "imports"], "commentPrefix": "--"}"##,
+        );
+        // Exports every "rule <name>" line, imports every "use <name>" line
+        let script = root.path().join("acme").join("analyze");
+        std::fs::write(
+            &script,
+            "#!/bin/sh\nin=$(cat)\n\
+             exports=$(printf '%s' \"$in\" | grep -o 'rule [A-Za-z]*' | sed 's/rule //' | sed 's/.*/\"&\"/' | paste -sd, -)\n\
+             imports=$(printf '%s' \"$in\" | grep -o 'use [a-z]*' | sed 's/use //' | sed 's/.*/\"&\"/' | paste -sd, -)\n\
+             printf '{\"exports\": [%s], \"imports\": [%s]}' \"$exports\" \"$imports\"\n",
+        )
+        .unwrap();
+        std::fs::set_permissions(&script, std::fs::Permissions::from_mode(0o755)).unwrap();
+
+        install(discover(root.path()));
+        assert!(analyzer::has_doc_extension("billing.acme"));
+        let content = "use ledger\nrule Total\nrule Tax\n";
+        assert_eq!(analyzer::detect_exports(content, "acme"), vec!["Total", "Tax"]);
+        assert_eq!(analyzer::detect_imports(content, "acme"), vec!["ledger"]);
+
+        let project = root.path().join("project");
+        std::fs::create_dir_all(&project).unwrap();
+        let file = project.join("billing.acme");
+        std::fs::write(&file, content).unwrap();
+        let doc = analyzer::generate_module_doc_for_file(&file.to_string_lossy(), &project.to_string_lossy()).unwrap();
+        let rendered = analyzer::render_doc_into(content, &file.to_string_lossy(), &doc).unwrap();
+        assert!(rendered.starts_with("-- @module billing\n"));
+        let reparsed = analyzer::parse_doc_header(&rendered).unwrap();
+        assert_eq!(reparsed.exports.len(), 2);
+        // Re-rendering replaces the header instead of stacking a second one
+        let again = analyzer::render_doc_into(&rendered, &file.to_string_lossy(), &doc).unwrap();
+        assert_eq!(again.matches("@module").count(), 1);
+
+        install(PluginScanReport::default());
+        assert!(!analyzer::has_doc_extension("billing.acme"));
+    }
+}
@@ -0,0 +1,242 @@
+//! @module core/policy
+//! @description Parse organization policy packs and compare a project's enforcement against one
+//!
+//! PURPOSE:
+//! - Parse and validate policy pack JSON files
+//! - Detect whether a project's CI runs the Project Jumpstart doc check
+//! - Turn a pack plus a project's current enforcement facts into PolicyChecks
+//!
+//! DEPENDENCIES:
+//! - serde_json - Pack file parsing
+//! - models::policy - PolicyPack, PolicyCheck
+//!
+//! EXPORTS:
+//! - HOOK_MODES - Hook modes a pack may require
+//! - parse_pack - Parse and validate a pack file's content
+//! - PolicyFacts - A project's current hook mode, CI check, coverage, and excluded globs
+//! - ci_doc_check_present - Whether any supported CI config (GitHub Actions, GitLab CI, Bitbucket,
+//!   CircleCI, Azure Pipelines) runs the doc check
+//! - missing_protected_paths - Pack globs not yet in the project's ai-exclude list
+//! - evaluate - One PolicyCheck per requirement the pack sets
+//!
+//! PATTERNS:
+//! - Requirements a pack leaves unset produce no check, so an empty pack is always compliant
+//! - Coverage is compared at one decimal place, matching core::doc_goals
+//!
+//! CLAUDE NOTES:
+//! - The CI check is recognised by CI_CHECK_MARKER, a line of the script in
+//!   commands::enforcement::doc_check_script; keep them in sync
+//! - Pack ids default to a slug of the name when the file has none
+
+use std::path::Path;
+
+use crate::models::policy::{PolicyCheck, PolicyPack};
+
+/// Hook modes a pack may require.
+pub const HOOK_MODES: &[&str] = &["block", "warn", "auto-update"];
+
+/// Text of the doc check script that identifies it in CI config files.
+const CI_CHECK_MARKER: &str = "Missing doc header";
+
+/// Parse a pack file and reject invalid requirements.
+pub fn parse_pack(content: &str) -> Result<PolicyPack, String> {
+    let mut pack: PolicyPack =
+        serde_json::from_str(content).map_err(|e| format!("Invalid policy pack: {}", e))?;
+
+    pack.name = pack.name.trim().to_string();
+    if pack.name.is_empty() {
+        return Err("Policy pack needs a name.".to_string());
+    }
+    if pack.id.trim().is_empty() {
+        pack.id = pack
+            .name
+            .to_lowercase()
+            .split(|c: char| !c.is_alphanumeric())
+            .filter(|s| !s.is_empty())
+            .collect::<Vec<_>>()
+            .join("-");
+    }
+    if let Some(ref mode) = pack.hook_mode {
+        if !HOOK_MODES.contains(&mode.as_str()) {
+            return Err(format!("Unknown hook mode \"{}\" (expected {}).", mode, HOOK_MODES.join(", ")));
+        }
+    }
+    if let Some(coverage) = pack.min_doc_coverage {
+        if !(0.0..=100.0).contains(&coverage) {
+            return Err("Doc coverage requirement must be between 0 and 100.".to_string());
+        }
+    }
+    pack.protected_paths = pack
+        .protected_paths
+        .iter()
+        .map(|p| p.trim().trim_start_matches("./").to_string())
+        .filter(|p| !p.is_empty())
+        .collect();
+    Ok(pack)
+}
+
+/// What a project currently enforces.
+#[derive(Debug, Clone, Default)]
+pub struct PolicyFacts {
+    /// HookStatus.mode: "none" | "external" | "block" | "warn" | "auto-update"
+    pub hook_mode: String,
+    pub ci_check: bool,
+    /// Documented files / total files (0-100)
+    pub doc_coverage: f64,
+    /// Globs in .claude/ai-exclude
+    pub ai_excludes: Vec<String>,
+}
+
+/// Whether any GitHub Actions workflow, .gitlab-ci.yml, bitbucket-pipelines.yml,
+/// .circleci/config.yml, or azure-pipelines.yml contains the doc check script.
+pub fn ci_doc_check_present(project_path: &str) -> bool {
+    let root = Path::new(project_path);
+    let mut files = vec![
+        root.join(".gitlab-ci.yml"),
+        root.join("bitbucket-pipelines.yml"),
+        root.join(".circleci").join("config.yml"),
+        root.join("azure-pipelines.yml"),
+    ];
+    if let Ok(entries) = std::fs::read_dir(root.join(".github").join("workflows")) {
+        files.extend(entries.flatten().map(|e| e.path()));
+    }
+    files.iter().any(|f| {
+        std::fs::read_to_string(f)
+            .map(|content| content.contains(CI_CHECK_MARKER))
+            .unwrap_or(false)
+    })
+}
+
+/// Pack globs that are not in the ai-exclude list (compared ignoring a leading "./").
+pub fn missing_protected_paths(pack: &PolicyPack, ai_excludes: &[String]) -> Vec<String> {
+    let normalized: Vec<&str> = ai_excludes.iter().map(|g| g.trim().trim_start_matches("./")).collect();
+    pack.protected_paths
+        .iter()
+        .filter(|p| !normalized.contains(&p.as_str()))
+        .cloned()
+        .collect()
+}
+
+/// One check per requirement the pack sets.
+pub fn evaluate(pack: &PolicyPack, facts: &PolicyFacts) -> Vec<PolicyCheck> {
+    let mut checks = Vec::new();
+
+    if let Some(ref mode) = pack.hook_mode {
+        checks.push(PolicyCheck {
+            requirement: "hook_mode".to_string(),
+            label: "Pre-commit hook mode".to_string(),
+            compliant: &facts.hook_mode == mode,
+            expected: mode.clone(),
+            actual: facts.hook_mode.clone(),
+            fixable: true,
+        });
+    }
+
+    if pack.require_ci_check {
+        checks.push(PolicyCheck {
+            requirement: "ci_check".to_string(),
+            label: "CI documentation check".to_string(),
+            compliant: facts.ci_check,
+            expected: "present".to_string(),
+            actual: if facts.ci_check { "present" } else { "missing" }.to_string(),
+            fixable: true,
+        });
+    }
+
+    if let Some(min) = pack.min_doc_coverage {
+        let actual = (facts.doc_coverage * 10.0).round() / 10.0;
+        checks.push(PolicyCheck {
+            requirement: "doc_coverage".to_string(),
+            label: "Documentation coverage".to_string(),
+            compliant: actual >= min,
+            expected: format!("≥ {}%", min),
+            actual: format!("{}%", actual),
+            fixable: false,
+        });
+    }
+
+    if !pack.protected_paths.is_empty() {
+        let missing = missing_protected_paths(pack, &facts.ai_excludes);
+        checks.push(PolicyCheck {
+            requirement: "protected_paths".to_string(),
+            label: "Protected paths excluded from AI".to_string(),
+            compliant: missing.is_empty(),
+            expected: pack.protected_paths.join(", "),
+            actual: if missing.is_empty() {
+                "all excluded".to_string()
+            } else {
+                format!("missing: {}", missing.join(", "))
+            },
+            fixable: true,
+        });
+    }
+
+    checks
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::fs;
+
+    #[test]
+    fn test_parse_pack_validates_and_normalizes() {
+        let pack = parse_pack(
+            r#"{"name": "Platform Baseline", "hookMode": "block", "minDocCoverage": 80, "protectedPaths": ["./secrets/", " "]}"#,
+        )
+        .unwrap();
+        assert_eq!(pack.id, "platform-baseline");
+        assert_eq!(pack.protected_paths, vec!["secrets/"]);
+        assert!(!pack.require_ci_check);
+
+        assert!(parse_pack(r#"{"name": "x", "hookMode": "strict"}"#).unwrap_err().contains("Unknown hook mode"));
+        assert!(parse_pack(r#"{"name": "x", "minDocCoverage": 120}"#).is_err());
+        assert!(parse_pack(r#"{"name": " "}"#).is_err());
+    }
+
+    #[test]
+    fn test_evaluate_reports_each_requirement() {
+        let pack = PolicyPack {
+            id: "p".to_string(),
+            name: "P".to_string(),
+            hook_mode: Some("block".to_string()),
+            require_ci_check: true,
+            min_doc_coverage: Some(90.0),
+            protected_paths: vec!["secrets/".to_string(), "*.pem".to_string()],
+            ..Default::default()
+        };
+        let facts = PolicyFacts {
+            hook_mode: "warn".to_string(),
+            ci_check: true,
+            doc_coverage: 89.96,
+            ai_excludes: vec!["./secrets/".to_string()],
+        };
+        let checks = evaluate(&pack, &facts);
+        let compliant: Vec<(&str, bool)> = checks.iter().map(|c| (c.requirement.as_str(), c.compliant)).collect();
+        assert_eq!(
+            compliant,
+            vec![("hook_mode", false), ("ci_check", true), ("doc_coverage", true), ("protected_paths", false)]
+        );
+        assert_eq!(checks[3].actual, "missing: *.pem");
+
+        assert!(evaluate(&PolicyPack::default(), &facts).is_empty());
+    }
+
+    #[test]
+    fn test_ci_doc_check_present() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().to_string_lossy().to_string();
+        assert!(!ci_doc_check_present(&path));
+
+        fs::create_dir_all(dir.path().join(".github/workflows")).unwrap();
+        fs::write(dir.path().join(".github/workflows/build.yml"), "name: Build\n").unwrap();
+        assert!(!ci_doc_check_present(&path));
+
+        fs::write(dir.path().join(".gitlab-ci.yml"), "doc-check:\n  script:\n    - echo \"Missing doc header: $file\"\n").unwrap();
+        assert!(ci_doc_check_present(&path));
+
+        let azure = tempfile::tempdir().unwrap();
+        fs::write(azure.path().join("azure-pipelines.yml"), "steps:\n  - script: echo \"Missing doc header: $file\"\n").unwrap();
+        assert!(ci_doc_check_present(&azure.path().to_string_lossy()));
+    }
+}
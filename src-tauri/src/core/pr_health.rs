@@ -0,0 +1,586 @@
+//! @module core/pr_health
+//! @description Doc coverage delta, newly stale docs, and test plan status between two git refs, as a PR Markdown block
+//!
+//! PURPOSE:
+//! - Resolve a PR's base (merge base) and head commits and list the files it changes
+//! - Measure doc coverage at both commits without checking either out
+//! - Find changed files whose docs went stale or that lack a header at head
+//! - Summarize the project's test plans and render everything as a Markdown block
+//!
+//! DEPENDENCIES:
+//! - rusqlite - Test plans and their latest runs
+//! - core::analyzer - Documentable filenames, generated/ignore markers, overrides
+//! - core::freshness - Scan scope and content-based freshness scoring
+//! - core::proc - git commands with a timeout
+//! - models::pr_health - DocCoverage, PrStaleFile, PrTestPlanStatus, PrHealthReport
+//!
+//! EXPORTS:
+//! - COMMENT_MARKER - Hidden first line so CI can find and update its earlier comment
+//! - build_report - Compare base_ref and head_ref of a project and render the block
+//! - test_plan_statuses - Latest run of each non-archived test plan
+//! - render_markdown - Markdown block for a report
+//!
+//! PATTERNS:
+//! - Files are read from git objects (ls-tree, grep, show), so the working tree and the
+//!   checked-out branch don't matter
+//! - Unchanged files count the same at both commits; they are classified with one
+//!   `git grep` per commit (markers anywhere in the file). Changed files are read in full
+//!   and scored exactly with freshness::check_content_freshness
+//! - "Newly stale" = outdated at head and not outdated (or absent) at base
+//!
+//! CLAUDE NOTES:
+//! - Generated overrides come from the working tree's .claude/generated-overrides
+//! - Lists in the Markdown are capped at MAX_LISTED entries; the report keeps them all
+//! - Blocking (runs git); the command calls it without holding the DB lock
+
+use std::collections::{HashMap, HashSet};
+use std::path::Path;
+use std::process::Command;
+
+use rusqlite::Connection;
+
+use crate::core::proc::{self, ProcLimits};
+use crate::core::{analyzer, freshness};
+use crate::models::pr_health::{DocCoverage, PrHealthReport, PrStaleFile, PrTestPlanStatus};
+
+/// First line of the block; lets a CI job find and edit its previous comment.
+pub const COMMENT_MARKER: &str = "<!-- project-jumpstart:pr-health -->";
+
+/// Files listed per section of the Markdown block.
+const MAX_LISTED: usize = 15;
+
+fn git(project_path: &str, args: &[&str]) -> Result<String, String> {
+    let output = proc::run(Command::new("git").args(args).current_dir(project_path), ProcLimits::GIT)
+        .map_err(|e| format!("Failed to run git: {}", e))?;
+    if !output.success() {
+        return Err(format!(
+            "git {} failed: {}",
+            args.first().unwrap_or(&""),
+            String::from_utf8_lossy(&output.stderr).trim()
+        ));
+    }
+    Ok(String::from_utf8_lossy(&output.stdout).to_string())
+}
+
+fn resolve_commit(project_path: &str, git_ref: &str) -> Result<String, String> {
+    git(project_path, &["rev-parse", "-q", "--verify", &format!("{}^{{commit}}", git_ref)])
+        .map(|s| s.trim().to_string())
+        .map_err(|_| format!("Unknown git ref '{}'", git_ref))
+}
+
+/// Documentation state of one file at one commit.
+#[derive(Debug, Clone, Default)]
+struct FileState {
+    tracked: bool,
+    documented: bool,
+    outdated: bool,
+    score: u32,
+    changes: Vec<String>,
+}
+
+/// Tracked and documented files at a commit, from ls-tree plus marker greps.
+#[derive(Debug, Default)]
+struct CommitScan {
+    tracked: HashSet<String>,
+    documented: HashSet<String>,
+}
+
+fn in_scope(path: &str) -> bool {
+    let name = path.rsplit('/').next().unwrap_or(path);
+    analyzer::is_documentable(name) && freshness::in_scan_scope(path)
+}
+
+/// Paths at `commit` containing any of `patterns` (extended regex), from one `git grep`.
+fn grep_paths(project_path: &str, commit: &str, pattern: &str) -> Result<HashSet<String>, String> {
+    let output = proc::run(
+        Command::new("git")
+            .args(["grep", "-l", "-z", "-I", "-E", pattern, commit])
+            .current_dir(project_path),
+        ProcLimits::GIT,
+    )
+    .map_err(|e| format!("Failed to run git: {}", e))?;
+    // git grep exits 1 when nothing matches
+    if !output.success() && output.status.code() != Some(1) {
+        return Err(format!("git grep failed: {}", String::from_utf8_lossy(&output.stderr).trim()));
+    }
+    let prefix = format!("{}:", commit);
+    Ok(String::from_utf8_lossy(&output.stdout)
+        .split('\0')
+        .filter_map(|p| p.strip_prefix(&prefix))
+        .map(str::to_string)
+        .collect())
+}
+
+fn scan_commit(project_path: &str, commit: &str, overrides: &[String]) -> Result<CommitScan, String> {
+    let listing = git(project_path, &["ls-tree", "-r", "-z", "--name-only", commit])?;
+    let generated = grep_paths(project_path, commit, "@generated|DO NOT EDIT")?;
+    let ignored = grep_paths(project_path, commit, analyzer::IGNORE_MARKER)?;
+    let headers = grep_paths(project_path, commit, "@module|@description")?;
+
+    let tracked: HashSet<String> = listing
+        .split('\0')
+        .filter(|p| !p.is_empty() && in_scope(p))
+        .filter(|p| !ignored.contains(*p) && (!generated.contains(*p) || overrides.iter().any(|o| o == p)))
+        .map(str::to_string)
+        .collect();
+    let documented = tracked.intersection(&headers).cloned().collect();
+    Ok(CommitScan { tracked, documented })
+}
+
+/// Exact state of `path` at `commit` from its full content (default state when absent).
+fn file_state(project_path: &str, commit: &str, path: &str, overrides: &[String]) -> FileState {
+    let Ok(content) = git(project_path, &["show", &format!("{}:{}", commit, path)]) else {
+        return FileState::default();
+    };
+    let name = path.rsplit('/').next().unwrap_or(path);
+    if !freshness::in_scan_scope(path) || !analyzer::should_track_file(name, path, &content, overrides) {
+        return FileState::default();
+    }
+    let ext = Path::new(path).extension().and_then(|e| e.to_str()).unwrap_or("");
+    let result = freshness::check_content_freshness(&content, ext);
+    FileState {
+        tracked: true,
+        documented: result.status != "missing",
+        outdated: result.status == "outdated",
+        score: result.score,
+        changes: result.changes,
+    }
+}
+
+/// Coverage at a commit: the grep-based scan for unchanged files, exact states for changed ones.
+fn coverage(scan: &CommitScan, changed: &HashMap<String, FileState>) -> DocCoverage {
+    let mut cov = DocCoverage::default();
+    for path in scan.tracked.iter().filter(|p| !changed.contains_key(*p)) {
+        cov.total += 1;
+        cov.documented += scan.documented.contains(path) as u32;
+    }
+    for state in changed.values().filter(|s| s.tracked) {
+        cov.total += 1;
+        cov.documented += state.documented as u32;
+    }
+    cov
+}
+
+/// Compare `base_ref` and `head_ref` (at their merge base, like a PR) and render the block.
+/// `test_plans` comes from test_plan_statuses with the changed files.
+pub fn build_report(
+    project_id: &str,
+    project_path: &str,
+    base_ref: &str,
+    head_ref: &str,
+    test_plans: impl FnOnce(&[String]) -> Result<Vec<PrTestPlanStatus>, String>,
+) -> Result<PrHealthReport, String> {
+    let head_commit = resolve_commit(project_path, head_ref)?;
+    let base_tip = resolve_commit(project_path, base_ref)?;
+    let base_commit = git(project_path, &["merge-base", &base_tip, &head_commit])
+        .map(|s| s.trim().to_string())
+        .map_err(|_| format!("'{}' and '{}' have no common history", base_ref, head_ref))?;
+
+    let changed_paths: Vec<String> = git(
+        project_path,
+        &["diff", "--name-only", "-z", "--no-renames", &base_commit, &head_commit],
+    )?
+    .split('\0')
+    .filter(|p| !p.is_empty())
+    .map(str::to_string)
+    .collect();
+
+    let overrides = analyzer::load_generated_overrides(project_path);
+    let relevant: Vec<&String> = changed_paths.iter().filter(|p| in_scope(p)).collect();
+    let base_states: HashMap<String, FileState> = relevant
+        .iter()
+        .map(|p| (p.to_string(), file_state(project_path, &base_commit, p, &overrides)))
+        .collect();
+    let head_states: HashMap<String, FileState> = relevant
+        .iter()
+        .map(|p| (p.to_string(), file_state(project_path, &head_commit, p, &overrides)))
+        .collect();
+
+    let base_coverage = coverage(&scan_commit(project_path, &base_commit, &overrides)?, &base_states);
+    let head_coverage = coverage(&scan_commit(project_path, &head_commit, &overrides)?, &head_states);
+
+    let mut newly_stale = Vec::new();
+    let mut undocumented = Vec::new();
+    for path in &relevant {
+        let head = &head_states[path.as_str()];
+        if !head.tracked {
+            continue;
+        }
+        if !head.documented {
+            undocumented.push(path.to_string());
+        } else if head.outdated && !base_states[path.as_str()].outdated {
+            newly_stale.push(PrStaleFile {
+                path: path.to_string(),
+                score: head.score,
+                changes: head.changes.clone(),
+            });
+        }
+    }
+    newly_stale.sort_by(|a, b| a.score.cmp(&b.score).then_with(|| a.path.cmp(&b.path)));
+    undocumented.sort();
+
+    let mut report = PrHealthReport {
+        project_id: project_id.to_string(),
+        base_ref: base_ref.to_string(),
+        head_ref: head_ref.to_string(),
+        base_commit,
+        head_commit,
+        changed_files: changed_paths.len() as u32,
+        base_coverage,
+        head_coverage,
+        newly_stale,
+        undocumented,
+        test_plans: test_plans(&changed_paths)?,
+        markdown: String::new(),
+    };
+    report.markdown = render_markdown(&report);
+    Ok(report)
+}
+
+/// Latest run of each non-archived test plan, with how many of its cases point at `changed_files`.
+pub fn test_plan_statuses(db: &Connection, project_id: &str, changed_files: &[String]) -> Result<Vec<PrTestPlanStatus>, String> {
+    let mut stmt = db
+        .prepare(
+            "SELECT p.id, p.name, r.status, COALESCE(r.completed_at, r.started_at),
+                    COALESCE(r.total_tests, 0), COALESCE(r.passed_tests, 0), COALESCE(r.failed_tests, 0),
+                    r.coverage_percent
+             FROM test_plans p
+             LEFT JOIN test_runs r ON r.id = (
+                 SELECT id FROM test_runs WHERE plan_id = p.id ORDER BY started_at DESC LIMIT 1
+             )
+             WHERE p.project_id = ?1 AND p.status != 'archived'
+             ORDER BY p.name",
+        )
+        .map_err(|e| format!("Failed to query test plans: {}", e))?;
+    let mut plans: Vec<PrTestPlanStatus> = stmt
+        .query_map([project_id], |row| {
+            Ok(PrTestPlanStatus {
+                plan_id: row.get(0)?,
+                name: row.get(1)?,
+                last_run_status: row.get(2)?,
+                last_run_at: row.get(3)?,
+                total_tests: row.get(4)?,
+                passed_tests: row.get(5)?,
+                failed_tests: row.get(6)?,
+                coverage_percent: row.get(7)?,
+                cases_for_changed_files: 0,
+            })
+        })
+        .map_err(|e| format!("Failed to read test plans: {}", e))?
+        .filter_map(|r| r.ok())
+        .collect();
+
+    let changed: HashSet<&str> = changed_files.iter().map(String::as_str).collect();
+    for plan in &mut plans {
+        let mut cases = db
+            .prepare("SELECT file_path FROM test_cases WHERE plan_id = ?1 AND file_path IS NOT NULL")
+            .map_err(|e| format!("Failed to query test cases: {}", e))?;
+        plan.cases_for_changed_files = cases
+            .query_map([&plan.plan_id], |row| row.get::<_, String>(0))
+            .map_err(|e| format!("Failed to read test cases: {}", e))?
+            .filter_map(|r| r.ok())
+            .filter(|path| changed.contains(path.trim_start_matches("./")))
+            .count() as u32;
+    }
+    Ok(plans)
+}
+
+fn short(commit: &str) -> &str {
+    &commit[..commit.len().min(7)]
+}
+
+fn push_capped(out: &mut String, lines: Vec<String>) {
+    let total = lines.len();
+    for line in lines.into_iter().take(MAX_LISTED) {
+        out.push_str(&line);
+        out.push('\n');
+    }
+    if total > MAX_LISTED {
+        out.push_str(&format!("- ...and {} more\n", total - MAX_LISTED));
+    }
+}
+
+/// Markdown block for a report, starting with COMMENT_MARKER.
+pub fn render_markdown(report: &PrHealthReport) -> String {
+    let (base, head) = (report.base_coverage, report.head_coverage);
+    let mut out = format!("{}\n### Project Jumpstart health\n\n", COMMENT_MARKER);
+    out.push_str(&format!(
+        "_`{}` ({}) ... `{}` ({}), {} changed file{}_\n\n",
+        report.base_ref,
+        short(&report.base_commit),
+        report.head_ref,
+        short(&report.head_commit),
+        report.changed_files,
+        if report.changed_files == 1 { "" } else { "s" }
+    ));
+    out.push_str("| | Base | Head | Change |\n|---|---|---|---|\n");
+    out.push_str(&format!(
+        "| Doc coverage | {:.1}% ({}/{}) | {:.1}% ({}/{}) | {:+.1} pts |\n",
+        base.percent(),
+        base.documented,
+        base.total,
+        head.percent(),
+        head.documented,
+        head.total,
+        head.percent() - base.percent()
+    ));
+
+    if !report.newly_stale.is_empty() {
+        out.push_str(&format!("\n**Newly stale docs ({})**\n\n", report.newly_stale.len()));
+        push_capped(
+            &mut out,
+            report
+                .newly_stale
+                .iter()
+                .map(|f| match f.changes.first() {
+                    Some(why) => format!("- `{}` (freshness {}): {}", f.path, f.score, why),
+                    None => format!("- `{}` (freshness {})", f.path, f.score),
+                })
+                .collect(),
+        );
+    }
+    if !report.undocumented.is_empty() {
+        out.push_str(&format!("\n**Changed files without doc headers ({})**\n\n", report.undocumented.len()));
+        push_capped(&mut out, report.undocumented.iter().map(|p| format!("- `{}`", p)).collect());
+    }
+    if report.newly_stale.is_empty() && report.undocumented.is_empty() {
+        out.push_str("\nNo changed file has stale or missing docs.\n");
+    }
+
+    out.push_str("\n**Test plans**\n\n");
+    if report.test_plans.is_empty() {
+        out.push_str("- No test plans\n");
+    }
+    for plan in &report.test_plans {
+        let mut line = match (&plan.last_run_status, &plan.last_run_at) {
+            (Some(status), at) => format!(
+                "- {}: {} ({}/{} passed{}){}",
+                plan.name,
+                status,
+                plan.passed_tests,
+                plan.total_tests,
+                plan.coverage_percent.map(|c| format!(", {:.1}% coverage", c)).unwrap_or_default(),
+                at.as_deref().map(|at| format!(", last run {}", &at[..at.len().min(10)])).unwrap_or_default()
+            ),
+            (None, _) => format!("- {}: never run", plan.name),
+        };
+        if plan.cases_for_changed_files > 0 {
+            line.push_str(&format!(" - {} case(s) cover changed files", plan.cases_for_changed_files));
+        }
+        out.push_str(&line);
+        out.push('\n');
+    }
+    out
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_support::with_project;
+
+    fn sh(dir: &Path, args: &[&str]) {
+        let ok = Command::new("git")
+            .args(["-c", "user.name=T", "-c", "user.email=t@example.com"])
+            .args(args)
+            .current_dir(dir)
+            .output()
+            .unwrap()
+            .status
+            .success();
+        assert!(ok, "git {:?}", args);
+    }
+
+    #[test]
+    fn test_build_report_between_branches() {
+        let dir = tempfile::tempdir().unwrap();
+        let root = dir.path();
+        let project = root.to_string_lossy().to_string();
+        std::fs::create_dir_all(root.join("src")).unwrap();
+        let header = "/**\n * @module api\n * @description API client\n *\n * PURPOSE:\n * - Call the API\n *\n * EXPORTS:\n * - fetchUser - Load a user\n */\n";
+        std::fs::write(root.join("src/api.ts"), format!("{}export function fetchUser() {{}}\n", header)).unwrap();
+        std::fs::write(root.join("src/util.ts"), "export const a = 1;\n").unwrap();
+        sh(root, &["init", "-q", "-b", "main"]);
+        sh(root, &["add", "."]);
+        sh(root, &["commit", "-qm", "base"]);
+
+        sh(root, &["checkout", "-qb", "feature"]);
+        let exports: String = (0..6).map(|i| format!("export function extra{}() {{}}\n", i)).collect();
+        std::fs::write(root.join("src/api.ts"), format!("{}export function fetchUser() {{}}\n{}", header, exports)).unwrap();
+        std::fs::write(root.join("src/new.ts"), "export const b = 2;\n").unwrap();
+        sh(root, &["add", "."]);
+        sh(root, &["commit", "-qm", "feature"]);
+
+        let report = build_report("p1", &project, "main", "feature", |changed| {
+            assert_eq!(changed.len(), 2);
+            Ok(Vec::new())
+        })
+        .unwrap();
+        assert_eq!(report.changed_files, 2);
+        assert_eq!(report.base_coverage, DocCoverage { documented: 1, total: 2 });
+        assert_eq!(report.head_coverage, DocCoverage { documented: 1, total: 3 });
+        assert_eq!(report.newly_stale.len(), 1);
+        assert_eq!(report.newly_stale[0].path, "src/api.ts");
+        assert_eq!(report.undocumented, vec!["src/new.ts"]);
+        assert!(report.markdown.starts_with(COMMENT_MARKER));
+        assert!(report.markdown.contains("| Doc coverage | 50.0% (1/2) | 33.3% (1/3) | -16.7 pts |"));
+        assert!(report.markdown.contains("- No test plans"));
+
+        assert!(build_report("p1", &project, "main", "nope", |_| Ok(Vec::new())).is_err());
+    }
+
+    #[test]
+    fn test_build_report_without_changes() {
+        let dir = tempfile::tempdir().unwrap();
+        let root = dir.path();
+        std::fs::write(root.join("util.ts"), "export const a = 1;\n").unwrap();
+        sh(root, &["init", "-q", "-b", "main"]);
+        sh(root, &["add", "."]);
+        sh(root, &["commit", "-qm", "base"]);
+
+        let report = build_report("p1", &root.to_string_lossy(), "main", "HEAD", |changed| {
+            assert!(changed.is_empty());
+            Ok(Vec::new())
+        })
+        .unwrap();
+        assert_eq!(report.changed_files, 0);
+        assert_eq!(report.base_commit, report.head_commit);
+        assert_eq!(report.base_coverage, DocCoverage { documented: 0, total: 1 });
+        assert_eq!(report.head_coverage, report.base_coverage);
+        assert!(report.newly_stale.is_empty() && report.undocumented.is_empty());
+        assert!(report.markdown.contains(", 0 changed files_"));
+        assert!(report.markdown.contains("| Doc coverage | 0.0% (0/1) | 0.0% (0/1) | +0.0 pts |"));
+        assert!(report.markdown.contains("\nNo changed file has stale or missing docs.\n"));
+    }
+
+    fn report() -> PrHealthReport {
+        PrHealthReport {
+            project_id: "p1".into(),
+            base_ref: "main".into(),
+            head_ref: "feature".into(),
+            base_commit: "0123456789abcdef".into(),
+            head_commit: "fedcba9876543210".into(),
+            changed_files: 1,
+            base_coverage: DocCoverage { documented: 3, total: 4 },
+            head_coverage: DocCoverage { documented: 4, total: 4 },
+            newly_stale: Vec::new(),
+            undocumented: Vec::new(),
+            test_plans: Vec::new(),
+            markdown: String::new(),
+        }
+    }
+
+    fn plan(name: &str, status: Option<&str>, passed: u32, total: u32, coverage: Option<f64>, cases: u32) -> PrTestPlanStatus {
+        PrTestPlanStatus {
+            plan_id: name.to_lowercase(),
+            name: name.into(),
+            last_run_status: status.map(str::to_string),
+            last_run_at: status.map(|_| "2025-01-02T03:04:05Z".to_string()),
+            total_tests: total,
+            passed_tests: passed,
+            failed_tests: total - passed,
+            coverage_percent: coverage,
+            cases_for_changed_files: cases,
+        }
+    }
+
+    #[test]
+    fn test_render_markdown_empty_report() {
+        let expected = format!(
+            "{}\n### Project Jumpstart health\n\n\
+             _`main` (0123456) ... `feature` (fedcba9), 1 changed file_\n\n\
+             | | Base | Head | Change |\n|---|---|---|---|\n\
+             | Doc coverage | 75.0% (3/4) | 100.0% (4/4) | +25.0 pts |\n\n\
+             No changed file has stale or missing docs.\n\n\
+             **Test plans**\n\n\
+             - No test plans\n",
+            COMMENT_MARKER
+        );
+        assert_eq!(render_markdown(&report()), expected);
+
+        // Nothing to document counts as full coverage
+        let mut empty = report();
+        empty.base_coverage = DocCoverage::default();
+        empty.head_coverage = DocCoverage { documented: 1, total: 2 };
+        assert!(render_markdown(&empty).contains("| Doc coverage | 100.0% (0/0) | 50.0% (1/2) | -50.0 pts |"));
+    }
+
+    #[test]
+    fn test_render_markdown_stale_files_and_plans() {
+        let mut report = report();
+        report.changed_files = 3;
+        report.newly_stale = vec![
+            PrStaleFile { path: "src/api.ts".into(), score: 40, changes: vec!["fetchAll is not in EXPORTS".into()] },
+            PrStaleFile { path: "src/db.ts".into(), score: 55, changes: Vec::new() },
+        ];
+        report.undocumented = vec!["src/new.ts".into()];
+        report.test_plans = vec![
+            plan("API", Some("passed"), 10, 10, Some(81.7), 2),
+            plan("E2E", Some("failed"), 1, 3, None, 0),
+            plan("Smoke", None, 0, 0, None, 1),
+        ];
+
+        let md = render_markdown(&report);
+        assert!(md.contains(", 3 changed files_"));
+        assert!(md.contains(
+            "\n**Newly stale docs (2)**\n\n\
+             - `src/api.ts` (freshness 40): fetchAll is not in EXPORTS\n\
+             - `src/db.ts` (freshness 55)\n"
+        ));
+        assert!(md.contains("\n**Changed files without doc headers (1)**\n\n- `src/new.ts`\n"));
+        assert!(!md.contains("No changed file has stale or missing docs."));
+        assert!(md.ends_with(
+            "\n**Test plans**\n\n\
+             - API: passed (10/10 passed, 81.7% coverage), last run 2025-01-02 - 2 case(s) cover changed files\n\
+             - E2E: failed (1/3 passed), last run 2025-01-02\n\
+             - Smoke: never run - 1 case(s) cover changed files\n"
+        ));
+    }
+
+    #[test]
+    fn test_render_markdown_caps_lists_at_max_listed() {
+        let mut report = report();
+        report.undocumented = (0..MAX_LISTED).map(|i| format!("src/f{:02}.ts", i)).collect();
+        let md = render_markdown(&report);
+        assert!(md.contains(&format!("- `src/f{:02}.ts`\n", MAX_LISTED - 1)));
+        assert!(!md.contains("more\n"));
+
+        report.undocumented.push("src/last.ts".into());
+        report.undocumented.push("src/later.ts".into());
+        let md = render_markdown(&report);
+        assert!(md.contains(&format!("**Changed files without doc headers ({})**", MAX_LISTED + 2)));
+        assert!(md.contains(&format!("- `src/f{:02}.ts`\n- ...and 2 more\n", MAX_LISTED - 1)));
+        assert!(!md.contains("src/last.ts"));
+    }
+
+    #[test]
+    fn test_test_plan_statuses_use_latest_run() {
+        let db = with_project();
+        db.execute_batch(
+            "INSERT INTO test_plans (id, project_id, name, status, created_at, updated_at) VALUES
+                 ('api', 'p1', 'API', 'active', '2025-01-01T00:00:00Z', '2025-01-01T00:00:00Z'),
+                 ('e2e', 'p1', 'E2E', 'draft', '2025-01-01T00:00:00Z', '2025-01-01T00:00:00Z'),
+                 ('old', 'p1', 'Old', 'archived', '2025-01-01T00:00:00Z', '2025-01-01T00:00:00Z');
+             INSERT INTO test_runs (id, plan_id, status, total_tests, passed_tests, failed_tests, coverage_percent, started_at, completed_at) VALUES
+                 ('r1', 'api', 'failed', 4, 2, 2, NULL, '2025-01-01T00:00:00Z', '2025-01-01T00:01:00Z'),
+                 ('r2', 'api', 'passed', 4, 4, 0, 72.5, '2025-01-02T00:00:00Z', '2025-01-02T00:01:00Z'),
+                 ('r3', 'old', 'passed', 1, 1, 0, NULL, '2025-01-02T00:00:00Z', '2025-01-02T00:01:00Z');
+             INSERT INTO test_cases (id, plan_id, name, file_path, created_at, updated_at) VALUES
+                 ('c1', 'api', 'fetch', './src/api.ts', '2025-01-01T00:00:00Z', '2025-01-01T00:00:00Z'),
+                 ('c2', 'api', 'other', 'src/other.ts', '2025-01-01T00:00:00Z', '2025-01-01T00:00:00Z'),
+                 ('c3', 'api', 'manual', NULL, '2025-01-01T00:00:00Z', '2025-01-01T00:00:00Z');",
+        )
+        .unwrap();
+
+        let plans = test_plan_statuses(&db, "p1", &["src/api.ts".to_string()]).unwrap();
+        assert_eq!(plans.len(), 2);
+        assert_eq!(plans[0].name, "API");
+        assert_eq!(plans[0].last_run_status.as_deref(), Some("passed"));
+        assert_eq!(plans[0].last_run_at.as_deref(), Some("2025-01-02T00:01:00Z"));
+        assert_eq!((plans[0].passed_tests, plans[0].total_tests), (4, 4));
+        assert_eq!(plans[0].coverage_percent, Some(72.5));
+        assert_eq!(plans[0].cases_for_changed_files, 1);
+        assert_eq!(plans[1].name, "E2E");
+        assert_eq!(plans[1].last_run_status, None);
+        assert_eq!(plans[1].cases_for_changed_files, 0);
+    }
+}
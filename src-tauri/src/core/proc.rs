@@ -0,0 +1,374 @@
+//! @module core/proc
+//! @description Process runner with timeouts, output byte limits, and kill-on-timeout
+//!
+//! PURPOSE:
+//! - Run a std::process::Command to completion without hanging forever
+//! - Kill the child when its timeout elapses or its cancel flag is set
+//! - Cap captured stdout/stderr, keeping the head and tail of long output
+//!
+//! DEPENDENCIES:
+//! - std::process - Child process spawning
+//! - std::thread / std::sync::mpsc - Pipe readers that drain output while the child runs
+//!
+//! EXPORTS:
+//! - ProcLimits - Timeout and per-stream byte limit (with presets for git, Claude, tests)
+//! - ProcOutput - Exit status, captured output, and whether it timed out, was cancelled, or was truncated
+//! - run - Spawn a command and wait for it within its limits
+//! - run_cancellable - run, also killing the child when a cancel flag is set
+//! - run_with_input - run with bytes written to the child's stdin
+//!
+//! PATTERNS:
+//! - Callers build the Command as usual and pass it to proc::run instead of calling .output()
+//! - stdin is null unless run_with_input supplies it; stdout and stderr are always piped and drained concurrently
+//! - A timeout is not an error: the child is killed and ProcOutput.timed_out is set, with a
+//!   note appended to stderr so it shows up in logs and loop outcomes; cancellation works the
+//!   same way with ProcOutput.cancelled
+//!
+//! CLAUDE NOTES:
+//! - Truncated streams keep the first and last half of max_output_bytes with a marker between,
+//!   so test summaries at the end of the output survive
+//! - Only the direct child is killed; grandchildren that keep the pipes open are abandoned
+//!   after READER_GRACE and their remaining output is discarded
+//! - Blocking: call from background tasks or blocking commands, not from the UI thread
+
+use std::collections::VecDeque;
+use std::io::{Read, Write};
+use std::process::{Child, Command, ExitStatus, Output, Stdio};
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::mpsc::{self, Receiver};
+use std::sync::{Arc, Mutex};
+use std::thread;
+use std::time::{Duration, Instant};
+
+/// How often the child's exit status is polled.
+const POLL_INTERVAL: Duration = Duration::from_millis(20);
+
+/// How long to wait for the pipe readers once the child has exited.
+const READER_GRACE: Duration = Duration::from_secs(2);
+
+/// Timeout and output cap for one process run.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct ProcLimits {
+    pub timeout: Duration,
+    /// Maximum bytes kept per stream (stdout and stderr are capped separately)
+    pub max_output_bytes: usize,
+}
+
+impl ProcLimits {
+    /// git and other quick local tools.
+    pub const GIT: ProcLimits = ProcLimits::new(Duration::from_secs(120), 1024 * 1024);
+    /// One Claude CLI invocation (a full agentic iteration).
+    pub const CLAUDE: ProcLimits = ProcLimits::new(Duration::from_secs(30 * 60), 4 * 1024 * 1024);
+    /// Test suites, type checks, and acceptance gates.
+    pub const TESTS: ProcLimits = ProcLimits::new(Duration::from_secs(15 * 60), 8 * 1024 * 1024);
+    /// Test listing commands used for discovery.
+    pub const TEST_LIST: ProcLimits = ProcLimits::new(Duration::from_secs(120), 4 * 1024 * 1024);
+
+    pub const fn new(timeout: Duration, max_output_bytes: usize) -> Self {
+        ProcLimits { timeout, max_output_bytes }
+    }
+}
+
+/// Result of a process run.
+#[derive(Debug, Clone)]
+pub struct ProcOutput {
+    pub status: ExitStatus,
+    pub stdout: Vec<u8>,
+    pub stderr: Vec<u8>,
+    /// The child was killed because it exceeded its timeout
+    pub timed_out: bool,
+    /// The child was killed because its cancel flag was set
+    pub cancelled: bool,
+    /// stdout or stderr exceeded max_output_bytes and was cut in the middle
+    pub truncated: bool,
+}
+
+impl ProcOutput {
+    /// True when the process exited successfully within its timeout.
+    pub fn success(&self) -> bool {
+        !self.timed_out && !self.cancelled && self.status.success()
+    }
+}
+
+impl From<ProcOutput> for Output {
+    fn from(p: ProcOutput) -> Output {
+        Output {
+            status: p.status,
+            stdout: p.stdout,
+            stderr: p.stderr,
+        }
+    }
+}
+
+/// Run a command to completion within `limits`. Errors only when the process
+/// cannot be spawned or waited on.
+pub fn run(cmd: &mut Command, limits: ProcLimits) -> Result<ProcOutput, String> {
+    run_cancellable(cmd, limits, &AtomicBool::new(false))
+}
+
+/// Like run, but the child is also killed as soon as `cancel` is set
+/// (checked every POLL_INTERVAL).
+pub fn run_cancellable(cmd: &mut Command, limits: ProcLimits, cancel: &AtomicBool) -> Result<ProcOutput, String> {
+    run_inner(cmd, None, limits, cancel)
+}
+
+/// Like run, with `input` written to the child's stdin (then closed) from a
+/// separate thread so a child that writes before reading cannot deadlock.
+pub fn run_with_input(cmd: &mut Command, input: Vec<u8>, limits: ProcLimits) -> Result<ProcOutput, String> {
+    run_inner(cmd, Some(input), limits, &AtomicBool::new(false))
+}
+
+fn run_inner(cmd: &mut Command, input: Option<Vec<u8>>, limits: ProcLimits, cancel: &AtomicBool) -> Result<ProcOutput, String> {
+    let mut child = cmd
+        .stdin(if input.is_some() { Stdio::piped() } else { Stdio::null() })
+        .stdout(Stdio::piped())
+        .stderr(Stdio::piped())
+        .spawn()
+        .map_err(|e| e.to_string())?;
+
+    if let (Some(input), Some(mut stdin)) = (input, child.stdin.take()) {
+        // A child that exits without reading closes the pipe; the write error is expected
+        thread::spawn(move || {
+            let _ = stdin.write_all(&input);
+        });
+    }
+
+    let stdout = StreamReader::spawn(child.stdout.take(), limits.max_output_bytes);
+    let stderr = StreamReader::spawn(child.stderr.take(), limits.max_output_bytes);
+
+    let (status, ended) = wait_with_timeout(&mut child, limits.timeout, cancel)?;
+
+    let (stdout, stdout_truncated) = stdout.finish();
+    let (mut stderr, stderr_truncated) = stderr.finish();
+    match ended {
+        Ended::TimedOut => stderr.extend_from_slice(
+            format!("\n[process killed after {}s timeout]\n", limits.timeout.as_secs()).as_bytes(),
+        ),
+        Ended::Cancelled => stderr.extend_from_slice(b"\n[process cancelled]\n"),
+        Ended::Exited => {}
+    }
+
+    Ok(ProcOutput {
+        status,
+        stdout,
+        stderr,
+        timed_out: ended == Ended::TimedOut,
+        cancelled: ended == Ended::Cancelled,
+        truncated: stdout_truncated || stderr_truncated,
+    })
+}
+
+/// How a waited-on child ended.
+#[derive(Debug, Clone, Copy, PartialEq)]
+enum Ended {
+    Exited,
+    TimedOut,
+    Cancelled,
+}
+
+fn wait_with_timeout(child: &mut Child, timeout: Duration, cancel: &AtomicBool) -> Result<(ExitStatus, Ended), String> {
+    let deadline = Instant::now() + timeout;
+    loop {
+        if let Some(status) = child.try_wait().map_err(|e| e.to_string())? {
+            return Ok((status, Ended::Exited));
+        }
+        let now = Instant::now();
+        let ended = if cancel.load(Ordering::Relaxed) {
+            Some(Ended::Cancelled)
+        } else if now >= deadline {
+            Some(Ended::TimedOut)
+        } else {
+            None
+        };
+        if let Some(ended) = ended {
+            let _ = child.kill();
+            let status = child.wait().map_err(|e| e.to_string())?;
+            return Ok((status, ended));
+        }
+        thread::sleep(POLL_INTERVAL.min(deadline - now));
+    }
+}
+
+/// Keeps the first half and the last half of a stream within `limit` bytes.
+struct CappedBuffer {
+    head: Vec<u8>,
+    tail: VecDeque<u8>,
+    head_limit: usize,
+    tail_limit: usize,
+    dropped: usize,
+}
+
+impl CappedBuffer {
+    fn new(limit: usize) -> Self {
+        let head_limit = limit / 2;
+        CappedBuffer {
+            head: Vec::new(),
+            tail: VecDeque::new(),
+            head_limit,
+            tail_limit: limit - head_limit,
+            dropped: 0,
+        }
+    }
+
+    fn push(&mut self, mut bytes: &[u8]) {
+        let room = self.head_limit.saturating_sub(self.head.len());
+        if room > 0 {
+            let n = room.min(bytes.len());
+            self.head.extend_from_slice(&bytes[..n]);
+            bytes = &bytes[n..];
+        }
+        self.tail.extend(bytes);
+        if self.tail.len() > self.tail_limit {
+            let excess = self.tail.len() - self.tail_limit;
+            self.tail.drain(..excess);
+            self.dropped += excess;
+        }
+    }
+
+    fn into_bytes(self) -> (Vec<u8>, bool) {
+        let mut out = self.head;
+        let truncated = self.dropped > 0;
+        if truncated {
+            out.extend_from_slice(format!("\n... [{} bytes truncated] ...\n", self.dropped).as_bytes());
+        }
+        out.extend(self.tail);
+        (out, truncated)
+    }
+}
+
+/// Drains one pipe on a background thread into a CappedBuffer.
+struct StreamReader {
+    buffer: Arc<Mutex<CappedBuffer>>,
+    done: Option<Receiver<()>>,
+}
+
+impl StreamReader {
+    fn spawn<R: Read + Send + 'static>(pipe: Option<R>, limit: usize) -> Self {
+        let buffer = Arc::new(Mutex::new(CappedBuffer::new(limit)));
+        let Some(mut pipe) = pipe else {
+            return StreamReader { buffer, done: None };
+        };
+
+        let (tx, rx) = mpsc::channel();
+        let shared = Arc::clone(&buffer);
+        thread::spawn(move || {
+            let mut chunk = [0u8; 8192];
+            loop {
+                match pipe.read(&mut chunk) {
+                    Ok(0) | Err(_) => break,
+                    Ok(n) => match shared.lock() {
+                        Ok(mut buf) => buf.push(&chunk[..n]),
+                        Err(_) => break,
+                    },
+                }
+            }
+            let _ = tx.send(());
+        });
+        StreamReader { buffer, done: Some(rx) }
+    }
+
+    /// Wait briefly for the reader to hit EOF, then take what was captured.
+    fn finish(self) -> (Vec<u8>, bool) {
+        if let Some(done) = self.done {
+            let _ = done.recv_timeout(READER_GRACE);
+        }
+        let captured = match self.buffer.lock() {
+            Ok(mut buf) => std::mem::replace(&mut *buf, CappedBuffer::new(0)),
+            Err(_) => return (Vec::new(), false),
+        };
+        captured.into_bytes()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_capped_buffer_keeps_head_and_tail() {
+        let mut buf = CappedBuffer::new(10);
+        buf.push(b"abc");
+        buf.push(b"defghijklmnopqrstuvwxyz");
+        let (bytes, truncated) = buf.into_bytes();
+        assert!(truncated);
+        let text = String::from_utf8(bytes).unwrap();
+        assert!(text.starts_with("abcde"));
+        assert!(text.ends_with("vwxyz"));
+        assert!(text.contains("[16 bytes truncated]"));
+
+        let mut small = CappedBuffer::new(10);
+        small.push(b"short");
+        assert_eq!(small.into_bytes(), (b"short".to_vec(), false));
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn test_run_with_input_feeds_stdin() {
+        let out = run_with_input(Command::new("sh").args(["-c", "tr a-z A-Z"]), b"plugin".to_vec(), ProcLimits::GIT).unwrap();
+        assert!(out.success());
+        assert_eq!(out.stdout, b"PLUGIN");
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn test_run_captures_output() {
+        let out = run(
+            Command::new("sh").args(["-c", "echo out; echo err >&2"]),
+            ProcLimits::GIT,
+        )
+        .unwrap();
+        assert!(out.success());
+        assert_eq!(out.stdout, b"out\n");
+        assert_eq!(out.stderr, b"err\n");
+        assert!(!out.timed_out && !out.truncated);
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn test_run_kills_on_timeout() {
+        let started = Instant::now();
+        let out = run(
+            Command::new("sleep").arg("10"),
+            ProcLimits::new(Duration::from_millis(200), 1024),
+        )
+        .unwrap();
+        assert!(out.timed_out);
+        assert!(!out.success());
+        assert!(started.elapsed() < Duration::from_secs(5));
+        assert!(String::from_utf8_lossy(&out.stderr).contains("timeout"));
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn test_run_truncates_large_output() {
+        let out = run(
+            Command::new("sh").args(["-c", "yes line | head -n 100000"]),
+            ProcLimits::new(Duration::from_secs(30), 1000),
+        )
+        .unwrap();
+        assert!(out.success());
+        assert!(out.truncated);
+        assert!(out.stdout.len() < 1100);
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn test_run_cancellable_kills_on_cancel() {
+        let cancel = Arc::new(AtomicBool::new(false));
+        let flag = Arc::clone(&cancel);
+        thread::spawn(move || {
+            thread::sleep(Duration::from_millis(100));
+            flag.store(true, Ordering::Relaxed);
+        });
+        let started = Instant::now();
+        let out = run_cancellable(Command::new("sleep").arg("10"), ProcLimits::TESTS, &cancel).unwrap();
+        assert!(out.cancelled && !out.timed_out);
+        assert!(!out.success());
+        assert!(started.elapsed() < Duration::from_secs(5));
+    }
+
+    #[test]
+    fn test_run_reports_spawn_failure() {
+        assert!(run(&mut Command::new("definitely-not-a-real-binary-xyz"), ProcLimits::GIT).is_err());
+    }
+}
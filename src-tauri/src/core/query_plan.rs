@@ -0,0 +1,182 @@
+//! @module core/query_plan
+//! @description EXPLAIN QUERY PLAN audit of the app's own SQLite database
+//!
+//! PURPOSE:
+//! - Run EXPLAIN QUERY PLAN on the hot list queries (activities, mistakes, test runs, ...)
+//! - Flag full table scans and ORDER BY sorts that need a temporary b-tree
+//! - Report expected indexes (db::schema::QUERY_INDEXES) that are missing
+//!
+//! DEPENDENCIES:
+//! - rusqlite - EXPLAIN QUERY PLAN, sqlite_master, pragmas
+//! - db::schema - QUERY_INDEXES
+//! - models::performance - QueryPlanCheck, DbPerformanceReport
+//!
+//! EXPORTS:
+//! - HOT_QUERIES - (name, sql) of the queries behind the list commands
+//! - parse_plan - Full scans, temp sort, and indexes from plan detail lines
+//! - explain - QueryPlanCheck for one query
+//! - missing_indexes - CREATE INDEX statements for QUERY_INDEXES entries that do not exist
+//! - analyze - Full report for a connection
+//!
+//! PATTERNS:
+//! - HOT_QUERIES mirror the WHERE/ORDER BY of the command queries; the select list is `*`
+//!   because only filtering and ordering affect the plan's index use
+//! - Parameters are bound as NULL; the plan does not depend on their values
+//!
+//! CLAUDE NOTES:
+//! - When adding a list command with a new WHERE/ORDER BY shape, add it to HOT_QUERIES and,
+//!   if it scans, an index to QUERY_INDEXES
+//! - Plan detail text differs across SQLite versions ("SCAN t" vs "SCAN TABLE t"); both parse
+
+use rusqlite::types::Null;
+use rusqlite::Connection;
+
+use crate::db::schema::QUERY_INDEXES;
+use crate::models::performance::{DbPerformanceReport, QueryPlanCheck};
+
+/// Queries behind the list commands, keyed by a short name.
+pub const HOT_QUERIES: &[(&str, &str)] = &[
+    (
+        "recent_activities",
+        "SELECT * FROM activities WHERE project_id = ?1 AND (?2 IS NULL OR activity_type = ?2)
+         ORDER BY created_at DESC LIMIT ?3",
+    ),
+    ("ralph_mistakes", "SELECT * FROM ralph_mistakes WHERE project_id = ?1 ORDER BY created_at DESC"),
+    ("ralph_loops", "SELECT * FROM ralph_loops WHERE project_id = ?1 ORDER BY created_at DESC"),
+    ("ralph_iterations", "SELECT * FROM ralph_iterations WHERE loop_id = ?1 ORDER BY started_at ASC"),
+    ("prompt_analyses", "SELECT * FROM prompt_analyses WHERE project_id = ?1 ORDER BY created_at DESC"),
+    ("test_plans", "SELECT * FROM test_plans WHERE project_id = ?1 ORDER BY updated_at DESC"),
+    ("test_runs", "SELECT * FROM test_runs WHERE plan_id = ?1 ORDER BY started_at DESC LIMIT ?2"),
+    ("tdd_sessions", "SELECT * FROM tdd_sessions WHERE project_id = ?1 ORDER BY updated_at DESC"),
+    ("checkpoints", "SELECT * FROM checkpoints WHERE project_id = ?1 ORDER BY created_at DESC"),
+    (
+        "enforcement_events",
+        "SELECT * FROM enforcement_events WHERE project_id = ?1 ORDER BY created_at DESC LIMIT ?2",
+    ),
+    ("performance_reviews", "SELECT * FROM performance_reviews WHERE project_id = ?1 ORDER BY created_at DESC"),
+    ("health_snapshots", "SELECT * FROM health_snapshots WHERE project_id = ?1 ORDER BY created_at ASC"),
+    ("import_conflicts", "SELECT * FROM import_conflicts WHERE status = 'pending' ORDER BY created_at ASC"),
+];
+
+/// (tables fully scanned, temp sort needed, indexes used) from plan detail lines.
+pub fn parse_plan(details: &[String]) -> (Vec<String>, bool, Vec<String>) {
+    let mut scans = Vec::new();
+    let mut indexes = Vec::new();
+    let mut temp_sort = false;
+    for detail in details {
+        if detail.starts_with("USE TEMP B-TREE FOR") && detail.contains("ORDER BY") {
+            temp_sort = true;
+        }
+        if let Some(pos) = detail.find(" INDEX ") {
+            let name = detail[pos + 7..].split_whitespace().next().unwrap_or("");
+            if !name.is_empty() && !indexes.iter().any(|i| i == name) {
+                indexes.push(name.to_string());
+            }
+        } else if let Some(rest) = detail.strip_prefix("SCAN ") {
+            let table = rest.strip_prefix("TABLE ").unwrap_or(rest);
+            if let Some(table) = table.split_whitespace().next() {
+                scans.push(table.to_string());
+            }
+        }
+    }
+    (scans, temp_sort, indexes)
+}
+
+/// EXPLAIN QUERY PLAN one query.
+pub fn explain(conn: &Connection, name: &str, sql: &str) -> Result<QueryPlanCheck, String> {
+    let mut stmt = conn
+        .prepare(&format!("EXPLAIN QUERY PLAN {}", sql))
+        .map_err(|e| format!("Failed to explain {}: {}", name, e))?;
+    let params = std::iter::repeat_n(Null, stmt.parameter_count());
+    let plan: Vec<String> = stmt
+        .query_map(rusqlite::params_from_iter(params), |row| row.get::<_, String>(3))
+        .map_err(|e| format!("Failed to explain {}: {}", name, e))?
+        .filter_map(|r| r.ok())
+        .collect();
+
+    let (full_scans, temp_sort, indexes_used) = parse_plan(&plan);
+    Ok(QueryPlanCheck {
+        name: name.to_string(),
+        sql: sql.split_whitespace().collect::<Vec<_>>().join(" "),
+        ok: full_scans.is_empty() && !temp_sort,
+        plan,
+        full_scans,
+        temp_sort,
+        indexes_used,
+    })
+}
+
+/// CREATE INDEX statements for the QUERY_INDEXES that do not exist.
+pub fn missing_indexes(conn: &Connection) -> Result<Vec<String>, String> {
+    let mut stmt = conn
+        .prepare("SELECT name FROM sqlite_master WHERE type = 'index'")
+        .map_err(|e| format!("Failed to list indexes: {}", e))?;
+    let existing: Vec<String> = stmt
+        .query_map([], |row| row.get(0))
+        .map_err(|e| format!("Failed to list indexes: {}", e))?
+        .filter_map(|r| r.ok())
+        .collect();
+
+    Ok(QUERY_INDEXES
+        .iter()
+        .filter(|(name, _, _)| !existing.iter().any(|e| e == name))
+        .map(|(name, table, columns)| format!("CREATE INDEX {} ON {}({});", name, table, columns))
+        .collect())
+}
+
+/// Plan checks for every HOT_QUERIES entry, missing indexes, and the database size.
+pub fn analyze(conn: &Connection) -> Result<DbPerformanceReport, String> {
+    let queries = HOT_QUERIES
+        .iter()
+        .map(|(name, sql)| explain(conn, name, sql))
+        .collect::<Result<Vec<_>, _>>()?;
+    let page_count: u64 = conn.query_row("PRAGMA page_count", [], |row| row.get(0)).unwrap_or(0);
+    let page_size: u64 = conn.query_row("PRAGMA page_size", [], |row| row.get(0)).unwrap_or(0);
+
+    Ok(DbPerformanceReport {
+        slow_queries: queries.iter().filter(|q| !q.ok).count() as u32,
+        queries,
+        missing_indexes: missing_indexes(conn)?,
+        db_size_bytes: page_count * page_size,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::db::schema;
+    use crate::test_support::memory_db;
+
+    #[test]
+    fn test_parse_plan() {
+        let lines = |v: &[&str]| v.iter().map(|s| s.to_string()).collect::<Vec<_>>();
+        assert_eq!(
+            parse_plan(&lines(&["SCAN ralph_mistakes", "USE TEMP B-TREE FOR ORDER BY"])),
+            (vec!["ralph_mistakes".to_string()], true, vec![])
+        );
+        assert_eq!(
+            parse_plan(&lines(&["SEARCH test_runs USING INDEX idx_test_runs_plan_started (plan_id=?)"])),
+            (vec![], false, vec!["idx_test_runs_plan_started".to_string()])
+        );
+        assert_eq!(parse_plan(&lines(&["SCAN TABLE checkpoints"])).0, vec!["checkpoints".to_string()]);
+    }
+
+    #[test]
+    fn test_analyze_before_and_after_indexes() {
+        let conn = memory_db();
+
+        let before = analyze(&conn).unwrap();
+        assert_eq!(before.missing_indexes.len(), QUERY_INDEXES.len());
+        let mistakes = before.queries.iter().find(|q| q.name == "ralph_mistakes").unwrap();
+        assert!(!mistakes.ok);
+        assert_eq!(mistakes.full_scans, vec!["ralph_mistakes"]);
+
+        schema::migrate_add_query_indexes(&conn).unwrap();
+        let after = analyze(&conn).unwrap();
+        assert!(after.missing_indexes.is_empty());
+        let slow: Vec<&str> = after.queries.iter().filter(|q| !q.ok).map(|q| q.name.as_str()).collect();
+        assert!(slow.is_empty(), "slow queries after migration: {:?}", slow);
+        assert_eq!(after.slow_queries, 0);
+        assert!(after.db_size_bytes > 0);
+    }
+}
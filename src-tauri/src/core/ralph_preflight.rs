@@ -0,0 +1,356 @@
+//! @module core/ralph_preflight
+//! @description Preflight checks run before a RALPH loop starts
+//!
+//! PURPOSE:
+//! - Check the Claude CLI is installed (and its version) and logged in
+//! - Check the project path exists and, when the loop needs it, is a git repository
+//! - Check the API key used for issue extraction and the free disk space
+//! - Return each result as a checklist item with a fix, so loops fail before they start
+//!
+//! DEPENDENCIES:
+//! - core::proc - claude, git, and df with short timeouts
+//! - models::ralph - RalphPreflightCheck, RalphPreflight
+//!
+//! EXPORTS:
+//! - MIN_FREE_BYTES / LOW_FREE_BYTES - Disk space fail and warn thresholds
+//! - parse_cli_version - Version number from `claude --version` output
+//! - auth_check - Classify `claude auth status` output
+//! - parse_df_available - Available bytes from `df -Pk` output
+//! - disk_check - Check item for an amount of free space
+//! - check_cli / check_auth / check_project / check_api_key / check_disk - Individual checks
+//! - preflight - Collect checks into a RalphPreflight
+//!
+//! PATTERNS:
+//! - Status is "pass" | "warn" | "fail" | "skipped"; only "fail" makes the loop not ready
+//! - A missing API key is a warning: the loop runs, but issues are not extracted between iterations
+//! - Auth is skipped when the CLI is missing, so the checklist shows one root cause
+//!
+//! CLAUDE NOTES:
+//! - Older CLIs have no `auth status` subcommand; that is a warning, not a failure
+//! - ANTHROPIC_API_KEY in the environment authenticates the CLI without a login
+//! - df is POSIX only; on Windows the disk check is "skipped"
+
+use std::path::Path;
+use std::process::Command;
+use std::time::Duration;
+
+use crate::core::proc::{self, ProcLimits};
+use crate::models::ralph::{RalphPreflight, RalphPreflightCheck};
+
+/// Below this much free space a loop is not started.
+pub const MIN_FREE_BYTES: u64 = 256 * 1024 * 1024;
+
+/// Below this much free space the check warns.
+pub const LOW_FREE_BYTES: u64 = 1024 * 1024 * 1024;
+
+/// claude --version, claude auth status, git rev-parse, df.
+const CHECK_LIMITS: ProcLimits = ProcLimits::new(Duration::from_secs(15), 64 * 1024);
+
+const AUTH_ID: &str = "claude_auth";
+
+const LOGIN_FIX: &str = "Run `claude` in a terminal and complete /login, or set ANTHROPIC_API_KEY.";
+
+fn check(id: &str, label: &str, status: &str, detail: String, fix: Option<&str>) -> RalphPreflightCheck {
+    RalphPreflightCheck {
+        id: id.to_string(),
+        label: label.to_string(),
+        status: status.to_string(),
+        detail,
+        fix: fix.map(str::to_string),
+    }
+}
+
+fn first_line(text: &str) -> Option<&str> {
+    text.lines().map(str::trim).find(|l| !l.is_empty())
+}
+
+/// The first dotted version number in `claude --version` output ("1.0.58 (Claude Code)").
+pub fn parse_cli_version(output: &str) -> Option<String> {
+    output
+        .split_whitespace()
+        .map(|t| t.trim_start_matches('v'))
+        .find(|t| t.contains('.') && t.split('.').all(|part| !part.is_empty() && part.chars().all(|c| c.is_ascii_digit())))
+        .map(str::to_string)
+}
+
+/// Check item for `claude auth status` output. `env_key` is whether ANTHROPIC_API_KEY
+/// is set, which authenticates the CLI on its own.
+pub fn auth_check(success: bool, stdout: &str, stderr: &str, env_key: bool) -> RalphPreflightCheck {
+    let label = "Claude CLI login";
+    let text = format!("{}\n{}", stdout, stderr);
+    let lower = text.to_lowercase();
+
+    if env_key && !success {
+        return check(AUTH_ID, label, "pass", "Using ANTHROPIC_API_KEY from the environment".to_string(), None);
+    }
+    if lower.contains("unknown command") || lower.contains("unknown option") || lower.contains("did you mean") {
+        return check(
+            AUTH_ID,
+            label,
+            "warn",
+            "This Claude CLI cannot report login status".to_string(),
+            Some("Update the CLI with `claude update`, or make sure `claude` runs without asking to log in."),
+        );
+    }
+    let logged_out = ["not logged in", "not authenticated", "no credentials", "please log in", "login required"]
+        .iter()
+        .any(|p| lower.contains(p));
+    if !success || logged_out {
+        let detail = first_line(&text).unwrap_or("claude auth status failed").to_string();
+        return check(AUTH_ID, label, "fail", detail, Some(LOGIN_FIX));
+    }
+    let detail = first_line(stdout).unwrap_or("Logged in").to_string();
+    check(AUTH_ID, label, "pass", detail, None)
+}
+
+/// Available bytes from `df -Pk <path>` output (the "Available" column of the data line).
+pub fn parse_df_available(output: &str) -> Option<u64> {
+    let line = output.lines().skip(1).find(|l| !l.trim().is_empty())?;
+    let kb: u64 = line.split_whitespace().nth(3)?.parse().ok()?;
+    Some(kb * 1024)
+}
+
+fn format_bytes(bytes: u64) -> String {
+    let gb = bytes as f64 / (1024.0 * 1024.0 * 1024.0);
+    if gb >= 1.0 {
+        format!("{:.1} GB", gb)
+    } else {
+        format!("{} MB", bytes / (1024 * 1024))
+    }
+}
+
+/// Check item for the free space on the project's volume (None when unknown).
+pub fn disk_check(available: Option<u64>) -> RalphPreflightCheck {
+    let label = "Disk space";
+    match available {
+        None => check(
+            "disk_space",
+            label,
+            "skipped",
+            "Could not determine free disk space".to_string(),
+            None,
+        ),
+        Some(bytes) if bytes < MIN_FREE_BYTES => check(
+            "disk_space",
+            label,
+            "fail",
+            format!("Only {} free", format_bytes(bytes)),
+            Some("Free up disk space; Claude runs, builds, and test output need room to write."),
+        ),
+        Some(bytes) if bytes < LOW_FREE_BYTES => check(
+            "disk_space",
+            label,
+            "warn",
+            format!("{} free", format_bytes(bytes)),
+            Some("Free up disk space before long loops; builds may run out of room."),
+        ),
+        Some(bytes) => check("disk_space", label, "pass", format!("{} free", format_bytes(bytes)), None),
+    }
+}
+
+/// Claude CLI presence and version. `cli` is the resolved CLI path, if any.
+pub fn check_cli(cli: Option<&str>) -> RalphPreflightCheck {
+    let label = "Claude CLI";
+    let Some(cli) = cli else {
+        return check(
+            "claude_cli",
+            label,
+            "fail",
+            "claude was not found on PATH".to_string(),
+            Some("Install it with `npm install -g @anthropic-ai/claude-code`."),
+        );
+    };
+    match proc::run(Command::new(cli).arg("--version"), CHECK_LIMITS) {
+        Ok(out) if out.success() => {
+            let stdout = String::from_utf8_lossy(&out.stdout);
+            let version = parse_cli_version(&stdout).unwrap_or_else(|| "unknown version".to_string());
+            check("claude_cli", label, "pass", format!("{} ({})", version, cli), None)
+        }
+        Ok(out) => check(
+            "claude_cli",
+            label,
+            "fail",
+            format!(
+                "`claude --version` failed: {}",
+                first_line(&String::from_utf8_lossy(&out.stderr)).unwrap_or("no output")
+            ),
+            Some("Reinstall the CLI with `npm install -g @anthropic-ai/claude-code`."),
+        ),
+        Err(e) => check(
+            "claude_cli",
+            label,
+            "fail",
+            format!("Could not run {}: {}", cli, e),
+            Some("Reinstall the CLI with `npm install -g @anthropic-ai/claude-code`."),
+        ),
+    }
+}
+
+/// Claude CLI login, via `claude auth status`.
+pub fn check_auth(cli: &str) -> RalphPreflightCheck {
+    let env_key = std::env::var("ANTHROPIC_API_KEY").map(|k| !k.trim().is_empty()).unwrap_or(false);
+    match proc::run(Command::new(cli).args(["auth", "status"]), CHECK_LIMITS) {
+        Ok(out) if out.timed_out => check(
+            AUTH_ID,
+            "Claude CLI login",
+            "warn",
+            "`claude auth status` did not finish".to_string(),
+            Some(LOGIN_FIX),
+        ),
+        Ok(out) => auth_check(
+            out.success(),
+            &String::from_utf8_lossy(&out.stdout),
+            &String::from_utf8_lossy(&out.stderr),
+            env_key,
+        ),
+        Err(e) => check(AUTH_ID, "Claude CLI login", "fail", format!("Could not run {}: {}", cli, e), Some(LOGIN_FIX)),
+    }
+}
+
+/// Project directory exists, and git repository status. Without `require_git` a
+/// non-repository is a warning (changed files are not tracked per iteration).
+pub fn check_project(path: &str, require_git: bool) -> Vec<RalphPreflightCheck> {
+    let dir = Path::new(path);
+    if !dir.is_dir() {
+        return vec![check(
+            "project_path",
+            "Project folder",
+            "fail",
+            format!("{} does not exist or is not a folder", path),
+            Some("Restore the folder or update the project's location."),
+        )];
+    }
+
+    let mut checks = vec![check("project_path", "Project folder", "pass", path.to_string(), None)];
+    let is_repo = proc::run(
+        Command::new("git").args(["rev-parse", "--is-inside-work-tree"]).current_dir(dir),
+        CHECK_LIMITS,
+    )
+    .map(|out| out.success() && String::from_utf8_lossy(&out.stdout).trim() == "true")
+    .unwrap_or(false);
+
+    checks.push(match (is_repo, require_git) {
+        (true, _) => check("git_repo", "Git repository", "pass", "Project is a git repository".to_string(), None),
+        (false, true) => check(
+            "git_repo",
+            "Git repository",
+            "fail",
+            "This loop commits or switches branches, but the project is not a git repository".to_string(),
+            Some("Run `git init` in the project folder and make an initial commit."),
+        ),
+        (false, false) => check(
+            "git_repo",
+            "Git repository",
+            "warn",
+            "Not a git repository; changed files will not be tracked per iteration".to_string(),
+            Some("Run `git init` in the project folder to review each iteration's changes."),
+        ),
+    });
+    checks
+}
+
+/// API key used to extract issues between iterations.
+pub fn check_api_key(has_key: bool) -> RalphPreflightCheck {
+    if has_key {
+        check("api_key", "API key", "pass", "Configured for issue extraction".to_string(), None)
+    } else {
+        check(
+            "api_key",
+            "API key",
+            "warn",
+            "No API key; issues will not be extracted between iterations".to_string(),
+            Some("Add your Anthropic API key in Settings."),
+        )
+    }
+}
+
+/// Free space on the volume holding `path`.
+pub fn check_disk(path: &str) -> RalphPreflightCheck {
+    let available = if cfg!(unix) && Path::new(path).exists() {
+        proc::run(Command::new("df").args(["-Pk", path]), CHECK_LIMITS)
+            .ok()
+            .filter(|out| out.success())
+            .and_then(|out| parse_df_available(&String::from_utf8_lossy(&out.stdout)))
+    } else {
+        None
+    };
+    disk_check(available)
+}
+
+/// Run every check for a project. `cli` is the resolved Claude CLI path.
+pub fn preflight(cli: Option<&str>, project_path: &str, require_git: bool, has_api_key: bool) -> RalphPreflight {
+    let mut checks = vec![check_cli(cli)];
+    match cli {
+        Some(cli) if checks[0].status == "pass" => checks.push(check_auth(cli)),
+        _ => checks.push(check(
+            AUTH_ID,
+            "Claude CLI login",
+            "skipped",
+            "Needs a working Claude CLI".to_string(),
+            None,
+        )),
+    }
+    checks.extend(check_project(project_path, require_git));
+    checks.push(check_api_key(has_api_key));
+    checks.push(check_disk(project_path));
+
+    RalphPreflight {
+        ready: checks.iter().all(|c| c.status != "fail"),
+        checks,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_cli_version() {
+        assert_eq!(parse_cli_version("1.0.58 (Claude Code)\n").as_deref(), Some("1.0.58"));
+        assert_eq!(parse_cli_version("claude v2.1.0").as_deref(), Some("2.1.0"));
+        assert_eq!(parse_cli_version("Claude Code"), None);
+    }
+
+    #[test]
+    fn test_auth_check() {
+        assert_eq!(auth_check(true, "Logged in as dev@example.com\n", "", false).status, "pass");
+        let out = auth_check(false, "", "Not logged in. Run /login.", false);
+        assert_eq!(out.status, "fail");
+        assert_eq!(out.detail, "Not logged in. Run /login.");
+        assert_eq!(out.fix.as_deref(), Some(LOGIN_FIX));
+        assert_eq!(auth_check(true, "Status: not logged in", "", false).status, "fail");
+        assert_eq!(auth_check(false, "", "error: unknown command 'auth'", false).status, "warn");
+        assert_eq!(auth_check(false, "", "Not logged in", true).status, "pass");
+    }
+
+    #[test]
+    fn test_parse_df_and_disk_check() {
+        let df = "Filesystem     1024-blocks      Used Available Capacity Mounted on\n\
+                  /dev/sda1        102400000  50000000  2097152      49% /\n";
+        assert_eq!(parse_df_available(df), Some(2 * 1024 * 1024 * 1024));
+        assert_eq!(parse_df_available("Filesystem\n"), None);
+
+        assert_eq!(disk_check(Some(2 * LOW_FREE_BYTES)).status, "pass");
+        assert_eq!(disk_check(Some(MIN_FREE_BYTES)).status, "warn");
+        let low = disk_check(Some(100 * 1024 * 1024));
+        assert_eq!((low.status.as_str(), low.detail.as_str()), ("fail", "Only 100 MB free"));
+        assert_eq!(disk_check(None).status, "skipped");
+    }
+
+    #[test]
+    fn test_preflight_without_cli_or_folder() {
+        let report = preflight(None, "/nonexistent/jumpstart-preflight", true, false);
+        let statuses: Vec<(&str, &str)> = report.checks.iter().map(|c| (c.id.as_str(), c.status.as_str())).collect();
+        assert_eq!(
+            statuses,
+            vec![
+                ("claude_cli", "fail"),
+                ("claude_auth", "skipped"),
+                ("project_path", "fail"),
+                ("api_key", "warn"),
+                ("disk_space", "skipped"),
+            ]
+        );
+        assert!(!report.ready);
+    }
+}
@@ -0,0 +1,347 @@
+//! @module core/readme
+//! @description Compose a README section from module docs, CLAUDE.md, and scanner results
+//!
+//! PURPOSE:
+//! - Pull the Overview/Architecture text out of CLAUDE.md
+//! - Build a module map from the @module/@description headers of documented files
+//! - Derive setup commands from the detected stack (scanner), falling back to CLAUDE.md
+//! - Merge the generated section into an existing README between marker comments
+//!
+//! DEPENDENCIES:
+//! - core::analyzer - Module scan and doc header parsing
+//! - core::scanner - Stack and package manager detection for setup commands
+//! - core::generator - setup_commands for a detected stack
+//! - models::readme - ReadmeOptions
+//!
+//! EXPORTS:
+//! - README_START / README_END - Marker comments around the generated section
+//! - ModuleEntry - A documented file's path, @module, and @description
+//! - collect_module_entries - Documented modules of a project, sorted by path
+//! - claude_md_sections - Body text of named "## " sections of CLAUDE.md
+//! - setup_commands_for - Setup commands for a project directory
+//! - render_section - Markdown for the generated section (markers included)
+//! - build_section - Collect everything and render the section for a project
+//! - merge_readme - Insert or replace the section in README content
+//!
+//! PATTERNS:
+//! - Only text between README_START and README_END is ever replaced; a README
+//!   without markers gets the section appended, never rewritten
+//! - Sections are "## Architecture", "## Module Map", "## Setup"; any can be turned off
+//!
+//! CLAUDE NOTES:
+//! - Files with status "missing" (no doc header) are left out of the module map
+//! - Pipes in descriptions are escaped so they cannot break the markdown tables
+
+use std::fs;
+use std::path::Path;
+
+use crate::core::package_manager::PackageManager;
+use crate::core::{analyzer, generator, scanner};
+use crate::models::readme::ReadmeOptions;
+
+pub const README_START: &str = "<!-- project-jumpstart:readme:start -->";
+pub const README_END: &str = "<!-- project-jumpstart:readme:end -->";
+
+/// Comment placed under README_START so readers know the section is generated.
+const GENERATED_NOTE: &str = "<!-- Generated by Project Jumpstart from module docs and CLAUDE.md. \
+                              Edits between these markers are replaced on regeneration. -->";
+
+/// Upper bound on modules listed in the module map.
+const MAX_MODULES: usize = 300;
+
+/// A documented file in the module map.
+#[derive(Debug, Clone, PartialEq)]
+pub struct ModuleEntry {
+    /// Project-relative file path
+    pub path: String,
+    pub module: String,
+    pub description: String,
+}
+
+/// Documented modules of a project, sorted by path.
+pub fn collect_module_entries(project_path: &str) -> Result<Vec<ModuleEntry>, String> {
+    let modules = analyzer::scan_all_modules(project_path)?;
+    let root = Path::new(project_path);
+
+    Ok(modules
+        .iter()
+        .filter(|m| m.status != "missing")
+        .take(MAX_MODULES)
+        .filter_map(|m| {
+            let file = root.join(&m.path);
+            let content = analyzer::read_source(&file.to_string_lossy()).ok()?;
+            let doc = analyzer::parse_doc_header(&content)?;
+            Some(ModuleEntry {
+                path: m.path.clone(),
+                module: doc.module_path,
+                description: doc.description,
+            })
+        })
+        .collect())
+}
+
+/// Body text of the "## " sections whose titles start with one of `titles`
+/// (case-insensitive), in document order and joined by blank lines.
+pub fn claude_md_sections(claude_md: &str, titles: &[&str]) -> Option<String> {
+    let mut bodies = Vec::new();
+    let mut current: Option<Vec<&str>> = None;
+    let mut in_fence = false;
+    let wanted: Vec<String> = titles.iter().map(|t| t.to_ascii_lowercase()).collect();
+
+    for line in claude_md.lines() {
+        if line.trim_start().starts_with("```") {
+            in_fence = !in_fence;
+        }
+        // "# comment" lines inside code blocks are not headings
+        let heading = !in_fence && line.starts_with('#');
+        if let Some(title) = line.strip_prefix("## ").filter(|_| heading) {
+            if let Some(body) = current.take() {
+                bodies.push(body);
+            }
+            let title = title.trim().to_ascii_lowercase();
+            if wanted.iter().any(|t| title == *t || title.starts_with(&format!("{} ", t))) {
+                current = Some(Vec::new());
+            }
+        } else if heading && line.starts_with("# ") {
+            if let Some(body) = current.take() {
+                bodies.push(body);
+            }
+        } else if let Some(body) = current.as_mut() {
+            body.push(line);
+        }
+    }
+    if let Some(body) = current {
+        bodies.push(body);
+    }
+
+    let text = bodies
+        .iter()
+        .map(|b| b.join("\n").trim().to_string())
+        .filter(|b| !b.is_empty())
+        .collect::<Vec<_>>()
+        .join("\n\n");
+    if text.is_empty() {
+        None
+    } else {
+        Some(text)
+    }
+}
+
+/// Setup commands for a project: from the scanner's detected stack, or the
+/// CLAUDE.md Commands code block when no language is detected.
+pub fn setup_commands_for(project_path: &str, claude_md: Option<&str>) -> Vec<String> {
+    if let Ok(detected) = scanner::scan_project_dir(project_path) {
+        if let Some(language) = detected.language {
+            return generator::setup_commands(
+                &language.value,
+                detected.framework.as_ref().map(|f| f.value.as_str()),
+                detected.testing.as_ref().map(|t| t.value.as_str()),
+                detected.package_manager.as_ref().and_then(|pm| PackageManager::parse(&pm.value)),
+            );
+        }
+    }
+
+    claude_md
+        .and_then(|md| claude_md_sections(md, &["Commands"]))
+        .map(|body| {
+            body.lines()
+                .filter(|l| !l.trim_start().starts_with("```"))
+                .filter(|l| !l.trim().is_empty())
+                .map(str::to_string)
+                .collect()
+        })
+        .unwrap_or_default()
+}
+
+/// Render the generated README section, markers included.
+pub fn render_section(
+    architecture: Option<&str>,
+    modules: &[ModuleEntry],
+    setup: &[String],
+    options: &ReadmeOptions,
+) -> String {
+    let mut lines: Vec<String> = vec![README_START.to_string(), GENERATED_NOTE.to_string()];
+
+    if options.include_architecture {
+        if let Some(text) = architecture {
+            lines.push(String::new());
+            lines.push("## Architecture".to_string());
+            lines.push(String::new());
+            lines.push(text.trim().to_string());
+        }
+    }
+
+    if options.include_module_map && !modules.is_empty() {
+        lines.push(String::new());
+        lines.push("## Module Map".to_string());
+        let mut current_dir: Option<&str> = None;
+        for entry in modules {
+            let dir = entry.path.rsplit_once('/').map(|(d, _)| d).unwrap_or(".");
+            if current_dir != Some(dir) {
+                current_dir = Some(dir);
+                lines.push(String::new());
+                lines.push(format!("### `{}`", dir));
+                lines.push(String::new());
+                lines.push("| Module | Description |".to_string());
+                lines.push("|--------|-------------|".to_string());
+            }
+            lines.push(format!(
+                "| [`{}`]({}) | {} |",
+                entry.module,
+                entry.path,
+                entry.description.replace('|', "\\|")
+            ));
+        }
+    }
+
+    if options.include_setup && !setup.is_empty() {
+        lines.push(String::new());
+        lines.push("## Setup".to_string());
+        lines.push(String::new());
+        lines.push("```bash".to_string());
+        lines.extend(setup.iter().cloned());
+        lines.push("```".to_string());
+    }
+
+    lines.push(String::new());
+    lines.push(README_END.to_string());
+    lines.join("\n")
+}
+
+/// Gather module docs, CLAUDE.md, and scanner results for a project and render the section.
+pub fn build_section(project_path: &str, options: &ReadmeOptions) -> Result<String, String> {
+    let claude_md = fs::read_to_string(Path::new(project_path).join("CLAUDE.md")).ok();
+
+    let architecture = if options.include_architecture {
+        claude_md
+            .as_deref()
+            .and_then(|md| claude_md_sections(md, &["Overview", "Architecture"]))
+    } else {
+        None
+    };
+    let modules = if options.include_module_map {
+        collect_module_entries(project_path)?
+    } else {
+        Vec::new()
+    };
+    let setup = if options.include_setup {
+        setup_commands_for(project_path, claude_md.as_deref())
+    } else {
+        Vec::new()
+    };
+
+    Ok(render_section(architecture.as_deref(), &modules, &setup, options))
+}
+
+/// Merge the generated section into README content. Replaces the text between
+/// existing markers, appends when there are none, and starts a new README with
+/// a title (and description) when `existing` is None.
+pub fn merge_readme(existing: Option<&str>, section: &str, title: &str, description: &str) -> String {
+    let section = section.trim_end();
+    let Some(existing) = existing else {
+        let mut out = format!("# {}\n\n", title);
+        if !description.trim().is_empty() {
+            out.push_str(description.trim());
+            out.push_str("\n\n");
+        }
+        out.push_str(section);
+        out.push('\n');
+        return out;
+    };
+
+    if let Some(start) = existing.find(README_START) {
+        if let Some(end) = existing[start..].find(README_END).map(|i| start + i + README_END.len()) {
+            return format!("{}{}{}", &existing[..start], section, &existing[end..]);
+        }
+    }
+
+    let mut out = existing.trim_end().to_string();
+    if !out.is_empty() {
+        out.push_str("\n\n");
+    }
+    out.push_str(section);
+    out.push('\n');
+    out
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const CLAUDE_MD: &str = "# App\n\n## Overview\n\nA todo service.\n\n## Tech Stack\n\n- Rust\n\n\
+        ## Architecture\n\nLayered: commands -> core -> db.\n\n### Database Schema\n\n| Table |\n\n\
+        ## Commands\n\n```bash\n# Run locally\nmake run   # Start\n```\n";
+
+    #[test]
+    fn test_claude_md_sections() {
+        assert_eq!(
+            claude_md_sections(CLAUDE_MD, &["Overview", "Architecture"]).unwrap(),
+            "A todo service.\n\nLayered: commands -> core -> db.\n\n### Database Schema\n\n| Table |"
+        );
+        assert_eq!(claude_md_sections(CLAUDE_MD, &["Missing"]), None);
+    }
+
+    #[test]
+    fn test_render_section() {
+        let modules = vec![
+            ModuleEntry { path: "src/core/a.rs".into(), module: "core/a".into(), description: "A | B".into() },
+            ModuleEntry { path: "src/core/b.rs".into(), module: "core/b".into(), description: "Bee".into() },
+            ModuleEntry { path: "src/db/c.rs".into(), module: "db/c".into(), description: "Sea".into() },
+        ];
+        let setup = vec!["cargo test".to_string()];
+        let section = render_section(Some("Layered."), &modules, &setup, &ReadmeOptions::default());
+        assert!(section.starts_with(README_START));
+        assert!(section.ends_with(README_END));
+        assert!(section.contains("## Architecture\n\nLayered.\n"));
+        assert!(section.contains("### `src/core`\n\n| Module | Description |\n|--------|-------------|\n| [`core/a`](src/core/a.rs) | A \\| B |\n| [`core/b`](src/core/b.rs) | Bee |\n\n### `src/db`"));
+        assert!(section.contains("## Setup\n\n```bash\ncargo test\n```"));
+
+        let options = ReadmeOptions { include_module_map: false, include_setup: false, ..Default::default() };
+        let minimal = render_section(Some("Layered."), &modules, &[], &options);
+        assert!(!minimal.contains("## Module Map"));
+        assert!(!minimal.contains("## Setup"));
+    }
+
+    #[test]
+    fn test_merge_readme() {
+        let section = format!("{}\nnew\n{}", README_START, README_END);
+
+        let fresh = merge_readme(None, &section, "App", "A todo service.");
+        assert_eq!(fresh, format!("# App\n\nA todo service.\n\n{}\n", section));
+
+        let appended = merge_readme(Some("# App\n\nHand-written intro.\n"), &section, "App", "");
+        assert_eq!(appended, format!("# App\n\nHand-written intro.\n\n{}\n", section));
+
+        let existing = format!("# App\n\nIntro.\n\n{}\nold\n{}\n\n## License\n\nMIT\n", README_START, README_END);
+        let replaced = merge_readme(Some(&existing), &section, "App", "");
+        assert_eq!(replaced, format!("# App\n\nIntro.\n\n{}\n\n## License\n\nMIT\n", section));
+        assert_eq!(merge_readme(Some(&replaced), &section, "App", ""), replaced);
+    }
+
+    #[test]
+    fn test_build_section_for_project() {
+        let dir = tempfile::tempdir().unwrap();
+        let root = dir.path();
+        fs::create_dir_all(root.join("src/core")).unwrap();
+        fs::write(root.join("CLAUDE.md"), CLAUDE_MD).unwrap();
+        let mut documented = String::from("//! @module core/math\n//! @description Math helpers\n\n");
+        for i in 0..10 {
+            documented.push_str(&format!("pub fn f{}() {{}}\n", i));
+        }
+        fs::write(root.join("src/core/math.rs"), documented).unwrap();
+        fs::write(root.join("src/core/undocumented.rs"), "pub fn x() {}\n".repeat(12)).unwrap();
+
+        let section = build_section(root.to_str().unwrap(), &ReadmeOptions::default()).unwrap();
+        assert!(section.contains("A todo service."));
+        assert!(section.contains("| [`core/math`](src/core/math.rs) | Math helpers |"));
+        assert!(!section.contains("undocumented"));
+        assert!(section.contains("```bash\ncargo build"));
+
+        // No language detected: commands come from CLAUDE.md
+        let empty = tempfile::tempdir().unwrap();
+        assert_eq!(
+            setup_commands_for(empty.path().to_str().unwrap(), Some(CLAUDE_MD)),
+            vec!["# Run locally", "make run   # Start"]
+        );
+    }
+}
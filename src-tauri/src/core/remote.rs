@@ -0,0 +1,255 @@
+//! @module core/remote
+//! @description Minimal executor for projects on a remote machine or dev container over SSH
+//!
+//! PURPOSE:
+//! - Validate a remote location (ssh host + absolute path)
+//! - Run shell commands in the remote project directory over ssh
+//! - Mirror the remote project into a local directory with rsync for scanning and freshness
+//! - Read and write single remote files (used for git hook install)
+//!
+//! DEPENDENCIES:
+//! - core::proc - ssh/rsync with timeouts and output caps
+//! - sha2 - Stable mirror directory name per host + path
+//! - dirs - Home directory for the mirror root
+//!
+//! EXPORTS:
+//! - RemoteTarget - ssh host and remote project path
+//! - shell_quote - Single-quote a string for a POSIX shell
+//! - mirror_dir - Local mirror directory for a target (~/.project-jumpstart/remote/<hash>)
+//! - run - Run a shell snippet in the remote project directory, returning stdout
+//! - check_connection - Verify ssh works and the remote path is a directory
+//! - sync_to_mirror - rsync the remote project (including .git) into its mirror
+//! - read_file - Read a project-relative remote file
+//! - write_file - Write a project-relative remote file, optionally executable
+//!
+//! PATTERNS:
+//! - Every ssh call uses BatchMode so a missing key fails fast instead of prompting
+//! - Hosts are anything ssh accepts (user@host, an ~/.ssh/config alias); paths must be absolute
+//! - Relative file paths are joined to the remote root and shell-quoted, never interpolated raw
+//!
+//! CLAUDE NOTES:
+//! - A remote project's `path` in the DB is its local mirror, so scanning, freshness, and
+//!   health run unchanged against the mirror; only writes (hooks) go to the remote side
+//! - The mirror is read-only from the app's point of view: sync uses --delete and will
+//!   discard local edits
+//! - proc::run gives children a null stdin, so write_file sends content as a quoted heredoc
+
+use std::path::PathBuf;
+use std::process::Command;
+use std::time::Duration;
+
+use sha2::{Digest, Sha256};
+
+use crate::core::proc::{self, ProcLimits};
+
+/// Single remote commands (cat, test, hook writes).
+const SSH_LIMITS: ProcLimits = ProcLimits::new(Duration::from_secs(60), 4 * 1024 * 1024);
+
+/// A full rsync of the project into its mirror.
+const SYNC_LIMITS: ProcLimits = ProcLimits::new(Duration::from_secs(10 * 60), 1024 * 1024);
+
+/// ssh options shared by ssh and rsync's transport.
+const SSH_OPTIONS: &[&str] = &["-o", "BatchMode=yes", "-o", "ConnectTimeout=10"];
+
+/// Directories not copied into the mirror (build output and dependencies).
+const SYNC_EXCLUDES: &[&str] = &[
+    "node_modules",
+    "target",
+    "dist",
+    "build",
+    ".next",
+    "__pycache__",
+    ".venv",
+    "venv",
+    "coverage",
+    ".turbo",
+];
+
+/// Heredoc delimiter used by write_file.
+const HEREDOC_END: &str = "JUMPSTART_REMOTE_EOF";
+
+/// A project directory on an ssh host.
+#[derive(Debug, Clone, PartialEq)]
+pub struct RemoteTarget {
+    pub host: String,
+    pub path: String,
+}
+
+impl RemoteTarget {
+    /// Validate host and path. Trailing slashes are dropped from the path.
+    pub fn new(host: &str, path: &str) -> Result<Self, String> {
+        let host = host.trim();
+        if host.is_empty() {
+            return Err("Remote host cannot be empty.".to_string());
+        }
+        if host.starts_with('-') || host.chars().any(|c| c.is_whitespace() || c == '\'' || c == '"') {
+            return Err(format!("Invalid remote host: {}", host));
+        }
+        let path = path.trim().trim_end_matches('/');
+        if !path.starts_with('/') {
+            return Err(format!("Remote path must be absolute: {}", path));
+        }
+        Ok(RemoteTarget {
+            host: host.to_string(),
+            path: path.to_string(),
+        })
+    }
+
+    /// "host:/path", for messages and hook paths.
+    pub fn display(&self) -> String {
+        format!("{}:{}", self.host, self.path)
+    }
+}
+
+/// Quote a string for a POSIX shell.
+pub fn shell_quote(s: &str) -> String {
+    format!("'{}'", s.replace('\'', r"'\''"))
+}
+
+/// Local directory the target is mirrored into.
+pub fn mirror_dir(target: &RemoteTarget) -> Result<PathBuf, String> {
+    let home = dirs::home_dir().ok_or("Could not find home directory")?;
+    let digest = Sha256::digest(target.display().as_bytes());
+    let name: String = digest.iter().take(8).map(|b| format!("{:02x}", b)).collect();
+    Ok(home.join(".project-jumpstart").join("remote").join(name))
+}
+
+fn output_text(output: &proc::ProcOutput, what: &str) -> Result<String, String> {
+    if output.timed_out {
+        return Err(format!("{} timed out", what));
+    }
+    if !output.success() {
+        let stderr = String::from_utf8_lossy(&output.stderr);
+        return Err(format!("{} failed: {}", what, stderr.trim()));
+    }
+    Ok(String::from_utf8_lossy(&output.stdout).to_string())
+}
+
+/// Run `script` with the remote project directory as the working directory.
+pub fn run(target: &RemoteTarget, script: &str) -> Result<String, String> {
+    let remote_command = format!("cd {} && {}", shell_quote(&target.path), script);
+    let output = proc::run(
+        Command::new("ssh").args(SSH_OPTIONS).arg(&target.host).arg(remote_command),
+        SSH_LIMITS,
+    )?;
+    output_text(&output, &format!("ssh {}", target.host))
+}
+
+/// Check that ssh connects and the remote path is a directory.
+pub fn check_connection(target: &RemoteTarget) -> Result<(), String> {
+    let out = run(target, "pwd")
+        .map_err(|e| format!("Cannot reach {}: {}", target.display(), e))?;
+    if out.trim().is_empty() {
+        return Err(format!("Cannot read {}", target.display()));
+    }
+    Ok(())
+}
+
+/// rsync arguments for mirroring `target` into `mirror`.
+fn sync_args(target: &RemoteTarget, mirror: &str) -> Vec<String> {
+    let mut args = vec![
+        "-az".to_string(),
+        "--delete".to_string(),
+        "-e".to_string(),
+        format!("ssh {}", SSH_OPTIONS.join(" ")),
+    ];
+    args.extend(SYNC_EXCLUDES.iter().map(|dir| format!("--exclude={}/", dir)));
+    args.push(format!("{}:{}/", target.host, target.path));
+    args.push(format!("{}/", mirror));
+    args
+}
+
+/// Mirror the remote project (with its .git, without dependencies and build output)
+/// into mirror_dir. Returns the mirror path.
+pub fn sync_to_mirror(target: &RemoteTarget) -> Result<PathBuf, String> {
+    let mirror = mirror_dir(target)?;
+    std::fs::create_dir_all(&mirror).map_err(|e| format!("Failed to create mirror directory: {}", e))?;
+    let output = proc::run(
+        Command::new("rsync").args(sync_args(target, &mirror.to_string_lossy())),
+        SYNC_LIMITS,
+    )?;
+    output_text(&output, &format!("rsync from {}", target.display()))?;
+    Ok(mirror)
+}
+
+/// Full remote path of a project-relative path, shell-quoted.
+fn quoted_remote_path(target: &RemoteTarget, rel_path: &str) -> Result<String, String> {
+    let rel = rel_path.trim_start_matches('/');
+    if rel.is_empty() || rel.split('/').any(|part| part == "..") {
+        return Err(format!("Invalid project path: {}", rel_path));
+    }
+    Ok(shell_quote(&format!("{}/{}", target.path, rel)))
+}
+
+/// Read a project-relative file on the remote side.
+pub fn read_file(target: &RemoteTarget, rel_path: &str) -> Result<String, String> {
+    run(target, &format!("cat {}", quoted_remote_path(target, rel_path)?))
+}
+
+/// Shell snippet that writes `content` to an already-quoted path.
+fn write_script(quoted_path: &str, content: &str, executable: bool) -> Result<String, String> {
+    if content.lines().any(|line| line == HEREDOC_END) {
+        return Err("File content contains the heredoc delimiter".to_string());
+    }
+    let mut body = content.to_string();
+    if !body.ends_with('\n') {
+        body.push('\n');
+    }
+    let mut script = format!(
+        "mkdir -p \"$(dirname {path})\" && cat > {path} <<'{end}'\n{body}{end}\n",
+        path = quoted_path,
+        end = HEREDOC_END,
+        body = body,
+    );
+    if executable {
+        script.push_str(&format!("chmod 755 {}\n", quoted_path));
+    }
+    Ok(script)
+}
+
+/// Write a project-relative file on the remote side, creating parent directories.
+pub fn write_file(target: &RemoteTarget, rel_path: &str, content: &str, executable: bool) -> Result<(), String> {
+    let script = write_script(&quoted_remote_path(target, rel_path)?, content, executable)?;
+    run(target, &script).map(|_| ())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_remote_target_validation() {
+        let target = RemoteTarget::new(" dev@box ", "/home/dev/app/").unwrap();
+        assert_eq!(target.display(), "dev@box:/home/dev/app");
+        assert!(RemoteTarget::new("", "/app").is_err());
+        assert!(RemoteTarget::new("-oProxyCommand=x", "/app").is_err());
+        assert!(RemoteTarget::new("box", "app").is_err());
+    }
+
+    #[test]
+    fn test_shell_quote_and_paths() {
+        assert_eq!(shell_quote("it's"), r"'it'\''s'");
+        let target = RemoteTarget::new("box", "/srv/my app").unwrap();
+        assert_eq!(quoted_remote_path(&target, "src/a.rs").unwrap(), "'/srv/my app/src/a.rs'");
+        assert!(quoted_remote_path(&target, "../etc/passwd").is_err());
+        assert!(quoted_remote_path(&target, "").is_err());
+    }
+
+    #[test]
+    fn test_write_script() {
+        let script = write_script("'/srv/app/.git/hooks/pre-commit'", "#!/bin/sh\nexit 0", true).unwrap();
+        assert!(script.starts_with("mkdir -p \"$(dirname '/srv/app/.git/hooks/pre-commit')\""));
+        assert!(script.contains("<<'JUMPSTART_REMOTE_EOF'\n#!/bin/sh\nexit 0\nJUMPSTART_REMOTE_EOF\n"));
+        assert!(script.ends_with("chmod 755 '/srv/app/.git/hooks/pre-commit'\n"));
+        assert!(write_script("'x'", "a\nJUMPSTART_REMOTE_EOF\nb", false).is_err());
+    }
+
+    #[test]
+    fn test_sync_args() {
+        let target = RemoteTarget::new("box", "/srv/app").unwrap();
+        let args = sync_args(&target, "/home/me/mirror");
+        assert_eq!(args[..4], ["-az", "--delete", "-e", "ssh -o BatchMode=yes -o ConnectTimeout=10"]);
+        assert!(args.contains(&"--exclude=node_modules/".to_string()));
+        assert_eq!(args[args.len() - 2..], ["box:/srv/app/", "/home/me/mirror/"]);
+    }
+}
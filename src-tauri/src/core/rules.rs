@@ -0,0 +1,580 @@
+//! @module core/rules
+//! @description Automation rules engine: evaluate user-defined rules on published events and run their actions
+//!
+//! PURPOSE:
+//! - Turn an AppEvent into a flat JSON object of facts (event fields plus derived values)
+//! - Evaluate a rule's conditions against those facts
+//! - Run a matching rule's action (queue doc proposals, run an approved command, log activity)
+//! - Load, store, and record firings of rules (automation_rules, rule_runs tables)
+//!
+//! DEPENDENCIES:
+//! - rusqlite - automation_rules and rule_runs tables
+//! - core::events - Subscriber trait, AppEvent, publish
+//! - core::command_guard - Approval lookup and validated execution for run_command
+//! - core::freshness - Stale files for queue_doc_proposals
+//! - commands::doc_proposals - propose_docs
+//! - models::automation - Rule, condition, action, and run types
+//!
+//! EXPORTS:
+//! - TRIGGERS - Event names a rule can trigger on
+//! - RulesSubscriber - Event bus subscriber that fires matching rules
+//! - event_facts - Facts for an event (camelCase fields plus staleCount for scans)
+//! - evaluate - Per-condition results for a set of facts
+//! - render_message - Fill "{field}" placeholders from facts
+//! - describe_action - One-line description of an action
+//! - validate_rule - Check trigger, fields, and action before storing a rule
+//! - list_rules / insert_rule / list_runs - DB helpers
+//!
+//! PATTERNS:
+//! - Rules are scoped to a project and only see events carrying that project's id
+//! - Slow actions (doc proposals, commands) run on a thread with their own DB connection;
+//!   the subscriber itself only records the run as "started"
+//! - Events published while a rule action runs do not trigger rules again (no cascades)
+//!
+//! CLAUDE NOTES:
+//! - run_command goes through command_guard: a command that is not approved for the project
+//!   is recorded as a "skipped" run and registered as pending approval
+//! - failureType on loop_iteration_finished comes from ralph::categorize_mistake
+
+use std::cell::Cell;
+use std::path::Path;
+
+use chrono::Utc;
+use rusqlite::Connection;
+use serde_json::Value;
+
+use crate::commands::doc_proposals;
+use crate::core::command_guard::{self, CommandDecision};
+use crate::core::events::{self, AppEvent, Subscriber};
+use crate::core::freshness;
+use crate::core::proc::ProcLimits;
+use crate::models::activity::ActivityType;
+use crate::models::automation::{
+    AutomationRule, NewAutomationRule, RuleAction, RuleCondition, RuleConditionResult, RuleOp, RuleRun,
+};
+
+/// Event names a rule can trigger on.
+pub const TRIGGERS: [&str; 4] = ["scan_completed", "loop_iteration_finished", "doc_applied", "activity"];
+
+/// Command output kept on a rule run (the tail, where errors usually are).
+const MAX_RUN_OUTPUT: usize = 8_000;
+
+thread_local! {
+    /// Set while this thread runs a rule action, so the events it publishes don't fire rules.
+    static FIRING: Cell<bool> = const { Cell::new(false) };
+}
+
+/// Run `f` with rule firing suppressed on this thread.
+fn without_rules<T>(f: impl FnOnce() -> T) -> T {
+    let previous = FIRING.with(|firing| firing.replace(true));
+    let result = f();
+    FIRING.with(|firing| firing.set(previous));
+    result
+}
+
+/// Facts a rule's conditions are evaluated against: the event's serialized fields
+/// (camelCase, including "type") plus derived values.
+pub fn event_facts(event: &AppEvent) -> Value {
+    let mut facts = serde_json::to_value(event).unwrap_or(Value::Null);
+    if let (AppEvent::ScanCompleted { missing, outdated, .. }, Some(map)) = (event, facts.as_object_mut()) {
+        map.insert("staleCount".to_string(), Value::from(missing + outdated));
+    }
+    facts
+}
+
+/// Look up a fact by name; dotted names reach into nested objects.
+fn fact<'a>(facts: &'a Value, field: &str) -> Option<&'a Value> {
+    field
+        .split('.')
+        .try_fold(facts, |value, key| value.get(key))
+        .filter(|value| !value.is_null())
+}
+
+fn condition_holds(op: RuleOp, actual: &Value, expected: &Value) -> bool {
+    let numbers = actual.as_f64().zip(expected.as_f64());
+    match op {
+        RuleOp::Eq => numbers.map_or(actual == expected, |(a, b)| a == b),
+        RuleOp::Ne => numbers.map_or(actual != expected, |(a, b)| a != b),
+        RuleOp::Gt => numbers.is_some_and(|(a, b)| a > b),
+        RuleOp::Gte => numbers.is_some_and(|(a, b)| a >= b),
+        RuleOp::Lt => numbers.is_some_and(|(a, b)| a < b),
+        RuleOp::Lte => numbers.is_some_and(|(a, b)| a <= b),
+        RuleOp::Contains => match (actual, expected) {
+            (Value::String(a), Value::String(b)) => a.to_lowercase().contains(&b.to_lowercase()),
+            (Value::Array(items), _) => items.contains(expected),
+            _ => false,
+        },
+    }
+}
+
+/// Evaluate each condition against `facts`. A missing fact fails its condition.
+pub fn evaluate(conditions: &[RuleCondition], facts: &Value) -> Vec<RuleConditionResult> {
+    conditions
+        .iter()
+        .map(|condition| {
+            let actual = fact(facts, &condition.field).cloned();
+            let passed = actual
+                .as_ref()
+                .is_some_and(|actual| condition_holds(condition.op, actual, &condition.value));
+            RuleConditionResult {
+                condition: condition.clone(),
+                actual,
+                passed,
+            }
+        })
+        .collect()
+}
+
+/// Replace "{field}" placeholders with fact values; unknown fields are left as-is.
+pub fn render_message(template: &str, facts: &Value) -> String {
+    let mut out = String::with_capacity(template.len());
+    let mut rest = template;
+    while let Some(start) = rest.find('{') {
+        out.push_str(&rest[..start]);
+        let after = &rest[start + 1..];
+        match after.find('}').map(|end| (end, fact(facts, &after[..end]))) {
+            Some((end, Some(value))) => {
+                match value {
+                    Value::String(s) => out.push_str(s),
+                    other => out.push_str(&other.to_string()),
+                }
+                rest = &after[end + 1..];
+            }
+            _ => {
+                out.push('{');
+                rest = after;
+            }
+        }
+    }
+    out.push_str(rest);
+    out
+}
+
+/// One-line description of what an action does.
+pub fn describe_action(action: &RuleAction) -> String {
+    match action {
+        RuleAction::QueueDocProposals => "Queue doc proposals for every missing or outdated file".to_string(),
+        RuleAction::RunCommand { command } => format!("Run `{}` and attach its output to the rule run", command.trim()),
+        RuleAction::LogActivity { message } => format!("Log activity: {}", message),
+    }
+}
+
+/// Check a rule before it is stored or simulated.
+pub fn validate_rule(rule: &NewAutomationRule) -> Result<(), String> {
+    if rule.name.trim().is_empty() {
+        return Err("Rule name cannot be empty".to_string());
+    }
+    if !TRIGGERS.contains(&rule.trigger.as_str()) {
+        return Err(format!(
+            "Unknown trigger '{}'. Expected one of: {}",
+            rule.trigger,
+            TRIGGERS.join(", ")
+        ));
+    }
+    if let Some(condition) = rule.conditions.iter().find(|c| c.field.trim().is_empty()) {
+        return Err(format!("Condition on '{}' has no field", condition.value));
+    }
+    match &rule.action {
+        RuleAction::RunCommand { command } if command.trim().is_empty() => {
+            Err("run_command needs a command".to_string())
+        }
+        RuleAction::LogActivity { message } if message.trim().is_empty() => {
+            Err("log_activity needs a message".to_string())
+        }
+        _ => Ok(()),
+    }
+}
+
+fn row_to_rule(row: &rusqlite::Row) -> rusqlite::Result<AutomationRule> {
+    let conditions: String = row.get(4)?;
+    let action: String = row.get(5)?;
+    Ok(AutomationRule {
+        id: row.get(0)?,
+        project_id: row.get(1)?,
+        name: row.get(2)?,
+        trigger: row.get(3)?,
+        conditions: serde_json::from_str(&conditions).unwrap_or_default(),
+        action: serde_json::from_str(&action).unwrap_or(RuleAction::LogActivity {
+            message: "Rule action could not be read".to_string(),
+        }),
+        enabled: row.get::<_, i32>(6)? != 0,
+        fire_count: row.get(7)?,
+        last_fired_at: row.get(8)?,
+        created_at: row.get(9)?,
+    })
+}
+
+const RULE_COLUMNS: &str =
+    "id, project_id, name, trigger_event, conditions, action, enabled, fire_count, last_fired_at, created_at";
+
+/// A project's rules, oldest first.
+pub fn list_rules(db: &Connection, project_id: &str) -> Result<Vec<AutomationRule>, String> {
+    let mut stmt = db
+        .prepare(&format!(
+            "SELECT {} FROM automation_rules WHERE project_id = ?1 ORDER BY created_at",
+            RULE_COLUMNS
+        ))
+        .map_err(|e| format!("Failed to query rules: {}", e))?;
+    let rules = stmt
+        .query_map([project_id], row_to_rule)
+        .map_err(|e| format!("Failed to read rules: {}", e))?
+        .filter_map(|r| r.ok())
+        .collect();
+    Ok(rules)
+}
+
+/// Store a validated rule (enabled).
+pub fn insert_rule(db: &Connection, rule: &NewAutomationRule) -> Result<AutomationRule, String> {
+    let stored = AutomationRule {
+        id: uuid::Uuid::new_v4().to_string(),
+        project_id: rule.project_id.clone(),
+        name: rule.name.trim().to_string(),
+        trigger: rule.trigger.clone(),
+        conditions: rule.conditions.clone(),
+        action: rule.action.clone(),
+        enabled: true,
+        fire_count: 0,
+        last_fired_at: None,
+        created_at: Utc::now().to_rfc3339(),
+    };
+    let conditions = serde_json::to_string(&stored.conditions).map_err(|e| format!("Failed to serialize conditions: {}", e))?;
+    let action = serde_json::to_string(&stored.action).map_err(|e| format!("Failed to serialize action: {}", e))?;
+    db.execute(
+        &format!("INSERT INTO automation_rules ({}) VALUES (?1, ?2, ?3, ?4, ?5, ?6, 1, 0, NULL, ?7)", RULE_COLUMNS),
+        rusqlite::params![
+            stored.id,
+            stored.project_id,
+            stored.name,
+            stored.trigger,
+            conditions,
+            action,
+            stored.created_at
+        ],
+    )
+    .map_err(|e| format!("Failed to save rule: {}", e))?;
+    Ok(stored)
+}
+
+/// A project's most recent rule runs, newest first.
+pub fn list_runs(db: &Connection, project_id: &str, limit: u32) -> Result<Vec<RuleRun>, String> {
+    let mut stmt = db
+        .prepare(
+            "SELECT id, rule_id, project_id, loop_id, event, status, output, fired_at, finished_at
+             FROM rule_runs WHERE project_id = ?1 ORDER BY fired_at DESC LIMIT ?2",
+        )
+        .map_err(|e| format!("Failed to query rule runs: {}", e))?;
+    let runs = stmt
+        .query_map(rusqlite::params![project_id, limit], |row| {
+            Ok(RuleRun {
+                id: row.get(0)?,
+                rule_id: row.get(1)?,
+                project_id: row.get(2)?,
+                loop_id: row.get(3)?,
+                event: row.get(4)?,
+                status: row.get(5)?,
+                output: row.get(6)?,
+                fired_at: row.get(7)?,
+                finished_at: row.get(8)?,
+            })
+        })
+        .map_err(|e| format!("Failed to read rule runs: {}", e))?
+        .filter_map(|r| r.ok())
+        .collect();
+    Ok(runs)
+}
+
+/// Insert a run; a finished run (status other than "started") gets finished_at.
+fn insert_run(db: &Connection, rule: &AutomationRule, event: &AppEvent, status: &str, output: &str) -> Result<String, String> {
+    let id = uuid::Uuid::new_v4().to_string();
+    let now = Utc::now().to_rfc3339();
+    let loop_id = match event {
+        AppEvent::LoopIterationFinished { loop_id, .. } => Some(loop_id.clone()),
+        _ => None,
+    };
+    let finished_at = (status != "started").then(|| now.clone());
+    db.execute(
+        "INSERT INTO rule_runs (id, rule_id, project_id, loop_id, event, status, output, fired_at, finished_at)
+         VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7, ?8, ?9)",
+        rusqlite::params![id, rule.id, rule.project_id, loop_id, event.name(), status, output, now, finished_at],
+    )
+    .map_err(|e| format!("Failed to record rule run: {}", e))?;
+    Ok(id)
+}
+
+fn finish_run(db: &Connection, run_id: &str, status: &str, output: &str) {
+    let _ = db.execute(
+        "UPDATE rule_runs SET status = ?1, output = ?2, finished_at = ?3 WHERE id = ?4",
+        rusqlite::params![status, output, Utc::now().to_rfc3339(), run_id],
+    );
+}
+
+/// Last MAX_RUN_OUTPUT bytes of a command's combined output.
+fn output_tail(stdout: &[u8], stderr: &[u8]) -> String {
+    let combined = format!("{}{}", String::from_utf8_lossy(stdout), String::from_utf8_lossy(stderr));
+    if combined.len() <= MAX_RUN_OUTPUT {
+        return combined;
+    }
+    let mut start = combined.len() - MAX_RUN_OUTPUT;
+    while !combined.is_char_boundary(start) {
+        start += 1;
+    }
+    format!("...{}", &combined[start..])
+}
+
+fn open_background_db() -> Result<Connection, String> {
+    Connection::open(crate::db::db_path()?).map_err(|e| format!("Failed to open database: {}", e))
+}
+
+fn project_path(db: &Connection, project_id: &str) -> Option<String> {
+    db.query_row("SELECT path FROM projects WHERE id = ?1", [project_id], |row| row.get(0))
+        .ok()
+}
+
+/// Run a matched rule's action. Slow actions continue on a background thread.
+fn fire(db: &Connection, rule: &AutomationRule, event: &AppEvent, facts: &Value) -> Result<(), String> {
+    db.execute(
+        "UPDATE automation_rules SET fire_count = fire_count + 1, last_fired_at = ?1 WHERE id = ?2",
+        rusqlite::params![Utc::now().to_rfc3339(), rule.id],
+    )
+    .map_err(|e| format!("Failed to update rule: {}", e))?;
+
+    let path = match event {
+        AppEvent::ScanCompleted { project_path, .. } => Some(project_path.clone()),
+        _ => project_path(db, &rule.project_id),
+    };
+
+    match &rule.action {
+        RuleAction::LogActivity { message } => {
+            let message = render_message(message, facts);
+            insert_run(db, rule, event, "ok", &message)?;
+            events::publish(db, AppEvent::activity(&rule.project_id, ActivityType::Info, &message));
+        }
+        RuleAction::QueueDocProposals => {
+            let Some(path) = path else {
+                insert_run(db, rule, event, "skipped", "Project path not found")?;
+                return Ok(());
+            };
+            let run_id = insert_run(db, rule, event, "started", "")?;
+            let (rule_name, project_id) = (rule.name.clone(), rule.project_id.clone());
+            std::thread::spawn(move || {
+                let Ok(db) = open_background_db() else {
+                    return;
+                };
+                without_rules(|| {
+                    let result = freshness::check_project_freshness(&path).and_then(|modules| {
+                        let files: Vec<String> = modules
+                            .into_iter()
+                            .filter(|m| m.status == "missing" || m.status == "outdated")
+                            .map(|m| Path::new(&path).join(&m.path).to_string_lossy().to_string())
+                            .collect();
+                        doc_proposals::propose_docs(&db, &path, &files, "rule")
+                    });
+                    match result {
+                        Ok(queued) => {
+                            let message = format!("Rule '{}' queued {} doc proposals", rule_name, queued);
+                            finish_run(&db, &run_id, "ok", &message);
+                            events::publish(&db, AppEvent::activity(&project_id, ActivityType::Generate, &message));
+                        }
+                        Err(e) => finish_run(&db, &run_id, "failed", &e),
+                    }
+                });
+            });
+        }
+        RuleAction::RunCommand { command } => {
+            let Some(path) = path else {
+                insert_run(db, rule, event, "skipped", "Project path not found")?;
+                return Ok(());
+            };
+            match command_guard::check_command(db, &rule.project_id, &path, command, "rule") {
+                Ok(CommandDecision::Approved(_)) => {}
+                Ok(CommandDecision::Pending) => {
+                    insert_run(db, rule, event, "skipped", &format!("`{}` is awaiting approval", command.trim()))?;
+                    return Ok(());
+                }
+                Ok(CommandDecision::Denied) => {
+                    insert_run(db, rule, event, "skipped", &format!("`{}` was denied for this project", command.trim()))?;
+                    return Ok(());
+                }
+                Err(e) => {
+                    insert_run(db, rule, event, "skipped", &e)?;
+                    return Ok(());
+                }
+            }
+            let run_id = insert_run(db, rule, event, "started", "")?;
+            let (rule_name, project_id, command) = (rule.name.clone(), rule.project_id.clone(), command.clone());
+            std::thread::spawn(move || {
+                let result = command_guard::run_validated(&path, &command, ProcLimits::TESTS);
+                let Ok(db) = open_background_db() else {
+                    return;
+                };
+                without_rules(|| {
+                    let (status, output) = match result {
+                        Ok(output) if output.success() => ("ok", output_tail(&output.stdout, &output.stderr)),
+                        Ok(output) if output.timed_out => ("failed", format!("Timed out\n{}", output_tail(&output.stdout, &output.stderr))),
+                        Ok(output) => ("failed", output_tail(&output.stdout, &output.stderr)),
+                        Err(e) => ("failed", e),
+                    };
+                    finish_run(&db, &run_id, status, &output);
+                    let message = format!("Rule '{}' ran `{}` ({})", rule_name, command.trim(), status);
+                    events::publish(&db, AppEvent::activity(&project_id, ActivityType::Info, &message));
+                });
+            });
+        }
+    }
+    Ok(())
+}
+
+/// Fires the enabled rules of the event's project whose trigger and conditions match.
+pub struct RulesSubscriber;
+
+impl Subscriber for RulesSubscriber {
+    fn handle(&self, db: &Connection, event: &AppEvent) {
+        if FIRING.with(Cell::get) {
+            return;
+        }
+        let Some(project_id) = event.project_id() else {
+            return;
+        };
+        let Ok(rules) = list_rules(db, project_id) else {
+            return;
+        };
+        let matching: Vec<AutomationRule> = rules
+            .into_iter()
+            .filter(|rule| rule.enabled && rule.trigger == event.name())
+            .collect();
+        if matching.is_empty() {
+            return;
+        }
+
+        let facts = event_facts(event);
+        without_rules(|| {
+            for rule in matching {
+                if evaluate(&rule.conditions, &facts).iter().all(|r| r.passed) {
+                    if let Err(e) = fire(db, &rule, event, &facts) {
+                        tracing::warn!(rule = %rule.name, error = %e, "Automation rule failed");
+                    }
+                }
+            }
+        });
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_support::with_project;
+    use serde_json::json;
+
+    fn condition(field: &str, op: RuleOp, value: Value) -> RuleCondition {
+        RuleCondition {
+            field: field.to_string(),
+            op,
+            value,
+        }
+    }
+
+    fn scan(missing: u32, outdated: u32) -> AppEvent {
+        AppEvent::ScanCompleted {
+            project_id: Some("p1".into()),
+            project_path: "/tmp/p1".into(),
+            total: 50,
+            missing,
+            outdated,
+        }
+    }
+
+    #[test]
+    fn test_event_facts_and_evaluate() {
+        let facts = event_facts(&scan(15, 8));
+        assert_eq!(facts["staleCount"], 23);
+        assert_eq!(facts["type"], "scan_completed");
+
+        let results = evaluate(
+            &[
+                condition("staleCount", RuleOp::Gt, json!(20)),
+                condition("missing", RuleOp::Lte, json!(15)),
+                condition("projectPath", RuleOp::Contains, json!("P1")),
+                condition("nope", RuleOp::Eq, json!(1)),
+            ],
+            &facts,
+        );
+        let passed: Vec<bool> = results.iter().map(|r| r.passed).collect();
+        assert_eq!(passed, vec![true, true, true, false]);
+        assert_eq!(results[0].actual, Some(json!(23)));
+        assert_eq!(results[3].actual, None);
+    }
+
+    #[test]
+    fn test_failure_type_condition() {
+        let event = AppEvent::LoopIterationFinished {
+            project_id: "p1".into(),
+            loop_id: "l1".into(),
+            iteration: 3,
+            story_index: None,
+            status: "failed".into(),
+            issues_count: 2,
+            files_changed: 1,
+            failure_type: Some("type_error".into()),
+        };
+        let facts = event_facts(&event);
+        let rule = [condition("failureType", RuleOp::Eq, json!("type_error"))];
+        assert!(evaluate(&rule, &facts)[0].passed);
+        let rule = [condition("status", RuleOp::Ne, json!("failed"))];
+        assert!(!evaluate(&rule, &facts)[0].passed);
+    }
+
+    #[test]
+    fn test_render_message() {
+        let facts = event_facts(&scan(3, 4));
+        assert_eq!(
+            render_message("{staleCount} stale of {total} in {projectPath} {unknown}", &facts),
+            "7 stale of 50 in /tmp/p1 {unknown}"
+        );
+    }
+
+    #[test]
+    fn test_validate_rule() {
+        let mut rule = NewAutomationRule {
+            project_id: "p1".into(),
+            name: "Too stale".into(),
+            trigger: "scan_completed".into(),
+            conditions: vec![condition("staleCount", RuleOp::Gt, json!(20))],
+            action: RuleAction::QueueDocProposals,
+        };
+        assert!(validate_rule(&rule).is_ok());
+        rule.trigger = "nightly".into();
+        assert!(validate_rule(&rule).unwrap_err().contains("Unknown trigger"));
+        rule.trigger = "scan_completed".into();
+        rule.action = RuleAction::RunCommand { command: " ".into() };
+        assert!(validate_rule(&rule).is_err());
+    }
+
+    #[test]
+    fn test_subscriber_fires_matching_rules_once() {
+        let db = with_project();
+        let rule = insert_rule(
+            &db,
+            &NewAutomationRule {
+                project_id: "p1".into(),
+                name: "Stale alert".into(),
+                trigger: "scan_completed".into(),
+                conditions: vec![condition("staleCount", RuleOp::Gt, json!(20))],
+                action: RuleAction::LogActivity {
+                    message: "{staleCount} files are stale".into(),
+                },
+            },
+        )
+        .unwrap();
+
+        RulesSubscriber.handle(&db, &scan(5, 5));
+        RulesSubscriber.handle(&db, &scan(15, 8));
+
+        let stored = &list_rules(&db, "p1").unwrap()[0];
+        assert_eq!(stored.id, rule.id);
+        assert_eq!(stored.fire_count, 1);
+        let runs = list_runs(&db, "p1", 10).unwrap();
+        assert_eq!(runs.len(), 1);
+        assert_eq!(runs[0].status, "ok");
+        assert_eq!(runs[0].output, "23 files are stale");
+    }
+}
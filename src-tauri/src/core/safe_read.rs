@@ -0,0 +1,197 @@
+//! @module core/safe_read
+//! @description Size-capped, lossy-decoding file reads so odd files never abort a project scan
+//!
+//! PURPOSE:
+//! - Read text files with invalid UTF-8 replaced instead of failing the read
+//! - Refuse files over a size cap (and binary files) before loading them
+//! - Stream only the first lines of a file when that is all a caller needs, with a per-line cap
+//! - Cut strings to a byte budget without splitting a UTF-8 character
+//!
+//! DEPENDENCIES:
+//! - std::fs / std::io - Metadata, buffered and bounded reads
+//!
+//! EXPORTS:
+//! - MAX_TEXT_BYTES - Default cap for read_text (config files, manifests, CLAUDE.md)
+//! - MAX_LINE_BYTES - Per-line cap for read_head and lines
+//! - read_text - Whole file as lossy UTF-8, capped at MAX_TEXT_BYTES
+//! - read_text_limited - read_text with a caller-chosen cap
+//! - read_head - First N lines, streamed (never reads the rest of the file)
+//! - lines - Streaming lossy line iterator over a whole file
+//! - is_binary - NUL-byte sniff over the first BINARY_SNIFF_BYTES
+//! - truncate_str - Longest prefix within a byte budget that ends on a char boundary
+//!
+//! PATTERNS:
+//! - Errors are Strings naming the file, like the rest of core ("File too large ...",
+//!   "Binary file ...", "Failed to read ...")
+//! - Invalid UTF-8 becomes U+FFFD; the file is never rejected for its encoding
+//! - Lines longer than MAX_LINE_BYTES (minified bundles) are cut there and the rest of the
+//!   line is skipped without being buffered
+//!
+//! CLAUDE NOTES:
+//! - Use these for anything that only analyzes a file. Code that rewrites a file in place
+//!   (apply_doc_to_file, export sync) keeps strict fs::read_to_string so a lossy decode can
+//!   never be written back over the original bytes
+//! - The cap is checked against metadata first and enforced again while reading, so a file
+//!   that grows mid-read cannot exceed it
+
+use std::fs::File;
+use std::io::{BufRead, BufReader, Read};
+use std::path::Path;
+
+/// Default size cap for read_text.
+pub const MAX_TEXT_BYTES: u64 = 2_000_000;
+
+/// Longest line returned by read_head and lines; the rest of a longer line is skipped.
+pub const MAX_LINE_BYTES: usize = 64 * 1024;
+
+/// Bytes inspected by is_binary.
+const BINARY_SNIFF_BYTES: usize = 8 * 1024;
+
+/// Read a whole file as text, decoding invalid UTF-8 lossily. Fails for files
+/// over MAX_TEXT_BYTES and for binary files.
+pub fn read_text(path: impl AsRef<Path>) -> Result<String, String> {
+    read_text_limited(path, MAX_TEXT_BYTES)
+}
+
+/// read_text with a custom size cap in bytes.
+pub fn read_text_limited(path: impl AsRef<Path>, max_bytes: u64) -> Result<String, String> {
+    let path = path.as_ref();
+    let file = File::open(path).map_err(|e| format!("Failed to read {}: {}", path.display(), e))?;
+    let size = file.metadata().map(|m| m.len()).unwrap_or(0);
+    if size > max_bytes {
+        return Err(format!("File too large ({} bytes, limit {}): {}", size, max_bytes, path.display()));
+    }
+
+    let mut bytes = Vec::with_capacity(size as usize);
+    file.take(max_bytes + 1)
+        .read_to_end(&mut bytes)
+        .map_err(|e| format!("Failed to read {}: {}", path.display(), e))?;
+    if bytes.len() as u64 > max_bytes {
+        return Err(format!("File too large (over {} bytes): {}", max_bytes, path.display()));
+    }
+    if is_binary(&bytes) {
+        return Err(format!("Binary file: {}", path.display()));
+    }
+    Ok(match String::from_utf8(bytes) {
+        Ok(text) => text,
+        Err(e) => String::from_utf8_lossy(e.as_bytes()).into_owned(),
+    })
+}
+
+/// The first `max_lines` lines of a file joined with "\n", read line by line so
+/// large files are never loaded. Invalid UTF-8 is decoded lossily.
+pub fn read_head(path: impl AsRef<Path>, max_lines: usize) -> Result<String, String> {
+    Ok(lines(path)?.take(max_lines).collect::<Vec<_>>().join("\n"))
+}
+
+/// Stream a file's lines (without line endings), decoding each lossily and cutting each at
+/// MAX_LINE_BYTES. Stops at the first read error.
+pub fn lines(path: impl AsRef<Path>) -> Result<impl Iterator<Item = String>, String> {
+    let path = path.as_ref();
+    let file = File::open(path).map_err(|e| format!("Failed to read {}: {}", path.display(), e))?;
+    let mut reader = BufReader::new(file);
+    let mut buf = Vec::new();
+    Ok(std::iter::from_fn(move || {
+        buf.clear();
+        match (&mut reader).take(MAX_LINE_BYTES as u64).read_until(b'\n', &mut buf) {
+            Ok(0) | Err(_) => None,
+            Ok(_) => {
+                if buf.len() == MAX_LINE_BYTES && buf.last() != Some(&b'\n') {
+                    skip_line(&mut reader).ok()?;
+                }
+                while matches!(buf.last(), Some(b'\n' | b'\r')) {
+                    buf.pop();
+                }
+                Some(String::from_utf8_lossy(&buf).into_owned())
+            }
+        }
+    }))
+}
+
+/// Skip the rest of the current line without buffering it.
+fn skip_line(reader: &mut impl BufRead) -> std::io::Result<()> {
+    loop {
+        let available = reader.fill_buf()?;
+        if available.is_empty() {
+            return Ok(());
+        }
+        match available.iter().position(|&b| b == b'\n') {
+            Some(i) => {
+                reader.consume(i + 1);
+                return Ok(());
+            }
+            None => {
+                let n = available.len();
+                reader.consume(n);
+            }
+        }
+    }
+}
+
+/// Whether content looks binary: a NUL byte in its first BINARY_SNIFF_BYTES.
+pub fn is_binary(bytes: &[u8]) -> bool {
+    bytes[..bytes.len().min(BINARY_SNIFF_BYTES)].contains(&0)
+}
+
+/// The longest prefix of `text` that fits in `max_bytes` without splitting a character.
+pub fn truncate_str(text: &str, max_bytes: usize) -> &str {
+    if text.len() <= max_bytes {
+        return text;
+    }
+    let mut cut = max_bytes;
+    while !text.is_char_boundary(cut) {
+        cut -= 1;
+    }
+    &text[..cut]
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_read_text_lossy_and_capped() {
+        let dir = tempfile::tempdir().unwrap();
+        let latin1 = dir.path().join("vendor.js");
+        std::fs::write(&latin1, b"// caf\xe9\nexport const a = 1;\n").unwrap();
+        let text = read_text(&latin1).unwrap();
+        assert!(text.starts_with("// caf\u{fffd}\n"));
+        assert!(text.contains("export const a"));
+
+        let big = dir.path().join("big.js");
+        std::fs::write(&big, "x".repeat(100)).unwrap();
+        assert!(read_text_limited(&big, 99).unwrap_err().contains("too large"));
+        assert_eq!(read_text_limited(&big, 100).unwrap().len(), 100);
+
+        let binary = dir.path().join("blob.ts");
+        std::fs::write(&binary, b"\x00\x01\x02").unwrap();
+        assert!(read_text(&binary).unwrap_err().contains("Binary"));
+        assert!(read_text(dir.path().join("missing.ts")).is_err());
+    }
+
+    #[test]
+    fn test_read_head_and_lines() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("a.py");
+        std::fs::write(&path, b"# one\r\n# tw\xffo\nthree\nfour").unwrap();
+        assert_eq!(read_head(&path, 2).unwrap(), "# one\n# tw\u{fffd}o");
+        assert_eq!(lines(&path).unwrap().count(), 4);
+        assert_eq!(lines(&path).unwrap().last().unwrap(), "four");
+
+        let minified = dir.path().join("bundle.min.js");
+        let mut content = "x".repeat(MAX_LINE_BYTES * 3);
+        content.push_str("\nsecond\n");
+        std::fs::write(&minified, content).unwrap();
+        assert_eq!(read_head(&minified, 1).unwrap().len(), MAX_LINE_BYTES);
+        let all: Vec<String> = lines(&minified).unwrap().collect();
+        assert_eq!(all.len(), 2);
+        assert_eq!(all[1], "second");
+    }
+
+    #[test]
+    fn test_truncate_str() {
+        assert_eq!(truncate_str("héllo", 2), "h");
+        assert_eq!(truncate_str("héllo", 3), "hé");
+        assert_eq!(truncate_str("hi", 10), "hi");
+    }
+}
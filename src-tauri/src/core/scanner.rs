@@ -9,12 +9,18 @@
 //!
 //! DEPENDENCIES:
 //! - std::path - File path operations
-//! - std::fs - File system reading
+//! - std::fs - Directory listing
+//! - core::safe_read - Lossy, size-capped reads of manifests and HTML files
 //! - serde_json - Parse package.json
+//! - core::package_manager - Package manager detection
 //! - models::project - DetectionResult, DetectedValue types
 //!
 //! EXPORTS:
 //! - scan_project_dir - Main scanning function that returns DetectionResult
+//! - detect_package_manager - npm/pnpm/yarn/bun/uv/poetry/pipenv from lockfiles and manifests
+//! - Archetype - Project layout archetype (frontend, Django, Rails, Spring, Go service, CLI tool)
+//! - detect_archetype - Detect the layout archetype used by template doc inference
+//! - detect_source_layout - Source directories, extensions present, and monorepo path filters
 //!
 //! PATTERNS:
 //! - High confidence: config file signals (package.json -> TypeScript/JavaScript)
@@ -30,13 +36,19 @@
 //! - CDN detection scans .html files in project root for known CDN URLs
 //! - Extension confidence uses proportion: (lang_count / total_source_files) * 0.85
 //! - Chrome Extension detection: manifest.json with manifest_version field
+//! - Archetype priority: Django > Rails > Spring > CLI tool > Go service > frontend > generic;
+//!   CLI is checked before Go service so cobra/urfave binaries are not treated as servers
+//! - Source layout reads workspaces from package.json, lerna.json, pnpm-workspace.yaml,
+//!   Cargo.toml [workspace] members, and go.work; only "dir", "dir/*" and "dir/**" patterns expand
 //! - See spec Part 5.1 for full scanner specification
 
 use std::collections::HashMap;
 use std::fs;
 use std::path::Path;
 
-use crate::models::project::{DetectedValue, DetectionResult};
+use crate::core::package_manager;
+use crate::core::safe_read;
+use crate::models::project::{DetectedValue, DetectionResult, SourceLayout};
 
 /// Scan a project directory and return detection results.
 /// This is the primary entry point for project analysis.
@@ -76,6 +88,12 @@ pub fn scan_project_dir(path: &str) -> Result<DetectionResult, String> {
     // Detect project type
     let project_type = detect_project_type(project_path, &language, &framework);
 
+    // Detect package manager (lockfiles, package.json packageManager)
+    let package_manager = detect_package_manager(project_path);
+
+    // Detect layout archetype for doc inference
+    let archetype = detect_archetype(project_path).as_str().to_string();
+
     // Overall confidence is based on highest signal strength
     let confidence = if language.as_ref().is_some_and(|l| l.confidence >= 0.9) {
         "high"
@@ -94,28 +112,52 @@ pub fn scan_project_dir(path: &str) -> Result<DetectionResult, String> {
         database,
         testing,
         styling,
+        package_manager,
         project_name,
         project_type,
+        archetype,
         file_count,
         has_existing_claude_md,
     })
 }
 
+/// Detect the project's package manager. A declared packageManager is more certain than a lockfile,
+/// and a lockfile more than a pyproject.toml table.
+pub fn detect_package_manager(path: &Path) -> Option<DetectedValue> {
+    package_manager::detect(path).map(|(pm, source)| {
+        let confidence = if source.starts_with("package.json") {
+            0.95
+        } else if source.starts_with("pyproject.toml") {
+            0.8
+        } else {
+            0.9
+        };
+        DetectedValue {
+            value: pm.as_str().to_string(),
+            confidence,
+            source,
+        }
+    })
+}
+
+/// Directories never counted as source (dependencies, build output, virtualenvs).
+const IGNORE_DIRS: [&str; 9] = [
+    "node_modules",
+    "target",
+    ".git",
+    "dist",
+    "build",
+    ".next",
+    "__pycache__",
+    ".venv",
+    "venv",
+];
+
 /// Count source files in the project directory (non-recursive for top-level,
 /// recursive for src/ and lib/ directories). Excludes node_modules, target, .git, etc.
 fn count_source_files(path: &Path) -> u32 {
     let mut count = 0u32;
-    let ignore_dirs = [
-        "node_modules",
-        "target",
-        ".git",
-        "dist",
-        "build",
-        ".next",
-        "__pycache__",
-        ".venv",
-        "venv",
-    ];
+    let ignore_dirs = IGNORE_DIRS;
 
     fn walk_dir(dir: &Path, count: &mut u32, ignore_dirs: &[&str], depth: u32) {
         if depth > 10 {
@@ -144,11 +186,204 @@ fn count_source_files(path: &Path) -> u32 {
 }
 
 fn is_source_file(name: &str) -> bool {
-    let source_extensions = [
-        ".ts", ".tsx", ".js", ".jsx", ".rs", ".py", ".go", ".dart", ".java", ".kt", ".swift",
-        ".rb", ".php", ".cs", ".cpp", ".c", ".h", ".vue", ".svelte",
+    source_extension(name).is_some()
+}
+
+/// The source extension of a file name (without the dot), if it is a source file.
+fn source_extension(name: &str) -> Option<&'static str> {
+    const SOURCE_EXTENSIONS: [&str; 19] = [
+        "ts", "tsx", "js", "jsx", "rs", "py", "go", "dart", "java", "kt", "swift",
+        "rb", "php", "cs", "cpp", "c", "h", "vue", "svelte",
     ];
-    source_extensions.iter().any(|ext| name.ends_with(ext))
+    let ext = name.rsplit_once('.')?.1;
+    SOURCE_EXTENSIONS.iter().copied().find(|e| *e == ext)
+}
+
+// ---------------------------------------------------------------------------
+// Source layout (for stack-aware CI snippets)
+// ---------------------------------------------------------------------------
+
+/// Conventional source directories, checked under the root and under each workspace package.
+const SOURCE_DIR_CANDIDATES: [&str; 8] = ["src", "lib", "app", "pkg", "cmd", "internal", "source", "src-tauri/src"];
+
+/// Files inspected per source directory when collecting extensions.
+const LAYOUT_FILE_LIMIT: usize = 20_000;
+
+/// Where a project's sources live: the directories holding them, the source
+/// extensions present there (most common first), and for monorepos the workspace
+/// package patterns as CI path filters ("packages/**").
+pub fn detect_source_layout(path: &Path) -> SourceLayout {
+    let patterns = workspace_patterns(path);
+    let packages = expand_workspace_patterns(path, &patterns);
+    let roots: Vec<String> = if packages.is_empty() { vec![String::new()] } else { packages };
+
+    let mut source_dirs = Vec::new();
+    let mut counts: HashMap<&'static str, usize> = HashMap::new();
+    for root in &roots {
+        let base = if root.is_empty() { path.to_path_buf() } else { path.join(root) };
+        let mut found = false;
+        for candidate in SOURCE_DIR_CANDIDATES {
+            let dir = base.join(candidate);
+            if dir.is_dir() && collect_extensions(&dir, &mut counts) > 0 {
+                source_dirs.push(join_rel(root, candidate));
+                found = true;
+            }
+        }
+        // Flat layouts (Go modules, Python packages at the root) are checked as a whole
+        if !found && collect_extensions(&base, &mut counts) > 0 {
+            source_dirs.push(if root.is_empty() { ".".to_string() } else { root.clone() });
+        }
+    }
+
+    let mut extensions: Vec<(&str, usize)> = counts.into_iter().collect();
+    extensions.sort_by(|a, b| b.1.cmp(&a.1).then(a.0.cmp(b.0)));
+
+    let mut path_filters: Vec<String> = Vec::new();
+    for pattern in &patterns {
+        let base = pattern.trim_end_matches("/**").trim_end_matches("/*").trim_matches('/');
+        let filter = format!("{}/**", base);
+        if !base.is_empty() && !path_filters.contains(&filter) {
+            path_filters.push(filter);
+        }
+    }
+
+    SourceLayout {
+        source_dirs,
+        extensions: extensions.into_iter().map(|(ext, _)| ext.to_string()).collect(),
+        path_filters,
+    }
+}
+
+fn join_rel(root: &str, dir: &str) -> String {
+    if root.is_empty() {
+        dir.to_string()
+    } else {
+        format!("{}/{}", root, dir)
+    }
+}
+
+/// Count source files under `dir` by extension; returns how many were found.
+fn collect_extensions(dir: &Path, counts: &mut HashMap<&'static str, usize>) -> usize {
+    fn walk(dir: &Path, counts: &mut HashMap<&'static str, usize>, seen: &mut usize, depth: u32) {
+        if depth > 10 || *seen >= LAYOUT_FILE_LIMIT {
+            return;
+        }
+        let Ok(entries) = fs::read_dir(dir) else {
+            return;
+        };
+        for entry in entries.flatten() {
+            let name = entry.file_name().to_string_lossy().to_string();
+            let path = entry.path();
+            if path.is_dir() {
+                if !IGNORE_DIRS.contains(&name.as_str()) && !name.starts_with('.') {
+                    walk(&path, counts, seen, depth + 1);
+                }
+            } else if let Some(ext) = source_extension(&name) {
+                *counts.entry(ext).or_default() += 1;
+                *seen += 1;
+            }
+        }
+    }
+
+    let mut seen = 0;
+    walk(dir, counts, &mut seen, 0);
+    seen
+}
+
+/// Workspace package patterns from package.json / lerna.json "workspaces"/"packages",
+/// pnpm-workspace.yaml, Cargo.toml [workspace] members, and go.work `use` lines.
+/// Negated patterns ("!packages/legacy") are dropped.
+fn workspace_patterns(path: &Path) -> Vec<String> {
+    let mut patterns: Vec<String> = Vec::new();
+
+    for (file, key) in [("package.json", "workspaces"), ("lerna.json", "packages")] {
+        let Ok(json) = safe_read::read_text(path.join(file)) else {
+            continue;
+        };
+        let Ok(value) = serde_json::from_str::<serde_json::Value>(&json) else {
+            continue;
+        };
+        let list = value.get(key).map(|v| v.get("packages").unwrap_or(v));
+        if let Some(items) = list.and_then(|v| v.as_array()) {
+            patterns.extend(items.iter().filter_map(|p| p.as_str()).map(str::to_string));
+        }
+    }
+
+    if let Ok(yaml) = safe_read::read_text(path.join("pnpm-workspace.yaml")) {
+        let mut in_packages = false;
+        for line in yaml.lines() {
+            let trimmed = line.trim();
+            if !line.starts_with(' ') && !line.starts_with('-') && !trimmed.is_empty() {
+                in_packages = trimmed.starts_with("packages:");
+            } else if in_packages {
+                if let Some(item) = trimmed.strip_prefix('-') {
+                    patterns.push(item.trim().trim_matches(['\'', '"']).to_string());
+                }
+            }
+        }
+    }
+
+    if let Ok(toml) = safe_read::read_text(path.join("Cargo.toml")) {
+        if let Some(workspace) = toml.split("[workspace]").nth(1) {
+            let section = workspace.split("\n[").next().unwrap_or_default();
+            if let Some(members) = section.split("members").nth(1).and_then(|m| m.split_once('[')) {
+                let list = members.1.split(']').next().unwrap_or_default();
+                patterns.extend(list.split(',').map(|m| m.trim().trim_matches('"').to_string()));
+            }
+        }
+    }
+
+    if let Ok(gowork) = safe_read::read_text(path.join("go.work")) {
+        let mut in_block = false;
+        for line in gowork.lines().map(|l| l.split("//").next().unwrap_or_default().trim()) {
+            if line.starts_with("use (") {
+                in_block = true;
+            } else if in_block && line == ")" {
+                in_block = false;
+            } else if in_block || line.starts_with("use ") {
+                let dir = line.trim_start_matches("use ").trim();
+                if !dir.is_empty() {
+                    patterns.push(dir.to_string());
+                }
+            }
+        }
+    }
+
+    let mut cleaned: Vec<String> = Vec::new();
+    for pattern in patterns {
+        let pattern = pattern.trim().trim_start_matches("./").trim_end_matches('/').to_string();
+        if !pattern.is_empty() && pattern != "." && !pattern.starts_with('!') && !cleaned.contains(&pattern) {
+            cleaned.push(pattern);
+        }
+    }
+    cleaned
+}
+
+/// Package directories (project-relative, sorted) matching workspace patterns.
+/// Supports exact directories and a trailing "/*" or "/**" (one level of packages).
+fn expand_workspace_patterns(path: &Path, patterns: &[String]) -> Vec<String> {
+    let mut packages = Vec::new();
+    for pattern in patterns {
+        let parent = pattern.strip_suffix("/**").or_else(|| pattern.strip_suffix("/*"));
+        match parent {
+            Some(parent) => {
+                let Ok(entries) = fs::read_dir(path.join(parent)) else {
+                    continue;
+                };
+                for entry in entries.flatten().filter(|e| e.path().is_dir()) {
+                    let name = entry.file_name().to_string_lossy().to_string();
+                    if !name.starts_with('.') && !IGNORE_DIRS.contains(&name.as_str()) {
+                        packages.push(format!("{}/{}", parent, name));
+                    }
+                }
+            }
+            None if !pattern.contains('*') && path.join(pattern).is_dir() => packages.push(pattern.clone()),
+            None => {}
+        }
+    }
+    packages.sort();
+    packages.dedup();
+    packages
 }
 
 // ---------------------------------------------------------------------------
@@ -381,7 +616,7 @@ fn detect_framework(path: &Path, language: &Option<DetectedValue>) -> Option<Det
         "Go" => detect_go_framework(path),
         "Dart" => {
             if path.join("pubspec.yaml").exists() {
-                let content = fs::read_to_string(path.join("pubspec.yaml")).unwrap_or_default();
+                let content = safe_read::read_text(path.join("pubspec.yaml")).unwrap_or_default();
                 if content.contains("flutter:") {
                     return Some(DetectedValue {
                         value: "Flutter".to_string(),
@@ -402,7 +637,7 @@ fn detect_js_framework(path: &Path) -> Option<DetectedValue> {
     // Check for Chrome Extension (manifest.json with manifest_version)
     let manifest_path = path.join("manifest.json");
     if manifest_path.exists() {
-        if let Ok(content) = fs::read_to_string(&manifest_path) {
+        if let Ok(content) = safe_read::read_text(&manifest_path) {
             if let Ok(manifest) = serde_json::from_str::<serde_json::Value>(&content) {
                 // Chrome extensions have manifest_version (2 or 3)
                 if manifest.get("manifest_version").is_some() {
@@ -432,7 +667,7 @@ fn detect_js_framework(path: &Path) -> Option<DetectedValue> {
 
     let pkg_json_path = path.join("package.json");
     if pkg_json_path.exists() {
-        if let Ok(content) = fs::read_to_string(&pkg_json_path) {
+        if let Ok(content) = safe_read::read_text(&pkg_json_path) {
             if let Ok(pkg) = serde_json::from_str::<serde_json::Value>(&content) {
                 let deps = merge_deps(&pkg);
 
@@ -505,7 +740,7 @@ fn scan_html_for_patterns(
     for entry in entries.flatten() {
         let file_path = entry.path();
         if file_path.extension().and_then(|e| e.to_str()) == Some("html") {
-            if let Ok(content) = fs::read_to_string(&file_path) {
+            if let Ok(content) = safe_read::read_text(&file_path) {
                 for (pattern, name) in patterns {
                     if content.contains(pattern) {
                         return Some(DetectedValue {
@@ -527,7 +762,7 @@ fn scan_html_for_patterns(
 
 fn detect_python_framework(path: &Path) -> Option<DetectedValue> {
     // Check pyproject.toml
-    if let Ok(content) = fs::read_to_string(path.join("pyproject.toml")) {
+    if let Ok(content) = safe_read::read_text(path.join("pyproject.toml")) {
         let frameworks = [
             ("django", "Django"),
             ("fastapi", "FastAPI"),
@@ -547,7 +782,7 @@ fn detect_python_framework(path: &Path) -> Option<DetectedValue> {
     }
 
     // Check requirements.txt
-    if let Ok(content) = fs::read_to_string(path.join("requirements.txt")) {
+    if let Ok(content) = safe_read::read_text(path.join("requirements.txt")) {
         let frameworks = [
             ("django", "Django"),
             ("fastapi", "FastAPI"),
@@ -568,7 +803,7 @@ fn detect_python_framework(path: &Path) -> Option<DetectedValue> {
 }
 
 fn detect_rust_framework(path: &Path) -> Option<DetectedValue> {
-    if let Ok(content) = fs::read_to_string(path.join("Cargo.toml")) {
+    if let Ok(content) = safe_read::read_text(path.join("Cargo.toml")) {
         let frameworks = [
             ("tauri", "Tauri"),
             ("actix-web", "Actix Web"),
@@ -593,7 +828,7 @@ fn detect_rust_framework(path: &Path) -> Option<DetectedValue> {
 }
 
 fn detect_go_framework(path: &Path) -> Option<DetectedValue> {
-    if let Ok(content) = fs::read_to_string(path.join("go.mod")) {
+    if let Ok(content) = safe_read::read_text(path.join("go.mod")) {
         let frameworks = [
             ("github.com/gin-gonic/gin", "Gin"),
             ("github.com/gofiber/fiber", "Fiber"),
@@ -614,7 +849,7 @@ fn detect_go_framework(path: &Path) -> Option<DetectedValue> {
 }
 
 fn detect_ruby_framework(path: &Path) -> Option<DetectedValue> {
-    if let Ok(content) = fs::read_to_string(path.join("Gemfile")) {
+    if let Ok(content) = safe_read::read_text(path.join("Gemfile")) {
         if content.contains("rails") {
             return Some(DetectedValue {
                 value: "Rails".to_string(),
@@ -634,7 +869,7 @@ fn detect_ruby_framework(path: &Path) -> Option<DetectedValue> {
 }
 
 fn detect_php_framework(path: &Path) -> Option<DetectedValue> {
-    if let Ok(content) = fs::read_to_string(path.join("composer.json")) {
+    if let Ok(content) = safe_read::read_text(path.join("composer.json")) {
         if content.contains("laravel/framework") {
             return Some(DetectedValue {
                 value: "Laravel".to_string(),
@@ -659,7 +894,7 @@ fn detect_php_framework(path: &Path) -> Option<DetectedValue> {
 
 fn detect_database(path: &Path) -> Option<DetectedValue> {
     // Check package.json
-    if let Ok(content) = fs::read_to_string(path.join("package.json")) {
+    if let Ok(content) = safe_read::read_text(path.join("package.json")) {
         if let Ok(pkg) = serde_json::from_str::<serde_json::Value>(&content) {
             let deps = merge_deps(&pkg);
             let dbs = [
@@ -689,7 +924,7 @@ fn detect_database(path: &Path) -> Option<DetectedValue> {
     }
 
     // Check Cargo.toml
-    if let Ok(content) = fs::read_to_string(path.join("Cargo.toml")) {
+    if let Ok(content) = safe_read::read_text(path.join("Cargo.toml")) {
         let dbs = [
             ("rusqlite", "SQLite"),
             ("sqlx", "PostgreSQL"),
@@ -709,7 +944,7 @@ fn detect_database(path: &Path) -> Option<DetectedValue> {
 
     // Check for Prisma schema
     if path.join("prisma").is_dir() || path.join("prisma/schema.prisma").exists() {
-        if let Ok(content) = fs::read_to_string(path.join("prisma/schema.prisma")) {
+        if let Ok(content) = safe_read::read_text(path.join("prisma/schema.prisma")) {
             if content.contains("postgresql") {
                 return Some(DetectedValue {
                     value: "PostgreSQL".to_string(),
@@ -736,7 +971,7 @@ fn detect_database(path: &Path) -> Option<DetectedValue> {
 
     // Check docker-compose for database services
     for dc_name in &["docker-compose.yml", "docker-compose.yaml", "compose.yml"] {
-        if let Ok(content) = fs::read_to_string(path.join(dc_name)) {
+        if let Ok(content) = safe_read::read_text(path.join(dc_name)) {
             if content.contains("postgres") {
                 return Some(DetectedValue {
                     value: "PostgreSQL".to_string(),
@@ -773,7 +1008,7 @@ fn detect_testing(path: &Path, language: &Option<DetectedValue>) -> Option<Detec
 
     match lang {
         "TypeScript" | "JavaScript" => {
-            if let Ok(content) = fs::read_to_string(path.join("package.json")) {
+            if let Ok(content) = safe_read::read_text(path.join("package.json")) {
                 if let Ok(pkg) = serde_json::from_str::<serde_json::Value>(&content) {
                     let deps = merge_deps(&pkg);
                     let testers = [
@@ -806,7 +1041,7 @@ fn detect_testing(path: &Path, language: &Option<DetectedValue>) -> Option<Detec
                 });
             }
             // Check pyproject.toml for pytest
-            if let Ok(content) = fs::read_to_string(path.join("pyproject.toml")) {
+            if let Ok(content) = safe_read::read_text(path.join("pyproject.toml")) {
                 if content.contains("pytest") {
                     return Some(DetectedValue {
                         value: "pytest".to_string(),
@@ -818,7 +1053,7 @@ fn detect_testing(path: &Path, language: &Option<DetectedValue>) -> Option<Detec
         }
         "Rust" => {
             // Rust has built-in testing; check for additional frameworks
-            if let Ok(content) = fs::read_to_string(path.join("Cargo.toml")) {
+            if let Ok(content) = safe_read::read_text(path.join("Cargo.toml")) {
                 if content.contains("insta") {
                     return Some(DetectedValue {
                         value: "insta (snapshot)".to_string(),
@@ -852,7 +1087,7 @@ fn detect_testing(path: &Path, language: &Option<DetectedValue>) -> Option<Detec
 
 fn detect_styling(path: &Path) -> Option<DetectedValue> {
     // Check package.json for CSS frameworks
-    if let Ok(content) = fs::read_to_string(path.join("package.json")) {
+    if let Ok(content) = safe_read::read_text(path.join("package.json")) {
         if let Ok(pkg) = serde_json::from_str::<serde_json::Value>(&content) {
             let deps = merge_deps(&pkg);
             let styles = [
@@ -936,7 +1171,7 @@ fn detect_project_type(
 
     // Check for CLI indicators
     if path.join("src/main.rs").exists() && fw.is_empty() && lang == "Rust" {
-        if let Ok(content) = fs::read_to_string(path.join("Cargo.toml")) {
+        if let Ok(content) = safe_read::read_text(path.join("Cargo.toml")) {
             if content.contains("clap") || content.contains("structopt") {
                 return Some("CLI".to_string());
             }
@@ -946,6 +1181,81 @@ fn detect_project_type(
     None
 }
 
+// ---------------------------------------------------------------------------
+// Archetype detection
+// ---------------------------------------------------------------------------
+
+/// Project layout archetypes that template doc inference is tuned for.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Archetype {
+    /// JS/TS app with components, hooks, and stores (React, Tauri, Vue, ...)
+    Frontend,
+    Django,
+    Rails,
+    Spring,
+    GoService,
+    CliTool,
+    Generic,
+}
+
+impl Archetype {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            Archetype::Frontend => "frontend",
+            Archetype::Django => "django",
+            Archetype::Rails => "rails",
+            Archetype::Spring => "spring",
+            Archetype::GoService => "go_service",
+            Archetype::CliTool => "cli_tool",
+            Archetype::Generic => "generic",
+        }
+    }
+}
+
+/// Detect the project's layout archetype from its manifests and entry points.
+pub fn detect_archetype(path: &Path) -> Archetype {
+    let read = |name: &str| safe_read::read_text(path.join(name)).unwrap_or_default();
+
+    let python = format!("{}\n{}", read("requirements.txt"), read("pyproject.toml")).to_lowercase();
+    if path.join("manage.py").exists() || python.contains("django") {
+        return Archetype::Django;
+    }
+
+    if path.join("config/application.rb").exists() || read("Gemfile").contains("rails") {
+        return Archetype::Rails;
+    }
+
+    let jvm = format!("{}\n{}\n{}", read("pom.xml"), read("build.gradle"), read("build.gradle.kts"));
+    if jvm.contains("org.springframework") || jvm.contains("spring-boot") {
+        return Archetype::Spring;
+    }
+
+    let cargo = read("Cargo.toml");
+    let go_mod = read("go.mod");
+    let rust_cli = path.join("src/main.rs").exists()
+        && (cargo.contains("clap") || cargo.contains("structopt"))
+        && detect_rust_framework(path).is_none();
+    let go_cli = go_mod.contains("github.com/spf13/cobra") || go_mod.contains("github.com/urfave/cli");
+    let python_cli = python.contains("[project.scripts]")
+        && (python.contains("click") || python.contains("typer") || python.contains("argparse"));
+    let node_cli = serde_json::from_str::<serde_json::Value>(&read("package.json"))
+        .map(|pkg| pkg.get("bin").is_some() && !merge_deps(&pkg).contains_key("react"))
+        .unwrap_or(false);
+    if rust_cli || go_cli || python_cli || node_cli {
+        return Archetype::CliTool;
+    }
+
+    if path.join("go.mod").exists() {
+        return Archetype::GoService;
+    }
+
+    if path.join("package.json").exists() {
+        return Archetype::Frontend;
+    }
+
+    Archetype::Generic
+}
+
 // ---------------------------------------------------------------------------
 // Helpers
 // ---------------------------------------------------------------------------
@@ -970,6 +1280,40 @@ mod tests {
     use super::*;
     use std::fs;
 
+    #[test]
+    fn test_detect_source_layout_monorepo() {
+        let dir = tempfile::tempdir().expect("Failed to create temp dir");
+        let root = dir.path();
+        fs::write(root.join("package.json"), r#"{"workspaces": ["packages/*", "!packages/legacy"]}"#).unwrap();
+        fs::create_dir_all(root.join("packages/web/src/components")).unwrap();
+        fs::write(root.join("packages/web/src/index.ts"), "").unwrap();
+        fs::write(root.join("packages/web/src/components/App.tsx"), "").unwrap();
+        fs::write(root.join("packages/web/src/components/Nav.tsx"), "").unwrap();
+        fs::create_dir_all(root.join("packages/api/node_modules/dep")).unwrap();
+        fs::write(root.join("packages/api/server.py"), "").unwrap();
+        fs::write(root.join("packages/api/node_modules/dep/index.js"), "").unwrap();
+
+        let layout = detect_source_layout(root);
+        assert_eq!(layout.source_dirs, vec!["packages/api", "packages/web/src"]);
+        assert_eq!(layout.extensions, vec!["tsx", "py", "ts"]);
+        assert_eq!(layout.path_filters, vec!["packages/**"]);
+    }
+
+    #[test]
+    fn test_detect_source_layout_single_package() {
+        let dir = tempfile::tempdir().expect("Failed to create temp dir");
+        let root = dir.path();
+        fs::create_dir_all(root.join("src")).unwrap();
+        fs::write(root.join("src/main.rs"), "").unwrap();
+        fs::create_dir_all(root.join("docs")).unwrap();
+        fs::write(root.join("docs/guide.md"), "").unwrap();
+
+        let layout = detect_source_layout(root);
+        assert_eq!(layout.source_dirs, vec!["src"]);
+        assert_eq!(layout.extensions, vec!["rs"]);
+        assert!(layout.path_filters.is_empty());
+    }
+
     #[test]
     fn test_scan_nonexistent_dir() {
         let result = scan_project_dir("/nonexistent/path/xyz");
@@ -985,9 +1329,62 @@ mod tests {
         assert_eq!(det.confidence, "high");
         assert!(det.language.is_some());
         assert_eq!(det.language.as_ref().unwrap().value, "TypeScript");
+        assert_eq!(det.archetype, "frontend");
         assert!(det.file_count > 0);
     }
 
+    #[test]
+    fn test_detect_package_manager() {
+        let dir = tempfile::tempdir().expect("Failed to create temp dir");
+        assert!(detect_package_manager(dir.path()).is_none());
+
+        fs::write(dir.path().join("package.json"), r#"{"packageManager": "yarn@4.1.0"}"#).unwrap();
+        fs::write(dir.path().join("pnpm-lock.yaml"), "").unwrap();
+        let pm = detect_package_manager(dir.path()).unwrap();
+        assert_eq!(pm.value, "yarn");
+        assert_eq!(pm.confidence, 0.95);
+
+        fs::write(dir.path().join("package.json"), "{}").unwrap();
+        let pm = detect_package_manager(dir.path()).unwrap();
+        assert_eq!((pm.value.as_str(), pm.source.as_str()), ("pnpm", "pnpm-lock.yaml"));
+    }
+
+    #[test]
+    fn test_detect_archetype() {
+        let archetype = |files: &[(&str, &str)]| {
+            let dir = tempfile::tempdir().expect("Failed to create temp dir");
+            for (name, content) in files {
+                let file = dir.path().join(name);
+                fs::create_dir_all(file.parent().unwrap()).unwrap();
+                fs::write(file, content).unwrap();
+            }
+            detect_archetype(dir.path())
+        };
+
+        assert_eq!(archetype(&[("manage.py", "")]), Archetype::Django);
+        assert_eq!(archetype(&[("requirements.txt", "Django==5.0\n")]), Archetype::Django);
+        assert_eq!(archetype(&[("Gemfile", "gem 'rails', '~> 7.1'\n")]), Archetype::Rails);
+        assert_eq!(
+            archetype(&[("pom.xml", "<groupId>org.springframework.boot</groupId>")]),
+            Archetype::Spring
+        );
+        assert_eq!(
+            archetype(&[("go.mod", "module x\nrequire github.com/spf13/cobra v1.8.0\n")]),
+            Archetype::CliTool
+        );
+        assert_eq!(archetype(&[("go.mod", "module x\n")]), Archetype::GoService);
+        assert_eq!(
+            archetype(&[("Cargo.toml", "[dependencies]\nclap = \"4\"\n"), ("src/main.rs", "fn main() {}")]),
+            Archetype::CliTool
+        );
+        assert_eq!(archetype(&[("package.json", r#"{"bin": {"x": "cli.js"}}"#)]), Archetype::CliTool);
+        assert_eq!(
+            archetype(&[("package.json", r#"{"dependencies": {"react": "^18"}}"#)]),
+            Archetype::Frontend
+        );
+        assert_eq!(archetype(&[("README.md", "")]), Archetype::Generic);
+    }
+
     #[test]
     fn test_is_source_file() {
         assert!(is_source_file("main.ts"));
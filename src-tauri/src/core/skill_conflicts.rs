@@ -0,0 +1,307 @@
+//! @module core/skill_conflicts
+//! @description Find skills and agents whose instructions contradict the project's CLAUDE.md
+//!
+//! PURPOSE:
+//! - Gather instruction texts: stored skills and agents (project and global) plus the
+//!   deployed .claude/skills/*/SKILL.md and .claude/agents/*.md files
+//! - Flag tool and convention choices that disagree with CLAUDE.md (rule-based)
+//! - Build the AI prompt for subtler contradictions and parse its JSON answer
+//!
+//! DEPENDENCIES:
+//! - rusqlite - skills and agents tables
+//! - serde_json - AI response parsing
+//! - core::ai - Token truncation for the prompt
+//! - core::safe_read - Lossy, size-capped reads of CLAUDE.md and deployed files
+//! - models::skill - SkillConflict
+//!
+//! EXPORTS:
+//! - InstructionSource - One skill or agent's instruction text and where it came from
+//! - collect_sources - Stored and deployed skills and agents for a project
+//! - rule_conflicts - Contradictions between CLAUDE.md and sources over CHOICE_GROUPS
+//! - AI_SYSTEM_PROMPT / ai_prompt / parse_ai_conflicts - AI pass over the same inputs
+//! - merge_conflicts - Add AI findings that don't repeat a rule finding
+//!
+//! PATTERNS:
+//! - A line "prefers" an option when the option appears with no negation before it on that
+//!   line ("use pnpm instead of npm" prefers pnpm and avoids npm)
+//! - Conflict: CLAUDE.md prefers A while a source prefers only other options of the group,
+//!   or one side prefers what the other avoids
+//! - Rule findings have detected_by "rule", AI findings "ai"
+//!
+//! CLAUDE NOTES:
+//! - Option matching is whole-word and case-insensitive on single lines, so it is cheap and
+//!   explainable but misses conventions phrased across sentences (the AI pass covers those)
+//! - Add new tool families to CHOICE_GROUPS; the first option a side mentions is its evidence
+
+use std::collections::HashMap;
+use std::path::Path;
+
+use rusqlite::Connection;
+
+use crate::core::{ai, safe_read};
+use crate::models::skill::SkillConflict;
+
+/// Mutually exclusive choices: (topic, options). Multi-word options match as phrases.
+const CHOICE_GROUPS: &[(&str, &[&str])] = &[
+    ("package manager", &["npm", "yarn", "pnpm", "bun"]),
+    ("JavaScript test runner", &["jest", "vitest", "mocha", "jasmine"]),
+    ("Python test runner", &["pytest", "unittest", "nose2"]),
+    ("Python environment", &["pip", "poetry", "uv", "pipenv", "conda"]),
+    ("indentation", &["tabs", "spaces"]),
+    ("string quotes", &["single quotes", "double quotes"]),
+    ("styling", &["tailwind", "styled-components", "css modules", "sass"]),
+    ("state management", &["redux", "zustand", "mobx", "jotai"]),
+    ("module format", &["commonjs", "esm"]),
+    ("JS formatter/linter", &["prettier", "biome"]),
+];
+
+/// Words that turn a following option into something to avoid.
+const NEGATIONS: &[&str] = &["never", "don't", "do not", "dont", "avoid", "instead of", "not ", "no ", "rather than", "without"];
+
+/// Characters of each source kept in the AI prompt.
+const AI_SOURCE_TOKENS: usize = 1_500;
+const AI_CLAUDE_MD_TOKENS: usize = 4_000;
+
+pub const AI_SYSTEM_PROMPT: &str = "You review instructions given to a coding assistant. Compare the project's CLAUDE.md \
+with each skill or agent and report only direct contradictions (one says to do X, the other says to do not-X or \
+something incompatible). Ignore differences in scope or detail. Respond with only a JSON array; each item has \
+\"source\" (the skill or agent name exactly as given), \"topic\" (a few words), \"claudeMd\" (the CLAUDE.md \
+instruction, quoted), and \"sourceSays\" (the conflicting instruction, quoted). Respond with [] when there are none.";
+
+/// One skill or agent's instructions.
+#[derive(Debug, Clone, PartialEq)]
+pub struct InstructionSource {
+    /// "skill" | "agent"
+    pub kind: String,
+    pub name: String,
+    /// Project-relative file for deployed skills and agents; None for stored ones
+    pub path: Option<String>,
+    pub text: String,
+}
+
+/// Stored skills and agents (project and global) and the project's deployed skill and agent files.
+pub fn collect_sources(db: &Connection, project_id: &str, project_path: &str) -> Result<Vec<InstructionSource>, String> {
+    let mut sources = Vec::new();
+    for (kind, sql) in [
+        ("skill", "SELECT name, content FROM skills WHERE project_id = ?1 OR project_id IS NULL ORDER BY name"),
+        ("agent", "SELECT name, instructions FROM agents WHERE project_id = ?1 OR project_id IS NULL ORDER BY name"),
+    ] {
+        let mut stmt = db.prepare(sql).map_err(|e| format!("Failed to query {}s: {}", kind, e))?;
+        let rows = stmt
+            .query_map([project_id], |row| Ok((row.get::<_, String>(0)?, row.get::<_, String>(1)?)))
+            .map_err(|e| format!("Failed to read {}s: {}", kind, e))?;
+        for (name, text) in rows.filter_map(|r| r.ok()) {
+            sources.push(InstructionSource { kind: kind.to_string(), name, path: None, text });
+        }
+    }
+
+    let claude_dir = Path::new(project_path).join(".claude");
+    if let Ok(entries) = std::fs::read_dir(claude_dir.join("skills")) {
+        let mut dirs: Vec<_> = entries.filter_map(|e| e.ok()).filter(|e| e.path().is_dir()).collect();
+        dirs.sort_by_key(|e| e.file_name());
+        for entry in dirs {
+            let name = entry.file_name().to_string_lossy().to_string();
+            if let Ok(text) = safe_read::read_text(entry.path().join("SKILL.md")) {
+                let path = Some(format!(".claude/skills/{}/SKILL.md", name));
+                sources.push(InstructionSource { kind: "skill".to_string(), name, path, text });
+            }
+        }
+    }
+    if let Ok(entries) = std::fs::read_dir(claude_dir.join("agents")) {
+        let mut files: Vec<_> = entries
+            .filter_map(|e| e.ok())
+            .map(|e| e.path())
+            .filter(|p| p.extension().and_then(|e| e.to_str()) == Some("md"))
+            .collect();
+        files.sort();
+        for file in files {
+            let name = file.file_stem().unwrap_or_default().to_string_lossy().to_string();
+            if let Ok(text) = safe_read::read_text(&file) {
+                let path = Some(format!(".claude/agents/{}.md", name));
+                sources.push(InstructionSource { kind: "agent".to_string(), name, path, text });
+            }
+        }
+    }
+    Ok(sources)
+}
+
+/// Where `option` occurs in `line` (lowercase) as a whole word or phrase.
+fn find_word(line: &str, option: &str) -> Option<usize> {
+    let is_word = |c: char| c.is_alphanumeric() || c == '_' || c == '-';
+    line.match_indices(option).map(|(i, _)| i).find(|&i| {
+        let before = line[..i].chars().next_back();
+        let after = line[i + option.len()..].chars().next();
+        !before.is_some_and(is_word) && !after.is_some_and(is_word)
+    })
+}
+
+/// Per topic: options preferred and avoided, each with the first line that says so.
+type Stances = HashMap<&'static str, (Vec<(&'static str, String)>, Vec<(&'static str, String)>)>;
+
+fn stances(text: &str) -> Stances {
+    let mut out: Stances = HashMap::new();
+    for raw in text.lines() {
+        let line = raw.to_lowercase();
+        for (topic, options) in CHOICE_GROUPS {
+            for option in *options {
+                let Some(at) = find_word(&line, option) else {
+                    continue;
+                };
+                let negated = NEGATIONS.iter().any(|n| line[..at].contains(n));
+                let (prefers, avoids) = out.entry(*topic).or_default();
+                let list = if negated { avoids } else { prefers };
+                if !list.iter().any(|(o, _)| o == option) {
+                    list.push((*option, raw.trim().trim_start_matches(['-', '*', ' ']).to_string()));
+                }
+            }
+        }
+    }
+    out
+}
+
+/// Contradictions between CLAUDE.md and each source over CHOICE_GROUPS.
+pub fn rule_conflicts(claude_md: &str, sources: &[InstructionSource]) -> Vec<SkillConflict> {
+    let wanted = stances(claude_md);
+    let mut conflicts = Vec::new();
+    for source in sources {
+        let theirs = stances(&source.text);
+        for (topic, _) in CHOICE_GROUPS {
+            let (Some((ours_pref, ours_avoid)), Some((their_pref, their_avoid))) = (wanted.get(topic), theirs.get(topic))
+            else {
+                continue;
+            };
+            let prefers_other = !ours_pref.is_empty()
+                && !their_pref.is_empty()
+                && !their_pref.iter().any(|(o, _)| ours_pref.iter().any(|(p, _)| p == o));
+            let clash = if prefers_other {
+                Some((&ours_pref[0].1, &their_pref[0].1))
+            } else {
+                // One side prefers what the other avoids
+                opposed(their_pref, ours_avoid)
+                    .map(|(theirs, ours)| (ours, theirs))
+                    .or_else(|| opposed(ours_pref, their_avoid))
+            };
+            if let Some((ours, theirs)) = clash {
+                conflicts.push(conflict(source, topic, ours, theirs, "rule"));
+            }
+        }
+    }
+    conflicts
+}
+
+/// The first option `pref` prefers that `avoid` avoids: (prefer line, avoid line).
+fn opposed<'a>(pref: &'a [(&str, String)], avoid: &'a [(&str, String)]) -> Option<(&'a String, &'a String)> {
+    pref.iter()
+        .find_map(|(o, p_line)| avoid.iter().find(|(a, _)| a == o).map(|(_, a_line)| (p_line, a_line)))
+}
+
+fn conflict(source: &InstructionSource, topic: &str, claude_md: &str, source_says: &str, detected_by: &str) -> SkillConflict {
+    SkillConflict {
+        source_kind: source.kind.clone(),
+        source_name: source.name.clone(),
+        source_path: source.path.clone(),
+        topic: topic.to_string(),
+        claude_md_says: claude_md.to_string(),
+        source_says: source_says.to_string(),
+        detected_by: detected_by.to_string(),
+    }
+}
+
+/// Prompt for the AI pass: CLAUDE.md followed by each source, truncated to a token budget.
+pub fn ai_prompt(claude_md: &str, sources: &[InstructionSource]) -> String {
+    let mut prompt = format!("## CLAUDE.md\n\n{}\n", ai::truncate_to_tokens(claude_md, AI_CLAUDE_MD_TOKENS));
+    for source in sources {
+        prompt.push_str(&format!(
+            "\n## {} \"{}\"\n\n{}\n",
+            source.kind,
+            source.name,
+            ai::truncate_to_tokens(&source.text, AI_SOURCE_TOKENS)
+        ));
+    }
+    prompt
+}
+
+/// Parse the AI's JSON array. Items naming an unknown source are dropped.
+pub fn parse_ai_conflicts(response: &str, sources: &[InstructionSource]) -> Result<Vec<SkillConflict>, String> {
+    let start = response.find('[').ok_or("AI response contained no JSON array")?;
+    let end = response.rfind(']').map(|i| i + 1).unwrap_or(response.len());
+    let items: Vec<serde_json::Value> =
+        serde_json::from_str(&response[start..end]).map_err(|e| format!("Failed to parse AI response: {}", e))?;
+
+    let field = |item: &serde_json::Value, key: &str| item.get(key).and_then(|v| v.as_str()).unwrap_or("").trim().to_string();
+    Ok(items
+        .iter()
+        .filter_map(|item| {
+            let name = field(item, "source");
+            let source = sources.iter().find(|s| s.name.eq_ignore_ascii_case(&name))?;
+            Some(conflict(source, &field(item, "topic"), &field(item, "claudeMd"), &field(item, "sourceSays"), "ai"))
+        })
+        .collect())
+}
+
+/// Rule findings plus AI findings for (source, topic) pairs the rules did not already report.
+pub fn merge_conflicts(mut rule: Vec<SkillConflict>, ai: Vec<SkillConflict>) -> Vec<SkillConflict> {
+    for found in ai {
+        let duplicate = rule.iter().any(|r| {
+            r.source_name == found.source_name && r.source_path == found.source_path && r.topic.eq_ignore_ascii_case(&found.topic)
+        });
+        if !duplicate {
+            rule.push(found);
+        }
+    }
+    rule
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn source(name: &str, text: &str) -> InstructionSource {
+        InstructionSource { kind: "skill".to_string(), name: name.to_string(), path: None, text: text.to_string() }
+    }
+
+    #[test]
+    fn test_rule_conflicts() {
+        let claude_md = "# Conventions\n- Use pnpm for all installs, never npm\n- Tests use vitest\n- Indent with spaces\n";
+        let sources = vec![
+            source("deps", "Install packages with `yarn add <pkg>`."),
+            source("ci", "Run pnpm install, then pnpm test."),
+            source("legacy", "Run `npm install` before building."),
+            source("tests", "Write jest tests; do not use vitest."),
+            source("unrelated", "Keep functions small."),
+        ];
+        let conflicts = rule_conflicts(claude_md, &sources);
+        let found: Vec<(&str, &str)> = conflicts.iter().map(|c| (c.source_name.as_str(), c.topic.as_str())).collect();
+        assert_eq!(
+            found,
+            vec![
+                ("deps", "package manager"),
+                ("legacy", "package manager"),
+                ("tests", "JavaScript test runner"),
+            ]
+        );
+        assert_eq!(conflicts[0].claude_md_says, "Use pnpm for all installs, never npm");
+        assert_eq!(conflicts[0].source_says, "Install packages with `yarn add <pkg>`.");
+        assert!(conflicts.iter().all(|c| c.detected_by == "rule"));
+        // "bun" inside "bundle" is not a mention
+        assert!(rule_conflicts(claude_md, &[source("b", "Run pnpm bundle")]).is_empty());
+    }
+
+    #[test]
+    fn test_parse_and_merge_ai_conflicts() {
+        let sources = vec![source("deps", "Use yarn"), source("style", "Prefer classes")];
+        let response = r#"Here you go:
+[{"source": "deps", "topic": "Package manager", "claudeMd": "Use pnpm", "sourceSays": "Use yarn"},
+ {"source": "Style", "topic": "components", "claudeMd": "Use function components", "sourceSays": "Prefer classes"},
+ {"source": "ghost", "topic": "x", "claudeMd": "a", "sourceSays": "b"}]"#;
+        let ai = parse_ai_conflicts(response, &sources).unwrap();
+        assert_eq!(ai.len(), 2);
+        assert_eq!(ai[1].source_name, "style");
+
+        let rule = rule_conflicts("Use pnpm", &sources);
+        let merged = merge_conflicts(rule, ai);
+        let found: Vec<(&str, &str)> = merged.iter().map(|c| (c.source_name.as_str(), c.detected_by.as_str())).collect();
+        assert_eq!(found, vec![("deps", "rule"), ("style", "ai")]);
+        assert!(parse_ai_conflicts("no conflicts", &sources).is_err());
+    }
+}
@@ -0,0 +1,239 @@
+//! @module core/slash_commands
+//! @description Render skills and saved prompts as Claude Code custom slash commands and detect drift
+//!
+//! PURPOSE:
+//! - Turn a skill or saved prompt into a `.claude/commands/<name>.md` file
+//! - Stamp each generated file with its source and a content hash
+//! - Compare deployed files against their sources to detect drift
+//!
+//! DEPENDENCIES:
+//! - sha2 - Content hash stored in the generated file
+//! - std::fs - Read deployed command files
+//!
+//! EXPORTS:
+//! - COMMANDS_DIR - Relative directory of project slash commands
+//! - SlashCommandSource - What a command is generated from
+//! - command_name - Slash command name (kebab-case) for a source name
+//! - render_command - Full file content for a source, including the marker
+//! - DeployedCommand - A generated command file found on disk
+//! - parse_deployed - Read the marker of a command file (None if not generated by Jumpstart)
+//! - scan_deployed - List generated command files in a project
+//! - is_modified - Whether a deployed file was edited after generation
+//!
+//! PATTERNS:
+//! - Files are frontmatter (description, argument-hint) + marker comment + body
+//! - Marker: <!-- jumpstart:slash-command source=<type>:<id> hash=<sha256> -->
+//! - The hash covers the whole file except the marker line
+//! - Bodies that do not use $ARGUMENTS get a trailing "$ARGUMENTS" section
+//!
+//! CLAUDE NOTES:
+//! - Command files without a marker belong to the user and are never overwritten or reported
+//! - Leading YAML frontmatter in a skill's content is dropped (the command has its own)
+
+use std::fs;
+use std::path::{Path, PathBuf};
+
+use sha2::{Digest, Sha256};
+
+/// Project-relative directory for Claude Code custom slash commands.
+pub const COMMANDS_DIR: &str = ".claude/commands";
+
+const MARKER_PREFIX: &str = "<!-- jumpstart:slash-command ";
+
+/// Source of a generated slash command.
+#[derive(Debug, Clone)]
+pub struct SlashCommandSource {
+    /// "skill" or "prompt"
+    pub source_type: String,
+    pub source_id: String,
+    pub name: String,
+    pub description: String,
+    pub body: String,
+}
+
+/// Kebab-case command name ("Review PR Checklist" -> "review-pr-checklist").
+pub fn command_name(name: &str) -> String {
+    let mut out = String::new();
+    for c in name.trim().chars() {
+        if c.is_ascii_alphanumeric() {
+            out.push(c.to_ascii_lowercase());
+        } else if !out.ends_with('-') && !out.is_empty() {
+            out.push('-');
+        }
+    }
+    let trimmed = out.trim_end_matches('-');
+    if trimmed.is_empty() {
+        "command".to_string()
+    } else {
+        trimmed.chars().take(64).collect::<String>().trim_end_matches('-').to_string()
+    }
+}
+
+/// Remove a leading YAML frontmatter block.
+fn strip_frontmatter(content: &str) -> &str {
+    let trimmed = content.trim_start();
+    if let Some(rest) = trimmed.strip_prefix("---\n") {
+        if let Some(end) = rest.find("\n---") {
+            let after = &rest[end + 4..];
+            return after.strip_prefix('\n').unwrap_or(after);
+        }
+    }
+    content
+}
+
+fn yaml_string(s: &str) -> String {
+    let single_line = s.split_whitespace().collect::<Vec<_>>().join(" ");
+    format!("\"{}\"", single_line.replace('\\', "\\\\").replace('"', "\\\""))
+}
+
+fn content_hash(content_without_marker: &str) -> String {
+    format!("{:x}", Sha256::digest(content_without_marker.as_bytes()))
+}
+
+fn marker_line(source_type: &str, source_id: &str, hash: &str) -> String {
+    format!("{}source={}:{} hash={} -->", MARKER_PREFIX, source_type, source_id, hash)
+}
+
+/// Remove the marker line (if any) from file content.
+fn without_marker(content: &str) -> String {
+    content
+        .split_inclusive('\n')
+        .filter(|line| !line.trim_start().starts_with(MARKER_PREFIX))
+        .collect()
+}
+
+/// Render the complete command file for a source.
+pub fn render_command(source: &SlashCommandSource) -> String {
+    let mut body = strip_frontmatter(&source.body).trim().to_string();
+    if !body.contains("$ARGUMENTS") {
+        body.push_str("\n\n## Additional context\n\n$ARGUMENTS");
+    }
+
+    let description = if source.description.trim().is_empty() {
+        source.name.clone()
+    } else {
+        source.description.clone()
+    };
+    let frontmatter = format!(
+        "---\ndescription: {}\nargument-hint: [optional context]\n---\n",
+        yaml_string(&description)
+    );
+    let rest = format!("\n{}\n", body);
+
+    let hash = content_hash(&format!("{}{}", frontmatter, rest));
+    format!(
+        "{}{}\n{}",
+        frontmatter,
+        marker_line(&source.source_type, &source.source_id, &hash),
+        rest
+    )
+}
+
+/// A command file generated by Jumpstart.
+#[derive(Debug, Clone, PartialEq)]
+pub struct DeployedCommand {
+    pub command_name: String,
+    pub file_path: PathBuf,
+    pub source_type: String,
+    pub source_id: String,
+    /// Hash recorded at generation time
+    pub hash: String,
+    pub content: String,
+}
+
+/// Parse a command file. Returns None when the file has no Jumpstart marker.
+pub fn parse_deployed(path: &Path) -> Option<DeployedCommand> {
+    let content = fs::read_to_string(path).ok()?;
+    let marker = content.lines().find(|l| l.trim_start().starts_with(MARKER_PREFIX))?;
+    let fields = marker.trim().strip_prefix(MARKER_PREFIX)?.strip_suffix("-->")?;
+
+    let mut source = None;
+    let mut hash = None;
+    for field in fields.split_whitespace() {
+        if let Some(v) = field.strip_prefix("source=") {
+            source = v.split_once(':');
+        } else if let Some(v) = field.strip_prefix("hash=") {
+            hash = Some(v.to_string());
+        }
+    }
+    let (source_type, source_id) = source?;
+
+    Some(DeployedCommand {
+        command_name: path.file_stem()?.to_string_lossy().to_string(),
+        file_path: path.to_path_buf(),
+        source_type: source_type.to_string(),
+        source_id: source_id.to_string(),
+        hash: hash?,
+        content,
+    })
+}
+
+/// All Jumpstart-generated command files in a project, sorted by name.
+pub fn scan_deployed(project_path: &Path) -> Vec<DeployedCommand> {
+    let Ok(entries) = fs::read_dir(project_path.join(COMMANDS_DIR)) else {
+        return Vec::new();
+    };
+    let mut deployed: Vec<DeployedCommand> = entries
+        .filter_map(|e| e.ok())
+        .map(|e| e.path())
+        .filter(|p| p.extension().is_some_and(|ext| ext == "md"))
+        .filter_map(|p| parse_deployed(&p))
+        .collect();
+    deployed.sort_by(|a, b| a.command_name.cmp(&b.command_name));
+    deployed
+}
+
+/// True when the file no longer matches the hash written at generation time.
+pub fn is_modified(deployed: &DeployedCommand) -> bool {
+    content_hash(&without_marker(&deployed.content)) != deployed.hash
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn source(body: &str) -> SlashCommandSource {
+        SlashCommandSource {
+            source_type: "skill".to_string(),
+            source_id: "s1".to_string(),
+            name: "Review PR: Checklist!".to_string(),
+            description: "Review a \"pull request\"".to_string(),
+            body: body.to_string(),
+        }
+    }
+
+    #[test]
+    fn test_command_name() {
+        assert_eq!(command_name("Review PR: Checklist!"), "review-pr-checklist");
+        assert_eq!(command_name("  --  "), "command");
+    }
+
+    #[test]
+    fn test_render_and_detect_edits() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("review-pr-checklist.md");
+
+        let rendered = render_command(&source("---\nname: x\n---\nCheck tests for $ARGUMENTS"));
+        assert!(rendered.starts_with("---\ndescription: \"Review a \\\"pull request\\\"\"\n"));
+        assert!(!rendered.contains("name: x"));
+        assert!(!rendered.contains("## Additional context"));
+        fs::write(&path, &rendered).unwrap();
+
+        let deployed = parse_deployed(&path).unwrap();
+        assert_eq!((deployed.source_type.as_str(), deployed.source_id.as_str()), ("skill", "s1"));
+        assert_eq!(deployed.command_name, "review-pr-checklist");
+        assert!(!is_modified(&deployed));
+
+        fs::write(&path, rendered.replace("Check tests", "Check all tests")).unwrap();
+        assert!(is_modified(&parse_deployed(&path).unwrap()));
+
+        fs::write(dir.path().join("mine.md"), "My own command").unwrap();
+        assert!(parse_deployed(&dir.path().join("mine.md")).is_none());
+    }
+
+    #[test]
+    fn test_render_appends_arguments() {
+        let rendered = render_command(&source("Run the checklist."));
+        assert!(rendered.trim_end().ends_with("$ARGUMENTS"));
+    }
+}
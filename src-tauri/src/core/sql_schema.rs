@@ -0,0 +1,805 @@
+//! @module core/sql_schema
+//! @description SQL statement parsing and the database schema overview written into CLAUDE.md
+//!
+//! PURPOSE:
+//! - Split SQL files into statements (comments, strings, and $$ bodies respected)
+//! - Detect CREATE TABLE/VIEW/FUNCTION/PROCEDURE statements as a .sql file's exports
+//! - Replay a project's .sql files (migrations included) into a final table/column list
+//! - Render that schema as markdown and merge it into CLAUDE.md's Architecture section
+//!
+//! DEPENDENCIES:
+//! - core::freshness - Scan-scope rules for which directories to walk
+//! - models::sql_schema - SchemaTable, SchemaObject, SchemaOverview
+//! - core::safe_read - Lossy, size-capped reads of .sql files
+//! - core::patch - CLAUDE.md rewrites go through the configured patch engine
+//!
+//! EXPORTS:
+//! - split_statements - Split SQL text into statements without comments
+//! - created_objects - (kind, name) of every object a SQL file creates
+//! - build_schema - Apply files in order into final tables and objects
+//! - collect_sql_files - Project-relative path and content of every .sql file, sorted
+//! - render_overview - Markdown "### Database Schema" subsection
+//! - merge_into_architecture - Insert or replace the subsection in CLAUDE.md content
+//! - generate_overview - Build the overview for a project and update CLAUDE.md
+//!
+//! PATTERNS:
+//! - Files are applied in sorted path order, which matches the timestamp/sequence
+//!   prefixes migration tools use (001_init.sql, 20240101_add_users.sql)
+//! - ALTER TABLE ADD/DROP/RENAME and DROP TABLE/VIEW are replayed; everything else
+//!   (INSERT, CREATE INDEX, GRANT, ...) is ignored
+//! - Parsing is keyword-based and dialect-agnostic; unknown syntax is skipped, never an error
+//!
+//! CLAUDE NOTES:
+//! - The analyzer skips migrations/ for per-file docs, but the overview reads them:
+//!   migrations are usually the only place the schema is written down
+//! - merge_into_architecture only touches the "### Database Schema" subsection, so
+//!   hand-written Architecture notes survive regeneration
+//! - CLAUDE.md is never created here; without one the overview is returned unwritten
+//! - updated_claude_md is true only when the merge changed CLAUDE.md and was written
+
+use std::fs;
+use std::path::Path;
+
+use crate::core::patch::{self, PatchEngine};
+use crate::core::{freshness, safe_read};
+use crate::models::sql_schema::{SchemaObject, SchemaOverview, SchemaTable};
+
+/// Heading of the generated subsection inside CLAUDE.md's Architecture section.
+const SCHEMA_HEADING: &str = "### Database Schema";
+
+/// Upper bound on .sql files read for one overview.
+const MAX_SQL_FILES: usize = 500;
+
+/// Columns listed per table before the rest are elided.
+const MAX_LISTED_COLUMNS: usize = 12;
+
+/// Table-level constraint keywords that start a non-column definition.
+const CONSTRAINT_KEYWORDS: &[&str] = &["CONSTRAINT", "PRIMARY", "FOREIGN", "CHECK", "EXCLUDE"];
+
+/// Keywords that start a non-column definition only when followed by "(" or KEY/INDEX.
+const INDEX_KEYWORDS: &[&str] = &["UNIQUE", "INDEX", "KEY", "FULLTEXT", "SPATIAL"];
+
+/// One schema-changing statement.
+#[derive(Debug, Clone, PartialEq)]
+enum Statement {
+    CreateTable { name: String, columns: Vec<String> },
+    CreateObject { kind: &'static str, name: String },
+    AddColumn { table: String, column: String },
+    DropColumn { table: String, column: String },
+    RenameColumn { table: String, from: String, to: String },
+    RenameTable { from: String, to: String },
+    Drop { names: Vec<String> },
+}
+
+/// Split SQL text into trimmed statements. `--` and `/* */` comments are
+/// removed; semicolons inside quotes or dollar-quoted bodies do not split.
+pub fn split_statements(sql: &str) -> Vec<String> {
+    let chars: Vec<char> = sql.chars().collect();
+    let mut statements = Vec::new();
+    let mut current = String::new();
+    let mut i = 0;
+
+    while i < chars.len() {
+        let c = chars[i];
+        let next = chars.get(i + 1).copied();
+        match c {
+            '-' if next == Some('-') => {
+                while i < chars.len() && chars[i] != '\n' {
+                    i += 1;
+                }
+                continue;
+            }
+            '/' if next == Some('*') => {
+                i += 2;
+                while i < chars.len() && !(chars[i] == '*' && chars.get(i + 1) == Some(&'/')) {
+                    i += 1;
+                }
+                i += 2;
+                current.push(' ');
+                continue;
+            }
+            '\'' | '"' | '`' => {
+                current.push(c);
+                i += 1;
+                while i < chars.len() {
+                    current.push(chars[i]);
+                    i += 1;
+                    if chars[i - 1] == c {
+                        break;
+                    }
+                }
+                continue;
+            }
+            '$' => {
+                if let Some(tag) = dollar_tag(&chars[i..]) {
+                    let tag_chars: Vec<char> = tag.chars().collect();
+                    current.push_str(&tag);
+                    i += tag_chars.len();
+                    while i < chars.len() && !chars[i..].starts_with(&tag_chars) {
+                        current.push(chars[i]);
+                        i += 1;
+                    }
+                    if i < chars.len() {
+                        current.push_str(&tag);
+                        i += tag_chars.len();
+                    }
+                    continue;
+                }
+                current.push(c);
+            }
+            ';' => {
+                push_statement(&mut statements, &current);
+                current.clear();
+            }
+            _ => current.push(c),
+        }
+        i += 1;
+    }
+    push_statement(&mut statements, &current);
+
+    statements
+}
+
+fn push_statement(statements: &mut Vec<String>, text: &str) {
+    let trimmed = text.trim();
+    if !trimmed.is_empty() {
+        statements.push(trimmed.to_string());
+    }
+}
+
+/// The opening tag of a dollar-quoted string (`$$` or `$body$`), if `chars` starts with one.
+fn dollar_tag(chars: &[char]) -> Option<String> {
+    let end = chars.iter().skip(1).position(|c| *c == '$')? + 1;
+    let inner = &chars[1..end];
+    let is_identifier = inner.iter().all(|c| c.is_ascii_alphanumeric() || *c == '_');
+    if is_identifier && !inner.first().is_some_and(|c| c.is_ascii_digit()) {
+        Some(chars[..=end].iter().collect())
+    } else {
+        None
+    }
+}
+
+/// (kind, name) of every table, view, function, and procedure created in `sql`,
+/// in file order. Used as the exports of a .sql file.
+pub fn created_objects(sql: &str) -> Vec<(&'static str, String)> {
+    split_statements(sql)
+        .iter()
+        .flat_map(|s| parse_statement(s))
+        .filter_map(|statement| match statement {
+            Statement::CreateTable { name, .. } => Some(("table", name)),
+            Statement::CreateObject { kind, name } => Some((kind, name)),
+            _ => None,
+        })
+        .collect()
+}
+
+/// Apply every file's statements in order and return the resulting tables and
+/// other objects. `files` holds (project-relative path, content) pairs.
+pub fn build_schema(files: &[(String, String)]) -> (Vec<SchemaTable>, Vec<SchemaObject>) {
+    let mut tables: Vec<SchemaTable> = Vec::new();
+    let mut objects: Vec<SchemaObject> = Vec::new();
+
+    for (path, content) in files {
+        for statement in split_statements(content).iter().flat_map(|s| parse_statement(s)) {
+            match statement {
+                Statement::CreateTable { name, columns } => {
+                    tables.retain(|t| !same_name(&t.name, &name));
+                    tables.push(SchemaTable { name, columns, source_file: path.clone() });
+                }
+                Statement::CreateObject { kind, name } => {
+                    objects.retain(|o| !same_name(&o.name, &name));
+                    objects.push(SchemaObject { kind: kind.to_string(), name, source_file: path.clone() });
+                }
+                Statement::AddColumn { table, column } => {
+                    if let Some(t) = tables.iter_mut().find(|t| same_name(&t.name, &table)) {
+                        if !t.columns.iter().any(|c| same_name(c, &column)) {
+                            t.columns.push(column);
+                        }
+                    }
+                }
+                Statement::DropColumn { table, column } => {
+                    if let Some(t) = tables.iter_mut().find(|t| same_name(&t.name, &table)) {
+                        t.columns.retain(|c| !same_name(c, &column));
+                    }
+                }
+                Statement::RenameColumn { table, from, to } => {
+                    if let Some(t) = tables.iter_mut().find(|t| same_name(&t.name, &table)) {
+                        if let Some(c) = t.columns.iter_mut().find(|c| same_name(c, &from)) {
+                            *c = to;
+                        }
+                    }
+                }
+                Statement::RenameTable { from, to } => {
+                    if let Some(t) = tables.iter_mut().find(|t| same_name(&t.name, &from)) {
+                        t.name = to;
+                    }
+                }
+                Statement::Drop { names } => {
+                    tables.retain(|t| !names.iter().any(|n| same_name(&t.name, n)));
+                    objects.retain(|o| !names.iter().any(|n| same_name(&o.name, n)));
+                }
+            }
+        }
+    }
+
+    (tables, objects)
+}
+
+/// SQL identifiers are case-insensitive unless quoted; quotes are already stripped.
+fn same_name(a: &str, b: &str) -> bool {
+    a.eq_ignore_ascii_case(b)
+}
+
+/// Schema changes made by one statement (several for multi-action ALTER TABLE).
+fn parse_statement(stmt: &str) -> Vec<Statement> {
+    let (first, _) = take_word(stmt);
+    match first.to_ascii_uppercase().as_str() {
+        "CREATE" => parse_create(stmt).into_iter().collect(),
+        "ALTER" => parse_alter(stmt),
+        "DROP" => parse_drop(stmt).into_iter().collect(),
+        _ => Vec::new(),
+    }
+}
+
+fn parse_create(stmt: &str) -> Option<Statement> {
+    let mut rest = eat(stmt, "CREATE")?;
+    rest = skip(rest, &["OR", "REPLACE"]);
+    while let Some(r) = ["TEMP", "TEMPORARY", "UNLOGGED", "GLOBAL", "LOCAL"].iter().find_map(|k| eat(rest, k)) {
+        rest = r;
+    }
+
+    let (kind, rest) = object_kind(rest)?;
+    let rest = skip(rest, &["IF", "NOT", "EXISTS"]);
+    let (name, rest) = take_word(rest);
+    let name = clean_identifier(name)?;
+
+    if kind == "table" {
+        let columns = if rest.trim_start().starts_with('(') { column_names(rest) } else { Vec::new() };
+        Some(Statement::CreateTable { name, columns })
+    } else {
+        Some(Statement::CreateObject { kind, name })
+    }
+}
+
+fn parse_alter(stmt: &str) -> Vec<Statement> {
+    let Some(rest) = eat(stmt, "ALTER").and_then(|r| eat(r, "TABLE")) else {
+        return Vec::new();
+    };
+    let rest = skip(rest, &["IF", "EXISTS"]);
+    let rest = skip(rest, &["ONLY"]);
+    let (table, actions) = take_word(rest);
+    let Some(table) = clean_identifier(table) else {
+        return Vec::new();
+    };
+
+    match eat(actions, "RENAME") {
+        Some(rename) => parse_rename(table, rename).into_iter().collect(),
+        None => parse_alter_actions(&table, actions),
+    }
+}
+
+/// RENAME TO new_name, or RENAME [COLUMN] old TO new.
+fn parse_rename(table: String, rename: &str) -> Option<Statement> {
+    if let Some(to) = eat(rename, "TO") {
+        let to = clean_identifier(take_word(to).0)?;
+        return Some(Statement::RenameTable { from: table, to });
+    }
+    let rest = skip(rename, &["COLUMN"]);
+    let (from, rest) = take_word(rest);
+    let to = clean_identifier(take_word(eat(rest, "TO")?).0)?;
+    Some(Statement::RenameColumn { table, from: clean_identifier(from)?, to })
+}
+
+/// ADD/DROP column actions of an ALTER TABLE, one statement per comma-separated action.
+fn parse_alter_actions(table: &str, actions: &str) -> Vec<Statement> {
+    split_top_level(actions)
+        .iter()
+        .filter_map(|action| {
+            if let Some(rest) = eat(action, "ADD") {
+                let explicit = eat(rest, "COLUMN");
+                let rest = skip(explicit.unwrap_or(rest), &["IF", "NOT", "EXISTS"]);
+                let (column, after) = take_word(rest);
+                if explicit.is_none() && is_constraint_start(column, after) {
+                    return None;
+                }
+                Some(Statement::AddColumn { table: table.to_string(), column: clean_identifier(column)? })
+            } else if let Some(rest) = eat(action, "DROP") {
+                let explicit = eat(rest, "COLUMN");
+                let rest = skip(explicit.unwrap_or(rest), &["IF", "EXISTS"]);
+                let (column, after) = take_word(rest);
+                if explicit.is_none() && is_constraint_start(column, after) {
+                    return None;
+                }
+                Some(Statement::DropColumn { table: table.to_string(), column: clean_identifier(column)? })
+            } else {
+                None
+            }
+        })
+        .collect()
+}
+
+fn parse_drop(stmt: &str) -> Option<Statement> {
+    let rest = eat(stmt, "DROP")?;
+    let (_, rest) = object_kind(rest)?;
+    let rest = skip(rest, &["IF", "EXISTS"]);
+    let names: Vec<String> = split_top_level(rest)
+        .iter()
+        .filter_map(|n| clean_identifier(take_word(n).0))
+        .collect();
+    if names.is_empty() {
+        None
+    } else {
+        Some(Statement::Drop { names })
+    }
+}
+
+/// Schema object keyword(s) at the start of `s`, mapped to a kind.
+fn object_kind(s: &str) -> Option<(&'static str, &str)> {
+    if let Some(rest) = eat(s, "TABLE") {
+        return Some(("table", rest));
+    }
+    if let Some(rest) = eat(s, "VIEW").or_else(|| eat(s, "MATERIALIZED").and_then(|r| eat(r, "VIEW"))) {
+        return Some(("view", rest));
+    }
+    if let Some(rest) = eat(s, "FUNCTION") {
+        return Some(("function", rest));
+    }
+    if let Some(rest) = eat(s, "PROCEDURE") {
+        return Some(("procedure", rest));
+    }
+    None
+}
+
+/// Whether an ADD/DROP target or table element is a constraint or index rather than a column.
+fn is_constraint_start(word: &str, after: &str) -> bool {
+    let upper = word.to_ascii_uppercase();
+    if CONSTRAINT_KEYWORDS.contains(&upper.as_str()) {
+        return true;
+    }
+    if !INDEX_KEYWORDS.contains(&upper.as_str()) {
+        return false;
+    }
+    let trimmed = after.trim_start();
+    let (next, next_rest) = take_word(trimmed);
+    if trimmed.starts_with('(') || next.eq_ignore_ascii_case("KEY") || next.eq_ignore_ascii_case("INDEX") {
+        return true;
+    }
+    // "KEY idx (a)" is an index but "key VARCHAR(10)" is a column named key:
+    // an index name is followed by whitespace before its column list
+    if upper == "KEY" {
+        next_rest.starts_with(char::is_whitespace) && next_rest.trim_start().starts_with('(')
+    } else {
+        next_rest.trim_start().starts_with('(')
+    }
+}
+
+/// Column names from a parenthesized CREATE TABLE element list.
+fn column_names(list: &str) -> Vec<String> {
+    let Some(inner) = parenthesized(list) else {
+        return Vec::new();
+    };
+    split_top_level(inner)
+        .iter()
+        .filter_map(|element| {
+            let (word, after) = take_word(element);
+            if is_constraint_start(word, after) {
+                None
+            } else {
+                clean_identifier(word)
+            }
+        })
+        .collect()
+}
+
+/// Text inside the first balanced pair of parentheses in `s`.
+fn parenthesized(s: &str) -> Option<&str> {
+    let open = s.find('(')?;
+    let mut depth = 0;
+    let mut quote: Option<char> = None;
+    for (i, c) in s[open..].char_indices() {
+        match (quote, c) {
+            (Some(q), _) if c == q => quote = None,
+            (Some(_), _) => {}
+            (None, '\'') | (None, '"') | (None, '`') => quote = Some(c),
+            (None, '(') => depth += 1,
+            (None, ')') => {
+                depth -= 1;
+                if depth == 0 {
+                    return Some(&s[open + 1..open + i]);
+                }
+            }
+            _ => {}
+        }
+    }
+    None
+}
+
+/// Split on commas that are not inside parentheses or quotes.
+fn split_top_level(s: &str) -> Vec<&str> {
+    let mut parts = Vec::new();
+    let mut depth = 0i32;
+    let mut quote: Option<char> = None;
+    let mut start = 0;
+    for (i, c) in s.char_indices() {
+        match (quote, c) {
+            (Some(q), _) if c == q => quote = None,
+            (Some(_), _) => {}
+            (None, '\'') | (None, '"') | (None, '`') => quote = Some(c),
+            (None, '(') => depth += 1,
+            (None, ')') => depth -= 1,
+            (None, ',') if depth == 0 => {
+                parts.push(s[start..i].trim());
+                start = i + 1;
+            }
+            _ => {}
+        }
+    }
+    parts.push(s[start..].trim());
+    parts.into_iter().filter(|p| !p.is_empty()).collect()
+}
+
+/// First word of `s` (ending at whitespace, "(" or ","; quoted identifiers kept
+/// whole) and the text after it.
+fn take_word(s: &str) -> (&str, &str) {
+    let s = s.trim_start();
+    let end = match s.chars().next() {
+        Some(q @ ('"' | '`' | '[')) => {
+            let close = if q == '[' { ']' } else { q };
+            s[1..].find(close).map(|i| i + 2).unwrap_or(s.len())
+        }
+        _ => s.find(|c: char| c.is_whitespace() || c == '(' || c == ',').unwrap_or(s.len()),
+    };
+    // Schema-qualified quoted names ("public"."users") continue after the dot
+    let mut end = end;
+    while s[end..].starts_with('.') {
+        let (next, _) = take_word(&s[end + 1..]);
+        end += 1 + next.len();
+    }
+    (&s[..end], &s[end..])
+}
+
+/// `s` with the keyword `kw` (case-insensitive) removed from its start, if present.
+fn eat<'a>(s: &'a str, kw: &str) -> Option<&'a str> {
+    let (word, rest) = take_word(s);
+    if word.eq_ignore_ascii_case(kw) {
+        Some(rest)
+    } else {
+        None
+    }
+}
+
+/// `s` with the keyword sequence `kws` removed from its start, or `s` unchanged.
+fn skip<'a>(s: &'a str, kws: &[&str]) -> &'a str {
+    let mut rest = s;
+    for kw in kws {
+        match eat(rest, kw) {
+            Some(r) => rest = r,
+            None => return s,
+        }
+    }
+    rest
+}
+
+/// Identifier without quoting; None for empty names.
+fn clean_identifier(word: &str) -> Option<String> {
+    let name: String = word.chars().filter(|c| !matches!(c, '"' | '`' | '[' | ']')).collect();
+    let name = name.trim_end_matches(';').trim();
+    if name.is_empty() {
+        None
+    } else {
+        Some(name.to_string())
+    }
+}
+
+/// Project-relative path and content of every .sql file under the project, sorted
+/// by path. Unlike module scanning this includes migrations/ directories.
+pub fn collect_sql_files(project_path: &str) -> Vec<(String, String)> {
+    let root = Path::new(project_path);
+    let mut paths = Vec::new();
+    walk_sql(root, root, &mut paths);
+    paths.sort();
+    paths.truncate(MAX_SQL_FILES);
+
+    paths
+        .into_iter()
+        .filter_map(|rel| {
+            let content = safe_read::read_text(root.join(&rel)).ok()?;
+            Some((rel, content))
+        })
+        .collect()
+}
+
+fn walk_sql(root: &Path, dir: &Path, paths: &mut Vec<String>) {
+    let Ok(entries) = fs::read_dir(dir) else {
+        return;
+    };
+    for entry in entries.flatten() {
+        let path = entry.path();
+        let Ok(rel) = path.strip_prefix(root) else {
+            continue;
+        };
+        let rel = rel.to_string_lossy().replace('\\', "/");
+        if path.is_dir() {
+            // in_scan_scope checks directories via a placeholder file name
+            if freshness::in_scan_scope(&format!("{}/_", rel)) {
+                walk_sql(root, &path, paths);
+            }
+        } else if rel.ends_with(".sql") && freshness::in_scan_scope(&rel) {
+            paths.push(rel);
+        }
+    }
+}
+
+/// Render the schema as the "### Database Schema" markdown subsection.
+pub fn render_overview(tables: &[SchemaTable], objects: &[SchemaObject], source_files: usize) -> String {
+    let mut lines = vec![
+        SCHEMA_HEADING.to_string(),
+        String::new(),
+        format!(
+            "_Generated from {} SQL file{} by Project Jumpstart. Regenerate instead of editing by hand._",
+            source_files,
+            if source_files == 1 { "" } else { "s" }
+        ),
+        String::new(),
+    ];
+
+    if tables.is_empty() {
+        lines.push("No tables found.".to_string());
+    } else {
+        lines.push("| Table | Columns | Defined in |".to_string());
+        lines.push("|-------|---------|------------|".to_string());
+        for table in tables {
+            let mut columns = table
+                .columns
+                .iter()
+                .take(MAX_LISTED_COLUMNS)
+                .cloned()
+                .collect::<Vec<_>>()
+                .join(", ");
+            if table.columns.len() > MAX_LISTED_COLUMNS {
+                columns.push_str(&format!(", … (+{})", table.columns.len() - MAX_LISTED_COLUMNS));
+            }
+            lines.push(format!("| `{}` | {} | `{}` |", table.name, columns, table.source_file));
+        }
+    }
+
+    for (kind, label) in [("view", "Views"), ("function", "Functions"), ("procedure", "Procedures")] {
+        let names: Vec<String> = objects
+            .iter()
+            .filter(|o| o.kind == kind)
+            .map(|o| format!("`{}`", o.name))
+            .collect();
+        if !names.is_empty() {
+            lines.push(String::new());
+            lines.push(format!("**{}:** {}", label, names.join(", ")));
+        }
+    }
+
+    lines.join("\n")
+}
+
+/// Insert `section` into the "## Architecture" section of CLAUDE.md content,
+/// replacing a previous "### Database Schema" subsection. Adds an Architecture
+/// section at the end when there is none.
+pub fn merge_into_architecture(claude_md: &str, section: &str) -> String {
+    let lines: Vec<&str> = claude_md.lines().collect();
+    let section_lines: Vec<&str> = section.lines().collect();
+
+    let Some(arch) = lines.iter().position(|l| is_architecture_heading(l)) else {
+        let mut out = claude_md.trim_end().to_string();
+        if !out.is_empty() {
+            out.push_str("\n\n");
+        }
+        out.push_str("## Architecture\n\n");
+        out.push_str(section.trim_end());
+        out.push('\n');
+        return out;
+    };
+    let arch_end = lines[arch + 1..]
+        .iter()
+        .position(|l| l.starts_with("## "))
+        .map(|i| arch + 1 + i)
+        .unwrap_or(lines.len());
+
+    let mut out: Vec<&str> = Vec::new();
+    match lines[arch + 1..arch_end].iter().position(|l| l.trim() == SCHEMA_HEADING) {
+        Some(offset) => {
+            let start = arch + 1 + offset;
+            let end = lines[start + 1..arch_end]
+                .iter()
+                .position(|l| l.starts_with("### "))
+                .map(|i| start + 1 + i)
+                .unwrap_or(arch_end);
+            out.extend(&lines[..start]);
+            out.extend(&section_lines);
+            if end < lines.len() {
+                out.push("");
+            }
+            out.extend(&lines[end..]);
+        }
+        None => {
+            let mut insert_at = arch_end;
+            while insert_at > arch + 1 && lines[insert_at - 1].trim().is_empty() {
+                insert_at -= 1;
+            }
+            out.extend(&lines[..insert_at]);
+            out.push("");
+            out.extend(&section_lines);
+            if arch_end < lines.len() {
+                out.push("");
+            }
+            out.extend(&lines[arch_end..]);
+        }
+    }
+
+    let mut merged = out.join("\n");
+    merged.push('\n');
+    merged
+}
+
+/// "## Architecture" (also "## Architecture Overview" and similar), but not
+/// "## Architectural Decisions".
+fn is_architecture_heading(line: &str) -> bool {
+    line.strip_prefix("## ").is_some_and(|title| {
+        let title = title.trim().to_ascii_lowercase();
+        title == "architecture" || title.starts_with("architecture ")
+    })
+}
+
+/// Build the schema overview for a project and merge it into CLAUDE.md when
+/// the project has one. The merge is written as a diff through `engine`.
+pub fn generate_overview(engine: PatchEngine, project_path: &str) -> Result<SchemaOverview, String> {
+    let files = collect_sql_files(project_path);
+    let (tables, objects) = build_schema(&files);
+    let markdown = render_overview(&tables, &objects, files.len());
+
+    let claude_md_path = Path::new(project_path).join("CLAUDE.md");
+    let updated_claude_md = if claude_md_path.exists() {
+        let existing =
+            fs::read_to_string(&claude_md_path).map_err(|e| format!("Failed to read CLAUDE.md: {}", e))?;
+        let merged = merge_into_architecture(&existing, &markdown);
+        patch::replace_file(engine, Path::new(project_path), "CLAUDE.md", &existing, &merged)?
+    } else {
+        false
+    };
+
+    Ok(SchemaOverview {
+        tables,
+        objects,
+        source_files: files.len(),
+        markdown,
+        updated_claude_md,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const INIT: &str = r#"-- @module db/init
+-- @description Initial schema
+
+CREATE TABLE IF NOT EXISTS users (
+    id INTEGER PRIMARY KEY,
+    email TEXT NOT NULL UNIQUE, -- login; unique
+    name TEXT DEFAULT 'a;b',
+    CONSTRAINT email_lower CHECK (email = lower(email))
+);
+
+CREATE TABLE "orders" (
+    id SERIAL,
+    user_id INTEGER REFERENCES users(id),
+    total NUMERIC(10, 2),
+    PRIMARY KEY (id),
+    UNIQUE (user_id, id)
+);
+
+/* views; functions */
+create or replace view active_users as select * from users where name is not null;
+
+CREATE FUNCTION order_total(o orders) RETURNS numeric AS $$
+BEGIN
+  RETURN o.total;
+END;
+$$ LANGUAGE plpgsql;
+
+CREATE INDEX idx_orders_user ON orders (user_id);
+"#;
+
+    #[test]
+    fn test_split_statements_respects_comments_and_quotes() {
+        let statements = split_statements(INIT);
+        assert_eq!(statements.len(), 5);
+        assert!(statements[0].contains("DEFAULT 'a;b'"));
+        assert!(!statements[0].contains("login"));
+        assert!(statements[3].contains("RETURN o.total;"));
+        assert!(statements[2].starts_with("create or replace view"));
+    }
+
+    #[test]
+    fn test_created_objects() {
+        assert_eq!(
+            created_objects(INIT),
+            vec![
+                ("table", "users".to_string()),
+                ("table", "orders".to_string()),
+                ("view", "active_users".to_string()),
+                ("function", "order_total".to_string()),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_build_schema_replays_migrations() {
+        let files = vec![
+            ("migrations/001_init.sql".to_string(), INIT.to_string()),
+            (
+                "migrations/002_changes.sql".to_string(),
+                "ALTER TABLE users ADD COLUMN created_at TIMESTAMP, DROP COLUMN name;\n\
+                 ALTER TABLE users ADD CONSTRAINT u UNIQUE (email);\n\
+                 ALTER TABLE orders RENAME COLUMN total TO amount;\n\
+                 ALTER TABLE orders RENAME TO purchases;\n\
+                 DROP VIEW IF EXISTS active_users;\n\
+                 CREATE TABLE tmp (x int); DROP TABLE tmp;"
+                    .to_string(),
+            ),
+        ];
+        let (tables, objects) = build_schema(&files);
+        assert_eq!(tables.len(), 2);
+        assert_eq!(tables[0].name, "users");
+        assert_eq!(tables[0].columns, vec!["id", "email", "created_at"]);
+        assert_eq!(tables[0].source_file, "migrations/001_init.sql");
+        assert_eq!(tables[1].name, "purchases");
+        assert_eq!(tables[1].columns, vec!["id", "user_id", "amount"]);
+        assert_eq!(objects.len(), 1);
+        assert_eq!(objects[0].name, "order_total");
+    }
+
+    #[test]
+    fn test_merge_into_architecture() {
+        let section = "### Database Schema\n\nnew";
+        let md = "# App\n\n## Architecture\n\nLayered.\n\n## Commands\n\nnpm test\n";
+        let merged = merge_into_architecture(md, section);
+        assert_eq!(
+            merged,
+            "# App\n\n## Architecture\n\nLayered.\n\n### Database Schema\n\nnew\n\n## Commands\n\nnpm test\n"
+        );
+
+        // Regenerating replaces only the schema subsection
+        let again = merge_into_architecture(&merged.replace("Layered.", "Layered!"), "### Database Schema\n\nnewer");
+        assert!(again.contains("Layered!\n\n### Database Schema\n\nnewer\n\n## Commands"));
+        assert!(!again.contains("\nnew\n"));
+
+        // "Architectural Decisions" is not the Architecture section
+        let appended = merge_into_architecture("# App\n\n## Architectural Decisions\n\n- x\n", section);
+        assert!(appended.ends_with("- x\n\n## Architecture\n\n### Database Schema\n\nnew\n"));
+    }
+
+    #[test]
+    fn test_generate_overview_writes_claude_md() {
+        let dir = tempfile::tempdir().unwrap();
+        let root = dir.path();
+        fs::create_dir_all(root.join("db/migrations")).unwrap();
+        fs::create_dir_all(root.join("node_modules/pkg")).unwrap();
+        fs::write(root.join("db/migrations/001_init.sql"), INIT).unwrap();
+        fs::write(root.join("node_modules/pkg/schema.sql"), "CREATE TABLE ignored (x int);").unwrap();
+
+        let path = root.to_str().unwrap();
+        let overview = generate_overview(PatchEngine::Builtin, path).unwrap();
+        assert!(!overview.updated_claude_md);
+        assert_eq!(overview.source_files, 1);
+        assert!(overview.markdown.contains("| `users` | id, email, name | `db/migrations/001_init.sql` |"));
+        assert!(overview.markdown.contains("**Views:** `active_users`"));
+
+        fs::write(root.join("CLAUDE.md"), "# App\n\n## Architecture\n\nNotes.\n").unwrap();
+        let overview = generate_overview(PatchEngine::Builtin, path).unwrap();
+        assert!(overview.updated_claude_md);
+        let written = fs::read_to_string(root.join("CLAUDE.md")).unwrap();
+        assert!(written.starts_with("# App\n\n## Architecture\n\nNotes.\n\n### Database Schema\n"));
+
+        // Nothing changed since the last run, so CLAUDE.md is not rewritten
+        let overview = generate_overview(PatchEngine::Builtin, path).unwrap();
+        assert!(!overview.updated_claude_md);
+        assert_eq!(fs::read_to_string(root.join("CLAUDE.md")).unwrap(), written);
+    }
+}
@@ -0,0 +1,387 @@
+//! @module core/stack_presets
+//! @description Built-in tech stack presets and preset matching for kickstart
+//!
+//! PURPOSE:
+//! - Provide the built-in preset library (Next.js+tRPC, Tauri+React, FastAPI+SQLModel, ...)
+//! - Snap a language/framework/database/styling selection to the closest preset
+//! - Render a preset's conventions, directory layout, and starter skills as prompt guidance
+//!
+//! DEPENDENCIES:
+//! - models::stack_preset - StackPreset, StarterSkill
+//!
+//! EXPORTS:
+//! - builtin_presets - The built-in preset library
+//! - find_preset - Look up a preset by ID
+//! - match_preset - Closest preset for a stack selection
+//! - preset_guidance - Markdown section describing a preset for kickstart prompts
+//!
+//! PATTERNS:
+//! - Language and framework must both match; database and styling only rank candidates
+//! - A selected database/styling that differs from the preset's rules the preset out
+//! - Values compare case-insensitively, ignoring spaces, dots, and dashes ("NextJS" == "Next.js")
+//!
+//! CLAUDE NOTES:
+//! - User presets are passed in alongside built-ins; on equal scores the earlier preset wins,
+//!   so callers list user presets first to let them shadow built-ins
+//! - Preset values use the names from INFER_STACK_SYSTEM_PROMPT's allowed options
+
+use crate::models::stack_preset::{StackPreset, StarterSkill};
+
+fn skill(name: &str, description: &str, content: &str) -> StarterSkill {
+    StarterSkill {
+        name: name.to_string(),
+        description: description.to_string(),
+        content: content.to_string(),
+    }
+}
+
+#[allow(clippy::too_many_arguments)]
+fn builtin(
+    id: &str,
+    name: &str,
+    description: &str,
+    (language, framework): (&str, &str),
+    database: Option<&str>,
+    styling: Option<&str>,
+    testing: Option<&str>,
+    conventions: &[&str],
+    directory_layout: &str,
+    starter_skills: Vec<StarterSkill>,
+) -> StackPreset {
+    StackPreset {
+        id: id.to_string(),
+        name: name.to_string(),
+        description: description.to_string(),
+        language: language.to_string(),
+        framework: framework.to_string(),
+        database: database.map(str::to_string),
+        styling: styling.map(str::to_string),
+        testing: testing.map(str::to_string),
+        conventions: conventions.iter().map(|c| c.to_string()).collect(),
+        directory_layout: directory_layout.to_string(),
+        starter_skills,
+        builtin: true,
+        created_at: None,
+        updated_at: None,
+    }
+}
+
+/// The built-in preset library.
+pub fn builtin_presets() -> Vec<StackPreset> {
+    vec![
+        builtin(
+            "nextjs-trpc",
+            "Next.js + tRPC",
+            "Full-stack TypeScript web app with end-to-end typed APIs",
+            ("TypeScript", "Next.js"),
+            Some("PostgreSQL"),
+            Some("Tailwind CSS"),
+            Some("Vitest + Playwright"),
+            &[
+                "App Router only; pages live in src/app, Server Components by default",
+                "All server logic goes through tRPC routers in src/server/api/routers, one router per domain",
+                "Validate every procedure input with a zod schema",
+                "Database access only through Prisma in src/server/db.ts, never from components",
+                "Mark client components with \"use client\" and keep them small",
+                "File names are kebab-case; React components are PascalCase named exports",
+            ],
+            "src/\n  app/\n    api/trpc/[trpc]/\n  components/\n    ui/\n  server/\n    api/\n      routers/\n    db.ts\n  lib/\nprisma/\n  schema.prisma\ntests/\n  e2e/",
+            vec![
+                skill(
+                    "trpc-router",
+                    "Add a tRPC router with zod-validated procedures",
+                    "Create src/server/api/routers/<domain>.ts exporting a router built with createTRPCRouter. \
+                     Validate inputs with zod, use protectedProcedure for authenticated calls, and register the \
+                     router in src/server/api/root.ts.",
+                ),
+                skill(
+                    "server-component-page",
+                    "Add an App Router page that fetches data on the server",
+                    "Create src/app/<route>/page.tsx as an async Server Component. Fetch through the server-side \
+                     tRPC caller, and move interactive pieces into a separate \"use client\" component.",
+                ),
+            ],
+        ),
+        builtin(
+            "tauri-react",
+            "Tauri + React",
+            "Desktop app with a Rust backend and a React + Vite frontend",
+            ("TypeScript", "Tauri"),
+            Some("SQLite"),
+            Some("Tailwind CSS"),
+            Some("Vitest + cargo test"),
+            &[
+                "Frontend in src/, Rust backend in src-tauri/src",
+                "Every IPC command is a #[tauri::command] returning Result<T, String> and is registered in lib.rs",
+                "Rust commands are thin wrappers; business logic lives in src-tauri/src/core",
+                "Mirror every Rust model in src/types and keep serde rename_all = \"camelCase\"",
+                "Call the backend only through typed invoke() wrappers in src/lib/tauri.ts",
+                "Global UI state in Zustand stores under src/stores",
+            ],
+            "src/\n  components/\n  hooks/\n  lib/\n    tauri.ts\n  stores/\n  types/\nsrc-tauri/\n  src/\n    commands/\n    core/\n    db/\n    models/\n    lib.rs\n  Cargo.toml\n  tauri.conf.json",
+            vec![
+                skill(
+                    "tauri-command",
+                    "Add a Tauri IPC command end to end",
+                    "Add the #[tauri::command] function in src-tauri/src/commands/<domain>.rs, register it in \
+                     generate_handler! in lib.rs, add the matching TypeScript type in src/types, and add a typed \
+                     wrapper in src/lib/tauri.ts.",
+                ),
+                skill(
+                    "sqlite-migration",
+                    "Add a column or table with an idempotent migration",
+                    "Add CREATE TABLE IF NOT EXISTS to create_tables, or a migrate_* function that checks for the \
+                     column before ALTER TABLE, and call it from init_db.",
+                ),
+            ],
+        ),
+        builtin(
+            "fastapi-sqlmodel",
+            "FastAPI + SQLModel",
+            "Typed Python API with SQLModel models and Alembic migrations",
+            ("Python", "FastAPI"),
+            Some("PostgreSQL"),
+            None,
+            Some("pytest"),
+            &[
+                "One APIRouter per resource in app/routers, included in app/main.py",
+                "SQLModel table models in app/models; request/response schemas are separate non-table models",
+                "Get sessions through a Depends(get_session) dependency; never create engines in routes",
+                "Every schema change gets an Alembic migration",
+                "Type hints everywhere; run mypy and ruff before committing",
+            ],
+            "app/\n  main.py\n  db.py\n  models/\n  routers/\n  services/\nalembic/\n  versions/\ntests/\n  conftest.py\npyproject.toml",
+            vec![
+                skill(
+                    "fastapi-resource",
+                    "Add a CRUD resource with router, models, and tests",
+                    "Create the SQLModel table and its Create/Read/Update schemas in app/models/<resource>.py, an \
+                     APIRouter in app/routers/<resource>.py, an Alembic migration, and pytest tests using the \
+                     TestClient fixture from tests/conftest.py.",
+                ),
+            ],
+        ),
+        builtin(
+            "django",
+            "Django",
+            "Server-rendered Python web app with the Django ORM and admin",
+            ("Python", "Django"),
+            Some("PostgreSQL"),
+            None,
+            Some("pytest-django"),
+            &[
+                "One Django app per domain under apps/",
+                "Fat models, thin views; business logic in services.py per app",
+                "Register every model in the app's admin.py",
+                "Settings split into config/settings/base.py, dev.py, and prod.py",
+                "Never edit generated migrations by hand once merged",
+            ],
+            "config/\n  settings/\n  urls.py\napps/\n  <app>/\n    models.py\n    views.py\n    services.py\n    admin.py\n    tests/\ntemplates/\nstatic/\nmanage.py",
+            vec![skill(
+                "django-app",
+                "Add a Django app with models, admin, and tests",
+                "Run `python manage.py startapp <name> apps/<name>`, add it to INSTALLED_APPS, create models with \
+                 __str__, register them in admin.py, run makemigrations, and add pytest-django tests.",
+            )],
+        ),
+        builtin(
+            "go-gin",
+            "Go + Gin",
+            "Go HTTP API with Gin handlers and a layered internal package",
+            ("Go", "Gin"),
+            Some("PostgreSQL"),
+            None,
+            Some("go test"),
+            &[
+                "Entry point in cmd/server/main.go; all other code under internal/",
+                "Handlers only bind and validate requests, then call a service",
+                "Return errors wrapped with fmt.Errorf(\"...: %w\", err); never panic in handlers",
+                "Pass context.Context as the first argument through service and repository layers",
+                "Table-driven tests next to the code (*_test.go)",
+            ],
+            "cmd/\n  server/\n    main.go\ninternal/\n  handlers/\n  services/\n  repository/\n  models/\nmigrations/\ngo.mod",
+            vec![skill(
+                "gin-endpoint",
+                "Add a Gin endpoint with service and repository",
+                "Add the handler in internal/handlers, the business logic in internal/services, the SQL in \
+                 internal/repository, register the route in the router setup, and add table-driven tests.",
+            )],
+        ),
+        builtin(
+            "rust-axum",
+            "Rust + Axum",
+            "Async Rust HTTP API with Axum, sqlx, and tokio",
+            ("Rust", "Axum"),
+            Some("PostgreSQL"),
+            None,
+            Some("cargo test"),
+            &[
+                "Routes are assembled in src/routes/mod.rs; one module per resource",
+                "Shared state (pool, config) in an AppState passed with State<AppState>",
+                "Errors implement IntoResponse through a single AppError type",
+                "Queries use sqlx::query_as! so they are checked at compile time",
+                "cargo clippy -- -D warnings must pass",
+            ],
+            "src/\n  main.rs\n  routes/\n  models/\n  error.rs\n  state.rs\nmigrations/\ntests/\nCargo.toml",
+            vec![skill(
+                "axum-route",
+                "Add an Axum route module",
+                "Create src/routes/<resource>.rs with handler functions taking State<AppState> and typed \
+                 extractors, return Result<Json<T>, AppError>, nest the router in src/routes/mod.rs, and add an \
+                 integration test under tests/.",
+            )],
+        ),
+    ]
+}
+
+fn normalize(value: &str) -> String {
+    value
+        .chars()
+        .filter(|c| !c.is_whitespace() && !matches!(c, '.' | '-' | '_'))
+        .flat_map(|c| c.to_lowercase())
+        .collect()
+}
+
+fn same(a: &str, b: &str) -> bool {
+    normalize(a) == normalize(b)
+}
+
+/// Look up a preset by ID.
+pub fn find_preset<'a>(presets: &'a [StackPreset], id: &str) -> Option<&'a StackPreset> {
+    presets.iter().find(|p| p.id == id)
+}
+
+/// Closest preset for a stack selection, or None when no preset shares the
+/// language and framework. Unset database/styling neither help nor hurt.
+pub fn match_preset<'a>(
+    presets: &'a [StackPreset],
+    language: Option<&str>,
+    framework: Option<&str>,
+    database: Option<&str>,
+    styling: Option<&str>,
+) -> Option<&'a StackPreset> {
+    let (language, framework) = (language?, framework?);
+    let optional_score = |selected: Option<&str>, preset: Option<&String>| -> Option<u32> {
+        match (selected.filter(|s| !s.trim().is_empty()), preset) {
+            (None, _) => Some(0),
+            (Some(s), Some(p)) if same(s, p) => Some(1),
+            (Some(_), Some(_)) => None,
+            (Some(_), None) => Some(0),
+        }
+    };
+
+    let mut best: Option<(&StackPreset, u32)> = None;
+    for preset in presets {
+        if !same(language, &preset.language) || !same(framework, &preset.framework) {
+            continue;
+        }
+        let (Some(db), Some(style)) = (
+            optional_score(database, preset.database.as_ref()),
+            optional_score(styling, preset.styling.as_ref()),
+        ) else {
+            continue;
+        };
+        if best.map(|(_, score)| db + style > score).unwrap_or(true) {
+            best = Some((preset, db + style));
+        }
+    }
+    best.map(|(preset, _)| preset)
+}
+
+/// Markdown section describing a preset, appended to kickstart prompts so the
+/// generated structure and conventions follow it.
+pub fn preset_guidance(preset: &StackPreset) -> String {
+    let mut out = format!("**Stack Preset: {}**\n{}\n", preset.name, preset.description);
+
+    let mut stack = vec![
+        format!("Language: {}", preset.language),
+        format!("Framework: {}", preset.framework),
+    ];
+    if let Some(ref db) = preset.database {
+        stack.push(format!("Database: {}", db));
+    }
+    if let Some(ref styling) = preset.styling {
+        stack.push(format!("Styling: {}", styling));
+    }
+    if let Some(ref testing) = preset.testing {
+        stack.push(format!("Testing: {}", testing));
+    }
+    out.push_str(&format!("{}\n", stack.join(" | ")));
+
+    if !preset.conventions.is_empty() {
+        out.push_str("\nConventions to use:\n");
+        for convention in &preset.conventions {
+            out.push_str(&format!("- {}\n", convention));
+        }
+    }
+    if !preset.directory_layout.trim().is_empty() {
+        out.push_str(&format!("\nDirectory layout to use:\n```\n{}\n```\n", preset.directory_layout.trim_end()));
+    }
+    if !preset.starter_skills.is_empty() {
+        out.push_str("\nStarter skills (reusable workflows to mention):\n");
+        for s in &preset.starter_skills {
+            out.push_str(&format!("- {}: {}\n", s.name, s.description));
+        }
+    }
+    out
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_builtin_ids_unique() {
+        let presets = builtin_presets();
+        let mut ids: Vec<&str> = presets.iter().map(|p| p.id.as_str()).collect();
+        ids.sort();
+        ids.dedup();
+        assert_eq!(ids.len(), presets.len());
+        assert!(presets.iter().all(|p| p.builtin && !p.conventions.is_empty()));
+    }
+
+    #[test]
+    fn test_match_preset() {
+        let presets = builtin_presets();
+        let id = |m: Option<&StackPreset>| m.map(|p| p.id.clone());
+
+        assert_eq!(
+            id(match_preset(&presets, Some("typescript"), Some("NextJS"), None, None)).as_deref(),
+            Some("nextjs-trpc")
+        );
+        assert_eq!(
+            id(match_preset(&presets, Some("Python"), Some("FastAPI"), Some("PostgreSQL"), Some(""))).as_deref(),
+            Some("fastapi-sqlmodel")
+        );
+        // A different database rules the preset out
+        assert!(match_preset(&presets, Some("TypeScript"), Some("Next.js"), Some("MongoDB"), None).is_none());
+        assert!(match_preset(&presets, Some("TypeScript"), None, None, None).is_none());
+        assert!(match_preset(&presets, Some("Ruby"), Some("Rails"), None, None).is_none());
+    }
+
+    #[test]
+    fn test_match_prefers_more_specific_and_earlier() {
+        let mut custom = builtin_presets()[0].clone();
+        custom.id = "custom".to_string();
+        custom.database = Some("MySQL".to_string());
+        let mut presets = vec![custom];
+        presets.extend(builtin_presets());
+
+        let pick = |db: Option<&str>| match_preset(&presets, Some("TypeScript"), Some("Next.js"), db, None).unwrap().id.clone();
+        assert_eq!(pick(Some("MySQL")), "custom");
+        assert_eq!(pick(Some("PostgreSQL")), "nextjs-trpc");
+        // Equal score: the user preset listed first wins
+        assert_eq!(pick(None), "custom");
+    }
+
+    #[test]
+    fn test_preset_guidance() {
+        let presets = builtin_presets();
+        let guidance = preset_guidance(find_preset(&presets, "tauri-react").unwrap());
+        assert!(guidance.starts_with("**Stack Preset: Tauri + React**"));
+        assert!(guidance.contains("Database: SQLite"));
+        assert!(guidance.contains("- Frontend in src/, Rust backend in src-tauri/src\n"));
+        assert!(guidance.contains("```\nsrc/\n  components/"));
+        assert!(guidance.contains("- tauri-command: Add a Tauri IPC command end to end\n"));
+    }
+}
@@ -0,0 +1,425 @@
+//! @module core/subagent_lint
+//! @description Lint Claude Code subagent files (.claude/agents/*.md)
+//!
+//! PURPOSE:
+//! - Parse a subagent file's YAML frontmatter (name, description, tools, model)
+//! - Check required fields, name format, tool names, and instruction length
+//! - Find agent names used by more than one file
+//! - Attach a concrete fix suggestion to every issue
+//!
+//! DEPENDENCIES:
+//! - core::slash_commands - command_name for kebab-case name suggestions
+//! - models::agent - SubagentLintIssue, SubagentLintReport
+//!
+//! EXPORTS:
+//! - KNOWN_TOOLS - Built-in Claude Code tool names accepted in `tools:`
+//! - parse_frontmatter - (fields, body) of a subagent file; None without a frontmatter block
+//! - lint_agent_file - Issues in one file and the agent name it declares
+//! - lint_agent_files - Lint a set of files, including duplicate names across them
+//!
+//! PATTERNS:
+//! - Errors are problems that make Claude Code skip or misread the agent (missing fields,
+//!   unknown tools, duplicate names); everything else is a warning
+//! - `tools:` accepts a comma-separated string, a [flow, list], or a block list
+//! - mcp__<server>__<tool> names are accepted without checking
+//!
+//! CLAUDE NOTES:
+//! - The frontmatter parser only handles flat `key: value` pairs and block lists, which is all
+//!   subagent files use; it is not a YAML parser
+//! - Keep KNOWN_TOOLS in sync with the tools Claude Code ships
+
+use crate::core::slash_commands::command_name;
+use crate::models::agent::{SubagentLintIssue, SubagentLintReport};
+
+/// Built-in Claude Code tools a subagent can be given.
+pub const KNOWN_TOOLS: &[&str] = &[
+    "Bash",
+    "BashOutput",
+    "Edit",
+    "ExitPlanMode",
+    "Glob",
+    "Grep",
+    "KillShell",
+    "LS",
+    "MultiEdit",
+    "NotebookEdit",
+    "NotebookRead",
+    "Read",
+    "SlashCommand",
+    "Task",
+    "TodoWrite",
+    "WebFetch",
+    "WebSearch",
+    "Write",
+];
+
+/// Frontmatter fields Claude Code reads for subagents.
+const KNOWN_FIELDS: &[&str] = &["name", "description", "tools", "model", "color"];
+
+/// Values accepted for `model:`.
+const KNOWN_MODELS: &[&str] = &["sonnet", "opus", "haiku", "inherit"];
+
+/// Instructions shorter than this rarely give a subagent enough to work with.
+const MIN_INSTRUCTION_CHARS: usize = 100;
+
+/// Instructions longer than this crowd the subagent's context window.
+const MAX_INSTRUCTION_CHARS: usize = 20_000;
+
+/// Descriptions shorter than this give Claude too little to decide when to delegate.
+const MIN_DESCRIPTION_CHARS: usize = 20;
+
+fn issue(file: &str, severity: &str, field: Option<&str>, message: String, suggestion: Option<String>) -> SubagentLintIssue {
+    SubagentLintIssue {
+        file: file.to_string(),
+        severity: severity.to_string(),
+        field: field.map(str::to_string),
+        message,
+        suggestion,
+    }
+}
+
+fn unquote(value: &str) -> &str {
+    let value = value.trim();
+    value
+        .strip_prefix('"')
+        .and_then(|v| v.strip_suffix('"'))
+        .or_else(|| value.strip_prefix('\'').and_then(|v| v.strip_suffix('\'')))
+        .unwrap_or(value)
+}
+
+/// (key, value) pairs of the leading frontmatter block and the body after it.
+/// Block list items are joined into the value with ", ". None when there is no
+/// closed frontmatter block at the top of the file.
+pub fn parse_frontmatter(content: &str) -> Option<(Vec<(String, String)>, String)> {
+    let content = content.trim_start_matches('\u{feff}');
+    let mut lines = content.lines();
+    if lines.next()?.trim_end() != "---" {
+        return None;
+    }
+
+    let mut fields: Vec<(String, String)> = Vec::new();
+    let mut closed = false;
+    let mut consumed = 1;
+    for line in lines.by_ref() {
+        consumed += 1;
+        if line.trim_end() == "---" {
+            closed = true;
+            break;
+        }
+        let trimmed = line.trim();
+        if trimmed.is_empty() || trimmed.starts_with('#') {
+            continue;
+        }
+        if let Some(item) = trimmed.strip_prefix("- ") {
+            if let Some((_, value)) = fields.last_mut() {
+                if !value.is_empty() {
+                    value.push_str(", ");
+                }
+                value.push_str(unquote(item));
+            }
+            continue;
+        }
+        if let Some((key, value)) = trimmed.split_once(':') {
+            fields.push((key.trim().to_string(), unquote(value).to_string()));
+        }
+    }
+    if !closed {
+        return None;
+    }
+
+    let body: Vec<&str> = content.lines().skip(consumed).collect();
+    Some((fields, body.join("\n")))
+}
+
+fn tool_list(value: &str) -> Vec<String> {
+    value
+        .trim()
+        .trim_start_matches('[')
+        .trim_end_matches(']')
+        .split(',')
+        .map(|t| unquote(t).to_string())
+        .filter(|t| !t.is_empty())
+        .collect()
+}
+
+fn check_tools(file: &str, value: &str, issues: &mut Vec<SubagentLintIssue>) {
+    let tools = tool_list(value);
+    if tools.is_empty() {
+        issues.push(issue(
+            file,
+            "warning",
+            Some("tools"),
+            "tools is empty".to_string(),
+            Some("Remove the tools line to give the agent every tool, or list the tools it needs.".to_string()),
+        ));
+        return;
+    }
+
+    let mut seen: Vec<&str> = Vec::new();
+    for tool in &tools {
+        if seen.contains(&tool.as_str()) {
+            issues.push(issue(
+                file,
+                "warning",
+                Some("tools"),
+                format!("{} is listed more than once", tool),
+                Some(format!("Remove the duplicate {}.", tool)),
+            ));
+            continue;
+        }
+        seen.push(tool);
+
+        if tool.starts_with("mcp__") || KNOWN_TOOLS.contains(&tool.as_str()) {
+            continue;
+        }
+        let suggestion = match KNOWN_TOOLS.iter().find(|known| known.eq_ignore_ascii_case(tool)) {
+            Some(known) => format!("Tool names are case-sensitive: use {}.", known),
+            None => format!("Use one of: {} (or an mcp__server__tool name).", KNOWN_TOOLS.join(", ")),
+        };
+        issues.push(issue(file, "error", Some("tools"), format!("Unknown tool: {}", tool), Some(suggestion)));
+    }
+}
+
+/// Lint one subagent file. `file` is the project-relative path (used for the expected
+/// name and in issues). Returns the declared agent name, if any, and the issues.
+pub fn lint_agent_file(file: &str, content: &str) -> (Option<String>, Vec<SubagentLintIssue>) {
+    let mut issues = Vec::new();
+    let stem = file
+        .rsplit('/')
+        .next()
+        .unwrap_or(file)
+        .trim_end_matches(".md")
+        .to_string();
+
+    let Some((fields, body)) = parse_frontmatter(content) else {
+        issues.push(issue(
+            file,
+            "error",
+            None,
+            "Missing frontmatter; Claude Code ignores agent files without it".to_string(),
+            Some(format!(
+                "Start the file with:\n---\nname: {}\ndescription: <when Claude should use this agent>\n---",
+                command_name(&stem)
+            )),
+        ));
+        return (None, issues);
+    };
+    let field = |key: &str| fields.iter().find(|(k, _)| k == key).map(|(_, v)| v.as_str());
+
+    let name = field("name").map(str::trim).filter(|n| !n.is_empty());
+    match name {
+        None => issues.push(issue(
+            file,
+            "error",
+            Some("name"),
+            "Missing name".to_string(),
+            Some(format!("Add `name: {}`.", command_name(&stem))),
+        )),
+        Some(name) => {
+            let slug = command_name(name);
+            if slug != name {
+                issues.push(issue(
+                    file,
+                    "error",
+                    Some("name"),
+                    format!("Name \"{}\" must use lowercase letters, digits, and hyphens", name),
+                    Some(format!("Rename it to `{}`.", slug)),
+                ));
+            } else if name != stem {
+                issues.push(issue(
+                    file,
+                    "warning",
+                    Some("name"),
+                    format!("Name \"{}\" does not match the file name", name),
+                    Some(format!("Rename the file to .claude/agents/{}.md.", name)),
+                ));
+            }
+        }
+    }
+
+    match field("description").map(str::trim) {
+        None | Some("") => issues.push(issue(
+            file,
+            "error",
+            Some("description"),
+            "Missing description; Claude uses it to decide when to delegate to the agent".to_string(),
+            Some("Add `description:` saying what the agent does and when to use it.".to_string()),
+        )),
+        Some(desc) if desc.chars().count() < MIN_DESCRIPTION_CHARS => issues.push(issue(
+            file,
+            "warning",
+            Some("description"),
+            format!("Description is very short ({} characters)", desc.chars().count()),
+            Some("Say when Claude should use the agent, e.g. \"Use after code changes to review ...\".".to_string()),
+        )),
+        Some(_) => {}
+    }
+
+    if let Some(tools) = field("tools") {
+        check_tools(file, tools, &mut issues);
+    }
+
+    if let Some(model) = field("model").map(str::trim) {
+        if !KNOWN_MODELS.contains(&model) {
+            issues.push(issue(
+                file,
+                "warning",
+                Some("model"),
+                format!("Unrecognized model: {}", model),
+                Some(format!("Use one of: {}.", KNOWN_MODELS.join(", "))),
+            ));
+        }
+    }
+
+    for (key, _) in &fields {
+        if KNOWN_FIELDS.contains(&key.as_str()) {
+            continue;
+        }
+        let suggestion = match KNOWN_FIELDS.iter().find(|known| known.eq_ignore_ascii_case(key)) {
+            Some(known) => format!("Field names are case-sensitive: use `{}`.", known),
+            None => "Remove it; Claude Code ignores unknown fields.".to_string(),
+        };
+        issues.push(issue(file, "warning", Some(key), format!("Unknown field: {}", key), Some(suggestion)));
+    }
+
+    let instructions = body.trim().chars().count();
+    if instructions == 0 {
+        issues.push(issue(
+            file,
+            "error",
+            None,
+            "No instructions after the frontmatter".to_string(),
+            Some("Add the agent's system prompt below the closing ---.".to_string()),
+        ));
+    } else if instructions < MIN_INSTRUCTION_CHARS {
+        issues.push(issue(
+            file,
+            "warning",
+            None,
+            format!("Instructions are very short ({} characters)", instructions),
+            Some("Describe the agent's role, rules, process, and output format.".to_string()),
+        ));
+    } else if instructions > MAX_INSTRUCTION_CHARS {
+        issues.push(issue(
+            file,
+            "warning",
+            None,
+            format!("Instructions are very long ({} characters)", instructions),
+            Some("Move reference material into project files the agent can Read.".to_string()),
+        ));
+    }
+
+    (name.map(str::to_string), issues)
+}
+
+/// Lint (path, content) pairs and flag agent names declared by more than one file.
+pub fn lint_agent_files(files: &[(String, String)]) -> SubagentLintReport {
+    let mut issues = Vec::new();
+    let mut names: Vec<(String, String)> = Vec::new();
+
+    for (file, content) in files {
+        let (name, file_issues) = lint_agent_file(file, content);
+        issues.extend(file_issues);
+        let Some(name) = name else { continue };
+        if let Some((_, first)) = names.iter().find(|(n, _)| *n == name) {
+            issues.push(issue(
+                file,
+                "error",
+                Some("name"),
+                format!("Agent name \"{}\" is also used by {}", name, first),
+                Some(format!("Give this agent a unique name, e.g. `{}-2`, or delete one of the files.", name)),
+            ));
+        } else {
+            names.push((name, file.clone()));
+        }
+    }
+
+    let error_count = issues.iter().filter(|i| i.severity == "error").count() as u32;
+    SubagentLintReport {
+        files_checked: files.len() as u32,
+        error_count,
+        warning_count: issues.len() as u32 - error_count,
+        issues,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const VALID: &str = "---\nname: code-reviewer\ndescription: Reviews changed code for bugs and style issues\n\
+        tools: Read, Grep, Glob, mcp__github__get_pr\nmodel: sonnet\n---\n\n\
+        You are a code reviewer. Read the diff, check each change for bugs, missing tests, and style \
+        problems, and report findings grouped by severity.\n";
+
+    fn messages(issues: &[SubagentLintIssue]) -> Vec<&str> {
+        issues.iter().map(|i| i.message.as_str()).collect()
+    }
+
+    #[test]
+    fn test_parse_frontmatter() {
+        let (fields, body) =
+            parse_frontmatter("---\nname: a\ntools:\n  - Read\n  - \"Grep\"\n---\nBody\nmore").unwrap();
+        assert_eq!(
+            fields,
+            vec![("name".to_string(), "a".to_string()), ("tools".to_string(), "Read, Grep".to_string())]
+        );
+        assert_eq!(body, "Body\nmore");
+        assert!(parse_frontmatter("# Title\n---\nname: a\n---\n").is_none());
+        assert!(parse_frontmatter("---\nname: a\n").is_none());
+    }
+
+    #[test]
+    fn test_valid_agent_has_no_issues() {
+        let (name, issues) = lint_agent_file(".claude/agents/code-reviewer.md", VALID);
+        assert_eq!(name.as_deref(), Some("code-reviewer"));
+        assert!(issues.is_empty(), "{:?}", issues);
+    }
+
+    #[test]
+    fn test_lint_agent_file_issues() {
+        let content = "---\nname: Code Reviewer\ntools: [read, Grep, Grep, Deploy]\nmodel: gpt-4\nColor: blue\n---\nReview.";
+        let (_, issues) = lint_agent_file(".claude/agents/reviewer.md", content);
+        assert_eq!(
+            messages(&issues),
+            vec![
+                "Name \"Code Reviewer\" must use lowercase letters, digits, and hyphens",
+                "Missing description; Claude uses it to decide when to delegate to the agent",
+                "Unknown tool: read",
+                "Grep is listed more than once",
+                "Unknown tool: Deploy",
+                "Unrecognized model: gpt-4",
+                "Unknown field: Color",
+                "Instructions are very short (7 characters)",
+            ]
+        );
+        assert_eq!(issues[0].suggestion.as_deref(), Some("Rename it to `code-reviewer`."));
+        assert_eq!(issues[2].suggestion.as_deref(), Some("Tool names are case-sensitive: use Read."));
+        assert_eq!(issues[6].suggestion.as_deref(), Some("Field names are case-sensitive: use `color`."));
+
+        let (name, issues) = lint_agent_file(".claude/agents/Test Writer.md", "You write tests.");
+        assert_eq!(name, None);
+        assert_eq!(issues.len(), 1);
+        assert!(issues[0].suggestion.as_deref().unwrap().contains("name: test-writer"));
+    }
+
+    #[test]
+    fn test_lint_agent_files_duplicates() {
+        let files = vec![
+            (".claude/agents/code-reviewer.md".to_string(), VALID.to_string()),
+            (".claude/agents/reviewer-copy.md".to_string(), VALID.to_string()),
+        ];
+        let report = lint_agent_files(&files);
+        assert_eq!(report.files_checked, 2);
+        assert_eq!(report.error_count, 1);
+        assert_eq!(report.warning_count, 1);
+        assert_eq!(
+            messages(&report.issues),
+            vec![
+                "Name \"code-reviewer\" does not match the file name",
+                "Agent name \"code-reviewer\" is also used by .claude/agents/code-reviewer.md",
+            ]
+        );
+        assert!(report.issues.iter().all(|i| i.file == ".claude/agents/reviewer-copy.md"));
+    }
+}
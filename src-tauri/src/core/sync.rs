@@ -0,0 +1,692 @@
+//! @module core/sync
+//! @description Encrypted settings and knowledge snapshots stored on a user-provided remote
+//!
+//! PURPOSE:
+//! - Load and save the sync remote, its secret, and the snapshot passphrase
+//! - Seal and open snapshots with a passphrase-derived key (PBKDF2 + AES-256-GCM)
+//! - Pick the settings that travel between machines and three-way merge them
+//! - Read and write the snapshot on S3 (SigV4), WebDAV (basic auth), or a git repository
+//!
+//! DEPENDENCIES:
+//! - aes-gcm / pbkdf2 / sha2 / rand - Snapshot encryption
+//! - hmac - AWS Signature Version 4 for S3
+//! - reqwest - S3 and WebDAV transfers
+//! - core::proc - git clone/fetch/commit/push with timeouts
+//! - core::crypto - Encrypt the remote secret and passphrase at rest
+//! - models::sync - SyncRemote, SyncSnapshot types
+//!
+//! EXPORTS:
+//! - SyncSettings / load_settings / save_settings - Remote, secret, and passphrase
+//! - load_last_sync / load_base_settings / record_sync - State recorded by the last successful sync
+//! - seal / open - Encrypt and decrypt a snapshot with the passphrase
+//! - local_settings / is_syncable_setting - Settings that are part of a snapshot
+//! - SettingsMerge / merge_settings - Three-way merge of local, remote, and last-synced settings
+//! - validate_remote - Check that a remote has the fields its kind needs
+//! - RemoteObject / fetch / store - Read and write the sealed snapshot on the remote
+//! - device_name - Host name recorded in pushed snapshots
+//! - SNAPSHOT_VERSION and the sync.* settings keys
+//!
+//! PATTERNS:
+//! - The remote only ever sees ciphertext; the passphrase never leaves the machine
+//! - Writes are conditional on the version that was read (ETag or git fast-forward), so two
+//!   machines syncing at once fail loudly instead of overwriting each other
+//! - Skills, agents, templates, and learnings merge through commands::imports / db::shared;
+//!   this module only handles settings, which have no updated_at
+//!
+//! CLAUDE NOTES:
+//! - Sealed format: MAGIC | PBKDF2 rounds (u32 BE) | 16-byte salt | 12-byte nonce | ciphertext
+//! - Secrets, machine-bound "enc:" values, sync./shared_db. state, and per-project settings
+//!   (keys ending in a project or plan id) never leave the machine
+//! - The git remote is cloned into ~/.project-jumpstart/sync/<hash>; the snapshot is one file
+//!   committed on the configured branch (created on first push)
+//! - Blocking for git remotes: call from commands, not from the UI thread
+
+use std::collections::{BTreeMap, BTreeSet, HashSet};
+use std::path::{Path, PathBuf};
+use std::process::Command;
+
+use aes_gcm::{
+    aead::{Aead, KeyInit},
+    Aes256Gcm, Nonce,
+};
+use chrono::Utc;
+use hmac::{Hmac, Mac};
+use rand::RngCore;
+use reqwest::{Client, StatusCode};
+use rusqlite::{Connection, OptionalExtension};
+use sha2::{Digest, Sha256};
+
+use crate::core::crypto;
+use crate::core::proc::{self, ProcLimits};
+use crate::models::sync::{SyncRemote, SyncRemoteKind};
+
+pub const REMOTE_SETTING: &str = "sync.remote";
+pub const SECRET_SETTING: &str = "sync.secret";
+pub const PASSPHRASE_SETTING: &str = "sync.passphrase";
+pub const LAST_SYNC_SETTING: &str = "sync.last_synced_at";
+/// Settings as of the last successful sync (JSON object), the base of the three-way merge.
+pub const BASE_SETTINGS_SETTING: &str = "sync.base_settings";
+
+pub const SNAPSHOT_VERSION: u32 = 1;
+
+/// Prefix of every sealed snapshot.
+const MAGIC: &[u8] = b"JSSYNC1\0";
+const SALT_LEN: usize = 16;
+const NONCE_LEN: usize = 12;
+const KDF_ROUNDS: u32 = 600_000;
+
+/// Snapshot path in the bucket or repository when none is configured.
+const DEFAULT_PATH: &str = "project-jumpstart/sync.jssync";
+const DEFAULT_REGION: &str = "us-east-1";
+const DEFAULT_BRANCH: &str = "main";
+
+/// Settings that never leave the machine (secrets and machine-local state).
+const LOCAL_ONLY_KEYS: &[&str] = &["anthropic_api_key", "github_token", "gitlab_token", "bitbucket_token"];
+const LOCAL_ONLY_PREFIXES: &[&str] = &["sync.", "shared_db.", "doc_index."];
+
+const CONFLICT_MESSAGE: &str = "The remote snapshot changed during sync; run sync again";
+
+/// Sync remote plus decrypted secret and passphrase.
+#[derive(Debug, Clone, Default)]
+pub struct SyncSettings {
+    pub remote: Option<SyncRemote>,
+    pub secret: Option<String>,
+    pub passphrase: Option<String>,
+}
+
+fn read_setting(db: &Connection, key: &str) -> Option<String> {
+    db.query_row("SELECT value FROM settings WHERE key = ?1", [key], |row| row.get::<_, String>(0))
+        .optional()
+        .ok()
+        .flatten()
+        .filter(|v| !v.is_empty())
+}
+
+fn read_encrypted(db: &Connection, key: &str, what: &str) -> Result<Option<String>, String> {
+    match read_setting(db, key) {
+        Some(v) => match v.strip_prefix("enc:") {
+            Some(enc) => Ok(Some(crypto::decrypt(enc).map_err(|e| format!("Failed to decrypt {}: {}", what, e))?)),
+            None => Ok(Some(v)),
+        },
+        None => Ok(None),
+    }
+}
+
+fn write_setting(db: &Connection, key: &str, value: &str) -> Result<(), String> {
+    db.execute(
+        "INSERT OR REPLACE INTO settings (key, value) VALUES (?1, ?2)",
+        rusqlite::params![key, value],
+    )
+    .map_err(|e| format!("Failed to save sync settings: {}", e))?;
+    Ok(())
+}
+
+fn encrypted_value(value: Option<&str>, what: &str) -> Result<String, String> {
+    match value.filter(|v| !v.is_empty()) {
+        Some(v) => Ok(format!("enc:{}", crypto::encrypt(v).map_err(|e| format!("Failed to encrypt {}: {}", what, e))?)),
+        None => Ok(String::new()),
+    }
+}
+
+pub fn load_settings(db: &Connection) -> Result<SyncSettings, String> {
+    let remote = match read_setting(db, REMOTE_SETTING) {
+        Some(json) => Some(serde_json::from_str(&json).map_err(|e| format!("Invalid sync remote setting: {}", e))?),
+        None => None,
+    };
+    Ok(SyncSettings {
+        remote,
+        secret: read_encrypted(db, SECRET_SETTING, "sync secret")?,
+        passphrase: read_encrypted(db, PASSPHRASE_SETTING, "sync passphrase")?,
+    })
+}
+
+/// Save the sync settings. The secret and passphrase are encrypted at rest.
+pub fn save_settings(db: &Connection, settings: &SyncSettings) -> Result<(), String> {
+    let remote = match &settings.remote {
+        Some(remote) => serde_json::to_string(remote).map_err(|e| format!("Failed to serialize sync remote: {}", e))?,
+        None => String::new(),
+    };
+    write_setting(db, REMOTE_SETTING, &remote)?;
+    write_setting(db, SECRET_SETTING, &encrypted_value(settings.secret.as_deref(), "sync secret")?)?;
+    write_setting(db, PASSPHRASE_SETTING, &encrypted_value(settings.passphrase.as_deref(), "sync passphrase")?)
+}
+
+/// Time of the last successful sync, if any.
+pub fn load_last_sync(db: &Connection) -> Option<String> {
+    read_setting(db, LAST_SYNC_SETTING)
+}
+
+/// Synced settings as of the last successful sync (None before the first sync).
+pub fn load_base_settings(db: &Connection) -> Option<BTreeMap<String, String>> {
+    read_setting(db, BASE_SETTINGS_SETTING).and_then(|json| serde_json::from_str(&json).ok())
+}
+
+/// Record a successful sync: its start time and the settings that were pushed.
+pub fn record_sync(db: &Connection, started_at: &str, settings: &BTreeMap<String, String>) -> Result<(), String> {
+    let base = serde_json::to_string(settings).map_err(|e| format!("Failed to serialize synced settings: {}", e))?;
+    write_setting(db, BASE_SETTINGS_SETTING, &base)?;
+    write_setting(db, LAST_SYNC_SETTING, started_at)
+}
+
+/// Host name recorded in pushed snapshots.
+pub fn device_name() -> String {
+    std::env::var("HOSTNAME")
+        .or_else(|_| std::env::var("COMPUTERNAME"))
+        .unwrap_or_else(|_| "unknown".to_string())
+}
+
+// ---------------------------------------------------------------------------
+// Encryption
+// ---------------------------------------------------------------------------
+
+fn derive_key(passphrase: &str, salt: &[u8], rounds: u32) -> [u8; 32] {
+    let mut key = [0u8; 32];
+    pbkdf2::pbkdf2_hmac::<Sha256>(passphrase.as_bytes(), salt, rounds, &mut key);
+    key
+}
+
+/// Encrypt a snapshot with a key derived from the passphrase.
+pub fn seal(plaintext: &[u8], passphrase: &str) -> Result<Vec<u8>, String> {
+    seal_with_rounds(plaintext, passphrase, KDF_ROUNDS)
+}
+
+fn seal_with_rounds(plaintext: &[u8], passphrase: &str, rounds: u32) -> Result<Vec<u8>, String> {
+    let mut salt = [0u8; SALT_LEN];
+    let mut nonce = [0u8; NONCE_LEN];
+    rand::thread_rng().fill_bytes(&mut salt);
+    rand::thread_rng().fill_bytes(&mut nonce);
+
+    let key = derive_key(passphrase, &salt, rounds);
+    let cipher = Aes256Gcm::new_from_slice(&key).map_err(|e| format!("Failed to create cipher: {}", e))?;
+    let ciphertext = cipher
+        .encrypt(Nonce::from_slice(&nonce), plaintext)
+        .map_err(|e| format!("Snapshot encryption failed: {}", e))?;
+
+    let mut sealed = MAGIC.to_vec();
+    sealed.extend_from_slice(&rounds.to_be_bytes());
+    sealed.extend_from_slice(&salt);
+    sealed.extend_from_slice(&nonce);
+    sealed.extend(ciphertext);
+    Ok(sealed)
+}
+
+/// Decrypt a sealed snapshot. A wrong passphrase and a tampered snapshot fail the same way.
+pub fn open(sealed: &[u8], passphrase: &str) -> Result<Vec<u8>, String> {
+    let body = sealed
+        .strip_prefix(MAGIC)
+        .ok_or("The remote file is not a Project Jumpstart sync snapshot")?;
+    if body.len() < 4 + SALT_LEN + NONCE_LEN + 16 {
+        return Err("Sync snapshot is truncated".to_string());
+    }
+    let (rounds, rest) = body.split_at(4);
+    let rounds = u32::from_be_bytes([rounds[0], rounds[1], rounds[2], rounds[3]]);
+    if rounds == 0 || rounds > 10 * KDF_ROUNDS {
+        return Err("Sync snapshot has an invalid key derivation setting".to_string());
+    }
+    let (salt, rest) = rest.split_at(SALT_LEN);
+    let (nonce, ciphertext) = rest.split_at(NONCE_LEN);
+
+    let key = derive_key(passphrase, salt, rounds);
+    let cipher = Aes256Gcm::new_from_slice(&key).map_err(|e| format!("Failed to create cipher: {}", e))?;
+    cipher
+        .decrypt(Nonce::from_slice(nonce), ciphertext)
+        .map_err(|_| "Could not decrypt the sync snapshot: wrong passphrase or corrupted data".to_string())
+}
+
+// ---------------------------------------------------------------------------
+// Settings
+// ---------------------------------------------------------------------------
+
+/// Whether a setting travels with the snapshot.
+pub fn is_syncable_setting(key: &str, value: &str) -> bool {
+    if value.is_empty() || value.starts_with("enc:") || LOCAL_ONLY_KEYS.contains(&key) {
+        return false;
+    }
+    if LOCAL_ONLY_PREFIXES.iter().any(|p| key.starts_with(p)) {
+        return false;
+    }
+    // Per-project and per-plan settings are keyed "<prefix>.<id>"
+    let last = key.rsplit('.').next().unwrap_or(key);
+    key == last || uuid::Uuid::parse_str(last).is_err()
+}
+
+/// Settings that are part of a snapshot, by key.
+pub fn local_settings(db: &Connection) -> Result<BTreeMap<String, String>, String> {
+    let mut stmt = db
+        .prepare("SELECT key, value FROM settings")
+        .map_err(|e| format!("Failed to query settings: {}", e))?;
+    let settings = stmt
+        .query_map([], |row| Ok((row.get::<_, String>(0)?, row.get::<_, String>(1)?)))
+        .map_err(|e| format!("Failed to read settings: {}", e))?
+        .filter_map(|r| r.ok())
+        .filter(|(key, value)| is_syncable_setting(key, value))
+        .collect();
+    Ok(settings)
+}
+
+/// Result of merging local settings with a remote snapshot.
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct SettingsMerge {
+    /// Remote changes to apply locally (None deletes the setting)
+    pub pull: Vec<(String, Option<String>)>,
+    /// Settings changed on both sides, with the incoming value
+    pub conflicts: Vec<(String, Option<String>)>,
+    /// Settings for the outgoing snapshot (conflicting keys keep the remote value)
+    pub outgoing: BTreeMap<String, String>,
+    /// Local changes that the outgoing snapshot carries to the remote
+    pub pushed: u32,
+}
+
+/// Three-way merge by key. A side that still matches `base` (the settings of the last
+/// sync) takes the other side's value; keys in `pending` stay conflicting until resolved.
+pub fn merge_settings(
+    local: &BTreeMap<String, String>,
+    remote: &BTreeMap<String, String>,
+    base: Option<&BTreeMap<String, String>>,
+    pending: &HashSet<String>,
+) -> SettingsMerge {
+    let empty = BTreeMap::new();
+    let base = base.unwrap_or(&empty);
+    let keys: BTreeSet<&String> = local.keys().chain(remote.keys()).chain(base.keys()).collect();
+
+    let mut merge = SettingsMerge::default();
+    for key in keys {
+        let (l, r, b) = (local.get(key), remote.get(key), base.get(key));
+        let outgoing = if l == r {
+            l
+        } else if pending.contains(key) {
+            merge.conflicts.push((key.clone(), r.cloned()));
+            r
+        } else if l == b {
+            merge.pull.push((key.clone(), r.cloned()));
+            r
+        } else if r == b {
+            merge.pushed += 1;
+            l
+        } else {
+            merge.conflicts.push((key.clone(), r.cloned()));
+            r
+        };
+        if let Some(value) = outgoing {
+            merge.outgoing.insert(key.clone(), value.clone());
+        }
+    }
+    merge
+}
+
+// ---------------------------------------------------------------------------
+// Remotes
+// ---------------------------------------------------------------------------
+
+/// A sealed snapshot read from the remote and the version it was read at.
+#[derive(Debug, Clone)]
+pub struct RemoteObject {
+    pub data: Vec<u8>,
+    /// ETag (S3, WebDAV) or commit hash (git)
+    pub version: Option<String>,
+}
+
+/// Check that a remote has what its kind needs.
+pub fn validate_remote(remote: &SyncRemote) -> Result<(), String> {
+    let url = remote.url.trim();
+    match remote.kind {
+        SyncRemoteKind::S3 | SyncRemoteKind::Webdav if !(url.starts_with("https://") || url.starts_with("http://")) => {
+            Err(format!("{} URL must start with https:// or http://", remote.kind.as_str()))
+        }
+        SyncRemoteKind::S3 if remote.bucket.as_deref().is_none_or(|b| b.trim().is_empty()) => {
+            Err("An S3 bucket is required".to_string())
+        }
+        SyncRemoteKind::S3 if remote.username.as_deref().is_none_or(|u| u.trim().is_empty()) => {
+            Err("An S3 access key id is required".to_string())
+        }
+        SyncRemoteKind::Git if url.is_empty() || url.starts_with('-') => Err("A git repository URL is required".to_string()),
+        _ => Ok(()),
+    }
+}
+
+fn snapshot_path(remote: &SyncRemote) -> String {
+    remote
+        .path
+        .as_deref()
+        .map(|p| p.trim().trim_matches('/'))
+        .filter(|p| !p.is_empty())
+        .unwrap_or(DEFAULT_PATH)
+        .to_string()
+}
+
+/// Read the sealed snapshot. Ok(None) when the remote has no snapshot yet.
+pub async fn fetch(client: &Client, remote: &SyncRemote, secret: Option<&str>) -> Result<Option<RemoteObject>, String> {
+    match remote.kind {
+        SyncRemoteKind::Git => git_fetch(remote),
+        SyncRemoteKind::S3 | SyncRemoteKind::Webdav => {
+            let response = http_request(client, remote, secret, reqwest::Method::GET, Vec::new(), None)?
+                .send()
+                .await
+                .map_err(|e| format!("Failed to reach sync remote: {}", e))?;
+            if response.status() == StatusCode::NOT_FOUND {
+                return Ok(None);
+            }
+            if !response.status().is_success() {
+                return Err(format!("Sync remote returned HTTP {} on download", response.status()));
+            }
+            let version = response
+                .headers()
+                .get(reqwest::header::ETAG)
+                .and_then(|v| v.to_str().ok())
+                .map(|v| v.to_string());
+            let data = response
+                .bytes()
+                .await
+                .map_err(|e| format!("Failed to download sync snapshot: {}", e))?;
+            Ok(Some(RemoteObject {
+                data: data.to_vec(),
+                version,
+            }))
+        }
+    }
+}
+
+/// Write the sealed snapshot, only if the remote is still at `read_version`
+/// (None means the remote had no snapshot when it was read).
+pub async fn store(
+    client: &Client,
+    remote: &SyncRemote,
+    secret: Option<&str>,
+    data: Vec<u8>,
+    read_version: Option<&str>,
+) -> Result<(), String> {
+    match remote.kind {
+        SyncRemoteKind::Git => git_store(remote, &data, read_version),
+        SyncRemoteKind::S3 | SyncRemoteKind::Webdav => {
+            let response = http_request(client, remote, secret, reqwest::Method::PUT, data, read_version)?
+                .send()
+                .await
+                .map_err(|e| format!("Failed to reach sync remote: {}", e))?;
+            match response.status() {
+                StatusCode::PRECONDITION_FAILED => Err(CONFLICT_MESSAGE.to_string()),
+                StatusCode::CONFLICT if remote.kind == SyncRemoteKind::Webdav => {
+                    Err("WebDAV folder for the snapshot does not exist; create it first".to_string())
+                }
+                status if status.is_success() => Ok(()),
+                status => Err(format!("Sync remote returned HTTP {} on upload", status)),
+            }
+        }
+    }
+}
+
+/// Build a signed S3 or authenticated WebDAV request for the snapshot.
+fn http_request(
+    client: &Client,
+    remote: &SyncRemote,
+    secret: Option<&str>,
+    method: reqwest::Method,
+    body: Vec<u8>,
+    read_version: Option<&str>,
+) -> Result<reqwest::RequestBuilder, String> {
+    let is_put = method == reqwest::Method::PUT;
+    let mut request = match remote.kind {
+        SyncRemoteKind::S3 => {
+            let bucket = remote.bucket.as_deref().unwrap_or_default().trim();
+            let canonical_uri = format!("/{}/{}", uri_encode(bucket), uri_encode_path(&snapshot_path(remote)));
+            let endpoint = remote.url.trim().trim_end_matches('/');
+            let host = endpoint.split("://").nth(1).unwrap_or(endpoint).split('/').next().unwrap_or_default();
+            let payload_hash = format!("{:x}", Sha256::digest(&body));
+            let amz_date = Utc::now().format("%Y%m%dT%H%M%SZ").to_string();
+            let authorization = sign_s3(
+                method.as_str(),
+                host,
+                &canonical_uri,
+                remote.region.as_deref().filter(|r| !r.trim().is_empty()).unwrap_or(DEFAULT_REGION),
+                remote.username.as_deref().unwrap_or_default().trim(),
+                secret.ok_or("An S3 secret access key is required")?,
+                &payload_hash,
+                &amz_date,
+            );
+            client
+                .request(method, format!("{}{}", endpoint, canonical_uri))
+                .header("x-amz-date", amz_date)
+                .header("x-amz-content-sha256", payload_hash)
+                .header(reqwest::header::AUTHORIZATION, authorization)
+        }
+        _ => {
+            let request = client.request(method, remote.url.trim());
+            match remote.username.as_deref().filter(|u| !u.is_empty()) {
+                Some(user) => request.basic_auth(user, secret),
+                None => request,
+            }
+        }
+    };
+    if is_put {
+        request = match read_version {
+            Some(version) => request.header(reqwest::header::IF_MATCH, version),
+            None => request.header(reqwest::header::IF_NONE_MATCH, "*"),
+        };
+        request = request.header(reqwest::header::CONTENT_TYPE, "application/octet-stream").body(body);
+    }
+    Ok(request)
+}
+
+/// Percent-encode everything but RFC 3986 unreserved characters (SigV4 rules).
+fn uri_encode(segment: &str) -> String {
+    segment
+        .bytes()
+        .map(|b| match b {
+            b'A'..=b'Z' | b'a'..=b'z' | b'0'..=b'9' | b'-' | b'_' | b'.' | b'~' => (b as char).to_string(),
+            _ => format!("%{:02X}", b),
+        })
+        .collect()
+}
+
+fn uri_encode_path(path: &str) -> String {
+    path.split('/').map(uri_encode).collect::<Vec<_>>().join("/")
+}
+
+fn hmac_sha256(key: &[u8], data: &str) -> Vec<u8> {
+    let mut mac = Hmac::<Sha256>::new_from_slice(key).expect("HMAC accepts any key length");
+    mac.update(data.as_bytes());
+    mac.finalize().into_bytes().to_vec()
+}
+
+/// AWS Signature Version 4 Authorization header for a request without a query string.
+#[allow(clippy::too_many_arguments)]
+fn sign_s3(
+    method: &str,
+    host: &str,
+    canonical_uri: &str,
+    region: &str,
+    access_key_id: &str,
+    secret_access_key: &str,
+    payload_hash: &str,
+    amz_date: &str,
+) -> String {
+    let date = &amz_date[..8];
+    let signed_headers = "host;x-amz-content-sha256;x-amz-date";
+    let canonical_request = format!(
+        "{}\n{}\n\nhost:{}\nx-amz-content-sha256:{}\nx-amz-date:{}\n\n{}\n{}",
+        method, canonical_uri, host, payload_hash, amz_date, signed_headers, payload_hash
+    );
+    let scope = format!("{}/{}/s3/aws4_request", date, region);
+    let string_to_sign = format!(
+        "AWS4-HMAC-SHA256\n{}\n{}\n{:x}",
+        amz_date,
+        scope,
+        Sha256::digest(canonical_request.as_bytes())
+    );
+
+    let mut key = hmac_sha256(format!("AWS4{}", secret_access_key).as_bytes(), date);
+    for part in [region, "s3", "aws4_request"] {
+        key = hmac_sha256(&key, part);
+    }
+    let signature: String = hmac_sha256(&key, &string_to_sign).iter().map(|b| format!("{:02x}", b)).collect();
+
+    format!(
+        "AWS4-HMAC-SHA256 Credential={}/{}, SignedHeaders={}, Signature={}",
+        access_key_id, scope, signed_headers, signature
+    )
+}
+
+/// Local clone of a git remote (~/.project-jumpstart/sync/<hash>).
+fn git_checkout_dir(remote: &SyncRemote) -> Result<PathBuf, String> {
+    let home = dirs::home_dir().ok_or("Could not find home directory")?;
+    let digest = Sha256::digest(remote.url.trim().as_bytes());
+    let name: String = digest.iter().take(8).map(|b| format!("{:02x}", b)).collect();
+    Ok(home.join(".project-jumpstart").join("sync").join(name))
+}
+
+fn git_branch(remote: &SyncRemote) -> String {
+    remote
+        .branch
+        .as_deref()
+        .map(str::trim)
+        .filter(|b| !b.is_empty())
+        .unwrap_or(DEFAULT_BRANCH)
+        .to_string()
+}
+
+fn git(dir: &Path, args: &[&str]) -> Result<String, String> {
+    let output = proc::run(Command::new("git").args(args).current_dir(dir), ProcLimits::GIT)?;
+    if output.timed_out {
+        return Err(format!("git {} timed out", args[0]));
+    }
+    if !output.success() {
+        return Err(format!("git {} failed: {}", args[0], String::from_utf8_lossy(&output.stderr).trim()));
+    }
+    Ok(String::from_utf8_lossy(&output.stdout).trim().to_string())
+}
+
+/// Clone or fetch the repository and check out the remote branch. Returns its commit,
+/// or None (with HEAD on the unborn branch) when the branch does not exist yet.
+fn git_checkout(remote: &SyncRemote) -> Result<(PathBuf, Option<String>), String> {
+    let dir = git_checkout_dir(remote)?;
+    if !dir.join(".git").exists() {
+        let parent = dir.parent().ok_or("Invalid sync directory")?;
+        std::fs::create_dir_all(parent).map_err(|e| format!("Failed to create sync directory: {}", e))?;
+        let target = dir.to_string_lossy().to_string();
+        git(parent, &["clone", "--quiet", remote.url.trim(), &target])?;
+    }
+
+    let branch = git_branch(remote);
+    git(&dir, &["fetch", "--quiet", "--prune", "origin"])?;
+    let remote_ref = format!("refs/remotes/origin/{}", branch);
+    match git(&dir, &["rev-parse", "--verify", "--quiet", &remote_ref]) {
+        Ok(commit) => {
+            git(&dir, &["checkout", "--quiet", "-B", &branch, &remote_ref])?;
+            git(&dir, &["reset", "--quiet", "--hard", &remote_ref])?;
+            Ok((dir, Some(commit)))
+        }
+        Err(_) => {
+            // Start the branch from scratch; untracked leftovers are never added
+            let local_ref = format!("refs/heads/{}", branch);
+            git(&dir, &["symbolic-ref", "HEAD", &local_ref])?;
+            let _ = git(&dir, &["update-ref", "-d", &local_ref]);
+            git(&dir, &["rm", "-r", "--cached", "--quiet", "--ignore-unmatch", "."])?;
+            Ok((dir, None))
+        }
+    }
+}
+
+fn git_fetch(remote: &SyncRemote) -> Result<Option<RemoteObject>, String> {
+    let (dir, commit) = git_checkout(remote)?;
+    let file = dir.join(snapshot_path(remote));
+    if commit.is_none() || !file.exists() {
+        return Ok(None);
+    }
+    let data = std::fs::read(&file).map_err(|e| format!("Failed to read sync snapshot: {}", e))?;
+    Ok(Some(RemoteObject { data, version: commit }))
+}
+
+fn git_store(remote: &SyncRemote, data: &[u8], read_version: Option<&str>) -> Result<(), String> {
+    let (dir, commit) = git_checkout(remote)?;
+    if commit.as_deref() != read_version {
+        return Err(CONFLICT_MESSAGE.to_string());
+    }
+
+    let path = snapshot_path(remote);
+    let file = dir.join(&path);
+    if let Some(parent) = file.parent() {
+        std::fs::create_dir_all(parent).map_err(|e| format!("Failed to create snapshot directory: {}", e))?;
+    }
+    std::fs::write(&file, data).map_err(|e| format!("Failed to write sync snapshot: {}", e))?;
+
+    let message = format!("Sync snapshot from {}", device_name());
+    git(&dir, &["add", "--", &path])?;
+    git(
+        &dir,
+        &[
+            "-c",
+            "user.name=Project Jumpstart",
+            "-c",
+            "user.email=sync@project-jumpstart.local",
+            "commit",
+            "--quiet",
+            "-m",
+            &message,
+        ],
+    )?;
+    git(&dir, &["push", "--quiet", "origin", &format!("HEAD:refs/heads/{}", git_branch(remote))])
+        .map_err(|e| if e.contains("rejected") || e.contains("fetch first") { CONFLICT_MESSAGE.to_string() } else { e })?;
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn map(pairs: &[(&str, &str)]) -> BTreeMap<String, String> {
+        pairs.iter().map(|(k, v)| (k.to_string(), v.to_string())).collect()
+    }
+
+    #[test]
+    fn test_seal_open_roundtrip_and_wrong_passphrase() {
+        let sealed = seal_with_rounds(b"{\"version\":1}", "correct horse", 1_000).unwrap();
+        assert!(sealed.starts_with(MAGIC));
+        assert_eq!(open(&sealed, "correct horse").unwrap(), b"{\"version\":1}");
+        assert!(open(&sealed, "wrong horse").unwrap_err().contains("wrong passphrase"));
+        assert!(open(b"plain text", "correct horse").is_err());
+
+        let mut tampered = sealed.clone();
+        let last = tampered.len() - 1;
+        tampered[last] ^= 1;
+        assert!(open(&tampered, "correct horse").is_err());
+    }
+
+    #[test]
+    fn test_is_syncable_setting() {
+        assert!(is_syncable_setting("ai_queue.max_concurrent", "2"));
+        assert!(is_syncable_setting("theme", "dark"));
+        assert!(!is_syncable_setting("anthropic_api_key", "sk-ant"));
+        assert!(!is_syncable_setting("custom_secret", "enc:abc"));
+        assert!(!is_syncable_setting("shared_db.url", "libsql://x"));
+        assert!(!is_syncable_setting("sync.remote", "{}"));
+        assert!(!is_syncable_setting("git_policy.6f1c1e5a-5d0b-4a7e-9b1e-2f0d8c3a4b5c", "{}"));
+    }
+
+    #[test]
+    fn test_merge_settings_three_way() {
+        let base = map(&[("a", "1"), ("b", "1"), ("c", "1"), ("d", "1")]);
+        let local = map(&[("a", "1"), ("b", "2"), ("c", "2"), ("d", "1"), ("new_local", "x")]);
+        let remote = map(&[("a", "3"), ("b", "1"), ("c", "3"), ("new_remote", "y")]);
+
+        let merge = merge_settings(&local, &remote, Some(&base), &HashSet::new());
+        assert_eq!(
+            merge.pull,
+            vec![
+                ("a".to_string(), Some("3".to_string())),
+                ("d".to_string(), None),
+                ("new_remote".to_string(), Some("y".to_string())),
+            ]
+        );
+        assert_eq!(merge.conflicts, vec![("c".to_string(), Some("3".to_string()))]);
+        assert_eq!(merge.pushed, 2);
+        assert_eq!(
+            merge.outgoing,
+            map(&[("a", "3"), ("b", "2"), ("c", "3"), ("new_local", "x"), ("new_remote", "y")])
+        );
+
+        // A pending conflict keeps the key blocked even when only one side moved
+        let pending: HashSet<String> = ["b".to_string()].into_iter().collect();
+        let merge = merge_settings(&local, &remote, Some(&base), &pending);
+        assert!(merge.conflicts.contains(&("b".to_string(), Some("1".to_string()))));
+        assert_eq!(merge.outgoing.get("b").map(String::as_str), Some("1"));
+    }
+}
@@ -0,0 +1,330 @@
+//! @module core/tdd_analytics
+//! @description Cycle times and trends from TDD session phase history
+//!
+//! PURPOSE:
+//! - Split a session's phase history into red → green → refactor cycles
+//! - Average red→green, green→refactor, and full cycle times per session and per project
+//! - Group cycles by week and tell whether red→green time is improving
+//!
+//! DEPENDENCIES:
+//! - chrono - Durations and week boundaries
+//! - models::test_plan - TDDSession, TDDPhaseEvent, TDDAnalytics and its parts
+//!
+//! EXPORTS:
+//! - TDDCycle - Timestamps of one cycle
+//! - session_cycles - Cycles in a session's phase history
+//! - session_stats - Per-session cycle counts and averages
+//! - build_analytics - Project analytics from all of its sessions
+//! - red_to_green_trend - Compare older and recent red→green times
+//!
+//! PATTERNS:
+//! - Every red entry starts a cycle; a cycle ends at the next red entry or the session's
+//!   completion, and is only timed in full once it reached refactor
+//! - Phase entries out of order (green before red) are ignored for timing
+//!
+//! CLAUDE NOTES:
+//! - Sessions created before phase history existed have a single red entry at created_at,
+//!   so they count as one cycle with no phase times
+//! - The trend needs at least MIN_TREND_CYCLES timed cycles; a 10% change counts as a trend
+
+use std::collections::BTreeMap;
+
+use chrono::{DateTime, Datelike, Duration, Utc};
+
+use crate::models::test_plan::{
+    TDDAnalytics, TDDPhase, TDDPhaseEvent, TDDSession, TDDSessionStats, TDDTrendPoint,
+};
+
+/// Timed red→green cycles needed before a trend is reported.
+const MIN_TREND_CYCLES: usize = 4;
+
+/// Relative change in red→green time that counts as improving or declining.
+const TREND_THRESHOLD: f64 = 0.10;
+
+/// One red → green → refactor cycle.
+#[derive(Debug, Clone, PartialEq)]
+pub struct TDDCycle {
+    pub red_at: DateTime<Utc>,
+    pub green_at: Option<DateTime<Utc>>,
+    pub refactor_at: Option<DateTime<Utc>>,
+    /// Next red entry or session completion
+    pub ended_at: Option<DateTime<Utc>>,
+}
+
+impl TDDCycle {
+    pub fn red_to_green_secs(&self) -> Option<f64> {
+        self.green_at.map(|green| secs(green - self.red_at))
+    }
+
+    pub fn green_to_refactor_secs(&self) -> Option<f64> {
+        match (self.green_at, self.refactor_at) {
+            (Some(green), Some(refactor)) => Some(secs(refactor - green)),
+            _ => None,
+        }
+    }
+
+    /// Full cycle time; only for cycles that reached refactor and ended.
+    pub fn cycle_secs(&self) -> Option<f64> {
+        match (self.refactor_at, self.ended_at) {
+            (Some(_), Some(end)) => Some(secs(end - self.red_at)),
+            _ => None,
+        }
+    }
+}
+
+fn secs(duration: Duration) -> f64 {
+    duration.num_seconds().max(0) as f64
+}
+
+fn average(values: impl Iterator<Item = f64>) -> Option<f64> {
+    let (sum, count) = values.fold((0.0, 0u32), |(sum, count), v| (sum + v, count + 1));
+    (count > 0).then(|| sum / count as f64)
+}
+
+/// Cycles in a phase history. `completed_at` closes the last cycle.
+pub fn session_cycles(history: &[TDDPhaseEvent], completed_at: Option<DateTime<Utc>>) -> Vec<TDDCycle> {
+    let mut cycles: Vec<TDDCycle> = Vec::new();
+    for event in history {
+        match event.phase {
+            TDDPhase::Red => {
+                if let Some(last) = cycles.last_mut() {
+                    last.ended_at.get_or_insert(event.at);
+                }
+                cycles.push(TDDCycle {
+                    red_at: event.at,
+                    green_at: None,
+                    refactor_at: None,
+                    ended_at: None,
+                });
+            }
+            TDDPhase::Green => {
+                if let Some(cycle) = cycles.last_mut().filter(|c| c.green_at.is_none()) {
+                    cycle.green_at = Some(event.at);
+                }
+            }
+            TDDPhase::Refactor => {
+                if let Some(cycle) = cycles
+                    .last_mut()
+                    .filter(|c| c.green_at.is_some() && c.refactor_at.is_none())
+                {
+                    cycle.refactor_at = Some(event.at);
+                }
+            }
+        }
+    }
+    if let (Some(last), Some(done)) = (cycles.last_mut(), completed_at) {
+        last.ended_at.get_or_insert(done);
+    }
+    cycles
+}
+
+/// Cycle counts and average phase times for one session.
+pub fn session_stats(session: &TDDSession, cycles: &[TDDCycle]) -> TDDSessionStats {
+    TDDSessionStats {
+        session_id: session.id.clone(),
+        feature_name: session.feature_name.clone(),
+        created_at: session.created_at,
+        completed_at: session.completed_at,
+        cycles: cycles.len() as u32,
+        completed_cycles: cycles.iter().filter(|c| c.refactor_at.is_some()).count() as u32,
+        avg_red_to_green_secs: average(cycles.iter().filter_map(TDDCycle::red_to_green_secs)),
+        avg_green_to_refactor_secs: average(cycles.iter().filter_map(TDDCycle::green_to_refactor_secs)),
+        avg_cycle_secs: average(cycles.iter().filter_map(TDDCycle::cycle_secs)),
+    }
+}
+
+/// "improving" when recent red→green times are at least 10% shorter than older ones,
+/// "declining" when 10% longer, otherwise "steady". `times` is oldest first.
+pub fn red_to_green_trend(times: &[f64]) -> String {
+    if times.len() < MIN_TREND_CYCLES {
+        return "insufficient_data".to_string();
+    }
+    let half = times.len() / 2;
+    let older = average(times[..half].iter().copied()).unwrap_or(0.0);
+    let recent = average(times[times.len() - half..].iter().copied()).unwrap_or(0.0);
+    if older <= 0.0 {
+        return "steady".to_string();
+    }
+    let change = (recent - older) / older;
+    if change <= -TREND_THRESHOLD {
+        "improving".to_string()
+    } else if change >= TREND_THRESHOLD {
+        "declining".to_string()
+    } else {
+        "steady".to_string()
+    }
+}
+
+/// Monday of the week containing `at`.
+fn week_start(at: DateTime<Utc>) -> String {
+    let date = at.date_naive();
+    (date - Duration::days(date.weekday().num_days_from_monday() as i64))
+        .format("%Y-%m-%d")
+        .to_string()
+}
+
+#[derive(Default)]
+struct WeekAccumulator {
+    sessions: u32,
+    cycles: u32,
+    red_to_green: Vec<f64>,
+    green_to_refactor: Vec<f64>,
+}
+
+/// Project analytics from all of its sessions (any order).
+pub fn build_analytics(project_id: &str, sessions: &[TDDSession]) -> TDDAnalytics {
+    let mut sessions: Vec<&TDDSession> = sessions.iter().collect();
+    sessions.sort_by_key(|s| s.created_at);
+
+    let mut weeks: BTreeMap<String, WeekAccumulator> = BTreeMap::new();
+    let mut all_cycles: Vec<TDDCycle> = Vec::new();
+    let mut stats: Vec<TDDSessionStats> = Vec::new();
+
+    for session in &sessions {
+        let cycles = session_cycles(&session.phase_history, session.completed_at);
+        weeks.entry(week_start(session.created_at)).or_default().sessions += 1;
+        for cycle in &cycles {
+            let week = weeks.entry(week_start(cycle.red_at)).or_default();
+            week.cycles += 1;
+            week.red_to_green.extend(cycle.red_to_green_secs());
+            week.green_to_refactor.extend(cycle.green_to_refactor_secs());
+        }
+        stats.push(session_stats(session, &cycles));
+        all_cycles.extend(cycles);
+    }
+
+    all_cycles.sort_by_key(|c| c.red_at);
+    let red_to_green: Vec<f64> = all_cycles.iter().filter_map(TDDCycle::red_to_green_secs).collect();
+    let total_sessions = sessions.len() as u32;
+    let total_cycles = all_cycles.len() as u32;
+    stats.reverse();
+
+    TDDAnalytics {
+        project_id: project_id.to_string(),
+        total_sessions,
+        completed_sessions: sessions.iter().filter(|s| s.completed_at.is_some()).count() as u32,
+        total_cycles,
+        avg_cycles_per_session: if total_sessions > 0 {
+            total_cycles as f64 / total_sessions as f64
+        } else {
+            0.0
+        },
+        avg_red_to_green_secs: average(red_to_green.iter().copied()),
+        avg_green_to_refactor_secs: average(all_cycles.iter().filter_map(TDDCycle::green_to_refactor_secs)),
+        avg_cycle_secs: average(all_cycles.iter().filter_map(TDDCycle::cycle_secs)),
+        red_to_green_trend: red_to_green_trend(&red_to_green),
+        sessions: stats,
+        weekly: weeks
+            .into_iter()
+            .map(|(week_start, w)| TDDTrendPoint {
+                week_start,
+                sessions: w.sessions,
+                cycles: w.cycles,
+                avg_red_to_green_secs: average(w.red_to_green.into_iter()),
+                avg_green_to_refactor_secs: average(w.green_to_refactor.into_iter()),
+            })
+            .collect(),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::models::test_plan::TDDPhaseStatus;
+
+    fn at(minutes: i64) -> DateTime<Utc> {
+        // Monday 2026-03-02 09:00 UTC
+        DateTime::parse_from_rfc3339("2026-03-02T09:00:00Z").unwrap().with_timezone(&Utc) + Duration::minutes(minutes)
+    }
+
+    fn event(phase: TDDPhase, minutes: i64) -> TDDPhaseEvent {
+        TDDPhaseEvent { phase, at: at(minutes) }
+    }
+
+    fn session(id: &str, history: Vec<TDDPhaseEvent>, completed: Option<i64>) -> TDDSession {
+        TDDSession {
+            id: id.to_string(),
+            project_id: "p1".to_string(),
+            feature_name: format!("feature {}", id),
+            test_file_path: None,
+            current_phase: TDDPhase::Red,
+            phase_status: TDDPhaseStatus::Active,
+            red_prompt: None,
+            red_output: None,
+            green_prompt: None,
+            green_output: None,
+            refactor_prompt: None,
+            refactor_output: None,
+            created_at: history.first().map(|e| e.at).unwrap_or_else(|| at(0)),
+            updated_at: at(0),
+            completed_at: completed.map(at),
+            phase_history: history,
+        }
+    }
+
+    #[test]
+    fn test_session_cycles() {
+        let history = vec![
+            event(TDDPhase::Red, 0),
+            event(TDDPhase::Green, 10),
+            event(TDDPhase::Refactor, 15),
+            event(TDDPhase::Red, 20),
+            event(TDDPhase::Green, 26),
+        ];
+        let cycles = session_cycles(&history, None);
+        assert_eq!(cycles.len(), 2);
+        assert_eq!(cycles[0].red_to_green_secs(), Some(600.0));
+        assert_eq!(cycles[0].green_to_refactor_secs(), Some(300.0));
+        assert_eq!(cycles[0].cycle_secs(), Some(1200.0));
+        assert_eq!(cycles[1].red_to_green_secs(), Some(360.0));
+        assert_eq!(cycles[1].cycle_secs(), None);
+
+        // A refactor without green is not timed
+        let cycles = session_cycles(&[event(TDDPhase::Red, 0), event(TDDPhase::Refactor, 5)], Some(at(9)));
+        assert_eq!(cycles[0].refactor_at, None);
+        assert_eq!(cycles[0].ended_at, Some(at(9)));
+    }
+
+    #[test]
+    fn test_red_to_green_trend() {
+        assert_eq!(red_to_green_trend(&[600.0, 500.0]), "insufficient_data");
+        assert_eq!(red_to_green_trend(&[600.0, 600.0, 300.0, 240.0]), "improving");
+        assert_eq!(red_to_green_trend(&[300.0, 240.0, 600.0, 600.0]), "declining");
+        assert_eq!(red_to_green_trend(&[300.0, 310.0, 305.0, 300.0, 295.0]), "steady");
+    }
+
+    #[test]
+    fn test_build_analytics() {
+        let first = session(
+            "a",
+            vec![event(TDDPhase::Red, 0), event(TDDPhase::Green, 20), event(TDDPhase::Refactor, 30)],
+            Some(40),
+        );
+        let second = session(
+            "b",
+            vec![
+                event(TDDPhase::Red, 7 * 24 * 60),
+                event(TDDPhase::Green, 7 * 24 * 60 + 10),
+                event(TDDPhase::Refactor, 7 * 24 * 60 + 12),
+                event(TDDPhase::Red, 7 * 24 * 60 + 15),
+            ],
+            None,
+        );
+        let analytics = build_analytics("p1", &[second, first]);
+        assert_eq!(analytics.total_sessions, 2);
+        assert_eq!(analytics.completed_sessions, 1);
+        assert_eq!(analytics.total_cycles, 3);
+        assert_eq!(analytics.avg_cycles_per_session, 1.5);
+        assert_eq!(analytics.avg_red_to_green_secs, Some(900.0));
+        assert_eq!(analytics.avg_cycle_secs, Some((2400.0 + 900.0) / 2.0));
+        assert_eq!(analytics.red_to_green_trend, "insufficient_data");
+        assert_eq!(analytics.sessions[0].session_id, "b");
+        assert_eq!(analytics.sessions[0].cycles, 2);
+        assert_eq!(analytics.sessions[0].completed_cycles, 1);
+
+        let weeks: Vec<(&str, u32, u32)> =
+            analytics.weekly.iter().map(|w| (w.week_start.as_str(), w.sessions, w.cycles)).collect();
+        assert_eq!(weeks, vec![("2026-03-02", 1, 1), ("2026-03-09", 1, 2)]);
+        assert_eq!(analytics.weekly[1].avg_red_to_green_secs, Some(600.0));
+    }
+}
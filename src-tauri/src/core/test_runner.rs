@@ -2,50 +2,71 @@
 //! @description Test framework detection and test execution engine
 //!
 //! PURPOSE:
-//! - Detect test frameworks from project configuration (vitest, jest, cargo test, playwright, pytest)
+//! - Detect test frameworks from project configuration (vitest, jest, cargo test, playwright, pytest,
+//!   go test, PHPUnit, RSpec, Gradle/JUnit)
 //! - Execute tests via detected framework commands
 //! - Parse test output (JSON reporters preferred) for structured results
 //! - Extract coverage information from lcov/istanbul reports
 //!
 //! DEPENDENCIES:
-//! - std::process - Command execution
+//! - std::process - Command construction (run via core::proc with timeouts and output limits)
 //! - std::fs - File system reading
 //! - std::path - Path operations
 //! - serde_json - JSON output parsing
 //! - crate::models::test_plan - Test framework info types
+//! - crate::core::command_guard - Unshelled, project-contained command execution
+//! - crate::core::package_manager - Runner prefix (npx, pnpm, yarn, bunx, uv run, ...) for test commands
 //!
 //! EXPORTS:
 //! - detect_test_framework - Detect test framework from project files
-//! - run_tests - Execute tests and return structured results
+//! - test_command - The command run_tests uses (coverage command when requested)
+//! - run_tests - Execute tests (with extra env vars, a timeout, and a cancel flag) and return structured results
 //! - parse_vitest_output - Parse Vitest JSON output
 //! - parse_jest_output - Parse Jest JSON output
 //! - parse_cargo_test_output - Parse cargo test output
+//! - parse_go_test_output - Parse `go test -json` event stream
+//! - parse_phpunit_output - Parse PHPUnit text summary and numbered failures
+//! - parse_rspec_output - Parse RSpec JSON formatter output
+//! - parse_junit_xml - Parse JUnit XML reports (Gradle build/test-results)
 //! - parse_coverage_lcov - Extract coverage % from lcov file
 //!
 //! PATTERNS:
 //! - Framework detection uses priority: config files > package.json deps > conventions
 //! - Test execution uses --reporter=json when available for structured output
 //! - Coverage is optional and extracted from standard lcov.info location
+//! - Test runs are killed after the caller's limits (ProcLimits::TESTS unless a plan sets a
+//!   timeout) or when their cancel flag is set; listing commands after ProcLimits::TEST_LIST
 //!
 //! CLAUDE NOTES:
 //! - Always prefer JSON reporters for reliable parsing
+//! - JS and pytest commands run through the detected package manager (pnpm / no prefix when undetected);
+//!   the examples below use pnpm
 //! - Vitest: pnpm vitest run --reporter=json
 //! - Jest: pnpm jest --json --outputFile=results.json
 //! - Cargo: cargo test -- --format=json (nightly only, fallback to text parsing)
 //! - Playwright: pnpm playwright test --reporter=json
+//! - Go: go test -json ./... (one JSON event per line; coverage from coverage.out)
+//! - PHPUnit: vendor/bin/phpunit (text summary; coverage from Clover XML)
+//! - RSpec: bundle exec rspec --format json (coverage from SimpleCov's .last_run.json)
+//! - Gradle: ./gradlew test, results read from build/test-results/test/*.xml (coverage from JaCoCo XML)
 //! - Coverage files typically at coverage/lcov.info or target/coverage/lcov.info
 
 use std::collections::HashMap;
 use std::fs;
 use std::path::Path;
 use std::process::{Command, Output};
+use std::sync::atomic::AtomicBool;
 
+use crate::core::command_guard;
+use crate::core::package_manager::{self, PackageManager};
+use crate::core::proc::{self, ProcLimits};
 use crate::models::test_plan::TestFrameworkInfo;
 
 /// Detect the test framework used in a project.
 /// Returns framework info with command to run tests.
 pub fn detect_test_framework(project_path: &str) -> Option<TestFrameworkInfo> {
     let path = Path::new(project_path);
+    let detected_pm = package_manager::detect(path).map(|(pm, _)| pm);
 
     // Check for Rust projects first (Cargo.toml)
     if path.join("Cargo.toml").exists() {
@@ -70,11 +91,12 @@ pub fn detect_test_framework(project_path: &str) -> Option<TestFrameworkInfo> {
             None
         };
 
+        let pm = detected_pm.filter(|pm| !pm.is_javascript()).unwrap_or(PackageManager::Pip);
         return Some(TestFrameworkInfo {
             name: "pytest".to_string(),
-            command: "pytest --tb=short -q".to_string(),
+            command: pm.exec("pytest --tb=short -q"),
             config_file,
-            coverage_command: Some("pytest --cov --cov-report=lcov".to_string()),
+            coverage_command: Some(pm.exec("pytest --cov --cov-report=lcov")),
         });
     }
 
@@ -82,9 +104,52 @@ pub fn detect_test_framework(project_path: &str) -> Option<TestFrameworkInfo> {
     if path.join("go.mod").exists() {
         return Some(TestFrameworkInfo {
             name: "go test".to_string(),
-            command: "go test ./...".to_string(),
+            command: "go test -json ./...".to_string(),
             config_file: Some("go.mod".to_string()),
-            coverage_command: Some("go test -coverprofile=coverage.out ./...".to_string()),
+            coverage_command: Some("go test -json -coverprofile=coverage.out ./...".to_string()),
+        });
+    }
+
+    // Check for PHP projects (PHPUnit config or composer dev dependency)
+    let phpunit_config = find_config_file(path, &["phpunit.xml", "phpunit.xml.dist"]);
+    let composer_has_phpunit = fs::read_to_string(path.join("composer.json"))
+        .map(|content| content.contains("phpunit/phpunit"))
+        .unwrap_or(false);
+    if phpunit_config.is_some() || composer_has_phpunit {
+        return Some(TestFrameworkInfo {
+            name: "PHPUnit".to_string(),
+            command: "vendor/bin/phpunit --colors=never".to_string(),
+            config_file: phpunit_config.or_else(|| Some("composer.json".to_string())),
+            coverage_command: Some(
+                "vendor/bin/phpunit --colors=never --coverage-clover coverage/clover.xml".to_string(),
+            ),
+        });
+    }
+
+    // Check for Ruby projects using RSpec
+    if path.join(".rspec").exists() || path.join("spec/spec_helper.rb").exists() {
+        let command = if path.join("Gemfile").exists() {
+            "bundle exec rspec --format json"
+        } else {
+            "rspec --format json"
+        };
+        return Some(TestFrameworkInfo {
+            name: "RSpec".to_string(),
+            command: command.to_string(),
+            config_file: find_config_file(path, &[".rspec", "spec/spec_helper.rb"]),
+            // SimpleCov reports whenever it is loaded by spec_helper
+            coverage_command: Some(command.to_string()),
+        });
+    }
+
+    // Check for JVM projects built with Gradle (JUnit results)
+    if let Some(config_file) = find_config_file(path, &["build.gradle.kts", "build.gradle"]) {
+        let gradle = if path.join("gradlew").exists() { "./gradlew" } else { "gradle" };
+        return Some(TestFrameworkInfo {
+            name: "Gradle".to_string(),
+            command: format!("{} test --console=plain", gradle),
+            config_file: Some(config_file),
+            coverage_command: Some(format!("{} test jacocoTestReport --console=plain", gradle)),
         });
     }
 
@@ -94,6 +159,7 @@ pub fn detect_test_framework(project_path: &str) -> Option<TestFrameworkInfo> {
         if let Ok(content) = fs::read_to_string(&pkg_json_path) {
             if let Ok(pkg) = serde_json::from_str::<serde_json::Value>(&content) {
                 let deps = merge_deps(&pkg);
+                let pm = detected_pm.filter(PackageManager::is_javascript).unwrap_or(PackageManager::Pnpm);
 
                 // Check for specific test frameworks in order of preference
                 // Vitest (preferred for Vite projects)
@@ -107,10 +173,10 @@ pub fn detect_test_framework(project_path: &str) -> Option<TestFrameworkInfo> {
                     ]);
                     return Some(TestFrameworkInfo {
                         name: "Vitest".to_string(),
-                        command: "pnpm vitest run --reporter=json".to_string(),
+                        command: pm.exec("vitest run --reporter=json"),
                         config_file,
                         coverage_command: Some(
-                            "pnpm vitest run --coverage --reporter=json".to_string(),
+                            pm.exec("vitest run --coverage --reporter=json"),
                         ),
                     });
                 }
@@ -123,7 +189,7 @@ pub fn detect_test_framework(project_path: &str) -> Option<TestFrameworkInfo> {
                     );
                     return Some(TestFrameworkInfo {
                         name: "Playwright".to_string(),
-                        command: "pnpm playwright test --reporter=json".to_string(),
+                        command: pm.exec("playwright test --reporter=json"),
                         config_file,
                         coverage_command: None, // Playwright doesn't have built-in coverage
                     });
@@ -137,9 +203,9 @@ pub fn detect_test_framework(project_path: &str) -> Option<TestFrameworkInfo> {
                     );
                     return Some(TestFrameworkInfo {
                         name: "Jest".to_string(),
-                        command: "pnpm jest --json".to_string(),
+                        command: pm.exec("jest --json"),
                         config_file,
-                        coverage_command: Some("pnpm jest --coverage --json".to_string()),
+                        coverage_command: Some(pm.exec("jest --coverage --json")),
                     });
                 }
 
@@ -149,9 +215,9 @@ pub fn detect_test_framework(project_path: &str) -> Option<TestFrameworkInfo> {
                         find_config_file(path, &[".mocharc.json", ".mocharc.js", "mocha.opts"]);
                     return Some(TestFrameworkInfo {
                         name: "Mocha".to_string(),
-                        command: "pnpm mocha --reporter json".to_string(),
+                        command: pm.exec("mocha --reporter json"),
                         config_file,
-                        coverage_command: Some("pnpm nyc mocha --reporter json".to_string()),
+                        coverage_command: Some(pm.exec("nyc mocha --reporter json")),
                     });
                 }
 
@@ -163,7 +229,7 @@ pub fn detect_test_framework(project_path: &str) -> Option<TestFrameworkInfo> {
                     );
                     return Some(TestFrameworkInfo {
                         name: "Cypress".to_string(),
-                        command: "pnpm cypress run --reporter json".to_string(),
+                        command: pm.exec("cypress run --reporter json"),
                         config_file,
                         coverage_command: None,
                     });
@@ -208,6 +274,10 @@ pub struct TestExecutionResult {
     pub skipped: u32,
     pub duration_ms: u64,
     pub coverage_percent: Option<f64>,
+    /// The run was killed after exceeding its timeout
+    pub timed_out: bool,
+    /// The run was killed by cancel_test_run
+    pub cancelled: bool,
     pub stdout: String,
     pub stderr: String,
     pub test_results: Vec<IndividualTestResult>,
@@ -224,36 +294,36 @@ pub struct IndividualTestResult {
     pub error_message: Option<String>,
 }
 
-/// Execute tests for a project using the detected framework.
-/// Returns structured test results.
-pub fn run_tests(
-    project_path: &str,
-    framework: &TestFrameworkInfo,
-    with_coverage: bool,
-) -> Result<TestExecutionResult, String> {
-    let command = if with_coverage {
+/// The command run_tests runs for a framework.
+pub fn test_command(framework: &TestFrameworkInfo, with_coverage: bool) -> &str {
+    if with_coverage {
         framework
             .coverage_command
             .as_ref()
             .unwrap_or(&framework.command)
     } else {
         &framework.command
-    };
-
-    // Parse command into program and args
-    let parts: Vec<&str> = command.split_whitespace().collect();
-    if parts.is_empty() {
-        return Err("Empty test command".to_string());
     }
+}
 
-    let program = parts[0];
-    let args = &parts[1..];
+/// Execute tests for a project using the detected framework, with `env` added
+/// to the inherited environment. The run is killed when it exceeds `limits` or
+/// `cancel` is set. Returns structured test results.
+pub fn run_tests(
+    project_path: &str,
+    framework: &TestFrameworkInfo,
+    with_coverage: bool,
+    env: &[(String, String)],
+    limits: ProcLimits,
+    cancel: &AtomicBool,
+) -> Result<TestExecutionResult, String> {
+    let command = test_command(framework, with_coverage);
 
-    let output = Command::new(program)
-        .args(args)
-        .current_dir(project_path)
-        .output()
+    // Built-in framework commands need no approval but still run unshelled inside the project
+    let proc_output = command_guard::run_validated_env(project_path, command, env, limits, cancel)
         .map_err(|e| format!("Failed to execute test command: {}", e))?;
+    let (timed_out, cancelled) = (proc_output.timed_out, proc_output.cancelled);
+    let output: Output = proc_output.into();
 
     let stdout = String::from_utf8_lossy(&output.stdout).to_string();
     let stderr = String::from_utf8_lossy(&output.stderr).to_string();
@@ -265,6 +335,10 @@ pub fn run_tests(
         "cargo test" => parse_cargo_test_output(&stdout, &stderr, &output),
         "Playwright" => parse_playwright_output(&stdout, &stderr, &output),
         "pytest" => parse_pytest_output(&stdout, &stderr, &output),
+        "go test" => parse_go_test_output(&stdout, &stderr, &output),
+        "PHPUnit" => parse_phpunit_output(&stdout, &stderr, &output),
+        "RSpec" => parse_rspec_output(&stdout, &stderr, &output),
+        "Gradle" => parse_gradle_output(project_path, &stdout, &stderr, &output),
         _ => parse_generic_output(&stdout, &stderr, &output),
     };
 
@@ -276,7 +350,10 @@ pub fn run_tests(
     };
 
     Ok(TestExecutionResult {
+        success: result.success && !timed_out && !cancelled,
         coverage_percent: coverage,
+        timed_out,
+        cancelled,
         ..result
     })
 }
@@ -371,6 +448,8 @@ pub fn parse_vitest_output(stdout: &str, stderr: &str, output: &Output) -> TestE
             skipped,
             duration_ms,
             coverage_percent: None,
+            timed_out: false,
+            cancelled: false,
             stdout: stdout.to_string(),
             stderr: stderr.to_string(),
             test_results,
@@ -462,6 +541,8 @@ pub fn parse_jest_output(stdout: &str, stderr: &str, output: &Output) -> TestExe
             skipped,
             duration_ms: 0,
             coverage_percent: None,
+            timed_out: false,
+            cancelled: false,
             stdout: stdout.to_string(),
             stderr: stderr.to_string(),
             test_results,
@@ -547,6 +628,8 @@ pub fn parse_cargo_test_output(stdout: &str, stderr: &str, output: &Output) -> T
         skipped,
         duration_ms: 0,
         coverage_percent: None,
+        timed_out: false,
+        cancelled: false,
         stdout: stdout.to_string(),
         stderr: stderr.to_string(),
         test_results,
@@ -594,6 +677,8 @@ pub fn parse_playwright_output(stdout: &str, stderr: &str, output: &Output) -> T
             skipped,
             duration_ms,
             coverage_percent: None,
+            timed_out: false,
+            cancelled: false,
             stdout: stdout.to_string(),
             stderr: stderr.to_string(),
             test_results,
@@ -741,12 +826,328 @@ pub fn parse_pytest_output(stdout: &str, stderr: &str, output: &Output) -> TestE
         skipped,
         duration_ms: 0,
         coverage_percent: None,
+        timed_out: false,
+        cancelled: false,
         stdout: stdout.to_string(),
         stderr: stderr.to_string(),
         test_results,
     }
 }
 
+/// Parse `go test -json` output (one test2json event per line).
+/// Package-level events (no "Test" field) only contribute their output.
+pub fn parse_go_test_output(stdout: &str, stderr: &str, output: &Output) -> TestExecutionResult {
+    let mut passed = 0u32;
+    let mut failed = 0u32;
+    let mut skipped = 0u32;
+    let mut elapsed_secs = 0f64;
+    let mut test_output: HashMap<String, String> = HashMap::new();
+    let mut test_results = Vec::new();
+
+    for line in stdout.lines() {
+        let Ok(event) = serde_json::from_str::<serde_json::Value>(line) else {
+            continue;
+        };
+        let action = event.get("Action").and_then(|v| v.as_str()).unwrap_or("");
+        let package = event.get("Package").and_then(|v| v.as_str()).unwrap_or("");
+        let Some(test) = event.get("Test").and_then(|v| v.as_str()) else {
+            if matches!(action, "pass" | "fail") {
+                elapsed_secs += event.get("Elapsed").and_then(|v| v.as_f64()).unwrap_or(0.0);
+            }
+            continue;
+        };
+        let key = format!("{}/{}", package, test);
+
+        match action {
+            "output" => {
+                let text = event.get("Output").and_then(|v| v.as_str()).unwrap_or("");
+                test_output.entry(key).or_default().push_str(text);
+            }
+            "pass" | "fail" | "skip" => {
+                let duration_ms = event
+                    .get("Elapsed")
+                    .and_then(|v| v.as_f64())
+                    .map(|secs| (secs * 1000.0) as u64);
+                let error_message = if action == "fail" {
+                    failed += 1;
+                    test_output.remove(&key).map(|o| o.trim().to_string())
+                } else {
+                    if action == "pass" {
+                        passed += 1;
+                    } else {
+                        skipped += 1;
+                    }
+                    None
+                };
+                test_results.push(IndividualTestResult {
+                    name: test.to_string(),
+                    file_path: (!package.is_empty()).then(|| package.to_string()),
+                    passed: action != "fail",
+                    duration_ms,
+                    error_message,
+                });
+            }
+            _ => {}
+        }
+    }
+
+    if test_results.is_empty() {
+        return parse_generic_output(stdout, stderr, output);
+    }
+
+    TestExecutionResult {
+        success: output.status.success() && failed == 0,
+        total: passed + failed + skipped,
+        passed,
+        failed,
+        skipped,
+        duration_ms: (elapsed_secs * 1000.0) as u64,
+        coverage_percent: None,
+        timed_out: false,
+        cancelled: false,
+        stdout: stdout.to_string(),
+        stderr: stderr.to_string(),
+        test_results,
+    }
+}
+
+/// Parse PHPUnit text output.
+/// Summary is either "OK (12 tests, 30 assertions)" or
+/// "Tests: 12, Assertions: 30, Failures: 1, Errors: 1, Skipped: 2, Incomplete: 1.";
+/// failures are listed as "1) Class::method".
+pub fn parse_phpunit_output(stdout: &str, stderr: &str, output: &Output) -> TestExecutionResult {
+    let mut total = None;
+    let mut failed = 0u32;
+    let mut skipped = 0u32;
+    let mut test_results = Vec::new();
+
+    for line in stdout.lines() {
+        let trimmed = line.trim();
+        if let Some(rest) = trimmed.strip_prefix("OK (") {
+            total = extract_number_before(rest, "test");
+        } else if trimmed.starts_with("Tests:") {
+            let count = |label: &str| {
+                trimmed
+                    .split(',')
+                    .find_map(|part| part.trim().strip_prefix(label))
+                    .and_then(|n| n.trim().trim_end_matches('.').parse::<u32>().ok())
+                    .unwrap_or(0)
+            };
+            total = Some(count("Tests:"));
+            failed = count("Failures:") + count("Errors:");
+            skipped = count("Skipped:") + count("Incomplete:") + count("Risky:");
+        } else if let Some((number, name)) = trimmed.split_once(") ") {
+            if number.parse::<u32>().is_ok() && name.contains("::") {
+                test_results.push(IndividualTestResult {
+                    name: name.trim().to_string(),
+                    file_path: None,
+                    passed: false,
+                    duration_ms: None,
+                    error_message: Some("Test failed".to_string()),
+                });
+            }
+        }
+    }
+
+    let Some(total) = total else {
+        return parse_generic_output(stdout, stderr, output);
+    };
+    // Skipped/incomplete tests are also numbered in their own sections
+    test_results.truncate(failed as usize);
+
+    TestExecutionResult {
+        success: output.status.success() && failed == 0,
+        total,
+        passed: total.saturating_sub(failed + skipped),
+        failed,
+        skipped,
+        duration_ms: 0,
+        coverage_percent: None,
+        timed_out: false,
+        cancelled: false,
+        stdout: stdout.to_string(),
+        stderr: stderr.to_string(),
+        test_results,
+    }
+}
+
+/// Parse RSpec `--format json` output. Anything printed before the JSON
+/// document (warnings, puts from specs) is skipped.
+pub fn parse_rspec_output(stdout: &str, stderr: &str, output: &Output) -> TestExecutionResult {
+    let json = stdout
+        .find("{\"version\"")
+        .or_else(|| stdout.find('{'))
+        .and_then(|start| serde_json::from_str::<serde_json::Value>(stdout[start..].trim()).ok());
+    let Some(json) = json else {
+        return parse_generic_output(stdout, stderr, output);
+    };
+
+    let mut passed = 0u32;
+    let mut failed = 0u32;
+    let mut skipped = 0u32;
+    let mut test_results = Vec::new();
+
+    for example in json.get("examples").and_then(|v| v.as_array()).into_iter().flatten() {
+        let status = example.get("status").and_then(|v| v.as_str()).unwrap_or("unknown");
+        let is_passed = match status {
+            "passed" => {
+                passed += 1;
+                true
+            }
+            "pending" => {
+                skipped += 1;
+                true
+            }
+            _ => {
+                failed += 1;
+                false
+            }
+        };
+        test_results.push(IndividualTestResult {
+            name: example
+                .get("full_description")
+                .or_else(|| example.get("description"))
+                .and_then(|v| v.as_str())
+                .unwrap_or("unknown")
+                .to_string(),
+            file_path: example.get("file_path").and_then(|v| v.as_str()).map(|s| s.to_string()),
+            passed: is_passed,
+            duration_ms: example
+                .get("run_time")
+                .and_then(|v| v.as_f64())
+                .map(|secs| (secs * 1000.0) as u64),
+            error_message: example
+                .get("exception")
+                .and_then(|e| e.get("message"))
+                .and_then(|v| v.as_str())
+                .map(|s| s.to_string()),
+        });
+    }
+
+    let duration_ms = json
+        .get("summary")
+        .and_then(|s| s.get("duration"))
+        .and_then(|v| v.as_f64())
+        .map(|secs| (secs * 1000.0) as u64)
+        .unwrap_or(0);
+
+    TestExecutionResult {
+        success: output.status.success() && failed == 0,
+        total: passed + failed + skipped,
+        passed,
+        failed,
+        skipped,
+        duration_ms,
+        coverage_percent: None,
+        timed_out: false,
+        cancelled: false,
+        stdout: stdout.to_string(),
+        stderr: stderr.to_string(),
+        test_results,
+    }
+}
+
+/// Parse a Gradle test run from the JUnit XML reports it writes to
+/// build/test-results/test/. Falls back to the console output when there are none.
+fn parse_gradle_output(project_path: &str, stdout: &str, stderr: &str, output: &Output) -> TestExecutionResult {
+    let results_dir = Path::new(project_path).join("build/test-results/test");
+    let mut test_results = Vec::new();
+    let mut skipped = 0u32;
+
+    if let Ok(entries) = fs::read_dir(&results_dir) {
+        for entry in entries.flatten() {
+            let path = entry.path();
+            if path.extension().and_then(|e| e.to_str()) != Some("xml") {
+                continue;
+            }
+            if let Ok(content) = fs::read_to_string(&path) {
+                let (results, file_skipped) = parse_junit_xml(&content);
+                test_results.extend(results);
+                skipped += file_skipped;
+            }
+        }
+    }
+
+    if test_results.is_empty() {
+        return parse_generic_output(stdout, stderr, output);
+    }
+
+    let failed = test_results.iter().filter(|r| !r.passed).count() as u32;
+    let total = test_results.len() as u32;
+    TestExecutionResult {
+        success: output.status.success() && failed == 0,
+        total,
+        passed: total - failed - skipped,
+        failed,
+        skipped,
+        duration_ms: test_results.iter().filter_map(|r| r.duration_ms).sum(),
+        coverage_percent: None,
+        timed_out: false,
+        cancelled: false,
+        stdout: stdout.to_string(),
+        stderr: stderr.to_string(),
+        test_results,
+    }
+}
+
+/// Parse the <testcase> elements of a JUnit XML report.
+/// Returns the results (skipped tests count as passed, like the other parsers)
+/// and the number of skipped tests.
+pub fn parse_junit_xml(xml: &str) -> (Vec<IndividualTestResult>, u32) {
+    let testcase = regex::Regex::new(r"(?s)<testcase\b([^>]*?)(?:/>|>(.*?)</testcase>)").expect("valid regex");
+    let failure = regex::Regex::new(r"(?s)<(?:failure|error)\b([^>]*?)(?:/>|>(.*?)</(?:failure|error)>)").expect("valid regex");
+
+    let mut results = Vec::new();
+    let mut skipped = 0u32;
+    for caps in testcase.captures_iter(xml) {
+        let attrs = caps.get(1).map_or("", |m| m.as_str());
+        let body = caps.get(2).map_or("", |m| m.as_str());
+        let name = xml_attr(attrs, "name").unwrap_or_else(|| "unknown".to_string());
+        let class = xml_attr(attrs, "classname");
+        let error_message = failure.captures(body).map(|f| {
+            xml_attr(f.get(1).map_or("", |m| m.as_str()), "message")
+                .unwrap_or_else(|| xml_unescape(f.get(2).map_or("", |m| m.as_str()).trim()))
+        });
+        if error_message.is_none() && body.contains("<skipped") {
+            skipped += 1;
+        }
+        results.push(IndividualTestResult {
+            name: match &class {
+                Some(class) => format!("{}.{}", class, name),
+                None => name,
+            },
+            file_path: class,
+            passed: error_message.is_none(),
+            duration_ms: xml_attr(attrs, "time")
+                .and_then(|t| t.parse::<f64>().ok())
+                .map(|secs| (secs * 1000.0) as u64),
+            error_message,
+        });
+    }
+    (results, skipped)
+}
+
+/// Value of `name="..."` in an XML start tag's attribute text.
+fn xml_attr(attrs: &str, name: &str) -> Option<String> {
+    let needle = format!("{}=\"", name);
+    let start = attrs
+        .match_indices(&needle)
+        .find(|(i, _)| *i == 0 || attrs[..*i].ends_with(char::is_whitespace))?
+        .0
+        + needle.len();
+    let end = attrs[start..].find('"')?;
+    Some(xml_unescape(&attrs[start..start + end]))
+}
+
+fn xml_unescape(text: &str) -> String {
+    text.replace("&lt;", "<")
+        .replace("&gt;", ">")
+        .replace("&quot;", "\"")
+        .replace("&apos;", "'")
+        .replace("&#10;", "\n")
+        .replace("&amp;", "&")
+}
+
 /// Generic output parser for unknown frameworks
 pub fn parse_generic_output(stdout: &str, stderr: &str, output: &Output) -> TestExecutionResult {
     // Try to extract basic counts from common patterns
@@ -777,6 +1178,8 @@ pub fn parse_generic_output(stdout: &str, stderr: &str, output: &Output) -> Test
         skipped: 0,
         duration_ms: 0,
         coverage_percent: None,
+        timed_out: false,
+        cancelled: false,
         stdout: stdout.to_string(),
         stderr: stderr.to_string(),
         test_results: Vec::new(),
@@ -797,9 +1200,33 @@ fn extract_number_before(text: &str, keyword: &str) -> Option<u32> {
 }
 
 /// Extract coverage percentage from coverage files
-fn extract_coverage(project_path: &str, _framework_name: &str) -> Option<f64> {
+fn extract_coverage(project_path: &str, framework_name: &str) -> Option<f64> {
     let path = Path::new(project_path);
 
+    // Formats written by the non-JS frameworks' coverage commands
+    let framework_coverage = match framework_name {
+        "go test" => fs::read_to_string(path.join("coverage.out"))
+            .ok()
+            .and_then(|content| parse_go_coverprofile(&content)),
+        "PHPUnit" => fs::read_to_string(path.join("coverage/clover.xml"))
+            .ok()
+            .and_then(|content| parse_clover_coverage(&content)),
+        "RSpec" => fs::read_to_string(path.join("coverage/.last_run.json"))
+            .ok()
+            .and_then(|content| serde_json::from_str::<serde_json::Value>(&content).ok())
+            .and_then(|json| {
+                let result = json.get("result")?;
+                result.get("line").or_else(|| result.get("covered_percent"))?.as_f64()
+            }),
+        "Gradle" => fs::read_to_string(path.join("build/reports/jacoco/test/jacocoTestReport.xml"))
+            .ok()
+            .and_then(|content| parse_jacoco_coverage(&content)),
+        _ => None,
+    };
+    if framework_coverage.is_some() {
+        return framework_coverage;
+    }
+
     // Common coverage file locations
     let coverage_files = [
         "coverage/lcov.info",
@@ -862,6 +1289,46 @@ pub fn parse_coverage_lcov(path: &Path) -> Option<f64> {
     }
 }
 
+/// Statement coverage % from a Go cover profile ("file:range numStmts count" lines).
+pub fn parse_go_coverprofile(content: &str) -> Option<f64> {
+    let mut statements = 0u64;
+    let mut covered = 0u64;
+    for line in content.lines().filter(|l| !l.starts_with("mode:")) {
+        let mut fields = line.rsplitn(3, ' ');
+        let (Some(count), Some(num_stmts)) = (fields.next(), fields.next()) else {
+            continue;
+        };
+        let (Ok(count), Ok(num_stmts)) = (count.parse::<u64>(), num_stmts.parse::<u64>()) else {
+            continue;
+        };
+        statements += num_stmts;
+        if count > 0 {
+            covered += num_stmts;
+        }
+    }
+    (statements > 0).then(|| covered as f64 / statements as f64 * 100.0)
+}
+
+/// Statement coverage % from a Clover report: the project-level <metrics> element,
+/// which is the last one in the file.
+fn parse_clover_coverage(xml: &str) -> Option<f64> {
+    let start = xml.rfind("<metrics ")?;
+    let attrs = &xml[start..start + xml[start..].find('>')?];
+    let statements: f64 = xml_attr(attrs, "statements")?.parse().ok()?;
+    let covered: f64 = xml_attr(attrs, "coveredstatements")?.parse().ok()?;
+    (statements > 0.0).then(|| covered / statements * 100.0)
+}
+
+/// Line coverage % from a JaCoCo report: the report-level LINE counter,
+/// which is the last one in the file.
+fn parse_jacoco_coverage(xml: &str) -> Option<f64> {
+    let start = xml.rfind("<counter type=\"LINE\"")?;
+    let attrs = &xml[start..start + xml[start..].find('>')?];
+    let missed: f64 = xml_attr(attrs, "missed")?.parse().ok()?;
+    let covered: f64 = xml_attr(attrs, "covered")?.parse().ok()?;
+    (missed + covered > 0.0).then(|| covered / (missed + covered) * 100.0)
+}
+
 // =============================================================================
 // Test Discovery (count tests without running them)
 // =============================================================================
@@ -914,12 +1381,14 @@ fn count_vitest(path: &Path) -> Option<(u32, String, String)> {
         return None;
     }
 
-    let output = Command::new("npx")
-        .args(["vitest", "--list", "--reporter=verbose"])
-        .current_dir(path)
-        .env("CI", "true")
-        .output()
-        .ok()?;
+    let output = proc::run(
+        Command::new("npx")
+            .args(["vitest", "--list", "--reporter=verbose"])
+            .current_dir(path)
+            .env("CI", "true"),
+        ProcLimits::TEST_LIST,
+    )
+    .ok()?;
 
     if !output.status.success() {
         return None;
@@ -966,11 +1435,11 @@ fn count_playwright(path: &Path) -> Option<(u32, String, String)> {
         return None;
     }
 
-    let output = Command::new("npx")
-        .args(["playwright", "test", "--list"])
-        .current_dir(path)
-        .output()
-        .ok()?;
+    let output = proc::run(
+        Command::new("npx").args(["playwright", "test", "--list"]).current_dir(path),
+        ProcLimits::TEST_LIST,
+    )
+    .ok()?;
 
     if !output.status.success() {
         return None;
@@ -999,11 +1468,11 @@ fn count_cargo_tests(path: &Path) -> Option<(u32, String, String)> {
         return None;
     }
 
-    let output = Command::new("cargo")
-        .args(["test", "--", "--list"])
-        .current_dir(path)
-        .output()
-        .ok()?;
+    let output = proc::run(
+        Command::new("cargo").args(["test", "--", "--list"]).current_dir(path),
+        ProcLimits::TEST_LIST,
+    )
+    .ok()?;
 
     // cargo test -- --list returns success even if there are no tests
     let stdout = String::from_utf8_lossy(&output.stdout);
@@ -1029,11 +1498,11 @@ fn count_pytest(path: &Path) -> Option<(u32, String, String)> {
         return None;
     }
 
-    let output = Command::new("pytest")
-        .args(["--collect-only", "-q"])
-        .current_dir(path)
-        .output()
-        .ok()?;
+    let output = proc::run(
+        Command::new("pytest").args(["--collect-only", "-q"]).current_dir(path),
+        ProcLimits::TEST_LIST,
+    )
+    .ok()?;
 
     let stdout = String::from_utf8_lossy(&output.stdout);
     // Last line is typically "X tests collected" or "X test collected"
@@ -1057,11 +1526,11 @@ fn count_go_tests(path: &Path) -> Option<(u32, String, String)> {
         return None;
     }
 
-    let output = Command::new("go")
-        .args(["test", "-list", ".*", "./..."])
-        .current_dir(path)
-        .output()
-        .ok()?;
+    let output = proc::run(
+        Command::new("go").args(["test", "-list", ".*", "./..."]).current_dir(path),
+        ProcLimits::TEST_LIST,
+    )
+    .ok()?;
 
     let stdout = String::from_utf8_lossy(&output.stdout);
     let count = stdout
@@ -1153,6 +1622,17 @@ pub fn is_test_file(name: &str) -> bool {
         return true;
     }
 
+    // PHPUnit: *Test.php; RSpec: *_spec.rb; JUnit: *Test.java / *Tests.kt etc.
+    if lower.ends_with("_spec.rb") {
+        return true;
+    }
+    if [".php", ".java", ".kt"].iter().any(|ext| {
+        name.strip_suffix(ext)
+            .is_some_and(|stem| stem.len() > 4 && (stem.ends_with("Test") || stem.ends_with("Tests")))
+    }) {
+        return true;
+    }
+
     // Rust files with inline tests are handled separately — here we look
     // for dedicated test files only. Rust inline tests in source files are
     // also counted if the source file itself is a test file name pattern.
@@ -1184,6 +1664,33 @@ pub fn count_test_calls(content: &str, filename: &str) -> u32 {
                 count += 1;
             }
         }
+    } else if lower_filename.ends_with(".php") {
+        // PHPUnit: `function test*` methods plus @test / #[Test] annotated ones
+        for line in content.lines() {
+            let trimmed = line.trim();
+            if trimmed.contains("function test") || trimmed == "#[Test]" || trimmed.ends_with("@test") {
+                count += 1;
+            }
+        }
+    } else if lower_filename.ends_with(".rb") {
+        // RSpec: count `it`, `specify`, and `example` blocks
+        for line in content.lines() {
+            let trimmed = line.trim();
+            if ["it ", "it(", "it {", "specify ", "specify {", "example "]
+                .iter()
+                .any(|prefix| trimmed.starts_with(prefix))
+            {
+                count += 1;
+            }
+        }
+    } else if lower_filename.ends_with(".java") || lower_filename.ends_with(".kt") {
+        // JUnit: count @Test / @ParameterizedTest annotations
+        for line in content.lines() {
+            let trimmed = line.trim();
+            if trimmed == "@Test" || trimmed.starts_with("@Test ") || trimmed.starts_with("@ParameterizedTest") {
+                count += 1;
+            }
+        }
     } else {
         // JS/TS: count `it(`, `test(`, `it.each(`, `test.each(`
         for line in content.lines() {
@@ -1291,6 +1798,129 @@ def helper_function():
         assert!(!is_test_file("main.py"));
         assert!(!is_test_file("server.go"));
         assert!(!is_test_file("package.json"));
+        assert!(is_test_file("UserServiceTest.php"));
+        assert!(is_test_file("user_spec.rb"));
+        assert!(is_test_file("ParserTests.java"));
+        assert!(!is_test_file("Contest.kt"));
+        assert!(!is_test_file("Test.java"));
+    }
+
+    fn exit_output(code: i32) -> Output {
+        Command::new(if code == 0 { "true" } else { "false" }).output().unwrap()
+    }
+
+    #[test]
+    fn test_detect_polyglot_frameworks() {
+        let dir = tempfile::tempdir().unwrap();
+        let project = dir.path().to_str().unwrap();
+
+        fs::write(dir.path().join("build.gradle.kts"), "plugins { java }").unwrap();
+        fs::write(dir.path().join("gradlew"), "").unwrap();
+        let gradle = detect_test_framework(project).unwrap();
+        assert_eq!(gradle.name, "Gradle");
+        assert_eq!(gradle.command, "./gradlew test --console=plain");
+
+        fs::write(dir.path().join(".rspec"), "--require spec_helper").unwrap();
+        assert_eq!(detect_test_framework(project).unwrap().name, "RSpec");
+
+        fs::write(dir.path().join("composer.json"), r#"{"require-dev": {"phpunit/phpunit": "^10"}}"#).unwrap();
+        assert_eq!(detect_test_framework(project).unwrap().name, "PHPUnit");
+    }
+
+    #[test]
+    fn test_detect_uses_package_manager() {
+        let js = tempfile::tempdir().unwrap();
+        fs::write(js.path().join("package.json"), r#"{"devDependencies": {"vitest": "^1"}}"#).unwrap();
+        let project = js.path().to_str().unwrap();
+        assert_eq!(detect_test_framework(project).unwrap().command, "pnpm vitest run --reporter=json");
+        fs::write(js.path().join("package-lock.json"), "{}").unwrap();
+        assert_eq!(detect_test_framework(project).unwrap().command, "npx vitest run --reporter=json");
+
+        let py = tempfile::tempdir().unwrap();
+        fs::write(py.path().join("pyproject.toml"), "[project]\nname = \"api\"\n").unwrap();
+        let project = py.path().to_str().unwrap();
+        assert_eq!(detect_test_framework(project).unwrap().command, "pytest --tb=short -q");
+        fs::write(py.path().join("poetry.lock"), "").unwrap();
+        assert_eq!(detect_test_framework(project).unwrap().command, "poetry run pytest --tb=short -q");
+    }
+
+    #[test]
+    fn test_parse_go_test_output() {
+        let stdout = r#"{"Action":"run","Package":"example.com/app","Test":"TestAdd"}
+{"Action":"output","Package":"example.com/app","Test":"TestAdd","Output":"--- PASS: TestAdd (0.00s)
+"}
+{"Action":"pass","Package":"example.com/app","Test":"TestAdd","Elapsed":0.01}
+{"Action":"output","Package":"example.com/app","Test":"TestSub","Output":"    math_test.go:12: got 2, want 1
+"}
+{"Action":"fail","Package":"example.com/app","Test":"TestSub","Elapsed":0}
+{"Action":"skip","Package":"example.com/app","Test":"TestSlow","Elapsed":0}
+{"Action":"fail","Package":"example.com/app","Elapsed":0.25}"#;
+        let result = parse_go_test_output(stdout, "", &exit_output(1));
+        assert_eq!((result.total, result.passed, result.failed, result.skipped), (3, 1, 1, 1));
+        assert_eq!(result.duration_ms, 250);
+        let failure = result.test_results.iter().find(|r| r.name == "TestSub").unwrap();
+        assert_eq!(failure.error_message.as_deref(), Some("math_test.go:12: got 2, want 1"));
+        assert!(!result.success);
+    }
+
+    #[test]
+    fn test_parse_phpunit_output() {
+        let stdout = "PHPUnit 10.5.0\n\nThere was 1 failure:\n\n1) Tests\\CartTest::testTotal\nFailed asserting that 3 matches expected 4.\n\nFAILURES!\nTests: 12, Assertions: 30, Failures: 1, Skipped: 2.\n";
+        let result = parse_phpunit_output(stdout, "", &exit_output(1));
+        assert_eq!((result.total, result.passed, result.failed, result.skipped), (12, 9, 1, 2));
+        assert_eq!(result.test_results[0].name, "Tests\\CartTest::testTotal");
+
+        let ok = parse_phpunit_output("OK (5 tests, 9 assertions)\n", "", &exit_output(0));
+        assert_eq!((ok.total, ok.passed, ok.failed), (5, 5, 0));
+        assert!(ok.success);
+    }
+
+    #[test]
+    fn test_parse_rspec_output() {
+        let stdout = r#"warning: something noisy
+{"version":"3.13.0","examples":[
+ {"full_description":"Cart totals items","status":"passed","file_path":"./spec/cart_spec.rb","run_time":0.002},
+ {"full_description":"Cart applies discounts","status":"failed","file_path":"./spec/cart_spec.rb","run_time":0.01,"exception":{"message":"expected 4, got 3"}},
+ {"full_description":"Cart ships","status":"pending","file_path":"./spec/cart_spec.rb","run_time":0}
+],"summary":{"duration":0.5,"example_count":3,"failure_count":1,"pending_count":1}}"#;
+        let result = parse_rspec_output(stdout, "", &exit_output(1));
+        assert_eq!((result.total, result.passed, result.failed, result.skipped), (3, 1, 1, 1));
+        assert_eq!(result.duration_ms, 500);
+        assert_eq!(result.test_results[1].error_message.as_deref(), Some("expected 4, got 3"));
+    }
+
+    #[test]
+    fn test_parse_junit_xml() {
+        let xml = r#"<?xml version="1.0" encoding="UTF-8"?>
+<testsuite name="com.example.ParserTest" tests="3" skipped="1" failures="1" errors="0">
+  <testcase name="parsesNumbers" classname="com.example.ParserTest" time="0.012"/>
+  <testcase name="rejectsGarbage()" classname="com.example.ParserTest" time="0.003">
+    <failure message="expected: &lt;true&gt; but was: &lt;false&gt;" type="AssertionFailedError">stack</failure>
+  </testcase>
+  <testcase name="slowPath" classname="com.example.ParserTest" time="0">
+    <skipped/>
+  </testcase>
+</testsuite>"#;
+        let (results, skipped) = parse_junit_xml(xml);
+        assert_eq!(results.len(), 3);
+        assert_eq!(skipped, 1);
+        assert_eq!(results[0].name, "com.example.ParserTest.parsesNumbers");
+        assert_eq!(results[0].duration_ms, Some(12));
+        assert!(!results[1].passed);
+        assert_eq!(results[1].error_message.as_deref(), Some("expected: <true> but was: <false>"));
+        assert!(results[2].passed);
+    }
+
+    #[test]
+    fn test_parse_coverage_reports() {
+        let profile = "mode: set\nexample.com/app/math.go:3.24,5.2 1 1\nexample.com/app/math.go:7.24,9.2 3 0\n";
+        assert_eq!(parse_go_coverprofile(profile), Some(25.0));
+
+        let clover = r#"<coverage><project><file><metrics statements="4" coveredstatements="4"/></file><metrics files="1" statements="10" coveredstatements="8"/></project></coverage>"#;
+        assert_eq!(parse_clover_coverage(clover), Some(80.0));
+
+        let jacoco = r#"<report><package><counter type="LINE" missed="9" covered="1"/></package><counter type="INSTRUCTION" missed="1" covered="1"/><counter type="LINE" missed="1" covered="3"/></report>"#;
+        assert_eq!(parse_jacoco_coverage(jacoco), Some(75.0));
     }
 
     #[test]
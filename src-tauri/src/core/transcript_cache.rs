@@ -0,0 +1,278 @@
+//! @module core/transcript_cache
+//! @description Parsed Claude Code session transcripts cached in the transcript_cache table
+//!
+//! PURPOSE:
+//! - Normalize JSONL transcript lines into role/text messages
+//! - Reuse a transcript's parsed messages until its file changes
+//! - Parse only the appended lines when a transcript grows
+//!
+//! DEPENDENCIES:
+//! - rusqlite - transcript_cache reads and writes
+//! - sha2 - Hash of the parsed prefix of a transcript
+//! - serde_json - JSONL parsing and the cached messages column
+//! - chrono - updated_at timestamps
+//!
+//! EXPORTS:
+//! - TranscriptMessage - One normalized user/assistant message
+//! - CachedTranscript - A transcript's parsed messages and the prefix they cover
+//! - MAX_MESSAGE_CHARS - Per-message text limit applied when parsing
+//! - extract_message_text - Human-readable text from a message's content (string or blocks)
+//! - parse_line - Normalize one JSONL line (None for non-message lines)
+//! - refresh - Bring a cached transcript up to date with its file
+//! - load_cached / save_cached - transcript_cache row access
+//!
+//! PATTERNS:
+//! - Only complete lines (up to the last "\n") are parsed; a line still being written is
+//!   picked up on the next refresh
+//! - A cache entry is valid while the file still starts with the bytes it was parsed from
+//!   (prefix_hash over parsed_bytes); otherwise the transcript is parsed from scratch
+//! - refresh does no DB access so callers can run it without holding the DB lock
+//!
+//! CLAUDE NOTES:
+//! - Transcript lines: {"type": "user"|"assistant", "message": {"role", "content"}}; content is a
+//!   string (human text) or an array of text/tool_use/tool_result/thinking blocks
+//! - tool_result and thinking blocks are dropped; tool_use becomes "[Used tool: <name>]"
+//! - Rows are keyed by transcript path; project_path lets data purges find a project's rows
+
+use std::fs;
+use std::path::Path;
+
+use chrono::Utc;
+use rusqlite::{Connection, OptionalExtension};
+use serde::{Deserialize, Serialize};
+use sha2::{Digest, Sha256};
+
+/// Per-message text limit applied when parsing.
+pub const MAX_MESSAGE_CHARS: usize = 800;
+
+/// One normalized user/assistant message.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct TranscriptMessage {
+    pub role: String,
+    pub text: String,
+}
+
+/// A transcript's parsed messages and the file prefix they were parsed from.
+#[derive(Debug, Clone)]
+pub struct CachedTranscript {
+    pub transcript_path: String,
+    pub project_path: String,
+    pub parsed_bytes: u64,
+    pub prefix_hash: String,
+    pub messages: Vec<TranscriptMessage>,
+}
+
+/// Extract human-readable text from message content.
+/// Handles both string content and arrays of content blocks.
+pub fn extract_message_text(content: &serde_json::Value) -> String {
+    if let Some(text) = content.as_str() {
+        return text.to_string();
+    }
+
+    let Some(arr) = content.as_array() else {
+        return String::new();
+    };
+    let mut texts = Vec::new();
+    for item in arr {
+        match item.get("type").and_then(|t| t.as_str()).unwrap_or("") {
+            "text" => {
+                if let Some(text) = item.get("text").and_then(|t| t.as_str()) {
+                    texts.push(text.to_string());
+                }
+            }
+            // Only mention tool use briefly, don't include full input
+            "tool_use" => {
+                if let Some(name) = item.get("name").and_then(|n| n.as_str()) {
+                    texts.push(format!("[Used tool: {}]", name));
+                }
+            }
+            // tool_result and thinking blocks are verbose and not useful for analysis
+            _ => {}
+        }
+    }
+    texts.join(" ")
+}
+
+/// Normalize one JSONL line. None for lines without message text.
+pub fn parse_line(line: &str) -> Option<TranscriptMessage> {
+    let json: serde_json::Value = serde_json::from_str(line).ok()?;
+    let msg_type = json.get("type").and_then(|v| v.as_str()).unwrap_or("");
+    let message = json.get("message")?;
+    let role = message.get("role").and_then(|v| v.as_str()).unwrap_or(msg_type);
+
+    let text = extract_message_text(message.get("content")?);
+    if text.is_empty() {
+        return None;
+    }
+    let text = if text.chars().count() > MAX_MESSAGE_CHARS {
+        format!("{}...", text.chars().take(MAX_MESSAGE_CHARS).collect::<String>())
+    } else {
+        text
+    };
+    Some(TranscriptMessage {
+        role: role.to_string(),
+        text,
+    })
+}
+
+fn parse_bytes(bytes: &[u8]) -> Vec<TranscriptMessage> {
+    String::from_utf8_lossy(bytes).lines().filter_map(parse_line).collect()
+}
+
+fn prefix_hash(bytes: &[u8]) -> String {
+    format!("{:x}", Sha256::digest(bytes))
+}
+
+/// Bring a cached transcript up to date with its file. Returns the transcript and
+/// whether it changed (and should be saved).
+pub fn refresh(
+    transcript_path: &Path,
+    project_path: &str,
+    cached: Option<CachedTranscript>,
+) -> Result<(CachedTranscript, bool), String> {
+    let bytes = fs::read(transcript_path)
+        .map_err(|e| format!("Failed to read transcript {}: {}", transcript_path.display(), e))?;
+    let complete = bytes.iter().rposition(|b| *b == b'\n').map(|i| i + 1).unwrap_or(0);
+
+    let reusable = cached.filter(|c| {
+        let parsed = c.parsed_bytes as usize;
+        parsed <= complete && prefix_hash(&bytes[..parsed]) == c.prefix_hash
+    });
+
+    let (mut messages, start) = match reusable {
+        Some(c) if c.parsed_bytes as usize == complete => return Ok((c, false)),
+        Some(c) => {
+            let start = c.parsed_bytes as usize;
+            (c.messages, start)
+        }
+        None => (Vec::new(), 0),
+    };
+    messages.extend(parse_bytes(&bytes[start..complete]));
+
+    Ok((
+        CachedTranscript {
+            transcript_path: transcript_path.to_string_lossy().to_string(),
+            project_path: project_path.to_string(),
+            parsed_bytes: complete as u64,
+            prefix_hash: prefix_hash(&bytes[..complete]),
+            messages,
+        },
+        true,
+    ))
+}
+
+/// Read a transcript's cache row (None when missing or unreadable).
+pub fn load_cached(db: &Connection, transcript_path: &str) -> Option<CachedTranscript> {
+    db.query_row(
+        "SELECT transcript_path, project_path, parsed_bytes, prefix_hash, messages
+         FROM transcript_cache WHERE transcript_path = ?1",
+        [transcript_path],
+        |row| {
+            let messages: String = row.get(4)?;
+            Ok(CachedTranscript {
+                transcript_path: row.get(0)?,
+                project_path: row.get(1)?,
+                parsed_bytes: row.get::<_, i64>(2)? as u64,
+                prefix_hash: row.get(3)?,
+                messages: serde_json::from_str(&messages).unwrap_or_default(),
+            })
+        },
+    )
+    .optional()
+    .ok()
+    .flatten()
+}
+
+/// Insert or replace a transcript's cache row.
+pub fn save_cached(db: &Connection, transcript: &CachedTranscript) -> Result<(), String> {
+    let messages = serde_json::to_string(&transcript.messages)
+        .map_err(|e| format!("Failed to serialize transcript: {}", e))?;
+    db.execute(
+        "INSERT OR REPLACE INTO transcript_cache
+         (transcript_path, project_path, parsed_bytes, prefix_hash, messages, updated_at)
+         VALUES (?1, ?2, ?3, ?4, ?5, ?6)",
+        rusqlite::params![
+            transcript.transcript_path,
+            transcript.project_path,
+            transcript.parsed_bytes as i64,
+            transcript.prefix_hash,
+            messages,
+            Utc::now().to_rfc3339(),
+        ],
+    )
+    .map_err(|e| format!("Failed to cache transcript: {}", e))?;
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_support::memory_db;
+    use std::io::Write;
+
+    fn line(role: &str, text: &str) -> String {
+        format!(
+            "{}\n",
+            serde_json::json!({"type": role, "message": {"role": role, "content": text}})
+        )
+    }
+
+    #[test]
+    fn test_parse_line() {
+        let blocks = r#"{"type":"assistant","message":{"role":"assistant","content":[{"type":"thinking","thinking":"x"},{"type":"text","text":"Done"},{"type":"tool_use","name":"Edit","input":{}}]}}"#;
+        assert_eq!(parse_line(blocks).unwrap().text, "Done [Used tool: Edit]");
+        assert!(parse_line(r#"{"type":"summary","summary":"s"}"#).is_none());
+        assert!(parse_line("not json").is_none());
+
+        let long = parse_line(&line("user", &"é".repeat(1000))).unwrap();
+        assert_eq!(long.text.chars().count(), MAX_MESSAGE_CHARS + 3);
+    }
+
+    #[test]
+    fn test_refresh_appends_and_invalidates() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("s.jsonl");
+        fs::write(&path, format!("{}{}", line("user", "Add tests"), line("assistant", "Added"))).unwrap();
+
+        let (first, changed) = refresh(&path, "/p", None).unwrap();
+        assert!(changed);
+        assert_eq!(first.messages.len(), 2);
+
+        let (same, changed) = refresh(&path, "/p", Some(first.clone())).unwrap();
+        assert!(!changed);
+        assert_eq!(same.parsed_bytes, first.parsed_bytes);
+
+        // Appended lines are parsed; a partial trailing line waits for its newline
+        let mut file = fs::OpenOptions::new().append(true).open(&path).unwrap();
+        write!(file, "{}{{\"type\":\"user\"", line("user", "Now docs")).unwrap();
+        drop(file);
+        let (grown, changed) = refresh(&path, "/p", Some(first.clone())).unwrap();
+        assert!(changed);
+        assert_eq!(grown.messages.len(), 3);
+        assert_eq!(grown.messages[2].text, "Now docs");
+        assert!(grown.parsed_bytes < fs::metadata(&path).unwrap().len());
+
+        // A rewritten file is parsed from scratch
+        fs::write(&path, line("user", "Fresh session")).unwrap();
+        let (fresh, _) = refresh(&path, "/p", Some(grown)).unwrap();
+        assert_eq!(fresh.messages, vec![TranscriptMessage { role: "user".into(), text: "Fresh session".into() }]);
+    }
+
+    #[test]
+    fn test_cache_roundtrip() {
+        let conn = memory_db();
+        assert!(load_cached(&conn, "/t.jsonl").is_none());
+
+        let transcript = CachedTranscript {
+            transcript_path: "/t.jsonl".to_string(),
+            project_path: "/p".to_string(),
+            parsed_bytes: 42,
+            prefix_hash: "abc".to_string(),
+            messages: vec![TranscriptMessage { role: "user".into(), text: "hi".into() }],
+        };
+        save_cached(&conn, &transcript).unwrap();
+        let loaded = load_cached(&conn, "/t.jsonl").unwrap();
+        assert_eq!(loaded.parsed_bytes, 42);
+        assert_eq!(loaded.messages, transcript.messages);
+    }
+}
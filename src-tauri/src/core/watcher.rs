@@ -6,33 +6,52 @@
 //! - Debounce rapid file system events (500ms window)
 //! - Emit structured change events to the frontend via Tauri events
 //! - Filter to relevant source files and CLAUDE.md
+//! - Fall back to polling on network filesystems, WSL mounts, or when native watching fails
 //!
 //! DEPENDENCIES:
-//! - notify - Cross-platform file watching (RecommendedWatcher)
+//! - notify - Cross-platform file watching (RecommendedWatcher, PollWatcher)
 //! - tauri - AppHandle for event emission
 //! - tokio - Async runtime for debounce timing
 //! - serde - Serialization for event payload
+//! - core::proc - `mount` on macOS for filesystem type detection
 //!
 //! EXPORTS:
 //! - ProjectWatcher - Struct wrapping the notify watcher
 //! - FileChangePayload - Event payload sent to frontend
+//! - WatcherMode - native | polling
+//! - WatchOptions - Mode preference ("auto" | "native" | "polling") and poll interval
+//! - WatcherStatus - Active mode, interval, fallback reason, and error count
+//! - DEFAULT_POLL_INTERVAL_MS - Poll interval when none is configured
+//! - parse_proc_mounts - (mount point, fs type) pairs from /proc/mounts
+//! - parse_mount_output - (mount point, fs type) pairs from BSD/macOS `mount`
+//! - network_fs_type - Filesystem type of a path when it is a network or WSL mount
 //!
 //! PATTERNS:
 //! - start() creates a watcher, spawns a debounce task, returns ProjectWatcher
 //! - stop() drops the watcher (cleanup is automatic via Drop)
+//! - start() takes an on_flush callback run on the debounce thread after each batch
 //! - Events are emitted as "file-changed" Tauri events
 //! - Only source files (.ts/.tsx/.js/.jsx/.rs/.py/.go) and CLAUDE.md trigger events
+//! - "auto" picks polling for network/WSL mounts and when the native watcher cannot start;
+//!   "native" never falls back; "polling" always polls
 //!
 //! CLAUDE NOTES:
 //! - The watcher uses notify-rs with recursive mode
 //! - Debounce is implemented via a tokio channel + sleep, not notify's built-in debouncer
 //! - ProjectWatcher is stored in AppState behind a std::sync::Mutex<Option<...>>
 //! - The frontend listens for "file-changed" events via @tauri-apps/api/event
+//! - Polling watches the root non-recursively plus each top-level directory except
+//!   POLL_SKIP_DIRS, so dependency and build trees are never walked; top-level directories
+//!   created after start are only picked up on the next start
+//! - Errors the native watcher reports after starting are counted in WatcherStatus, not
+//!   recovered from; set watcher.mode to "polling" for filesystems that drop events
 
-use notify::{Config, Event, RecommendedWatcher, RecursiveMode, Watcher};
+use notify::{Config, Event, PollWatcher, RecommendedWatcher, RecursiveMode, Watcher};
 use serde::Serialize;
-use std::path::Path;
-use std::sync::mpsc;
+use std::path::{Path, PathBuf};
+use std::sync::atomic::{AtomicU32, Ordering};
+use std::sync::{mpsc, Arc, Mutex};
+use std::time::Duration;
 use tauri::{AppHandle, Emitter};
 
 /// Payload emitted to the frontend when a file changes.
@@ -42,10 +61,108 @@ pub struct FileChangePayload {
     pub kind: String,
 }
 
+/// Poll interval used when watcher.poll_interval_ms is not set.
+pub const DEFAULT_POLL_INTERVAL_MS: u64 = 2000;
+
+/// Bounds for a configured poll interval.
+const MIN_POLL_INTERVAL_MS: u64 = 500;
+const MAX_POLL_INTERVAL_MS: u64 = 60_000;
+
+/// Top-level directories polling never walks (dependencies, build output, VCS data).
+const POLL_SKIP_DIRS: &[&str] = &[
+    "node_modules", "target", ".git", "dist", "build", ".next", "__pycache__", ".venv", "venv", "coverage",
+];
+
+/// Filesystem types where native change notifications are missing or unreliable.
+const NETWORK_FS_TYPES: &[&str] = &[
+    "nfs", "nfs4", "cifs", "smbfs", "smb3", "afpfs", "webdav", "davfs", "afs", "ceph", "glusterfs",
+    "9p", "drvfs", "sshfs", "fuse.sshfs", "fuse.rclone", "fuse.s3fs", "fuse.gvfsd-fuse",
+];
+
+/// How a project is being watched.
+#[derive(Debug, Clone, Copy, PartialEq, Serialize)]
+#[serde(rename_all = "lowercase")]
+pub enum WatcherMode {
+    Native,
+    Polling,
+}
+
+/// Watcher configuration (settings watcher.mode and watcher.poll_interval_ms).
+#[derive(Debug, Clone, PartialEq)]
+pub struct WatchOptions {
+    /// "auto" | "native" | "polling"
+    pub preference: String,
+    pub poll_interval: Duration,
+}
+
+impl Default for WatchOptions {
+    fn default() -> Self {
+        WatchOptions {
+            preference: "auto".to_string(),
+            poll_interval: Duration::from_millis(DEFAULT_POLL_INTERVAL_MS),
+        }
+    }
+}
+
+impl WatchOptions {
+    /// Build options from raw setting values; unknown modes mean "auto" and the
+    /// interval is clamped to 500ms..60s.
+    pub fn from_settings(mode: Option<&str>, poll_interval_ms: Option<&str>) -> Self {
+        let preference = match mode.map(str::trim) {
+            Some(m @ ("native" | "polling")) => m.to_string(),
+            _ => "auto".to_string(),
+        };
+        let interval = poll_interval_ms
+            .and_then(|v| v.trim().parse::<u64>().ok())
+            .unwrap_or(DEFAULT_POLL_INTERVAL_MS)
+            .clamp(MIN_POLL_INTERVAL_MS, MAX_POLL_INTERVAL_MS);
+        WatchOptions {
+            preference,
+            poll_interval: Duration::from_millis(interval),
+        }
+    }
+}
+
+/// Current watcher state, returned by get_watcher_status.
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct WatcherStatus {
+    pub watching: bool,
+    pub project_path: Option<String>,
+    pub mode: Option<WatcherMode>,
+    pub poll_interval_ms: Option<u64>,
+    /// Why polling was chosen in auto mode (network mount or native watcher error)
+    pub fallback_reason: Option<String>,
+    /// Errors reported by the watcher since it started
+    pub error_count: u32,
+    pub last_error: Option<String>,
+}
+
+impl WatcherStatus {
+    /// Status when nothing is being watched.
+    pub fn idle() -> Self {
+        WatcherStatus {
+            watching: false,
+            project_path: None,
+            mode: None,
+            poll_interval_ms: None,
+            fallback_reason: None,
+            error_count: 0,
+            last_error: None,
+        }
+    }
+}
+
 /// A file system watcher for a single project directory.
 /// Dropping this struct stops the watcher automatically.
 pub struct ProjectWatcher {
-    _watcher: RecommendedWatcher,
+    _watcher: Box<dyn Watcher>,
+    project_path: String,
+    mode: WatcherMode,
+    poll_interval: Option<Duration>,
+    fallback_reason: Option<String>,
+    error_count: Arc<AtomicU32>,
+    last_error: Arc<Mutex<Option<String>>>,
 }
 
 // notify::RecommendedWatcher is not Send on all platforms, but we only store it
@@ -86,30 +203,188 @@ fn event_kind_str(kind: &notify::EventKind) -> &'static str {
     }
 }
 
+/// Octal escapes (\040 for a space) used in /proc/mounts fields.
+fn unescape_mount_field(field: &str) -> String {
+    let mut out = String::new();
+    let mut chars = field.chars();
+    while let Some(c) = chars.next() {
+        if c == '\\' {
+            let digits: String = chars.clone().take(3).collect();
+            if digits.len() == 3 {
+                if let Ok(code) = u8::from_str_radix(&digits, 8) {
+                    out.push(code as char);
+                    chars.nth(2);
+                    continue;
+                }
+            }
+        }
+        out.push(c);
+    }
+    out
+}
+
+/// (mount point, fs type) pairs from /proc/mounts (or /proc/self/mounts).
+pub fn parse_proc_mounts(content: &str) -> Vec<(String, String)> {
+    content
+        .lines()
+        .filter_map(|line| {
+            let mut fields = line.split_whitespace();
+            let _device = fields.next()?;
+            let mount_point = unescape_mount_field(fields.next()?);
+            Some((mount_point, fields.next()?.to_string()))
+        })
+        .collect()
+}
+
+/// (mount point, fs type) pairs from BSD/macOS `mount` output:
+/// "//me@nas/share on /Volumes/share (smbfs, nodev, nosuid, mounted by me)".
+#[cfg_attr(not(target_os = "macos"), allow(dead_code))]
+pub fn parse_mount_output(output: &str) -> Vec<(String, String)> {
+    output
+        .lines()
+        .filter_map(|line| {
+            let (_, rest) = line.split_once(" on ")?;
+            let (mount_point, options) = rest.rsplit_once(" (")?;
+            let fs_type = options.trim_end_matches(')').split(',').next()?.trim();
+            Some((mount_point.to_string(), fs_type.to_string()))
+        })
+        .collect()
+}
+
+/// Filesystem type of the longest mount point containing `path`.
+fn fs_type_for<'a>(path: &Path, mounts: &'a [(String, String)]) -> Option<&'a str> {
+    mounts
+        .iter()
+        .filter(|(mount_point, _)| path.starts_with(mount_point))
+        .max_by_key(|(mount_point, _)| mount_point.len())
+        .map(|(_, fs_type)| fs_type.as_str())
+}
+
+fn is_network_fs(fs_type: &str) -> bool {
+    NETWORK_FS_TYPES.contains(&fs_type)
+}
+
+/// The filesystem type of `path` when it is a network or WSL mount, else None.
+pub fn network_fs_type(path: &Path) -> Option<String> {
+    let path = path.canonicalize().unwrap_or_else(|_| path.to_path_buf());
+    let display = path.to_string_lossy();
+    // Windows UNC shares: \\server\share, or \\?\UNC\server\share once canonicalized
+    if display.starts_with(r"\\?\UNC\") || (display.starts_with(r"\\") && !display.starts_with(r"\\?\")) {
+        return Some("unc".to_string());
+    }
+    let mounts = mount_table();
+    fs_type_for(&path, &mounts).filter(|t| is_network_fs(t)).map(str::to_string)
+}
+
+#[cfg(target_os = "linux")]
+fn mount_table() -> Vec<(String, String)> {
+    std::fs::read_to_string("/proc/self/mounts")
+        .or_else(|_| std::fs::read_to_string("/proc/mounts"))
+        .map(|content| parse_proc_mounts(&content))
+        .unwrap_or_default()
+}
+
+#[cfg(target_os = "macos")]
+fn mount_table() -> Vec<(String, String)> {
+    use crate::core::proc::{self, ProcLimits};
+    let limits = ProcLimits::new(Duration::from_secs(5), 1024 * 1024);
+    proc::run(&mut std::process::Command::new("mount"), limits)
+        .ok()
+        .filter(|out| out.success())
+        .map(|out| parse_mount_output(&String::from_utf8_lossy(&out.stdout)))
+        .unwrap_or_default()
+}
+
+#[cfg(not(any(target_os = "linux", target_os = "macos")))]
+fn mount_table() -> Vec<(String, String)> {
+    Vec::new()
+}
+
+/// Create a polling watcher over the root (non-recursive) and each top-level directory
+/// outside POLL_SKIP_DIRS (recursive), plus .git/hooks.
+fn start_polling<H: notify::EventHandler>(root: &Path, handler: H, interval: Duration) -> notify::Result<PollWatcher> {
+    let mut watcher = PollWatcher::new(handler, Config::default().with_poll_interval(interval))?;
+    watcher.watch(root, RecursiveMode::NonRecursive)?;
+    if let Ok(entries) = std::fs::read_dir(root) {
+        for entry in entries.filter_map(|e| e.ok()) {
+            let name = entry.file_name().to_string_lossy().to_string();
+            if entry.path().is_dir() && !POLL_SKIP_DIRS.contains(&name.as_str()) {
+                watcher.watch(&entry.path(), RecursiveMode::Recursive)?;
+            }
+        }
+    }
+    let hooks = root.join(".git").join("hooks");
+    if hooks.is_dir() {
+        watcher.watch(&hooks, RecursiveMode::Recursive)?;
+    }
+    Ok(watcher)
+}
+
 impl ProjectWatcher {
     /// Start watching a project directory for source file changes.
-    /// Emits "file-changed" events to the frontend via the AppHandle.
-    pub fn start(app_handle: AppHandle, project_path: String) -> Result<Self, String> {
+    /// Emits "file-changed" events to the frontend via the AppHandle, then
+    /// calls `on_flush` with every path touched in the debounce window
+    /// (not only watched source files, so removed directories are included).
+    pub fn start<F>(app_handle: AppHandle, project_path: String, options: WatchOptions, on_flush: F) -> Result<Self, String>
+    where
+        F: Fn(Vec<PathBuf>) + Send + 'static,
+    {
         let path = Path::new(&project_path);
         if !path.exists() {
             return Err(format!("Path does not exist: {}", project_path));
         }
 
         let (tx, rx) = mpsc::channel::<Event>();
-
-        let mut watcher = RecommendedWatcher::new(
-            move |res: Result<Event, notify::Error>| {
-                if let Ok(event) = res {
+        let error_count = Arc::new(AtomicU32::new(0));
+        let last_error: Arc<Mutex<Option<String>>> = Arc::new(Mutex::new(None));
+        let handler = {
+            let error_count = error_count.clone();
+            let last_error = last_error.clone();
+            move |res: Result<Event, notify::Error>| match res {
+                Ok(event) => {
                     let _ = tx.send(event);
                 }
-            },
-            Config::default(),
-        )
-        .map_err(|e| format!("Failed to create watcher: {}", e))?;
+                Err(e) => {
+                    error_count.fetch_add(1, Ordering::Relaxed);
+                    if let Ok(mut last) = last_error.lock() {
+                        *last = Some(e.to_string());
+                    }
+                }
+            }
+        };
+
+        let network_fs = match options.preference.as_str() {
+            "auto" => network_fs_type(path),
+            _ => None,
+        };
+        let mut fallback_reason = network_fs.map(|fs| format!("Network or WSL filesystem ({})", fs));
+
+        // The handler moves into whichever watcher is created; a failed native start drops
+        // it, so polling gets a second handler sharing the same channel and counters
+        let native = if options.preference == "native" || (options.preference == "auto" && fallback_reason.is_none()) {
+            let polling_handler = handler.clone();
+            let attempt = RecommendedWatcher::new(handler, Config::default())
+                .and_then(|mut w| w.watch(path, RecursiveMode::Recursive).map(|_| w));
+            match attempt {
+                Ok(w) => Ok(w),
+                Err(e) if options.preference == "auto" => {
+                    fallback_reason = Some(format!("Native watcher failed: {}", e));
+                    Err(polling_handler)
+                }
+                Err(e) => return Err(format!("Failed to start watching: {}", e)),
+            }
+        } else {
+            Err(handler)
+        };
 
-        watcher
-            .watch(path, RecursiveMode::Recursive)
-            .map_err(|e| format!("Failed to start watching: {}", e))?;
+        let (watcher, mode): (Box<dyn Watcher>, WatcherMode) = match native {
+            Ok(w) => (Box::new(w), WatcherMode::Native),
+            Err(handler) => {
+                let w = start_polling(path, handler, options.poll_interval)
+                    .map_err(|e| format!("Failed to start polling watcher: {}", e))?;
+                (Box::new(w), WatcherMode::Polling)
+            }
+        };
 
         // Spawn a debounce task that collects events and emits after 500ms of quiet
         let handle = app_handle.clone();
@@ -121,12 +396,14 @@ impl ProjectWatcher {
             let mut pending: HashSet<String> = HashSet::new();
             let mut pending_kind: std::collections::HashMap<String, String> =
                 std::collections::HashMap::new();
+            let mut touched: HashSet<PathBuf> = HashSet::new();
             let mut last_event = Instant::now();
 
             loop {
                 match rx.recv_timeout(debounce_ms) {
                     Ok(event) => {
                         for path in &event.paths {
+                            touched.insert(path.clone());
                             if is_watched_file(path) {
                                 let path_str = path.to_string_lossy().to_string();
                                 let kind = event_kind_str(&event.kind).to_string();
@@ -137,7 +414,7 @@ impl ProjectWatcher {
                         last_event = Instant::now();
                     }
                     Err(mpsc::RecvTimeoutError::Timeout) => {
-                        if !pending.is_empty() && last_event.elapsed() >= debounce_ms {
+                        if (!pending.is_empty() || !touched.is_empty()) && last_event.elapsed() >= debounce_ms {
                             for path in pending.drain() {
                                 let kind = pending_kind
                                     .remove(&path)
@@ -151,6 +428,9 @@ impl ProjectWatcher {
                                 );
                             }
                             pending_kind.clear();
+                            if !touched.is_empty() {
+                                on_flush(touched.drain().collect());
+                            }
                         }
                     }
                     Err(mpsc::RecvTimeoutError::Disconnected) => {
@@ -163,8 +443,27 @@ impl ProjectWatcher {
 
         Ok(ProjectWatcher {
             _watcher: watcher,
+            project_path,
+            mode,
+            poll_interval: (mode == WatcherMode::Polling).then_some(options.poll_interval),
+            fallback_reason,
+            error_count,
+            last_error,
         })
     }
+
+    /// Mode, interval, fallback reason, and errors of this watcher.
+    pub fn status(&self) -> WatcherStatus {
+        WatcherStatus {
+            watching: true,
+            project_path: Some(self.project_path.clone()),
+            mode: Some(self.mode),
+            poll_interval_ms: self.poll_interval.map(|d| d.as_millis() as u64),
+            fallback_reason: self.fallback_reason.clone(),
+            error_count: self.error_count.load(Ordering::Relaxed),
+            last_error: self.last_error.lock().ok().and_then(|last| last.clone()),
+        }
+    }
 }
 
 #[cfg(test)]
@@ -184,6 +483,39 @@ mod tests {
         assert!(!is_watched_file(&PathBuf::from("image.png")));
     }
 
+    #[test]
+    fn test_watch_options_from_settings() {
+        assert_eq!(WatchOptions::from_settings(None, None), WatchOptions::default());
+        let options = WatchOptions::from_settings(Some("polling"), Some("5000"));
+        assert_eq!(options.preference, "polling");
+        assert_eq!(options.poll_interval, Duration::from_millis(5000));
+        assert_eq!(WatchOptions::from_settings(Some("fast"), Some("10")).preference, "auto");
+        assert_eq!(
+            WatchOptions::from_settings(None, Some("10")).poll_interval,
+            Duration::from_millis(MIN_POLL_INTERVAL_MS)
+        );
+    }
+
+    #[test]
+    fn test_mount_parsing_and_lookup() {
+        let proc_mounts = "/dev/sda1 / ext4 rw 0 0\n\
+            nas:/export /mnt/nas nfs4 rw 0 0\n\
+            drvfs /mnt/c 9p rw 0 0\n\
+            //srv/share /mnt/my\\040share cifs rw 0 0\n";
+        let mounts = parse_proc_mounts(proc_mounts);
+        assert_eq!(mounts[3], ("/mnt/my share".to_string(), "cifs".to_string()));
+        assert_eq!(fs_type_for(Path::new("/mnt/nas/app/src"), &mounts), Some("nfs4"));
+        assert_eq!(fs_type_for(Path::new("/mnt/c/Users/me/app"), &mounts), Some("9p"));
+        assert_eq!(fs_type_for(Path::new("/home/me/app"), &mounts), Some("ext4"));
+        assert!(is_network_fs("9p") && is_network_fs("cifs") && !is_network_fs("ext4"));
+
+        let mac = "/dev/disk3s1s1 on / (apfs, sealed, local, read-only, journaled)\n\
+            //me@nas._smb._tcp.local/code on /Volumes/code (smbfs, nodev, nosuid, mounted by me)\n";
+        let mounts = parse_mount_output(mac);
+        assert_eq!(fs_type_for(Path::new("/Volumes/code/app"), &mounts), Some("smbfs"));
+        assert_eq!(fs_type_for(Path::new("/Users/me/app"), &mounts), Some("apfs"));
+    }
+
     #[test]
     fn test_event_kind_str() {
         assert_eq!(
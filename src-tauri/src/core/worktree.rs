@@ -0,0 +1,419 @@
+//! @module core/worktree
+//! @description Git working-tree snapshots for attributing file changes to a RALPH iteration
+//!
+//! PURPOSE:
+//! - Capture HEAD and the dirty files (with content hashes) before and after a Claude run
+//! - Compare two snapshots to list the files the run added, modified, or deleted
+//! - Include files from commits made during the run (HEAD moved)
+//! - Flag destructive runs: files removed, or a large share of a file's lines deleted
+//!
+//! DEPENDENCIES:
+//! - core::proc - git status / rev-parse / diff with ProcLimits::GIT
+//! - sha2 - Content hashes so re-edits of already-dirty files are noticed
+//! - models::ralph - RalphIterationFile
+//!
+//! EXPORTS:
+//! - WorktreeSnapshot - HEAD sha and fingerprints of dirty paths
+//! - parse_porcelain - (status, path) pairs from `git status --porcelain`
+//! - take_snapshot - Snapshot a project's working tree (None outside a git repo)
+//! - diff_snapshots - Files changed between two snapshots (plus committed changes)
+//! - changed_between - Files changed in a project since a snapshot
+//! - DEFAULT_MAX_DELETED_PERCENT - Default share of a file's lines a run may delete
+//! - DestructiveChange - A removed file or a file that lost too many lines
+//! - parse_numstat - (path, added, deleted) rows from `git diff --numstat`
+//! - destructive_changes - Apply the deletion threshold to a run's changed files
+//! - check_destructive - Destructive changes since a snapshot, with their diff
+//!
+//! PATTERNS:
+//! - change: "added" | "modified" | "deleted"
+//! - A dirty path is changed when its status or content hash differs between snapshots;
+//!   files in commits between the two HEADs are merged in from `git diff --name-status`
+//!
+//! CLAUDE NOTES:
+//! - Untracked files are listed individually (-uall) so new files inside new directories count
+//! - Renames are reported as the new path, "added"
+//! - Paths are relative to the git root, assumed to be the project root
+//! - WorktreeSnapshot.base is a `git stash create` commit of the tracked working tree (HEAD when
+//!   clean), so line counts compare against the state before the run, not the last commit
+//! - Files that lose fewer than MIN_DELETED_LINES lines are never flagged by percentage
+
+use std::collections::{BTreeMap, HashMap};
+use std::fs;
+use std::path::Path;
+use std::process::Command;
+
+use sha2::{Digest, Sha256};
+
+use crate::core::proc::{self, ProcLimits};
+use crate::models::ralph::RalphIterationFile;
+
+/// HEAD and the dirty files of a working tree at one point in time.
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct WorktreeSnapshot {
+    pub head: Option<String>,
+    /// Commit holding the tracked working tree at snapshot time (stash commit, or HEAD when clean)
+    pub base: Option<String>,
+    /// Dirty path -> (porcelain status, content hash; empty when the file is gone)
+    pub entries: HashMap<String, (String, String)>,
+}
+
+fn git(project_path: &Path, args: &[&str]) -> Option<String> {
+    let output = proc::run(Command::new("git").args(args).current_dir(project_path), ProcLimits::GIT).ok()?;
+    if !output.success() {
+        return None;
+    }
+    Some(String::from_utf8_lossy(&output.stdout).to_string())
+}
+
+/// (two-letter status, path) pairs from `git status --porcelain` output.
+pub fn parse_porcelain(output: &str) -> Vec<(String, String)> {
+    output
+        .lines()
+        .filter(|l| l.len() > 3)
+        .map(|l| {
+            let (status, path) = l.split_at(2);
+            let path = path.trim_start();
+            // Renames and copies: "R  old -> new"
+            let path = path.rsplit_once(" -> ").map(|(_, new)| new).unwrap_or(path);
+            (status.to_string(), path.trim_matches('"').to_string())
+        })
+        .collect()
+}
+
+fn content_hash(path: &Path) -> String {
+    match fs::read(path) {
+        Ok(bytes) => format!("{:x}", Sha256::digest(&bytes)),
+        Err(_) => String::new(),
+    }
+}
+
+/// Snapshot HEAD and the dirty files of a project. None outside a git repo.
+pub fn take_snapshot(project_path: &str) -> Option<WorktreeSnapshot> {
+    let root = Path::new(project_path);
+    let status = git(root, &["status", "--porcelain", "-uall"])?;
+    let head = git(root, &["rev-parse", "HEAD"]).map(|s| s.trim().to_string()).filter(|s| !s.is_empty());
+    // Writes a dangling commit object only; the working tree and stash list are untouched
+    let base = git(root, &["stash", "create"])
+        .map(|s| s.trim().to_string())
+        .filter(|s| !s.is_empty())
+        .or_else(|| head.clone());
+
+    let entries = parse_porcelain(&status)
+        .into_iter()
+        .map(|(status, path)| {
+            let hash = content_hash(&root.join(&path));
+            (path, (status, hash))
+        })
+        .collect();
+    Some(WorktreeSnapshot { head, base, entries })
+}
+
+/// Files changed between two snapshots. `committed` is the `git diff --name-status`
+/// output between the two HEADs, when HEAD moved.
+pub fn diff_snapshots(
+    before: &WorktreeSnapshot,
+    after: &WorktreeSnapshot,
+    committed: Option<&str>,
+) -> Vec<RalphIterationFile> {
+    let mut changes: BTreeMap<String, &'static str> = BTreeMap::new();
+
+    for line in committed.unwrap_or("").lines() {
+        let mut parts = line.split('\t');
+        let (Some(status), Some(first)) = (parts.next(), parts.next()) else {
+            continue;
+        };
+        let path = parts.next().unwrap_or(first);
+        let kind = match status.chars().next() {
+            Some('A') | Some('R') | Some('C') => "added",
+            Some('D') => "deleted",
+            _ => "modified",
+        };
+        changes.insert(path.to_string(), kind);
+    }
+
+    for (path, entry) in &after.entries {
+        let prior = before.entries.get(path);
+        if prior == Some(entry) {
+            continue;
+        }
+        let status = entry.0.as_str();
+        let committed_kind = changes.get(path.as_str()).copied();
+        let kind = if status.contains('D') {
+            "deleted"
+        } else if committed_kind == Some("added")
+            || (prior.is_none() && committed_kind.is_none() && (status == "??" || status.contains(['A', 'R'])))
+        {
+            "added"
+        } else {
+            "modified"
+        };
+        changes.insert(path.clone(), kind);
+    }
+
+    // Dirty before and clean after without a commit: the run reverted the change
+    for path in before.entries.keys() {
+        if !after.entries.contains_key(path) && !changes.contains_key(path) && before.head == after.head {
+            changes.insert(path.clone(), "modified");
+        }
+    }
+
+    changes
+        .into_iter()
+        .map(|(path, change)| RalphIterationFile { path, change: change.to_string() })
+        .collect()
+}
+
+/// Files changed in a project from `before` until now. Empty when git is unavailable.
+pub fn changed_between(project_path: &str, before: &WorktreeSnapshot) -> Vec<RalphIterationFile> {
+    let Some(after) = take_snapshot(project_path) else {
+        return Vec::new();
+    };
+    let committed = match (&before.head, &after.head) {
+        (Some(from), Some(to)) if from != to => git(Path::new(project_path), &["diff", "--name-status", from, to]),
+        _ => None,
+    };
+    diff_snapshots(before, &after, committed.as_deref())
+}
+
+/// Default maximum percentage of a file's lines one run may delete.
+pub const DEFAULT_MAX_DELETED_PERCENT: u32 = 50;
+
+/// Deletions smaller than this never count as destructive (small files, tiny edits).
+const MIN_DELETED_LINES: u32 = 10;
+
+/// Upper bound on the diff attached to a loop held for review.
+const MAX_REVIEW_DIFF_BYTES: usize = 50_000;
+
+/// A change that should stop a loop for human review.
+#[derive(Debug, Clone, PartialEq)]
+pub struct DestructiveChange {
+    pub path: String,
+    /// True when the file was removed entirely
+    pub removed: bool,
+    pub deleted_lines: u32,
+    /// Line count before the run (0 when unknown)
+    pub original_lines: u32,
+}
+
+impl DestructiveChange {
+    /// Short description, e.g. "src/a.rs (120 of 150 lines deleted)" or "src/b.rs (removed)".
+    pub fn describe(&self) -> String {
+        if self.removed {
+            format!("{} (removed)", self.path)
+        } else {
+            format!("{} ({} of {} lines deleted)", self.path, self.deleted_lines, self.original_lines)
+        }
+    }
+}
+
+/// (path, added, deleted) rows from `git diff --numstat`. Binary files are skipped.
+pub fn parse_numstat(output: &str) -> Vec<(String, u32, u32)> {
+    output
+        .lines()
+        .filter_map(|line| {
+            let mut parts = line.splitn(3, '\t');
+            let added = parts.next()?.parse().ok()?;
+            let deleted = parts.next()?.parse().ok()?;
+            Some((parts.next()?.to_string(), added, deleted))
+        })
+        .collect()
+}
+
+/// Removed files, plus files whose deleted lines exceed `max_percent` of their line count
+/// before the run. `current_lines` returns a file's line count now.
+pub fn destructive_changes<F>(
+    files: &[RalphIterationFile],
+    numstat: &[(String, u32, u32)],
+    max_percent: u32,
+    current_lines: F,
+) -> Vec<DestructiveChange>
+where
+    F: Fn(&str) -> Option<u32>,
+{
+    files
+        .iter()
+        .filter_map(|file| {
+            let (deleted, original) = numstat
+                .iter()
+                .find(|(path, _, _)| *path == file.path)
+                .map(|(_, added, deleted)| {
+                    let now = if file.change == "deleted" { 0 } else { current_lines(&file.path).unwrap_or(0) };
+                    (*deleted, (now + deleted).saturating_sub(*added))
+                })
+                .unwrap_or((0, 0));
+
+            let removed = file.change == "deleted";
+            let over_threshold = deleted >= MIN_DELETED_LINES
+                && original > 0
+                && u64::from(deleted) * 100 > u64::from(original) * u64::from(max_percent);
+            (removed || over_threshold).then(|| DestructiveChange {
+                path: file.path.clone(),
+                removed,
+                deleted_lines: deleted,
+                original_lines: original,
+            })
+        })
+        .collect()
+}
+
+/// Destructive changes among `files` (changed since `before`) and the diff of the flagged
+/// files against the pre-run state. None when nothing crosses the threshold.
+pub fn check_destructive(
+    project_path: &str,
+    before: &WorktreeSnapshot,
+    files: &[RalphIterationFile],
+    max_percent: u32,
+) -> Option<(Vec<DestructiveChange>, String)> {
+    let root = Path::new(project_path);
+    let paths: Vec<&str> = files.iter().map(|f| f.path.as_str()).collect();
+    if paths.is_empty() {
+        return None;
+    }
+
+    let numstat = match &before.base {
+        Some(base) => {
+            let mut args = vec!["diff", "--numstat", base.as_str(), "--"];
+            args.extend(&paths);
+            git(root, &args).map(|out| parse_numstat(&out)).unwrap_or_default()
+        }
+        None => Vec::new(),
+    };
+    let changes = destructive_changes(files, &numstat, max_percent, |path| {
+        fs::read_to_string(root.join(path)).ok().map(|c| c.lines().count() as u32)
+    });
+    if changes.is_empty() {
+        return None;
+    }
+
+    let mut diff = match &before.base {
+        Some(base) => {
+            let mut args = vec!["diff", base.as_str(), "--"];
+            args.extend(changes.iter().map(|c| c.path.as_str()));
+            git(root, &args).unwrap_or_default()
+        }
+        None => String::new(),
+    };
+    if diff.len() > MAX_REVIEW_DIFF_BYTES {
+        let mut cut = MAX_REVIEW_DIFF_BYTES;
+        while !diff.is_char_boundary(cut) {
+            cut -= 1;
+        }
+        diff.truncate(cut);
+        diff.push_str("\n[Diff truncated]\n");
+    }
+    Some((changes, diff))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn snapshot(head: &str, entries: &[(&str, &str, &str)]) -> WorktreeSnapshot {
+        WorktreeSnapshot {
+            head: Some(head.to_string()),
+            base: Some(head.to_string()),
+            entries: entries
+                .iter()
+                .map(|(p, s, h)| (p.to_string(), (s.to_string(), h.to_string())))
+                .collect(),
+        }
+    }
+
+    fn kinds(files: &[RalphIterationFile]) -> Vec<(&str, &str)> {
+        files.iter().map(|f| (f.path.as_str(), f.change.as_str())).collect()
+    }
+
+    #[test]
+    fn test_parse_porcelain() {
+        let out = " M src/a.rs\n?? new dir/b.rs\nR  old.rs -> src/moved.rs\n D gone.rs\n";
+        assert_eq!(
+            parse_porcelain(out),
+            vec![
+                (" M".to_string(), "src/a.rs".to_string()),
+                ("??".to_string(), "new dir/b.rs".to_string()),
+                ("R ".to_string(), "src/moved.rs".to_string()),
+                (" D".to_string(), "gone.rs".to_string()),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_diff_snapshots_working_tree() {
+        let before = snapshot("abc", &[("src/a.rs", " M", "h1"), ("src/same.rs", " M", "s1")]);
+        let after = snapshot(
+            "abc",
+            &[("src/a.rs", " M", "h2"), ("src/same.rs", " M", "s1"), ("src/new.rs", "??", "n1"), ("old.rs", " D", "")],
+        );
+        assert_eq!(
+            kinds(&diff_snapshots(&before, &after, None)),
+            vec![("old.rs", "deleted"), ("src/a.rs", "modified"), ("src/new.rs", "added")]
+        );
+    }
+
+    #[test]
+    fn test_diff_snapshots_with_commit() {
+        let before = snapshot("abc", &[]);
+        let after = snapshot("def", &[("README.md", " M", "r1")]);
+        let committed = "A\tsrc/new.rs\nM\tsrc/lib.rs\nD\tsrc/old.rs\nR100\tsrc/x.rs\tsrc/y.rs\n";
+        assert_eq!(
+            kinds(&diff_snapshots(&before, &after, Some(committed))),
+            vec![
+                ("README.md", "modified"),
+                ("src/lib.rs", "modified"),
+                ("src/new.rs", "added"),
+                ("src/old.rs", "deleted"),
+                ("src/y.rs", "added"),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_diff_snapshots_reverted_change() {
+        let before = snapshot("abc", &[("src/a.rs", " M", "h1")]);
+        let after = snapshot("abc", &[]);
+        assert_eq!(kinds(&diff_snapshots(&before, &after, None)), vec![("src/a.rs", "modified")]);
+    }
+
+    #[test]
+    fn test_parse_numstat() {
+        let out = "3\t120\tsrc/a.rs\n-\t-\tlogo.png\n0\t4\tdir with space/b.rs\n";
+        assert_eq!(
+            parse_numstat(out),
+            vec![("src/a.rs".to_string(), 3, 120), ("dir with space/b.rs".to_string(), 0, 4)]
+        );
+    }
+
+    #[test]
+    fn test_destructive_changes() {
+        let file = |path: &str, change: &str| RalphIterationFile { path: path.into(), change: change.into() };
+        let files = vec![
+            file("src/gutted.rs", "modified"),
+            file("src/edited.rs", "modified"),
+            file("src/tiny.rs", "modified"),
+            file("src/gone.rs", "deleted"),
+            file("src/new.rs", "added"),
+        ];
+        let numstat = vec![
+            ("src/gutted.rs".to_string(), 5, 120),
+            ("src/edited.rs".to_string(), 30, 40),
+            ("src/tiny.rs".to_string(), 0, 4),
+            ("src/gone.rs".to_string(), 0, 80),
+        ];
+        let lines = |path: &str| match path {
+            "src/gutted.rs" => Some(35),  // 150 before
+            "src/edited.rs" => Some(190), // 200 before
+            "src/tiny.rs" => Some(1),     // 5 before
+            _ => None,
+        };
+
+        let changes = destructive_changes(&files, &numstat, DEFAULT_MAX_DELETED_PERCENT, lines);
+        assert_eq!(
+            changes.iter().map(|c| c.describe()).collect::<Vec<_>>(),
+            vec!["src/gutted.rs (120 of 150 lines deleted)", "src/gone.rs (removed)"]
+        );
+        assert!(changes[1].removed);
+
+        // A stricter threshold also catches the 20% edit
+        assert_eq!(destructive_changes(&files, &numstat, 10, lines).len(), 3);
+    }
+}
@@ -10,9 +10,13 @@
 //!
 //! EXPORTS:
 //! - schema - Database schema and migrations
-//! - init_db - Initialize the database at the standard location
+//! - shared - Optional team-shared backend (libsql/Postgres) and sync
+//! - DB_PATH_ENV - Environment variable that overrides the database location (test builds only)
+//! - db_path - Database location (~/.project-jumpstart/jumpstart.db unless overridden)
+//! - init_db - Initialize the database at db_path()
+//! - run_migrations - Create missing tables and apply all migrations to a connection
 //! - AppState - Shared application state holding the DB connection and HTTP client
-//! - log_activity_db - Direct DB insert for activity logging (avoids IPC)
+//! - log_activity_db - Direct DB insert for activity logging (used by core::events)
 //!
 //! DEPENDENCIES:
 //! - rusqlite - SQLite database driver
@@ -23,29 +27,43 @@
 //! - chrono - Timestamp generation
 //!
 //! PATTERNS:
-//! - Database file location: ~/.project-jumpstart/jumpstart.db
+//! - Database file location: ~/.project-jumpstart/jumpstart.db; in test builds only,
+//!   JUMPSTART_DB_PATH (a path or a SQLite URI such as "file:name?mode=memory&cache=shared")
+//!   overrides it
+//! - Background tasks open their own connection with db_path(), never a hard-coded path
 //! - Migrations run automatically on init_db()
 //! - AppState is managed via Tauri's State<AppState>
-//! - log_activity_db is called directly by commands, not via IPC
+//! - Commands publish core::events::AppEvent; the activity log subscriber calls log_activity_db
 //!
 //! CLAUDE NOTES:
-//! - Database is local-first, no server dependency
+//! - Database is local-first; a team-shared backend (db/shared.rs) is optional and synced on demand
 //! - All timestamps stored in UTC as ISO 8601 strings
 //! - Mutex is used because rusqlite::Connection is not Send+Sync
 //! - reqwest::Client is internally Arc'd, no Mutex needed
+//! - test_runs maps running test run ids to cancel flags (see commands::test_plans::cancel_test_run)
 //! - See spec Part 6.2 for table definitions
 
 pub mod schema;
+pub mod shared;
 
 use rusqlite::Connection;
+use std::collections::HashMap;
 use std::fs;
-use std::sync::Mutex;
+use std::path::PathBuf;
+use std::sync::atomic::AtomicBool;
+use std::sync::{Arc, Mutex};
+
+use crate::models::activity::ActivityType;
 
 /// Shared application state, managed by Tauri
 pub struct AppState {
     pub db: Mutex<Connection>,
     pub http_client: reqwest::Client,
     pub watcher: Mutex<Option<crate::core::watcher::ProjectWatcher>>,
+    /// Incrementally maintained doc health for the watched project
+    pub health_cache: Mutex<Option<crate::core::health::DocHealthCache>>,
+    /// Cancel flags of in-progress test runs, by run id
+    pub test_runs: Mutex<HashMap<String, Arc<AtomicBool>>>,
 }
 
 /// Log an activity directly to the database.
@@ -54,7 +72,7 @@ pub struct AppState {
 pub fn log_activity_db(
     db: &Connection,
     project_id: &str,
-    activity_type: &str,
+    activity_type: ActivityType,
     message: &str,
 ) -> Result<(), String> {
     let id = uuid::Uuid::new_v4().to_string();
@@ -62,23 +80,36 @@ pub fn log_activity_db(
 
     db.execute(
         "INSERT INTO activities (id, project_id, activity_type, message, created_at) VALUES (?1, ?2, ?3, ?4, ?5)",
-        rusqlite::params![id, project_id, activity_type, message, created_at],
+        rusqlite::params![id, project_id, activity_type.as_str(), message, created_at],
     )
     .map_err(|e| format!("Failed to log activity: {}", e))?;
 
     Ok(())
 }
 
-/// Initialize the database at ~/.project-jumpstart/jumpstart.db
+/// Environment variable that overrides the database location (test builds only).
+#[cfg(test)]
+pub const DB_PATH_ENV: &str = "JUMPSTART_DB_PATH";
+
+/// Database location: ~/.project-jumpstart/jumpstart.db, or JUMPSTART_DB_PATH in test builds.
+pub fn db_path() -> Result<PathBuf, String> {
+    #[cfg(test)]
+    if let Some(path) = std::env::var_os(DB_PATH_ENV).filter(|p| !p.is_empty()) {
+        return Ok(PathBuf::from(path));
+    }
+    let home = dirs::home_dir().ok_or("Could not determine home directory")?;
+    Ok(home.join(".project-jumpstart").join("jumpstart.db"))
+}
+
+/// Initialize the database at db_path() (~/.project-jumpstart/jumpstart.db by default).
 /// Creates the directory and database file if they don't exist.
 /// Runs all schema migrations.
 pub fn init_db() -> Result<Connection, String> {
-    let home = dirs::home_dir().ok_or("Could not determine home directory")?;
-    let data_dir = home.join(".project-jumpstart");
-
-    fs::create_dir_all(&data_dir).map_err(|e| format!("Failed to create data directory: {}", e))?;
+    let db_path = db_path()?;
+    if let Some(data_dir) = db_path.parent() {
+        fs::create_dir_all(data_dir).map_err(|e| format!("Failed to create data directory: {}", e))?;
+    }
 
-    let db_path = data_dir.join("jumpstart.db");
     let conn =
         Connection::open(&db_path).map_err(|e| format!("Failed to open database: {}", e))?;
 
@@ -86,13 +117,54 @@ pub fn init_db() -> Result<Connection, String> {
     conn.execute_batch("PRAGMA journal_mode=WAL;")
         .map_err(|e| format!("Failed to set WAL mode: {}", e))?;
 
-    schema::create_tables(&conn).map_err(|e| format!("Failed to create tables: {}", e))?;
+    run_migrations(&conn)?;
+
+    Ok(conn)
+}
+
+/// Create missing tables and run every migration. Idempotent; also used by the doctor's
+/// "run_migrations" fix.
+pub fn run_migrations(conn: &Connection) -> Result<(), String> {
+    schema::create_tables(conn).map_err(|e| format!("Failed to create tables: {}", e))?;
 
     // Run migrations for existing databases
-    schema::migrate_add_stack_extras(&conn)
+    schema::migrate_add_stack_extras(conn)
         .map_err(|e| format!("Failed to migrate stack_extras: {}", e))?;
-    schema::migrate_add_prd_columns(&conn)
+    schema::migrate_add_prd_columns(conn)
         .map_err(|e| format!("Failed to migrate PRD columns: {}", e))?;
+    schema::migrate_add_skill_tags(conn)
+        .map_err(|e| format!("Failed to migrate skill tags: {}", e))?;
+    schema::migrate_add_injected_patterns(conn)
+        .map_err(|e| format!("Failed to migrate injected patterns: {}", e))?;
+    schema::migrate_add_loop_options(conn)
+        .map_err(|e| format!("Failed to migrate loop options: {}", e))?;
+    schema::migrate_add_review_diff(conn)
+        .map_err(|e| format!("Failed to migrate review diff: {}", e))?;
+    schema::migrate_add_learning_source(conn)
+        .map_err(|e| format!("Failed to migrate learning source: {}", e))?;
+    schema::migrate_add_learning_promotion(conn)
+        .map_err(|e| format!("Failed to migrate learning promotion: {}", e))?;
+    schema::migrate_add_snapshot_goals(conn)
+        .map_err(|e| format!("Failed to migrate snapshot goals: {}", e))?;
+    schema::migrate_add_project_location(conn)
+        .map_err(|e| format!("Failed to migrate project location: {}", e))?;
+    schema::migrate_add_tdd_phase_history(conn)
+        .map_err(|e| format!("Failed to migrate TDD phase history: {}", e))?;
+    schema::migrate_add_query_indexes(conn)
+        .map_err(|e| format!("Failed to migrate query indexes: {}", e))?;
+    schema::migrate_normalize_activity_types(conn)
+        .map_err(|e| format!("Failed to migrate activity types: {}", e))?;
+    schema::migrate_add_mistake_location(conn)
+        .map_err(|e| format!("Failed to migrate mistake location: {}", e))?;
+    schema::migrate_add_parent_loop(conn)
+        .map_err(|e| format!("Failed to migrate parent loop: {}", e))?;
+    schema::migrate_add_prd_eta(conn)
+        .map_err(|e| format!("Failed to migrate PRD ETA: {}", e))?;
+    schema::migrate_add_checkpoint_state(conn)
+        .map_err(|e| format!("Failed to migrate checkpoint state: {}", e))?;
+    schema::migrate_add_package_manager(conn)
+        .map_err(|e| format!("Failed to migrate package manager: {}", e))?;
 
-    Ok(conn)
+    schema::set_schema_version(conn).map_err(|e| format!("Failed to record schema version: {}", e))?;
+    Ok(())
 }
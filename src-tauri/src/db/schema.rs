@@ -13,6 +13,25 @@
 //! - create_tables - Creates all tables if they don't exist
 //! - migrate_add_stack_extras - Migration for stack_extras column
 //! - migrate_add_prd_columns - Migration for PRD mode columns (mode, current_story, total_stories)
+//! - migrate_add_skill_tags - Migration for skills.tags column (JSON array)
+//! - migrate_add_injected_patterns - Migration for ralph_loops.injected_patterns column (JSON array)
+//! - migrate_add_loop_options - Migration for ralph_loops.loop_options column (JSON)
+//! - migrate_add_review_diff - Migration for ralph_loops.review_diff column
+//! - migrate_add_learning_source - Migration for learnings.source and learnings.content_hash columns
+//! - migrate_add_learning_promotion - Migration for learnings promotion/verification/demotion columns
+//! - migrate_add_snapshot_goals - Migration for health_snapshots.goals_met column
+//! - migrate_add_project_location - Migration for projects.location_type/remote_host/remote_path
+//! - migrate_add_tdd_phase_history - Migration for tdd_sessions.phase_history
+//! - migrate_add_query_indexes - Composite indexes for project/plan-scoped, date-ordered list queries
+//! - QUERY_INDEXES - (name, table, columns) of every index migrate_add_query_indexes creates
+//! - migrate_normalize_activity_types - Rewrite legacy activity_type values to ActivityType names
+//! - migrate_add_mistake_location - Migration for ralph_mistakes.file_path/line
+//! - migrate_add_parent_loop - Migration for ralph_loops.parent_loop_id (follow-up chains)
+//! - migrate_add_checkpoint_state - Migration for checkpoints.context_state
+//! - migrate_add_package_manager - Migration for projects.package_manager
+//! - SCHEMA_VERSION - Version stored in PRAGMA user_version once all migrations ran
+//! - MIGRATED_COLUMNS - (table, column) added by migrations, checked by the doctor
+//! - schema_version / set_schema_version - Read and write PRAGMA user_version
 //!
 //! PATTERNS:
 //! - Uses CREATE TABLE IF NOT EXISTS for idempotent setup
@@ -24,22 +43,124 @@
 //!   ralph_loops (Phase 7), checkpoints (Phase 8), enforcement_events (Phase 9), settings,
 //!   activities (Phase 10), ralph_mistakes (for learning from loop errors),
 //!   test_plans, test_cases, test_runs, test_case_results, tdd_sessions (Test Plan Manager),
-//!   learnings (Memory Management)
+//!   learnings (Memory Management), skill_versions, agent_versions (version history),
+//!   prompt_analyses (RALPH prompt editor history), import_conflicts (shared knowledge merges),
+//!   health_snapshots (health per git commit), ralph_templates (reusable loop configurations),
+//!   ralph_iterations (per-iteration timeline of RALPH loops),
+//!   command_approvals (per-project command allowlist), api_contract_baselines (API drift),
+//!   patch_proposals (reviewable AI fixes for RALPH issues), stack_presets (user kickstart stacks),
+//!   transcript_cache (parsed session transcripts), session_reports (session analysis reports),
+//!   doc_proposals (doc header approval queue), claude_md_edits (reviewable CLAUDE.md diffs),
+//!   merge_drift (post-merge doc drift), automation_rules / rule_runs (event-driven automation),
+//!   test_plan_env (per-plan test environment), module_doc_index (FTS5 over doc headers),
+//!   policy_packs (organization enforcement requirements), ai_audit_log (opt-in AI prompt audit),
+//!   weekly_digests (per-project weekly summaries), doc_bootstraps (resumable CLAUDE.md bootstraps),
+//!   ralph_workspace_runs (PRD runs spanning several projects), conventions (CLAUDE.md rules),
+//!   health_alert_state (per-metric health alert crossings)
 //! - freshness_history stores per-file freshness snapshots for trend analysis
-//! - ralph_loops tracks RALPH loop execution with status (idle/running/paused/completed/failed)
+//! - activities.activity_type holds ActivityType names; old rows are pruned per-type by retention
+//! - ralph_loops tracks RALPH loop execution with status (idle/running/paused/needs_review/completed/failed)
+//! - ralph_loops.review_diff: diff of the destructive change that set needs_review (cleared on resume)
 //! - ralph_loops.mode: "iterative" (default, accumulated context) or "prd" (fresh context per story)
+//! - ralph_loops.loop_options: JSON LoopOptions for template-started loops (NULL = defaults)
+//! - ralph_loops.parent_loop_id: the loop a follow-up loop was seeded from (NULL for first loops)
+//! - ralph_loops.eta_at / avg_story_secs: rolling PRD finish estimate, set after each finished story
+//! - checkpoints.context_state: JSON CheckpointState (CLAUDE.md, skills, MCP servers, token
+//!   breakdown) captured at creation; NULL for checkpoints created before it existed
+//! - ralph_iterations.files_changed is a JSON array of RalphIterationFile ({path, change})
+//! - health_snapshots.degraded_files is a JSON array of relative paths that were missing or outdated
+//! - health_snapshots.goals_met: 1/0 when the project had doc goals at that commit, NULL otherwise
+//! - projects.location_type: "local" | "ssh"; ssh projects keep remote_host/remote_path and
+//!   projects.path is their local mirror
+//! - projects.package_manager: core::package_manager name ("pnpm", "uv", ...); NULL detects at use
+//! - command_approvals.status: "pending" | "approved" | "denied"; command is the normalized
+//!   (single-space joined) command line
+//! - import_conflicts keeps resolved keep_local rows so the same incoming version is not re-raised
 //! - ralph_mistakes stores mistakes and learned patterns for RALPH context enhancement
+//! - transcript_cache.messages is a JSON array of TranscriptMessage ({role, text}) parsed from the
+//!   first parsed_bytes of the transcript; prefix_hash detects rewritten files
+//! - session_reports.report is a JSON SessionAnalysis covering the first messages_analyzed messages
+//! - doc_proposals.doc is a JSON ModuleDoc; status: "pending" | "applied" | "rejected"; at most one
+//!   pending row per file_path
+//! - ralph_mistakes.file_path/line: location of an extracted issue (NULL when unlocated)
+//! - patch_proposals.status: "pending" | "conflict" | "applied" | "rejected"; mistake_id is not a
+//!   foreign key because mistakes are pruned per project
+//! - claude_md_edits.status uses the patch_proposals statuses; diff always targets CLAUDE.md
+//! - automation_rules.conditions is a JSON array of RuleCondition; action is a JSON RuleAction;
+//!   trigger_event is an AppEvent name
+//! - rule_runs.status: "started" | "ok" | "failed" | "skipped"; output holds the command output tail
+//! - test_plan_env.vars is a JSON array of TestEnvVar; secret values are "enc:<base64>"
+//! - merge_drift.header is the doc header at detection; a different current header resolves the row
+//! - module_doc_index is an FTS5 table; exports/notes hold identifiers split into words
+//!   ("TestPlanEnv" -> "test plan env") so prompts match them
+//! - policy_packs.pack is a JSON PolicyPack; a project's pack id is the "policy_pack.<project_id>" setting
+//! - weekly_digests.stats is a JSON DigestStats; week_start is Monday 00:00 UTC (RFC 3339)
+//! - ralph_workspace_runs.steps is a JSON array of WorkspaceRunStep; status: "running" | "completed" |
+//!   "failed" | "cancelled"; step loops are ordinary ralph_loops rows referenced by loop_id
+//! - doc_bootstraps.clusters is a JSON array of BootstrapCluster; stage advances
+//!   planned -> summarizing -> writing -> overview -> completed (or failed, which resumes)
+//! - conventions.kind: "naming" | "directory" | "forbidden"; sample_violations is a JSON array of
+//!   "file[:line]"; checked_at is NULL until the first check_conventions
+//! - health_alert_state.metric: "health_score" | "stale_files" | "failing_tests"; active is 1 from the
+//!   crossing that fired the alert until the metric recovers; thresholds are the
+//!   "health_alerts.<project_id>" setting, and saving them clears the project's rows
+//! - ai_audit_log rows are written only when the "ai_audit.<project_id>" setting is "1"
+//! - stack_presets.conventions/starter_skills are JSON arrays (strings / StarterSkill)
+//! - learnings.source: "local" | "imported"; content_hash is a sha256 of the normalized content
+//! - learnings.promoted_to/promoted_at are kept after demotion as provenance (demoted_at set)
 //! - test_plans: Organize test cases by feature with target coverage
 //! - test_cases: Individual test cases linked to files with type/priority/status
 //! - test_runs: Test execution history with pass/fail counts and coverage
 //! - test_case_results: Per-case results for each run
 //! - tdd_sessions: Track TDD workflow phases (red/green/refactor)
+//! - tdd_sessions.phase_history is a JSON array of TDDPhaseEvent ({phase, at}), one per phase entered
+//! - skill_versions/agent_versions: Numbered snapshots written on every create/update/rollback
 //! - See spec Part 6.2 for full table definitions
 //! - Add new tables here and call in create_tables()
+//! - Adding a migration: call it from db::run_migrations, bump SCHEMA_VERSION, and list any new
+//!   column in MIGRATED_COLUMNS
 //! - stack_extras column stores JSON for additional services (auth, hosting, payments, etc.)
 
 use rusqlite::Connection;
 
+/// Schema version written after db::run_migrations completes.
+pub const SCHEMA_VERSION: i32 = 18;
+
+/// Columns added by ALTER TABLE migrations; missing ones mean a migration did not run.
+pub const MIGRATED_COLUMNS: &[(&str, &str)] = &[
+    ("projects", "stack_extras"),
+    ("ralph_loops", "mode"),
+    ("ralph_loops", "current_story"),
+    ("ralph_loops", "total_stories"),
+    ("skills", "tags"),
+    ("ralph_loops", "injected_patterns"),
+    ("ralph_loops", "loop_options"),
+    ("ralph_loops", "review_diff"),
+    ("learnings", "source"),
+    ("learnings", "content_hash"),
+    ("learnings", "promoted_to"),
+    ("learnings", "demoted_at"),
+    ("health_snapshots", "goals_met"),
+    ("projects", "location_type"),
+    ("tdd_sessions", "phase_history"),
+    ("ralph_mistakes", "file_path"),
+    ("ralph_loops", "parent_loop_id"),
+    ("ralph_loops", "eta_at"),
+    ("ralph_loops", "avg_story_secs"),
+    ("checkpoints", "context_state"),
+    ("projects", "package_manager"),
+];
+
+/// The database's PRAGMA user_version (0 for databases created before versioning).
+pub fn schema_version(conn: &Connection) -> Result<i32, rusqlite::Error> {
+    conn.query_row("PRAGMA user_version", [], |row| row.get(0))
+}
+
+/// Record that the database is at SCHEMA_VERSION.
+pub fn set_schema_version(conn: &Connection) -> Result<(), rusqlite::Error> {
+    conn.execute_batch(&format!("PRAGMA user_version = {};", SCHEMA_VERSION))
+}
+
 /// Migrate existing database to add stack_extras column if it doesn't exist.
 /// Called after create_tables to ensure the column exists for existing databases.
 pub fn migrate_add_stack_extras(conn: &Connection) -> Result<(), rusqlite::Error> {
@@ -79,6 +200,250 @@ pub fn migrate_add_prd_columns(conn: &Connection) -> Result<(), rusqlite::Error>
     Ok(())
 }
 
+/// Migrate existing database to add the tags column to skills.
+/// Tags are stored as a JSON array of strings.
+pub fn migrate_add_skill_tags(conn: &Connection) -> Result<(), rusqlite::Error> {
+    let has_column = conn
+        .prepare("SELECT tags FROM skills LIMIT 1")
+        .is_ok();
+
+    if !has_column {
+        conn.execute("ALTER TABLE skills ADD COLUMN tags TEXT NOT NULL DEFAULT '[]'", [])?;
+    }
+    Ok(())
+}
+
+/// Migrate existing database to add injected_patterns to ralph_loops.
+/// Stores the JSON list of learned patterns prepended to the loop prompt.
+pub fn migrate_add_injected_patterns(conn: &Connection) -> Result<(), rusqlite::Error> {
+    let has_column = conn
+        .prepare("SELECT injected_patterns FROM ralph_loops LIMIT 1")
+        .is_ok();
+
+    if !has_column {
+        conn.execute("ALTER TABLE ralph_loops ADD COLUMN injected_patterns TEXT", [])?;
+    }
+    Ok(())
+}
+
+/// Migrate existing database to add loop_options to ralph_loops.
+/// Stores template execution settings (tools, gates, iterations, branch) as JSON.
+pub fn migrate_add_loop_options(conn: &Connection) -> Result<(), rusqlite::Error> {
+    let has_column = conn
+        .prepare("SELECT loop_options FROM ralph_loops LIMIT 1")
+        .is_ok();
+
+    if !has_column {
+        conn.execute("ALTER TABLE ralph_loops ADD COLUMN loop_options TEXT", [])?;
+    }
+    Ok(())
+}
+
+/// Migrate existing database to add review_diff to ralph_loops.
+/// Holds the diff shown when a loop is stopped in "needs_review".
+pub fn migrate_add_review_diff(conn: &Connection) -> Result<(), rusqlite::Error> {
+    let has_column = conn
+        .prepare("SELECT review_diff FROM ralph_loops LIMIT 1")
+        .is_ok();
+
+    if !has_column {
+        conn.execute("ALTER TABLE ralph_loops ADD COLUMN review_diff TEXT", [])?;
+    }
+    Ok(())
+}
+
+/// Migrate existing database to add file_path and line to ralph_mistakes.
+/// Set for issues the extractor located, so fixes can be proposed as patches.
+pub fn migrate_add_mistake_location(conn: &Connection) -> Result<(), rusqlite::Error> {
+    let has_column = conn
+        .prepare("SELECT file_path FROM ralph_mistakes LIMIT 1")
+        .is_ok();
+
+    if !has_column {
+        conn.execute("ALTER TABLE ralph_mistakes ADD COLUMN file_path TEXT", [])?;
+        conn.execute("ALTER TABLE ralph_mistakes ADD COLUMN line INTEGER", [])?;
+    }
+    Ok(())
+}
+
+/// Migrate existing database to add parent_loop_id to ralph_loops.
+/// Links a follow-up loop to the loop it was seeded from.
+pub fn migrate_add_parent_loop(conn: &Connection) -> Result<(), rusqlite::Error> {
+    let has_column = conn
+        .prepare("SELECT parent_loop_id FROM ralph_loops LIMIT 1")
+        .is_ok();
+
+    if !has_column {
+        conn.execute("ALTER TABLE ralph_loops ADD COLUMN parent_loop_id TEXT", [])?;
+        conn.execute(
+            "CREATE INDEX IF NOT EXISTS idx_ralph_loops_parent ON ralph_loops(parent_loop_id)",
+            [],
+        )?;
+    }
+    Ok(())
+}
+
+/// Migrate ralph_loops to add the PRD ETA columns.
+pub fn migrate_add_prd_eta(conn: &Connection) -> Result<(), rusqlite::Error> {
+    let has_column = conn.prepare("SELECT eta_at FROM ralph_loops LIMIT 1").is_ok();
+
+    if !has_column {
+        conn.execute("ALTER TABLE ralph_loops ADD COLUMN eta_at TEXT", [])?;
+        conn.execute("ALTER TABLE ralph_loops ADD COLUMN avg_story_secs INTEGER", [])?;
+    }
+    Ok(())
+}
+
+/// Migrate checkpoints to store the context state they were taken from.
+pub fn migrate_add_checkpoint_state(conn: &Connection) -> Result<(), rusqlite::Error> {
+    let has_column = conn.prepare("SELECT context_state FROM checkpoints LIMIT 1").is_ok();
+
+    if !has_column {
+        conn.execute("ALTER TABLE checkpoints ADD COLUMN context_state TEXT", [])?;
+    }
+    Ok(())
+}
+
+/// Migrate projects to store the detected package manager.
+pub fn migrate_add_package_manager(conn: &Connection) -> Result<(), rusqlite::Error> {
+    let has_column = conn.prepare("SELECT package_manager FROM projects LIMIT 1").is_ok();
+
+    if !has_column {
+        conn.execute("ALTER TABLE projects ADD COLUMN package_manager TEXT", [])?;
+    }
+    Ok(())
+}
+
+/// Migrate existing database to add source and content_hash to learnings.
+/// content_hash lets memory-file imports skip entries that were already imported.
+pub fn migrate_add_learning_source(conn: &Connection) -> Result<(), rusqlite::Error> {
+    let has_column = conn
+        .prepare("SELECT source FROM learnings LIMIT 1")
+        .is_ok();
+
+    if !has_column {
+        conn.execute("ALTER TABLE learnings ADD COLUMN source TEXT NOT NULL DEFAULT 'local'", [])?;
+        conn.execute("ALTER TABLE learnings ADD COLUMN content_hash TEXT", [])?;
+        conn.execute(
+            "CREATE INDEX IF NOT EXISTS idx_learnings_hash ON learnings(project_id, content_hash)",
+            [],
+        )?;
+    }
+    Ok(())
+}
+
+/// Migrate existing database to track where learnings were promoted and when they were verified.
+/// Adds: promoted_to, promoted_at, last_verified_at, demoted_at
+pub fn migrate_add_learning_promotion(conn: &Connection) -> Result<(), rusqlite::Error> {
+    let has_column = conn
+        .prepare("SELECT promoted_to FROM learnings LIMIT 1")
+        .is_ok();
+
+    if !has_column {
+        conn.execute("ALTER TABLE learnings ADD COLUMN promoted_to TEXT", [])?;
+        conn.execute("ALTER TABLE learnings ADD COLUMN promoted_at TEXT", [])?;
+        conn.execute("ALTER TABLE learnings ADD COLUMN last_verified_at TEXT", [])?;
+        conn.execute("ALTER TABLE learnings ADD COLUMN demoted_at TEXT", [])?;
+    }
+    Ok(())
+}
+
+/// Migrate existing database to support projects on remote hosts.
+/// Adds: location_type (default 'local'), remote_host, remote_path
+pub fn migrate_add_project_location(conn: &Connection) -> Result<(), rusqlite::Error> {
+    let has_column = conn
+        .prepare("SELECT location_type FROM projects LIMIT 1")
+        .is_ok();
+
+    if !has_column {
+        conn.execute("ALTER TABLE projects ADD COLUMN location_type TEXT NOT NULL DEFAULT 'local'", [])?;
+        conn.execute("ALTER TABLE projects ADD COLUMN remote_host TEXT", [])?;
+        conn.execute("ALTER TABLE projects ADD COLUMN remote_path TEXT", [])?;
+    }
+    Ok(())
+}
+
+/// Migrate existing database to record whether doc goals were met per health snapshot.
+/// Adds: goals_met
+pub fn migrate_add_snapshot_goals(conn: &Connection) -> Result<(), rusqlite::Error> {
+    let has_column = conn
+        .prepare("SELECT goals_met FROM health_snapshots LIMIT 1")
+        .is_ok();
+
+    if !has_column {
+        conn.execute("ALTER TABLE health_snapshots ADD COLUMN goals_met INTEGER", [])?;
+    }
+    Ok(())
+}
+
+/// Migrate existing database to record when TDD sessions enter each phase.
+/// Adds: phase_history (existing sessions start with their red entry at created_at)
+pub fn migrate_add_tdd_phase_history(conn: &Connection) -> Result<(), rusqlite::Error> {
+    let has_column = conn
+        .prepare("SELECT phase_history FROM tdd_sessions LIMIT 1")
+        .is_ok();
+
+    if !has_column {
+        conn.execute("ALTER TABLE tdd_sessions ADD COLUMN phase_history TEXT", [])?;
+        conn.execute(
+            "UPDATE tdd_sessions SET phase_history = '[{\"phase\":\"red\",\"at\":\"' || created_at || '\"}]'",
+            [],
+        )?;
+    }
+    Ok(())
+}
+
+/// Indexes for the list queries: rows scoped to a project or plan, newest first.
+/// (name, table, columns). core::query_plan reports any that are missing.
+pub const QUERY_INDEXES: &[(&str, &str, &str)] = &[
+    ("idx_ralph_mistakes_project_created", "ralph_mistakes", "project_id, created_at"),
+    ("idx_ralph_loops_project_created", "ralph_loops", "project_id, created_at"),
+    ("idx_prompt_analyses_project_created", "prompt_analyses", "project_id, created_at"),
+    ("idx_checkpoints_project_created", "checkpoints", "project_id, created_at"),
+    ("idx_enforcement_events_project_created", "enforcement_events", "project_id, created_at"),
+    ("idx_performance_reviews_project_created", "performance_reviews", "project_id, created_at"),
+    ("idx_learnings_project_created", "learnings", "project_id, created_at"),
+    ("idx_test_plans_project_updated", "test_plans", "project_id, updated_at"),
+    ("idx_test_runs_plan_started", "test_runs", "plan_id, started_at"),
+    ("idx_tdd_sessions_project_updated", "tdd_sessions", "project_id, updated_at"),
+    ("idx_import_conflicts_status_created", "import_conflicts", "status, created_at"),
+];
+
+/// Single-column indexes covered by the leading column of a QUERY_INDEXES composite.
+const SUPERSEDED_INDEXES: &[&str] = &[
+    "idx_ralph_mistakes_project",
+    "idx_prompt_analyses_project",
+    "idx_performance_reviews_project",
+    "idx_learnings_project",
+    "idx_test_plans_project",
+    "idx_test_runs_plan",
+    "idx_tdd_sessions_project",
+];
+
+/// Create the QUERY_INDEXES composites and drop the single-column indexes they replace.
+/// Idempotent; runs on every startup.
+pub fn migrate_add_query_indexes(conn: &Connection) -> Result<(), rusqlite::Error> {
+    for name in SUPERSEDED_INDEXES {
+        conn.execute(&format!("DROP INDEX IF EXISTS {}", name), [])?;
+    }
+    for (name, table, columns) in QUERY_INDEXES {
+        conn.execute(&format!("CREATE INDEX IF NOT EXISTS {} ON {}({})", name, table, columns), [])?;
+    }
+    Ok(())
+}
+
+/// Rewrite legacy free-form activity types to their ActivityType names
+/// (e.g. "tdd" -> "test", "learn" -> "memory"). Idempotent.
+pub fn migrate_normalize_activity_types(conn: &Connection) -> Result<(), rusqlite::Error> {
+    conn.execute_batch(
+        "
+        UPDATE activities SET activity_type = 'test' WHERE activity_type IN ('tdd', 'test_plan', 'test_run');
+        UPDATE activities SET activity_type = 'memory' WHERE activity_type = 'learn';
+        UPDATE activities SET activity_type = 'enforcement' WHERE activity_type = 'hooks_configured';
+        ",
+    )
+}
+
 pub fn create_tables(conn: &Connection) -> Result<(), rusqlite::Error> {
     conn.execute_batch(
         "
@@ -126,6 +491,7 @@ pub fn create_tables(conn: &Connection) -> Result<(), rusqlite::Error> {
             description     TEXT NOT NULL DEFAULT '',
             content         TEXT NOT NULL DEFAULT '',
             usage_count     INTEGER NOT NULL DEFAULT 0,
+            tags            TEXT NOT NULL DEFAULT '[]',
             created_at      TEXT NOT NULL,
             updated_at      TEXT NOT NULL,
             FOREIGN KEY (project_id) REFERENCES projects(id)
@@ -212,6 +578,8 @@ pub fn create_tables(conn: &Connection) -> Result<(), rusqlite::Error> {
             created_at      TEXT NOT NULL,
             FOREIGN KEY (project_id) REFERENCES projects(id)
         );
+        CREATE INDEX IF NOT EXISTS idx_activities_project ON activities(project_id, created_at);
+        CREATE INDEX IF NOT EXISTS idx_activities_type ON activities(activity_type, created_at);
 
         CREATE TABLE IF NOT EXISTS ralph_mistakes (
             id              TEXT PRIMARY KEY,
@@ -227,7 +595,19 @@ pub fn create_tables(conn: &Connection) -> Result<(), rusqlite::Error> {
             FOREIGN KEY (loop_id) REFERENCES ralph_loops(id)
         );
 
-        CREATE INDEX IF NOT EXISTS idx_ralph_mistakes_project ON ralph_mistakes(project_id);
+        -- Prompt analysis history (RALPH prompt editor)
+        CREATE TABLE IF NOT EXISTS prompt_analyses (
+            id              TEXT PRIMARY KEY,
+            project_id      TEXT,
+            prompt          TEXT NOT NULL,
+            quality_score   INTEGER NOT NULL DEFAULT 0,
+            criteria        TEXT NOT NULL DEFAULT '[]',
+            suggestions     TEXT NOT NULL DEFAULT '[]',
+            enhanced_prompt TEXT,
+            source          TEXT NOT NULL DEFAULT 'heuristic',
+            created_at      TEXT NOT NULL,
+            FOREIGN KEY (project_id) REFERENCES projects(id)
+        );
 
         -- Test Plan Manager tables
         CREATE TABLE IF NOT EXISTS test_plans (
@@ -305,11 +685,8 @@ pub fn create_tables(conn: &Connection) -> Result<(), rusqlite::Error> {
             FOREIGN KEY (project_id) REFERENCES projects(id)
         );
 
-        CREATE INDEX IF NOT EXISTS idx_test_plans_project ON test_plans(project_id);
         CREATE INDEX IF NOT EXISTS idx_test_cases_plan ON test_cases(plan_id);
-        CREATE INDEX IF NOT EXISTS idx_test_runs_plan ON test_runs(plan_id);
         CREATE INDEX IF NOT EXISTS idx_test_case_results_run ON test_case_results(run_id);
-        CREATE INDEX IF NOT EXISTS idx_tdd_sessions_project ON tdd_sessions(project_id);
 
         -- Team Templates table
         CREATE TABLE IF NOT EXISTS team_templates (
@@ -345,7 +722,6 @@ pub fn create_tables(conn: &Connection) -> Result<(), rusqlite::Error> {
             updated_at      TEXT NOT NULL,
             FOREIGN KEY (project_id) REFERENCES projects(id)
         );
-        CREATE INDEX IF NOT EXISTS idx_learnings_project ON learnings(project_id);
         CREATE INDEX IF NOT EXISTS idx_learnings_status ON learnings(status);
 
         -- Performance Reviews table
@@ -359,7 +735,372 @@ pub fn create_tables(conn: &Connection) -> Result<(), rusqlite::Error> {
             created_at      TEXT NOT NULL,
             FOREIGN KEY (project_id) REFERENCES projects(id)
         );
-        CREATE INDEX IF NOT EXISTS idx_performance_reviews_project ON performance_reviews(project_id);
+
+        -- Skill and agent version history
+        CREATE TABLE IF NOT EXISTS skill_versions (
+            id              TEXT PRIMARY KEY,
+            skill_id        TEXT NOT NULL,
+            version         INTEGER NOT NULL,
+            name            TEXT NOT NULL,
+            description     TEXT NOT NULL DEFAULT '',
+            content         TEXT NOT NULL DEFAULT '',
+            created_at      TEXT NOT NULL,
+            UNIQUE (skill_id, version)
+        );
+        CREATE INDEX IF NOT EXISTS idx_skill_versions_skill ON skill_versions(skill_id);
+
+        CREATE TABLE IF NOT EXISTS agent_versions (
+            id                TEXT PRIMARY KEY,
+            agent_id          TEXT NOT NULL,
+            version           INTEGER NOT NULL,
+            name              TEXT NOT NULL,
+            description       TEXT NOT NULL DEFAULT '',
+            tier              TEXT NOT NULL DEFAULT 'basic',
+            category          TEXT NOT NULL DEFAULT 'feature-development',
+            instructions      TEXT NOT NULL DEFAULT '',
+            workflow          TEXT,
+            tools             TEXT,
+            trigger_patterns  TEXT,
+            created_at        TEXT NOT NULL,
+            UNIQUE (agent_id, version)
+        );
+        CREATE INDEX IF NOT EXISTS idx_agent_versions_agent ON agent_versions(agent_id);
+
+        -- Import conflicts (shared backend sync and bundle imports)
+        CREATE TABLE IF NOT EXISTS import_conflicts (
+            id                  TEXT PRIMARY KEY,
+            entity_type         TEXT NOT NULL,
+            local_id            TEXT NOT NULL,
+            incoming_id         TEXT NOT NULL,
+            name                TEXT NOT NULL,
+            incoming_row        TEXT NOT NULL,
+            changed_fields      TEXT NOT NULL DEFAULT '[]',
+            local_updated_at    TEXT NOT NULL,
+            incoming_updated_at TEXT NOT NULL,
+            source              TEXT NOT NULL,
+            status              TEXT NOT NULL DEFAULT 'pending',
+            resolution          TEXT,
+            created_at          TEXT NOT NULL,
+            resolved_at         TEXT
+        );
+        CREATE UNIQUE INDEX IF NOT EXISTS idx_import_conflicts_pair ON import_conflicts(entity_type, local_id, incoming_id);
+
+        -- RALPH loop templates (reusable loop configurations)
+        CREATE TABLE IF NOT EXISTS ralph_templates (
+            id                TEXT PRIMARY KEY,
+            project_id        TEXT,
+            name              TEXT NOT NULL,
+            description       TEXT NOT NULL DEFAULT '',
+            prompt_skeleton   TEXT NOT NULL,
+            allowed_tools     TEXT NOT NULL DEFAULT '[]',
+            acceptance_gates  TEXT NOT NULL DEFAULT '[]',
+            max_iterations    INTEGER NOT NULL DEFAULT 5,
+            branch_strategy   TEXT NOT NULL DEFAULT 'current',
+            usage_count       INTEGER NOT NULL DEFAULT 0,
+            created_at        TEXT NOT NULL,
+            updated_at        TEXT NOT NULL,
+            FOREIGN KEY (project_id) REFERENCES projects(id)
+        );
+        CREATE INDEX IF NOT EXISTS idx_ralph_templates_project ON ralph_templates(project_id);
+
+        -- Per-iteration outcomes and changed files of RALPH loops (timeline)
+        CREATE TABLE IF NOT EXISTS ralph_iterations (
+            id                TEXT PRIMARY KEY,
+            loop_id           TEXT NOT NULL,
+            iteration         INTEGER NOT NULL,
+            story_index       INTEGER,
+            status            TEXT NOT NULL,
+            summary           TEXT NOT NULL DEFAULT '',
+            issues_count      INTEGER NOT NULL DEFAULT 0,
+            files_changed     TEXT NOT NULL DEFAULT '[]',
+            started_at        TEXT NOT NULL,
+            completed_at      TEXT NOT NULL,
+            FOREIGN KEY (loop_id) REFERENCES ralph_loops(id)
+        );
+        CREATE INDEX IF NOT EXISTS idx_ralph_iterations_loop ON ralph_iterations(loop_id, started_at);
+
+        -- Health snapshots keyed to git HEAD (regression detection)
+        CREATE TABLE IF NOT EXISTS health_snapshots (
+            id                TEXT PRIMARY KEY,
+            project_id        TEXT NOT NULL,
+            commit_sha        TEXT NOT NULL,
+            health_score      INTEGER NOT NULL DEFAULT 0,
+            doc_coverage      REAL NOT NULL DEFAULT 0,
+            avg_freshness     REAL NOT NULL DEFAULT 0,
+            total_files       INTEGER NOT NULL DEFAULT 0,
+            documented_files  INTEGER NOT NULL DEFAULT 0,
+            degraded_files    TEXT NOT NULL DEFAULT '[]',
+            created_at        TEXT NOT NULL,
+            FOREIGN KEY (project_id) REFERENCES projects(id)
+        );
+        CREATE INDEX IF NOT EXISTS idx_health_snapshots_project ON health_snapshots(project_id, created_at);
+
+        -- Per-project allowlist for commands taken from PRDs and templates
+        CREATE TABLE IF NOT EXISTS command_approvals (
+            id                TEXT PRIMARY KEY,
+            project_id        TEXT NOT NULL,
+            command           TEXT NOT NULL,
+            status            TEXT NOT NULL DEFAULT 'pending',
+            source            TEXT NOT NULL,
+            requested_at      TEXT NOT NULL,
+            decided_at        TEXT,
+            FOREIGN KEY (project_id) REFERENCES projects(id)
+        );
+        CREATE UNIQUE INDEX IF NOT EXISTS idx_command_approvals_command ON command_approvals(project_id, command);
+
+        -- Fingerprints of API schemas and handlers at the last accepted contract check
+        CREATE TABLE IF NOT EXISTS api_contract_baselines (
+            project_id        TEXT NOT NULL,
+            path              TEXT NOT NULL,
+            kind              TEXT NOT NULL,
+            fingerprint       TEXT NOT NULL,
+            doc_fingerprint   TEXT NOT NULL DEFAULT '',
+            recorded_at       TEXT NOT NULL,
+            PRIMARY KEY (project_id, path),
+            FOREIGN KEY (project_id) REFERENCES projects(id)
+        );
+
+        -- AI-proposed diffs for located RALPH issues, applied on review
+        CREATE TABLE IF NOT EXISTS patch_proposals (
+            id                TEXT PRIMARY KEY,
+            loop_id           TEXT NOT NULL,
+            project_id        TEXT NOT NULL,
+            mistake_id        TEXT,
+            file_path         TEXT NOT NULL,
+            line              INTEGER,
+            issue             TEXT NOT NULL,
+            diff              TEXT NOT NULL,
+            explanation       TEXT NOT NULL DEFAULT '',
+            status            TEXT NOT NULL DEFAULT 'pending',
+            error             TEXT,
+            created_at        TEXT NOT NULL,
+            applied_at        TEXT,
+            FOREIGN KEY (loop_id) REFERENCES ralph_loops(id),
+            FOREIGN KEY (project_id) REFERENCES projects(id)
+        );
+        CREATE INDEX IF NOT EXISTS idx_patch_proposals_loop ON patch_proposals(loop_id, created_at);
+
+        -- User-defined kickstart stack presets (built-ins live in core::stack_presets)
+        CREATE TABLE IF NOT EXISTS stack_presets (
+            id                TEXT PRIMARY KEY,
+            name              TEXT NOT NULL,
+            description       TEXT NOT NULL DEFAULT '',
+            language          TEXT NOT NULL,
+            framework         TEXT NOT NULL,
+            database_name     TEXT,
+            styling           TEXT,
+            testing           TEXT,
+            conventions       TEXT NOT NULL DEFAULT '[]',
+            directory_layout  TEXT NOT NULL DEFAULT '',
+            starter_skills    TEXT NOT NULL DEFAULT '[]',
+            created_at        TEXT NOT NULL,
+            updated_at        TEXT NOT NULL
+        );
+
+        -- Parsed session transcripts, reused until the JSONL file changes (session analysis)
+        CREATE TABLE IF NOT EXISTS transcript_cache (
+            transcript_path   TEXT PRIMARY KEY,
+            project_path      TEXT NOT NULL,
+            parsed_bytes      INTEGER NOT NULL DEFAULT 0,
+            prefix_hash       TEXT NOT NULL,
+            messages          TEXT NOT NULL DEFAULT '[]',
+            updated_at        TEXT NOT NULL
+        );
+        CREATE INDEX IF NOT EXISTS idx_transcript_cache_project ON transcript_cache(project_path);
+
+        -- Session analysis reports, extended as a session grows (incremental analysis)
+        CREATE TABLE IF NOT EXISTS session_reports (
+            session_id        TEXT PRIMARY KEY,
+            project_path      TEXT NOT NULL,
+            messages_analyzed INTEGER NOT NULL DEFAULT 0,
+            report            TEXT NOT NULL,
+            updated_at        TEXT NOT NULL
+        );
+
+        -- Queued doc header proposals awaiting approval (watcher/freshness)
+        CREATE TABLE IF NOT EXISTS doc_proposals (
+            id           TEXT PRIMARY KEY,
+            project_path TEXT NOT NULL,
+            file_path    TEXT NOT NULL,
+            reason       TEXT NOT NULL,
+            source       TEXT NOT NULL,
+            doc          TEXT NOT NULL,
+            status       TEXT NOT NULL DEFAULT 'pending',
+            created_at   TEXT NOT NULL,
+            decided_at   TEXT
+        );
+
+        CREATE INDEX IF NOT EXISTS idx_doc_proposals_project ON doc_proposals(project_path, status);
+
+        -- AI-proposed CLAUDE.md diffs for natural-language edit instructions, applied on review
+        CREATE TABLE IF NOT EXISTS claude_md_edits (
+            id           TEXT PRIMARY KEY,
+            project_id   TEXT NOT NULL,
+            instruction  TEXT NOT NULL,
+            diff         TEXT NOT NULL,
+            explanation  TEXT NOT NULL DEFAULT '',
+            status       TEXT NOT NULL DEFAULT 'pending',
+            error        TEXT,
+            created_at   TEXT NOT NULL,
+            applied_at   TEXT,
+            FOREIGN KEY (project_id) REFERENCES projects(id)
+        );
+        CREATE INDEX IF NOT EXISTS idx_claude_md_edits_project ON claude_md_edits(project_id, created_at);
+
+        -- Files whose code changed in a merge without a header update (cleared when the header changes)
+        CREATE TABLE IF NOT EXISTS merge_drift (
+            project_id   TEXT NOT NULL,
+            file_path    TEXT NOT NULL,
+            header       TEXT NOT NULL,
+            merge_commit TEXT NOT NULL,
+            detected_at  TEXT NOT NULL,
+            PRIMARY KEY (project_id, file_path)
+        );
+
+        -- User-defined automation rules: trigger event + conditions -> action
+        CREATE TABLE IF NOT EXISTS automation_rules (
+            id            TEXT PRIMARY KEY,
+            project_id    TEXT NOT NULL,
+            name          TEXT NOT NULL,
+            trigger_event TEXT NOT NULL,
+            conditions    TEXT NOT NULL DEFAULT '[]',
+            action        TEXT NOT NULL,
+            enabled       INTEGER NOT NULL DEFAULT 1,
+            fire_count    INTEGER NOT NULL DEFAULT 0,
+            last_fired_at TEXT,
+            created_at    TEXT NOT NULL,
+            FOREIGN KEY (project_id) REFERENCES projects(id)
+        );
+        CREATE INDEX IF NOT EXISTS idx_automation_rules_project ON automation_rules(project_id, trigger_event);
+
+        -- One row per rule firing, with the action's outcome
+        CREATE TABLE IF NOT EXISTS rule_runs (
+            id          TEXT PRIMARY KEY,
+            rule_id     TEXT NOT NULL,
+            project_id  TEXT NOT NULL,
+            loop_id     TEXT,
+            event       TEXT NOT NULL,
+            status      TEXT NOT NULL,
+            output      TEXT NOT NULL DEFAULT '',
+            fired_at    TEXT NOT NULL,
+            finished_at TEXT
+        );
+        CREATE INDEX IF NOT EXISTS idx_rule_runs_project ON rule_runs(project_id, fired_at);
+
+        -- Environment variables and .env file loading for a test plan's runs
+        CREATE TABLE IF NOT EXISTS test_plan_env (
+            plan_id       TEXT PRIMARY KEY,
+            vars          TEXT NOT NULL DEFAULT '[]',
+            load_env_file INTEGER NOT NULL DEFAULT 0,
+            env_file      TEXT NOT NULL DEFAULT '.env.test',
+            updated_at    TEXT NOT NULL,
+            FOREIGN KEY (plan_id) REFERENCES test_plans(id)
+        );
+
+        -- Full-text index over module doc headers (rebuilt per project by core::doc_index)
+        CREATE VIRTUAL TABLE IF NOT EXISTS module_doc_index USING fts5(
+            project_id UNINDEXED,
+            file_path,
+            module,
+            description,
+            exports,
+            notes
+        );
+
+        -- Organization policy packs (JSON PolicyPack); assignments live in settings
+        CREATE TABLE IF NOT EXISTS policy_packs (
+            id          TEXT PRIMARY KEY,
+            name        TEXT NOT NULL,
+            pack        TEXT NOT NULL,
+            imported_at TEXT NOT NULL
+        );
+
+        -- Opt-in audit of prompts/responses sent to AI providers (redacted before insert)
+        CREATE TABLE IF NOT EXISTS ai_audit_log (
+            id            TEXT PRIMARY KEY,
+            project_id    TEXT NOT NULL,
+            feature       TEXT NOT NULL,
+            provider      TEXT NOT NULL,
+            model         TEXT NOT NULL,
+            system_prompt TEXT NOT NULL,
+            prompt        TEXT NOT NULL,
+            response      TEXT,
+            error         TEXT,
+            redactions    INTEGER NOT NULL DEFAULT 0,
+            prompt_tokens INTEGER NOT NULL DEFAULT 0,
+            created_at    TEXT NOT NULL
+        );
+        CREATE INDEX IF NOT EXISTS idx_ai_audit_log_project ON ai_audit_log(project_id, created_at);
+
+        -- Per-project weekly summaries (one per Monday-to-Monday UTC week)
+        CREATE TABLE IF NOT EXISTS weekly_digests (
+            id          TEXT PRIMARY KEY,
+            project_id  TEXT NOT NULL,
+            week_start  TEXT NOT NULL,
+            week_end    TEXT NOT NULL,
+            stats       TEXT NOT NULL DEFAULT '{}',
+            markdown    TEXT NOT NULL,
+            created_at  TEXT NOT NULL,
+            UNIQUE (project_id, week_start)
+        );
+
+        -- Resumable multi-stage CLAUDE.md bootstraps (clusters/root_files/estimate are JSON)
+        CREATE TABLE IF NOT EXISTS doc_bootstraps (
+            id            TEXT PRIMARY KEY,
+            project_id    TEXT NOT NULL,
+            depth         INTEGER NOT NULL,
+            stage         TEXT NOT NULL,
+            clusters      TEXT NOT NULL DEFAULT '[]',
+            root_files    TEXT NOT NULL DEFAULT '[]',
+            estimate      TEXT NOT NULL DEFAULT '{}',
+            overview      TEXT,
+            overview_path TEXT,
+            error         TEXT,
+            created_at    TEXT NOT NULL,
+            updated_at    TEXT NOT NULL
+        );
+        CREATE INDEX IF NOT EXISTS idx_doc_bootstraps_project ON doc_bootstraps(project_id, created_at);
+
+        -- PRD runs spanning several projects (steps is a JSON array of WorkspaceRunStep)
+        CREATE TABLE IF NOT EXISTS ralph_workspace_runs (
+            id            TEXT PRIMARY KEY,
+            name          TEXT NOT NULL,
+            prd_json      TEXT NOT NULL,
+            status        TEXT NOT NULL DEFAULT 'running',
+            current_step  INTEGER NOT NULL DEFAULT 0,
+            steps         TEXT NOT NULL DEFAULT '[]',
+            outcome       TEXT,
+            started_at    TEXT NOT NULL,
+            completed_at  TEXT,
+            created_at    TEXT NOT NULL
+        );
+
+        -- Rules parsed from CLAUDE.md with their last violation check
+        CREATE TABLE IF NOT EXISTS conventions (
+            id                 TEXT PRIMARY KEY,
+            project_id         TEXT NOT NULL,
+            kind               TEXT NOT NULL,
+            rule               TEXT NOT NULL,
+            pattern            TEXT NOT NULL,
+            scope              TEXT,
+            violation_count    INTEGER NOT NULL DEFAULT 0,
+            sample_violations  TEXT NOT NULL DEFAULT '[]',
+            checked_at         TEXT,
+            created_at         TEXT NOT NULL
+        );
+        CREATE INDEX IF NOT EXISTS idx_conventions_project ON conventions(project_id);
+
+        -- Per-project health alert state (one row per metric that has fired)
+        CREATE TABLE IF NOT EXISTS health_alert_state (
+            project_id  TEXT NOT NULL,
+            metric      TEXT NOT NULL,
+            threshold   INTEGER NOT NULL,
+            value       INTEGER NOT NULL,
+            active      INTEGER NOT NULL DEFAULT 0,
+            message     TEXT NOT NULL,
+            changed_at  TEXT NOT NULL,
+            PRIMARY KEY (project_id, metric)
+        );
         ",
     )?;
 
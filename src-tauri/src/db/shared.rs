@@ -0,0 +1,665 @@
+//! @module db/shared
+//! @description Team-shared database backend (libsql/Turso or Postgres) and sync with local SQLite
+//!
+//! PURPOSE:
+//! - Describe which tables hold team-shareable knowledge (skills, agents, team templates, learnings)
+//! - Load and save the shared backend settings (URL and encrypted auth token)
+//! - Talk to a remote libsql (Hrana HTTP) or Postgres instance through one SharedBackend type
+//! - Plan pushes and pulls by updated_at (named tables also go through core::merge)
+//!
+//! DEPENDENCIES:
+//! - rusqlite - Local SQLite access and the Value type used for rows
+//! - reqwest - libsql/Turso HTTP pipeline API
+//! - tokio_postgres / postgres_native_tls - Postgres client with TLS
+//! - core::crypto - Encrypt the auth token at rest
+//! - models::shared_db - SharedBackendKind
+//!
+//! EXPORTS:
+//! - SHARED_TABLES - Tables synced with the shared backend
+//! - SharedTable - Table name and shared column list
+//! - SharedBackendSettings - Backend kind, URL, and decrypted auth token
+//! - load_settings / save_settings - Read and write the shared backend settings
+//! - load_last_sync - Time of the last successful sync
+//! - SharedBackend - Connected remote backend (libsql or Postgres)
+//! - SyncPlan / plan_sync - Decide which rows to push and which to pull
+//! - read_local_rows / apply_local_rows - Read and upsert shared rows in local SQLite
+//! - replace_local_row - Overwrite one local row regardless of updated_at
+//! - shared_table - Look up a SharedTable by name
+//! - text_at / is_newer - Row and timestamp helpers shared with core::merge
+//! - LAST_SYNC_SETTING - Settings key holding the last successful sync time
+//!
+//! PATTERNS:
+//! - Local SQLite stays the working copy; the shared backend is synced on demand
+//! - Shared rows never carry project_id: project paths are machine-local
+//! - Rows pulled for the first time arrive as global (project_id NULL); existing rows keep theirs
+//! - Upserts only overwrite when the incoming updated_at is newer (both sides)
+//!
+//! CLAUDE NOTES:
+//! - Projects, activities, loops, test runs and all file-local data are never shared
+//! - Deletions are not propagated; deleting a shared row locally only removes the local copy
+//! - Remote tables are created on first connect with the same names as the local ones
+//! - libsql URLs may be libsql://, https:// or http://; libsql:// is sent over https
+
+use std::collections::HashMap;
+
+use rusqlite::types::Value;
+use rusqlite::Connection;
+use serde_json::{json, Value as JsonValue};
+
+use crate::core::crypto;
+use crate::models::shared_db::SharedBackendKind;
+
+pub const KIND_SETTING: &str = "shared_db.kind";
+pub const URL_SETTING: &str = "shared_db.url";
+pub const TOKEN_SETTING: &str = "shared_db.auth_token";
+pub const LAST_SYNC_SETTING: &str = "shared_db.last_synced_at";
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ColumnKind {
+    Text,
+    Integer,
+}
+
+use ColumnKind::{Integer, Text};
+
+/// A table whose rows are shared with the team. `id` is always the first
+/// column; project_id is deliberately not listed.
+pub struct SharedTable {
+    pub name: &'static str,
+    pub columns: &'static [(&'static str, ColumnKind)],
+}
+
+pub const SHARED_TABLES: &[SharedTable] = &[
+    SharedTable {
+        name: "skills",
+        columns: &[
+            ("id", Text),
+            ("name", Text),
+            ("description", Text),
+            ("content", Text),
+            ("usage_count", Integer),
+            ("tags", Text),
+            ("created_at", Text),
+            ("updated_at", Text),
+        ],
+    },
+    SharedTable {
+        name: "agents",
+        columns: &[
+            ("id", Text),
+            ("name", Text),
+            ("description", Text),
+            ("tier", Text),
+            ("category", Text),
+            ("instructions", Text),
+            ("workflow", Text),
+            ("tools", Text),
+            ("trigger_patterns", Text),
+            ("usage_count", Integer),
+            ("created_at", Text),
+            ("updated_at", Text),
+        ],
+    },
+    SharedTable {
+        name: "team_templates",
+        columns: &[
+            ("id", Text),
+            ("name", Text),
+            ("description", Text),
+            ("orchestration_pattern", Text),
+            ("category", Text),
+            ("teammates", Text),
+            ("tasks", Text),
+            ("hooks", Text),
+            ("lead_spawn_instructions", Text),
+            ("usage_count", Integer),
+            ("created_at", Text),
+            ("updated_at", Text),
+        ],
+    },
+    SharedTable {
+        name: "learnings",
+        columns: &[
+            ("id", Text),
+            ("session_id", Text),
+            ("category", Text),
+            ("content", Text),
+            ("topic", Text),
+            ("confidence", Text),
+            ("status", Text),
+            ("source_file", Text),
+            ("created_at", Text),
+            ("updated_at", Text),
+        ],
+    },
+];
+
+impl SharedTable {
+    fn column_list(&self) -> String {
+        self.columns.iter().map(|(c, _)| *c).collect::<Vec<_>>().join(", ")
+    }
+
+    pub fn column_index(&self, column: &str) -> Option<usize> {
+        self.columns.iter().position(|(c, _)| *c == column)
+    }
+
+    pub fn updated_at_index(&self) -> usize {
+        self.column_index("updated_at").expect("shared tables have updated_at")
+    }
+
+    /// Upsert statement that only overwrites rows with an older updated_at.
+    /// `placeholder` renders the n-th (1-based) parameter for the dialect.
+    fn upsert_sql(&self, placeholder: fn(usize) -> String) -> String {
+        format!("{} WHERE {}.updated_at < excluded.updated_at", self.replace_sql(placeholder), self.name)
+    }
+
+    /// Upsert statement that always overwrites an existing row.
+    fn replace_sql(&self, placeholder: fn(usize) -> String) -> String {
+        let values: Vec<String> = (1..=self.columns.len()).map(placeholder).collect();
+        let updates: Vec<String> = self
+            .columns
+            .iter()
+            .skip(1)
+            .map(|(c, _)| format!("{c} = excluded.{c}"))
+            .collect();
+        format!(
+            "INSERT INTO {table} ({cols}) VALUES ({values}) ON CONFLICT (id) DO UPDATE SET {updates}",
+            table = self.name,
+            cols = self.column_list(),
+            values = values.join(", "),
+            updates = updates.join(", "),
+        )
+    }
+
+    fn create_sql(&self, integer_type: &str) -> String {
+        let cols: Vec<String> = self
+            .columns
+            .iter()
+            .map(|(c, kind)| match (*c, kind) {
+                ("id", _) => "id TEXT PRIMARY KEY".to_string(),
+                (c, Integer) => format!("{} {} NOT NULL DEFAULT 0", c, integer_type),
+                (c, Text) => format!("{} TEXT", c),
+            })
+            .collect();
+        format!("CREATE TABLE IF NOT EXISTS {} ({})", self.name, cols.join(", "))
+    }
+}
+
+fn sqlite_placeholder(n: usize) -> String {
+    format!("?{}", n)
+}
+
+fn postgres_placeholder(n: usize) -> String {
+    format!("${}", n)
+}
+
+/// Shared backend settings as stored in the settings table.
+#[derive(Debug, Clone)]
+pub struct SharedBackendSettings {
+    pub kind: SharedBackendKind,
+    pub url: Option<String>,
+    pub auth_token: Option<String>,
+}
+
+fn read_setting(db: &Connection, key: &str) -> Option<String> {
+    db.query_row("SELECT value FROM settings WHERE key = ?1", [key], |row| row.get::<_, String>(0))
+        .ok()
+        .filter(|v| !v.is_empty())
+}
+
+/// Load the shared backend settings. Missing settings mean "local".
+pub fn load_settings(db: &Connection) -> Result<SharedBackendSettings, String> {
+    let kind = read_setting(db, KIND_SETTING)
+        .and_then(|k| SharedBackendKind::parse(&k))
+        .unwrap_or(SharedBackendKind::Local);
+    let auth_token = match read_setting(db, TOKEN_SETTING) {
+        Some(v) => match v.strip_prefix("enc:") {
+            Some(enc) => Some(crypto::decrypt(enc).map_err(|e| format!("Failed to decrypt shared DB token: {}", e))?),
+            None => Some(v),
+        },
+        None => None,
+    };
+
+    Ok(SharedBackendSettings {
+        kind,
+        url: read_setting(db, URL_SETTING),
+        auth_token,
+    })
+}
+
+/// Time of the last successful sync, if any.
+pub fn load_last_sync(db: &Connection) -> Option<String> {
+    read_setting(db, LAST_SYNC_SETTING)
+}
+
+/// Save the shared backend settings. The auth token is encrypted at rest.
+pub fn save_settings(db: &Connection, settings: &SharedBackendSettings) -> Result<(), String> {
+    let token = match settings.auth_token.as_deref().filter(|t| !t.is_empty()) {
+        Some(t) => format!("enc:{}", crypto::encrypt(t).map_err(|e| format!("Failed to encrypt shared DB token: {}", e))?),
+        None => String::new(),
+    };
+
+    for (key, value) in [
+        (KIND_SETTING, settings.kind.as_str().to_string()),
+        (URL_SETTING, settings.url.clone().unwrap_or_default()),
+        (TOKEN_SETTING, token),
+    ] {
+        db.execute(
+            "INSERT OR REPLACE INTO settings (key, value) VALUES (?1, ?2)",
+            rusqlite::params![key, value],
+        )
+        .map_err(|e| format!("Failed to save shared DB settings: {}", e))?;
+    }
+    Ok(())
+}
+
+/// Read all shared rows of a table from local SQLite, in column order.
+pub fn read_local_rows(db: &Connection, table: &SharedTable) -> Result<Vec<Vec<Value>>, String> {
+    let mut stmt = db
+        .prepare(&format!("SELECT {} FROM {}", table.column_list(), table.name))
+        .map_err(|e| format!("Failed to query {}: {}", table.name, e))?;
+
+    let rows = stmt
+        .query_map([], |row| {
+            (0..table.columns.len()).map(|i| row.get::<_, Value>(i)).collect::<Result<Vec<_>, _>>()
+        })
+        .map_err(|e| format!("Failed to read {}: {}", table.name, e))?
+        .filter_map(|r| r.ok())
+        .collect();
+
+    Ok(rows)
+}
+
+/// Upsert pulled rows into local SQLite. New rows are inserted without a
+/// project (global); existing rows keep their project_id. Returns rows changed.
+pub fn apply_local_rows(db: &Connection, table: &SharedTable, rows: &[Vec<Value>]) -> Result<u32, String> {
+    let mut stmt = db
+        .prepare(&table.upsert_sql(sqlite_placeholder))
+        .map_err(|e| format!("Failed to prepare {} upsert: {}", table.name, e))?;
+
+    let mut changed = 0u32;
+    for row in rows {
+        changed += stmt
+            .execute(rusqlite::params_from_iter(row.iter()))
+            .map_err(|e| format!("Failed to store shared {} row: {}", table.name, e))? as u32;
+    }
+    Ok(changed)
+}
+
+/// Insert or overwrite one row locally regardless of updated_at (used when
+/// the user explicitly chooses the incoming version). project_id is kept.
+pub fn replace_local_row(db: &Connection, table: &SharedTable, row: &[Value]) -> Result<(), String> {
+    db.execute(&table.replace_sql(sqlite_placeholder), rusqlite::params_from_iter(row.iter()))
+        .map_err(|e| format!("Failed to store shared {} row: {}", table.name, e))?;
+    Ok(())
+}
+
+/// Look up a shared table by name.
+pub fn shared_table(name: &str) -> Option<&'static SharedTable> {
+    SHARED_TABLES.iter().find(|t| t.name == name)
+}
+
+/// Rows to send to the shared backend and rows to store locally.
+#[derive(Debug, Default)]
+pub struct SyncPlan {
+    pub push: Vec<Vec<Value>>,
+    pub pull: Vec<Vec<Value>>,
+}
+
+pub fn text_at(row: &[Value], index: usize) -> &str {
+    match row.get(index) {
+        Some(Value::Text(s)) => s,
+        _ => "",
+    }
+}
+
+/// Compare two RFC 3339 timestamps, falling back to string order.
+pub fn is_newer(a: &str, b: &str) -> bool {
+    match (chrono::DateTime::parse_from_rfc3339(a), chrono::DateTime::parse_from_rfc3339(b)) {
+        (Ok(a), Ok(b)) => a > b,
+        _ => a > b,
+    }
+}
+
+/// Last-write-wins merge keyed by id: rows only on one side go to the other,
+/// rows on both sides go from whichever has the newer updated_at.
+pub fn plan_sync(table: &SharedTable, local: Vec<Vec<Value>>, remote: Vec<Vec<Value>>) -> SyncPlan {
+    let updated = table.updated_at_index();
+    let mut remote_by_id: HashMap<String, Vec<Value>> = remote
+        .into_iter()
+        .map(|row| (text_at(&row, 0).to_string(), row))
+        .collect();
+
+    let mut plan = SyncPlan::default();
+    for row in local {
+        match remote_by_id.remove(text_at(&row, 0)) {
+            None => plan.push.push(row),
+            Some(theirs) => {
+                if is_newer(text_at(&row, updated), text_at(&theirs, updated)) {
+                    plan.push.push(row);
+                } else if is_newer(text_at(&theirs, updated), text_at(&row, updated)) {
+                    plan.pull.push(theirs);
+                }
+            }
+        }
+    }
+    plan.pull.extend(remote_by_id.into_values());
+    plan
+}
+
+/// A connected shared backend.
+pub enum SharedBackend {
+    Libsql {
+        client: reqwest::Client,
+        endpoint: String,
+        auth_token: Option<String>,
+    },
+    Postgres(tokio_postgres::Client),
+}
+
+impl SharedBackend {
+    /// Connect to the configured backend. Fails for "local".
+    pub async fn connect(settings: &SharedBackendSettings, http: &reqwest::Client) -> Result<SharedBackend, String> {
+        let url = settings
+            .url
+            .as_deref()
+            .map(str::trim)
+            .filter(|u| !u.is_empty())
+            .ok_or("No shared database URL configured")?;
+
+        match settings.kind {
+            SharedBackendKind::Local => Err("No shared database configured".to_string()),
+            SharedBackendKind::Libsql => Ok(SharedBackend::Libsql {
+                client: http.clone(),
+                endpoint: libsql_endpoint(url)?,
+                auth_token: settings.auth_token.clone(),
+            }),
+            SharedBackendKind::Postgres => {
+                let connector = native_tls::TlsConnector::new()
+                    .map_err(|e| format!("Failed to initialize TLS: {}", e))?;
+                let tls = postgres_native_tls::MakeTlsConnector::new(connector);
+                let (client, connection) = tokio_postgres::connect(url, tls)
+                    .await
+                    .map_err(|e| format!("Failed to connect to Postgres: {}", e))?;
+                tokio::spawn(async move {
+                    if let Err(e) = connection.await {
+                        tracing::error!(error = %e, "Shared Postgres connection error");
+                    }
+                });
+                Ok(SharedBackend::Postgres(client))
+            }
+        }
+    }
+
+    /// Create the shared tables on the backend if they do not exist.
+    pub async fn ensure_schema(&self) -> Result<(), String> {
+        match self {
+            SharedBackend::Libsql { .. } => {
+                let stmts = SHARED_TABLES.iter().map(|t| (t.create_sql("INTEGER"), Vec::new())).collect();
+                self.libsql_pipeline(stmts).await.map(|_| ())
+            }
+            SharedBackend::Postgres(client) => {
+                let sql: Vec<String> = SHARED_TABLES.iter().map(|t| t.create_sql("BIGINT")).collect();
+                client
+                    .batch_execute(&sql.join(";\n"))
+                    .await
+                    .map_err(|e| format!("Failed to create shared tables: {}", e))
+            }
+        }
+    }
+
+    /// Fetch every row of a shared table, in column order.
+    pub async fn fetch_rows(&self, table: &SharedTable) -> Result<Vec<Vec<Value>>, String> {
+        let sql = format!("SELECT {} FROM {}", table.column_list(), table.name);
+        match self {
+            SharedBackend::Libsql { .. } => {
+                let mut results = self.libsql_pipeline(vec![(sql, Vec::new())]).await?;
+                Ok(results.pop().unwrap_or_default())
+            }
+            SharedBackend::Postgres(client) => {
+                let rows = client
+                    .query(sql.as_str(), &[])
+                    .await
+                    .map_err(|e| format!("Failed to read shared {}: {}", table.name, e))?;
+                rows.iter()
+                    .map(|row| {
+                        table
+                            .columns
+                            .iter()
+                            .enumerate()
+                            .map(|(i, (_, kind))| {
+                                let value = match kind {
+                                    Integer => row.try_get::<_, Option<i64>>(i)?.map(Value::Integer),
+                                    Text => row.try_get::<_, Option<String>>(i)?.map(Value::Text),
+                                };
+                                Ok(value.unwrap_or(Value::Null))
+                            })
+                            .collect::<Result<Vec<_>, tokio_postgres::Error>>()
+                    })
+                    .collect::<Result<Vec<_>, _>>()
+                    .map_err(|e| format!("Failed to decode shared {}: {}", table.name, e))
+            }
+        }
+    }
+
+    /// Upsert rows into a shared table (newer updated_at wins).
+    pub async fn upsert_rows(&self, table: &SharedTable, rows: Vec<Vec<Value>>) -> Result<(), String> {
+        if rows.is_empty() {
+            return Ok(());
+        }
+        match self {
+            SharedBackend::Libsql { .. } => {
+                let sql = table.upsert_sql(sqlite_placeholder);
+                let stmts = rows.into_iter().map(|row| (sql.clone(), row)).collect();
+                self.libsql_pipeline(stmts).await.map(|_| ())
+            }
+            SharedBackend::Postgres(client) => {
+                let stmt = client
+                    .prepare(&table.upsert_sql(postgres_placeholder))
+                    .await
+                    .map_err(|e| format!("Failed to prepare shared {} upsert: {}", table.name, e))?;
+                for row in rows {
+                    let params = postgres_params(table, row);
+                    let refs: Vec<&(dyn tokio_postgres::types::ToSql + Sync)> =
+                        params.iter().map(|p| p.as_ref() as &(dyn tokio_postgres::types::ToSql + Sync)).collect();
+                    client
+                        .execute(&stmt, &refs)
+                        .await
+                        .map_err(|e| format!("Failed to write shared {} row: {}", table.name, e))?;
+                }
+                Ok(())
+            }
+        }
+    }
+
+    /// Run statements through the libsql Hrana-over-HTTP pipeline in one
+    /// request. Returns the result rows of each statement.
+    async fn libsql_pipeline(&self, stmts: Vec<(String, Vec<Value>)>) -> Result<Vec<Vec<Vec<Value>>>, String> {
+        let SharedBackend::Libsql { client, endpoint, auth_token } = self else {
+            return Err("Not a libsql backend".to_string());
+        };
+
+        let mut requests: Vec<JsonValue> = stmts
+            .iter()
+            .map(|(sql, args)| {
+                json!({
+                    "type": "execute",
+                    "stmt": { "sql": sql, "args": args.iter().map(libsql_arg).collect::<Vec<_>>() }
+                })
+            })
+            .collect();
+        requests.push(json!({ "type": "close" }));
+
+        let mut request = client.post(endpoint).json(&json!({ "requests": requests }));
+        if let Some(token) = auth_token {
+            request = request.bearer_auth(token);
+        }
+        let response = request
+            .send()
+            .await
+            .map_err(|e| format!("Failed to reach shared database: {}", e))?;
+        let status = response.status();
+        let body: JsonValue = response
+            .json()
+            .await
+            .map_err(|e| format!("Invalid response from shared database: {}", e))?;
+        if !status.is_success() {
+            return Err(format!("Shared database returned {}: {}", status, body));
+        }
+
+        let results = body["results"].as_array().ok_or("Shared database response has no results")?;
+        results
+            .iter()
+            .take(stmts.len())
+            .map(|result| {
+                if result["type"] != "ok" {
+                    return Err(format!(
+                        "Shared database error: {}",
+                        result["error"]["message"].as_str().unwrap_or("unknown error")
+                    ));
+                }
+                let rows = result["response"]["result"]["rows"].as_array().cloned().unwrap_or_default();
+                Ok(rows
+                    .iter()
+                    .map(|row| row.as_array().map(|cells| cells.iter().map(libsql_cell).collect()).unwrap_or_default())
+                    .collect())
+            })
+            .collect()
+    }
+}
+
+/// Map a configured libsql URL to its Hrana HTTP pipeline endpoint.
+fn libsql_endpoint(url: &str) -> Result<String, String> {
+    let base = if let Some(rest) = url.strip_prefix("libsql://") {
+        format!("https://{}", rest)
+    } else if url.starts_with("https://") || url.starts_with("http://") {
+        url.to_string()
+    } else {
+        return Err("libsql URL must start with libsql://, https:// or http://".to_string());
+    };
+    Ok(format!("{}/v2/pipeline", base.trim_end_matches('/')))
+}
+
+fn libsql_arg(value: &Value) -> JsonValue {
+    match value {
+        Value::Null => json!({ "type": "null" }),
+        Value::Integer(i) => json!({ "type": "integer", "value": i.to_string() }),
+        Value::Real(f) => json!({ "type": "float", "value": f }),
+        Value::Text(s) => json!({ "type": "text", "value": s }),
+        Value::Blob(b) => {
+            use base64::Engine;
+            json!({ "type": "blob", "base64": base64::engine::general_purpose::STANDARD.encode(b) })
+        }
+    }
+}
+
+fn libsql_cell(cell: &JsonValue) -> Value {
+    match cell["type"].as_str() {
+        Some("integer") => cell["value"]
+            .as_str()
+            .and_then(|v| v.parse().ok())
+            .map(Value::Integer)
+            .unwrap_or(Value::Null),
+        Some("float") => cell["value"].as_f64().map(Value::Real).unwrap_or(Value::Null),
+        Some("text") => cell["value"].as_str().map(|s| Value::Text(s.to_string())).unwrap_or(Value::Null),
+        _ => Value::Null,
+    }
+}
+
+type PgParam = Box<dyn tokio_postgres::types::ToSql + Sync + Send>;
+
+fn postgres_params(table: &SharedTable, row: Vec<Value>) -> Vec<PgParam> {
+    row.into_iter()
+        .zip(table.columns.iter())
+        .map(|(value, (_, kind))| -> PgParam {
+            match (value, kind) {
+                (Value::Integer(i), _) => Box::new(i),
+                (Value::Text(s), _) => Box::new(s),
+                (_, Integer) => Box::new(None::<i64>),
+                (_, Text) => Box::new(None::<String>),
+            }
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_support::with_project;
+
+    fn skill_row(id: &str, name: &str, updated_at: &str) -> Vec<Value> {
+        vec![
+            Value::Text(id.into()),
+            Value::Text(name.into()),
+            Value::Text(String::new()),
+            Value::Text("content".into()),
+            Value::Integer(0),
+            Value::Text("[]".into()),
+            Value::Text("2025-01-01T00:00:00Z".into()),
+            Value::Text(updated_at.into()),
+        ]
+    }
+
+    #[test]
+    fn test_plan_sync_last_write_wins() {
+        let skills = &SHARED_TABLES[0];
+        let local = vec![
+            skill_row("a", "local only", "2025-01-01T00:00:00Z"),
+            skill_row("b", "local newer", "2025-03-01T00:00:00Z"),
+            skill_row("c", "local older", "2025-01-01T00:00:00Z"),
+            skill_row("d", "same", "2025-01-01T00:00:00Z"),
+        ];
+        let remote = vec![
+            skill_row("b", "remote older", "2025-02-01T00:00:00+00:00"),
+            skill_row("c", "remote newer", "2025-02-01T00:00:00+00:00"),
+            skill_row("d", "same", "2025-01-01T00:00:00+00:00"),
+            skill_row("e", "remote only", "2025-01-01T00:00:00Z"),
+        ];
+
+        let plan = plan_sync(skills, local, remote);
+        let mut pushed: Vec<&str> = plan.push.iter().map(|r| text_at(r, 0)).collect();
+        let mut pulled: Vec<&str> = plan.pull.iter().map(|r| text_at(r, 0)).collect();
+        pushed.sort();
+        pulled.sort();
+        assert_eq!(pushed, vec!["a", "b"]);
+        assert_eq!(pulled, vec!["c", "e"]);
+    }
+
+    #[test]
+    fn test_apply_local_rows_keeps_project_and_skips_older() {
+        let conn = with_project();
+        conn.execute(
+            "INSERT INTO skills (id, project_id, name, description, content, usage_count, tags, created_at, updated_at)
+             VALUES ('s1', 'p1', 'Mine', '', 'x', 3, '[]', '2025-01-01T00:00:00Z', '2025-02-01T00:00:00Z')",
+            [],
+        )
+        .unwrap();
+        let skills = &SHARED_TABLES[0];
+
+        let rows = vec![
+            skill_row("s1", "Stale", "2025-01-15T00:00:00Z"),
+            skill_row("s2", "From team", "2025-01-15T00:00:00Z"),
+        ];
+        assert_eq!(apply_local_rows(&conn, skills, &rows).unwrap(), 1);
+
+        let (name, project): (String, Option<String>) = conn
+            .query_row("SELECT name, project_id FROM skills WHERE id = 's1'", [], |r| Ok((r.get(0)?, r.get(1)?)))
+            .unwrap();
+        assert_eq!((name.as_str(), project.as_deref()), ("Mine", Some("p1")));
+
+        let project: Option<String> = conn
+            .query_row("SELECT project_id FROM skills WHERE id = 's2'", [], |r| r.get(0))
+            .unwrap();
+        assert_eq!(project, None);
+
+        assert_eq!(apply_local_rows(&conn, skills, &[skill_row("s1", "Team edit", "2025-03-01T00:00:00Z")]).unwrap(), 1);
+        assert_eq!(read_local_rows(&conn, skills).unwrap().len(), 2);
+    }
+
+    #[test]
+    fn test_libsql_endpoint() {
+        assert_eq!(libsql_endpoint("libsql://team.turso.io").unwrap(), "https://team.turso.io/v2/pipeline");
+        assert_eq!(libsql_endpoint("http://localhost:8080/").unwrap(), "http://localhost:8080/v2/pipeline");
+        assert!(libsql_endpoint("postgres://x").is_err());
+    }
+}
@@ -15,6 +15,7 @@
 //! - core - Business logic modules (scanner, generator, health, analyzer, freshness, test_runner)
 //! - models - Data structures
 //! - db - Database layer and AppState
+//! - test_support - Integration test harness (test builds only)
 //!
 //! EXPORTS:
 //! - run - Main application entry point
@@ -28,75 +29,174 @@
 //! - Add new command modules to both mod declarations and invoke_handler
 //! - The run function is called from main.rs (desktop) and mobile entry points
 //! - Database is initialized before the app starts via .setup()
+//! - Logging (core::logging) is installed first in run() so setup failures are captured
+//! - core::control serves the jumpstart-cli companion (src/bin/jumpstart-cli.rs) once the app is set up
 //! - Dialog plugin enables native folder picker for onboarding
+//! - Monitor windows ("monitor-*") are created at runtime by commands::windows
+//! - Command-level integration tests use test_support::TestEnv (mock app, in-memory DB, fake API)
 
 mod commands;
 mod core;
 mod db;
 mod models;
+#[cfg(test)]
+mod test_support;
 
+use std::collections::HashMap;
 use std::sync::Mutex;
 
 use tauri::Manager;
 
-use commands::activity::{get_recent_activities, log_activity};
-use commands::claude_md::{generate_claude_md, get_health_score, read_claude_md, write_claude_md};
-use commands::context::{create_checkpoint, get_context_health, get_mcp_status, list_checkpoints};
-use commands::freshness::{check_freshness, get_stale_files};
-use commands::modules::{apply_module_doc, batch_generate_docs, generate_module_doc, parse_module_doc, scan_modules};
+use commands::activity::{
+    get_activity_retention, get_recent_activities, log_activity, prune_activities, set_activity_retention,
+};
+use commands::claude_md::{
+    generate_claude_md, generate_schema_overview, get_health_score, read_claude_md, write_claude_md,
+    apply_claude_md_edit, list_claude_md_edits, propose_claude_md_edit, reject_claude_md_edit,
+};
+use commands::context::{
+    add_jumpstart_mcp_server, add_mcp_server_to_project, compare_checkpoint_to_current, create_checkpoint,
+    get_context_health, get_mcp_status, list_checkpoints, list_mcp_catalog, remove_mcp_server,
+};
+use commands::data_purge::{purge_ai_content, purge_project_data};
+use commands::doc_proposals::{apply_doc_proposals, list_doc_proposals, queue_doc_proposals};
+use commands::git_policy::{answer_git_permission, get_git_policy, save_git_policy};
+use commands::ai_queue::{
+    get_ai_queue, pause_ai_queue, resume_ai_queue, set_ai_job_priority, set_ai_queue_concurrency,
+    get_ai_token_limits, set_ai_token_limits,
+};
+use commands::logs::get_app_logs;
+use commands::doctor::{run_doctor, apply_doctor_fix};
+use commands::claude_backups::{list_claude_dir_backups, restore_claude_dir_backup};
+use commands::plugins::{list_analyzer_plugins, reload_analyzer_plugins};
+use commands::sync::{get_sync_config, run_sync, set_sync_config};
+use commands::ai_audit::{export_ai_audit_log, get_ai_audit_enabled, get_ai_audit_log, set_ai_audit_enabled};
+use commands::digest::{generate_weekly_digest, list_weekly_digests};
+use commands::bootstrap::{bootstrap_project_docs, get_project_bootstrap, plan_project_bootstrap};
+use commands::pr_health::generate_pr_health_comment;
+use commands::git_forge::{detect_forge, get_forge_config, save_forge_config};
+use commands::ralph_workspace::{cancel_workspace_run, get_workspace_run, list_workspace_runs, start_workspace_prd};
+use commands::conventions::{check_conventions, extract_conventions, list_conventions};
+use commands::health_alerts::{get_health_alert_thresholds, list_health_alerts, save_health_alert_thresholds};
+use commands::policy::{
+    import_policy_pack, list_policy_packs, delete_policy_pack, get_project_policy_pack, apply_policy_pack,
+    audit_policy_compliance,
+};
+use commands::rules::{
+    create_rule, delete_rule, list_rule_runs, list_rules, set_rule_enabled, simulate_rule,
+};
+use commands::freshness::{
+    accept_api_contracts, check_api_contracts, check_freshness, explain_freshness, get_freshness_policies,
+    get_stale_files, post_merge_scan, save_freshness_policies, validate_doc_dependencies,
+};
+use commands::modules::{
+    apply_module_doc, auto_sync_exports, batch_generate_docs, generate_module_doc, get_ai_excludes,
+    get_generated_overrides, parse_module_doc, scan_modules, set_ai_excludes, set_generated_overrides,
+};
 use commands::onboarding::{check_git_installed, install_git, save_project, scan_project};
-use commands::project::{get_project, list_projects, remove_project};
+use commands::project::{get_project, list_projects, merge_projects, remove_project, update_project_path};
 use commands::ralph::{
     analyze_ralph_prompt, analyze_ralph_prompt_with_ai, kill_ralph_loop, list_ralph_loops,
     list_ralph_mistakes, pause_ralph_loop, resume_ralph_loop, start_ralph_loop, start_ralph_loop_prd,
-    get_ralph_context, record_ralph_mistake, update_claude_md_with_pattern,
+    get_ralph_context, record_ralph_mistake, update_claude_md_with_pattern, generate_changelog,
+    list_prompt_analyses, get_ralph_iterations, get_prompt_criteria, save_prompt_criteria,
+    check_ralph_prerequisites, create_followup_loop, get_ralph_loop_chain, suggest_relevant_files,
+    retry_failed_prd_stories, quick_analyze_ralph_prompt,
 };
 use commands::enforcement::{
-    check_hooks_configured, get_ci_snippets, get_enforcement_events, get_hook_health, get_hook_status, init_git, install_git_hooks, reset_hook_health,
+    check_hooks_configured, get_ci_snippets, get_enforcement_events, get_hook_health, get_hook_key_status, get_hook_status, init_git, install_git_hooks, reset_hook_health,
+    rotate_hook_api_key, sync_ci_enforcement, uninstall_git_hooks, install_merge_drift_hook,
+};
+use commands::imports::{import_knowledge_bundle, list_import_conflicts, resolve_import_conflicts};
+use commands::slash_commands::{check_slash_command_drift, deploy_slash_commands};
+use commands::health_history::{find_health_regressions, get_doc_goals, list_health_snapshots, save_doc_goals};
+use commands::ralph_templates::{
+    create_ralph_template, delete_ralph_template, list_ralph_templates, start_ralph_loop_from_template,
+    update_ralph_template,
+};
+use commands::command_guard::{list_command_approvals, remove_command_approval, set_command_approval};
+use commands::ralph_patches::{
+    apply_patch_proposal, list_patch_proposals, propose_issue_fixes, reject_patch_proposal,
 };
+use commands::readme::generate_readme;
+use commands::remote::{
+    get_remote_hook_status, install_remote_git_hooks, prepare_remote_project, sync_remote_project,
+};
+use commands::shared_db::{get_shared_db_config, set_shared_db_config, sync_shared_db, test_shared_db_connection};
 use commands::settings::{get_all_settings, get_setting, save_setting, validate_api_key};
-use commands::watcher::{start_file_watcher, stop_file_watcher};
+use commands::versions::{diff_versions, list_versions, rollback_to_version};
+use commands::watcher::{get_watcher_status, start_file_watcher, stop_file_watcher};
+use commands::windows::{close_monitor_window, list_monitor_windows, open_monitor_window};
 use commands::skills::{
-    create_skill, delete_skill, detect_patterns, increment_skill_usage, list_skills, update_skill,
+    bulk_delete_skills, bulk_update_skills, create_skill, delete_skill, detect_patterns,
+    detect_skill_conflicts, increment_skill_usage, list_skills, update_skill,
 };
 use commands::agents::{
-    create_agent, delete_agent, enhance_agent_instructions, increment_agent_usage, list_agents, update_agent,
+    create_agent, delete_agent, enhance_agent_instructions, increment_agent_usage, lint_subagent_configs, list_agents,
+    update_agent,
+};
+use commands::kickstart::{
+    create_stack_preset, delete_stack_preset, generate_kickstart_claude_md, generate_kickstart_prompt,
+    infer_tech_stack, list_stack_presets, update_stack_preset,
 };
-use commands::kickstart::{generate_kickstart_prompt, generate_kickstart_claude_md, infer_tech_stack};
 use commands::test_plans::{
     list_test_plans, get_test_plan, create_test_plan, update_test_plan, delete_test_plan,
+    export_test_plan, import_test_plan,
     list_test_cases, create_test_case, update_test_case, delete_test_case,
     detect_project_test_framework, run_test_plan, get_test_runs, generate_test_suggestions,
-    create_tdd_session, update_tdd_session, get_tdd_session, list_tdd_sessions,
+    get_test_plan_env, save_test_plan_env, dry_run_test_command,
+    cancel_test_run, get_test_plan_timeout, set_test_plan_timeout,
+    create_tdd_session, update_tdd_session, get_tdd_session, list_tdd_sessions, get_tdd_analytics,
     check_test_staleness, generate_subagent_config, generate_hooks_config,
     count_project_tests,
 };
-use commands::session_analysis::{analyze_session, get_session_transcript};
+use commands::session_analysis::{
+    analyze_session, analyze_session_incremental, extract_plan_prd, get_session_transcript,
+};
 use commands::team_templates::{
     list_team_templates, create_team_template, update_team_template, delete_team_template,
-    increment_team_template_usage, generate_team_deploy_output,
+    increment_team_template_usage, generate_team_deploy_output, validate_team_template_variables,
 };
 use commands::memory::{
     list_memory_sources, list_learnings, update_learning_status, analyze_claude_md,
-    get_memory_health, promote_learning, append_to_project_file,
+    get_memory_health, promote_learning, append_to_project_file, import_memory_learnings,
+    review_stale_learnings, demote_learning,
 };
 use commands::performance::{
     analyze_performance, list_performance_reviews, get_performance_review, delete_performance_review,
-    remediate_performance_file,
+    remediate_performance_file, analyze_db_performance,
 };
 
 #[cfg_attr(mobile, tauri::mobile_entry_point)]
 pub fn run() {
+    crate::core::logging::init();
     tauri::Builder::default()
         .plugin(tauri_plugin_opener::init())
         .plugin(tauri_plugin_dialog::init())
         .setup(|app| {
+            crate::core::doctor::mark_app_started();
             let conn = db::init_db().expect("Failed to initialize database");
+            // Re-export an expired hook key, or remove it if no hook needs it
+            if let Err(e) = commands::enforcement::refresh_exported_hook_key(&conn, false) {
+                tracing::warn!(error = %e, "Failed to refresh exported hook key");
+            }
+            commands::activity::spawn_activity_pruner();
+            crate::core::ai_queue::load_settings(&conn);
+            crate::core::ai::load_token_limits(&conn);
+            crate::core::plugins::load();
+            let http_client = reqwest::Client::new();
+            crate::core::events::install_app_subscribers(app.handle(), http_client.clone());
+            commands::digest::spawn_digest_scheduler();
             app.manage(db::AppState {
                 db: Mutex::new(conn),
-                http_client: reqwest::Client::new(),
+                http_client,
                 watcher: Mutex::new(None),
+                health_cache: Mutex::new(None),
+                test_runs: Mutex::new(HashMap::new()),
             });
+            if let Err(e) = crate::core::control::start(app.handle()) {
+                tracing::warn!(error = %e, "Control channel for jumpstart-cli is unavailable");
+            }
             Ok(())
         })
         .invoke_handler(tauri::generate_handler![
@@ -107,24 +207,47 @@ pub fn run() {
             list_projects,
             get_project,
             remove_project,
+            update_project_path,
+            merge_projects,
             read_claude_md,
             write_claude_md,
             generate_claude_md,
+            generate_schema_overview,
+            propose_claude_md_edit,
+            list_claude_md_edits,
+            apply_claude_md_edit,
+            reject_claude_md_edit,
             get_health_score,
             scan_modules,
             parse_module_doc,
             generate_module_doc,
             apply_module_doc,
             batch_generate_docs,
+            auto_sync_exports,
+            get_generated_overrides,
+            set_generated_overrides,
+            get_ai_excludes,
+            set_ai_excludes,
             check_freshness,
             get_stale_files,
+            check_api_contracts,
+            accept_api_contracts,
+            validate_doc_dependencies,
+            post_merge_scan,
+            explain_freshness,
+            get_freshness_policies,
+            save_freshness_policies,
             list_skills,
             create_skill,
             update_skill,
             delete_skill,
             detect_patterns,
             increment_skill_usage,
+            bulk_update_skills,
+            bulk_delete_skills,
+            detect_skill_conflicts,
             list_agents,
+            lint_subagent_configs,
             create_agent,
             update_agent,
             delete_agent,
@@ -132,42 +255,166 @@ pub fn run() {
             enhance_agent_instructions,
             analyze_ralph_prompt,
             analyze_ralph_prompt_with_ai,
+            quick_analyze_ralph_prompt,
+            suggest_relevant_files,
+            list_prompt_analyses,
+            get_prompt_criteria,
+            save_prompt_criteria,
+            check_ralph_prerequisites,
             start_ralph_loop,
             start_ralph_loop_prd,
             pause_ralph_loop,
             resume_ralph_loop,
             kill_ralph_loop,
             list_ralph_loops,
+            create_followup_loop,
+            retry_failed_prd_stories,
+            get_ralph_loop_chain,
             list_ralph_mistakes,
+            get_ralph_iterations,
             get_ralph_context,
             record_ralph_mistake,
             update_claude_md_with_pattern,
+            generate_changelog,
             get_context_health,
             get_mcp_status,
             create_checkpoint,
             list_checkpoints,
+            compare_checkpoint_to_current,
+            list_mcp_catalog,
+            add_mcp_server_to_project,
+            remove_mcp_server,
+            add_jumpstart_mcp_server,
             install_git_hooks,
             init_git,
             get_hook_status,
             check_hooks_configured,
             get_enforcement_events,
+            sync_ci_enforcement,
             get_ci_snippets,
             get_hook_health,
             reset_hook_health,
+            uninstall_git_hooks,
+            install_merge_drift_hook,
+            rotate_hook_api_key,
+            get_hook_key_status,
             get_setting,
             save_setting,
             get_all_settings,
             validate_api_key,
             log_activity,
             get_recent_activities,
+            get_activity_retention,
+            set_activity_retention,
+            prune_activities,
             start_file_watcher,
             stop_file_watcher,
+            get_watcher_status,
+            open_monitor_window,
+            close_monitor_window,
+            list_monitor_windows,
+            get_shared_db_config,
+            set_shared_db_config,
+            test_shared_db_connection,
+            sync_shared_db,
+            import_knowledge_bundle,
+            list_import_conflicts,
+            resolve_import_conflicts,
+            deploy_slash_commands,
+            check_slash_command_drift,
+            list_health_snapshots,
+            find_health_regressions,
+            get_doc_goals,
+            save_doc_goals,
+            list_ralph_templates,
+            create_ralph_template,
+            update_ralph_template,
+            delete_ralph_template,
+            start_ralph_loop_from_template,
+            propose_issue_fixes,
+            list_patch_proposals,
+            apply_patch_proposal,
+            reject_patch_proposal,
+            purge_project_data,
+            purge_ai_content,
+            queue_doc_proposals,
+            list_doc_proposals,
+            apply_doc_proposals,
+            get_git_policy,
+            save_git_policy,
+            answer_git_permission,
+            get_ai_queue,
+            pause_ai_queue,
+            resume_ai_queue,
+            get_ai_token_limits,
+            set_ai_token_limits,
+            set_ai_queue_concurrency,
+            set_ai_job_priority,
+            list_rules,
+            create_rule,
+            simulate_rule,
+            set_rule_enabled,
+            delete_rule,
+            list_rule_runs,
+            get_app_logs,
+            run_doctor,
+            apply_doctor_fix,
+            import_policy_pack,
+            list_policy_packs,
+            delete_policy_pack,
+            get_project_policy_pack,
+            apply_policy_pack,
+            audit_policy_compliance,
+            list_claude_dir_backups,
+            restore_claude_dir_backup,
+            list_analyzer_plugins,
+            reload_analyzer_plugins,
+            get_sync_config,
+            set_sync_config,
+            run_sync,
+            get_ai_audit_enabled,
+            set_ai_audit_enabled,
+            get_ai_audit_log,
+            export_ai_audit_log,
+            generate_weekly_digest,
+            list_weekly_digests,
+            plan_project_bootstrap,
+            bootstrap_project_docs,
+            get_project_bootstrap,
+            generate_pr_health_comment,
+            get_forge_config,
+            save_forge_config,
+            detect_forge,
+            start_workspace_prd,
+            get_workspace_run,
+            list_workspace_runs,
+            cancel_workspace_run,
+            extract_conventions,
+            list_conventions,
+            check_conventions,
+            get_health_alert_thresholds,
+            save_health_alert_thresholds,
+            list_health_alerts,
+            list_command_approvals,
+            set_command_approval,
+            remove_command_approval,
+            generate_readme,
+            prepare_remote_project,
+            sync_remote_project,
+            install_remote_git_hooks,
+            get_remote_hook_status,
             generate_kickstart_prompt,
             generate_kickstart_claude_md,
             infer_tech_stack,
+            list_stack_presets,
+            create_stack_preset,
+            update_stack_preset,
+            delete_stack_preset,
             // Test Plan Manager commands
             list_test_plans,
             get_test_plan,
+            export_test_plan,
+            import_test_plan,
             create_test_plan,
             update_test_plan,
             delete_test_plan,
@@ -177,18 +424,27 @@ pub fn run() {
             delete_test_case,
             detect_project_test_framework,
             run_test_plan,
+            get_test_plan_env,
+            save_test_plan_env,
+            dry_run_test_command,
+            cancel_test_run,
+            get_test_plan_timeout,
+            set_test_plan_timeout,
             get_test_runs,
             generate_test_suggestions,
             create_tdd_session,
             update_tdd_session,
             get_tdd_session,
             list_tdd_sessions,
+            get_tdd_analytics,
             check_test_staleness,
             generate_subagent_config,
             generate_hooks_config,
             count_project_tests,
             // Session Analysis commands
             analyze_session,
+            analyze_session_incremental,
+            extract_plan_prd,
             get_session_transcript,
             // Team Template commands
             list_team_templates,
@@ -197,6 +453,7 @@ pub fn run() {
             delete_team_template,
             increment_team_template_usage,
             generate_team_deploy_output,
+            validate_team_template_variables,
             // Memory Management commands
             list_memory_sources,
             list_learnings,
@@ -204,6 +461,9 @@ pub fn run() {
             analyze_claude_md,
             get_memory_health,
             promote_learning,
+            import_memory_learnings,
+            review_stale_learnings,
+            demote_learning,
             append_to_project_file,
             // Performance Engineering commands
             analyze_performance,
@@ -211,6 +471,11 @@ pub fn run() {
             get_performance_review,
             delete_performance_review,
             remediate_performance_file,
+            analyze_db_performance,
+            // Version History commands
+            list_versions,
+            diff_versions,
+            rollback_to_version,
         ])
         .run(tauri::generate_context!())
         .expect("error while running tauri application");
@@ -0,0 +1,133 @@
+//! @module models/activity
+//! @description Data models for the activity feed and its retention policy
+//!
+//! PURPOSE:
+//! - Define ActivityType, the fixed set of activity categories
+//! - Define Activity for a single feed entry
+//! - Define ActivityRetention for per-type retention settings
+//!
+//! DEPENDENCIES:
+//! - serde - Serialization for Tauri IPC
+//!
+//! EXPORTS:
+//! - ActivityType - Activity category with default retention period
+//! - Activity - A recorded project activity
+//! - ActivityRetention - Retention period for one activity type
+//!
+//! PATTERNS:
+//! - ActivityType serializes as snake_case ("scan", "enforcement", ...)
+//! - ActivityType::parse maps legacy/free-form strings to a type (unknown -> Info)
+//! - retention_days of 0 means "keep forever"
+//!
+//! CLAUDE NOTES:
+//! - Keep in sync with TypeScript types in src/types/
+//! - Legacy types: tdd/test_plan/test_run -> test, learn -> memory, hooks_configured -> enforcement
+
+use serde::{Deserialize, Serialize};
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum ActivityType {
+    Scan,
+    Generate,
+    Edit,
+    Health,
+    Enforcement,
+    Ralph,
+    Test,
+    Memory,
+    Settings,
+    Skill,
+    Agent,
+    Team,
+    Info,
+}
+
+impl ActivityType {
+    pub const ALL: [ActivityType; 13] = [
+        ActivityType::Scan,
+        ActivityType::Generate,
+        ActivityType::Edit,
+        ActivityType::Health,
+        ActivityType::Enforcement,
+        ActivityType::Ralph,
+        ActivityType::Test,
+        ActivityType::Memory,
+        ActivityType::Settings,
+        ActivityType::Skill,
+        ActivityType::Agent,
+        ActivityType::Team,
+        ActivityType::Info,
+    ];
+
+    /// The string stored in the activities table and sent over IPC.
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            ActivityType::Scan => "scan",
+            ActivityType::Generate => "generate",
+            ActivityType::Edit => "edit",
+            ActivityType::Health => "health",
+            ActivityType::Enforcement => "enforcement",
+            ActivityType::Ralph => "ralph",
+            ActivityType::Test => "test",
+            ActivityType::Memory => "memory",
+            ActivityType::Settings => "settings",
+            ActivityType::Skill => "skill",
+            ActivityType::Agent => "agent",
+            ActivityType::Team => "team",
+            ActivityType::Info => "info",
+        }
+    }
+
+    /// Map a stored or caller-supplied string to a type.
+    /// Accepts legacy names; anything unrecognized becomes Info.
+    pub fn parse(s: &str) -> ActivityType {
+        match s.trim().to_lowercase().as_str() {
+            "scan" => ActivityType::Scan,
+            "generate" => ActivityType::Generate,
+            "edit" => ActivityType::Edit,
+            "health" => ActivityType::Health,
+            "enforcement" | "hooks_configured" => ActivityType::Enforcement,
+            "ralph" => ActivityType::Ralph,
+            "test" | "tdd" | "test_plan" | "test_run" => ActivityType::Test,
+            "memory" | "learn" => ActivityType::Memory,
+            "settings" => ActivityType::Settings,
+            "skill" => ActivityType::Skill,
+            "agent" => ActivityType::Agent,
+            "team" => ActivityType::Team,
+            _ => ActivityType::Info,
+        }
+    }
+
+    /// Default number of days to keep activities of this type (0 = forever).
+    pub fn default_retention_days(&self) -> u32 {
+        match self {
+            ActivityType::Enforcement | ActivityType::Settings => 365,
+            ActivityType::Memory => 365,
+            ActivityType::Test | ActivityType::Ralph => 180,
+            ActivityType::Skill | ActivityType::Agent | ActivityType::Team => 180,
+            ActivityType::Generate | ActivityType::Edit | ActivityType::Health => 90,
+            ActivityType::Scan | ActivityType::Info => 30,
+        }
+    }
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct Activity {
+    pub id: String,
+    pub project_id: String,
+    pub activity_type: ActivityType,
+    pub message: String,
+    pub created_at: String,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ActivityRetention {
+    pub activity_type: ActivityType,
+    /// Days to keep activities of this type; 0 keeps them forever
+    pub retention_days: u32,
+    /// Whether retention_days differs from the built-in default
+    pub customized: bool,
+}
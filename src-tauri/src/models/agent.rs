@@ -5,6 +5,7 @@
 //! - Define Agent struct for reusable Claude Code agent configurations
 //! - Define WorkflowStep for advanced agent workflows
 //! - Define AgentTool for agent tool definitions
+//! - Define lint results for .claude/agents/*.md subagent files
 //!
 //! DEPENDENCIES:
 //! - serde - Serialization for Tauri IPC
@@ -14,6 +15,8 @@
 //! - Agent - A reusable Claude Code agent configuration
 //! - WorkflowStep - A step in an advanced agent workflow
 //! - AgentTool - A tool definition for advanced agents
+//! - SubagentLintIssue - One problem found in a subagent file, with a fix suggestion
+//! - SubagentLintReport - Lint results for all subagent files in a project
 //!
 //! PATTERNS:
 //! - Agents have markdown instructions and optional workflow definitions
@@ -24,6 +27,7 @@
 //! - Keep in sync with TypeScript types in src/types/agent.ts
 //! - workflow, tools, trigger_patterns are Option<Vec<T>> for basic agents
 //! - tier is "basic" or "advanced"
+//! - SubagentLintIssue.severity: "error" (Claude Code will ignore or misread the agent) | "warning"
 
 use chrono::{DateTime, Utc};
 use serde::{Deserialize, Serialize};
@@ -61,3 +65,24 @@ pub struct AgentTool {
     pub description: String,
     pub required: bool,
 }
+
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub struct SubagentLintIssue {
+    /// Path relative to the project root (e.g. ".claude/agents/reviewer.md")
+    pub file: String,
+    pub severity: String,
+    /// Frontmatter field the issue is about (None for body or file-level issues)
+    pub field: Option<String>,
+    pub message: String,
+    pub suggestion: Option<String>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct SubagentLintReport {
+    pub files_checked: u32,
+    pub error_count: u32,
+    pub warning_count: u32,
+    pub issues: Vec<SubagentLintIssue>,
+}
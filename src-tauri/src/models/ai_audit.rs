@@ -0,0 +1,53 @@
+//! @module models/ai_audit
+//! @description Data models for the per-project AI prompt audit log
+//!
+//! PURPOSE:
+//! - Define one audited AI exchange (prompt sent, response or error received)
+//! - Define the time range and limit accepted by get_ai_audit_log / export_ai_audit_log
+//!
+//! DEPENDENCIES:
+//! - serde - Serialization for Tauri IPC and JSON export
+//!
+//! EXPORTS:
+//! - AiAuditEntry - One prompt/response pair sent to an AI provider for a project
+//! - AiAuditRange - Optional from/to bounds (RFC 3339) and row limit
+//!
+//! PATTERNS:
+//! - provider is "anthropic_api" (core::ai calls) or "claude_cli" (RALPH iterations)
+//! - Stored text is already redacted; redactions counts the replacements made
+//!
+//! CLAUDE NOTES:
+//! - Keep in sync with TypeScript types in src/types/
+
+use serde::{Deserialize, Serialize};
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct AiAuditEntry {
+    pub id: String,
+    pub project_id: String,
+    /// What the call was for ("doc generation", "RALPH iteration", ...)
+    pub feature: String,
+    pub provider: String,
+    pub model: String,
+    pub system_prompt: String,
+    pub prompt: String,
+    pub response: Option<String>,
+    pub error: Option<String>,
+    /// Secrets and excluded paths replaced before storing
+    pub redactions: u32,
+    /// Estimated tokens of system prompt + prompt
+    pub prompt_tokens: u32,
+    pub created_at: String,
+}
+
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase", default)]
+pub struct AiAuditRange {
+    /// Inclusive lower bound on created_at
+    pub from: Option<String>,
+    /// Exclusive upper bound on created_at
+    pub to: Option<String>,
+    /// Newest entries first, at most this many (no limit when None)
+    pub limit: Option<u32>,
+}
@@ -0,0 +1,49 @@
+//! @module models/ai_limits
+//! @description Data model for per-model token budgets applied to AI prompts
+//!
+//! PURPOSE:
+//! - Describe how many tokens of file content, tool output, and transcript each AI call may send
+//!
+//! DEPENDENCIES:
+//! - serde - Serialization for Tauri IPC and the settings value
+//!
+//! EXPORTS:
+//! - AiTokenLimits - Context window and per-use token budgets for one model
+//!
+//! PATTERNS:
+//! - Stored as JSON in settings under "ai.token_limits.<model>"; missing fields take defaults
+//!
+//! CLAUDE NOTES:
+//! - Keep in sync with TypeScript types in src/types/ai-limits.ts
+
+use serde::{Deserialize, Serialize};
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase", default)]
+pub struct AiTokenLimits {
+    /// Model id the limits apply to
+    pub model: String,
+    /// Model context window; budgets must leave room for the system prompt and the response
+    pub context_tokens: usize,
+    /// Files up to this size go to doc generation whole; larger files are summarized in chunks
+    pub doc_file_tokens: usize,
+    /// Target size of each chunk of a large file sent for summarization
+    pub doc_chunk_tokens: usize,
+    /// Claude Code output sent for RALPH issue extraction
+    pub issue_output_tokens: usize,
+    /// Transcript messages sent for session analysis
+    pub session_transcript_tokens: usize,
+}
+
+impl Default for AiTokenLimits {
+    fn default() -> Self {
+        AiTokenLimits {
+            model: String::new(),
+            context_tokens: 200_000,
+            doc_file_tokens: 3_000,
+            doc_chunk_tokens: 2_000,
+            issue_output_tokens: 2_000,
+            session_transcript_tokens: 12_000,
+        }
+    }
+}
@@ -0,0 +1,92 @@
+//! @module models/ai_queue
+//! @description Data models for the shared AI work queue
+//!
+//! PURPOSE:
+//! - Define the kinds of AI work the backend schedules and their default priorities
+//! - Define the queue snapshot returned to the frontend
+//!
+//! DEPENDENCIES:
+//! - serde - Serialization for Tauri IPC
+//!
+//! EXPORTS:
+//! - AiPriority - "interactive" | "normal" | "background"
+//! - AiJobKind - What an AI call is for (doc generation, test suggestions, ...)
+//! - AiJobState - "queued" | "running"
+//! - AiJob - One queued or running AI call
+//! - AiQueueStatus - Snapshot of the queue (limits, pause flag, jobs)
+//!
+//! PATTERNS:
+//! - AiPriority orders highest first (Interactive < Normal < Background in Ord)
+//! - Each AiJobKind has a default priority; a queued job's priority can be changed
+//!
+//! CLAUDE NOTES:
+//! - Keep in sync with TypeScript types in src/types/
+
+use serde::{Deserialize, Serialize};
+
+/// Scheduling priority of an AI call. Variants are declared highest first.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum AiPriority {
+    Interactive,
+    Normal,
+    Background,
+}
+
+/// What an AI call is for.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum AiJobKind {
+    /// Prompt analysis, agent enhancement, kickstart, remediation: a user is waiting.
+    Interactive,
+    DocGeneration,
+    TestSuggestions,
+    SessionAnalysis,
+    MemoryConsolidation,
+    /// Issue extraction and patch proposals inside RALPH loops.
+    RalphLoop,
+}
+
+impl AiJobKind {
+    /// Priority a job of this kind is queued with.
+    pub fn default_priority(&self) -> AiPriority {
+        match self {
+            AiJobKind::Interactive => AiPriority::Interactive,
+            AiJobKind::SessionAnalysis | AiJobKind::TestSuggestions | AiJobKind::RalphLoop => AiPriority::Normal,
+            AiJobKind::DocGeneration | AiJobKind::MemoryConsolidation => AiPriority::Background,
+        }
+    }
+}
+
+/// Whether a job is waiting for a slot or calling the API.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum AiJobState {
+    Queued,
+    Running,
+}
+
+/// One queued or running AI call.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct AiJob {
+    pub id: String,
+    pub kind: AiJobKind,
+    pub priority: AiPriority,
+    pub label: String,
+    pub state: AiJobState,
+    pub queued_at: String,
+    pub started_at: Option<String>,
+}
+
+/// Snapshot of the AI queue.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct AiQueueStatus {
+    pub paused: bool,
+    pub max_concurrent: u32,
+    pub running: u32,
+    pub queued: u32,
+    /// Running jobs first, then queued jobs in the order they will start.
+    pub jobs: Vec<AiJob>,
+}
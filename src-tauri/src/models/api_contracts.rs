@@ -0,0 +1,57 @@
+//! @module models/api_contracts
+//! @description Data models for API schema (OpenAPI/GraphQL) drift detection
+//!
+//! PURPOSE:
+//! - Define ApiSchemaSummary for a discovered schema file and its operations
+//! - Define ApiContractDrift for a single contract problem
+//! - Define ApiContractReport returned by check_api_contracts
+//!
+//! DEPENDENCIES:
+//! - serde - Serialization for Tauri IPC
+//!
+//! EXPORTS:
+//! - ApiSchemaSummary - Schema file path, format, and operation keys
+//! - ApiContractDrift - One drift finding (kind, file, operation, detail)
+//! - ApiContractReport - Schemas, handler files, drift, and baseline time
+//!
+//! PATTERNS:
+//! - format: "openapi" | "graphql"
+//! - drift kind: "undocumented_route" | "missing_handler" | "handler_changed"
+//! - Operation keys: "GET /users/{id}" (OpenAPI), "Query.user" or "type User" (GraphQL)
+//!
+//! CLAUDE NOTES:
+//! - Keep in sync with TypeScript types in src/types/
+
+use serde::{Deserialize, Serialize};
+
+/// A schema file found in the project.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ApiSchemaSummary {
+    pub path: String,
+    pub format: String,
+    pub operations: Vec<String>,
+}
+
+/// A place where code and the API schema disagree.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ApiContractDrift {
+    pub kind: String,
+    /// Handler or schema file the finding is about (project-relative)
+    pub path: String,
+    pub operation: Option<String>,
+    pub detail: String,
+}
+
+/// Result of an API contract check.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ApiContractReport {
+    pub schemas: Vec<ApiSchemaSummary>,
+    /// Files that declare routes or GraphQL resolvers
+    pub handler_files: Vec<String>,
+    pub drift: Vec<ApiContractDrift>,
+    /// When the fingerprint baseline was recorded (None until the first check)
+    pub baseline_at: Option<String>,
+}
@@ -0,0 +1,134 @@
+//! @module models/automation
+//! @description Data models for user-defined automation rules (when event + conditions, then action)
+//!
+//! PURPOSE:
+//! - Define a rule: trigger event, conditions on the event's fields, and one action
+//! - Define rule run records and dry-run (simulation) results
+//!
+//! DEPENDENCIES:
+//! - serde / serde_json - Serialization for Tauri IPC and condition values
+//!
+//! EXPORTS:
+//! - RuleOp - Comparison operator for a condition
+//! - RuleCondition - "<field> <op> <value>" over an event's facts
+//! - RuleAction - What a matching rule does (queue doc proposals, run a command, log activity)
+//! - AutomationRule - A stored rule with its firing stats
+//! - NewAutomationRule - Input for create_rule and simulate_rule
+//! - RuleConditionResult - One condition's outcome in a simulation
+//! - RuleSimulation - Dry run of a rule against the project's current state
+//! - RuleRun - One firing of a rule and its action's outcome
+//!
+//! PATTERNS:
+//! - trigger is an AppEvent name ("scan_completed", "loop_iteration_finished", "doc_applied", "activity")
+//! - Condition fields are the event's camelCase fields plus derived facts (e.g. staleCount)
+//! - All conditions must hold (AND); a rule with no conditions fires on every trigger event
+//!
+//! CLAUDE NOTES:
+//! - Keep in sync with TypeScript types in src/types/
+
+use serde::{Deserialize, Serialize};
+
+/// Comparison operator. Ordering operators compare numbers; eq/ne compare any JSON
+/// value; contains matches substrings or array members.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum RuleOp {
+    Eq,
+    Ne,
+    Gt,
+    Gte,
+    Lt,
+    Lte,
+    Contains,
+}
+
+/// One condition over an event's facts.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct RuleCondition {
+    pub field: String,
+    pub op: RuleOp,
+    pub value: serde_json::Value,
+}
+
+/// What a matching rule does.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(tag = "type", rename_all = "snake_case")]
+pub enum RuleAction {
+    /// Queue doc header proposals for every missing/outdated file (doc fix batch)
+    QueueDocProposals,
+    /// Run an allowlisted project command and keep its output on the rule run
+    #[serde(rename_all = "camelCase")]
+    RunCommand { command: String },
+    /// Add an activity feed entry; "{field}" placeholders are filled from the event's facts
+    #[serde(rename_all = "camelCase")]
+    LogActivity { message: String },
+}
+
+/// A stored automation rule.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct AutomationRule {
+    pub id: String,
+    pub project_id: String,
+    pub name: String,
+    pub trigger: String,
+    pub conditions: Vec<RuleCondition>,
+    pub action: RuleAction,
+    pub enabled: bool,
+    pub fire_count: u32,
+    pub last_fired_at: Option<String>,
+    pub created_at: String,
+}
+
+/// Rule definition supplied by the frontend.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct NewAutomationRule {
+    pub project_id: String,
+    pub name: String,
+    pub trigger: String,
+    #[serde(default)]
+    pub conditions: Vec<RuleCondition>,
+    pub action: RuleAction,
+}
+
+/// Outcome of one condition in a simulation.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct RuleConditionResult {
+    pub condition: RuleCondition,
+    /// The fact's value, None when the event has no such field
+    pub actual: Option<serde_json::Value>,
+    pub passed: bool,
+}
+
+/// Dry run of a rule against facts built from the project's current state.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct RuleSimulation {
+    pub matched: bool,
+    /// Facts the conditions were evaluated against (None when there was nothing to build them from)
+    pub facts: Option<serde_json::Value>,
+    pub conditions: Vec<RuleConditionResult>,
+    /// What the action would do, in words
+    pub action_preview: String,
+    pub note: Option<String>,
+}
+
+/// One firing of a rule.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct RuleRun {
+    pub id: String,
+    pub rule_id: String,
+    pub project_id: String,
+    /// RALPH loop the triggering event came from, if any
+    pub loop_id: Option<String>,
+    pub event: String,
+    /// "started" | "ok" | "failed" | "skipped"
+    pub status: String,
+    pub output: String,
+    pub fired_at: String,
+    pub finished_at: Option<String>,
+}
@@ -0,0 +1,73 @@
+//! @module models/bootstrap
+//! @description Data models for the multi-stage CLAUDE.md bootstrap of undocumented repos
+//!
+//! PURPOSE:
+//! - Define a directory cluster and its summary / nested CLAUDE.md outcome
+//! - Define the up-front cost estimate shown before a bootstrap starts
+//! - Define a stored, resumable bootstrap run
+//!
+//! DEPENDENCIES:
+//! - serde - Serialization for Tauri IPC and the doc_bootstraps JSON columns
+//!
+//! EXPORTS:
+//! - BootstrapCluster - Source files grouped under one directory, with summary and write outcome
+//! - BootstrapEstimate - AI calls, tokens, and approximate USD cost of a run
+//! - BootstrapRun - One bootstrap (plan or stored run) with its stage and clusters
+//!
+//! PATTERNS:
+//! - BootstrapRun.stage: "planned" | "summarizing" | "writing" | "overview" | "completed" | "failed"
+//! - BootstrapCluster.outcome: None until written, then "created" | "kept_existing"
+//! - BootstrapCluster.source: "ai" | "heuristic" once summarized
+//!
+//! CLAUDE NOTES:
+//! - Keep in sync with TypeScript types in src/types/
+//! - Cluster dirs and files are project-relative with "/" separators
+
+use serde::{Deserialize, Serialize};
+
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase", default)]
+pub struct BootstrapCluster {
+    /// Project-relative directory ("src/api")
+    pub dir: String,
+    pub files: Vec<String>,
+    /// Estimated prompt tokens for summarizing this cluster
+    pub prompt_tokens: u32,
+    pub summary: Option<String>,
+    pub source: Option<String>,
+    pub outcome: Option<String>,
+}
+
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase", default)]
+pub struct BootstrapEstimate {
+    /// False without an API key: every summary is heuristic and the run is free
+    pub uses_ai: bool,
+    pub clusters: u32,
+    pub files: u32,
+    pub ai_calls: u32,
+    pub input_tokens: u32,
+    pub output_tokens: u32,
+    pub estimated_usd: f64,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct BootstrapRun {
+    /// Empty for a plan that has not been started
+    pub id: String,
+    pub project_id: String,
+    pub depth: u32,
+    pub stage: String,
+    pub clusters: Vec<BootstrapCluster>,
+    /// Source files directly in the project root (described in the overview only)
+    pub root_files: Vec<String>,
+    pub estimate: BootstrapEstimate,
+    /// Architecture overview Markdown, once generated
+    pub overview: Option<String>,
+    /// Project-relative file the overview was written to (None when CLAUDE.md already existed)
+    pub overview_path: Option<String>,
+    pub error: Option<String>,
+    pub created_at: String,
+    pub updated_at: String,
+}
@@ -0,0 +1,31 @@
+//! @module models/claude_backup
+//! @description Data model for timestamped backups of a project's .claude directory
+//!
+//! PURPOSE:
+//! - Describe one .claude snapshot taken before Jumpstart writes into the directory
+//!
+//! DEPENDENCIES:
+//! - serde - Serialization for Tauri IPC and the backup manifest
+//!
+//! EXPORTS:
+//! - ClaudeDirBackup - Backup id, reason, time, and size
+//!
+//! PATTERNS:
+//! - Stored as manifest.json next to the copied files in .jumpstart/backups/<id>/
+//!
+//! CLAUDE NOTES:
+//! - Keep in sync with TypeScript types in src/types/claude-backup.ts
+
+use serde::{Deserialize, Serialize};
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ClaudeDirBackup {
+    /// Directory name under .jumpstart/backups ("YYYYMMDD-HHMMSS-mmm-xxxxxx"), sorts by time
+    pub id: String,
+    /// What was about to write into .claude, e.g. "Before deploying slash commands"
+    pub reason: String,
+    pub created_at: String,
+    pub file_count: u32,
+    pub total_bytes: u64,
+}
@@ -0,0 +1,38 @@
+//! @module models/claude_md
+//! @description Data models for AI-proposed CLAUDE.md edits
+//!
+//! PURPOSE:
+//! - Define a reviewable, diff-based edit to a project's CLAUDE.md
+//!
+//! DEPENDENCIES:
+//! - serde - Serialization for Tauri IPC
+//!
+//! EXPORTS:
+//! - ClaudeMdEdit - An instruction, the AI's unified diff for it, and its review status
+//!
+//! PATTERNS:
+//! - Stored in the claude_md_edits table; status follows patch proposals
+//!   ("pending" | "conflict" | "applied" | "rejected")
+//!
+//! CLAUDE NOTES:
+//! - Keep in sync with TypeScript types in src/types/
+
+use serde::{Deserialize, Serialize};
+
+/// An AI-proposed edit to CLAUDE.md awaiting review.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ClaudeMdEdit {
+    pub id: String,
+    pub project_id: String,
+    /// The natural-language instruction the edit implements
+    pub instruction: String,
+    /// Unified diff of CLAUDE.md (a/CLAUDE.md, b/CLAUDE.md headers)
+    pub diff: String,
+    pub explanation: String,
+    /// "pending" | "conflict" | "applied" | "rejected"
+    pub status: String,
+    pub error: Option<String>,
+    pub created_at: String,
+    pub applied_at: Option<String>,
+}
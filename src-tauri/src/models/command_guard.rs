@@ -0,0 +1,34 @@
+//! @module models/command_guard
+//! @description Data models for the per-project command allowlist
+//!
+//! PURPOSE:
+//! - Define an allowlist entry for a command taken from a PRD or loop template
+//!
+//! DEPENDENCIES:
+//! - serde - Serialization for Tauri IPC
+//!
+//! EXPORTS:
+//! - CommandApproval - A command and its approval status for one project
+//!
+//! PATTERNS:
+//! - status: "pending" | "approved" | "denied"
+//! - source: where the command was first seen ("prd" | "acceptance_gate")
+//!
+//! CLAUDE NOTES:
+//! - Keep in sync with TypeScript types in src/types/
+
+use serde::{Deserialize, Serialize};
+
+/// A command seen for a project, with the user's decision about running it.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct CommandApproval {
+    pub id: String,
+    pub project_id: String,
+    /// Normalized command line (arguments joined by single spaces)
+    pub command: String,
+    pub status: String,
+    pub source: String,
+    pub requested_at: String,
+    pub decided_at: Option<String>,
+}
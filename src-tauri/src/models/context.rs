@@ -6,20 +6,29 @@
 //! - Define TokenBreakdown for token usage by category
 //! - Define McpServerStatus for MCP server monitoring
 //! - Define Checkpoint for context state snapshots
+//! - Define CheckpointState and CheckpointDiff for comparing a checkpoint to the current state
+//! - Define McpCatalogEntry and McpConfigChange for MCP server management
 //!
 //! DEPENDENCIES:
 //! - serde - Serialization for Tauri IPC
+//! - models::version - DiffLine for the CLAUDE.md diff
 //!
 //! EXPORTS:
 //! - ContextHealth - Context usage summary with token breakdown and risk level
 //! - TokenBreakdown - Token counts by category (conversation, code, mcp, skills)
 //! - McpServerStatus - Individual MCP server status and recommendations
 //! - Checkpoint - Context checkpoint record
+//! - CheckpointState - CLAUDE.md, skills, MCP servers, and token breakdown captured by a checkpoint
+//! - CheckpointDiff - What restoring a checkpoint would change relative to the current state
+//! - McpCatalogEntry - Curated MCP server (package, launch command, required env, token cost)
+//! - McpEnvVar - Environment variable an MCP server needs
+//! - McpConfigChange - Result of adding/removing a server in a project's .mcp.json
 //!
 //! PATTERNS:
 //! - ContextHealth.rot_risk: "low" (>=70%), "medium" (40-69%), "high" (<40%)
 //! - TokenBreakdown categories should sum to total_tokens
 //! - McpServerStatus.recommendation: "keep" | "optimize" | "disable"
+//! - CheckpointDiff runs from the current state to the checkpoint: "added" is what restoring brings back
 //!
 //! CLAUDE NOTES:
 //! - Keep in sync with TypeScript types in src/types/health.ts
@@ -29,6 +38,8 @@
 
 use serde::{Deserialize, Serialize};
 
+use crate::models::version::DiffLine;
+
 /// Overall context health summary.
 #[derive(Debug, Clone, Serialize, Deserialize)]
 #[serde(rename_all = "camelCase")]
@@ -40,7 +51,7 @@ pub struct ContextHealth {
 }
 
 /// Token usage breakdown by category.
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
 #[serde(rename_all = "camelCase")]
 pub struct TokenBreakdown {
     pub conversation: u32,
@@ -72,3 +83,78 @@ pub struct Checkpoint {
     pub context_percent: f64,
     pub created_at: String,
 }
+
+/// Context state stored with a checkpoint (checkpoints.context_state JSON).
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct CheckpointState {
+    pub claude_md: String,
+    /// Skill names, sorted
+    pub skills: Vec<String>,
+    /// MCP server names from .mcp.json and .claude/mcp_servers.json, sorted
+    pub mcp_servers: Vec<String>,
+    pub breakdown: TokenBreakdown,
+}
+
+/// Result of compare_checkpoint_to_current.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct CheckpointDiff {
+    pub checkpoint: Checkpoint,
+    /// False for checkpoints created before state was captured; only token totals are compared
+    pub has_state: bool,
+    pub claude_md_changed: bool,
+    /// Line diff from the current CLAUDE.md to the checkpoint's
+    pub claude_md_diff: Vec<DiffLine>,
+    /// Skills in the checkpoint that are gone now (restoring adds them back)
+    pub skills_added: Vec<String>,
+    /// Skills added since the checkpoint (restoring removes them)
+    pub skills_removed: Vec<String>,
+    pub mcp_servers_added: Vec<String>,
+    pub mcp_servers_removed: Vec<String>,
+    pub current_tokens: u32,
+    pub checkpoint_tokens: u32,
+    /// checkpoint_tokens - current_tokens
+    pub token_delta: i64,
+    pub current_breakdown: TokenBreakdown,
+    pub checkpoint_breakdown: Option<TokenBreakdown>,
+}
+
+/// Environment variable required (or accepted) by an MCP server.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct McpEnvVar {
+    pub name: String,
+    pub description: String,
+    pub required: bool,
+    /// Secrets must be passed as "${VAR}" references (literal values are rejected) so the value stays out of .mcp.json
+    pub secret: bool,
+}
+
+/// A curated MCP server from the built-in catalog.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct McpCatalogEntry {
+    pub id: String,
+    pub name: String,
+    pub description: String,
+    pub category: String,
+    pub package: String,
+    pub command: String,
+    pub args: Vec<String>,
+    pub env: Vec<McpEnvVar>,
+    /// Approximate tokens the server's tool schemas add to every session
+    pub token_cost_estimate: u32,
+    /// Whether the server is already configured in the project (false when no project given)
+    pub installed: bool,
+}
+
+/// Result of adding or removing an MCP server in a project config.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct McpConfigChange {
+    pub server: String,
+    pub config_path: String,
+    /// "added" | "replaced" | "unchanged" | "removed" | "not_found"
+    pub action: String,
+}
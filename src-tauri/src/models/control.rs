@@ -0,0 +1,50 @@
+//! @module models/control
+//! @description Wire types for the local control channel used by the jumpstart-cli and jumpstart-mcp companions
+//!
+//! PURPOSE:
+//! - Define the endpoint file the running app writes for the CLI to find it
+//! - Define a CLI request
+//!
+//! DEPENDENCIES:
+//! - serde / serde_json - JSON lines on the control socket
+//!
+//! EXPORTS:
+//! - ControlEndpoint - Port, token, and pid of the running app (~/.project-jumpstart/control.json)
+//! - ControlRequest - One request line: token, command, and arguments
+//!
+//! PATTERNS:
+//! - One request per connection; responses are JSON lines:
+//!   {"ok": true, "data": ...} | {"ok": false, "error": "..."}
+//!   follow_ralph_loop streams {"event": AppEvent} lines and ends with {"done": true, "status": ..., "outcome": ...}
+//! - Module doc commands (jumpstart-mcp):
+//!   get_module_doc {project, path} -> {"path", "documented", "doc": ModuleDoc | null}
+//!   search_module_docs {project, query, limit?} -> [{"path", "score", "reasons", "description"}]
+//!   get_project_conventions {project} -> {"project", "stack": {...}, "conventions": string | null}
+//!
+//! CLAUDE NOTES:
+//! - src/bin/jumpstart-cli.rs and src/bin/jumpstart-mcp.rs mirror these shapes with
+//!   serde_json::Value; keep them in sync
+
+use serde::{Deserialize, Serialize};
+
+/// Written by the running app at startup. The file can outlive the app, so the CLI
+/// treats a refused connection as "app not running".
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ControlEndpoint {
+    pub port: u16,
+    pub token: String,
+    pub pid: u32,
+}
+
+/// One request from the CLI.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ControlRequest {
+    pub token: String,
+    /// "list_ralph_loops" | "start_ralph_loop" | "follow_ralph_loop" | "get_module_doc" |
+    /// "search_module_docs" | "get_project_conventions"
+    pub command: String,
+    #[serde(default)]
+    pub args: serde_json::Value,
+}
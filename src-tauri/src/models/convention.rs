@@ -0,0 +1,102 @@
+//! @module models/convention
+//! @description Data models for conventions parsed from CLAUDE.md and their violations
+//!
+//! PURPOSE:
+//! - Name the kinds of convention the checker understands
+//! - Define a stored convention (one CLAUDE.md rule) with its last check result
+//! - Define violations and the report returned by check_conventions
+//!
+//! DEPENDENCIES:
+//! - serde - Serialization for Tauri IPC
+//!
+//! EXPORTS:
+//! - ConventionKind - naming | directory | forbidden
+//! - Convention - A rule parsed from CLAUDE.md, stored in the conventions table
+//! - ConventionViolation - One file (and line) breaking a convention
+//! - ConventionReport - Conventions, their violations, and how many files were checked
+//!
+//! PATTERNS:
+//! - Convention.pattern by kind: naming = case style ("kebab-case"), directory = required
+//!   directory ("src/components"), forbidden = literal text ("console.log")
+//! - Convention.scope is a project-relative glob ("*.tsx", "src/components/**/*.tsx");
+//!   None means every source file
+//!
+//! CLAUDE NOTES:
+//! - Keep in sync with TypeScript types in src/types/
+//! - Directory conventions always have a scope (the files that must live in the directory)
+
+use serde::{Deserialize, Serialize};
+
+/// What a convention constrains.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum ConventionKind {
+    /// File names follow a case style
+    Naming,
+    /// Files matching the scope live under a directory
+    Directory,
+    /// Text that must not appear in source files
+    Forbidden,
+}
+
+impl ConventionKind {
+    /// Value stored in conventions.kind.
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            ConventionKind::Naming => "naming",
+            ConventionKind::Directory => "directory",
+            ConventionKind::Forbidden => "forbidden",
+        }
+    }
+
+    /// Parse a stored conventions.kind value.
+    pub fn parse(value: &str) -> Option<Self> {
+        match value {
+            "naming" => Some(ConventionKind::Naming),
+            "directory" => Some(ConventionKind::Directory),
+            "forbidden" => Some(ConventionKind::Forbidden),
+            _ => None,
+        }
+    }
+}
+
+/// A rule parsed from CLAUDE.md.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct Convention {
+    pub id: String,
+    pub kind: ConventionKind,
+    /// The CLAUDE.md line the rule came from
+    pub rule: String,
+    pub pattern: String,
+    pub scope: Option<String>,
+    /// Violations found by the last check
+    pub violation_count: u32,
+    /// Up to a few "file:line" locations from the last check
+    pub sample_violations: Vec<String>,
+    pub checked_at: Option<String>,
+}
+
+/// A file (and line, for forbidden text) that breaks a convention.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ConventionViolation {
+    pub convention_id: String,
+    pub kind: ConventionKind,
+    pub rule: String,
+    /// Project-relative path
+    pub file: String,
+    pub line: Option<u32>,
+    /// The offending line, or what the file name should look like
+    pub detail: String,
+}
+
+/// Result of checking a project against its CLAUDE.md conventions.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ConventionReport {
+    pub conventions: Vec<Convention>,
+    pub violations: Vec<ConventionViolation>,
+    pub files_checked: u32,
+    pub checked_at: String,
+}
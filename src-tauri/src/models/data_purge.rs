@@ -0,0 +1,97 @@
+//! @module models/data_purge
+//! @description Data models for purging stored project data
+//!
+//! PURPOSE:
+//! - Define the data classes a user can purge (analyses, learnings, loop outcomes, activities)
+//! - Report per-table row counts for a dry run or a completed purge
+//!
+//! DEPENDENCIES:
+//! - serde - Serialization for Tauri IPC
+//!
+//! EXPORTS:
+//! - PurgeScope - A purgeable data class and the tables it covers
+//! - PurgeCount - Rows matched in one table of one scope
+//! - PurgeReport - Result of purge_project_data / purge_ai_content
+//!
+//! PATTERNS:
+//! - PurgeScope serializes as snake_case ("analyses", "loop_outcomes", ...)
+//! - PurgeScope::AI_DERIVED is every scope holding AI-generated or AI-extracted content
+//!
+//! CLAUDE NOTES:
+//! - Keep in sync with TypeScript types in src/types/
+//! - When a new table stores AI-derived project data, add it to the matching scope's tables()
+
+use serde::{Deserialize, Serialize};
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum PurgeScope {
+    /// Saved prompt analyses, performance reviews, session reports, and cached transcripts
+    Analyses,
+    /// Memory learnings extracted from sessions
+    Learnings,
+    /// RALPH loops with their iterations, mistakes, and patch proposals
+    LoopOutcomes,
+    /// The activity feed
+    Activities,
+}
+
+impl PurgeScope {
+    pub const ALL: [PurgeScope; 4] = [
+        PurgeScope::Analyses,
+        PurgeScope::Learnings,
+        PurgeScope::LoopOutcomes,
+        PurgeScope::Activities,
+    ];
+
+    pub const AI_DERIVED: [PurgeScope; 3] = [PurgeScope::Analyses, PurgeScope::Learnings, PurgeScope::LoopOutcomes];
+
+    pub fn as_str(self) -> &'static str {
+        match self {
+            PurgeScope::Analyses => "analyses",
+            PurgeScope::Learnings => "learnings",
+            PurgeScope::LoopOutcomes => "loop_outcomes",
+            PurgeScope::Activities => "activities",
+        }
+    }
+
+    /// (table, project filter) pairs in delete order; the filter binds the project ID as ?1.
+    pub fn tables(self) -> &'static [(&'static str, &'static str)] {
+        match self {
+            PurgeScope::Analyses => &[
+                ("prompt_analyses", "project_id = ?1"),
+                ("performance_reviews", "project_id = ?1"),
+                ("session_reports", "project_path = (SELECT path FROM projects WHERE id = ?1)"),
+                ("transcript_cache", "project_path = (SELECT path FROM projects WHERE id = ?1)"),
+            ],
+            PurgeScope::Learnings => &[("learnings", "project_id = ?1")],
+            PurgeScope::LoopOutcomes => &[
+                ("patch_proposals", "project_id = ?1"),
+                ("ralph_iterations", "loop_id IN (SELECT id FROM ralph_loops WHERE project_id = ?1)"),
+                ("ralph_mistakes", "project_id = ?1"),
+                ("ralph_loops", "project_id = ?1"),
+            ],
+            PurgeScope::Activities => &[("activities", "project_id = ?1")],
+        }
+    }
+}
+
+/// Rows matched in one table of one scope.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct PurgeCount {
+    pub scope: PurgeScope,
+    pub table: String,
+    pub rows: u32,
+}
+
+/// Result of a purge. With dry_run the counts are what would be deleted.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct PurgeReport {
+    /// None for a purge across all projects
+    pub project_id: Option<String>,
+    pub dry_run: bool,
+    pub counts: Vec<PurgeCount>,
+    pub total_rows: u32,
+}
@@ -0,0 +1,67 @@
+//! @module models/digest
+//! @description Data models for per-project weekly digests
+//!
+//! PURPOSE:
+//! - Define the numbers a weekly digest summarizes
+//! - Define a stored digest with its rendered Markdown
+//!
+//! DEPENDENCIES:
+//! - serde - Serialization for Tauri IPC and the weekly_digests.stats column
+//!
+//! EXPORTS:
+//! - DigestStats - Docs generated, loops run, test runs, health delta, and new learnings for one week
+//! - WeeklyDigest - A stored digest for one project and week
+//!
+//! PATTERNS:
+//! - Weeks run Monday 00:00 UTC to the next Monday (week_end is exclusive)
+//! - Health fields are None when the project has no health snapshots up to week_end
+//!
+//! CLAUDE NOTES:
+//! - Keep in sync with TypeScript types in src/types/
+
+use serde::{Deserialize, Serialize};
+
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase", default)]
+pub struct DigestStats {
+    /// "generate" activities (doc headers written, CLAUDE.md generated, ...)
+    pub docs_generated: u32,
+    pub loops_run: u32,
+    pub loops_completed: u32,
+    pub loops_failed: u32,
+    pub test_runs: u32,
+    pub test_runs_passed: u32,
+    pub test_runs_failed: u32,
+    /// Health score at the start of the week (latest snapshot before it, else the week's first)
+    pub health_start: Option<u32>,
+    /// Health score of the week's latest snapshot
+    pub health_end: Option<u32>,
+    pub new_learnings: u32,
+    /// Up to five new learnings, highest confidence first
+    pub top_learnings: Vec<String>,
+}
+
+impl DigestStats {
+    /// health_end - health_start, when both are known.
+    pub fn health_delta(&self) -> Option<i32> {
+        Some(self.health_end? as i32 - self.health_start? as i32)
+    }
+
+    /// Nothing happened during the week (the scheduler skips these).
+    pub fn is_empty(&self) -> bool {
+        self.docs_generated == 0 && self.loops_run == 0 && self.test_runs == 0 && self.new_learnings == 0
+    }
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct WeeklyDigest {
+    pub id: String,
+    pub project_id: String,
+    /// Monday 00:00 UTC, RFC 3339
+    pub week_start: String,
+    pub week_end: String,
+    pub stats: DigestStats,
+    pub markdown: String,
+    pub created_at: String,
+}
@@ -0,0 +1,58 @@
+//! @module models/doc_proposal
+//! @description Data models for the doc generation approval queue
+//!
+//! PURPOSE:
+//! - Define a queued doc header proposal with its preview diff
+//! - Define the per-proposal result of a bulk approve/reject
+//!
+//! DEPENDENCIES:
+//! - serde - Serialization for Tauri IPC
+//! - models::module_doc - ModuleDoc (the proposed header)
+//! - models::version - DiffLine (preview diff lines)
+//!
+//! EXPORTS:
+//! - DocProposal - A proposed header for one file, awaiting review
+//! - DocProposalResult - Outcome of approving or rejecting one proposal
+//!
+//! PATTERNS:
+//! - status: "pending" | "applied" | "rejected"; reason: "missing" | "outdated"
+//! - diff is computed against the file's current content when proposals are listed
+//!
+//! CLAUDE NOTES:
+//! - Keep in sync with TypeScript types in src/types/
+
+use serde::{Deserialize, Serialize};
+
+use crate::models::module_doc::ModuleDoc;
+use crate::models::version::DiffLine;
+
+/// A proposed doc header for one file, awaiting review.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct DocProposal {
+    pub id: String,
+    pub project_path: String,
+    /// Absolute path of the file the header is for
+    pub file_path: String,
+    /// "missing" (no header yet) or "outdated" (header no longer matches the code)
+    pub reason: String,
+    /// "watcher" or "freshness"
+    pub source: String,
+    pub doc: ModuleDoc,
+    /// Changed lines (with context) between the current file and the file with the header applied
+    pub diff: Vec<DiffLine>,
+    pub status: String,
+    pub created_at: String,
+    pub decided_at: Option<String>,
+}
+
+/// Outcome of approving or rejecting one proposal.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct DocProposalResult {
+    pub id: String,
+    pub file_path: String,
+    /// "applied" | "rejected" | "failed"
+    pub status: String,
+    pub error: Option<String>,
+}
@@ -0,0 +1,48 @@
+//! @module models/doctor
+//! @description Data models for the self-diagnostics (doctor) report
+//!
+//! PURPOSE:
+//! - Define one diagnostic check with its fix instructions and optional one-click fix
+//! - Define the full doctor report
+//!
+//! DEPENDENCIES:
+//! - serde - Serialization for Tauri IPC
+//!
+//! EXPORTS:
+//! - DoctorCheck - One check (database, tools, API key, key storage, disk, jobs, project paths)
+//! - DoctorReport - All checks plus an overall healthy flag
+//!
+//! PATTERNS:
+//! - status is "pass" | "warn" | "fail" | "skipped", as in RalphPreflightCheck
+//! - fix_action names a fix apply_doctor_fix can run ("run_migrations", "reindex_db",
+//!   "fail_orphaned_jobs", "clear_undecryptable_secrets"); None means the fix is manual
+//!
+//! CLAUDE NOTES:
+//! - Keep in sync with TypeScript types in src/types/
+
+use serde::{Deserialize, Serialize};
+
+/// One diagnostic check.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct DoctorCheck {
+    pub id: String,
+    pub label: String,
+    /// "pass" | "warn" | "fail" | "skipped"
+    pub status: String,
+    pub detail: String,
+    /// What to do when the check warns or fails
+    pub fix: Option<String>,
+    /// One-click fix for apply_doctor_fix, when there is one
+    pub fix_action: Option<String>,
+}
+
+/// Result of run_doctor.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct DoctorReport {
+    pub checks: Vec<DoctorCheck>,
+    /// False when any check failed
+    pub healthy: bool,
+    pub generated_at: String,
+}
@@ -5,21 +5,27 @@
 //! - Define EnforcementEvent for tracking hook/CI activity
 //! - Define HookStatus for git hook installation state
 //! - Define CiSnippet for CI integration templates
+//! - Define CiSyncReport for CI run imports (GitHub, GitLab, Bitbucket)
 //!
 //! DEPENDENCIES:
 //! - serde - Serialization for Tauri IPC
+//! - models::git_forge - ForgeProvider
+//! - models::project - SourceLayout the CI snippets were generated for
 //!
 //! EXPORTS:
 //! - EnforcementEvent - A hook block/warning event record
 //! - HookStatus - Git hook installation status
 //! - HookHealth - Auto-update hook health and downgrade tracking
-//! - CiSnippet - CI template with provider and content
+//! - HookKeyStatus - Exported hook API key expiry and dependent projects
+//! - CiSnippet - CI template with provider, content, and the source layout it scans
+//! - CiSyncReport - Result of importing doc-check workflow runs
 //!
 //! PATTERNS:
 //! - EnforcementEvent.event_type: "block" | "warning" | "info"
 //! - EnforcementEvent.source: "hook" | "ci" | "watcher"
 //! - HookStatus tracks pre-commit hook presence and mode
-//! - CiSnippet.provider: "github_actions" | "gitlab_ci"
+//! - CiSnippet.provider: "github_actions" | "gitlab_ci" | "bitbucket_pipelines" | "circleci" |
+//!   "azure_pipelines"
 //!
 //! CLAUDE NOTES:
 //! - Keep in sync with TypeScript types in src/types/enforcement.ts
@@ -28,6 +34,9 @@
 
 use serde::{Deserialize, Serialize};
 
+use crate::models::git_forge::ForgeProvider;
+use crate::models::project::SourceLayout;
+
 /// A recorded enforcement event (hook block, warning, etc.)
 #[derive(Debug, Clone, Serialize, Deserialize)]
 #[serde(rename_all = "camelCase")]
@@ -74,6 +83,20 @@ pub struct HookHealth {
     pub total_failures: u32,
 }
 
+/// Status of the API key exported to ~/.project-jumpstart/settings.json
+/// for auto-update hooks. The key itself is never returned.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct HookKeyStatus {
+    pub exported: bool,
+    pub exported_at: Option<String>,
+    pub expires_at: Option<String>,
+    /// True when the export is past its expiry (or predates expiry support)
+    pub expired: bool,
+    /// Paths of projects with an auto-update hook installed
+    pub projects: Vec<String>,
+}
+
 /// CI integration template snippet.
 #[derive(Debug, Clone, Serialize, Deserialize)]
 #[serde(rename_all = "camelCase")]
@@ -83,4 +106,23 @@ pub struct CiSnippet {
     pub description: String,
     pub filename: String,
     pub content: String,
+    /// True when the provider's config file already exists in the project
+    #[serde(default)]
+    pub configured: bool,
+    /// Source layout the snippet was generated for
+    #[serde(default)]
+    pub layout: SourceLayout,
+}
+
+/// Result of importing doc-check runs from the project's forge.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct CiSyncReport {
+    pub provider: ForgeProvider,
+    /// "owner/repo" (GitLab: full group path)
+    pub repository: String,
+    pub runs_checked: u32,
+    pub failures_found: u32,
+    /// Failures not recorded by an earlier sync
+    pub new_events: u32,
 }
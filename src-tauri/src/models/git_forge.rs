@@ -0,0 +1,65 @@
+//! @module models/git_forge
+//! @description Data models for per-project git forge (GitHub, GitLab, Bitbucket) selection
+//!
+//! PURPOSE:
+//! - Name the forges the integration supports
+//! - Define a project's forge settings (provider override, self-hosted API URL)
+//! - Define the forge a project resolves to
+//!
+//! DEPENDENCIES:
+//! - serde - Serialization for Tauri IPC and settings storage
+//!
+//! EXPORTS:
+//! - ForgeProvider - github | gitlab | bitbucket
+//! - ForgeConfig - A project's forge settings, stored in settings as JSON
+//! - DetectedForge - Provider, host, and repository resolved for a project
+//!
+//! PATTERNS:
+//! - ForgeConfig.provider None means "detect from the origin remote's host"
+//! - ForgeConfig.api_url overrides the API base (self-hosted GitLab, e.g.
+//!   "https://git.example.com/api/v4"); None derives it from the remote host
+//!
+//! CLAUDE NOTES:
+//! - Keep in sync with TypeScript types in src/types/
+
+use serde::{Deserialize, Serialize};
+
+/// A git hosting service with an API the app talks to.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum ForgeProvider {
+    Github,
+    Gitlab,
+    Bitbucket,
+}
+
+impl ForgeProvider {
+    /// Display name for messages.
+    pub fn label(&self) -> &'static str {
+        match self {
+            ForgeProvider::Github => "GitHub",
+            ForgeProvider::Gitlab => "GitLab",
+            ForgeProvider::Bitbucket => "Bitbucket",
+        }
+    }
+}
+
+/// Forge settings for one project.
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase", default)]
+pub struct ForgeConfig {
+    pub provider: Option<ForgeProvider>,
+    pub api_url: Option<String>,
+}
+
+/// The forge and repository a project resolves to.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct DetectedForge {
+    /// None when the host is unrecognized and no provider is configured
+    pub provider: Option<ForgeProvider>,
+    pub host: String,
+    pub repository: String,
+    /// True when the provider came from the project's ForgeConfig
+    pub configured: bool,
+}
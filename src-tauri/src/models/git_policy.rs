@@ -0,0 +1,94 @@
+//! @module models/git_policy
+//! @description Data models for per-project git permissions
+//!
+//! PURPOSE:
+//! - Define the yes/no/ask permission for each git mutation the backend performs
+//! - Define the confirmation request sent to the frontend for "ask"
+//!
+//! DEPENDENCIES:
+//! - serde - Serialization for Tauri IPC
+//!
+//! EXPORTS:
+//! - GitPermission - "yes" | "no" | "ask"
+//! - GitOperation - Branch creation, commit, or push
+//! - GitPolicy - Permission per operation for one project
+//! - GitPermissionRequest - Payload of the "git-permission-request" event
+//!
+//! PATTERNS:
+//! - GitPolicy fields default individually, so stored policies survive new operations
+//! - Defaults keep the previous behavior for branches and commits; pushes ask
+//!
+//! CLAUDE NOTES:
+//! - Keep in sync with TypeScript types in src/types/
+
+use serde::{Deserialize, Serialize};
+
+/// Whether a git operation may run without asking.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum GitPermission {
+    Yes,
+    No,
+    Ask,
+}
+
+/// A git mutation performed by the backend.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum GitOperation {
+    Branch,
+    Commit,
+    Push,
+}
+
+impl GitOperation {
+    /// Human-readable action for prompts and errors.
+    pub fn label(&self) -> &'static str {
+        match self {
+            GitOperation::Branch => "create or switch branches",
+            GitOperation::Commit => "commit",
+            GitOperation::Push => "push",
+        }
+    }
+}
+
+/// Git permissions for one project, stored in settings.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase", default)]
+pub struct GitPolicy {
+    pub allow_branch: GitPermission,
+    pub allow_commit: GitPermission,
+    pub allow_push: GitPermission,
+}
+
+impl Default for GitPolicy {
+    fn default() -> Self {
+        GitPolicy {
+            allow_branch: GitPermission::Yes,
+            allow_commit: GitPermission::Yes,
+            allow_push: GitPermission::Ask,
+        }
+    }
+}
+
+impl GitPolicy {
+    pub fn permission(&self, operation: GitOperation) -> GitPermission {
+        match operation {
+            GitOperation::Branch => self.allow_branch,
+            GitOperation::Commit => self.allow_commit,
+            GitOperation::Push => self.allow_push,
+        }
+    }
+}
+
+/// A pending "ask" confirmation, answered with answer_git_permission.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct GitPermissionRequest {
+    pub request_id: String,
+    pub project_id: String,
+    pub operation: GitOperation,
+    /// What would run, e.g. the branch name or commit message
+    pub detail: String,
+    pub requested_at: String,
+}
@@ -0,0 +1,94 @@
+//! @module models/health_alert
+//! @description Data models for per-project health alert thresholds and their alert state
+//!
+//! PURPOSE:
+//! - Define the thresholds a project can alert on (health, stale files, failing tests)
+//! - Name the metrics an alert watches
+//! - Define the tracked state of one alert (active or cleared, with the value that changed it)
+//!
+//! DEPENDENCIES:
+//! - serde - Serialization for Tauri IPC and the settings JSON
+//!
+//! EXPORTS:
+//! - HealthAlertThresholds - Per-project thresholds (every field optional)
+//! - HealthAlertMetric - health_score | stale_files | failing_tests
+//! - HealthAlert - One metric's alert state: threshold, last value, active flag
+//!
+//! PATTERNS:
+//! - Unset thresholds are not checked; all unset means alerts are off for the project
+//! - health_below alerts when the score is strictly below it; the "above" thresholds alert
+//!   when the count is strictly above them (failing_tests_above = 0 alerts on any failure)
+//!
+//! CLAUDE NOTES:
+//! - Keep in sync with TypeScript types in src/types/
+
+use serde::{Deserialize, Serialize};
+
+/// Alert thresholds for one project, stored in settings.
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase", default)]
+pub struct HealthAlertThresholds {
+    /// Alert when the health score (0-100) drops below this
+    pub health_below: Option<u32>,
+    /// Alert when more than this many documented files are outdated
+    pub stale_files_above: Option<u32>,
+    /// Alert when the latest test run has more than this many failures
+    pub failing_tests_above: Option<u32>,
+}
+
+impl HealthAlertThresholds {
+    pub fn is_empty(&self) -> bool {
+        self.health_below.is_none() && self.stale_files_above.is_none() && self.failing_tests_above.is_none()
+    }
+
+    /// The threshold set for a metric, if any.
+    pub fn threshold(&self, metric: HealthAlertMetric) -> Option<u32> {
+        match metric {
+            HealthAlertMetric::HealthScore => self.health_below,
+            HealthAlertMetric::StaleFiles => self.stale_files_above,
+            HealthAlertMetric::FailingTests => self.failing_tests_above,
+        }
+    }
+}
+
+/// A metric a health alert watches.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum HealthAlertMetric {
+    HealthScore,
+    StaleFiles,
+    FailingTests,
+}
+
+impl HealthAlertMetric {
+    pub const ALL: [HealthAlertMetric; 3] =
+        [HealthAlertMetric::HealthScore, HealthAlertMetric::StaleFiles, HealthAlertMetric::FailingTests];
+
+    /// Value stored in health_alert_state.metric.
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            HealthAlertMetric::HealthScore => "health_score",
+            HealthAlertMetric::StaleFiles => "stale_files",
+            HealthAlertMetric::FailingTests => "failing_tests",
+        }
+    }
+
+    /// Parse a stored health_alert_state.metric value.
+    pub fn parse(value: &str) -> Option<Self> {
+        HealthAlertMetric::ALL.into_iter().find(|m| m.as_str() == value)
+    }
+}
+
+/// Tracked state of one metric's alert.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct HealthAlert {
+    pub metric: HealthAlertMetric,
+    pub threshold: u32,
+    /// Value when the alert last fired or cleared
+    pub value: u32,
+    /// True from the crossing that fired the alert until the metric recovers
+    pub active: bool,
+    pub message: String,
+    pub changed_at: String,
+}
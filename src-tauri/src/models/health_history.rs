@@ -0,0 +1,108 @@
+//! @module models/health_history
+//! @description Data models for health snapshots tied to git commits and detected regressions
+//!
+//! PURPOSE:
+//! - Define a health snapshot taken at a specific git HEAD
+//! - Define a regression: a commit range after which doc coverage or freshness dropped
+//! - Define per-project documentation goals and the result of checking them
+//!
+//! DEPENDENCIES:
+//! - serde - Serialization for Tauri IPC
+//!
+//! EXPORTS:
+//! - HealthSnapshot - Health, doc coverage, and freshness at one commit
+//! - HealthRegression - Commit range, before/after metrics, and files involved
+//! - DocGoals - Per-project targets (coverage, stale age, health score) and CI enforcement flag
+//! - DocGoalCheck - One goal's target, actual value, and offending files
+//! - DocGoalsReport - All checked goals and whether every one was met
+//!
+//! PATTERNS:
+//! - doc_coverage and avg_freshness are percentages (0-100)
+//! - degraded_files / files are project-relative paths
+//! - Every DocGoals field is optional on the wire; unset targets are not checked
+//!
+//! CLAUDE NOTES:
+//! - Keep in sync with TypeScript types in src/types/
+
+use serde::{Deserialize, Serialize};
+
+/// Health metrics recorded at a git HEAD.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct HealthSnapshot {
+    pub id: String,
+    pub project_id: String,
+    pub commit_sha: String,
+    pub health_score: u32,
+    pub doc_coverage: f64,
+    pub avg_freshness: f64,
+    pub total_files: u32,
+    pub documented_files: u32,
+    /// Files that were missing docs or outdated at this commit
+    pub degraded_files: Vec<String>,
+    /// Whether the project's doc goals were met at this commit (None when no goals were set)
+    pub goals_met: Option<bool>,
+    pub created_at: String,
+}
+
+/// A sharp drop in doc coverage or freshness between two snapshots.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct HealthRegression {
+    /// Last good commit (exclusive end of the range)
+    pub from_commit: String,
+    /// First commit where the drop was observed
+    pub to_commit: String,
+    /// "<short sha> <subject>" for each commit in from..to (empty if git history is unavailable)
+    pub commits: Vec<String>,
+    pub coverage_before: f64,
+    pub coverage_after: f64,
+    pub freshness_before: f64,
+    pub freshness_after: f64,
+    pub health_before: u32,
+    pub health_after: u32,
+    /// Files that became undocumented or outdated in the range
+    pub files: Vec<String>,
+    pub detected_at: String,
+}
+
+/// Documentation targets for one project, stored in settings.
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase", default)]
+pub struct DocGoals {
+    /// Minimum documented files / total files (0-100)
+    pub min_doc_coverage: Option<f64>,
+    /// Maximum days an outdated doc header may go without an update
+    pub max_stale_days: Option<u32>,
+    /// Minimum overall health score (0-100)
+    pub min_health_score: Option<u32>,
+    /// Fail the generated CI check when coverage is under min_doc_coverage
+    pub enforce_in_ci: bool,
+}
+
+impl DocGoals {
+    pub fn is_empty(&self) -> bool {
+        self.min_doc_coverage.is_none() && self.max_stale_days.is_none() && self.min_health_score.is_none()
+    }
+}
+
+/// Result of checking one goal.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct DocGoalCheck {
+    /// "doc_coverage" | "stale_days" | "health_score"
+    pub goal: String,
+    pub target: f64,
+    pub actual: f64,
+    pub met: bool,
+    /// Files behind a missed stale_days goal (oldest first); empty for other goals
+    pub files: Vec<String>,
+}
+
+/// Result of checking every goal set for a project.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct DocGoalsReport {
+    pub checks: Vec<DocGoalCheck>,
+    pub met: bool,
+}
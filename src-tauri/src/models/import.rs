@@ -0,0 +1,89 @@
+//! @module models/import
+//! @description Data models for merging imported or synced shared knowledge
+//!
+//! PURPOSE:
+//! - Define ImportConflict for an incoming skill/agent/template that needs a decision
+//! - Define the decisions accepted by resolve_import_conflicts
+//! - Define per-entity import and resolution summaries
+//!
+//! DEPENDENCIES:
+//! - serde - Serialization for Tauri IPC
+//!
+//! EXPORTS:
+//! - ImportConflict - A pending collision between a local and an incoming row
+//! - ConflictResolution - keep_local | take_incoming | keep_both
+//! - ConflictDecision - Resolution chosen for one conflict
+//! - ImportSummary - Counts of added/updated/identical/skipped/conflicting rows
+//! - ResolveSummary - Counts of applied decisions and remaining conflicts
+//!
+//! PATTERNS:
+//! - entity_type is "skill", "agent", "team_template", or "setting" (settings changed on two machines)
+//! - source is "shared" (shared backend sync), "bundle" (JSON bundle import), or "sync" (remote snapshot sync)
+//!
+//! CLAUDE NOTES:
+//! - Keep in sync with TypeScript types in src/types/
+
+use serde::{Deserialize, Serialize};
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ImportConflict {
+    pub id: String,
+    pub entity_type: String,
+    pub name: String,
+    pub local_id: String,
+    pub incoming_id: String,
+    /// Content fields whose values differ between the local and incoming rows
+    pub changed_fields: Vec<String>,
+    pub local_updated_at: String,
+    pub incoming_updated_at: String,
+    pub source: String,
+    pub created_at: String,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum ConflictResolution {
+    KeepLocal,
+    TakeIncoming,
+    KeepBoth,
+}
+
+impl ConflictResolution {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            ConflictResolution::KeepLocal => "keep_local",
+            ConflictResolution::TakeIncoming => "take_incoming",
+            ConflictResolution::KeepBoth => "keep_both",
+        }
+    }
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ConflictDecision {
+    pub conflict_id: String,
+    pub resolution: ConflictResolution,
+}
+
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ImportSummary {
+    pub entity_type: String,
+    pub source: String,
+    pub added: u32,
+    pub updated: u32,
+    pub identical: u32,
+    /// Incoming rows older than the local copy (nothing to import)
+    pub skipped: u32,
+    pub conflicts: u32,
+}
+
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ResolveSummary {
+    pub kept_local: u32,
+    pub took_incoming: u32,
+    pub kept_both: u32,
+    pub remaining: u32,
+}
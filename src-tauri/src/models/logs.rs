@@ -0,0 +1,47 @@
+//! @module models/logs
+//! @description Data models for the application log viewer
+//!
+//! PURPOSE:
+//! - Define one parsed log line as returned by get_app_logs
+//! - Define the log query result with the directory the files live in
+//!
+//! DEPENDENCIES:
+//! - serde / serde_json - Serialization for Tauri IPC and structured fields
+//!
+//! EXPORTS:
+//! - LogEntry - Timestamp, level, module, message, and extra fields of one log line
+//! - AppLogs - Matching entries (newest first) plus the log directory
+//!
+//! PATTERNS:
+//! - level is upper case ("ERROR", "WARN", "INFO", "DEBUG", "TRACE")
+//! - module is the Rust module path without the crate name (e.g. "commands::ralph")
+//!
+//! CLAUDE NOTES:
+//! - Keep in sync with TypeScript types in src/types/
+//! - Log files are written by core::logging as JSON lines
+
+use serde::{Deserialize, Serialize};
+
+/// One log line.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct LogEntry {
+    pub timestamp: String,
+    pub level: String,
+    pub module: String,
+    pub message: String,
+    /// Structured fields other than the message (e.g. {"error": "..."}); empty object when none
+    pub fields: serde_json::Value,
+}
+
+/// Result of get_app_logs.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct AppLogs {
+    /// Matching entries, newest first
+    pub entries: Vec<LogEntry>,
+    /// True when more entries matched than the limit allowed
+    pub truncated: bool,
+    /// Directory holding the rotated log files (for attaching to bug reports)
+    pub log_dir: Option<String>,
+}
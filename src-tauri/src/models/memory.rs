@@ -10,7 +10,11 @@
 //!
 //! EXPORTS:
 //! - MemorySource - Represents a memory file (CLAUDE.md, rules, auto-memory, etc.)
-//! - Learning - An extracted learning with category, topic, confidence, status
+//! - Learning - An extracted learning with category, topic, confidence, status, source
+//! - MemoryImportResult - Counts and files from importing memory files into learnings
+//! - StaleLearning - A promoted learning due for review and the referenced files that changed
+//! - ReferencedFileChange - Git churn of a file referenced by a learning
+//! - LearningDemotion - Result of removing a promoted learning from its target file
 //! - MemoryHealth - Overall memory health metrics
 //! - ClaudeMdAnalysis - Analysis results for CLAUDE.md quality
 //! - AnalysisSuggestion - Individual suggestion for CLAUDE.md improvement
@@ -28,6 +32,8 @@
 //! - Learning.category values: "Preference", "Solution", "Pattern", "Gotcha"
 //! - Learning.confidence values: "high", "medium", "low"
 //! - Learning.status values: "active", "verified", "deprecated", "archived"
+//! - Learning.source values: "local" (sessions, CLAUDE.local.md), "imported" (memory files)
+//! - Demoted learnings keep promoted_to/promoted_at and get status "deprecated" plus demoted_at
 //! - MemoryHealth.health_rating values: "excellent", "good", "needs-attention", "poor"
 
 use serde::{Deserialize, Serialize};
@@ -58,6 +64,27 @@ pub struct Learning {
     pub source_file: String,
     pub created_at: String,
     pub updated_at: String,
+    #[serde(default = "default_learning_source")]
+    pub source: String,
+    /// When the learning was promoted or last confirmed as still correct
+    #[serde(default)]
+    pub last_verified_at: Option<String>,
+}
+
+fn default_learning_source() -> String {
+    "local".to_string()
+}
+
+/// Result of importing existing memory files into the learnings table.
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct MemoryImportResult {
+    /// New learnings inserted
+    pub imported: u32,
+    /// Entries skipped because a learning with the same content hash already exists
+    pub skipped: u32,
+    /// Files that contained importable entries
+    pub source_files: Vec<String>,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -112,3 +139,40 @@ pub struct LineMoveTarget {
     pub target_file: String,
     pub reason: String,
 }
+
+/// Git churn of a file referenced by a learning since it was last verified.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ReferencedFileChange {
+    pub path: String,
+    /// Added + deleted lines
+    pub lines_changed: u32,
+    pub commits: u32,
+    /// The file no longer exists
+    pub deleted: bool,
+}
+
+/// A promoted learning whose referenced files changed significantly since it was verified.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct StaleLearning {
+    pub learning: Learning,
+    /// File the learning was promoted into (as given to promote_learning)
+    pub promoted_to: String,
+    pub promoted_at: String,
+    /// Days since promotion or last verification
+    pub age_days: u32,
+    pub changed_files: Vec<ReferencedFileChange>,
+}
+
+/// Result of demoting a learning out of the file it was promoted into.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct LearningDemotion {
+    pub learning_id: String,
+    /// Absolute path of the file the learning was removed from
+    pub file: String,
+    /// Bullet lines removed (0 if the line had already been edited away)
+    pub removed_lines: u32,
+    pub demoted_at: String,
+}
@@ -16,6 +16,39 @@
 //! - enforcement - EnforcementEvent, HookStatus, CiSnippet types
 //! - test_plan - TestPlan, TestCase, TestRun, TestCaseResult, TDDSession types
 //! - memory - MemorySource, Learning, MemoryHealth, ClaudeMdAnalysis types
+//! - version - EntityVersion, VersionDiff, DiffLine types
+//! - activity - ActivityType, Activity, ActivityRetention types
+//! - monitor - MonitorWindow type
+//! - shared_db - Shared database backend config and sync report types
+//! - import - ImportConflict, ConflictDecision, ImportSummary types
+//! - slash_command - SlashCommandDeployResult, SlashCommandDrift types
+//! - health_history - HealthSnapshot, HealthRegression types
+//! - command_guard - CommandApproval type
+//! - sql_schema - SchemaTable, SchemaObject, SchemaOverview types
+//! - api_contracts - ApiSchemaSummary, ApiContractDrift, ApiContractReport types
+//! - readme - ReadmeOptions, ReadmeResult types
+//! - stack_preset - StackPreset, StarterSkill types
+//! - data_purge - PurgeScope, PurgeCount, PurgeReport types
+//! - doc_proposal - DocProposal, DocProposalResult types
+//! - git_policy - GitPolicy, GitPermission, GitOperation, GitPermissionRequest types
+//! - ai_queue - AiJob, AiJobKind, AiPriority, AiQueueStatus types
+//! - claude_md - ClaudeMdEdit type
+//! - automation - AutomationRule, RuleCondition, RuleAction, RuleSimulation, RuleRun types
+//! - logs - LogEntry, AppLogs types
+//! - doctor - DoctorCheck, DoctorReport types
+//! - control - ControlEndpoint, ControlRequest wire types for jumpstart-cli
+//! - policy - PolicyPack, PolicyCheck, PolicyComplianceReport types
+//! - claude_backup - ClaudeDirBackup type
+//! - ai_limits - AiTokenLimits type
+//! - plugin - AnalyzerPlugin, PluginAnalysis, PluginScanReport types
+//! - sync - SyncRemote, SyncConfig, SyncSnapshot, SyncReport types
+//! - ai_audit - AiAuditEntry, AiAuditRange types
+//! - digest - DigestStats, WeeklyDigest types
+//! - bootstrap - BootstrapCluster, BootstrapEstimate, BootstrapRun types
+//! - pr_health - DocCoverage, PrStaleFile, PrTestPlanStatus, PrHealthReport types
+//! - git_forge - ForgeProvider, ForgeConfig, DetectedForge types
+//! - convention - ConventionKind, Convention, ConventionViolation, ConventionReport types
+//! - health_alert - HealthAlertThresholds, HealthAlertMetric, HealthAlert types
 //!
 //! PATTERNS:
 //! - All models derive Serialize, Deserialize for Tauri IPC
@@ -37,3 +70,36 @@ pub mod test_plan;
 pub mod team_template;
 pub mod memory;
 pub mod performance;
+pub mod version;
+pub mod activity;
+pub mod monitor;
+pub mod shared_db;
+pub mod import;
+pub mod slash_command;
+pub mod health_history;
+pub mod command_guard;
+pub mod sql_schema;
+pub mod api_contracts;
+pub mod readme;
+pub mod stack_preset;
+pub mod data_purge;
+pub mod doc_proposal;
+pub mod git_policy;
+pub mod ai_queue;
+pub mod claude_md;
+pub mod automation;
+pub mod logs;
+pub mod doctor;
+pub mod control;
+pub mod policy;
+pub mod claude_backup;
+pub mod ai_limits;
+pub mod plugin;
+pub mod sync;
+pub mod ai_audit;
+pub mod digest;
+pub mod bootstrap;
+pub mod pr_health;
+pub mod git_forge;
+pub mod convention;
+pub mod health_alert;
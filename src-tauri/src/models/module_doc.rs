@@ -3,22 +3,42 @@
 //!
 //! PURPOSE:
 //! - Define ModuleStatus for tracking documentation state per file
+//! - Define ChangeAuthor for routing stale-doc work to whoever changed the code
+//! - Define ModuleQuality for the composite per-file quality score
 //! - Define ModuleDoc for documentation content
+//! - Define ExportSyncResult for in-place EXPORTS section updates
+//! - Define DocFileValidation/DocValidationReport for DEPENDENCIES/EXPORTS lint results
+//! - Define MergeDriftFile/MergeDriftReport for post-merge doc drift scans
+//! - Define FreshnessPolicy for per-directory doc update deadlines
 //!
 //! DEPENDENCIES:
 //! - serde - Serialization for Tauri IPC
 //!
 //! EXPORTS:
 //! - ModuleStatus - Documentation status for a single file
+//! - ChangeAuthor - An author's commits and lines changed since a file's doc header last changed
+//! - ModuleQuality - Composite quality score (freshness, doc lint, test linkage, churn) for a file
 //! - ModuleDoc - Parsed documentation header content
+//! - ExportSyncResult - Exports added/removed by an EXPORTS section sync
+//! - DocFileValidation - Phantom and undocumented dependencies/exports for one file
+//! - DocValidationReport - Project totals plus files with findings
+//! - MergeDriftFile - A file whose code changed in a merge without a header change
+//! - MergeDriftReport - Merges scanned and files newly found drifted
+//! - FreshnessPolicy - Glob plus the days a matching file's header may lag its code
 //!
 //! PATTERNS:
-//! - Status is one of: "current", "outdated", "missing"
+//! - Status is one of: "current", "outdated", "missing", "ignored"
+//!   ("ignored" = the file opts out with a `jumpstart:ignore` marker)
 //! - Freshness score is 0-100
+//! - ModuleQuality.score is 0-100 and the sum of its four component scores
 //!
 //! CLAUDE NOTES:
 //! - Keep in sync with TypeScript types in src/types/module.ts
 //! - changes field lists what has changed since docs were last updated
+//! - quality is only filled by scan_modules (None from other producers of ModuleStatus)
+//! - change_authors is only filled by get_stale_files, for outdated files in a git repo
+//! - analyzer is filled by project scans for files handled by a plugin (core::plugins)
+//! - FreshnessPolicy patterns use the .claude/ai-exclude glob syntax ("src/core/**")
 
 use serde::{Deserialize, Serialize};
 
@@ -30,6 +50,49 @@ pub struct ModuleStatus {
     pub freshness_score: u32,
     pub changes: Option<Vec<String>>,
     pub suggested_doc: Option<ModuleDoc>,
+    #[serde(default)]
+    pub quality: Option<ModuleQuality>,
+    /// Name of the analyzer plugin that handled the file (None for built-in languages)
+    #[serde(default)]
+    pub analyzer: Option<String>,
+    /// Who changed the code since the doc header last changed (outdated files from get_stale_files)
+    #[serde(default)]
+    pub change_authors: Option<Vec<ChangeAuthor>>,
+}
+
+/// One author's commits to a file since its doc header last changed, from git log.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ChangeAuthor {
+    pub name: String,
+    pub email: String,
+    pub commits: u32,
+    pub lines_added: u32,
+    pub lines_removed: u32,
+}
+
+impl ChangeAuthor {
+    /// Lines touched (added + removed), used to rank who should update the docs.
+    pub fn lines_changed(&self) -> u32 {
+        self.lines_added + self.lines_removed
+    }
+}
+
+/// Composite quality score for one file, used to sort "worst files first".
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ModuleQuality {
+    pub score: u32,
+    pub freshness_score: u32,
+    pub lint_score: u32,
+    pub test_score: u32,
+    pub churn_score: u32,
+    /// Phantom/undocumented DEPENDENCIES and EXPORTS entries in the header
+    pub lint_violations: u32,
+    /// A test file (or inline test module) exists for the file, or it is a test itself
+    pub has_tests: bool,
+    /// Commits touching the file in the churn window
+    pub recent_commits: u32,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -43,3 +106,93 @@ pub struct ModuleDoc {
     pub patterns: Vec<String>,
     pub claude_notes: Vec<String>,
 }
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ExportSyncResult {
+    pub file_path: String,
+    /// Export entries appended to the EXPORTS section
+    pub added: Vec<String>,
+    /// Documented exports that no longer exist in code
+    pub removed: Vec<String>,
+    /// Whether the file was rewritten
+    pub updated: bool,
+}
+
+/// DEPENDENCIES/EXPORTS findings for one documented file.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct DocFileValidation {
+    pub path: String,
+    /// Freshness status of the file ("current" | "outdated")
+    pub status: String,
+    /// Documented project-local dependencies that are not imported
+    pub phantom_dependencies: Vec<String>,
+    /// Detected imports not listed in DEPENDENCIES
+    pub undocumented_dependencies: Vec<String>,
+    /// Documented exports that no longer exist in code
+    pub phantom_exports: Vec<String>,
+    /// Detected exports not listed in EXPORTS
+    pub undocumented_exports: Vec<String>,
+    /// Lint findings from the file's analyzer plugin ("<plugin>: <message> (line N)")
+    #[serde(default)]
+    pub plugin_findings: Vec<String>,
+}
+
+impl DocFileValidation {
+    pub fn issue_count(&self) -> usize {
+        self.phantom_dependencies.len()
+            + self.undocumented_dependencies.len()
+            + self.phantom_exports.len()
+            + self.undocumented_exports.len()
+            + self.plugin_findings.len()
+    }
+}
+
+/// Doc lint results for a project: totals plus the files with findings.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct DocValidationReport {
+    pub project_id: String,
+    pub files_checked: u32,
+    pub files_with_issues: u32,
+    pub phantom_dependencies: u32,
+    pub undocumented_dependencies: u32,
+    pub phantom_exports: u32,
+    pub undocumented_exports: u32,
+    #[serde(default)]
+    pub plugin_findings: u32,
+    /// Files with at least one finding, outdated first
+    pub files: Vec<DocFileValidation>,
+}
+
+/// A documented file whose code changed in a merge while its header did not.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct MergeDriftFile {
+    /// Project-relative path
+    pub path: String,
+    /// Short hash of the merge result the drift was found in
+    pub merge_commit: String,
+    pub detected_at: String,
+}
+
+/// Result of a post-merge drift scan.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct MergeDriftReport {
+    pub project_id: String,
+    /// Merge ranges diffed (hook-recorded ranges, or the latest merge)
+    pub merges_scanned: u32,
+    pub drifted: Vec<MergeDriftFile>,
+}
+
+/// A per-directory freshness rule: headers of files matching `pattern` must be updated
+/// within `max_days` of a committed code change.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct FreshnessPolicy {
+    /// Project-relative glob, e.g. "src/core/**"
+    pub pattern: String,
+    pub max_days: u32,
+}
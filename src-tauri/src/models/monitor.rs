@@ -0,0 +1,28 @@
+//! @module models/monitor
+//! @description Data models for detachable monitor windows
+//!
+//! PURPOSE:
+//! - Define MonitorWindow describing an open loop/test monitor window
+//!
+//! DEPENDENCIES:
+//! - serde - Serialization for Tauri IPC
+//!
+//! EXPORTS:
+//! - MonitorWindow - Label, kind, and target of a monitor window
+//!
+//! PATTERNS:
+//! - kind is "ralph" (target_id = loop id) or "test_plan" (target_id = plan id)
+//!
+//! CLAUDE NOTES:
+//! - Keep in sync with TypeScript types in src/types/
+//! - Progress event payloads live in core/monitor.rs
+
+use serde::{Deserialize, Serialize};
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct MonitorWindow {
+    pub label: String,
+    pub kind: String,
+    pub target_id: String,
+}
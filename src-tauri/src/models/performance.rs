@@ -7,6 +7,7 @@
 //! - Define PerformanceIssue for code-level findings
 //! - Define ArchitectureFinding for architecture-level analysis
 //! - Define RemediationResult for per-issue fix results
+//! - Define the app database query-plan audit (QueryPlanCheck, DbPerformanceReport)
 //!
 //! DEPENDENCIES:
 //! - serde - Serialization for Tauri IPC
@@ -17,6 +18,8 @@
 //! - PerformanceIssue - Individual code-level performance issue
 //! - ArchitectureFinding - Architecture-level finding with status
 //! - RemediationResult - Result of auto-fixing a single performance issue
+//! - QueryPlanCheck - EXPLAIN QUERY PLAN result for one hot query in the app database
+//! - DbPerformanceReport - Query plan checks, missing indexes, and database size
 //!
 //! PATTERNS:
 //! - All structs derive Clone, Debug, Serialize, Deserialize
@@ -84,3 +87,29 @@ pub struct RemediationResult {
     pub status: String,
     pub message: String,
 }
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct QueryPlanCheck {
+    pub name: String,
+    pub sql: String,
+    /// EXPLAIN QUERY PLAN detail lines
+    pub plan: Vec<String>,
+    /// Tables read with a full scan
+    pub full_scans: Vec<String>,
+    /// ORDER BY needs a temporary b-tree sort
+    pub temp_sort: bool,
+    pub indexes_used: Vec<String>,
+    /// No full scans and no temporary sort
+    pub ok: bool,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct DbPerformanceReport {
+    pub queries: Vec<QueryPlanCheck>,
+    /// CREATE INDEX statements for expected indexes that do not exist
+    pub missing_indexes: Vec<String>,
+    pub slow_queries: u32,
+    pub db_size_bytes: u64,
+}
@@ -0,0 +1,114 @@
+//! @module models/plugin
+//! @description Data models for custom analyzer plugins and their results
+//!
+//! PURPOSE:
+//! - Describe an analyzer plugin manifest (plugin.json) discovered in the plugins directory
+//! - Carry a plugin's analysis of one file: exports, imports, lint findings, freshness signals
+//!
+//! DEPENDENCIES:
+//! - serde - Serialization for Tauri IPC and the plugin wire protocol
+//!
+//! EXPORTS:
+//! - AnalyzerPlugin - A validated plugin manifest plus the directory it was loaded from
+//! - PluginCapability - What a plugin contributes (exports, imports, lint, freshness)
+//! - PluginFinding - One lint finding or freshness signal reported by a plugin
+//! - PluginAnalysis - A plugin's response for one file
+//! - PluginScanReport - Loaded plugins and the manifests that were rejected
+//!
+//! PATTERNS:
+//! - Manifests and responses use camelCase JSON; every response field is optional
+//!
+//! CLAUDE NOTES:
+//! - Keep in sync with TypeScript types in src/types/plugin.ts
+//! - The request/response protocol itself is documented in core::plugins
+
+use serde::{Deserialize, Serialize};
+
+/// A custom analyzer, loaded from `<plugins dir>/<name>/plugin.json`.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct AnalyzerPlugin {
+    pub name: String,
+    #[serde(default)]
+    pub version: String,
+    #[serde(default)]
+    pub description: String,
+    /// File extensions handled, with the leading dot (".acme")
+    pub extensions: Vec<String>,
+    /// Executable to run; relative paths resolve against the plugin directory
+    pub command: String,
+    #[serde(default)]
+    pub args: Vec<String>,
+    #[serde(default = "default_capabilities")]
+    pub capabilities: Vec<PluginCapability>,
+    /// Line-comment marker used for doc headers in handled files: "#", "//" or "--"
+    #[serde(default = "default_comment_prefix")]
+    pub comment_prefix: String,
+    #[serde(default = "default_timeout_ms")]
+    pub timeout_ms: u64,
+    /// Directory the manifest was loaded from (set on load, not read from the manifest)
+    #[serde(default)]
+    pub dir: String,
+}
+
+fn default_capabilities() -> Vec<PluginCapability> {
+    vec![PluginCapability::Exports, PluginCapability::Imports]
+}
+
+fn default_comment_prefix() -> String {
+    "#".to_string()
+}
+
+fn default_timeout_ms() -> u64 {
+    5_000
+}
+
+impl AnalyzerPlugin {
+    pub fn has(&self, capability: PluginCapability) -> bool {
+        self.capabilities.contains(&capability)
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum PluginCapability {
+    /// Exported symbols, used for EXPORTS generation, sync, and freshness
+    Exports,
+    /// Imported modules, used for DEPENDENCIES generation and freshness
+    Imports,
+    /// Doc lint findings reported alongside DEPENDENCIES/EXPORTS validation
+    Lint,
+    /// Extra staleness signals that lower the file's freshness score
+    Freshness,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct PluginFinding {
+    pub message: String,
+    /// 1-based line the finding refers to
+    #[serde(default)]
+    pub line: Option<u32>,
+    /// Freshness penalty (freshness signals only; clamped to 0-50)
+    #[serde(default)]
+    pub weight: u32,
+}
+
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase", default)]
+pub struct PluginAnalysis {
+    pub exports: Vec<String>,
+    pub imports: Vec<String>,
+    pub lint: Vec<PluginFinding>,
+    pub freshness: Vec<PluginFinding>,
+}
+
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct PluginScanReport {
+    /// Directory plugins were discovered in
+    pub plugins_dir: String,
+    pub plugins: Vec<AnalyzerPlugin>,
+    /// One message per rejected manifest ("<dir>: <reason>")
+    pub errors: Vec<String>,
+}
@@ -0,0 +1,67 @@
+//! @module models/policy
+//! @description Data models for organization policy packs and compliance audits
+//!
+//! PURPOSE:
+//! - Define PolicyPack, a shareable set of required enforcement settings
+//! - Define the per-requirement check and full compliance report of an audit
+//!
+//! DEPENDENCIES:
+//! - serde - Serialization for Tauri IPC and pack files
+//!
+//! EXPORTS:
+//! - PolicyPack - Required hook mode, CI check, doc coverage, and protected paths
+//! - PolicyCheck - One requirement compared against the project
+//! - PolicyComplianceReport - Every check of a project's assigned pack
+//!
+//! PATTERNS:
+//! - Pack files are JSON PolicyPack objects; unset requirements are not enforced
+//! - PolicyCheck.requirement: "hook_mode" | "ci_check" | "doc_coverage" | "protected_paths"
+//!
+//! CLAUDE NOTES:
+//! - Keep in sync with TypeScript types in src/types/policy.ts
+//! - Protected paths are enforced through .claude/ai-exclude (never sent to AI)
+
+use serde::{Deserialize, Serialize};
+
+/// A set of enforcement settings a platform team requires across projects.
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase", default)]
+pub struct PolicyPack {
+    /// Stable id from the pack file; packs with the same id replace each other on import
+    pub id: String,
+    pub name: String,
+    pub description: String,
+    pub version: String,
+    /// Required pre-commit hook mode: "block" | "warn" | "auto-update"
+    pub hook_mode: Option<String>,
+    /// Require the Project Jumpstart doc check in GitHub Actions, GitLab CI, or Bitbucket Pipelines
+    pub require_ci_check: bool,
+    /// Minimum documented files / total files (0-100)
+    pub min_doc_coverage: Option<f64>,
+    /// Globs that must be listed in .claude/ai-exclude
+    pub protected_paths: Vec<String>,
+}
+
+/// One requirement of a pack compared against a project.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct PolicyCheck {
+    pub requirement: String,
+    pub label: String,
+    pub compliant: bool,
+    pub expected: String,
+    pub actual: String,
+    /// Whether apply_policy_pack can bring this requirement into compliance
+    pub fixable: bool,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct PolicyComplianceReport {
+    pub project_id: String,
+    pub pack_id: String,
+    pub pack_name: String,
+    pub checks: Vec<PolicyCheck>,
+    pub compliant: bool,
+    pub audited_at: String,
+}
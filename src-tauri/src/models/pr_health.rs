@@ -0,0 +1,89 @@
+//! @module models/pr_health
+//! @description Data models for the Markdown health block embedded in pull request descriptions and comments
+//!
+//! PURPOSE:
+//! - Define doc coverage at one git revision
+//! - Define a changed file whose docs went stale in the PR
+//! - Define a test plan's latest run as shown in the block
+//! - Define the full PR health report with its rendered Markdown
+//!
+//! DEPENDENCIES:
+//! - serde - Serialization for Tauri IPC
+//!
+//! EXPORTS:
+//! - DocCoverage - Documented vs tracked source files at one revision
+//! - PrStaleFile - A changed file that is outdated at head but was not at base
+//! - PrTestPlanStatus - A test plan's latest run and how many of its cases touch changed files
+//! - PrHealthReport - Coverage delta, newly stale files, test plans, and the Markdown block
+//!
+//! PATTERNS:
+//! - Base values are taken at the merge base of base_ref and head_ref, like a PR diff
+//! - PrTestPlanStatus.last_run_status is a TestRunStatus string, None when the plan never ran
+//!
+//! CLAUDE NOTES:
+//! - Keep in sync with TypeScript types in src/types/
+//! - Paths are project-relative with "/" separators
+
+use serde::{Deserialize, Serialize};
+
+#[derive(Debug, Clone, Copy, Default, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct DocCoverage {
+    pub documented: u32,
+    pub total: u32,
+}
+
+impl DocCoverage {
+    /// Documented share in percent (100 when there is nothing to document).
+    pub fn percent(&self) -> f64 {
+        if self.total == 0 {
+            100.0
+        } else {
+            self.documented as f64 * 100.0 / self.total as f64
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct PrStaleFile {
+    pub path: String,
+    /// Freshness score at head
+    pub score: u32,
+    pub changes: Vec<String>,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct PrTestPlanStatus {
+    pub plan_id: String,
+    pub name: String,
+    pub last_run_status: Option<String>,
+    pub last_run_at: Option<String>,
+    pub total_tests: u32,
+    pub passed_tests: u32,
+    pub failed_tests: u32,
+    pub coverage_percent: Option<f64>,
+    /// Test cases whose file is among the PR's changed files
+    pub cases_for_changed_files: u32,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct PrHealthReport {
+    pub project_id: String,
+    pub base_ref: String,
+    pub head_ref: String,
+    /// Merge base of base_ref and head_ref (full hash)
+    pub base_commit: String,
+    pub head_commit: String,
+    pub changed_files: u32,
+    pub base_coverage: DocCoverage,
+    pub head_coverage: DocCoverage,
+    pub newly_stale: Vec<PrStaleFile>,
+    /// Changed files that are tracked at head but have no doc header
+    pub undocumented: Vec<String>,
+    pub test_plans: Vec<PrTestPlanStatus>,
+    /// The block to post, starting with pr_health::COMMENT_MARKER
+    pub markdown: String,
+}
@@ -10,6 +10,7 @@
 //! DEPENDENCIES:
 //! - serde - Serialization for Tauri IPC
 //! - chrono - Timestamp handling
+//! - models::health_history - DocGoalsReport attached to HealthScore
 //!
 //! EXPORTS:
 //! - StackExtras - Additional services configuration (auth, hosting, payments, etc.)
@@ -19,7 +20,11 @@
 //! - QuickWin - Prioritized improvement suggestion
 //! - DetectionResult - Full auto-detection output from project scanning
 //! - DetectedValue - A detected value with confidence and source
+//! - SourceLayout - Source directories, extensions, and monorepo path filters of a project
 //! - ProjectSetup - Configuration collected during onboarding
+//! - ProjectRelocation - Project after a path change and how many stored paths were rewritten
+//! - ProjectMerge - Target project after a merge and how many rows moved or were dropped
+//! - RemoteSync - Local mirror path of a remote project and when it was synced
 //!
 //! PATTERNS:
 //! - All structs derive Clone, Debug, Serialize, Deserialize
@@ -35,6 +40,8 @@
 use chrono::{DateTime, Utc};
 use serde::{Deserialize, Serialize};
 
+use crate::models::health_history::DocGoalsReport;
+
 /// Additional services configuration (auth, hosting, payments, etc.)
 #[derive(Debug, Clone, Serialize, Deserialize, Default)]
 #[serde(rename_all = "camelCase")]
@@ -69,6 +76,45 @@ pub struct Project {
     pub stack_extras: Option<StackExtras>,
     pub health_score: u32,
     pub created_at: DateTime<Utc>,
+    /// "local" or "ssh"; for ssh projects `path` is the local mirror of remote_host:remote_path
+    pub location_type: String,
+    pub remote_host: Option<String>,
+    pub remote_path: Option<String>,
+    /// npm, pnpm, yarn, bun, uv, poetry, pipenv, or pip; None means detect from the project files
+    #[serde(default)]
+    pub package_manager: Option<String>,
+}
+
+/// Result of mirroring a remote project locally.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct RemoteSync {
+    /// Local mirror directory (use as the project path)
+    pub mirror_path: String,
+    pub remote: String,
+    pub synced_at: String,
+}
+
+/// Result of moving a project to a new path on disk.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ProjectRelocation {
+    pub project: Project,
+    pub old_path: String,
+    /// Stored file paths (module docs, events, test files, learnings) rewritten to the new root
+    pub rewritten_paths: u32,
+}
+
+/// Result of merging a duplicate project registration into another.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ProjectMerge {
+    pub project: Project,
+    /// Rows (skills, loops, activities, ...) moved from the source project
+    pub moved_rows: u32,
+    /// Source rows dropped because the target already had an equivalent (e.g. same approved command)
+    pub dropped_rows: u32,
+    pub rewritten_paths: u32,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -80,6 +126,12 @@ pub struct HealthScore {
     pub context_rot_risk: String,
     #[serde(skip_serializing_if = "Option::is_none")]
     pub discovered_test_count: Option<u32>,
+    /// Doc goal results when the project has goals set
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub goals: Option<DocGoalsReport>,
+    /// Violations found by the last convention check, when one has run
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub convention_violations: Option<u32>,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -113,8 +165,13 @@ pub struct DetectionResult {
     pub database: Option<DetectedValue>,
     pub testing: Option<DetectedValue>,
     pub styling: Option<DetectedValue>,
+    /// npm, pnpm, yarn, bun, uv, poetry, or pipenv (see core::package_manager)
+    #[serde(default)]
+    pub package_manager: Option<DetectedValue>,
     pub project_name: Option<String>,
     pub project_type: Option<String>,
+    /// Layout archetype used for template doc inference (see core::scanner::Archetype)
+    pub archetype: String,
     pub file_count: u32,
     pub has_existing_claude_md: bool,
 }
@@ -127,6 +184,18 @@ pub struct DetectedValue {
     pub source: String,
 }
 
+/// Where a project's sources live, as used by the CI doc-check snippets
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct SourceLayout {
+    /// Project-relative directories holding source files ("." for a flat layout)
+    pub source_dirs: Vec<String>,
+    /// Source extensions present, without the dot, most common first
+    pub extensions: Vec<String>,
+    /// Monorepo workspace globs for CI path filters ("packages/**"); empty for single-package repos
+    pub path_filters: Vec<String>,
+}
+
 /// Configuration collected during onboarding wizard
 #[derive(Debug, Clone, Serialize, Deserialize)]
 #[serde(rename_all = "camelCase")]
@@ -144,4 +213,13 @@ pub struct ProjectSetup {
     pub goals: Vec<String>,
     pub generate_module_docs: bool,
     pub setup_enforcement: bool,
+    /// ssh host for a remote project (path is then the mirror from prepare_remote_project)
+    #[serde(default)]
+    pub remote_host: Option<String>,
+    /// Project directory on remote_host
+    #[serde(default)]
+    pub remote_path: Option<String>,
+    /// Package manager from the scan (or chosen by the user)
+    #[serde(default)]
+    pub package_manager: Option<String>,
 }
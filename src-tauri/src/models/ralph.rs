@@ -13,27 +13,55 @@
 //! EXPORTS:
 //! - RalphLoop - A RALPH loop execution record
 //! - PromptAnalysis - Quality analysis result for a prompt
-//! - PromptCriterion - Individual scored criterion (clarity, specificity, context, scope)
+//! - PromptCriterion - Individual scored criterion (clarity, specificity, context, scope, or custom)
+//! - QuickPromptAnalysis - Score and top suggestion for live feedback while typing
+//! - PromptCriteriaConfig - Team-defined extra criteria and keywords for heuristic prompt scoring
+//! - CustomPromptCriterion - A named criterion scored by keyword matches
+//! - PromptKeywordExtras - Extra (e.g. non-English) keywords for the four built-in criteria
+//! - PromptAnalysisRecord - A saved prompt analysis with its prompt text
 //! - RalphMistake - A recorded mistake from a RALPH loop for learning
 //! - RalphLoopContext - Context data (CLAUDE.md summary, mistakes, patterns) for enhanced analysis
 //! - PrdStory - A single story/task in a PRD file
 //! - PrdFile - Full PRD document with metadata and stories
+//! - ChangelogResult - Generated CHANGELOG.md fragment from completed loops
+//! - RalphTemplate - Reusable loop configuration (prompt skeleton, tools, gates, budget, branch)
+//! - RalphIteration - One Claude run of a loop with its outcome and the files it changed
+//! - RalphIterationFile - A file changed by an iteration (path and change kind)
+//! - RalphPreflightCheck - One preflight check (CLI, login, project, API key, disk space)
+//! - RalphPreflight - Preflight checklist run before starting a loop
+//! - PatchProposal - AI-proposed unified diff for one located loop issue, applied on review
+//! - FileSuggestion - A project file ranked as relevant to a prompt, with why
+//! - WorkspaceRun - A PRD run whose stories span several registered projects
+//! - WorkspaceRunStep - The consecutive stories of a workspace run that share a project
 //!
 //! PATTERNS:
-//! - RalphLoop status: "idle" | "running" | "paused" | "completed" | "failed"
+//! - RalphLoop status: "idle" | "running" | "paused" | "needs_review" | "completed" | "failed"
 //! - RalphLoop mode: "iterative" (default) | "prd" (PRD-driven fresh context per story)
 //! - PromptAnalysis quality_score is 0-100
-//! - Each PromptCriterion scores 0-25 (four criteria sum to 100 max)
+//! - Each built-in PromptCriterion scores 0-25; custom criteria score 0-weight, and
+//!   quality_score is the total normalized to 100
 //!
 //! CLAUDE NOTES:
 //! - RALPH = Review, Analyze, List, Plan, Handoff (our interpretation)
 //! - Original "Ralph" is named after Ralph Wiggum from The Simpsons
 //! - PRD mode: fresh context per story, git commits between, like original Ralph
+//! - Workspace runs: PrdStory.project picks the repository; each step is an ordinary PRD loop
+//!   whose PrdFile.cross_repo_context summarizes the steps before it
 //! - Iterative mode: accumulated context with AI-powered issue extraction
 //! - Keep in sync with TypeScript types in src/types/ralph.ts
-//! - Loop status transitions: idle -> running -> paused/completed/failed
+//! - Loop status transitions: idle -> running -> paused/needs_review/completed/failed;
+//!   needs_review is set automatically after a destructive iteration and resumes like paused
 //! - RalphMistake.mistake_type: "implementation" | "logic" | "scope" | "testing" | "other"
 //! - RalphLoopContext is returned by get_ralph_context for enhanced AI analysis
+//! - ChangelogResult.written_path is only set when the fragment was written to CHANGELOG.md
+//! - RalphTemplate.branch_strategy: "current" | "new_branch"; variables are the {{name}}
+//!   placeholders found in prompt_skeleton
+//! - RalphIteration.status: "passed" | "issues" | "failed" | "needs_review"; story_index is set in PRD mode
+//! - RalphIterationFile.change: "added" | "modified" | "deleted"
+//! - RalphPreflightCheck.status: "pass" | "warn" | "fail" | "skipped"; RalphPreflight.ready is
+//!   false when any check failed
+//! - PatchProposal.status: "pending" | "conflict" | "applied" | "rejected"; conflict means
+//!   `git apply --check` failed and error holds its output
 
 use serde::{Deserialize, Serialize};
 
@@ -59,6 +87,21 @@ pub struct RalphLoop {
     pub current_story: Option<u32>,
     /// Total stories for PRD mode
     pub total_stories: Option<u32>,
+    /// Learned patterns/mistakes prepended to the prompt at execution time (iterative mode)
+    #[serde(default)]
+    pub injected_patterns: Option<Vec<String>>,
+    /// Diff of the destructive change that put the loop in "needs_review"
+    #[serde(default)]
+    pub review_diff: Option<String>,
+    /// Loop this one follows up on (create_followup_loop)
+    #[serde(default)]
+    pub parent_loop_id: Option<String>,
+    /// Estimated PRD finish time (RFC 3339), updated after each finished story
+    #[serde(default)]
+    pub eta_at: Option<String>,
+    /// Mean duration of recently finished PRD stories, in seconds
+    #[serde(default)]
+    pub avg_story_secs: Option<u32>,
 }
 
 fn default_mode() -> String {
@@ -83,6 +126,74 @@ pub struct PromptCriterion {
     pub feedback: String,
 }
 
+/// Live score shown while typing a prompt (the full breakdown is PromptAnalysis).
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct QuickPromptAnalysis {
+    pub quality_score: u32,
+    /// Suggestion for the weakest criterion; None when no criterion needs one
+    pub top_suggestion: Option<String>,
+}
+
+/// A saved prompt analysis from the RALPH prompt editor
+/// Team configuration for heuristic prompt scoring, stored as JSON in the settings table.
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase", default)]
+pub struct PromptCriteriaConfig {
+    /// Criteria scored in addition to clarity, specificity, context, and scope
+    pub custom_criteria: Vec<CustomPromptCriterion>,
+    /// Keywords counted alongside the built-in English keyword lists
+    pub extra_keywords: PromptKeywordExtras,
+}
+
+/// A team-defined scoring criterion (e.g. "Security considerations").
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct CustomPromptCriterion {
+    pub name: String,
+    /// Case-insensitive phrases; two or more matches earn the full weight
+    pub keywords: Vec<String>,
+    /// Maximum score before normalization (built-in criteria are worth 25)
+    #[serde(default = "default_criterion_weight")]
+    pub weight: u32,
+    /// Suggestion shown when the prompt does not address the criterion
+    #[serde(default)]
+    pub suggestion: Option<String>,
+}
+
+fn default_criterion_weight() -> u32 {
+    25
+}
+
+/// Extra keywords for the built-in criteria, e.g. action verbs in the team's language.
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase", default)]
+pub struct PromptKeywordExtras {
+    /// Action verbs
+    pub clarity: Vec<String>,
+    /// Technical terms
+    pub specificity: Vec<String>,
+    /// Background and motivation phrases
+    pub context: Vec<String>,
+    /// Boundary phrases
+    pub scope: Vec<String>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct PromptAnalysisRecord {
+    pub id: String,
+    pub project_id: Option<String>,
+    pub prompt: String,
+    pub quality_score: u32,
+    pub criteria: Vec<PromptCriterion>,
+    pub suggestions: Vec<String>,
+    pub enhanced_prompt: Option<String>,
+    /// "heuristic" or "ai"
+    pub source: String,
+    pub created_at: String,
+}
+
 #[derive(Debug, Clone, Serialize, Deserialize)]
 #[serde(rename_all = "camelCase")]
 pub struct RalphMistake {
@@ -125,6 +236,12 @@ pub struct PrdStory {
     pub completed: bool,
     /// Git commit hash when completed (if any)
     pub commit_hash: Option<String>,
+    /// Ids of stories that must be completed before this one runs
+    #[serde(default)]
+    pub depends_on: Vec<String>,
+    /// Registered project (id or name) the story runs in; only used by workspace runs
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub project: Option<String>,
 }
 
 fn default_priority() -> u32 {
@@ -151,6 +268,12 @@ pub struct PrdFile {
     pub max_iterations_per_story: u32,
     /// List of stories to implement
     pub stories: Vec<PrdStory>,
+    /// What earlier steps of a workspace run changed in other repositories; added to every story prompt
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub cross_repo_context: Option<String>,
+    /// Wall-clock limit for the run; no story or story iteration starts once it has passed
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub max_duration_minutes: Option<u32>,
 }
 
 fn default_branch() -> String {
@@ -160,3 +283,158 @@ fn default_branch() -> String {
 fn default_max_iterations() -> u32 {
     3
 }
+
+/// Result of generating a changelog fragment from RALPH/PRD history
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ChangelogResult {
+    /// Markdown fragment in Keep a Changelog format
+    pub content: String,
+    /// Number of entries included in the fragment
+    pub entry_count: u32,
+    /// Path of CHANGELOG.md if the fragment was written to the repo
+    pub written_path: Option<String>,
+}
+
+/// A reusable RALPH loop configuration, instantiated with start_ralph_loop_from_template.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct RalphTemplate {
+    pub id: String,
+    pub project_id: Option<String>,
+    pub name: String,
+    pub description: String,
+    /// Prompt with {{variable}} placeholders
+    pub prompt_skeleton: String,
+    /// Placeholder names in prompt_skeleton, in order of first use
+    pub variables: Vec<String>,
+    /// Claude --allowedTools entries (empty = loop defaults)
+    pub allowed_tools: Vec<String>,
+    /// Commands that must pass before the loop completes (no shell syntax)
+    pub acceptance_gates: Vec<String>,
+    /// Iteration budget
+    pub max_iterations: u32,
+    /// "current" (work on the checked-out branch) or "new_branch"
+    pub branch_strategy: String,
+    pub usage_count: u32,
+    pub created_at: String,
+    pub updated_at: String,
+}
+
+/// A file changed during one RALPH iteration.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct RalphIterationFile {
+    /// Path relative to the project root
+    pub path: String,
+    /// "added" | "modified" | "deleted"
+    pub change: String,
+}
+
+/// One Claude run of a RALPH loop, for the per-iteration timeline.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct RalphIteration {
+    pub id: String,
+    pub loop_id: String,
+    /// 1-based iteration number (per story in PRD mode)
+    pub iteration: u32,
+    /// PRD mode: 0-indexed story the iteration worked on
+    pub story_index: Option<u32>,
+    /// "passed" | "issues" | "failed" | "needs_review"
+    pub status: String,
+    /// Short outcome text (issues found, validation result, or error)
+    pub summary: String,
+    pub issues_count: u32,
+    pub files_changed: Vec<RalphIterationFile>,
+    pub started_at: String,
+    pub completed_at: String,
+}
+
+/// One item of the RALPH preflight checklist.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct RalphPreflightCheck {
+    /// "claude_cli" | "claude_auth" | "project_path" | "git_repo" | "api_key" | "disk_space"
+    pub id: String,
+    pub label: String,
+    /// "pass" | "warn" | "fail" | "skipped"
+    pub status: String,
+    pub detail: String,
+    /// What to do when the check warns or fails
+    pub fix: Option<String>,
+}
+
+/// Result of check_ralph_prerequisites.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct RalphPreflight {
+    /// No check failed; warnings do not block a loop
+    pub ready: bool,
+    pub checks: Vec<RalphPreflightCheck>,
+}
+
+/// A proposed fix for one located issue of a RALPH loop, as a unified diff.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct PatchProposal {
+    pub id: String,
+    pub loop_id: String,
+    pub project_id: String,
+    /// ralph_mistakes row the issue came from (may have been pruned since)
+    pub mistake_id: Option<String>,
+    /// Project-relative path the diff touches
+    pub file_path: String,
+    pub line: Option<u32>,
+    pub issue: String,
+    pub diff: String,
+    pub explanation: String,
+    /// "pending" | "conflict" | "applied" | "rejected"
+    pub status: String,
+    pub error: Option<String>,
+    pub created_at: String,
+    pub applied_at: Option<String>,
+}
+
+/// A project file ranked as likely relevant to a prompt (see core::doc_index).
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct FileSuggestion {
+    /// Project-relative path
+    pub path: String,
+    /// 0-100
+    pub score: u32,
+    pub reasons: Vec<String>,
+}
+
+/// A workspace PRD run: stories spread over several registered projects, run one project
+/// step at a time, each step as a PRD loop in that project.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct WorkspaceRun {
+    pub id: String,
+    pub name: String,
+    /// "running" | "completed" | "failed" | "cancelled"
+    pub status: String,
+    /// Index into steps of the step running (or last run)
+    pub current_step: u32,
+    pub steps: Vec<WorkspaceRunStep>,
+    pub outcome: Option<String>,
+    pub started_at: String,
+    pub completed_at: Option<String>,
+}
+
+/// Consecutive stories of a workspace PRD that run in the same project.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct WorkspaceRunStep {
+    pub project_id: String,
+    pub project_name: String,
+    pub story_ids: Vec<String>,
+    /// PRD loop running the step, once started
+    pub loop_id: Option<String>,
+    /// "pending" | "running" | "completed" | "failed" | "skipped"
+    pub status: String,
+    /// What the step changed, as passed to later steps
+    pub summary: Option<String>,
+}
@@ -0,0 +1,67 @@
+//! @module models/readme
+//! @description Data models for README generation from module docs and CLAUDE.md
+//!
+//! PURPOSE:
+//! - Define ReadmeOptions selecting which parts of the generated section to include
+//! - Define ReadmeResult with the generated section, merged README, and a line diff
+//!
+//! DEPENDENCIES:
+//! - serde - Serialization for Tauri IPC
+//! - models::version - DiffLine for the README diff
+//!
+//! EXPORTS:
+//! - ReadmeOptions - Section toggles and whether to write the merged README
+//! - ReadmeResult - Generated section, current and merged README, diff, written path
+//!
+//! PATTERNS:
+//! - Every ReadmeOptions field is optional on the wire; sections default to included
+//!   and write defaults to false (preview only)
+//!
+//! CLAUDE NOTES:
+//! - Keep in sync with TypeScript types in src/types/
+
+use serde::{Deserialize, Serialize};
+
+use crate::models::version::DiffLine;
+
+/// Which parts of the README section to generate, and whether to write it.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase", default)]
+pub struct ReadmeOptions {
+    /// Overview and Architecture text from CLAUDE.md
+    pub include_architecture: bool,
+    /// Table of documented modules grouped by directory
+    pub include_module_map: bool,
+    /// Install/dev/build/test commands for the detected stack
+    pub include_setup: bool,
+    /// Write the merged README.md instead of only previewing it
+    pub write: bool,
+}
+
+impl Default for ReadmeOptions {
+    fn default() -> Self {
+        ReadmeOptions {
+            include_architecture: true,
+            include_module_map: true,
+            include_setup: true,
+            write: false,
+        }
+    }
+}
+
+/// Result of generating the README section.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ReadmeResult {
+    /// The generated section, including its start/end markers
+    pub section: String,
+    /// Current README.md content (None when the project has no README)
+    pub existing: Option<String>,
+    /// README.md content with the section merged in
+    pub merged: String,
+    /// Line diff from existing to merged
+    pub diff: Vec<DiffLine>,
+    pub has_changes: bool,
+    /// Path of README.md when options.write was set and the file changed
+    pub written_path: Option<String>,
+}
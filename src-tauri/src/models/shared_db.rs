@@ -0,0 +1,79 @@
+//! @module models/shared_db
+//! @description Data models for the team-shared database backend
+//!
+//! PURPOSE:
+//! - Define which backend holds team-shared knowledge (local only, libsql/Turso, Postgres)
+//! - Define the config shape returned to the settings UI
+//! - Define per-table sync results
+//!
+//! DEPENDENCIES:
+//! - serde - Serialization for Tauri IPC
+//!
+//! EXPORTS:
+//! - SharedBackendKind - local | libsql | postgres
+//! - SharedDbConfig - Backend kind, URL, token presence, last sync time
+//! - SharedTableSync - Rows pushed/pulled for one shared table
+//! - SharedSyncReport - Result of a full sync
+//!
+//! PATTERNS:
+//! - The auth token is never returned to the frontend; only has_auth_token
+//! - "local" means no shared backend: everything stays in the local SQLite file
+//!
+//! CLAUDE NOTES:
+//! - Keep in sync with TypeScript types in src/types/
+
+use serde::{Deserialize, Serialize};
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum SharedBackendKind {
+    Local,
+    Libsql,
+    Postgres,
+}
+
+impl SharedBackendKind {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            SharedBackendKind::Local => "local",
+            SharedBackendKind::Libsql => "libsql",
+            SharedBackendKind::Postgres => "postgres",
+        }
+    }
+
+    pub fn parse(s: &str) -> Option<SharedBackendKind> {
+        match s.trim().to_lowercase().as_str() {
+            "local" | "" => Some(SharedBackendKind::Local),
+            "libsql" | "turso" => Some(SharedBackendKind::Libsql),
+            "postgres" | "postgresql" => Some(SharedBackendKind::Postgres),
+            _ => None,
+        }
+    }
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct SharedDbConfig {
+    pub kind: SharedBackendKind,
+    pub url: Option<String>,
+    pub has_auth_token: bool,
+    pub last_synced_at: Option<String>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct SharedTableSync {
+    pub table: String,
+    pub pushed: u32,
+    pub pulled: u32,
+    /// Incoming rows held back for a decision (see list_import_conflicts)
+    pub conflicts: u32,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct SharedSyncReport {
+    pub backend: SharedBackendKind,
+    pub tables: Vec<SharedTableSync>,
+    pub synced_at: String,
+}
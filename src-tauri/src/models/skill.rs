@@ -4,6 +4,7 @@
 //! PURPOSE:
 //! - Define Skill struct for reusable Claude Code patterns
 //! - Define Pattern for detected request patterns
+//! - Define SkillConflict for skill/agent instructions that contradict CLAUDE.md
 //!
 //! DEPENDENCIES:
 //! - serde - Serialization for Tauri IPC
@@ -12,10 +13,13 @@
 //! EXPORTS:
 //! - Skill - A reusable Claude Code skill/pattern
 //! - Pattern - A detected recurring request pattern
+//! - SkillConflict - A skill or agent instruction that contradicts CLAUDE.md
 //!
 //! PATTERNS:
 //! - Skills have markdown content and usage analytics
+//! - Skill tags are stored as a JSON array in the skills.tags column
 //! - Patterns are detected from request history
+//! - SkillConflict.detected_by is "rule" or "ai"
 //!
 //! CLAUDE NOTES:
 //! - Skills reduce token usage by avoiding re-explanation
@@ -33,6 +37,9 @@ pub struct Skill {
     pub content: String,
     pub project_id: Option<String>,
     pub usage_count: u32,
+    /// Free-form labels for filtering and bulk curation
+    #[serde(default)]
+    pub tags: Vec<String>,
     pub created_at: DateTime<Utc>,
     pub updated_at: DateTime<Utc>,
 }
@@ -45,3 +52,21 @@ pub struct Pattern {
     pub frequency: u32,
     pub suggested_skill: Option<String>,
 }
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct SkillConflict {
+    /// "skill" | "agent"
+    pub source_kind: String,
+    pub source_name: String,
+    /// Project-relative file for deployed skills and agents; None for stored ones
+    pub source_path: Option<String>,
+    /// What the two disagree about, e.g. "package manager"
+    pub topic: String,
+    /// The CLAUDE.md line (or quote) on the topic
+    pub claude_md_says: String,
+    /// The conflicting line (or quote) from the skill or agent
+    pub source_says: String,
+    /// "rule" | "ai"
+    pub detected_by: String,
+}
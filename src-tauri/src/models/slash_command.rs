@@ -0,0 +1,49 @@
+//! @module models/slash_command
+//! @description Data models for Claude Code slash command deployment
+//!
+//! PURPOSE:
+//! - Define the per-item result of deploying slash commands
+//! - Define the drift status of a deployed slash command
+//!
+//! DEPENDENCIES:
+//! - serde - Serialization for Tauri IPC
+//!
+//! EXPORTS:
+//! - SlashCommandDeployResult - Outcome of deploying one skill or prompt
+//! - SlashCommandDrift - Sync state of one generated command file
+//!
+//! PATTERNS:
+//! - source_type is "skill" or "prompt"
+//! - Deploy status: "created" | "updated" | "unchanged" | "skipped"
+//! - Drift status: "in_sync" | "outdated" | "modified" | "orphaned"
+//!
+//! CLAUDE NOTES:
+//! - Keep in sync with TypeScript types in src/types/
+
+use serde::{Deserialize, Serialize};
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct SlashCommandDeployResult {
+    pub source_id: String,
+    pub source_type: Option<String>,
+    pub command_name: Option<String>,
+    pub file_path: Option<String>,
+    pub status: String,
+    /// Why the item was skipped (None otherwise)
+    pub message: Option<String>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct SlashCommandDrift {
+    pub command_name: String,
+    pub file_path: String,
+    pub source_type: String,
+    pub source_id: String,
+    /// Current name of the source skill/prompt (None when orphaned)
+    pub source_name: Option<String>,
+    /// "outdated": source changed since deploy; "modified": file edited in the project;
+    /// "orphaned": source deleted
+    pub status: String,
+}
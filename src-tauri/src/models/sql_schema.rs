@@ -0,0 +1,52 @@
+//! @module models/sql_schema
+//! @description Data models for the database schema summarized from a project's SQL files
+//!
+//! PURPOSE:
+//! - Define SchemaTable for a table and its columns after all migrations are applied
+//! - Define SchemaObject for views, functions, and procedures
+//! - Define SchemaOverview returned by generate_schema_overview
+//!
+//! DEPENDENCIES:
+//! - serde - Serialization for Tauri IPC
+//!
+//! EXPORTS:
+//! - SchemaTable - Table name, column names, and the file that created it
+//! - SchemaObject - Non-table schema object (kind is "view", "function", or "procedure")
+//! - SchemaOverview - Tables, objects, rendered markdown, and whether CLAUDE.md was updated
+//!
+//! PATTERNS:
+//! - source_file is project-relative with forward slashes
+//!
+//! CLAUDE NOTES:
+//! - Keep in sync with TypeScript types in src/types/module.ts
+
+use serde::{Deserialize, Serialize};
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct SchemaTable {
+    pub name: String,
+    pub columns: Vec<String>,
+    pub source_file: String,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct SchemaObject {
+    pub kind: String,
+    pub name: String,
+    pub source_file: String,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct SchemaOverview {
+    pub tables: Vec<SchemaTable>,
+    pub objects: Vec<SchemaObject>,
+    /// Number of .sql files read
+    pub source_files: usize,
+    /// The "### Database Schema" subsection written to CLAUDE.md
+    pub markdown: String,
+    /// True only when CLAUDE.md was rewritten (false without one, or when it was already current)
+    pub updated_claude_md: bool,
+}
@@ -0,0 +1,54 @@
+//! @module models/stack_preset
+//! @description Data models for kickstart tech stack presets
+//!
+//! PURPOSE:
+//! - Define a tech stack preset (built-in or user-defined) with its conventions and layout
+//! - Define a starter skill suggested for projects on a preset
+//!
+//! DEPENDENCIES:
+//! - serde - Serialization for Tauri IPC
+//!
+//! EXPORTS:
+//! - StackPreset - A named stack with CLAUDE.md conventions, directory layout, and starter skills
+//! - StarterSkill - A skill to create for new projects on a preset
+//!
+//! PATTERNS:
+//! - Built-in presets have slug IDs ("nextjs-trpc"); user presets have UUIDs
+//! - language/framework/database/styling use the kickstart option values ("TypeScript", "Next.js")
+//!
+//! CLAUDE NOTES:
+//! - Built-in presets live in core::stack_presets and are never stored in the DB
+//! - Keep in sync with TypeScript types in src/types/kickstart.ts
+
+use serde::{Deserialize, Serialize};
+
+/// A skill to create for new projects on a preset.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct StarterSkill {
+    pub name: String,
+    pub description: String,
+    pub content: String,
+}
+
+/// A tech stack preset that kickstart prompts and stack inference can snap to.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct StackPreset {
+    pub id: String,
+    pub name: String,
+    pub description: String,
+    pub language: String,
+    pub framework: String,
+    pub database: Option<String>,
+    pub styling: Option<String>,
+    pub testing: Option<String>,
+    /// CLAUDE.md convention bullets for this stack
+    pub conventions: Vec<String>,
+    /// Directory tree (one path per line, indented with two spaces per level)
+    pub directory_layout: String,
+    pub starter_skills: Vec<StarterSkill>,
+    pub builtin: bool,
+    pub created_at: Option<String>,
+    pub updated_at: Option<String>,
+}
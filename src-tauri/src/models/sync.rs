@@ -0,0 +1,113 @@
+//! @module models/sync
+//! @description Data models for encrypted settings and knowledge sync through a user-provided remote
+//!
+//! PURPOSE:
+//! - Describe the remote that holds the encrypted snapshot (S3 bucket, WebDAV file, or git repo)
+//! - Define the config shape returned to the settings UI
+//! - Define the decrypted snapshot layout and the result of a sync
+//!
+//! DEPENDENCIES:
+//! - serde / serde_json - Serialization for Tauri IPC and the snapshot payload
+//! - models::shared_db - SharedTableSync (per-table push/pull counts)
+//!
+//! EXPORTS:
+//! - SyncRemoteKind - s3 | webdav | git
+//! - SyncRemote - Where the snapshot lives (URL plus kind-specific fields)
+//! - SyncConfig - Remote, secret/passphrase presence, last sync time
+//! - SyncSnapshot - Decrypted snapshot: settings plus shared table rows
+//! - SyncReport - Result of a full sync
+//!
+//! PATTERNS:
+//! - The remote secret and the passphrase are never returned to the frontend; only has_* flags
+//! - Snapshot rows use the shared column names from db/shared.rs, keyed by table name
+//!
+//! CLAUDE NOTES:
+//! - Keep in sync with TypeScript types in src/types/
+//! - Bump SNAPSHOT_VERSION in core::sync when the snapshot layout changes
+
+use std::collections::BTreeMap;
+
+use serde::{Deserialize, Serialize};
+use serde_json::{Map, Value as JsonValue};
+
+use crate::models::shared_db::SharedTableSync;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum SyncRemoteKind {
+    S3,
+    Webdav,
+    Git,
+}
+
+impl SyncRemoteKind {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            SyncRemoteKind::S3 => "s3",
+            SyncRemoteKind::Webdav => "webdav",
+            SyncRemoteKind::Git => "git",
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct SyncRemote {
+    pub kind: SyncRemoteKind,
+    /// S3: endpoint (https://s3.<region>.amazonaws.com or a compatible service);
+    /// WebDAV: URL of the snapshot file; git: repository URL
+    pub url: String,
+    /// S3 bucket name
+    #[serde(default)]
+    pub bucket: Option<String>,
+    /// S3 region (default us-east-1)
+    #[serde(default)]
+    pub region: Option<String>,
+    /// S3 object key or path of the snapshot file inside the git repo
+    #[serde(default)]
+    pub path: Option<String>,
+    /// S3 access key id or WebDAV user name
+    #[serde(default)]
+    pub username: Option<String>,
+    /// git branch (default main)
+    #[serde(default)]
+    pub branch: Option<String>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct SyncConfig {
+    pub remote: Option<SyncRemote>,
+    /// S3 secret access key or WebDAV password is stored
+    pub has_secret: bool,
+    /// The snapshot passphrase is stored (required before syncing)
+    pub has_passphrase: bool,
+    pub last_synced_at: Option<String>,
+}
+
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct SyncSnapshot {
+    pub version: u32,
+    pub created_at: String,
+    /// Host name of the machine that pushed the snapshot
+    #[serde(default)]
+    pub device: String,
+    #[serde(default)]
+    pub settings: BTreeMap<String, String>,
+    /// Shared table name -> rows as JSON objects
+    #[serde(default)]
+    pub tables: BTreeMap<String, Vec<Map<String, JsonValue>>>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct SyncReport {
+    pub remote: SyncRemoteKind,
+    pub tables: Vec<SharedTableSync>,
+    pub settings_pulled: u32,
+    pub settings_pushed: u32,
+    /// Settings changed on both machines, staged for a decision (see list_import_conflicts)
+    pub setting_conflicts: u32,
+    pub synced_at: String,
+}
@@ -15,10 +15,15 @@
 //! - TeamTaskDef - Definition of a task with dependencies
 //! - TeamHookDef - Definition of a hook for coordination
 //! - ProjectContext - Active project tech stack context for deploy output personalization
+//! - TemplateVariable - A template variable and the value it resolves to
+//! - UnresolvedTemplateVariable - A {{variable}} with no value and where it appears
+//! - TemplateVariableReport - Resolved and unresolved variables of a template for a project
 //!
 //! PATTERNS:
 //! - Team templates have JSON-serialized teammates, tasks, hooks
 //! - Pattern field is a string enum (leader/pipeline/parallel/swarm/council)
+//! - Template text may contain {{project_name}}, {{language}}, {{test_command}}, etc.,
+//!   resolved at deploy time (see commands::team_templates::TEMPLATE_VARIABLES)
 //!
 //! CLAUDE NOTES:
 //! - Keep in sync with TypeScript types in src/types/team-template.ts
@@ -82,3 +87,28 @@ pub struct TeamTemplate {
     pub created_at: DateTime<Utc>,
     pub updated_at: DateTime<Utc>,
 }
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct TemplateVariable {
+    pub name: String,
+    pub value: String,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct UnresolvedTemplateVariable {
+    pub name: String,
+    /// Where the variable appears, e.g. "teammate Dev spawn prompt"
+    pub locations: Vec<String>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct TemplateVariableReport {
+    /// Variables used by the template that have a value
+    pub resolved: Vec<TemplateVariable>,
+    pub unresolved: Vec<UnresolvedTemplateVariable>,
+    /// True when nothing is unresolved and the template can be deployed
+    pub ready: bool,
+}
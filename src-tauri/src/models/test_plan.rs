@@ -8,6 +8,8 @@
 //! - Define TestCaseResult for per-case results
 //! - Define TDDSession for guided TDD workflow tracking
 //! - Define GeneratedTestSuggestion for AI-powered test suggestions
+//! - Define the portable test plan file format used by export/import
+//! - Define TDD phase history and cycle-time analytics
 //!
 //! DEPENDENCIES:
 //! - serde - Serialization for Tauri IPC
@@ -21,15 +23,25 @@
 //! - TestPriority - Priority enum (low, medium, high, critical)
 //! - TestCaseStatus - Status enum (pending, passing, failing, skipped)
 //! - TestRun - A test execution run with results
-//! - TestRunStatus - Status enum (running, passed, failed, cancelled)
+//! - TestRunStatus - Status enum (running, passed, failed, cancelled, timed_out)
 //! - TestCaseResult - Result for a single test case in a run
 //! - TestPlanSummary - Aggregated stats for a test plan
 //! - TDDSession - A TDD workflow session tracking red/green/refactor phases
 //! - TDDPhase - Phase enum (red, green, refactor)
 //! - TDDPhaseStatus - Phase status enum (pending, active, complete, failed)
+//! - TDDPhaseEvent - When a session entered a phase
+//! - TDDSessionStats - Cycle counts and average phase times for one session
+//! - TDDTrendPoint - Sessions, cycles, and phase times for one week
+//! - TDDAnalytics - Project-wide TDD cycle analytics with weekly trend
 //! - GeneratedTestSuggestion - AI-generated test case suggestion
 //! - TestStalenessResult - Per-file staleness detection result
 //! - TestStalenessReport - Aggregated staleness report for a project
+//! - TestPlanExport - Portable plan definition (JSON file committed to the repo)
+//! - TestCaseExport - Portable test case definition inside a TestPlanExport
+//! - TestEnvVar - One environment variable configured for a plan's test runs
+//! - TestPlanEnv - Per-plan environment variables and .env file loading
+//! - ResolvedTestEnvVar - An env var as it will be passed to the test command (secrets masked)
+//! - TestCommandPreview - Resolved command, working directory, and environment for a dry run
 //!
 //! PATTERNS:
 //! - All models derive Serialize, Deserialize for Tauri IPC
@@ -41,6 +53,9 @@
 //! - TestType: unit = isolated, integration = cross-module, e2e = full stack
 //! - TestPriority: affects execution order and reporting
 //! - TDDPhase: red = failing test, green = minimal pass, refactor = cleanup
+//! - A TDD cycle starts at each red entry in TDDSession.phase_history; durations are seconds
+//! - TestPlanExport carries definitions only (no IDs, timestamps, run results, or case
+//!   status) so exported files diff cleanly; bump TEST_PLAN_EXPORT_VERSION on breaking changes
 //! - Keep in sync with TypeScript types in src/types/test-plan.ts
 
 use chrono::{DateTime, Utc};
@@ -229,9 +244,11 @@ pub enum TestRunStatus {
     Passed,
     Failed,
     Cancelled,
+    /// Killed after exceeding the plan's timeout
+    #[serde(rename = "timed_out")]
+    TimedOut,
 }
 
-
 impl std::fmt::Display for TestRunStatus {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
         match self {
@@ -239,6 +256,7 @@ impl std::fmt::Display for TestRunStatus {
             TestRunStatus::Passed => write!(f, "passed"),
             TestRunStatus::Failed => write!(f, "failed"),
             TestRunStatus::Cancelled => write!(f, "cancelled"),
+            TestRunStatus::TimedOut => write!(f, "timed_out"),
         }
     }
 }
@@ -251,6 +269,7 @@ impl std::str::FromStr for TestRunStatus {
             "passed" => Ok(TestRunStatus::Passed),
             "failed" => Ok(TestRunStatus::Failed),
             "cancelled" => Ok(TestRunStatus::Cancelled),
+            "timed_out" => Ok(TestRunStatus::TimedOut),
             _ => Err(format!("Invalid test run status: {}", s)),
         }
     }
@@ -394,6 +413,67 @@ pub struct TDDSession {
     pub created_at: DateTime<Utc>,
     pub updated_at: DateTime<Utc>,
     pub completed_at: Option<DateTime<Utc>>,
+    /// Every phase the session entered, oldest first
+    #[serde(default)]
+    pub phase_history: Vec<TDDPhaseEvent>,
+}
+
+/// A TDD session entering a phase
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub struct TDDPhaseEvent {
+    pub phase: TDDPhase,
+    pub at: DateTime<Utc>,
+}
+
+/// Cycle statistics for one TDD session
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct TDDSessionStats {
+    pub session_id: String,
+    pub feature_name: String,
+    pub created_at: DateTime<Utc>,
+    pub completed_at: Option<DateTime<Utc>>,
+    /// Red phases entered (each starts a cycle)
+    pub cycles: u32,
+    /// Cycles that reached refactor
+    pub completed_cycles: u32,
+    pub avg_red_to_green_secs: Option<f64>,
+    pub avg_green_to_refactor_secs: Option<f64>,
+    /// Red start to the next red (or session completion)
+    pub avg_cycle_secs: Option<f64>,
+}
+
+/// TDD activity for one week (weeks start on Monday)
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct TDDTrendPoint {
+    /// YYYY-MM-DD of the Monday starting the week
+    pub week_start: String,
+    pub sessions: u32,
+    pub cycles: u32,
+    pub avg_red_to_green_secs: Option<f64>,
+    pub avg_green_to_refactor_secs: Option<f64>,
+}
+
+/// TDD cycle analytics for a project
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct TDDAnalytics {
+    pub project_id: String,
+    pub total_sessions: u32,
+    pub completed_sessions: u32,
+    pub total_cycles: u32,
+    pub avg_cycles_per_session: f64,
+    pub avg_red_to_green_secs: Option<f64>,
+    pub avg_green_to_refactor_secs: Option<f64>,
+    pub avg_cycle_secs: Option<f64>,
+    /// "improving" | "declining" | "steady" | "insufficient_data" (red→green time, older vs recent cycles)
+    pub red_to_green_trend: String,
+    /// Newest session first
+    pub sessions: Vec<TDDSessionStats>,
+    /// Oldest week first
+    pub weekly: Vec<TDDTrendPoint>,
 }
 
 /// AI-generated test case suggestion
@@ -408,6 +488,60 @@ pub struct GeneratedTestSuggestion {
     pub suggested_file_path: Option<String>,
 }
 
+/// One environment variable configured for a plan's test runs
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub struct TestEnvVar {
+    pub key: String,
+    pub value: String,
+    /// Stored encrypted, masked when read back and in stored test output
+    #[serde(default)]
+    pub secret: bool,
+}
+
+/// Per-plan test environment: explicit variables plus an optional .env file
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub struct TestPlanEnv {
+    pub plan_id: String,
+    #[serde(default)]
+    pub vars: Vec<TestEnvVar>,
+    /// Load env_file (relative to the project root) before applying vars
+    #[serde(default)]
+    pub load_env_file: bool,
+    #[serde(default = "default_env_file")]
+    pub env_file: String,
+}
+
+fn default_env_file() -> String {
+    ".env.test".to_string()
+}
+
+/// An env var as it will be passed to the test command
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ResolvedTestEnvVar {
+    pub key: String,
+    /// Masked when secret
+    pub value: String,
+    pub secret: bool,
+    /// "plan" | "env_file"
+    pub source: String,
+}
+
+/// What run_test_plan would execute, for debugging before a real run
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct TestCommandPreview {
+    pub framework: String,
+    pub command: String,
+    pub working_dir: String,
+    pub env: Vec<ResolvedTestEnvVar>,
+    /// The .env file that was loaded, if any
+    pub env_file: Option<String>,
+    pub warnings: Vec<String>,
+}
+
 /// Detected test framework information
 #[derive(Debug, Clone, Serialize, Deserialize)]
 #[serde(rename_all = "camelCase")]
@@ -447,3 +581,41 @@ pub struct TestDiscoveryResult {
     pub method: String, // "list_command" | "static_grep"
     pub discovered_at: String,
 }
+
+/// Format version written by export_test_plan and accepted by import_test_plan.
+pub const TEST_PLAN_EXPORT_VERSION: u32 = 1;
+
+/// A test plan as written to a JSON file for review and re-import.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct TestPlanExport {
+    pub version: u32,
+    pub name: String,
+    #[serde(default)]
+    pub description: String,
+    #[serde(default)]
+    pub status: TestPlanStatus,
+    #[serde(default = "default_target_coverage")]
+    pub target_coverage: u32,
+    #[serde(default)]
+    pub cases: Vec<TestCaseExport>,
+}
+
+fn default_target_coverage() -> u32 {
+    80
+}
+
+/// A test case inside a TestPlanExport.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct TestCaseExport {
+    pub name: String,
+    #[serde(default)]
+    pub description: String,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub file_path: Option<String>,
+    #[serde(default)]
+    pub test_type: TestType,
+    #[serde(default)]
+    pub priority: TestPriority,
+}
@@ -0,0 +1,58 @@
+//! @module models/version
+//! @description Data models for skill and agent version history
+//!
+//! PURPOSE:
+//! - Define EntityVersion for a stored snapshot of a skill or agent
+//! - Define VersionDiff and DiffLine for comparing two snapshots
+//!
+//! DEPENDENCIES:
+//! - serde - Serialization for Tauri IPC
+//!
+//! EXPORTS:
+//! - EntityVersion - A numbered snapshot of a skill or agent
+//! - VersionDiff - Field and line-level differences between two versions
+//! - DiffLine - A single line in a content diff
+//!
+//! PATTERNS:
+//! - entity_type is "skill" or "agent"
+//! - content holds skill content or agent instructions
+//! - DiffLine.kind is "added" | "removed" | "unchanged"
+//!
+//! CLAUDE NOTES:
+//! - Versions start at 1 and increase per entity; rollbacks create a new version
+//! - Keep in sync with TypeScript types in src/types/
+
+use serde::{Deserialize, Serialize};
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct EntityVersion {
+    pub id: String,
+    pub entity_type: String,
+    pub entity_id: String,
+    pub version: u32,
+    pub name: String,
+    pub description: String,
+    pub content: String,
+    pub created_at: String,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct VersionDiff {
+    pub entity_type: String,
+    pub entity_id: String,
+    pub from_version: u32,
+    pub to_version: u32,
+    /// Names of fields whose values differ between the two versions
+    pub changed_fields: Vec<String>,
+    /// Line diff of the content (skill content or agent instructions)
+    pub lines: Vec<DiffLine>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub struct DiffLine {
+    pub kind: String,
+    pub text: String,
+}
@@ -0,0 +1,291 @@
+//! @module test_support
+//! @description Harness for command-level integration tests (test builds only)
+//!
+//! PURPOSE:
+//! - Build a mock Tauri app whose AppState holds an in-memory SQLite database with the full schema
+//! - Point the Anthropic API at a wiremock server and the Claude CLI at a stub script
+//! - Compare command results against stored JSON snapshots with volatile values redacted
+//!
+//! DEPENDENCIES:
+//! - tauri::test - MockRuntime app, so commands get a real AppHandle and State
+//! - wiremock - Fake Anthropic messages endpoint
+//! - tempfile - Per-test directory for projects and stub executables
+//! - db - run_migrations, AppState, DB_PATH_ENV
+//! - core::ai - API_URL_ENV; commands::ralph - CLAUDE_CLI_ENV
+//!
+//! EXPORTS:
+//! - TestEnv - Mock app, database, and temp directory for one test
+//! - wait_for - Poll a condition until it holds (background tasks)
+//! - redact - Replace ids, timestamps, and temp paths in a JSON value
+//! - assert_snapshot - Compare a value against test_support/snapshots/<name>.json
+//! - memory_db - Private in-memory database with every table, for unit tests
+//! - with_project - memory_db with project 'p1' at /tmp/p1
+//! - insert_project - Register a project (name "Test") on an existing connection
+//!
+//! PATTERNS:
+//! - Unit tests that only need tables use memory_db() / with_project(); TestEnv is for commands
+//! - let env = TestEnv::new(); call commands with env.state() / env.handle(); read back with env.db()
+//! - The database is a named shared-cache in-memory SQLite URI exported as JUMPSTART_DB_PATH, so
+//!   background tasks that open their own connection (RALPH loops) see the same data
+//! - A missing or changed snapshot fails the test. Run with UPDATE_SNAPSHOTS=1 to write new
+//!   snapshots and rewrite changed ones, then review and commit the files
+//!
+//! CLAUDE NOTES:
+//! - The overrides are process-wide environment variables, so TestEnv holds a global lock for
+//!   its lifetime; tests using it run one at a time and must not spawn another TestEnv
+//! - The overrides are read only in test builds (#[cfg(test)]); release builds ignore them
+//! - Commands under test must be generic over tauri::Runtime when they take an AppHandle
+//!   (start_ralph_loop, run_test_plan); MockRuntime is not Wry
+
+use std::path::{Path, PathBuf};
+use std::sync::{Mutex, MutexGuard};
+use std::time::{Duration, Instant};
+
+use rusqlite::Connection;
+use serde::Serialize;
+use serde_json::{json, Value};
+use tauri::test::MockRuntime;
+use tauri::{App, AppHandle, Manager, State};
+use wiremock::matchers::{method, path};
+use wiremock::{Mock, MockServer, ResponseTemplate};
+
+use crate::db::{self, AppState};
+
+/// Serializes tests that set the process-wide overrides.
+static ENV_LOCK: Mutex<()> = Mutex::new(());
+
+/// Environment variables a TestEnv sets and clears.
+const OVERRIDES: &[&str] = &[db::DB_PATH_ENV, crate::core::ai::API_URL_ENV, crate::commands::ralph::CLAUDE_CLI_ENV];
+
+/// Mock app, in-memory database, and temp directory for one integration test.
+pub struct TestEnv {
+    pub app: App<MockRuntime>,
+    pub dir: tempfile::TempDir,
+    _lock: MutexGuard<'static, ()>,
+}
+
+impl TestEnv {
+    pub fn new() -> Self {
+        let lock = ENV_LOCK.lock().unwrap_or_else(|poisoned| poisoned.into_inner());
+
+        let uri = format!("file:jumpstart-test-{}?mode=memory&cache=shared", uuid::Uuid::new_v4().simple());
+        std::env::set_var(db::DB_PATH_ENV, &uri);
+        let conn = Connection::open(&uri).expect("open in-memory database");
+        db::run_migrations(&conn).expect("run migrations");
+        // Background connections write while this one holds the AppState lock; read through
+        // their uncommitted pages instead of failing with SQLITE_LOCKED
+        conn.execute_batch("PRAGMA read_uncommitted = 1;").expect("set read_uncommitted");
+
+        let app = tauri::test::mock_app();
+        app.manage(AppState {
+            db: Mutex::new(conn),
+            http_client: reqwest::Client::new(),
+            watcher: Mutex::new(None),
+            health_cache: Mutex::new(None),
+            test_runs: Mutex::new(Default::default()),
+        });
+
+        TestEnv {
+            app,
+            dir: tempfile::tempdir().expect("create temp dir"),
+            _lock: lock,
+        }
+    }
+
+    pub fn state(&self) -> State<'_, AppState> {
+        self.app.state::<AppState>()
+    }
+
+    pub fn handle(&self) -> AppHandle<MockRuntime> {
+        self.app.handle().clone()
+    }
+
+    /// The AppState connection (the same database background tasks open).
+    pub fn db(&self) -> MutexGuard<'_, Connection> {
+        self.state().inner().db.lock().expect("DB lock")
+    }
+
+    /// Register a project whose directory is `<temp dir>/<name>`. Returns (id, path).
+    pub fn add_project(&self, name: &str) -> (String, String) {
+        let dir = self.dir.path().join(name);
+        std::fs::create_dir_all(&dir).expect("create project dir");
+        let id = uuid::Uuid::new_v4().to_string();
+        let path = dir.to_string_lossy().to_string();
+        self.db()
+            .execute(
+                "INSERT INTO projects (id, name, path, language, created_at) VALUES (?1, ?2, ?3, 'typescript', ?4)",
+                rusqlite::params![id, name, path, chrono::Utc::now().to_rfc3339()],
+            )
+            .expect("insert project");
+        (id, path)
+    }
+
+    /// Write `content` to `rel_path` under the temp dir (creating parents). Returns the full path.
+    pub fn write_file(&self, rel_path: &str, content: &str) -> PathBuf {
+        let file = self.dir.path().join(rel_path);
+        if let Some(parent) = file.parent() {
+            std::fs::create_dir_all(parent).expect("create parent dir");
+        }
+        std::fs::write(&file, content).expect("write file");
+        file
+    }
+
+    /// Write an executable shell script to `rel_path` under the temp dir.
+    pub fn write_executable(&self, rel_path: &str, script: &str) -> PathBuf {
+        let file = self.write_file(rel_path, &format!("#!/bin/sh\n{}\n", script));
+        #[cfg(unix)]
+        {
+            use std::os::unix::fs::PermissionsExt;
+            std::fs::set_permissions(&file, std::fs::Permissions::from_mode(0o755)).expect("chmod");
+        }
+        file
+    }
+
+    /// Install a stub Claude CLI running `script` ("$2" is the -p prompt) and return its path.
+    pub fn stub_claude_cli(&self, script: &str) -> PathBuf {
+        let cli = self.write_executable("bin/claude", script);
+        std::env::set_var(crate::commands::ralph::CLAUDE_CLI_ENV, &cli);
+        cli
+    }
+
+    /// Start a fake Anthropic API answering each messages request with the next of `replies`
+    /// (the last one repeats), and store an API key so commands take their AI path.
+    pub async fn mock_anthropic(&self, replies: &[&str]) -> MockServer {
+        let server = MockServer::start().await;
+        for (i, reply) in replies.iter().enumerate() {
+            let mock = Mock::given(method("POST")).and(path("/v1/messages")).respond_with(
+                ResponseTemplate::new(200).set_body_json(json!({ "content": [{ "type": "text", "text": reply }] })),
+            );
+            let mock = if i + 1 < replies.len() { mock.up_to_n_times(1) } else { mock };
+            mock.mount(&server).await;
+        }
+        std::env::set_var(crate::core::ai::API_URL_ENV, format!("{}/v1/messages", server.uri()));
+        self.db()
+            .execute(
+                "INSERT OR REPLACE INTO settings (key, value) VALUES ('anthropic_api_key', 'sk-ant-test')",
+                [],
+            )
+            .expect("store API key");
+        server
+    }
+
+    /// `value` with this env's temp dir, ids, and timestamps redacted.
+    pub fn redact(&self, value: &impl Serialize) -> Value {
+        let root = self.dir.path().to_string_lossy().to_string();
+        redact(serde_json::to_value(value).expect("serialize"), &root)
+    }
+}
+
+impl Drop for TestEnv {
+    fn drop(&mut self) {
+        for var in OVERRIDES {
+            std::env::remove_var(var);
+        }
+    }
+}
+
+/// A private in-memory database with every table (schema::create_tables). Tests that need
+/// newer columns apply the matching schema::migrate_* functions themselves.
+pub fn memory_db() -> Connection {
+    let conn = Connection::open_in_memory().expect("open in-memory database");
+    db::schema::create_tables(&conn).expect("create tables");
+    conn
+}
+
+/// memory_db() with project 'p1' ("Test") registered at /tmp/p1.
+pub fn with_project() -> Connection {
+    let conn = memory_db();
+    insert_project(&conn, "p1", "/tmp/p1");
+    conn
+}
+
+/// Register project `id` named "Test" at `path`.
+pub fn insert_project(conn: &Connection, id: &str, path: &str) {
+    conn.execute(
+        "INSERT INTO projects (id, name, path, created_at) VALUES (?1, 'Test', ?2, '2025-01-01T00:00:00Z')",
+        rusqlite::params![id, path],
+    )
+    .expect("insert project");
+}
+
+/// Poll `condition` every 20ms until it holds or `timeout` passes. Returns whether it held.
+pub async fn wait_for(timeout: Duration, mut condition: impl FnMut() -> bool) -> bool {
+    let deadline = Instant::now() + timeout;
+    while Instant::now() < deadline {
+        if condition() {
+            return true;
+        }
+        tokio::time::sleep(Duration::from_millis(20)).await;
+    }
+    condition()
+}
+
+/// Replace volatile strings: `root` (the temp dir) becomes "<tmp>", UUIDs "<uuid>",
+/// RFC 3339 timestamps "<timestamp>". Numeric durations are left to the caller.
+pub fn redact(value: Value, root: &str) -> Value {
+    match value {
+        Value::String(s) => Value::String(redact_str(&s, root)),
+        Value::Array(items) => Value::Array(items.into_iter().map(|v| redact(v, root)).collect()),
+        Value::Object(map) => Value::Object(map.into_iter().map(|(k, v)| (k, redact(v, root))).collect()),
+        other => other,
+    }
+}
+
+fn redact_str(s: &str, root: &str) -> String {
+    let uuid = regex::Regex::new(r"[0-9a-f]{8}-[0-9a-f]{4}-[0-9a-f]{4}-[0-9a-f]{4}-[0-9a-f]{12}").expect("uuid regex");
+    let timestamp = regex::Regex::new(r"\d{4}-\d{2}-\d{2}T\d{2}:\d{2}:\d{2}(\.\d+)?(Z|[+-]\d{2}:\d{2})")
+        .expect("timestamp regex");
+    let s = if root.is_empty() { s.to_string() } else { s.replace(root, "<tmp>") };
+    let s = uuid.replace_all(&s, "<uuid>");
+    timestamp.replace_all(&s, "<timestamp>").to_string()
+}
+
+fn snapshot_path(name: &str) -> PathBuf {
+    Path::new(env!("CARGO_MANIFEST_DIR"))
+        .join("src")
+        .join("test_support")
+        .join("snapshots")
+        .join(format!("{}.json", name))
+}
+
+/// Compare `actual` (already redacted) with the stored snapshot `name`. With
+/// UPDATE_SNAPSHOTS=1 the snapshot is written instead of compared.
+pub fn assert_snapshot(name: &str, actual: &Value) {
+    let file = snapshot_path(name);
+    let rendered = serde_json::to_string_pretty(actual).expect("render snapshot") + "\n";
+    if std::env::var("UPDATE_SNAPSHOTS").is_ok_and(|v| v == "1") {
+        std::fs::write(&file, rendered).expect("write snapshot");
+        return;
+    }
+    match std::fs::read_to_string(&file) {
+        Ok(stored) => assert_eq!(
+            stored, rendered,
+            "snapshot {} changed; rerun with UPDATE_SNAPSHOTS=1 to accept",
+            name
+        ),
+        Err(_) => panic!(
+            "snapshot {} is missing ({}); rerun with UPDATE_SNAPSHOTS=1 to create it",
+            name,
+            file.display()
+        ),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_redact() {
+        let value = json!({
+            "id": "0b5e3c9a-1f2d-4e6a-9b7c-8d0e1f2a3b4c",
+            "path": "/tmp/.tmpAbC/web/src/a.ts",
+            "at": ["2026-01-02T03:04:05.123+00:00", "2026-01-02T03:04:05Z"],
+            "n": 3
+        });
+        assert_eq!(
+            redact(value, "/tmp/.tmpAbC"),
+            json!({ "id": "<uuid>", "path": "<tmp>/web/src/a.ts", "at": ["<timestamp>", "<timestamp>"], "n": 3 })
+        );
+    }
+}